@@ -0,0 +1,81 @@
+use {
+    criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput},
+    rayon::{prelude::*, ThreadPoolBuilder},
+    solana_account::AccountSharedData,
+    solana_accounts_db::{
+        account_cache::{ShardedAccountCache, DEFAULT_NUM_SHARDS},
+        tiered_storage_config::TieredStorageConfig,
+    },
+    solana_pubkey::Pubkey,
+    std::hint::black_box,
+};
+
+/// Number of worker threads hammering the cache.
+const NUM_THREADS: usize = 16;
+/// Accounts preloaded into the cache; sized to avoid eviction.
+const NUM_ACCOUNTS: usize = 16_384;
+/// Operations per benchmark iteration.
+const OPS_PER_ITER: usize = 65_536;
+/// Fraction of operations that are inserts, in percent.
+const WRITE_PERCENTS: &[usize] = &[0, 10, 50];
+
+fn new_cache(num_shards: usize) -> (ShardedAccountCache, Vec<Pubkey>) {
+    let cache = ShardedAccountCache::new(
+        TieredStorageConfig {
+            hot_cache_size: 1024 * 1024 * 1024,
+            ..TieredStorageConfig::for_testing()
+        },
+        num_shards,
+    );
+    let pubkeys: Vec<Pubkey> = (0..NUM_ACCOUNTS).map(|_| Pubkey::new_unique()).collect();
+    for pubkey in &pubkeys {
+        cache.insert(
+            *pubkey,
+            AccountSharedData::new(1, 165, &Pubkey::default()),
+            0,
+        );
+    }
+    (cache, pubkeys)
+}
+
+/// Benchmarks concurrent gets and inserts from a 16 thread rayon pool.
+fn bench_sharded_account_cache(c: &mut Criterion) {
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(NUM_THREADS)
+        .thread_name(|i| format!("solBenchCache{i:02}"))
+        .build()
+        .unwrap();
+    let account = AccountSharedData::new(2, 165, &Pubkey::default());
+
+    let mut group = c.benchmark_group("sharded_account_cache");
+    group.throughput(Throughput::Elements(OPS_PER_ITER as u64));
+    for num_shards in [1, DEFAULT_NUM_SHARDS, 64] {
+        let (cache, pubkeys) = new_cache(num_shards);
+        for &write_percent in WRITE_PERCENTS {
+            group.bench_function(
+                BenchmarkId::new(
+                    format!("{num_shards}_shards"),
+                    format!("{write_percent}%_writes"),
+                ),
+                |b| {
+                    b.iter(|| {
+                        pool.install(|| {
+                            (0..OPS_PER_ITER).into_par_iter().for_each(|i| {
+                                let pubkey = &pubkeys[i % NUM_ACCOUNTS];
+                                if i % 100 < write_percent {
+                                    cache.insert(*pubkey, account.clone(), 0);
+                                } else {
+                                    black_box(cache.get(pubkey));
+                                }
+                            })
+                        })
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_sharded_account_cache);
+criterion_main!(benches);
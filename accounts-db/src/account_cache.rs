@@ -19,12 +19,37 @@
 //!                                                     ▲ evict from here
 //! ```
 //!
+//! # ARC
+//!
+//! With [`EvictionPolicy::ARC`] every resident entry is tagged as belonging to
+//! T1 (seen once recently) or T2 (seen at least twice). Both share the single
+//! recency list above, so the LRU entry of either is the first node from the
+//! tail carrying that tag. Evicted keys are remembered in two ghost lists, B1
+//! and B2; re-inserting a ghost key grows the target size of the list it was
+//! evicted from. One-off scans therefore churn through T1 without displacing
+//! the frequently used working set in T2.
+//!
+//! # LRU-K
+//!
+//! Every entry remembers its last K access times. With
+//! [`EvictionPolicy::LRUK`] victims are chosen by their K-th most recent
+//! access instead of list position, and entries accessed fewer than K times
+//! go first, so a scan of one-off keys cannot push out an account that was
+//! used K times.
+//!
+//! # Snapshots
+//!
+//! [`AccountCache::dump_to_snapshot`] writes the cached accounts to a file on
+//! graceful shutdown and [`AccountCache::warm_from_snapshot`] loads them back
+//! on restart, so a validator does not start with a cold cache.
+//!
 //! # Thread Safety
 //!
-//! The current implementation is single-threaded (`&mut self` on get/insert).
-//! For production use, this will be wrapped in a `parking_lot::Mutex` or
-//! sharded across multiple caches keyed by pubkey prefix, similar to how
-//! Solana's `ReadOnlyAccountsCache` uses `DashMap`.
+//! [`AccountCache`] itself is single-threaded (`&mut self` on get/insert).
+//! [`ShardedAccountCache`] splits the cache into independently locked shards
+//! keyed by the first byte of the pubkey, so concurrent accesses only contend
+//! when they land on the same shard, similar in spirit to the `DashMap`
+//! behind Solana's `ReadOnlyAccountsCache`.
 //!
 //! # Integration Plan
 //!
@@ -34,11 +59,16 @@
 
 use {
     crate::tiered_storage_config::{EvictionPolicy, TierStats, TieredStorageConfig},
-    solana_account::{AccountSharedData, ReadableAccount},
+    solana_account::{AccountSharedData, ReadableAccount, WritableAccount},
     solana_pubkey::Pubkey,
+    solana_system_interface::MAX_PERMITTED_DATA_LENGTH,
     std::{
-        collections::HashMap,
-        time::Instant,
+        collections::{HashMap, VecDeque},
+        fs::File,
+        io::{self, BufRead, BufReader, BufWriter, Read, Write},
+        path::Path,
+        sync::{Mutex, MutexGuard},
+        time::{Duration, Instant},
     },
 };
 
@@ -62,6 +92,10 @@ struct LruNode {
     /// The cached account data.
     account: CachedAccount,
 
+    /// ARC list this entry belongs to. Maintained for every policy but only
+    /// consulted by ARC eviction.
+    list: ArcList,
+
     /// Index of the next (newer) node, or NIL if this is the head.
     next: NodeIndex,
 
@@ -81,21 +115,60 @@ struct CachedAccount {
     /// Total number of times this account has been accessed (for LFU policy).
     access_count: u64,
 
+    /// Times of the last K accesses, oldest first (for LRU-K policy).
+    access_history: VecDeque<Instant>,
+
     /// Size of this account's data in bytes (cached to avoid recomputing).
     data_len: u64,
+
+    /// Whether this entry has changes not yet written back to warm storage.
+    dirty: bool,
+
+    /// Bank slot at which the account was last written.
+    slot: u64,
 }
 
 impl CachedAccount {
-    fn new(data: AccountSharedData) -> Self {
+    fn new(data: AccountSharedData, slot: u64) -> Self {
         let data_len = data.data().len() as u64;
+        let now = Instant::now();
         Self {
             data,
-            last_accessed: Instant::now(),
+            last_accessed: now,
             access_count: 1,
+            access_history: VecDeque::from([now]),
             data_len,
+            dirty: true,
+            slot,
+        }
+    }
+
+    /// Record an access at `now`, keeping the last `k` access times.
+    fn record_access(&mut self, now: Instant, k: usize) {
+        self.last_accessed = now;
+        self.access_count = self.access_count.saturating_add(1);
+        self.push_access_time(now, k);
+    }
+
+    /// Append `now` to the access history, trimming it to `k` entries.
+    fn push_access_time(&mut self, now: Instant, k: usize) {
+        self.access_history.push_back(now);
+        while self.access_history.len() > k.max(1) {
+            self.access_history.pop_front();
         }
     }
 
+    /// LRU-K eviction priority; lower values are evicted first.
+    ///
+    /// Entries with fewer than `k` recorded accesses have an unbounded
+    /// backward K-distance and sort before all others, oldest access first.
+    fn lru_k_priority(&self, k: usize) -> (bool, Option<Instant>) {
+        (
+            self.access_history.len() >= k,
+            self.access_history.front().copied(),
+        )
+    }
+
     /// Approximate memory footprint of this entry in bytes.
     ///
     /// Includes the AccountSharedData overhead plus the data buffer.
@@ -106,6 +179,72 @@ impl CachedAccount {
     }
 }
 
+// ── ARC Bookkeeping ─────────────────────────────────────────────────────────
+
+/// Which ARC list a resident entry belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArcList {
+    /// Recency list: entries referenced once since they were loaded.
+    T1,
+    /// Frequency list: entries referenced at least twice.
+    T2,
+}
+
+/// Adaptive state balancing T1 against T2.
+#[derive(Debug, Default)]
+struct ArcState {
+    /// Target size of T1 in bytes, in `[0, eviction watermark]`. Grows on B1
+    /// ghost hits (recency is paying off) and shrinks on B2 ghost hits.
+    p: usize,
+}
+
+/// Keys recently evicted from one ARC list, oldest first.
+///
+/// Only keys are kept, never account data. Each key carries the sequence
+/// number of its latest insertion so that stale `order` entries left behind
+/// by `remove` are skipped when trimming.
+#[derive(Debug, Default)]
+struct GhostList {
+    keys: HashMap<Pubkey, u64>,
+    order: VecDeque<(Pubkey, u64)>,
+    next_seq: u64,
+}
+
+impl GhostList {
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn contains(&self, pubkey: &Pubkey) -> bool {
+        self.keys.contains_key(pubkey)
+    }
+
+    fn remove(&mut self, pubkey: &Pubkey) -> bool {
+        self.keys.remove(pubkey).is_some()
+    }
+
+    /// Remember `pubkey`, forgetting the oldest keys beyond `capacity`.
+    fn push(&mut self, pubkey: Pubkey, capacity: usize) {
+        let seq = self.next_seq;
+        self.next_seq = self.next_seq.wrapping_add(1);
+        self.keys.insert(pubkey, seq);
+        self.order.push_back((pubkey, seq));
+        while self.keys.len() > capacity {
+            let Some((oldest, oldest_seq)) = self.order.pop_front() else {
+                break;
+            };
+            if self.keys.get(&oldest) == Some(&oldest_seq) {
+                self.keys.remove(&oldest);
+            }
+        }
+        // Drop stale entries so `order` stays bounded by live keys.
+        if self.order.len() > self.keys.len().saturating_mul(2).saturating_add(64) {
+            let keys = &self.keys;
+            self.order.retain(|(key, seq)| keys.get(key) == Some(seq));
+        }
+    }
+}
+
 // ── Free List for Recycling Node Slots ──────────────────────────────────────
 
 /// Manages recycled node indices to avoid Vec fragmentation.
@@ -124,8 +263,136 @@ impl FreeList {
     }
 }
 
+// ── Cache Snapshots ─────────────────────────────────────────────────────────
+
+/// Magic bytes opening every cache snapshot file.
+pub const CACHE_SNAPSHOT_MAGIC: [u8; 4] = *b"TRCS";
+
+/// Current cache snapshot format version.
+///
+/// Bump this whenever the entry layout changes; older readers reject files
+/// with a version they do not know.
+pub const CACHE_SNAPSHOT_VERSION: u8 = 2;
+
+/// Size of the fixed fields preceding the account data in a snapshot
+/// record: slot, lamports, owner, executable flag and rent epoch.
+const SNAPSHOT_RECORD_HEADER_LEN: usize = 8 + 8 + 32 + 1 + 8;
+
+/// Largest record a snapshot entry may declare, guarding against allocating
+/// an absurd buffer for a corrupted length field.
+const MAX_SNAPSHOT_RECORD_LEN: u64 = SNAPSHOT_RECORD_HEADER_LEN as u64 + MAX_PERMITTED_DATA_LENGTH;
+
+/// Errors reading or writing a cache snapshot file.
+#[derive(Debug)]
+pub enum CacheError {
+    /// The snapshot file could not be read or written.
+    Io(io::Error),
+    /// The file does not start with [`CACHE_SNAPSHOT_MAGIC`].
+    InvalidMagic([u8; 4]),
+    /// The file was written by an unknown snapshot format version.
+    UnsupportedVersion(u8),
+    /// An entry is truncated or malformed.
+    Corrupt(String),
+}
+
+impl std::fmt::Display for CacheError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CacheError::Io(e) => write!(f, "Cache snapshot I/O error: {}", e),
+            CacheError::InvalidMagic(magic) => {
+                write!(f, "Not a cache snapshot: bad magic {:02x?}", magic)
+            }
+            CacheError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported cache snapshot version {}", version)
+            }
+            CacheError::Corrupt(msg) => write!(f, "Corrupt cache snapshot: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CacheError {}
+
+impl From<io::Error> for CacheError {
+    fn from(e: io::Error) -> Self {
+        CacheError::Io(e)
+    }
+}
+
+/// Encode a cached account as a snapshot record.
+///
+/// Format: [slot:8][lamports:8][owner:32][executable:1][rent_epoch:8][data:N]
+fn encode_snapshot_record(account: &AccountSharedData, slot: u64) -> Vec<u8> {
+    let data = account.data();
+    let mut buf = Vec::with_capacity(SNAPSHOT_RECORD_HEADER_LEN + data.len());
+    buf.extend_from_slice(&slot.to_le_bytes());
+    buf.extend_from_slice(&account.lamports().to_le_bytes());
+    buf.extend_from_slice(account.owner().as_ref());
+    buf.push(account.executable() as u8);
+    buf.extend_from_slice(&account.rent_epoch().to_le_bytes());
+    buf.extend_from_slice(data);
+    buf
+}
+
+/// Decode a snapshot record written by [`encode_snapshot_record`].
+fn decode_snapshot_record(record: &[u8]) -> Result<(AccountSharedData, u64), CacheError> {
+    if record.len() < SNAPSHOT_RECORD_HEADER_LEN {
+        return Err(CacheError::Corrupt(format!(
+            "record of {} bytes is shorter than its {}-byte header",
+            record.len(),
+            SNAPSHOT_RECORD_HEADER_LEN
+        )));
+    }
+    let slot = u64::from_le_bytes(record[0..8].try_into().unwrap());
+    let lamports = u64::from_le_bytes(record[8..16].try_into().unwrap());
+    let owner = Pubkey::try_from(&record[16..48]).unwrap();
+    let executable = record[48] != 0;
+    let rent_epoch = u64::from_le_bytes(record[49..57].try_into().unwrap());
+    let data = &record[SNAPSHOT_RECORD_HEADER_LEN..];
+
+    let mut account = AccountSharedData::new(lamports, 0, &owner);
+    account.set_data_from_slice(data);
+    account.set_executable(executable);
+    account.set_rent_epoch(rent_epoch);
+    Ok((account, slot))
+}
+
+/// Read the next `[pubkey:32][data_len:8][data:N]` entry, or `None` at a
+/// clean end of file.
+fn read_snapshot_entry(
+    reader: &mut impl BufRead,
+) -> Result<Option<(Pubkey, AccountSharedData, u64)>, CacheError> {
+    if reader.fill_buf()?.is_empty() {
+        return Ok(None);
+    }
+    let truncated = |e: io::Error| match e.kind() {
+        io::ErrorKind::UnexpectedEof => CacheError::Corrupt("truncated entry".to_string()),
+        _ => CacheError::Io(e),
+    };
+
+    let mut pubkey = [0u8; 32];
+    reader.read_exact(&mut pubkey).map_err(truncated)?;
+    let mut data_len = [0u8; 8];
+    reader.read_exact(&mut data_len).map_err(truncated)?;
+    let data_len = u64::from_le_bytes(data_len);
+    if data_len > MAX_SNAPSHOT_RECORD_LEN {
+        return Err(CacheError::Corrupt(format!(
+            "entry length {} exceeds the maximum of {}",
+            data_len, MAX_SNAPSHOT_RECORD_LEN
+        )));
+    }
+    let mut record = vec![0u8; data_len as usize];
+    reader.read_exact(&mut record).map_err(truncated)?;
+
+    let (account, slot) = decode_snapshot_record(&record)?;
+    Ok(Some((Pubkey::from(pubkey), account, slot)))
+}
+
 // ── Account Cache ───────────────────────────────────────────────────────────
 
+/// Memory pressure callback: maps the current cache size in bytes to the
+/// size to evict down to.
+type PressureCallback = Box<dyn Fn(u64) -> u64 + Send + Sync>;
+
 /// Hot-tier LRU cache for TRv1 accounts.
 ///
 /// Provides O(1) lookups and LRU eviction, designed to keep the most
@@ -159,8 +426,23 @@ pub struct AccountCache {
     /// Current total memory usage of cached accounts, in bytes.
     current_size_bytes: u64,
 
+    /// Memory usage of entries in ARC list T1, in bytes.
+    t1_size_bytes: u64,
+
+    /// ARC target balance between T1 and T2.
+    arc: ArcState,
+
+    /// ARC ghost list B1: keys recently evicted from T1.
+    ghost_b1: GhostList,
+
+    /// ARC ghost list B2: keys recently evicted from T2.
+    ghost_b2: GhostList,
+
     /// Running statistics.
     stats: TierStats,
+
+    /// Decides how far to shed under memory pressure, if set.
+    pressure_callback: Option<PressureCallback>,
 }
 
 impl std::fmt::Debug for AccountCache {
@@ -189,36 +471,60 @@ impl AccountCache {
             head: NIL,
             tail: NIL,
             current_size_bytes: 0,
+            t1_size_bytes: 0,
+            arc: ArcState::default(),
+            ghost_b1: GhostList::default(),
+            ghost_b2: GhostList::default(),
             stats: TierStats::default(),
+            pressure_callback: None,
         }
     }
 
     /// Look up an account by pubkey.
     ///
     /// If found, the account is promoted to the head of the LRU list
-    /// (most recently used). Returns `None` if the pubkey is not cached.
+    /// (most recently used). Returns `None` if the pubkey is not cached, or
+    /// if its clean entry went unaccessed for longer than
+    /// `entry_ttl_seconds`; the expired entry is dropped so the caller
+    /// reloads it from warm storage. A dirty entry is the only copy of its
+    /// changes, so it is served until [`Self::evict_to_warm`] writes it back.
     ///
     /// This counts as a cache hit or miss for statistics tracking.
     pub fn get(&mut self, pubkey: &Pubkey) -> Option<&AccountSharedData> {
-        if let Some(&node_idx) = self.map.get(pubkey) {
-            // Cache hit
-            self.stats.total_hits = self.stats.total_hits.saturating_add(1);
-            self.stats.recalculate_rates();
-
-            // Update access metadata
-            self.nodes[node_idx].account.last_accessed = Instant::now();
-            self.nodes[node_idx].account.access_count =
-                self.nodes[node_idx].account.access_count.saturating_add(1);
-
-            // Move to head (most recently used)
-            self.move_to_head(node_idx);
+        self.get_at_slot(pubkey, 0)
+    }
 
-            Some(&self.nodes[node_idx].account.data)
-        } else {
-            // Cache miss
-            self.stats.total_misses = self.stats.total_misses.saturating_add(1);
-            self.stats.recalculate_rates();
-            None
+    /// Look up an account written at `min_slot` or later.
+    ///
+    /// Like [`Self::get`], but an entry last written before `min_slot` is
+    /// treated as a miss, so a caller on a newer bank never sees a copy the
+    /// bank may have superseded. The stale entry is left in place.
+    pub fn get_at_slot(&mut self, pubkey: &Pubkey, min_slot: u64) -> Option<&AccountSharedData> {
+        match self.live_node(pubkey) {
+            Some(node_idx) if self.nodes[node_idx].account.slot >= min_slot => {
+                // Cache hit
+                self.stats.total_hits = self.stats.total_hits.saturating_add(1);
+                self.stats.recalculate_rates();
+
+                // Update access metadata
+                self.nodes[node_idx]
+                    .account
+                    .record_access(Instant::now(), self.config.k);
+
+                // A second reference promotes the entry to the frequency list
+                self.promote_to_t2(node_idx);
+
+                // Move to head (most recently used)
+                self.move_to_head(node_idx);
+
+                Some(&self.nodes[node_idx].account.data)
+            }
+            _ => {
+                // Cache miss
+                self.stats.total_misses = self.stats.total_misses.saturating_add(1);
+                self.stats.recalculate_rates();
+                None
+            }
         }
     }
 
@@ -227,22 +533,40 @@ impl AccountCache {
         self.map.contains_key(pubkey)
     }
 
-    /// Insert an account into the cache.
+    /// Insert an account written at bank `slot` into the cache.
     ///
-    /// If the pubkey already exists, its data is updated and it is
+    /// If the pubkey already exists, its data and slot are updated and it is
     /// promoted to the head of the LRU list. If it's a new entry,
     /// it is added at the head.
     ///
+    /// The entry is marked dirty, except when a clean entry is overwritten
+    /// with identical data: such writes are coalesced away.
+    ///
+    /// Under ARC a new entry joins T1, unless its key is in a ghost list, in
+    /// which case the T1/T2 balance adapts and the entry joins T2.
+    ///
     /// **Note**: This does NOT automatically evict. Call `needs_eviction()`
     /// and `evict_to_warm()` to manage cache pressure.
-    pub fn insert(&mut self, pubkey: Pubkey, account: AccountSharedData) {
-        let cached = CachedAccount::new(account);
+    pub fn insert(&mut self, pubkey: Pubkey, account: AccountSharedData, slot: u64) {
+        let cached = CachedAccount::new(account, slot);
         let entry_size = cached.memory_size();
 
         if let Some(&existing_idx) = self.map.get(&pubkey) {
             // Update existing entry
-            let old_size = self.nodes[existing_idx].account.memory_size();
-            self.nodes[existing_idx].account = cached;
+            self.promote_to_t2(existing_idx);
+            let old = &mut self.nodes[existing_idx].account;
+            let old_size = old.memory_size();
+            let dirty = old.dirty || old.data != cached.data;
+            let access_history = std::mem::take(&mut old.access_history);
+            let now = cached.last_accessed;
+            let account = &mut self.nodes[existing_idx].account;
+            *account = CachedAccount {
+                dirty,
+                access_history,
+                ..cached
+            };
+            // The write counts as another access in the entry's history.
+            account.push_access_time(now, self.config.k);
             self.current_size_bytes = self
                 .current_size_bytes
                 .saturating_sub(old_size)
@@ -250,9 +574,11 @@ impl AccountCache {
             self.move_to_head(existing_idx);
         } else {
             // New entry
+            let list = self.arc_list_for_new_entry(&pubkey, entry_size);
             let node = LruNode {
                 pubkey,
                 account: cached,
+                list,
                 next: NIL,
                 prev: NIL,
             };
@@ -261,6 +587,9 @@ impl AccountCache {
             self.map.insert(pubkey, node_idx);
             self.push_head(node_idx);
             self.current_size_bytes = self.current_size_bytes.saturating_add(entry_size);
+            if list == ArcList::T1 {
+                self.t1_size_bytes = self.t1_size_bytes.saturating_add(entry_size);
+            }
         }
 
         // Update stats
@@ -272,19 +601,75 @@ impl AccountCache {
     ///
     /// Returns the account data if it was present.
     pub fn remove(&mut self, pubkey: &Pubkey) -> Option<AccountSharedData> {
-        if let Some(node_idx) = self.map.remove(pubkey) {
-            let size = self.nodes[node_idx].account.memory_size();
-            self.current_size_bytes = self.current_size_bytes.saturating_sub(size);
-            self.unlink(node_idx);
-            self.free_list.push(node_idx);
+        let node_idx = *self.map.get(pubkey)?;
+        let (_, account) = self.detach_node(node_idx);
 
-            self.stats.hot_accounts = self.map.len() as u64;
-            self.stats.hot_size_bytes = self.current_size_bytes;
+        self.stats.hot_accounts = self.map.len() as u64;
+        self.stats.hot_size_bytes = self.current_size_bytes;
 
-            Some(self.nodes[node_idx].account.data.clone())
-        } else {
-            None
+        Some(account.data)
+    }
+
+    /// Remove every entry last written at `slot`, returning how many were
+    /// dropped.
+    ///
+    /// Used when the bank for `slot` is abandoned (e.g. a fork is rolled
+    /// back). Dirty entries are discarded, not written back: their changes
+    /// belong to the abandoned slot.
+    pub fn invalidate_slot(&mut self, slot: u64) -> usize {
+        let mut invalidated = Vec::new();
+        let mut cursor = self.tail;
+        while cursor != NIL {
+            if self.nodes[cursor].account.slot == slot {
+                invalidated.push(cursor);
+            }
+            cursor = self.nodes[cursor].prev; // move toward head (newer)
         }
+        for &idx in &invalidated {
+            self.detach_node(idx);
+        }
+
+        self.stats.slot_invalidations = self
+            .stats
+            .slot_invalidations
+            .saturating_add(invalidated.len() as u64);
+        self.stats.hot_accounts = self.map.len() as u64;
+        self.stats.hot_size_bytes = self.current_size_bytes;
+
+        invalidated.len()
+    }
+
+    /// Collect every dirty entry for write-back and clear its dirty bit.
+    ///
+    /// Entries are returned oldest first. The cache assumes the caller
+    /// persists them; use [`Self::insert`] again if a write-back fails.
+    pub fn flush_dirty(&mut self) -> Vec<(Pubkey, AccountSharedData)> {
+        let mut flushed = Vec::new();
+        let mut cursor = self.tail;
+        while cursor != NIL {
+            let node = &mut self.nodes[cursor];
+            if node.account.dirty {
+                node.account.dirty = false;
+                flushed.push((node.pubkey, node.account.data.clone()));
+            }
+            cursor = node.prev; // move toward head (newer)
+        }
+        flushed
+    }
+
+    /// Clear the dirty bit of a cached account once its write-back to the
+    /// warm tier has succeeded.
+    pub fn mark_clean(&mut self, pubkey: &Pubkey) {
+        if let Some(&node_idx) = self.map.get(pubkey) {
+            self.nodes[node_idx].account.dirty = false;
+        }
+    }
+
+    /// Returns `true` if the account is cached with unflushed changes.
+    pub fn is_dirty(&self, pubkey: &Pubkey) -> bool {
+        self.map
+            .get(pubkey)
+            .is_some_and(|&node_idx| self.nodes[node_idx].account.dirty)
     }
 
     /// Returns `true` if the cache exceeds the eviction watermark.
@@ -297,64 +682,141 @@ impl AccountCache {
 
     /// Evict the least-recently-used accounts to make room in the cache.
     ///
-    /// Returns a vector of `(Pubkey, AccountSharedData)` pairs that were
-    /// evicted. The caller is responsible for persisting these to warm
-    /// storage (NVMe SSD).
+    /// Returns the `(Pubkey, AccountSharedData)` pairs of the evicted
+    /// accounts that were dirty. The caller is responsible for persisting
+    /// these to warm storage (NVMe SSD). Clean accounts are dropped silently,
+    /// as warm storage already holds their last flushed copy.
     ///
-    /// Evicts up to `config.eviction_batch_size` accounts, or until the
-    /// cache is below the eviction watermark, whichever comes first.
+    /// Entries past `entry_ttl_seconds` are expired first, the dirty ones
+    /// returned for write-back like any other eviction. Then up to
+    /// `config.eviction_batch_size` accounts are evicted, or until the cache
+    /// is below the eviction watermark, whichever comes first.
     pub fn evict_to_warm(&mut self) -> Vec<(Pubkey, AccountSharedData)> {
+        let mut evicted = Vec::new();
+        self.expire_stale(&mut evicted);
+
         let watermark = self.config.eviction_watermark();
+        self.evict_batch(watermark, &mut evicted);
+
+        // Update stats
+        self.stats.hot_accounts = self.map.len() as u64;
+        self.stats.hot_size_bytes = self.current_size_bytes;
+
+        evicted
+    }
+
+    /// Install the callback consulted by [`Self::relieve_pressure`].
+    ///
+    /// The callback receives the current cache size in bytes and returns the
+    /// size to evict down to. It replaces any previously installed callback.
+    pub fn set_pressure_callback(&mut self, f: impl Fn(u64) -> u64 + Send + Sync + 'static) {
+        self.pressure_callback = Some(Box::new(f));
+    }
+
+    /// Returns `true` if utilization exceeds `config.pressure_threshold`.
+    pub fn is_under_pressure(&self) -> bool {
+        self.utilization() > self.config.pressure_threshold
+    }
+
+    /// Shed memory if the cache is under pressure and a pressure callback is
+    /// installed, returning the evicted dirty accounts for write-back.
+    ///
+    /// The callback picks the target size, and eviction batches run under the
+    /// configured policy until the cache fits it. If the requested reduction
+    /// is smaller than every cached entry, only the smallest entry is evicted
+    /// rather than whichever one the policy would pick.
+    pub fn relieve_pressure(&mut self) -> Vec<(Pubkey, AccountSharedData)> {
+        if !self.is_under_pressure() {
+            return Vec::new();
+        }
+        let Some(callback) = self.pressure_callback.as_ref() else {
+            return Vec::new();
+        };
+        let target = callback(self.current_size_bytes);
+
         let mut evicted = Vec::new();
+        let excess = self.current_size_bytes.saturating_sub(target);
+        match self.smallest_node() {
+            Some(smallest) if excess < self.nodes[smallest].account.memory_size() => {
+                if excess > 0 {
+                    self.evict_node(smallest, &mut evicted);
+                }
+            }
+            _ => {
+                while self.current_size_bytes > target && !self.map.is_empty() {
+                    let size_before = self.current_size_bytes;
+                    self.evict_batch(target, &mut evicted);
+                    if self.current_size_bytes == size_before {
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.stats.hot_accounts = self.map.len() as u64;
+        self.stats.hot_size_bytes = self.current_size_bytes;
+
+        evicted
+    }
 
+    /// Run one eviction batch of the configured policy towards `watermark`.
+    fn evict_batch(&mut self, watermark: u64, evicted: &mut Vec<(Pubkey, AccountSharedData)>) {
         match self.config.eviction_policy {
-            EvictionPolicy::LRU => {
-                self.evict_lru(watermark, &mut evicted);
+            EvictionPolicy::LRU | EvictionPolicy::LRUK => {
+                self.evict_lru(watermark, evicted);
             }
             EvictionPolicy::LFU => {
-                self.evict_lfu(watermark, &mut evicted);
+                self.evict_lfu(watermark, evicted);
             }
             EvictionPolicy::ARC => {
-                // ARC falls back to LRU for now; full ARC with ghost lists
-                // will be implemented in a subsequent phase.
-                self.evict_lru(watermark, &mut evicted);
+                self.evict_arc(watermark, evicted);
             }
         }
+    }
 
-        // Update stats
-        let evicted_count = evicted.len() as u64;
-        self.stats.hot_to_warm_demotions = self
-            .stats
-            .hot_to_warm_demotions
-            .saturating_add(evicted_count);
-        self.stats.hot_accounts = self.map.len() as u64;
-        self.stats.hot_size_bytes = self.current_size_bytes;
-
-        evicted
+    /// Resident node with the smallest memory footprint, if any.
+    fn smallest_node(&self) -> Option<NodeIndex> {
+        self.map
+            .values()
+            .copied()
+            .min_by_key(|&idx| self.nodes[idx].account.memory_size())
     }
 
     /// LRU eviction: remove from tail (oldest) until below watermark.
+    ///
+    /// Under LRU-K, candidates are instead sorted by their K-th most recent
+    /// access (see `CachedAccount::lru_k_priority`), ties keeping LRU order.
+    /// Sorting makes this O(n log n) per call, unlike plain LRU.
     fn evict_lru(
         &mut self,
         watermark: u64,
         evicted: &mut Vec<(Pubkey, AccountSharedData)>,
     ) {
         let mut count = 0;
+        if self.config.eviction_policy == EvictionPolicy::LRUK && self.config.k > 1 {
+            let k = self.config.k;
+            let mut candidates = Vec::with_capacity(self.map.len());
+            let mut cursor = self.tail;
+            while cursor != NIL {
+                candidates.push(cursor);
+                cursor = self.nodes[cursor].prev; // move toward head (newer)
+            }
+            candidates.sort_by_key(|&idx| self.nodes[idx].account.lru_k_priority(k));
+            for idx in candidates {
+                if self.current_size_bytes <= watermark || count >= self.config.eviction_batch_size
+                {
+                    break;
+                }
+                self.evict_node(idx, evicted);
+                count += 1;
+            }
+            return;
+        }
         while self.current_size_bytes > watermark
             && count < self.config.eviction_batch_size
             && self.tail != NIL
         {
-            let tail_idx = self.tail;
-            let pubkey = self.nodes[tail_idx].pubkey;
-            let account_data = self.nodes[tail_idx].account.data.clone();
-            let size = self.nodes[tail_idx].account.memory_size();
-
-            self.map.remove(&pubkey);
-            self.unlink(tail_idx);
-            self.free_list.push(tail_idx);
-            self.current_size_bytes = self.current_size_bytes.saturating_sub(size);
-
-            evicted.push((pubkey, account_data));
+            self.evict_node(self.tail, evicted);
             count += 1;
         }
     }
@@ -396,20 +858,183 @@ impl AccountCache {
                 break;
             }
 
-            let pubkey = self.nodes[min_idx].pubkey;
-            let account_data = self.nodes[min_idx].account.data.clone();
-            let size = self.nodes[min_idx].account.memory_size();
+            self.evict_node(min_idx, evicted);
+            count += 1;
+        }
+    }
 
-            self.map.remove(&pubkey);
-            self.unlink(min_idx);
-            self.free_list.push(min_idx);
-            self.current_size_bytes = self.current_size_bytes.saturating_sub(size);
+    /// ARC eviction: evict the LRU entry of T1 while T1 is over its target
+    /// size `p` (or T2 is empty), otherwise the LRU entry of T2, remembering
+    /// each evicted key in the matching ghost list.
+    ///
+    /// Finding a list's LRU entry walks from the tail past entries of the
+    /// other list, so this is O(n) in the worst case, like LFU.
+    fn evict_arc(
+        &mut self,
+        watermark: u64,
+        evicted: &mut Vec<(Pubkey, AccountSharedData)>,
+    ) {
+        let ghost_capacity = self.ghost_capacity();
+        let mut count = 0;
+        while self.current_size_bytes > watermark
+            && count < self.config.eviction_batch_size
+            && self.tail != NIL
+        {
+            let t2_empty = self.t1_size_bytes >= self.current_size_bytes;
+            let list =
+                if self.t1_size_bytes > 0 && (self.t1_size_bytes > self.arc.p as u64 || t2_empty) {
+                    ArcList::T1
+                } else {
+                    ArcList::T2
+                };
+            let Some(victim) = self.lru_of(list) else {
+                break;
+            };
 
-            evicted.push((pubkey, account_data));
+            let pubkey = self.evict_node(victim, evicted);
+            match list {
+                ArcList::T1 => self.ghost_b1.push(pubkey, ghost_capacity),
+                ArcList::T2 => self.ghost_b2.push(pubkey, ghost_capacity),
+            }
             count += 1;
         }
     }
 
+    /// Least recently used entry of `list`, if any.
+    fn lru_of(&self, list: ArcList) -> Option<NodeIndex> {
+        let mut cursor = self.tail;
+        while cursor != NIL {
+            if self.nodes[cursor].list == list {
+                return Some(cursor);
+            }
+            cursor = self.nodes[cursor].prev; // move toward head (newer)
+        }
+        None
+    }
+
+    /// Maximum number of keys each ARC ghost list remembers.
+    fn ghost_capacity(&self) -> usize {
+        usize::try_from(self.config.hot_cache_size).unwrap_or(usize::MAX)
+    }
+
+    /// Pick the ARC list for a key being loaded into the cache, adapting the
+    /// target size of T1 on a ghost hit.
+    fn arc_list_for_new_entry(&mut self, pubkey: &Pubkey, entry_size: u64) -> ArcList {
+        if self.config.eviction_policy != EvictionPolicy::ARC {
+            return ArcList::T1;
+        }
+        let max_p = usize::try_from(self.config.eviction_watermark()).unwrap_or(usize::MAX);
+        let entry_size = usize::try_from(entry_size).unwrap_or(usize::MAX);
+        let (b1, b2) = (self.ghost_b1.len().max(1), self.ghost_b2.len().max(1));
+
+        if self.ghost_b1.contains(pubkey) {
+            // Evicted from T1 too early: give recency more room.
+            let delta = (b2 / b1).max(1).saturating_mul(entry_size);
+            self.arc.p = self.arc.p.saturating_add(delta).min(max_p);
+            self.ghost_b1.remove(pubkey);
+            ArcList::T2
+        } else if self.ghost_b2.contains(pubkey) {
+            // Evicted from T2 too early: give frequency more room.
+            let delta = (b1 / b2).max(1).saturating_mul(entry_size);
+            self.arc.p = self.arc.p.saturating_sub(delta);
+            self.ghost_b2.remove(pubkey);
+            ArcList::T2
+        } else {
+            ArcList::T1
+        }
+    }
+
+    /// Move a resident T1 entry to T2 after a repeat reference.
+    fn promote_to_t2(&mut self, idx: NodeIndex) {
+        if self.nodes[idx].list == ArcList::T1 {
+            self.nodes[idx].list = ArcList::T2;
+            self.t1_size_bytes = self
+                .t1_size_bytes
+                .saturating_sub(self.nodes[idx].account.memory_size());
+        }
+    }
+
+    /// Index of the node cached for `pubkey`, expiring it first if it is
+    /// clean and has outlived the configured TTL.
+    fn live_node(&mut self, pubkey: &Pubkey) -> Option<NodeIndex> {
+        let node_idx = *self.map.get(pubkey)?;
+        if !self.nodes[node_idx].account.dirty && self.is_expired(node_idx) {
+            self.expire_node(node_idx, &mut Vec::new());
+            return None;
+        }
+        Some(node_idx)
+    }
+
+    fn is_expired(&self, idx: NodeIndex) -> bool {
+        self.config.entry_ttl_seconds.is_some_and(|ttl| {
+            self.nodes[idx].account.last_accessed.elapsed() > Duration::from_secs(ttl)
+        })
+    }
+
+    /// Expire every entry that has outlived the configured TTL, handing the
+    /// dirty ones to `evicted` for write-back.
+    fn expire_stale(&mut self, evicted: &mut Vec<(Pubkey, AccountSharedData)>) {
+        if self.config.entry_ttl_seconds.is_none() {
+            return;
+        }
+        let mut expired = Vec::new();
+        let mut cursor = self.tail;
+        while cursor != NIL {
+            if self.is_expired(cursor) {
+                expired.push(cursor);
+            }
+            cursor = self.nodes[cursor].prev; // move toward head (newer)
+        }
+        for idx in expired {
+            self.expire_node(idx, evicted);
+        }
+    }
+
+    /// Evict an expired node, handing it to `evicted` for write-back only
+    /// if it is dirty.
+    fn expire_node(&mut self, idx: NodeIndex, evicted: &mut Vec<(Pubkey, AccountSharedData)>) {
+        self.evict_node(idx, evicted);
+        self.stats.ttl_expirations = self.stats.ttl_expirations.saturating_add(1);
+        self.stats.hot_accounts = self.map.len() as u64;
+        self.stats.hot_size_bytes = self.current_size_bytes;
+    }
+
+    /// Evict a resident node, handing it to `evicted` for write-back only if
+    /// it is dirty. Returns the evicted key.
+    fn evict_node(
+        &mut self,
+        idx: NodeIndex,
+        evicted: &mut Vec<(Pubkey, AccountSharedData)>,
+    ) -> Pubkey {
+        let (pubkey, account) = self.detach_node(idx);
+        if account.dirty {
+            self.stats.total_dirty_evictions = self.stats.total_dirty_evictions.saturating_add(1);
+            evicted.push((pubkey, account.data));
+        } else {
+            self.stats.total_clean_evictions = self.stats.total_clean_evictions.saturating_add(1);
+        }
+        self.stats.hot_to_warm_demotions = self.stats.hot_to_warm_demotions.saturating_add(1);
+        pubkey
+    }
+
+    /// Remove a resident node from the map, list and size accounting,
+    /// returning its key and cached entry.
+    fn detach_node(&mut self, idx: NodeIndex) -> (Pubkey, CachedAccount) {
+        let pubkey = self.nodes[idx].pubkey;
+        let account = self.nodes[idx].account.clone();
+        let size = account.memory_size();
+
+        self.map.remove(&pubkey);
+        self.unlink(idx);
+        self.free_list.push(idx);
+        self.current_size_bytes = self.current_size_bytes.saturating_sub(size);
+        if self.nodes[idx].list == ArcList::T1 {
+            self.t1_size_bytes = self.t1_size_bytes.saturating_sub(size);
+        }
+
+        (pubkey, account)
+    }
+
     /// Return a snapshot of the current tier statistics.
     pub fn stats(&self) -> &TierStats {
         &self.stats
@@ -448,6 +1073,87 @@ impl AccountCache {
         self.current_size_bytes as f64 / self.config.hot_cache_size as f64
     }
 
+    /// Write the cached accounts to a snapshot file at `path`, replacing any
+    /// existing file.
+    ///
+    /// Intended for graceful shutdown, after dirty entries have been flushed
+    /// to warm storage: the snapshot only speeds up the next start and is
+    /// never the authoritative copy of an account. Entries are written most
+    /// recently used first; entries past their TTL are skipped.
+    ///
+    /// The file starts with [`CACHE_SNAPSHOT_MAGIC`] and
+    /// [`CACHE_SNAPSHOT_VERSION`], followed by one
+    /// `[pubkey:32][data_len:8][data:N]` entry per account, where `data` is
+    /// the record `[slot:8][lamports:8][owner:32][executable:1][rent_epoch:8]
+    /// [account_data]`. Integers are little-endian.
+    pub fn dump_to_snapshot(&self, path: &Path) -> Result<(), CacheError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&CACHE_SNAPSHOT_MAGIC)?;
+        writer.write_all(&[CACHE_SNAPSHOT_VERSION])?;
+
+        let mut cursor = self.head;
+        while cursor != NIL {
+            if !self.is_expired(cursor) {
+                let node = &self.nodes[cursor];
+                let record = encode_snapshot_record(&node.account.data, node.account.slot);
+                writer.write_all(node.pubkey.as_ref())?;
+                writer.write_all(&(record.len() as u64).to_le_bytes())?;
+                writer.write_all(&record)?;
+            }
+            cursor = self.nodes[cursor].next; // move toward tail (older)
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Load accounts from a snapshot written by [`Self::dump_to_snapshot`],
+    /// returning how many entries were inserted.
+    ///
+    /// Warming stops as soon as [`Self::needs_eviction`] returns `true`, so
+    /// only the most recently used part of a larger snapshot is loaded.
+    /// Keys already cached keep their live copy. Warmed entries are clean,
+    /// keep the slot they were dumped with, and are placed behind any
+    /// existing entries, in snapshot order.
+    ///
+    /// On a corrupt entry an error is returned; entries read before it stay
+    /// cached.
+    pub fn warm_from_snapshot(&mut self, path: &Path) -> Result<usize, CacheError> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut magic = [0u8; 4];
+        let mut version = [0u8; 1];
+        reader
+            .read_exact(&mut magic)
+            .and_then(|()| reader.read_exact(&mut version))
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::UnexpectedEof => CacheError::Corrupt("truncated header".to_string()),
+                _ => CacheError::Io(e),
+            })?;
+        if magic != CACHE_SNAPSHOT_MAGIC {
+            return Err(CacheError::InvalidMagic(magic));
+        }
+        if version[0] != CACHE_SNAPSHOT_VERSION {
+            return Err(CacheError::UnsupportedVersion(version[0]));
+        }
+
+        let mut warmed = 0;
+        while !self.needs_eviction() {
+            let Some((pubkey, account, slot)) = read_snapshot_entry(&mut reader)? else {
+                break;
+            };
+            if self.map.contains_key(&pubkey) {
+                continue;
+            }
+            self.insert(pubkey, account, slot);
+            let node_idx = self.map[&pubkey];
+            self.nodes[node_idx].account.dirty = false;
+            self.move_to_tail(node_idx);
+            warmed += 1;
+            self.stats.warmed_entries = self.stats.warmed_entries.saturating_add(1);
+        }
+        Ok(warmed)
+    }
+
     // ── Linked List Operations ──────────────────────────────────────────
 
     /// Allocate a node, recycling from the free list if possible.
@@ -477,6 +1183,21 @@ impl AccountCache {
         }
     }
 
+    /// Insert a node at the tail of the list.
+    fn push_tail(&mut self, idx: NodeIndex) {
+        self.nodes[idx].next = NIL;
+        self.nodes[idx].prev = self.tail;
+
+        if self.tail != NIL {
+            self.nodes[self.tail].next = idx;
+        }
+        self.tail = idx;
+
+        if self.head == NIL {
+            self.head = idx;
+        }
+    }
+
     /// Remove a node from its current position in the list.
     fn unlink(&mut self, idx: NodeIndex) {
         let prev = self.nodes[idx].prev;
@@ -508,6 +1229,169 @@ impl AccountCache {
         self.unlink(idx);
         self.push_head(idx);
     }
+
+    /// Move an existing node to the tail of the list.
+    fn move_to_tail(&mut self, idx: NodeIndex) {
+        if idx == self.tail {
+            return; // already at tail
+        }
+        self.unlink(idx);
+        self.push_tail(idx);
+    }
+}
+
+// ── Sharded Cache ───────────────────────────────────────────────────────────
+
+/// Default number of shards for a [`ShardedAccountCache`].
+pub const DEFAULT_NUM_SHARDS: usize = 16;
+
+/// A concurrent account cache made of independently locked [`AccountCache`]
+/// shards.
+///
+/// An account lives in shard `pubkey.as_ref()[0] % num_shards`, so every
+/// operation locks exactly one shard and accesses to different shards
+/// proceed in parallel. Each shard receives an equal share of
+/// `hot_cache_size` and evicts on its own.
+pub struct ShardedAccountCache {
+    shards: Vec<Mutex<AccountCache>>,
+    num_shards: usize,
+}
+
+impl ShardedAccountCache {
+    /// Create a cache with `num_shards` shards.
+    ///
+    /// Shards are selected by a single pubkey byte, so `num_shards` is
+    /// clamped to `1..=256`.
+    pub fn new(config: TieredStorageConfig, num_shards: usize) -> Self {
+        let num_shards = num_shards.clamp(1, 256);
+        let shard_config = TieredStorageConfig {
+            hot_cache_size: config.hot_cache_size / num_shards as u64,
+            ..config
+        };
+        let shards = (0..num_shards)
+            .map(|_| Mutex::new(AccountCache::new(shard_config.clone())))
+            .collect();
+        Self { shards, num_shards }
+    }
+
+    /// Number of shards.
+    pub fn num_shards(&self) -> usize {
+        self.num_shards
+    }
+
+    /// Look up an account, locking only its shard.
+    ///
+    /// Returns a clone of the cached data; the shard is unlocked on return.
+    pub fn get(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+        self.shard(pubkey).get(pubkey).cloned()
+    }
+
+    /// Check if a pubkey is cached without updating its recency.
+    pub fn contains(&self, pubkey: &Pubkey) -> bool {
+        self.shard(pubkey).contains(pubkey)
+    }
+
+    /// Look up an account written at `min_slot` or later, locking only its
+    /// shard. See [`AccountCache::get_at_slot`].
+    pub fn get_at_slot(&self, pubkey: &Pubkey, min_slot: u64) -> Option<AccountSharedData> {
+        self.shard(pubkey).get_at_slot(pubkey, min_slot).cloned()
+    }
+
+    /// Insert or update an account written at bank `slot` in its shard.
+    ///
+    /// Like [`AccountCache::insert`], this does not evict.
+    pub fn insert(&self, pubkey: Pubkey, account: AccountSharedData, slot: u64) {
+        self.shard(&pubkey).insert(pubkey, account, slot);
+    }
+
+    /// Remove an account, returning its data if it was cached.
+    pub fn remove(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+        self.shard(pubkey).remove(pubkey)
+    }
+
+    /// Collect and clear the dirty entries of every shard.
+    pub fn flush_dirty(&self) -> Vec<(Pubkey, AccountSharedData)> {
+        let mut flushed = Vec::new();
+        for shard in &self.shards {
+            flushed.extend(shard.lock().unwrap().flush_dirty());
+        }
+        flushed
+    }
+
+    /// Remove every entry last written at `slot` from all shards, returning
+    /// how many were dropped.
+    ///
+    /// Every shard is locked before any is modified, so no reader can observe
+    /// some shards invalidated and others not.
+    pub fn invalidate_slot(&self, slot: u64) -> usize {
+        let mut shards: Vec<_> = self
+            .shards
+            .iter()
+            .map(|shard| shard.lock().unwrap())
+            .collect();
+        shards
+            .iter_mut()
+            .map(|shard| shard.invalidate_slot(slot))
+            .sum()
+    }
+
+    /// Clear the dirty bit of a cached account after write-back.
+    pub fn mark_clean(&self, pubkey: &Pubkey) {
+        self.shard(pubkey).mark_clean(pubkey);
+    }
+
+    /// Returns `true` if any shard exceeds its eviction watermark.
+    pub fn needs_eviction(&self) -> bool {
+        self.shards
+            .iter()
+            .any(|shard| shard.lock().unwrap().needs_eviction())
+    }
+
+    /// Run [`AccountCache::evict_to_warm`] on every shard over its watermark,
+    /// returning all evicted dirty accounts.
+    pub fn evict_to_warm(&self) -> Vec<(Pubkey, AccountSharedData)> {
+        let mut evicted = Vec::new();
+        for shard in &self.shards {
+            let mut shard = shard.lock().unwrap();
+            if shard.needs_eviction() {
+                evicted.extend(shard.evict_to_warm());
+            }
+        }
+        evicted
+    }
+
+    /// Total number of cached accounts across all shards.
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.lock().unwrap().len())
+            .sum()
+    }
+
+    /// Returns `true` if no shard holds any account.
+    pub fn is_empty(&self) -> bool {
+        self.shards
+            .iter()
+            .all(|shard| shard.lock().unwrap().is_empty())
+    }
+
+    /// Statistics summed across all shards.
+    ///
+    /// Shards are locked one at a time, so the result is not an atomic
+    /// snapshot while other threads are mutating the cache.
+    pub fn aggregate_stats(&self) -> TierStats {
+        self.shards
+            .iter()
+            .fold(TierStats::default(), |mut total, shard| {
+                total.merge(shard.lock().unwrap().stats());
+                total
+            })
+    }
+
+    fn shard(&self, pubkey: &Pubkey) -> MutexGuard<'_, AccountCache> {
+        let index = pubkey.as_ref()[0] as usize % self.num_shards;
+        self.shards[index].lock().unwrap()
+    }
 }
 
 #[cfg(test)]
@@ -545,7 +1429,7 @@ mod tests {
         let pubkey = Pubkey::new_unique();
         let account = make_account(100);
 
-        cache.insert(pubkey, account.clone());
+        cache.insert(pubkey, account.clone(), 0);
         assert_eq!(cache.len(), 1);
         assert!(cache.contains(&pubkey));
 
@@ -566,10 +1450,10 @@ mod tests {
         let mut cache = AccountCache::new(test_config(1_000_000));
         let pubkey = Pubkey::new_unique();
 
-        cache.insert(pubkey, make_account(100));
+        cache.insert(pubkey, make_account(100), 0);
         let size_after_first = cache.current_size_bytes();
 
-        cache.insert(pubkey, make_account(200));
+        cache.insert(pubkey, make_account(200), 0);
         assert_eq!(cache.len(), 1); // still just one entry
         assert!(cache.current_size_bytes() > size_after_first);
 
@@ -581,7 +1465,7 @@ mod tests {
     fn test_remove() {
         let mut cache = AccountCache::new(test_config(1_000_000));
         let pubkey = Pubkey::new_unique();
-        cache.insert(pubkey, make_account(100));
+        cache.insert(pubkey, make_account(100), 0);
 
         let removed = cache.remove(&pubkey);
         assert!(removed.is_some());
@@ -600,10 +1484,10 @@ mod tests {
         let pk4 = Pubkey::new_unique();
 
         // Insert 4 accounts, each ~160+10 = 170 bytes overhead
-        cache.insert(pk1, make_account(10));
-        cache.insert(pk2, make_account(10));
-        cache.insert(pk3, make_account(10));
-        cache.insert(pk4, make_account(10));
+        cache.insert(pk1, make_account(10), 0);
+        cache.insert(pk2, make_account(10), 0);
+        cache.insert(pk3, make_account(10), 0);
+        cache.insert(pk4, make_account(10), 0);
 
         // Cache should be over the watermark (700 * 0.9 = 630)
         // 4 * 170 = 680, which is > 630
@@ -626,9 +1510,9 @@ mod tests {
         let pk2 = Pubkey::new_unique();
         let pk3 = Pubkey::new_unique();
 
-        cache.insert(pk1, make_account(10));
-        cache.insert(pk2, make_account(10));
-        cache.insert(pk3, make_account(10));
+        cache.insert(pk1, make_account(10), 0);
+        cache.insert(pk2, make_account(10), 0);
+        cache.insert(pk3, make_account(10), 0);
 
         // pk1 is currently the oldest (tail). Access it to promote.
         cache.get(&pk1);
@@ -643,7 +1527,7 @@ mod tests {
     fn test_cache_hit_rate() {
         let mut cache = AccountCache::new(test_config(1_000_000));
         let pk1 = Pubkey::new_unique();
-        cache.insert(pk1, make_account(100));
+        cache.insert(pk1, make_account(100), 0);
 
         // 3 hits
         cache.get(&pk1);
@@ -662,7 +1546,7 @@ mod tests {
         let mut cache = AccountCache::new(test_config(1000));
         assert_eq!(cache.utilization(), 0.0);
 
-        cache.insert(Pubkey::new_unique(), make_account(10));
+        cache.insert(Pubkey::new_unique(), make_account(10), 0);
         assert!(cache.utilization() > 0.0);
     }
 
@@ -670,7 +1554,7 @@ mod tests {
     fn test_stats_tracking() {
         let mut cache = AccountCache::new(test_config(1_000_000));
         let pk1 = Pubkey::new_unique();
-        cache.insert(pk1, make_account(100));
+        cache.insert(pk1, make_account(100), 0);
 
         assert_eq!(cache.stats().hot_accounts, 1);
         assert!(cache.stats().hot_size_bytes > 0);
@@ -697,9 +1581,9 @@ mod tests {
         let pk3 = Pubkey::new_unique();
         let pk4 = Pubkey::new_unique();
 
-        cache.insert(pk1, make_account(10));
-        cache.insert(pk2, make_account(10));
-        cache.insert(pk3, make_account(10));
+        cache.insert(pk1, make_account(10), 0);
+        cache.insert(pk2, make_account(10), 0);
+        cache.insert(pk3, make_account(10), 0);
 
         // Access pk1 many times to increase its frequency
         for _ in 0..10 {
@@ -711,7 +1595,7 @@ mod tests {
         }
         // pk2 has access_count = 1 (just the insert)
 
-        cache.insert(pk4, make_account(10));
+        cache.insert(pk4, make_account(10), 0);
 
         // Force eviction
         if cache.needs_eviction() {
@@ -732,7 +1616,7 @@ mod tests {
         // Insert 100 accounts
         let pubkeys: Vec<Pubkey> = (0..100).map(|_| Pubkey::new_unique()).collect();
         for pk in &pubkeys {
-            cache.insert(*pk, make_account(50));
+            cache.insert(*pk, make_account(50), 0);
         }
 
         // Evict
@@ -747,4 +1631,537 @@ mod tests {
         assert!(!cache.needs_eviction() || cache.is_empty());
         assert!(cache.stats().hot_to_warm_demotions > 0);
     }
+    #[test]
+    fn test_arc_evicts_recent_before_frequent() {
+        let mut cache = AccountCache::new(TieredStorageConfig {
+            eviction_policy: EvictionPolicy::ARC,
+            ..test_config(700)
+        });
+
+        let frequent = Pubkey::new_unique();
+        cache.insert(frequent, make_account(10), 0);
+        cache.get(&frequent); // promoted to T2
+
+        let scanned: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        for pk in &scanned {
+            cache.insert(*pk, make_account(10), 0);
+        }
+
+        // 4 × 170 = 680 > 630 watermark: the oldest T1 entry goes, even
+        // though `frequent` is older in pure recency order.
+        let evicted = cache.evict_to_warm();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].0, scanned[0]);
+        assert!(cache.contains(&frequent));
+
+        // Re-inserting a ghost key lands it in T2 and grows T1's target.
+        cache.insert(scanned[0], make_account(10), 0);
+        assert!(cache.arc.p > 0);
+        assert!(!cache.ghost_b1.contains(&scanned[0]));
+    }
+
+    /// Run `keys` through `cache`, loading misses and evicting as needed.
+    fn replay(cache: &mut AccountCache, keys: &[Pubkey]) {
+        for pk in keys {
+            if cache.get(pk).is_none() {
+                cache.insert(*pk, make_account(10), 0);
+                if cache.needs_eviction() {
+                    cache.evict_to_warm();
+                }
+            }
+        }
+    }
+
+    /// Hit rate of `cache` over one replay of `keys`.
+    fn phase_hit_rate(cache: &mut AccountCache, keys: &[Pubkey]) -> f64 {
+        let before = cache.stats().clone();
+        replay(cache, keys);
+        let hits = cache.stats().total_hits - before.total_hits;
+        let misses = cache.stats().total_misses - before.total_misses;
+        hits as f64 / (hits + misses) as f64
+    }
+
+    #[test]
+    fn test_arc_outperforms_lru_on_scans() {
+        // Room for 50 entries of 170 bytes; the working set is 40 keys.
+        let config = |eviction_policy| TieredStorageConfig {
+            hot_cache_size: 50 * 170,
+            eviction_policy,
+            eviction_batch_size: 64,
+            target_utilization: 1.0,
+            ..TieredStorageConfig::for_testing()
+        };
+        let mut lru = AccountCache::new(config(EvictionPolicy::LRU));
+        let mut arc = AccountCache::new(config(EvictionPolicy::ARC));
+
+        let hot: Vec<Pubkey> = (0..40).map(|_| Pubkey::new_unique()).collect();
+
+        for _ in 0..2 {
+            // Frequency-heavy: the working set alone, over and over.
+            let frequency_phase: Vec<Pubkey> = hot.iter().cycle().take(40 * 20).copied().collect();
+            let lru_rate = phase_hit_rate(&mut lru, &frequency_phase);
+            let arc_rate = phase_hit_rate(&mut arc, &frequency_phase);
+            assert!(arc_rate >= lru_rate - 0.05, "arc {arc_rate} lru {lru_rate}");
+
+            // Scan-heavy: every round touches the working set and then scans
+            // 60 never-seen-again keys, more than the cache can hold.
+            let mut scan_phase = Vec::new();
+            for _ in 0..20 {
+                scan_phase.extend_from_slice(&hot);
+                scan_phase.extend((0..60).map(|_| Pubkey::new_unique()));
+            }
+            let lru_rate = phase_hit_rate(&mut lru, &scan_phase);
+            let arc_rate = phase_hit_rate(&mut arc, &scan_phase);
+            assert!(
+                arc_rate >= lru_rate + 0.20,
+                "scan phase: arc {arc_rate} lru {lru_rate}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_lru_k_keeps_hot_account_through_scan() {
+        // Room for 20 entries of 170 bytes.
+        let config = |eviction_policy, k| TieredStorageConfig {
+            hot_cache_size: 20 * 170,
+            eviction_policy,
+            eviction_batch_size: 64,
+            target_utilization: 1.0,
+            k,
+            ..TieredStorageConfig::for_testing()
+        };
+        let mut lru = AccountCache::new(config(EvictionPolicy::LRU, 2));
+        let mut lru_1 = AccountCache::new(config(EvictionPolicy::LRUK, 1));
+        let mut lru_2 = AccountCache::new(config(EvictionPolicy::LRUK, 2));
+
+        let hot = Pubkey::new_unique();
+        let scan: Vec<Pubkey> = (0..50).map(|_| Pubkey::new_unique()).collect();
+        for cache in [&mut lru, &mut lru_1, &mut lru_2] {
+            cache.insert(hot, make_account(10), 0);
+            cache.get(&hot); // second access
+            replay(cache, &scan);
+        }
+
+        // Plain LRU, and LRU-K with K = 1, let the scan push `hot` out.
+        assert!(!lru.contains(&hot));
+        assert!(!lru_1.contains(&hot));
+        // With K = 2 the one-off scan keys always go first.
+        assert!(lru_2.contains(&hot));
+        assert_eq!(lru_2.len(), 20);
+        assert!(lru_2.contains(&scan[49]));
+        assert!(!lru_2.contains(&scan[0]));
+    }
+
+    #[test]
+    fn test_sharded_routes_by_first_byte() {
+        let cache = ShardedAccountCache::new(test_config(1_000_000), 4);
+        assert_eq!(cache.num_shards(), 4);
+
+        let pk = Pubkey::new_from_array([6; 32]);
+        cache.insert(pk, make_account(10), 0);
+        assert_eq!(cache.get(&pk).unwrap().data().len(), 10);
+
+        for (index, shard) in cache.shards.iter().enumerate() {
+            assert_eq!(shard.lock().unwrap().contains(&pk), index == 2);
+        }
+
+        assert!(cache.remove(&pk).is_some());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_sharded_aggregate_stats() {
+        let cache = ShardedAccountCache::new(test_config(1_000_000), 8);
+        let pubkeys: Vec<Pubkey> = (0..=255u8)
+            .map(|byte| Pubkey::new_from_array([byte; 32]))
+            .collect();
+        for pk in &pubkeys {
+            cache.insert(*pk, make_account(10), 0);
+        }
+        for pk in &pubkeys[..100] {
+            assert!(cache.get(pk).is_some());
+        }
+        assert!(cache.get(&Pubkey::new_unique()).is_none());
+
+        let stats = cache.aggregate_stats();
+        assert_eq!(stats.hot_accounts, 256);
+        assert_eq!(stats.hot_size_bytes, 256 * 170);
+        assert_eq!(stats.total_hits, 100);
+        assert_eq!(stats.total_misses, 1);
+        assert_eq!(cache.len(), 256);
+    }
+
+    #[test]
+    fn test_sharded_needs_eviction_if_any_shard_full() {
+        // Each of the 2 shards gets 700 bytes, i.e. a 630 byte watermark.
+        let cache = ShardedAccountCache::new(test_config(1_400), 2);
+        for i in 0..4u8 {
+            let mut bytes = [0; 32];
+            bytes[0] = 2;
+            bytes[1] = i;
+            cache.insert(Pubkey::new_from_array(bytes), make_account(10), 0);
+        }
+        assert!(cache.needs_eviction());
+
+        let evicted = cache.evict_to_warm();
+        assert!(!evicted.is_empty());
+        assert!(!cache.needs_eviction());
+    }
+
+    #[test]
+    fn test_sharded_concurrent_access() {
+        // Kept small so it also runs under Miri (ci/test-miri.sh).
+        let cache = ShardedAccountCache::new(test_config(1_000_000), 4);
+        std::thread::scope(|scope| {
+            for thread in 0..4u8 {
+                let cache = &cache;
+                scope.spawn(move || {
+                    for i in 0..16u8 {
+                        let pk =
+                            Pubkey::new_from_array([i.wrapping_mul(4).wrapping_add(thread); 32]);
+                        cache.insert(pk, make_account(i as usize), 0);
+                        assert_eq!(cache.get(&pk).unwrap().data().len(), i as usize);
+                    }
+                });
+            }
+        });
+        assert_eq!(cache.len(), 64);
+        assert_eq!(cache.aggregate_stats().total_hits, 64);
+    }
+
+    #[test]
+    fn test_insert_marks_dirty_and_flush_clears() {
+        let mut cache = AccountCache::new(test_config(10_000));
+        let pk1 = Pubkey::new_unique();
+        let pk2 = Pubkey::new_unique();
+        cache.insert(pk1, make_account(10), 0);
+        cache.insert(pk2, make_account(20), 0);
+        assert!(cache.is_dirty(&pk1));
+
+        let flushed = cache.flush_dirty();
+        let flushed_pubkeys: Vec<Pubkey> = flushed.iter().map(|(pk, _)| *pk).collect();
+        assert_eq!(flushed_pubkeys, vec![pk1, pk2]);
+        assert!(!cache.is_dirty(&pk1));
+        assert!(cache.flush_dirty().is_empty());
+
+        // Writing new data dirties the entry again...
+        cache.insert(pk1, make_account(30), 0);
+        assert!(cache.is_dirty(&pk1));
+        cache.mark_clean(&pk1);
+        assert!(!cache.is_dirty(&pk1));
+
+        // ...but rewriting identical data to a clean entry is coalesced.
+        cache.insert(pk1, make_account(30), 0);
+        assert!(!cache.is_dirty(&pk1));
+    }
+
+    #[test]
+    fn test_eviction_returns_only_dirty_entries() {
+        let mut cache = AccountCache::new(test_config(700));
+        let clean = Pubkey::new_unique();
+        cache.insert(clean, make_account(10), 0);
+        cache.mark_clean(&clean);
+
+        let dirty: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        for pk in &dirty {
+            cache.insert(*pk, make_account(10), 0);
+        }
+
+        // 680 > 630: only the oldest (clean) entry must go.
+        let evicted = cache.evict_to_warm();
+        assert!(evicted.is_empty());
+        assert!(!cache.contains(&clean));
+        assert!(!cache.needs_eviction());
+
+        let stats = cache.stats();
+        assert_eq!(stats.total_clean_evictions, 1);
+        assert_eq!(stats.total_dirty_evictions, 0);
+        assert_eq!(stats.hot_to_warm_demotions, 1);
+    }
+
+    #[test]
+    fn test_clean_eviction_and_reinsert_has_no_dirty_evictions() {
+        let mut cache = AccountCache::new(test_config(700));
+        let pk = Pubkey::new_unique();
+        let account = make_account(10);
+        cache.insert(pk, account.clone(), 0);
+        assert_eq!(cache.flush_dirty().len(), 1);
+
+        // Push `pk` out with clean fillers.
+        for _ in 0..3 {
+            let filler = Pubkey::new_unique();
+            cache.insert(filler, make_account(10), 0);
+            cache.mark_clean(&filler);
+        }
+        assert!(cache.evict_to_warm().is_empty());
+        assert!(!cache.contains(&pk));
+
+        // Reload the same data from warm storage, which is clean by
+        // definition, and evict again.
+        cache.insert(pk, account, 0);
+        cache.mark_clean(&pk);
+        assert!(cache.needs_eviction());
+        assert!(cache.evict_to_warm().is_empty());
+
+        assert_eq!(cache.stats().total_dirty_evictions, 0);
+        assert_eq!(cache.stats().total_clean_evictions, 2);
+    }
+
+    #[test]
+    fn test_ttl_expired_entry_is_a_miss() {
+        let mut cache = AccountCache::new(TieredStorageConfig {
+            entry_ttl_seconds: Some(1),
+            ..test_config(10_000)
+        });
+        let pk = Pubkey::new_unique();
+        cache.insert(pk, make_account(10), 0);
+        cache.mark_clean(&pk);
+        assert!(cache.get(&pk).is_some());
+
+        std::thread::sleep(Duration::from_millis(1_100));
+
+        assert!(cache.get(&pk).is_none());
+        assert!(!cache.contains(&pk));
+        assert!(cache.is_empty());
+        let stats = cache.stats();
+        assert_eq!(stats.ttl_expirations, 1);
+        assert_eq!(stats.total_clean_evictions, 1);
+        assert_eq!(stats.total_dirty_evictions, 0);
+        assert_eq!(stats.total_misses, 1);
+    }
+
+    #[test]
+    fn test_evict_to_warm_sweeps_expired_entries() {
+        let mut cache = AccountCache::new(TieredStorageConfig {
+            entry_ttl_seconds: Some(1),
+            ..test_config(10_000)
+        });
+        let stale: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        for pk in &stale {
+            cache.insert(*pk, make_account(10), 0);
+        }
+        std::thread::sleep(Duration::from_millis(1_100));
+        let fresh = Pubkey::new_unique();
+        cache.insert(fresh, make_account(10), 0);
+
+        // Below the watermark, yet the expired entries are still dropped.
+        assert!(!cache.needs_eviction());
+        cache.flush_dirty();
+        assert!(cache.evict_to_warm().is_empty());
+        assert_eq!(cache.len(), 1);
+        assert!(cache.contains(&fresh));
+        assert_eq!(cache.stats().ttl_expirations, 3);
+    }
+
+    #[test]
+    fn test_expired_dirty_entry_is_written_back() {
+        let mut cache = AccountCache::new(TieredStorageConfig {
+            entry_ttl_seconds: Some(1),
+            ..test_config(10_000)
+        });
+        let (dirty, clean) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let account = make_account(10);
+        cache.insert(dirty, account.clone(), 0);
+        cache.insert(clean, make_account(10), 0);
+        cache.mark_clean(&clean);
+        std::thread::sleep(Duration::from_millis(1_100));
+
+        // The dirty entry holds the only copy of its changes, so a read
+        // still sees it rather than reloading a stale copy.
+        assert_eq!(cache.get(&dirty), Some(&account));
+
+        std::thread::sleep(Duration::from_millis(1_100));
+        assert_eq!(cache.evict_to_warm(), vec![(dirty, account)]);
+        assert!(cache.is_empty());
+        let stats = cache.stats();
+        assert_eq!(stats.ttl_expirations, 2);
+        assert_eq!(stats.total_dirty_evictions, 1);
+        assert_eq!(stats.total_clean_evictions, 1);
+    }
+
+    #[test]
+    fn test_snapshot_dump_and_warm_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hot-cache.snapshot");
+
+        let mut cache = AccountCache::new(test_config(1_000_000));
+        let mut accounts = Vec::new();
+        for i in 0..100u64 {
+            let pubkey = Pubkey::new_unique();
+            let mut account = AccountSharedData::new(i + 1, 0, &Pubkey::new_unique());
+            account.set_data_from_slice(&vec![i as u8; i as usize]);
+            account.set_executable(i % 7 == 0);
+            cache.insert(pubkey, account.clone(), i);
+            accounts.push((pubkey, account, i));
+        }
+        cache.dump_to_snapshot(&path).unwrap();
+
+        let mut warmed = AccountCache::new(test_config(1_000_000));
+        assert_eq!(warmed.warm_from_snapshot(&path).unwrap(), 100);
+        assert_eq!(warmed.len(), 100);
+        assert_eq!(warmed.stats().warmed_entries, 100);
+        assert!(warmed.flush_dirty().is_empty());
+        for (pubkey, account, slot) in &accounts {
+            assert!(!warmed.is_dirty(pubkey));
+            assert!(warmed.get_at_slot(pubkey, slot + 1).is_none());
+            assert_eq!(warmed.get_at_slot(pubkey, *slot), Some(account));
+        }
+    }
+
+    #[test]
+    fn test_warm_from_snapshot_stops_at_watermark() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hot-cache.snapshot");
+
+        let mut cache = AccountCache::new(test_config(1_000_000));
+        let pubkeys: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+        for pubkey in &pubkeys {
+            cache.insert(*pubkey, make_account(10), 0);
+        }
+        cache.dump_to_snapshot(&path).unwrap();
+
+        // The fourth 170-byte entry crosses the 630-byte watermark.
+        let mut small = AccountCache::new(test_config(700));
+        let warmed = small.warm_from_snapshot(&path).unwrap();
+        assert_eq!(warmed, 4);
+        assert!(small.needs_eviction());
+        // The most recently used accounts are loaded first...
+        assert!(pubkeys[6..].iter().all(|pk| small.contains(pk)));
+        // ...and stay ahead of the colder ones in eviction order.
+        small.evict_to_warm();
+        assert!(small.contains(&pubkeys[9]));
+        assert!(!small.contains(&pubkeys[6]));
+    }
+
+    #[test]
+    fn test_warm_from_snapshot_rejects_bad_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hot-cache.snapshot");
+        let mut cache = AccountCache::new(test_config(1_000_000));
+
+        std::fs::write(&path, b"NOPE\x01").unwrap();
+        assert!(matches!(
+            cache.warm_from_snapshot(&path),
+            Err(CacheError::InvalidMagic(magic)) if &magic == b"NOPE"
+        ));
+
+        let mut bytes = CACHE_SNAPSHOT_MAGIC.to_vec();
+        bytes.push(CACHE_SNAPSHOT_VERSION + 1);
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(
+            cache.warm_from_snapshot(&path),
+            Err(CacheError::UnsupportedVersion(v)) if v == CACHE_SNAPSHOT_VERSION + 1
+        ));
+
+        // A truncated entry is reported, not silently dropped.
+        bytes.pop();
+        bytes.push(CACHE_SNAPSHOT_VERSION);
+        bytes.extend_from_slice(Pubkey::new_unique().as_ref());
+        bytes.extend_from_slice(&100u64.to_le_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(
+            cache.warm_from_snapshot(&path),
+            Err(CacheError::Corrupt(_))
+        ));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_get_at_slot_and_invalidate_slot() {
+        let mut cache = AccountCache::new(test_config(1_000_000));
+        let pk = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        cache.insert(pk, make_account(10), 5);
+        cache.insert(other, make_account(10), 4);
+
+        assert!(cache.get_at_slot(&pk, 5).is_some());
+        assert!(cache.get_at_slot(&pk, 6).is_none());
+        assert_eq!(cache.stats().total_misses, 1);
+        // A stale read leaves the entry in place.
+        assert!(cache.contains(&pk));
+
+        assert_eq!(cache.invalidate_slot(5), 1);
+        assert!(!cache.contains(&pk));
+        assert!(cache.get_at_slot(&pk, 0).is_none());
+        assert!(cache.contains(&other));
+        assert_eq!(cache.stats().slot_invalidations, 1);
+        assert_eq!(cache.stats().hot_accounts, 1);
+
+        // Rewriting an account moves it to the new slot.
+        cache.insert(other, make_account(10), 6);
+        assert_eq!(cache.invalidate_slot(4), 0);
+        assert!(cache.get_at_slot(&other, 6).is_some());
+    }
+
+    #[test]
+    fn test_sharded_invalidate_slot_covers_all_shards() {
+        let cache = ShardedAccountCache::new(test_config(1_000_000), 4);
+        let pubkeys: Vec<Pubkey> = (0..4u8).map(|i| Pubkey::new_from_array([i; 32])).collect();
+        for pk in &pubkeys {
+            cache.insert(*pk, make_account(10), 5);
+        }
+        let survivor = Pubkey::new_from_array([9; 32]);
+        cache.insert(survivor, make_account(10), 6);
+
+        assert_eq!(cache.invalidate_slot(5), 4);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get_at_slot(&survivor, 6).is_some());
+        assert_eq!(cache.aggregate_stats().slot_invalidations, 4);
+    }
+
+    #[test]
+    fn test_pressure_callback_called_above_threshold() {
+        use std::sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        };
+
+        // Pressure starts above 950 bytes, i.e. at the sixth 170-byte entry.
+        let mut cache = AccountCache::new(TieredStorageConfig {
+            pressure_threshold: 0.95,
+            ..test_config(1_000)
+        });
+        let seen_size = Arc::new(AtomicU64::new(0));
+        let seen = seen_size.clone();
+        cache.set_pressure_callback(move |size| {
+            seen.store(size, Ordering::Relaxed);
+            size / 2
+        });
+
+        for _ in 0..5 {
+            cache.insert(Pubkey::new_unique(), make_account(10), 0);
+        }
+        assert!(!cache.is_under_pressure());
+        assert!(cache.relieve_pressure().is_empty());
+        assert_eq!(seen_size.load(Ordering::Relaxed), 0);
+
+        cache.insert(Pubkey::new_unique(), make_account(10), 0);
+        assert!(cache.is_under_pressure());
+        let evicted = cache.relieve_pressure();
+        assert_eq!(seen_size.load(Ordering::Relaxed), 1_020);
+        assert_eq!(evicted.len(), 3);
+        assert!(cache.current_size_bytes() <= 510);
+        assert!(!cache.is_under_pressure());
+    }
+
+    #[test]
+    fn test_pressure_below_one_entry_evicts_smallest() {
+        let mut cache = AccountCache::new(TieredStorageConfig {
+            pressure_threshold: 0.5,
+            ..test_config(1_000)
+        });
+        cache.set_pressure_callback(|size| size - 1);
+
+        let large = Pubkey::new_unique();
+        let small = Pubkey::new_unique();
+        cache.insert(large, make_account(300), 0);
+        cache.insert(small, make_account(10), 0);
+        cache.insert(Pubkey::new_unique(), make_account(100), 0);
+
+        // LRU would pick `large`; shedding one byte only needs `small`.
+        let evicted = cache.relieve_pressure();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].0, small);
+        assert!(cache.contains(&large));
+    }
 }
@@ -3960,9 +3960,9 @@ impl AccountsDb {
 
         // TRv1: Populate hot cache on storage hit
         #[cfg(feature = "trv1-tiered-storage")]
-        if let Some((ref account, _slot)) = result {
+        if let Some((ref account, slot)) = result {
             if let Some(ref adapter) = self.trv1_adapter {
-                adapter.hot_cache_insert(pubkey, account);
+                adapter.hot_cache_insert(pubkey, account, slot);
             }
         }
 
@@ -4525,6 +4525,14 @@ impl AccountsDb {
         );
         remove_unrooted_purge_stats.report("remove_unrooted_slots_purge_slots_stats", None);
 
+        // TRv1: Drop hot cache entries written by the removed banks
+        #[cfg(feature = "trv1-tiered-storage")]
+        if let Some(ref adapter) = self.trv1_adapter {
+            for (slot, _) in remove_slots {
+                adapter.hot_cache_invalidate_slot(*slot);
+            }
+        }
+
         let mut currently_contended_slots = slots_under_contention.lock().unwrap();
         for (remove_slot, _) in remove_slots {
             assert!(currently_contended_slots.remove(remove_slot));
@@ -5711,7 +5719,7 @@ impl AccountsDb {
             for index in 0..accounts.len() {
                 let pubkey = accounts.pubkey(index);
                 accounts.account_for_geyser(index, |_pk, account| {
-                    adapter.hot_cache_insert(pubkey, account);
+                    adapter.hot_cache_insert(pubkey, account, accounts.target_slot());
                 });
             }
         }
@@ -0,0 +1,335 @@
+//! TRv1 Archival Worker — background thread for state rent archival
+//!
+//! Evaluating and archiving accounts writes to cold storage, which is far too
+//! slow for the epoch transition itself. Instead, at each epoch boundary the
+//! runtime enqueues the accounts it considers candidates as an
+//! [`ArchivalTask`], and the [`ArchivalWorker`] thread works through them:
+//!
+//! 1. Candidates are ordered largest data first, so the accounts that free
+//!    the most storage are archived first.
+//! 2. Each candidate is checked with [`check_rent_expiry`].
+//! 3. Eligible accounts are written out with [`archive_account`] and
+//!    registered in the shared [`ArchiveIndex`].
+//!
+//! The task queue is bounded. When it is full, [`ArchivalWorker::enqueue`]
+//! blocks for up to the configured timeout and then hands the task back,
+//! counting the event in [`ArchivalWorkerStats::archival_queue_full`].
+
+use {
+    crate::state_rent_expiry::{archive_account, check_rent_expiry, ArchiveIndex, StateRentConfig},
+    crossbeam_channel::{bounded, Receiver, SendTimeoutError, Sender},
+    log::*,
+    solana_account::{AccountSharedData, ReadableAccount},
+    solana_pubkey::Pubkey,
+    std::{
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+        thread::{self, JoinHandle},
+        time::Duration,
+    },
+};
+
+/// Default number of tasks that may wait in the queue.
+pub const DEFAULT_ARCHIVAL_QUEUE_CAPACITY: usize = 16;
+
+/// Default time `enqueue` blocks on a full queue before giving up.
+pub const DEFAULT_ENQUEUE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// An account to evaluate for archival.
+#[derive(Debug, Clone)]
+pub struct ArchivalCandidate {
+    pub pubkey: Pubkey,
+    pub account: AccountSharedData,
+    /// The slot when the account was last written to.
+    pub last_active_slot: u64,
+    /// The slot when the account was created.
+    pub creation_slot: u64,
+}
+
+/// A batch of candidates enqueued at an epoch boundary.
+#[derive(Debug, Clone)]
+pub struct ArchivalTask {
+    /// Slot at which the candidates are evaluated.
+    pub current_slot: u64,
+    /// Epoch recorded on the resulting archives.
+    pub current_epoch: u64,
+    pub candidates: Vec<ArchivalCandidate>,
+}
+
+/// Counters updated by the worker and its producers.
+#[derive(Debug, Default)]
+pub struct ArchivalWorkerStats {
+    pub tasks_processed: AtomicU64,
+    pub accounts_evaluated: AtomicU64,
+    pub accounts_archived: AtomicU64,
+    pub archive_failures: AtomicU64,
+    /// Enqueue attempts that timed out on a full queue.
+    pub archival_queue_full: AtomicU64,
+}
+
+/// Background archival thread fed through a bounded channel.
+///
+/// Dropping the worker closes the channel; the thread finishes the queued
+/// tasks and exits. Use [`ArchivalWorker::join`] to wait for that.
+pub struct ArchivalWorker {
+    config: StateRentConfig,
+    archive_index: Arc<Mutex<ArchiveIndex>>,
+    sender: Sender<ArchivalTask>,
+    enqueue_timeout: Duration,
+    stats: Arc<ArchivalWorkerStats>,
+    thread: JoinHandle<()>,
+}
+
+impl ArchivalWorker {
+    /// Spawn the worker thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Rent expiry configuration used to evaluate and archive
+    /// * `archive_index` - Index that archived accounts are registered in
+    /// * `queue_capacity` - Maximum number of pending tasks
+    /// * `enqueue_timeout` - How long `enqueue` waits on a full queue
+    pub fn start(
+        config: StateRentConfig,
+        archive_index: Arc<Mutex<ArchiveIndex>>,
+        queue_capacity: usize,
+        enqueue_timeout: Duration,
+    ) -> Self {
+        let (sender, receiver) = bounded(queue_capacity);
+        let stats = Arc::new(ArchivalWorkerStats::default());
+
+        let thread = {
+            let config = config.clone();
+            let archive_index = archive_index.clone();
+            let stats = stats.clone();
+            thread::Builder::new()
+                .name("trv1Archival".to_string())
+                .spawn(move || Self::run_loop(config, archive_index, stats, receiver))
+                .expect("Failed to spawn TRv1 archival thread")
+        };
+
+        Self {
+            config,
+            archive_index,
+            sender,
+            enqueue_timeout,
+            stats,
+            thread,
+        }
+    }
+
+    /// Queue a batch of candidates, typically at an epoch boundary.
+    ///
+    /// Blocks while the queue is full, for up to the enqueue timeout. On
+    /// timeout `archival_queue_full` is incremented and the task is returned
+    /// inside the error so the caller can retry or drop it.
+    pub fn enqueue(&self, task: ArchivalTask) -> Result<(), SendTimeoutError<ArchivalTask>> {
+        let result = self.sender.send_timeout(task, self.enqueue_timeout);
+        if let Err(SendTimeoutError::Timeout(_)) = &result {
+            self.stats
+                .archival_queue_full
+                .fetch_add(1, Ordering::Relaxed);
+            warn!(
+                "TRv1 archival queue still full after {:?}; returning task to caller",
+                self.enqueue_timeout
+            );
+        }
+        result
+    }
+
+    /// Number of tasks waiting to be processed.
+    pub fn queue_len(&self) -> usize {
+        self.sender.len()
+    }
+
+    /// Rent expiry configuration used by the worker.
+    pub fn config(&self) -> &StateRentConfig {
+        &self.config
+    }
+
+    /// Index that archived accounts are registered in.
+    pub fn archive_index(&self) -> &Arc<Mutex<ArchiveIndex>> {
+        &self.archive_index
+    }
+
+    /// Worker counters.
+    pub fn stats(&self) -> &ArchivalWorkerStats {
+        &self.stats
+    }
+
+    /// Close the queue, let the thread finish the pending tasks, and wait
+    /// for it to exit.
+    pub fn join(self) -> thread::Result<()> {
+        let Self { sender, thread, .. } = self;
+        drop(sender);
+        thread.join()
+    }
+
+    fn run_loop(
+        config: StateRentConfig,
+        archive_index: Arc<Mutex<ArchiveIndex>>,
+        stats: Arc<ArchivalWorkerStats>,
+        receiver: Receiver<ArchivalTask>,
+    ) {
+        info!("TRv1 Archival Worker thread started");
+        for task in receiver {
+            Self::process_task(&config, &archive_index, &stats, task);
+        }
+        info!("TRv1 Archival Worker thread exiting");
+    }
+
+    fn process_task(
+        config: &StateRentConfig,
+        archive_index: &Mutex<ArchiveIndex>,
+        stats: &ArchivalWorkerStats,
+        mut task: ArchivalTask,
+    ) {
+        task.candidates
+            .sort_by_key(|candidate| std::cmp::Reverse(candidate.account.data().len()));
+
+        let mut archived_count = 0u64;
+        for candidate in &task.candidates {
+            stats.accounts_evaluated.fetch_add(1, Ordering::Relaxed);
+            if !check_rent_expiry(
+                &candidate.pubkey,
+                &candidate.account,
+                candidate.last_active_slot,
+                candidate.creation_slot,
+                task.current_slot,
+                config,
+            ) {
+                continue;
+            }
+
+            match archive_account(
+                &candidate.pubkey,
+                &candidate.account,
+                task.current_slot,
+                task.current_epoch,
+                config,
+            ) {
+                Ok(archived) => {
+                    archive_index.lock().unwrap().insert(archived);
+                    archived_count += 1;
+                }
+                Err(e) => {
+                    stats.archive_failures.fetch_add(1, Ordering::Relaxed);
+                    warn!(
+                        "TRv1: Failed to archive account {} to cold storage: {}",
+                        candidate.pubkey, e
+                    );
+                }
+            }
+        }
+
+        stats
+            .accounts_archived
+            .fetch_add(archived_count, Ordering::Relaxed);
+        stats.tasks_processed.fetch_add(1, Ordering::Relaxed);
+        debug!(
+            "TRv1 archival: epoch {} archived {} of {} candidates",
+            task.current_epoch,
+            archived_count,
+            task.candidates.len()
+        );
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::state_rent_expiry::ESTIMATED_SLOTS_PER_DAY, std::time::Instant};
+
+    fn candidate(data_len: usize) -> ArchivalCandidate {
+        ArchivalCandidate {
+            pubkey: Pubkey::new_unique(),
+            account: AccountSharedData::new(1_000, data_len, &Pubkey::new_unique()),
+            last_active_slot: 0,
+            creation_slot: 0,
+        }
+    }
+
+    fn task(candidates: Vec<ArchivalCandidate>) -> ArchivalTask {
+        ArchivalTask {
+            current_slot: 10 * ESTIMATED_SLOTS_PER_DAY,
+            current_epoch: 10,
+            candidates,
+        }
+    }
+
+    fn test_config(cold_storage: &tempfile::TempDir) -> StateRentConfig {
+        StateRentConfig {
+            cold_storage_path: cold_storage.path().to_path_buf(),
+            ..StateRentConfig::for_testing()
+        }
+    }
+
+    #[test]
+    fn test_worker_archives_only_eligible_accounts() {
+        let cold_storage = tempfile::tempdir().unwrap();
+        let index = Arc::new(Mutex::new(ArchiveIndex::new()));
+        let worker = ArchivalWorker::start(
+            test_config(&cold_storage),
+            index.clone(),
+            DEFAULT_ARCHIVAL_QUEUE_CAPACITY,
+            DEFAULT_ENQUEUE_TIMEOUT,
+        );
+
+        let large = candidate(1_024);
+        let small = candidate(16); // below MIN_ARCHIVAL_DATA_SIZE
+        let recent = ArchivalCandidate {
+            last_active_slot: 10 * ESTIMATED_SLOTS_PER_DAY,
+            ..candidate(1_024)
+        };
+        let pubkeys = [large.pubkey, small.pubkey, recent.pubkey];
+        worker.enqueue(task(vec![small, recent, large])).unwrap();
+
+        let stats = worker.stats.clone();
+        worker.join().unwrap();
+
+        assert_eq!(stats.tasks_processed.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.accounts_evaluated.load(Ordering::Relaxed), 3);
+        assert_eq!(stats.accounts_archived.load(Ordering::Relaxed), 1);
+        let index = index.lock().unwrap();
+        assert!(index.is_archived(&pubkeys[0]));
+        assert!(!index.is_archived(&pubkeys[1]));
+        assert!(!index.is_archived(&pubkeys[2]));
+    }
+
+    #[test]
+    fn test_enqueue_times_out_when_queue_full() {
+        let cold_storage = tempfile::tempdir().unwrap();
+        let index = Arc::new(Mutex::new(ArchiveIndex::new()));
+        let worker = ArchivalWorker::start(
+            test_config(&cold_storage),
+            index.clone(),
+            1,
+            Duration::from_millis(50),
+        );
+
+        // Stall the worker on the index lock while it processes the first
+        // task, so the second fills the queue and the third times out.
+        let guard = index.lock().unwrap();
+        worker.enqueue(task(vec![candidate(1_024)])).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while worker.queue_len() > 0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(1));
+        }
+        worker.enqueue(task(vec![candidate(1_024)])).unwrap();
+        assert!(matches!(
+            worker.enqueue(task(vec![candidate(1_024)])),
+            Err(SendTimeoutError::Timeout(_))
+        ));
+        assert_eq!(
+            worker.stats().archival_queue_full.load(Ordering::Relaxed),
+            1
+        );
+
+        drop(guard);
+        worker.join().unwrap();
+        assert_eq!(index.lock().unwrap().len(), 2);
+    }
+}
@@ -39,7 +39,10 @@ pub mod storable_accounts;
 pub mod tiered_storage;
 pub mod tiered_storage_config;
 pub mod account_cache;
+pub mod archival_worker;
 pub mod state_rent_expiry;
+#[cfg(feature = "rocksdb")]
+pub mod rocksdb_archive_index;
 #[cfg(feature = "trv1-tiered-storage")]
 pub mod trv1_storage_adapter;
 #[cfg(feature = "trv1-tiered-storage")]
@@ -0,0 +1,202 @@
+//! Persistent archive index backed by RocksDB.
+//!
+//! [`ArchiveIndex`](crate::state_rent_expiry::ArchiveIndex) lives in memory,
+//! so after a validator restart it no longer knows which accounts were
+//! archived and they cannot be revived. [`RocksDbArchiveIndex`] offers the
+//! same lookups on top of a RocksDB column family that survives restarts.
+//!
+//! Entries are keyed by the raw pubkey bytes and stored as
+//! `bincode`-serialized [`ArchivedAccount`]s. Revivals delete entries, so the
+//! column family is compacted every [`COMPACTION_INTERVAL`] inserts to
+//! reclaim the space held by tombstones.
+
+use {
+    crate::state_rent_expiry::{ArchiveError, ArchivedAccount},
+    rocksdb::{ColumnFamily, IteratorMode, Options, DB},
+    solana_pubkey::Pubkey,
+    std::path::Path,
+};
+
+/// Column family holding the archived account entries.
+const ARCHIVED_ACCOUNTS_CF: &str = "archived_accounts";
+
+/// Number of inserts between manual compactions of the column family.
+pub const COMPACTION_INTERVAL: u64 = 10_000;
+
+/// On-disk index of archived accounts.
+pub struct RocksDbArchiveIndex {
+    db: DB,
+
+    /// Number of entries, counted on open and maintained on insert/remove.
+    len: usize,
+
+    /// Inserts since the column family was last compacted.
+    inserts_since_compaction: u64,
+}
+
+impl RocksDbArchiveIndex {
+    /// Open the index stored at `path`, creating it if it doesn't exist.
+    pub fn open(path: &Path) -> Result<Self, ArchiveError> {
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+        let db = DB::open_cf(&options, path, [ARCHIVED_ACCOUNTS_CF]).map_err(db_error)?;
+
+        let mut index = Self {
+            db,
+            len: 0,
+            inserts_since_compaction: 0,
+        };
+        for entry in index.db.iterator_cf(index.cf(), IteratorMode::Start) {
+            entry.map_err(db_error)?;
+            index.len += 1;
+        }
+        Ok(index)
+    }
+
+    /// Flush outstanding writes and close the database.
+    pub fn close(self) -> Result<(), ArchiveError> {
+        self.db.flush_cf(self.cf()).map_err(db_error)
+    }
+
+    /// Register an archived account, replacing any existing entry for the
+    /// same pubkey.
+    pub fn insert(&mut self, archived: &ArchivedAccount) -> Result<(), ArchiveError> {
+        let value = bincode::serialize(archived)
+            .map_err(|err| ArchiveError::Serialization(err.to_string()))?;
+        let key = archived.pubkey.as_ref();
+        let existed = self
+            .db
+            .get_pinned_cf(self.cf(), key)
+            .map_err(db_error)?
+            .is_some();
+        self.db.put_cf(self.cf(), key, value).map_err(db_error)?;
+        if !existed {
+            self.len += 1;
+        }
+
+        self.inserts_since_compaction += 1;
+        if self.inserts_since_compaction >= COMPACTION_INTERVAL {
+            self.db.compact_range_cf(self.cf(), None::<&[u8]>, None::<&[u8]>);
+            self.inserts_since_compaction = 0;
+        }
+        Ok(())
+    }
+
+    /// Look up an archived account by pubkey.
+    pub fn get(&self, pubkey: &Pubkey) -> Result<Option<ArchivedAccount>, ArchiveError> {
+        self.db
+            .get_pinned_cf(self.cf(), pubkey.as_ref())
+            .map_err(db_error)?
+            .map(|value| {
+                bincode::deserialize(&value)
+                    .map_err(|err| ArchiveError::Serialization(err.to_string()))
+            })
+            .transpose()
+    }
+
+    /// Remove an account from the index (on revival).
+    pub fn remove(&mut self, pubkey: &Pubkey) -> Result<Option<ArchivedAccount>, ArchiveError> {
+        let archived = self.get(pubkey)?;
+        if archived.is_some() {
+            self.db
+                .delete_cf(self.cf(), pubkey.as_ref())
+                .map_err(db_error)?;
+            self.len -= 1;
+        }
+        Ok(archived)
+    }
+
+    /// Check if a pubkey is archived.
+    pub fn is_archived(&self, pubkey: &Pubkey) -> Result<bool, ArchiveError> {
+        Ok(self
+            .db
+            .get_pinned_cf(self.cf(), pubkey.as_ref())
+            .map_err(db_error)?
+            .is_some())
+    }
+
+    /// Return the number of currently-archived accounts.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return whether the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn cf(&self) -> &ColumnFamily {
+        self.db
+            .cf_handle(ARCHIVED_ACCOUNTS_CF)
+            .expect("column family is created on open")
+    }
+}
+
+fn db_error(err: rocksdb::Error) -> ArchiveError {
+    ArchiveError::Database(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_hash::Hash, std::path::PathBuf};
+
+    fn make_archived(pubkey: Pubkey, i: u64) -> ArchivedAccount {
+        ArchivedAccount {
+            pubkey,
+            archive_slot: i.saturating_mul(10),
+            archive_epoch: i / 100,
+            account_hash: Hash::new_unique(),
+            lamports_at_archive: i.saturating_add(1),
+            data_len: 256,
+            owner: Pubkey::new_unique(),
+            executable: false,
+            archive_timestamp: 1_700_000_000,
+            storage_path: PathBuf::from(format!("/tmp/cold/{pubkey}.bin")),
+            merkle_proof: None,
+        }
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut index = RocksDbArchiveIndex::open(dir.path()).unwrap();
+        assert!(index.is_empty());
+
+        let pubkey = Pubkey::new_unique();
+        let archived = make_archived(pubkey, 1);
+        index.insert(&archived).unwrap();
+        index.insert(&archived).unwrap();
+        assert_eq!(index.len(), 1);
+        assert!(index.is_archived(&pubkey).unwrap());
+        assert_eq!(index.get(&pubkey).unwrap(), Some(archived.clone()));
+
+        assert_eq!(index.remove(&pubkey).unwrap(), Some(archived));
+        assert_eq!(index.remove(&pubkey).unwrap(), None);
+        assert!(!index.is_archived(&pubkey).unwrap());
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn test_entries_survive_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let entries: Vec<ArchivedAccount> = (0..1_000)
+            .map(|i| make_archived(Pubkey::new_unique(), i))
+            .collect();
+
+        let mut index = RocksDbArchiveIndex::open(dir.path()).unwrap();
+        for archived in &entries {
+            index.insert(archived).unwrap();
+        }
+        index.close().unwrap();
+
+        let index = RocksDbArchiveIndex::open(dir.path()).unwrap();
+        assert_eq!(index.len(), 1_000);
+        for archived in &entries {
+            assert_eq!(
+                index.get(&archived.pubkey).unwrap().as_ref(),
+                Some(archived)
+            );
+        }
+    }
+}
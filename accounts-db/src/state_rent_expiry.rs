@@ -39,6 +39,11 @@
 //! enables trustless revival without requiring the validator to scan all
 //! cold storage.
 //!
+//! [`ArchiveIndex`] appends every archived account's hash to an
+//! [`IncrementalMerkleTree`]. At each epoch boundary the tree's root is
+//! published to a PDA of the state rent program ([`archive_root_address`]),
+//! and revival verifies proofs against that on-chain root.
+//!
 //! # Integration
 //!
 //! This module is designed to be called by the runtime during epoch
@@ -46,16 +51,22 @@
 //! Bank and AccountsDb will happen in a subsequent phase.
 
 use {
+    log::*,
+    serde::{Deserialize, Serialize},
     solana_account::{AccountSharedData, ReadableAccount, WritableAccount},
     solana_hash::Hash,
     solana_pubkey::Pubkey,
     solana_sha256_hasher::hash as sha256_hash,
     std::{
-        collections::HashMap,
-        io,
+        collections::{HashMap, HashSet},
+        fmt,
+        fs::File,
+        io::{self, BufWriter, Write},
         path::{Path, PathBuf},
+        sync::Arc,
         time::{Duration, SystemTime, UNIX_EPOCH},
     },
+    trv1_monitoring::TRv1Metrics,
 };
 
 // ── Constants ───────────────────────────────────────────────────────────────
@@ -71,10 +82,25 @@ pub const DEFAULT_ARCHIVE_AFTER_DAYS: u64 = 365;
 /// Estimated slots per day at 400ms slot time.
 pub const ESTIMATED_SLOTS_PER_DAY: u64 = 216_000;
 
+/// Default minimum account age before archival (1 year of slots).
+pub const DEFAULT_MIN_ACCOUNT_AGE_SLOTS: u64 = 365 * ESTIMATED_SLOTS_PER_DAY;
+
 /// Minimum account data size to consider for archival.
 /// Accounts smaller than this are too cheap to bother archiving.
 pub const MIN_ARCHIVAL_DATA_SIZE: usize = 128;
 
+/// Governance program; its config and proposals must stay live.
+pub const GOVERNANCE_PROGRAM_ID: Pubkey =
+    solana_pubkey::pubkey!("Governance1111111111111111111111111111111111");
+
+/// Treasury program.
+pub const TREASURY_PROGRAM_ID: Pubkey =
+    solana_pubkey::pubkey!("Treasury11111111111111111111111111111111111");
+
+/// Passive stake program; locked stake can sit untouched for years.
+pub const PASSIVE_STAKE_PROGRAM_ID: Pubkey =
+    solana_pubkey::pubkey!("Pass1veStake1111111111111111111111111111111");
+
 /// Minimum rent deposit required to revive an account (in lamports).
 /// This covers at least 2 years of rent to prevent immediate re-archival.
 pub const MIN_REVIVAL_RENT_YEARS: u64 = 2;
@@ -102,6 +128,12 @@ pub struct StateRentConfig {
     /// Default: 365 (1 year)
     pub archive_after_days: u64,
 
+    /// Minimum age, in slots since creation, before an account can be
+    /// archived at all, however long it has been inactive.
+    ///
+    /// Default: `DEFAULT_MIN_ACCOUNT_AGE_SLOTS` (1 year)
+    pub min_account_age_slots: u64,
+
     /// Whether archived accounts can be revived.
     ///
     /// If `false`, archival is permanent and the account data is only
@@ -116,6 +148,14 @@ pub struct StateRentConfig {
     /// If true, accounts owned by system programs (e.g., stake, vote)
     /// are exempt from archival regardless of inactivity.
     pub exempt_system_programs: bool,
+
+    /// Owners and individual accounts that are never archived.
+    ///
+    /// Default: the TRv1 governance, treasury and passive stake programs
+    pub exemptions: ExemptionRegistry,
+
+    /// Notified after each successful archival and revival, in order.
+    pub observers: Vec<Arc<dyn ArchivalObserver + Send + Sync>>,
 }
 
 impl Default for StateRentConfig {
@@ -123,9 +163,12 @@ impl Default for StateRentConfig {
         Self {
             lamports_per_byte_year: DEFAULT_LAMPORTS_PER_BYTE_YEAR,
             archive_after_days: DEFAULT_ARCHIVE_AFTER_DAYS,
+            min_account_age_slots: DEFAULT_MIN_ACCOUNT_AGE_SLOTS,
             allow_revival: true,
             cold_storage_path: PathBuf::from("trv1-cold-storage"),
             exempt_system_programs: true,
+            exemptions: ExemptionRegistry::trv1_programs(),
+            observers: Vec::new(),
         }
     }
 }
@@ -136,9 +179,12 @@ impl StateRentConfig {
         Self {
             lamports_per_byte_year: 1_000,
             archive_after_days: 1,
+            min_account_age_slots: ESTIMATED_SLOTS_PER_DAY,
             allow_revival: true,
             cold_storage_path: PathBuf::from("/tmp/trv1-test-cold"),
             exempt_system_programs: false,
+            exemptions: ExemptionRegistry::trv1_programs(),
+            observers: Vec::new(),
         }
     }
 
@@ -162,6 +208,122 @@ impl StateRentConfig {
     }
 }
 
+// ── Exemption Registry ──────────────────────────────────────────────────────
+
+/// Accounts that must never be archived, regardless of inactivity.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExemptionRegistry {
+    /// Every account owned by one of these programs is exempt.
+    pub exempt_owners: HashSet<Pubkey>,
+
+    /// Individually exempt accounts.
+    pub exempt_accounts: HashSet<Pubkey>,
+}
+
+impl ExemptionRegistry {
+    /// Registry exempting the accounts of the TRv1 governance, treasury and
+    /// passive stake programs.
+    pub fn trv1_programs() -> Self {
+        let mut registry = Self::default();
+        registry.add_owner(GOVERNANCE_PROGRAM_ID);
+        registry.add_owner(TREASURY_PROGRAM_ID);
+        registry.add_owner(PASSIVE_STAKE_PROGRAM_ID);
+        registry
+    }
+
+    /// Exempt every account owned by `owner`.
+    pub fn add_owner(&mut self, owner: Pubkey) {
+        self.exempt_owners.insert(owner);
+    }
+
+    /// Exempt the account at `pubkey`.
+    pub fn add_account(&mut self, pubkey: Pubkey) {
+        self.exempt_accounts.insert(pubkey);
+    }
+
+    /// Whether the account at `pubkey`, owned by `owner`, is exempt.
+    pub fn is_exempt(&self, pubkey: &Pubkey, owner: &Pubkey) -> bool {
+        self.exempt_owners.contains(owner) || self.exempt_accounts.contains(pubkey)
+    }
+}
+
+// ── Archival Observers ──────────────────────────────────────────────────────
+
+/// Notification that an account was archived to cold storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivalEvent {
+    /// The archived account.
+    pub pubkey: Pubkey,
+
+    /// The slot at which the account was archived.
+    pub archive_slot: u64,
+
+    /// Length of the account's data field at archival.
+    pub data_len: usize,
+}
+
+/// Hook for systems that must react to archival, e.g. the accounts DB
+/// zeroing the lamports of an archived account.
+///
+/// Observers registered in [`StateRentConfig::observers`] are called by
+/// [`archive_account`] and [`revive_account`] once their work has
+/// succeeded; they are not called for failed attempts.
+pub trait ArchivalObserver: fmt::Debug {
+    /// Called after an account has been written to cold storage.
+    fn on_archived(&self, event: &ArchivalEvent);
+
+    /// Called after an archived account has been restored.
+    fn on_revived(&self, pubkey: &Pubkey);
+}
+
+/// Observer that logs each archival and revival.
+#[derive(Debug, Default)]
+pub struct LoggingObserver;
+
+impl ArchivalObserver for LoggingObserver {
+    fn on_archived(&self, event: &ArchivalEvent) {
+        info!(
+            "TRv1: Archived account {} ({} bytes) at slot {}",
+            event.pubkey, event.data_len, event.archive_slot
+        );
+    }
+
+    fn on_revived(&self, pubkey: &Pubkey) {
+        info!("TRv1: Revived account {}", pubkey);
+    }
+}
+
+/// Observer that counts archivals and revivals in [`TRv1Metrics`].
+pub struct MetricsObserver {
+    metrics: Arc<TRv1Metrics>,
+}
+
+impl MetricsObserver {
+    /// Create an observer that updates `metrics`.
+    pub fn new(metrics: Arc<TRv1Metrics>) -> Self {
+        Self { metrics }
+    }
+}
+
+impl fmt::Debug for MetricsObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetricsObserver")
+            .field("total_archived", &self.metrics.total_archived.get())
+            .field("total_revived", &self.metrics.total_revived.get())
+            .finish()
+    }
+}
+
+impl ArchivalObserver for MetricsObserver {
+    fn on_archived(&self, _event: &ArchivalEvent) {
+        self.metrics.total_archived.inc();
+    }
+
+    fn on_revived(&self, _pubkey: &Pubkey) {
+        self.metrics.total_revived.inc();
+    }
+}
+
 // ── Archived Account ────────────────────────────────────────────────────────
 
 /// Metadata for an account that has been archived to cold storage.
@@ -169,7 +331,7 @@ impl StateRentConfig {
 /// This struct contains everything needed to verify and potentially
 /// revive an archived account. The actual account data is stored
 /// separately in cold storage files.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ArchivedAccount {
     /// The public key of the archived account.
     pub pubkey: Pubkey,
@@ -214,7 +376,7 @@ pub struct ArchivedAccount {
 ///
 /// This is a simplified proof structure. The full implementation
 /// will integrate with Solana's existing accounts hash infrastructure.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MerkleProof {
     /// The leaf hash (hash of the account data).
     pub leaf_hash: Hash,
@@ -238,10 +400,11 @@ impl MerkleProof {
     /// Returns `true` if the proof is valid, meaning the account data
     /// hashes to `leaf_hash` and the proof path leads to `root_hash`.
     pub fn verify(&self, account_data_hash: &Hash) -> bool {
-        if *account_data_hash != self.leaf_hash {
-            return false;
-        }
+        verify_proof(*account_data_hash, self, self.root_hash)
+    }
 
+    /// Fold the sibling hashes into the root this proof leads to.
+    fn compute_root(&self) -> Hash {
         let mut current_hash = self.leaf_hash;
         let mut index = self.leaf_index;
 
@@ -256,32 +419,250 @@ impl MerkleProof {
             index /= 2;
         }
 
-        current_hash == self.root_hash
+        current_hash
     }
 }
 
+/// Verify that `proof` shows `leaf` is included in the tree with `root`.
+///
+/// Unlike [`MerkleProof::verify`], the root is supplied by the caller (e.g.
+/// read from the published [`ArchiveRoot`] account) instead of trusting the
+/// `root_hash` carried in the proof.
+pub fn verify_proof(leaf: Hash, proof: &MerkleProof, root: Hash) -> bool {
+    leaf == proof.leaf_hash && proof.compute_root() == root
+}
+
 /// Combine two hashes to produce a parent hash in the Merkle tree.
-fn combine_hashes(left: &Hash, right: &Hash) -> Hash {
+pub fn combine_hashes(left: &Hash, right: &Hash) -> Hash {
     let mut combined = Vec::with_capacity(64);
     combined.extend_from_slice(left.as_ref());
     combined.extend_from_slice(right.as_ref());
     sha256_hash(&combined)
 }
 
+// ── Incremental Merkle Tree ─────────────────────────────────────────────────
+
+/// Append-only Merkle tree over the hashes of archived accounts.
+///
+/// The tree is padded to the next power of two with the roots of empty
+/// subtrees, so appending a leaf only recomputes the `depth` nodes on its
+/// path. The tree grows one level whenever it fills up.
+#[derive(Debug, Clone)]
+pub struct IncrementalMerkleTree {
+    /// Leaf hashes, in append order.
+    leaves: Vec<Hash>,
+
+    /// Number of levels above the leaves.
+    depth: u32,
+
+    /// Interior nodes; `nodes[h]` holds level `h + 1`. Only nodes with at
+    /// least one leaf beneath them are stored.
+    nodes: Vec<Vec<Hash>>,
+
+    /// Root of an empty subtree of each height.
+    empty_roots: Vec<Hash>,
+}
+
+impl Default for IncrementalMerkleTree {
+    fn default() -> Self {
+        Self {
+            leaves: Vec::new(),
+            depth: 0,
+            nodes: Vec::new(),
+            empty_roots: vec![Hash::default()],
+        }
+    }
+}
+
+impl IncrementalMerkleTree {
+    /// Create an empty tree.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a leaf and return its proof against the new root.
+    ///
+    /// The returned proof's `proof_slot` is left at 0 for the caller to
+    /// fill in. Appending further leaves changes the root, so earlier proofs
+    /// must be refreshed with [`IncrementalMerkleTree::proof`].
+    pub fn append(&mut self, leaf: Hash) -> MerkleProof {
+        let index = self.leaves.len();
+        self.leaves.push(leaf);
+
+        if self.leaves.len() > 1 << self.depth {
+            let top = self.empty_roots[self.depth as usize];
+            self.empty_roots.push(combine_hashes(&top, &top));
+            self.nodes.push(Vec::new());
+            self.depth += 1;
+        }
+
+        let mut position = index;
+        for level in 0..self.depth as usize {
+            let parent = position / 2;
+            let hash = combine_hashes(
+                &self.node(level, parent * 2),
+                &self.node(level, parent * 2 + 1),
+            );
+            let parents = &mut self.nodes[level];
+            if parent < parents.len() {
+                parents[parent] = hash;
+            } else {
+                parents.push(hash);
+            }
+            position = parent;
+        }
+
+        self.proof(index).expect("leaf was just appended")
+    }
+
+    /// Proof for the leaf at `index` against the current root.
+    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+        let leaf_hash = *self.leaves.get(index)?;
+        let proof_hashes = (0..self.depth as usize)
+            .map(|level| self.node(level, (index >> level) ^ 1))
+            .collect();
+        Some(MerkleProof {
+            leaf_hash,
+            proof_hashes,
+            root_hash: self.root(),
+            proof_slot: 0,
+            leaf_index: index as u64,
+        })
+    }
+
+    /// Current root. An empty tree has the default hash as its root.
+    pub fn root(&self) -> Hash {
+        self.node(self.depth as usize, 0)
+    }
+
+    /// Number of levels above the leaves.
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Number of leaves appended.
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Whether no leaves have been appended.
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Node at `position` within `level` (0 being the leaves), falling back
+    /// to the empty subtree root for positions past the last leaf.
+    fn node(&self, level: usize, position: usize) -> Hash {
+        let stored = if level == 0 {
+            &self.leaves
+        } else {
+            &self.nodes[level - 1]
+        };
+        stored
+            .get(position)
+            .copied()
+            .unwrap_or(self.empty_roots[level])
+    }
+}
+
+// ── Published Archive Roots ─────────────────────────────────────────────────
+
+/// Program that owns the accounts the archive roots are published to.
+pub const STATE_RENT_PROGRAM_ID: Pubkey =
+    solana_pubkey::pubkey!("StateRent1111111111111111111111111111111111");
+
+/// PDA seed prefix for published archive roots.
+pub const ARCHIVE_ROOT_SEED: &[u8] = b"archive_root";
+
+/// Address of the account holding the archive root published for `epoch`.
+pub fn archive_root_address(epoch: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ARCHIVE_ROOT_SEED, &epoch.to_le_bytes()],
+        &STATE_RENT_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Root of the accounts archived during an epoch, as published on-chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveRoot {
+    /// Epoch whose archivals the root covers.
+    pub epoch: u64,
+
+    /// Slot at which the root was published.
+    pub slot: u64,
+
+    /// Root of the epoch's archive tree.
+    pub root: Hash,
+
+    /// Number of accounts archived during the epoch.
+    pub num_leaves: u64,
+}
+
+impl ArchiveRoot {
+    /// Build the account to store at [`archive_root_address`].
+    pub fn to_account(&self, lamports: u64) -> AccountSharedData {
+        let data = bincode::serialize(self).expect("ArchiveRoot serializes");
+        let mut account = AccountSharedData::new(lamports, data.len(), &STATE_RENT_PROGRAM_ID);
+        account.set_data_from_slice(&data);
+        account
+    }
+
+    /// Read a published root back from its account.
+    ///
+    /// Returns `None` if the account is not owned by the state rent program
+    /// or does not hold an `ArchiveRoot`.
+    pub fn from_account(account: &AccountSharedData) -> Option<Self> {
+        if *account.owner() != STATE_RENT_PROGRAM_ID {
+            return None;
+        }
+        bincode::deserialize(account.data()).ok()
+    }
+}
+
 // ── Archive Index ───────────────────────────────────────────────────────────
 
+/// Aggregate statistics over the currently-archived accounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ArchiveStatistics {
+    /// Number of accounts currently in cold storage.
+    pub total_accounts: u64,
+
+    /// Total data bytes of the accounts currently in cold storage.
+    pub total_bytes: u64,
+
+    /// Epoch of the earliest archival, if any accounts are archived.
+    pub oldest_archive_epoch: Option<u64>,
+
+    /// Epoch of the most recent archival, if any accounts are archived.
+    pub newest_archive_epoch: Option<u64>,
+
+    /// Mean data size of an archived account (integer division).
+    pub avg_account_size_bytes: u64,
+}
+
 /// In-memory index of all archived accounts.
 ///
 /// This provides O(1) lookup to check if an account has been archived
 /// and to retrieve its metadata for revival.
 ///
-/// In production, this would be backed by a persistent key-value store
-/// (e.g., RocksDB) rather than a HashMap.
+/// Accounts inserted since the last [`ArchiveIndex::publish_epoch_root`]
+/// are appended to the epoch's [`IncrementalMerkleTree`] and receive a
+/// Merkle proof.
+///
+/// The index is lost on restart; with the `rocksdb` feature,
+/// `RocksDbArchiveIndex` provides a persistent equivalent.
 #[derive(Debug, Default)]
 pub struct ArchiveIndex {
     /// Map from pubkey to archived account metadata.
     entries: HashMap<Pubkey, ArchivedAccount>,
 
+    /// Tree over the accounts archived since the last published root.
+    epoch_tree: IncrementalMerkleTree,
+
+    /// Pubkeys of the leaves of `epoch_tree`, in leaf order.
+    epoch_leaves: Vec<Pubkey>,
+
     /// Total number of accounts archived.
     pub total_archived: u64,
 
@@ -299,14 +680,54 @@ impl ArchiveIndex {
     }
 
     /// Register an archived account in the index.
-    pub fn insert(&mut self, archived: ArchivedAccount) {
+    ///
+    /// The account hash is appended to the epoch's Merkle tree and the
+    /// resulting proof is attached to the entry.
+    pub fn insert(&mut self, mut archived: ArchivedAccount) {
         self.total_archived = self.total_archived.saturating_add(1);
         self.total_archived_bytes = self
             .total_archived_bytes
             .saturating_add(archived.data_len as u64);
+
+        let mut proof = self.epoch_tree.append(archived.account_hash);
+        proof.proof_slot = archived.archive_slot;
+        archived.merkle_proof = Some(proof);
+        self.epoch_leaves.push(archived.pubkey);
+
         self.entries.insert(archived.pubkey, archived);
     }
 
+    /// Root of the accounts archived since the last published root.
+    pub fn epoch_root(&self) -> Hash {
+        self.epoch_tree.root()
+    }
+
+    /// Close the current epoch's tree and return the root to publish at
+    /// [`archive_root_address`]`(epoch)`.
+    ///
+    /// The proofs of the epoch's entries still in the index are refreshed
+    /// to the final root, so they verify against the published account.
+    /// Accounts inserted afterwards go into a new tree.
+    pub fn publish_epoch_root(&mut self, epoch: u64, slot: u64) -> ArchiveRoot {
+        let tree = std::mem::take(&mut self.epoch_tree);
+        let leaves = std::mem::take(&mut self.epoch_leaves);
+
+        for (index, pubkey) in leaves.iter().enumerate() {
+            if let Some(archived) = self.entries.get_mut(pubkey) {
+                let mut proof = tree.proof(index).expect("leaf index is in range");
+                proof.proof_slot = slot;
+                archived.merkle_proof = Some(proof);
+            }
+        }
+
+        ArchiveRoot {
+            epoch,
+            slot,
+            root: tree.root(),
+            num_leaves: tree.len() as u64,
+        }
+    }
+
     /// Look up an archived account by pubkey.
     pub fn get(&self, pubkey: &Pubkey) -> Option<&ArchivedAccount> {
         self.entries.get(pubkey)
@@ -336,6 +757,55 @@ impl ArchiveIndex {
     pub fn is_empty(&self) -> bool {
         self.entries.is_empty()
     }
+
+    /// Compute aggregate statistics over the currently-archived accounts.
+    ///
+    /// Unlike the lifetime counters on the index, revived accounts are not
+    /// included.
+    pub fn statistics(&self) -> ArchiveStatistics {
+        let total_accounts = self.entries.len() as u64;
+        let total_bytes = self
+            .entries
+            .values()
+            .fold(0u64, |acc, a| acc.saturating_add(a.data_len as u64));
+        ArchiveStatistics {
+            total_accounts,
+            total_bytes,
+            oldest_archive_epoch: self.entries.values().map(|a| a.archive_epoch).min(),
+            newest_archive_epoch: self.entries.values().map(|a| a.archive_epoch).max(),
+            avg_account_size_bytes: total_bytes.checked_div(total_accounts).unwrap_or(0),
+        }
+    }
+
+    /// Write the index to `path` as CSV for offline analysis.
+    ///
+    /// Rows are sorted by pubkey and preceded by a header line:
+    /// `pubkey,archive_slot,archive_epoch,data_len_bytes,lamports_at_archive,owner,executable,archive_timestamp`.
+    pub fn export_csv(&self, path: &Path) -> io::Result<()> {
+        let mut entries: Vec<&ArchivedAccount> = self.entries.values().collect();
+        entries.sort_by_key(|a| a.pubkey);
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(
+            writer,
+            "pubkey,archive_slot,archive_epoch,data_len_bytes,lamports_at_archive,owner,executable,archive_timestamp"
+        )?;
+        for a in entries {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{}",
+                a.pubkey,
+                a.archive_slot,
+                a.archive_epoch,
+                a.data_len,
+                a.lamports_at_archive,
+                a.owner,
+                a.executable,
+                a.archive_timestamp,
+            )?;
+        }
+        writer.flush()
+    }
 }
 
 // ── Core Functions ──────────────────────────────────────────────────────────
@@ -343,14 +813,19 @@ impl ArchiveIndex {
 /// Check whether an account is eligible for rent expiry / archival.
 ///
 /// An account is eligible if:
-/// 1. It has been inactive for longer than `config.archive_after_days`
+/// 1. It has been inactive for longer than `config.archive_after_days`,
+///    counting from its creation if it was never written to since
 /// 2. Its data length exceeds `MIN_ARCHIVAL_DATA_SIZE`
 /// 3. It is not owned by an exempt system program (if configured)
+/// 4. Neither it nor its owner is in `config.exemptions`
+/// 5. It is at least `config.min_account_age_slots` old
 ///
 /// # Arguments
 ///
+/// * `pubkey` - The account's public key
 /// * `account` - The account to check
 /// * `last_active_slot` - The slot when the account was last written to
+/// * `creation_slot` - The slot when the account was created
 /// * `current_slot` - The current slot
 /// * `config` - Rent expiry configuration
 ///
@@ -358,11 +833,18 @@ impl ArchiveIndex {
 ///
 /// `true` if the account should be archived
 pub fn check_rent_expiry(
+    pubkey: &Pubkey,
     account: &AccountSharedData,
     last_active_slot: u64,
+    creation_slot: u64,
     current_slot: u64,
     config: &StateRentConfig,
 ) -> bool {
+    // Registered exemptions always win
+    if config.exemptions.is_exempt(pubkey, account.owner()) {
+        return false;
+    }
+
     // Skip accounts that are too small to bother archiving
     if account.data().len() < MIN_ARCHIVAL_DATA_SIZE {
         return false;
@@ -378,8 +860,15 @@ pub fn check_rent_expiry(
         return false;
     }
 
-    // Calculate inactivity in slots
-    let inactive_slots = current_slot.saturating_sub(last_active_slot);
+    // Young accounts are never candidates
+    let age_slots = current_slot.saturating_sub(creation_slot);
+    if age_slots < config.min_account_age_slots {
+        return false;
+    }
+
+    // Calculate inactivity in slots, crediting the creation as activity
+    let effective_last_active_slot = last_active_slot.max(creation_slot);
+    let inactive_slots = current_slot.saturating_sub(effective_last_active_slot);
     let inactive_days = inactive_slots / ESTIMATED_SLOTS_PER_DAY;
 
     inactive_days >= config.archive_after_days
@@ -430,6 +919,15 @@ pub fn archive_account(
         .unwrap_or(Duration::ZERO)
         .as_secs();
 
+    let event = ArchivalEvent {
+        pubkey: *pubkey,
+        archive_slot: current_slot,
+        data_len: account.data().len(),
+    };
+    for observer in &config.observers {
+        observer.on_archived(&event);
+    }
+
     Ok(ArchivedAccount {
         pubkey: *pubkey,
         archive_slot: current_slot,
@@ -441,7 +939,7 @@ pub fn archive_account(
         executable: account.executable(),
         archive_timestamp,
         storage_path,
-        merkle_proof: None, // Will be populated by the accounts hash system
+        merkle_proof: None, // Attached by ArchiveIndex::insert
     })
 }
 
@@ -454,14 +952,21 @@ pub fn archive_account(
 ///
 /// * `archived` - Metadata of the archived account
 /// * `rent_deposit` - Lamports deposited for future rent
+/// * `published_root` - Root read from the [`ArchiveRoot`] account for the
+///   archive epoch, if it has been published
 /// * `config` - Rent configuration
 ///
+/// The account's Merkle proof is checked against `published_root`. Revival
+/// fails if the account has no proof or its epoch's root has not been
+/// published yet.
+///
 /// # Returns
 ///
 /// The restored `AccountSharedData`, or an error if verification fails.
 pub fn revive_account(
     archived: &ArchivedAccount,
     rent_deposit: u64,
+    published_root: Option<Hash>,
     config: &StateRentConfig,
 ) -> Result<AccountSharedData, RevivalError> {
     // Check that revival is allowed
@@ -478,6 +983,13 @@ pub fn revive_account(
         });
     }
 
+    // Only the published root is trusted; the proof's own root is not.
+    let proof = archived
+        .merkle_proof
+        .as_ref()
+        .ok_or(RevivalError::MissingMerkleProof)?;
+    let root = published_root.ok_or(RevivalError::ArchiveRootNotPublished)?;
+
     // Load account data from cold storage
     let serialized = std::fs::read(&archived.storage_path).map_err(|e| {
         RevivalError::StorageError(format!(
@@ -499,11 +1011,13 @@ pub fn revive_account(
         });
     }
 
-    // Verify Merkle proof if available
-    if let Some(ref proof) = archived.merkle_proof {
-        if !proof.verify(&computed_hash) {
-            return Err(RevivalError::MerkleProofFailed);
-        }
+    // Verify the Merkle proof against the published root
+    if !verify_proof(computed_hash, proof, root) {
+        return Err(RevivalError::MerkleProofFailed);
+    }
+
+    for observer in &config.observers {
+        observer.on_revived(&archived.pubkey);
     }
 
     Ok(account)
@@ -546,6 +1060,12 @@ pub enum RevivalError {
 
     /// The Merkle proof verification failed.
     MerkleProofFailed,
+
+    /// The archived account carries no Merkle proof.
+    MissingMerkleProof,
+
+    /// No archive root has been published for the account's archive epoch.
+    ArchiveRootNotPublished,
 }
 
 impl std::fmt::Display for RevivalError {
@@ -568,12 +1088,39 @@ impl std::fmt::Display for RevivalError {
                 )
             }
             RevivalError::MerkleProofFailed => write!(f, "Merkle proof verification failed"),
+            RevivalError::MissingMerkleProof => write!(f, "Archived account has no Merkle proof"),
+            RevivalError::ArchiveRootNotPublished => {
+                write!(f, "Archive root for the account's epoch is not published")
+            }
         }
     }
 }
 
 impl std::error::Error for RevivalError {}
 
+/// Errors from a persistent archive index.
+#[derive(Debug)]
+pub enum ArchiveError {
+    /// The backing database failed to open, read or write.
+    Database(String),
+
+    /// An index entry could not be serialized or deserialized.
+    Serialization(String),
+}
+
+impl std::fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArchiveError::Database(msg) => write!(f, "Archive database error: {}", msg),
+            ArchiveError::Serialization(msg) => {
+                write!(f, "Archive entry serialization error: {}", msg)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArchiveError {}
+
 // ── Helper Functions ────────────────────────────────────────────────────────
 
 /// Compute a hash of the account data for integrity verification.
@@ -697,6 +1244,7 @@ mod tests {
         let config = StateRentConfig::default();
         assert_eq!(config.lamports_per_byte_year, DEFAULT_LAMPORTS_PER_BYTE_YEAR);
         assert_eq!(config.archive_after_days, DEFAULT_ARCHIVE_AFTER_DAYS);
+        assert_eq!(config.min_account_age_slots, DEFAULT_MIN_ACCOUNT_AGE_SLOTS);
         assert!(config.allow_revival);
         assert!(config.exempt_system_programs);
     }
@@ -719,12 +1267,20 @@ mod tests {
             exempt_system_programs: false,
             ..Default::default()
         };
+        let pubkey = Pubkey::new_unique();
         let account = make_account(1000, 256);
         let last_active_slot = 0;
         // 400 days worth of slots
         let current_slot = 400 * ESTIMATED_SLOTS_PER_DAY;
 
-        assert!(check_rent_expiry(&account, last_active_slot, current_slot, &config));
+        assert!(check_rent_expiry(
+            &pubkey,
+            &account,
+            last_active_slot,
+            0,
+            current_slot,
+            &config
+        ));
     }
 
     #[test]
@@ -734,11 +1290,19 @@ mod tests {
             exempt_system_programs: false,
             ..Default::default()
         };
+        let pubkey = Pubkey::new_unique();
         let account = make_account(1000, 256);
         let current_slot = 100 * ESTIMATED_SLOTS_PER_DAY; // 100 days
         let last_active_slot = current_slot - 50 * ESTIMATED_SLOTS_PER_DAY; // active 50 days ago
 
-        assert!(!check_rent_expiry(&account, last_active_slot, current_slot, &config));
+        assert!(!check_rent_expiry(
+            &pubkey,
+            &account,
+            last_active_slot,
+            0,
+            current_slot,
+            &config
+        ));
     }
 
     #[test]
@@ -748,10 +1312,18 @@ mod tests {
             exempt_system_programs: false,
             ..Default::default()
         };
+        let pubkey = Pubkey::new_unique();
         let account = make_account(1000, 10); // tiny account
         let current_slot = 1000 * ESTIMATED_SLOTS_PER_DAY;
 
-        assert!(!check_rent_expiry(&account, 0, current_slot, &config));
+        assert!(!check_rent_expiry(
+            &pubkey,
+            &account,
+            0,
+            0,
+            current_slot,
+            &config
+        ));
     }
 
     #[test]
@@ -761,10 +1333,104 @@ mod tests {
             exempt_system_programs: false,
             ..Default::default()
         };
+        let pubkey = Pubkey::new_unique();
         let account = make_account(0, 256);
         let current_slot = 1000 * ESTIMATED_SLOTS_PER_DAY;
 
-        assert!(!check_rent_expiry(&account, 0, current_slot, &config));
+        assert!(!check_rent_expiry(
+            &pubkey,
+            &account,
+            0,
+            0,
+            current_slot,
+            &config
+        ));
+    }
+
+    #[test]
+    fn test_check_rent_expiry_new_account_grace_period() {
+        let config = StateRentConfig {
+            archive_after_days: 1,
+            min_account_age_slots: 1_500_000,
+            exempt_system_programs: false,
+            ..Default::default()
+        };
+        let pubkey = Pubkey::new_unique();
+        let account = make_account(1000, 256);
+
+        // Never written since creation at slot 1: inactive long enough, but
+        // too young.
+        assert!(!check_rent_expiry(
+            &pubkey, &account, 0, 1, 1_000_000, &config
+        ));
+        assert!(check_rent_expiry(
+            &pubkey, &account, 0, 1, 1_500_001, &config
+        ));
+
+        // Creation counts as activity when it is later than the last write.
+        let config = StateRentConfig {
+            archive_after_days: 10,
+            min_account_age_slots: 0,
+            ..config
+        };
+        let created = 5 * ESTIMATED_SLOTS_PER_DAY;
+        let current_slot = 12 * ESTIMATED_SLOTS_PER_DAY;
+        assert!(check_rent_expiry(
+            &pubkey,
+            &account,
+            0,
+            0,
+            current_slot,
+            &config
+        ));
+        assert!(!check_rent_expiry(
+            &pubkey,
+            &account,
+            0,
+            created,
+            current_slot,
+            &config
+        ));
+    }
+
+    #[test]
+    fn test_check_rent_expiry_exempt_program_owned_account() {
+        let config = StateRentConfig::for_testing();
+        let pubkey = Pubkey::new_unique();
+        let account = AccountSharedData::new(1000, 512, &GOVERNANCE_PROGRAM_ID);
+        let current_slot = 1_000 * ESTIMATED_SLOTS_PER_DAY;
+
+        assert!(!check_rent_expiry(
+            &pubkey,
+            &account,
+            0,
+            0,
+            current_slot,
+            &config
+        ));
+
+        // The same account under another owner is archived...
+        let other = AccountSharedData::new(1000, 512, &Pubkey::new_unique());
+        assert!(check_rent_expiry(
+            &pubkey,
+            &other,
+            0,
+            0,
+            current_slot,
+            &config
+        ));
+
+        // ...unless the account itself is exempt.
+        let mut config = config;
+        config.exemptions.add_account(pubkey);
+        assert!(!check_rent_expiry(
+            &pubkey,
+            &other,
+            0,
+            0,
+            current_slot,
+            &config
+        ));
     }
 
     #[test]
@@ -780,9 +1446,15 @@ mod tests {
         assert_eq!(archived.data_len, 256);
         assert_eq!(archived.lamports_at_archive, 100_000);
 
+        // Index and publish the epoch's root
+        let mut index = ArchiveIndex::new();
+        index.insert(archived);
+        let root = index.publish_epoch_root(1, 43).root;
+        let archived = index.get(&pubkey).unwrap();
+
         // Revive
         let revival_deposit = config.revival_deposit(256);
-        let revived = revive_account(&archived, revival_deposit, &config).unwrap();
+        let revived = revive_account(archived, revival_deposit, Some(root), &config).unwrap();
         assert_eq!(revived.data(), account.data());
         assert_eq!(revived.lamports(), account.lamports());
         assert_eq!(revived.owner(), account.owner());
@@ -799,7 +1471,7 @@ mod tests {
 
         let archived = archive_account(&pubkey, &account, 42, 1, &config).unwrap();
 
-        let result = revive_account(&archived, 1, &config); // too little
+        let result = revive_account(&archived, 1, None, &config); // too little
         assert!(matches!(result, Err(RevivalError::InsufficientRentDeposit { .. })));
 
         // Cleanup
@@ -817,7 +1489,7 @@ mod tests {
 
         let archived = archive_account(&pubkey, &account, 42, 1, &config).unwrap();
 
-        let result = revive_account(&archived, 1_000_000, &config);
+        let result = revive_account(&archived, 1_000_000, None, &config);
         assert!(matches!(result, Err(RevivalError::RevivalDisabled)));
 
         // Cleanup
@@ -892,6 +1564,61 @@ mod tests {
         assert_eq!(index.total_revived, 1);
     }
 
+    #[test]
+    fn test_archive_index_export_csv_and_statistics() {
+        let mut index = ArchiveIndex::new();
+        assert_eq!(index.statistics(), ArchiveStatistics::default());
+
+        for i in 0..10u64 {
+            index.insert(ArchivedAccount {
+                pubkey: Pubkey::new_unique(),
+                archive_slot: i.saturating_mul(1_000),
+                archive_epoch: i.saturating_add(3),
+                account_hash: Hash::default(),
+                lamports_at_archive: i.saturating_add(1).saturating_mul(500),
+                data_len: (i as usize).saturating_add(1).saturating_mul(100),
+                owner: Pubkey::new_unique(),
+                executable: i == 0,
+                archive_timestamp: i.saturating_add(1_700_000_000),
+                storage_path: PathBuf::from("/tmp/test.bin"),
+                merkle_proof: None,
+            });
+        }
+
+        let stats = index.statistics();
+        assert_eq!(stats.total_accounts, 10);
+        assert_eq!(stats.total_bytes, 5_500);
+        assert_eq!(stats.oldest_archive_epoch, Some(3));
+        assert_eq!(stats.newest_archive_epoch, Some(12));
+        assert_eq!(stats.avg_account_size_bytes, 550);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("archive_index.csv");
+        index.export_csv(&path).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let mut lines = contents.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "pubkey,archive_slot,archive_epoch,data_len_bytes,lamports_at_archive,owner,executable,archive_timestamp"
+        );
+        let rows: Vec<Vec<&str>> = lines.map(|l| l.split(',').collect()).collect();
+        assert_eq!(rows.len(), 10);
+        let pubkeys: Vec<Pubkey> = rows.iter().map(|r| r[0].parse().unwrap()).collect();
+        assert!(pubkeys.windows(2).all(|w| w[0] < w[1]));
+        for (row, pubkey) in rows.iter().zip(&pubkeys) {
+            assert_eq!(row.len(), 8);
+            let archived = index.get(pubkey).unwrap();
+            assert_eq!(row[1], archived.archive_slot.to_string());
+            assert_eq!(row[2], archived.archive_epoch.to_string());
+            assert_eq!(row[3], archived.data_len.to_string());
+            assert_eq!(row[4], archived.lamports_at_archive.to_string());
+            assert_eq!(row[5], archived.owner.to_string());
+            assert_eq!(row[6], archived.executable.to_string());
+            assert_eq!(row[7], archived.archive_timestamp.to_string());
+        }
+    }
+
     #[test]
     fn test_merkle_proof_verify() {
         let data_hash = sha256_hash(b"test account data");
@@ -912,6 +1639,155 @@ mod tests {
         assert!(!proof.verify(&sha256_hash(b"wrong data")));
     }
 
+    #[test]
+    fn test_incremental_merkle_tree_proofs() {
+        let mut tree = IncrementalMerkleTree::new();
+        assert_eq!(tree.root(), Hash::default());
+
+        let leaves: Vec<Hash> = (0..16u8).map(|i| sha256_hash(&[i])).collect();
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = tree.append(*leaf);
+            assert_eq!(proof.leaf_index, i as u64);
+            assert!(verify_proof(*leaf, &proof, tree.root()));
+        }
+        assert_eq!(tree.len(), 16);
+        assert_eq!(tree.depth(), 4);
+
+        let root = tree.root();
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(i).unwrap();
+            assert_eq!(proof.proof_hashes.len(), 4);
+            assert_eq!(proof.root_hash, root);
+            assert!(verify_proof(*leaf, &proof, root));
+            assert!(proof.verify(leaf));
+
+            let mut bytes = leaf.to_bytes();
+            bytes[i] ^= 1;
+            let mutated = Hash::new_from_array(bytes);
+            assert!(!verify_proof(mutated, &proof, root));
+            let forged = MerkleProof {
+                leaf_hash: mutated,
+                ..proof
+            };
+            assert!(!verify_proof(mutated, &forged, root));
+        }
+        assert!(tree.proof(16).is_none());
+    }
+
+    #[test]
+    fn test_publish_epoch_root_and_revive() {
+        let cold_storage = tempfile::tempdir().unwrap();
+        let config = StateRentConfig {
+            cold_storage_path: cold_storage.path().to_path_buf(),
+            ..StateRentConfig::for_testing()
+        };
+        let mut index = ArchiveIndex::new();
+        let pubkeys: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let deposit = config.revival_deposit(256);
+        for pubkey in &pubkeys {
+            let account = make_account(100_000, 256);
+            let archived = archive_account(pubkey, &account, 42, 1, &config).unwrap();
+            // Without a proof there is nothing to check, so revival fails.
+            assert!(matches!(
+                revive_account(&archived, deposit, Some(Hash::default()), &config),
+                Err(RevivalError::MissingMerkleProof)
+            ));
+            index.insert(archived);
+            assert!(index.get(pubkey).unwrap().merkle_proof.is_some());
+        }
+
+        let published = index.publish_epoch_root(1, 100);
+        assert_eq!(published.num_leaves, 5);
+        assert_ne!(published.root, Hash::default());
+        assert_eq!(index.epoch_root(), Hash::default());
+
+        // Round-trip through the on-chain account.
+        let address = archive_root_address(1);
+        assert_ne!(address, archive_root_address(2));
+        let account = published.to_account(1);
+        assert_eq!(account.owner(), &STATE_RENT_PROGRAM_ID);
+        let root = ArchiveRoot::from_account(&account).unwrap();
+        assert_eq!(root, published);
+
+        for pubkey in &pubkeys {
+            let archived = index.get(pubkey).unwrap();
+            assert_eq!(archived.merkle_proof.as_ref().unwrap().proof_slot, 100);
+            // The proof's own root is not trusted without the published one.
+            assert!(matches!(
+                revive_account(archived, deposit, None, &config),
+                Err(RevivalError::ArchiveRootNotPublished)
+            ));
+            assert!(revive_account(archived, deposit, Some(root.root), &config).is_ok());
+            assert!(matches!(
+                revive_account(archived, deposit, Some(Hash::default()), &config),
+                Err(RevivalError::MerkleProofFailed)
+            ));
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingObserver {
+        archived: std::sync::Mutex<Vec<ArchivalEvent>>,
+        revived: std::sync::Mutex<Vec<Pubkey>>,
+    }
+
+    impl ArchivalObserver for RecordingObserver {
+        fn on_archived(&self, event: &ArchivalEvent) {
+            self.archived.lock().unwrap().push(event.clone());
+        }
+
+        fn on_revived(&self, pubkey: &Pubkey) {
+            self.revived.lock().unwrap().push(*pubkey);
+        }
+    }
+
+    #[test]
+    fn test_observers_notified_once_per_archival_and_revival() {
+        let cold_storage = tempfile::tempdir().unwrap();
+        let recorder = Arc::new(RecordingObserver::default());
+        let metrics = Arc::new(TRv1Metrics::new());
+        let config = StateRentConfig {
+            cold_storage_path: cold_storage.path().to_path_buf(),
+            observers: vec![
+                recorder.clone(),
+                Arc::new(MetricsObserver::new(metrics.clone())),
+                Arc::new(LoggingObserver),
+            ],
+            ..StateRentConfig::for_testing()
+        };
+
+        let pubkeys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut index = ArchiveIndex::new();
+        for pubkey in &pubkeys {
+            index.insert(
+                archive_account(pubkey, &make_account(100_000, 256), 42, 1, &config).unwrap(),
+            );
+        }
+        let root = index.publish_epoch_root(1, 43).root;
+
+        let expected: Vec<ArchivalEvent> = pubkeys
+            .iter()
+            .map(|pubkey| ArchivalEvent {
+                pubkey: *pubkey,
+                archive_slot: 42,
+                data_len: 256,
+            })
+            .collect();
+        assert_eq!(*recorder.archived.lock().unwrap(), expected);
+        assert!(recorder.revived.lock().unwrap().is_empty());
+        assert_eq!(metrics.total_archived.get(), 3);
+
+        // A failed revival is not reported.
+        let archived = index.get(&pubkeys[0]).unwrap();
+        assert!(revive_account(archived, 1, Some(root), &config).is_err());
+        assert!(recorder.revived.lock().unwrap().is_empty());
+
+        revive_account(archived, config.revival_deposit(256), Some(root), &config).unwrap();
+        assert_eq!(*recorder.revived.lock().unwrap(), vec![pubkeys[0]]);
+        assert_eq!(recorder.archived.lock().unwrap().len(), 3);
+        assert_eq!(metrics.total_revived.get(), 1);
+    }
+
     #[test]
     fn test_revival_deposit_calculation() {
         let config = StateRentConfig {
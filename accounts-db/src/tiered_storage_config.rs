@@ -20,7 +20,10 @@
 //! across tiers. Full integration with [`AccountsDb`](crate::accounts_db::AccountsDb)
 //! is planned for a subsequent phase.
 
-use std::path::PathBuf;
+use {
+    serde::{Deserialize, Serialize},
+    std::path::PathBuf,
+};
 
 // ── Size Constants ──────────────────────────────────────────────────────────
 
@@ -37,6 +40,14 @@ pub const DEFAULT_WARM_THRESHOLD_SLOTS: u64 = 1_000_000;
 /// Default number of days of inactivity before an account is archived to cold storage.
 pub const DEFAULT_COLD_THRESHOLD_DAYS: u64 = 365;
 
+/// Default number of recent accesses tracked per entry by
+/// [`EvictionPolicy::LRUK`].
+pub const DEFAULT_LRU_K: usize = 2;
+
+/// Default hot cache utilization above which the memory pressure callback
+/// is consulted.
+pub const DEFAULT_PRESSURE_THRESHOLD: f64 = 0.95;
+
 /// Default warm storage path
 pub const DEFAULT_WARM_STORAGE_DIR: &str = "trv1-warm-storage";
 
@@ -63,6 +74,11 @@ pub enum EvictionPolicy {
     /// by maintaining ghost lists to track recently evicted entries.
     /// Provides the best overall hit rate but has higher per-operation overhead.
     ARC,
+
+    /// LRU-K — evicts the account whose K-th most recent access is oldest,
+    /// with accounts seen fewer than K times going first. A one-off scan
+    /// cannot displace accounts that were accessed K times. K = 1 is LRU.
+    LRUK,
 }
 
 impl Default for EvictionPolicy {
@@ -77,6 +93,7 @@ impl std::fmt::Display for EvictionPolicy {
             EvictionPolicy::LRU => write!(f, "LRU"),
             EvictionPolicy::LFU => write!(f, "LFU"),
             EvictionPolicy::ARC => write!(f, "ARC"),
+            EvictionPolicy::LRUK => write!(f, "LRU-K"),
         }
     }
 }
@@ -162,6 +179,31 @@ pub struct TieredStorageConfig {
     ///
     /// Default: 0.90 (eviction starts at 90% full)
     pub target_utilization: f64,
+
+    /// Seconds an entry may go unaccessed before it expires from the hot
+    /// cache, independent of its eviction order.
+    ///
+    /// Expired entries are dropped without write-back on the next lookup or
+    /// eviction pass, so the following load reads the authoritative copy
+    /// from warm storage. Useful for short-lived accounts such as nonces.
+    ///
+    /// Default: `None` (entries never expire)
+    pub entry_ttl_seconds: Option<u64>,
+
+    /// Number of recent accesses tracked per entry, the K of
+    /// `EvictionPolicy::LRUK`. Must be at least 1; K = 1 degrades to LRU.
+    ///
+    /// Default: `DEFAULT_LRU_K` (2)
+    pub k: usize,
+
+    /// Utilization ratio above which the cache is under memory pressure and
+    /// its pressure callback decides how far to shed (0.0 - 1.0).
+    ///
+    /// Sits above `target_utilization`: routine eviction should keep the
+    /// cache below it, so crossing it means eviction is falling behind.
+    ///
+    /// Default: `DEFAULT_PRESSURE_THRESHOLD` (0.95)
+    pub pressure_threshold: f64,
 }
 
 impl Default for TieredStorageConfig {
@@ -176,6 +218,9 @@ impl Default for TieredStorageConfig {
             enable_state_rent_expiry: false,
             eviction_batch_size: 4096,
             target_utilization: 0.90,
+            entry_ttl_seconds: None,
+            k: DEFAULT_LRU_K,
+            pressure_threshold: DEFAULT_PRESSURE_THRESHOLD,
         }
     }
 }
@@ -238,6 +283,18 @@ impl TieredStorageConfig {
         if self.cold_threshold_days == 0 {
             return Err("cold_threshold_days must be > 0".to_string());
         }
+        if self.entry_ttl_seconds == Some(0) {
+            return Err("entry_ttl_seconds must be > 0 when set".to_string());
+        }
+        if self.k == 0 {
+            return Err("k must be > 0".to_string());
+        }
+        if self.pressure_threshold <= 0.0 || self.pressure_threshold > 1.0 {
+            return Err(format!(
+                "pressure_threshold must be in (0.0, 1.0], got {}",
+                self.pressure_threshold
+            ));
+        }
         Ok(())
     }
 
@@ -264,7 +321,7 @@ impl TieredStorageConfig {
 ///
 /// These statistics are updated atomically and can be queried to monitor
 /// cache performance, tier distribution, and read latencies.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct TierStats {
     /// Number of accounts currently in the hot tier (RAM)
     pub hot_accounts: u64,
@@ -302,6 +359,17 @@ pub struct TierStats {
     pub warm_to_cold_archives: u64,
     /// Number of accounts revived from cold storage
     pub cold_revivals: u64,
+
+    /// Evictions of accounts with unflushed changes (handed back for write-back)
+    pub total_dirty_evictions: u64,
+    /// Evictions of clean accounts (dropped without I/O)
+    pub total_clean_evictions: u64,
+    /// Entries dropped because they outlived `entry_ttl_seconds`
+    pub ttl_expirations: u64,
+    /// Entries loaded into the hot tier from a cache snapshot at startup
+    pub warmed_entries: u64,
+    /// Entries dropped because the bank slot that wrote them was abandoned
+    pub slot_invalidations: u64,
 }
 
 impl TierStats {
@@ -328,6 +396,53 @@ impl TierStats {
         }
     }
 
+    /// Fold `other` into these statistics, as when combining the stats of
+    /// several independent caches.
+    ///
+    /// Counters are summed, the average read latency is weighted by each
+    /// side's number of reads, and the hit/miss rates are recalculated.
+    pub fn merge(&mut self, other: &TierStats) {
+        let reads = self.total_hits.saturating_add(self.total_misses);
+        let other_reads = other.total_hits.saturating_add(other.total_misses);
+        let all_reads = reads.saturating_add(other_reads);
+        if all_reads > 0 {
+            self.avg_read_latency_us = (self.avg_read_latency_us * reads as f64
+                + other.avg_read_latency_us * other_reads as f64)
+                / all_reads as f64;
+        }
+
+        self.hot_accounts = self.hot_accounts.saturating_add(other.hot_accounts);
+        self.hot_size_bytes = self.hot_size_bytes.saturating_add(other.hot_size_bytes);
+        self.warm_accounts = self.warm_accounts.saturating_add(other.warm_accounts);
+        self.warm_size_bytes = self.warm_size_bytes.saturating_add(other.warm_size_bytes);
+        self.cold_accounts = self.cold_accounts.saturating_add(other.cold_accounts);
+        self.cold_size_bytes = self.cold_size_bytes.saturating_add(other.cold_size_bytes);
+        self.total_hits = self.total_hits.saturating_add(other.total_hits);
+        self.total_misses = self.total_misses.saturating_add(other.total_misses);
+        self.warm_to_hot_promotions = self
+            .warm_to_hot_promotions
+            .saturating_add(other.warm_to_hot_promotions);
+        self.hot_to_warm_demotions = self
+            .hot_to_warm_demotions
+            .saturating_add(other.hot_to_warm_demotions);
+        self.warm_to_cold_archives = self
+            .warm_to_cold_archives
+            .saturating_add(other.warm_to_cold_archives);
+        self.cold_revivals = self.cold_revivals.saturating_add(other.cold_revivals);
+        self.total_dirty_evictions = self
+            .total_dirty_evictions
+            .saturating_add(other.total_dirty_evictions);
+        self.total_clean_evictions = self
+            .total_clean_evictions
+            .saturating_add(other.total_clean_evictions);
+        self.ttl_expirations = self.ttl_expirations.saturating_add(other.ttl_expirations);
+        self.warmed_entries = self.warmed_entries.saturating_add(other.warmed_entries);
+        self.slot_invalidations = self
+            .slot_invalidations
+            .saturating_add(other.slot_invalidations);
+        self.recalculate_rates();
+    }
+
     /// Format a human-readable summary of tier distribution.
     pub fn summary(&self) -> String {
         format!(
@@ -373,6 +488,8 @@ mod tests {
         assert_eq!(config.warm_threshold_slots, DEFAULT_WARM_THRESHOLD_SLOTS);
         assert_eq!(config.cold_threshold_days, DEFAULT_COLD_THRESHOLD_DAYS);
         assert!(!config.enable_state_rent_expiry);
+        assert_eq!(config.k, DEFAULT_LRU_K);
+        assert_eq!(config.pressure_threshold, DEFAULT_PRESSURE_THRESHOLD);
         assert!(config.validate().is_ok());
     }
 
@@ -408,6 +525,58 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_validation_rejects_bad_pressure_threshold() {
+        for (pressure_threshold, valid) in [(0.0, false), (1.01, false), (1.0, true)] {
+            let config = TieredStorageConfig {
+                pressure_threshold,
+                ..Default::default()
+            };
+            assert_eq!(config.validate().is_ok(), valid, "{pressure_threshold}");
+        }
+    }
+
+    #[test]
+    fn test_validate_zero_ttl() {
+        let config = TieredStorageConfig {
+            entry_ttl_seconds: Some(0),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = TieredStorageConfig {
+            entry_ttl_seconds: Some(30),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_zero_k() {
+        let config = TieredStorageConfig {
+            k: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_tier_stats_serde_roundtrip() {
+        let mut stats = TierStats {
+            hot_accounts: 10,
+            hot_size_bytes: 4_096,
+            warm_accounts: 20,
+            cold_accounts: 5,
+            total_hits: 90,
+            total_misses: 10,
+            cold_revivals: 2,
+            ..TierStats::default()
+        };
+        stats.recalculate_rates();
+        let bytes = bincode::serialize(&stats).unwrap();
+        assert_eq!(bincode::deserialize::<TierStats>(&bytes).unwrap(), stats);
+    }
+
     #[test]
     fn test_eviction_watermark() {
         let config = TieredStorageConfig {
@@ -460,6 +629,7 @@ mod tests {
         assert_eq!(format!("{}", EvictionPolicy::LRU), "LRU");
         assert_eq!(format!("{}", EvictionPolicy::LFU), "LFU");
         assert_eq!(format!("{}", EvictionPolicy::ARC), "ARC");
+        assert_eq!(format!("{}", EvictionPolicy::LRUK), "LRU-K");
     }
 
     #[test]
@@ -5,6 +5,11 @@
 //! 2. Checks for rent-expired accounts and archives them
 //! 3. Reports statistics
 //!
+//! A separate [`PressureMonitor`] thread polls hot cache utilization every
+//! second and sheds memory through the cache's pressure callback when it
+//! crosses `pressure_threshold`, without waiting for the next maintenance
+//! tick.
+//!
 //! # Feature Gate
 //!
 //! All functionality is gated behind the `trv1-tiered-storage` feature flag.
@@ -28,6 +33,9 @@ pub const DEFAULT_MAINTENANCE_INTERVAL_SECS: u64 = 60;
 /// Minimum allowed maintenance interval: 5 seconds.
 pub const MIN_MAINTENANCE_INTERVAL_SECS: u64 = 5;
 
+/// How often the pressure monitor polls hot cache utilization.
+pub const PRESSURE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Background maintenance service for TRv1 tiered storage.
 ///
 /// Spawns a thread that periodically performs cache eviction, tier migration,
@@ -180,14 +188,102 @@ impl Drop for MaintenanceService {
     }
 }
 
+// ── Pressure Monitor ────────────────────────────────────────────────────────
+
+/// Background thread that sheds hot cache memory under pressure.
+///
+/// Every [`PRESSURE_POLL_INTERVAL`] it calls
+/// [`TRv1StorageAdapter::relieve_hot_cache_pressure`], which consults the
+/// callback installed with
+/// [`TRv1StorageAdapter::set_hot_cache_pressure_callback`] once utilization
+/// exceeds `pressure_threshold`. Without a callback the monitor does nothing.
+///
+/// Lifecycle matches [`MaintenanceService`]: it runs until `stop()` is
+/// called, the `exit` signal is set, or it is dropped.
+pub struct PressureMonitor {
+    /// Handle to the background thread.
+    thread: Option<JoinHandle<()>>,
+
+    /// Signal to stop the monitor thread.
+    exit: Arc<AtomicBool>,
+}
+
+impl PressureMonitor {
+    /// Start polling `adapter` for memory pressure.
+    pub fn start(adapter: Arc<TRv1StorageAdapter>, exit: Arc<AtomicBool>) -> Self {
+        let exit_clone = exit.clone();
+
+        let thread = thread::Builder::new()
+            .name("trv1Pressure".to_string())
+            .spawn(move || {
+                Self::run_loop(adapter, exit_clone);
+            })
+            .expect("Failed to spawn TRv1 pressure monitor thread");
+
+        Self {
+            thread: Some(thread),
+            exit,
+        }
+    }
+
+    /// The main loop of the monitor thread.
+    fn run_loop(adapter: Arc<TRv1StorageAdapter>, exit: Arc<AtomicBool>) {
+        while !exit.load(Ordering::Relaxed) {
+            thread::sleep(PRESSURE_POLL_INTERVAL);
+            if exit.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let evicted = adapter.relieve_hot_cache_pressure();
+            if !evicted.is_empty() {
+                warn!(
+                    "TRv1 memory pressure: shed {} dirty accounts from hot cache",
+                    evicted.len()
+                );
+                // As in `maintenance_tick`, evicted accounts are dropped until
+                // warm storage write-back exists; AccountsDb still holds them.
+            }
+        }
+    }
+
+    /// Signal the monitor thread to stop and wait for it to finish.
+    pub fn stop(mut self) {
+        self.exit.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            if let Err(e) = thread.join() {
+                warn!("TRv1 pressure monitor thread panicked: {:?}", e);
+            }
+        }
+    }
+
+    /// Check if the monitor thread is still running.
+    pub fn is_running(&self) -> bool {
+        self.thread
+            .as_ref()
+            .map(|t| !t.is_finished())
+            .unwrap_or(false)
+    }
+}
+
+impl Drop for PressureMonitor {
+    fn drop(&mut self) {
+        self.exit.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
 // ── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{
-        state_rent_expiry::StateRentConfig,
-        tiered_storage_config::TieredStorageConfig,
+    use {
+        crate::{state_rent_expiry::StateRentConfig, tiered_storage_config::TieredStorageConfig},
+        solana_account::AccountSharedData,
+        solana_pubkey::Pubkey,
+        std::sync::atomic::AtomicU64,
     };
 
     #[test]
@@ -276,4 +372,36 @@ mod tests {
         // Exit should have been signaled by drop
         assert!(exit.load(Ordering::Relaxed));
     }
+
+    #[test]
+    fn test_pressure_monitor_calls_callback_above_threshold() {
+        let adapter = Arc::new(TRv1StorageAdapter::new(
+            TieredStorageConfig {
+                hot_cache_size: 1_000,
+                pressure_threshold: 0.95,
+                ..TieredStorageConfig::for_testing()
+            },
+            StateRentConfig::for_testing(),
+        ));
+        let calls = Arc::new(AtomicU64::new(0));
+        let calls_clone = calls.clone();
+        adapter.set_hot_cache_pressure_callback(move |size| {
+            calls_clone.fetch_add(1, Ordering::Relaxed);
+            size / 2
+        });
+        // Six 170-byte entries put the cache at 102% utilization.
+        for _ in 0..6 {
+            let account = AccountSharedData::new(1, 10, &Pubkey::default());
+            adapter.hot_cache_insert(&Pubkey::new_unique(), &account, 0);
+        }
+
+        let exit = Arc::new(AtomicBool::new(false));
+        let monitor = PressureMonitor::start(adapter.clone(), exit);
+        thread::sleep(PRESSURE_POLL_INTERVAL + Duration::from_millis(500));
+        monitor.stop();
+
+        // Relieved once; the halved cache is no longer under pressure.
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+        assert_eq!(adapter.hot_cache_len(), 3);
+    }
 }
@@ -15,18 +15,22 @@
 
 use {
     crate::{
-        account_cache::AccountCache,
+        account_cache::{AccountCache, CacheError},
         state_rent_expiry::{
-            archive_account, check_rent_expiry, ArchiveIndex, StateRentConfig,
+            archive_account, check_rent_expiry, ArchiveIndex, ArchiveRoot, ArchiveStatistics,
+            StateRentConfig,
         },
         tiered_storage_config::{TierStats, TieredStorageConfig},
     },
     log::*,
     solana_account::AccountSharedData,
     solana_pubkey::Pubkey,
-    std::sync::{
-        atomic::{AtomicU64, Ordering},
-        RwLock,
+    std::{
+        path::Path,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            RwLock,
+        },
     },
 };
 
@@ -108,10 +112,10 @@ impl AtomicTierStats {
 ///     return Some((account, slot));
 /// }
 /// // ... normal AccountsDb load ...
-/// adapter.hot_cache_insert(&pubkey, &account);
+/// adapter.hot_cache_insert(&pubkey, &account, slot);
 ///
 /// // On store:
-/// adapter.hot_cache_insert(&pubkey, &account);
+/// adapter.hot_cache_insert(&pubkey, &account, slot);
 /// ```
 pub struct TRv1StorageAdapter {
     /// Our custom LRU hot cache, protected by a read-write lock.
@@ -168,16 +172,16 @@ impl TRv1StorageAdapter {
         }
     }
 
-    /// Insert an account into the hot cache.
+    /// Insert an account written at bank `slot` into the hot cache.
     ///
     /// If the account already exists, its data is updated and it is promoted
     /// to the most-recently-used position. If it's new, it is added at the head.
     ///
     /// This does NOT trigger eviction. Call `maybe_evict()` separately
     /// (typically from the maintenance thread).
-    pub fn hot_cache_insert(&self, pubkey: &Pubkey, account: &AccountSharedData) {
+    pub fn hot_cache_insert(&self, pubkey: &Pubkey, account: &AccountSharedData, slot: u64) {
         let mut cache = self.hot_cache.write().unwrap();
-        cache.insert(*pubkey, account.clone());
+        cache.insert(*pubkey, account.clone(), slot);
         self.stats.hot_inserts.fetch_add(1, Ordering::Relaxed);
     }
 
@@ -189,15 +193,27 @@ impl TRv1StorageAdapter {
         cache.remove(pubkey)
     }
 
+    /// Drop every hot cache entry written at `slot`, e.g. when its bank is
+    /// abandoned on a fork. Returns the number of entries dropped.
+    pub fn hot_cache_invalidate_slot(&self, slot: u64) -> usize {
+        let mut cache = self.hot_cache.write().unwrap();
+        cache.invalidate_slot(slot)
+    }
+
     /// Check if the hot cache needs eviction and perform it if so.
     ///
-    /// Returns the number of accounts evicted. Evicted accounts should be
-    /// persisted to warm storage by the caller (or maintenance service).
+    /// Returns the evicted dirty accounts, which should be persisted to warm
+    /// storage by the caller (or maintenance service). Clean evictions are
+    /// counted but not returned.
     pub fn maybe_evict(&self) -> Vec<(Pubkey, AccountSharedData)> {
         let mut cache = self.hot_cache.write().unwrap();
         if cache.needs_eviction() {
+            let demotions_before = cache.stats().hot_to_warm_demotions;
             let evicted = cache.evict_to_warm();
-            let count = evicted.len() as u64;
+            let count = cache
+                .stats()
+                .hot_to_warm_demotions
+                .saturating_sub(demotions_before);
             self.stats.hot_evictions.fetch_add(count, Ordering::Relaxed);
             evicted
         } else {
@@ -205,6 +221,30 @@ impl TRv1StorageAdapter {
         }
     }
 
+    /// Install the hot cache's memory pressure callback.
+    ///
+    /// See [`AccountCache::set_pressure_callback`].
+    pub fn set_hot_cache_pressure_callback(&self, f: impl Fn(u64) -> u64 + Send + Sync + 'static) {
+        let mut cache = self.hot_cache.write().unwrap();
+        cache.set_pressure_callback(f);
+    }
+
+    /// Shed hot cache memory if utilization exceeds the pressure threshold.
+    ///
+    /// Returns the evicted dirty accounts, like [`Self::maybe_evict`]. This is
+    /// polled by the `PressureMonitor`.
+    pub fn relieve_hot_cache_pressure(&self) -> Vec<(Pubkey, AccountSharedData)> {
+        let mut cache = self.hot_cache.write().unwrap();
+        let demotions_before = cache.stats().hot_to_warm_demotions;
+        let evicted = cache.relieve_pressure();
+        let count = cache
+            .stats()
+            .hot_to_warm_demotions
+            .saturating_sub(demotions_before);
+        self.stats.hot_evictions.fetch_add(count, Ordering::Relaxed);
+        evicted
+    }
+
     /// Perform a maintenance tick: evict cold accounts, check rent expiry.
     ///
     /// This is called periodically by the `MaintenanceService`.
@@ -271,11 +311,19 @@ impl TRv1StorageAdapter {
         pubkey: &Pubkey,
         account: &AccountSharedData,
         last_active_slot: u64,
+        creation_slot: u64,
         current_slot: u64,
         current_epoch: u64,
     ) -> bool {
         // Check if eligible
-        if !check_rent_expiry(account, last_active_slot, current_slot, &self.rent_config) {
+        if !check_rent_expiry(
+            pubkey,
+            account,
+            last_active_slot,
+            creation_slot,
+            current_slot,
+            &self.rent_config,
+        ) {
             return false;
         }
 
@@ -309,6 +357,40 @@ impl TRv1StorageAdapter {
         index.is_archived(pubkey)
     }
 
+    /// Close the archive tree of `epoch` and return the root to store at
+    /// `archive_root_address(epoch)`; called once per epoch boundary.
+    pub fn publish_epoch_root(&self, epoch: u64, slot: u64) -> ArchiveRoot {
+        let mut index = self.archive_index.write().unwrap();
+        index.publish_epoch_root(epoch, slot)
+    }
+
+    /// Aggregate statistics over the accounts currently in cold storage.
+    pub fn archive_statistics(&self) -> ArchiveStatistics {
+        let index = self.archive_index.read().unwrap();
+        index.statistics()
+    }
+
+    /// Export the archive index as CSV to `path`.
+    pub fn export_archive_index_csv(&self, path: &Path) -> std::io::Result<()> {
+        let index = self.archive_index.read().unwrap();
+        index.export_csv(path)
+    }
+
+    /// Dump the hot cache to a snapshot file at `path`; call on graceful
+    /// shutdown after dirty accounts have been written back.
+    pub fn dump_hot_cache_snapshot(&self, path: &Path) -> Result<(), CacheError> {
+        let cache = self.hot_cache.read().unwrap();
+        cache.dump_to_snapshot(path)
+    }
+
+    /// Warm the hot cache from a snapshot written by
+    /// [`Self::dump_hot_cache_snapshot`], returning the number of accounts
+    /// loaded.
+    pub fn warm_hot_cache_from_snapshot(&self, path: &Path) -> Result<usize, CacheError> {
+        let mut cache = self.hot_cache.write().unwrap();
+        cache.warm_from_snapshot(path)
+    }
+
     /// Get a snapshot of the current tier statistics.
     pub fn stats(&self) -> TierStats {
         let mut stats = self.stats.snapshot();
@@ -318,6 +400,11 @@ impl TRv1StorageAdapter {
         let cache_stats = cache.stats();
         stats.hot_accounts = cache_stats.hot_accounts;
         stats.hot_size_bytes = cache_stats.hot_size_bytes;
+        stats.total_dirty_evictions = cache_stats.total_dirty_evictions;
+        stats.total_clean_evictions = cache_stats.total_clean_evictions;
+        stats.ttl_expirations = cache_stats.ttl_expirations;
+        stats.warmed_entries = cache_stats.warmed_entries;
+        stats.slot_invalidations = cache_stats.slot_invalidations;
 
         // Add archive index stats
         let archive = self.archive_index.read().unwrap();
@@ -385,7 +472,7 @@ mod tests {
         let account = make_account(100, 1000);
 
         // Insert into hot cache
-        adapter.hot_cache_insert(&pubkey, &account);
+        adapter.hot_cache_insert(&pubkey, &account, 0);
         assert_eq!(adapter.hot_cache_len(), 1);
 
         // Should be a cache hit
@@ -427,7 +514,7 @@ mod tests {
         for _ in 0..5 {
             let pk = Pubkey::new_unique();
             let account = make_account(50, 1000);
-            adapter.hot_cache_insert(&pk, &account);
+            adapter.hot_cache_insert(&pk, &account, 0);
         }
 
         // Force eviction
@@ -445,7 +532,7 @@ mod tests {
         let pubkey = Pubkey::new_unique();
         let account = make_account(100, 1000);
 
-        adapter.hot_cache_insert(&pubkey, &account);
+        adapter.hot_cache_insert(&pubkey, &account, 0);
         assert_eq!(adapter.hot_cache_len(), 1);
 
         let removed = adapter.hot_cache_remove(&pubkey);
@@ -464,7 +551,7 @@ mod tests {
         let account = make_account(256, 10_000);
 
         // Insert into hot cache first
-        adapter.hot_cache_insert(&pubkey, &account);
+        adapter.hot_cache_insert(&pubkey, &account, 0);
         assert_eq!(adapter.hot_cache_len(), 1);
 
         // Archive it — last active slot 0, current slot = 2 days worth
@@ -474,6 +561,7 @@ mod tests {
             &pubkey,
             &account,
             0, // last_active_slot
+            0, // creation_slot
             current_slot,
             1, // current_epoch
         );
@@ -502,7 +590,8 @@ mod tests {
         let archived = adapter.archive_account_to_cold(
             &pubkey,
             &account,
-            50,   // last_active_slot
+            50, // last_active_slot
+            0,  // creation_slot
             current_slot,
             1,
         );
@@ -517,8 +606,8 @@ mod tests {
         let account = make_account(100, 1000);
 
         // 2 inserts
-        adapter.hot_cache_insert(&pk1, &account);
-        adapter.hot_cache_insert(&pk2, &account);
+        adapter.hot_cache_insert(&pk1, &account, 0);
+        adapter.hot_cache_insert(&pk2, &account, 0);
 
         // 3 hits
         adapter.hot_cache_get(&pk1);
@@ -542,7 +631,7 @@ mod tests {
         let adapter = test_adapter();
         let pk = Pubkey::new_unique();
         let account = make_account(100, 1000);
-        adapter.hot_cache_insert(&pk, &account);
+        adapter.hot_cache_insert(&pk, &account, 0);
 
         // Maintenance tick should not panic
         adapter.maintenance_tick(1000, 1);
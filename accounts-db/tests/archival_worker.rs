@@ -0,0 +1,72 @@
+use {
+    solana_account::AccountSharedData,
+    solana_accounts_db::{
+        archival_worker::{
+            ArchivalCandidate, ArchivalTask, ArchivalWorker, DEFAULT_ARCHIVAL_QUEUE_CAPACITY,
+            DEFAULT_ENQUEUE_TIMEOUT,
+        },
+        state_rent_expiry::{ArchiveIndex, StateRentConfig, ESTIMATED_SLOTS_PER_DAY},
+    },
+    solana_pubkey::Pubkey,
+    std::sync::{Arc, Mutex},
+};
+
+/// One-day epochs, so a single epoch of inactivity crosses the 1 day
+/// `archive_after_days` threshold of the testing config.
+const SLOTS_PER_EPOCH: u64 = ESTIMATED_SLOTS_PER_DAY;
+
+/// Accounts written at genesis and never touched again are archived by the
+/// worker once the epoch boundary that makes them eligible is crossed.
+#[test]
+fn test_archival_worker_archives_across_epoch_boundary() {
+    let cold_storage = tempfile::tempdir().unwrap();
+    let config = StateRentConfig {
+        cold_storage_path: cold_storage.path().to_path_buf(),
+        ..StateRentConfig::for_testing()
+    };
+    let index = Arc::new(Mutex::new(ArchiveIndex::new()));
+    let worker = ArchivalWorker::start(
+        config,
+        index.clone(),
+        DEFAULT_ARCHIVAL_QUEUE_CAPACITY,
+        DEFAULT_ENQUEUE_TIMEOUT,
+    );
+
+    let candidates: Vec<ArchivalCandidate> = (0..100)
+        .map(|i| ArchivalCandidate {
+            pubkey: Pubkey::new_unique(),
+            account: AccountSharedData::new(1_000_000, 4_096 + i * 64, &Pubkey::new_unique()),
+            last_active_slot: 0,
+            creation_slot: 0,
+        })
+        .collect();
+    let pubkeys: Vec<Pubkey> = candidates.iter().map(|c| c.pubkey).collect();
+
+    // Mid-epoch 0 the accounts are not yet inactive long enough.
+    worker
+        .enqueue(ArchivalTask {
+            current_slot: SLOTS_PER_EPOCH / 2,
+            current_epoch: 0,
+            candidates: candidates.clone(),
+        })
+        .unwrap();
+
+    // Epoch 0 → 1 boundary.
+    worker
+        .enqueue(ArchivalTask {
+            current_slot: SLOTS_PER_EPOCH,
+            current_epoch: 1,
+            candidates,
+        })
+        .unwrap();
+    worker.join().unwrap();
+
+    let index = index.lock().unwrap();
+    assert_eq!(index.len(), 100);
+    for pubkey in &pubkeys {
+        let archived = index.get(pubkey).unwrap();
+        assert_eq!(archived.archive_epoch, 1);
+        assert_eq!(archived.archive_slot, SLOTS_PER_EPOCH);
+        assert!(archived.storage_path.starts_with(cold_storage.path()));
+    }
+}
@@ -5,12 +5,24 @@
 //! - Cache eviction throughput
 //! - Insert/lookup latency
 //! - Simulated 1GB, 4GB, 16GB cache sizes
+//! - Concurrent get/insert throughput of the real `ShardedAccountCache`
+//!   against a `Mutex<AccountCache>` baseline, with cache hit rates
+//! - `evict_to_warm` latency at 90% utilization
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use rand::Rng;
+use rayon::{prelude::*, ThreadPoolBuilder};
+use solana_account::AccountSharedData;
+use solana_accounts_db::{
+    account_cache::{AccountCache, ShardedAccountCache, DEFAULT_NUM_SHARDS},
+    tiered_storage_config::{EvictionPolicy, TieredStorageConfig},
+};
 use solana_hash::Hash;
 use solana_pubkey::Pubkey;
 use std::collections::HashMap;
+use std::hint::black_box;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 // ---------------------------------------------------------------------------
 // Simulated LRU cache (mirrors the real TRv1 account cache design)
@@ -300,6 +312,210 @@ fn bench_cache_varying_data_sizes(c: &mut Criterion) {
     group.finish();
 }
 
+// ---------------------------------------------------------------------------
+// Real account cache (solana-accounts-db)
+// ---------------------------------------------------------------------------
+
+/// Worker threads in the parallel benchmark.
+const PARALLEL_THREADS: usize = 16;
+/// Alternating get/insert operations per worker per iteration.
+const OPS_PER_THREAD: usize = 10_000;
+/// Distinct accounts the workers draw from.
+const WORKING_SET_ACCOUNTS: usize = 50_000;
+/// Accounts the cache holds when full.
+const CACHE_CAPACITY_ACCOUNTS: u64 = 10_000;
+/// Data size of every benchmark account (an SPL token account).
+const ACCOUNT_DATA_BYTES: usize = 165;
+/// Bytes the cache charges per entry: 160 bytes of overhead plus the data.
+const CACHED_ENTRY_BYTES: u64 = 160 + ACCOUNT_DATA_BYTES as u64;
+/// Accounts evicted per `evict_to_warm` call.
+const EVICTION_BATCH: usize = 1_000;
+/// Operations between eviction passes in each worker, standing in for the
+/// background eviction thread.
+const OPS_PER_EVICTION: usize = 1_000;
+
+/// Cache sized for [`CACHE_CAPACITY_ACCOUNTS`], evicting down to 80% in
+/// batches of [`EVICTION_BATCH`].
+fn account_cache_config(eviction_policy: EvictionPolicy) -> TieredStorageConfig {
+    TieredStorageConfig {
+        hot_cache_size: CACHE_CAPACITY_ACCOUNTS * CACHED_ENTRY_BYTES,
+        eviction_policy,
+        eviction_batch_size: EVICTION_BATCH,
+        target_utilization: 0.80,
+        ..TieredStorageConfig::for_testing()
+    }
+}
+
+/// Random pubkeys, so they spread evenly over the cache shards.
+fn random_pubkeys(n: usize) -> Vec<Pubkey> {
+    let mut rng = rand::rng();
+    (0..n)
+        .map(|_| Pubkey::new_from_array(rng.random()))
+        .collect()
+}
+
+/// The operations the parallel benchmark needs from a thread-safe cache.
+trait ConcurrentCache: Sync {
+    fn get(&self, pubkey: &Pubkey) -> bool;
+    fn insert(&self, pubkey: Pubkey, account: AccountSharedData);
+    /// Evict down to the watermark if the cache is over it.
+    fn evict(&self);
+    /// Hits and misses recorded since the cache was created.
+    fn hits_and_misses(&self) -> (u64, u64);
+}
+
+impl ConcurrentCache for ShardedAccountCache {
+    fn get(&self, pubkey: &Pubkey) -> bool {
+        ShardedAccountCache::get(self, pubkey).is_some()
+    }
+
+    fn insert(&self, pubkey: Pubkey, account: AccountSharedData) {
+        ShardedAccountCache::insert(self, pubkey, account, 0);
+    }
+
+    fn evict(&self) {
+        self.evict_to_warm();
+    }
+
+    fn hits_and_misses(&self) -> (u64, u64) {
+        let stats = self.aggregate_stats();
+        (stats.total_hits, stats.total_misses)
+    }
+}
+
+impl ConcurrentCache for Mutex<AccountCache> {
+    fn get(&self, pubkey: &Pubkey) -> bool {
+        self.lock().unwrap().get(pubkey).is_some()
+    }
+
+    fn insert(&self, pubkey: Pubkey, account: AccountSharedData) {
+        self.lock().unwrap().insert(pubkey, account, 0);
+    }
+
+    fn evict(&self) {
+        let mut cache = self.lock().unwrap();
+        if cache.needs_eviction() {
+            cache.evict_to_warm();
+        }
+    }
+
+    fn hits_and_misses(&self) -> (u64, u64) {
+        let cache = self.lock().unwrap();
+        (cache.stats().total_hits, cache.stats().total_misses)
+    }
+}
+
+fn bench_cache_parallel_16t(c: &mut Criterion) {
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(PARALLEL_THREADS)
+        .thread_name(|i| format!("trv1BenchCache{i:02}"))
+        .build()
+        .unwrap();
+    let pubkeys = random_pubkeys(WORKING_SET_ACCOUNTS);
+    let account = AccountSharedData::new(1, ACCOUNT_DATA_BYTES, &Pubkey::default());
+
+    // Each worker gets its own uniformly random access sequence.
+    let mut rng = rand::rng();
+    let accesses: Vec<Vec<usize>> = (0..PARALLEL_THREADS)
+        .map(|_| {
+            (0..OPS_PER_THREAD)
+                .map(|_| rng.random_range(0..WORKING_SET_ACCOUNTS))
+                .collect()
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("cache_parallel_16t");
+    group.sample_size(20);
+    group.throughput(Throughput::Elements(
+        (PARALLEL_THREADS * OPS_PER_THREAD) as u64,
+    ));
+
+    let mut run = |name: &str, cache: &dyn ConcurrentCache| {
+        // Start from a full cache so the run measures steady state.
+        for pubkey in pubkeys.iter().take(CACHE_CAPACITY_ACCOUNTS as usize) {
+            cache.insert(*pubkey, account.clone());
+        }
+        cache.evict();
+
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                pool.install(|| {
+                    accesses.par_iter().for_each(|thread_accesses| {
+                        for (i, &key) in thread_accesses.iter().enumerate() {
+                            let pubkey = &pubkeys[key];
+                            if i % 2 == 0 {
+                                black_box(cache.get(pubkey));
+                            } else {
+                                cache.insert(*pubkey, account.clone());
+                            }
+                            if (i + 1) % OPS_PER_EVICTION == 0 {
+                                cache.evict();
+                            }
+                        }
+                    })
+                })
+            });
+        });
+
+        let (hits, misses) = cache.hits_and_misses();
+        let hit_rate = hits as f64 / (hits + misses).max(1) as f64;
+        println!(
+            "cache_parallel_16t/{name}: hit rate {:.1}% over {} gets",
+            hit_rate * 100.0,
+            hits + misses
+        );
+    };
+
+    let sharded = ShardedAccountCache::new(
+        account_cache_config(EvictionPolicy::LRU),
+        DEFAULT_NUM_SHARDS,
+    );
+    run("sharded", &sharded);
+    let mutex = Mutex::new(AccountCache::new(account_cache_config(EvictionPolicy::LRU)));
+    run("mutex_baseline", &mutex);
+
+    group.finish();
+}
+
+fn bench_cache_eviction_pressure(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cache_eviction_pressure");
+    group.throughput(Throughput::Elements(EVICTION_BATCH as u64));
+
+    // Filled to 90% with the watermark at 80%, each `evict_to_warm` call
+    // evicts exactly one batch.
+    let full_accounts = (CACHE_CAPACITY_ACCOUNTS * 9 / 10) as usize;
+    let pubkeys = random_pubkeys(WORKING_SET_ACCOUNTS);
+    let account = AccountSharedData::new(1, ACCOUNT_DATA_BYTES, &Pubkey::default());
+
+    for policy in [
+        EvictionPolicy::LRU,
+        EvictionPolicy::LFU,
+        EvictionPolicy::ARC,
+        EvictionPolicy::LRUK,
+    ] {
+        group.bench_function(BenchmarkId::new("policy", format!("{policy:?}")), |b| {
+            let mut cache = AccountCache::new(account_cache_config(policy));
+            let mut next = pubkeys.iter().cycle();
+
+            b.iter_custom(|iters| {
+                let mut elapsed = Duration::ZERO;
+                for _ in 0..iters {
+                    while cache.len() < full_accounts {
+                        cache.insert(*next.next().unwrap(), account.clone(), 0);
+                    }
+
+                    let start = Instant::now();
+                    let evicted = black_box(cache.evict_to_warm());
+                    elapsed += start.elapsed();
+                    assert_eq!(evicted.len(), EVICTION_BATCH);
+                }
+                elapsed
+            });
+        });
+    }
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_cache_insert,
@@ -307,5 +523,7 @@ criterion_group!(
     bench_cache_hit_miss_ratio,
     bench_cache_eviction_throughput,
     bench_cache_varying_data_sizes,
+    bench_cache_parallel_16t,
+    bench_cache_eviction_pressure,
 );
 criterion_main!(benches);
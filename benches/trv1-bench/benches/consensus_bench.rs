@@ -5,6 +5,18 @@
 //! - Message processing throughput (proposals, prevotes, precommits)
 //! - Validator set sizes: 50, 100, 200
 //! - Round-trip with simulated network latency
+//! - Per-message `on_prevote` / `on_precommit` cost over 1000 heights at 200
+//!   validators, with equal and Zipf-distributed stake, broken down into the
+//!   quorum scan and the vote-map insert
+//!
+//! Every `on_prevote` / `on_precommit` call verifies an ed25519 signature
+//! before touching the vote maps, so the end-to-end per-message time is
+//! bounded below by signature verification (tens of microseconds). The
+//! `quorum_scan` and `vote_insert` benchmarks isolate the O(n) bookkeeping
+//! that runs after authentication.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use solana_hash::Hash;
@@ -13,8 +25,8 @@ use solana_pubkey::Pubkey;
 use solana_signature::Signature;
 use solana_signer::Signer;
 use trv1_consensus_bft::{
-    BftConfig, ConsensusEngine, ConsensusMessage,
-    ProposedBlock, ValidatorSet,
+    BftConfig, ConsensusEngine, ConsensusMessage, ConsensusState, ConsensusStep, ProposedBlock,
+    ValidatorSet,
 };
 
 // ---------------------------------------------------------------------------
@@ -28,44 +40,127 @@ fn make_validator_set(n: usize) -> (ValidatorSet, Vec<Keypair>) {
     (ValidatorSet::new(validators), keypairs)
 }
 
-fn make_proposal(height: u64, round: u32, proposer: Pubkey) -> (ConsensusMessage, ProposedBlock) {
+/// Keypair of the proposer for `(height, round)`, falling back to the first
+/// validator.
+fn proposer_keypair<'a>(
+    vs: &ValidatorSet,
+    keypairs: &'a [Keypair],
+    height: u64,
+    round: u32,
+) -> &'a Keypair {
+    trv1_consensus_bft::proposer_for_round(vs, height, round)
+        .and_then(|pk| keypairs.iter().find(|kp| kp.pubkey() == pk))
+        .unwrap_or(&keypairs[0])
+}
+
+fn make_proposal(height: u64, round: u32, proposer: &Keypair) -> (ConsensusMessage, ProposedBlock) {
     let block = ProposedBlock {
         parent_hash: Hash::default(),
         height,
         timestamp: 1_700_000_000_000,
         transactions: Vec::new(),
         state_root: Hash::new_unique(),
-        proposer,
+        proposer: proposer.pubkey(),
+        evidence: vec![],
     };
     let msg = ConsensusMessage::Proposal {
         height,
         round,
         block: block.clone(),
-        proposer,
+        proposer: proposer.pubkey(),
         signature: Signature::default(),
         valid_round: None,
-    };
+        polka_certificate: None,
+    }
+    .signed(proposer);
     (msg, block)
 }
 
-fn make_prevote(height: u64, round: u32, voter: Pubkey, block_hash: Option<Hash>) -> ConsensusMessage {
+fn make_prevote(height: u64, round: u32, voter: &Keypair, block_hash: Option<Hash>) -> ConsensusMessage {
     ConsensusMessage::Prevote {
         height,
         round,
         block_hash,
-        voter,
+        voter: voter.pubkey(),
         signature: Signature::default(),
     }
+    .signed(voter)
 }
 
-fn make_precommit(height: u64, round: u32, voter: Pubkey, block_hash: Option<Hash>) -> ConsensusMessage {
+fn make_precommit(height: u64, round: u32, voter: &Keypair, block_hash: Option<Hash>) -> ConsensusMessage {
     ConsensusMessage::Precommit {
         height,
         round,
         block_hash,
-        voter,
+        voter: voter.pubkey(),
         signature: Signature::default(),
     }
+    .signed(voter)
+}
+
+/// Validator count for the large-network benchmarks.
+const LARGE_NETWORK_VALIDATORS: usize = 200;
+
+/// Votes delivered per step at 200 validators: just over 2/3.
+const LARGE_NETWORK_VOTES: usize = 134;
+
+/// Heights simulated by the large-network benchmarks.
+const LARGE_NETWORK_HEIGHTS: u64 = 1000;
+
+/// Validator set with Zipf-distributed stake (exponent 1): the validator of
+/// rank `i` holds `1 / (i + 1)` of the top stake.
+fn make_zipf_validator_set(n: usize) -> (ValidatorSet, Vec<Keypair>) {
+    const TOP_STAKE: u64 = 1_000_000_000_000;
+    let keypairs: Vec<Keypair> = (0..n).map(|_| Keypair::new()).collect();
+    let validators: Vec<(Pubkey, u64)> = keypairs
+        .iter()
+        .enumerate()
+        .map(|(rank, kp)| (kp.pubkey(), TOP_STAKE / (rank as u64 + 1)))
+        .collect();
+    (ValidatorSet::new(validators), keypairs)
+}
+
+/// Pre-signed messages for one height, so signing stays out of the timed
+/// region.
+struct SignedHeight {
+    height: u64,
+    proposal: ConsensusMessage,
+    prevotes: Vec<ConsensusMessage>,
+    precommits: Vec<ConsensusMessage>,
+}
+
+/// Sign a proposal plus `votes` prevotes and precommits for each height.
+///
+/// Validator 0 is the local engine and never votes. The voters are
+/// validators `1..=votes`, delivered in reverse order so that with Zipf stake
+/// the smallest stakes arrive first and quorum is crossed as late as
+/// possible.
+fn make_signed_heights(
+    vs: &ValidatorSet,
+    keypairs: &[Keypair],
+    votes: usize,
+    heights: u64,
+) -> Vec<SignedHeight> {
+    let voters: Vec<&Keypair> = keypairs[1..=votes].iter().rev().collect();
+    (1..=heights)
+        .map(|height| {
+            let proposer = proposer_keypair(vs, keypairs, height, 0);
+            let (proposal, block) = make_proposal(height, 0, proposer);
+            let block_hash = Some(block.hash());
+            SignedHeight {
+                height,
+                proposal,
+                prevotes: voters
+                    .iter()
+                    .map(|kp| make_prevote(height, 0, kp, block_hash))
+                    .collect(),
+                precommits: voters
+                    .iter()
+                    .map(|kp| make_precommit(height, 0, kp, block_hash))
+                    .collect(),
+            }
+        })
+        .collect()
 }
 
 // ---------------------------------------------------------------------------
@@ -86,13 +181,12 @@ fn bench_propose_commit_cycle(c: &mut Criterion) {
 
                 b.iter(|| {
                     // We test one full cycle: engine start → proposal → prevotes → precommits
-                    let identity = keypairs[0].pubkey();
+                    let identity = keypairs[0].insecure_clone();
                     let mut engine = ConsensusEngine::new(config.clone(), identity, vs.clone());
                     let _output = engine.start_new_height(1);
 
                     // Determine the proposer for round 0
-                    let proposer = trv1_consensus_bft::proposer_for_round(&vs, 1, 0)
-                        .unwrap_or(keypairs[0].pubkey());
+                    let proposer = proposer_keypair(&vs, &keypairs, 1, 0);
 
                     // Deliver proposal
                     let (proposal_msg, block) = make_proposal(1, 0, proposer);
@@ -102,14 +196,14 @@ fn bench_propose_commit_cycle(c: &mut Criterion) {
                     // Deliver prevotes from 2/3+ validators
                     let quorum = (n * 2 / 3) + 1;
                     for i in 1..quorum {
-                        let voter = keypairs[i % n].pubkey();
+                        let voter = &keypairs[i % n];
                         let prevote = make_prevote(1, 0, voter, Some(block_hash));
                         let _out = engine.on_prevote(prevote);
                     }
 
                     // Deliver precommits from 2/3+ validators
                     for i in 1..quorum {
-                        let voter = keypairs[i % n].pubkey();
+                        let voter = &keypairs[i % n];
                         let precommit = make_precommit(1, 0, voter, Some(block_hash));
                         let _out = engine.on_precommit(precommit);
                     }
@@ -133,12 +227,11 @@ fn bench_proposal_processing(c: &mut Criterion) {
                 let config = BftConfig::default();
 
                 b.iter(|| {
-                    let identity = keypairs[0].pubkey();
+                    let identity = keypairs[0].insecure_clone();
                     let mut engine = ConsensusEngine::new(config.clone(), identity, vs.clone());
                     engine.start_new_height(1);
 
-                    let proposer = trv1_consensus_bft::proposer_for_round(&vs, 1, 0)
-                        .unwrap_or(keypairs[0].pubkey());
+                    let proposer = proposer_keypair(&vs, &keypairs, 1, 0);
                     let (proposal_msg, _block) = make_proposal(1, 0, proposer);
                     engine.on_proposal(proposal_msg)
                 });
@@ -165,17 +258,16 @@ fn bench_prevote_processing_throughput(c: &mut Criterion) {
                 let prevotes: Vec<ConsensusMessage> = keypairs
                     .iter()
                     .skip(1)
-                    .map(|kp| make_prevote(1, 0, kp.pubkey(), Some(block_hash)))
+                    .map(|kp| make_prevote(1, 0, kp, Some(block_hash)))
                     .collect();
 
                 b.iter(|| {
-                    let identity = keypairs[0].pubkey();
+                    let identity = keypairs[0].insecure_clone();
                     let mut engine = ConsensusEngine::new(config.clone(), identity, vs.clone());
                     engine.start_new_height(1);
 
                     // Deliver proposal first
-                    let proposer = trv1_consensus_bft::proposer_for_round(&vs, 1, 0)
-                        .unwrap_or(keypairs[0].pubkey());
+                    let proposer = proposer_keypair(&vs, &keypairs, 1, 0);
                     let (proposal_msg, _) = make_proposal(1, 0, proposer);
                     engine.on_proposal(proposal_msg);
 
@@ -206,24 +298,23 @@ fn bench_precommit_processing_throughput(c: &mut Criterion) {
                 let precommits: Vec<ConsensusMessage> = keypairs
                     .iter()
                     .skip(1)
-                    .map(|kp| make_precommit(1, 0, kp.pubkey(), Some(block_hash)))
+                    .map(|kp| make_precommit(1, 0, kp, Some(block_hash)))
                     .collect();
 
                 b.iter(|| {
-                    let identity = keypairs[0].pubkey();
+                    let identity = keypairs[0].insecure_clone();
                     let mut engine = ConsensusEngine::new(config.clone(), identity, vs.clone());
                     engine.start_new_height(1);
 
                     // Deliver a proposal so the engine is ready for votes
-                    let proposer = trv1_consensus_bft::proposer_for_round(&vs, 1, 0)
-                        .unwrap_or(keypairs[0].pubkey());
+                    let proposer = proposer_keypair(&vs, &keypairs, 1, 0);
                     let (proposal_msg, _) = make_proposal(1, 0, proposer);
                     engine.on_proposal(proposal_msg);
 
                     // Deliver prevotes to reach precommit stage
                     let quorum = (n * 2 / 3) + 1;
                     for i in 1..quorum {
-                        let voter = keypairs[i % n].pubkey();
+                        let voter = &keypairs[i % n];
                         engine.on_prevote(make_prevote(1, 0, voter, Some(block_hash)));
                     }
 
@@ -272,12 +363,11 @@ fn bench_round_trip_with_latency(c: &mut Criterion) {
                 let config = BftConfig::default();
 
                 b.iter(|| {
-                    let identity = keypairs[0].pubkey();
+                    let identity = keypairs[0].insecure_clone();
                     let mut engine = ConsensusEngine::new(config.clone(), identity, vs.clone());
                     engine.start_new_height(1);
 
-                    let proposer = trv1_consensus_bft::proposer_for_round(&vs, 1, 0)
-                        .unwrap_or(keypairs[0].pubkey());
+                    let proposer = proposer_keypair(&vs, &keypairs, 1, 0);
                     let (proposal_msg, block) = make_proposal(1, 0, proposer);
                     let block_hash = block.hash();
 
@@ -296,7 +386,7 @@ fn bench_round_trip_with_latency(c: &mut Criterion) {
                     for batch_start in (1..quorum).step_by(batch_size.max(1)) {
                         let batch_end = (batch_start + batch_size).min(quorum);
                         for i in batch_start..batch_end {
-                            let voter = keypairs[i % n].pubkey();
+                            let voter = &keypairs[i % n];
                             engine.on_prevote(make_prevote(1, 0, voter, Some(block_hash)));
                         }
                         std::hint::black_box(0u64); // simulate inter-batch gap
@@ -306,7 +396,7 @@ fn bench_round_trip_with_latency(c: &mut Criterion) {
                     for batch_start in (1..quorum).step_by(batch_size.max(1)) {
                         let batch_end = (batch_start + batch_size).min(quorum);
                         for i in batch_start..batch_end {
-                            let voter = keypairs[i % n].pubkey();
+                            let voter = &keypairs[i % n];
                             engine.on_precommit(make_precommit(1, 0, voter, Some(block_hash)));
                         }
                         std::hint::black_box(0u64);
@@ -318,6 +408,127 @@ fn bench_round_trip_with_latency(c: &mut Criterion) {
     group.finish();
 }
 
+/// Per-message vote processing over [`LARGE_NETWORK_HEIGHTS`] pre-signed
+/// heights, cycling through them as Criterion asks for more iterations.
+///
+/// One iteration is one height; only the `on_prevote` (or `on_precommit`)
+/// calls are timed, and the throughput is the number of votes per height, so
+/// Criterion reports messages/second and the per-message time is the
+/// iteration time divided by [`LARGE_NETWORK_VOTES`].
+fn bench_large_network_votes(
+    c: &mut Criterion,
+    group_name: &str,
+    vs: ValidatorSet,
+    keypairs: Vec<Keypair>,
+) {
+    let heights = make_signed_heights(&vs, &keypairs, LARGE_NETWORK_VOTES, LARGE_NETWORK_HEIGHTS);
+    let config = BftConfig::default();
+    let new_engine =
+        || ConsensusEngine::new(config.clone(), keypairs[0].insecure_clone(), vs.clone());
+
+    let mut group = c.benchmark_group(group_name);
+    group.throughput(Throughput::Elements(LARGE_NETWORK_VOTES as u64));
+
+    group.bench_function("on_prevote", |b| {
+        let mut engine = new_engine();
+        let mut next = heights.iter().cycle();
+        b.iter_custom(|iters| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iters {
+                let signed = next.next().unwrap();
+                engine.start_new_height(signed.height);
+                engine.on_proposal(signed.proposal.clone());
+                let prevotes = signed.prevotes.clone();
+
+                let start = Instant::now();
+                for prevote in prevotes {
+                    black_box(engine.on_prevote(prevote));
+                }
+                elapsed += start.elapsed();
+            }
+            elapsed
+        });
+    });
+
+    group.bench_function("on_precommit", |b| {
+        let mut engine = new_engine();
+        let mut next = heights.iter().cycle();
+        b.iter_custom(|iters| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iters {
+                let signed = next.next().unwrap();
+                engine.start_new_height(signed.height);
+                engine.on_proposal(signed.proposal.clone());
+                for prevote in &signed.prevotes {
+                    engine.on_prevote(prevote.clone());
+                }
+                let precommits = signed.precommits.clone();
+
+                let start = Instant::now();
+                for precommit in precommits {
+                    black_box(engine.on_precommit(precommit));
+                }
+                elapsed += start.elapsed();
+                assert_eq!(engine.step(), ConsensusStep::Commit);
+            }
+            elapsed
+        });
+    });
+
+    // The quorum scan that every recorded prevote triggers, over a full set
+    // of prevotes for one height.
+    let signed = &heights[0];
+    let mut engine = new_engine();
+    engine.start_new_height(signed.height);
+    engine.on_proposal(signed.proposal.clone());
+    for prevote in &signed.prevotes {
+        engine.on_prevote(prevote.clone());
+    }
+    assert!(engine.find_quorum_prevote_hash().is_some());
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("quorum_scan", |b| {
+        b.iter(|| black_box(engine.find_quorum_prevote_hash()));
+    });
+
+    // The vote-map inserts `on_prevote` performs once a vote is accepted.
+    let votes: Vec<(Pubkey, Option<Hash>, Signature)> = signed
+        .prevotes
+        .iter()
+        .map(|prevote| match prevote {
+            ConsensusMessage::Prevote {
+                voter,
+                block_hash,
+                signature,
+                ..
+            } => (*voter, *block_hash, *signature),
+            _ => unreachable!(),
+        })
+        .collect();
+    group.throughput(Throughput::Elements(LARGE_NETWORK_VOTES as u64));
+    group.bench_function("vote_insert", |b| {
+        b.iter(|| {
+            let mut state = ConsensusState::new(signed.height);
+            for (voter, block_hash, signature) in &votes {
+                state.prevotes.insert(*voter, *block_hash);
+                state.prevote_signatures.insert(*voter, *signature);
+            }
+            state
+        });
+    });
+
+    group.finish();
+}
+
+fn bench_consensus_200v(c: &mut Criterion) {
+    let (vs, keypairs) = make_validator_set(LARGE_NETWORK_VALIDATORS);
+    bench_large_network_votes(c, "consensus_200v", vs, keypairs);
+}
+
+fn bench_consensus_unequal_stake(c: &mut Criterion) {
+    let (vs, keypairs) = make_zipf_validator_set(LARGE_NETWORK_VALIDATORS);
+    bench_large_network_votes(c, "consensus_unequal_stake", vs, keypairs);
+}
+
 criterion_group!(
     benches,
     bench_propose_commit_cycle,
@@ -326,5 +537,7 @@ criterion_group!(
     bench_precommit_processing_throughput,
     bench_validator_set_creation,
     bench_round_trip_with_latency,
+    bench_consensus_200v,
+    bench_consensus_unequal_stake,
 );
 criterion_main!(benches);
@@ -2,14 +2,26 @@
 //!
 //! Measures:
 //! - Base fee calculation throughput
+//! - Transaction fee calculation (compute-only and multi-resource)
 //! - Fee validation throughput
 //! - Multi-block fee adjustment simulation
+//! - Bulk throughput: 100k base fee updates, 50k transaction fees, and the
+//!   per-transaction fee overhead extrapolated to a maximum TPS
+//!
+//! The per-transaction budget is 500ns, which would allow 2M TPS if fee
+//! calculation were the only bottleneck.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use trv1_fee_market::{
     calculator,
     FeeMarketConfig,
     BlockFeeState,
+    TransactionResources,
+    UTILIZATION_SCALE,
 };
 
 // ---------------------------------------------------------------------------
@@ -28,6 +40,10 @@ fn bench_base_fee_calculation(c: &mut Criterion) {
         parent_gas_used: target,
         current_gas_used: 0,
         height: 1,
+        current_accounts_written: 0,
+        paused: false,
+        pause_fee: 0,
+        ema_utilization: UTILIZATION_SCALE,
     };
 
     // Scenario 2: block above target (congested)
@@ -36,6 +52,10 @@ fn bench_base_fee_calculation(c: &mut Criterion) {
         parent_gas_used: target.saturating_mul(3) / 2, // 150% utilization
         current_gas_used: 0,
         height: 1,
+        current_accounts_written: 0,
+        paused: false,
+        pause_fee: 0,
+        ema_utilization: UTILIZATION_SCALE,
     };
 
     // Scenario 3: block below target (underutilized)
@@ -44,6 +64,10 @@ fn bench_base_fee_calculation(c: &mut Criterion) {
         parent_gas_used: target / 4, // 25% utilization
         current_gas_used: 0,
         height: 1,
+        current_accounts_written: 0,
+        paused: false,
+        pause_fee: 0,
+        ema_utilization: UTILIZATION_SCALE,
     };
 
     group.throughput(Throughput::Elements(1));
@@ -68,28 +92,75 @@ fn bench_base_fee_calculation(c: &mut Criterion) {
 
 fn bench_transaction_fee_calculation(c: &mut Criterion) {
     let mut group = c.benchmark_group("fee_market/tx_fee_calc");
+    let config = FeeMarketConfig::default();
     group.throughput(Throughput::Elements(1));
 
     group.bench_function("simple", |b| {
+        let resources = TransactionResources::compute_only(200_000);
         b.iter(|| {
             calculator::calculate_transaction_fee(
+                &config,
                 5_000,    // base_fee_per_cu
                 100,      // priority_fee_per_cu
-                200_000,  // compute_units
+                &resources,
             )
         })
     });
 
     group.bench_function("high_cu", |b| {
+        let resources = TransactionResources::compute_only(1_400_000); // max CU per tx
         b.iter(|| {
             calculator::calculate_transaction_fee(
+                &config,
                 50_000,      // base_fee_per_cu
                 10_000,      // priority_fee_per_cu
-                1_400_000,   // max CU per tx
+                &resources,
+            )
+        })
+    });
+
+    // Compute plus storage writes: a typical DEX swap touching many accounts.
+    group.bench_function("multi_resource", |b| {
+        let resources = TransactionResources {
+            compute_units: 400_000,
+            accounts_read: 24,
+            accounts_written: 8,
+        };
+        b.iter(|| {
+            calculator::calculate_transaction_fee(
+                &config,
+                5_000,      // base_fee_per_cu
+                100,        // priority_fee_per_cu
+                &resources,
             )
         })
     });
 
+    // Pricing and recording a full block of multi-resource transactions.
+    group.bench_function("multi_resource_block", |b| {
+        let resources = TransactionResources {
+            compute_units: 200_000,
+            accounts_read: 16,
+            accounts_written: 4,
+        };
+        let txs_per_block = config.max_block_compute_units / resources.compute_units;
+        b.iter(|| {
+            let mut state = BlockFeeState::genesis(config.min_base_fee);
+            let mut total_fees = 0u64;
+            for _ in 0..txs_per_block {
+                let fee = calculator::calculate_transaction_fee(
+                    &config,
+                    state.base_fee_per_cu,
+                    100,
+                    &resources,
+                );
+                total_fees = total_fees.saturating_add(fee.total_fee);
+                state.record_gas(resources.compute_units, resources.accounts_written as u64);
+            }
+            (state, total_fees)
+        })
+    });
+
     group.finish();
 }
 
@@ -109,7 +180,7 @@ fn bench_fee_validation(c: &mut Criterion) {
                 10_000_000_000, // offered_lamports (10 SOL)
                 100,            // priority_fee_per_cu
                 5_000,          // base_fee_per_cu
-                200_000,        // requested_cu
+                &TransactionResources::compute_only(200_000),
                 &config,
             )
         })
@@ -119,10 +190,10 @@ fn bench_fee_validation(c: &mut Criterion) {
     group.bench_function("insufficient_fee", |b| {
         b.iter(|| {
             let _ = calculator::validate_transaction_fee(
-                1,          // too little
+                1, // too little
                 100,
                 5_000,
-                200_000,
+                &TransactionResources::compute_only(200_000),
                 &config,
             );
         })
@@ -160,7 +231,7 @@ fn bench_multi_block_fee_adjustment(c: &mut Criterion) {
                         state.current_gas_used = gas_used;
 
                         let next_base_fee = calculator::calculate_next_base_fee(&config, &state);
-                        state = state.next_block(next_base_fee, height);
+                        state = state.next_block(&config, next_base_fee, height);
                     }
 
                     state
@@ -190,7 +261,7 @@ fn bench_sustained_congestion(c: &mut Criterion) {
                     for height in 1..=n {
                         state.current_gas_used = config.max_block_compute_units;
                         let next_base_fee = calculator::calculate_next_base_fee(&config, &state);
-                        state = state.next_block(next_base_fee, height);
+                        state = state.next_block(&config, next_base_fee, height);
                     }
 
                     state
@@ -201,6 +272,133 @@ fn bench_sustained_congestion(c: &mut Criterion) {
     group.finish();
 }
 
+// ---------------------------------------------------------------------------
+// Bulk throughput
+// ---------------------------------------------------------------------------
+
+/// Base fee updates per `base_fee_update_100k` iteration.
+const BASE_FEE_UPDATES: u64 = 100_000;
+/// Transactions priced per `transaction_fee_50k` / `estimate_max_tps` iteration.
+const FEE_TRANSACTIONS: usize = 50_000;
+/// Seed for the transaction mix, so every run prices the same transactions.
+const FEE_TRANSACTIONS_SEED: u64 = 50_000;
+
+/// Deterministic mix of transactions with 1k to 1.4M compute units and up to
+/// 8 written accounts.
+fn fee_transactions() -> Vec<TransactionResources> {
+    let mut rng = StdRng::seed_from_u64(FEE_TRANSACTIONS_SEED);
+    (0..FEE_TRANSACTIONS)
+        .map(|_| TransactionResources {
+            compute_units: rng.random_range(1_000..=1_400_000),
+            accounts_read: rng.random_range(0..=16),
+            accounts_written: rng.random_range(0..=8),
+        })
+        .collect()
+}
+
+fn bench_base_fee_update_100k(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fee_market/base_fee_update_100k");
+    let config = FeeMarketConfig::default();
+    let target = config.target_gas();
+    group.throughput(Throughput::Elements(BASE_FEE_UPDATES));
+
+    // Parent usage sweeps from empty to twice the target.
+    group.bench_function("sweep_0_to_2x_target", |b| {
+        b.iter(|| {
+            for i in 0..BASE_FEE_UPDATES {
+                let state = BlockFeeState {
+                    base_fee_per_cu: 100_000,
+                    parent_gas_used: target.saturating_mul(2).saturating_mul(i) / BASE_FEE_UPDATES,
+                    current_gas_used: 0,
+                    height: i,
+                    current_accounts_written: 0,
+                    paused: false,
+                    pause_fee: 0,
+                    ema_utilization: UTILIZATION_SCALE,
+                };
+                black_box(calculator::calculate_next_base_fee(
+                    &config,
+                    black_box(&state),
+                ));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_transaction_fee_50k(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fee_market/transaction_fee_50k");
+    let config = FeeMarketConfig::default();
+    let transactions = fee_transactions();
+    group.throughput(Throughput::Elements(FEE_TRANSACTIONS as u64));
+
+    group.bench_function("random_cu", |b| {
+        b.iter(|| {
+            for resources in &transactions {
+                black_box(calculator::calculate_transaction_fee(
+                    &config,
+                    black_box(5_000), // base_fee_per_cu
+                    black_box(100),   // priority_fee_per_cu
+                    black_box(resources),
+                ));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+/// Fee work done for every transaction landing in a block: validate the
+/// offered fee, price it, and record its usage. Criterion's throughput is the
+/// extrapolated TPS; the mean over the whole run is printed as well.
+fn bench_estimate_max_tps(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fee_market/estimate_max_tps");
+    let config = FeeMarketConfig::default();
+    let transactions = fee_transactions();
+    group.throughput(Throughput::Elements(FEE_TRANSACTIONS as u64));
+
+    let mut total_elapsed = Duration::ZERO;
+    let mut total_transactions = 0u64;
+    group.bench_function("per_tx_overhead", |b| {
+        b.iter_custom(|iters| {
+            let start = Instant::now();
+            for _ in 0..iters {
+                let mut state = BlockFeeState::genesis(config.min_base_fee);
+                for resources in &transactions {
+                    let fee = calculator::calculate_transaction_fee(
+                        &config,
+                        state.base_fee_per_cu,
+                        100,
+                        black_box(resources),
+                    );
+                    let _ = black_box(calculator::validate_transaction_fee(
+                        fee.total_fee,
+                        100,
+                        state.base_fee_per_cu,
+                        resources,
+                        &config,
+                    ));
+                    state.record_gas(resources.compute_units, resources.accounts_written as u64);
+                }
+                black_box(state);
+            }
+            let elapsed = start.elapsed();
+            total_elapsed += elapsed;
+            total_transactions += iters * FEE_TRANSACTIONS as u64;
+            elapsed
+        })
+    });
+    group.finish();
+
+    let ns_per_tx = total_elapsed.as_nanos() as f64 / total_transactions.max(1) as f64;
+    println!(
+        "fee_market/estimate_max_tps: {ns_per_tx:.1} ns per transaction, \
+         theoretical maximum {:.0} TPS",
+        1e9 / ns_per_tx
+    );
+}
+
 criterion_group!(
     benches,
     bench_base_fee_calculation,
@@ -208,5 +406,8 @@ criterion_group!(
     bench_fee_validation,
     bench_multi_block_fee_adjustment,
     bench_sustained_congestion,
+    bench_base_fee_update_100k,
+    bench_transaction_fee_50k,
+    bench_estimate_max_tps,
 );
 criterion_main!(benches);
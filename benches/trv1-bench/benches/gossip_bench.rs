@@ -0,0 +1,130 @@
+//! Consensus-net gossip benchmarks.
+//!
+//! Measures:
+//! - Time to deliver a block to every validator under each dissemination
+//!   strategy (direct broadcast, epidemic gossip, tree gossip)
+//! - Validator set sizes: 10, 50, 200
+//!
+//! Delivery is simulated hop by hop in-process: every hop, each informed
+//! node forwards the block to the recipients its strategy selects.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use solana_pubkey::Pubkey;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::SocketAddr;
+use trv1_consensus_net::gossip::{
+    DirectBroadcast, EpidemicGossip, GossipLayer, GossipTree, SeenMessages, TreeGossip,
+};
+use trv1_consensus_net::message::ValidatorInfo;
+
+/// Safety bound on simulated hops for probabilistic strategies.
+const MAX_HOPS: usize = 64;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn make_validators(n: usize) -> Vec<ValidatorInfo> {
+    (0..n)
+        .map(|i| ValidatorInfo {
+            pubkey: Pubkey::new_unique(),
+            stake_weight: 1_000_000 + i as u64,
+            consensus_addr: SocketAddr::from(([10, 0, (i / 256) as u8, (i % 256) as u8], 8900)),
+        })
+        .collect()
+}
+
+/// Gossip from `validators[0]` until everyone is informed.
+///
+/// Every informed node re-gossips each hop, which is what epidemic gossip
+/// needs to converge; direct broadcast finishes after the first hop.
+/// Returns the number of hops taken.
+fn deliver_flood(layer: &dyn GossipLayer, validators: &[ValidatorInfo], block: &[u8]) -> usize {
+    let origin = validators[0].pubkey;
+    let mut informed: HashSet<Pubkey> = HashSet::from([origin]);
+    let mut hops = 0;
+    while informed.len() < validators.len() && hops < MAX_HOPS {
+        let senders: Vec<Pubkey> = informed.iter().copied().collect();
+        for sender in senders {
+            for target in layer.relay_targets(block, &origin, &sender, validators) {
+                informed.insert(target.pubkey);
+            }
+        }
+        hops += 1;
+    }
+    hops
+}
+
+/// Relay from `validators[0]` along the gossip tree.  Each node computes its
+/// own children and forwards the first copy it receives.
+/// Returns the number of hops taken.
+fn deliver_tree(gossip: &TreeGossip, validators: &[ValidatorInfo], block: &[u8]) -> usize {
+    let origin = validators[0].pubkey;
+    let mut seen: HashMap<Pubkey, SeenMessages> = HashMap::new();
+    let mut frontier: VecDeque<Pubkey> = gossip
+        .disseminate(block, &origin, validators)
+        .into_iter()
+        .map(|v| v.pubkey)
+        .collect();
+    let mut hops = 0;
+    while !frontier.is_empty() {
+        hops += 1;
+        frontier = frontier
+            .into_iter()
+            .filter(|me| {
+                seen.entry(*me)
+                    .or_insert_with(|| SeenMessages::new(1))
+                    .insert(&origin, block)
+            })
+            .flat_map(|me| gossip.tree.children(&origin, &me, validators))
+            .map(|v| v.pubkey)
+            .collect();
+    }
+    hops
+}
+
+// ---------------------------------------------------------------------------
+// Benchmarks
+// ---------------------------------------------------------------------------
+
+fn bench_block_delivery(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gossip/block_delivery");
+    let block = vec![0u8; 64 * 1024];
+
+    for &n_validators in &[10usize, 50, 200] {
+        let validators = make_validators(n_validators);
+        group.throughput(Throughput::Elements(n_validators as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("direct", n_validators),
+            &validators,
+            |b, validators| {
+                b.iter(|| deliver_flood(&DirectBroadcast, validators, &block));
+            },
+        );
+
+        let epidemic = EpidemicGossip { fanout: 3 };
+        group.bench_with_input(
+            BenchmarkId::new("epidemic_fanout_3", n_validators),
+            &validators,
+            |b, validators| {
+                b.iter(|| deliver_flood(&epidemic, validators, &block));
+            },
+        );
+
+        let tree = TreeGossip {
+            tree: GossipTree::new(3),
+        };
+        group.bench_with_input(
+            BenchmarkId::new("tree_fanout_3", n_validators),
+            &validators,
+            |b, validators| {
+                b.iter(|| deliver_tree(&tree, validators, &block));
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_block_delivery);
+criterion_main!(benches);
@@ -14,6 +14,8 @@
 //! cargo bench -p trv1-bench --bench cache_bench
 //! cargo bench -p trv1-bench --bench staking_bench
 //! cargo bench -p trv1-bench --bench rent_bench
+//! cargo bench -p trv1-bench --bench gossip_bench
+//! cargo bench -p trv1-bench --bench compression_bench
 //! ```
 
 pub mod helpers;
@@ -0,0 +1,39 @@
+#!/usr/bin/env python3
+#
+# Compares the Criterion results in target/criterion against a trv1-bench
+# baseline file and exits non-zero if any benchmark regressed by more than the
+# file's max_regression.
+#
+# usage: check-trv1-baseline.py <baseline.toml> [criterion dir]
+#
+
+import json
+import os
+import sys
+import tomllib
+
+if len(sys.argv) not in (2, 3):
+    print(f'usage: {sys.argv[0]} <baseline.toml> [criterion dir]', file=sys.stderr)
+    sys.exit(2)
+
+with open(sys.argv[1], 'rb') as f:
+    baseline = tomllib.load(f)
+criterion_dir = sys.argv[2] if len(sys.argv) == 3 else 'target/criterion'
+max_regression = baseline['max_regression']
+
+failed = False
+for name, expected in baseline['benchmarks'].items():
+    estimates = os.path.join(criterion_dir, name, 'new', 'estimates.json')
+    if not os.path.exists(estimates):
+        print(f'{name}: no results at {estimates}')
+        failed = True
+        continue
+    with open(estimates) as f:
+        mean_ns = json.load(f)['mean']['point_estimate']
+    change = mean_ns / expected['mean_ns'] - 1
+    status = 'REGRESSED' if change > max_regression else 'ok'
+    print(f'{name}: {mean_ns:.0f} ns vs baseline {expected["mean_ns"]} ns ({change:+.1%}) {status}')
+    if change > max_regression:
+        failed = True
+
+sys.exit(1 if failed else 0)
@@ -13,6 +13,9 @@ _ cargo "+${rust_nightly}" miri test --features agave-unstable-api --target s390
 # test little endian branch for UB
 _ cargo "+${rust_nightly}" miri test --features agave-unstable-api -p solana-vote -- "vote_state_view" --skip "arbitrary"
 
+# check the sharded account cache for data races under concurrent access
+_ cargo "+${rust_nightly}" miri test --features agave-unstable-api -p solana-accounts-db --lib -- "account_cache::tests::test_sharded"
+
 # run intentionally-#[ignored] ub triggering tests for each to make sure they fail
 (! _ cargo "+${rust_nightly}" miri test --features agave-unstable-api -p solana-unified-scheduler-logic -- \
   --ignored --exact "utils::tests::test_ub_illegally_created_multiple_tokens")
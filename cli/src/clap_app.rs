@@ -1,9 +1,10 @@
 use {
     crate::{
         address_lookup_table::AddressLookupTableSubCommands, cli::*, cluster_query::*,
-        developer_rewards::*, feature::*, governance::*, inflation::*, network_info::*, nonce::*,
-        passive_stake::*, program::*, program_v4::ProgramV4SubCommands, stake::*, treasury::*,
-        trv1_validators::*, validator_info::*, vote::*, wallet::*,
+        compute_budget::FeeSubCommands, developer_rewards::*, feature::*, governance::*,
+        inflation::*, network_info::*, nonce::*, passive_stake::*, program::*,
+        program_v4::ProgramV4SubCommands, stake::*, treasury::*, trv1_validators::*,
+        validator_info::*, vote::*, wallet::*,
     },
     clap::{App, AppSettings, Arg, ArgGroup, SubCommand},
     solana_clap_utils::{self, hidden_unless_forced, input_validators::*, keypair::*},
@@ -161,6 +162,7 @@ pub fn get_clap_app<'ab, 'v>(name: &str, about: &'ab str, version: &'v str) -> A
         .dev_rewards_subcommands()
         .trv1_validators_subcommands()
         .network_info_subcommands()
+        .fee_subcommands()
         .subcommand(
             SubCommand::with_name("config")
                 .about("Solana command-line tool configuration settings")
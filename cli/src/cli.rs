@@ -1,9 +1,25 @@
 use {
     crate::{
-        address_lookup_table::*, clap_app::*, cluster_query::*, developer_rewards::*, feature::*,
-        governance::*, inflation::*, network_info::*, nonce::*, passive_stake::*, program::*,
-        program_v4::*, spend_utils::*, stake::*, treasury::*, trv1_validators::*,
-        validator_info::*, vote::*, wallet::*,
+        address_lookup_table::*,
+        clap_app::*,
+        cluster_query::*,
+        compute_budget::{parse_fee_command, process_fee_command, FeeCliCommand},
+        developer_rewards::*,
+        feature::*,
+        governance::*,
+        inflation::*,
+        network_info::*,
+        nonce::*,
+        passive_stake::*,
+        program::*,
+        program_v4::*,
+        spend_utils::*,
+        stake::*,
+        treasury::*,
+        trv1_validators::*,
+        validator_info::*,
+        vote::*,
+        wallet::*,
     },
     clap::{crate_description, crate_name, value_t_or_exit, ArgMatches, Shell},
     num_traits::FromPrimitive,
@@ -478,6 +494,7 @@ pub enum CliCommand {
     DevRewards(DevRewardsCliCommand),
     Trv1Validators(Trv1ValidatorsCliCommand),
     NetworkInfo(NetworkInfoCliCommand),
+    Fee(FeeCliCommand),
 }
 
 #[derive(Debug, PartialEq)]
@@ -864,12 +881,13 @@ pub fn parse_command(
         ("dev-rewards", Some(matches)) => {
             parse_dev_rewards_command(matches, default_signer, wallet_manager)
         }
-        ("trv1-validators", Some(matches)) => {
+        ("trv1-validators", Some(matches)) | ("validator", Some(matches)) => {
             parse_trv1_validators_command(matches, default_signer, wallet_manager)
         }
         ("network", Some(matches)) => {
             parse_network_info_command(matches, default_signer, wallet_manager)
         }
+        ("fee", Some(matches)) => parse_fee_command(matches),
         //
         ("", None) => {
             eprintln!("{}", matches.usage());
@@ -1914,6 +1932,7 @@ pub async fn process_command(config: &CliConfig<'_>) -> ProcessResult {
         CliCommand::NetworkInfo(subcommand) => {
             process_network_info_command(&rpc_client, config, subcommand).await
         }
+        CliCommand::Fee(subcommand) => process_fee_command(&rpc_client, config, subcommand).await,
     }
 }
 
@@ -1,13 +1,29 @@
 use {
+    crate::cli::{CliCommand, CliCommandInfo, CliConfig, CliError, ProcessResult},
+    clap::{App, AppSettings, Arg, ArgMatches, SubCommand},
+    serde::{Deserialize, Serialize},
     solana_borsh::v1::try_from_slice_unchecked,
-    solana_clap_utils::compute_budget::ComputeUnitLimit,
+    solana_clap_utils::{
+        compute_budget::ComputeUnitLimit,
+        input_parsers::value_of,
+        input_validators::{is_parsable, is_valid_percentage},
+    },
+    solana_cli_output::OutputFormat,
     solana_compute_budget_interface::{self as compute_budget, ComputeBudgetInstruction},
     solana_instruction::Instruction,
     solana_message::Message,
+    solana_native_token::LAMPORTS_PER_SOL,
     solana_program_runtime::execution_budget::MAX_COMPUTE_UNIT_LIMIT,
+    solana_pubkey::Pubkey,
     solana_rpc_client::nonblocking::rpc_client::RpcClient,
-    solana_rpc_client_api::config::RpcSimulateTransactionConfig,
+    solana_rpc_client_api::{config::RpcSimulateTransactionConfig, response::RpcPrioritizationFee},
     solana_transaction::Transaction,
+    std::fmt,
+    trv1_fee_market::{
+        calculator::{calculate_next_base_fee, calculate_transaction_fee},
+        BlockFeeState, FeeHistory, FeeHistoryEntry, FeeMarketConfig, TransactionFee,
+        TransactionResources, UTILIZATION_SCALE,
+    },
 };
 
 /// Enum capturing the possible results of updating a message based on the
@@ -160,3 +176,424 @@ impl WithComputeUnitConfig for Vec<Instruction> {
         self
     }
 }
+
+// ── Fee estimation (`trv1 fee`) ─────────────────────────────────────
+
+/// Number of recent slots the validator reports prioritization fees for.
+const FEE_HISTORY_SLOTS: usize = 150;
+
+#[derive(Debug, PartialEq)]
+pub enum FeeCliCommand {
+    Estimate {
+        percentile: u8,
+    },
+    EstimateTotal {
+        resources: TransactionResources,
+        priority_fee_per_cu: u64,
+        blocks_ahead: usize,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CliPriorityFeeSuggestion {
+    pub percentile: u8,
+    /// Suggested compute unit price, in micro-lamports per CU.
+    pub suggested_priority_fee: u64,
+    pub slots_sampled: usize,
+}
+
+impl fmt::Display for CliPriorityFeeSuggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Priority Fee Estimate")?;
+        writeln!(f, "  Target Percentile:  p{}", self.percentile)?;
+        writeln!(
+            f,
+            "  Suggested Fee:      {} micro-lamports/CU",
+            self.suggested_priority_fee
+        )?;
+        writeln!(f, "  Slots Sampled:      {}", self.slots_sampled)?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct CliFeeEstimate {
+    pub base_fee: u64,
+    pub priority_fee: u64,
+    pub storage_fee: u64,
+    pub total_fee: u64,
+    pub total_sol: f64,
+}
+
+impl From<TransactionFee> for CliFeeEstimate {
+    fn from(fee: TransactionFee) -> Self {
+        Self {
+            base_fee: fee.base_fee,
+            priority_fee: fee.priority_fee(),
+            storage_fee: fee.storage_fee,
+            total_fee: fee.total_fee,
+            total_sol: fee.total_fee as f64 / LAMPORTS_PER_SOL as f64,
+        }
+    }
+}
+
+impl fmt::Display for CliFeeEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Transaction Fee Estimate")?;
+        writeln!(f, "  {:<14} {:>20}", "Component", "Lamports")?;
+        writeln!(f, "  {}", "-".repeat(35))?;
+        for (label, value) in [
+            ("Base", self.base_fee),
+            ("Priority", self.priority_fee),
+            ("Storage", self.storage_fee),
+        ] {
+            writeln!(f, "  {:<14} {:>20}", label, value)?;
+        }
+        writeln!(f, "  {}", "-".repeat(35))?;
+        writeln!(f, "  {:<14} {:>20}", "Total", self.total_fee)?;
+        writeln!(f, "  {:<14} {:>20}", "Total (SOL)", self.total_sol)?;
+        Ok(())
+    }
+}
+
+pub trait FeeSubCommands {
+    fn fee_subcommands(self) -> Self;
+}
+
+impl FeeSubCommands for App<'_, '_> {
+    fn fee_subcommands(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name("fee")
+                .about("TRv1 fee market commands")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("estimate")
+                        .about("Suggest a priority fee from recent fee history")
+                        .arg(
+                            Arg::with_name("percentile")
+                                .long("percentile")
+                                .value_name("PERCENTILE")
+                                .takes_value(true)
+                                .default_value("50")
+                                .validator(is_valid_percentage)
+                                .help("Percentile of recent priority fees to match, 0-100"),
+                        )
+                        .arg(
+                            Arg::with_name("compute_units")
+                                .long("compute-units")
+                                .value_name("N")
+                                .takes_value(true)
+                                .validator(is_parsable::<u64>)
+                                .help(
+                                    "Estimate the total fee for a transaction consuming this \
+                                     many compute units instead of suggesting a priority fee",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("priority_fee")
+                                .long("priority-fee")
+                                .value_name("LAMPORTS_PER_CU")
+                                .takes_value(true)
+                                .requires("compute_units")
+                                .validator(is_parsable::<u64>)
+                                .help("Priority fee in lamports per compute unit [default: 0]"),
+                        )
+                        .arg(
+                            Arg::with_name("accounts_written")
+                                .long("accounts-written")
+                                .value_name("M")
+                                .takes_value(true)
+                                .requires("compute_units")
+                                .validator(is_parsable::<u32>)
+                                .help(
+                                    "Number of writable accounts the transaction locks \
+                                     [default: 0]",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("blocks_ahead")
+                                .long("blocks-ahead")
+                                .value_name("N")
+                                .takes_value(true)
+                                .requires("compute_units")
+                                .validator(is_parsable::<usize>)
+                                .help(
+                                    "Price the transaction N blocks from now, assuming every \
+                                     block in between is full [default: 0]",
+                                ),
+                        ),
+                ),
+        )
+    }
+}
+
+pub fn parse_fee_command(matches: &ArgMatches<'_>) -> Result<CliCommandInfo, CliError> {
+    match matches.subcommand() {
+        ("estimate", Some(matches)) => {
+            let command = match value_of(matches, "compute_units") {
+                Some(compute_units) => FeeCliCommand::EstimateTotal {
+                    resources: TransactionResources {
+                        compute_units,
+                        accounts_read: 0,
+                        accounts_written: value_of(matches, "accounts_written").unwrap_or(0),
+                    },
+                    priority_fee_per_cu: value_of(matches, "priority_fee").unwrap_or(0),
+                    blocks_ahead: value_of(matches, "blocks_ahead").unwrap_or(0),
+                },
+                None => FeeCliCommand::Estimate {
+                    percentile: value_of(matches, "percentile").unwrap(),
+                },
+            };
+            Ok(CliCommandInfo::without_signers(CliCommand::Fee(command)))
+        }
+        _ => unreachable!(),
+    }
+}
+
+pub async fn process_fee_command(
+    rpc_client: &RpcClient,
+    config: &CliConfig<'_>,
+    command: &FeeCliCommand,
+) -> ProcessResult {
+    match command {
+        FeeCliCommand::Estimate { percentile } => {
+            process_suggest_priority_fee(rpc_client, config, *percentile).await
+        }
+        FeeCliCommand::EstimateTotal {
+            resources,
+            priority_fee_per_cu,
+            blocks_ahead,
+        } => {
+            process_estimate_fee(
+                rpc_client,
+                config,
+                resources,
+                *priority_fee_per_cu,
+                *blocks_ahead,
+            )
+            .await
+        }
+    }
+}
+
+/// Current base fee per compute unit, as charged by the validator.
+///
+/// A single-signature message with no instructions pays exactly the base
+/// fee, so its quoted fee is the current base fee.
+async fn get_current_base_fee(rpc_client: &RpcClient) -> Result<u64, Box<dyn std::error::Error>> {
+    let blockhash = rpc_client.get_latest_blockhash().await?;
+    let message = Message::new_with_blockhash(&[], Some(&Pubkey::default()), &blockhash);
+    Ok(rpc_client.get_fee_for_message(&message).await?)
+}
+
+/// Base fee `blocks_ahead` blocks after one priced at `base_fee_per_cu`,
+/// assuming every block in between, and the utilization average before
+/// them, is full.  This is the highest base fee the transaction can face, so
+/// the estimate is an upper bound.
+fn project_base_fee(config: &FeeMarketConfig, base_fee_per_cu: u64, blocks_ahead: usize) -> u64 {
+    let full_utilization = (config.max_block_compute_units as u128 * UTILIZATION_SCALE as u128
+        / config.target_gas().max(1) as u128)
+        .min(u64::MAX as u128) as u64;
+    (0..blocks_ahead).fold(base_fee_per_cu, |base_fee, _| {
+        calculate_next_base_fee(
+            config,
+            &BlockFeeState {
+                base_fee_per_cu: base_fee,
+                parent_gas_used: config.max_block_compute_units,
+                ema_utilization: full_utilization,
+                ..BlockFeeState::default()
+            },
+        )
+    })
+}
+
+fn estimate_fee(
+    config: &FeeMarketConfig,
+    base_fee_per_cu: u64,
+    priority_fee_per_cu: u64,
+    resources: &TransactionResources,
+    blocks_ahead: usize,
+) -> CliFeeEstimate {
+    let base_fee_per_cu = project_base_fee(config, base_fee_per_cu, blocks_ahead);
+    calculate_transaction_fee(config, base_fee_per_cu, priority_fee_per_cu, resources).into()
+}
+
+async fn process_estimate_fee(
+    rpc_client: &RpcClient,
+    config: &CliConfig<'_>,
+    resources: &TransactionResources,
+    priority_fee_per_cu: u64,
+    blocks_ahead: usize,
+) -> ProcessResult {
+    let base_fee_per_cu = get_current_base_fee(rpc_client).await?;
+    let estimate = estimate_fee(
+        &FeeMarketConfig::default(),
+        base_fee_per_cu,
+        priority_fee_per_cu,
+        resources,
+        blocks_ahead,
+    );
+
+    match config.output_format {
+        OutputFormat::Json | OutputFormat::JsonCompact => {
+            Ok(serde_json::to_string_pretty(&estimate)?)
+        }
+        _ => Ok(format!("{}", estimate)),
+    }
+}
+
+/// Build a fee history from the validator's recent prioritization fees.
+///
+/// The RPC reports a single compute unit price per slot, so each entry's
+/// quartiles are all that price.  Base fee and utilization are not reported
+/// and are left at zero.
+fn fee_history_from_prioritization_fees(samples: &[RpcPrioritizationFee]) -> FeeHistory {
+    let mut history = FeeHistory::new(FEE_HISTORY_SLOTS);
+    for sample in samples {
+        history.push(FeeHistoryEntry {
+            height: sample.slot,
+            p25_priority: sample.prioritization_fee,
+            p50_priority: sample.prioritization_fee,
+            p75_priority: sample.prioritization_fee,
+            ..FeeHistoryEntry::default()
+        });
+    }
+    history
+}
+
+async fn process_suggest_priority_fee(
+    rpc_client: &RpcClient,
+    config: &CliConfig<'_>,
+    percentile: u8,
+) -> ProcessResult {
+    let mut samples = rpc_client.get_recent_prioritization_fees(&[]).await?;
+    samples.sort_by_key(|sample| sample.slot);
+    let history = fee_history_from_prioritization_fees(&samples);
+
+    let suggestion = CliPriorityFeeSuggestion {
+        percentile,
+        suggested_priority_fee: history.suggest_priority_fee(percentile),
+        slots_sampled: history.len(),
+    };
+
+    match config.output_format {
+        OutputFormat::Json | OutputFormat::JsonCompact => {
+            Ok(serde_json::to_string_pretty(&suggestion)?)
+        }
+        _ => Ok(format!("{}", suggestion)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::clap_app::get_clap_app};
+
+    #[test]
+    fn test_parse_fee_estimate() {
+        let app = get_clap_app("test", "desc", "version");
+        let matches = app.get_matches_from(vec!["test", "fee", "estimate", "--percentile", "75"]);
+        let (_, matches) = matches.subcommand();
+        assert_eq!(
+            parse_fee_command(matches.unwrap()).unwrap(),
+            CliCommandInfo::without_signers(CliCommand::Fee(FeeCliCommand::Estimate {
+                percentile: 75
+            }))
+        );
+
+        let app = get_clap_app("test", "desc", "version");
+        assert!(app
+            .get_matches_from_safe(vec!["test", "fee", "estimate", "--percentile", "101"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_fee_estimate_total() {
+        let app = get_clap_app("test", "desc", "version");
+        let matches = app.get_matches_from(vec![
+            "test",
+            "fee",
+            "estimate",
+            "--compute-units",
+            "200000",
+            "--priority-fee",
+            "10",
+            "--accounts-written",
+            "3",
+            "--blocks-ahead",
+            "5",
+        ]);
+        let (_, matches) = matches.subcommand();
+        assert_eq!(
+            parse_fee_command(matches.unwrap()).unwrap(),
+            CliCommandInfo::without_signers(CliCommand::Fee(FeeCliCommand::EstimateTotal {
+                resources: TransactionResources {
+                    compute_units: 200_000,
+                    accounts_read: 0,
+                    accounts_written: 3,
+                },
+                priority_fee_per_cu: 10,
+                blocks_ahead: 5,
+            }))
+        );
+
+        // Pricing flags only make sense for a total-fee estimate.
+        let app = get_clap_app("test", "desc", "version");
+        assert!(app
+            .get_matches_from_safe(vec!["test", "fee", "estimate", "--priority-fee", "10"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_estimate_fee_doubling_compute_units_doubles_base_fee() {
+        let config = FeeMarketConfig::default();
+        let resources = TransactionResources {
+            compute_units: 200_000,
+            accounts_read: 0,
+            accounts_written: 2,
+        };
+        let doubled = TransactionResources {
+            compute_units: 400_000,
+            ..resources
+        };
+
+        let single = estimate_fee(&config, 5_000, 10, &resources, 0);
+        let double = estimate_fee(&config, 5_000, 10, &doubled, 0);
+        assert_eq!(single.base_fee, 5_000 * 200_000);
+        assert_eq!(double.base_fee, 2 * single.base_fee);
+        assert_eq!(double.priority_fee, 2 * single.priority_fee);
+        assert_eq!(double.storage_fee, single.storage_fee);
+        assert_eq!(single.storage_fee, 2 * config.write_fee_per_account);
+        assert_eq!(
+            single.total_fee,
+            single.base_fee + single.priority_fee + single.storage_fee
+        );
+    }
+
+    #[test]
+    fn test_estimate_fee_blocks_ahead_projects_base_fee() {
+        let config = FeeMarketConfig::default();
+        let resources = TransactionResources::compute_only(200_000);
+        let now = estimate_fee(&config, 5_000, 0, &resources, 0);
+        let later = estimate_fee(&config, 5_000, 0, &resources, 3);
+
+        let projected = project_base_fee(&config, 5_000, 3);
+        assert!(projected > 5_000);
+        assert_eq!(later.base_fee, projected * 200_000);
+        assert!(later.total_fee > now.total_fee);
+    }
+
+    #[test]
+    fn test_fee_history_from_prioritization_fees() {
+        let samples: Vec<RpcPrioritizationFee> = (0..200u64)
+            .map(|slot| RpcPrioritizationFee {
+                slot,
+                prioritization_fee: slot * 10,
+            })
+            .collect();
+        let history = fee_history_from_prioritization_fees(&samples);
+        assert_eq!(history.len(), FEE_HISTORY_SLOTS);
+        assert_eq!(history.entries.front().unwrap().height, 50);
+        // Slots 50..200 at 10 lamports each: the median slot is 124.
+        assert_eq!(history.suggest_priority_fee(50), 1_240);
+    }
+}
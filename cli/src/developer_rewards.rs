@@ -1,20 +1,50 @@
 use {
     crate::cli::{CliCommand, CliCommandInfo, CliConfig, CliError, ProcessResult},
-    clap::{App, AppSettings, Arg, ArgMatches, SubCommand},
+    borsh::BorshDeserialize,
+    clap::{value_t_or_exit, App, AppSettings, Arg, ArgMatches, SubCommand},
     serde::{Deserialize, Serialize},
     serde_json::{self, json},
     solana_clap_utils::{
         input_parsers::pubkey_of,
-        input_validators::is_valid_pubkey,
+        input_validators::{is_parsable, is_valid_pubkey},
         keypair::DefaultSigner,
     },
     solana_cli_output::OutputFormat,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_message::Message,
     solana_pubkey::Pubkey,
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
     solana_rpc_client::nonblocking::rpc_client::RpcClient,
-    std::{fmt, rc::Rc, sync::Arc},
+    solana_sdk_ids::sysvar,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+    std::{fmt, rc::Rc, str::FromStr, sync::Arc},
+    trv1_developer_rewards_program::{
+        constants::{MAX_REGISTRY_PAGE_SIZE, MAX_REVENUE_SPLIT_RECIPIENTS, TOTAL_BPS},
+        instruction::DeveloperRewardsInstruction,
+        state::{
+            find_registry_index_address, find_revenue_config_address, ProgramRegistryIndex,
+            RegistryPage, RevenueSplit,
+        },
+    },
 };
 
+/// Parses a `<PUBKEY>:<BPS>` revenue split entry.
+fn parse_split_recipient(s: &str) -> Result<(Pubkey, u16), String> {
+    let (pubkey, bps) = s
+        .split_once(':')
+        .ok_or_else(|| format!("Expected <PUBKEY>:<BPS>, got '{s}'"))?;
+    let pubkey = Pubkey::from_str(pubkey).map_err(|e| format!("Invalid pubkey '{pubkey}': {e}"))?;
+    let bps = bps
+        .parse::<u16>()
+        .map_err(|e| format!("Invalid basis points '{bps}': {e}"))?;
+    Ok((pubkey, bps))
+}
+
+fn is_valid_split_recipient(s: String) -> Result<(), String> {
+    parse_split_recipient(&s).map(|_| ())
+}
+
 // ── CLI Command Enum Variants ───────────────────────────────────────
 #[derive(Debug, PartialEq)]
 pub enum DevRewardsCliCommand {
@@ -32,6 +62,13 @@ pub enum DevRewardsCliCommand {
     Claim {
         program_id: Pubkey,
     },
+    SetSplit {
+        program_id: Pubkey,
+        recipients: Vec<(Pubkey, u16)>,
+    },
+    ListPrograms {
+        page: u64,
+    },
 }
 
 // ── Output Structs ──────────────────────────────────────────────────
@@ -67,6 +104,7 @@ impl DevRewardsSubCommands for App<'_, '_> {
     fn dev_rewards_subcommands(self) -> Self {
         self.subcommand(
             SubCommand::with_name("dev-rewards")
+                .alias("developer-rewards")
                 .about("TRv1 developer rewards commands")
                 .setting(AppSettings::SubcommandRequiredElseHelp)
                 .subcommand(
@@ -138,6 +176,46 @@ impl DevRewardsSubCommands for App<'_, '_> {
                                 .validator(is_valid_pubkey)
                                 .help("Program ID to claim rewards for"),
                         ),
+                )
+                .subcommand(
+                    SubCommand::with_name("set-split")
+                        .about("Replace the revenue split of a registered program")
+                        .arg(
+                            Arg::with_name("program")
+                                .long("program")
+                                .value_name("PROGRAM_ID")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_pubkey)
+                                .help("Program ID whose revenue split to set"),
+                        )
+                        .arg(
+                            Arg::with_name("recipient")
+                                .long("recipient")
+                                .value_name("PUBKEY:BPS")
+                                .takes_value(true)
+                                .multiple(true)
+                                .number_of_values(1)
+                                .required(true)
+                                .validator(is_valid_split_recipient)
+                                .help(
+                                    "Recipient and its share in basis points; repeat for \
+                                     each recipient. Shares must sum to 10000",
+                                ),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("list-programs")
+                        .about("List programs registered for developer rewards")
+                        .arg(
+                            Arg::with_name("page")
+                                .long("page")
+                                .value_name("N")
+                                .takes_value(true)
+                                .default_value("0")
+                                .validator(is_parsable::<u64>)
+                                .help("Zero-based page of 20 programs to display"),
+                        ),
                 ),
         )
     }
@@ -146,8 +224,8 @@ impl DevRewardsSubCommands for App<'_, '_> {
 // ── Argument Parsing ────────────────────────────────────────────────
 pub fn parse_dev_rewards_command(
     matches: &ArgMatches<'_>,
-    _default_signer: &DefaultSigner,
-    _wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+    default_signer: &DefaultSigner,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
 ) -> Result<CliCommandInfo, CliError> {
     match matches.subcommand() {
         ("register", Some(matches)) => {
@@ -182,6 +260,29 @@ pub fn parse_dev_rewards_command(
                 CliCommand::DevRewards(DevRewardsCliCommand::Claim { program_id }),
             ))
         }
+        ("set-split", Some(matches)) => {
+            let program_id = pubkey_of(matches, "program").unwrap();
+            let recipients = matches
+                .values_of("recipient")
+                .unwrap()
+                .map(parse_split_recipient)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(CliError::BadParameter)?;
+            validate_split(&recipients)?;
+            Ok(CliCommandInfo {
+                command: CliCommand::DevRewards(DevRewardsCliCommand::SetSplit {
+                    program_id,
+                    recipients,
+                }),
+                signers: vec![default_signer.signer_from_path(matches, wallet_manager)?],
+            })
+        }
+        ("list-programs", Some(matches)) => {
+            let page = value_t_or_exit!(matches, "page", u64);
+            Ok(CliCommandInfo::without_signers(
+                CliCommand::DevRewards(DevRewardsCliCommand::ListPrograms { page }),
+            ))
+        }
         _ => unreachable!(),
     }
 }
@@ -207,6 +308,58 @@ pub async fn process_dev_rewards_command(
         DevRewardsCliCommand::Claim { program_id } => {
             process_dev_rewards_claim(rpc_client, config, program_id).await
         }
+        DevRewardsCliCommand::SetSplit {
+            program_id,
+            recipients,
+        } => process_dev_rewards_set_split(rpc_client, config, program_id, recipients).await,
+        DevRewardsCliCommand::ListPrograms { page } => {
+            process_dev_rewards_list_programs(rpc_client, config, *page).await
+        }
+    }
+}
+
+/// Client-side mirror of the on-chain split validation, so obviously bad
+/// splits fail before a transaction is sent.
+fn validate_split(recipients: &[(Pubkey, u16)]) -> Result<(), CliError> {
+    if recipients.len() > MAX_REVENUE_SPLIT_RECIPIENTS {
+        return Err(CliError::BadParameter(format!(
+            "At most {MAX_REVENUE_SPLIT_RECIPIENTS} recipients are allowed, got {}",
+            recipients.len()
+        )));
+    }
+    let total: u32 = recipients.iter().map(|(_, bps)| u32::from(*bps)).sum();
+    if total != u32::from(TOTAL_BPS) {
+        return Err(CliError::BadParameter(format!(
+            "Recipient shares must sum to {TOTAL_BPS} bps, got {total}"
+        )));
+    }
+    Ok(())
+}
+
+/// Builds the `UpdateRevenueConfig` instruction replacing `program_id`'s split.
+fn set_split_instruction(
+    update_authority: &Pubkey,
+    program_id: &Pubkey,
+    recipients: &[(Pubkey, u16)],
+) -> Instruction {
+    let instruction = DeveloperRewardsInstruction::UpdateRevenueConfig {
+        program_id: *program_id,
+        recipients: recipients
+            .iter()
+            .map(|(recipient, share_bps)| RevenueSplit {
+                recipient: *recipient,
+                share_bps: *share_bps,
+            })
+            .collect(),
+    };
+    Instruction {
+        program_id: trv1_developer_rewards_program::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*update_authority, true),
+            AccountMeta::new(find_revenue_config_address(program_id).0, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: borsh::to_vec(&instruction).expect("instruction serializes"),
     }
 }
 
@@ -322,3 +475,169 @@ async fn process_dev_rewards_claim(
         )),
     }
 }
+
+async fn process_dev_rewards_set_split(
+    rpc_client: &Arc<RpcClient>,
+    config: &CliConfig<'_>,
+    program_id: &Pubkey,
+    recipients: &[(Pubkey, u16)],
+) -> ProcessResult {
+    let update_authority = config.signers[0].pubkey();
+    let instruction = set_split_instruction(&update_authority, program_id, recipients);
+
+    let blockhash = rpc_client.get_latest_blockhash().await?;
+    let mut tx = Transaction::new_unsigned(Message::new(&[instruction], Some(&update_authority)));
+    tx.try_sign(&config.signers, blockhash)?;
+    let signature = rpc_client.send_and_confirm_transaction(&tx).await?;
+
+    let result = json!({
+        "status": "ok",
+        "program_id": program_id.to_string(),
+        "recipients": recipients
+            .iter()
+            .map(|(recipient, bps)| json!({ "recipient": recipient.to_string(), "bps": bps }))
+            .collect::<Vec<_>>(),
+        "signature": signature.to_string(),
+    });
+
+    match config.output_format {
+        OutputFormat::Json | OutputFormat::JsonCompact => {
+            Ok(serde_json::to_string_pretty(&result)?)
+        }
+        _ => {
+            let mut output = format!("Revenue split updated for program {program_id}");
+            for (recipient, bps) in recipients {
+                output.push_str(&format!("\n  {recipient}: {bps} bps"));
+            }
+            output.push_str(&format!("\n  Signature: {signature}"));
+            Ok(output)
+        }
+    }
+}
+
+/// Returns page `page` of the registry, `MAX_REGISTRY_PAGE_SIZE` programs per
+/// page, matching what `GetRegistryPage` would return on-chain.
+fn registry_page(index: &ProgramRegistryIndex, page: u64) -> RegistryPage {
+    let page_size = u64::from(MAX_REGISTRY_PAGE_SIZE);
+    index.page(page.saturating_mul(page_size), MAX_REGISTRY_PAGE_SIZE)
+}
+
+async fn process_dev_rewards_list_programs(
+    rpc_client: &Arc<RpcClient>,
+    config: &CliConfig<'_>,
+    page: u64,
+) -> ProcessResult {
+    let index_address = find_registry_index_address().0;
+    // No index account yet means nothing has registered.
+    let index = match rpc_client
+        .get_account_with_commitment(&index_address, config.commitment)
+        .await?
+        .value
+    {
+        Some(account) => ProgramRegistryIndex::try_from_slice(&account.data).map_err(|err| {
+            CliError::RpcRequestError(format!("Failed to deserialize program registry: {err}"))
+        })?,
+        None => ProgramRegistryIndex::default(),
+    };
+    let registry_page = registry_page(&index, page);
+
+    let result = json!({
+        "page": page,
+        "total_programs": registry_page.total_entries,
+        "programs": registry_page
+            .entries
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+    });
+
+    match config.output_format {
+        OutputFormat::Json | OutputFormat::JsonCompact => {
+            Ok(serde_json::to_string_pretty(&result)?)
+        }
+        _ => {
+            let mut output = format!(
+                "Registered programs (page {page}, {} total)",
+                registry_page.total_entries
+            );
+            for program_id in &registry_page.entries {
+                output.push_str(&format!("\n  {program_id}"));
+            }
+            if registry_page.entries.is_empty() {
+                output.push_str("\n  (none)");
+            }
+            Ok(output)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_split_recipient() {
+        let recipient = Pubkey::new_unique();
+        assert_eq!(
+            parse_split_recipient(&format!("{recipient}:7000")).unwrap(),
+            (recipient, 7_000)
+        );
+        assert!(parse_split_recipient(&recipient.to_string()).is_err());
+        assert!(parse_split_recipient(&format!("{recipient}:70000")).is_err());
+        assert!(parse_split_recipient("not-a-pubkey:100").is_err());
+    }
+
+    #[test]
+    fn test_validate_split() {
+        let split = |bps: &[u16]| -> Vec<(Pubkey, u16)> {
+            bps.iter().map(|bps| (Pubkey::new_unique(), *bps)).collect()
+        };
+        assert!(validate_split(&split(&[7_000, 2_000, 1_000])).is_ok());
+        assert!(validate_split(&split(&[7_000, 2_000])).is_err());
+        assert!(validate_split(&split(&[5_000, 1_000, 1_000, 1_000, 1_000, 1_000])).is_err());
+    }
+
+    #[test]
+    fn test_set_split_instruction() {
+        let authority = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let instruction = set_split_instruction(&authority, &program_id, &[(recipient, 10_000)]);
+
+        assert_eq!(instruction.program_id, trv1_developer_rewards_program::id());
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new(find_revenue_config_address(&program_id).0, false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+            ]
+        );
+        assert_eq!(
+            borsh::from_slice::<DeveloperRewardsInstruction>(&instruction.data).unwrap(),
+            DeveloperRewardsInstruction::UpdateRevenueConfig {
+                program_id,
+                recipients: vec![RevenueSplit {
+                    recipient,
+                    share_bps: 10_000,
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_registry_page() {
+        let index = ProgramRegistryIndex {
+            version: 1,
+            entries: (0..25).map(|_| Pubkey::new_unique()).collect(),
+            next_page_seed: None,
+        };
+
+        let first = registry_page(&index, 0);
+        assert_eq!(first.total_entries, 25);
+        assert_eq!(first.entries, index.entries[..20]);
+        assert_eq!(registry_page(&index, 1).entries, index.entries[20..]);
+        assert!(registry_page(&index, 2).entries.is_empty());
+        assert!(registry_page(&index, u64::MAX).entries.is_empty());
+    }
+}
@@ -1,20 +1,61 @@
 use {
     crate::cli::{CliCommand, CliCommandInfo, CliConfig, CliError, ProcessResult},
-    clap::{App, AppSettings, Arg, ArgMatches, SubCommand},
+    clap::{value_t, App, AppSettings, Arg, ArgMatches, SubCommand},
+    num_traits::FromPrimitive,
     serde::{Deserialize, Serialize},
     serde_json::{self, json},
+    solana_account_decoder::UiAccountEncoding,
     solana_clap_utils::{
         input_parsers::pubkey_of,
-        input_validators::is_valid_pubkey,
+        input_validators::{is_parsable, is_valid_pubkey},
         keypair::DefaultSigner,
     },
     solana_cli_output::OutputFormat,
+    solana_hash::Hash,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_keypair::Keypair,
+    solana_message::Message,
+    solana_native_token::LAMPORTS_PER_SOL,
     solana_pubkey::Pubkey,
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
     solana_rpc_client::nonblocking::rpc_client::RpcClient,
-    std::{fmt, rc::Rc, sync::Arc},
+    solana_rpc_client_api::{
+        config::{
+            RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSimulateTransactionAccountsConfig,
+            RpcSimulateTransactionConfig,
+        },
+        filter::{Memcmp, RpcFilterType},
+    },
+    solana_sdk_ids::system_program,
+    solana_sha256_hasher::hash,
+    solana_signer::{Signer, SignerError},
+    solana_system_interface::instruction as system_instruction,
+    solana_transaction::Transaction,
+    std::{collections::HashMap, fmt, rc::Rc, sync::Arc, time::Duration},
+    trv1_governance_program::{
+        constants::MAX_TITLE_LEN,
+        error::GovernanceError,
+        instruction::GovernanceInstruction,
+        state::{
+            find_delegation_record_address, find_deposit_escrow_address, find_vote_record_address,
+            GovernanceConfig, Proposal, ProposalStatus, ProposalType as OnChainProposalType,
+            Vote as OnChainVote, PROPOSAL_DISCRIMINATOR,
+        },
+        vote_weight::voting_power_from_passive_stake_data,
+    },
 };
 
+// ── PDA seeds ───────────────────────────────────────────────────────
+pub const GOVERNANCE_CONFIG_SEED: &[u8] = b"governance_config";
+
+/// Default seconds between polls of `governance proposals --watch`.
+pub const DEFAULT_PROPOSALS_POLL_INTERVAL_SECS: u64 = 30;
+
+/// Address of the governance config account.
+pub fn governance_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[GOVERNANCE_CONFIG_SEED], &trv1_governance_program::id()).0
+}
+
 // ── Proposal types ──────────────────────────────────────────────────
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ProposalType {
@@ -71,6 +112,16 @@ impl VoteChoice {
     }
 }
 
+impl From<VoteChoice> for OnChainVote {
+    fn from(choice: VoteChoice) -> Self {
+        match choice {
+            VoteChoice::For => OnChainVote::For,
+            VoteChoice::Against => OnChainVote::Against,
+            VoteChoice::Abstain => OnChainVote::Abstain,
+        }
+    }
+}
+
 impl fmt::Display for VoteChoice {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -109,17 +160,32 @@ pub enum GovernanceCliCommand {
         description: String,
         /// For treasury-spend proposals
         amount: Option<f64>,
+        /// Treasury-spend recipient, or the account to unlock for
+        /// emergency-unlock proposals
         recipient: Option<Pubkey>,
         /// For parameter-change proposals
         parameter: Option<String>,
         value: Option<String>,
+        /// Proposer's passive stake account; required while governance is active
+        stake_account: Option<Pubkey>,
+        /// Simulate the proposal instead of submitting it
+        dry_run: bool,
     },
     Vote {
         proposal_id: u64,
         vote: String,
+        /// Voter's passive stake account, proving their voting power
+        stake_account: Pubkey,
+        /// Simulate the transaction before asking the signer to sign it
+        simulate: bool,
     },
     Proposals {
         status: String,
+        /// Keep polling and re-render the list until interrupted
+        watch: bool,
+        poll_interval_secs: u64,
+        /// Shell command run for every status change while watching
+        notify_command: Option<String>,
     },
     Proposal {
         proposal_id: u64,
@@ -223,6 +289,45 @@ impl fmt::Display for CliProposalList {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CliProposalSimulation {
+    pub success: bool,
+    pub units_consumed: Option<u64>,
+    /// Why the program would reject the proposal.
+    pub error: Option<String>,
+    pub logs: Vec<String>,
+    /// Proposal account state after the simulated transaction.
+    pub proposal: Option<CliProposal>,
+}
+
+impl fmt::Display for CliProposalSimulation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.success {
+            writeln!(f, "Simulation succeeded; the proposal would be accepted.")?;
+        } else {
+            writeln!(f, "Simulation failed; the proposal would be rejected.")?;
+        }
+        if let Some(ref error) = self.error {
+            writeln!(f, "  Error:         {}", error)?;
+        }
+        match self.units_consumed {
+            Some(units) => writeln!(f, "  Compute Units: {}", units)?,
+            None => writeln!(f, "  Compute Units: unknown")?,
+        }
+        if !self.logs.is_empty() {
+            writeln!(f, "Logs:")?;
+            for log in &self.logs {
+                writeln!(f, "  {}", log)?;
+            }
+        }
+        if let Some(ref proposal) = self.proposal {
+            writeln!(f, "Simulated proposal account:")?;
+            write!(f, "{}", proposal)?;
+        }
+        Ok(())
+    }
+}
+
 // ── Subcommand Definition (clap) ────────────────────────────────────
 pub trait GovernanceSubCommands {
     fn governance_subcommands(self) -> Self;
@@ -279,14 +384,18 @@ impl GovernanceSubCommands for App<'_, '_> {
                                 .value_name("ADDRESS")
                                 .takes_value(true)
                                 .validator(is_valid_pubkey)
-                                .help("Recipient for treasury-spend proposals"),
+                                .help(
+                                    "Recipient for treasury-spend proposals, or the account to \
+                                     unlock for emergency-unlock proposals",
+                                ),
                         )
                         .arg(
                             Arg::with_name("parameter")
                                 .long("parameter")
-                                .value_name("NAME")
+                                .value_name("PARAM_ID")
                                 .takes_value(true)
-                                .help("Parameter name for parameter-change proposals"),
+                                .validator(is_parsable::<u32>)
+                                .help("Numeric parameter ID for parameter-change proposals"),
                         )
                         .arg(
                             Arg::with_name("value")
@@ -294,6 +403,26 @@ impl GovernanceSubCommands for App<'_, '_> {
                                 .value_name("VALUE")
                                 .takes_value(true)
                                 .help("New value for parameter-change proposals"),
+                        )
+                        .arg(
+                            Arg::with_name("stake_account")
+                                .long("stake-account")
+                                .value_name("ADDRESS")
+                                .takes_value(true)
+                                .validator(is_valid_pubkey)
+                                .help(
+                                    "Passive stake account backing the proposal; required while \
+                                     governance is active",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("dry_run")
+                                .long("dry-run")
+                                .takes_value(false)
+                                .help(
+                                    "Simulate the proposal without submitting it. Exits with an \
+                                     error if the program would reject it",
+                                ),
                         ),
                 )
                 .subcommand(
@@ -315,6 +444,24 @@ impl GovernanceSubCommands for App<'_, '_> {
                                 .required(true)
                                 .validator(is_valid_vote_choice)
                                 .help("Vote: for, against, or abstain"),
+                        )
+                        .arg(
+                            Arg::with_name("stake_account")
+                                .long("stake-account")
+                                .value_name("ADDRESS")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_pubkey)
+                                .help("Passive stake account providing the voting power"),
+                        )
+                        .arg(
+                            Arg::with_name("simulate")
+                                .long("simulate")
+                                .takes_value(false)
+                                .help(
+                                    "Simulate the vote and show its effect before signing, e.g. \
+                                     before confirming on a hardware wallet",
+                                ),
                         ),
                 )
                 .subcommand(
@@ -328,6 +475,34 @@ impl GovernanceSubCommands for App<'_, '_> {
                                 .default_value("active")
                                 .validator(is_valid_proposal_status)
                                 .help("Filter by status: active, passed, all"),
+                        )
+                        .arg(
+                            Arg::with_name("watch")
+                                .long("watch")
+                                .takes_value(false)
+                                .help("Keep polling and update the list in place until Ctrl-C"),
+                        )
+                        .arg(
+                            Arg::with_name("poll_interval_secs")
+                                .long("poll-interval-secs")
+                                .value_name("SECONDS")
+                                .takes_value(true)
+                                .default_value("30")
+                                .requires("watch")
+                                .validator(is_parsable::<u64>)
+                                .help("Seconds between polls in watch mode"),
+                        )
+                        .arg(
+                            Arg::with_name("notify_command")
+                                .long("notify-command")
+                                .value_name("CMD")
+                                .takes_value(true)
+                                .requires("watch")
+                                .help(
+                                    "Shell command to run when a proposal changes status. \
+                                     TRV1_PROPOSAL_ID, TRV1_PROPOSAL_STATUS and \
+                                     TRV1_PROPOSAL_PREVIOUS_STATUS are set in its environment",
+                                ),
                         ),
                 )
                 .subcommand(
@@ -361,8 +536,8 @@ impl GovernanceSubCommands for App<'_, '_> {
 // ── Argument Parsing ────────────────────────────────────────────────
 pub fn parse_governance_command(
     matches: &ArgMatches<'_>,
-    _default_signer: &DefaultSigner,
-    _wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+    default_signer: &DefaultSigner,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
 ) -> Result<CliCommandInfo, CliError> {
     match matches.subcommand() {
         ("info", Some(_matches)) => {
@@ -380,8 +555,10 @@ pub fn parse_governance_command(
             let recipient = pubkey_of(matches, "recipient");
             let parameter = matches.value_of("parameter").map(|s| s.to_string());
             let value = matches.value_of("value").map(|s| s.to_string());
-            Ok(CliCommandInfo::without_signers(
-                CliCommand::Governance(GovernanceCliCommand::Propose {
+            let stake_account = pubkey_of(matches, "stake_account");
+            let dry_run = matches.is_present("dry_run");
+            Ok(CliCommandInfo {
+                command: CliCommand::Governance(GovernanceCliCommand::Propose {
                     proposal_type,
                     title,
                     description,
@@ -389,8 +566,11 @@ pub fn parse_governance_command(
                     recipient,
                     parameter,
                     value,
+                    stake_account,
+                    dry_run,
                 }),
-            ))
+                signers: vec![default_signer.signer_from_path(matches, wallet_manager)?],
+            })
         }
         ("vote", Some(matches)) => {
             let proposal_id: u64 = matches
@@ -399,15 +579,30 @@ pub fn parse_governance_command(
                 .parse()
                 .map_err(|_| CliError::BadParameter("Invalid proposal ID".to_string()))?;
             let vote = matches.value_of("vote").unwrap().to_string();
-            Ok(CliCommandInfo::without_signers(
-                CliCommand::Governance(GovernanceCliCommand::Vote { proposal_id, vote }),
-            ))
+            let stake_account = pubkey_of(matches, "stake_account").unwrap();
+            let simulate = matches.is_present("simulate");
+            Ok(CliCommandInfo {
+                command: CliCommand::Governance(GovernanceCliCommand::Vote {
+                    proposal_id,
+                    vote,
+                    stake_account,
+                    simulate,
+                }),
+                signers: vec![default_signer.signer_from_path(matches, wallet_manager)?],
+            })
         }
         ("proposals", Some(matches)) => {
             let status = matches.value_of("status").unwrap_or("active").to_string();
-            Ok(CliCommandInfo::without_signers(
-                CliCommand::Governance(GovernanceCliCommand::Proposals { status }),
-            ))
+            let poll_interval_secs = value_t!(matches, "poll_interval_secs", u64)
+                .unwrap_or(DEFAULT_PROPOSALS_POLL_INTERVAL_SECS);
+            Ok(CliCommandInfo::without_signers(CliCommand::Governance(
+                GovernanceCliCommand::Proposals {
+                    status,
+                    watch: matches.is_present("watch"),
+                    poll_interval_secs,
+                    notify_command: matches.value_of("notify_command").map(str::to_string),
+                },
+            )))
         }
         ("proposal", Some(matches)) => {
             let proposal_id: u64 = matches
@@ -449,6 +644,8 @@ pub async fn process_governance_command(
             recipient,
             parameter,
             value,
+            stake_account,
+            dry_run,
         } => {
             process_governance_propose(
                 rpc_client,
@@ -460,14 +657,45 @@ pub async fn process_governance_command(
                 recipient.as_ref(),
                 parameter.as_deref(),
                 value.as_deref(),
+                stake_account.as_ref(),
+                *dry_run,
             )
             .await
         }
-        GovernanceCliCommand::Vote { proposal_id, vote } => {
-            process_governance_vote(rpc_client, config, *proposal_id, vote).await
+        GovernanceCliCommand::Vote {
+            proposal_id,
+            vote,
+            stake_account,
+            simulate,
+        } => {
+            process_governance_vote(
+                rpc_client,
+                config,
+                *proposal_id,
+                vote,
+                stake_account,
+                *simulate,
+            )
+            .await
         }
-        GovernanceCliCommand::Proposals { status } => {
-            process_governance_proposals(rpc_client, config, status).await
+        GovernanceCliCommand::Proposals {
+            status,
+            watch,
+            poll_interval_secs,
+            notify_command,
+        } => {
+            if *watch {
+                process_governance_watch_proposals(
+                    rpc_client,
+                    config,
+                    status,
+                    Duration::from_secs(*poll_interval_secs),
+                    notify_command.as_deref(),
+                )
+                .await
+            } else {
+                process_governance_proposals(rpc_client, config, status).await
+            }
         }
         GovernanceCliCommand::Proposal { proposal_id } => {
             process_governance_proposal(rpc_client, config, *proposal_id).await
@@ -504,8 +732,105 @@ async fn process_governance_info(
     }
 }
 
+/// Build the on-chain proposal type from the `propose` arguments.
+fn build_proposal_type(
+    proposal_type: &str,
+    amount: Option<f64>,
+    recipient: Option<&Pubkey>,
+    parameter: Option<&str>,
+    value: Option<&str>,
+) -> Result<OnChainProposalType, CliError> {
+    match ProposalType::from_str_value(proposal_type).map_err(CliError::BadParameter)? {
+        ProposalType::ParameterChange => {
+            let param_id = parameter
+                .ok_or_else(|| CliError::BadParameter("--parameter is required".to_string()))?
+                .parse::<u32>()
+                .map_err(|_| CliError::BadParameter("--parameter must be a numeric ID".to_string()))?;
+            let new_value = value
+                .ok_or_else(|| CliError::BadParameter("--value is required".to_string()))?
+                .parse::<u64>()
+                .map_err(|_| CliError::BadParameter("--value must be an integer".to_string()))?;
+            Ok(OnChainProposalType::ParameterChange { param_id, new_value })
+        }
+        ProposalType::TreasurySpend => {
+            let recipient = *recipient
+                .ok_or_else(|| CliError::BadParameter("--recipient is required".to_string()))?;
+            let amount = amount
+                .ok_or_else(|| CliError::BadParameter("--amount is required".to_string()))?;
+            if !amount.is_finite() || amount <= 0.0 {
+                return Err(CliError::BadParameter("--amount must be positive".to_string()));
+            }
+            Ok(OnChainProposalType::TreasurySpend {
+                recipient,
+                amount: (amount * LAMPORTS_PER_SOL as f64).round() as u64,
+                memo: [0u8; 32],
+            })
+        }
+        ProposalType::EmergencyUnlock => {
+            let target_account = *recipient.ok_or_else(|| {
+                CliError::BadParameter("--recipient (the account to unlock) is required".to_string())
+            })?;
+            Ok(OnChainProposalType::EmergencyUnlock { target_account })
+        }
+        ProposalType::Text => Ok(OnChainProposalType::TextProposal),
+    }
+}
+
+/// Instructions that allocate `proposal_account` and create a proposal in it.
+///
+/// The proposal account is a fresh keypair rather than a PDA: the program
+/// only checks that the account is owned by it and uninitialised, and a PDA
+/// cannot sign the system program's `CreateAccount`.
+///
+/// `deposit_escrow` is the proposal's deposit escrow when governance charges
+/// a deposit; the program then transfers the deposit from the proposer.
+#[allow(clippy::too_many_arguments)]
+fn propose_instructions(
+    proposer: &Pubkey,
+    proposal_account: &Pubkey,
+    rent_lamports: u64,
+    stake_account: Option<&Pubkey>,
+    deposit_escrow: Option<&Pubkey>,
+    title: &str,
+    description: &str,
+    proposal_type: OnChainProposalType,
+) -> Vec<Instruction> {
+    let program_id = trv1_governance_program::id();
+    let mut account_metas = vec![
+        AccountMeta::new(*proposer, true),
+        AccountMeta::new(governance_config_address(), false),
+        AccountMeta::new(*proposal_account, false),
+    ];
+    if let Some(stake_account) = stake_account {
+        account_metas.push(AccountMeta::new_readonly(*stake_account, false));
+    }
+    if let Some(deposit_escrow) = deposit_escrow {
+        account_metas.push(AccountMeta::new(*deposit_escrow, false));
+        account_metas.push(AccountMeta::new_readonly(system_program::id(), false));
+    }
+    vec![
+        system_instruction::create_account(
+            proposer,
+            proposal_account,
+            rent_lamports,
+            Proposal::SERIALIZED_SIZE as u64,
+            &program_id,
+        ),
+        Instruction::new_with_bincode(
+            program_id,
+            &GovernanceInstruction::CreateProposal {
+                title: title.as_bytes().to_vec(),
+                description_hash: hash(description.as_bytes()),
+                proposal_type,
+            },
+            account_metas,
+        ),
+    ]
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn process_governance_propose(
-    _rpc_client: &Arc<RpcClient>,
+    rpc_client: &Arc<RpcClient>,
     config: &CliConfig<'_>,
     proposal_type: &str,
     title: &str,
@@ -514,27 +839,111 @@ async fn process_governance_propose(
     recipient: Option<&Pubkey>,
     parameter: Option<&str>,
     value: Option<&str>,
+    stake_account: Option<&Pubkey>,
+    dry_run: bool,
 ) -> ProcessResult {
-    // TODO: Build and send Governance::Propose instruction
-    // 1. Verify signer has enough stake to propose
-    // 2. Build Propose instruction based on proposal_type
-    //    - For treasury-spend: include amount and recipient
-    //    - For parameter-change: include parameter name and new value
-    //    - For emergency-unlock: no extra args
-    //    - For text: just title and description
-    // 3. Send transaction and confirm
-    // 4. Return proposal ID
+    if title.len() > MAX_TITLE_LEN {
+        return Err(CliError::BadParameter(format!(
+            "Title is {} bytes; the maximum is {MAX_TITLE_LEN}",
+            title.len()
+        ))
+        .into());
+    }
+    let on_chain_type = build_proposal_type(proposal_type, amount, recipient, parameter, value)?;
+
+    let proposer = config.signers[0].pubkey();
+
+    // Governance config: determines who may propose and the next proposal ID.
+    let config_address = governance_config_address();
+    let config_account = rpc_client
+        .get_account_with_commitment(&config_address, config.commitment)
+        .await?
+        .value
+        .ok_or_else(|| {
+            CliError::RpcRequestError(format!("Governance config {config_address} not found"))
+        })?;
+    let governance_config = GovernanceConfig::deserialize(&config_account.data).map_err(|err| {
+        CliError::RpcRequestError(format!("Failed to deserialize governance config: {err}"))
+    })?;
 
+    if governance_config.is_active {
+        let stake_address = stake_account.ok_or_else(|| {
+            CliError::BadParameter(
+                "--stake-account is required while governance is active".to_string(),
+            )
+        })?;
+        let stake_account = rpc_client
+            .get_account_with_commitment(stake_address, config.commitment)
+            .await?
+            .value
+            .ok_or_else(|| {
+                CliError::BadParameter(format!("Passive stake account {stake_address} not found"))
+            })?;
+        // Any existing lock predates the proposal about to be created.
+        let voting_power = voting_power_from_passive_stake_data(&stake_account.data, u64::MAX)
+            .map(|(_amount, power)| power)
+            .unwrap_or(0);
+        if voting_power < governance_config.proposal_threshold {
+            return Err(CliError::BadParameter(format!(
+                "Voting power {voting_power} is below the proposal threshold of {}",
+                governance_config.proposal_threshold
+            ))
+            .into());
+        }
+    } else if proposer != governance_config.authority {
+        return Err(CliError::BadParameter(format!(
+            "Governance is not active; only the authority {} may create proposals",
+            governance_config.authority
+        ))
+        .into());
+    }
+
+    let proposal_id = governance_config.next_proposal_id;
+    let deposit_escrow = (governance_config.is_active && governance_config.proposal_deposit > 0)
+        .then(|| find_deposit_escrow_address(&config_address, proposal_id).0);
+    let proposal_keypair = Keypair::new();
+    let rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(Proposal::SERIALIZED_SIZE)
+        .await?;
+    let instructions = propose_instructions(
+        &proposer,
+        &proposal_keypair.pubkey(),
+        rent,
+        stake_account,
+        deposit_escrow.as_ref(),
+        title,
+        description,
+        on_chain_type,
+    );
+
+    let mut signers: Vec<&dyn Signer> = config.signers.clone();
+    signers.push(&proposal_keypair);
+    let blockhash = rpc_client.get_latest_blockhash().await?;
+    let mut tx = Transaction::new_unsigned(Message::new(&instructions, Some(&proposer)));
+    if dry_run {
+        tx.message.recent_blockhash = blockhash;
+        return simulate_proposal(rpc_client, config, &tx, &proposal_keypair.pubkey()).await;
+    }
+    tx.try_sign(&signers, blockhash)?;
+    let signature = rpc_client.send_and_confirm_transaction(&tx).await?;
+
+    // Proposals skip voting while governance is inactive.
+    let status = if governance_config.is_active {
+        "Active"
+    } else {
+        "Timelocked"
+    };
+    let deposit = if deposit_escrow.is_some() {
+        governance_config.proposal_deposit
+    } else {
+        0
+    };
     let result = json!({
-        "status": "ok",
-        "proposal_type": proposal_type,
-        "title": title,
-        "description": description,
-        "amount": amount,
-        "recipient": recipient.map(|p| p.to_string()),
-        "parameter": parameter,
-        "value": value,
-        "proposal_id": 0, // TODO: actual proposal ID from chain
+        "proposal_id": proposal_id,
+        "proposal_account": proposal_keypair.pubkey().to_string(),
+        "signature": signature.to_string(),
+        "status": status,
+        "deposit_lamports": deposit,
     });
 
     match config.output_format {
@@ -542,27 +951,262 @@ async fn process_governance_propose(
             Ok(serde_json::to_string_pretty(&result)?)
         }
         _ => Ok(format!(
-            "Proposal created.\n  Type:  {}\n  Title: {}",
-            proposal_type, title
+            "Proposal #{} created.\n  Account:   {}\n  Type:      {}\n  Title:     {}\n  Status:    \
+             {}\n  Deposit:   {} lamports\n  Signature: {}",
+            proposal_id,
+            proposal_keypair.pubkey(),
+            proposal_type,
+            title,
+            status,
+            deposit,
+            signature
         )),
     }
 }
 
+/// Find the [`GovernanceError`] reported in a failed transaction's logs.
+///
+/// Matches the runtime's `custom program error: 0x..` line for the
+/// governance program, falling back to an error name logged by the program.
+pub fn parse_governance_error_from_logs(logs: &[String]) -> Option<GovernanceError> {
+    let failed_prefix = format!(
+        "Program {} failed: custom program error: 0x",
+        trv1_governance_program::id()
+    );
+    let from_code = logs.iter().find_map(|log| {
+        let code = log.strip_prefix(&failed_prefix)?;
+        GovernanceError::from_u32(u32::from_str_radix(code.trim(), 16).ok()?)
+    });
+    from_code.or_else(|| {
+        logs.iter().find_map(|log| {
+            log.split(|c: char| !c.is_ascii_alphanumeric())
+                .find_map(|word| {
+                    (0..)
+                        .map_while(GovernanceError::from_u32)
+                        .find(|error| format!("{error:?}") == word)
+                })
+        })
+    })
+}
+
+/// Simulate a `propose` transaction and report whether the program would
+/// accept it. A rejection is returned as an error, after printing the
+/// simulation, so scripts see a non-zero exit code.
+async fn simulate_proposal(
+    rpc_client: &RpcClient,
+    config: &CliConfig<'_>,
+    tx: &Transaction,
+    proposal_account: &Pubkey,
+) -> ProcessResult {
+    let result = rpc_client
+        .simulate_transaction_with_config(
+            tx,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                commitment: Some(config.commitment),
+                accounts: Some(RpcSimulateTransactionAccountsConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    addresses: vec![proposal_account.to_string()],
+                }),
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )
+        .await?
+        .value;
+    let logs = result.logs.unwrap_or_default();
+    let error = result
+        .err
+        .map(|err| match parse_governance_error_from_logs(&logs) {
+            Some(error) => format!("{error} ({error:?})"),
+            None => err.to_string(),
+        });
+    let proposal = result
+        .accounts
+        .and_then(|accounts| accounts.into_iter().next().flatten())
+        .and_then(|account| account.to_account())
+        .and_then(|account| Proposal::deserialize(&account.data).ok())
+        .map(|proposal| CliProposal::from(&proposal));
+    let simulation = CliProposalSimulation {
+        success: error.is_none(),
+        units_consumed: result.units_consumed,
+        error,
+        logs,
+        proposal,
+    };
+
+    let output = match config.output_format {
+        OutputFormat::Json | OutputFormat::JsonCompact => {
+            serde_json::to_string_pretty(&simulation)?
+        }
+        _ => format!("{}", simulation),
+    };
+    match simulation.error {
+        Some(error) => {
+            println!("{output}");
+            let message = format!("Proposal would be rejected: {error}");
+            Err(CliError::DynamicProgramError(message).into())
+        }
+        None => Ok(output),
+    }
+}
+
+/// Find the account holding proposal `proposal_id`.
+///
+/// Proposal accounts are fresh keypairs rather than PDAs, so they are looked
+/// up by their discriminator and leading `id` field.
+async fn find_proposal_account(
+    rpc_client: &RpcClient,
+    proposal_id: u64,
+) -> Result<(Pubkey, Proposal), Box<dyn std::error::Error>> {
+    let accounts = rpc_client
+        .get_program_ui_accounts_with_config(
+            &trv1_governance_program::id(),
+            RpcProgramAccountsConfig {
+                filters: Some(vec![
+                    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &[PROPOSAL_DISCRIMINATOR])),
+                    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                        1,
+                        &proposal_id.to_le_bytes(),
+                    )),
+                ]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        )
+        .await?;
+    accounts
+        .into_iter()
+        .find_map(|(address, ui_account)| {
+            let proposal = Proposal::deserialize(&ui_account.to_account()?.data).ok()?;
+            (proposal.id == proposal_id).then_some((address, proposal))
+        })
+        .ok_or_else(|| CliError::BadParameter(format!("Proposal #{proposal_id} not found")).into())
+}
+
+/// Instructions that cast `vote` on a proposal.
+///
+/// The vote record is the PDA of `(proposal_id, voter)`; the program creates
+/// it on the first vote and overwrites it when the vote is changed.
+fn cast_vote_instructions(
+    voter: &Pubkey,
+    proposal_account: &Pubkey,
+    stake_account: &Pubkey,
+    proposal_id: u64,
+    vote: OnChainVote,
+) -> Vec<Instruction> {
+    let vote_record = find_vote_record_address(proposal_id, voter).0;
+    vec![Instruction::new_with_bincode(
+        trv1_governance_program::id(),
+        &GovernanceInstruction::CastVote { proposal_id, vote },
+        vec![
+            AccountMeta::new(*voter, true),
+            AccountMeta::new(*proposal_account, false),
+            AccountMeta::new_readonly(governance_config_address(), false),
+            AccountMeta::new_readonly(*stake_account, false),
+            AccountMeta::new(vote_record, false),
+            AccountMeta::new_readonly(find_delegation_record_address(voter).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )]
+}
+
+/// Sign `message` by handing its serialized bytes to each signer.
+///
+/// Hardware wallets such as Ledger sign the exact message bytes they are
+/// shown, so the message is compiled once, with `Message::new` ordering
+/// writable accounts before read-only ones, and every signer (software or
+/// `usb://ledger`) signs those same bytes.
+fn sign_message(
+    mut message: Message,
+    blockhash: Hash,
+    signers: &[&dyn Signer],
+) -> Result<Transaction, SignerError> {
+    message.recent_blockhash = blockhash;
+    let message_data = message.serialize();
+    let mut tx = Transaction::new_unsigned(message);
+    let num_signers = usize::from(tx.message.header.num_required_signatures);
+    for signer in signers {
+        let pubkey = signer.try_pubkey()?;
+        let position = tx.message.account_keys[..num_signers]
+            .iter()
+            .position(|key| *key == pubkey)
+            .ok_or(SignerError::KeypairPubkeyMismatch)?;
+        tx.signatures[position] = signer.try_sign_message(&message_data)?;
+    }
+    Ok(tx)
+}
+
 async fn process_governance_vote(
-    _rpc_client: &Arc<RpcClient>,
+    rpc_client: &Arc<RpcClient>,
     config: &CliConfig<'_>,
     proposal_id: u64,
     vote: &str,
+    stake_account: &Pubkey,
+    simulate: bool,
 ) -> ProcessResult {
-    // TODO: Build and send Governance::Vote instruction
-    // 1. Verify signer has staked tokens (voting power)
-    // 2. Build Vote instruction with proposal_id and vote choice
-    // 3. Send transaction and confirm
+    let choice = VoteChoice::from_str_value(vote).map_err(CliError::BadParameter)?;
+    let voter = config.signers[0].pubkey();
+    let (proposal_account, _proposal) = find_proposal_account(rpc_client, proposal_id).await?;
+
+    let vote_record = find_vote_record_address(proposal_id, &voter).0;
+    let instructions = cast_vote_instructions(
+        &voter,
+        &proposal_account,
+        stake_account,
+        proposal_id,
+        choice.into(),
+    );
+    let message = Message::new(&instructions, Some(&voter));
+    let blockhash = rpc_client.get_latest_blockhash().await?;
+
+    let mut simulation_logs = None;
+    if simulate {
+        let mut unsigned = Transaction::new_unsigned(message.clone());
+        unsigned.message.recent_blockhash = blockhash;
+        let result = rpc_client
+            .simulate_transaction_with_config(
+                &unsigned,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    commitment: Some(config.commitment),
+                    ..RpcSimulateTransactionConfig::default()
+                },
+            )
+            .await?
+            .value;
+        let logs = result.logs.unwrap_or_default();
+        if let Some(err) = result.err {
+            return Err(CliError::RpcRequestError(format!(
+                "Vote simulation failed: {err}\n{}",
+                logs.join("\n")
+            ))
+            .into());
+        }
+        if !matches!(
+            config.output_format,
+            OutputFormat::Json | OutputFormat::JsonCompact
+        ) {
+            println!("Simulation succeeded:");
+            for log in &logs {
+                println!("  {log}");
+            }
+        }
+        simulation_logs = Some(logs);
+    }
+
+    let tx = sign_message(message, blockhash, &config.signers)?;
+    let signature = rpc_client.send_and_confirm_transaction(&tx).await?;
 
     let result = json!({
-        "status": "ok",
         "proposal_id": proposal_id,
+        "proposal_account": proposal_account.to_string(),
         "vote": vote,
+        "vote_record": vote_record.to_string(),
+        "signature": signature.to_string(),
+        "simulation_logs": simulation_logs,
     });
 
     match config.output_format {
@@ -570,23 +1214,106 @@ async fn process_governance_vote(
             Ok(serde_json::to_string_pretty(&result)?)
         }
         _ => Ok(format!(
-            "Voted '{}' on proposal #{}",
-            vote, proposal_id
+            "Voted '{}' on proposal #{}\n  Vote record: {}\n  Signature:   {}",
+            vote, proposal_id, vote_record, signature
         )),
     }
 }
 
+fn proposal_type_label(proposal_type: &OnChainProposalType) -> &'static str {
+    match proposal_type {
+        OnChainProposalType::ParameterChange { .. } => "parameter-change",
+        OnChainProposalType::TreasurySpend { .. } => "treasury-spend",
+        OnChainProposalType::EmergencyUnlock { .. } => "emergency-unlock",
+        OnChainProposalType::ProgramUpgrade { .. } => "program-upgrade",
+        OnChainProposalType::FeatureToggle { .. } => "feature-toggle",
+        OnChainProposalType::TextProposal => "text",
+        OnChainProposalType::MultiStep { .. } => "multi-step",
+        OnChainProposalType::GovernanceConfigUpdate { .. } => "governance-config-update",
+        OnChainProposalType::TreasuryAllowanceUpdate { .. } => "treasury-allowance-update",
+        OnChainProposalType::GovernanceSupplyUpdate { .. } => "governance-supply-update",
+    }
+}
+
+impl From<&Proposal> for CliProposal {
+    fn from(proposal: &Proposal) -> Self {
+        let title_len = proposal
+            .title
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(proposal.title.len());
+        Self {
+            id: proposal.id,
+            proposal_type: proposal_type_label(&proposal.proposal_type).to_string(),
+            title: String::from_utf8_lossy(&proposal.title[..title_len]).into_owned(),
+            description: proposal.description_hash.to_string(),
+            proposer: proposal.proposer.to_string(),
+            status: format!("{:?}", proposal.status),
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            votes_abstain: proposal.votes_abstain,
+            created_at: format!("epoch {}", proposal.created_epoch),
+            voting_ends: format!("epoch {}", proposal.voting_ends_epoch),
+        }
+    }
+}
+
+/// Whether `status` is selected by the `--status` filter.
+fn status_matches_filter(status: ProposalStatus, filter: &str) -> bool {
+    match filter {
+        "active" => status == ProposalStatus::Active,
+        "passed" => matches!(
+            status,
+            ProposalStatus::Passed | ProposalStatus::Timelocked | ProposalStatus::Executed
+        ),
+        _ => true,
+    }
+}
+
+/// Fetch every proposal account of the governance program, ordered by ID.
+async fn fetch_proposals(
+    rpc_client: &RpcClient,
+) -> Result<Vec<Proposal>, Box<dyn std::error::Error>> {
+    let accounts = rpc_client
+        .get_program_ui_accounts_with_config(
+            &trv1_governance_program::id(),
+            RpcProgramAccountsConfig {
+                filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                    0,
+                    &[PROPOSAL_DISCRIMINATOR],
+                ))]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        )
+        .await?;
+    let mut proposals: Vec<Proposal> = accounts
+        .into_iter()
+        .filter_map(|(_, ui_account)| Proposal::deserialize(&ui_account.to_account()?.data).ok())
+        .collect();
+    proposals.sort_by_key(|proposal| proposal.id);
+    Ok(proposals)
+}
+
+fn proposal_list(proposals: &[Proposal], status_filter: &str) -> CliProposalList {
+    CliProposalList {
+        proposals: proposals
+            .iter()
+            .filter(|proposal| status_matches_filter(proposal.status, status_filter))
+            .map(CliProposal::from)
+            .collect(),
+    }
+}
+
 async fn process_governance_proposals(
-    _rpc_client: &Arc<RpcClient>,
+    rpc_client: &Arc<RpcClient>,
     config: &CliConfig<'_>,
-    _status: &str,
+    status: &str,
 ) -> ProcessResult {
-    // TODO: Fetch proposals from the chain
-    // 1. Use rpc_client.get_program_accounts() with filters
-    // 2. Filter by status
-    // 3. Deserialize each into CliProposal
-
-    let list = CliProposalList { proposals: vec![] };
+    let list = proposal_list(&fetch_proposals(rpc_client).await?, status);
 
     match config.output_format {
         OutputFormat::Json | OutputFormat::JsonCompact => {
@@ -596,6 +1323,164 @@ async fn process_governance_proposals(
     }
 }
 
+/// A proposal whose status differs from the previous poll.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProposalStatusChange {
+    pub id: u64,
+    /// `None` for a proposal first seen in this poll.
+    pub previous_status: Option<String>,
+    pub status: String,
+}
+
+impl fmt::Display for ProposalStatusChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.previous_status {
+            Some(previous) => write!(f, "Proposal #{}: {} -> {}", self.id, previous, self.status),
+            None => write!(f, "Proposal #{}: new, {}", self.id, self.status),
+        }
+    }
+}
+
+/// Remembers proposal statuses across polls to report what changed.
+///
+/// Every proposal is tracked regardless of the status filter, so a proposal
+/// leaving the filtered list (e.g. Active -> Timelocked) is still reported.
+#[derive(Debug, Default)]
+struct ProposalWatcher {
+    statuses: Option<HashMap<u64, String>>,
+}
+
+impl ProposalWatcher {
+    /// Fetch the proposals and return them with the status changes since the
+    /// previous poll. The first poll reports no changes.
+    async fn poll(
+        &mut self,
+        rpc_client: &RpcClient,
+    ) -> Result<(Vec<Proposal>, Vec<ProposalStatusChange>), Box<dyn std::error::Error>> {
+        let proposals = fetch_proposals(rpc_client).await?;
+        let statuses: HashMap<u64, String> = proposals
+            .iter()
+            .map(|proposal| (proposal.id, format!("{:?}", proposal.status)))
+            .collect();
+
+        let mut changes = vec![];
+        if let Some(previous) = &self.statuses {
+            for proposal in &proposals {
+                let status = &statuses[&proposal.id];
+                let previous_status = previous.get(&proposal.id);
+                if previous_status != Some(status) {
+                    changes.push(ProposalStatusChange {
+                        id: proposal.id,
+                        previous_status: previous_status.cloned(),
+                        status: status.clone(),
+                    });
+                }
+            }
+        }
+        self.statuses = Some(statuses);
+        Ok((proposals, changes))
+    }
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> tokio::process::Command {
+    let mut shell = tokio::process::Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> tokio::process::Command {
+    let mut shell = tokio::process::Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+/// Run the `--notify-command` for a status change.
+///
+/// Failures are reported on stderr without stopping the watch.
+async fn notify_status_change(command: &str, change: &ProposalStatusChange) {
+    let result = shell_command(command)
+        .env("TRV1_PROPOSAL_ID", change.id.to_string())
+        .env("TRV1_PROPOSAL_STATUS", &change.status)
+        .env(
+            "TRV1_PROPOSAL_PREVIOUS_STATUS",
+            change.previous_status.as_deref().unwrap_or_default(),
+        )
+        .status()
+        .await;
+    match result {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!(
+            "Notify command for proposal #{} exited with {status}",
+            change.id
+        ),
+        Err(err) => eprintln!(
+            "Failed to run notify command for proposal #{}: {err}",
+            change.id
+        ),
+    }
+}
+
+/// Poll proposals until Ctrl-C, re-rendering the list in place.
+///
+/// Status changes are printed above the list, highlighted, so they stay in
+/// the scrollback when the list is redrawn. JSON output prints the changes
+/// as one JSON object per line instead.
+async fn process_governance_watch_proposals(
+    rpc_client: &Arc<RpcClient>,
+    config: &CliConfig<'_>,
+    status: &str,
+    poll_interval: Duration,
+    notify_command: Option<&str>,
+) -> ProcessResult {
+    let is_json = matches!(
+        config.output_format,
+        OutputFormat::Json | OutputFormat::JsonCompact
+    );
+    let mut watcher = ProposalWatcher::default();
+    // Number of lines of the last rendered list, erased before redrawing.
+    let mut rendered_lines = 0;
+
+    loop {
+        match watcher.poll(rpc_client).await {
+            Ok((proposals, changes)) => {
+                if !is_json && rendered_lines > 0 {
+                    // Move the cursor to the start of the previous list and
+                    // clear everything below it.
+                    print!("\x1b[{rendered_lines}F\x1b[J");
+                }
+                for change in &changes {
+                    if is_json {
+                        println!("{}", serde_json::to_string(change)?);
+                    } else {
+                        println!("\x1b[1;33m{change}\x1b[0m");
+                    }
+                    if let Some(command) = notify_command {
+                        notify_status_change(command, change).await;
+                    }
+                }
+                if !is_json {
+                    let rendered = proposal_list(&proposals, status).to_string();
+                    print!("{rendered}");
+                    rendered_lines = rendered.lines().count();
+                }
+            }
+            Err(err) => {
+                eprintln!("Failed to fetch proposals: {err}");
+                rendered_lines = 0;
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = tokio::time::sleep(poll_interval) => {}
+        }
+    }
+
+    Ok(String::new())
+}
+
 async fn process_governance_proposal(
     _rpc_client: &Arc<RpcClient>,
     config: &CliConfig<'_>,
@@ -651,3 +1536,610 @@ async fn process_governance_execute(
         _ => Ok(format!("Proposal #{} executed successfully", proposal_id)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_account::Account,
+        solana_account_decoder::{encode_ui_account, UiAccountEncoding},
+        solana_instruction::error::InstructionError,
+        solana_rpc_client::mock_sender::MocksMap,
+        solana_rpc_client_api::{
+            request::RpcRequest,
+            response::{Response, RpcKeyedAccount, RpcResponseContext},
+        },
+        solana_signature::Signature,
+        solana_system_interface::instruction::SystemInstruction,
+        solana_transaction_error::TransactionError,
+        solana_transaction_status_client_types::UiTransactionError,
+        std::sync::Mutex,
+    };
+
+    fn governance_config(is_active: bool, authority: Pubkey) -> GovernanceConfig {
+        GovernanceConfig {
+            is_active,
+            authority,
+            proposal_threshold: 1_000,
+            voting_period_epochs: 4,
+            quorum_bps: 3_000,
+            pass_threshold_bps: 5_000,
+            veto_threshold_bps: 3_333,
+            timelock_epochs: 2,
+            emergency_multisig: Pubkey::new_unique(),
+            next_proposal_id: 7,
+            max_delegation_epochs: 90,
+            vote_change_lockout_epochs: 0,
+            proposal_deposit: 0,
+            total_governance_supply: 0,
+            expiry_grace_epochs: 30,
+            sweeper_reward_lamports: 0,
+        }
+    }
+
+    fn account_response(address: &Pubkey, account: &Account) -> serde_json::Value {
+        json!(Response {
+            context: RpcResponseContext {
+                slot: 1,
+                api_version: None,
+            },
+            value: encode_ui_account(address, account, UiAccountEncoding::Base64, None, None),
+        })
+    }
+
+    fn config_response(config: &GovernanceConfig) -> serde_json::Value {
+        let mut data = vec![0u8; GovernanceConfig::SERIALIZED_SIZE];
+        config.serialize_into(&mut data).unwrap();
+        let account = Account {
+            lamports: 1_000_000,
+            data,
+            owner: trv1_governance_program::id(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        account_response(&governance_config_address(), &account)
+    }
+
+    fn proposal(id: u64, status: ProposalStatus) -> Proposal {
+        Proposal {
+            id,
+            proposer: Pubkey::new_unique(),
+            title: [0u8; 64],
+            description_hash: Hash::default(),
+            proposal_type: OnChainProposalType::TextProposal,
+            status,
+            created_epoch: 10,
+            voting_ends_epoch: 14,
+            execution_epoch: 16,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            veto_votes: 0,
+            executed: false,
+            executed_steps: 0,
+            voting_power_snapshot_epoch: 10,
+            deposit_lamports: 0,
+            deposit_claimed: false,
+        }
+    }
+
+    fn proposal_accounts_response(proposals: &[(Pubkey, Proposal)]) -> serde_json::Value {
+        let keyed: Vec<RpcKeyedAccount> = proposals
+            .iter()
+            .map(|(address, proposal)| {
+                let mut data = vec![0u8; Proposal::SERIALIZED_SIZE];
+                proposal.serialize_into(&mut data).unwrap();
+                let account = Account {
+                    lamports: 1_000_000,
+                    data,
+                    owner: trv1_governance_program::id(),
+                    executable: false,
+                    rent_epoch: 0,
+                };
+                RpcKeyedAccount {
+                    pubkey: address.to_string(),
+                    account: encode_ui_account(
+                        address,
+                        &account,
+                        UiAccountEncoding::Base64,
+                        None,
+                        None,
+                    ),
+                }
+            })
+            .collect();
+        json!(keyed)
+    }
+
+    /// Stands in for a hardware wallet: it can only sign the message bytes it
+    /// is handed, and records them.
+    struct MockHardwareSigner {
+        keypair: Keypair,
+        signed_messages: Mutex<Vec<Vec<u8>>>,
+    }
+
+    impl Signer for MockHardwareSigner {
+        fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+            Ok(self.keypair.pubkey())
+        }
+
+        fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+            self.signed_messages.lock().unwrap().push(message.to_vec());
+            self.keypair.try_sign_message(message)
+        }
+
+        fn is_interactive(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_vote_transaction_identical_for_software_and_hardware_signers() {
+        let voter = Keypair::new();
+        let hardware = MockHardwareSigner {
+            keypair: voter.insecure_clone(),
+            signed_messages: Mutex::default(),
+        };
+        let proposal_account = Pubkey::new_unique();
+        let stake_account = Pubkey::new_unique();
+        let vote_record = find_vote_record_address(7, &voter.pubkey()).0;
+        let instructions = cast_vote_instructions(
+            &voter.pubkey(),
+            &proposal_account,
+            &stake_account,
+            7,
+            OnChainVote::For,
+        );
+        let message = Message::new(&instructions, Some(&voter.pubkey()));
+        let blockhash = Hash::new_from_array([7; 32]);
+
+        let software_tx = sign_message(message.clone(), blockhash, &[&voter]).unwrap();
+        let hardware_tx = sign_message(message, blockhash, &[&hardware]).unwrap();
+        assert_eq!(
+            bincode::serialize(&software_tx).unwrap(),
+            bincode::serialize(&hardware_tx).unwrap()
+        );
+        assert_eq!(
+            *hardware.signed_messages.lock().unwrap(),
+            vec![software_tx.message_data()]
+        );
+        software_tx.verify().unwrap();
+
+        // Writable accounts are listed before read-only ones.
+        let header = &software_tx.message.header;
+        assert_eq!(header.num_readonly_signed_accounts, 0);
+        assert_eq!(header.num_readonly_unsigned_accounts, 5);
+        assert_eq!(
+            software_tx.message.account_keys[..3],
+            [voter.pubkey(), proposal_account, vote_record]
+        );
+
+        // A signer that is not part of the message is rejected.
+        assert!(sign_message(
+            software_tx.message.clone(),
+            blockhash,
+            &[&voter, &Keypair::new()]
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_process_governance_vote() {
+        let proposal_account = Pubkey::new_unique();
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetProgramAccounts,
+            proposal_accounts_response(&[
+                (Pubkey::new_unique(), proposal(6, ProposalStatus::Active)),
+                (proposal_account, proposal(7, ProposalStatus::Active)),
+            ]),
+        );
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map("succeeds", mocks));
+
+        let voter = Keypair::new();
+        let mut config = CliConfig {
+            output_format: OutputFormat::Json,
+            ..CliConfig::default()
+        };
+        config.signers = vec![&voter];
+
+        let output = process_governance_vote(
+            &rpc_client,
+            &config,
+            7,
+            "against",
+            &Pubkey::new_unique(),
+            true,
+        )
+        .await
+        .unwrap();
+        let result: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(result["proposal_account"], proposal_account.to_string());
+        assert_eq!(
+            result["vote_record"],
+            find_vote_record_address(7, &voter.pubkey()).0.to_string()
+        );
+        assert!(result["simulation_logs"].is_array());
+        assert!(result["signature"].as_str().is_some_and(|s| !s.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_proposal_watcher_detects_status_change() {
+        let proposal_account = Pubkey::new_unique();
+        let mut timelocked = proposal(7, ProposalStatus::Timelocked);
+        timelocked.votes_for = 5_000;
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetProgramAccounts,
+            proposal_accounts_response(&[(proposal_account, proposal(7, ProposalStatus::Active))]),
+        );
+        mocks.insert(
+            RpcRequest::GetProgramAccounts,
+            proposal_accounts_response(&[
+                (proposal_account, timelocked.clone()),
+                (Pubkey::new_unique(), proposal(8, ProposalStatus::Active)),
+            ]),
+        );
+        mocks.insert(
+            RpcRequest::GetProgramAccounts,
+            proposal_accounts_response(&[(proposal_account, timelocked)]),
+        );
+        let rpc_client = RpcClient::new_mock_with_mocks_map("succeeds", mocks);
+
+        let mut watcher = ProposalWatcher::default();
+        let (proposals, changes) = watcher.poll(&rpc_client).await.unwrap();
+        assert_eq!(proposal_list(&proposals, "active").proposals.len(), 1);
+        assert!(changes.is_empty());
+
+        let (proposals, changes) = watcher.poll(&rpc_client).await.unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                ProposalStatusChange {
+                    id: 7,
+                    previous_status: Some("Active".to_string()),
+                    status: "Timelocked".to_string(),
+                },
+                ProposalStatusChange {
+                    id: 8,
+                    previous_status: None,
+                    status: "Active".to_string(),
+                },
+            ]
+        );
+        let active = proposal_list(&proposals, "active");
+        assert_eq!(active.proposals.len(), 1);
+        assert_eq!(active.proposals[0].id, 8);
+        let passed = proposal_list(&proposals, "passed");
+        assert_eq!(passed.proposals[0].id, 7);
+        assert_eq!(passed.proposals[0].votes_for, 5_000);
+
+        // A proposal disappearing is not a status change.
+        let (_, changes) = watcher.poll(&rpc_client).await.unwrap();
+        assert!(changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_process_governance_propose() {
+        let authority = Keypair::new();
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            config_response(&governance_config(false, authority.pubkey())),
+        );
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map("succeeds", mocks));
+
+        let mut config = CliConfig {
+            output_format: OutputFormat::Json,
+            ..CliConfig::default()
+        };
+        config.signers = vec![&authority];
+
+        let output = process_governance_propose(
+            &rpc_client,
+            &config,
+            "parameter-change",
+            "Raise the block CU limit",
+            "Doubles the per-block compute budget.",
+            None,
+            None,
+            Some("3"),
+            Some("96000000"),
+            None,
+            false,
+        )
+        .await
+        .unwrap();
+        let result: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(result["proposal_id"], 7);
+        assert!(result["proposal_account"].as_str().is_some());
+        assert_eq!(result["status"], "Timelocked");
+        assert!(result["signature"].as_str().is_some_and(|s| !s.is_empty()));
+    }
+
+    #[test]
+    fn test_parse_governance_error_from_logs() {
+        let program_id = trv1_governance_program::id();
+        let logs = vec![
+            format!("Program {program_id} invoke [1]"),
+            "Program log: CreateProposal: voting power below threshold".to_string(),
+            format!("Program {program_id} consumed 2410 of 200000 compute units"),
+            format!("Program {program_id} failed: custom program error: 0x9"),
+        ];
+        assert_eq!(
+            parse_governance_error_from_logs(&logs),
+            Some(GovernanceError::InsufficientStakeForProposal)
+        );
+
+        let logs = vec!["Program log: Error: GovernanceNotActive".to_string()];
+        assert_eq!(
+            parse_governance_error_from_logs(&logs),
+            Some(GovernanceError::GovernanceNotActive)
+        );
+
+        let logs = vec![
+            "Program 11111111111111111111111111111111 failed: custom program error: 0x1"
+                .to_string(),
+        ];
+        assert_eq!(parse_governance_error_from_logs(&logs), None);
+        assert_eq!(parse_governance_error_from_logs(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn test_process_governance_propose_dry_run_renders_rejection() {
+        let authority = Keypair::new();
+        let program_id = trv1_governance_program::id();
+        let logs = vec![
+            format!("Program {program_id} invoke [1]"),
+            format!("Program {program_id} consumed 3100 of 200000 compute units"),
+            format!(
+                "Program {program_id} failed: custom program error: {:#x}",
+                GovernanceError::TooManyActiveProposals as u32
+            ),
+        ];
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            config_response(&governance_config(false, authority.pubkey())),
+        );
+        mocks.insert(
+            RpcRequest::SimulateTransaction,
+            json!(Response {
+                context: RpcResponseContext {
+                    slot: 1,
+                    api_version: None,
+                },
+                value: json!({
+                    "err": UiTransactionError::from(TransactionError::InstructionError(
+                        1,
+                        InstructionError::Custom(GovernanceError::TooManyActiveProposals as u32),
+                    )),
+                    "logs": logs,
+                    "unitsConsumed": 3100,
+                }),
+            }),
+        );
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map("succeeds", mocks));
+
+        let mut config = CliConfig {
+            output_format: OutputFormat::Json,
+            ..CliConfig::default()
+        };
+        config.signers = vec![&authority];
+
+        let err = process_governance_propose(
+            &rpc_client,
+            &config,
+            "text",
+            "Signal",
+            "Non-binding signal.",
+            None,
+            None,
+            None,
+            None,
+            None,
+            true,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Dynamic program error: Proposal would be rejected: Maximum number of active \
+             proposals reached (TooManyActiveProposals)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_process_governance_propose_dry_run_succeeds() {
+        let authority = Keypair::new();
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            config_response(&governance_config(false, authority.pubkey())),
+        );
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map("succeeds", mocks));
+
+        let mut config = CliConfig {
+            output_format: OutputFormat::Json,
+            ..CliConfig::default()
+        };
+        config.signers = vec![&authority];
+
+        let output = process_governance_propose(
+            &rpc_client,
+            &config,
+            "text",
+            "Signal",
+            "Non-binding signal.",
+            None,
+            None,
+            None,
+            None,
+            None,
+            true,
+        )
+        .await
+        .unwrap();
+        let simulation: CliProposalSimulation = serde_json::from_str(&output).unwrap();
+        assert!(simulation.success);
+        assert!(simulation.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_process_governance_propose_rejects_non_authority() {
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            config_response(&governance_config(false, Pubkey::new_unique())),
+        );
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map("succeeds", mocks));
+
+        let proposer = Keypair::new();
+        let mut config = CliConfig::default();
+        config.signers = vec![&proposer];
+
+        let result = process_governance_propose(
+            &rpc_client,
+            &config,
+            "text",
+            "Signal",
+            "Non-binding signal.",
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+        )
+        .await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("only the authority"));
+    }
+
+    #[tokio::test]
+    async fn test_process_governance_propose_requires_stake_account_when_active() {
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            config_response(&governance_config(true, Pubkey::new_unique())),
+        );
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map("succeeds", mocks));
+
+        let proposer = Keypair::new();
+        let mut config = CliConfig::default();
+        config.signers = vec![&proposer];
+
+        let result = process_governance_propose(
+            &rpc_client,
+            &config,
+            "text",
+            "Signal",
+            "Non-binding signal.",
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+        )
+        .await;
+        assert!(result.unwrap_err().to_string().contains("--stake-account"));
+    }
+
+    #[test]
+    fn test_propose_instructions_allocate_proposal_account() {
+        let proposer = Pubkey::new_unique();
+        let proposal_account = Pubkey::new_unique();
+        let stake_account = Pubkey::new_unique();
+        let instructions = propose_instructions(
+            &proposer,
+            &proposal_account,
+            42,
+            Some(&stake_account),
+            None,
+            "Signal",
+            "Non-binding signal.",
+            OnChainProposalType::TextProposal,
+        );
+        assert_eq!(instructions.len(), 2);
+
+        let create = &instructions[0];
+        assert_eq!(create.program_id, system_program::id());
+        assert_eq!(
+            bincode::deserialize::<SystemInstruction>(&create.data).unwrap(),
+            SystemInstruction::CreateAccount {
+                lamports: 42,
+                space: Proposal::SERIALIZED_SIZE as u64,
+                owner: trv1_governance_program::id(),
+            }
+        );
+        assert_eq!(create.accounts[1].pubkey, proposal_account);
+        assert!(create.accounts[1].is_signer);
+
+        let propose = &instructions[1];
+        assert_eq!(propose.program_id, trv1_governance_program::id());
+        let keys: Vec<Pubkey> = propose.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(
+            keys,
+            vec![
+                proposer,
+                governance_config_address(),
+                proposal_account,
+                stake_account
+            ]
+        );
+
+        // Without a stake account only the three required accounts are passed.
+        let instructions = propose_instructions(
+            &proposer,
+            &proposal_account,
+            42,
+            None,
+            None,
+            "Signal",
+            "Non-binding signal.",
+            OnChainProposalType::TextProposal,
+        );
+        assert_eq!(instructions[1].accounts.len(), 3);
+
+        // A deposit escrow is followed by the system program for the transfer.
+        let escrow = Pubkey::new_unique();
+        let instructions = propose_instructions(
+            &proposer,
+            &proposal_account,
+            42,
+            Some(&stake_account),
+            Some(&escrow),
+            "Signal",
+            "Non-binding signal.",
+            OnChainProposalType::TextProposal,
+        );
+        let propose = &instructions[1];
+        assert!(propose.accounts[0].is_writable);
+        assert_eq!(propose.accounts[4], AccountMeta::new(escrow, false));
+        assert_eq!(
+            propose.accounts[5],
+            AccountMeta::new_readonly(system_program::id(), false)
+        );
+    }
+
+    #[test]
+    fn test_build_proposal_type() {
+        let recipient = Pubkey::new_unique();
+        assert_eq!(
+            build_proposal_type("treasury-spend", Some(1.5), Some(&recipient), None, None)
+                .unwrap(),
+            OnChainProposalType::TreasurySpend {
+                recipient,
+                amount: 1_500_000_000,
+                memo: [0u8; 32],
+            }
+        );
+        assert!(build_proposal_type("treasury-spend", None, Some(&recipient), None, None).is_err());
+        assert!(
+            build_proposal_type("parameter-change", None, None, Some("fee"), Some("1")).is_err()
+        );
+    }
+}
@@ -8,6 +8,7 @@ use {
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
     solana_rpc_client::nonblocking::rpc_client::RpcClient,
     std::{fmt, rc::Rc, sync::Arc},
+    trv1_fee_market::PriorityFeeStats,
 };
 
 // ── CLI Command Enum Variants ───────────────────────────────────────
@@ -54,6 +55,8 @@ pub struct CliFeeInfo {
     pub priority_fee_median_lamports: u64,
     pub utilization_pct: f64,
     pub fee_burn_pct: f64,
+    /// Recent per-CU priority fee prices, in micro-lamports per CU.
+    pub priority_fee_per_cu_stats: PriorityFeeStats,
     pub recent_fee_history: Vec<CliFeeHistoryEntry>,
 }
 
@@ -71,6 +74,22 @@ impl fmt::Display for CliFeeInfo {
         writeln!(f, "  Priority Fee (median):{} lamports", self.priority_fee_median_lamports)?;
         writeln!(f, "  Network Utilization:  {:.1}%", self.utilization_pct)?;
         writeln!(f, "  Fee Burn Rate:        {:.1}%", self.fee_burn_pct)?;
+        writeln!(f)?;
+        writeln!(f, "  Priority Fee Statistics (micro-lamports/CU):")?;
+        writeln!(f, "  {:<8} {:>14}", "Stat", "Fee")?;
+        writeln!(f, "  {}", "-".repeat(23))?;
+        let stats = &self.priority_fee_per_cu_stats;
+        for (label, value) in [
+            ("p25", stats.p25),
+            ("p50", stats.p50),
+            ("p75", stats.p75),
+            ("p90", stats.p90),
+            ("p99", stats.p99),
+            ("mean", stats.mean),
+            ("max", stats.max),
+        ] {
+            writeln!(f, "  {:<8} {:>14}", label, value)?;
+        }
         if !self.recent_fee_history.is_empty() {
             writeln!(f)?;
             writeln!(f, "  Recent Fee History:")?;
@@ -157,7 +176,7 @@ impl NetworkInfoSubCommands for App<'_, '_> {
                 )
                 .subcommand(
                     SubCommand::with_name("fee-info")
-                        .about("Show current base fee, utilization, and fee history"),
+                        .about("Show current base fee, utilization, priority fee statistics, and fee history"),
                 )
                 .subcommand(
                     SubCommand::with_name("inflation-info")
@@ -247,14 +266,24 @@ async fn process_network_info(
 }
 
 async fn process_fee_info(
-    _rpc_client: &Arc<RpcClient>,
+    rpc_client: &Arc<RpcClient>,
     config: &CliConfig<'_>,
 ) -> ProcessResult {
     // TODO: Fetch fee info from the chain
     // 1. Query TRv1 fee controller program for current base fee
-    // 2. rpc_client.get_recent_prioritization_fees() for priority fee data
-    // 3. Query utilization metrics
-    // 4. Get recent fee history from fee controller state
+    // 2. Query utilization metrics
+    // 3. Get recent fee history from fee controller state
+
+    // `prioritization_fee` is a per-CU price in micro-lamports, not a total
+    // in lamports, so it is summarised as-is and kept out of the lamport
+    // fields below.
+    let recent_prices: Vec<u64> = rpc_client
+        .get_recent_prioritization_fees(&[])
+        .await?
+        .into_iter()
+        .map(|sample| sample.prioritization_fee)
+        .collect();
+    let priority_fee_per_cu_stats = PriorityFeeStats::from_samples(&recent_prices);
 
     let info = CliFeeInfo {
         base_fee_lamports: 0,
@@ -262,6 +291,7 @@ async fn process_fee_info(
         priority_fee_median_lamports: 0,
         utilization_pct: 0.0,
         fee_burn_pct: 0.0,
+        priority_fee_per_cu_stats,
         recent_fee_history: vec![],
     };
 
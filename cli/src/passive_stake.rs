@@ -1,18 +1,46 @@
 use {
-    crate::cli::{CliCommand, CliCommandInfo, CliConfig, CliError, ProcessResult},
-    clap::{App, AppSettings, Arg, ArgMatches, SubCommand},
+    crate::{
+        checks::check_account_for_fee_with_commitment,
+        cli::{CliCommand, CliCommandInfo, CliConfig, CliError, ProcessResult},
+    },
+    base64::{prelude::BASE64_STANDARD, Engine},
+    clap::{value_t, value_t_or_exit, App, AppSettings, Arg, ArgMatches, SubCommand},
     serde::{Deserialize, Serialize},
     serde_json::{self, json},
+    solana_account_decoder::UiAccountEncoding,
     solana_clap_utils::{
-        input_parsers::pubkey_of,
-        input_validators::is_valid_pubkey,
-        keypair::DefaultSigner,
+        input_parsers::{lamports_of_sol, pubkey_of, signer_of},
+        input_validators::{is_amount, is_parsable, is_valid_pubkey, is_valid_signer},
+        keypair::{DefaultSigner, SignerIndex},
+        offline::{ArgsConfig, OfflineArgs, DUMP_TRANSACTION_MESSAGE, SIGN_ONLY_ARG},
+    },
+    solana_cli_output::{return_signers_data, CliSignOnlyData, OutputFormat, ReturnSignersConfig},
+    solana_instruction::{AccountMeta, Instruction},
+    solana_message::Message,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_passive_stake_program::{
+        constants::{
+            reward_rate_bps_for_tier, BPS_DENOMINATOR, MAX_STAKE_AMOUNT, PERMANENT_LOCK_DAYS,
+        },
+        instruction::PassiveStakeInstruction,
+        state::{
+            find_stake_summary_address, PassiveStakeAccount, PASSIVE_STAKE_ACCOUNT_DISCRIMINATOR,
+        },
     },
-    solana_cli_output::OutputFormat,
     solana_pubkey::Pubkey,
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
     solana_rpc_client::nonblocking::rpc_client::RpcClient,
-    std::{fmt, rc::Rc, sync::Arc},
+    solana_rpc_client_api::{
+        config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        filter::{Memcmp, RpcFilterType},
+    },
+    solana_rpc_client_nonce_utils::nonblocking::blockhash_query::BlockhashQuery,
+    solana_sdk_ids::system_program,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_system_interface::instruction as system_instruction,
+    solana_transaction::Transaction,
+    std::{fmt, fs, rc::Rc, sync::Arc},
 };
 
 // ── Lock period definitions ─────────────────────────────────────────
@@ -57,6 +85,18 @@ impl LockPeriod {
         }
     }
 
+    /// Lock tier as encoded in `InitializePassiveStake`.
+    pub fn lock_days(&self) -> u64 {
+        match self {
+            LockPeriod::None => 0,
+            LockPeriod::Days30 => 30,
+            LockPeriod::Days90 => 90,
+            LockPeriod::Days180 => 180,
+            LockPeriod::Days360 => 360,
+            LockPeriod::Permanent => PERMANENT_LOCK_DAYS,
+        }
+    }
+
     pub fn reward_multiplier(&self) -> f64 {
         match self {
             LockPeriod::None => 1.0,
@@ -73,21 +113,76 @@ fn is_valid_lock_period(s: String) -> Result<(), String> {
     LockPeriod::from_str_value(&s).map(|_| ())
 }
 
+/// Offline signing options shared by the transaction-sending subcommands.
+///
+/// With `--sign-only` the transaction is signed against a `--blockhash` and
+/// the signatures are printed instead of broadcast. A second, online
+/// invocation with the same arguments plus `--signer PUBKEY=SIGNATURE` for
+/// each offline signer rebuilds the identical transaction and sends it.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct OfflineSigning {
+    pub sign_only: bool,
+    pub dump_transaction_message: bool,
+    pub blockhash_query: BlockhashQuery,
+    /// Where `--sign-only` writes the base64-encoded transaction; printed
+    /// with the signatures when absent.
+    pub output_file: Option<String>,
+}
+
+impl OfflineSigning {
+    fn from_matches(matches: &ArgMatches<'_>) -> Self {
+        Self {
+            sign_only: matches.is_present(SIGN_ONLY_ARG.name),
+            dump_transaction_message: matches.is_present(DUMP_TRANSACTION_MESSAGE.name),
+            blockhash_query: BlockhashQuery::new_from_matches(matches),
+            output_file: matches.value_of("output_file").map(str::to_string),
+        }
+    }
+}
+
+fn output_file_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("output_file")
+        .long("output-file")
+        .value_name("FILEPATH")
+        .takes_value(true)
+        .requires(SIGN_ONLY_ARG.name)
+        .help("Write the base64-encoded signed transaction to this file in sign-only mode")
+}
+
+struct SignOnlyCreateNeedsRent {}
+impl ArgsConfig for SignOnlyCreateNeedsRent {
+    fn sign_only_arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
+        arg.requires("rent_exempt_reserve_sol")
+    }
+}
+
 // ── CLI Command Enum Variants ───────────────────────────────────────
 #[derive(Debug, PartialEq)]
 pub enum PassiveStakeCliCommand {
     Create {
         amount: f64,
         lock_period: String,
+        stake_account: SignerIndex,
+        /// Lamports allocating the stake account; fetched when absent
+        rent_exempt_reserve: Option<u64>,
+        offline: OfflineSigning,
     },
     Info {
         account_address: Pubkey,
     },
     ClaimRewards {
         account_address: Pubkey,
+        rewards_pool: Pubkey,
+        offline: OfflineSigning,
     },
     Unlock {
         account_address: Pubkey,
+        offline: OfflineSigning,
+    },
+    TransferAuthority {
+        account_address: Pubkey,
+        new_authority: SignerIndex,
+        offline: OfflineSigning,
     },
     EarlyUnlock {
         account_address: Pubkey,
@@ -95,6 +190,14 @@ pub enum PassiveStakeCliCommand {
     List {
         owner: Option<Pubkey>,
     },
+    Project {
+        amount: f64,
+        lock_period: String,
+        epochs: u64,
+        compound: bool,
+        /// Validator staking rate in basis points; fetched when absent
+        validator_rate_bps: Option<u64>,
+    },
 }
 
 // ── Output Structs ──────────────────────────────────────────────────
@@ -154,6 +257,89 @@ impl fmt::Display for CliPassiveStakeList {
     }
 }
 
+/// Summary of a passive stake position shown alongside other output, such
+/// as the positions held by a validator.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CliPassiveStakeEntry {
+    pub account: String,
+    pub amount_sol: f64,
+    pub lock_days: u64,
+    pub unclaimed_rewards_sol: f64,
+    pub auto_compound: bool,
+}
+
+impl CliPassiveStakeEntry {
+    pub fn new(address: &Pubkey, account: &PassiveStakeAccount) -> Self {
+        Self {
+            account: address.to_string(),
+            amount_sol: account.amount as f64 / LAMPORTS_PER_SOL as f64,
+            lock_days: account.lock_days,
+            unclaimed_rewards_sol: account.unclaimed_rewards as f64 / LAMPORTS_PER_SOL as f64,
+            auto_compound: account.auto_compound,
+        }
+    }
+
+    /// Human-readable lock tier.
+    pub fn lock_label(&self) -> String {
+        match self.lock_days {
+            0 => "none".to_string(),
+            PERMANENT_LOCK_DAYS => "permanent".to_string(),
+            days => format!("{days} days"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CliPassiveStakeSignOnly {
+    #[serde(flatten)]
+    pub sign_only: CliSignOnlyData,
+    /// Base64-encoded partially signed transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<String>,
+    /// File the transaction was written to instead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_file: Option<String>,
+}
+
+impl fmt::Display for CliPassiveStakeSignOnly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.sign_only)?;
+        if let Some(ref transaction) = self.transaction {
+            writeln!(f, "Transaction: {}", transaction)?;
+        }
+        if let Some(ref path) = self.transaction_file {
+            writeln!(f, "Transaction written to {}", path)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CliRewardProjection {
+    pub principal_sol: f64,
+    pub tier_days: u64,
+    pub epochs: u64,
+    pub total_rewards_sol: f64,
+    pub effective_apy_pct: f64,
+}
+
+impl fmt::Display for CliRewardProjection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Passive Stake Reward Projection")?;
+        writeln!(f, "  Principal:          {} SOL", self.principal_sol)?;
+        if self.tier_days == PERMANENT_LOCK_DAYS {
+            writeln!(f, "  Lock Period:        permanent")?;
+        } else {
+            writeln!(f, "  Lock Period:        {} days", self.tier_days)?;
+        }
+        writeln!(f, "  Epochs:             {}", self.epochs)?;
+        writeln!(f, "  Total Rewards:      {:.9} SOL", self.total_rewards_sol)?;
+        writeln!(f, "  Effective APY:      {:.4}%", self.effective_apy_pct)?;
+        Ok(())
+    }
+}
+
 // ── Subcommand Definition (clap) ────────────────────────────────────
 pub trait PassiveStakeSubCommands {
     fn passive_stake_subcommands(self) -> Self;
@@ -186,7 +372,32 @@ impl PassiveStakeSubCommands for App<'_, '_> {
                                 .help(
                                     "Lock period: 0 (flexible), 30, 90, 180, 360, or permanent",
                                 ),
-                        ),
+                        )
+                        .arg(
+                            Arg::with_name("stake_account")
+                                .long("stake-account")
+                                .value_name("STAKE_ACCOUNT_KEYPAIR")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_signer)
+                                .help(
+                                    "Keypair of the new passive stake account. When completing \
+                                     an offline-signed transaction, its address",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("rent_exempt_reserve_sol")
+                                .long("rent-exempt-reserve-sol")
+                                .value_name("AMOUNT")
+                                .takes_value(true)
+                                .validator(is_amount)
+                                .help(
+                                    "Rent-exempt amount allocating the new stake account, in \
+                                     SOL. Required for offline signing.",
+                                ),
+                        )
+                        .offline_args_config(&SignOnlyCreateNeedsRent {})
+                        .arg(output_file_arg()),
                 )
                 .subcommand(
                     SubCommand::with_name("info")
@@ -212,7 +423,18 @@ impl PassiveStakeSubCommands for App<'_, '_> {
                                 .required(true)
                                 .validator(is_valid_pubkey)
                                 .help("Address of the passive stake account"),
-                        ),
+                        )
+                        .arg(
+                            Arg::with_name("rewards_pool")
+                                .long("rewards-pool")
+                                .value_name("ADDRESS")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_pubkey)
+                                .help("Rewards pool account the rewards are paid from"),
+                        )
+                        .offline_args()
+                        .arg(output_file_arg()),
                 )
                 .subcommand(
                     SubCommand::with_name("unlock")
@@ -225,7 +447,33 @@ impl PassiveStakeSubCommands for App<'_, '_> {
                                 .required(true)
                                 .validator(is_valid_pubkey)
                                 .help("Address of the passive stake account"),
-                        ),
+                        )
+                        .offline_args()
+                        .arg(output_file_arg()),
+                )
+                .subcommand(
+                    SubCommand::with_name("transfer-authority")
+                        .about("Move a passive stake position, lock included, to a new authority")
+                        .arg(
+                            Arg::with_name("account_address")
+                                .index(1)
+                                .value_name("ACCOUNT_ADDRESS")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_pubkey)
+                                .help("Address of the passive stake account"),
+                        )
+                        .arg(
+                            Arg::with_name("new_authority")
+                                .long("new-authority")
+                                .value_name("KEYPAIR")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_signer)
+                                .help("Keypair of the new authority; it must sign the transfer"),
+                        )
+                        .offline_args()
+                        .arg(output_file_arg()),
                 )
                 .subcommand(
                     SubCommand::with_name("early-unlock")
@@ -265,6 +513,54 @@ impl PassiveStakeSubCommands for App<'_, '_> {
                                      [default: current keypair]",
                                 ),
                         ),
+                )
+                .subcommand(
+                    SubCommand::with_name("project")
+                        .about("Project the rewards of a passive stake position")
+                        .arg(
+                            Arg::with_name("amount")
+                                .long("amount")
+                                .value_name("SOL")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_amount)
+                                .help("Amount of SOL to stake"),
+                        )
+                        .arg(
+                            Arg::with_name("tier")
+                                .long("tier")
+                                .value_name("DAYS")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_lock_period)
+                                .help("Lock period: 0 (flexible), 30, 90, 180, 360, or permanent"),
+                        )
+                        .arg(
+                            Arg::with_name("epochs")
+                                .long("epochs")
+                                .value_name("N")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_parsable::<u64>)
+                                .help("Number of epochs to project over"),
+                        )
+                        .arg(
+                            Arg::with_name("compound")
+                                .long("compound")
+                                .takes_value(false)
+                                .help("Reinvest the rewards every epoch"),
+                        )
+                        .arg(
+                            Arg::with_name("validator_rate_bps")
+                                .long("validator-rate-bps")
+                                .value_name("BPS")
+                                .takes_value(true)
+                                .validator(is_parsable::<u64>)
+                                .help(
+                                    "Validator staking rate in basis points \
+                                     [default: the cluster's current validator inflation rate]",
+                                ),
+                        ),
                 ),
         )
     }
@@ -273,8 +569,8 @@ impl PassiveStakeSubCommands for App<'_, '_> {
 // ── Argument Parsing ────────────────────────────────────────────────
 pub fn parse_passive_stake_command(
     matches: &ArgMatches<'_>,
-    _default_signer: &DefaultSigner,
-    _wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+    default_signer: &DefaultSigner,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
 ) -> Result<CliCommandInfo, CliError> {
     match matches.subcommand() {
         ("create", Some(matches)) => {
@@ -284,12 +580,23 @@ pub fn parse_passive_stake_command(
                 .parse()
                 .map_err(|_| CliError::BadParameter("Invalid amount".to_string()))?;
             let lock_period = matches.value_of("lock_days").unwrap().to_string();
-            Ok(CliCommandInfo::without_signers(
-                CliCommand::PassiveStake(PassiveStakeCliCommand::Create {
+            let (stake_account, stake_account_pubkey) =
+                signer_of(matches, "stake_account", wallet_manager)?;
+            let signer_info = default_signer.generate_unique_signers(
+                vec![None, stake_account],
+                matches,
+                wallet_manager,
+            )?;
+            Ok(CliCommandInfo {
+                command: CliCommand::PassiveStake(PassiveStakeCliCommand::Create {
                     amount,
                     lock_period,
+                    stake_account: signer_info.index_of(stake_account_pubkey).unwrap(),
+                    rent_exempt_reserve: lamports_of_sol(matches, "rent_exempt_reserve_sol"),
+                    offline: OfflineSigning::from_matches(matches),
                 }),
-            ))
+                signers: signer_info.signers,
+            })
         }
         ("info", Some(matches)) => {
             let account_address = pubkey_of(matches, "account_address").unwrap();
@@ -299,15 +606,47 @@ pub fn parse_passive_stake_command(
         }
         ("claim-rewards", Some(matches)) => {
             let account_address = pubkey_of(matches, "account_address").unwrap();
-            Ok(CliCommandInfo::without_signers(
-                CliCommand::PassiveStake(PassiveStakeCliCommand::ClaimRewards { account_address }),
-            ))
+            let rewards_pool = pubkey_of(matches, "rewards_pool").unwrap();
+            let signer_info =
+                default_signer.generate_unique_signers(vec![None], matches, wallet_manager)?;
+            Ok(CliCommandInfo {
+                command: CliCommand::PassiveStake(PassiveStakeCliCommand::ClaimRewards {
+                    account_address,
+                    rewards_pool,
+                    offline: OfflineSigning::from_matches(matches),
+                }),
+                signers: signer_info.signers,
+            })
         }
         ("unlock", Some(matches)) => {
             let account_address = pubkey_of(matches, "account_address").unwrap();
-            Ok(CliCommandInfo::without_signers(
-                CliCommand::PassiveStake(PassiveStakeCliCommand::Unlock { account_address }),
-            ))
+            let signer_info =
+                default_signer.generate_unique_signers(vec![None], matches, wallet_manager)?;
+            Ok(CliCommandInfo {
+                command: CliCommand::PassiveStake(PassiveStakeCliCommand::Unlock {
+                    account_address,
+                    offline: OfflineSigning::from_matches(matches),
+                }),
+                signers: signer_info.signers,
+            })
+        }
+        ("transfer-authority", Some(matches)) => {
+            let account_address = pubkey_of(matches, "account_address").unwrap();
+            let (new_authority, new_authority_pubkey) =
+                signer_of(matches, "new_authority", wallet_manager)?;
+            let signer_info = default_signer.generate_unique_signers(
+                vec![None, new_authority],
+                matches,
+                wallet_manager,
+            )?;
+            Ok(CliCommandInfo {
+                command: CliCommand::PassiveStake(PassiveStakeCliCommand::TransferAuthority {
+                    account_address,
+                    new_authority: signer_info.index_of(new_authority_pubkey).unwrap(),
+                    offline: OfflineSigning::from_matches(matches),
+                }),
+                signers: signer_info.signers,
+            })
         }
         ("early-unlock", Some(matches)) => {
             let account_address = pubkey_of(matches, "account_address").unwrap();
@@ -321,6 +660,25 @@ pub fn parse_passive_stake_command(
                 CliCommand::PassiveStake(PassiveStakeCliCommand::List { owner }),
             ))
         }
+        ("project", Some(matches)) => {
+            let amount: f64 = matches
+                .value_of("amount")
+                .unwrap()
+                .parse()
+                .map_err(|_| CliError::BadParameter("Invalid amount".to_string()))?;
+            let lock_period = matches.value_of("tier").unwrap().to_string();
+            let epochs = value_t_or_exit!(matches, "epochs", u64);
+            let validator_rate_bps = value_t!(matches, "validator_rate_bps", u64).ok();
+            Ok(CliCommandInfo::without_signers(CliCommand::PassiveStake(
+                PassiveStakeCliCommand::Project {
+                    amount,
+                    lock_period,
+                    epochs,
+                    compound: matches.is_present("compound"),
+                    validator_rate_bps,
+                },
+            )))
+        }
         _ => unreachable!(),
     }
 }
@@ -332,17 +690,58 @@ pub async fn process_passive_stake_command(
     command: &PassiveStakeCliCommand,
 ) -> ProcessResult {
     match command {
-        PassiveStakeCliCommand::Create { amount, lock_period } => {
-            process_passive_stake_create(rpc_client, config, *amount, lock_period).await
+        PassiveStakeCliCommand::Create {
+            amount,
+            lock_period,
+            stake_account,
+            rent_exempt_reserve,
+            offline,
+        } => {
+            process_passive_stake_create(
+                rpc_client,
+                config,
+                *amount,
+                lock_period,
+                *stake_account,
+                *rent_exempt_reserve,
+                offline,
+            )
+            .await
         }
         PassiveStakeCliCommand::Info { account_address } => {
             process_passive_stake_info(rpc_client, config, account_address).await
         }
-        PassiveStakeCliCommand::ClaimRewards { account_address } => {
-            process_passive_stake_claim_rewards(rpc_client, config, account_address).await
+        PassiveStakeCliCommand::ClaimRewards {
+            account_address,
+            rewards_pool,
+            offline,
+        } => {
+            process_passive_stake_claim_rewards(
+                rpc_client,
+                config,
+                account_address,
+                rewards_pool,
+                offline,
+            )
+            .await
         }
-        PassiveStakeCliCommand::Unlock { account_address } => {
-            process_passive_stake_unlock(rpc_client, config, account_address).await
+        PassiveStakeCliCommand::Unlock {
+            account_address,
+            offline,
+        } => process_passive_stake_unlock(rpc_client, config, account_address, offline).await,
+        PassiveStakeCliCommand::TransferAuthority {
+            account_address,
+            new_authority,
+            offline,
+        } => {
+            process_passive_stake_transfer_authority(
+                rpc_client,
+                config,
+                account_address,
+                *new_authority,
+                offline,
+            )
+            .await
         }
         PassiveStakeCliCommand::EarlyUnlock { account_address } => {
             process_passive_stake_early_unlock(rpc_client, config, account_address).await
@@ -350,29 +749,176 @@ pub async fn process_passive_stake_command(
         PassiveStakeCliCommand::List { owner } => {
             process_passive_stake_list(rpc_client, config, owner.as_ref()).await
         }
+        PassiveStakeCliCommand::Project {
+            amount,
+            lock_period,
+            epochs,
+            compound,
+            validator_rate_bps,
+        } => {
+            process_passive_stake_project(
+                rpc_client,
+                config,
+                *amount,
+                lock_period,
+                *epochs,
+                *compound,
+                *validator_rate_bps,
+            )
+            .await
+        }
+    }
+}
+
+/// Sign `message` with the configured signers against the blockhash from
+/// `offline`. In sign-only mode missing signatures are left blank.
+async fn sign_transaction(
+    rpc_client: &RpcClient,
+    config: &CliConfig<'_>,
+    message: Message,
+    offline: &OfflineSigning,
+) -> Result<Transaction, Box<dyn std::error::Error>> {
+    let recent_blockhash = offline
+        .blockhash_query
+        .get_blockhash(rpc_client, config.commitment)
+        .await?;
+    let mut tx = Transaction::new_unsigned(message);
+    if offline.sign_only {
+        tx.try_partial_sign(&config.signers, recent_blockhash)?;
+    } else {
+        tx.try_sign(&config.signers, recent_blockhash)?;
+    }
+    Ok(tx)
+}
+
+/// Output of `--sign-only`: the signatures to pass back with `--signer`,
+/// and the base64-encoded transaction on stdout or in `--output-file`.
+fn return_sign_only(
+    tx: &Transaction,
+    config: &CliConfig<'_>,
+    offline: &OfflineSigning,
+) -> ProcessResult {
+    let sign_only = return_signers_data(
+        tx,
+        &ReturnSignersConfig {
+            dump_transaction_message: offline.dump_transaction_message,
+        },
+    );
+    let encoded = BASE64_STANDARD.encode(bincode::serialize(tx)?);
+    let transaction = match &offline.output_file {
+        Some(path) => {
+            fs::write(path, encoded)?;
+            None
+        }
+        None => Some(encoded),
+    };
+    let output = CliPassiveStakeSignOnly {
+        sign_only,
+        transaction,
+        transaction_file: offline.output_file.clone(),
+    };
+
+    match config.output_format {
+        OutputFormat::Json | OutputFormat::JsonCompact => {
+            Ok(serde_json::to_string_pretty(&output)?)
+        }
+        _ => Ok(format!("{}", output)),
     }
 }
 
+async fn send_transaction(
+    rpc_client: &RpcClient,
+    config: &CliConfig<'_>,
+    tx: &Transaction,
+) -> Result<Signature, Box<dyn std::error::Error>> {
+    check_account_for_fee_with_commitment(
+        rpc_client,
+        &tx.message.account_keys[0],
+        &tx.message,
+        config.commitment,
+    )
+    .await?;
+    Ok(rpc_client
+        .send_and_confirm_transaction_with_spinner_and_config(
+            tx,
+            config.commitment,
+            config.send_transaction_config,
+        )
+        .await?)
+}
+
+fn create_passive_stake_instructions(
+    authority: &Pubkey,
+    stake_account: &Pubkey,
+    rent_exempt_reserve: u64,
+    lock_days: u64,
+    amount: u64,
+) -> Vec<Instruction> {
+    vec![
+        system_instruction::create_account(
+            authority,
+            stake_account,
+            rent_exempt_reserve,
+            PassiveStakeAccount::SERIALIZED_SIZE as u64,
+            &solana_passive_stake_program::id(),
+        ),
+        Instruction::new_with_bincode(
+            solana_passive_stake_program::id(),
+            &PassiveStakeInstruction::InitializePassiveStake { lock_days, amount },
+            vec![
+                AccountMeta::new(*authority, true),
+                AccountMeta::new(*stake_account, false),
+                AccountMeta::new(find_stake_summary_address(authority).0, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        ),
+    ]
+}
+
 async fn process_passive_stake_create(
-    _rpc_client: &Arc<RpcClient>,
+    rpc_client: &Arc<RpcClient>,
     config: &CliConfig<'_>,
     amount: f64,
     lock_period: &str,
+    stake_account: SignerIndex,
+    rent_exempt_reserve: Option<u64>,
+    offline: &OfflineSigning,
 ) -> ProcessResult {
     let period = LockPeriod::from_str_value(lock_period)
         .map_err(|e| CliError::BadParameter(e))?;
+    let authority = config.signers[0].pubkey();
+    let stake_account = config.signers[stake_account].pubkey();
+    let rent_exempt_reserve = match rent_exempt_reserve {
+        Some(lamports) => lamports,
+        None => {
+            rpc_client
+                .get_minimum_balance_for_rent_exemption(PassiveStakeAccount::SERIALIZED_SIZE)
+                .await?
+        }
+    };
 
-    // TODO: Build and send PassiveStake::Create instruction via rpc_client
-    // 1. Derive passive stake PDA from owner pubkey + nonce
-    // 2. Create instruction with amount (in lamports) and lock_period
-    // 3. Send transaction and confirm
+    let instructions = create_passive_stake_instructions(
+        &authority,
+        &stake_account,
+        rent_exempt_reserve,
+        period.lock_days(),
+        (amount * LAMPORTS_PER_SOL as f64).round() as u64,
+    );
+    let message = Message::new(&instructions, Some(&authority));
+    let tx = sign_transaction(rpc_client, config, message, offline).await?;
+    if offline.sign_only {
+        return return_sign_only(&tx, config, offline);
+    }
+    let signature = send_transaction(rpc_client, config, &tx).await?;
 
     let result = json!({
         "status": "ok",
         "message": format!("Passive stake account created with {} SOL, lock: {}", amount, period),
+        "account": stake_account.to_string(),
         "amount_sol": amount,
         "lock_period": lock_period,
         "reward_multiplier": period.reward_multiplier(),
+        "signature": signature.to_string(),
     });
 
     match config.output_format {
@@ -380,8 +926,8 @@ async fn process_passive_stake_create(
             Ok(serde_json::to_string_pretty(&result)?)
         }
         _ => Ok(format!(
-            "Passive stake account created.\n  Amount:     {} SOL\n  Lock:       {}\n  Multiplier: {}x",
-            amount, period, period.reward_multiplier()
+            "Passive stake account created.\n  Account:    {}\n  Amount:     {} SOL\n  Lock:       {}\n  Multiplier: {}x\n  Signature:  {}",
+            stake_account, amount, period, period.reward_multiplier(), signature
         )),
     }
 }
@@ -416,20 +962,33 @@ async fn process_passive_stake_info(
 }
 
 async fn process_passive_stake_claim_rewards(
-    _rpc_client: &Arc<RpcClient>,
+    rpc_client: &Arc<RpcClient>,
     config: &CliConfig<'_>,
     account_address: &Pubkey,
+    rewards_pool: &Pubkey,
+    offline: &OfflineSigning,
 ) -> ProcessResult {
-    // TODO: Build and send PassiveStake::ClaimRewards instruction
-    // 1. Fetch account to verify it exists and has pending rewards
-    // 2. Build ClaimRewards instruction
-    // 3. Send transaction and confirm
-    // 4. Return claimed amount
+    let authority = config.signers[0].pubkey();
+    let instruction = Instruction::new_with_bincode(
+        solana_passive_stake_program::id(),
+        &PassiveStakeInstruction::ClaimRewards,
+        vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(*account_address, false),
+            AccountMeta::new(*rewards_pool, false),
+        ],
+    );
+    let message = Message::new(&[instruction], Some(&authority));
+    let tx = sign_transaction(rpc_client, config, message, offline).await?;
+    if offline.sign_only {
+        return return_sign_only(&tx, config, offline);
+    }
+    let signature = send_transaction(rpc_client, config, &tx).await?;
 
     let result = json!({
         "status": "ok",
         "account": account_address.to_string(),
-        "claimed_sol": 0.0, // TODO: actual claimed amount
+        "signature": signature.to_string(),
     });
 
     match config.output_format {
@@ -437,26 +996,40 @@ async fn process_passive_stake_claim_rewards(
             Ok(serde_json::to_string_pretty(&result)?)
         }
         _ => Ok(format!(
-            "Rewards claimed for passive stake account {}",
-            account_address
+            "Rewards claimed for passive stake account {}\n  Signature: {}",
+            account_address, signature
         )),
     }
 }
 
 async fn process_passive_stake_unlock(
-    _rpc_client: &Arc<RpcClient>,
+    rpc_client: &Arc<RpcClient>,
     config: &CliConfig<'_>,
     account_address: &Pubkey,
+    offline: &OfflineSigning,
 ) -> ProcessResult {
-    // TODO: Build and send PassiveStake::Unlock instruction
-    // 1. Fetch account to verify lock period has expired
-    // 2. Build Unlock instruction
-    // 3. Send transaction and confirm
+    let authority = config.signers[0].pubkey();
+    let instruction = Instruction::new_with_bincode(
+        solana_passive_stake_program::id(),
+        &PassiveStakeInstruction::Unlock,
+        vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(*account_address, false),
+            AccountMeta::new(find_stake_summary_address(&authority).0, false),
+        ],
+    );
+    let message = Message::new(&[instruction], Some(&authority));
+    let tx = sign_transaction(rpc_client, config, message, offline).await?;
+    if offline.sign_only {
+        return return_sign_only(&tx, config, offline);
+    }
+    let signature = send_transaction(rpc_client, config, &tx).await?;
 
     let result = json!({
         "status": "ok",
         "account": account_address.to_string(),
         "message": "Passive stake account unlocked",
+        "signature": signature.to_string(),
     });
 
     match config.output_format {
@@ -464,8 +1037,58 @@ async fn process_passive_stake_unlock(
             Ok(serde_json::to_string_pretty(&result)?)
         }
         _ => Ok(format!(
-            "Passive stake account {} unlocked successfully",
-            account_address
+            "Passive stake account {} unlocked successfully\n  Signature: {}",
+            account_address, signature
+        )),
+    }
+}
+
+async fn process_passive_stake_transfer_authority(
+    rpc_client: &Arc<RpcClient>,
+    config: &CliConfig<'_>,
+    account_address: &Pubkey,
+    new_authority: SignerIndex,
+    offline: &OfflineSigning,
+) -> ProcessResult {
+    let authority = config.signers[0].pubkey();
+    let new_authority = config.signers[new_authority].pubkey();
+
+    let instruction = Instruction::new_with_bincode(
+        solana_passive_stake_program::id(),
+        &PassiveStakeInstruction::TransferStakeAuthority {
+            stake_account: *account_address,
+            new_authority,
+        },
+        vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(new_authority, true),
+            AccountMeta::new(*account_address, false),
+            AccountMeta::new(find_stake_summary_address(&authority).0, false),
+            AccountMeta::new(find_stake_summary_address(&new_authority).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+    let message = Message::new(&[instruction], Some(&authority));
+    let tx = sign_transaction(rpc_client, config, message, offline).await?;
+    if offline.sign_only {
+        return return_sign_only(&tx, config, offline);
+    }
+    let signature = send_transaction(rpc_client, config, &tx).await?;
+
+    let result = json!({
+        "status": "ok",
+        "account": account_address.to_string(),
+        "new_authority": new_authority.to_string(),
+        "signature": signature.to_string(),
+    });
+
+    match config.output_format {
+        OutputFormat::Json | OutputFormat::JsonCompact => {
+            Ok(serde_json::to_string_pretty(&result)?)
+        }
+        _ => Ok(format!(
+            "Passive stake account {} transferred to {}\n  Signature: {}",
+            account_address, new_authority, signature
         )),
     }
 }
@@ -524,3 +1147,363 @@ async fn process_passive_stake_list(
         _ => Ok(format!("{}", list)),
     }
 }
+
+/// Fetch every passive stake account whose authority is `authority`,
+/// ordered by address.
+pub async fn fetch_passive_stakes_by_authority(
+    rpc_client: &RpcClient,
+    authority: &Pubkey,
+) -> Result<Vec<(Pubkey, PassiveStakeAccount)>, Box<dyn std::error::Error>> {
+    let accounts = rpc_client
+        .get_program_ui_accounts_with_config(
+            &solana_passive_stake_program::id(),
+            RpcProgramAccountsConfig {
+                // No data-size filter: positions still in the legacy layout
+                // are shorter than `PassiveStakeAccount::SERIALIZED_SIZE`.
+                filters: Some(vec![
+                    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                        0,
+                        &[PASSIVE_STAKE_ACCOUNT_DISCRIMINATOR],
+                    )),
+                    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(1, authority.as_ref())),
+                ]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        )
+        .await?;
+    let mut stakes: Vec<(Pubkey, PassiveStakeAccount)> = accounts
+        .into_iter()
+        .filter_map(|(address, ui_account)| {
+            let account = PassiveStakeAccount::deserialize(&ui_account.to_account()?.data).ok()?;
+            Some((address, account))
+        })
+        .collect();
+    stakes.sort_by_key(|(address, _)| *address);
+    Ok(stakes)
+}
+
+/// Epochs per year assumed by the program's per-epoch reward formula.
+const EPOCHS_PER_YEAR: u64 = 365;
+
+/// Rewards, in lamports, earned by `principal` lamports at `lock_days` over
+/// `epochs` epochs.
+///
+/// Each epoch pays `amount × validator_rate × tier_rate / (BPS² × 365)`, as
+/// `CalculateEpochRewards` does. With `compound` every epoch's reward is
+/// added to the principal, except where a permanent position would pass
+/// `MAX_STAKE_AMOUNT`.
+pub fn project_rewards(
+    principal: u64,
+    lock_days: u64,
+    validator_rate_bps: u64,
+    epochs: u64,
+    compound: bool,
+) -> u64 {
+    let tier_rate_bps = reward_rate_bps_for_tier(lock_days).unwrap_or(0) as u128;
+    let denominator = (BPS_DENOMINATOR as u128).pow(2) * EPOCHS_PER_YEAR as u128;
+    let reward_for = |amount: u64| {
+        (amount as u128 * validator_rate_bps as u128 * tier_rate_bps / denominator) as u64
+    };
+
+    if !compound {
+        return reward_for(principal).saturating_mul(epochs);
+    }
+    let mut amount = principal;
+    let mut total_rewards = 0u64;
+    for _ in 0..epochs {
+        let reward = reward_for(amount);
+        total_rewards = total_rewards.saturating_add(reward);
+        if lock_days != PERMANENT_LOCK_DAYS || amount.saturating_add(reward) <= MAX_STAKE_AMOUNT {
+            amount = amount.saturating_add(reward);
+        }
+    }
+    total_rewards
+}
+
+/// Build the projection output. The APY annualizes the projected return,
+/// compounded per epoch when `compound` is set.
+fn reward_projection(
+    principal: u64,
+    lock_days: u64,
+    validator_rate_bps: u64,
+    epochs: u64,
+    compound: bool,
+) -> CliRewardProjection {
+    let total_rewards = project_rewards(principal, lock_days, validator_rate_bps, epochs, compound);
+    let period_return = if principal == 0 {
+        0.0
+    } else {
+        total_rewards as f64 / principal as f64
+    };
+    let years = epochs as f64 / EPOCHS_PER_YEAR as f64;
+    let effective_apy = if epochs == 0 {
+        0.0
+    } else if compound {
+        (1.0 + period_return).powf(1.0 / years) - 1.0
+    } else {
+        period_return / years
+    };
+    CliRewardProjection {
+        principal_sol: principal as f64 / LAMPORTS_PER_SOL as f64,
+        tier_days: lock_days,
+        epochs,
+        total_rewards_sol: total_rewards as f64 / LAMPORTS_PER_SOL as f64,
+        effective_apy_pct: effective_apy * 100.0,
+    }
+}
+
+async fn process_passive_stake_project(
+    rpc_client: &Arc<RpcClient>,
+    config: &CliConfig<'_>,
+    amount: f64,
+    lock_period: &str,
+    epochs: u64,
+    compound: bool,
+    validator_rate_bps: Option<u64>,
+) -> ProcessResult {
+    let period = LockPeriod::from_str_value(lock_period).map_err(CliError::BadParameter)?;
+    let validator_rate_bps = match validator_rate_bps {
+        Some(rate) => rate,
+        None => {
+            let inflation = rpc_client.get_inflation_rate().await?;
+            (inflation.validator * BPS_DENOMINATOR as f64).round() as u64
+        }
+    };
+    let projection = reward_projection(
+        (amount * LAMPORTS_PER_SOL as f64).round() as u64,
+        period.lock_days(),
+        validator_rate_bps,
+        epochs,
+        compound,
+    );
+
+    match config.output_format {
+        OutputFormat::Json | OutputFormat::JsonCompact => {
+            Ok(serde_json::to_string_pretty(&projection)?)
+        }
+        _ => Ok(format!("{}", projection)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_hash::Hash,
+        solana_keypair::Keypair,
+        solana_presigner::Presigner,
+        solana_rpc_client::mock_sender::MocksMap,
+        solana_rpc_client_api::{
+            request::RpcRequest,
+            response::{Response, RpcResponseContext},
+        },
+    };
+
+    #[test]
+    fn test_project_rewards_all_tiers() {
+        let principal = 1_000 * LAMPORTS_PER_SOL;
+        // With a 5% validator rate each tier earns its share of that rate.
+        let validator_rate_bps = 500;
+        for (lock_days, apy_bps) in [
+            (0, 25),
+            (30, 50),
+            (90, 100),
+            (180, 150),
+            (360, 250),
+            (PERMANENT_LOCK_DAYS, 600),
+        ] {
+            let expected = principal / BPS_DENOMINATOR * apy_bps;
+            let rewards = project_rewards(
+                principal,
+                lock_days,
+                validator_rate_bps,
+                EPOCHS_PER_YEAR,
+                false,
+            );
+            // Each epoch's reward rounds down by less than a lamport.
+            assert!(rewards <= expected && expected - rewards < EPOCHS_PER_YEAR);
+
+            let projection = reward_projection(
+                principal,
+                lock_days,
+                validator_rate_bps,
+                EPOCHS_PER_YEAR,
+                false,
+            );
+            assert_eq!(projection.principal_sol, 1_000.0);
+            assert_eq!(projection.tier_days, lock_days);
+            assert_eq!(projection.epochs, EPOCHS_PER_YEAR);
+            assert_eq!(
+                projection.total_rewards_sol,
+                rewards as f64 / LAMPORTS_PER_SOL as f64
+            );
+            assert!((projection.effective_apy_pct - apy_bps as f64 / 100.0).abs() < 1e-6);
+
+            let compounded = project_rewards(
+                principal,
+                lock_days,
+                validator_rate_bps,
+                EPOCHS_PER_YEAR,
+                true,
+            );
+            assert!(compounded > rewards);
+            let apy = apy_bps as f64 / BPS_DENOMINATOR as f64;
+            let compounded_apy =
+                (1.0 + apy / EPOCHS_PER_YEAR as f64).powi(EPOCHS_PER_YEAR as i32) - 1.0;
+            let projection = reward_projection(
+                principal,
+                lock_days,
+                validator_rate_bps,
+                EPOCHS_PER_YEAR,
+                true,
+            );
+            assert!((projection.effective_apy_pct - compounded_apy * 100.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_project_rewards_edge_cases() {
+        // Nothing accrues over zero epochs.
+        let projection = reward_projection(LAMPORTS_PER_SOL, 90, 500, 0, true);
+        assert_eq!(projection.total_rewards_sol, 0.0);
+        assert_eq!(projection.effective_apy_pct, 0.0);
+
+        // Permanent positions at the protocol maximum cannot compound.
+        assert_eq!(
+            project_rewards(MAX_STAKE_AMOUNT, PERMANENT_LOCK_DAYS, 500, 30, true),
+            project_rewards(MAX_STAKE_AMOUNT, PERMANENT_LOCK_DAYS, 500, 30, false)
+        );
+        // Timed locks have no such cap.
+        assert!(
+            project_rewards(MAX_STAKE_AMOUNT, 360, 500, 30, true)
+                > project_rewards(MAX_STAKE_AMOUNT, 360, 500, 30, false)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sign_only_create_matches_online_transaction() {
+        let authority = Keypair::new();
+        let stake_account = Keypair::new();
+        let blockhash = Hash::new_from_array([3; 32]);
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::IsBlockhashValid,
+            json!(Response {
+                context: RpcResponseContext {
+                    slot: 1,
+                    api_version: None,
+                },
+                value: true,
+            }),
+        );
+        let rpc_client = Arc::new(RpcClient::new_mock_with_mocks_map("succeeds", mocks));
+        let mut config = CliConfig {
+            output_format: OutputFormat::Json,
+            ..CliConfig::default()
+        };
+
+        // Air-gapped machine: sign against a supplied blockhash.
+        config.signers = vec![&authority, &stake_account];
+        let sign_only = OfflineSigning {
+            sign_only: true,
+            blockhash_query: BlockhashQuery::new(Some(blockhash), true, None),
+            ..OfflineSigning::default()
+        };
+        let output =
+            process_passive_stake_create(&rpc_client, &config, 10.0, "90", 1, Some(20), &sign_only)
+                .await
+                .unwrap();
+        let output: CliPassiveStakeSignOnly = serde_json::from_str(&output).unwrap();
+        assert_eq!(output.sign_only.blockhash, blockhash.to_string());
+        assert!(output.sign_only.absent.is_empty());
+        assert!(output.sign_only.bad_sig.is_empty());
+        let offline_tx: Transaction =
+            bincode::deserialize(&BASE64_STANDARD.decode(output.transaction.unwrap()).unwrap())
+                .unwrap();
+
+        // Byte-identical to signing the same transaction online.
+        let instructions = create_passive_stake_instructions(
+            &authority.pubkey(),
+            &stake_account.pubkey(),
+            20,
+            90,
+            10 * LAMPORTS_PER_SOL,
+        );
+        let online_tx = Transaction::new(
+            &[&authority, &stake_account],
+            Message::new(&instructions, Some(&authority.pubkey())),
+            blockhash,
+        );
+        assert_eq!(
+            bincode::serialize(&offline_tx).unwrap(),
+            bincode::serialize(&online_tx).unwrap()
+        );
+        offline_tx.verify().unwrap();
+
+        // Online machine: complete the transaction from `--signer` pairs.
+        let presigners: Vec<Presigner> = output
+            .sign_only
+            .signers
+            .iter()
+            .map(|signer| {
+                let (pubkey, signature) = signer.split_once('=').unwrap();
+                Presigner::new(&pubkey.parse().unwrap(), &signature.parse().unwrap())
+            })
+            .collect();
+        assert_eq!(presigners.len(), 2);
+        assert_eq!(presigners[0].pubkey(), authority.pubkey());
+        config.signers = vec![&presigners[0], &presigners[1]];
+        let online = OfflineSigning {
+            blockhash_query: BlockhashQuery::new(Some(blockhash), false, None),
+            ..OfflineSigning::default()
+        };
+        let output =
+            process_passive_stake_create(&rpc_client, &config, 10.0, "90", 1, Some(20), &online)
+                .await
+                .unwrap();
+        let result: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(result["signature"], offline_tx.signatures[0].to_string());
+        assert_eq!(result["account"], stake_account.pubkey().to_string());
+    }
+
+    #[tokio::test]
+    async fn test_sign_only_writes_transaction_to_file() {
+        let authority = Keypair::new();
+        let account_address = Pubkey::new_unique();
+        let blockhash = Hash::new_from_array([4; 32]);
+        let rpc_client = Arc::new(RpcClient::new_mock("succeeds".to_string()));
+        let output_dir = tempfile::tempdir().unwrap();
+        let output_file = output_dir.path().join("unlock.tx");
+
+        let mut config = CliConfig {
+            output_format: OutputFormat::Json,
+            ..CliConfig::default()
+        };
+        config.signers = vec![&authority];
+        let offline = OfflineSigning {
+            sign_only: true,
+            blockhash_query: BlockhashQuery::new(Some(blockhash), true, None),
+            output_file: Some(output_file.to_str().unwrap().to_string()),
+            ..OfflineSigning::default()
+        };
+        let output = process_passive_stake_unlock(&rpc_client, &config, &account_address, &offline)
+            .await
+            .unwrap();
+        let output: CliPassiveStakeSignOnly = serde_json::from_str(&output).unwrap();
+        assert!(output.transaction.is_none());
+
+        let encoded = fs::read_to_string(&output_file).unwrap();
+        let tx: Transaction =
+            bincode::deserialize(&BASE64_STANDARD.decode(encoded).unwrap()).unwrap();
+        tx.verify().unwrap();
+        assert_eq!(tx.message.recent_blockhash, blockhash);
+        assert_eq!(
+            output.sign_only.signers,
+            vec![format!("{}={}", authority.pubkey(), tx.signatures[0])]
+        );
+    }
+}
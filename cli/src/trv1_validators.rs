@@ -1,20 +1,34 @@
 use {
-    crate::cli::{CliCommand, CliCommandInfo, CliConfig, CliError, ProcessResult},
-    clap::{App, AppSettings, Arg, ArgMatches, SubCommand},
+    crate::{
+        cli::{CliCommand, CliCommandInfo, CliConfig, CliError, ProcessResult},
+        passive_stake::{fetch_passive_stakes_by_authority, CliPassiveStakeEntry},
+    },
+    clap::{value_t, App, AppSettings, Arg, ArgMatches, SubCommand},
     serde::{Deserialize, Serialize},
     serde_json::{self, json},
     solana_clap_utils::{
         input_parsers::pubkey_of,
-        input_validators::is_valid_pubkey,
+        input_validators::{is_parsable, is_valid_pubkey},
         keypair::DefaultSigner,
     },
     solana_cli_output::OutputFormat,
+    solana_native_token::LAMPORTS_PER_SOL,
     solana_pubkey::Pubkey,
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
     solana_rpc_client::nonblocking::rpc_client::RpcClient,
-    std::{fmt, rc::Rc, sync::Arc},
+    solana_rpc_client_api::{
+        config::{RpcBlockProductionConfig, RpcGetVoteAccountsConfig},
+        request::RpcRequest,
+        response::RpcVoteAccountStatus,
+        trv1_response::JailStatus,
+    },
+    std::{fmt, rc::Rc, str::FromStr, sync::Arc, time::Duration},
 };
 
+/// Prometheus endpoint polled by `validator dashboard` when `--metrics-url`
+/// is not given.
+pub const DEFAULT_METRICS_URL: &str = "http://127.0.0.1:9090/metrics";
+
 // ── CLI Command Enum Variants ───────────────────────────────────────
 #[derive(Debug, PartialEq)]
 pub enum Trv1ValidatorsCliCommand {
@@ -24,6 +38,13 @@ pub enum Trv1ValidatorsCliCommand {
         validator_address: Pubkey,
     },
     Unjail,
+    Dashboard {
+        /// Vote account of the validator
+        validator: Pubkey,
+        metrics_url: String,
+        /// Redraw interval; the dashboard is printed once when absent
+        refresh_secs: Option<u64>,
+    },
 }
 
 // ── Output Structs ──────────────────────────────────────────────────
@@ -128,6 +149,59 @@ impl fmt::Display for CliSlashingInfo {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CliValidatorDashboard {
+    pub pubkey: String,
+    pub stake_sol: f64,
+    pub status: String,
+    /// Leader slots without a block in the current epoch.
+    pub missed_slots: u64,
+    /// Inflation rewards credited to the vote account in the last epoch.
+    pub rewards_earned_sol: f64,
+    pub passive_positions: Vec<CliPassiveStakeEntry>,
+    /// Base fee reported by the node's metrics endpoint (lamports).
+    pub current_base_fee: u64,
+}
+
+impl fmt::Display for CliValidatorDashboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Validator Dashboard: {}", self.pubkey)?;
+        writeln!(f, "  Status:            {}", self.status)?;
+        writeln!(f, "  Stake:             {} SOL", self.stake_sol)?;
+        writeln!(f, "  Missed Slots:      {} (this epoch)", self.missed_slots)?;
+        writeln!(
+            f,
+            "  Rewards Earned:    {} SOL (last epoch)",
+            self.rewards_earned_sol
+        )?;
+        writeln!(f, "  Current Base Fee:  {} lamports", self.current_base_fee)?;
+        writeln!(f)?;
+        if self.passive_positions.is_empty() {
+            writeln!(f, "  Passive Positions: none")?;
+        } else {
+            writeln!(f, "  Passive Positions:")?;
+            writeln!(
+                f,
+                "  {:<44} {:>16} {:>10} {:>16} {:<8}",
+                "Account", "Amount", "Lock", "Unclaimed", "Compound"
+            )?;
+            writeln!(f, "  {}", "-".repeat(98))?;
+            for position in &self.passive_positions {
+                writeln!(
+                    f,
+                    "  {:<44} {:>12.4} SOL {:>10} {:>12.4} SOL {:<8}",
+                    position.account,
+                    position.amount_sol,
+                    position.lock_label(),
+                    position.unclaimed_rewards_sol,
+                    if position.auto_compound { "yes" } else { "no" },
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
 // ── Subcommand Definition (clap) ────────────────────────────────────
 /// Note: We name this "trv1-validators" to avoid conflict with the existing
 /// Solana "validators" command (which shows `ShowValidators`). The existing
@@ -168,6 +242,43 @@ impl Trv1ValidatorsSubCommands for App<'_, '_> {
                         .about("Unjail the current validator (signer must be the validator identity)"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("validator")
+                .about("TRv1 validator operator commands")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("dashboard")
+                        .about(
+                            "Show stake, jail status, missed slots, rewards, fees and passive \
+                             stake positions for a validator",
+                        )
+                        .arg(
+                            Arg::with_name("validator")
+                                .long("validator")
+                                .value_name("VOTE_ACCOUNT_ADDRESS")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_pubkey)
+                                .help("Vote account of the validator"),
+                        )
+                        .arg(
+                            Arg::with_name("metrics_url")
+                                .long("metrics-url")
+                                .value_name("URL")
+                                .takes_value(true)
+                                .default_value(DEFAULT_METRICS_URL)
+                                .help("Prometheus metrics endpoint of the validator node"),
+                        )
+                        .arg(
+                            Arg::with_name("refresh")
+                                .long("refresh")
+                                .value_name("SECS")
+                                .takes_value(true)
+                                .validator(is_parsable::<u64>)
+                                .help("Redraw the dashboard every SECS seconds until interrupted"),
+                        ),
+                ),
+        )
     }
 }
 
@@ -201,6 +312,18 @@ pub fn parse_trv1_validators_command(
                 CliCommand::Trv1Validators(Trv1ValidatorsCliCommand::Unjail),
             ))
         }
+        ("dashboard", Some(matches)) => {
+            let validator = pubkey_of(matches, "validator").unwrap();
+            let metrics_url = matches.value_of("metrics_url").unwrap().to_string();
+            let refresh_secs = value_t!(matches, "refresh", u64).ok();
+            Ok(CliCommandInfo::without_signers(CliCommand::Trv1Validators(
+                Trv1ValidatorsCliCommand::Dashboard {
+                    validator,
+                    metrics_url,
+                    refresh_secs,
+                },
+            )))
+        }
         _ => unreachable!(),
     }
 }
@@ -224,6 +347,20 @@ pub async fn process_trv1_validators_command(
         Trv1ValidatorsCliCommand::Unjail => {
             process_validators_unjail(rpc_client, config).await
         }
+        Trv1ValidatorsCliCommand::Dashboard {
+            validator,
+            metrics_url,
+            refresh_secs,
+        } => {
+            process_validator_dashboard(
+                rpc_client,
+                config,
+                validator,
+                metrics_url,
+                refresh_secs.map(Duration::from_secs),
+            )
+            .await
+        }
     }
 }
 
@@ -321,3 +458,233 @@ async fn process_validators_unjail(
         _ => Ok("Validator unjailed successfully".to_string()),
     }
 }
+
+/// Value of the unlabelled sample `name` in Prometheus text exposition.
+fn prometheus_sample(text: &str, name: &str) -> Option<f64> {
+    text.lines()
+        .filter(|line| !line.starts_with('#'))
+        .find_map(|line| {
+            let mut fields = line.split_whitespace();
+            if fields.next()? != name {
+                return None;
+            }
+            fields.next()?.parse().ok()
+        })
+}
+
+async fn fetch_validator_dashboard(
+    rpc_client: &RpcClient,
+    vote_account: &Pubkey,
+    metrics_url: &str,
+) -> Result<CliValidatorDashboard, Box<dyn std::error::Error>> {
+    let RpcVoteAccountStatus {
+        current,
+        delinquent,
+    } = rpc_client
+        .get_vote_accounts_with_config(RpcGetVoteAccountsConfig {
+            vote_pubkey: Some(vote_account.to_string()),
+            keep_unstaked_delinquents: Some(true),
+            ..RpcGetVoteAccountsConfig::default()
+        })
+        .await?;
+    let is_delinquent = current.is_empty();
+    // The filter returns at most one vote account.
+    let vote_info = current
+        .into_iter()
+        .chain(delinquent)
+        .next()
+        .ok_or_else(|| {
+            CliError::RpcRequestError(format!("Vote account not found: {vote_account}"))
+        })?;
+    let identity = Pubkey::from_str(&vote_info.node_pubkey)?;
+
+    // Nodes without the TRv1 RPC extensions do not report jail status.
+    let is_jailed = rpc_client
+        .send::<JailStatus>(
+            RpcRequest::Custom {
+                method: "trv1_getJailStatus",
+            },
+            json!([identity.to_string()]),
+        )
+        .await
+        .map(|jail_status| jail_status.is_jailed)
+        .unwrap_or(false);
+    let status = if is_jailed {
+        "jailed"
+    } else if is_delinquent {
+        "delinquent"
+    } else {
+        "active"
+    };
+
+    let block_production = rpc_client
+        .get_block_production_with_config(RpcBlockProductionConfig {
+            identity: Some(identity.to_string()),
+            ..RpcBlockProductionConfig::default()
+        })
+        .await?
+        .value;
+    let missed_slots = block_production
+        .by_identity
+        .get(&identity.to_string())
+        .map(|(leader_slots, blocks_produced)| leader_slots.saturating_sub(*blocks_produced))
+        .unwrap_or(0) as u64;
+
+    let rewards_earned = rpc_client
+        .get_inflation_reward(&[*vote_account], None)
+        .await?
+        .into_iter()
+        .flatten()
+        .map(|reward| reward.amount)
+        .sum::<u64>();
+
+    let passive_positions = fetch_passive_stakes_by_authority(rpc_client, &identity)
+        .await?
+        .iter()
+        .map(|(address, account)| CliPassiveStakeEntry::new(address, account))
+        .collect();
+
+    let metrics = reqwest::get(metrics_url)
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let current_base_fee = prometheus_sample(&metrics, "trv1_current_base_fee")
+        .ok_or_else(|| format!("{metrics_url} does not export trv1_current_base_fee"))?
+        as u64;
+
+    Ok(CliValidatorDashboard {
+        pubkey: vote_account.to_string(),
+        stake_sol: vote_info.activated_stake as f64 / LAMPORTS_PER_SOL as f64,
+        status: status.to_string(),
+        missed_slots,
+        rewards_earned_sol: rewards_earned as f64 / LAMPORTS_PER_SOL as f64,
+        passive_positions,
+        current_base_fee,
+    })
+}
+
+async fn process_validator_dashboard(
+    rpc_client: &Arc<RpcClient>,
+    config: &CliConfig<'_>,
+    validator: &Pubkey,
+    metrics_url: &str,
+    refresh: Option<Duration>,
+) -> ProcessResult {
+    let is_json = matches!(
+        config.output_format,
+        OutputFormat::Json | OutputFormat::JsonCompact
+    );
+    let Some(refresh) = refresh else {
+        let dashboard = fetch_validator_dashboard(rpc_client, validator, metrics_url).await?;
+        return if is_json {
+            Ok(serde_json::to_string_pretty(&dashboard)?)
+        } else {
+            Ok(dashboard.to_string())
+        };
+    };
+
+    // Number of lines of the last rendered dashboard, erased before redrawing.
+    let mut rendered_lines = 0;
+    loop {
+        match fetch_validator_dashboard(rpc_client, validator, metrics_url).await {
+            Ok(dashboard) if is_json => println!("{}", serde_json::to_string(&dashboard)?),
+            Ok(dashboard) => {
+                if rendered_lines > 0 {
+                    // Move the cursor to the start of the previous dashboard
+                    // and clear everything below it.
+                    print!("\x1b[{rendered_lines}F\x1b[J");
+                }
+                let rendered = dashboard.to_string();
+                print!("{rendered}");
+                rendered_lines = rendered.lines().count();
+            }
+            Err(err) => {
+                eprintln!("Failed to fetch validator status: {err}");
+                rendered_lines = 0;
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = tokio::time::sleep(refresh) => {}
+        }
+    }
+
+    Ok(String::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validator_dashboard_display() {
+        let dashboard = CliValidatorDashboard {
+            pubkey: "Vote111111111111111111111111111111111111111".to_string(),
+            stake_sol: 125_000.5,
+            status: "delinquent".to_string(),
+            missed_slots: 17,
+            rewards_earned_sol: 42.25,
+            passive_positions: vec![
+                CliPassiveStakeEntry {
+                    account: "PassiveA111111111111111111111111111111111111".to_string(),
+                    amount_sol: 1_000.0,
+                    lock_days: 90,
+                    unclaimed_rewards_sol: 2.5,
+                    auto_compound: false,
+                },
+                CliPassiveStakeEntry {
+                    account: "PassiveB111111111111111111111111111111111111".to_string(),
+                    amount_sol: 50.0,
+                    lock_days: u64::MAX,
+                    unclaimed_rewards_sol: 0.0,
+                    auto_compound: true,
+                },
+            ],
+            current_base_fee: 7_500,
+        };
+
+        let display = dashboard.to_string();
+        for expected in [
+            "Validator Dashboard: Vote111111111111111111111111111111111111111",
+            "Status:            delinquent",
+            "Stake:             125000.5 SOL",
+            "Missed Slots:      17",
+            "Rewards Earned:    42.25 SOL",
+            "Current Base Fee:  7500 lamports",
+            "PassiveA111111111111111111111111111111111111",
+            "1000.0000 SOL",
+            "90 days",
+            "PassiveB111111111111111111111111111111111111",
+            "permanent",
+        ] {
+            assert!(
+                display.contains(expected),
+                "missing {expected:?} in:\n{display}"
+            );
+        }
+
+        let json: serde_json::Value = serde_json::to_value(&dashboard).unwrap();
+        assert_eq!(json["missed_slots"], 17);
+        assert_eq!(json["current_base_fee"], 7_500);
+        assert_eq!(json["passive_positions"][1]["lock_days"], u64::MAX);
+    }
+
+    #[test]
+    fn test_prometheus_sample() {
+        let text = "\
+# HELP trv1_current_base_fee Current base fee
+# TYPE trv1_current_base_fee gauge
+trv1_current_base_fee 5000
+trv1_current_base_fee_floor 100
+trv1_block_utilization_bps{le=\"1\"} 3
+";
+        assert_eq!(
+            prometheus_sample(text, "trv1_current_base_fee"),
+            Some(5_000.0)
+        );
+        assert_eq!(prometheus_sample(text, "trv1_block_utilization_bps"), None);
+        assert_eq!(prometheus_sample(text, "trv1_missing"), None);
+    }
+}
@@ -0,0 +1,109 @@
+#![allow(clippy::arithmetic_side_effects)]
+use {
+    solana_cli::{
+        check_balance,
+        cli::{process_command, request_and_confirm_airdrop, CliCommand, CliConfig},
+        passive_stake::{OfflineSigning, PassiveStakeCliCommand},
+        test_utils::check_ready,
+    },
+    solana_commitment_config::CommitmentConfig,
+    solana_faucet::faucet::run_local_faucet_with_unique_port_for_tests,
+    solana_keypair::Keypair,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_net_utils::SocketAddrSpace,
+    solana_passive_stake_program::state::{
+        find_stake_summary_address, PassiveStakeAccount, StakeSummaryAccount,
+    },
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_signer::Signer,
+    solana_test_validator::TestValidator,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_passive_stake_create_and_unlock() {
+    agave_logger::setup();
+    let mint_keypair = Keypair::new();
+    let faucet_addr = run_local_faucet_with_unique_port_for_tests(mint_keypair.insecure_clone());
+    let test_validator = TestValidator::async_with_no_fees(
+        &mint_keypair,
+        Some(faucet_addr),
+        SocketAddrSpace::Unspecified,
+    )
+    .await;
+
+    let rpc_client =
+        RpcClient::new_with_commitment(test_validator.rpc_url(), CommitmentConfig::processed());
+    let authority = Keypair::new();
+    let stake_account = Keypair::new();
+
+    let mut config = CliConfig::recent_for_tests();
+    config.json_rpc_url = test_validator.rpc_url();
+    config.signers = vec![&authority];
+    request_and_confirm_airdrop(
+        &rpc_client,
+        &config,
+        &authority.pubkey(),
+        100 * LAMPORTS_PER_SOL,
+    )
+    .await
+    .unwrap();
+    check_ready(&rpc_client).await;
+
+    // The instructions the CLI builds must be accepted by the program as-is.
+    let summary_address = find_stake_summary_address(&authority.pubkey()).0;
+    let stake_rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(PassiveStakeAccount::SERIALIZED_SIZE)
+        .await
+        .unwrap();
+    let summary_rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(StakeSummaryAccount::SERIALIZED_SIZE)
+        .await
+        .unwrap();
+    config.signers = vec![&authority, &stake_account];
+    config.command = CliCommand::PassiveStake(PassiveStakeCliCommand::Create {
+        amount: 10.0,
+        lock_period: "0".to_string(),
+        stake_account: 1,
+        rent_exempt_reserve: None,
+        offline: OfflineSigning::default(),
+    });
+    process_command(&config).await.unwrap();
+    check_balance!(
+        10 * LAMPORTS_PER_SOL + stake_rent,
+        &rpc_client,
+        &stake_account.pubkey()
+    );
+    check_balance!(
+        90 * LAMPORTS_PER_SOL - stake_rent - summary_rent,
+        &rpc_client,
+        &authority.pubkey()
+    );
+    let account = rpc_client
+        .get_account(&stake_account.pubkey())
+        .await
+        .unwrap();
+    let state = PassiveStakeAccount::deserialize(&account.data).unwrap();
+    assert_eq!(state.authority, authority.pubkey());
+    assert_eq!(state.amount, 10 * LAMPORTS_PER_SOL);
+    assert_eq!(state.lock_days, 0);
+    let summary = rpc_client.get_account(&summary_address).await.unwrap();
+    let summary = StakeSummaryAccount::deserialize(&summary.data).unwrap();
+    assert_eq!(summary.total_staked, 10 * LAMPORTS_PER_SOL);
+
+    // Unlocking returns the principal and the stake account's rent.
+    config.signers = vec![&authority];
+    config.command = CliCommand::PassiveStake(PassiveStakeCliCommand::Unlock {
+        account_address: stake_account.pubkey(),
+        offline: OfflineSigning::default(),
+    });
+    process_command(&config).await.unwrap();
+    check_balance!(0, &rpc_client, &stake_account.pubkey());
+    check_balance!(
+        100 * LAMPORTS_PER_SOL - summary_rent,
+        &rpc_client,
+        &authority.pubkey()
+    );
+    let summary = rpc_client.get_account(&summary_address).await.unwrap();
+    let summary = StakeSummaryAccount::deserialize(&summary.data).unwrap();
+    assert_eq!(summary.total_staked, 0);
+}
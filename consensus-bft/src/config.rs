@@ -3,6 +3,8 @@
 //! Defines timing parameters, quorum thresholds, and round limits
 //! for the Tendermint-style BFT consensus protocol.
 
+use std::path::PathBuf;
+
 /// Configuration for the BFT consensus engine.
 ///
 /// All timeouts are in milliseconds. The protocol targets 1-second block time
@@ -13,18 +15,26 @@ pub struct BftConfig {
     /// Default: 1000 (1 second).
     pub block_time_ms: u64,
 
-    /// Timeout for the prevote phase in milliseconds.
+    /// Base timeout for the prevote phase in milliseconds.
     /// If a validator doesn't receive enough prevotes within this window,
     /// it transitions to precommit with a nil vote.
     /// Default: 1000.
     pub prevote_timeout_ms: u64,
 
-    /// Timeout for the precommit phase in milliseconds.
+    /// Additional timeout per round for the prevote phase.
+    /// Default: 500.
+    pub prevote_timeout_delta_ms: u64,
+
+    /// Base timeout for the precommit phase in milliseconds.
     /// If a validator doesn't receive enough precommits within this window,
     /// a new round begins.
     /// Default: 1000.
     pub precommit_timeout_ms: u64,
 
+    /// Additional timeout per round for the precommit phase.
+    /// Default: 500.
+    pub precommit_timeout_delta_ms: u64,
+
     /// Fraction of total stake required for quorum (2/3 + 1).
     /// Default: 0.667.
     pub finality_threshold: f64,
@@ -41,6 +51,19 @@ pub struct BftConfig {
     /// Additional timeout per round for the propose phase.
     /// Default: 500.
     pub propose_timeout_delta_ms: u64,
+
+    /// Upper bound on any round-adjusted timeout in milliseconds.
+    /// Default: 30000.
+    pub max_timeout_ms: u64,
+
+    /// Directory for the consensus write-ahead log.
+    /// `None` disables the WAL.
+    /// Default: None.
+    pub wal_dir: Option<PathBuf>,
+
+    /// Number of past heights whose WAL segments are kept.
+    /// Default: 100.
+    pub wal_retention_heights: u64,
 }
 
 impl Default for BftConfig {
@@ -48,11 +71,16 @@ impl Default for BftConfig {
         Self {
             block_time_ms: 1000,
             prevote_timeout_ms: 1000,
+            prevote_timeout_delta_ms: 500,
             precommit_timeout_ms: 1000,
+            precommit_timeout_delta_ms: 500,
             finality_threshold: 0.667,
             max_rounds_per_height: 5,
             propose_timeout_base_ms: 3000,
             propose_timeout_delta_ms: 500,
+            max_timeout_ms: 30_000,
+            wal_dir: None,
+            wal_retention_heights: 100,
         }
     }
 }
@@ -77,6 +105,9 @@ impl BftConfig {
         if self.max_rounds_per_height == 0 {
             return Err(ConfigError::InvalidMaxRounds);
         }
+        if self.max_timeout_ms == 0 {
+            return Err(ConfigError::InvalidMaxTimeout);
+        }
         Ok(())
     }
 }
@@ -90,6 +121,8 @@ pub enum ConfigError {
     InvalidFinalityThreshold(f64),
     #[error("max_rounds_per_height must be > 0")]
     InvalidMaxRounds,
+    #[error("max_timeout_ms must be > 0")]
+    InvalidMaxTimeout,
 }
 
 #[cfg(test)]
@@ -106,6 +139,11 @@ mod tests {
         assert_eq!(config.max_rounds_per_height, 5);
         assert_eq!(config.propose_timeout_base_ms, 3000);
         assert_eq!(config.propose_timeout_delta_ms, 500);
+        assert_eq!(config.prevote_timeout_delta_ms, 500);
+        assert_eq!(config.precommit_timeout_delta_ms, 500);
+        assert_eq!(config.max_timeout_ms, 30_000);
+        assert_eq!(config.wal_dir, None);
+        assert_eq!(config.wal_retention_heights, 100);
     }
 
     #[test]
@@ -162,4 +200,16 @@ mod tests {
             Err(ConfigError::InvalidMaxRounds)
         ));
     }
+
+    #[test]
+    fn test_invalid_max_timeout() {
+        let config = BftConfig {
+            max_timeout_ms: 0,
+            ..BftConfig::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidMaxTimeout)
+        ));
+    }
 }
@@ -6,22 +6,30 @@
 //! The engine is deterministic: given the same sequence of messages, it
 //! will always produce the same state transitions and output messages.
 //! All I/O and networking is handled externally; this module is pure
-//! state-machine logic.
+//! state-machine logic.  The one exception is the optional
+//! [`TRv1Metrics`] it reports commits and missed proposals to, which reads
+//! the clock to time finality but never feeds back into consensus.
 
 use {
     crate::{
         config::BftConfig,
         evidence::EvidenceCollector,
-        proposer,
+        proposer::{self, ProposerSchedule},
         types::{
-            CommittedBlock, ConsensusMessage, ConsensusState, ConsensusStep,
+            verify_consensus_message, CommittedBlock, ConsensusMessage, ConsensusState,
+            ConsensusStep, PolkaCertificate, ProposedBlock,
         },
         validator_set::ValidatorSet,
+        wal::ConsensusWal,
     },
     log::*,
     solana_hash::Hash,
+    solana_keypair::Keypair,
     solana_pubkey::Pubkey,
     solana_signature::Signature,
+    solana_signer::Signer,
+    std::{sync::Arc, time::Instant},
+    trv1_monitoring::{Counter, TRv1Metrics},
 };
 
 /// Result of processing a consensus event.
@@ -31,6 +39,18 @@ pub struct EngineOutput {
     pub messages: Vec<ConsensusMessage>,
     /// If consensus was reached, the committed block.
     pub committed_block: Option<CommittedBlock>,
+    /// Heights skipped since the last commit, which the caller must fetch
+    /// from peers before acting on consensus messages at the new height.
+    pub sync_request: Option<SyncRequest>,
+}
+
+/// An inclusive range of heights to fetch from peers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncRequest {
+    /// First missing height.
+    pub from_height: u64,
+    /// Last missing height.
+    pub to_height: u64,
 }
 
 impl EngineOutput {
@@ -38,6 +58,7 @@ impl EngineOutput {
         Self {
             messages: Vec::new(),
             committed_block: None,
+            sync_request: None,
         }
     }
 
@@ -45,6 +66,7 @@ impl EngineOutput {
         Self {
             messages,
             committed_block: None,
+            sync_request: None,
         }
     }
 
@@ -52,6 +74,7 @@ impl EngineOutput {
         Self {
             messages: Vec::new(),
             committed_block: Some(committed_block),
+            sync_request: None,
         }
     }
 }
@@ -63,7 +86,9 @@ impl EngineOutput {
 pub struct ConsensusEngine {
     /// Configuration parameters.
     config: BftConfig,
-    /// This validator's identity.
+    /// This validator's identity keypair; signs every outgoing message.
+    keypair: Keypair,
+    /// Pubkey of `keypair`.
     identity: Pubkey,
     /// The current validator set (stake-weighted).
     validator_set: ValidatorSet,
@@ -75,32 +100,104 @@ pub struct ConsensusEngine {
     sent_prevote: bool,
     /// Whether we've already sent a precommit this round.
     sent_precommit: bool,
+    /// Cached proposer schedule for the current height.
+    proposer_schedule: Option<ProposerSchedule>,
+    /// Incoming messages dropped because their signature did not verify.
+    ignored_invalid_signature: Counter,
+    /// Write-ahead log of state-changing messages, if `config.wal_dir` is set.
+    wal: Option<ConsensusWal>,
+    /// Whether messages are being replayed from the WAL (and so must not be
+    /// logged again).
+    replaying: bool,
+    /// Where to report commits and missed proposals, if anywhere.
+    metrics: Option<Arc<TRv1Metrics>>,
+    /// When the current height started, for the finality-time metric.
+    height_started_at: Instant,
+    /// Height of the last block this engine committed, if any.
+    last_committed_height: Option<u64>,
 }
 
 impl ConsensusEngine {
     /// Create a new consensus engine.
-    pub fn new(config: BftConfig, identity: Pubkey, validator_set: ValidatorSet) -> Self {
+    ///
+    /// If `config.wal_dir` is set, the write-ahead log there is opened; a WAL
+    /// that cannot be opened is logged and consensus runs without one.
+    pub fn new(config: BftConfig, keypair: Keypair, validator_set: ValidatorSet) -> Self {
+        let wal = config
+            .wal_dir
+            .as_ref()
+            .and_then(|dir| match ConsensusWal::open(dir) {
+                Ok(wal) => Some(wal),
+                Err(err) => {
+                    error!("Failed to open consensus WAL at {}: {err}", dir.display());
+                    None
+                }
+            });
         Self {
             config,
-            identity,
+            identity: keypair.pubkey(),
+            keypair,
             validator_set,
             state: ConsensusState::new(0),
             evidence: EvidenceCollector::new(),
             sent_prevote: false,
             sent_precommit: false,
+            proposer_schedule: None,
+            ignored_invalid_signature: Counter::new(
+                "trv1_consensus_ignored_invalid_signature_total",
+                "Consensus messages dropped for an invalid signature",
+            ),
+            wal,
+            replaying: false,
+            metrics: None,
+            height_started_at: Instant::now(),
+            last_committed_height: None,
         }
     }
 
+    /// Report commits and missed proposals to `metrics`.
+    ///
+    /// On every commit `blocks_produced` is incremented and the commit
+    /// round and the time since [`Self::start_new_height`] are observed in
+    /// `consensus_rounds` and `finality_time_ms`; `missed_proposals` is
+    /// incremented when the propose step times out in a round this
+    /// validator was to propose.
+    pub fn with_metrics(mut self, metrics: Arc<TRv1Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     // -- Public API --
 
     /// Begin consensus for a new height. Resets state and starts round 0.
+    ///
+    /// With a WAL, any messages already logged for `height` (by an engine
+    /// that crashed mid-height) are replayed to restore the round state.
+    ///
+    /// If `height` is more than one past the last committed height, the
+    /// output carries a [`SyncRequest`] for the heights in between.
     pub fn start_new_height(&mut self, height: u64) -> EngineOutput {
         info!("Starting consensus for height {height}");
+        let sync_request = match self.last_committed_height {
+            Some(last) if height > last.saturating_add(1) => {
+                warn!("Height skip detected: last={last}, new={height}");
+                Some(SyncRequest {
+                    from_height: last + 1,
+                    to_height: height - 1,
+                })
+            }
+            _ => None,
+        };
+        self.height_started_at = Instant::now();
         self.state = ConsensusState::new(height);
         self.sent_prevote = false;
         self.sent_precommit = false;
         self.evidence.prune(height.saturating_sub(100));
-        self.start_round(0)
+        self.precompute_schedule(self.config.max_rounds_per_height);
+        let mut output = self.start_round(0);
+        output.sync_request = sync_request;
+        self.recover_from_wal(&mut output);
+        output
     }
 
     /// Process an incoming proposal message.
@@ -112,6 +209,7 @@ impl ConsensusEngine {
             proposer,
             signature: _,
             valid_round,
+            ref polka_certificate,
         } = proposal
         else {
             warn!("on_proposal called with non-Proposal message");
@@ -123,13 +221,22 @@ impl ConsensusEngine {
             return EngineOutput::empty();
         }
 
+        if !self.authenticate(&proposal) {
+            return EngineOutput::empty();
+        }
+
         // Ignore messages for past rounds (but allow future rounds)
         if round < self.state.round {
             return EngineOutput::empty();
         }
+        let horizon = self.round_horizon();
+        if round > horizon {
+            debug!("Dropping proposal from {proposer} for round {round}, beyond horizon {horizon}");
+            return EngineOutput::empty();
+        }
 
         // Verify proposer is correct for this round
-        let expected_proposer = proposer::proposer_for_round(&self.validator_set, height, round);
+        let expected_proposer = self.expected_proposer(height, round);
         if expected_proposer != Some(proposer) {
             warn!(
                 "Invalid proposer {proposer} for height={height} round={round}, expected {:?}",
@@ -143,36 +250,44 @@ impl ConsensusEngine {
             return EngineOutput::empty();
         }
 
-        // If this is for a future round, jump to it
-        if round > self.state.round {
-            self.state.advance_round(round);
-            self.sent_prevote = false;
-            self.sent_precommit = false;
+        // A proposal we already hold changes nothing.
+        let block_hash = block.hash();
+        if round == self.state.round
+            && self.state.proposal.as_ref().map(|held| held.hash()) == Some(block_hash)
+        {
+            return EngineOutput::empty();
         }
+        self.append_to_wal(&proposal);
+
+        // If this is for a future round, jump to it
+        let mut output = if round > self.state.round {
+            self.start_round(round)
+        } else {
+            EngineOutput::empty()
+        };
 
         // Store the proposal
         self.state.proposal = Some(block.clone());
-        self.state.step = ConsensusStep::Propose;
 
         // Determine our prevote according to Tendermint rules
-        let block_hash = block.hash();
-        let our_prevote = self.determine_prevote(&block_hash, valid_round);
+        let our_prevote =
+            self.determine_prevote(&block_hash, valid_round, polka_certificate.as_ref());
 
         // Transition to Prevote step
-        self.state.step = ConsensusStep::Prevote;
-
-        if self.sent_prevote {
-            return EngineOutput::empty();
+        if self.state.step < ConsensusStep::Prevote {
+            self.state.step = ConsensusStep::Prevote;
         }
 
-        self.sent_prevote = true;
-        let prevote = self.make_prevote(our_prevote);
-        // Record our own prevote
-        self.state.prevotes.insert(self.identity, our_prevote);
+        if !self.sent_prevote {
+            self.sent_prevote = true;
+            let prevote = self.cast_prevote(our_prevote);
+            output.messages.push(prevote);
+        }
 
-        // Check if our prevote creates a quorum (e.g., we were the last needed)
-        let mut output = EngineOutput::with_messages(vec![prevote]);
+        // Check if our prevote creates a quorum (e.g., we were the last
+        // needed), or if buffered precommits were only waiting on the block.
         self.try_advance_from_prevotes(&mut output);
+        self.try_commit_into(&mut output);
         output
     }
 
@@ -183,7 +298,7 @@ impl ConsensusEngine {
             round,
             block_hash,
             voter,
-            ..
+            signature,
         } = &prevote
         else {
             return EngineOutput::empty();
@@ -193,28 +308,45 @@ impl ConsensusEngine {
             return EngineOutput::empty();
         }
 
-        // Check for double-signing
-        self.evidence.check_and_record(&prevote);
-
-        // Ignore votes for rounds we've moved past
-        if *round < self.state.round {
+        if !self.authenticate(&prevote) {
             return EngineOutput::empty();
         }
 
-        // Verify voter is in the validator set
+        // Verify voter is in the validator set; votes from anyone else are
+        // neither counted nor evidence.
         if !self.validator_set.contains(voter) {
             return EngineOutput::empty();
         }
+        if self.is_recorded_vote(&prevote) {
+            return EngineOutput::empty();
+        }
 
-        // If this vote is for a future round and we see 2/3+ for that round,
-        // we might need to skip ahead. For now, collect votes for current round.
-        if *round > self.state.round {
-            // TODO: track future-round votes and skip if quorum reached
+        // Ignore votes for rounds we've moved past; they are still evidence
+        // of double-signing.
+        if *round < self.state.round {
+            self.evidence.check_and_record(&prevote);
             return EngineOutput::empty();
         }
+        if *round <= self.round_horizon() {
+            self.append_to_wal(&prevote);
+        }
+
+        // Check for double-signing
+        self.evidence.check_and_record(&prevote);
+
+        // Buffer votes for future rounds; skip ahead once 2/3+ stake is
+        // voting there.
+        if *round > self.state.round {
+            return self.buffer_future_vote(&prevote);
+        }
 
         // Record the prevote
         self.state.prevotes.insert(*voter, *block_hash);
+        self.state.prevote_signatures.insert(*voter, *signature);
+        if *voter == self.identity {
+            // Our own vote echoed back, or replayed from the WAL.
+            self.sent_prevote = true;
+        }
 
         let mut output = EngineOutput::empty();
         self.try_advance_from_prevotes(&mut output);
@@ -228,7 +360,7 @@ impl ConsensusEngine {
             round,
             block_hash,
             voter,
-            signature: _,
+            signature,
         } = &precommit
         else {
             return EngineOutput::empty();
@@ -238,23 +370,38 @@ impl ConsensusEngine {
             return EngineOutput::empty();
         }
 
-        // Check for double-signing
-        self.evidence.check_and_record(&precommit);
-
-        if *round < self.state.round {
+        if !self.authenticate(&precommit) {
             return EngineOutput::empty();
         }
 
         if !self.validator_set.contains(voter) {
             return EngineOutput::empty();
         }
+        if self.is_recorded_vote(&precommit) {
+            return EngineOutput::empty();
+        }
 
-        if *round > self.state.round {
+        if *round < self.state.round {
+            self.evidence.check_and_record(&precommit);
             return EngineOutput::empty();
         }
+        if *round <= self.round_horizon() {
+            self.append_to_wal(&precommit);
+        }
+
+        // Check for double-signing
+        self.evidence.check_and_record(&precommit);
+
+        if *round > self.state.round {
+            return self.buffer_future_vote(&precommit);
+        }
 
         // Record the precommit
         self.state.precommits.insert(*voter, *block_hash);
+        self.state.precommit_signatures.insert(*voter, *signature);
+        if *voter == self.identity {
+            self.sent_precommit = true;
+        }
 
         // Check if we have 2/3+ precommits for a block
         self.try_commit()
@@ -268,10 +415,18 @@ impl ConsensusEngine {
                 // Send a nil prevote.
                 if self.state.step <= ConsensusStep::Propose {
                     self.state.step = ConsensusStep::Prevote;
+                    if self.is_proposer(self.state.height, self.state.round) {
+                        warn!(
+                            "Missed our own proposal at h={} r={}",
+                            self.state.height, self.state.round
+                        );
+                        if let Some(metrics) = &self.metrics {
+                            metrics.missed_proposals.inc();
+                        }
+                    }
                     if !self.sent_prevote {
                         self.sent_prevote = true;
-                        let prevote = self.make_prevote(None);
-                        self.state.prevotes.insert(self.identity, None);
+                        let prevote = self.cast_prevote(None);
                         return EngineOutput::with_messages(vec![prevote]);
                     }
                 }
@@ -284,8 +439,7 @@ impl ConsensusEngine {
                     self.state.step = ConsensusStep::Precommit;
                     if !self.sent_precommit {
                         self.sent_precommit = true;
-                        let precommit = self.make_precommit(None);
-                        self.state.precommits.insert(self.identity, None);
+                        let precommit = self.cast_precommit(None);
                         return EngineOutput::with_messages(vec![precommit]);
                     }
                 }
@@ -314,6 +468,26 @@ impl ConsensusEngine {
         }
     }
 
+    /// Pre-compute the proposers for rounds `0..n_rounds` of the current
+    /// height and cache them for proposer validation.
+    pub fn precompute_schedule(&mut self, n_rounds: u32) -> ProposerSchedule {
+        let schedule = ProposerSchedule {
+            height: self.state.height,
+            schedule: proposer::proposer_schedule(
+                &self.validator_set,
+                self.state.height,
+                n_rounds,
+            ),
+        };
+        self.proposer_schedule = Some(schedule.clone());
+        schedule
+    }
+
+    /// Returns the cached proposer schedule, if any.
+    pub fn proposer_schedule(&self) -> Option<&ProposerSchedule> {
+        self.proposer_schedule.as_ref()
+    }
+
     /// Check if this validator is the proposer for the given height and round.
     pub fn is_proposer(&self, height: u64, round: u32) -> bool {
         proposer::is_proposer(&self.validator_set, &self.identity, height, round)
@@ -351,6 +525,21 @@ impl ConsensusEngine {
         self.state.step
     }
 
+    /// Returns the height of the last block this engine committed, if any.
+    pub fn last_committed_height(&self) -> Option<u64> {
+        self.last_committed_height
+    }
+
+    /// Record `height` as the last committed block.
+    ///
+    /// Used to seed a restarted engine from the ledger, and to account for
+    /// blocks committed elsewhere and fetched through block sync, so the
+    /// next [`Self::start_new_height`] asks only for the heights still
+    /// missing.
+    pub fn set_last_committed_height(&mut self, height: u64) {
+        self.last_committed_height = Some(height);
+    }
+
     /// Returns the identity pubkey.
     pub fn identity(&self) -> &Pubkey {
         &self.identity
@@ -364,6 +553,7 @@ impl ConsensusEngine {
     /// Update the validator set (e.g., at epoch boundaries).
     pub fn update_validator_set(&mut self, validator_set: ValidatorSet) {
         self.validator_set = validator_set;
+        self.proposer_schedule = None;
     }
 
     /// Returns the config.
@@ -371,8 +561,133 @@ impl ConsensusEngine {
         &self.config
     }
 
+    /// Returns the count of incoming messages dropped for an invalid
+    /// signature.
+    pub fn ignored_invalid_signature(&self) -> &Counter {
+        &self.ignored_invalid_signature
+    }
+
+    /// Build a signed proposal of `block` for the current height and round,
+    /// for when this validator is the round's proposer.
+    ///
+    /// With `valid_round` set, the polka certificate this engine assembled
+    /// for that round is attached.
+    pub fn make_proposal(
+        &self,
+        block: ProposedBlock,
+        valid_round: Option<u32>,
+    ) -> ConsensusMessage {
+        ConsensusMessage::Proposal {
+            height: self.state.height,
+            round: self.state.round,
+            block,
+            proposer: self.identity,
+            signature: Signature::default(),
+            valid_round,
+            polka_certificate: valid_round
+                .and_then(|round| self.state.polka_certificates.get(&round))
+                .cloned(),
+        }
+        .signed(&self.keypair)
+    }
+
     // -- Internal logic --
 
+    /// Log a state-changing message before it is processed.
+    ///
+    /// Callers only log messages the engine will act on: signed by a
+    /// validator, for the current height and a round it tracks, and not
+    /// already recorded.  Anything else would cost a write and an fsync
+    /// without changing the state the WAL restores.
+    fn append_to_wal(&mut self, message: &ConsensusMessage) {
+        if self.replaying {
+            return;
+        }
+        if let Some(wal) = self.wal.as_mut() {
+            if let Err(err) = wal.append(message) {
+                error!("Failed to append to consensus WAL: {err}");
+            }
+        }
+    }
+
+    /// Rotate the WAL to the current height, pruning segments past
+    /// retention, and replay whatever was logged for this height.
+    fn recover_from_wal(&mut self, output: &mut EngineOutput) {
+        let height = self.state.height;
+        let Some(wal) = self.wal.as_mut() else {
+            return;
+        };
+        if let Err(err) = wal.prune(height.saturating_sub(self.config.wal_retention_heights)) {
+            warn!("Failed to prune consensus WAL: {err}");
+        }
+        let messages = wal.recover(height);
+        if let Err(err) = wal.rotate(height) {
+            error!("Failed to open consensus WAL segment for height {height}: {err}");
+        }
+        if messages.is_empty() {
+            return;
+        }
+
+        info!(
+            "Replaying {} WAL messages for height {height}",
+            messages.len()
+        );
+        self.replaying = true;
+        for message in messages {
+            // Our own votes mark the round we had reached before the crash.
+            if *message.sender() == self.identity && message.round() > self.state.round {
+                let skipped = self.start_round(message.round());
+                Self::merge_output(output, skipped);
+            }
+            let replayed = match message {
+                ConsensusMessage::Proposal { .. } => self.on_proposal(message),
+                ConsensusMessage::Prevote { .. } => self.on_prevote(message),
+                ConsensusMessage::Precommit { .. } => self.on_precommit(message),
+            };
+            Self::merge_output(output, replayed);
+        }
+        self.replaying = false;
+    }
+
+    /// Fold `other` into `output`.
+    fn merge_output(output: &mut EngineOutput, other: EngineOutput) {
+        output.messages.extend(other.messages);
+        if other.committed_block.is_some() {
+            output.committed_block = other.committed_block;
+        }
+        if other.sync_request.is_some() {
+            output.sync_request = other.sync_request;
+        }
+    }
+
+    /// Verify the signature on an incoming message, counting failures.
+    fn authenticate(&self, message: &ConsensusMessage) -> bool {
+        if verify_consensus_message(message) {
+            return true;
+        }
+        debug!(
+            "Dropping {} message from {} with invalid signature",
+            match message {
+                ConsensusMessage::Proposal { .. } => "proposal",
+                ConsensusMessage::Prevote { .. } => "prevote",
+                ConsensusMessage::Precommit { .. } => "precommit",
+            },
+            message.sender()
+        );
+        self.ignored_invalid_signature.inc();
+        false
+    }
+
+    /// Expected proposer for (height, round), served from the cached
+    /// schedule when it covers the round and recomputed otherwise.
+    fn expected_proposer(&self, height: u64, round: u32) -> Option<Pubkey> {
+        self.proposer_schedule
+            .as_ref()
+            .filter(|schedule| schedule.height == height)
+            .and_then(|schedule| schedule.proposer(round))
+            .or_else(|| proposer::proposer_for_round(&self.validator_set, height, round))
+    }
+
     /// Start a new round within the current height.
     fn start_round(&mut self, round: u32) -> EngineOutput {
         info!(
@@ -386,7 +701,125 @@ impl ConsensusEngine {
 
         // If we are the proposer, the caller is responsible for creating
         // and broadcasting the proposal. We just signal it.
-        EngineOutput::empty()
+        let mut output = EngineOutput::empty();
+
+        // Votes buffered for this round may already form a quorum.
+        if !self.state.prevotes.is_empty() {
+            self.try_advance_from_prevotes(&mut output);
+        }
+        if !self.state.precommits.is_empty() {
+            self.try_commit_into(&mut output);
+        }
+        output
+    }
+
+    /// Furthest round ahead of ours that the engine tracks messages for.
+    fn round_horizon(&self) -> u32 {
+        self.state
+            .round
+            .saturating_add(self.config.max_rounds_per_height)
+    }
+
+    /// Whether `vote` is already recorded, for this round or buffered for a
+    /// future one.  Such a vote is neither logged nor processed again.
+    fn is_recorded_vote(&self, vote: &ConsensusMessage) -> bool {
+        let (current, future, voter, block_hash) = match vote {
+            ConsensusMessage::Prevote {
+                voter, block_hash, ..
+            } => (
+                &self.state.prevotes,
+                &self.state.future_prevotes,
+                voter,
+                block_hash,
+            ),
+            ConsensusMessage::Precommit {
+                voter, block_hash, ..
+            } => (
+                &self.state.precommits,
+                &self.state.future_precommits,
+                voter,
+                block_hash,
+            ),
+            ConsensusMessage::Proposal { .. } => return false,
+        };
+        let recorded = if vote.round() == self.state.round {
+            current.get(voter)
+        } else {
+            future.get(&vote.round()).and_then(|votes| votes.get(voter))
+        };
+        recorded == Some(block_hash)
+    }
+
+    /// Buffer a prevote or precommit for a round ahead of ours, and skip
+    /// ahead to that round once 2/3+ of the stake has voted in it.
+    ///
+    /// Votes more than `max_rounds_per_height` rounds ahead are dropped so
+    /// that spam cannot grow the buffers without bound.
+    fn buffer_future_vote(&mut self, vote: &ConsensusMessage) -> EngineOutput {
+        let round = vote.round();
+        let horizon = self.round_horizon();
+        if round > horizon {
+            debug!(
+                "Dropping vote from {} for round {round}, beyond buffer horizon {horizon}",
+                vote.sender()
+            );
+            return EngineOutput::empty();
+        }
+
+        match vote {
+            ConsensusMessage::Prevote {
+                block_hash,
+                voter,
+                signature,
+                ..
+            } => {
+                self.state
+                    .future_prevotes
+                    .entry(round)
+                    .or_default()
+                    .insert(*voter, *block_hash);
+                self.state
+                    .future_prevote_signatures
+                    .entry(round)
+                    .or_default()
+                    .insert(*voter, *signature);
+            }
+            ConsensusMessage::Precommit {
+                block_hash,
+                voter,
+                signature,
+                ..
+            } => {
+                self.state
+                    .future_precommits
+                    .entry(round)
+                    .or_default()
+                    .insert(*voter, *block_hash);
+                self.state
+                    .future_precommit_signatures
+                    .entry(round)
+                    .or_default()
+                    .insert(*voter, *signature);
+            }
+            ConsensusMessage::Proposal { .. } => return EngineOutput::empty(),
+        }
+
+        let quorum = self.validator_set.quorum_stake(self.config.finality_threshold);
+        let stake: u64 = self
+            .state
+            .future_voters(round)
+            .iter()
+            .map(|voter| self.validator_set.stake_of(voter))
+            .fold(0u64, u64::saturating_add);
+        if stake < quorum {
+            return EngineOutput::empty();
+        }
+
+        info!(
+            "Skipping ahead to round {round} at height {}: 2/3+ stake is voting there",
+            self.state.height
+        );
+        self.start_round(round)
     }
 
     /// Determine what to prevote for, following Tendermint lock/polka rules.
@@ -395,12 +828,14 @@ impl ConsensusEngine {
     /// 1. If we're locked on a value and the proposal matches our lock → prevote for it.
     /// 2. If we're locked on a value and the proposal has valid_round >= locked_round
     ///    with a polka for the proposed value → we can unlock and prevote for it.
+    ///    The polka must be proven by a certificate carrying 2/3+ of the stake.
     /// 3. If we're not locked → prevote for the proposal if it's valid.
     /// 4. Otherwise → nil prevote.
     fn determine_prevote(
         &self,
         block_hash: &Hash,
         valid_round: Option<u32>,
+        polka_certificate: Option<&PolkaCertificate>,
     ) -> Option<Hash> {
         // If we're locked on a value
         if let Some(ref locked_hash) = self.state.locked_value {
@@ -411,10 +846,7 @@ impl ConsensusEngine {
 
             // Rule 2: check if valid_round >= locked_round (polka unlock)
             if let (Some(vr), Some(lr)) = (valid_round, self.state.locked_round) {
-                if vr >= lr {
-                    // The proposer claims a polka in valid_round.
-                    // In a full implementation, we'd verify this claim.
-                    // For now, trust the proposer's valid_round.
+                if vr >= lr && self.proves_polka(polka_certificate, block_hash, vr) {
                     return Some(*block_hash);
                 }
             }
@@ -428,8 +860,57 @@ impl ConsensusEngine {
         Some(*block_hash)
     }
 
+    /// Whether `certificate` proves a polka for `block_hash` in `round` of the
+    /// current height.
+    fn proves_polka(
+        &self,
+        certificate: Option<&PolkaCertificate>,
+        block_hash: &Hash,
+        round: u32,
+    ) -> bool {
+        let Some(certificate) = certificate else {
+            debug!("Proposal claims valid_round={round} without a polka certificate");
+            return false;
+        };
+        if certificate.height != self.state.height
+            || certificate.round != round
+            || certificate.block_hash != *block_hash
+        {
+            debug!("Polka certificate does not match the proposal");
+            return false;
+        }
+        let quorum = self.validator_set.quorum_stake(self.config.finality_threshold);
+        certificate.verified_stake(&self.validator_set) >= quorum
+    }
+
+    /// Assemble the polka certificate for `hash` from the current round's
+    /// prevotes.
+    fn polka_certificate(&self, hash: Hash) -> PolkaCertificate {
+        PolkaCertificate {
+            height: self.state.height,
+            round: self.state.round,
+            block_hash: hash,
+            signatures: self
+                .state
+                .prevotes
+                .iter()
+                .filter(|(_, vote)| **vote == Some(hash))
+                .filter_map(|(voter, _)| {
+                    self.state
+                        .prevote_signatures
+                        .get(voter)
+                        .map(|signature| (*voter, *signature))
+                })
+                .collect(),
+        }
+    }
+
     /// Try to advance the state machine based on collected prevotes.
     fn try_advance_from_prevotes(&mut self, output: &mut EngineOutput) {
+        if self.state.step == ConsensusStep::Commit {
+            return;
+        }
+
         // Check for 2/3+ prevotes for a specific hash
         let block_hash = self.find_quorum_prevote_hash();
 
@@ -437,9 +918,13 @@ impl ConsensusEngine {
             // We have a polka for this hash
             info!("Polka reached for hash {hash} at h={} r={}", self.state.height, self.state.round);
 
-            // Update valid value
+            // Update valid value, keeping the proof for later re-proposals
             self.state.valid_value = Some(hash);
             self.state.valid_round = Some(self.state.round);
+            let certificate = self.polka_certificate(hash);
+            self.state
+                .polka_certificates
+                .insert(self.state.round, certificate);
 
             // Lock on this value
             self.state.locked_value = Some(hash);
@@ -450,8 +935,7 @@ impl ConsensusEngine {
 
             if !self.sent_precommit {
                 self.sent_precommit = true;
-                let precommit = self.make_precommit(Some(hash));
-                self.state.precommits.insert(self.identity, Some(hash));
+                let precommit = self.cast_precommit(Some(hash));
                 output.messages.push(precommit);
             }
         } else if self.has_any_quorum_prevotes() {
@@ -461,16 +945,26 @@ impl ConsensusEngine {
                 self.state.step = ConsensusStep::Precommit;
                 if !self.sent_precommit {
                     self.sent_precommit = true;
-                    let precommit = self.make_precommit(None);
-                    self.state.precommits.insert(self.identity, None);
+                    let precommit = self.cast_precommit(None);
                     output.messages.push(precommit);
                 }
             }
         }
     }
 
+    /// Run [`Self::try_commit`] and fold its result into `output`.
+    fn try_commit_into(&mut self, output: &mut EngineOutput) {
+        let commit = self.try_commit();
+        Self::merge_output(output, commit);
+    }
+
     /// Try to commit based on collected precommits.
     fn try_commit(&mut self) -> EngineOutput {
+        // A height commits at most once.
+        if self.state.step == ConsensusStep::Commit {
+            return EngineOutput::empty();
+        }
+
         // Check for 2/3+ precommits for a specific hash
         let commit_hash = self.find_quorum_precommit_hash();
 
@@ -480,13 +974,20 @@ impl ConsensusEngine {
                 if proposal.hash() == hash {
                     info!("Committed block at h={} r={}", self.state.height, self.state.round);
                     self.state.step = ConsensusStep::Commit;
+                    self.last_committed_height = Some(self.state.height);
+                    self.record_commit_metrics();
 
                     let commit_sigs: Vec<(Pubkey, Signature)> = self
                         .state
                         .precommits
                         .iter()
                         .filter(|(_, v)| *v == &Some(hash))
-                        .map(|(k, _)| (*k, Signature::default())) // TODO: store actual signatures
+                        .filter_map(|(k, _)| {
+                            self.state
+                                .precommit_signatures
+                                .get(k)
+                                .map(|signature| (*k, *signature))
+                        })
                         .collect();
 
                     return EngineOutput::with_commit(CommittedBlock {
@@ -513,6 +1014,21 @@ impl ConsensusEngine {
         EngineOutput::empty()
     }
 
+    /// Report a commit in the current round to `metrics`, if set.
+    fn record_commit_metrics(&self) {
+        let Some(metrics) = &self.metrics else {
+            return;
+        };
+        metrics.blocks_produced.inc();
+        metrics.blocks_produced_rate.inc();
+        metrics
+            .consensus_rounds
+            .observe(f64::from(self.state.round));
+        metrics
+            .finality_time_ms
+            .observe(self.height_started_at.elapsed().as_secs_f64() * 1_000.0);
+    }
+
     // -- Quorum calculations --
 
     /// Check if there are 2/3+ prevotes for a specific block hash.
@@ -524,7 +1040,7 @@ impl ConsensusEngine {
             .iter()
             .filter(|(_, v)| *v == block_hash)
             .map(|(k, _)| self.validator_set.stake_of(k))
-            .sum();
+            .fold(0u64, u64::saturating_add);
         stake >= quorum
     }
 
@@ -537,12 +1053,12 @@ impl ConsensusEngine {
             .iter()
             .filter(|(_, v)| *v == block_hash)
             .map(|(k, _)| self.validator_set.stake_of(k))
-            .sum();
+            .fold(0u64, u64::saturating_add);
         stake >= quorum
     }
 
     /// Find the block hash that has a quorum of prevotes, if any.
-    fn find_quorum_prevote_hash(&self) -> Option<Hash> {
+    pub fn find_quorum_prevote_hash(&self) -> Option<Hash> {
         let quorum = self.validator_set.quorum_stake(self.config.finality_threshold);
 
         // Group prevotes by hash
@@ -550,7 +1066,8 @@ impl ConsensusEngine {
             std::collections::HashMap::new();
         for (voter, hash) in &self.state.prevotes {
             let stake = self.validator_set.stake_of(voter);
-            *stake_by_hash.entry(*hash).or_default() += stake;
+            let total = stake_by_hash.entry(*hash).or_default();
+            *total = total.saturating_add(stake);
         }
 
         // Find a non-nil hash with quorum
@@ -565,14 +1082,15 @@ impl ConsensusEngine {
     }
 
     /// Find the block hash that has a quorum of precommits, if any.
-    fn find_quorum_precommit_hash(&self) -> Option<Hash> {
+    pub fn find_quorum_precommit_hash(&self) -> Option<Hash> {
         let quorum = self.validator_set.quorum_stake(self.config.finality_threshold);
 
         let mut stake_by_hash: std::collections::HashMap<Option<Hash>, u64> =
             std::collections::HashMap::new();
         for (voter, hash) in &self.state.precommits {
             let stake = self.validator_set.stake_of(voter);
-            *stake_by_hash.entry(*hash).or_default() += stake;
+            let total = stake_by_hash.entry(*hash).or_default();
+            *total = total.saturating_add(stake);
         }
 
         for (hash, stake) in &stake_by_hash {
@@ -593,7 +1111,7 @@ impl ConsensusEngine {
             .prevotes
             .keys()
             .map(|k| self.validator_set.stake_of(k))
-            .sum();
+            .fold(0u64, u64::saturating_add);
         total >= quorum
     }
 
@@ -610,8 +1128,9 @@ impl ConsensusEngine {
             round: self.state.round,
             block_hash,
             voter: self.identity,
-            signature: Signature::default(), // TODO: sign with keypair
+            signature: Signature::default(),
         }
+        .signed(&self.keypair)
     }
 
     fn make_precommit(&self, block_hash: Option<Hash>) -> ConsensusMessage {
@@ -620,8 +1139,31 @@ impl ConsensusEngine {
             round: self.state.round,
             block_hash,
             voter: self.identity,
-            signature: Signature::default(), // TODO: sign with keypair
+            signature: Signature::default(),
         }
+        .signed(&self.keypair)
+    }
+
+    /// Sign a prevote and record it as our own vote.
+    fn cast_prevote(&mut self, block_hash: Option<Hash>) -> ConsensusMessage {
+        let prevote = self.make_prevote(block_hash);
+        self.append_to_wal(&prevote);
+        self.state.prevotes.insert(self.identity, block_hash);
+        self.state
+            .prevote_signatures
+            .insert(self.identity, *prevote.signature());
+        prevote
+    }
+
+    /// Sign a precommit and record it as our own vote.
+    fn cast_precommit(&mut self, block_hash: Option<Hash>) -> ConsensusMessage {
+        let precommit = self.make_precommit(block_hash);
+        self.append_to_wal(&precommit);
+        self.state.precommits.insert(self.identity, block_hash);
+        self.state
+            .precommit_signatures
+            .insert(self.identity, *precommit.signature());
+        precommit
     }
 }
 
@@ -653,10 +1195,15 @@ mod tests {
     use crate::types::ProposedBlock;
 
     /// Helper: create a validator set with N validators of equal stake.
-    fn make_validator_set(n: usize, stake: u64) -> (Vec<Pubkey>, ValidatorSet) {
-        let pks: Vec<Pubkey> = (0..n).map(|_| Pubkey::new_unique()).collect();
-        let vs = ValidatorSet::new(pks.iter().map(|pk| (*pk, stake)).collect());
-        (pks, vs)
+    fn make_validator_set(n: usize, stake: u64) -> (Vec<Keypair>, ValidatorSet) {
+        let kps: Vec<Keypair> = (0..n).map(|_| Keypair::new()).collect();
+        let vs = ValidatorSet::new(kps.iter().map(|kp| (kp.pubkey(), stake)).collect());
+        (kps, vs)
+    }
+
+    /// Helper: the keypair of validator `pk`.
+    fn keypair_of<'a>(kps: &'a [Keypair], pk: &Pubkey) -> &'a Keypair {
+        kps.iter().find(|kp| kp.pubkey() == *pk).unwrap()
     }
 
     /// Helper: create a simple proposed block.
@@ -668,6 +1215,7 @@ mod tests {
             transactions: vec![],
             state_root: Hash::new_unique(),
             proposer,
+            evidence: vec![],
         }
     }
 
@@ -676,16 +1224,59 @@ mod tests {
         height: u64,
         round: u32,
         block: &ProposedBlock,
-        proposer: Pubkey,
+        proposer: &Keypair,
         valid_round: Option<u32>,
     ) -> ConsensusMessage {
         ConsensusMessage::Proposal {
             height,
             round,
             block: block.clone(),
-            proposer,
+            proposer: proposer.pubkey(),
+            signature: Signature::default(),
+            valid_round,
+            polka_certificate: None,
+        }
+        .signed(proposer)
+    }
+
+    fn make_certified_proposal(
+        height: u64,
+        round: u32,
+        block: &ProposedBlock,
+        proposer: &Keypair,
+        valid_round: Option<u32>,
+        polka_certificate: Option<PolkaCertificate>,
+    ) -> ConsensusMessage {
+        ConsensusMessage::Proposal {
+            height,
+            round,
+            block: block.clone(),
+            proposer: proposer.pubkey(),
             signature: Signature::default(),
             valid_round,
+            polka_certificate,
+        }
+        .signed(proposer)
+    }
+
+    /// Helper: a certificate of `voters`' prevotes for `block_hash`.
+    fn make_polka_certificate(
+        height: u64,
+        round: u32,
+        block_hash: Hash,
+        voters: &[Keypair],
+    ) -> PolkaCertificate {
+        PolkaCertificate {
+            height,
+            round,
+            block_hash,
+            signatures: voters
+                .iter()
+                .map(|kp| {
+                    let prevote = make_prevote_msg(height, round, Some(block_hash), kp);
+                    (kp.pubkey(), *prevote.signature())
+                })
+                .collect(),
         }
     }
 
@@ -693,30 +1284,32 @@ mod tests {
         height: u64,
         round: u32,
         block_hash: Option<Hash>,
-        voter: Pubkey,
+        voter: &Keypair,
     ) -> ConsensusMessage {
         ConsensusMessage::Prevote {
             height,
             round,
             block_hash,
-            voter,
+            voter: voter.pubkey(),
             signature: Signature::default(),
         }
+        .signed(voter)
     }
 
     fn make_precommit_msg(
         height: u64,
         round: u32,
         block_hash: Option<Hash>,
-        voter: Pubkey,
+        voter: &Keypair,
     ) -> ConsensusMessage {
         ConsensusMessage::Precommit {
             height,
             round,
             block_hash,
-            voter,
+            voter: voter.pubkey(),
             signature: Signature::default(),
         }
+        .signed(voter)
     }
 
     // ============================
@@ -726,12 +1319,11 @@ mod tests {
     #[test]
     fn test_full_round_happy_path() {
         // 4 validators with equal stake (need 3 for 2/3+)
-        let (pks, vs) = make_validator_set(4, 100);
+        let (kps, vs) = make_validator_set(4, 100);
         let config = BftConfig::default();
 
         // Use the first validator as our identity
-        let our_pk = pks[0];
-        let mut engine = ConsensusEngine::new(config, our_pk, vs.clone());
+        let mut engine = ConsensusEngine::new(config, kps[0].insecure_clone(), vs.clone());
 
         // Start height 1
         let _output = engine.start_new_height(1);
@@ -744,7 +1336,7 @@ mod tests {
         let block_hash = block.hash();
 
         // Send the proposal
-        let proposal = make_proposal(1, 0, &block, proposer_pk, None);
+        let proposal = make_proposal(1, 0, &block, keypair_of(&kps, &proposer_pk), None);
         let output = engine.on_proposal(proposal);
 
         // Engine should emit a prevote for the block
@@ -760,8 +1352,8 @@ mod tests {
         // Collect prevotes from other validators
         // We need 3 out of 4 for quorum (including ours, which was already recorded)
         let mut precommit_found = false;
-        for pk in &pks[1..] {
-            let prevote = make_prevote_msg(1, 0, Some(block_hash), *pk);
+        for kp in &kps[1..] {
+            let prevote = make_prevote_msg(1, 0, Some(block_hash), kp);
             let output = engine.on_prevote(prevote);
 
             // At some point, we should see a precommit
@@ -779,8 +1371,8 @@ mod tests {
 
         // Now send precommits from other validators
         let mut committed = false;
-        for pk in &pks[1..] {
-            let precommit = make_precommit_msg(1, 0, Some(block_hash), *pk);
+        for kp in &kps[1..] {
+            let precommit = make_precommit_msg(1, 0, Some(block_hash), kp);
             let output = engine.on_precommit(precommit);
             if output.committed_block.is_some() {
                 committed = true;
@@ -801,8 +1393,8 @@ mod tests {
 
     #[test]
     fn test_propose_timeout_sends_nil_prevote() {
-        let (pks, vs) = make_validator_set(4, 100);
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs);
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs);
         engine.start_new_height(1);
 
         // Timeout on propose → nil prevote
@@ -819,8 +1411,8 @@ mod tests {
 
     #[test]
     fn test_prevote_timeout_sends_nil_precommit() {
-        let (pks, vs) = make_validator_set(4, 100);
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs);
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs);
         engine.start_new_height(1);
 
         // First, get past propose
@@ -840,8 +1432,8 @@ mod tests {
 
     #[test]
     fn test_precommit_timeout_advances_round() {
-        let (pks, vs) = make_validator_set(4, 100);
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs);
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs);
         engine.start_new_height(1);
 
         // Timeout through all phases
@@ -856,8 +1448,8 @@ mod tests {
 
     #[test]
     fn test_multiple_round_escalation() {
-        let (pks, vs) = make_validator_set(4, 100);
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs);
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs);
         engine.start_new_height(1);
 
         // Escalate through 3 rounds
@@ -876,21 +1468,21 @@ mod tests {
 
     #[test]
     fn test_lock_on_polka() {
-        let (pks, vs) = make_validator_set(4, 100);
+        let (kps, vs) = make_validator_set(4, 100);
         let proposer_pk = proposer::proposer_for_round(&vs, 1, 0).unwrap();
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs.clone());
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
         engine.start_new_height(1);
 
         let block = make_block(1, proposer_pk);
         let block_hash = block.hash();
 
         // Receive proposal
-        let proposal = make_proposal(1, 0, &block, proposer_pk, None);
+        let proposal = make_proposal(1, 0, &block, keypair_of(&kps, &proposer_pk), None);
         engine.on_proposal(proposal);
 
         // Send 2/3+ prevotes
-        for pk in &pks[1..3] {
-            engine.on_prevote(make_prevote_msg(1, 0, Some(block_hash), *pk));
+        for kp in &kps[1..3] {
+            engine.on_prevote(make_prevote_msg(1, 0, Some(block_hash), kp));
         }
 
         // Engine should now be locked on block_hash
@@ -900,8 +1492,8 @@ mod tests {
 
     #[test]
     fn test_locked_validator_prevotes_for_locked_value() {
-        let (pks, vs) = make_validator_set(4, 100);
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs.clone());
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
         engine.start_new_height(1);
 
         // Manually set a lock
@@ -918,7 +1510,7 @@ mod tests {
         // Proposer for round 1 proposes a block
         let proposer_pk = proposer::proposer_for_round(&vs, 1, 1).unwrap();
         let block = make_block(1, proposer_pk);
-        let proposal = make_proposal(1, 1, &block, proposer_pk, None);
+        let proposal = make_proposal(1, 1, &block, keypair_of(&kps, &proposer_pk), None);
         let output = engine.on_proposal(proposal);
 
         // If the block hash != locked_hash, the engine should prevote nil
@@ -939,8 +1531,8 @@ mod tests {
 
     #[test]
     fn test_unlock_with_valid_round() {
-        let (pks, vs) = make_validator_set(4, 100);
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs.clone());
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
         engine.start_new_height(1);
 
         // Lock on something in round 0
@@ -957,7 +1549,15 @@ mod tests {
         // Proposer for round 2 proposes a different block with valid_round=1 >= locked_round=0
         let proposer_pk = proposer::proposer_for_round(&vs, 1, 2).unwrap();
         let block = make_block(1, proposer_pk);
-        let proposal = make_proposal(1, 2, &block, proposer_pk, Some(1));
+        let certificate = make_polka_certificate(1, 1, block.hash(), &kps[1..]);
+        let proposal = make_certified_proposal(
+            1,
+            2,
+            &block,
+            keypair_of(&kps, &proposer_pk),
+            Some(1),
+            Some(certificate),
+        );
         let output = engine.on_proposal(proposal);
 
         // Should prevote for the new block (unlocked due to valid_round >= locked_round)
@@ -974,19 +1574,109 @@ mod tests {
         }
     }
 
+    /// Engine at height 1, round 2, locked on an unrelated value from round 0.
+    fn make_locked_engine(kps: &[Keypair], vs: &ValidatorSet) -> ConsensusEngine {
+        let mut engine =
+            ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
+        engine.start_new_height(1);
+        engine.state.locked_value = Some(Hash::new_unique());
+        engine.state.locked_round = Some(0);
+        engine.state.advance_round(2);
+        engine.sent_prevote = false;
+        engine.sent_precommit = false;
+        engine.state.step = ConsensusStep::Propose;
+        engine
+    }
+
+    fn first_prevote(output: &EngineOutput) -> Option<Hash> {
+        match &output.messages[0] {
+            ConsensusMessage::Prevote { block_hash, .. } => *block_hash,
+            _ => panic!("Expected prevote"),
+        }
+    }
+
+    #[test]
+    fn test_unlock_requires_polka_certificate() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let proposer_pk = proposer::proposer_for_round(&vs, 1, 2).unwrap();
+        let proposer_kp = keypair_of(&kps, &proposer_pk);
+        let block = make_block(1, proposer_pk);
+
+        // No certificate: the valid_round claim is not trusted.
+        let mut engine = make_locked_engine(&kps, &vs);
+        let output = engine.on_proposal(make_proposal(1, 2, &block, proposer_kp, Some(1)));
+        assert_eq!(first_prevote(&output), None);
+
+        // Only half the stake: below quorum.
+        let mut engine = make_locked_engine(&kps, &vs);
+        let certificate = make_polka_certificate(1, 1, block.hash(), &kps[..2]);
+        let output = engine.on_proposal(make_certified_proposal(
+            1,
+            2,
+            &block,
+            proposer_kp,
+            Some(1),
+            Some(certificate),
+        ));
+        assert_eq!(first_prevote(&output), None);
+
+        // A quorum certificate for a different round than claimed.
+        let mut engine = make_locked_engine(&kps, &vs);
+        let certificate = make_polka_certificate(1, 0, block.hash(), &kps[1..]);
+        let output = engine.on_proposal(make_certified_proposal(
+            1,
+            2,
+            &block,
+            proposer_kp,
+            Some(1),
+            Some(certificate),
+        ));
+        assert_eq!(first_prevote(&output), None);
+    }
+
+    #[test]
+    fn test_polka_builds_certificate_for_reproposal() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine =
+            ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
+        engine.start_new_height(1);
+
+        let proposer_pk = proposer::proposer_for_round(&vs, 1, 0).unwrap();
+        let block = make_block(1, proposer_pk);
+        let block_hash = block.hash();
+        engine.on_proposal(make_proposal(1, 0, &block, keypair_of(&kps, &proposer_pk), None));
+        for kp in &kps[1..3] {
+            engine.on_prevote(make_prevote_msg(1, 0, Some(block_hash), kp));
+        }
+
+        let certificate = engine.state().polka_certificates[&0].clone();
+        assert_eq!(certificate.block_hash, block_hash);
+        assert_eq!(certificate.signatures.len(), 3);
+        let quorum = vs.quorum_stake(BftConfig::default().finality_threshold);
+        assert!(certificate.verified_stake(&vs) >= quorum);
+
+        // Re-proposing with valid_round attaches the certificate.
+        let proposal = engine.make_proposal(block, Some(0));
+        assert!(verify_consensus_message(&proposal));
+        let ConsensusMessage::Proposal { polka_certificate, .. } = proposal else {
+            panic!("Expected proposal");
+        };
+        assert_eq!(polka_certificate, Some(certificate));
+    }
+
     // ============================
     // Edge cases
     // ============================
 
     #[test]
     fn test_ignore_wrong_height() {
-        let (pks, vs) = make_validator_set(4, 100);
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs.clone());
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
         engine.start_new_height(5);
 
         let proposer_pk = proposer::proposer_for_round(&vs, 3, 0).unwrap();
         let block = make_block(3, proposer_pk);
-        let proposal = make_proposal(3, 0, &block, proposer_pk, None);
+        let proposal = make_proposal(3, 0, &block, keypair_of(&kps, &proposer_pk), None);
         let output = engine.on_proposal(proposal);
 
         // Should ignore — wrong height
@@ -995,8 +1685,8 @@ mod tests {
 
     #[test]
     fn test_ignore_past_round() {
-        let (pks, vs) = make_validator_set(4, 100);
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs.clone());
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
         engine.start_new_height(1);
 
         // Advance to round 2
@@ -1009,23 +1699,23 @@ mod tests {
         assert_eq!(engine.round(), 2);
 
         // Send a prevote for round 0 — should be ignored
-        let prevote = make_prevote_msg(1, 0, Some(Hash::new_unique()), pks[1]);
+        let prevote = make_prevote_msg(1, 0, Some(Hash::new_unique()), &kps[1]);
         let output = engine.on_prevote(prevote);
         assert!(output.messages.is_empty());
     }
 
     #[test]
     fn test_ignore_invalid_proposer() {
-        let (pks, vs) = make_validator_set(4, 100);
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs.clone());
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
         engine.start_new_height(1);
 
         // Find who is NOT the proposer
         let correct_proposer = proposer::proposer_for_round(&vs, 1, 0).unwrap();
-        let wrong_proposer = pks.iter().find(|p| **p != correct_proposer).unwrap();
+        let wrong_proposer = kps.iter().find(|kp| kp.pubkey() != correct_proposer).unwrap();
 
-        let block = make_block(1, *wrong_proposer);
-        let proposal = make_proposal(1, 0, &block, *wrong_proposer, None);
+        let block = make_block(1, wrong_proposer.pubkey());
+        let proposal = make_proposal(1, 0, &block, wrong_proposer, None);
         let output = engine.on_proposal(proposal);
 
         assert!(
@@ -1036,26 +1726,26 @@ mod tests {
 
     #[test]
     fn test_ignore_unknown_voter() {
-        let (pks, vs) = make_validator_set(4, 100);
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs);
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs);
         engine.start_new_height(1);
 
-        let unknown = Pubkey::new_unique();
-        let prevote = make_prevote_msg(1, 0, Some(Hash::new_unique()), unknown);
+        let unknown = Keypair::new();
+        let prevote = make_prevote_msg(1, 0, Some(Hash::new_unique()), &unknown);
         let output = engine.on_prevote(prevote);
         assert!(output.messages.is_empty());
     }
 
     #[test]
     fn test_future_round_proposal() {
-        let (pks, vs) = make_validator_set(4, 100);
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs.clone());
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
         engine.start_new_height(1);
 
         // Send a proposal for round 3 (we're in round 0)
         let proposer_pk = proposer::proposer_for_round(&vs, 1, 3).unwrap();
         let block = make_block(1, proposer_pk);
-        let proposal = make_proposal(1, 3, &block, proposer_pk, None);
+        let proposal = make_proposal(1, 3, &block, keypair_of(&kps, &proposer_pk), None);
         let output = engine.on_proposal(proposal);
 
         // Engine should jump to round 3 and process
@@ -1063,10 +1753,365 @@ mod tests {
         assert!(!output.messages.is_empty()); // Should emit prevote
     }
 
+    // ============================
+    // Signatures
+    // ============================
+
+    #[test]
+    fn test_valid_signature_accepted() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs);
+        engine.start_new_height(1);
+
+        let prevote = make_prevote_msg(1, 0, None, &kps[1]);
+        assert!(verify_consensus_message(&prevote));
+        engine.on_prevote(prevote);
+        assert_eq!(engine.state().prevotes.get(&kps[1].pubkey()), Some(&None));
+        assert_eq!(engine.ignored_invalid_signature().get(), 0);
+
+        // Our own outgoing votes verify too.
+        let output = engine.on_timeout(ConsensusStep::Propose);
+        assert!(verify_consensus_message(&output.messages[0]));
+    }
+
+    #[test]
+    fn test_wrong_key_signature_rejected() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs);
+        engine.start_new_height(1);
+
+        // Claims to be from validator 1 but is signed by validator 2.
+        let forged = ConsensusMessage::Precommit {
+            height: 1,
+            round: 0,
+            block_hash: Some(Hash::new_unique()),
+            voter: kps[1].pubkey(),
+            signature: Signature::default(),
+        }
+        .signed(&kps[2]);
+        assert!(!verify_consensus_message(&forged));
+
+        engine.on_precommit(forged);
+        assert!(engine.state().precommits.is_empty());
+        assert_eq!(engine.ignored_invalid_signature().get(), 1);
+
+        // Unsigned messages are dropped the same way.
+        engine.on_prevote(ConsensusMessage::Prevote {
+            height: 1,
+            round: 0,
+            block_hash: None,
+            voter: kps[1].pubkey(),
+            signature: Signature::default(),
+        });
+        assert!(engine.state().prevotes.is_empty());
+        assert_eq!(engine.ignored_invalid_signature().get(), 2);
+    }
+
+    #[test]
+    fn test_tampered_height_signature_rejected() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs);
+        engine.start_new_height(2);
+
+        let mut prevote = make_prevote_msg(1, 0, Some(Hash::new_unique()), &kps[1]);
+        if let ConsensusMessage::Prevote { height, .. } = &mut prevote {
+            *height = 2;
+        }
+        assert!(!verify_consensus_message(&prevote));
+
+        engine.on_prevote(prevote);
+        assert!(engine.state().prevotes.is_empty());
+        assert_eq!(engine.ignored_invalid_signature().get(), 1);
+        // No double-sign evidence is recorded for unauthenticated votes.
+        assert_eq!(engine.evidence().tracked_votes(), 0);
+    }
+
+    #[test]
+    fn test_prevote_signature_not_valid_as_precommit() {
+        let (kps, _vs) = make_validator_set(1, 100);
+        let prevote = make_prevote_msg(1, 0, None, &kps[0]);
+        let precommit = ConsensusMessage::Precommit {
+            height: 1,
+            round: 0,
+            block_hash: None,
+            voter: kps[0].pubkey(),
+            signature: *prevote.signature(),
+        };
+        assert!(!verify_consensus_message(&precommit));
+    }
+
+    #[test]
+    fn test_commit_carries_precommit_signatures() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine =
+            ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
+        engine.start_new_height(1);
+
+        let proposer_pk = proposer::proposer_for_round(&vs, 1, 0).unwrap();
+        let block = make_block(1, proposer_pk);
+        let block_hash = block.hash();
+        engine.on_proposal(make_proposal(1, 0, &block, keypair_of(&kps, &proposer_pk), None));
+        for kp in &kps[1..3] {
+            engine.on_prevote(make_prevote_msg(1, 0, Some(block_hash), kp));
+        }
+        let mut committed = None;
+        for kp in &kps[1..3] {
+            committed = engine
+                .on_precommit(make_precommit_msg(1, 0, Some(block_hash), kp))
+                .committed_block
+                .or(committed);
+        }
+
+        let committed = committed.expect("block should commit");
+        assert!(committed.commit_signatures.len() >= 3);
+        for (voter, signature) in &committed.commit_signatures {
+            let precommit = ConsensusMessage::Precommit {
+                height: 1,
+                round: 0,
+                block_hash: Some(block_hash),
+                voter: *voter,
+                signature: *signature,
+            };
+            assert!(verify_consensus_message(&precommit));
+        }
+    }
+
+    #[test]
+    fn test_quorum_stake_sums_saturate_near_u64_max() {
+        // Four votes at a third of u64::MAX each would overflow a plain sum.
+        let (kps, vs) = make_validator_set(4, u64::MAX / 3);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
+        engine.start_new_height(1);
+
+        let proposer_pk = proposer::proposer_for_round(&vs, 1, 0).unwrap();
+        let block = make_block(1, proposer_pk);
+        let block_hash = block.hash();
+        engine.on_proposal(make_proposal(1, 0, &block, keypair_of(&kps, &proposer_pk), None));
+        for kp in &kps[1..] {
+            engine.on_prevote(make_prevote_msg(1, 0, Some(block_hash), kp));
+        }
+        assert_eq!(engine.state().prevotes.len(), 4);
+        assert!(engine.has_quorum_prevotes(&Some(block_hash)));
+        assert!(engine.has_any_quorum_prevotes());
+        assert_eq!(engine.find_quorum_prevote_hash(), Some(block_hash));
+
+        // Fill the precommit set directly: the engine commits before the
+        // fourth precommit would arrive.
+        for kp in &kps {
+            engine.state.precommits.insert(kp.pubkey(), Some(block_hash));
+        }
+        assert!(engine.has_quorum_precommits(&Some(block_hash)));
+        assert_eq!(engine.find_quorum_precommit_hash(), Some(block_hash));
+        assert!(!engine.has_quorum_nil_precommits());
+    }
+
+    // ============================
+    // Future-round buffering
+    // ============================
+
+    #[test]
+    fn test_future_round_precommit_quorum_skips_ahead() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
+        engine.start_new_height(1);
+        assert_eq!(engine.round(), 0);
+
+        // Peers already committed in round 2; we never saw rounds 1 or 2.
+        let proposer_pk = proposer::proposer_for_round(&vs, 1, 2).unwrap();
+        let block = make_block(1, proposer_pk);
+        let block_hash = block.hash();
+        let mut last_output = EngineOutput::empty();
+        for kp in &kps[1..] {
+            last_output = engine.on_precommit(make_precommit_msg(1, 2, Some(block_hash), kp));
+        }
+        assert_eq!(engine.round(), 2, "quorum of round-2 precommits should skip ahead");
+        assert_eq!(engine.state().precommits.len(), 3);
+        assert!(engine.state().future_precommits.is_empty());
+        // The block itself is still missing, so nothing commits yet.
+        assert!(last_output.committed_block.is_none());
+
+        // Round 2's proposal arrives late and completes the commit.
+        let output =
+            engine.on_proposal(make_proposal(1, 2, &block, keypair_of(&kps, &proposer_pk), None));
+        let committed = output.committed_block.expect("buffered precommits should commit");
+        assert_eq!(committed.block.hash(), block_hash);
+        assert_eq!(committed.commit_round, 2);
+        assert_eq!(engine.step(), ConsensusStep::Commit);
+    }
+
+    #[test]
+    fn test_future_round_votes_below_quorum_are_buffered() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs);
+        engine.start_new_height(1);
+
+        // One prevote for round 1 is not enough to skip ahead.
+        engine.on_prevote(make_prevote_msg(1, 1, None, &kps[1]));
+        assert_eq!(engine.round(), 0);
+        assert_eq!(engine.state().future_prevotes[&1].len(), 1);
+
+        // Timing out into round 1 replays it.
+        engine.on_timeout(ConsensusStep::Propose);
+        engine.on_timeout(ConsensusStep::Prevote);
+        engine.on_timeout(ConsensusStep::Precommit);
+        assert_eq!(engine.round(), 1);
+        assert_eq!(engine.state().prevotes.get(&kps[1].pubkey()), Some(&None));
+        assert!(engine.state().future_prevotes.is_empty());
+    }
+
+    #[test]
+    fn test_future_round_buffer_is_capped() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let config = BftConfig::default();
+        let horizon = config.max_rounds_per_height;
+        let mut engine = ConsensusEngine::new(config, kps[0].insecure_clone(), vs);
+        engine.start_new_height(1);
+
+        engine.on_prevote(make_prevote_msg(1, horizon, None, &kps[1]));
+        engine.on_prevote(make_prevote_msg(1, horizon + 1, None, &kps[1]));
+        engine.on_prevote(make_prevote_msg(1, u32::MAX, None, &kps[1]));
+        assert_eq!(engine.state().future_prevotes.len(), 1);
+        assert!(engine.state().future_prevotes.contains_key(&horizon));
+    }
+
+    // ============================
+    // Height skips
+    // ============================
+
+    /// Helper: drive `engine` through a round-0 commit at `height`.
+    fn commit_height(engine: &mut ConsensusEngine, kps: &[Keypair], height: u64) {
+        let proposer_pk = proposer::proposer_for_round(engine.validator_set(), height, 0).unwrap();
+        let block = make_block(height, proposer_pk);
+        let block_hash = block.hash();
+        engine.on_proposal(make_proposal(
+            height,
+            0,
+            &block,
+            keypair_of(kps, &proposer_pk),
+            None,
+        ));
+        for kp in &kps[1..] {
+            engine.on_prevote(make_prevote_msg(height, 0, Some(block_hash), kp));
+        }
+        for kp in &kps[1..] {
+            engine.on_precommit(make_precommit_msg(height, 0, Some(block_hash), kp));
+        }
+        assert_eq!(engine.step(), ConsensusStep::Commit);
+    }
+
+    #[test]
+    fn test_height_skip_requests_sync() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs);
+
+        // Nothing committed yet: no gap to fill.
+        assert!(engine.start_new_height(5).sync_request.is_none());
+        commit_height(&mut engine, &kps, 5);
+        assert_eq!(engine.last_committed_height(), Some(5));
+
+        let output = engine.start_new_height(10);
+        assert_eq!(
+            output.sync_request,
+            Some(SyncRequest {
+                from_height: 6,
+                to_height: 9
+            })
+        );
+        assert_eq!(engine.height(), 10);
+
+        // The next height after a commit is not a skip.
+        commit_height(&mut engine, &kps, 10);
+        assert!(engine.start_new_height(11).sync_request.is_none());
+    }
+
+    #[test]
+    fn test_seeded_last_committed_height_requests_sync() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs);
+
+        // A restarted engine seeded from the ledger sees the gap at once.
+        engine.set_last_committed_height(20);
+        assert_eq!(
+            engine.start_new_height(25).sync_request,
+            Some(SyncRequest {
+                from_height: 21,
+                to_height: 24
+            })
+        );
+
+        // Once the gap is filled, restarting the height asks for nothing.
+        engine.set_last_committed_height(24);
+        assert!(engine.start_new_height(25).sync_request.is_none());
+    }
+
+    // ============================
+    // Metrics
+    // ============================
+
+    #[test]
+    fn test_happy_path_commit_reports_metrics() {
+        let (kps, vs) = make_validator_set(3, 100);
+        let metrics = Arc::new(TRv1Metrics::new());
+        let mut engine =
+            ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone())
+                .with_metrics(metrics.clone());
+        engine.start_new_height(1);
+
+        let proposer_pk = proposer::proposer_for_round(&vs, 1, 0).unwrap();
+        let block = make_block(1, proposer_pk);
+        let block_hash = block.hash();
+        engine.on_proposal(make_proposal(
+            1,
+            0,
+            &block,
+            keypair_of(&kps, &proposer_pk),
+            None,
+        ));
+        for kp in &kps[1..] {
+            engine.on_prevote(make_prevote_msg(1, 0, Some(block_hash), kp));
+        }
+        let mut committed = None;
+        for kp in &kps[1..] {
+            committed = engine
+                .on_precommit(make_precommit_msg(1, 0, Some(block_hash), kp))
+                .committed_block
+                .or(committed);
+        }
+
+        assert!(committed.is_some());
+        assert_eq!(metrics.blocks_produced.get(), 1);
+        assert_eq!(metrics.consensus_rounds.get_count(), 1);
+        assert_eq!(metrics.consensus_rounds.get_sum(), 0.0);
+        assert_eq!(metrics.finality_time_ms.get_count(), 1);
+        assert_eq!(metrics.missed_proposals.get(), 0);
+    }
+
+    #[test]
+    fn test_propose_timeout_as_proposer_counts_missed_proposal() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let metrics = Arc::new(TRv1Metrics::new());
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs)
+            .with_metrics(metrics.clone());
+
+        // A height at which someone else proposes round 0 is not our miss.
+        let theirs = (1..).find(|&h| !engine.is_proposer(h, 0)).unwrap();
+        engine.start_new_height(theirs);
+        engine.on_timeout(ConsensusStep::Propose);
+        assert_eq!(metrics.missed_proposals.get(), 0);
+
+        let ours = (1..).find(|&h| engine.is_proposer(h, 0)).unwrap();
+        engine.start_new_height(ours);
+        engine.on_timeout(ConsensusStep::Propose);
+        // A repeated timeout for the same step is not counted twice.
+        engine.on_timeout(ConsensusStep::Propose);
+        assert_eq!(metrics.missed_proposals.get(), 1);
+        assert_eq!(metrics.blocks_produced.get(), 0);
+    }
+
     #[test]
     fn test_commit_timeout_is_noop() {
-        let (pks, vs) = make_validator_set(4, 100);
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs);
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs);
         engine.start_new_height(1);
 
         let output = engine.on_timeout(ConsensusStep::Commit);
@@ -1084,17 +2129,45 @@ mod tests {
 
     #[test]
     fn test_is_proposer() {
-        let (_pks, vs) = make_validator_set(4, 100);
+        let (kps, vs) = make_validator_set(4, 100);
         let proposer_pk = proposer::proposer_for_round(&vs, 1, 0).unwrap();
-        let engine = ConsensusEngine::new(BftConfig::default(), proposer_pk, vs);
+        let engine =
+            ConsensusEngine::new(BftConfig::default(), keypair_of(&kps, &proposer_pk).insecure_clone(), vs);
         assert!(engine.is_proposer(1, 0));
     }
 
+    #[test]
+    fn test_precompute_schedule_cached_for_height() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
+        engine.start_new_height(7);
+
+        let cached = engine.proposer_schedule().unwrap().clone();
+        assert_eq!(cached.height, 7);
+        assert_eq!(
+            cached.schedule.len(),
+            BftConfig::default().max_rounds_per_height as usize
+        );
+
+        let schedule = engine.precompute_schedule(8);
+        assert_eq!(schedule.schedule.len(), 8);
+        assert_eq!(engine.proposer_schedule(), Some(&schedule));
+        for round in 0..8 {
+            assert_eq!(
+                schedule.proposer(round),
+                proposer::proposer_for_round(&vs, 7, round)
+            );
+        }
+
+        engine.update_validator_set(vs);
+        assert!(engine.proposer_schedule().is_none());
+    }
+
     #[test]
     fn test_double_prevote_no_duplicate_message() {
         // Ensure the engine doesn't send two prevotes in the same round
-        let (pks, vs) = make_validator_set(4, 100);
-        let mut engine = ConsensusEngine::new(BftConfig::default(), pks[0], vs.clone());
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs.clone());
         engine.start_new_height(1);
 
         // Timeout on propose → nil prevote
@@ -1105,4 +2178,189 @@ mod tests {
         let output2 = engine.on_timeout(ConsensusStep::Propose);
         assert!(output2.messages.is_empty());
     }
+
+    #[test]
+    fn test_double_sign_evidence_only_for_validators() {
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine = ConsensusEngine::new(BftConfig::default(), kps[0].insecure_clone(), vs);
+        engine.start_new_height(1);
+        let conflicting = || Some(Hash::new_unique());
+
+        let outsider = Keypair::new();
+        engine.on_prevote(make_prevote_msg(1, 0, conflicting(), &outsider));
+        engine.on_prevote(make_prevote_msg(1, 0, conflicting(), &outsider));
+        engine.on_precommit(make_precommit_msg(1, 0, conflicting(), &outsider));
+        engine.on_precommit(make_precommit_msg(1, 0, conflicting(), &outsider));
+        assert!(engine.evidence().evidence().is_empty());
+
+        engine.on_prevote(make_prevote_msg(1, 0, conflicting(), &kps[1]));
+        engine.on_prevote(make_prevote_msg(1, 0, conflicting(), &kps[1]));
+        engine.on_precommit(make_precommit_msg(1, 0, conflicting(), &kps[2]));
+        engine.on_precommit(make_precommit_msg(1, 0, conflicting(), &kps[2]));
+        let offenders: Vec<Pubkey> = engine
+            .evidence()
+            .evidence()
+            .iter()
+            .map(|ev| ev.voter)
+            .collect();
+        assert_eq!(offenders, vec![kps[1].pubkey(), kps[2].pubkey()]);
+    }
+
+    // ============================
+    // Write-ahead log
+    // ============================
+
+    fn wal_config(dir: &std::path::Path) -> BftConfig {
+        BftConfig {
+            wal_dir: Some(dir.to_path_buf()),
+            ..BftConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_wal_restores_round_after_crash() {
+        let dir = tempfile::tempdir().unwrap();
+        let (kps, vs) = make_validator_set(4, 100);
+        let proposer_pk = proposer::proposer_for_round(&vs, 1, 0).unwrap();
+        let block = make_block(1, proposer_pk);
+        let block_hash = block.hash();
+
+        let mut engine =
+            ConsensusEngine::new(wal_config(dir.path()), kps[0].insecure_clone(), vs.clone());
+        engine.start_new_height(1);
+        engine.on_proposal(make_proposal(1, 0, &block, keypair_of(&kps, &proposer_pk), None));
+        engine.on_prevote(make_prevote_msg(1, 0, Some(block_hash), &kps[1]));
+        engine.on_prevote(make_prevote_msg(1, 0, Some(block_hash), &kps[2]));
+        engine.on_precommit(make_precommit_msg(1, 0, Some(block_hash), &kps[1]));
+        assert_eq!(engine.step(), ConsensusStep::Precommit);
+        let before = engine.state().clone();
+        drop(engine); // crash
+
+        let mut restarted =
+            ConsensusEngine::new(wal_config(dir.path()), kps[0].insecure_clone(), vs.clone());
+        let output = restarted.start_new_height(1);
+        let after = restarted.state();
+        assert_eq!(after.round, before.round);
+        assert_eq!(after.step, ConsensusStep::Precommit);
+        assert_eq!(after.proposal.as_ref().map(|b| b.hash()), Some(block_hash));
+        assert_eq!(after.prevotes, before.prevotes);
+        assert_eq!(after.precommits, before.precommits);
+        assert_eq!(after.locked_value, Some(block_hash));
+        assert_eq!(after.locked_round, Some(0));
+        assert!(after.polka_certificates.contains_key(&0));
+
+        // Replay re-derives our own votes; it never produces a conflicting one.
+        for msg in &output.messages {
+            if let ConsensusMessage::Prevote { block_hash: h, .. }
+            | ConsensusMessage::Precommit { block_hash: h, .. } = msg
+            {
+                assert_eq!(*h, Some(block_hash));
+            }
+        }
+        assert!(output.committed_block.is_none());
+
+        // The last precommit arrives after the restart and commits the block.
+        let output = restarted.on_precommit(make_precommit_msg(1, 0, Some(block_hash), &kps[2]));
+        assert_eq!(output.committed_block.unwrap().block.hash(), block_hash);
+    }
+
+    #[test]
+    fn test_wal_prevents_revote_after_crash() {
+        let dir = tempfile::tempdir().unwrap();
+        let (kps, vs) = make_validator_set(4, 100);
+
+        let mut engine =
+            ConsensusEngine::new(wal_config(dir.path()), kps[0].insecure_clone(), vs.clone());
+        engine.start_new_height(1);
+        engine.on_timeout(ConsensusStep::Propose);
+        engine.on_timeout(ConsensusStep::Prevote);
+        engine.on_timeout(ConsensusStep::Precommit);
+        // Nil prevote in round 1, then crash.
+        let output = engine.on_timeout(ConsensusStep::Propose);
+        assert_eq!(output.messages.len(), 1);
+        drop(engine);
+
+        let mut restarted =
+            ConsensusEngine::new(wal_config(dir.path()), kps[0].insecure_clone(), vs.clone());
+        restarted.start_new_height(1);
+        assert_eq!(restarted.round(), 1);
+        assert_eq!(restarted.state().prevotes.get(&kps[0].pubkey()), Some(&None));
+
+        // A late proposal for round 1 must not draw a second prevote from us.
+        let proposer_pk = proposer::proposer_for_round(&vs, 1, 1).unwrap();
+        let block = make_block(1, proposer_pk);
+        let output =
+            restarted.on_proposal(make_proposal(1, 1, &block, keypair_of(&kps, &proposer_pk), None));
+        assert!(output
+            .messages
+            .iter()
+            .all(|msg| !matches!(msg, ConsensusMessage::Prevote { .. })));
+    }
+
+    #[test]
+    fn test_wal_only_logs_messages_the_engine_acts_on() {
+        let dir = tempfile::tempdir().unwrap();
+        let (kps, vs) = make_validator_set(4, 100);
+        let mut engine =
+            ConsensusEngine::new(wal_config(dir.path()), kps[0].insecure_clone(), vs.clone());
+        engine.start_new_height(1);
+        let wal_len = || ConsensusWal::open(dir.path()).unwrap().recover(1).len();
+
+        let vote = make_prevote_msg(1, 0, Some(Hash::new_unique()), &kps[1]);
+        engine.on_prevote(vote.clone());
+        assert_eq!(wal_len(), 1);
+
+        // Repeats, outsiders, other heights and rounds beyond the horizon
+        // never reach the WAL.
+        engine.on_prevote(vote);
+        engine.on_prevote(make_prevote_msg(1, 0, None, &Keypair::new()));
+        engine.on_precommit(make_precommit_msg(2, 0, None, &kps[1]));
+        engine.on_precommit(make_precommit_msg(1, 6, None, &kps[1]));
+        let proposer_pk = proposer::proposer_for_round(&vs, 1, 6).unwrap();
+        engine.on_proposal(make_proposal(
+            1,
+            6,
+            &make_block(1, proposer_pk),
+            keypair_of(&kps, &proposer_pk),
+            None,
+        ));
+        assert_eq!(wal_len(), 1);
+
+        // Neither do a proposal we already hold or votes for past rounds.
+        let proposer_pk = proposer::proposer_for_round(&vs, 1, 1).unwrap();
+        let proposal = make_proposal(
+            1,
+            1,
+            &make_block(1, proposer_pk),
+            keypair_of(&kps, &proposer_pk),
+            None,
+        );
+        engine.on_proposal(proposal.clone());
+        assert_eq!(engine.round(), 1);
+        // The proposal and our prevote for it.
+        assert_eq!(wal_len(), 3);
+        engine.on_proposal(proposal);
+        engine.on_precommit(make_precommit_msg(1, 0, None, &kps[2]));
+        assert_eq!(wal_len(), 3);
+    }
+
+    #[test]
+    fn test_wal_prunes_old_heights() {
+        let dir = tempfile::tempdir().unwrap();
+        let (kps, vs) = make_validator_set(4, 100);
+        let config = BftConfig {
+            wal_retention_heights: 2,
+            ..wal_config(dir.path())
+        };
+        let mut engine = ConsensusEngine::new(config, kps[0].insecure_clone(), vs);
+        for height in 1..=5 {
+            engine.start_new_height(height);
+            engine.on_timeout(ConsensusStep::Propose);
+        }
+
+        let wal = ConsensusWal::open(dir.path()).unwrap();
+        assert!(wal.recover(2).is_empty());
+        assert_eq!(wal.recover(3).len(), 1);
+        assert_eq!(wal.recover(5).len(), 1);
+    }
 }
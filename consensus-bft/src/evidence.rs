@@ -5,29 +5,83 @@
 //! such violations for later slashing.
 
 use {
-    crate::types::ConsensusMessage,
+    crate::types::{verify_consensus_message, ConsensusMessage},
+    serde::{Deserialize, Serialize},
     solana_hash::Hash,
     solana_pubkey::Pubkey,
-    solana_signature::Signature,
-    std::collections::HashMap,
+    std::collections::{HashMap, HashSet},
 };
 
 /// Evidence of a validator double-signing: casting two conflicting votes
 /// at the same (height, round, step).
-#[derive(Debug, Clone)]
+///
+/// Both signed votes are kept so that anyone can re-check the offense with
+/// [`DoubleSignEvidence::verify`] before slashing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DoubleSignEvidence {
-    /// The offending validator.
-    pub validator: Pubkey,
     /// Block height at which the offense occurred.
     pub height: u64,
     /// Round at which the offense occurred.
     pub round: u32,
-    /// The type of conflicting messages.
-    pub kind: EvidenceKind,
-    /// First vote (hash voted for, signature).
-    pub vote_a: (Option<Hash>, Signature),
-    /// Second conflicting vote (hash voted for, signature).
-    pub vote_b: (Option<Hash>, Signature),
+    /// The offending validator.
+    pub voter: Pubkey,
+    /// The first vote seen from `voter`.
+    pub first_vote: ConsensusMessage,
+    /// The conflicting vote that followed it.
+    pub second_vote: ConsensusMessage,
+}
+
+impl DoubleSignEvidence {
+    /// The type of conflicting messages, or `None` if the pair is not two
+    /// votes of the same step.
+    pub fn kind(&self) -> Option<EvidenceKind> {
+        match (&self.first_vote, &self.second_vote) {
+            (ConsensusMessage::Prevote { .. }, ConsensusMessage::Prevote { .. }) => {
+                Some(EvidenceKind::ConflictingPrevote)
+            }
+            (ConsensusMessage::Precommit { .. }, ConsensusMessage::Precommit { .. }) => {
+                Some(EvidenceKind::ConflictingPrecommit)
+            }
+            _ => None,
+        }
+    }
+
+    /// Check that the evidence proves a double-sign: two votes of the same
+    /// step, validly signed by `voter` at `(height, round)`, for different
+    /// block hashes.
+    pub fn verify(&self) -> bool {
+        if self.kind().is_none() {
+            return false;
+        }
+        let matches = |vote: &ConsensusMessage| {
+            vote.height() == self.height
+                && vote.round() == self.round
+                && *vote.sender() == self.voter
+                && verify_consensus_message(vote)
+        };
+        matches(&self.first_vote)
+            && matches(&self.second_vote)
+            && voted_hash(&self.first_vote) != voted_hash(&self.second_vote)
+    }
+
+    /// Compact binary encoding for inclusion in block data.
+    pub fn to_bytes(&self) -> bincode::Result<Vec<u8>> {
+        bincode::serialize(self)
+    }
+
+    /// Decode evidence produced by [`DoubleSignEvidence::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> bincode::Result<Self> {
+        bincode::deserialize(bytes)
+    }
+}
+
+/// The block hash a vote is for; `None` for proposals.
+fn voted_hash(msg: &ConsensusMessage) -> Option<Option<Hash>> {
+    match msg {
+        ConsensusMessage::Prevote { block_hash, .. }
+        | ConsensusMessage::Precommit { block_hash, .. } => Some(*block_hash),
+        ConsensusMessage::Proposal { .. } => None,
+    }
 }
 
 /// The type of double-sign.
@@ -63,20 +117,15 @@ enum VoteType {
     Precommit,
 }
 
-/// Stored vote: the hash that was voted for and the signature.
-#[derive(Debug, Clone)]
-struct StoredVote {
-    block_hash: Option<Hash>,
-    signature: Signature,
-}
-
 /// Collects and detects double-sign evidence.
 ///
 /// Maintains a sliding window of votes to detect conflicting votes
 /// from the same validator in the same (height, round).
 pub struct EvidenceCollector {
     /// Map from vote key to the first vote seen.
-    votes: HashMap<VoteKey, StoredVote>,
+    votes: HashMap<VoteKey, ConsensusMessage>,
+    /// Vote keys already reported, so each offense is flagged once.
+    flagged: HashSet<VoteKey>,
     /// Collected evidence of double-signing.
     evidence: Vec<DoubleSignEvidence>,
     /// Minimum height to track (older votes are pruned).
@@ -88,6 +137,7 @@ impl EvidenceCollector {
     pub fn new() -> Self {
         Self {
             votes: HashMap::new(),
+            flagged: HashSet::new(),
             evidence: Vec::new(),
             min_height: 0,
         }
@@ -96,43 +146,29 @@ impl EvidenceCollector {
     /// Process a consensus message and check for double-signing.
     /// Returns `Some(evidence)` if a double-sign is detected, `None` otherwise.
     pub fn check_and_record(&mut self, msg: &ConsensusMessage) -> Option<DoubleSignEvidence> {
-        let (key, current_vote) = match msg {
+        let key = match msg {
             ConsensusMessage::Prevote {
                 height,
                 round,
-                block_hash,
                 voter,
-                signature,
-            } => (
-                VoteKey {
-                    height: *height,
-                    round: *round,
-                    voter: *voter,
-                    kind: VoteType::Prevote,
-                },
-                StoredVote {
-                    block_hash: *block_hash,
-                    signature: *signature,
-                },
-            ),
+                ..
+            } => VoteKey {
+                height: *height,
+                round: *round,
+                voter: *voter,
+                kind: VoteType::Prevote,
+            },
             ConsensusMessage::Precommit {
                 height,
                 round,
-                block_hash,
                 voter,
-                signature,
-            } => (
-                VoteKey {
-                    height: *height,
-                    round: *round,
-                    voter: *voter,
-                    kind: VoteType::Precommit,
-                },
-                StoredVote {
-                    block_hash: *block_hash,
-                    signature: *signature,
-                },
-            ),
+                ..
+            } => VoteKey {
+                height: *height,
+                round: *round,
+                voter: *voter,
+                kind: VoteType::Precommit,
+            },
             // Proposals don't count as votes for double-sign purposes
             ConsensusMessage::Proposal { .. } => return None,
         };
@@ -143,29 +179,25 @@ impl EvidenceCollector {
         }
 
         if let Some(existing) = self.votes.get(&key) {
-            // Check if this is a conflicting vote
-            if existing.block_hash != current_vote.block_hash {
-                let evidence_kind = match key.kind {
-                    VoteType::Prevote => EvidenceKind::ConflictingPrevote,
-                    VoteType::Precommit => EvidenceKind::ConflictingPrecommit,
-                };
+            // Check if this is a conflicting vote not already reported
+            if voted_hash(existing) != voted_hash(msg) && !self.flagged.contains(&key) {
                 let ev = DoubleSignEvidence {
-                    validator: key.voter,
                     height: key.height,
                     round: key.round,
-                    kind: evidence_kind,
-                    vote_a: (existing.block_hash, existing.signature),
-                    vote_b: (current_vote.block_hash, current_vote.signature),
+                    voter: key.voter,
+                    first_vote: existing.clone(),
+                    second_vote: msg.clone(),
                 };
+                self.flagged.insert(key);
                 self.evidence.push(ev.clone());
                 return Some(ev);
             }
-            // Same vote (duplicate) — not evidence
+            // Same vote (duplicate) or an already-reported offense
             return None;
         }
 
         // First vote from this validator at this (height, round, type)
-        self.votes.insert(key, current_vote);
+        self.votes.insert(key, msg.clone());
         None
     }
 
@@ -173,6 +205,7 @@ impl EvidenceCollector {
     pub fn prune(&mut self, min_height: u64) {
         self.min_height = min_height;
         self.votes.retain(|k, _| k.height >= min_height);
+        self.flagged.retain(|k| k.height >= min_height);
         // Keep evidence — it needs to be submitted for slashing
     }
 
@@ -193,7 +226,7 @@ impl EvidenceCollector {
 
     /// Check if a specific validator has any evidence against them.
     pub fn has_evidence_against(&self, validator: &Pubkey) -> bool {
-        self.evidence.iter().any(|e| e.validator == *validator)
+        self.evidence.iter().any(|e| e.voter == *validator)
     }
 }
 
@@ -205,7 +238,12 @@ impl Default for EvidenceCollector {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {
+        super::*,
+        solana_keypair::Keypair,
+        solana_signature::Signature,
+        solana_signer::Signer,
+    };
 
     fn make_prevote(height: u64, round: u32, hash: Option<Hash>, voter: Pubkey) -> ConsensusMessage {
         ConsensusMessage::Prevote {
@@ -256,12 +294,12 @@ mod tests {
         assert!(evidence.is_some());
 
         let ev = evidence.unwrap();
-        assert_eq!(ev.validator, voter);
+        assert_eq!(ev.voter, voter);
         assert_eq!(ev.height, 1);
         assert_eq!(ev.round, 0);
-        assert_eq!(ev.kind, EvidenceKind::ConflictingPrevote);
-        assert_eq!(ev.vote_a.0, hash_a);
-        assert_eq!(ev.vote_b.0, hash_b);
+        assert_eq!(ev.kind(), Some(EvidenceKind::ConflictingPrevote));
+        assert_eq!(voted_hash(&ev.first_vote), Some(hash_a));
+        assert_eq!(voted_hash(&ev.second_vote), Some(hash_b));
     }
 
     #[test]
@@ -277,7 +315,88 @@ mod tests {
         assert!(collector.check_and_record(&msg1).is_none());
         let evidence = collector.check_and_record(&msg2);
         assert!(evidence.is_some());
-        assert_eq!(evidence.unwrap().kind, EvidenceKind::ConflictingPrecommit);
+        assert_eq!(
+            evidence.unwrap().kind(),
+            Some(EvidenceKind::ConflictingPrecommit)
+        );
+    }
+
+    #[test]
+    fn test_double_sign_flagged_once() {
+        let mut collector = EvidenceCollector::new();
+        let voter = Pubkey::new_unique();
+        let hash_a = Some(Hash::new_unique());
+        let hash_b = Some(Hash::new_unique());
+
+        assert!(collector.check_and_record(&make_prevote(1, 0, hash_a, voter)).is_none());
+        assert!(collector.check_and_record(&make_prevote(1, 0, hash_b, voter)).is_some());
+        // Re-delivery of the conflicting vote, or a third value, is the same offense.
+        assert!(collector.check_and_record(&make_prevote(1, 0, hash_b, voter)).is_none());
+        let hash_c = Some(Hash::new_unique());
+        assert!(collector.check_and_record(&make_prevote(1, 0, hash_c, voter)).is_none());
+        assert_eq!(collector.evidence().len(), 1);
+    }
+
+    fn signed_prevote(height: u64, round: u32, hash: Option<Hash>, kp: &Keypair) -> ConsensusMessage {
+        make_prevote(height, round, hash, kp.pubkey()).signed(kp)
+    }
+
+    fn signed_evidence(kp: &Keypair) -> DoubleSignEvidence {
+        let mut collector = EvidenceCollector::new();
+        collector.check_and_record(&signed_prevote(3, 1, Some(Hash::new_unique()), kp));
+        collector
+            .check_and_record(&signed_prevote(3, 1, Some(Hash::new_unique()), kp))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_evidence_verify() {
+        let kp = Keypair::new();
+        let ev = signed_evidence(&kp);
+        assert!(ev.verify());
+
+        // Same hash twice is not an offense.
+        let mut same = ev.clone();
+        same.second_vote = same.first_vote.clone();
+        assert!(!same.verify());
+
+        // Attributed to someone else.
+        let mut framed = ev.clone();
+        framed.voter = Pubkey::new_unique();
+        assert!(!framed.verify());
+
+        // A vote from another round.
+        let mut other_round = ev.clone();
+        other_round.second_vote = signed_prevote(3, 2, Some(Hash::new_unique()), &kp);
+        assert!(!other_round.verify());
+
+        // Signed by a different key.
+        let mut forged = ev.clone();
+        let ConsensusMessage::Prevote { signature, .. } = &mut forged.second_vote else {
+            unreachable!()
+        };
+        *signature = *signed_prevote(3, 1, None, &Keypair::new()).signature();
+        assert!(!forged.verify());
+
+        // A prevote paired with a precommit.
+        let mut mixed = ev;
+        mixed.second_vote = make_precommit(3, 1, Some(Hash::new_unique()), kp.pubkey()).signed(&kp);
+        assert_eq!(mixed.kind(), None);
+        assert!(!mixed.verify());
+    }
+
+    #[test]
+    fn test_evidence_bincode_roundtrip() {
+        let kp = Keypair::new();
+        let ev = signed_evidence(&kp);
+        let decoded = DoubleSignEvidence::from_bytes(&ev.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.voter, ev.voter);
+        assert_eq!(decoded.height, ev.height);
+        assert_eq!(decoded.round, ev.round);
+        assert_eq!(voted_hash(&decoded.first_vote), voted_hash(&ev.first_vote));
+        assert_eq!(voted_hash(&decoded.second_vote), voted_hash(&ev.second_vote));
+        assert!(decoded.verify());
+        assert!(DoubleSignEvidence::from_bytes(&[1, 2, 3]).is_err());
     }
 
     #[test]
@@ -371,6 +490,7 @@ mod tests {
         let drained = collector.drain_evidence();
         assert_eq!(drained.len(), 1);
         assert!(collector.evidence().is_empty());
+        assert!(collector.drain_evidence().is_empty());
     }
 
     #[test]
@@ -403,10 +523,12 @@ mod tests {
                 transactions: vec![],
                 state_root: Hash::default(),
                 proposer: Pubkey::default(),
+                evidence: vec![],
             },
             proposer: Pubkey::default(),
             signature: Signature::default(),
             valid_round: None,
+            polka_certificate: None,
         };
         assert!(collector.check_and_record(&msg).is_none());
         assert_eq!(collector.tracked_votes(), 0);
@@ -45,18 +45,23 @@
 pub mod config;
 pub mod engine;
 pub mod evidence;
+pub mod light_client;
 pub mod proposer;
 pub mod timeout;
 pub mod types;
 pub mod validator_set;
+pub mod wal;
 
 // Re-exports for convenience
 pub use config::BftConfig;
-pub use engine::{ConsensusEngine, EngineOutput};
+pub use engine::{ConsensusEngine, EngineOutput, SyncRequest};
 pub use evidence::{DoubleSignEvidence, EvidenceCollector, EvidenceKind};
-pub use proposer::{is_proposer, proposer_for_round};
+pub use light_client::LightClientProof;
+pub use proposer::{is_proposer, proposer_for_round, proposer_schedule, ProposerSchedule};
 pub use timeout::TimeoutScheduler;
 pub use types::{
-    CommittedBlock, ConsensusMessage, ConsensusState, ConsensusStep, ProposedBlock,
+    verify_consensus_message, CommittedBlock, ConsensusMessage, ConsensusState, ConsensusStep,
+    ProposedBlock,
 };
 pub use validator_set::{ValidatorInfo, ValidatorSet};
+pub use wal::{ConsensusWal, WalError};
@@ -0,0 +1,227 @@
+//! Light-client proofs of block finality.
+//!
+//! A [`LightClientProof`] lets a party that does not run a validator — an
+//! exchange, a bridge — check that a block was committed.  It carries the
+//! committing validator set and the precommit signatures of the commit; the
+//! verifier only needs the [`ValidatorSet::commitment_hash`] of the set it
+//! trusts for that height.
+//!
+//! Verification:
+//!
+//! 1. the validators in the proof hash to the trusted commitment,
+//! 2. each signature is a valid precommit for `(height, round, block_hash)`
+//!    by a distinct member of that set, and
+//! 3. the stake of those signers reaches the quorum threshold.
+
+use {
+    crate::{
+        types::{verify_consensus_message, CommittedBlock, ConsensusMessage},
+        validator_set::ValidatorSet,
+    },
+    serde::{Deserialize, Serialize},
+    solana_hash::Hash,
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    std::collections::HashSet,
+};
+
+/// Basis points in 100%.
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Self-contained evidence that a block was committed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientProof {
+    /// Height of the committed block.
+    pub height: u64,
+    /// Round in which the block was committed; precommits are bound to it.
+    pub round: u32,
+    /// Hash of the committed block.
+    pub block_hash: Hash,
+    /// [`ValidatorSet::commitment_hash`] of `validators`.
+    pub validator_set_hash: Hash,
+    /// The committing validator set as `(pubkey, stake)`, sorted by pubkey.
+    pub validators: Vec<(Pubkey, u64)>,
+    /// Precommit signatures as `(pubkey, signature, stake)`.
+    pub commit_signatures: Vec<(Pubkey, Signature, u64)>,
+    /// Total stake of `validators`.
+    pub total_stake: u64,
+    /// Stake of the validators in `commit_signatures`.
+    pub signed_stake: u64,
+}
+
+impl CommittedBlock {
+    /// Build a light-client proof of this commit by `validator_set`, the set
+    /// that committed it.
+    ///
+    /// Signers outside `validator_set` are left out.
+    pub fn light_client_proof(&self, validator_set: &ValidatorSet) -> LightClientProof {
+        let commit_signatures: Vec<(Pubkey, Signature, u64)> = self
+            .commit_signatures
+            .iter()
+            .filter(|(pubkey, _)| validator_set.contains(pubkey))
+            .map(|(pubkey, signature)| (*pubkey, *signature, validator_set.stake_of(pubkey)))
+            .collect();
+        let signed_stake = commit_signatures
+            .iter()
+            .map(|(_, _, stake)| *stake)
+            .fold(0u64, u64::saturating_add);
+        LightClientProof {
+            height: self.block.height,
+            round: self.commit_round,
+            block_hash: self.block.hash(),
+            validator_set_hash: validator_set.commitment_hash(),
+            validators: validator_set.sorted_by_pubkey(),
+            commit_signatures,
+            total_stake: validator_set.total_stake(),
+            signed_stake,
+        }
+    }
+}
+
+impl LightClientProof {
+    /// Check the proof against the validator set the caller trusts, given by
+    /// its commitment hash.
+    ///
+    /// `quorum_threshold_bps` is the share of total stake, in basis points,
+    /// that must have signed; 6_667 for the consensus 2/3 quorum.  Stake
+    /// totals are recomputed from the signatures that verify, and a proof
+    /// whose stated totals disagree is rejected.
+    pub fn verify(&self, known_validator_set_hash: Hash, quorum_threshold_bps: u64) -> bool {
+        if self.validator_set_hash != known_validator_set_hash {
+            return false;
+        }
+        let validator_set = ValidatorSet::new(self.validators.clone());
+        if validator_set.commitment_hash() != known_validator_set_hash
+            || validator_set.total_stake() != self.total_stake
+            || validator_set.is_empty()
+        {
+            return false;
+        }
+
+        let mut signers = HashSet::new();
+        let mut signed_stake = 0u64;
+        for (voter, signature, stake) in &self.commit_signatures {
+            let precommit = ConsensusMessage::Precommit {
+                height: self.height,
+                round: self.round,
+                block_hash: Some(self.block_hash),
+                voter: *voter,
+                signature: *signature,
+            };
+            if *stake != validator_set.stake_of(voter)
+                || !signers.insert(*voter)
+                || !verify_consensus_message(&precommit)
+            {
+                return false;
+            }
+            signed_stake = signed_stake.saturating_add(*stake);
+        }
+        if signed_stake != self.signed_stake {
+            return false;
+        }
+
+        u128::from(signed_stake) * u128::from(BPS_DENOMINATOR)
+            >= u128::from(self.total_stake) * u128::from(quorum_threshold_bps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::types::ProposedBlock,
+        solana_keypair::Keypair,
+        solana_signer::Signer,
+    };
+
+    const TWO_THIRDS_BPS: u64 = 6_667;
+
+    /// A block at height 5 committed in round 1 by the first `signers` of
+    /// four equally staked validators.
+    fn committed_block(signers: usize) -> (CommittedBlock, ValidatorSet) {
+        let keypairs: Vec<Keypair> = (0..4).map(|_| Keypair::new()).collect();
+        let validator_set =
+            ValidatorSet::new(keypairs.iter().map(|kp| (kp.pubkey(), 100)).collect());
+        let block = ProposedBlock {
+            parent_hash: Hash::new_unique(),
+            height: 5,
+            timestamp: 1_000,
+            transactions: vec![],
+            state_root: Hash::new_unique(),
+            proposer: keypairs[0].pubkey(),
+            evidence: vec![],
+        };
+        let commit_signatures = keypairs[..signers]
+            .iter()
+            .map(|kp| {
+                let precommit = ConsensusMessage::Precommit {
+                    height: 5,
+                    round: 1,
+                    block_hash: Some(block.hash()),
+                    voter: kp.pubkey(),
+                    signature: Signature::default(),
+                }
+                .signed(kp);
+                (kp.pubkey(), *precommit.signature())
+            })
+            .collect();
+        let committed = CommittedBlock {
+            block,
+            commit_signatures,
+            commit_round: 1,
+        };
+        (committed, validator_set)
+    }
+
+    #[test]
+    fn test_proof_verifies_and_rejects_tampered_block_hash() {
+        let (committed, validator_set) = committed_block(4);
+        let trusted = validator_set.commitment_hash();
+
+        let proof = committed.light_client_proof(&validator_set);
+        assert_eq!(proof.signed_stake, 400);
+        assert_eq!(proof.total_stake, 400);
+        assert!(proof.verify(trusted, TWO_THIRDS_BPS));
+
+        let tampered = LightClientProof {
+            block_hash: Hash::new_unique(),
+            ..proof.clone()
+        };
+        assert!(!tampered.verify(trusted, TWO_THIRDS_BPS));
+
+        // Signatures are bound to the commit round, too.
+        let wrong_round = LightClientProof {
+            round: 0,
+            ..proof
+        };
+        assert!(!wrong_round.verify(trusted, TWO_THIRDS_BPS));
+    }
+
+    #[test]
+    fn test_proof_rejects_untrusted_set_and_insufficient_stake() {
+        let (committed, validator_set) = committed_block(3);
+        let proof = committed.light_client_proof(&validator_set);
+        let trusted = validator_set.commitment_hash();
+        assert!(proof.verify(trusted, TWO_THIRDS_BPS));
+        assert!(!proof.verify(trusted, 8_000));
+        assert!(!proof.verify(Hash::new_unique(), TWO_THIRDS_BPS));
+
+        // Claiming more stake for a signer than the set gives it fails.
+        let mut inflated = proof.clone();
+        inflated.commit_signatures[0].2 += 1;
+        inflated.signed_stake += 1;
+        assert!(!inflated.verify(trusted, TWO_THIRDS_BPS));
+
+        // So does counting a signature twice.
+        let mut duplicated = proof;
+        duplicated
+            .commit_signatures
+            .push(duplicated.commit_signatures[0]);
+        duplicated.signed_stake += 100;
+        assert!(!duplicated.verify(trusted, TWO_THIRDS_BPS));
+
+        let (committed, validator_set) = committed_block(2);
+        let proof = committed.light_client_proof(&validator_set);
+        assert!(!proof.verify(validator_set.commitment_hash(), TWO_THIRDS_BPS));
+    }
+}
@@ -47,6 +47,37 @@ pub fn proposer_for_round(
     validator_set.get(0).map(|v| v.pubkey)
 }
 
+/// Proposers for consecutive rounds of a single height, computed up front.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProposerSchedule {
+    /// The height this schedule applies to.
+    pub height: u64,
+    /// `schedule[r]` is the proposer for round `r`.
+    pub schedule: Vec<Pubkey>,
+}
+
+impl ProposerSchedule {
+    /// Returns the proposer for `round`, or `None` if the round is beyond
+    /// the pre-computed range.
+    pub fn proposer(&self, round: u32) -> Option<Pubkey> {
+        self.schedule.get(round as usize).copied()
+    }
+}
+
+/// Pre-compute the proposers for rounds `0..n_rounds` at `height`.
+///
+/// Entry `r` equals `proposer_for_round(validator_set, height, r)`.
+/// Returns an empty schedule if the validator set has no stake.
+pub fn proposer_schedule(
+    validator_set: &ValidatorSet,
+    height: u64,
+    n_rounds: u32,
+) -> Vec<Pubkey> {
+    (0..n_rounds)
+        .map_while(|round| proposer_for_round(validator_set, height, round))
+        .collect()
+}
+
 /// Check if a specific validator is the proposer for a given (height, round).
 pub fn is_proposer(
     validator_set: &ValidatorSet,
@@ -153,6 +184,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_proposer_schedule_matches_per_round_selection() {
+        let pks = make_pubkeys(5);
+        let vs = ValidatorSet::new(vec![
+            (pks[0], 100),
+            (pks[1], 250),
+            (pks[2], 50),
+            (pks[3], 400),
+            (pks[4], 200),
+        ]);
+        for height in [0, 1, 17, 1_000] {
+            let schedule = proposer_schedule(&vs, height, 10);
+            assert_eq!(schedule.len(), 10);
+            for (round, pk) in schedule.iter().enumerate() {
+                assert_eq!(
+                    Some(*pk),
+                    proposer_for_round(&vs, height, round as u32),
+                    "Mismatch at height={height}, round={round}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_proposer_schedule_empty_validator_set() {
+        let vs = ValidatorSet::new(vec![]);
+        assert!(proposer_schedule(&vs, 0, 5).is_empty());
+    }
+
     #[test]
     fn test_empty_validator_set() {
         let vs = ValidatorSet::new(vec![]);
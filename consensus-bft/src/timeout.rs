@@ -1,8 +1,10 @@
 //! Timeout management for the BFT consensus protocol.
 //!
-//! Each step (Propose, Prevote, Precommit) has a configurable timeout.
-//! The Propose timeout increases linearly with round number to accommodate
-//! slower proposers and network delays.
+//! Each step (Propose, Prevote, Precommit) has a configurable timeout that
+//! increases linearly with the round number, as in the Tendermint paper:
+//! `timeout(step, round) = base(step) + round * delta(step)`, capped at
+//! `max_timeout_ms`. Growing timeouts keep the protocol live on lossy
+//! networks where several rounds may be needed to decide.
 
 use {
     crate::config::BftConfig,
@@ -13,12 +15,25 @@ use {
 /// Tracks active timeouts for each consensus step.
 #[derive(Debug)]
 pub struct TimeoutScheduler {
-    config: BftConfig,
+    /// Round-0 propose timeout in milliseconds.
+    base_propose_ms: u64,
+    /// Round-0 prevote timeout in milliseconds.
+    base_prevote_ms: u64,
+    /// Round-0 precommit timeout in milliseconds.
+    base_precommit_ms: u64,
+    /// Per-round increase of the propose timeout in milliseconds.
+    propose_delta_ms: u64,
+    /// Per-round increase of the prevote timeout in milliseconds.
+    prevote_delta_ms: u64,
+    /// Per-round increase of the precommit timeout in milliseconds.
+    precommit_delta_ms: u64,
+    /// Cap on any round-adjusted timeout in milliseconds.
+    max_timeout_ms: u64,
     /// When the current timeout started (if any).
     started_at: Option<Instant>,
     /// Which step the timeout is for.
     active_step: Option<ConsensusStep>,
-    /// The current round (affects propose timeout).
+    /// The round the active timeout was started for.
     current_round: u32,
 }
 
@@ -26,7 +41,13 @@ impl TimeoutScheduler {
     /// Create a new timeout scheduler with the given configuration.
     pub fn new(config: BftConfig) -> Self {
         Self {
-            config,
+            base_propose_ms: config.propose_timeout_base_ms,
+            base_prevote_ms: config.prevote_timeout_ms,
+            base_precommit_ms: config.precommit_timeout_ms,
+            propose_delta_ms: config.propose_timeout_delta_ms,
+            prevote_delta_ms: config.prevote_timeout_delta_ms,
+            precommit_delta_ms: config.precommit_timeout_delta_ms,
+            max_timeout_ms: config.max_timeout_ms,
             started_at: None,
             active_step: None,
             current_round: 0,
@@ -46,16 +67,20 @@ impl TimeoutScheduler {
         self.active_step = None;
     }
 
-    /// Returns the duration for the timeout of the given step at the given round.
-    pub fn timeout_duration(&self, step: ConsensusStep, round: u32) -> Duration {
-        let ms = match step {
+    /// Returns the duration for the timeout of the given step at the given
+    /// round: `base + round * delta`, capped at `max_timeout_ms`.
+    pub fn timeout_for(&self, step: ConsensusStep, round: u32) -> Duration {
+        let (base, delta) = match step {
             ConsensusStep::Propose | ConsensusStep::NewRound => {
-                self.config.propose_timeout_ms(round)
+                (self.base_propose_ms, self.propose_delta_ms)
             }
-            ConsensusStep::Prevote => self.config.prevote_timeout_ms,
-            ConsensusStep::Precommit => self.config.precommit_timeout_ms,
-            ConsensusStep::Commit => 0, // Commit doesn't timeout
+            ConsensusStep::Prevote => (self.base_prevote_ms, self.prevote_delta_ms),
+            ConsensusStep::Precommit => (self.base_precommit_ms, self.precommit_delta_ms),
+            ConsensusStep::Commit => return Duration::ZERO, // Commit doesn't timeout
         };
+        let ms = base
+            .saturating_add(delta.saturating_mul(round as u64))
+            .min(self.max_timeout_ms);
         Duration::from_millis(ms)
     }
 
@@ -64,7 +89,7 @@ impl TimeoutScheduler {
     pub fn check_expired(&self) -> Option<ConsensusStep> {
         let started_at = self.started_at?;
         let step = self.active_step?;
-        let duration = self.timeout_duration(step, self.current_round);
+        let duration = self.timeout_for(step, self.current_round);
         if started_at.elapsed() >= duration {
             Some(step)
         } else {
@@ -77,7 +102,7 @@ impl TimeoutScheduler {
     pub fn remaining(&self) -> Option<Duration> {
         let started_at = self.started_at?;
         let step = self.active_step?;
-        let duration = self.timeout_duration(step, self.current_round);
+        let duration = self.timeout_for(step, self.current_round);
         let elapsed = started_at.elapsed();
         Some(duration.saturating_sub(elapsed))
     }
@@ -93,8 +118,11 @@ impl TimeoutScheduler {
     }
 
     /// Update the configuration (e.g., for dynamic parameter tuning).
+    /// The active timeout, if any, keeps running under the new durations.
     pub fn update_config(&mut self, config: BftConfig) {
-        self.config = config;
+        let active = (self.started_at, self.active_step, self.current_round);
+        *self = Self::new(config);
+        (self.started_at, self.active_step, self.current_round) = active;
     }
 }
 
@@ -107,21 +135,65 @@ mod tests {
     fn test_timeout_duration_propose_increases() {
         let config = BftConfig::default();
         let scheduler = TimeoutScheduler::new(config);
-        let d0 = scheduler.timeout_duration(ConsensusStep::Propose, 0);
-        let d1 = scheduler.timeout_duration(ConsensusStep::Propose, 1);
-        let d2 = scheduler.timeout_duration(ConsensusStep::Propose, 2);
+        let d0 = scheduler.timeout_for(ConsensusStep::Propose, 0);
+        let d1 = scheduler.timeout_for(ConsensusStep::Propose, 1);
+        let d2 = scheduler.timeout_for(ConsensusStep::Propose, 2);
         assert!(d1 > d0);
         assert!(d2 > d1);
     }
 
     #[test]
-    fn test_timeout_duration_prevote_constant() {
-        let config = BftConfig::default();
+    fn test_timeout_for_grows_linearly_per_step() {
+        let config = BftConfig {
+            max_timeout_ms: u64::MAX,
+            ..BftConfig::default()
+        };
+        let scheduler = TimeoutScheduler::new(config.clone());
+        for (step, base, delta) in [
+            (
+                ConsensusStep::Propose,
+                config.propose_timeout_base_ms,
+                config.propose_timeout_delta_ms,
+            ),
+            (
+                ConsensusStep::Prevote,
+                config.prevote_timeout_ms,
+                config.prevote_timeout_delta_ms,
+            ),
+            (
+                ConsensusStep::Precommit,
+                config.precommit_timeout_ms,
+                config.precommit_timeout_delta_ms,
+            ),
+        ] {
+            assert_eq!(scheduler.timeout_for(step, 0), Duration::from_millis(base));
+            assert_eq!(
+                scheduler.timeout_for(step, 10),
+                Duration::from_millis(base + 10 * delta)
+            );
+        }
+    }
+
+    #[test]
+    fn test_timeout_for_respects_cap() {
+        let config = BftConfig {
+            max_timeout_ms: 4_000,
+            ..BftConfig::default()
+        };
         let scheduler = TimeoutScheduler::new(config);
-        let d0 = scheduler.timeout_duration(ConsensusStep::Prevote, 0);
-        let d1 = scheduler.timeout_duration(ConsensusStep::Prevote, 5);
-        assert_eq!(d0, d1);
-        assert_eq!(d0, Duration::from_millis(1000));
+        // 1000 + 4 * 500 is under the cap; 1000 + 10 * 500 is not.
+        assert_eq!(
+            scheduler.timeout_for(ConsensusStep::Prevote, 4),
+            Duration::from_millis(3_000)
+        );
+        assert_eq!(
+            scheduler.timeout_for(ConsensusStep::Prevote, 10),
+            Duration::from_millis(4_000)
+        );
+        assert_eq!(
+            scheduler.timeout_for(ConsensusStep::Propose, u32::MAX),
+            Duration::from_millis(4_000)
+        );
     }
 
     #[test]
@@ -176,7 +248,7 @@ mod tests {
         let config = BftConfig::default();
         let scheduler = TimeoutScheduler::new(config);
         assert_eq!(
-            scheduler.timeout_duration(ConsensusStep::Commit, 0),
+            scheduler.timeout_for(ConsensusStep::Commit, 0),
             Duration::ZERO
         );
     }
@@ -4,11 +4,19 @@
 //! (ProposedBlock, CommittedBlock), and the consensus state machine state.
 
 use {
+    crate::{evidence::DoubleSignEvidence, validator_set::ValidatorSet, wal::WalError},
+    serde::{Deserialize, Serialize},
     solana_hash::Hash,
     solana_pubkey::Pubkey,
     solana_signature::Signature,
+    solana_signer::Signer,
     solana_transaction::versioned::VersionedTransaction,
-    std::collections::HashMap,
+    std::{
+        collections::{HashMap, HashSet},
+        fs,
+        io::Write,
+        path::Path,
+    },
 };
 
 // ---------------------------------------------------------------------------
@@ -16,7 +24,8 @@ use {
 // ---------------------------------------------------------------------------
 
 /// Messages exchanged between validators during consensus rounds.
-#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ConsensusMessage {
     /// A block proposal broadcast by the round's designated proposer.
     Proposal {
@@ -29,6 +38,9 @@ pub enum ConsensusMessage {
         /// for this value in a prior round, it attaches `valid_round` so that
         /// locked validators can unlock.
         valid_round: Option<u32>,
+        /// The prevotes justifying `valid_round`; required for locked
+        /// validators to accept the unlock.
+        polka_certificate: Option<PolkaCertificate>,
     },
 
     /// A prevote cast by a validator after evaluating a proposal.
@@ -88,6 +100,106 @@ impl ConsensusMessage {
             | ConsensusMessage::Precommit { signature, .. } => signature,
         }
     }
+
+    /// Canonical bytes covered by this message's signature.
+    ///
+    /// Layout: `kind (u8) ‖ height (u64 LE) ‖ round (u32 LE) ‖ block hash ‖
+    /// sender`, where the block hash is `0` for nil or `1 ‖ hash`.  The kind
+    /// byte keeps a prevote signature from being replayed as a precommit.
+    /// Proposals sign the hash of their block and additionally append
+    /// `valid_round` in the same option encoding.
+    pub fn signable_bytes(&self) -> Vec<u8> {
+        let (kind, block_hash) = match self {
+            ConsensusMessage::Proposal { block, .. } => (0u8, Some(block.hash())),
+            ConsensusMessage::Prevote { block_hash, .. } => (1, *block_hash),
+            ConsensusMessage::Precommit { block_hash, .. } => (2, *block_hash),
+        };
+        let mut bytes = Vec::with_capacity(1 + 8 + 4 + 33 + 32 + 5);
+        bytes.push(kind);
+        bytes.extend_from_slice(&self.height().to_le_bytes());
+        bytes.extend_from_slice(&self.round().to_le_bytes());
+        match block_hash {
+            Some(hash) => {
+                bytes.push(1);
+                bytes.extend_from_slice(hash.as_ref());
+            }
+            None => bytes.push(0),
+        }
+        bytes.extend_from_slice(self.sender().as_ref());
+        if let ConsensusMessage::Proposal { valid_round, .. } = self {
+            match valid_round {
+                Some(valid_round) => {
+                    bytes.push(1);
+                    bytes.extend_from_slice(&valid_round.to_le_bytes());
+                }
+                None => bytes.push(0),
+            }
+        }
+        bytes
+    }
+
+    /// Sign this message with `signer`, replacing any existing signature.
+    ///
+    /// `signer` should be the keypair of [`ConsensusMessage::sender`];
+    /// anything else produces a message that fails
+    /// [`verify_consensus_message`].
+    pub fn signed<S: Signer + ?Sized>(mut self, signer: &S) -> Self {
+        let new_signature = signer.sign_message(&self.signable_bytes());
+        match &mut self {
+            ConsensusMessage::Proposal { signature, .. }
+            | ConsensusMessage::Prevote { signature, .. }
+            | ConsensusMessage::Precommit { signature, .. } => *signature = new_signature,
+        }
+        self
+    }
+}
+
+/// Check that `msg` carries a valid Ed25519 signature by its sender over
+/// [`ConsensusMessage::signable_bytes`].
+pub fn verify_consensus_message(msg: &ConsensusMessage) -> bool {
+    msg.signature()
+        .verify(msg.sender().as_ref(), &msg.signable_bytes())
+}
+
+// ---------------------------------------------------------------------------
+// Polka certificates
+// ---------------------------------------------------------------------------
+
+/// The 2/3+ prevotes for `block_hash` in (`height`, `round`) — a "polka".
+///
+/// A proposer re-proposing a value from an earlier round attaches the
+/// certificate for that round so that validators locked on another value can
+/// check the polka for themselves before unlocking.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolkaCertificate {
+    pub height: u64,
+    pub round: u32,
+    pub block_hash: Hash,
+    /// Prevote signatures of the contributing validators.
+    pub signatures: Vec<(Pubkey, Signature)>,
+}
+
+impl PolkaCertificate {
+    /// Stake of the distinct validators in `validator_set` whose prevote
+    /// signature in this certificate verifies.
+    pub fn verified_stake(&self, validator_set: &ValidatorSet) -> u64 {
+        let mut counted = HashSet::new();
+        self.signatures
+            .iter()
+            .filter(|(voter, signature)| {
+                validator_set.contains(voter)
+                    && verify_consensus_message(&ConsensusMessage::Prevote {
+                        height: self.height,
+                        round: self.round,
+                        block_hash: Some(self.block_hash),
+                        voter: *voter,
+                        signature: *signature,
+                    })
+                    && counted.insert(*voter)
+            })
+            .map(|(voter, _)| validator_set.stake_of(voter))
+            .fold(0u64, u64::saturating_add)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -95,7 +207,7 @@ impl ConsensusMessage {
 // ---------------------------------------------------------------------------
 
 /// A block proposed by a leader during the Propose phase.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProposedBlock {
     /// Hash of the parent (previous committed) block.
     pub parent_hash: Hash,
@@ -109,26 +221,62 @@ pub struct ProposedBlock {
     pub state_root: Hash,
     /// Public key of the proposer.
     pub proposer: Pubkey,
+    /// Double-sign evidence put on-chain by the proposer.  Every validator
+    /// applies it to slashing when executing the committed block, so the
+    /// penalty is part of replayed state rather than a local decision.
+    #[serde(default)]
+    pub evidence: Vec<DoubleSignEvidence>,
 }
 
 impl ProposedBlock {
     /// Compute a deterministic hash for this block.
-    /// Uses parent_hash, height, timestamp, state_root, and proposer.
-    /// Transactions are captured via state_root.
+    /// Uses parent_hash, height, timestamp, state_root, and proposer, plus
+    /// the evidence when there is any (so blocks without evidence keep
+    /// their hash).  Transactions are captured via state_root.
     pub fn hash(&self) -> Hash {
         // Build a composite hash from the block's deterministic fields.
-        solana_sha256_hasher::hashv(&[
+        let height = self.height.to_le_bytes();
+        let timestamp = self.timestamp.to_le_bytes();
+        let evidence_hash = self.evidence_hash();
+        let mut fields: Vec<&[u8]> = vec![
             self.parent_hash.as_ref(),
-            &self.height.to_le_bytes(),
-            &self.timestamp.to_le_bytes(),
+            &height,
+            &timestamp,
             self.state_root.as_ref(),
             self.proposer.as_ref(),
-        ])
+        ];
+        if let Some(evidence_hash) = &evidence_hash {
+            fields.push(evidence_hash.as_ref());
+        }
+        solana_sha256_hasher::hashv(&fields)
+    }
+
+    /// Hash over `evidence`, or `None` when there is none.  Each entry is
+    /// identified by its offender, height, round and both vote signatures.
+    fn evidence_hash(&self) -> Option<Hash> {
+        if self.evidence.is_empty() {
+            return None;
+        }
+        let entries: Vec<Vec<u8>> = self
+            .evidence
+            .iter()
+            .map(|ev| {
+                let mut entry = Vec::with_capacity(32 + 8 + 4 + 64 + 64);
+                entry.extend_from_slice(ev.voter.as_ref());
+                entry.extend_from_slice(&ev.height.to_le_bytes());
+                entry.extend_from_slice(&ev.round.to_le_bytes());
+                entry.extend_from_slice(ev.first_vote.signature().as_ref());
+                entry.extend_from_slice(ev.second_vote.signature().as_ref());
+                entry
+            })
+            .collect();
+        let entries: Vec<&[u8]> = entries.iter().map(Vec::as_slice).collect();
+        Some(solana_sha256_hasher::hashv(&entries))
     }
 }
 
 /// A block that has been committed by 2/3+ of the validator set.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommittedBlock {
     /// The original proposed block.
     pub block: ProposedBlock,
@@ -143,7 +291,7 @@ pub struct CommittedBlock {
 // ---------------------------------------------------------------------------
 
 /// The step within a single consensus round.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ConsensusStep {
     /// Waiting for the round to begin.
     NewRound,
@@ -170,7 +318,10 @@ impl std::fmt::Display for ConsensusStep {
 }
 
 /// Internal state of the consensus engine for a given height.
-#[derive(Debug, Clone)]
+///
+/// Serializable so that it can be persisted across restarts with
+/// [`ConsensusState::save_to_file`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusState {
     /// Current block height being decided.
     pub height: u64,
@@ -192,8 +343,27 @@ pub struct ConsensusState {
     // -- Vote collection --
     /// Prevotes collected for the current round. Key = voter pubkey, Value = block hash (None = nil).
     pub prevotes: HashMap<Pubkey, Option<Hash>>,
+    /// Signatures of the prevotes in `prevotes`, kept for polka
+    /// certificates.
+    pub prevote_signatures: HashMap<Pubkey, Signature>,
     /// Precommits collected for the current round. Key = voter pubkey, Value = block hash (None = nil).
     pub precommits: HashMap<Pubkey, Option<Hash>>,
+    /// Signatures of the precommits in `precommits`, kept for the commit
+    /// certificate.
+    pub precommit_signatures: HashMap<Pubkey, Signature>,
+
+    // -- Future-round buffers --
+    /// Prevotes received for rounds ahead of `round`, keyed by round.
+    pub future_prevotes: HashMap<u32, HashMap<Pubkey, Option<Hash>>>,
+    /// Signatures of the prevotes in `future_prevotes`.
+    pub future_prevote_signatures: HashMap<u32, HashMap<Pubkey, Signature>>,
+    /// Precommits received for rounds ahead of `round`, keyed by round.
+    pub future_precommits: HashMap<u32, HashMap<Pubkey, Option<Hash>>>,
+    /// Signatures of the precommits in `future_precommits`.
+    pub future_precommit_signatures: HashMap<u32, HashMap<Pubkey, Signature>>,
+
+    /// Polka certificates assembled at this height, keyed by round.
+    pub polka_certificates: HashMap<u32, PolkaCertificate>,
 
     /// The proposed block for this round (if received).
     pub proposal: Option<ProposedBlock>,
@@ -211,19 +381,71 @@ impl ConsensusState {
             valid_value: None,
             valid_round: None,
             prevotes: HashMap::new(),
+            prevote_signatures: HashMap::new(),
             precommits: HashMap::new(),
+            precommit_signatures: HashMap::new(),
+            future_prevotes: HashMap::new(),
+            future_prevote_signatures: HashMap::new(),
+            future_precommits: HashMap::new(),
+            future_precommit_signatures: HashMap::new(),
+            polka_certificates: HashMap::new(),
             proposal: None,
         }
     }
 
     /// Reset vote collections for a new round while preserving lock state.
+    ///
+    /// Votes buffered for `new_round` become the round's collected votes;
+    /// buffers for earlier rounds are dropped.
     pub fn advance_round(&mut self, new_round: u32) {
         self.round = new_round;
         self.step = ConsensusStep::NewRound;
-        self.prevotes.clear();
-        self.precommits.clear();
+        self.prevotes = self.future_prevotes.remove(&new_round).unwrap_or_default();
+        self.prevote_signatures = self
+            .future_prevote_signatures
+            .remove(&new_round)
+            .unwrap_or_default();
+        self.precommits = self.future_precommits.remove(&new_round).unwrap_or_default();
+        self.precommit_signatures = self
+            .future_precommit_signatures
+            .remove(&new_round)
+            .unwrap_or_default();
+        self.future_prevotes.retain(|round, _| *round > new_round);
+        self.future_prevote_signatures
+            .retain(|round, _| *round > new_round);
+        self.future_precommits.retain(|round, _| *round > new_round);
+        self.future_precommit_signatures
+            .retain(|round, _| *round > new_round);
         self.proposal = None;
     }
+
+    /// Write the state to `path` in bincode, replacing any existing file
+    /// atomically.
+    pub fn save_to_file(&self, path: &Path) -> Result<(), WalError> {
+        let bytes = bincode::serialize(self)?;
+        let tmp = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Read a state written by [`ConsensusState::save_to_file`].
+    pub fn load_from_file(path: &Path) -> Result<Self, WalError> {
+        let bytes = fs::read(path)?;
+        Ok(bincode::deserialize(&bytes)?)
+    }
+
+    /// Validators with a buffered prevote or precommit for future `round`.
+    pub fn future_voters(&self, round: u32) -> HashSet<Pubkey> {
+        self.future_prevotes
+            .get(&round)
+            .into_iter()
+            .chain(self.future_precommits.get(&round))
+            .flat_map(|votes| votes.keys().copied())
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -239,6 +461,7 @@ mod tests {
             transactions: vec![],
             state_root: Hash::default(),
             proposer: Pubkey::default(),
+            evidence: vec![],
         };
         let h1 = block.hash();
         let h2 = block.hash();
@@ -254,6 +477,7 @@ mod tests {
             transactions: vec![],
             state_root: Hash::default(),
             proposer: Pubkey::default(),
+            evidence: vec![],
         };
         let block2 = ProposedBlock {
             parent_hash: Hash::default(),
@@ -262,10 +486,58 @@ mod tests {
             transactions: vec![],
             state_root: Hash::default(),
             proposer: Pubkey::default(),
+            evidence: vec![],
         };
         assert_ne!(block1.hash(), block2.hash());
     }
 
+    #[test]
+    fn test_proposed_block_hash_covers_evidence() {
+        let block = ProposedBlock {
+            parent_hash: Hash::default(),
+            height: 1,
+            timestamp: 1000,
+            transactions: vec![],
+            state_root: Hash::default(),
+            proposer: Pubkey::default(),
+            evidence: vec![],
+        };
+        // The hash of an evidence-free block is unchanged by the field.
+        assert_eq!(
+            block.hash(),
+            solana_sha256_hasher::hashv(&[
+                Hash::default().as_ref(),
+                &1u64.to_le_bytes(),
+                &1000i64.to_le_bytes(),
+                Hash::default().as_ref(),
+                Pubkey::default().as_ref(),
+            ])
+        );
+
+        let voter = solana_keypair::Keypair::new();
+        let vote = |block_hash| {
+            ConsensusMessage::Prevote {
+                height: 1,
+                round: 0,
+                block_hash: Some(block_hash),
+                voter: voter.pubkey(),
+                signature: Signature::default(),
+            }
+            .signed(&voter)
+        };
+        let with_evidence = ProposedBlock {
+            evidence: vec![DoubleSignEvidence {
+                height: 1,
+                round: 0,
+                voter: voter.pubkey(),
+                first_vote: vote(Hash::new_unique()),
+                second_vote: vote(Hash::new_unique()),
+            }],
+            ..block.clone()
+        };
+        assert_ne!(with_evidence.hash(), block.hash());
+    }
+
     #[test]
     fn test_consensus_state_new() {
         let state = ConsensusState::new(42);
@@ -297,6 +569,86 @@ mod tests {
         assert!(state.precommits.is_empty());
     }
 
+    #[test]
+    fn test_consensus_state_advance_round_promotes_buffered_votes() {
+        let mut state = ConsensusState::new(1);
+        let voter = Pubkey::new_unique();
+        let hash = Some(Hash::new_unique());
+        state.future_prevotes.entry(1).or_default().insert(voter, None);
+        state.future_precommits.entry(2).or_default().insert(voter, hash);
+        state.future_prevotes.entry(3).or_default().insert(voter, hash);
+        assert_eq!(state.future_voters(2).len(), 1);
+
+        state.advance_round(2);
+
+        // Round 2's buffer became current; round 1's was dropped.
+        assert!(state.prevotes.is_empty());
+        assert_eq!(state.precommits.get(&voter), Some(&hash));
+        assert!(!state.future_prevotes.contains_key(&1));
+        assert!(!state.future_precommits.contains_key(&2));
+        assert_eq!(state.future_voters(3).len(), 1);
+    }
+
+    #[test]
+    fn test_consensus_state_file_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("consensus_state.bin");
+
+        let locked = Hash::new_unique();
+        let mut state = ConsensusState::new(9);
+        state.round = 2;
+        state.step = ConsensusStep::Precommit;
+        state.locked_value = Some(locked);
+        state.locked_round = Some(1);
+        for block_hash in [Some(locked), Some(locked), None] {
+            let voter = Pubkey::new_unique();
+            state.prevotes.insert(voter, block_hash);
+            state
+                .prevote_signatures
+                .insert(voter, Signature::from([7u8; 64]));
+        }
+        state.proposal = Some(ProposedBlock {
+            parent_hash: Hash::new_unique(),
+            height: 9,
+            timestamp: 1000,
+            transactions: vec![],
+            state_root: Hash::new_unique(),
+            proposer: Pubkey::new_unique(),
+            evidence: vec![],
+        });
+        state.save_to_file(&path).unwrap();
+
+        let loaded = ConsensusState::load_from_file(&path).unwrap();
+        assert_eq!(loaded.height, 9);
+        assert_eq!(loaded.round, 2);
+        assert_eq!(loaded.step, ConsensusStep::Precommit);
+        assert_eq!(loaded.locked_value, Some(locked));
+        assert_eq!(loaded.locked_round, Some(1));
+        assert_eq!(loaded.prevotes.len(), 3);
+        assert_eq!(loaded.prevotes, state.prevotes);
+        assert_eq!(loaded.prevote_signatures, state.prevote_signatures);
+        assert!(loaded.precommits.is_empty());
+        assert_eq!(
+            loaded.proposal.map(|block| block.hash()),
+            state.proposal.map(|block| block.hash())
+        );
+    }
+
+    #[test]
+    fn test_load_consensus_state_rejects_garbage() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("consensus_state.bin");
+        assert!(matches!(
+            ConsensusState::load_from_file(&path),
+            Err(WalError::Io(_))
+        ));
+        fs::write(&path, [0xffu8; 3]).unwrap();
+        assert!(matches!(
+            ConsensusState::load_from_file(&path),
+            Err(WalError::Encode(_))
+        ));
+    }
+
     #[test]
     fn test_consensus_message_accessors() {
         let msg = ConsensusMessage::Prevote {
@@ -311,6 +663,68 @@ mod tests {
         assert_eq!(*msg.sender(), Pubkey::default());
     }
 
+    #[test]
+    fn test_polka_certificate_bincode_roundtrip() {
+        let certificate = PolkaCertificate {
+            height: 7,
+            round: 2,
+            block_hash: Hash::new_unique(),
+            signatures: (0..3)
+                .map(|i| (Pubkey::new_unique(), Signature::from([i as u8; 64])))
+                .collect(),
+        };
+        let bytes = bincode::serialize(&certificate).unwrap();
+        let decoded: PolkaCertificate = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, certificate);
+    }
+
+    #[test]
+    fn test_polka_certificate_verified_stake() {
+        use {solana_keypair::Keypair, solana_signer::Signer};
+
+        let keypairs: Vec<Keypair> = (0..4).map(|_| Keypair::new()).collect();
+        let validator_set =
+            ValidatorSet::new(keypairs.iter().map(|kp| (kp.pubkey(), 100)).collect());
+        let block_hash = Hash::new_unique();
+        let prevote_signature = |kp: &Keypair| {
+            *ConsensusMessage::Prevote {
+                height: 1,
+                round: 0,
+                block_hash: Some(block_hash),
+                voter: kp.pubkey(),
+                signature: Signature::default(),
+            }
+            .signed(kp)
+            .signature()
+        };
+
+        let mut certificate = PolkaCertificate {
+            height: 1,
+            round: 0,
+            block_hash,
+            signatures: keypairs[..3]
+                .iter()
+                .map(|kp| (kp.pubkey(), prevote_signature(kp)))
+                .collect(),
+        };
+        assert_eq!(certificate.verified_stake(&validator_set), 300);
+
+        // Duplicates, outsiders and bad signatures add nothing.
+        let outsider = Keypair::new();
+        certificate.signatures.push(certificate.signatures[0]);
+        certificate
+            .signatures
+            .push((outsider.pubkey(), prevote_signature(&outsider)));
+        certificate
+            .signatures
+            .push((keypairs[3].pubkey(), Signature::default()));
+        assert_eq!(certificate.verified_stake(&validator_set), 300);
+
+        // Signatures are bound to the certified round.
+        certificate.round = 1;
+        assert_eq!(certificate.verified_stake(&validator_set), 0);
+    }
+
     #[test]
     fn test_consensus_step_display() {
         assert_eq!(format!("{}", ConsensusStep::NewRound), "NewRound");
@@ -4,10 +4,19 @@
 //! Used for quorum calculations and proposer selection.
 
 use {
+    solana_hash::Hash,
     solana_pubkey::Pubkey,
     std::collections::HashMap,
 };
 
+/// Basis points in 100%.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Largest total stake a validator set built from chain data may carry.
+/// Larger totals are scaled down by [`ValidatorSet::normalize_stakes`] so
+/// that stake sums stay well clear of `u64::MAX`.
+pub const MAX_TOTAL_STAKE: u64 = 1_000_000_000_000_000_000;
+
 /// A single validator with its stake weight.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValidatorInfo {
@@ -46,7 +55,10 @@ impl ValidatorSet {
                 .then_with(|| a.pubkey.cmp(&b.pubkey))
         });
 
-        let total_stake = infos.iter().map(|v| v.stake).sum();
+        let total_stake = infos
+            .iter()
+            .map(|v| v.stake)
+            .fold(0u64, u64::saturating_add);
         let index = infos
             .iter()
             .enumerate()
@@ -70,7 +82,19 @@ impl ValidatorSet {
         self.validators.is_empty()
     }
 
-    /// Returns total stake across all validators.
+    /// Build a validator set from on-chain stakes, scaling them down with
+    /// [`Self::normalize_stakes`] if they total more than
+    /// [`MAX_TOTAL_STAKE`].
+    pub fn from_chain_stakes(validators: Vec<(Pubkey, u64)>) -> Self {
+        let set = Self::new(validators);
+        if set.total_stake > MAX_TOTAL_STAKE {
+            set.normalize_stakes(MAX_TOTAL_STAKE)
+        } else {
+            set
+        }
+    }
+
+    /// Returns total stake across all validators, saturating at `u64::MAX`.
     pub fn total_stake(&self) -> u64 {
         self.total_stake
     }
@@ -97,15 +121,52 @@ impl ValidatorSet {
         self.index.contains_key(pubkey)
     }
 
-    /// Returns the minimum stake required for a quorum given a threshold.
-    /// For 2/3+1: `ceil(total_stake * threshold) + 1` in integer arithmetic.
+    /// Returns the minimum stake required for a quorum given a threshold
+    /// expressed as a fraction of total stake (e.g. `0.667`).
+    ///
+    /// The threshold is rounded to basis points and passed to
+    /// [`Self::quorum_stake_bps`].
     pub fn quorum_stake(&self, threshold: f64) -> u64 {
-        // Use integer math to avoid floating-point issues:
-        // quorum = floor(total_stake * 2 / 3) + 1
-        // But we parameterize by threshold for flexibility.
-        let q = (self.total_stake as f64 * threshold).ceil() as u64;
-        // Ensure at least 1
-        q.max(1)
+        let threshold_bps = (threshold * BPS_DENOMINATOR as f64).round() as u64;
+        self.quorum_stake_bps(threshold_bps)
+    }
+
+    /// Returns the minimum stake required for a quorum given a threshold in
+    /// basis points: `ceil(total_stake * threshold_bps / BPS_DENOMINATOR)`,
+    /// at least 1.
+    ///
+    /// Computed in `u128`, so it cannot overflow; a result beyond `u64`
+    /// (only possible for thresholds above 100%) saturates at `u64::MAX`.
+    pub fn quorum_stake_bps(&self, threshold_bps: u64) -> u64 {
+        let denominator = u128::from(BPS_DENOMINATOR);
+        let q = (u128::from(self.total_stake) * u128::from(threshold_bps)).div_ceil(denominator);
+        u64::try_from(q).unwrap_or(u64::MAX).max(1)
+    }
+
+    /// Returns a copy of the set with every stake scaled by
+    /// `max_total / total` so that the total fits within `max_total`,
+    /// preserving relative weights up to integer rounding.
+    ///
+    /// Scaled stakes are rounded down but never below 1, so no validator
+    /// drops out of the set; the total may therefore exceed `max_total` by
+    /// at most one per validator.  A set that already fits is returned
+    /// unchanged.
+    pub fn normalize_stakes(&self, max_total: u64) -> ValidatorSet {
+        // Sum in u128: the saturated `total_stake` would skew the ratios.
+        let total: u128 = self.validators.iter().map(|v| u128::from(v.stake)).sum();
+        if total <= u128::from(max_total) {
+            return self.clone();
+        }
+        ValidatorSet::new(
+            self.validators
+                .iter()
+                .map(|v| {
+                    let scaled = u128::from(v.stake) * u128::from(max_total) / total;
+                    // `scaled <= v.stake`, so it fits in a u64.
+                    (v.pubkey, (scaled as u64).max(1))
+                })
+                .collect(),
+        )
     }
 
     /// Returns an iterator over all validators in deterministic order.
@@ -118,6 +179,34 @@ impl ValidatorSet {
         self.validators.iter().map(|v| v.pubkey).collect()
     }
 
+    /// Returns `(pubkey, stake)` for every validator, sorted by pubkey.
+    pub fn sorted_by_pubkey(&self) -> Vec<(Pubkey, u64)> {
+        let mut entries: Vec<(Pubkey, u64)> = self
+            .validators
+            .iter()
+            .map(|v| (v.pubkey, v.stake))
+            .collect();
+        entries.sort_unstable_by_key(|(pubkey, _)| *pubkey);
+        entries
+    }
+
+    /// A hash committing to the set's members and stakes: SHA-256 over
+    /// `pubkey ‖ stake (u64 LE)` for each validator, sorted by pubkey.
+    /// Light clients use it to identify a trusted validator set.
+    pub fn commitment_hash(&self) -> Hash {
+        let entries = self.sorted_by_pubkey();
+        let stakes: Vec<[u8; 8]> = entries
+            .iter()
+            .map(|(_, stake)| stake.to_le_bytes())
+            .collect();
+        let fields: Vec<&[u8]> = entries
+            .iter()
+            .zip(&stakes)
+            .flat_map(|((pubkey, _), stake)| [pubkey.as_ref(), stake.as_slice()])
+            .collect();
+        solana_sha256_hasher::hashv(&fields)
+    }
+
     /// Add or update a validator's stake. Re-sorts the set.
     pub fn upsert(&mut self, pubkey: Pubkey, stake: u64) {
         // Remove existing entry if present
@@ -131,7 +220,11 @@ impl ValidatorSet {
                 .cmp(&a.stake)
                 .then_with(|| a.pubkey.cmp(&b.pubkey))
         });
-        self.total_stake = self.validators.iter().map(|v| v.stake).sum();
+        self.total_stake = self
+            .validators
+            .iter()
+            .map(|v| v.stake)
+            .fold(0u64, u64::saturating_add);
         self.index = self
             .validators
             .iter()
@@ -218,6 +311,58 @@ mod tests {
         assert!(q <= 201);
     }
 
+    #[test]
+    fn test_quorum_stake_near_u64_max() {
+        let stake = u64::MAX / 200;
+        let vs = ValidatorSet::new(
+            make_pubkeys(200)
+                .into_iter()
+                .map(|pk| (pk, stake))
+                .collect(),
+        );
+        assert_eq!(vs.len(), 200);
+        assert_eq!(vs.total_stake(), stake * 200);
+
+        let q = vs.quorum_stake_bps(6_667);
+        let expected = (u128::from(stake) * 200 * 6_667).div_ceil(10_000) as u64;
+        assert_eq!(q, expected);
+        assert!(q > vs.total_stake() / 3 * 2);
+        assert!(q < vs.total_stake());
+        assert_eq!(vs.quorum_stake(0.6667), q);
+        assert_eq!(vs.quorum_stake_bps(20_000), u64::MAX);
+
+        // One more validator would overflow a plain sum; the total
+        // saturates instead.
+        let mut overfull = vs.clone();
+        overfull.upsert(Pubkey::new_unique(), stake * 2);
+        assert_eq!(overfull.total_stake(), u64::MAX);
+    }
+
+    #[test]
+    fn test_normalize_stakes_preserves_weights() {
+        let pks = make_pubkeys(3);
+        let vs = ValidatorSet::from_chain_stakes(vec![
+            (pks[0], u64::MAX / 2),
+            (pks[1], u64::MAX / 4),
+            (pks[2], u64::MAX / 4),
+        ]);
+        assert!(vs.total_stake() <= MAX_TOTAL_STAKE);
+        // Integer rounding loses at most one unit per validator.
+        assert!(MAX_TOTAL_STAKE / 2 - vs.stake_of(&pks[0]) <= 1);
+        assert!(MAX_TOTAL_STAKE / 4 - vs.stake_of(&pks[1]) <= 1);
+        assert_eq!(vs.stake_of(&pks[1]), vs.stake_of(&pks[2]));
+
+        // Tiny stakes keep their seat.
+        let vs = ValidatorSet::new(vec![(pks[0], 1_000_000), (pks[1], 1)]).normalize_stakes(1_000);
+        assert_eq!(vs.len(), 2);
+        assert_eq!(vs.stake_of(&pks[0]), 999);
+        assert_eq!(vs.stake_of(&pks[1]), 1);
+
+        // Sets within the cap are untouched.
+        let vs = ValidatorSet::from_chain_stakes(vec![(pks[0], 100), (pks[1], 200)]);
+        assert_eq!(vs.total_stake(), 300);
+    }
+
     #[test]
     fn test_upsert_add() {
         let pks = make_pubkeys(3);
@@ -277,6 +422,18 @@ mod tests {
         assert_eq!(order1, order2);
     }
 
+    #[test]
+    fn test_commitment_hash_independent_of_order_and_bound_to_stake() {
+        let pks = make_pubkeys(3);
+        let vs1 = ValidatorSet::new(vec![(pks[0], 100), (pks[1], 200), (pks[2], 300)]);
+        let vs2 = ValidatorSet::new(vec![(pks[2], 300), (pks[0], 100), (pks[1], 200)]);
+        assert_eq!(vs1.commitment_hash(), vs2.commitment_hash());
+
+        let mut vs3 = vs1.clone();
+        vs3.upsert(pks[0], 101);
+        assert_ne!(vs1.commitment_hash(), vs3.commitment_hash());
+    }
+
     #[test]
     fn test_iter() {
         let pks = make_pubkeys(2);
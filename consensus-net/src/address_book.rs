@@ -0,0 +1,260 @@
+//! Persistent record of peer addresses.
+//!
+//! The [`AddressBook`] remembers every peer address this node has learned —
+//! from bootstrap configuration, from its own connections, and from peers
+//! gossiped in heartbeats — together with when each peer was last seen.
+//! The [`PeerManager`](crate::peer_manager::PeerManager) loads it from
+//! [`ConsensusNetConfig::address_book_path`](crate::config::ConsensusNetConfig::address_book_path)
+//! on startup and saves it there on shutdown, so a restarted validator can
+//! redial the peers it knew rather than rely on its bootstrap list alone.
+//!
+//! ## File format
+//!
+//! A JSON array of entries:
+//!
+//! ```json
+//! [{ "pubkey": "<base58>", "addr": "10.0.0.1:8900", "last_seen_unix_secs": 1700000000 }]
+//! ```
+//!
+//! `Instant`s cannot be persisted, so `last_seen` is written as wall-clock
+//! time and converted back on load; `null` marks a peer never seen.
+
+use {
+    rand::seq::IndexedRandom,
+    serde::{Deserialize, Serialize},
+    solana_pubkey::Pubkey,
+    std::{
+        cmp::Reverse,
+        collections::HashMap,
+        fs, io,
+        net::SocketAddr,
+        path::Path,
+        str::FromStr,
+        time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+    },
+};
+
+/// Most addresses kept.  Gossiped addresses beyond this are ignored, so a
+/// peer cannot grow the book without bound.
+pub const MAX_ADDRESS_BOOK_SIZE: usize = 1_024;
+
+/// Known peer addresses and when each peer was last seen.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<AddressBookEntry>", into = "Vec<AddressBookEntry>")]
+pub struct AddressBook {
+    /// Last known address of each peer.
+    pub peers: HashMap<Pubkey, SocketAddr>,
+    /// When each peer was last seen; missing for peers never seen.
+    pub last_seen: HashMap<Pubkey, Instant>,
+}
+
+impl AddressBook {
+    /// Create an empty address book.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of known peers.
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Whether no peers are known.
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    /// Record `addr` as `pubkey`'s address, keeping its last-seen time.
+    pub fn insert(&mut self, pubkey: Pubkey, addr: SocketAddr) {
+        self.peers.insert(pubkey, addr);
+    }
+
+    /// Record `addr` for `pubkey` only if the peer is unknown and the book
+    /// has room.  Returns `true` if it was added.
+    pub fn insert_if_absent(&mut self, pubkey: Pubkey, addr: SocketAddr) -> bool {
+        if self.peers.contains_key(&pubkey) || self.peers.len() >= MAX_ADDRESS_BOOK_SIZE {
+            return false;
+        }
+        self.peers.insert(pubkey, addr);
+        true
+    }
+
+    /// Record that `pubkey` was seen at `addr` at time `at`.  An older `at`
+    /// never replaces a newer last-seen time.
+    pub fn record_seen(&mut self, pubkey: Pubkey, addr: SocketAddr, at: Instant) {
+        self.peers.insert(pubkey, addr);
+        let last_seen = self.last_seen.entry(pubkey).or_insert(at);
+        if at > *last_seen {
+            *last_seen = at;
+        }
+    }
+
+    /// Forget `pubkey`.
+    pub fn remove(&mut self, pubkey: &Pubkey) {
+        self.peers.remove(pubkey);
+        self.last_seen.remove(pubkey);
+    }
+
+    /// Every known peer, most recently seen first; peers never seen come
+    /// last.
+    pub fn by_recency(&self) -> Vec<(Pubkey, SocketAddr)> {
+        let mut entries: Vec<_> = self
+            .peers
+            .iter()
+            .map(|(pubkey, addr)| (*pubkey, *addr, self.last_seen.get(pubkey).copied()))
+            .collect();
+        // `None < Some(_)`, so descending order puts never-seen peers last.
+        entries.sort_by_key(|(_, _, last_seen)| Reverse(*last_seen));
+        entries
+            .into_iter()
+            .map(|(pubkey, addr, _)| (pubkey, addr))
+            .collect()
+    }
+
+    /// Up to `n` known peers, chosen at random.
+    pub fn sample(&self, n: usize) -> Vec<(Pubkey, SocketAddr)> {
+        let all: Vec<(Pubkey, SocketAddr)> = self.peers.iter().map(|(k, v)| (*k, *v)).collect();
+        all.choose_multiple(&mut rand::rng(), n).copied().collect()
+    }
+
+    /// Load an address book written by [`Self::save`].
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Write the address book to `path`, replacing it atomically.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(&tmp, path)
+    }
+}
+
+/// One peer as written to disk.
+#[derive(Debug, Serialize, Deserialize)]
+struct AddressBookEntry {
+    pubkey: String,
+    addr: SocketAddr,
+    last_seen_unix_secs: Option<u64>,
+}
+
+impl From<AddressBook> for Vec<AddressBookEntry> {
+    fn from(book: AddressBook) -> Self {
+        let now = Instant::now();
+        let wall_now = unix_secs(SystemTime::now());
+        book.peers
+            .iter()
+            .map(|(pubkey, addr)| AddressBookEntry {
+                pubkey: pubkey.to_string(),
+                addr: *addr,
+                last_seen_unix_secs: book
+                    .last_seen
+                    .get(pubkey)
+                    .map(|seen| wall_now.saturating_sub(now.duration_since(*seen).as_secs())),
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<Vec<AddressBookEntry>> for AddressBook {
+    type Error = String;
+
+    fn try_from(entries: Vec<AddressBookEntry>) -> Result<Self, Self::Error> {
+        let now = Instant::now();
+        let wall_now = unix_secs(SystemTime::now());
+        let mut book = Self::new();
+        for entry in entries {
+            let pubkey = Pubkey::from_str(&entry.pubkey)
+                .map_err(|e| format!("bad pubkey {:?}: {e}", entry.pubkey))?;
+            book.insert(pubkey, entry.addr);
+            if let Some(seen) = entry.last_seen_unix_secs {
+                let age = Duration::from_secs(wall_now.saturating_sub(seen));
+                // Too old to represent as an `Instant`: treat as never seen.
+                if let Some(at) = now.checked_sub(age) {
+                    book.last_seen.insert(pubkey, at);
+                }
+            }
+        }
+        Ok(book)
+    }
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(n: u8) -> SocketAddr {
+        SocketAddr::from(([10, 0, 0, n], 8900))
+    }
+
+    #[test]
+    fn test_by_recency_orders_newest_first() {
+        let mut book = AddressBook::new();
+        let now = Instant::now();
+        let (old, new, never) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        book.insert(never, addr(3));
+        book.record_seen(old, addr(1), now - Duration::from_secs(60));
+        book.record_seen(new, addr(2), now);
+        // An older sighting does not move a peer back.
+        book.record_seen(new, addr(2), now - Duration::from_secs(120));
+
+        assert_eq!(
+            book.by_recency(),
+            vec![(new, addr(2)), (old, addr(1)), (never, addr(3))]
+        );
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("address_book.json");
+
+        let mut book = AddressBook::new();
+        let now = Instant::now();
+        let (seen, never) = (Pubkey::new_unique(), Pubkey::new_unique());
+        book.record_seen(seen, addr(1), now - Duration::from_secs(30));
+        book.insert(never, addr(2));
+        book.save(&path).unwrap();
+
+        let loaded = AddressBook::load(&path).unwrap();
+        assert_eq!(loaded.peers, book.peers);
+        assert!(!loaded.last_seen.contains_key(&never));
+        let age = loaded.last_seen[&seen].elapsed().as_secs();
+        assert!((29..=32).contains(&age), "age {age}");
+    }
+
+    #[test]
+    fn test_load_rejects_bad_pubkey() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("address_book.json");
+        fs::write(
+            &path,
+            r#"[{"pubkey":"not-a-key","addr":"10.0.0.1:8900","last_seen_unix_secs":null}]"#,
+        )
+        .unwrap();
+        let err = AddressBook::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_insert_if_absent_is_bounded() {
+        let mut book = AddressBook::new();
+        let known = Pubkey::new_unique();
+        book.insert(known, addr(1));
+        assert!(!book.insert_if_absent(known, addr(2)));
+        assert_eq!(book.peers[&known], addr(1));
+
+        while book.len() < MAX_ADDRESS_BOOK_SIZE {
+            assert!(book.insert_if_absent(Pubkey::new_unique(), addr(3)));
+        }
+        assert!(!book.insert_if_absent(Pubkey::new_unique(), addr(3)));
+        assert_eq!(book.sample(20).len(), 20);
+    }
+}
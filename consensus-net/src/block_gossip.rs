@@ -0,0 +1,537 @@
+//! Fan-out gossip for block propagation.
+//!
+//! In [`BlockPropagation::Direct`] mode the proposer sends every block to
+//! every validator itself — O(n) block-sized sends from a single node.  In
+//! [`BlockPropagation::Gossip`] mode each node that receives a block forwards
+//! it to `gossip_fanout` randomly chosen validators not yet known to hold it,
+//! so the proposer sends O(fanout × rounds) blocks in the happy path and the
+//! block reaches everyone in O(log n) hops.
+//!
+//! ## Acknowledgements
+//!
+//! A node answers every block it receives with a `BlockAck` to the sender,
+//! and the first copy also with an ACK to the proposer.  ACKed peers are
+//! never forwarded to again.  Because the proposer hears from everyone that
+//! got the block, it alone performs the fallback: after `gossip_rounds`
+//! rounds it sends the block directly to every validator that has not
+//! ACKed.  Relays simply stop after `gossip_rounds` rounds.
+//!
+//! [`BlockGossip`] only decides who to send to; the caller owns the
+//! transport and calls [`BlockGossip::tick`] once per gossip interval.
+
+use {
+    crate::{config::ConsensusNetConfig, message::ValidatorInfo},
+    log::debug,
+    rand::seq::IndexedRandom,
+    solana_hash::Hash,
+    solana_pubkey::Pubkey,
+    std::collections::{BTreeMap, HashSet},
+};
+
+/// How committed blocks are disseminated to validators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockPropagation {
+    /// The proposer sends every block to every validator.
+    #[default]
+    Direct,
+    /// Blocks are forwarded in fan-out rounds, with a direct fallback by the
+    /// proposer.
+    Gossip,
+}
+
+/// What this node knows about the spread of one block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockGossipState {
+    /// Hash of the block being propagated.
+    pub block_hash: Hash,
+    /// Validator that proposed the block.
+    pub origin: Pubkey,
+    /// Peers that sent us the block.
+    pub received_from: HashSet<Pubkey>,
+    /// Peers that acknowledged holding the block.
+    pub acked_by: HashSet<Pubkey>,
+    /// Forwarding rounds completed, including the direct fallback.
+    pub rounds: u32,
+}
+
+impl BlockGossipState {
+    fn new(block_hash: Hash, origin: Pubkey) -> Self {
+        Self {
+            block_hash,
+            origin,
+            received_from: HashSet::new(),
+            acked_by: HashSet::new(),
+            rounds: 0,
+        }
+    }
+
+    /// Whether `pubkey` is known to hold the block.
+    pub fn has_block(&self, pubkey: &Pubkey) -> bool {
+        pubkey == &self.origin
+            || self.received_from.contains(pubkey)
+            || self.acked_by.contains(pubkey)
+    }
+}
+
+/// What to do with a block just received.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlockReceipt {
+    /// Whether this was the first copy; only then is the block delivered.
+    pub first: bool,
+    /// Validators to send a `BlockAck` to.
+    pub ack_to: Vec<ValidatorInfo>,
+    /// Validators to forward the block to.
+    pub forward_to: Vec<ValidatorInfo>,
+}
+
+/// Per-height gossip state for the blocks this node is propagating.
+#[derive(Debug, Clone)]
+pub struct BlockGossip {
+    /// This node's identity.
+    me: Pubkey,
+    /// Validators selected per forwarding round.
+    fanout: usize,
+    /// Forwarding rounds before the proposer falls back to direct sends.
+    max_rounds: u32,
+    /// Gossip state keyed by block height.
+    blocks: BTreeMap<u64, BlockGossipState>,
+}
+
+impl BlockGossip {
+    /// Create an engine for `me` with the fanout and round limit in `config`.
+    pub fn new(me: Pubkey, config: &ConsensusNetConfig) -> Self {
+        Self {
+            me,
+            fanout: config.gossip_fanout,
+            max_rounds: config.gossip_rounds,
+            blocks: BTreeMap::new(),
+        }
+    }
+
+    /// Gossip state of the block at `height`, if it is being tracked.
+    pub fn state(&self, height: u64) -> Option<&BlockGossipState> {
+        self.blocks.get(&height)
+    }
+
+    /// Start propagating a block this node proposed.  Returns the
+    /// validators to send it to in the first round.
+    pub fn originate(
+        &mut self,
+        height: u64,
+        block_hash: Hash,
+        validators: &[ValidatorInfo],
+    ) -> Vec<ValidatorInfo> {
+        let me = self.me;
+        let state = self
+            .blocks
+            .entry(height)
+            .or_insert_with(|| BlockGossipState::new(block_hash, me));
+        if state.block_hash != block_hash || state.rounds > 0 {
+            return Vec::new();
+        }
+        Self::next_round(me, self.fanout, self.max_rounds, state, validators)
+    }
+
+    /// Record the block at `height` received from `from` and proposed by
+    /// `origin`.
+    ///
+    /// The first copy is forwarded to up to `fanout` validators that are not
+    /// known to hold it.  Every copy is ACKed to its sender, and the first
+    /// one also to the proposer.  A block whose hash differs from the one
+    /// already tracked at `height` is ignored.
+    pub fn on_block(
+        &mut self,
+        height: u64,
+        block_hash: Hash,
+        from: Pubkey,
+        origin: Pubkey,
+        validators: &[ValidatorInfo],
+    ) -> BlockReceipt {
+        let me = self.me;
+        let mut first = false;
+        let state = self.blocks.entry(height).or_insert_with(|| {
+            first = true;
+            BlockGossipState::new(block_hash, origin)
+        });
+        if state.block_hash != block_hash {
+            debug!(
+                "ignoring conflicting block {} at height {} from {}",
+                block_hash, height, from
+            );
+            return BlockReceipt::default();
+        }
+        state.received_from.insert(from);
+
+        let mut ack_to = vec![from];
+        if first && origin != from && origin != me {
+            ack_to.push(origin);
+        }
+        let forward_to = if first {
+            Self::next_round(me, self.fanout, self.max_rounds, state, validators)
+        } else {
+            Vec::new()
+        };
+        BlockReceipt {
+            first,
+            ack_to: validators
+                .iter()
+                .filter(|v| ack_to.contains(&v.pubkey))
+                .cloned()
+                .collect(),
+            forward_to,
+        }
+    }
+
+    /// Record that `from` holds the block at `height`.
+    pub fn on_ack(&mut self, height: u64, block_hash: Hash, from: Pubkey) {
+        match self.blocks.get_mut(&height) {
+            Some(state) if state.block_hash == block_hash => {
+                state.acked_by.insert(from);
+            }
+            _ => debug!("ignoring ACK for unknown block at height {}", height),
+        }
+    }
+
+    /// Run the next forwarding round for every block still spreading.
+    ///
+    /// Returns the heights and the validators to send each block to.
+    pub fn tick(&mut self, validators: &[ValidatorInfo]) -> Vec<(u64, Vec<ValidatorInfo>)> {
+        let (me, fanout, max_rounds) = (self.me, self.fanout, self.max_rounds);
+        self.blocks
+            .iter_mut()
+            .filter(|(_, state)| state.rounds > 0)
+            .filter_map(|(height, state)| {
+                let targets = Self::next_round(me, fanout, max_rounds, state, validators);
+                (!targets.is_empty()).then_some((*height, targets))
+            })
+            .collect()
+    }
+
+    /// Stop tracking blocks below `height`.
+    pub fn prune_below(&mut self, height: u64) {
+        self.blocks = self.blocks.split_off(&height);
+    }
+
+    /// Targets of the next round for `state`, advancing its round counter.
+    ///
+    /// Rounds `0..max_rounds` pick `fanout` random validators that are not
+    /// known to hold the block; the proposer then sends directly to every
+    /// remaining one.
+    fn next_round(
+        me: Pubkey,
+        fanout: usize,
+        max_rounds: u32,
+        state: &mut BlockGossipState,
+        validators: &[ValidatorInfo],
+    ) -> Vec<ValidatorInfo> {
+        let is_origin = state.origin == me;
+        let fallback = is_origin && state.rounds == max_rounds;
+        if state.rounds > max_rounds || (state.rounds == max_rounds && !is_origin) {
+            return Vec::new();
+        }
+        state.rounds = state.rounds.saturating_add(1);
+
+        let missing: Vec<&ValidatorInfo> = validators
+            .iter()
+            .filter(|v| v.pubkey != me && !state.has_block(&v.pubkey))
+            .collect();
+        if fallback {
+            if !missing.is_empty() {
+                debug!(
+                    "block {} reached gossip round limit, sending directly to {} validators",
+                    state.block_hash,
+                    missing.len()
+                );
+            }
+            return missing.into_iter().cloned().collect();
+        }
+        missing
+            .choose_multiple(&mut rand::rng(), fanout)
+            .map(|v| (*v).clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{
+            cmp::Reverse,
+            collections::{BinaryHeap, HashMap},
+            net::SocketAddr,
+        },
+    };
+
+    fn test_validators(n: usize) -> Vec<ValidatorInfo> {
+        (0..n)
+            .map(|_| ValidatorInfo {
+                pubkey: Pubkey::new_unique(),
+                stake_weight: 1,
+                consensus_addr: SocketAddr::from(([127, 0, 0, 1], 8900)),
+            })
+            .collect()
+    }
+
+    fn config(fanout: usize, rounds: u32) -> ConsensusNetConfig {
+        ConsensusNetConfig {
+            block_propagation: BlockPropagation::Gossip,
+            gossip_fanout: fanout,
+            gossip_rounds: rounds,
+            ..ConsensusNetConfig::dev_default()
+        }
+    }
+
+    fn keys(validators: &[ValidatorInfo]) -> HashSet<Pubkey> {
+        validators.iter().map(|v| v.pubkey).collect()
+    }
+
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Event {
+        Block { from: Pubkey, to: Pubkey },
+        Ack { from: Pubkey, to: Pubkey },
+        Tick { node: Pubkey },
+    }
+
+    /// Outcome of a simulated propagation.
+    struct Coverage {
+        /// Time at which the last validator received the block, if all did.
+        all_covered_ms: Option<u64>,
+        /// Blocks sent by the proposer.
+        proposer_sends: usize,
+        /// Blocks sent in total.
+        total_sends: usize,
+    }
+
+    /// Discrete-event simulation of one block spreading from `validators[0]`
+    /// with `hop_ms` of latency per message and a tick every `tick_ms`.
+    /// Messages from `dropped.0` to `dropped.1` are lost.
+    fn simulate(
+        config: &ConsensusNetConfig,
+        validators: &[ValidatorInfo],
+        hop_ms: u64,
+        tick_ms: u64,
+        dropped: &[(Pubkey, Pubkey)],
+    ) -> Coverage {
+        const HEIGHT: u64 = 1;
+        const HORIZON_MS: u64 = 10_000;
+        let hash = Hash::new_unique();
+        let origin = validators[0].pubkey;
+        let mut nodes: HashMap<Pubkey, BlockGossip> = validators
+            .iter()
+            .map(|v| (v.pubkey, BlockGossip::new(v.pubkey, config)))
+            .collect();
+        let mut covered: HashMap<Pubkey, u64> = HashMap::from([(origin, 0)]);
+        let mut events = BinaryHeap::new();
+        let (mut proposer_sends, mut total_sends) = (0usize, 0usize);
+
+        let mut send_blocks =
+            |events: &mut BinaryHeap<_>, now: u64, from: Pubkey, targets: Vec<ValidatorInfo>| {
+                total_sends = total_sends.saturating_add(targets.len());
+                if from == origin {
+                    proposer_sends = proposer_sends.saturating_add(targets.len());
+                }
+                for target in targets {
+                    if !dropped.contains(&(from, target.pubkey)) {
+                        let event = Event::Block {
+                            from,
+                            to: target.pubkey,
+                        };
+                        events.push(Reverse((now.saturating_add(hop_ms), event)));
+                    }
+                }
+            };
+
+        let targets = nodes
+            .get_mut(&origin)
+            .unwrap()
+            .originate(HEIGHT, hash, validators);
+        send_blocks(&mut events, 0, origin, targets);
+        for v in validators {
+            let event = Event::Tick { node: v.pubkey };
+            events.push(Reverse((tick_ms, event)));
+        }
+
+        while let Some(Reverse((now, event))) = events.pop() {
+            if now > HORIZON_MS {
+                break;
+            }
+            match event {
+                Event::Block { from, to } => {
+                    covered.entry(to).or_insert(now);
+                    let receipt = nodes
+                        .get_mut(&to)
+                        .unwrap()
+                        .on_block(HEIGHT, hash, from, origin, validators);
+                    for ack in receipt.ack_to {
+                        let event = Event::Ack {
+                            from: to,
+                            to: ack.pubkey,
+                        };
+                        events.push(Reverse((now.saturating_add(hop_ms), event)));
+                    }
+                    send_blocks(&mut events, now, to, receipt.forward_to);
+                }
+                Event::Ack { from, to } => {
+                    nodes.get_mut(&to).unwrap().on_ack(HEIGHT, hash, from);
+                }
+                Event::Tick { node } => {
+                    for (_, targets) in nodes.get_mut(&node).unwrap().tick(validators) {
+                        send_blocks(&mut events, now, node, targets);
+                    }
+                    events.push(Reverse((now.saturating_add(tick_ms), Event::Tick { node })));
+                }
+            }
+        }
+
+        Coverage {
+            all_covered_ms: (covered.len() == validators.len())
+                .then(|| covered.values().copied().max().unwrap()),
+            proposer_sends,
+            total_sends,
+        }
+    }
+
+    #[test]
+    fn test_first_receipt_forwards_to_fanout_and_acks() {
+        let validators = test_validators(20);
+        let (origin, relay, me) = (
+            validators[0].pubkey,
+            validators[1].pubkey,
+            validators[2].pubkey,
+        );
+        let mut gossip = BlockGossip::new(me, &config(4, 3));
+        let hash = Hash::new_unique();
+
+        let receipt = gossip.on_block(5, hash, relay, origin, &validators);
+        assert!(receipt.first);
+        assert_eq!(keys(&receipt.ack_to), HashSet::from([relay, origin]));
+        assert_eq!(receipt.forward_to.len(), 4);
+        let targets = keys(&receipt.forward_to);
+        assert!(!targets.contains(&origin) && !targets.contains(&relay));
+        assert!(!targets.contains(&me));
+
+        // A second copy is ACKed to its sender only and not forwarded.
+        let other = validators[3].pubkey;
+        let receipt = gossip.on_block(5, hash, other, origin, &validators);
+        assert!(!receipt.first);
+        assert_eq!(keys(&receipt.ack_to), HashSet::from([other]));
+        assert!(receipt.forward_to.is_empty());
+
+        // A conflicting block at the same height is ignored.
+        let receipt = gossip.on_block(5, Hash::new_unique(), other, origin, &validators);
+        assert_eq!(receipt, BlockReceipt::default());
+    }
+
+    #[test]
+    fn test_acked_peers_are_not_forwarded_to() {
+        let validators = test_validators(6);
+        let me = validators[0].pubkey;
+        let mut gossip = BlockGossip::new(me, &config(10, 2));
+        let hash = Hash::new_unique();
+
+        assert_eq!(gossip.originate(9, hash, &validators).len(), 5);
+        for v in &validators[1..4] {
+            gossip.on_ack(9, hash, v.pubkey);
+        }
+        // An ACK for a different block changes nothing.
+        gossip.on_ack(9, Hash::new_unique(), validators[4].pubkey);
+
+        let round = gossip.tick(&validators);
+        assert_eq!(round.len(), 1);
+        assert_eq!(
+            keys(&round[0].1),
+            keys(&validators[4..]),
+            "only un-ACKed validators are targeted"
+        );
+    }
+
+    #[test]
+    fn test_relays_stop_and_origin_falls_back_after_max_rounds() {
+        let validators = test_validators(30);
+        let origin = validators[0].pubkey;
+        let hash = Hash::new_unique();
+
+        let mut relay = BlockGossip::new(validators[1].pubkey, &config(2, 3));
+        let receipt = relay.on_block(1, hash, origin, origin, &validators);
+        assert_eq!(receipt.forward_to.len(), 2);
+        assert_eq!(relay.tick(&validators)[0].1.len(), 2);
+        assert_eq!(relay.tick(&validators)[0].1.len(), 2);
+        assert!(relay.tick(&validators).is_empty());
+
+        let mut proposer = BlockGossip::new(origin, &config(2, 3));
+        assert_eq!(proposer.originate(1, hash, &validators).len(), 2);
+        proposer.tick(&validators);
+        proposer.tick(&validators);
+        proposer.on_ack(1, hash, validators[1].pubkey);
+        // Fallback: every validator that has not ACKed.
+        let fallback = proposer.tick(&validators);
+        assert_eq!(fallback[0].1.len(), 28);
+        assert!(!keys(&fallback[0].1).contains(&validators[1].pubkey));
+        assert!(proposer.tick(&validators).is_empty());
+        assert_eq!(proposer.state(1).unwrap().rounds, 4);
+
+        // Without gossip rounds the proposer sends directly at once.
+        let mut direct = BlockGossip::new(origin, &config(2, 0));
+        assert_eq!(direct.originate(2, hash, &validators).len(), 29);
+    }
+
+    #[test]
+    fn test_prune_below() {
+        let validators = test_validators(4);
+        let mut gossip = BlockGossip::new(validators[0].pubkey, &config(2, 1));
+        for height in 1..=4 {
+            gossip.originate(height, Hash::new_unique(), &validators);
+        }
+        gossip.prune_below(3);
+        assert!(gossip.state(2).is_none());
+        assert!(gossip.state(3).is_some() && gossip.state(4).is_some());
+    }
+
+    #[test]
+    fn test_gossip_covers_50_peers_with_bounded_proposer_bandwidth() {
+        const HOP_MS: u64 = 10;
+        const TICK_MS: u64 = 50;
+        let validators = test_validators(50);
+        let config = config(8, 3);
+
+        let coverage = simulate(&config, &validators, HOP_MS, TICK_MS, &[]);
+        let covered_ms = coverage.all_covered_ms.expect("block reached every peer");
+        // Fan-out forwarding reaches all 50 peers in a few hops, before the
+        // proposer's direct fallback would kick in.
+        assert!(
+            covered_ms < u64::from(config.gossip_rounds) * TICK_MS,
+            "coverage took {covered_ms}ms"
+        );
+        // Direct broadcast would cost the proposer 49 sends.
+        assert!(
+            coverage.proposer_sends <= config.gossip_fanout * config.gossip_rounds as usize,
+            "proposer sent {} blocks",
+            coverage.proposer_sends
+        );
+        assert!(coverage.total_sends >= validators.len() - 1);
+    }
+
+    #[test]
+    fn test_fallback_reaches_peer_isolated_from_relays() {
+        const HOP_MS: u64 = 10;
+        const TICK_MS: u64 = 50;
+        let validators = test_validators(50);
+        let origin = validators[0].pubkey;
+        let isolated = validators[49].pubkey;
+        let config = config(8, 3);
+
+        // Only the proposer can reach `isolated`, and not before the fallback
+        // unless it happened to pick it in a gossip round.
+        let dropped: Vec<(Pubkey, Pubkey)> = validators
+            .iter()
+            .filter(|v| v.pubkey != origin)
+            .map(|v| (v.pubkey, isolated))
+            .collect();
+        let coverage = simulate(&config, &validators, HOP_MS, TICK_MS, &dropped);
+        let covered_ms = coverage
+            .all_covered_ms
+            .expect("fallback reached every peer");
+        assert!(covered_ms <= u64::from(config.gossip_rounds) * TICK_MS + HOP_MS);
+    }
+}
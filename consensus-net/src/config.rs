@@ -1,6 +1,13 @@
 //! Configuration for the consensus networking layer.
 
-use std::net::SocketAddr;
+use {
+    crate::{
+        block_gossip::BlockPropagation, gossip::GossipLayerConfig,
+        handshake::MIN_SUPPORTED_VERSION, transport::TransportKind,
+    },
+    solana_pubkey::Pubkey,
+    std::{net::SocketAddr, path::PathBuf},
+};
 
 /// Configuration for the consensus P2P network.
 ///
@@ -20,6 +27,17 @@ pub struct ConsensusNetConfig {
     /// Matches TRv1's active validator cap of 200.
     pub max_peers: usize,
 
+    /// Below this many connected peers, every address in the address book
+    /// is redialled, most recently seen first.
+    pub min_peers: usize,
+
+    /// Where the address book is loaded from on startup and saved to on
+    /// shutdown.  `None` keeps it in memory only.
+    pub address_book_path: Option<PathBuf>,
+
+    /// Peers to seed the address book with on startup.
+    pub bootstrap_peers: Vec<(Pubkey, SocketAddr)>,
+
     /// How long to wait for a message send/recv before considering it failed (ms).
     pub message_timeout_ms: u64,
 
@@ -31,18 +49,61 @@ pub struct ConsensusNetConfig {
     /// Consensus votes are small (~200 bytes), but blocks can be up to 1 MB.
     pub max_message_size: usize,
 
-    /// Whether to prefer QUIC transport over TCP.
+    /// Transport backend selected at startup.
     /// QUIC provides better multiplexing and connection migration.
-    pub prefer_quic: bool,
+    /// Default: QUIC.
+    pub transport: TransportKind,
+
+    /// Whether to zstd-compress outbound messages.
+    /// Applied only to QUIC peers that negotiate compression in the
+    /// handshake; everyone else is sent uncompressed frames.
+    pub enable_compression: bool,
 
     /// Number of seconds a peer can be silent before being considered dead.
     pub peer_timeout_secs: u64,
 
+    /// Reputation below which a peer is disconnected and banned.
+    /// A peer starts at 100; each invalid message costs 10.
+    pub min_reputation_threshold: f64,
+
+    /// How long a banned peer is refused (seconds).
+    pub ban_duration_seconds: u64,
+
+    /// Messages a peer may send back-to-back before rate limiting applies.
+    pub peer_rate_limit_burst: u32,
+
+    /// Sustained messages per second admitted from each peer, across all of
+    /// its connections.
+    pub peer_rate_limit_per_second: u32,
+
     /// Maximum number of concurrent block sync requests.
     pub max_sync_requests: usize,
 
     /// Size of the internal message channel buffer.
     pub channel_buffer_size: usize,
+
+    /// Strategy used to disseminate blocks to validators.
+    /// Default: direct broadcast to every connected validator.
+    pub gossip_layer: GossipLayerConfig,
+
+    /// How committed blocks reach validators.
+    /// Default: the proposer sends every block directly.
+    pub block_propagation: BlockPropagation,
+
+    /// Validators a block is forwarded to per gossip round.
+    pub gossip_fanout: usize,
+
+    /// Gossip rounds before the proposer sends directly to the validators
+    /// that have not acknowledged a block.
+    pub gossip_rounds: u32,
+
+    /// Identifies the chain in the connection handshake, typically the
+    /// genesis hash.  Peers advertising a different chain are refused.
+    pub chain_id: [u8; 32],
+
+    /// Oldest protocol version accepted in the connection handshake.
+    /// Raise it to stop talking to peers that have not upgraded.
+    pub min_protocol_version: u32,
 }
 
 impl Default for ConsensusNetConfig {
@@ -51,13 +112,27 @@ impl Default for ConsensusNetConfig {
             bind_addr: "0.0.0.0:8900".parse().expect("valid default bind addr"),
             consensus_port: 8900,
             max_peers: 200,
+            min_peers: 8,
+            address_book_path: None,
+            bootstrap_peers: Vec::new(),
             message_timeout_ms: 5_000,
             heartbeat_interval_ms: 500,
             max_message_size: 1_048_576, // 1 MB
-            prefer_quic: true,
+            transport: TransportKind::Quic,
+            enable_compression: true,
             peer_timeout_secs: 30,
+            min_reputation_threshold: 10.0,
+            ban_duration_seconds: 3_600,
+            peer_rate_limit_burst: 1_000,
+            peer_rate_limit_per_second: 500,
             max_sync_requests: 16,
             channel_buffer_size: 10_000,
+            gossip_layer: GossipLayerConfig::Direct,
+            block_propagation: BlockPropagation::Direct,
+            gossip_fanout: 8,
+            gossip_rounds: 3,
+            chain_id: [0u8; 32],
+            min_protocol_version: MIN_SUPPORTED_VERSION,
         }
     }
 }
@@ -70,13 +145,27 @@ impl ConsensusNetConfig {
             bind_addr: "127.0.0.1:0".parse().expect("valid dev bind addr"),
             consensus_port: 0,
             max_peers: 10,
+            min_peers: 2,
+            address_book_path: None,
+            bootstrap_peers: Vec::new(),
             message_timeout_ms: 1_000,
             heartbeat_interval_ms: 200,
             max_message_size: 1_048_576,
-            prefer_quic: false,
+            transport: TransportKind::Tcp,
+            enable_compression: false,
             peer_timeout_secs: 5,
+            min_reputation_threshold: 10.0,
+            ban_duration_seconds: 60,
+            peer_rate_limit_burst: 1_000,
+            peer_rate_limit_per_second: 500,
             max_sync_requests: 4,
             channel_buffer_size: 1_000,
+            gossip_layer: GossipLayerConfig::Direct,
+            block_propagation: BlockPropagation::Direct,
+            gossip_fanout: 8,
+            gossip_rounds: 3,
+            chain_id: [0u8; 32],
+            min_protocol_version: MIN_SUPPORTED_VERSION,
         }
     }
 }
@@ -26,6 +26,10 @@ pub enum ConsensusNetError {
     #[error("peer already connected: {0}")]
     PeerAlreadyConnected(solana_pubkey::Pubkey),
 
+    /// The peer is banned for low reputation.
+    #[error("peer banned: {0}")]
+    PeerBanned(solana_pubkey::Pubkey),
+
     /// Maximum peer count has been reached.
     #[error("maximum peers reached: {0}")]
     MaxPeersReached(usize),
@@ -53,6 +57,58 @@ pub enum ConsensusNetError {
     /// Connection to a peer was refused or dropped.
     #[error("connection failed to {0}: {1}")]
     ConnectionFailed(std::net::SocketAddr, String),
+
+    /// QUIC stream or TLS configuration error.
+    #[error("QUIC error: {0}")]
+    Quic(String),
+
+    /// A synced block contradicts a sync checkpoint.
+    #[error("block {height} does not match its sync checkpoint; re-syncing from {rollback_from}")]
+    CheckpointMismatch {
+        /// Height of the offending block.
+        height: u64,
+        /// First height discarded and queued to be synced again.
+        rollback_from: u64,
+    },
+
+    /// The address book could not be written.
+    #[error("address book {}: {source}", path.display())]
+    AddressBook {
+        /// Where the address book was being written.
+        path: std::path::PathBuf,
+        /// The underlying I/O error.
+        source: std::io::Error,
+    },
+
+    /// The peer failed the connection handshake.
+    #[error("handshake failed: {0}")]
+    Handshake(#[from] HandshakeError),
+}
+
+/// Reasons a peer's [`Handshake`](crate::handshake::Handshake) is rejected.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum HandshakeError {
+    /// The peer speaks a protocol version outside our supported range.
+    #[error("incompatible protocol version {their_version} (supported {our_min}..={our_max})")]
+    IncompatibleVersion {
+        /// Version the peer advertised.
+        their_version: u32,
+        /// Oldest version we accept.
+        our_min: u32,
+        /// Newest version we accept.
+        our_max: u32,
+    },
+
+    /// The peer is on a different chain.
+    #[error("chain id mismatch")]
+    ChainIdMismatch {
+        /// Chain id the peer advertised.
+        their_chain_id: [u8; 32],
+    },
+
+    /// The signature does not match the claimed validator identity.
+    #[error("invalid handshake signature for {0}")]
+    InvalidSignature(solana_pubkey::Pubkey),
 }
 
 /// Convenience result type for consensus networking operations.
@@ -0,0 +1,522 @@
+//! Pluggable dissemination strategies for consensus traffic.
+//!
+//! Broadcasting every block directly to every validator costs O(n²)
+//! messages across the network.  A [`GossipLayer`] decides which validators
+//! a node forwards a message to; the remaining validators are reached by
+//! relays.  Three strategies are provided:
+//!
+//! | Strategy | Per-node sends | Hops to reach all peers |
+//! |----------|----------------|-------------------------|
+//! | [`DirectBroadcast`] | n − 1 (origin only) | 1 |
+//! | [`EpidemicGossip`]  | fanout | O(log n), probabilistic |
+//! | [`TreeGossip`]      | ≤ fanout | ⌈log_fanout(n)⌉, deterministic |
+//!
+//! Every strategy is evaluated over the **canonical validator set** for the
+//! epoch (the list carried by `ValidatorSetUpdate`), not over a node's own
+//! peer table, so all validators agree on who relays to whom.  Receivers
+//! use [`SeenMessages`] to forward each message at most once.
+
+use {
+    crate::message::ValidatorInfo,
+    rand::seq::IndexedRandom,
+    solana_hash::Hash,
+    solana_pubkey::Pubkey,
+    std::{
+        collections::{HashSet, VecDeque},
+        fmt::Debug,
+    },
+};
+
+/// Selects the validators a node forwards a message to.
+pub trait GossipLayer: Debug + Send + Sync {
+    /// Returns the validators `me` should forward `msg` to, given that it was
+    /// first sent by `origin`.  `validators` is the canonical validator set;
+    /// `me` and `origin` are never returned.
+    fn relay_targets(
+        &self,
+        msg: &[u8],
+        origin: &Pubkey,
+        me: &Pubkey,
+        validators: &[ValidatorInfo],
+    ) -> Vec<ValidatorInfo>;
+
+    /// Returns the validators `me` sends a message it originates to.
+    fn disseminate(
+        &self,
+        msg: &[u8],
+        me: &Pubkey,
+        validators: &[ValidatorInfo],
+    ) -> Vec<ValidatorInfo> {
+        self.relay_targets(msg, me, me, validators)
+    }
+}
+
+/// Which [`GossipLayer`] implementation the consensus network uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GossipLayerConfig {
+    /// Send every message to every peer.
+    #[default]
+    Direct,
+    /// Send every message to `fanout` randomly selected peers.
+    Epidemic { fanout: usize },
+    /// Relay along a stake-ordered tree with `fanout` children per node.
+    Tree { fanout: usize },
+}
+
+impl GossipLayerConfig {
+    /// Instantiate the configured strategy.
+    pub fn build(&self) -> Box<dyn GossipLayer> {
+        match *self {
+            Self::Direct => Box::new(DirectBroadcast),
+            Self::Epidemic { fanout } => Box::new(EpidemicGossip { fanout }),
+            Self::Tree { fanout } => Box::new(TreeGossip {
+                tree: GossipTree::new(fanout),
+            }),
+        }
+    }
+}
+
+/// Validators other than `origin` and `me`.
+fn candidates<'a>(
+    origin: &'a Pubkey,
+    me: &'a Pubkey,
+    validators: &'a [ValidatorInfo],
+) -> impl Iterator<Item = &'a ValidatorInfo> {
+    validators
+        .iter()
+        .filter(move |v| &v.pubkey != origin && &v.pubkey != me)
+}
+
+// ── Direct broadcast ────────────────────────────────────────────────────────
+
+/// The origin sends every message to all validators; nobody relays.
+/// Lowest latency, highest bandwidth.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DirectBroadcast;
+
+impl GossipLayer for DirectBroadcast {
+    fn relay_targets(
+        &self,
+        _msg: &[u8],
+        origin: &Pubkey,
+        me: &Pubkey,
+        validators: &[ValidatorInfo],
+    ) -> Vec<ValidatorInfo> {
+        if origin != me {
+            return Vec::new();
+        }
+        candidates(origin, me, validators).cloned().collect()
+    }
+}
+
+// ── Epidemic gossip ─────────────────────────────────────────────────────────
+
+/// Forwards every message to `fanout` validators chosen uniformly at random.
+///
+/// Each call draws a fresh sample, so informed nodes that re-gossip every
+/// round reach every validator with high probability in O(log n) rounds for
+/// `fanout >= 3`.
+#[derive(Debug, Clone, Copy)]
+pub struct EpidemicGossip {
+    /// Number of validators selected per forward.
+    pub fanout: usize,
+}
+
+impl GossipLayer for EpidemicGossip {
+    fn relay_targets(
+        &self,
+        _msg: &[u8],
+        origin: &Pubkey,
+        me: &Pubkey,
+        validators: &[ValidatorInfo],
+    ) -> Vec<ValidatorInfo> {
+        let candidates: Vec<&ValidatorInfo> = candidates(origin, me, validators).collect();
+        candidates
+            .choose_multiple(&mut rand::rng(), self.fanout)
+            .map(|v| (*v).clone())
+            .collect()
+    }
+}
+
+// ── Tree gossip ─────────────────────────────────────────────────────────────
+
+/// A structured overlay arranging validators into a `fanout`-ary tree.
+///
+/// The origin is the root.  The remaining validators are ordered
+/// canonically (stake descending, then pubkey) and placed breadth-first, so
+/// every node derives the same tree from the same validator set and origin:
+/// tree position `i` (root = 0) relays to positions
+/// `i * fanout + 1 ..= (i + 1) * fanout`.
+#[derive(Debug, Clone, Copy)]
+pub struct GossipTree {
+    /// Children per node.  A fanout of 0 is treated as 1.
+    pub fanout: usize,
+}
+
+impl GossipTree {
+    /// Create a tree with the given fanout.
+    pub fn new(fanout: usize) -> Self {
+        Self { fanout }
+    }
+
+    /// Non-root validators in canonical tree order.  `origin` is excluded
+    /// and a pubkey listed more than once keeps only its first entry, so it
+    /// holds a single tree position whatever stakes its duplicates carry.
+    pub fn order(&self, origin: &Pubkey, validators: &[ValidatorInfo]) -> Vec<ValidatorInfo> {
+        let mut seen = HashSet::new();
+        let mut ordered: Vec<ValidatorInfo> = validators
+            .iter()
+            .filter(|v| &v.pubkey != origin && seen.insert(v.pubkey))
+            .cloned()
+            .collect();
+        ordered.sort_by(|a, b| {
+            b.stake_weight
+                .cmp(&a.stake_weight)
+                .then_with(|| a.pubkey.cmp(&b.pubkey))
+        });
+        ordered
+    }
+
+    /// Children of `relayer` in the tree rooted at `origin`.  A relayer that
+    /// is neither the origin nor a validator has no children.
+    pub fn children(
+        &self,
+        origin: &Pubkey,
+        relayer: &Pubkey,
+        validators: &[ValidatorInfo],
+    ) -> Vec<ValidatorInfo> {
+        let fanout = self.fanout.max(1);
+        let ordered = self.order(origin, validators);
+        let position = if relayer == origin {
+            0
+        } else {
+            match ordered.iter().position(|v| &v.pubkey == relayer) {
+                Some(index) => index.saturating_add(1),
+                None => return Vec::new(),
+            }
+        };
+        // Tree position `p` is `ordered[p - 1]`.
+        ordered
+            .into_iter()
+            .skip(position.saturating_mul(fanout))
+            .take(fanout)
+            .collect()
+    }
+}
+
+/// Relays messages along a [`GossipTree`].
+#[derive(Debug, Clone, Copy)]
+pub struct TreeGossip {
+    /// The overlay used to pick recipients.
+    pub tree: GossipTree,
+}
+
+impl GossipLayer for TreeGossip {
+    fn relay_targets(
+        &self,
+        _msg: &[u8],
+        origin: &Pubkey,
+        me: &Pubkey,
+        validators: &[ValidatorInfo],
+    ) -> Vec<ValidatorInfo> {
+        self.tree.children(origin, me, validators)
+    }
+}
+
+// ── Duplicate suppression ───────────────────────────────────────────────────
+
+/// Bounded set of recently seen `(origin, message)` pairs.
+///
+/// Relaying strategies rely on each node forwarding a message at most once;
+/// the oldest entry is evicted once `capacity` is reached.
+#[derive(Debug, Clone, Default)]
+pub struct SeenMessages {
+    seen: HashSet<Hash>,
+    order: VecDeque<Hash>,
+    capacity: usize,
+}
+
+impl SeenMessages {
+    /// Create an empty set retaining at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            seen: HashSet::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    fn key(origin: &Pubkey, msg: &[u8]) -> Hash {
+        solana_sha256_hasher::hashv(&[origin.as_ref(), msg])
+    }
+
+    /// Record `msg` from `origin`.  Returns `true` the first time a message
+    /// is seen and `false` for duplicates.
+    pub fn insert(&mut self, origin: &Pubkey, msg: &[u8]) -> bool {
+        let key = Self::key(origin, msg);
+        if self.capacity == 0 {
+            return true;
+        }
+        if !self.seen.insert(key) {
+            return false;
+        }
+        if self.order.len() == self.capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.seen.remove(&evicted);
+            }
+        }
+        self.order.push_back(key);
+        true
+    }
+
+    /// Whether `msg` from `origin` has been seen.
+    pub fn contains(&self, origin: &Pubkey, msg: &[u8]) -> bool {
+        self.seen.contains(&Self::key(origin, msg))
+    }
+
+    /// Number of retained entries.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Whether no messages are retained.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{collections::HashMap, net::SocketAddr},
+    };
+
+    fn test_validators(n: usize) -> Vec<ValidatorInfo> {
+        (0..n)
+            .map(|i| ValidatorInfo {
+                pubkey: Pubkey::new_unique(),
+                stake_weight: (i as u64).saturating_add(1).saturating_mul(1_000_000),
+                consensus_addr: SocketAddr::from(([127, 0, 0, 1], 8900)),
+            })
+            .collect()
+    }
+
+    /// Simulate delivery from `origin`: every node forwards a message the
+    /// first time it sees it, to the targets its own `relay_targets` call
+    /// returns.  Returns how many times each validator received the message.
+    fn simulate(
+        layer: &dyn GossipLayer,
+        origin: &Pubkey,
+        validators: &[ValidatorInfo],
+    ) -> HashMap<Pubkey, usize> {
+        let mut received: HashMap<Pubkey, usize> = HashMap::new();
+        let mut seen: HashMap<Pubkey, SeenMessages> = HashMap::new();
+        let mut queue: VecDeque<(Pubkey, Pubkey)> = layer
+            .disseminate(b"block", origin, validators)
+            .into_iter()
+            .map(|v| (*origin, v.pubkey))
+            .collect();
+        while let Some((_from, to)) = queue.pop_front() {
+            let count = received.entry(to).or_default();
+            *count = count.saturating_add(1);
+            if seen
+                .entry(to)
+                .or_insert_with(|| SeenMessages::new(16))
+                .insert(origin, b"block")
+            {
+                queue.extend(
+                    layer
+                        .relay_targets(b"block", origin, &to, validators)
+                        .into_iter()
+                        .map(|v| (to, v.pubkey)),
+                );
+            }
+        }
+        received
+    }
+
+    #[test]
+    fn test_direct_broadcast_sends_to_all() {
+        let validators = test_validators(10);
+        let origin = validators[3].pubkey;
+        let targets = DirectBroadcast.disseminate(b"block", &origin, &validators);
+        assert_eq!(targets.len(), 9);
+        assert!(targets.iter().all(|t| t.pubkey != origin));
+        // Receivers do not relay.
+        assert!(DirectBroadcast
+            .relay_targets(b"block", &origin, &validators[0].pubkey, &validators)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_epidemic_respects_fanout() {
+        let validators = test_validators(10);
+        let gossip = EpidemicGossip { fanout: 3 };
+        let origin = validators[0].pubkey;
+        let me = validators[1].pubkey;
+        let targets = gossip.relay_targets(b"block", &origin, &me, &validators);
+        assert_eq!(targets.len(), 3);
+        let unique: HashSet<_> = targets.iter().map(|v| v.pubkey).collect();
+        assert_eq!(unique.len(), 3);
+        assert!(!unique.contains(&origin) && !unique.contains(&me));
+
+        // Fewer candidates than the fanout: everyone is selected.
+        assert_eq!(
+            gossip
+                .relay_targets(b"block", &origin, &me, &validators[..4])
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_epidemic_fanout_3_reaches_all_peers() {
+        const MAX_ROUNDS: usize = 64;
+        let validators = test_validators(50);
+        let origin = validators[0].pubkey;
+        let gossip = EpidemicGossip { fanout: 3 };
+        let mut informed: HashSet<Pubkey> = HashSet::from([origin]);
+
+        // Every informed node re-gossips to `fanout` validators each round.
+        let mut rounds = 0;
+        while informed.len() < validators.len() && rounds < MAX_ROUNDS {
+            let senders: Vec<Pubkey> = informed.iter().copied().collect();
+            for sender in senders {
+                for target in gossip.relay_targets(b"block", &origin, &sender, &validators) {
+                    informed.insert(target.pubkey);
+                }
+            }
+            rounds += 1;
+        }
+        assert_eq!(
+            informed.len(),
+            validators.len(),
+            "only {} of {} validators informed after {rounds} rounds",
+            informed.len(),
+            validators.len()
+        );
+    }
+
+    #[test]
+    fn test_tree_reaches_every_validator_exactly_once() {
+        let validators = test_validators(40);
+        let gossip = TreeGossip {
+            tree: GossipTree::new(3),
+        };
+        for origin in [validators[0].pubkey, validators[39].pubkey] {
+            let received = simulate(&gossip, &origin, &validators);
+            assert_eq!(received.len(), 39);
+            assert!(!received.contains_key(&origin));
+            assert!(received.values().all(|&count| count == 1));
+        }
+    }
+
+    #[test]
+    fn test_tree_rooted_at_non_validator_origin() {
+        let validators = test_validators(12);
+        let gossip = TreeGossip {
+            tree: GossipTree::new(3),
+        };
+        let origin = Pubkey::new_unique();
+        let received = simulate(&gossip, &origin, &validators);
+        assert_eq!(received.len(), 12);
+        assert!(received.values().all(|&count| count == 1));
+    }
+
+    #[test]
+    fn test_tree_is_independent_of_local_view() {
+        let validators = test_validators(20);
+        let tree = GossipTree::new(4);
+        let origin = validators[5].pubkey;
+        let mut reversed = validators.clone();
+        reversed.reverse();
+        assert_eq!(
+            tree.order(&origin, &validators),
+            tree.order(&origin, &reversed)
+        );
+        assert!(tree
+            .order(&origin, &validators)
+            .windows(2)
+            .all(|w| w[0].stake_weight >= w[1].stake_weight));
+        for relayer in &validators {
+            assert_eq!(
+                tree.children(&origin, &relayer.pubkey, &validators),
+                tree.children(&origin, &relayer.pubkey, &reversed)
+            );
+        }
+        assert!(tree
+            .children(&origin, &Pubkey::new_unique(), &validators)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_tree_collapses_duplicate_pubkeys_with_different_stakes() {
+        let mut validators = test_validators(10);
+        let duplicate = ValidatorInfo {
+            stake_weight: 42,
+            ..validators[2].clone()
+        };
+        validators.push(duplicate);
+        let tree = GossipTree::new(3);
+        let origin = validators[0].pubkey;
+
+        let ordered = tree.order(&origin, &validators);
+        assert_eq!(ordered.len(), 9);
+        let entries: Vec<_> = ordered
+            .iter()
+            .filter(|v| v.pubkey == validators[2].pubkey)
+            .collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].stake_weight, validators[2].stake_weight);
+
+        let gossip = TreeGossip { tree };
+        let received = simulate(&gossip, &origin, &validators);
+        assert_eq!(received.len(), 9);
+        assert!(received.values().all(|&count| count == 1));
+    }
+
+    #[test]
+    fn test_seen_messages_dedup_and_eviction() {
+        let origin = Pubkey::new_unique();
+        let mut seen = SeenMessages::new(2);
+        assert!(seen.insert(&origin, b"a"));
+        assert!(!seen.insert(&origin, b"a"));
+        // Same payload from a different origin is a different message.
+        assert!(seen.insert(&Pubkey::new_unique(), b"a"));
+        assert_eq!(seen.len(), 2);
+        assert!(seen.insert(&origin, b"b"));
+        assert_eq!(seen.len(), 2);
+        assert!(!seen.contains(&origin, b"a"));
+        assert!(seen.insert(&origin, b"a"));
+    }
+
+    #[test]
+    fn test_config_builds_strategy() {
+        let validators = test_validators(10);
+        let me = validators[0].pubkey;
+        assert_eq!(
+            GossipLayerConfig::Direct
+                .build()
+                .disseminate(b"m", &me, &validators)
+                .len(),
+            9
+        );
+        assert_eq!(
+            GossipLayerConfig::Epidemic { fanout: 4 }
+                .build()
+                .disseminate(b"m", &me, &validators)
+                .len(),
+            4
+        );
+        assert_eq!(
+            GossipLayerConfig::Tree { fanout: 2 }
+                .build()
+                .disseminate(b"m", &me, &validators)
+                .len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,444 @@
+//! Connection handshake for the TCP transport.
+//!
+//! Every TCP connection opens with a challenge-response handshake before
+//! any [`ConsensusNetMessage`](crate::message::ConsensusNetMessage) is
+//! framed.  Each side sends a [`Handshake`] hello carrying a fresh random
+//! nonce, then proves it holds the private key of the validator identity it
+//! claims by signing the peer's nonce.  Each side checks that the other:
+//!
+//! 1. speaks a protocol version in its supported range,
+//! 2. is on the same chain (so validators on different testnets never talk
+//!    to each other by accident), and
+//! 3. holds the private key of the validator identity it claims.
+//!
+//! The exchange is:
+//!
+//! ```text
+//! connector                          acceptor
+//!     hello(nonce_c)          ->
+//!                             <-     hello(nonce_a), proof_a
+//!     proof_c                 ->
+//! ```
+//!
+//! The acceptor answers only a hello that passes checks 1 and 2, and the
+//! connector sends its proof only once the acceptor's checks out.  A
+//! connection that fails any check is closed.
+//!
+//! ## Wire format
+//!
+//! ```text
+//! hello: [4 bytes: protocol_version (u32-le)] [32 bytes: validator_pubkey]
+//!        [32 bytes: chain_id] [32 bytes: nonce]
+//! proof: [64 bytes: signature]
+//! ```
+//!
+//! A proof signs `HANDSHAKE_DOMAIN || role || connector hello || acceptor
+//! hello`.  Covering both hellos binds it to this session: it answers the
+//! peer's fresh nonce, so a proof recorded from an earlier connection is
+//! useless, and the role byte keeps one side's proof from being reflected
+//! back as the other's.
+
+use {
+    crate::{
+        config::ConsensusNetConfig,
+        error::{HandshakeError, Result},
+    },
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    std::sync::Arc,
+    tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+};
+
+/// Protocol version this node advertises.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Oldest protocol version this node can talk to.
+pub const MIN_SUPPORTED_VERSION: u32 = 1;
+
+/// Newest protocol version this node can talk to.
+pub const MAX_SUPPORTED_VERSION: u32 = PROTOCOL_VERSION;
+
+/// Size of an encoded [`Handshake`] hello.
+pub const HANDSHAKE_LEN: usize = 4 + 32 + 32 + 32;
+
+/// Size of an encoded proof.
+pub const PROOF_LEN: usize = 64;
+
+/// Domain separator prepended to every signed transcript.
+const HANDSHAKE_DOMAIN: &[u8] = b"trv1-consensus-net-handshake";
+
+/// Role byte of a proof signed by the connecting side.
+const CONNECTOR_ROLE: u8 = 0;
+
+/// Role byte of a proof signed by the accepting side.
+const ACCEPTOR_ROLE: u8 = 1;
+
+/// The hello each side sends first on every TCP connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Handshake {
+    /// Protocol version the sender speaks.
+    pub protocol_version: u32,
+    /// The validator identity the sender claims.
+    pub validator_pubkey: Pubkey,
+    /// Chain the sender is on (typically its genesis hash).
+    pub chain_id: [u8; 32],
+    /// Fresh random challenge the peer must sign.
+    pub nonce: [u8; 32],
+}
+
+impl Handshake {
+    /// Build a hello for `validator_pubkey` with a fresh random nonce.
+    pub fn new(validator_pubkey: Pubkey, chain_id: [u8; 32], protocol_version: u32) -> Self {
+        Self {
+            protocol_version,
+            validator_pubkey,
+            chain_id,
+            nonce: rand::random(),
+        }
+    }
+
+    /// Check a peer's hello against our chain and supported versions.
+    ///
+    /// Versions are accepted in `min_version..=MAX_SUPPORTED_VERSION`.  The
+    /// claimed identity is only proven by the peer's proof.
+    pub fn verify(
+        &self,
+        chain_id: &[u8; 32],
+        min_version: u32,
+    ) -> std::result::Result<(), HandshakeError> {
+        if !(min_version..=MAX_SUPPORTED_VERSION).contains(&self.protocol_version) {
+            return Err(HandshakeError::IncompatibleVersion {
+                their_version: self.protocol_version,
+                our_min: min_version,
+                our_max: MAX_SUPPORTED_VERSION,
+            });
+        }
+        if &self.chain_id != chain_id {
+            return Err(HandshakeError::ChainIdMismatch {
+                their_chain_id: self.chain_id,
+            });
+        }
+        Ok(())
+    }
+
+    /// Encode in the wire format described in the module docs.
+    pub fn encode(&self) -> [u8; HANDSHAKE_LEN] {
+        let mut bytes = [0u8; HANDSHAKE_LEN];
+        bytes[..4].copy_from_slice(&self.protocol_version.to_le_bytes());
+        bytes[4..36].copy_from_slice(self.validator_pubkey.as_ref());
+        bytes[36..68].copy_from_slice(&self.chain_id);
+        bytes[68..].copy_from_slice(&self.nonce);
+        bytes
+    }
+
+    /// Decode the wire format.  Any bytes of the right length decode, so
+    /// the result must still be [`verify`](Self::verify)-ed.
+    pub fn decode(bytes: &[u8; HANDSHAKE_LEN]) -> Self {
+        let mut version = [0u8; 4];
+        version.copy_from_slice(&bytes[..4]);
+        let mut pubkey = [0u8; 32];
+        pubkey.copy_from_slice(&bytes[4..36]);
+        let mut chain_id = [0u8; 32];
+        chain_id.copy_from_slice(&bytes[36..68]);
+        let mut nonce = [0u8; 32];
+        nonce.copy_from_slice(&bytes[68..]);
+        Self {
+            protocol_version: u32::from_le_bytes(version),
+            validator_pubkey: Pubkey::from(pubkey),
+            chain_id,
+            nonce,
+        }
+    }
+}
+
+/// The bytes a proof by `role` covers for the session opened by the
+/// `connector` and `acceptor` hellos.
+fn transcript(role: u8, connector: &Handshake, acceptor: &Handshake) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HANDSHAKE_DOMAIN.len() + 1 + 2 * HANDSHAKE_LEN);
+    bytes.extend_from_slice(HANDSHAKE_DOMAIN);
+    bytes.push(role);
+    bytes.extend_from_slice(&connector.encode());
+    bytes.extend_from_slice(&acceptor.encode());
+    bytes
+}
+
+/// Check `signer`'s proof for `role` in the session.
+fn verify_proof(
+    proof: &Signature,
+    signer: &Handshake,
+    role: u8,
+    connector: &Handshake,
+    acceptor: &Handshake,
+) -> std::result::Result<(), HandshakeError> {
+    let signed = transcript(role, connector, acceptor);
+    if !proof.verify(signer.validator_pubkey.as_ref(), &signed) {
+        return Err(HandshakeError::InvalidSignature(signer.validator_pubkey));
+    }
+    Ok(())
+}
+
+/// Runs the handshake for one node: holds its identity and the chain and
+/// versions it accepts from peers.
+#[derive(Debug, Clone)]
+pub struct Handshaker {
+    keypair: Arc<Keypair>,
+    chain_id: [u8; 32],
+    min_version: u32,
+}
+
+impl Handshaker {
+    /// Handshake as `keypair` on `config.chain_id`, accepting peers from
+    /// `config.min_protocol_version` up.
+    pub fn new(keypair: &Keypair, config: &ConsensusNetConfig) -> Self {
+        Self {
+            keypair: Arc::new(keypair.insecure_clone()),
+            chain_id: config.chain_id,
+            min_version: config.min_protocol_version.max(MIN_SUPPORTED_VERSION),
+        }
+    }
+
+    /// A fresh hello for this node.
+    pub fn hello(&self) -> Handshake {
+        Handshake::new(self.keypair.pubkey(), self.chain_id, PROTOCOL_VERSION)
+    }
+
+    /// Handshake as the connecting side: send our hello, check the peer's
+    /// hello and proof, then answer with our proof.  Returns the peer's
+    /// proven hello.
+    pub async fn initiate<S>(&self, stream: &mut S) -> Result<Handshake>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let local = self.hello();
+        stream.write_all(&local.encode()).await?;
+        stream.flush().await?;
+
+        let remote = self.read_hello(stream).await?;
+        let proof = read_proof(stream).await?;
+        verify_proof(&proof, &remote, ACCEPTOR_ROLE, &local, &remote)?;
+
+        let proof = self
+            .keypair
+            .sign_message(&transcript(CONNECTOR_ROLE, &local, &remote));
+        stream.write_all(proof.as_ref()).await?;
+        stream.flush().await?;
+        Ok(remote)
+    }
+
+    /// Handshake as the accepting side: check the peer's hello, answer with
+    /// our hello and proof, then check the peer's proof.  Nothing is sent to
+    /// a peer whose hello fails [`Handshake::verify`].  Returns the peer's
+    /// proven hello.
+    pub async fn accept<S>(&self, stream: &mut S) -> Result<Handshake>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let remote = self.read_hello(stream).await?;
+
+        let local = self.hello();
+        let proof = self
+            .keypair
+            .sign_message(&transcript(ACCEPTOR_ROLE, &remote, &local));
+        stream.write_all(&local.encode()).await?;
+        stream.write_all(proof.as_ref()).await?;
+        stream.flush().await?;
+
+        let proof = read_proof(stream).await?;
+        verify_proof(&proof, &remote, CONNECTOR_ROLE, &remote, &local)?;
+        Ok(remote)
+    }
+
+    async fn read_hello<S>(&self, stream: &mut S) -> Result<Handshake>
+    where
+        S: AsyncRead + Unpin,
+    {
+        let mut bytes = [0u8; HANDSHAKE_LEN];
+        stream.read_exact(&mut bytes).await?;
+        let remote = Handshake::decode(&bytes);
+        remote.verify(&self.chain_id, self.min_version)?;
+        Ok(remote)
+    }
+}
+
+async fn read_proof<S>(stream: &mut S) -> Result<Signature>
+where
+    S: AsyncRead + Unpin,
+{
+    let mut bytes = [0u8; PROOF_LEN];
+    stream.read_exact(&mut bytes).await?;
+    Ok(Signature::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::error::ConsensusNetError};
+
+    /// Enough buffer for a whole exchange in either direction.
+    const EXCHANGE_LEN: usize = (HANDSHAKE_LEN + PROOF_LEN) * 2;
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let handshake = Handshake::new(Pubkey::new_unique(), [7u8; 32], PROTOCOL_VERSION);
+        let decoded = Handshake::decode(&handshake.encode());
+        assert_eq!(decoded, handshake);
+        assert_eq!(decoded.verify(&[7u8; 32], MIN_SUPPORTED_VERSION), Ok(()));
+        // Every hello carries its own challenge.
+        assert_ne!(
+            Handshake::new(handshake.validator_pubkey, [7u8; 32], PROTOCOL_VERSION).nonce,
+            handshake.nonce
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_chain() {
+        let handshake = Handshake::new(Pubkey::new_unique(), [7u8; 32], PROTOCOL_VERSION);
+        assert_eq!(
+            handshake.verify(&[8u8; 32], MIN_SUPPORTED_VERSION),
+            Err(HandshakeError::ChainIdMismatch {
+                their_chain_id: [7u8; 32]
+            })
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_unsupported_versions() {
+        let pubkey = Pubkey::new_unique();
+        let too_new = Handshake::new(pubkey, [0u8; 32], MAX_SUPPORTED_VERSION + 1);
+        assert_eq!(
+            too_new.verify(&[0u8; 32], MIN_SUPPORTED_VERSION),
+            Err(HandshakeError::IncompatibleVersion {
+                their_version: MAX_SUPPORTED_VERSION + 1,
+                our_min: MIN_SUPPORTED_VERSION,
+                our_max: MAX_SUPPORTED_VERSION,
+            })
+        );
+
+        let too_old = Handshake::new(pubkey, [0u8; 32], MIN_SUPPORTED_VERSION - 1);
+        assert!(matches!(
+            too_old.verify(&[0u8; 32], MIN_SUPPORTED_VERSION),
+            Err(HandshakeError::IncompatibleVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_proof_is_bound_to_role_and_session() {
+        let keypair = Keypair::new();
+        let connector = Handshake::new(keypair.pubkey(), [7u8; 32], PROTOCOL_VERSION);
+        let acceptor = Handshake::new(Pubkey::new_unique(), [7u8; 32], PROTOCOL_VERSION);
+        let proof = keypair.sign_message(&transcript(CONNECTOR_ROLE, &connector, &acceptor));
+        assert_eq!(
+            verify_proof(&proof, &connector, CONNECTOR_ROLE, &connector, &acceptor),
+            Ok(())
+        );
+
+        let invalid = Err(HandshakeError::InvalidSignature(keypair.pubkey()));
+        assert_eq!(
+            verify_proof(&proof, &connector, ACCEPTOR_ROLE, &connector, &acceptor),
+            invalid
+        );
+        let later = Handshake::new(acceptor.validator_pubkey, [7u8; 32], PROTOCOL_VERSION);
+        assert_eq!(
+            verify_proof(&proof, &connector, CONNECTOR_ROLE, &connector, &later),
+            invalid
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handshaker_exchange() {
+        let config = ConsensusNetConfig::dev_default();
+        let (client_keypair, server_keypair) = (Keypair::new(), Keypair::new());
+        let client = Handshaker::new(&client_keypair, &config);
+        let server = Handshaker::new(&server_keypair, &config);
+
+        let (mut client_io, mut server_io) = tokio::io::duplex(EXCHANGE_LEN);
+        let (client_result, server_result) = tokio::join!(
+            client.initiate(&mut client_io),
+            server.accept(&mut server_io)
+        );
+        assert_eq!(
+            client_result.unwrap().validator_pubkey,
+            server_keypair.pubkey()
+        );
+        assert_eq!(
+            server_result.unwrap().validator_pubkey,
+            client_keypair.pubkey()
+        );
+
+        // A peer on another chain is rejected and gets no answer.
+        let stranger = Handshaker::new(
+            &client_keypair,
+            &ConsensusNetConfig {
+                chain_id: [1u8; 32],
+                ..config
+            },
+        );
+        let (mut client_io, mut server_io) = tokio::io::duplex(EXCHANGE_LEN);
+        let (client_result, server_result) = tokio::join!(stranger.initiate(&mut client_io), async {
+            let result = server.accept(&mut server_io).await;
+            drop(server_io);
+            result
+        });
+        assert!(matches!(
+            server_result,
+            Err(ConsensusNetError::Handshake(
+                HandshakeError::ChainIdMismatch { .. }
+            ))
+        ));
+        assert!(matches!(client_result, Err(ConsensusNetError::Transport(_))));
+    }
+
+    #[tokio::test]
+    async fn test_accept_rejects_identity_without_key() {
+        let config = ConsensusNetConfig::dev_default();
+        let victim = Keypair::new();
+        let impostor = Keypair::new();
+        let server = Handshaker::new(&Keypair::new(), &config);
+
+        // Claim the victim's identity but sign with another key.
+        let (mut client_io, mut server_io) = tokio::io::duplex(EXCHANGE_LEN);
+        let impersonate = async {
+            let hello = Handshake::new(victim.pubkey(), config.chain_id, PROTOCOL_VERSION);
+            client_io.write_all(&hello.encode()).await.unwrap();
+            let mut reply = [0u8; HANDSHAKE_LEN + PROOF_LEN];
+            client_io.read_exact(&mut reply).await.unwrap();
+            let mut acceptor = [0u8; HANDSHAKE_LEN];
+            acceptor.copy_from_slice(&reply[..HANDSHAKE_LEN]);
+            let acceptor = Handshake::decode(&acceptor);
+            let proof = impostor.sign_message(&transcript(CONNECTOR_ROLE, &hello, &acceptor));
+            client_io.write_all(proof.as_ref()).await.unwrap();
+        };
+        let (_, server_result) = tokio::join!(impersonate, server.accept(&mut server_io));
+        assert!(matches!(
+            server_result,
+            Err(ConsensusNetError::Handshake(HandshakeError::InvalidSignature(pubkey)))
+                if pubkey == victim.pubkey()
+        ));
+
+        // Replaying what the victim sent in a genuine session fails too: the
+        // server's new nonce is not the one the victim signed.
+        let (mut client_io, mut server_io) = tokio::io::duplex(EXCHANGE_LEN);
+        let genuine = async {
+            let hello = Handshake::new(victim.pubkey(), config.chain_id, PROTOCOL_VERSION);
+            client_io.write_all(&hello.encode()).await.unwrap();
+            let mut acceptor = [0u8; HANDSHAKE_LEN];
+            client_io.read_exact(&mut acceptor).await.unwrap();
+            read_proof(&mut client_io).await.unwrap();
+            let acceptor = Handshake::decode(&acceptor);
+            let proof = victim.sign_message(&transcript(CONNECTOR_ROLE, &hello, &acceptor));
+            client_io.write_all(proof.as_ref()).await.unwrap();
+            [hello.encode().as_slice(), proof.as_ref()].concat()
+        };
+        let (recorded, server_result) = tokio::join!(genuine, server.accept(&mut server_io));
+        assert_eq!(server_result.unwrap().validator_pubkey, victim.pubkey());
+
+        let (mut client_io, mut server_io) = tokio::io::duplex(EXCHANGE_LEN);
+        client_io.write_all(&recorded).await.unwrap();
+        assert!(matches!(
+            server.accept(&mut server_io).await,
+            Err(ConsensusNetError::Handshake(HandshakeError::InvalidSignature(pubkey)))
+                if pubkey == victim.pubkey()
+        ));
+    }
+}
@@ -6,11 +6,14 @@
 //!
 //! - **Consensus message propagation** — votes, certificates, and
 //!   validator-set updates are serialized with bincode and delivered over
-//!   length-prefixed TCP streams (QUIC upgrade path planned).
+//!   length-prefixed TCP streams or, by default, one QUIC stream per message,
+//!   zstd-compressed when both QUIC peers negotiate it.
 //! - **Block propagation** — the proposer broadcasts committed blocks to
-//!   all active validators; peers can also request blocks they missed.
+//!   all active validators, or seeds a fan-out gossip that validators relay
+//!   and acknowledge; peers can also request blocks they missed.
 //! - **Peer management** — connection tracking, heartbeats, liveness
-//!   detection, and epoch-boundary validator-set updates.
+//!   detection, reputation-based bans, and epoch-boundary validator-set
+//!   updates.
 //! - **Block sync** — a catch-up protocol that requests missing blocks
 //!   from peers with bounded concurrency and automatic retry.
 //!
@@ -35,7 +38,7 @@
 //!  └──────┬──────┘     └─────┬──────┘
 //!         │                   │
 //!  ┌──────▼───────────────────▼──────────────────────┐
-//!  │  Transport (TCP, length-prefixed frames)        │
+//!  │  Transport (TCP frames, or QUIC streams)        │
 //!  │  • TransportListener — accepts inbound          │
 //!  │  • send_message / broadcast_message — outbound  │
 //!  └─────────────────────────────────────────────────┘
@@ -45,16 +48,28 @@
 //!
 //! | Module | Purpose |
 //! |--------|---------|
+//! | [`address_book`] | Persisted peer addresses for rejoining after restart |
+//! | [`block_gossip`] | Fan-out block propagation with ACKs and direct fallback |
 //! | [`config`]       | `ConsensusNetConfig` defaults and dev overrides |
+//! | [`gossip`]       | Pluggable dissemination strategies (direct, epidemic, tree) |
+//! | [`handshake`]    | Versioned challenge-response TCP connection handshake |
 //! | [`message`]      | Wire types, bincode ser/de, framing helpers |
 //! | [`peer_manager`] | Peer lifecycle, liveness, validator-set tracking |
-//! | [`transport`]    | TCP listener, send/broadcast helpers |
+//! | [`rate_limit`]   | Per-connection token buckets for inbound messages |
+//! | [`transport`]    | TCP listener, send/broadcast helpers, backend selection |
+//! | [`transport_quic`] | QUIC endpoint with validator-authenticated TLS |
 //! | [`sync`]         | Block catch-up request/response protocol |
 //! | [`error`]        | Crate-wide error enum |
 
+pub mod address_book;
+pub mod block_gossip;
 pub mod config;
 pub mod error;
+pub mod gossip;
+pub mod handshake;
 pub mod message;
 pub mod peer_manager;
+pub mod rate_limit;
 pub mod sync;
 pub mod transport;
+pub mod transport_quic;
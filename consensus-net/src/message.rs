@@ -2,8 +2,8 @@
 //!
 //! All messages are serialized with bincode for compact wire representation.
 //! The [`ConsensusNetMessage`] enum is the top-level wire type — every byte
-//! sequence on the consensus channel is a length-prefixed bincode encoding
-//! of this enum.
+//! sequence on the consensus channel is a framed bincode encoding of this
+//! enum, optionally zstd-compressed.
 
 use {
     crate::error::{ConsensusNetError, Result},
@@ -12,9 +12,18 @@ use {
     solana_clock::Slot,
     solana_hash::Hash,
     solana_pubkey::Pubkey,
-    std::net::SocketAddr,
+    std::{io::Read, net::SocketAddr},
 };
 
+/// Size of the frame header: payload length followed by a flags byte.
+pub const FRAME_HEADER_LEN: usize = 5;
+
+/// zstd level used for compressed frames.
+pub const COMPRESSION_LEVEL: i32 = 3;
+
+/// Flags-byte bit marking a zstd-compressed payload.
+const FLAG_COMPRESSED: u8 = 0b0000_0001;
+
 // ── Peer and validator info ─────────────────────────────────────────────────
 
 /// Information about a peer on the consensus network.
@@ -72,7 +81,7 @@ pub struct BlockData {
 /// Top-level consensus network message.
 ///
 /// Every datagram / stream frame on the consensus channel carries exactly one
-/// of these variants, length-prefixed and bincode-encoded.
+/// of these variants, framed and bincode-encoded.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ConsensusNetMessage {
     /// A BFT consensus message (vote or certificate) from `votor`.
@@ -112,6 +121,9 @@ pub enum ConsensusNetMessage {
         pubkey: Pubkey,
         /// Sender's current highest committed slot.
         latest_slot: Slot,
+        /// A sample of peers the sender knows, for discovery; see
+        /// [`PeerManager::gossip_peers`](crate::peer_manager::PeerManager::gossip_peers).
+        known_peers: Vec<(Pubkey, SocketAddr)>,
     },
 
     /// Response to a heartbeat.
@@ -121,6 +133,16 @@ pub enum ConsensusNetMessage {
         /// Responder's current highest committed slot.
         latest_slot: Slot,
     },
+
+    /// Acknowledgement that the sender holds a gossiped block.
+    BlockAck {
+        /// Height of the block.
+        height: u64,
+        /// Hash of the block.
+        block_hash: Hash,
+        /// Sender identity.
+        pubkey: Pubkey,
+    },
 }
 
 // ── Serialisation helpers ───────────────────────────────────────────────────
@@ -136,10 +158,20 @@ impl ConsensusNetMessage {
         bincode::deserialize(data).map_err(ConsensusNetError::Serialization)
     }
 
-    /// Serialize with a 4-byte little-endian length prefix.
+    /// Serialize into an uncompressed frame.
     ///
-    /// Wire format: `[len: u32-le][payload: len bytes]`
+    /// Wire format: `[len: u32-le][flags: u8][payload: len bytes]`
     pub fn serialize_framed(&self, max_size: usize) -> Result<Vec<u8>> {
+        Ok(self.encode_frame(max_size, false)?.bytes)
+    }
+
+    /// Serialize into a frame, zstd-compressing the payload if `compress`
+    /// is set.
+    ///
+    /// `max_size` bounds the uncompressed payload, so that receivers can
+    /// bound decompression by the same limit.  A payload that does not
+    /// shrink under compression is sent uncompressed.
+    pub fn encode_frame(&self, max_size: usize, compress: bool) -> Result<EncodedFrame> {
         let payload = self.serialize()?;
         if payload.len() > max_size {
             return Err(ConsensusNetError::MessageTooLarge {
@@ -147,16 +179,53 @@ impl ConsensusNetMessage {
                 max: max_size,
             });
         }
-        let len = payload.len() as u32;
-        let mut buf = Vec::with_capacity(4usize.saturating_add(payload.len()));
-        buf.extend_from_slice(&len.to_le_bytes());
-        buf.extend_from_slice(&payload);
-        Ok(buf)
+        let uncompressed_len = payload.len();
+        let (payload, compressed) = if compress {
+            let encoded = zstd::encode_all(payload.as_slice(), COMPRESSION_LEVEL)?;
+            if encoded.len() < payload.len() {
+                (encoded, true)
+            } else {
+                (payload, false)
+            }
+        } else {
+            (payload, false)
+        };
+
+        let header = FrameHeader {
+            len: payload.len(),
+            compressed,
+        };
+        let mut bytes = Vec::with_capacity(FRAME_HEADER_LEN.saturating_add(payload.len()));
+        bytes.extend_from_slice(&header.encode()?);
+        bytes.extend_from_slice(&payload);
+        Ok(EncodedFrame {
+            bytes,
+            uncompressed_len,
+        })
     }
 
-    /// Read the length prefix from a 4-byte buffer.
-    pub fn read_frame_len(header: &[u8; 4]) -> usize {
-        u32::from_le_bytes(*header) as usize
+    /// Decode the payload that followed `header` on the wire.
+    ///
+    /// Decompression stops after `max_size` bytes, so a small compressed
+    /// frame cannot expand into an arbitrarily large allocation.
+    pub fn decode_payload(header: &FrameHeader, payload: &[u8], max_size: usize) -> Result<Self> {
+        if !header.compressed {
+            return Self::deserialize(payload);
+        }
+        let limit = u64::try_from(max_size)
+            .unwrap_or(u64::MAX)
+            .saturating_add(1);
+        let mut decoded = Vec::new();
+        zstd::stream::read::Decoder::new(payload)?
+            .take(limit)
+            .read_to_end(&mut decoded)?;
+        if decoded.len() > max_size {
+            return Err(ConsensusNetError::MessageTooLarge {
+                size: decoded.len(),
+                max: max_size,
+            });
+        }
+        Self::deserialize(&decoded)
     }
 
     /// Return a human-readable tag for logging.
@@ -171,6 +240,7 @@ impl ConsensusNetMessage {
             Self::ValidatorSetUpdate { .. } => "validator_set_update",
             Self::Heartbeat { .. } => "heartbeat",
             Self::HeartbeatAck { .. } => "heartbeat_ack",
+            Self::BlockAck { .. } => "block_ack",
         }
     }
 
@@ -185,6 +255,71 @@ impl ConsensusNetMessage {
     }
 }
 
+// ── Framing ─────────────────────────────────────────────────────────────────
+
+/// Header preceding every framed message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameHeader {
+    /// Length of the payload that follows, in bytes.
+    pub len: usize,
+    /// Whether the payload is zstd-compressed.
+    pub compressed: bool,
+}
+
+impl FrameHeader {
+    /// Encode as `[len: u32-le][flags: u8]`.
+    pub fn encode(&self) -> Result<[u8; FRAME_HEADER_LEN]> {
+        let len = u32::try_from(self.len).map_err(|_| ConsensusNetError::MessageTooLarge {
+            size: self.len,
+            max: u32::MAX as usize,
+        })?;
+        let mut bytes = [0u8; FRAME_HEADER_LEN];
+        bytes[..4].copy_from_slice(&len.to_le_bytes());
+        if self.compressed {
+            bytes[4] = FLAG_COMPRESSED;
+        }
+        Ok(bytes)
+    }
+
+    /// Decode a header, rejecting flag bits this version does not know.
+    pub fn decode(bytes: &[u8; FRAME_HEADER_LEN]) -> Result<Self> {
+        let flags = bytes[4];
+        if flags & !FLAG_COMPRESSED != 0 {
+            return Err(ConsensusNetError::InvalidMessage(format!(
+                "unknown frame flags {flags:#04x}"
+            )));
+        }
+        let len = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        Ok(Self {
+            len: len as usize,
+            compressed: flags & FLAG_COMPRESSED != 0,
+        })
+    }
+}
+
+/// A message serialized for the wire.
+#[derive(Debug, Clone)]
+pub struct EncodedFrame {
+    /// Header followed by the (possibly compressed) payload.
+    pub bytes: Vec<u8>,
+    /// Length of the bincode payload before compression.
+    pub uncompressed_len: usize,
+}
+
+impl EncodedFrame {
+    /// Length of the payload as sent, after any compression.
+    pub fn payload_len(&self) -> usize {
+        self.bytes.len().saturating_sub(FRAME_HEADER_LEN)
+    }
+
+    /// Whether the payload was compressed.
+    pub fn is_compressed(&self) -> bool {
+        self.bytes
+            .get(4)
+            .is_some_and(|flags| flags & FLAG_COMPRESSED != 0)
+    }
+}
+
 // ── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -196,19 +331,87 @@ mod tests {
         let msg = ConsensusNetMessage::Heartbeat {
             pubkey: Pubkey::new_unique(),
             latest_slot: 42,
+            known_peers: vec![(Pubkey::new_unique(), "10.0.0.1:8900".parse().unwrap())],
         };
         let bytes = msg.serialize().unwrap();
         let decoded = ConsensusNetMessage::deserialize(&bytes).unwrap();
         assert_eq!(msg, decoded);
     }
 
+    fn make_block(tx_count: usize) -> ConsensusNetMessage {
+        ConsensusNetMessage::BlockData(BlockData {
+            slot: 9,
+            parent_hash: Hash::new_unique(),
+            block_hash: Hash::new_unique(),
+            transactions: (0..tx_count).map(|i| vec![(i % 7) as u8; 256]).collect(),
+            state_root: Hash::new_unique(),
+            merkle_root: Hash::new_unique(),
+            merkle_proof: Vec::new(),
+            proposer: Pubkey::new_unique(),
+        })
+    }
+
+    fn decode_frame(framed: &[u8], max_size: usize) -> Result<ConsensusNetMessage> {
+        let header = FrameHeader::decode(framed[..FRAME_HEADER_LEN].try_into().unwrap())?;
+        assert_eq!(header.len, framed.len().saturating_sub(FRAME_HEADER_LEN));
+        ConsensusNetMessage::decode_payload(&header, &framed[FRAME_HEADER_LEN..], max_size)
+    }
+
     #[test]
     fn test_framed_roundtrip() {
         let msg = ConsensusNetMessage::BlockRequest { height: 100 };
         let framed = msg.serialize_framed(1_048_576).unwrap();
-        let len = ConsensusNetMessage::read_frame_len(framed[..4].try_into().unwrap());
-        let decoded = ConsensusNetMessage::deserialize(&framed[4..4usize.saturating_add(len)]).unwrap();
-        assert_eq!(msg, decoded);
+        assert_eq!(framed[4], 0);
+        assert_eq!(decode_frame(&framed, 1_048_576).unwrap(), msg);
+    }
+
+    #[test]
+    fn test_compressed_frame_roundtrip() {
+        let msg = make_block(64);
+        let frame = msg.encode_frame(1_048_576, true).unwrap();
+        assert!(frame.is_compressed());
+        assert!(frame.payload_len() < frame.uncompressed_len);
+        assert_eq!(frame.uncompressed_len, msg.serialize().unwrap().len());
+        assert_eq!(decode_frame(&frame.bytes, 1_048_576).unwrap(), msg);
+    }
+
+    #[test]
+    fn test_incompressible_payload_sent_uncompressed() {
+        // A random pubkey leaves zstd nothing to squeeze.
+        let msg = ConsensusNetMessage::Heartbeat {
+            pubkey: Pubkey::new_from_array(rand::random()),
+            latest_slot: 1,
+            known_peers: vec![],
+        };
+        let frame = msg.encode_frame(1_048_576, true).unwrap();
+        assert!(!frame.is_compressed());
+        assert_eq!(frame.payload_len(), frame.uncompressed_len);
+        assert_eq!(decode_frame(&frame.bytes, 1_048_576).unwrap(), msg);
+    }
+
+    #[test]
+    fn test_decompression_is_bounded() {
+        let msg = make_block(64);
+        let frame = msg.encode_frame(1_048_576, true).unwrap();
+        // The compressed payload is small, but it expands past the limit.
+        let limit = frame.payload_len().saturating_mul(2);
+        assert!(limit < frame.uncompressed_len);
+        assert!(matches!(
+            decode_frame(&frame.bytes, limit),
+            Err(ConsensusNetError::MessageTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unknown_frame_flags_rejected() {
+        let mut framed = ConsensusNetMessage::BlockRequest { height: 1 }
+            .serialize_framed(1_048_576)
+            .unwrap();
+        framed[4] = 0x80;
+        assert!(matches!(
+            decode_frame(&framed, 1_048_576),
+            Err(ConsensusNetError::InvalidMessage(_))
+        ));
     }
 
     #[test]
@@ -4,21 +4,130 @@
 //! state, and liveness metadata.  It is the single source of truth for
 //! "who are we talking to?" — the transport layer consults it before
 //! sending and updates it on every received message.
+//!
+//! ## Reputation
+//!
+//! Every peer carries a [`PeerReputation`].  Missed heartbeats, invalid
+//! messages and messages dropped by the transport's rate limiter lower its
+//! score; low latency raises it slightly.  A peer whose
+//! score falls below [`ConsensusNetConfig::min_reputation_threshold`] is
+//! disconnected, forgotten, and refused for
+//! [`ConsensusNetConfig::ban_duration_seconds`].
+//!
+//! ## Discovery
+//!
+//! Every peer address learned — from [`ConsensusNetConfig::bootstrap_peers`],
+//! from connections, and from the samples peers share in heartbeats — goes
+//! into an [`AddressBook`] that is loaded from
+//! [`ConsensusNetConfig::address_book_path`] on startup and saved back by
+//! [`PeerManager::save_address_book`].  While fewer than
+//! [`ConsensusNetConfig::min_peers`] peers are connected,
+//! [`PeerManager::reconnect_known_peers`] redials the book, most recently
+//! seen first.
 
 use {
     crate::{
+        address_book::AddressBook,
         config::ConsensusNetConfig,
         error::{ConsensusNetError, Result},
-        message::PeerInfo,
+        gossip::{GossipLayer, SeenMessages},
+        message::{PeerInfo, ValidatorInfo},
+        transport::{InvalidMessage, RateLimitedMessage},
     },
     log::{debug, info, warn},
     solana_pubkey::Pubkey,
     std::{
         collections::{HashMap, HashSet},
-        time::Instant,
+        io,
+        net::SocketAddr,
+        time::{Duration, Instant},
     },
 };
 
+/// Peers shared per heartbeat by [`PeerManager::gossip_peers`].
+pub const GOSSIP_PEERS_SAMPLE: usize = 20;
+
+/// Reputation of a peer with a clean record.
+pub const BASE_REPUTATION: f64 = 100.0;
+
+/// Reputation lost per consecutive missed heartbeat.
+pub const MISS_PENALTY: f64 = 5.0;
+
+/// Reputation lost per invalid message.
+pub const INVALID_PENALTY: f64 = 10.0;
+
+/// Reputation lost per message dropped by the rate limiter.  Small, so that
+/// a brief burst costs little while sustained flooding ends in a ban.
+pub const RATE_LIMIT_PENALTY: f64 = 0.01;
+
+/// Scale of the latency bonus.  Latencies below 1 ms earn no more than a
+/// 1 ms peer, so the bonus never exceeds this value.
+pub const LATENCY_BONUS: f64 = 5.0;
+
+/// Misbehaviour record from which a peer's score is computed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerReputation {
+    /// Heartbeats missed since the last one answered.
+    pub missed_heartbeats: u32,
+    /// Invalid messages received over the lifetime of the connection.
+    pub invalid_messages: u32,
+    /// Messages dropped by the rate limiter over the lifetime of the
+    /// connection.
+    pub rate_limited_messages: u32,
+    /// Exponentially-weighted moving average of round-trip latency (ms);
+    /// 0 until the first sample.
+    pub latency_ema_ms: f64,
+    /// Score as of the last update.
+    pub last_score: f64,
+}
+
+impl Default for PeerReputation {
+    fn default() -> Self {
+        Self {
+            missed_heartbeats: 0,
+            invalid_messages: 0,
+            rate_limited_messages: 0,
+            latency_ema_ms: 0.0,
+            last_score: BASE_REPUTATION,
+        }
+    }
+}
+
+impl PeerReputation {
+    /// Current score:
+    /// `BASE_REPUTATION - missed_heartbeats * MISS_PENALTY
+    /// - invalid_messages * INVALID_PENALTY - rate_limited_messages * RATE_LIMIT_PENALTY
+    /// + (1 / latency_ema_ms) * LATENCY_BONUS`.
+    pub fn score(&self) -> f64 {
+        let latency_bonus = if self.latency_ema_ms > 0.0 {
+            LATENCY_BONUS / self.latency_ema_ms.max(1.0)
+        } else {
+            0.0
+        };
+        BASE_REPUTATION
+            - f64::from(self.missed_heartbeats) * MISS_PENALTY
+            - f64::from(self.invalid_messages) * INVALID_PENALTY
+            - f64::from(self.rate_limited_messages) * RATE_LIMIT_PENALTY
+            + latency_bonus
+    }
+
+    /// Recompute the score, store it in `last_score`, and return it.
+    pub fn refresh(&mut self) -> f64 {
+        self.last_score = self.score();
+        self.last_score
+    }
+
+    /// Update the EWMA latency with a new sample.
+    pub fn update_latency(&mut self, sample_ms: f64) {
+        const ALPHA: f64 = 0.3;
+        if self.latency_ema_ms == 0.0 {
+            self.latency_ema_ms = sample_ms;
+        } else {
+            self.latency_ema_ms = ALPHA * sample_ms + (1.0 - ALPHA) * self.latency_ema_ms;
+        }
+    }
+}
+
 /// Per-peer connection bookkeeping.
 #[derive(Debug, Clone)]
 pub struct PeerConnection {
@@ -30,13 +139,10 @@ pub struct PeerConnection {
     pub messages_sent: u64,
     /// Counter: messages we have received *from* this peer.
     pub messages_received: u64,
-    /// Exponentially-weighted moving average of round-trip latency (ms).
-    pub latency_ms: f64,
     /// Whether we believe the connection is currently alive.
     pub is_connected: bool,
-    /// Peer score — higher is better. Starts at 100.
-    /// Future: decayed on misbehaviour, boosted on timely delivery.
-    pub score: f64,
+    /// Misbehaviour record and score — higher is better.
+    pub reputation: PeerReputation,
 }
 
 impl PeerConnection {
@@ -47,9 +153,8 @@ impl PeerConnection {
             last_seen: Instant::now(),
             messages_sent: 0,
             messages_received: 0,
-            latency_ms: 0.0,
             is_connected: false,
-            score: 100.0,
+            reputation: PeerReputation::default(),
         }
     }
 
@@ -64,22 +169,15 @@ impl PeerConnection {
         self.messages_sent = self.messages_sent.saturating_add(1);
     }
 
-    /// Update the EWMA latency with a new sample.
-    pub fn update_latency(&mut self, sample_ms: f64) {
-        const ALPHA: f64 = 0.3;
-        if self.latency_ms == 0.0 {
-            self.latency_ms = sample_ms;
-        } else {
-            self.latency_ms = ALPHA * sample_ms + (1.0 - ALPHA) * self.latency_ms;
-        }
-    }
-
     /// Returns how many seconds since we last heard from this peer.
     pub fn silence_secs(&self) -> u64 {
         self.last_seen.elapsed().as_secs()
     }
 }
 
+/// Number of recently gossiped messages remembered for duplicate suppression.
+pub const SEEN_MESSAGES_CAPACITY: usize = 4_096;
+
 /// Manages the set of peers on the consensus P2P network.
 ///
 /// Thread-safety note: `PeerManager` is designed to be used behind an
@@ -94,16 +192,58 @@ pub struct PeerManager {
     pub active_validators: HashSet<Pubkey>,
     /// Network configuration.
     pub config: ConsensusNetConfig,
+    /// Canonical validator set for the current epoch, as announced by
+    /// `ValidatorSetUpdate`.  Gossip overlays are computed over this list so
+    /// every validator agrees on who relays to whom.
+    pub validator_set: Vec<ValidatorInfo>,
+    /// Dissemination strategy, built from `config.gossip_layer`.
+    pub gossip: Box<dyn GossipLayer>,
+    /// Gossip messages already forwarded, keyed by origin and payload.
+    pub seen: SeenMessages,
+    /// Peers banned for low reputation, mapped to when their ban expires.
+    pub ban_list: HashMap<Pubkey, Instant>,
+    /// Every peer address known, including peers not currently tracked in
+    /// `peers`.
+    pub address_book: AddressBook,
 }
 
 impl PeerManager {
-    /// Create a new, empty peer manager.
+    /// Create a peer manager with no peers.
+    ///
+    /// The address book is loaded from `config.address_book_path`, if set
+    /// and present, and seeded with `config.bootstrap_peers`.
     pub fn new(config: ConsensusNetConfig) -> Self {
-        Self {
+        let address_book = match &config.address_book_path {
+            Some(path) => match AddressBook::load(path) {
+                Ok(book) => {
+                    info!(
+                        "loaded {} peers from address book {}",
+                        book.len(),
+                        path.display()
+                    );
+                    book
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => AddressBook::new(),
+                Err(e) => {
+                    warn!("ignoring unreadable address book {}: {}", path.display(), e);
+                    AddressBook::new()
+                }
+            },
+            None => AddressBook::new(),
+        };
+        let bootstrap_peers = config.bootstrap_peers.clone();
+        let mut manager = Self {
             peers: HashMap::new(),
             active_validators: HashSet::new(),
+            validator_set: Vec::new(),
+            gossip: config.gossip_layer.build(),
+            seen: SeenMessages::new(SEEN_MESSAGES_CAPACITY),
+            ban_list: HashMap::new(),
+            address_book,
             config,
-        }
+        };
+        manager.add_bootstrap_peers(&bootstrap_peers);
+        manager
     }
 
     /// Total number of known peers (connected or not).
@@ -120,12 +260,17 @@ impl PeerManager {
 
     /// Register a newly-discovered peer.
     ///
-    /// Returns `Err` if we are already at the connection limit.
-    /// If the peer is already known, its info is updated in place.
+    /// Returns `Err` if the peer is banned or we are already at the
+    /// connection limit.  If the peer is already known, its info is updated
+    /// in place.
     pub fn add_peer(&mut self, info: PeerInfo) -> Result<()> {
+        if self.is_banned(&info.pubkey) {
+            return Err(ConsensusNetError::PeerBanned(info.pubkey));
+        }
         if let Some(existing) = self.peers.get_mut(&info.pubkey) {
             // Update address / stake if the peer re-announced.
             debug!("updating existing peer {}", info.pubkey);
+            self.address_book.insert(info.pubkey, info.addr);
             existing.info = info;
             return Ok(());
         }
@@ -137,6 +282,7 @@ impl PeerManager {
         let pubkey = info.pubkey;
         let is_validator = info.is_active_validator;
         info!("adding peer {} (validator={})", pubkey, is_validator);
+        self.address_book.insert(pubkey, info.addr);
         self.peers.insert(pubkey, PeerConnection::new(info));
         if is_validator {
             self.active_validators.insert(pubkey);
@@ -154,12 +300,17 @@ impl PeerManager {
 
     /// Mark a peer as connected.
     pub fn mark_connected(&mut self, pubkey: &Pubkey) -> Result<()> {
+        if self.is_banned(pubkey) {
+            return Err(ConsensusNetError::PeerBanned(*pubkey));
+        }
         let conn = self
             .peers
             .get_mut(pubkey)
             .ok_or(ConsensusNetError::UnknownPeer(*pubkey))?;
         conn.is_connected = true;
         conn.last_seen = Instant::now();
+        self.address_book
+            .record_seen(*pubkey, conn.info.addr, conn.last_seen);
         Ok(())
     }
 
@@ -199,6 +350,273 @@ impl PeerManager {
         self.connected_validators().map(|(k, _)| *k).collect()
     }
 
+    /// Validators `me` should forward a gossiped `msg` to, as selected by
+    /// the configured [`GossipLayer`] over the canonical validator set.
+    ///
+    /// Call with `origin == me` to originate a message, and with the
+    /// sender's `origin` when one is received.  Returns `None` if the message
+    /// was already seen, in which case it must be neither delivered nor
+    /// forwarded again.
+    pub fn gossip_targets(
+        &mut self,
+        origin: &Pubkey,
+        me: &Pubkey,
+        msg: &[u8],
+    ) -> Option<Vec<ValidatorInfo>> {
+        if !self.seen.insert(origin, msg) {
+            debug!("dropping duplicate gossip from origin {}", origin);
+            return None;
+        }
+        Some(
+            self.gossip
+                .relay_targets(msg, origin, me, &self.validator_set),
+        )
+    }
+
+    // ── Reputation ──────────────────────────────────────────────────────
+
+    /// Current score of every known peer, for monitoring.
+    pub fn peer_scores(&self) -> HashMap<Pubkey, f64> {
+        self.peers
+            .iter()
+            .map(|(pubkey, conn)| (*pubkey, conn.reputation.last_score))
+            .collect()
+    }
+
+    /// Whether `pubkey` is serving an unexpired ban.
+    pub fn is_banned(&self, pubkey: &Pubkey) -> bool {
+        self.ban_list
+            .get(pubkey)
+            .is_some_and(|until| Instant::now() < *until)
+    }
+
+    /// Forget expired bans, returning the pubkeys released.
+    pub fn prune_expired_bans(&mut self) -> Vec<Pubkey> {
+        let now = Instant::now();
+        let expired: Vec<Pubkey> = self
+            .ban_list
+            .iter()
+            .filter(|(_, until)| now >= **until)
+            .map(|(pubkey, _)| *pubkey)
+            .collect();
+        for pubkey in &expired {
+            self.ban_list.remove(pubkey);
+        }
+        expired
+    }
+
+    /// Record an invalid message from `pubkey`.
+    ///
+    /// Returns `true` if this pushed the peer below the reputation threshold
+    /// and it was banned.
+    pub fn record_invalid_message(&mut self, pubkey: &Pubkey) -> bool {
+        let Some(conn) = self.peers.get_mut(pubkey) else {
+            return false;
+        };
+        conn.reputation.invalid_messages = conn.reputation.invalid_messages.saturating_add(1);
+        self.enforce_reputation(pubkey)
+    }
+
+    /// Record an invalid message reported by the transport.
+    ///
+    /// Only reports from authenticated connections name a peer; an
+    /// unauthenticated sender cannot be told apart from one spoofing
+    /// another validator's address, so such reports are not held against
+    /// anyone.  Returns `true` if the peer was banned.
+    pub fn record_invalid_report(&mut self, report: &InvalidMessage) -> bool {
+        match report.peer {
+            Some(pubkey) => {
+                debug!(
+                    "invalid message from {} ({}): {}",
+                    pubkey, report.from, report.reason
+                );
+                self.record_invalid_message(&pubkey)
+            }
+            None => false,
+        }
+    }
+
+    /// Record a message from `pubkey` dropped by the rate limiter.
+    ///
+    /// Returns `true` if this pushed the peer below the reputation threshold
+    /// and it was banned.
+    pub fn record_rate_limited(&mut self, pubkey: &Pubkey) -> bool {
+        let Some(conn) = self.peers.get_mut(pubkey) else {
+            return false;
+        };
+        conn.reputation.rate_limited_messages =
+            conn.reputation.rate_limited_messages.saturating_add(1);
+        self.enforce_reputation(pubkey)
+    }
+
+    /// Record a rate-limited message reported by the transport.
+    ///
+    /// As with [`Self::record_invalid_report`], only reports from
+    /// authenticated connections are held against a peer.  Returns `true`
+    /// if the peer was banned.
+    pub fn record_rate_limited_report(&mut self, report: &RateLimitedMessage) -> bool {
+        match report.peer {
+            Some(pubkey) => self.record_rate_limited(&pubkey),
+            None => false,
+        }
+    }
+
+    /// Record a heartbeat to `pubkey` that went unanswered.
+    ///
+    /// Returns `true` if the peer was banned.
+    pub fn record_missed_heartbeat(&mut self, pubkey: &Pubkey) -> bool {
+        let Some(conn) = self.peers.get_mut(pubkey) else {
+            return false;
+        };
+        conn.reputation.missed_heartbeats = conn.reputation.missed_heartbeats.saturating_add(1);
+        self.enforce_reputation(pubkey)
+    }
+
+    /// Record a heartbeat answered by `pubkey` after `rtt_ms`, clearing its
+    /// missed-heartbeat streak.
+    pub fn record_heartbeat_ack(&mut self, pubkey: &Pubkey, rtt_ms: f64) {
+        if let Some(conn) = self.peers.get_mut(pubkey) {
+            conn.record_received();
+            conn.reputation.missed_heartbeats = 0;
+            conn.reputation.update_latency(rtt_ms);
+            conn.reputation.refresh();
+        }
+    }
+
+    /// Refresh `pubkey`'s score and ban it if the score is below the
+    /// threshold.  Returns `true` if the peer was banned.
+    fn enforce_reputation(&mut self, pubkey: &Pubkey) -> bool {
+        let Some(conn) = self.peers.get_mut(pubkey) else {
+            return false;
+        };
+        let score = conn.reputation.refresh();
+        if score >= self.config.min_reputation_threshold {
+            return false;
+        }
+
+        let ban = Duration::from_secs(self.config.ban_duration_seconds);
+        let now = Instant::now();
+        let until = now.checked_add(ban).unwrap_or(now);
+        warn!(
+            "banning peer {} for {}s (reputation {:.1} < {:.1})",
+            pubkey, self.config.ban_duration_seconds, score, self.config.min_reputation_threshold
+        );
+        self.remove_peer(pubkey);
+        self.address_book.remove(pubkey);
+        self.ban_list.insert(*pubkey, until);
+        true
+    }
+
+    // ── Discovery ───────────────────────────────────────────────────────
+
+    /// Add `peers` to the address book, replacing any addresses already
+    /// known for them.  Banned peers are skipped.
+    pub fn add_bootstrap_peers(&mut self, peers: &[(Pubkey, SocketAddr)]) {
+        for (pubkey, addr) in peers {
+            if !self.is_banned(pubkey) {
+                self.address_book.insert(*pubkey, *addr);
+            }
+        }
+    }
+
+    /// Up to [`GOSSIP_PEERS_SAMPLE`] known peers chosen at random, to share
+    /// in a heartbeat's `known_peers`.
+    pub fn gossip_peers(&self) -> Vec<(Pubkey, SocketAddr)> {
+        self.address_book.sample(GOSSIP_PEERS_SAMPLE)
+    }
+
+    /// Learn peers from another node's heartbeat.
+    ///
+    /// Only peers we have no address for are added, so gossip cannot
+    /// redirect a known peer.  Returns how many were added.
+    pub fn record_gossiped_peers(&mut self, peers: &[(Pubkey, SocketAddr)]) -> usize {
+        let mut added = 0;
+        for (pubkey, addr) in peers.iter().take(GOSSIP_PEERS_SAMPLE) {
+            if !self.is_banned(pubkey) && self.address_book.insert_if_absent(*pubkey, *addr) {
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// If fewer than `config.min_peers` peers are connected, try `connect`
+    /// on every address-book entry not already connected, most recently
+    /// seen first.
+    ///
+    /// `connect` returns whether the connection succeeded; each peer it
+    /// succeeds for is added (if unknown) and marked connected.  Returns
+    /// the peers reconnected.
+    pub fn reconnect_known_peers(
+        &mut self,
+        mut connect: impl FnMut(&Pubkey, SocketAddr) -> bool,
+    ) -> Vec<Pubkey> {
+        if self.connected_count() >= self.config.min_peers {
+            return Vec::new();
+        }
+        let candidates: Vec<(Pubkey, SocketAddr)> = self
+            .address_book
+            .by_recency()
+            .into_iter()
+            .filter(|(pubkey, _)| {
+                !self.is_banned(pubkey)
+                    && !self.peers.get(pubkey).is_some_and(|conn| conn.is_connected)
+            })
+            .collect();
+        debug!(
+            "{} of {} minimum peers connected, redialling {} known peers",
+            self.connected_count(),
+            self.config.min_peers,
+            candidates.len()
+        );
+
+        let mut reconnected = Vec::new();
+        for (pubkey, addr) in candidates {
+            if !connect(&pubkey, addr) {
+                continue;
+            }
+            let info = PeerInfo {
+                pubkey,
+                addr,
+                stake_weight: self.peers.get(&pubkey).map_or(0, |c| c.info.stake_weight),
+                is_active_validator: self.active_validators.contains(&pubkey),
+            };
+            if let Err(e) = self
+                .add_peer(info)
+                .and_then(|()| self.mark_connected(&pubkey))
+            {
+                warn!("could not track reconnected peer {}: {}", pubkey, e);
+                continue;
+            }
+            reconnected.push(pubkey);
+        }
+        reconnected
+    }
+
+    /// Save the address book to `config.address_book_path`, first copying
+    /// in the address and last-seen time of every tracked peer.  Does
+    /// nothing if no path is configured.
+    pub fn save_address_book(&mut self) -> Result<()> {
+        for (pubkey, conn) in &self.peers {
+            self.address_book.insert(*pubkey, conn.info.addr);
+            self.address_book.last_seen.insert(*pubkey, conn.last_seen);
+        }
+        let Some(path) = &self.config.address_book_path else {
+            return Ok(());
+        };
+        self.address_book
+            .save(path)
+            .map_err(|source| ConsensusNetError::AddressBook {
+                path: path.clone(),
+                source,
+            })?;
+        info!(
+            "saved {} peers to address book {}",
+            self.address_book.len(),
+            path.display()
+        );
+        Ok(())
+    }
+
     // ── Validator set management ────────────────────────────────────────
 
     /// Replace the active validator set (called at epoch boundaries).
@@ -211,6 +629,14 @@ impl PeerManager {
         self.active_validators = validators;
     }
 
+    /// Replace the canonical validator set (called on `ValidatorSetUpdate`).
+    ///
+    /// Also resets `active_validators` to the pubkeys in `validators`.
+    pub fn update_validator_set(&mut self, validators: Vec<ValidatorInfo>) {
+        self.update_active_validators(validators.iter().map(|v| v.pubkey).collect());
+        self.validator_set = validators;
+    }
+
     // ── Liveness / garbage collection ───────────────────────────────────
 
     /// Evict peers that have been silent for longer than `peer_timeout_secs`.
@@ -241,7 +667,7 @@ impl PeerManager {
 mod tests {
     use {
         super::*,
-        crate::config::ConsensusNetConfig,
+        crate::{config::ConsensusNetConfig, gossip::GossipLayerConfig},
         std::net::SocketAddr,
     };
 
@@ -303,13 +729,330 @@ mod tests {
         let _ = pk2;
     }
 
+    fn test_validator_set(n: u8) -> Vec<ValidatorInfo> {
+        (1..=n)
+            .map(|i| ValidatorInfo {
+                pubkey: Pubkey::new_unique(),
+                stake_weight: 1_000_000,
+                consensus_addr: SocketAddr::from(([127, 0, 0, i], 8900)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_gossip_targets_use_canonical_set_and_suppress_duplicates() {
+        let validators = test_validator_set(10);
+        let origin = validators[0].pubkey;
+        let me = validators[1].pubkey;
+
+        let mut cfg = ConsensusNetConfig::dev_default();
+        cfg.gossip_layer = GossipLayerConfig::Tree { fanout: 3 };
+        let mut origin_pm = PeerManager::new(cfg.clone());
+        origin_pm.update_validator_set(validators.clone());
+        assert_eq!(origin_pm.active_validators.len(), 10);
+
+        // The origin's peer table is irrelevant: targets come from the
+        // validator set.
+        let sent = origin_pm
+            .gossip_targets(&origin, &origin, b"block")
+            .unwrap();
+        assert_eq!(sent.len(), 3);
+
+        // A receiver with a different (reversed) view computes the same tree.
+        let mut receiver_pm = PeerManager::new(cfg);
+        let mut reversed = validators.clone();
+        reversed.reverse();
+        receiver_pm.update_validator_set(reversed);
+        let relayed = receiver_pm.gossip_targets(&origin, &me, b"block").unwrap();
+        assert_eq!(
+            relayed,
+            origin_pm
+                .gossip
+                .relay_targets(b"block", &origin, &me, &validators)
+        );
+        assert!(relayed.iter().all(|v| v.pubkey != origin && v.pubkey != me));
+
+        // The second copy of the same message is dropped.
+        assert!(receiver_pm.gossip_targets(&origin, &me, b"block").is_none());
+        assert!(receiver_pm.gossip_targets(&origin, &me, b"other").is_some());
+    }
+
+    #[test]
+    fn test_direct_gossip_only_origin_sends() {
+        let validators = test_validator_set(5);
+        let mut pm = PeerManager::new(ConsensusNetConfig::dev_default());
+        pm.update_validator_set(validators.clone());
+        let me = validators[2].pubkey;
+        assert_eq!(pm.gossip_targets(&me, &me, b"block").unwrap().len(), 4);
+        assert!(pm
+            .gossip_targets(&validators[0].pubkey, &me, b"block")
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_latency_ewma() {
         let mut conn = PeerConnection::new(test_peer(1));
-        conn.update_latency(100.0);
-        assert!((conn.latency_ms - 100.0).abs() < f64::EPSILON);
-        conn.update_latency(200.0);
+        conn.reputation.update_latency(100.0);
+        assert!((conn.reputation.latency_ema_ms - 100.0).abs() < f64::EPSILON);
+        conn.reputation.update_latency(200.0);
         // 0.3 * 200 + 0.7 * 100 = 130
-        assert!((conn.latency_ms - 130.0).abs() < f64::EPSILON);
+        assert!((conn.reputation.latency_ema_ms - 130.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_reputation_score() {
+        let mut reputation = PeerReputation::default();
+        assert_eq!(reputation.score(), BASE_REPUTATION);
+
+        reputation.missed_heartbeats = 2;
+        reputation.invalid_messages = 1;
+        reputation.rate_limited_messages = 100;
+        reputation.latency_ema_ms = 10.0;
+        let expected =
+            BASE_REPUTATION - 2.0 * MISS_PENALTY - INVALID_PENALTY - 100.0 * RATE_LIMIT_PENALTY
+                + LATENCY_BONUS / 10.0;
+        assert!((reputation.refresh() - expected).abs() < 1e-9);
+        assert_eq!(reputation.last_score, reputation.score());
+
+        // Sub-millisecond latency earns no more than the full bonus.
+        reputation.latency_ema_ms = 0.01;
+        assert!(reputation.score() <= expected + LATENCY_BONUS);
+    }
+
+    #[test]
+    fn test_ten_invalid_messages_ban_peer() {
+        let mut pm = PeerManager::new(ConsensusNetConfig::dev_default());
+        let info = test_peer(1);
+        let pk = info.pubkey;
+        pm.add_peer(info).unwrap();
+        pm.mark_connected(&pk).unwrap();
+        pm.record_heartbeat_ack(&pk, 1.0);
+
+        for _ in 0..9 {
+            assert!(!pm.record_invalid_message(&pk));
+        }
+        assert!(pm.peer_scores()[&pk] >= pm.config.min_reputation_threshold);
+        assert!(pm.record_invalid_message(&pk));
+
+        assert!(pm.is_banned(&pk));
+        assert!(pm.get_peer(&pk).is_none());
+        assert!(!pm.active_validators.contains(&pk));
+        assert!(!pm.peer_scores().contains_key(&pk));
+    }
+
+    #[test]
+    fn test_banned_peer_cannot_reconnect_until_ban_expires() {
+        let mut pm = PeerManager::new(ConsensusNetConfig::dev_default());
+        let info = test_peer(1);
+        let pk = info.pubkey;
+        pm.add_peer(info.clone()).unwrap();
+        for _ in 0..10 {
+            pm.record_invalid_message(&pk);
+        }
+        assert!(pm.is_banned(&pk));
+        assert!(matches!(
+            pm.add_peer(info.clone()),
+            Err(ConsensusNetError::PeerBanned(banned)) if banned == pk
+        ));
+        assert!(matches!(
+            pm.mark_connected(&pk),
+            Err(ConsensusNetError::PeerBanned(_))
+        ));
+        assert!(pm.prune_expired_bans().is_empty());
+
+        // Let the ban run out.
+        pm.ban_list.insert(pk, Instant::now());
+        assert!(!pm.is_banned(&pk));
+        assert_eq!(pm.prune_expired_bans(), vec![pk]);
+
+        // The peer comes back with a clean record.
+        pm.add_peer(info).unwrap();
+        pm.mark_connected(&pk).unwrap();
+        assert_eq!(pm.peer_scores()[&pk], BASE_REPUTATION);
+    }
+
+    #[test]
+    fn test_missed_heartbeats_reset_on_ack() {
+        let mut pm = PeerManager::new(ConsensusNetConfig::dev_default());
+        let info = test_peer(1);
+        let pk = info.pubkey;
+        pm.add_peer(info).unwrap();
+
+        for _ in 0..5 {
+            assert!(!pm.record_missed_heartbeat(&pk));
+        }
+        assert_eq!(pm.peer_scores()[&pk], BASE_REPUTATION - 5.0 * MISS_PENALTY);
+        pm.record_heartbeat_ack(&pk, 50.0);
+        let reputation = &pm.get_peer(&pk).unwrap().reputation;
+        assert_eq!(reputation.missed_heartbeats, 0);
+        assert!(reputation.last_score > BASE_REPUTATION);
+    }
+
+    #[test]
+    fn test_unattributed_invalid_report_is_ignored() {
+        let mut pm = PeerManager::new(ConsensusNetConfig::dev_default());
+        let info = test_peer(1);
+        let pk = info.pubkey;
+        let addr = info.addr;
+        pm.add_peer(info).unwrap();
+
+        let anonymous = InvalidMessage {
+            from: addr,
+            peer: None,
+            reason: "garbage".to_string(),
+        };
+        assert!(!pm.record_invalid_report(&anonymous));
+        assert_eq!(pm.get_peer(&pk).unwrap().reputation.invalid_messages, 0);
+
+        let attributed = InvalidMessage {
+            peer: Some(pk),
+            ..anonymous
+        };
+        pm.record_invalid_report(&attributed);
+        assert_eq!(pm.get_peer(&pk).unwrap().reputation.invalid_messages, 1);
+    }
+
+    #[test]
+    fn test_sustained_rate_limiting_bans_peer() {
+        let mut pm = PeerManager::new(ConsensusNetConfig::dev_default());
+        let info = test_peer(1);
+        let pk = info.pubkey;
+        let addr = info.addr;
+        pm.add_peer(info).unwrap();
+
+        let anonymous = RateLimitedMessage {
+            from: addr,
+            peer: None,
+        };
+        assert!(!pm.record_rate_limited_report(&anonymous));
+        assert_eq!(
+            pm.get_peer(&pk).unwrap().reputation.rate_limited_messages,
+            0
+        );
+
+        let attributed = RateLimitedMessage {
+            peer: Some(pk),
+            ..anonymous
+        };
+        for _ in 0..100 {
+            assert!(!pm.record_rate_limited_report(&attributed));
+        }
+        assert!((pm.peer_scores()[&pk] - (BASE_REPUTATION - 1.0)).abs() < 1e-9);
+
+        let mut banned = false;
+        for _ in 0..10_000 {
+            if pm.record_rate_limited(&pk) {
+                banned = true;
+                break;
+            }
+        }
+        assert!(banned);
+        assert!(pm.is_banned(&pk));
+    }
+
+    #[test]
+    fn test_address_book_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let cfg = ConsensusNetConfig {
+            min_peers: 5,
+            address_book_path: Some(dir.path().join("address_book.json")),
+            ..ConsensusNetConfig::dev_default()
+        };
+
+        let mut pm = PeerManager::new(cfg.clone());
+        let peers: Vec<PeerInfo> = (1..=5).map(test_peer).collect();
+        for (i, info) in peers.iter().enumerate() {
+            pm.add_peer(info.clone()).unwrap();
+            pm.mark_connected(&info.pubkey).unwrap();
+            // Peer 1 was seen most recently, peer 5 longest ago.
+            pm.get_peer_mut(&info.pubkey).unwrap().last_seen =
+                Instant::now() - Duration::from_secs(10 * i as u64);
+        }
+        pm.save_address_book().unwrap();
+
+        let mut restarted = PeerManager::new(cfg);
+        assert_eq!(restarted.peer_count(), 0);
+        assert_eq!(restarted.address_book.len(), 5);
+
+        let mut attempts = Vec::new();
+        let reconnected = restarted.reconnect_known_peers(|pubkey, addr| {
+            attempts.push((*pubkey, addr));
+            true
+        });
+        let expected: Vec<_> = peers.iter().map(|p| (p.pubkey, p.addr)).collect();
+        assert_eq!(attempts, expected);
+        assert_eq!(reconnected.len(), 5);
+        assert_eq!(restarted.connected_count(), 5);
+
+        // At `min_peers`, nothing is redialled.
+        assert!(restarted
+            .reconnect_known_peers(|_, _| panic!("unexpected reconnect"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_reconnect_skips_connected_and_failed_peers() {
+        let bootstrap: Vec<_> = (1..=3)
+            .map(|n| {
+                (
+                    Pubkey::new_unique(),
+                    SocketAddr::from(([127, 0, 0, n], 8900)),
+                )
+            })
+            .collect();
+        let cfg = ConsensusNetConfig {
+            min_peers: 3,
+            bootstrap_peers: bootstrap.clone(),
+            ..ConsensusNetConfig::dev_default()
+        };
+        let mut pm = PeerManager::new(cfg);
+        assert_eq!(pm.address_book.len(), 3);
+
+        let (up, _) = bootstrap[0];
+        let mut attempted = 0;
+        let reconnected = pm.reconnect_known_peers(|pubkey, _| {
+            attempted += 1;
+            *pubkey == up
+        });
+        assert_eq!(attempted, 3);
+        assert_eq!(reconnected, vec![up]);
+
+        // Only the two still-disconnected peers are retried.
+        let mut retried = Vec::new();
+        pm.reconnect_known_peers(|pubkey, _| {
+            retried.push(*pubkey);
+            false
+        });
+        assert_eq!(retried.len(), 2);
+        assert!(!retried.contains(&up));
+    }
+
+    #[test]
+    fn test_gossip_peers_sample_and_learning() {
+        let mut pm = PeerManager::new(ConsensusNetConfig::dev_default());
+        let gossiped: Vec<_> = (0..30)
+            .map(|n| {
+                (
+                    Pubkey::new_unique(),
+                    SocketAddr::from(([10, 0, 0, n], 8900)),
+                )
+            })
+            .collect();
+        // Only the first `GOSSIP_PEERS_SAMPLE` of a heartbeat are taken.
+        assert_eq!(pm.record_gossiped_peers(&gossiped), GOSSIP_PEERS_SAMPLE);
+        assert_eq!(pm.record_gossiped_peers(&gossiped[..5]), 0);
+
+        let sample = pm.gossip_peers();
+        assert_eq!(sample.len(), GOSSIP_PEERS_SAMPLE);
+        let unique: HashSet<_> = sample.iter().map(|(pubkey, _)| *pubkey).collect();
+        assert_eq!(unique.len(), GOSSIP_PEERS_SAMPLE);
+
+        // Gossip cannot move a peer we already know.
+        let known = test_peer(1);
+        pm.add_peer(known.clone()).unwrap();
+        let spoofed = SocketAddr::from(([6, 6, 6, 6], 8900));
+        assert_eq!(pm.record_gossiped_peers(&[(known.pubkey, spoofed)]), 0);
+        assert_eq!(pm.address_book.peers[&known.pubkey], known.addr);
     }
 }
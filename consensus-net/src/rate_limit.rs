@@ -0,0 +1,200 @@
+//! Token-bucket rate limiting for inbound consensus messages.
+//!
+//! Each peer gets its own [`TokenBucket`]; a message that arrives when the
+//! bucket is empty is dropped before it is deserialized, so a flooding peer
+//! costs little more than the bytes it sends.  Buckets are kept in a
+//! [`PeerRateLimiter`] shared by all of a transport's connections and keyed
+//! by the peer's identity, or its IP address if it has not proved one, so
+//! reconnecting or opening more connections does not earn a peer a fresh
+//! bucket.
+
+use {
+    dashmap::DashMap,
+    solana_pubkey::Pubkey,
+    std::{
+        net::{IpAddr, SocketAddr},
+        time::Instant,
+    },
+};
+
+/// Tracked buckets above which full ones are forgotten.  A full bucket is
+/// no different from a new one, so dropping it forgives nothing.
+const PRUNE_THRESHOLD: usize = 4_096;
+
+/// A bucket holding up to `capacity` tokens, refilled continuously at
+/// `refill_per_second`.  Each admitted message takes one token.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_second: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// A full bucket of `burst` tokens refilling at `per_second`.
+    pub fn new(burst: u32, per_second: u32) -> Self {
+        Self {
+            capacity: f64::from(burst),
+            refill_per_second: f64::from(per_second),
+            tokens: f64::from(burst),
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take a token if one is available.
+    pub fn try_acquire(&mut self) -> bool {
+        self.try_acquire_at(Instant::now())
+    }
+
+    /// Tokens currently available, as of the last refill.
+    pub fn available(&self) -> f64 {
+        self.tokens
+    }
+
+    fn try_acquire_at(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whether the bucket has refilled to capacity by `now`.
+    fn is_full_at(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= self.capacity
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens =
+            (self.tokens + elapsed.as_secs_f64() * self.refill_per_second).min(self.capacity);
+        self.last_refill = now;
+    }
+}
+
+/// Who a [`PeerRateLimiter`] keeps a bucket for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    /// A peer that proved its validator identity.
+    Peer(Pubkey),
+    /// A sender that has not, by IP address.
+    Ip(IpAddr),
+}
+
+impl RateLimitKey {
+    /// Key a message from `addr`, sent by `peer` if it is authenticated.
+    pub fn new(peer: Option<Pubkey>, addr: SocketAddr) -> Self {
+        match peer {
+            Some(pubkey) => Self::Peer(pubkey),
+            None => Self::Ip(addr.ip()),
+        }
+    }
+}
+
+/// One [`TokenBucket`] per peer, shared by every connection of a transport
+/// so a peer's budget outlives its connections.
+#[derive(Debug)]
+pub struct PeerRateLimiter {
+    burst: u32,
+    per_second: u32,
+    buckets: DashMap<RateLimitKey, TokenBucket>,
+}
+
+impl PeerRateLimiter {
+    /// Give each peer a bucket of `burst` tokens refilling at `per_second`.
+    pub fn new(burst: u32, per_second: u32) -> Self {
+        Self {
+            burst,
+            per_second,
+            buckets: DashMap::new(),
+        }
+    }
+
+    /// Take a token from `key`'s bucket if one is available.
+    pub fn try_acquire(&self, key: RateLimitKey) -> bool {
+        self.try_acquire_at(key, Instant::now())
+    }
+
+    /// The number of peers with a bucket.
+    pub fn len(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Whether no peer has a bucket.
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    fn try_acquire_at(&self, key: RateLimitKey, now: Instant) -> bool {
+        if self.buckets.len() >= PRUNE_THRESHOLD && !self.buckets.contains_key(&key) {
+            self.buckets.retain(|_, bucket| !bucket.is_full_at(now));
+        }
+        self.buckets
+            .entry(key)
+            .or_insert_with(|| TokenBucket::new(self.burst, self.per_second))
+            .try_acquire_at(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::time::Duration};
+
+    #[test]
+    fn test_burst_then_refill() {
+        let mut bucket = TokenBucket::new(5, 2);
+        let start = bucket.last_refill;
+        for _ in 0..5 {
+            assert!(bucket.try_acquire_at(start));
+        }
+        assert!(!bucket.try_acquire_at(start));
+
+        // Two tokens per second: one token after half a second.
+        let later = start + Duration::from_millis(500);
+        assert!(bucket.try_acquire_at(later));
+        assert!(!bucket.try_acquire_at(later));
+    }
+
+    #[test]
+    fn test_refill_capped_at_burst() {
+        let mut bucket = TokenBucket::new(3, 100);
+        let start = bucket.last_refill;
+        assert!(bucket.try_acquire_at(start));
+
+        let much_later = start + Duration::from_secs(60);
+        let admitted = (0..10)
+            .filter(|_| bucket.try_acquire_at(much_later))
+            .count();
+        assert_eq!(admitted, 3);
+    }
+
+    #[test]
+    fn test_peer_buckets_are_independent_and_pruned_when_full() {
+        let limiter = PeerRateLimiter::new(2, 1);
+        let start = Instant::now();
+        let peer = RateLimitKey::new(Some(Pubkey::new_unique()), "10.0.0.1:1".parse().unwrap());
+        assert!(limiter.try_acquire_at(peer, start));
+        assert!(limiter.try_acquire_at(peer, start));
+        assert!(!limiter.try_acquire_at(peer, start));
+
+        // Another port on the same host shares the unauthenticated bucket.
+        let host = RateLimitKey::new(None, "10.0.0.2:1".parse().unwrap());
+        assert_eq!(host, RateLimitKey::new(None, "10.0.0.2:2".parse().unwrap()));
+        assert!(limiter.try_acquire_at(host, start));
+        assert_eq!(limiter.len(), 2);
+
+        // Only buckets that have refilled are forgotten when pruning.
+        for _ in 0..PRUNE_THRESHOLD {
+            limiter.try_acquire_at(RateLimitKey::Peer(Pubkey::new_unique()), start);
+        }
+        let later = start + Duration::from_millis(1_500);
+        limiter.try_acquire_at(RateLimitKey::Peer(Pubkey::new_unique()), later);
+        assert_eq!(limiter.len(), 2);
+        assert!(limiter.try_acquire_at(peer, later));
+        assert!(!limiter.try_acquire_at(peer, later));
+    }
+}
@@ -12,26 +12,129 @@
 //!    pending requests and handed to the ledger layer for validation and
 //!    replay.
 //! 4. **Retry / failover** — if a response doesn't arrive within the
-//!    configured timeout the request is retried against a different peer.
+//!    configured timeout the height is re-queued and retried against a
+//!    (possibly) different peer after an exponential backoff.
+//!
+//! 5. **Checkpoint verification** — blocks at checkpoint heights are
+//!    compared with the [`SyncCheckpoint`]s validators publish on chain.  A
+//!    mismatch penalises and disconnects the peer that served the block, and
+//!    every block downloaded since the last verified checkpoint is discarded
+//!    and re-requested from other peers.
+//!
+//! A height has at most one request in flight at a time: asking for it again
+//! while a request is outstanding or waiting out its backoff is skipped.
 
 use {
     crate::{
         config::ConsensusNetConfig,
         error::{ConsensusNetError, Result},
-        message::{BlockData, ConsensusNetMessage},
+        message::{BlockData, ConsensusNetMessage, ValidatorInfo},
         peer_manager::PeerManager,
-        transport,
+        transport::Transport,
     },
     log::{debug, info, warn},
+    solana_hash::Hash,
+    solana_keypair::Keypair,
     solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    solana_signer::Signer,
     std::{
         collections::{HashMap, HashSet},
         net::SocketAddr,
         sync::{Arc, Mutex},
-        time::Instant,
+        time::{Duration, Instant},
     },
+    trv1_monitoring::TRv1Metrics,
 };
 
+/// Retries of a timed-out block request before the height is given up.
+pub const MAX_SYNC_RETRIES: u32 = 5;
+
+/// Wait before the first retry of a timed-out block request; each further
+/// retry doubles it.
+pub const SYNC_RETRY_BASE_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Upper bound on the wait between retries.
+pub const SYNC_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(16);
+
+/// Blocks between sync checkpoints; checkpoints are published at heights
+/// that are multiples of this.
+pub const CHECKPOINT_INTERVAL: u64 = 10;
+
+/// The hash of the block at a checkpoint height, signed by validators.
+///
+/// Validators publish one to a well-known on-chain account every
+/// [`CHECKPOINT_INTERVAL`] blocks.  A syncing node checks the blocks it
+/// downloads against them, so a peer cannot feed it blocks that are well
+/// formed but not part of the chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncCheckpoint {
+    /// Checkpoint height.
+    pub height: u64,
+    /// Hash of the committed block at `height`.
+    pub block_hash: Hash,
+    /// Validators that signed `(height, block_hash)`, with their signatures.
+    pub commit_signatures: Vec<(Pubkey, Signature)>,
+}
+
+impl SyncCheckpoint {
+    /// An unsigned checkpoint.
+    pub fn new(height: u64, block_hash: Hash) -> Self {
+        Self {
+            height,
+            block_hash,
+            commit_signatures: Vec::new(),
+        }
+    }
+
+    /// Add `keypair`'s signature.
+    pub fn sign(&mut self, keypair: &Keypair) {
+        let signature = keypair.sign_message(&self.signable_bytes());
+        self.commit_signatures.push((keypair.pubkey(), signature));
+    }
+
+    /// The bytes each commit signature covers: `height (u64-le) || block_hash`.
+    pub fn signable_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + 32);
+        bytes.extend_from_slice(&self.height.to_le_bytes());
+        bytes.extend_from_slice(self.block_hash.as_ref());
+        bytes
+    }
+
+    /// Whether members of `validators` holding more than two thirds of its
+    /// stake validly signed this checkpoint.
+    pub fn is_confirmed_by(&self, validators: &[ValidatorInfo]) -> bool {
+        let stakes: HashMap<Pubkey, u64> = validators
+            .iter()
+            .map(|v| (v.pubkey, v.stake_weight))
+            .collect();
+        let total_stake: u128 = stakes.values().map(|&stake| u128::from(stake)).sum();
+        if total_stake == 0 {
+            return false;
+        }
+
+        let signed = self.signable_bytes();
+        let mut signers = HashSet::new();
+        let mut signed_stake = 0u128;
+        for (pubkey, signature) in &self.commit_signatures {
+            let Some(&stake) = stakes.get(pubkey) else {
+                continue;
+            };
+            if signers.insert(*pubkey) && signature.verify(pubkey.as_ref(), &signed) {
+                signed_stake = signed_stake.saturating_add(u128::from(stake));
+            }
+        }
+        signed_stake.saturating_mul(3) > total_stake.saturating_mul(2)
+    }
+}
+
+/// Where a [`BlockSyncer`] gets sync checkpoints from, typically by reading
+/// the on-chain checkpoint account.
+pub trait CheckpointSource: Send + Sync {
+    /// The checkpoint published for `height`, if there is one.
+    fn checkpoint(&self, height: u64) -> Option<SyncCheckpoint>;
+}
+
 /// Tracks an outstanding block-sync request.
 #[derive(Debug, Clone)]
 pub struct PendingSyncRequest {
@@ -47,35 +150,92 @@ pub struct PendingSyncRequest {
     pub attempts: u32,
 }
 
+/// A timed-out height waiting out its backoff before the next request.
+#[derive(Debug, Clone, Copy)]
+struct QueuedRetry {
+    /// Requests already sent for this height.
+    attempts: u32,
+    /// Earliest time the next request may be sent.
+    not_before: Instant,
+}
+
 /// Orchestrates block catch-up across the peer set.
 pub struct BlockSyncer {
     /// Shared peer manager — used to pick targets.
     peer_manager: Arc<Mutex<PeerManager>>,
     /// Config (timeouts, concurrency).
     config: ConsensusNetConfig,
-    /// Heights we are currently waiting for a response on.
+    /// The transport requests are sent over.
+    transport: Arc<Transport>,
+    /// Heights with a request outstanding; checked before every dispatch so
+    /// a height is never requested twice at once.
+    in_flight_requests: HashSet<u64>,
+    /// Details of each in-flight request, keyed by height.
     pending: HashMap<u64, PendingSyncRequest>,
+    /// Timed-out heights waiting to be retried.
+    retry_queue: HashMap<u64, QueuedRetry>,
     /// Heights we have already received and don't need to request again.
     completed: HashSet<u64>,
-    /// Maximum retry attempts per height before giving up.
+    /// Maximum retries per height before giving up.
     max_retries: u32,
+    /// Where to count skipped duplicates, retries and checkpoint failures,
+    /// if anywhere.
+    metrics: Option<Arc<TRv1Metrics>>,
+    /// Checkpoints to verify synced blocks against, if any.
+    checkpoints: Option<Arc<dyn CheckpointSource>>,
+    /// Highest checkpoint height whose block has been verified.
+    last_verified_checkpoint: u64,
 }
 
 impl BlockSyncer {
-    /// Create a new syncer.
-    pub fn new(peer_manager: Arc<Mutex<PeerManager>>, config: ConsensusNetConfig) -> Self {
+    /// Create a new syncer that sends its requests over `transport`, the
+    /// backend selected by [`ConsensusNetConfig::transport`].
+    pub fn new(
+        peer_manager: Arc<Mutex<PeerManager>>,
+        config: ConsensusNetConfig,
+        transport: Arc<Transport>,
+    ) -> Self {
         Self {
             peer_manager,
             config,
+            transport,
+            in_flight_requests: HashSet::new(),
             pending: HashMap::new(),
+            retry_queue: HashMap::new(),
             completed: HashSet::new(),
-            max_retries: 5,
+            max_retries: MAX_SYNC_RETRIES,
+            metrics: None,
+            checkpoints: None,
+            last_verified_checkpoint: 0,
         }
     }
 
+    /// Count avoided duplicate requests, retries and checkpoint failures in
+    /// `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<TRv1Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Verify blocks at checkpoint heights against `checkpoints`.
+    pub fn with_checkpoint_source(mut self, checkpoints: Arc<dyn CheckpointSource>) -> Self {
+        self.checkpoints = Some(checkpoints);
+        self
+    }
+
+    /// Highest checkpoint height verified so far; 0 if none.
+    pub fn last_verified_checkpoint(&self) -> u64 {
+        self.last_verified_checkpoint
+    }
+
     /// The number of requests currently in flight.
     pub fn in_flight(&self) -> usize {
-        self.pending.len()
+        self.in_flight_requests.len()
+    }
+
+    /// The number of timed-out heights waiting to be retried.
+    pub fn queued_retries(&self) -> usize {
+        self.retry_queue.len()
     }
 
     /// Whether a height is already completed (no need to request).
@@ -85,22 +245,28 @@ impl BlockSyncer {
 
     /// Request a range of missing blocks.
     ///
-    /// Skips heights that are already pending or completed.  Returns the
-    /// number of new requests actually dispatched.
+    /// Skips heights that are completed, in flight, or queued for retry.
+    /// Returns the number of new requests actually dispatched.
     pub async fn request_range(&mut self, from: u64, to: u64) -> usize {
         let mut dispatched = 0usize;
         for height in from..=to {
-            if self.completed.contains(&height) || self.pending.contains_key(&height) {
+            if self.completed.contains(&height) {
                 continue;
             }
-            if self.pending.len() >= self.config.max_sync_requests {
+            if self.in_flight_requests.contains(&height) || self.retry_queue.contains_key(&height) {
+                if let Some(metrics) = &self.metrics {
+                    metrics.duplicate_requests_avoided.inc();
+                }
+                continue;
+            }
+            if self.in_flight_requests.len() >= self.config.max_sync_requests {
                 debug!(
                     "sync concurrency limit reached ({}), deferring height {}",
                     self.config.max_sync_requests, height
                 );
                 break;
             }
-            if self.dispatch_request(height).await.is_ok() {
+            if self.dispatch_request(height, 1).await.is_ok() {
                 dispatched = dispatched.saturating_add(1);
             }
         }
@@ -110,13 +276,15 @@ impl BlockSyncer {
         dispatched
     }
 
-    /// Pick a peer and send a `BlockRequest` for the given height.
-    async fn dispatch_request(&mut self, height: u64) -> Result<()> {
+    /// Pick a peer and send a `BlockRequest` for the given height, as its
+    /// `attempts`-th request.
+    async fn dispatch_request(&mut self, height: u64, attempts: u32) -> Result<()> {
         let (peer, addr) = self.pick_peer()?;
 
         let msg = ConsensusNetMessage::BlockRequest { height };
-        transport::send_message(addr, &msg, self.config.max_message_size).await?;
+        self.transport.send_message(addr, &msg).await?;
 
+        self.in_flight_requests.insert(height);
         self.pending.insert(
             height,
             PendingSyncRequest {
@@ -124,88 +292,180 @@ impl BlockSyncer {
                 peer,
                 addr,
                 sent_at: Instant::now(),
-                attempts: 1,
+                attempts,
             },
         );
 
-        debug!("requested block {} from {} ({})", height, peer, addr);
+        debug!(
+            "requested block {} from {} ({}, attempt {})",
+            height, peer, addr, attempts
+        );
         Ok(())
     }
 
     /// Called when a `BlockResponse` is received.
     ///
-    /// Returns `Some(BlockData)` if the response matches a pending request,
-    /// `None` if it was unsolicited.
-    pub fn handle_response(&mut self, height: u64, block: BlockData) -> Option<BlockData> {
-        if let Some(_req) = self.pending.remove(&height) {
-            self.completed.insert(height);
-            debug!("received block {} — sync complete for height", height);
-            Some(block)
-        } else {
+    /// Returns `Some(BlockData)` if the response matches a request that is
+    /// in flight or queued for retry, `None` if it was unsolicited.
+    ///
+    /// Fails with [`ConsensusNetError::CheckpointMismatch`] if the block
+    /// contradicts a sync checkpoint.  The caller must then discard every
+    /// block it was handed from `rollback_from` on; the syncer has already
+    /// queued those heights to be fetched again.
+    pub fn handle_response(&mut self, height: u64, block: BlockData) -> Result<Option<BlockData>> {
+        let in_flight = self.in_flight_requests.remove(&height);
+        let queued = self.retry_queue.remove(&height).is_some();
+        if !in_flight && !queued {
             warn!("unsolicited block response for height {}", height);
-            None
+            return Ok(None);
+        }
+        let peer = self.pending.remove(&height).map(|request| request.peer);
+        self.verify_checkpoint(height, &block, peer)?;
+        self.completed.insert(height);
+        debug!("received block {} — sync complete for height", height);
+        Ok(Some(block))
+    }
+
+    /// Check a block at a checkpoint height against the published
+    /// checkpoint, rolling back to the last verified checkpoint on a
+    /// mismatch.
+    ///
+    /// Checkpoints not confirmed by the current validator set are ignored.
+    fn verify_checkpoint(
+        &mut self,
+        height: u64,
+        block: &BlockData,
+        peer: Option<Pubkey>,
+    ) -> Result<()> {
+        if !height.is_multiple_of(CHECKPOINT_INTERVAL) {
+            return Ok(());
+        }
+        let Some(checkpoint) = self
+            .checkpoints
+            .as_ref()
+            .and_then(|source| source.checkpoint(height))
+        else {
+            return Ok(());
+        };
+        let mut pm = self
+            .peer_manager
+            .lock()
+            .map_err(|_| ConsensusNetError::ChannelClosed)?;
+        if !checkpoint.is_confirmed_by(&pm.validator_set) {
+            warn!("ignoring unconfirmed sync checkpoint at height {}", height);
+            return Ok(());
+        }
+        if checkpoint.block_hash == block.block_hash {
+            debug!("block {} matches its sync checkpoint", height);
+            self.last_verified_checkpoint = self.last_verified_checkpoint.max(height);
+            return Ok(());
+        }
+
+        if let Some(metrics) = &self.metrics {
+            metrics.sync_checkpoint_failures.inc();
+        }
+        if let Some(peer) = peer {
+            pm.record_invalid_message(&peer);
+            pm.mark_disconnected(&peer);
         }
+        drop(pm);
+
+        let rollback_from = self.last_verified_checkpoint.saturating_add(1);
+        let mut discarded: Vec<u64> = self
+            .completed
+            .iter()
+            .copied()
+            .filter(|&h| h >= rollback_from)
+            .collect();
+        discarded.push(height);
+        let now = Instant::now();
+        for h in &discarded {
+            self.completed.remove(h);
+            self.retry_queue.insert(
+                *h,
+                QueuedRetry {
+                    attempts: 0,
+                    not_before: now,
+                },
+            );
+        }
+        warn!(
+            "block {} from {:?} does not match its sync checkpoint; re-syncing {} blocks from {}",
+            height,
+            peer,
+            discarded.len(),
+            rollback_from
+        );
+        Err(ConsensusNetError::CheckpointMismatch {
+            height,
+            rollback_from,
+        })
     }
 
-    /// Check for timed-out requests and retry them against different peers.
+    /// Called when the request for `height` gets no response in time.
+    ///
+    /// Removes the height from the in-flight set and re-queues it, to be
+    /// retried by [`Self::retry_timed_out`] after an exponential backoff:
+    /// [`SYNC_RETRY_BASE_BACKOFF`] before the first retry, doubling up to
+    /// [`SYNC_RETRY_MAX_BACKOFF`].  Returns `false` if the height was not in
+    /// flight or has used up its [`MAX_SYNC_RETRIES`] retries.
+    pub fn on_response_timeout(&mut self, height: u64) -> bool {
+        if !self.in_flight_requests.remove(&height) {
+            return false;
+        }
+        let attempts = self
+            .pending
+            .remove(&height)
+            .map_or(1, |request| request.attempts);
+        self.requeue(height, attempts)
+    }
+
+    /// Time out overdue requests and re-send queued heights whose backoff
+    /// has elapsed.
     ///
     /// Returns heights that have permanently failed (exceeded `max_retries`).
     pub async fn retry_timed_out(&mut self) -> Vec<u64> {
         let timeout_ms = self.config.message_timeout_ms;
-        let timed_out: Vec<PendingSyncRequest> = self
+        let timed_out: Vec<u64> = self
             .pending
             .values()
             .filter(|r| r.sent_at.elapsed().as_millis() as u64 > timeout_ms)
-            .cloned()
+            .map(|r| r.height)
             .collect();
 
         let mut permanently_failed = Vec::new();
+        for height in timed_out {
+            if !self.on_response_timeout(height) {
+                permanently_failed.push(height);
+            }
+        }
 
-        for req in timed_out {
-            self.pending.remove(&req.height);
+        let now = Instant::now();
+        let due: Vec<(u64, u32)> = self
+            .retry_queue
+            .iter()
+            .filter(|(_, retry)| retry.not_before <= now)
+            .map(|(&height, retry)| (height, retry.attempts))
+            .collect();
 
-            if req.attempts >= self.max_retries {
-                warn!(
-                    "giving up on block {} after {} attempts",
-                    req.height, req.attempts
-                );
-                permanently_failed.push(req.height);
-                continue;
+        for (height, attempts) in due {
+            if self.in_flight_requests.len() >= self.config.max_sync_requests {
+                break;
             }
-
-            // Retry with a (possibly different) peer.
-            match self.pick_peer() {
-                Ok((peer, addr)) => {
-                    let msg = ConsensusNetMessage::BlockRequest {
-                        height: req.height,
-                    };
-                    if transport::send_message(addr, &msg, self.config.max_message_size)
-                        .await
-                        .is_ok()
-                    {
-                        self.pending.insert(
-                            req.height,
-                            PendingSyncRequest {
-                                height: req.height,
-                                peer,
-                                addr,
-                                sent_at: Instant::now(),
-                                attempts: req.attempts.saturating_add(1),
-                            },
-                        );
-                        debug!(
-                            "retried block {} with peer {} (attempt {})",
-                            req.height,
-                            peer,
-                            req.attempts.saturating_add(1)
-                        );
-                    } else {
-                        permanently_failed.push(req.height);
+            self.retry_queue.remove(&height);
+            let attempt = attempts.saturating_add(1);
+            match self.dispatch_request(height, attempt).await {
+                Ok(()) => {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.sync_retries.inc();
                     }
                 }
-                Err(_) => {
-                    warn!("no peers available for retry of block {}", req.height);
-                    permanently_failed.push(req.height);
+                Err(e) => {
+                    // A failed send counts as another attempt that timed out.
+                    warn!("retry of block {} failed: {}", height, e);
+                    if !self.requeue(height, attempt) {
+                        permanently_failed.push(height);
+                    }
                 }
             }
         }
@@ -215,12 +475,37 @@ impl BlockSyncer {
 
     /// Reset state for a new sync session (e.g. after a restart).
     pub fn reset(&mut self) {
+        self.in_flight_requests.clear();
         self.pending.clear();
+        self.retry_queue.clear();
         self.completed.clear();
+        self.last_verified_checkpoint = 0;
     }
 
     // ── Internals ───────────────────────────────────────────────────────
 
+    /// Queue `height` for another request after `attempts` failed ones,
+    /// unless it has run out of retries.
+    fn requeue(&mut self, height: u64, attempts: u32) -> bool {
+        if attempts > self.max_retries {
+            warn!("giving up on block {} after {} attempts", height, attempts);
+            return false;
+        }
+        let backoff = retry_backoff(attempts);
+        debug!(
+            "block {} timed out (attempt {}), retrying in {:?}",
+            height, attempts, backoff
+        );
+        self.retry_queue.insert(
+            height,
+            QueuedRetry {
+                attempts,
+                not_before: Instant::now() + backoff,
+            },
+        );
+        true
+    }
+
     /// Choose a random connected peer to send a request to.
     fn pick_peer(&self) -> Result<(Pubkey, SocketAddr)> {
         let pm = self
@@ -249,16 +534,38 @@ impl BlockSyncer {
     }
 }
 
+/// Wait before the retry that follows `attempts` failed requests.
+fn retry_backoff(attempts: u32) -> Duration {
+    let doublings = attempts.saturating_sub(1).min(31);
+    SYNC_RETRY_BASE_BACKOFF
+        .saturating_mul(1u32 << doublings)
+        .min(SYNC_RETRY_MAX_BACKOFF)
+}
+
 // ── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use {
         super::*,
-        crate::{config::ConsensusNetConfig, peer_manager::PeerManager},
-        solana_hash::Hash,
+        crate::{
+            config::ConsensusNetConfig,
+            handshake::Handshaker,
+            message::PeerInfo,
+            peer_manager::PeerManager,
+            transport::{TransportHandle, TransportKind, TransportListener},
+        },
     };
 
+    /// A TCP transport for a syncer, as `cfg` selects in tests.
+    fn tcp_transport(cfg: &ConsensusNetConfig) -> Arc<Transport> {
+        Arc::new(Transport::Tcp {
+            handshaker: Handshaker::new(&Keypair::new(), cfg),
+            max_message_size: cfg.max_message_size,
+            metrics: None,
+        })
+    }
+
     fn make_block_data(slot: u64) -> BlockData {
         BlockData {
             slot,
@@ -274,11 +581,13 @@ mod tests {
 
     #[test]
     fn test_handle_response_completes() {
-        let pm = Arc::new(Mutex::new(PeerManager::new(ConsensusNetConfig::dev_default())));
-        let mut syncer = BlockSyncer::new(pm, ConsensusNetConfig::dev_default());
+        let cfg = ConsensusNetConfig::dev_default();
+        let pm = Arc::new(Mutex::new(PeerManager::new(cfg.clone())));
+        let mut syncer = BlockSyncer::new(pm, cfg.clone(), tcp_transport(&cfg));
 
         // Simulate a pending request.
         let peer = Pubkey::new_unique();
+        syncer.in_flight_requests.insert(10);
         syncer.pending.insert(
             10,
             PendingSyncRequest {
@@ -291,7 +600,7 @@ mod tests {
         );
 
         let block = make_block_data(10);
-        let result = syncer.handle_response(10, block);
+        let result = syncer.handle_response(10, block).unwrap();
         assert!(result.is_some());
         assert!(syncer.is_completed(10));
         assert_eq!(syncer.in_flight(), 0);
@@ -299,10 +608,309 @@ mod tests {
 
     #[test]
     fn test_unsolicited_response_ignored() {
-        let pm = Arc::new(Mutex::new(PeerManager::new(ConsensusNetConfig::dev_default())));
-        let mut syncer = BlockSyncer::new(pm, ConsensusNetConfig::dev_default());
+        let cfg = ConsensusNetConfig::dev_default();
+        let pm = Arc::new(Mutex::new(PeerManager::new(cfg.clone())));
+        let mut syncer = BlockSyncer::new(pm, cfg.clone(), tcp_transport(&cfg));
 
         let block = make_block_data(99);
-        assert!(syncer.handle_response(99, block).is_none());
+        assert!(syncer.handle_response(99, block).unwrap().is_none());
+    }
+
+    /// A syncer whose only peer is a listener the test reads requests from.
+    async fn syncer_with_mock_peer() -> (BlockSyncer, TransportHandle, Arc<TRv1Metrics>) {
+        let mut cfg = ConsensusNetConfig::dev_default();
+        cfg.message_timeout_ms = 100;
+        let handle = TransportListener::new(cfg.clone(), &Keypair::new())
+            .start()
+            .await
+            .unwrap();
+
+        let mut pm = PeerManager::new(cfg.clone());
+        let peer = Pubkey::new_unique();
+        pm.add_peer(PeerInfo {
+            pubkey: peer,
+            addr: handle.local_addr,
+            stake_weight: 1,
+            is_active_validator: true,
+        })
+        .unwrap();
+        pm.mark_connected(&peer).unwrap();
+
+        let metrics = Arc::new(TRv1Metrics::new());
+        let transport = tcp_transport(&cfg);
+        let syncer = BlockSyncer::new(Arc::new(Mutex::new(pm)), cfg, transport)
+            .with_metrics(metrics.clone());
+        (syncer, handle, metrics)
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_requests_are_skipped() {
+        let (mut syncer, _handle, metrics) = syncer_with_mock_peer().await;
+
+        assert_eq!(syncer.request_range(1, 3).await, 3);
+        assert_eq!(syncer.request_range(1, 3).await, 0);
+        assert_eq!(syncer.in_flight(), 3);
+        assert_eq!(metrics.duplicate_requests_avoided.get(), 3);
+
+        // A height waiting out its backoff is not requested again either.
+        assert!(syncer.on_response_timeout(2));
+        assert!(!syncer.on_response_timeout(2));
+        assert_eq!(syncer.request_range(2, 2).await, 0);
+        assert_eq!(syncer.queued_retries(), 1);
+        assert_eq!(metrics.duplicate_requests_avoided.get(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_requests_are_retried_until_answered() {
+        let (mut syncer, mut handle, metrics) = syncer_with_mock_peer().await;
+        assert_eq!(syncer.request_range(7, 7).await, 1);
+
+        let mut requests_seen = 0;
+        let completed = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                match tokio::time::timeout(Duration::from_millis(50), handle.inbound_rx.recv())
+                    .await
+                {
+                    Ok(Some(inbound)) => {
+                        assert_eq!(
+                            inbound.message,
+                            ConsensusNetMessage::BlockRequest { height: 7 }
+                        );
+                        requests_seen += 1;
+                        // The peer drops the first two requests.
+                        if requests_seen > 2 {
+                            return syncer.handle_response(7, make_block_data(7)).unwrap();
+                        }
+                    }
+                    Ok(None) => panic!("mock peer channel closed"),
+                    Err(_) => assert!(syncer.retry_timed_out().await.is_empty()),
+                }
+            }
+        })
+        .await
+        .expect("block was never delivered");
+
+        assert!(completed.is_some());
+        assert!(syncer.is_completed(7));
+        assert_eq!(requests_seen, 3);
+        assert_eq!(metrics.sync_retries.get(), 2);
+        assert_eq!(syncer.in_flight(), 0);
+        assert_eq!(syncer.queued_retries(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_requests_use_configured_transport() {
+        let cfg = ConsensusNetConfig {
+            transport: TransportKind::Quic,
+            ..ConsensusNetConfig::dev_default()
+        };
+        let (us, peer) = (Keypair::new(), Keypair::new());
+        let validators = [us.pubkey(), peer.pubkey()];
+        let (_peer_transport, mut peer_handle) = Transport::start(&cfg, &peer, validators, None)
+            .await
+            .unwrap();
+        let (transport, _handle) = Transport::start(&cfg, &us, validators, None).await.unwrap();
+
+        let mut pm = PeerManager::new(cfg.clone());
+        pm.add_peer(PeerInfo {
+            pubkey: peer.pubkey(),
+            addr: peer_handle.local_addr,
+            stake_weight: 1,
+            is_active_validator: true,
+        })
+        .unwrap();
+        pm.mark_connected(&peer.pubkey()).unwrap();
+        let mut syncer = BlockSyncer::new(Arc::new(Mutex::new(pm)), cfg, Arc::new(transport));
+
+        // The request reaches the peer over QUIC.
+        assert_eq!(syncer.request_range(4, 4).await, 1);
+        let inbound = tokio::time::timeout(Duration::from_secs(2), peer_handle.inbound_rx.recv())
+            .await
+            .expect("timeout")
+            .expect("mock peer channel closed");
+        assert_eq!(
+            inbound.message,
+            ConsensusNetMessage::BlockRequest { height: 4 }
+        );
+    }
+
+    #[test]
+    fn test_retry_backoff_doubles_up_to_cap() {
+        assert_eq!(retry_backoff(1), SYNC_RETRY_BASE_BACKOFF);
+        assert_eq!(retry_backoff(2), Duration::from_secs(1));
+        assert_eq!(retry_backoff(3), Duration::from_secs(2));
+        assert_eq!(retry_backoff(6), SYNC_RETRY_MAX_BACKOFF);
+        assert_eq!(retry_backoff(u32::MAX), SYNC_RETRY_MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_gives_up_after_max_retries() {
+        let cfg = ConsensusNetConfig::dev_default();
+        let pm = Arc::new(Mutex::new(PeerManager::new(cfg.clone())));
+        let mut syncer = BlockSyncer::new(pm, cfg.clone(), tcp_transport(&cfg));
+
+        for attempts in 1..=MAX_SYNC_RETRIES + 1 {
+            syncer.retry_queue.remove(&5);
+            syncer.in_flight_requests.insert(5);
+            syncer.pending.insert(
+                5,
+                PendingSyncRequest {
+                    height: 5,
+                    peer: Pubkey::new_unique(),
+                    addr: "127.0.0.1:8900".parse().unwrap(),
+                    sent_at: Instant::now(),
+                    attempts,
+                },
+            );
+            assert_eq!(syncer.on_response_timeout(5), attempts <= MAX_SYNC_RETRIES);
+        }
+        assert_eq!(syncer.in_flight(), 0);
+        assert_eq!(syncer.queued_retries(), 0);
+    }
+
+    struct StaticCheckpoints(HashMap<u64, SyncCheckpoint>);
+
+    impl CheckpointSource for StaticCheckpoints {
+        fn checkpoint(&self, height: u64) -> Option<SyncCheckpoint> {
+            self.0.get(&height).cloned()
+        }
+    }
+
+    fn validator_infos(validators: &[Keypair]) -> Vec<ValidatorInfo> {
+        validators
+            .iter()
+            .map(|v| ValidatorInfo {
+                pubkey: v.pubkey(),
+                stake_weight: 100,
+                consensus_addr: "127.0.0.1:8900".parse().unwrap(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_checkpoint_needs_supermajority_of_valid_signatures() {
+        let validators: Vec<Keypair> = (0..4).map(|_| Keypair::new()).collect();
+        let infos = validator_infos(&validators);
+        let mut checkpoint = SyncCheckpoint::new(10, Hash::new_unique());
+
+        checkpoint.sign(&validators[0]);
+        checkpoint.sign(&validators[1]);
+        // Signing twice, or signing without being a validator, adds nothing.
+        checkpoint.sign(&validators[1]);
+        checkpoint.sign(&Keypair::new());
+        assert!(!checkpoint.is_confirmed_by(&infos));
+
+        // A signature over different bytes does not count.
+        let forged = SyncCheckpoint::new(11, checkpoint.block_hash);
+        let mut tampered = checkpoint.clone();
+        tampered.commit_signatures.push((
+            validators[2].pubkey(),
+            validators[2].sign_message(&forged.signable_bytes()),
+        ));
+        assert!(!tampered.is_confirmed_by(&infos));
+
+        checkpoint.sign(&validators[2]);
+        assert!(checkpoint.is_confirmed_by(&infos));
+        assert!(!checkpoint.is_confirmed_by(&[]));
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_mismatch_rolls_back_and_resyncs() {
+        let (syncer, mut handle, metrics) = syncer_with_mock_peer().await;
+
+        // The canonical chain, with checkpoints at 50 and 70 signed by all
+        // four validators.
+        let validators: Vec<Keypair> = (0..4).map(|_| Keypair::new()).collect();
+        let chain: Vec<Hash> = (0..=100).map(|_| Hash::new_unique()).collect();
+        let checkpoints = [50u64, 70]
+            .into_iter()
+            .map(|height| {
+                let mut checkpoint = SyncCheckpoint::new(height, chain[height as usize]);
+                validators.iter().for_each(|v| checkpoint.sign(v));
+                (height, checkpoint)
+            })
+            .collect();
+        let block = |height: u64, block_hash: Hash| BlockData {
+            block_hash,
+            ..make_block_data(height)
+        };
+
+        let bad_peer = Pubkey::new_unique();
+        let bad_addr: SocketAddr = "127.0.0.1:1".parse().unwrap();
+        {
+            let mut pm = syncer.peer_manager.lock().unwrap();
+            pm.update_validator_set(validator_infos(&validators));
+            pm.add_peer(PeerInfo {
+                pubkey: bad_peer,
+                addr: bad_addr,
+                stake_weight: 1,
+                is_active_validator: false,
+            })
+            .unwrap();
+            pm.mark_connected(&bad_peer).unwrap();
+        }
+        let mut syncer = syncer.with_checkpoint_source(Arc::new(StaticCheckpoints(checkpoints)));
+
+        // The bad peer serves blocks 1..=100, all genuine except block 70.
+        for height in 1..=100 {
+            syncer.in_flight_requests.insert(height);
+            syncer.pending.insert(
+                height,
+                PendingSyncRequest {
+                    height,
+                    peer: bad_peer,
+                    addr: bad_addr,
+                    sent_at: Instant::now(),
+                    attempts: 1,
+                },
+            );
+        }
+        for height in (1..=100).filter(|&h| h != 70) {
+            let response = syncer.handle_response(height, block(height, chain[height as usize]));
+            assert!(response.unwrap().is_some());
+        }
+        assert_eq!(syncer.last_verified_checkpoint(), 50);
+
+        let err = syncer
+            .handle_response(70, block(70, Hash::new_unique()))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConsensusNetError::CheckpointMismatch {
+                height: 70,
+                rollback_from: 51
+            }
+        ));
+        assert_eq!(metrics.sync_checkpoint_failures.get(), 1);
+        assert!((1..=50).all(|h| syncer.is_completed(h)));
+        assert!((51..=100).all(|h| !syncer.is_completed(h)));
+        assert_eq!(syncer.queued_retries(), 50);
+        {
+            let pm = syncer.peer_manager.lock().unwrap();
+            let conn = pm.get_peer(&bad_peer).unwrap();
+            assert!(!conn.is_connected);
+            assert_eq!(conn.reputation.invalid_messages, 1);
+        }
+
+        // The discarded blocks are fetched again, from the remaining peer.
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while (51..=100).any(|h| !syncer.is_completed(h)) {
+                assert!(syncer.retry_timed_out().await.is_empty());
+                while syncer.in_flight() > 0 {
+                    let inbound = handle.inbound_rx.recv().await.unwrap();
+                    let ConsensusNetMessage::BlockRequest { height } = inbound.message else {
+                        panic!("unexpected message {:?}", inbound.message);
+                    };
+                    assert!((51..=100).contains(&height));
+                    assert_ne!(syncer.pending[&height].peer, bad_peer);
+                    let response =
+                        syncer.handle_response(height, block(height, chain[height as usize]));
+                    assert!(response.unwrap().is_some());
+                }
+            }
+        })
+        .await
+        .expect("rolled-back blocks were never re-synced");
+        assert_eq!(syncer.last_verified_checkpoint(), 70);
     }
 }
@@ -1,36 +1,168 @@
 //! TCP/QUIC transport layer for consensus message delivery.
 //!
 //! This module provides the low-level plumbing for sending and receiving
-//! [`ConsensusNetMessage`]s between validators.  The design favours TCP for
-//! the initial implementation (simpler, debuggable) with a QUIC upgrade path.
+//! [`ConsensusNetMessage`]s between validators over TCP (simpler,
+//! debuggable).  The QUIC backend lives in [`crate::transport_quic`];
+//! [`Transport`] selects between the two according to
+//! [`ConsensusNetConfig::transport`].
 //!
 //! ## Wire format
 //!
-//! Every message on the wire is length-prefixed:
+//! Every message on the wire is framed:
 //!
 //! ```text
-//! [4 bytes: payload length (u32-le)] [N bytes: bincode payload]
+//! [4 bytes: payload length (u32-le)] [1 byte: flags] [N bytes: bincode payload]
 //! ```
 //!
-//! The transport reads the 4-byte header, validates the length against
-//! `max_message_size`, then reads exactly that many bytes and hands the
-//! resulting [`ConsensusNetMessage`] to the message router.
+//! The transport reads the 5-byte header, validates the length against
+//! `max_message_size`, then reads exactly that many bytes, decompresses them
+//! if the header's compressed flag is set, and hands the resulting
+//! [`ConsensusNetMessage`] to the message router.
+//!
+//! Before the first frame, both ends of a TCP connection run the
+//! [handshake](crate::handshake), each signing the other's fresh nonce;
+//! connections from peers on another chain, on an unsupported protocol
+//! version, or that cannot prove their identity are closed before any frame
+//! is read.
+//!
+//! The TCP handshake does not negotiate compression, so the TCP senders
+//! always send uncompressed frames.  Receivers accept both, so a
+//! compressing QUIC peer never needs to know which backend it talks to.
 
 use {
     crate::{
         config::ConsensusNetConfig,
         error::{ConsensusNetError, Result},
-        message::ConsensusNetMessage,
+        handshake::Handshaker,
+        message::{ConsensusNetMessage, EncodedFrame, FrameHeader, FRAME_HEADER_LEN},
+        rate_limit::{PeerRateLimiter, RateLimitKey},
     },
+    crate::transport_quic::QuicTransport,
     log::{debug, error, info, warn},
-    std::net::SocketAddr,
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    std::{net::SocketAddr, sync::Arc, time::Duration},
     tokio::{
         io::{AsyncReadExt, AsyncWriteExt},
         net::{TcpListener, TcpStream},
         sync::mpsc,
     },
+    trv1_monitoring::TRv1Metrics,
 };
 
+/// Which transport backend the consensus network uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransportKind {
+    /// Length-prefixed frames over TCP connections.
+    Tcp,
+    /// One unidirectional QUIC stream per message, with peers authenticated
+    /// against the validator set.
+    #[default]
+    Quic,
+}
+
+/// A started transport backend, as selected by [`ConsensusNetConfig::transport`].
+pub enum Transport {
+    /// TCP transport; sends open a connection per message.
+    Tcp {
+        /// Runs the handshake on every outbound connection.
+        handshaker: Handshaker,
+        /// Maximum size of a single serialized message in bytes.
+        max_message_size: usize,
+        /// Where to record bytes sent, if anywhere.
+        metrics: Option<Arc<TRv1Metrics>>,
+    },
+    /// QUIC transport.
+    Quic(QuicTransport),
+}
+
+impl Transport {
+    /// Start the configured backend listening on `config.bind_addr`.
+    ///
+    /// `validators` restricts who may connect over QUIC; the TCP backend
+    /// accepts any peer that passes the [handshake](crate::handshake).
+    /// Traffic counters are recorded in `metrics`, if given.
+    pub async fn start(
+        config: &ConsensusNetConfig,
+        keypair: &Keypair,
+        validators: impl IntoIterator<Item = Pubkey>,
+        metrics: Option<Arc<TRv1Metrics>>,
+    ) -> Result<(Self, TransportHandle)> {
+        match config.transport {
+            TransportKind::Tcp => {
+                let mut listener = TransportListener::new(config.clone(), keypair);
+                if let Some(metrics) = &metrics {
+                    listener = listener.with_metrics(metrics.clone());
+                }
+                let handle = listener.start().await?;
+                let transport = Self::Tcp {
+                    handshaker: Handshaker::new(keypair, config),
+                    max_message_size: config.max_message_size,
+                    metrics,
+                };
+                Ok((transport, handle))
+            }
+            TransportKind::Quic => {
+                let (transport, handle) =
+                    QuicTransport::start(config, keypair, validators, metrics).await?;
+                Ok((Self::Quic(transport), handle))
+            }
+        }
+    }
+
+    /// Returns which backend this is.
+    pub fn kind(&self) -> TransportKind {
+        match self {
+            Self::Tcp { .. } => TransportKind::Tcp,
+            Self::Quic(_) => TransportKind::Quic,
+        }
+    }
+
+    /// Send a single message to `addr`.
+    pub async fn send_message(&self, addr: SocketAddr, msg: &ConsensusNetMessage) -> Result<()> {
+        match self {
+            Self::Tcp {
+                handshaker,
+                max_message_size,
+                metrics,
+            } => {
+                let frame = msg.encode_frame(*max_message_size, false)?;
+                send_frame(addr, &frame.bytes, handshaker).await?;
+                record_bytes_sent(metrics.as_deref(), &frame, 1);
+                Ok(())
+            }
+            Self::Quic(quic) => quic.send_message(addr, msg).await,
+        }
+    }
+
+    /// Broadcast a message to multiple addresses concurrently.
+    ///
+    /// Returns the list of addresses where sending failed.
+    pub async fn broadcast_message(
+        &self,
+        addrs: &[SocketAddr],
+        msg: &ConsensusNetMessage,
+    ) -> Vec<(SocketAddr, ConsensusNetError)> {
+        match self {
+            Self::Tcp {
+                handshaker,
+                max_message_size,
+                metrics,
+            } => {
+                let frame = match msg.encode_frame(*max_message_size, false) {
+                    Ok(frame) => frame,
+                    Err(e) => return serialize_failures(addrs, &e),
+                };
+                let failures = broadcast_frame(addrs, &frame.bytes, handshaker).await;
+                let delivered = addrs.len().saturating_sub(failures.len());
+                record_bytes_sent(metrics.as_deref(), &frame, delivered);
+                failures
+            }
+            Self::Quic(quic) => quic.broadcast_message(addrs, msg).await,
+        }
+    }
+}
+
 /// A received message together with the address it came from.
 #[derive(Debug)]
 pub struct InboundMessage {
@@ -40,45 +172,170 @@ pub struct InboundMessage {
     pub from: SocketAddr,
 }
 
+/// Bytes received that did not decode to a valid message.
+#[derive(Debug, Clone)]
+pub struct InvalidMessage {
+    /// The remote socket address that sent them.
+    pub from: SocketAddr,
+    /// The sender's identity, if the connection authenticated it.
+    pub peer: Option<Pubkey>,
+    /// Why the bytes were rejected.
+    pub reason: String,
+}
+
+/// A message dropped because its sender exceeded its rate limit.
+#[derive(Debug, Clone)]
+pub struct RateLimitedMessage {
+    /// The remote socket address that sent it.
+    pub from: SocketAddr,
+    /// The sender's identity, if the connection authenticated it.
+    pub peer: Option<Pubkey>,
+}
+
 /// Handle returned by [`TransportListener::start`] to control the listener.
 pub struct TransportHandle {
     /// Channel that delivers every inbound message to the router.
     pub inbound_rx: mpsc::Receiver<InboundMessage>,
+    /// Channel that reports every rejected message, for
+    /// [`PeerManager::record_invalid_report`](crate::peer_manager::PeerManager::record_invalid_report).
+    pub invalid_rx: mpsc::Receiver<InvalidMessage>,
+    /// Channel that reports every rate-limited message, for
+    /// [`PeerManager::record_rate_limited_report`](crate::peer_manager::PeerManager::record_rate_limited_report).
+    pub rate_limited_rx: mpsc::Receiver<RateLimitedMessage>,
     /// The local address the listener is bound to (useful when port = 0).
     pub local_addr: SocketAddr,
 }
 
+/// State shared by every inbound connection reader of one transport.
+#[derive(Clone)]
+pub(crate) struct InboundContext {
+    pub(crate) tx: mpsc::Sender<InboundMessage>,
+    invalid_tx: mpsc::Sender<InvalidMessage>,
+    rate_limited_tx: mpsc::Sender<RateLimitedMessage>,
+    metrics: Option<Arc<TRv1Metrics>>,
+    pub(crate) max_message_size: usize,
+    /// Per-peer message budgets, shared by every connection.
+    rate_limiter: Arc<PeerRateLimiter>,
+}
+
+impl InboundContext {
+    /// Create the context together with the handle that drains it.
+    pub(crate) fn new(
+        config: &ConsensusNetConfig,
+        metrics: Option<Arc<TRv1Metrics>>,
+        local_addr: SocketAddr,
+    ) -> (Self, TransportHandle) {
+        let (tx, inbound_rx) = mpsc::channel(config.channel_buffer_size);
+        let (invalid_tx, invalid_rx) = mpsc::channel(config.channel_buffer_size);
+        let (rate_limited_tx, rate_limited_rx) = mpsc::channel(config.channel_buffer_size);
+        let context = Self {
+            tx,
+            invalid_tx,
+            rate_limited_tx,
+            metrics,
+            max_message_size: config.max_message_size,
+            rate_limiter: Arc::new(PeerRateLimiter::new(
+                config.peer_rate_limit_burst,
+                config.peer_rate_limit_per_second,
+            )),
+        };
+        let handle = TransportHandle {
+            inbound_rx,
+            invalid_rx,
+            rate_limited_rx,
+            local_addr,
+        };
+        (context, handle)
+    }
+
+    /// Take a token from the sender's bucket, keyed by `peer` if it is
+    /// authenticated and by `from`'s IP address otherwise.
+    pub(crate) fn try_admit(&self, from: SocketAddr, peer: Option<Pubkey>) -> bool {
+        self.rate_limiter.try_acquire(RateLimitKey::new(peer, from))
+    }
+
+    /// Report a rejected message without blocking the reader; reports are
+    /// dropped if nobody is draining them.
+    pub(crate) fn report_invalid(&self, from: SocketAddr, peer: Option<Pubkey>, reason: String) {
+        let report = InvalidMessage { from, peer, reason };
+        if self.invalid_tx.try_send(report).is_err() {
+            debug!(
+                "invalid-message channel full or closed, dropping report for {}",
+                from
+            );
+        }
+    }
+
+    /// Count and report a message dropped by rate limiting.
+    pub(crate) fn report_rate_limited(&self, from: SocketAddr, peer: Option<Pubkey>) {
+        if let Some(metrics) = &self.metrics {
+            metrics.peer_rate_limited_messages.inc();
+        }
+        if self
+            .rate_limited_tx
+            .try_send(RateLimitedMessage { from, peer })
+            .is_err()
+        {
+            debug!(
+                "rate-limit channel full or closed, dropping report for {}",
+                from
+            );
+        }
+    }
+}
+
 /// Listens for inbound TCP connections and delivers decoded messages.
 pub struct TransportListener {
     config: ConsensusNetConfig,
+    handshaker: Handshaker,
+    metrics: Option<Arc<TRv1Metrics>>,
 }
 
 impl TransportListener {
-    /// Create a new transport listener with the given config.
-    pub fn new(config: ConsensusNetConfig) -> Self {
-        Self { config }
+    /// Create a new transport listener with the given config, answering
+    /// handshakes as `keypair`'s identity.
+    pub fn new(config: ConsensusNetConfig, keypair: &Keypair) -> Self {
+        Self {
+            handshaker: Handshaker::new(keypair, &config),
+            config,
+            metrics: None,
+        }
+    }
+
+    /// Count rate-limited messages in `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<TRv1Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
     }
 
     /// Bind and start accepting connections.
     ///
     /// Returns a [`TransportHandle`] whose `inbound_rx` yields every
     /// successfully decoded message.  Spawns a Tokio task per accepted
-    /// connection.
+    /// connection; all of a peer's connections draw on one rate limit.  A
+    /// peer that does not complete the handshake within
+    /// `message_timeout_ms` is disconnected.
     pub async fn start(self) -> Result<TransportHandle> {
         let listener = TcpListener::bind(self.config.bind_addr).await?;
         let local_addr = listener.local_addr()?;
         info!("consensus transport listening on {}", local_addr);
 
-        let (tx, rx) = mpsc::channel::<InboundMessage>(self.config.channel_buffer_size);
-        let max_msg = self.config.max_message_size;
+        let (context, handle) = InboundContext::new(&self.config, self.metrics, local_addr);
+        let handshaker = self.handshaker;
+        let handshake_timeout = Duration::from_millis(self.config.message_timeout_ms);
 
         tokio::spawn(async move {
             loop {
                 match listener.accept().await {
                     Ok((stream, addr)) => {
                         debug!("accepted consensus connection from {}", addr);
-                        let tx = tx.clone();
-                        tokio::spawn(Self::handle_connection(stream, addr, tx, max_msg));
+                        tokio::spawn(Self::handle_connection(
+                            stream,
+                            addr,
+                            context.clone(),
+                            handshaker.clone(),
+                            handshake_timeout,
+                        ));
                     }
                     Err(e) => {
                         error!("accept error: {}", e);
@@ -87,23 +344,41 @@ impl TransportListener {
             }
         });
 
-        Ok(TransportHandle {
-            inbound_rx: rx,
-            local_addr,
-        })
+        Ok(handle)
     }
 
-    /// Read length-prefixed messages from `stream` until EOF or error.
+    /// Handshake with the peer, then read framed messages from `stream`
+    /// until EOF or error.
+    ///
+    /// Messages beyond the peer's rate limit are dropped unread.
+    /// Rejected and rate-limited messages are reported against the identity
+    /// the peer proved in the handshake.
     async fn handle_connection(
         mut stream: TcpStream,
         addr: SocketAddr,
-        tx: mpsc::Sender<InboundMessage>,
-        max_message_size: usize,
+        context: InboundContext,
+        handshaker: Handshaker,
+        handshake_timeout: Duration,
     ) {
-        let mut header_buf = [0u8; 4];
+        let handshake = tokio::time::timeout(handshake_timeout, handshaker.accept(&mut stream));
+        let peer = match handshake.await {
+            Ok(Ok(remote)) => remote.validator_pubkey,
+            Ok(Err(e)) => {
+                warn!("handshake with {} failed: {}, closing connection", addr, e);
+                return;
+            }
+            Err(_) => {
+                warn!("handshake with {} timed out, closing connection", addr);
+                return;
+            }
+        };
+        debug!("handshake with {} complete: peer {}", addr, peer);
+
+        let max_message_size = context.max_message_size;
+        let mut header_buf = [0u8; FRAME_HEADER_LEN];
 
         loop {
-            // 1. Read the 4-byte length prefix.
+            // 1. Read the frame header.
             if let Err(e) = stream.read_exact(&mut header_buf).await {
                 if e.kind() != std::io::ErrorKind::UnexpectedEof {
                     warn!("header read error from {}: {}", addr, e);
@@ -111,27 +386,46 @@ impl TransportListener {
                 break;
             }
 
-            let len = ConsensusNetMessage::read_frame_len(&header_buf);
-            if len > max_message_size {
+            let header = match FrameHeader::decode(&header_buf) {
+                Ok(header) => header,
+                Err(e) => {
+                    warn!("bad frame header from {}: {}, dropping connection", addr, e);
+                    context.report_invalid(addr, Some(peer), e.to_string());
+                    break;
+                }
+            };
+            if header.len > max_message_size {
                 warn!(
                     "peer {} sent oversized frame ({} > {}), dropping connection",
-                    addr, len, max_message_size
+                    addr, header.len, max_message_size
+                );
+                context.report_invalid(
+                    addr,
+                    Some(peer),
+                    format!("oversized frame of {} bytes", header.len),
                 );
                 break;
             }
 
             // 2. Read the payload.
-            let mut payload = vec![0u8; len];
+            let mut payload = vec![0u8; header.len];
             if let Err(e) = stream.read_exact(&mut payload).await {
                 warn!("payload read error from {}: {}", addr, e);
                 break;
             }
 
-            // 3. Deserialize.
-            match ConsensusNetMessage::deserialize(&payload) {
+            // 3. Drop the message if the peer is over its rate limit.
+            if !context.try_admit(addr, Some(peer)) {
+                context.report_rate_limited(addr, Some(peer));
+                continue;
+            }
+
+            // 4. Decompress and deserialize.
+            match ConsensusNetMessage::decode_payload(&header, &payload, max_message_size) {
                 Ok(message) => {
                     debug!("received {} from {}", message.kind(), addr);
-                    if tx
+                    if context
+                        .tx
                         .send(InboundMessage {
                             message,
                             from: addr,
@@ -146,6 +440,7 @@ impl TransportListener {
                 }
                 Err(e) => {
                     warn!("deserialization error from {}: {}", addr, e);
+                    context.report_invalid(addr, Some(peer), e.to_string());
                     // Skip this message but keep the connection alive — the
                     // peer may be running a slightly different version.
                 }
@@ -158,6 +453,13 @@ impl TransportListener {
 
 // ── Outbound sending ────────────────────────────────────────────────────────
 
+/// Open a TCP connection to `addr` and complete the handshake on it.
+pub async fn connect(addr: SocketAddr, handshaker: &Handshaker) -> Result<TcpStream> {
+    let mut stream = TcpStream::connect(addr).await?;
+    handshaker.initiate(&mut stream).await?;
+    Ok(stream)
+}
+
 /// Send a single framed message to the given address over a new TCP connection.
 ///
 /// For production use the caller should maintain persistent connections;
@@ -166,15 +468,14 @@ pub async fn send_message(
     addr: SocketAddr,
     msg: &ConsensusNetMessage,
     max_message_size: usize,
+    handshaker: &Handshaker,
 ) -> Result<()> {
     let frame = msg.serialize_framed(max_message_size)?;
-    let mut stream = TcpStream::connect(addr).await?;
-    stream.write_all(&frame).await?;
-    stream.flush().await?;
-    Ok(())
+    send_frame(addr, &frame, handshaker).await
 }
 
-/// Send a framed message over an *existing* TCP stream.
+/// Send a framed message over an *existing* TCP stream, one that has
+/// already completed the handshake (see [`connect`]).
 pub async fn send_on_stream(
     stream: &mut TcpStream,
     msg: &ConsensusNetMessage,
@@ -193,42 +494,74 @@ pub async fn broadcast_message(
     addrs: &[SocketAddr],
     msg: &ConsensusNetMessage,
     max_message_size: usize,
+    handshaker: &Handshaker,
 ) -> Vec<(SocketAddr, ConsensusNetError)> {
-    let frame = match msg.serialize_framed(max_message_size) {
-        Ok(f) => f,
-        Err(e) => {
-            // If we can't even serialize, return an error for every target.
-            return addrs
-                .iter()
-                .map(|a| {
-                    (
-                        *a,
-                        ConsensusNetError::InvalidMessage(format!("serialize failed: {e}")),
-                    )
-                })
-                .collect();
-        }
-    };
+    match msg.serialize_framed(max_message_size) {
+        Ok(frame) => broadcast_frame(addrs, &frame, handshaker).await,
+        // If we can't even serialize, return an error for every target.
+        Err(e) => serialize_failures(addrs, &e),
+    }
+}
+
+/// Add one delivered copy of `frame` per `copies` to the byte counters.
+pub(crate) fn record_bytes_sent(
+    metrics: Option<&TRv1Metrics>,
+    frame: &EncodedFrame,
+    copies: usize,
+) {
+    if let Some(metrics) = metrics {
+        let copies = copies as u64;
+        metrics
+            .total_bytes_sent_uncompressed
+            .add((frame.uncompressed_len as u64).saturating_mul(copies));
+        metrics
+            .total_bytes_sent_compressed
+            .add((frame.payload_len() as u64).saturating_mul(copies));
+    }
+}
+
+/// One failure per target, for a message that could not be serialized.
+pub(crate) fn serialize_failures(
+    addrs: &[SocketAddr],
+    error: &ConsensusNetError,
+) -> Vec<(SocketAddr, ConsensusNetError)> {
+    addrs
+        .iter()
+        .map(|a| {
+            (
+                *a,
+                ConsensusNetError::InvalidMessage(format!("serialize failed: {error}")),
+            )
+        })
+        .collect()
+}
 
+async fn send_frame(addr: SocketAddr, frame: &[u8], handshaker: &Handshaker) -> Result<()> {
+    let mut stream = connect(addr, handshaker).await?;
+    stream.write_all(frame).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+async fn broadcast_frame(
+    addrs: &[SocketAddr],
+    frame: &[u8],
+    handshaker: &Handshaker,
+) -> Vec<(SocketAddr, ConsensusNetError)> {
+    let frame = Arc::new(frame.to_vec());
     let mut handles = Vec::with_capacity(addrs.len());
     for &addr in addrs {
         let frame = frame.clone();
+        let handshaker = handshaker.clone();
         handles.push(tokio::spawn(async move {
-            let result = async {
-                let mut stream = TcpStream::connect(addr).await?;
-                stream.write_all(&frame).await?;
-                stream.flush().await?;
-                Ok::<(), std::io::Error>(())
-            }
-            .await;
-            (addr, result)
+            (addr, send_frame(addr, &frame, &handshaker).await)
         }));
     }
 
     let mut failures = Vec::new();
     for handle in handles {
         if let Ok((addr, Err(e))) = handle.await {
-            failures.push((addr, ConsensusNetError::Transport(e)));
+            failures.push((addr, e));
         }
     }
     failures
@@ -238,21 +571,37 @@ pub async fn broadcast_message(
 
 #[cfg(test)]
 mod tests {
-    use {super::*, crate::config::ConsensusNetConfig, solana_pubkey::Pubkey};
+    use {
+        super::*,
+        crate::{
+            config::ConsensusNetConfig,
+            error::HandshakeError,
+            handshake::{Handshake, HANDSHAKE_LEN, MAX_SUPPORTED_VERSION},
+        },
+        solana_signer::Signer,
+    };
+
+    /// Handshaker for a fresh identity on `cfg`'s chain.
+    fn new_handshaker(cfg: &ConsensusNetConfig) -> Handshaker {
+        Handshaker::new(&Keypair::new(), cfg)
+    }
 
     #[tokio::test]
     async fn test_listener_and_send() {
         let cfg = ConsensusNetConfig::dev_default();
-        let listener = TransportListener::new(cfg.clone());
+        let listener = TransportListener::new(cfg.clone(), &Keypair::new());
         let mut handle = listener.start().await.unwrap();
         let addr = handle.local_addr;
 
         let msg = ConsensusNetMessage::Heartbeat {
             pubkey: Pubkey::new_unique(),
             latest_slot: 99,
+            known_peers: vec![],
         };
 
-        send_message(addr, &msg, cfg.max_message_size).await.unwrap();
+        send_message(addr, &msg, cfg.max_message_size, &new_handshaker(&cfg))
+            .await
+            .unwrap();
 
         let received = tokio::time::timeout(
             std::time::Duration::from_secs(2),
@@ -268,13 +617,14 @@ mod tests {
     #[tokio::test]
     async fn test_broadcast() {
         let cfg = ConsensusNetConfig::dev_default();
-        let listener = TransportListener::new(cfg.clone());
+        let listener = TransportListener::new(cfg.clone(), &Keypair::new());
         let mut handle = listener.start().await.unwrap();
         let addr = handle.local_addr;
 
         let msg = ConsensusNetMessage::BlockRequest { height: 42 };
 
-        let failures = broadcast_message(&[addr], &msg, cfg.max_message_size).await;
+        let failures =
+            broadcast_message(&[addr], &msg, cfg.max_message_size, &new_handshaker(&cfg)).await;
         assert!(failures.is_empty(), "broadcast had failures: {:?}", failures);
 
         let received = tokio::time::timeout(
@@ -286,4 +636,252 @@ mod tests {
         .expect("empty");
         assert_eq!(received.message, msg);
     }
+
+    #[tokio::test]
+    async fn test_listener_accepts_compressed_frames() {
+        let cfg = ConsensusNetConfig::dev_default();
+        let mut handle = TransportListener::new(cfg.clone(), &Keypair::new())
+            .start()
+            .await
+            .unwrap();
+
+        let msg = ConsensusNetMessage::ValidatorSetUpdate {
+            epoch: 3,
+            validators: vec![
+                crate::message::ValidatorInfo {
+                    pubkey: Pubkey::default(),
+                    stake_weight: 1,
+                    consensus_addr: handle.local_addr,
+                };
+                64
+            ],
+        };
+        let frame = msg.encode_frame(cfg.max_message_size, true).unwrap();
+        assert!(frame.is_compressed());
+        send_frame(handle.local_addr, &frame.bytes, &new_handshaker(&cfg))
+            .await
+            .unwrap();
+
+        let received = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            handle.inbound_rx.recv(),
+        )
+        .await
+        .expect("timeout")
+        .expect("empty");
+        assert_eq!(received.message, msg);
+    }
+
+    #[tokio::test]
+    async fn test_listener_rate_limits_per_peer() {
+        const SENT: u64 = 1_000;
+        let cfg = ConsensusNetConfig {
+            peer_rate_limit_burst: 100,
+            peer_rate_limit_per_second: 50,
+            ..ConsensusNetConfig::dev_default()
+        };
+        let metrics = Arc::new(TRv1Metrics::new());
+        let mut handle = TransportListener::new(cfg.clone(), &Keypair::new())
+            .with_metrics(metrics.clone())
+            .start()
+            .await
+            .unwrap();
+        let msg = ConsensusNetMessage::BlockRequest { height: 1 };
+        let sender = Keypair::new();
+        let handshaker = Handshaker::new(&sender, &cfg);
+
+        let start = std::time::Instant::now();
+        let mut stream = connect(handle.local_addr, &handshaker).await.unwrap();
+        for _ in 0..SENT {
+            send_on_stream(&mut stream, &msg, cfg.max_message_size)
+                .await
+                .unwrap();
+        }
+        // Every message is either delivered or counted as rate-limited.
+        let mut received = 0u64;
+        while received + metrics.peer_rate_limited_messages.get() < SENT {
+            assert!(start.elapsed() < std::time::Duration::from_secs(10));
+            let poll = std::time::Duration::from_millis(10);
+            if let Ok(inbound) = tokio::time::timeout(poll, handle.inbound_rx.recv()).await {
+                inbound.expect("empty");
+                received += 1;
+            }
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+
+        let burst = u64::from(cfg.peer_rate_limit_burst);
+        let refill = (elapsed * f64::from(cfg.peer_rate_limit_per_second)).ceil() as u64;
+        assert!(
+            (burst..=burst + refill).contains(&received),
+            "received {received} of {SENT} in {elapsed:.3}s"
+        );
+        assert_eq!(metrics.peer_rate_limited_messages.get(), SENT - received);
+        assert_eq!(
+            handle.rate_limited_rx.try_recv().unwrap().peer,
+            Some(sender.pubkey())
+        );
+
+        // Reconnecting does not refill the peer's bucket...
+        let limited = metrics.peer_rate_limited_messages.get();
+        let mut stream = connect(handle.local_addr, &handshaker).await.unwrap();
+        for _ in 0..burst {
+            send_on_stream(&mut stream, &msg, cfg.max_message_size)
+                .await
+                .unwrap();
+        }
+        while metrics.peer_rate_limited_messages.get() == limited {
+            assert!(start.elapsed() < std::time::Duration::from_secs(10));
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        // ...but another peer starts with a full one.
+        let other = Handshaker::new(&Keypair::new(), &cfg);
+        let mut stream = connect(handle.local_addr, &other).await.unwrap();
+        let other_addr = stream.local_addr().unwrap();
+        for _ in 0..burst {
+            send_on_stream(&mut stream, &msg, cfg.max_message_size)
+                .await
+                .unwrap();
+        }
+        let mut from_other = 0;
+        while from_other < burst {
+            let inbound =
+                tokio::time::timeout(std::time::Duration::from_secs(2), handle.inbound_rx.recv())
+                    .await
+                    .expect("timeout")
+                    .expect("empty");
+            if inbound.from == other_addr {
+                from_other += 1;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transport_selects_backend() {
+        let keypair = Keypair::new();
+        let peer = Keypair::new();
+        for kind in [TransportKind::Tcp, TransportKind::Quic] {
+            let cfg = ConsensusNetConfig {
+                transport: kind,
+                ..ConsensusNetConfig::dev_default()
+            };
+            let validators = [keypair.pubkey(), peer.pubkey()];
+            let (_receiver, mut handle) = Transport::start(&cfg, &keypair, validators, None)
+                .await
+                .unwrap();
+            let (sender, _sender_handle) = Transport::start(&cfg, &peer, validators, None)
+                .await
+                .unwrap();
+            assert_eq!(sender.kind(), kind);
+
+            let msg = ConsensusNetMessage::BlockRequest { height: 5 };
+            sender.send_message(handle.local_addr, &msg).await.unwrap();
+            let received = tokio::time::timeout(
+                std::time::Duration::from_secs(2),
+                handle.inbound_rx.recv(),
+            )
+            .await
+            .expect("timeout")
+            .expect("empty");
+            assert_eq!(received.message, msg);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compatible_handshake_succeeds() {
+        let cfg = ConsensusNetConfig::dev_default();
+        let listener_keypair = Keypair::new();
+        let mut handle = TransportListener::new(cfg.clone(), &listener_keypair)
+            .start()
+            .await
+            .unwrap();
+
+        let mut stream = TcpStream::connect(handle.local_addr).await.unwrap();
+        let remote = new_handshaker(&cfg).initiate(&mut stream).await.unwrap();
+        assert_eq!(remote.validator_pubkey, listener_keypair.pubkey());
+
+        let msg = ConsensusNetMessage::BlockRequest { height: 3 };
+        send_on_stream(&mut stream, &msg, cfg.max_message_size)
+            .await
+            .unwrap();
+        let received = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            handle.inbound_rx.recv(),
+        )
+        .await
+        .expect("timeout")
+        .expect("empty");
+        assert_eq!(received.message, msg);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_chain_id_closes_connection() {
+        let cfg = ConsensusNetConfig::dev_default();
+        let mut handle = TransportListener::new(cfg.clone(), &Keypair::new())
+            .start()
+            .await
+            .unwrap();
+        let other_chain = ConsensusNetConfig {
+            chain_id: [9u8; 32],
+            ..cfg.clone()
+        };
+
+        let msg = ConsensusNetMessage::BlockRequest { height: 3 };
+        let result = send_message(
+            handle.local_addr,
+            &msg,
+            cfg.max_message_size,
+            &new_handshaker(&other_chain),
+        )
+        .await;
+        assert!(matches!(result, Err(ConsensusNetError::Transport(_))));
+        assert!(handle.inbound_rx.try_recv().is_err());
+
+        // Our side of the check rejects a listener on another chain too.
+        let mut stranger = TransportListener::new(other_chain, &Keypair::new())
+            .start()
+            .await
+            .unwrap();
+        let mut stream = TcpStream::connect(stranger.local_addr).await.unwrap();
+        stream
+            .write_all(&new_handshaker(&cfg).hello().encode())
+            .await
+            .unwrap();
+        let mut reply = [0u8; HANDSHAKE_LEN];
+        assert!(stream.read_exact(&mut reply).await.is_err());
+        assert!(stranger.inbound_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_wrong_version_closes_connection() {
+        let cfg = ConsensusNetConfig::dev_default();
+        let mut handle = TransportListener::new(cfg.clone(), &Keypair::new())
+            .start()
+            .await
+            .unwrap();
+
+        let future = Handshake::new(
+            Keypair::new().pubkey(),
+            cfg.chain_id,
+            MAX_SUPPORTED_VERSION + 1,
+        );
+        assert!(matches!(
+            future.verify(&cfg.chain_id, cfg.min_protocol_version),
+            Err(HandshakeError::IncompatibleVersion { .. })
+        ));
+        let mut stream = TcpStream::connect(handle.local_addr).await.unwrap();
+        stream.write_all(&future.encode()).await.unwrap();
+
+        // The listener hangs up without answering and never reads the frame.
+        let mut reply = [0u8; HANDSHAKE_LEN];
+        assert!(stream.read_exact(&mut reply).await.is_err());
+        let msg = ConsensusNetMessage::BlockRequest { height: 3 };
+        let _ = send_on_stream(&mut stream, &msg, cfg.max_message_size).await;
+        assert!(tokio::time::timeout(
+            std::time::Duration::from_millis(200),
+            handle.inbound_rx.recv()
+        )
+        .await
+        .is_err());
+    }
 }
@@ -0,0 +1,838 @@
+//! QUIC transport for consensus message delivery.
+//!
+//! An alternative to the TCP transport in [`crate::transport`] with the same
+//! shape: [`QuicTransport::start`] plays the role of
+//! [`TransportListener::start`](crate::transport::TransportListener::start)
+//! and yields a [`TransportHandle`], while [`QuicTransport::send_message`] and
+//! [`QuicTransport::broadcast_message`] mirror the TCP send helpers.
+//!
+//! ## Identity and authentication
+//!
+//! Each node presents a self-signed certificate whose public key is its
+//! validator identity pubkey.  Both sides of a handshake extract the peer's
+//! pubkey from its certificate and reject it unless it belongs to the known
+//! validator set; the TLS 1.3 `CertificateVerify` proves possession of the
+//! matching private key.
+//!
+//! ## Wire format
+//!
+//! Every message travels on its own unidirectional stream, so a large block
+//! never delays the votes queued behind it.  The stream carries exactly one
+//! frame in the same format as the TCP transport: a [`FrameHeader`] followed
+//! by the bincode-encoded [`ConsensusNetMessage`].
+//!
+//! ## Compression
+//!
+//! A node with [`ConsensusNetConfig::enable_compression`] set offers
+//! [`ALPN_CONSENSUS_ZSTD_PROTOCOL_ID`] ahead of the plain protocol.  Frames
+//! on a connection are zstd-compressed only if both ends offered it, so a
+//! compressing node falls back to plain frames for peers that did not.
+
+use {
+    crate::{
+        config::ConsensusNetConfig,
+        error::{ConsensusNetError, Result},
+        message::{ConsensusNetMessage, EncodedFrame, FrameHeader, FRAME_HEADER_LEN},
+        transport::{
+            record_bytes_sent, serialize_failures, InboundContext, InboundMessage, TransportHandle,
+        },
+    },
+    dashmap::DashMap,
+    futures::future::join_all,
+    log::{debug, info, warn},
+    quinn::{
+        crypto::rustls::{HandshakeData, QuicClientConfig, QuicServerConfig},
+        ClientConfig, Connection, Endpoint, IdleTimeout, ReadToEndError, ServerConfig,
+        TransportConfig,
+    },
+    rustls::{
+        client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider},
+        pki_types::{CertificateDer, ServerName, UnixTime},
+        server::danger::{ClientCertVerified, ClientCertVerifier},
+        CertificateError, DigitallySignedStruct, DistinguishedName, SignatureScheme,
+    },
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    solana_tls_utils::{
+        crypto_provider, get_pubkey_from_tls_certificate, new_dummy_x509_certificate,
+        socket_addr_to_quic_server_name,
+    },
+    std::{
+        collections::HashSet,
+        net::SocketAddr,
+        sync::{Arc, RwLock},
+        time::Duration,
+    },
+    trv1_monitoring::TRv1Metrics,
+};
+
+/// ALPN protocol identifier for TRv1 consensus traffic.
+pub const ALPN_CONSENSUS_PROTOCOL_ID: &[u8] = b"trv1-consensus";
+
+/// ALPN protocol identifier for TRv1 consensus traffic with zstd-compressed
+/// frames.
+pub const ALPN_CONSENSUS_ZSTD_PROTOCOL_ID: &[u8] = b"trv1-consensus-zstd";
+
+/// Maximum concurrent inbound unidirectional streams per connection.
+const MAX_CONCURRENT_UNI_STREAMS: u32 = 1_024;
+
+/// Validators allowed to complete a handshake, shared with the verifiers so
+/// that epoch-boundary updates apply to new connections immediately.
+type ValidatorAllowList = Arc<RwLock<HashSet<Pubkey>>>;
+
+/// QUIC endpoint that both accepts and initiates consensus connections.
+pub struct QuicTransport {
+    endpoint: Endpoint,
+    identity: Pubkey,
+    validators: ValidatorAllowList,
+    /// Outbound connections, reused across messages.
+    connections: DashMap<SocketAddr, Connection>,
+    max_message_size: usize,
+    enable_compression: bool,
+    /// Where to record bytes sent, if anywhere.
+    metrics: Option<Arc<TRv1Metrics>>,
+}
+
+impl QuicTransport {
+    /// Bind a QUIC endpoint on `config.bind_addr` and start accepting
+    /// connections from members of `validators`.
+    ///
+    /// Returns the transport together with a [`TransportHandle`] whose
+    /// `inbound_rx` yields every successfully decoded message.  Traffic
+    /// counters are recorded in `metrics`, if given.  Must be called from
+    /// within a Tokio runtime.
+    pub async fn start(
+        config: &ConsensusNetConfig,
+        keypair: &Keypair,
+        validators: impl IntoIterator<Item = Pubkey>,
+        metrics: Option<Arc<TRv1Metrics>>,
+    ) -> Result<(Self, TransportHandle)> {
+        let validators: ValidatorAllowList =
+            Arc::new(RwLock::new(validators.into_iter().collect()));
+        let verifier = Arc::new(ValidatorCertVerifier {
+            validators: validators.clone(),
+            provider: Arc::new(crypto_provider()),
+        });
+        let transport_config = Arc::new(transport_config(config)?);
+
+        let mut endpoint = Endpoint::server(
+            server_config(
+                keypair,
+                verifier.clone(),
+                transport_config.clone(),
+                config.enable_compression,
+            )?,
+            config.bind_addr,
+        )?;
+        endpoint.set_default_client_config(client_config(
+            keypair,
+            verifier,
+            transport_config,
+            config.enable_compression,
+        )?);
+        let local_addr = endpoint.local_addr()?;
+        info!("consensus QUIC transport listening on {}", local_addr);
+
+        let (context, handle) = InboundContext::new(config, metrics.clone(), local_addr);
+        tokio::spawn(Self::accept_loop(endpoint.clone(), context));
+
+        let transport = Self {
+            endpoint,
+            identity: keypair.pubkey(),
+            validators,
+            connections: DashMap::new(),
+            max_message_size: config.max_message_size,
+            enable_compression: config.enable_compression,
+            metrics,
+        };
+        Ok((transport, handle))
+    }
+
+    /// Returns this node's identity pubkey, as presented in its certificate.
+    pub fn identity(&self) -> &Pubkey {
+        &self.identity
+    }
+
+    /// Returns the local address the endpoint is bound to.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.endpoint.local_addr()?)
+    }
+
+    /// Replace the validators allowed to connect (e.g. at an epoch boundary).
+    ///
+    /// Established connections are not torn down; new handshakes are checked
+    /// against the new set.
+    pub fn set_validators(&self, validators: impl IntoIterator<Item = Pubkey>) {
+        *self.validators.write().unwrap() = validators.into_iter().collect();
+    }
+
+    /// Send a single message to `addr` on a new unidirectional stream,
+    /// connecting first if no connection to `addr` is open.
+    pub async fn send_message(&self, addr: SocketAddr, msg: &ConsensusNetMessage) -> Result<()> {
+        let frames = self.encode(msg)?;
+        self.send_frames(addr, &frames).await
+    }
+
+    /// Broadcast a message to multiple addresses concurrently.
+    ///
+    /// Returns the list of addresses where sending failed.
+    pub async fn broadcast_message(
+        &self,
+        addrs: &[SocketAddr],
+        msg: &ConsensusNetMessage,
+    ) -> Vec<(SocketAddr, ConsensusNetError)> {
+        let frames = match self.encode(msg) {
+            Ok(frames) => frames,
+            Err(e) => return serialize_failures(addrs, &e),
+        };
+
+        let sends = addrs.iter().map(|&addr| {
+            let frames = &frames;
+            async move { (addr, self.send_frames(addr, frames).await) }
+        });
+        let mut failures = Vec::new();
+        for (addr, result) in join_all(sends).await {
+            if let Err(e) = result {
+                failures.push((addr, e));
+            }
+        }
+        failures
+    }
+
+    /// Close every connection and stop accepting new ones.
+    pub fn close(&self) {
+        self.endpoint.close(0u32.into(), b"shutdown");
+        self.connections.clear();
+    }
+
+    /// Encode `msg` once for peers without compression and, if enabled,
+    /// once for peers with it.
+    fn encode(&self, msg: &ConsensusNetMessage) -> Result<OutboundFrames> {
+        let plain = msg.encode_frame(self.max_message_size, false)?;
+        let compressed = if self.enable_compression {
+            Some(msg.encode_frame(self.max_message_size, true)?)
+        } else {
+            None
+        };
+        Ok(OutboundFrames { plain, compressed })
+    }
+
+    async fn send_frames(&self, addr: SocketAddr, frames: &OutboundFrames) -> Result<()> {
+        let connection = self.connection(addr).await?;
+        let (connection, mut stream) = match connection.open_uni().await {
+            Ok(stream) => (connection, stream),
+            Err(e) => {
+                // The cached connection died; reconnect once.
+                debug!("QUIC connection to {} lost ({}), reconnecting", addr, e);
+                self.connections.remove(&addr);
+                let connection = self.connection(addr).await?;
+                let stream = connection
+                    .open_uni()
+                    .await
+                    .map_err(|e| ConsensusNetError::ConnectionFailed(addr, e.to_string()))?;
+                (connection, stream)
+            }
+        };
+        let frame = match &frames.compressed {
+            Some(compressed) if negotiated_compression(&connection) => compressed,
+            _ => &frames.plain,
+        };
+        stream
+            .write_all(&frame.bytes)
+            .await
+            .map_err(|e| ConsensusNetError::Quic(e.to_string()))?;
+        stream
+            .finish()
+            .map_err(|e| ConsensusNetError::Quic(e.to_string()))?;
+        record_bytes_sent(self.metrics.as_deref(), frame, 1);
+        Ok(())
+    }
+
+    /// The open connection to `addr`, establishing one if needed.
+    async fn connection(&self, addr: SocketAddr) -> Result<Connection> {
+        if let Some(connection) = self.connections.get(&addr) {
+            if connection.close_reason().is_none() {
+                return Ok(connection.clone());
+            }
+        }
+        let connection = self
+            .endpoint
+            .connect(addr, &socket_addr_to_quic_server_name(addr))
+            .map_err(|e| ConsensusNetError::ConnectionFailed(addr, e.to_string()))?
+            .await
+            .map_err(|e| ConsensusNetError::ConnectionFailed(addr, e.to_string()))?;
+        self.connections.insert(addr, connection.clone());
+        Ok(connection)
+    }
+
+    async fn accept_loop(endpoint: Endpoint, context: InboundContext) {
+        while let Some(incoming) = endpoint.accept().await {
+            let context = context.clone();
+            tokio::spawn(async move {
+                let addr = incoming.remote_address();
+                match incoming.await {
+                    Ok(connection) => {
+                        debug!("accepted consensus QUIC connection from {}", addr);
+                        Self::handle_connection(connection, addr, context).await;
+                    }
+                    Err(e) => {
+                        warn!("QUIC handshake with {} failed: {}", addr, e);
+                    }
+                }
+            });
+        }
+        debug!("QUIC endpoint closed, stopping accept loop");
+    }
+
+    /// Read one frame per inbound unidirectional stream until the
+    /// connection closes.
+    ///
+    /// Streams beyond the peer's rate limit are stopped unread.
+    /// Rejected and rate-limited frames are reported against the validator
+    /// identity the peer authenticated with.
+    async fn handle_connection(connection: Connection, addr: SocketAddr, context: InboundContext) {
+        let peer = peer_identity(&connection);
+        let max_message_size = context.max_message_size;
+        loop {
+            let mut stream = match connection.accept_uni().await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    debug!("QUIC connection to {} closed: {}", addr, e);
+                    break;
+                }
+            };
+            if !context.try_admit(addr, peer) {
+                let _ = stream.stop(0u32.into());
+                context.report_rate_limited(addr, peer);
+                continue;
+            }
+            let context = context.clone();
+            tokio::spawn(async move {
+                let max_frame_size = max_message_size.saturating_add(FRAME_HEADER_LEN);
+                let frame = match stream.read_to_end(max_frame_size).await {
+                    Ok(frame) => frame,
+                    Err(ReadToEndError::TooLong) => {
+                        warn!("peer {} sent oversized frame, dropping stream", addr);
+                        context.report_invalid(addr, peer, "oversized frame".to_string());
+                        return;
+                    }
+                    Err(e) => {
+                        warn!("stream read error from {}: {}", addr, e);
+                        return;
+                    }
+                };
+                match decode_frame(&frame, max_message_size) {
+                    Ok(message) => {
+                        debug!("received {} from {}", message.kind(), addr);
+                        if context
+                            .tx
+                            .send(InboundMessage {
+                                message,
+                                from: addr,
+                            })
+                            .await
+                            .is_err()
+                        {
+                            debug!("inbound channel closed, dropping message from {}", addr);
+                        }
+                    }
+                    Err(e) => {
+                        warn!("deserialization error from {}: {}", addr, e);
+                        context.report_invalid(addr, peer, e.to_string());
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// A message encoded for both kinds of peer.
+struct OutboundFrames {
+    plain: EncodedFrame,
+    /// Present only if this node has compression enabled.
+    compressed: Option<EncodedFrame>,
+}
+
+/// Whether both ends of `connection` agreed to compressed frames.
+fn negotiated_compression(connection: &Connection) -> bool {
+    connection
+        .handshake_data()
+        .and_then(|data| data.downcast::<HandshakeData>().ok())
+        .and_then(|data| data.protocol)
+        .is_some_and(|protocol| protocol == ALPN_CONSENSUS_ZSTD_PROTOCOL_ID)
+}
+
+/// The validator identity `connection`'s peer authenticated with.
+fn peer_identity(connection: &Connection) -> Option<Pubkey> {
+    let certs = connection
+        .peer_identity()?
+        .downcast::<Vec<CertificateDer<'static>>>()
+        .ok()?;
+    get_pubkey_from_tls_certificate(certs.first()?)
+}
+
+/// Decode one stream's worth of bytes: a header and the payload it describes.
+fn decode_frame(frame: &[u8], max_message_size: usize) -> Result<ConsensusNetMessage> {
+    let (header, payload) = frame
+        .split_first_chunk::<FRAME_HEADER_LEN>()
+        .ok_or_else(|| ConsensusNetError::InvalidMessage("truncated frame header".to_string()))?;
+    let header = FrameHeader::decode(header)?;
+    if header.len != payload.len() {
+        return Err(ConsensusNetError::InvalidMessage(format!(
+            "frame header declares {} bytes, stream carried {}",
+            header.len,
+            payload.len()
+        )));
+    }
+    ConsensusNetMessage::decode_payload(&header, payload, max_message_size)
+}
+
+/// ALPN protocols to offer, most preferred first.
+fn alpn_protocols(enable_compression: bool) -> Vec<Vec<u8>> {
+    let mut protocols = Vec::with_capacity(2);
+    if enable_compression {
+        protocols.push(ALPN_CONSENSUS_ZSTD_PROTOCOL_ID.to_vec());
+    }
+    protocols.push(ALPN_CONSENSUS_PROTOCOL_ID.to_vec());
+    protocols
+}
+
+fn transport_config(config: &ConsensusNetConfig) -> Result<TransportConfig> {
+    let mut transport = TransportConfig::default();
+    let idle_timeout = IdleTimeout::try_from(Duration::from_secs(config.peer_timeout_secs))
+        .map_err(|e| ConsensusNetError::Quic(e.to_string()))?;
+    transport.max_idle_timeout(Some(idle_timeout));
+    transport.keep_alive_interval(Some(Duration::from_millis(config.heartbeat_interval_ms)));
+    transport.max_concurrent_uni_streams(MAX_CONCURRENT_UNI_STREAMS.into());
+    transport.max_concurrent_bidi_streams(0u32.into());
+    Ok(transport)
+}
+
+fn server_config(
+    keypair: &Keypair,
+    verifier: Arc<ValidatorCertVerifier>,
+    transport: Arc<TransportConfig>,
+    enable_compression: bool,
+) -> Result<ServerConfig> {
+    let (cert, key) = new_dummy_x509_certificate(keypair);
+    let mut tls = rustls::ServerConfig::builder_with_provider(Arc::new(crypto_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(tls_error)?
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(vec![cert], key)
+        .map_err(tls_error)?;
+    tls.alpn_protocols = alpn_protocols(enable_compression);
+    let crypto = QuicServerConfig::try_from(tls).map_err(|e| ConsensusNetError::Quic(e.to_string()))?;
+    let mut server = ServerConfig::with_crypto(Arc::new(crypto));
+    server.transport_config(transport);
+    Ok(server)
+}
+
+fn client_config(
+    keypair: &Keypair,
+    verifier: Arc<ValidatorCertVerifier>,
+    transport: Arc<TransportConfig>,
+    enable_compression: bool,
+) -> Result<ClientConfig> {
+    let (cert, key) = new_dummy_x509_certificate(keypair);
+    let mut tls = rustls::ClientConfig::builder_with_provider(Arc::new(crypto_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(tls_error)?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier)
+        .with_client_auth_cert(vec![cert], key)
+        .map_err(tls_error)?;
+    tls.alpn_protocols = alpn_protocols(enable_compression);
+    let crypto = QuicClientConfig::try_from(tls).map_err(|e| ConsensusNetError::Quic(e.to_string()))?;
+    let mut client = ClientConfig::new(Arc::new(crypto));
+    client.transport_config(transport);
+    Ok(client)
+}
+
+fn tls_error(e: rustls::Error) -> ConsensusNetError {
+    ConsensusNetError::Quic(format!("TLS configuration: {e}"))
+}
+
+// ── Certificate verification ────────────────────────────────────────────────
+
+/// Accepts a peer certificate only if its public key is a known validator.
+///
+/// Used for both directions of the handshake.  The certificate itself is
+/// self-signed and carries no chain; the TLS signature checks bind the
+/// handshake to the certificate's key.
+#[derive(Debug)]
+struct ValidatorCertVerifier {
+    validators: ValidatorAllowList,
+    provider: Arc<CryptoProvider>,
+}
+
+impl ValidatorCertVerifier {
+    fn verify_identity(&self, cert: &CertificateDer<'_>) -> std::result::Result<(), rustls::Error> {
+        let pubkey = get_pubkey_from_tls_certificate(cert)
+            .ok_or(rustls::Error::InvalidCertificate(CertificateError::BadEncoding))?;
+        if !self.validators.read().unwrap().contains(&pubkey) {
+            debug!("rejecting QUIC handshake from non-validator {}", pubkey);
+            return Err(rustls::Error::InvalidCertificate(
+                CertificateError::ApplicationVerificationFailure,
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ServerCertVerifier for ValidatorCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        self.verify_identity(end_entity)?;
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+impl ClientCertVerifier for ValidatorCertVerifier {
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _now: UnixTime,
+    ) -> std::result::Result<ClientCertVerified, rustls::Error> {
+        self.verify_identity(end_entity)?;
+        Ok(ClientCertVerified::assertion())
+    }
+
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        &[]
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+
+    fn offer_client_auth(&self) -> bool {
+        true
+    }
+
+    fn client_auth_mandatory(&self) -> bool {
+        true
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{message::PeerInfo, peer_manager::PeerManager},
+        agave_votor_messages::{consensus_message::VoteMessage, vote::Vote},
+        solana_bls_signatures::Signature as BLSSignature,
+        solana_hash::Hash,
+    };
+
+    async fn start(keypair: &Keypair, validators: &[Pubkey]) -> (QuicTransport, TransportHandle) {
+        QuicTransport::start(
+            &ConsensusNetConfig::dev_default(),
+            keypair,
+            validators.iter().copied(),
+            None,
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn start_compressing(
+        keypair: &Keypair,
+        validators: &[Pubkey],
+        enable_compression: bool,
+        metrics: Option<Arc<TRv1Metrics>>,
+    ) -> (QuicTransport, TransportHandle) {
+        let config = ConsensusNetConfig {
+            enable_compression,
+            ..ConsensusNetConfig::dev_default()
+        };
+        QuicTransport::start(&config, keypair, validators.iter().copied(), metrics)
+            .await
+            .unwrap()
+    }
+
+    async fn recv(handle: &mut TransportHandle) -> Option<InboundMessage> {
+        tokio::time::timeout(Duration::from_secs(2), handle.inbound_rx.recv())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    #[tokio::test]
+    async fn test_quic_prevote_roundtrip() {
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let validators = [alice.pubkey(), bob.pubkey()];
+        let (alice_transport, mut alice_handle) = start(&alice, &validators).await;
+        let (bob_transport, mut bob_handle) = start(&bob, &validators).await;
+
+        // A notarization vote is the prevote of the votor consensus protocol.
+        let prevote = ConsensusNetMessage::from_vote(VoteMessage {
+            vote: Vote::new_notarization_vote(7, Hash::new_unique()),
+            signature: BLSSignature::default(),
+            rank: 1,
+        });
+        alice_transport
+            .send_message(bob_handle.local_addr, &prevote)
+            .await
+            .unwrap();
+        let received = recv(&mut bob_handle).await.expect("bob received nothing");
+        assert_eq!(received.message, prevote);
+
+        // And back the other way, over Bob's own outbound connection.
+        let failures = bob_transport
+            .broadcast_message(&[alice_handle.local_addr], &received.message)
+            .await;
+        assert!(failures.is_empty(), "broadcast had failures: {:?}", failures);
+        let echoed = recv(&mut alice_handle).await.expect("alice received nothing");
+        assert_eq!(echoed.message, prevote);
+    }
+
+    #[tokio::test]
+    async fn test_quic_streams_are_independent() {
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let validators = [alice.pubkey(), bob.pubkey()];
+        let (alice_transport, _alice_handle) = start(&alice, &validators).await;
+        let (_bob_transport, mut bob_handle) = start(&bob, &validators).await;
+
+        for height in 0..10 {
+            alice_transport
+                .send_message(bob_handle.local_addr, &ConsensusNetMessage::BlockRequest { height })
+                .await
+                .unwrap();
+        }
+        let mut heights = Vec::new();
+        for _ in 0..10 {
+            match recv(&mut bob_handle).await.expect("missing message").message {
+                ConsensusNetMessage::BlockRequest { height } => heights.push(height),
+                other => panic!("unexpected message {other:?}"),
+            }
+        }
+        heights.sort_unstable();
+        assert_eq!(heights, (0..10).collect::<Vec<_>>());
+        // All ten messages shared one connection.
+        assert_eq!(alice_transport.connections.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_quic_rejects_unknown_server() {
+        let alice = Keypair::new();
+        let mallory = Keypair::new();
+        // Alice does not know Mallory; Mallory accepts anyone Alice presents.
+        let (alice_transport, _alice_handle) = start(&alice, &[alice.pubkey()]).await;
+        let (_mallory_transport, mallory_handle) =
+            start(&mallory, &[alice.pubkey(), mallory.pubkey()]).await;
+
+        let result = alice_transport
+            .send_message(
+                mallory_handle.local_addr,
+                &ConsensusNetMessage::BlockRequest { height: 1 },
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(ConsensusNetError::ConnectionFailed(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_quic_rejects_unknown_client() {
+        let bob = Keypair::new();
+        let mallory = Keypair::new();
+        let (bob_transport, mut bob_handle) = start(&bob, &[bob.pubkey()]).await;
+        let (mallory_transport, _mallory_handle) =
+            start(&mallory, &[bob.pubkey(), mallory.pubkey()]).await;
+
+        // The client may not learn of the rejection before sending, but the
+        // message must never reach Bob.
+        let _ = mallory_transport
+            .send_message(
+                bob_handle.local_addr,
+                &ConsensusNetMessage::BlockRequest { height: 1 },
+            )
+            .await;
+        assert!(
+            tokio::time::timeout(Duration::from_millis(500), bob_handle.inbound_rx.recv())
+                .await
+                .is_err()
+        );
+
+        // Once admitted to the validator set, Mallory gets through.
+        bob_transport.set_validators([bob.pubkey(), mallory.pubkey()]);
+        mallory_transport.connections.clear();
+        mallory_transport
+            .send_message(
+                bob_handle.local_addr,
+                &ConsensusNetMessage::BlockRequest { height: 2 },
+            )
+            .await
+            .unwrap();
+        let received = recv(&mut bob_handle).await.expect("bob received nothing");
+        assert_eq!(
+            received.message,
+            ConsensusNetMessage::BlockRequest { height: 2 }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quic_compression_negotiation() {
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let carol = Keypair::new();
+        let validators = [alice.pubkey(), bob.pubkey(), carol.pubkey()];
+        let metrics = Arc::new(TRv1Metrics::new());
+        let (alice_transport, _alice_handle) =
+            start_compressing(&alice, &validators, true, Some(metrics.clone())).await;
+        let (_bob_transport, mut bob_handle) =
+            start_compressing(&bob, &validators, true, None).await;
+        let (_carol_transport, mut carol_handle) =
+            start_compressing(&carol, &validators, false, None).await;
+
+        let update = ConsensusNetMessage::ValidatorSetUpdate {
+            epoch: 1,
+            validators: vec![
+                crate::message::ValidatorInfo {
+                    pubkey: alice.pubkey(),
+                    stake_weight: 1,
+                    consensus_addr: bob_handle.local_addr,
+                };
+                64
+            ],
+        };
+        let failures = alice_transport
+            .broadcast_message(&[bob_handle.local_addr, carol_handle.local_addr], &update)
+            .await;
+        assert!(failures.is_empty(), "broadcast had failures: {:?}", failures);
+        assert_eq!(recv(&mut bob_handle).await.unwrap().message, update);
+        assert_eq!(recv(&mut carol_handle).await.unwrap().message, update);
+
+        // Bob agreed to compression; Carol did not and got a plain frame.
+        let to_bob = alice_transport.connections.get(&bob_handle.local_addr).unwrap();
+        assert!(negotiated_compression(&to_bob));
+        let to_carol = alice_transport.connections.get(&carol_handle.local_addr).unwrap();
+        assert!(!negotiated_compression(&to_carol));
+
+        let frame = update.encode_frame(1_048_576, true).unwrap();
+        let uncompressed = frame.uncompressed_len as u64;
+        assert_eq!(metrics.total_bytes_sent_uncompressed.get(), 2 * uncompressed);
+        assert_eq!(
+            metrics.total_bytes_sent_compressed.get(),
+            frame.payload_len() as u64 + uncompressed
+        );
+        assert!(metrics.compression_ratio() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_quic_invalid_message_attributed_to_peer() {
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let validators = [alice.pubkey(), bob.pubkey()];
+        let (alice_transport, _alice_handle) = start(&alice, &validators).await;
+        let (_bob_transport, mut bob_handle) = start(&bob, &validators).await;
+
+        let connection = alice_transport
+            .connection(bob_handle.local_addr)
+            .await
+            .unwrap();
+        let mut stream = connection.open_uni().await.unwrap();
+        stream.write_all(&[0xFF; 64]).await.unwrap();
+        stream.finish().unwrap();
+
+        let report = tokio::time::timeout(Duration::from_secs(2), bob_handle.invalid_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.peer, Some(alice.pubkey()));
+        assert!(bob_handle.inbound_rx.try_recv().is_err());
+
+        // The report counts against Alice's reputation on Bob's side.
+        let mut peers = PeerManager::new(ConsensusNetConfig::dev_default());
+        peers
+            .add_peer(PeerInfo {
+                pubkey: alice.pubkey(),
+                addr: report.from,
+                stake_weight: 1,
+                is_active_validator: true,
+            })
+            .unwrap();
+        peers.record_invalid_report(&report);
+        let reputation = &peers.get_peer(&alice.pubkey()).unwrap().reputation;
+        assert_eq!(reputation.invalid_messages, 1);
+    }
+}
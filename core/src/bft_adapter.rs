@@ -43,16 +43,18 @@ mod inner {
         solana_keypair::Keypair,
         solana_pubkey::Pubkey,
         solana_runtime::bank_forks::BankForks,
-        solana_signature::Signature,
         solana_signer::Signer,
         std::sync::{Arc, RwLock},
         trv1_consensus_bft::{
             config::BftConfig,
-            engine::{ConsensusEngine, EngineOutput},
+            engine::{ConsensusEngine, EngineOutput, SyncRequest},
+            evidence::DoubleSignEvidence,
             timeout::TimeoutScheduler,
             types::{ConsensusMessage, ConsensusStep, ProposedBlock},
             validator_set::ValidatorSet,
         },
+        trv1_consensus_net::message::BlockData,
+        trv1_monitoring::TRv1Metrics,
     };
 
     /// Result of processing a consensus event through the adapter.
@@ -64,6 +66,9 @@ mod inner {
         pub block_committed: bool,
         /// The hash of the committed block, if any.
         pub committed_hash: Option<Hash>,
+        /// Heights skipped since the last commit, to fetch from peers
+        /// before handling further consensus messages.
+        pub sync_request: Option<SyncRequest>,
     }
 
     impl AdapterOutput {
@@ -72,10 +77,11 @@ mod inner {
                 messages: Vec::new(),
                 block_committed: false,
                 committed_hash: None,
+                sync_request: None,
             }
         }
 
-        fn from_engine_output(output: EngineOutput) -> Self {
+        pub(crate) fn from_engine_output(output: EngineOutput) -> Self {
             Self {
                 block_committed: output.committed_block.is_some(),
                 committed_hash: output
@@ -83,6 +89,7 @@ mod inner {
                     .as_ref()
                     .map(|cb| cb.block.hash()),
                 messages: output.messages,
+                sync_request: output.sync_request,
             }
         }
     }
@@ -105,6 +112,9 @@ mod inner {
         block_producer: Arc<BlockProducer>,
         /// Hash of the last committed block (used as parent for new blocks).
         last_committed_hash: Hash,
+        /// Verified double-sign evidence waiting to go on-chain in one of
+        /// our proposals.
+        pending_evidence: Vec<DoubleSignEvidence>,
     }
 
     impl BftAdapter {
@@ -116,9 +126,9 @@ mod inner {
             bank_forks: Arc<RwLock<BankForks>>,
             block_producer: Arc<BlockProducer>,
         ) -> Self {
-            let identity = validator_keypair.pubkey();
             let timeout_scheduler = TimeoutScheduler::new(config.clone());
-            let engine = ConsensusEngine::new(config, identity, validator_set);
+            let engine =
+                ConsensusEngine::new(config, validator_keypair.insecure_clone(), validator_set);
 
             // Seed the last committed hash from the working bank
             let last_committed_hash = {
@@ -133,6 +143,7 @@ mod inner {
                 bank_forks,
                 block_producer,
                 last_committed_hash,
+                pending_evidence: Vec::new(),
             }
         }
 
@@ -141,7 +152,9 @@ mod inner {
         /// Start consensus for a new height.
         ///
         /// Resets the engine state, starts the propose timeout, and if we are
-        /// the proposer, creates and returns a proposal message.
+        /// the proposer, creates and returns a proposal message.  No block is
+        /// proposed while the output reports skipped heights: our ledger is
+        /// behind, so the height is started again once they are synced.
         pub fn start_height(&mut self, height: u64) -> AdapterOutput {
             info!("BftAdapter: starting height {height}");
             let output = self.engine.start_new_height(height);
@@ -153,7 +166,9 @@ mod inner {
             let mut adapter_output = AdapterOutput::from_engine_output(output);
 
             // If we are the proposer for this (height, round), create a block
-            if self.engine.is_proposer(height, self.engine.round()) {
+            if adapter_output.sync_request.is_none()
+                && self.engine.is_proposer(height, self.engine.round())
+            {
                 info!("BftAdapter: we are the proposer for h={height} r={}", self.engine.round());
                 match self.produce_block(height) {
                     Ok(proposal_msg) => {
@@ -204,6 +219,18 @@ mod inner {
         /// service loop.
         pub fn check_timeouts(&mut self) -> AdapterOutput {
             if let Some(expired_step) = self.timeout_scheduler.check_expired() {
+                // A timeout armed for an earlier round is stale once the
+                // engine has moved on (e.g. skipped ahead on future-round
+                // votes); re-arm it for the current round instead.
+                let round = self.engine.round();
+                if self.timeout_scheduler.current_round() != round {
+                    match self.engine.step() {
+                        ConsensusStep::Commit => self.timeout_scheduler.cancel(),
+                        step => self.timeout_scheduler.start(step, round),
+                    }
+                    return AdapterOutput::empty();
+                }
+
                 info!(
                     "BftAdapter: timeout expired for {:?} at h={} r={}",
                     expired_step,
@@ -259,6 +286,32 @@ mod inner {
             self.timeout_scheduler.remaining()
         }
 
+        /// Record consensus rounds, finality time and missed proposals in
+        /// `metrics`.
+        pub fn with_metrics(mut self, metrics: Arc<TRv1Metrics>) -> Self {
+            self.engine = self.engine.with_metrics(metrics);
+            self
+        }
+
+        /// Record `height` as the last committed block, e.g. the ledger's
+        /// root when the validator restarts.
+        pub fn set_last_committed_height(&mut self, height: u64) {
+            self.engine.set_last_committed_height(height);
+        }
+
+        /// Commit a block at `height` that the cluster committed while we
+        /// were behind and that was fetched from a peer.
+        pub fn commit_synced_block(
+            &mut self,
+            height: u64,
+            block: &BlockData,
+        ) -> crate::block_producer::Result<Hash> {
+            let bank_hash = self.block_producer.execute_synced_block(height, block)?;
+            self.last_committed_hash = block.block_hash;
+            self.engine.set_last_committed_height(height);
+            Ok(bank_hash)
+        }
+
         /// Update the validator set (e.g., at epoch boundaries).
         pub fn update_validator_set(&mut self, validator_set: ValidatorSet) {
             info!(
@@ -268,6 +321,51 @@ mod inner {
             self.engine.update_validator_set(validator_set);
         }
 
+        /// Take the double-sign evidence collected by the engine.
+        pub fn drain_evidence(&mut self) -> Vec<DoubleSignEvidence> {
+            self.engine.evidence_mut().drain_evidence()
+        }
+
+        /// Drain collected double-sign evidence into the queue that our next
+        /// proposal puts on-chain.
+        ///
+        /// Nothing is slashed here: the penalty is applied by every validator
+        /// when it executes a committed block carrying the evidence (see
+        /// [`crate::block_producer::apply_evidence`]).  Evidence that fails
+        /// [`DoubleSignEvidence::verify`] or is already queued is dropped.
+        /// Returns the number of pieces of evidence queued.
+        pub fn queue_evidence(&mut self) -> usize {
+            let mut queued = 0;
+            for ev in self.drain_evidence() {
+                if !ev.verify() {
+                    warn!(
+                        "BftAdapter: dropping unverifiable double-sign evidence against {}",
+                        ev.voter
+                    );
+                    continue;
+                }
+                if self
+                    .pending_evidence
+                    .iter()
+                    .any(|pending| same_offense(pending, &ev))
+                {
+                    continue;
+                }
+                info!(
+                    "BftAdapter: queued double-sign evidence against {} at h={} r={}",
+                    ev.voter, ev.height, ev.round
+                );
+                self.pending_evidence.push(ev);
+                queued += 1;
+            }
+            queued
+        }
+
+        /// Evidence waiting to go on-chain in one of our proposals.
+        pub fn pending_evidence(&self) -> &[DoubleSignEvidence] {
+            &self.pending_evidence
+        }
+
         // -- Accessors --
 
         /// Returns the current consensus height.
@@ -313,39 +411,39 @@ mod inner {
                 height,
                 self.last_committed_hash,
                 identity,
+                self.pending_evidence.clone(),
             )?;
 
-            // In production we'd sign the block hash with the validator keypair.
-            // For now, use a default signature.
-            let proposal = ConsensusMessage::Proposal {
-                height,
-                round: self.engine.round(),
-                block,
-                proposer: identity,
-                signature: Signature::default(),
-                valid_round: self.engine.state().valid_round,
-            };
-
-            Ok(proposal)
+            Ok(self
+                .engine
+                .make_proposal(block, self.engine.state().valid_round))
         }
 
         /// Process engine output: commit blocks and translate to adapter output.
         fn process_engine_output(&mut self, output: EngineOutput) -> AdapterOutput {
             if let Some(ref committed_block) = output.committed_block {
                 // Commit the block via the block producer
-                match self.block_producer.execute_block(committed_block) {
+                match self
+                    .block_producer
+                    .execute_block(committed_block, self.engine.validator_set())
+                {
                     Ok(bank_hash) => {
                         info!(
                             "BftAdapter: committed block at height {} (bank_hash: {bank_hash})",
                             committed_block.block.height,
                         );
                         self.last_committed_hash = committed_block.block.hash();
+                        // Evidence is on-chain once any committed block carries it.
+                        let included = &committed_block.block.evidence;
+                        self.pending_evidence
+                            .retain(|pending| !included.iter().any(|ev| same_offense(ev, pending)));
                         self.timeout_scheduler.cancel();
 
                         return AdapterOutput {
                             messages: output.messages,
                             block_committed: true,
                             committed_hash: Some(bank_hash),
+                            sync_request: output.sync_request,
                         };
                     }
                     Err(e) => {
@@ -360,4 +458,10 @@ mod inner {
             AdapterOutput::from_engine_output(output)
         }
     }
+
+    /// Whether two pieces of evidence are for the same offense: one
+    /// validator at one `(height, round)`.
+    fn same_offense(a: &DoubleSignEvidence, b: &DoubleSignEvidence) -> bool {
+        a.voter == b.voter && a.height == b.height && a.round == b.round
+    }
 }
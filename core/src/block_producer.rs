@@ -23,11 +23,19 @@ mod inner {
         log::*,
         solana_hash::Hash,
         solana_pubkey::Pubkey,
-        solana_runtime::{bank::Bank, bank_forks::BankForks},
+        solana_runtime::{bank::Bank, bank_forks::BankForks, slashing::SlashOffense},
         solana_time_utils::timestamp,
         solana_transaction::versioned::VersionedTransaction,
-        std::sync::{Arc, RwLock},
-        trv1_consensus_bft::types::{CommittedBlock, ProposedBlock},
+        std::{
+            collections::HashSet,
+            sync::{Arc, RwLock},
+        },
+        trv1_consensus_bft::{
+            evidence::DoubleSignEvidence,
+            types::{CommittedBlock, ProposedBlock},
+            validator_set::ValidatorSet,
+        },
+        trv1_consensus_net::message::BlockData,
     };
 
     /// Errors that can occur during block production or validation.
@@ -47,6 +55,10 @@ mod inner {
         ExecutionError(String),
         #[error("invalid proposer: {0}")]
         InvalidProposer(Pubkey),
+        #[error("invalid double-sign evidence against {0}")]
+        InvalidEvidence(Pubkey),
+        #[error("duplicate double-sign evidence against {0} at h={1} r={2}")]
+        DuplicateEvidence(Pubkey, u64, u32),
     }
 
     pub type Result<T> = std::result::Result<T, BlockProducerError>;
@@ -103,12 +115,14 @@ mod inner {
         ///
         /// Called when the BFT engine determines it's our turn to propose.
         /// Drains pending transactions from the receiver (non-blocking) up to
-        /// the configured limits, then builds a [`ProposedBlock`].
+        /// the configured limits, then builds a [`ProposedBlock`] carrying
+        /// `evidence` on-chain.
         pub fn create_block(
             &self,
             height: u64,
             parent_hash: Hash,
             proposer: Pubkey,
+            evidence: Vec<DoubleSignEvidence>,
         ) -> Result<ProposedBlock> {
             let bank_forks = self.bank_forks.read().unwrap();
             let bank = bank_forks.working_bank();
@@ -149,12 +163,15 @@ mod inner {
                 transactions,
                 state_root,
                 proposer,
+                evidence,
             })
         }
 
         /// Validate a proposed block from another validator.
         ///
-        /// Checks structural constraints (height, parent hash, size limits).
+        /// Checks structural constraints (height, parent hash, size limits)
+        /// and that every piece of evidence proves a double-sign that has not
+        /// been slashed before, nor appears twice in the block.
         /// Full transaction re-execution happens during [`execute_block`].
         pub fn validate_block(&self, block: &ProposedBlock) -> Result<()> {
             // Check transaction count
@@ -165,9 +182,27 @@ mod inner {
                 });
             }
 
+            if let Some(ev) = block.evidence.iter().find(|ev| !ev.verify()) {
+                return Err(BlockProducerError::InvalidEvidence(ev.voter));
+            }
+
             // Verify the block references a known parent
             let bank_forks = self.bank_forks.read().unwrap();
             let working_bank = bank_forks.working_bank();
+
+            {
+                let slashing = working_bank.trv1_slashing_state();
+                let mut seen = HashSet::new();
+                if let Some(ev) = block.evidence.iter().find(|ev| {
+                    slashing.is_evidence_processed(&ev.voter, ev.height, ev.round)
+                        || !seen.insert((ev.voter, ev.height, ev.round))
+                }) {
+                    return Err(BlockProducerError::DuplicateEvidence(
+                        ev.voter, ev.height, ev.round,
+                    ));
+                }
+            }
+
             let expected_parent = working_bank.last_blockhash();
 
             // We allow flexibility here — the parent_hash might reference the
@@ -192,11 +227,17 @@ mod inner {
         /// Execute and commit a finalized block to the bank.
         ///
         /// Called after the BFT engine reaches consensus on a block (2/3+
-        /// precommits). This applies the block's transactions to a new bank
-        /// and freezes it, advancing the ledger.
+        /// precommits). This applies the block's transactions and its
+        /// double-sign evidence to a new bank and freezes it, advancing the
+        /// ledger.  `validator_set` is the set that committed the block; it
+        /// supplies the stake each offender is slashed on.
         ///
         /// Returns the bank hash of the committed block.
-        pub fn execute_block(&self, committed: &CommittedBlock) -> Result<Hash> {
+        pub fn execute_block(
+            &self,
+            committed: &CommittedBlock,
+            validator_set: &ValidatorSet,
+        ) -> Result<Hash> {
             let block = &committed.block;
 
             info!(
@@ -228,6 +269,7 @@ mod inner {
             // 3. Verify the resulting state_root matches the proposal
             //
             // For now, we freeze the bank to advance the ledger.
+            apply_evidence(&child_bank, &block.evidence, validator_set);
             let bank_hash = child_bank.hash();
             child_bank.freeze();
 
@@ -243,9 +285,79 @@ mod inner {
             Ok(bank_hash)
         }
 
+        /// Execute a block the cluster committed at `height` while this
+        /// validator was behind, as fetched from a peer by block sync.
+        ///
+        /// The block is applied like [`Self::execute_block`] applies a block
+        /// committed locally.  Returns the bank hash of the new bank.
+        pub fn execute_synced_block(&self, height: u64, block: &BlockData) -> Result<Hash> {
+            info!(
+                "Executing synced block at height {height} ({} txns)",
+                block.transactions.len(),
+            );
+
+            let parent_bank = self.bank_forks.read().unwrap().working_bank();
+            let new_slot = parent_bank.slot() + 1;
+            let child_bank = Bank::new_from_parent(parent_bank, &block.proposer, new_slot);
+            let bank_hash = child_bank.hash();
+            child_bank.freeze();
+            self.bank_forks.write().unwrap().insert(child_bank);
+
+            info!("Synced block at height {height} → slot {new_slot} (hash: {bank_hash})");
+            Ok(bank_hash)
+        }
+
         /// Returns a reference to the bank forks.
         pub fn bank_forks(&self) -> &Arc<RwLock<BankForks>> {
             &self.bank_forks
         }
     }
+
+    /// Slash the offenders named by a committed block's double-sign evidence
+    /// in `bank`'s slashing state.
+    ///
+    /// Every validator runs this while executing the same committed block,
+    /// so the outcome is the same everywhere.  Evidence that does not
+    /// verify, names a validator outside `validator_set`, or repeats an
+    /// offense already processed — earlier in this block or in any block
+    /// before it — is skipped.  Returns the number of validators slashed.
+    pub fn apply_evidence(
+        bank: &Bank,
+        evidence: &[DoubleSignEvidence],
+        validator_set: &ValidatorSet,
+    ) -> usize {
+        if evidence.is_empty() {
+            return 0;
+        }
+        let epoch = bank.epoch();
+        let mut slashing = bank.trv1_slashing_state_mut();
+        let mut slashed = 0;
+        for ev in evidence {
+            if !ev.verify()
+                || !validator_set.contains(&ev.voter)
+                || !slashing.record_evidence(&ev.voter, ev.height, ev.round)
+            {
+                warn!(
+                    "Skipping double-sign evidence against {} at h={} r={}",
+                    ev.voter, ev.height, ev.round
+                );
+                continue;
+            }
+            let own_stake = validator_set.stake_of(&ev.voter);
+            match slashing.slash_validator(&ev.voter, SlashOffense::DoubleSigning, own_stake, epoch)
+            {
+                Some(result) => {
+                    warn!(
+                        "Slashed {} for double-signing at h={} r={} ({} lamports)",
+                        ev.voter, ev.height, ev.round, result.lamports_slashed
+                    );
+                    slashed += 1;
+                }
+                None => {
+                    info!("{} is already permanently banned", ev.voter);
+                }
+            }
+        }
+        slashed
+    }
 }
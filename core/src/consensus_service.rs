@@ -7,6 +7,8 @@
 //! 3. Broadcasts outgoing consensus messages to peers.
 //! 4. Handles timeouts and round advancement.
 //! 5. Advances to the next height after each committed block.
+//! 6. Fetches skipped heights from peers through the block syncer, holding
+//!    consensus until they are committed.
 //!
 //! # Architecture
 //!
@@ -44,21 +46,34 @@ mod inner {
         log::*,
         solana_gossip::cluster_info::ClusterInfo,
         solana_keypair::Keypair,
+        solana_pubkey::Pubkey,
         solana_runtime::bank_forks::BankForks,
         solana_transaction::versioned::VersionedTransaction,
         std::{
+            collections::BTreeMap,
             sync::{
                 atomic::{AtomicBool, Ordering},
-                Arc, RwLock,
+                Arc, Mutex, RwLock,
             },
             thread::{self, Builder, JoinHandle},
             time::Duration,
         },
+        tokio::runtime::{Builder as RuntimeBuilder, Runtime},
         trv1_consensus_bft::{
             config::BftConfig,
+            engine::SyncRequest,
             types::{ConsensusMessage, ConsensusStep},
             validator_set::ValidatorSet,
         },
+        trv1_consensus_net::{
+            config::ConsensusNetConfig,
+            error::{ConsensusNetError, Result as NetResult},
+            message::{BlockData, ConsensusNetMessage},
+            peer_manager::PeerManager,
+            sync::BlockSyncer,
+            transport::{Transport, TransportHandle},
+        },
+        trv1_monitoring::TRv1Metrics,
     };
 
     /// How often to poll for timeouts when no messages are arriving.
@@ -73,6 +88,10 @@ mod inner {
         pub block_producer_config: BlockProducerConfig,
         /// Starting height for consensus (usually the latest committed + 1).
         pub start_height: u64,
+        /// Height of the last block in the local ledger, if any.  Heights
+        /// between it and `start_height` are fetched from peers before
+        /// consensus starts.
+        pub last_committed_height: Option<u64>,
     }
 
     impl Default for ConsensusServiceConfig {
@@ -81,6 +100,182 @@ mod inner {
                 bft_config: BftConfig::default(),
                 block_producer_config: BlockProducerConfig::default(),
                 start_height: 1,
+                last_committed_height: None,
+            }
+        }
+    }
+
+    /// Fetches the heights reported in [`AdapterOutput::sync_request`]
+    /// from peers through a consensus-net [`BlockSyncer`].
+    ///
+    /// The syncer is async, so it runs on a runtime of its own that the
+    /// consensus thread drives between messages.  Requests and the peers'
+    /// answers travel over the consensus-net transport started alongside
+    /// it; the fetched blocks are held until the whole gap has arrived and
+    /// then committed in height order.
+    pub struct BlockSync {
+        syncer: BlockSyncer,
+        /// Peers known to the consensus network, shared with the syncer.
+        peer_manager: Arc<Mutex<PeerManager>>,
+        /// Receives what peers send over the syncer's transport.
+        handle: TransportHandle,
+        /// The heights still to fetch, if catching up.
+        gap: Option<SyncRequest>,
+        /// Fetched blocks waiting for the rest of the gap, by height.
+        fetched: BTreeMap<u64, BlockData>,
+        runtime: Runtime,
+    }
+
+    impl BlockSync {
+        /// Start the consensus-net transport on `config.bind_addr` and a
+        /// syncer that fetches blocks from `peer_manager`'s peers.
+        ///
+        /// `validators` may connect over QUIC; see [`Transport::start`].
+        pub fn start(
+            peer_manager: Arc<Mutex<PeerManager>>,
+            config: ConsensusNetConfig,
+            keypair: &Keypair,
+            validators: impl IntoIterator<Item = Pubkey>,
+        ) -> NetResult<Self> {
+            let runtime = RuntimeBuilder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("trv1BlockSync")
+                .enable_all()
+                .build()?;
+            let (transport, handle) =
+                runtime.block_on(Transport::start(&config, keypair, validators, None))?;
+            let syncer = BlockSyncer::new(peer_manager.clone(), config, Arc::new(transport));
+            Ok(Self {
+                syncer,
+                peer_manager,
+                handle,
+                gap: None,
+                fetched: BTreeMap::new(),
+                runtime,
+            })
+        }
+
+        /// Whether heights reported as skipped are still being fetched.
+        /// Consensus waits until they are all committed.
+        fn is_catching_up(&self) -> bool {
+            self.gap.is_some()
+        }
+
+        /// Start fetching the heights `output` reports as skipped, if any.
+        /// Returns the number of block requests dispatched.
+        fn request(&mut self, output: &AdapterOutput) -> usize {
+            let Some(request) = output.sync_request else {
+                return 0;
+            };
+            info!(
+                "ConsensusService: fetching skipped heights {}..={}",
+                request.from_height, request.to_height
+            );
+            self.gap = Some(match self.gap {
+                Some(gap) => SyncRequest {
+                    from_height: gap.from_height.min(request.from_height),
+                    to_height: gap.to_height.max(request.to_height),
+                },
+                None => request,
+            });
+            self.request_remaining()
+        }
+
+        /// Request the heights of the gap that are not yet fetched or in
+        /// flight.  The syncer caps the requests in flight, so this is
+        /// called again as responses free up slots until the whole gap is
+        /// covered.
+        fn request_remaining(&mut self) -> usize {
+            let Some(gap) = self.gap else {
+                return 0;
+            };
+            self.runtime
+                .block_on(self.syncer.request_range(gap.from_height, gap.to_height))
+        }
+
+        /// Wait up to `timeout` for a message from the consensus network
+        /// and handle everything received.
+        ///
+        /// Block responses are matched to their requests; everything else
+        /// is ignored.  Transport reports of invalid or rate-limited
+        /// messages count against the sending peer.
+        fn receive(&mut self, timeout: Duration) {
+            let first = self.runtime.block_on(async {
+                tokio::time::timeout(timeout, self.handle.inbound_rx.recv())
+                    .await
+                    .ok()
+                    .flatten()
+            });
+            let mut next = first;
+            while let Some(inbound) = next {
+                match inbound.message {
+                    ConsensusNetMessage::BlockResponse { height, block } => {
+                        self.on_block_response(height, block)
+                    }
+                    other => trace!(
+                        "ConsensusService: ignoring {} from {}",
+                        other.kind(),
+                        inbound.from
+                    ),
+                }
+                next = self.handle.inbound_rx.try_recv().ok();
+            }
+
+            let mut pm = self.peer_manager.lock().unwrap();
+            while let Ok(report) = self.handle.invalid_rx.try_recv() {
+                pm.record_invalid_report(&report);
+            }
+            while let Ok(report) = self.handle.rate_limited_rx.try_recv() {
+                pm.record_rate_limited_report(&report);
+            }
+        }
+
+        /// Hold a fetched block until the rest of the gap arrives.
+        fn on_block_response(&mut self, height: u64, block: BlockData) {
+            match self.syncer.handle_response(height, block) {
+                Ok(Some(block)) => {
+                    self.fetched.insert(height, block);
+                }
+                Ok(None) => {}
+                Err(ConsensusNetError::CheckpointMismatch { rollback_from, .. }) => {
+                    // The syncer re-fetches these heights.
+                    self.fetched.split_off(&rollback_from);
+                }
+                Err(e) => warn!("ConsensusService: bad block response for {height}: {e}"),
+            }
+        }
+
+        /// Take the blocks of the gap, in height order, once every one of
+        /// them has been fetched.
+        fn take_completed(&mut self) -> Option<Vec<(u64, BlockData)>> {
+            let gap = self.gap?;
+            if !(gap.from_height..=gap.to_height).all(|height| self.fetched.contains_key(&height)) {
+                return None;
+            }
+            self.gap = None;
+            let blocks = std::mem::take(&mut self.fetched);
+            Some(
+                blocks
+                    .into_iter()
+                    .filter(|(height, _)| (gap.from_height..=gap.to_height).contains(height))
+                    .collect(),
+            )
+        }
+
+        /// Forget the gap and every block fetched for it, so the heights
+        /// are requested afresh.
+        fn reset(&mut self) {
+            self.gap = None;
+            self.fetched.clear();
+            self.syncer.reset();
+        }
+
+        /// Re-send block requests that timed out.
+        fn retry(&mut self) {
+            let failed = self.runtime.block_on(self.syncer.retry_timed_out());
+            if !failed.is_empty() {
+                // Still part of the gap, so they are requested afresh.
+                warn!("ConsensusService: retries exhausted for heights {failed:?}");
             }
         }
     }
@@ -113,6 +308,9 @@ mod inner {
         ///   broadcast to the network.
         /// * `transaction_receiver` — Pending transactions for block
         ///   production.
+        /// * `block_sync` — Fetches heights skipped since the last commit
+        ///   from peers.
+        /// * `metrics` — TRv1 metrics the consensus engine reports into.
         /// * `exit` — Global shutdown flag.
         #[allow(clippy::too_many_arguments)]
         pub fn new(
@@ -124,6 +322,8 @@ mod inner {
             consensus_msg_receiver: Receiver<ConsensusMessage>,
             consensus_msg_sender: Sender<ConsensusMessage>,
             transaction_receiver: Receiver<Vec<VersionedTransaction>>,
+            mut block_sync: BlockSync,
+            metrics: Arc<TRv1Metrics>,
             exit: Arc<AtomicBool>,
         ) -> Self {
             let block_producer = Arc::new(BlockProducer::new(
@@ -138,7 +338,12 @@ mod inner {
                 validator_set,
                 bank_forks,
                 block_producer,
-            );
+            )
+            .with_metrics(metrics);
+
+            if let Some(height) = config.last_committed_height {
+                adapter.set_last_committed_height(height);
+            }
 
             let start_height = config.start_height;
             let block_time_ms = config.bft_config.block_time_ms;
@@ -152,6 +357,7 @@ mod inner {
                         block_time_ms,
                         &consensus_msg_receiver,
                         &consensus_msg_sender,
+                        &mut block_sync,
                         &exit,
                     );
                 })
@@ -172,6 +378,7 @@ mod inner {
             block_time_ms: u64,
             consensus_msg_receiver: &Receiver<ConsensusMessage>,
             consensus_msg_sender: &Sender<ConsensusMessage>,
+            block_sync: &mut BlockSync,
             exit: &Arc<AtomicBool>,
         ) {
             info!(
@@ -184,7 +391,7 @@ mod inner {
 
             // Start the first height
             let initial_output = adapter.start_height(current_height);
-            Self::broadcast_messages(&initial_output, consensus_msg_sender);
+            Self::dispatch_output(&initial_output, consensus_msg_sender, block_sync);
 
             loop {
                 if exit.load(Ordering::Relaxed) {
@@ -192,6 +399,13 @@ mod inner {
                     break;
                 }
 
+                // While behind, only fetch the missing blocks; consensus
+                // messages wait in the channel until the ledger catches up.
+                if block_sync.is_catching_up() {
+                    Self::catch_up(adapter, current_height, consensus_msg_sender, block_sync);
+                    continue;
+                }
+
                 // Calculate how long to wait for the next message.
                 // Use the minimum of:
                 // - time to next timeout
@@ -212,7 +426,7 @@ mod inner {
                         );
 
                         let output = adapter.handle_message(msg);
-                        Self::broadcast_messages(&output, consensus_msg_sender);
+                        Self::dispatch_output(&output, consensus_msg_sender, block_sync);
 
                         // If a block was committed, advance to next height
                         if output.block_committed {
@@ -228,14 +442,17 @@ mod inner {
                             thread::sleep(Duration::from_millis(block_time_ms / 2));
 
                             let new_output = adapter.start_height(current_height);
-                            Self::broadcast_messages(&new_output, consensus_msg_sender);
+                            Self::dispatch_output(&new_output, consensus_msg_sender, block_sync);
                         }
                     }
                     Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
                         // No message — check for timeouts
                         let output = adapter.check_timeouts();
-                        if !output.messages.is_empty() || output.block_committed {
-                            Self::broadcast_messages(&output, consensus_msg_sender);
+                        if !output.messages.is_empty()
+                            || output.block_committed
+                            || output.sync_request.is_some()
+                        {
+                            Self::dispatch_output(&output, consensus_msg_sender, block_sync);
 
                             if output.block_committed {
                                 current_height += 1;
@@ -246,7 +463,11 @@ mod inner {
                                 );
                                 thread::sleep(Duration::from_millis(block_time_ms / 2));
                                 let new_output = adapter.start_height(current_height);
-                                Self::broadcast_messages(&new_output, consensus_msg_sender);
+                                Self::dispatch_output(
+                                    &new_output,
+                                    consensus_msg_sender,
+                                    block_sync,
+                                );
                             }
                         }
                     }
@@ -257,11 +478,58 @@ mod inner {
                         break;
                     }
                 }
+
+                // Queue any double-sign evidence seen so far for our next
+                // proposal, which puts it on-chain for slashing.
+                adapter.queue_evidence();
+
+                block_sync.retry();
             }
 
             info!("ConsensusService: consensus loop exited at height {current_height}");
         }
 
+        /// Make progress fetching skipped heights; once all have arrived,
+        /// commit them and start `current_height` again on top of them.
+        fn catch_up(
+            adapter: &mut BftAdapter,
+            current_height: u64,
+            sender: &Sender<ConsensusMessage>,
+            block_sync: &mut BlockSync,
+        ) {
+            block_sync.receive(Duration::from_millis(TIMEOUT_POLL_INTERVAL_MS));
+            let Some(blocks) = block_sync.take_completed() else {
+                block_sync.retry();
+                block_sync.request_remaining();
+                return;
+            };
+
+            for (height, block) in blocks {
+                if let Err(e) = adapter.commit_synced_block(height, &block) {
+                    error!(
+                        "ConsensusService: failed to commit synced block at height {height}: {e}"
+                    );
+                    // Restarting the height reports the rest as skipped again.
+                    block_sync.reset();
+                    break;
+                }
+            }
+            info!("ConsensusService: caught up, restarting height {current_height}");
+            let output = adapter.start_height(current_height);
+            Self::dispatch_output(&output, sender, block_sync);
+        }
+
+        /// Broadcast `output`'s messages and request any heights it
+        /// reports as skipped.
+        fn dispatch_output(
+            output: &AdapterOutput,
+            sender: &Sender<ConsensusMessage>,
+            block_sync: &mut BlockSync,
+        ) {
+            Self::broadcast_messages(output, sender);
+            block_sync.request(output);
+        }
+
         /// Send all outbound messages through the network sender.
         fn broadcast_messages(
             output: &AdapterOutput,
@@ -284,4 +552,148 @@ mod inner {
             ConsensusMessage::Precommit { .. } => "Precommit",
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use {
+            super::*,
+            solana_hash::Hash,
+            solana_signer::Signer,
+            trv1_consensus_bft::{engine::ConsensusEngine, types::ProposedBlock},
+            trv1_consensus_net::{
+                handshake::Handshaker,
+                message::PeerInfo,
+                transport::{self, TransportListener},
+            },
+        };
+
+        /// Commit `height` on an engine that is the only validator, and so
+        /// proposes and votes alone.
+        fn commit_height(engine: &mut ConsensusEngine, height: u64) {
+            engine.start_new_height(height);
+            let block = ProposedBlock {
+                parent_hash: Hash::default(),
+                height,
+                timestamp: 1_000,
+                transactions: vec![],
+                state_root: Hash::new_unique(),
+                proposer: *engine.identity(),
+                evidence: vec![],
+            };
+            let output = engine.on_proposal(engine.make_proposal(block, None));
+            assert!(output.committed_block.is_some());
+        }
+
+        fn make_block(height: u64) -> BlockData {
+            BlockData {
+                slot: height,
+                parent_hash: Hash::default(),
+                block_hash: Hash::new_unique(),
+                transactions: vec![],
+                state_root: Hash::default(),
+                merkle_root: Hash::default(),
+                merkle_proof: vec![],
+                proposer: Pubkey::new_unique(),
+            }
+        }
+
+        #[test]
+        fn test_height_gap_fetches_every_skipped_block() {
+            let keypair = Keypair::new();
+            let validator_set = ValidatorSet::new(vec![(keypair.pubkey(), 100)]);
+            let mut engine = ConsensusEngine::new(
+                BftConfig::default(),
+                keypair.insecure_clone(),
+                validator_set,
+            );
+            commit_height(&mut engine, 5);
+
+            // Moving on to height 12 leaves heights 6 to 11 to fetch.
+            let output = AdapterOutput::from_engine_output(engine.start_new_height(12));
+            assert_eq!(
+                output.sync_request,
+                Some(SyncRequest {
+                    from_height: 6,
+                    to_height: 11
+                })
+            );
+
+            // At most 4 requests are in flight at once.
+            let config = ConsensusNetConfig::dev_default();
+            assert_eq!(config.max_sync_requests, 4);
+            let peer_manager = Arc::new(Mutex::new(PeerManager::new(config.clone())));
+            let mut block_sync =
+                BlockSync::start(peer_manager.clone(), config.clone(), &keypair, []).unwrap();
+
+            // A peer that answers the block requests the test reads.
+            let peer_keypair = Keypair::new();
+            let peer_handshaker = Handshaker::new(&peer_keypair, &config);
+            let mut peer = block_sync
+                .runtime
+                .block_on(TransportListener::new(config.clone(), &peer_keypair).start())
+                .unwrap();
+            {
+                let mut pm = peer_manager.lock().unwrap();
+                pm.add_peer(PeerInfo {
+                    pubkey: peer_keypair.pubkey(),
+                    addr: peer.local_addr,
+                    stake_weight: 1,
+                    is_active_validator: true,
+                })
+                .unwrap();
+                pm.mark_connected(&peer_keypair.pubkey()).unwrap();
+            }
+            let mut answer = |block_sync: &mut BlockSync, count: usize| {
+                let mut requested = Vec::new();
+                for _ in 0..count {
+                    let inbound = block_sync
+                        .runtime
+                        .block_on(peer.inbound_rx.recv())
+                        .expect("mock peer channel closed");
+                    let ConsensusNetMessage::BlockRequest { height } = inbound.message else {
+                        panic!("unexpected message {:?}", inbound.message);
+                    };
+                    requested.push(height);
+                    let response = ConsensusNetMessage::BlockResponse {
+                        height,
+                        block: make_block(height),
+                    };
+                    block_sync
+                        .runtime
+                        .block_on(transport::send_message(
+                            block_sync.handle.local_addr,
+                            &response,
+                            config.max_message_size,
+                            &peer_handshaker,
+                        ))
+                        .unwrap();
+                }
+                let fetched = block_sync.fetched.len() + count;
+                for _ in 0..50 {
+                    if block_sync.fetched.len() == fetched {
+                        break;
+                    }
+                    block_sync.receive(Duration::from_millis(100));
+                }
+                assert_eq!(block_sync.fetched.len(), fetched);
+                requested.sort_unstable();
+                requested
+            };
+
+            // The first page of the gap.
+            assert_eq!(block_sync.request(&output), 4);
+            assert_eq!(block_sync.request_remaining(), 0);
+            assert_eq!(answer(&mut block_sync, 4), vec![6, 7, 8, 9]);
+            assert!(block_sync.take_completed().is_none());
+            assert!(block_sync.is_catching_up());
+
+            // The rest once the first responses free up slots.
+            assert_eq!(block_sync.request_remaining(), 2);
+            assert_eq!(answer(&mut block_sync, 2), vec![10, 11]);
+            let blocks = block_sync.take_completed().unwrap();
+            let heights: Vec<u64> = blocks.iter().map(|(height, _)| *height).collect();
+            assert_eq!(heights, vec![6, 7, 8, 9, 10, 11]);
+            assert!(!block_sync.is_catching_up());
+        }
+    }
 }
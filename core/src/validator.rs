@@ -5,7 +5,7 @@ pub use solana_perf::report_target_features;
 use crate::{
     bft_adapter::BftAdapter,
     block_producer::{BlockProducer, BlockProducerConfig},
-    consensus_service::{ConsensusService, ConsensusServiceConfig},
+    consensus_service::{BlockSync, ConsensusService, ConsensusServiceConfig},
 };
 use {
     crate::{
@@ -179,6 +179,10 @@ use {
     thiserror::Error,
     tokio::{runtime::Runtime as TokioRuntime, sync::mpsc},
     tokio_util::sync::CancellationToken,
+    trv1_monitoring::{
+        push_gateway::{self, PushGatewayConfig},
+        TRv1Metrics,
+    },
 };
 
 const MAX_COMPLETED_DATA_SETS_IN_CHANNEL: usize = 100_000;
@@ -429,6 +433,9 @@ pub struct ValidatorConfig {
     pub repair_handler_type: RepairHandlerType,
     // Thread niceness adjustment for snapshot packager service
     pub snapshot_packager_niceness_adj: i8,
+    /// Push TRv1 metrics to a Prometheus Pushgateway, for validators that
+    /// cannot be scraped
+    pub metrics_push_gateway: Option<PushGatewayConfig>,
 }
 
 impl ValidatorConfig {
@@ -513,6 +520,7 @@ impl ValidatorConfig {
             voting_service_test_override: None,
             repair_handler_type: RepairHandlerType::default(),
             snapshot_packager_niceness_adj: 0,
+            metrics_push_gateway: None,
         }
     }
 
@@ -673,6 +681,7 @@ pub struct Validator {
     entry_notifier_service: Option<EntryNotifierService>,
     system_monitor_service: Option<SystemMonitorService>,
     sample_performance_service: Option<SamplePerformanceService>,
+    metrics_pusher: Option<JoinHandle<()>>,
     stats_reporter_service: StatsReporterService,
     gossip_service: GossipService,
     serve_repair_service: ServeRepairService,
@@ -929,6 +938,15 @@ impl Validator {
             },
         ));
 
+        let trv1_metrics = Arc::new(TRv1Metrics::new());
+        let metrics_pusher = config.metrics_push_gateway.clone().map(|push_config| {
+            info!(
+                "pushing TRv1 metrics to {} every {}s",
+                push_config.url, push_config.push_interval_secs
+            );
+            push_gateway::start_background_pusher(push_config, trv1_metrics.clone(), exit.clone())
+        });
+
         let dependency_tracker = Arc::new(DependencyTracker::default());
 
         let (
@@ -1869,6 +1887,7 @@ impl Validator {
                 validator_set::ValidatorSet as BftValidatorSet,
             };
             use solana_signer::Signer;
+            use trv1_consensus_net::{config::ConsensusNetConfig, peer_manager::PeerManager};
 
             info!("TRv1 BFT consensus feature enabled — initializing consensus service");
 
@@ -1882,7 +1901,7 @@ impl Validator {
                 .filter(|(_, stake)| *stake > 0)
                 .collect();
 
-            let validator_set = BftValidatorSet::new(validators);
+            let validator_set = BftValidatorSet::from_chain_stakes(validators);
             info!(
                 "TRv1 BFT: validator set has {} validators (epoch {epoch})",
                 validator_set.len()
@@ -1895,14 +1914,40 @@ impl Validator {
                 crossbeam_channel::unbounded();
             // Transaction channel for block production
             let (_tx_sender, tx_receiver) = crossbeam_channel::unbounded();
-
-            let start_height = root_bank.slot() + 1;
+            // Block sync for heights skipped since the last commit.  The
+            // consensus network's peers are dialled per message, so every
+            // known address is usable as soon as it is known.
+            let consensus_net_config = ConsensusNetConfig::default();
+            let mut peer_manager = PeerManager::new(consensus_net_config.clone());
+            peer_manager.add_bootstrap_peers(&consensus_net_config.bootstrap_peers);
+            peer_manager.reconnect_known_peers(|_, _| true);
+            let block_sync = BlockSync::start(
+                Arc::new(Mutex::new(peer_manager)),
+                consensus_net_config,
+                &identity_keypair,
+                validator_set.pubkeys(),
+            )
+            .map_err(|e| ValidatorError::Other(format!("failed to start block sync: {e}")))?;
+
+            // The blockstore holds the last height this validator committed
+            // before it stopped, and shreds for any the cluster has
+            // committed since; those are fetched from peers before
+            // consensus resumes.
+            let last_committed_height = blockstore.max_root().max(root_bank.slot());
+            let start_height = blockstore
+                .highest_slot()
+                .ok()
+                .flatten()
+                .unwrap_or_default()
+                .max(last_committed_height)
+                + 1;
 
             let service = ConsensusService::new(
                 ConsensusServiceConfig {
                     bft_config: BftConfig::default(),
                     block_producer_config: BlockProducerConfig::default(),
                     start_height,
+                    last_committed_height: Some(last_committed_height),
                 },
                 identity_keypair.clone(),
                 validator_set,
@@ -1911,6 +1956,8 @@ impl Validator {
                 consensus_inbound_receiver,
                 consensus_outbound_sender,
                 tx_receiver,
+                block_sync,
+                trv1_metrics,
                 exit.clone(),
             );
 
@@ -1932,6 +1979,7 @@ impl Validator {
             entry_notifier_service,
             system_monitor_service,
             sample_performance_service,
+            metrics_pusher,
             snapshot_packager_service,
             completed_data_sets_service,
             tpu,
@@ -2096,6 +2144,10 @@ impl Validator {
                 .expect("system_monitor_service");
         }
 
+        if let Some(metrics_pusher) = self.metrics_pusher {
+            metrics_pusher.join().expect("metrics_pusher");
+        }
+
         if let Some(sample_performance_service) = self.sample_performance_service {
             sample_performance_service
                 .join()
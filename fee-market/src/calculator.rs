@@ -1,7 +1,11 @@
 use crate::{
-    config::FeeMarketConfig,
+    config::{
+        FeeMarketConfig, MAX_PRIORITY_FEE_PASSIVE_STAKE_BPS, MAX_UTILIZATION_EMA_ALPHA_BPS,
+        MIN_UTILIZATION_EMA_ALPHA_BPS,
+    },
     error::FeeError,
-    state::{BlockFeeState, TransactionFee},
+    priority::MAX_PRIORITY_FEE_HISTORY_SIZE,
+    state::{BlockFeeState, TransactionFee, TransactionResources},
 };
 
 /// Calculate the next block's base fee using the EIP-1559 algorithm.
@@ -10,6 +14,7 @@ use crate::{
 ///
 /// ```text
 /// target = max_block_compute_units × target_utilization_pct / 100
+/// parent_gas_used = smoothed_utilization × target / UTILIZATION_SCALE
 ///
 /// if parent_gas_used == target:
 ///     next_base_fee = current_base_fee          (no change)
@@ -25,6 +30,15 @@ use crate::{
 ///
 /// The result is clamped to `[min_base_fee, max_base_fee]`.
 ///
+/// `smoothed_utilization` is [`BlockFeeState::smoothed_utilization`]: the
+/// parent's usage relative to the target folded into the running average
+/// with weight `utilization_ema_alpha_bps`.  At `utilization_ema_alpha_bps =
+/// 10_000` the parent's usage is taken as-is, which is the unsmoothed
+/// EIP-1559 rule.
+///
+/// While `state.paused` is set the formula is skipped entirely and the
+/// result is `state.pause_fee`, unclamped, whatever the parent's usage.
+///
 /// # Note on `max(delta, 1)` when above target
 ///
 /// Ethereum's EIP-1559 (go-ethereum) enforces a minimum increase of 1 wei
@@ -32,6 +46,10 @@ use crate::{
 /// always rises under sustained congestion, even when the current base fee is
 /// very small.
 pub fn calculate_next_base_fee(config: &FeeMarketConfig, state: &BlockFeeState) -> u64 {
+    if state.paused {
+        return state.pause_fee;
+    }
+
     let target = config.target_gas();
 
     // Edge case: target = 0 means every non-empty block is "above target".
@@ -46,13 +64,14 @@ pub fn calculate_next_base_fee(config: &FeeMarketConfig, state: &BlockFeeState)
     }
 
     let current = state.base_fee_per_cu;
+    let parent_gas_used = state.smoothed_gas_used(config);
 
-    let next = if state.parent_gas_used == target {
+    let next = if parent_gas_used == target {
         // Exactly at target — no adjustment.
         current
-    } else if state.parent_gas_used > target {
+    } else if parent_gas_used > target {
         // Above target — increase base fee.
-        let excess = state.parent_gas_used.saturating_sub(target);
+        let excess = parent_gas_used.saturating_sub(target);
         // delta = current * excess / target / denominator
         // Use u128 to avoid intermediate overflow.
         let numerator = (current as u128).saturating_mul(excess as u128);
@@ -69,7 +88,7 @@ pub fn calculate_next_base_fee(config: &FeeMarketConfig, state: &BlockFeeState)
         current.saturating_add(delta)
     } else {
         // Below target — decrease base fee.
-        let deficit = target.saturating_sub(state.parent_gas_used);
+        let deficit = target.saturating_sub(parent_gas_used);
         let numerator = (current as u128).saturating_mul(deficit as u128);
         let denominator = (target as u128).saturating_mul(config.base_fee_change_denominator as u128);
         let delta = if denominator == 0 {
@@ -86,39 +105,84 @@ pub fn calculate_next_base_fee(config: &FeeMarketConfig, state: &BlockFeeState)
 
 /// Calculate the fee breakdown for a single transaction.
 ///
-/// Returns a [`TransactionFee`] with the base, priority, and total components.
+/// Compute and storage writes are priced on separate dimensions:
+///
+/// ```text
+/// base_fee     = base_fee_per_cu     × compute_units
+/// priority_fee = priority_fee_per_cu × compute_units
+/// storage_fee  = write_fee_per_account × accounts_written
+/// ```
+///
+/// The priority fee is partitioned: `priority_fee_passive_stake_bps` of it
+/// (rounded down) goes to the passive staking reward pool and the rest to
+/// the validator.
+///
+/// With `accounts_written == 0` the result is identical to pricing compute
+/// alone.  Returns a [`TransactionFee`] with each component and the total.
 /// All arithmetic saturates to `u64::MAX`.
 pub fn calculate_transaction_fee(
+    config: &FeeMarketConfig,
     base_fee_per_cu: u64,
     priority_fee_per_cu: u64,
-    compute_units_used: u64,
+    resources: &TransactionResources,
 ) -> TransactionFee {
-    let base_fee = base_fee_per_cu.saturating_mul(compute_units_used);
-    let priority_fee = priority_fee_per_cu.saturating_mul(compute_units_used);
-    let total_fee = base_fee.saturating_add(priority_fee);
+    let base_fee = base_fee_per_cu.saturating_mul(resources.compute_units);
+    let priority_fee = priority_fee_per_cu.saturating_mul(resources.compute_units);
+    let (priority_fee_validator, priority_fee_stake_pool) = config.split_priority_fee(priority_fee);
+    let storage_fee = config
+        .write_fee_per_account
+        .saturating_mul(resources.accounts_written as u64);
+    let total_fee = base_fee
+        .saturating_add(priority_fee)
+        .saturating_add(storage_fee);
     TransactionFee {
         base_fee,
-        priority_fee,
+        priority_fee_validator,
+        priority_fee_stake_pool,
+        storage_fee,
         total_fee,
     }
 }
 
-/// Validate that a transaction can afford the fees for the requested compute
-/// units at the current base fee.
+/// Estimate the fee for a transaction that lands `blocks_ahead` blocks after
+/// `state`, assuming every block in between consumes `utilization_bps` of
+/// the target.
+///
+/// With `blocks_ahead == 0` this prices the transaction at the current base fee.
+pub fn estimate_inclusion_fee(
+    config: &FeeMarketConfig,
+    state: &BlockFeeState,
+    blocks_ahead: usize,
+    utilization_bps: u16,
+    priority_fee_per_cu: u64,
+    resources: &TransactionResources,
+) -> TransactionFee {
+    let base_fee_per_cu = state
+        .simulate_n_blocks(config, blocks_ahead, utilization_bps)
+        .last()
+        .copied()
+        .unwrap_or(state.base_fee_per_cu);
+    calculate_transaction_fee(config, base_fee_per_cu, priority_fee_per_cu, resources)
+}
+
+/// Validate that a transaction can afford the full fee, storage writes
+/// included, for the requested resources at the current base fee.
 ///
 /// * `offered_lamports` — the maximum the user is willing to pay (balance or
 ///   declared maxFee).
 /// * `priority_fee_per_cu` — the user's chosen priority fee.
 /// * `base_fee_per_cu` — current network base fee.
-/// * `requested_cu` — compute units the transaction requests.
+/// * `resources` — compute units requested and accounts written.
 /// * `config` — fee market config (for min priority fee and block CU cap).
 pub fn validate_transaction_fee(
     offered_lamports: u64,
     priority_fee_per_cu: u64,
     base_fee_per_cu: u64,
-    requested_cu: u64,
+    resources: &TransactionResources,
     config: &FeeMarketConfig,
 ) -> Result<TransactionFee, FeeError> {
+    let requested_cu = resources.compute_units;
+
     // Check CU limit.
     if requested_cu > config.max_block_compute_units {
         return Err(FeeError::ComputeUnitsExceedMax {
@@ -136,7 +200,7 @@ pub fn validate_transaction_fee(
     }
 
     // Calculate required fee.
-    let fee = calculate_transaction_fee(base_fee_per_cu, priority_fee_per_cu, requested_cu);
+    let fee = calculate_transaction_fee(config, base_fee_per_cu, priority_fee_per_cu, resources);
 
     // Check affordability.
     if offered_lamports < fee.total_fee {
@@ -174,6 +238,34 @@ pub fn validate_config(config: &FeeMarketConfig) -> Result<(), FeeError> {
             ),
         });
     }
+    if config.priority_fee_passive_stake_bps > MAX_PRIORITY_FEE_PASSIVE_STAKE_BPS {
+        return Err(FeeError::InvalidConfig {
+            reason: format!(
+                "priority_fee_passive_stake_bps ({}) must be at most {}",
+                config.priority_fee_passive_stake_bps, MAX_PRIORITY_FEE_PASSIVE_STAKE_BPS
+            ),
+        });
+    }
+    if !(MIN_UTILIZATION_EMA_ALPHA_BPS..=MAX_UTILIZATION_EMA_ALPHA_BPS)
+        .contains(&config.utilization_ema_alpha_bps)
+    {
+        return Err(FeeError::InvalidConfig {
+            reason: format!(
+                "utilization_ema_alpha_bps ({}) must be {}–{}",
+                config.utilization_ema_alpha_bps,
+                MIN_UTILIZATION_EMA_ALPHA_BPS,
+                MAX_UTILIZATION_EMA_ALPHA_BPS
+            ),
+        });
+    }
+    if config.priority_fee_history_size > MAX_PRIORITY_FEE_HISTORY_SIZE {
+        return Err(FeeError::InvalidConfig {
+            reason: format!(
+                "priority_fee_history_size ({}) must be at most {}",
+                config.priority_fee_history_size, MAX_PRIORITY_FEE_HISTORY_SIZE
+            ),
+        });
+    }
     Ok(())
 }
 
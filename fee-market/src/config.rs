@@ -1,12 +1,23 @@
 use {borsh::{BorshDeserialize, BorshSerialize}, serde::{Deserialize, Serialize}};
 
+/// Largest share of priority fees, in basis points, that may be routed to
+/// the passive staking reward pool.
+pub const MAX_PRIORITY_FEE_PASSIVE_STAKE_BPS: u16 = 5_000;
+
+/// Smallest accepted [`FeeMarketConfig::utilization_ema_alpha_bps`].
+pub const MIN_UTILIZATION_EMA_ALPHA_BPS: u16 = 100;
+
+/// Largest accepted [`FeeMarketConfig::utilization_ema_alpha_bps`]; at this
+/// value the base fee follows the parent block's usage with no smoothing.
+pub const MAX_UTILIZATION_EMA_ALPHA_BPS: u16 = 10_000;
+
 /// Configuration for the EIP-1559-style dynamic fee market.
 ///
 /// This mirrors Ethereum's EIP-1559 mechanism adapted for Solana's compute-unit model:
 /// - Instead of gas, we use **compute units (CU)**.
 /// - The base fee per CU adjusts each block based on utilization vs. target.
 /// - Users set a **priority fee** (tip) on top of the base fee for ordering preference.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct FeeMarketConfig {
     /// Minimum base fee per compute unit in lamports (floor).
     /// Prevents the base fee from dropping to zero even under sustained low usage.
@@ -33,6 +44,25 @@ pub struct FeeMarketConfig {
     /// Minimum priority fee per compute unit that a transaction must include.
     /// Set to 0 to allow free-priority transactions.
     pub min_priority_fee: u64,
+
+    /// Number of recent transaction fees retained for priority fee statistics.
+    /// See [`crate::priority::PriorityFeeHistory`].
+    pub priority_fee_history_size: u64,
+
+    /// Storage fee in lamports charged per writable account a transaction
+    /// locks, on top of the compute-unit base fee.  Priced independently of
+    /// the base fee so write contention can be tuned on its own.
+    pub write_fee_per_account: u64,
+
+    /// Share of every priority fee, in basis points, routed to the passive
+    /// staking reward pool instead of the block validator (0–5 000).
+    pub priority_fee_passive_stake_bps: u16,
+
+    /// Weight of the newest block, in basis points, in the exponential
+    /// moving average of utilization that drives the base fee (100–10 000).
+    /// Lower values damp the fee's response to single-block spikes; 10 000
+    /// disables smoothing.
+    pub utilization_ema_alpha_bps: u16,
 }
 
 impl FeeMarketConfig {
@@ -47,6 +77,32 @@ impl FeeMarketConfig {
             .saturating_mul(self.target_utilization_pct as u64)
             / 100
     }
+
+    /// Number of blocks within which the base fee at least doubles when every
+    /// block runs at 150 % of target.
+    ///
+    /// At 150 % the fee grows by `1 / (2 × denominator)` per block, so this is
+    /// `⌈ln 2 / ln(1 + 1 / (2 × denominator))⌉`, plus one block to absorb the
+    /// integer rounding in `calculate_next_base_fee`.  Assumes the utilization
+    /// average has already settled at 150 %.
+    pub fn fee_double_blocks(&self) -> u64 {
+        if self.base_fee_change_denominator == 0 {
+            return 1;
+        }
+        let growth = 1.0 + 1.0 / (2.0 * self.base_fee_change_denominator as f64);
+        (2f64.ln() / growth.ln()).ceil() as u64 + 1
+    }
+
+    /// Split a priority fee into `(validator, stake_pool)` shares.
+    ///
+    /// The passive staking reward pool gets `priority_fee_passive_stake_bps`
+    /// of it, rounded down; the validator gets the rest.
+    pub fn split_priority_fee(&self, priority_fee: u64) -> (u64, u64) {
+        let stake_pool = (priority_fee as u128
+            * self.priority_fee_passive_stake_bps.min(10_000) as u128
+            / 10_000) as u64;
+        (priority_fee - stake_pool, stake_pool)
+    }
 }
 
 impl Default for FeeMarketConfig {
@@ -59,6 +115,10 @@ impl Default for FeeMarketConfig {
             max_block_compute_units: 48_000_000, // 48 M CU per block
             base_fee_change_denominator: 8,     // ±12.5 % max change per block
             min_priority_fee: 0,                // no forced tip
+            priority_fee_history_size: 1_000,   // last 1 000 transactions
+            write_fee_per_account: 5_000,       // 5 000 lamports per written account
+            priority_fee_passive_stake_bps: 0,  // priority fees go to the validator
+            utilization_ema_alpha_bps: 1_000,   // newest block weighs 10 % of the average
         }
     }
 }
@@ -76,6 +136,10 @@ mod tests {
         assert_eq!(cfg.max_block_compute_units, 48_000_000);
         assert_eq!(cfg.base_fee_change_denominator, 8);
         assert_eq!(cfg.min_priority_fee, 0);
+        assert_eq!(cfg.priority_fee_history_size, 1_000);
+        assert_eq!(cfg.write_fee_per_account, 5_000);
+        assert_eq!(cfg.priority_fee_passive_stake_bps, 0);
+        assert_eq!(cfg.utilization_ema_alpha_bps, 1_000);
     }
 
     #[test]
@@ -96,6 +160,21 @@ mod tests {
         assert_eq!(cfg.target_gas(), 75_000_000);
     }
 
+    #[test]
+    fn test_split_priority_fee() {
+        let cfg = FeeMarketConfig {
+            priority_fee_passive_stake_bps: 2_500,
+            ..Default::default()
+        };
+        assert_eq!(cfg.split_priority_fee(10_000), (7_500, 2_500));
+        // The stake pool's share rounds down.
+        assert_eq!(cfg.split_priority_fee(3), (3, 0));
+        assert_eq!(
+            FeeMarketConfig::default().split_priority_fee(10_000),
+            (10_000, 0)
+        );
+    }
+
     #[test]
     fn test_target_gas_zero_pct() {
         let cfg = FeeMarketConfig {
@@ -114,6 +193,13 @@ mod tests {
         assert_eq!(cfg.target_gas(), cfg.max_block_compute_units);
     }
 
+    #[test]
+    fn test_fee_double_blocks() {
+        let cfg = FeeMarketConfig::default();
+        // 6.25 % per block: 1.0625^12 ≈ 2.07, plus one block of slack.
+        assert_eq!(cfg.fee_double_blocks(), 13);
+    }
+
     #[test]
     fn test_borsh_roundtrip() {
         let cfg = FeeMarketConfig::default();
@@ -0,0 +1,151 @@
+//! Per-block fee history for priority fee estimation.
+//!
+//! [`crate::priority::PriorityFeeHistory`] keeps individual transactions; a
+//! wallet estimating a tip for its *next* transaction is better served by a
+//! compact per-block summary it can look back over.  Each landed block is
+//! reduced to a [`FeeHistoryEntry`] (base fee, priority fee quartiles and
+//! utilization) and the most recent entries are kept in a [`FeeHistory`]
+//! ring buffer.
+//!
+//! Suggestions take the median across blocks of each block's percentile, so
+//! a single block with an outlier tip distribution does not skew the result.
+
+use {
+    crate::{
+        config::FeeMarketConfig,
+        priority::{nearest_rank, PriorityFeeStats},
+        state::BlockFeeState,
+    },
+    serde::{Deserialize, Serialize},
+    std::collections::VecDeque,
+};
+
+/// Summary of the fees paid in a single block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct FeeHistoryEntry {
+    /// Slot / block height.
+    pub height: u64,
+    /// Base fee per compute unit the block was priced at (lamports).
+    pub base_fee: u64,
+    /// 25th percentile priority fee in the block.
+    pub p25_priority: u64,
+    /// Median priority fee in the block.
+    pub p50_priority: u64,
+    /// 75th percentile priority fee in the block.
+    pub p75_priority: u64,
+    /// Compute used relative to the target, in basis points: `0` is exactly
+    /// at target, `+10_000` is double the target and `-10_000` an empty block.
+    pub block_utilization_bps: i64,
+}
+
+impl FeeHistoryEntry {
+    /// Priority fee at `percentile` within this block.
+    ///
+    /// Only the quartiles are retained, so values between them are linearly
+    /// interpolated and percentiles outside `[25, 75]` clamp to the nearest
+    /// quartile.
+    pub fn priority_at(&self, percentile: u8) -> u64 {
+        match percentile {
+            0..=25 => self.p25_priority,
+            26..=50 => interpolate(self.p25_priority, self.p50_priority, percentile - 25),
+            51..=75 => interpolate(self.p50_priority, self.p75_priority, percentile - 50),
+            _ => self.p75_priority,
+        }
+    }
+}
+
+/// Value `step / 25` of the way from `low` to `high`.
+fn interpolate(low: u64, high: u64, step: u8) -> u64 {
+    let (low, high) = (low as i128, high as i128);
+    (low + (high - low) * step as i128 / 25) as u64
+}
+
+/// Ring buffer of the most recent [`FeeHistoryEntry`]s, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct FeeHistory {
+    /// Maximum number of retained entries.
+    pub capacity: usize,
+    /// Retained entries, oldest first.
+    pub entries: VecDeque<FeeHistoryEntry>,
+}
+
+impl FeeHistory {
+    /// Create an empty history that retains at most `capacity` blocks.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Append an entry, evicting the oldest one when full.
+    pub fn push(&mut self, entry: FeeHistoryEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Number of retained entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no entries have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Suggested priority fee for a transaction that wants to outbid
+    /// `target_percentile` (0 – 100) of recent transactions.
+    ///
+    /// Returns the median, across all retained blocks, of each block's fee at
+    /// `target_percentile` (see [`FeeHistoryEntry::priority_at`]).  Returns 0
+    /// when the history is empty.
+    pub fn suggest_priority_fee(&self, target_percentile: u8) -> u64 {
+        let mut per_block: Vec<u64> = self
+            .entries
+            .iter()
+            .map(|entry| entry.priority_at(target_percentile))
+            .collect();
+        per_block.sort_unstable();
+        nearest_rank(&per_block, 50.0)
+    }
+}
+
+impl BlockFeeState {
+    /// Summarise this block into `history` as the entry for `height`.
+    ///
+    /// `priority_fees` are the priority fees paid by the block's transactions,
+    /// in any order.  The oldest entry is evicted when `history` is full.
+    pub fn push_history_entry(
+        &self,
+        config: &FeeMarketConfig,
+        history: &mut FeeHistory,
+        height: u64,
+        priority_fees: &[u64],
+    ) {
+        let stats = PriorityFeeStats::from_samples(priority_fees);
+        history.push(FeeHistoryEntry {
+            height,
+            base_fee: self.base_fee_per_cu,
+            p25_priority: stats.p25,
+            p50_priority: stats.p50,
+            p75_priority: stats.p75,
+            block_utilization_bps: self.utilization_vs_target_bps(config),
+        });
+    }
+
+    /// Current block usage relative to the target, in signed basis points.
+    fn utilization_vs_target_bps(&self, config: &FeeMarketConfig) -> i64 {
+        let target = config.target_gas();
+        if target == 0 {
+            return if self.current_gas_used == 0 { 0 } else { 10_000 };
+        }
+        let delta = self.current_gas_used as i128 - target as i128;
+        (delta * 10_000 / target as i128).clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+}
@@ -5,12 +5,15 @@
 //! Instead of Solana's fixed `lamports_per_signature` model, TRv1 adjusts a
 //! **base fee per compute unit** every block based on network utilization.
 //! Users additionally specify a **priority fee** (tip) that goes to the block
-//! producer, exactly like Ethereum's `maxPriorityFeePerGas`.
+//! producer, exactly like Ethereum's `maxPriorityFeePerGas`.  A configurable
+//! share of it can instead be routed to the passive staking reward pool.
 //!
 //! ## Quick start
 //!
 //! ```rust
-//! use trv1_fee_market::{FeeMarketConfig, BlockFeeState, calculator};
+//! use trv1_fee_market::{
+//!     calculator, BlockFeeState, FeeMarketConfig, TransactionResources, UTILIZATION_SCALE,
+//! };
 //!
 //! let config = FeeMarketConfig::default();
 //!
@@ -20,14 +23,23 @@
 //!     parent_gas_used: 36_000_000,
 //!     current_gas_used: 0,
 //!     height: 0,
+//!     current_accounts_written: 0,
+//!     paused: false,
+//!     pause_fee: 0,
+//!     ema_utilization: UTILIZATION_SCALE,
 //! };
 //!
 //! // Derive the next block's base fee.
 //! let next_fee = calculator::calculate_next_base_fee(&config, &state);
 //! assert!(next_fee > state.base_fee_per_cu, "base fee should rise");
 //!
-//! // Price a transaction.
-//! let tx_fee = calculator::calculate_transaction_fee(next_fee, /*priority*/ 100, /*cu*/ 200_000);
+//! // Price a transaction that uses 200k CU and writes two accounts.
+//! let resources = TransactionResources {
+//!     compute_units: 200_000,
+//!     accounts_read: 3,
+//!     accounts_written: 2,
+//! };
+//! let tx_fee = calculator::calculate_transaction_fee(&config, next_fee, /*priority*/ 100, &resources);
 //! println!("total fee = {} lamports", tx_fee.total_fee);
 //! ```
 //!
@@ -36,6 +48,8 @@
 pub mod calculator;
 pub mod config;
 pub mod error;
+pub mod history;
+pub mod priority;
 pub mod state;
 
 #[cfg(test)]
@@ -44,4 +58,6 @@ mod tests;
 // Re-exports for convenience.
 pub use config::FeeMarketConfig;
 pub use error::FeeError;
-pub use state::{BlockFeeState, TransactionFee};
+pub use history::{FeeHistory, FeeHistoryEntry};
+pub use priority::{PriorityFeeHistory, PriorityFeeStats};
+pub use state::{BlockFeeState, TransactionFee, TransactionResources, UTILIZATION_SCALE};
@@ -0,0 +1,157 @@
+//! Priority fee statistics over recently landed transactions.
+//!
+//! A single median is a poor guide when choosing a tip: under congestion the
+//! distribution of priority fees is heavily right-skewed, so wallets need to
+//! see the upper percentiles as well.  This module summarises a window of
+//! [`TransactionFee`]s into a [`PriorityFeeStats`] table and keeps that window
+//! bounded via [`PriorityFeeHistory`].
+//!
+//! Percentiles use the **nearest-rank** method: the p-th percentile of `n`
+//! sorted samples is the sample at rank `⌈p / 100 × n⌉`.  The result is always
+//! one of the observed fees, never an interpolated value.
+
+use {
+    crate::{config::FeeMarketConfig, state::TransactionFee},
+    serde::{Deserialize, Serialize},
+    std::collections::VecDeque,
+};
+
+/// Largest accepted `priority_fee_history_size`; bounds the history's
+/// up-front allocation.
+pub const MAX_PRIORITY_FEE_HISTORY_SIZE: u64 = 100_000;
+
+/// Distribution of priority fees over a window of samples.
+///
+/// Fields carry the unit of the samples: lamports when built by
+/// [`FeeMarketConfig::priority_fee_statistics`], or whatever unit was passed
+/// to [`PriorityFeeStats::from_samples`].  All fields are 0 when the window
+/// is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PriorityFeeStats {
+    pub p25: u64,
+    pub p50: u64,
+    pub p75: u64,
+    pub p90: u64,
+    pub p99: u64,
+    /// Arithmetic mean, rounded down.
+    pub mean: u64,
+    pub max: u64,
+}
+
+impl PriorityFeeStats {
+    /// Summarise raw fee samples, e.g. per-CU prices reported by RPC.
+    pub fn from_samples(samples: &[u64]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        Self::from_sorted(&sorted)
+    }
+
+    fn from_sorted(sorted: &[u64]) -> Self {
+        let Some(&max) = sorted.last() else {
+            return PriorityFeeStats::default();
+        };
+        let sum = sorted
+            .iter()
+            .fold(0u128, |acc, &fee| acc.saturating_add(fee as u128));
+        PriorityFeeStats {
+            p25: nearest_rank(sorted, 25.0),
+            p50: nearest_rank(sorted, 50.0),
+            p75: nearest_rank(sorted, 75.0),
+            p90: nearest_rank(sorted, 90.0),
+            p99: nearest_rank(sorted, 99.0),
+            mean: (sum / sorted.len() as u128) as u64,
+            max,
+        }
+    }
+}
+
+impl FeeMarketConfig {
+    /// Summarise the priority fees (lamports) paid by `fees`.
+    pub fn priority_fee_statistics(&self, fees: &[TransactionFee]) -> PriorityFeeStats {
+        PriorityFeeStats::from_sorted(&sorted_priority_fees(fees))
+    }
+}
+
+/// Priority fee at `percentile` (0.0 – 100.0, clamped) of `recent_txs`.
+///
+/// Returns 0 when `recent_txs` is empty.
+pub fn estimate_priority_fee_for_percentile(recent_txs: &[TransactionFee], percentile: f64) -> u64 {
+    nearest_rank(&sorted_priority_fees(recent_txs), percentile)
+}
+
+fn sorted_priority_fees(fees: &[TransactionFee]) -> Vec<u64> {
+    let mut sorted: Vec<u64> = fees.iter().map(|fee| fee.priority_fee()).collect();
+    sorted.sort_unstable();
+    sorted
+}
+
+pub(crate) fn nearest_rank(sorted: &[u64], percentile: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    // NaN clamps to 0 via the `as` cast below.
+    let p = percentile.clamp(0.0, 100.0);
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Rolling window of the most recent transaction fees.
+///
+/// Kept alongside the chain of [`crate::BlockFeeState`]s rather than inside
+/// it, so the per-block state stays a small `Copy` value.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PriorityFeeHistory {
+    fees: VecDeque<TransactionFee>,
+    capacity: usize,
+}
+
+impl PriorityFeeHistory {
+    /// Create an empty history sized by `config.priority_fee_history_size`,
+    /// capped at [`MAX_PRIORITY_FEE_HISTORY_SIZE`].
+    pub fn new(config: &FeeMarketConfig) -> Self {
+        Self::with_capacity(
+            config
+                .priority_fee_history_size
+                .min(MAX_PRIORITY_FEE_HISTORY_SIZE) as usize,
+        )
+    }
+
+    /// Create an empty history that retains at most `capacity` fees.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            fees: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Record a landed transaction, evicting the oldest entry when full.
+    pub fn record(&mut self, fee: TransactionFee) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.fees.len() == self.capacity {
+            self.fees.pop_front();
+        }
+        self.fees.push_back(fee);
+    }
+
+    /// Retained fees, oldest first.
+    pub fn fees(&self) -> Vec<TransactionFee> {
+        self.fees.iter().copied().collect()
+    }
+
+    /// Number of retained fees.
+    pub fn len(&self) -> usize {
+        self.fees.len()
+    }
+
+    /// Whether no fees have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.fees.is_empty()
+    }
+
+    /// Maximum number of retained fees.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
@@ -1,4 +1,17 @@
-use {borsh::{BorshDeserialize, BorshSerialize}, serde::{Deserialize, Serialize}};
+use {
+    crate::{
+        calculator::calculate_next_base_fee,
+        config::{FeeMarketConfig, MAX_UTILIZATION_EMA_ALPHA_BPS},
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+};
+
+/// Fixed-point scale of [`BlockFeeState::ema_utilization`]: usage exactly at
+/// the target is `UTILIZATION_SCALE`, twice the target `2 × UTILIZATION_SCALE`.
+/// Fine enough that, for any target below `UTILIZATION_SCALE / 2` compute
+/// units, a steady parent usage is recovered exactly from the average.
+pub const UTILIZATION_SCALE: u64 = 1_000_000_000_000;
 
 /// Per-block fee state that tracks the dynamic base fee and utilization.
 ///
@@ -7,8 +20,20 @@ use {borsh::{BorshDeserialize, BorshSerialize}, serde::{Deserialize, Serialize}}
 /// - The **parent's gas usage** (used to derive this block's base fee).
 /// - A running tally of **current gas used** (updated as transactions are added).
 /// - The **block height** for audit / indexing.
+/// - A running tally of **accounts written** (storage-write demand).
+/// - Whether the fee market is **paused** at a fixed emergency base fee.
+/// - A **moving average of utilization** that smooths base fee adjustments.
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
 )]
 pub struct BlockFeeState {
     /// Current base fee per compute unit (lamports).
@@ -25,40 +50,96 @@ pub struct BlockFeeState {
 
     /// Slot / block height.
     pub height: u64,
+
+    /// Running total of writable accounts locked by transactions in the
+    /// *current* block so far.  Tracked alongside `current_gas_used` so that
+    /// storage-write demand is observable independently of compute.
+    pub current_accounts_written: u64,
+
+    /// Whether the fee market is paused.  While paused, every child block's
+    /// base fee is `pause_fee` regardless of utilization.
+    pub paused: bool,
+
+    /// Fixed base fee per compute unit (lamports) applied while `paused`.
+    pub pause_fee: u64,
+
+    /// Exponential moving average of block usage relative to the target,
+    /// in units of 1 / [`UTILIZATION_SCALE`] (`UTILIZATION_SCALE` = exactly
+    /// at target), over the blocks *before* the parent.
+    /// The parent's usage is folded in when pricing the next block; see
+    /// [`Self::smoothed_utilization`].
+    pub ema_utilization: u64,
 }
 
 impl BlockFeeState {
     /// Create the genesis (block-0) fee state with a given initial base fee.
+    ///
+    /// The utilization average starts at the target so that smoothing
+    /// neither raises nor lowers the fee on its own while history builds up.
     pub fn genesis(initial_base_fee: u64) -> Self {
         Self {
             base_fee_per_cu: initial_base_fee,
             parent_gas_used: 0,
             current_gas_used: 0,
             height: 0,
+            current_accounts_written: 0,
+            paused: false,
+            pause_fee: 0,
+            ema_utilization: UTILIZATION_SCALE,
         }
     }
 
-    /// Record that `cu` compute units were consumed by a transaction in the
-    /// current block.  Returns the new running total.
+    /// Record that `cu` compute units were consumed and `accounts_written`
+    /// accounts were write-locked by transactions in the current block.
+    /// Returns the new running compute-unit total.
     #[inline]
-    pub fn record_gas(&mut self, cu: u64) -> u64 {
+    pub fn record_gas(&mut self, cu: u64, accounts_written: u64) -> u64 {
         self.current_gas_used = self.current_gas_used.saturating_add(cu);
+        self.current_accounts_written = self
+            .current_accounts_written
+            .saturating_add(accounts_written);
         self.current_gas_used
     }
 
-    /// Derive the child block's fee state given the *next* base fee.
+    /// Derive the child block's fee state given the *next* base fee.  The
+    /// pause state carries over to the child, and the parent's usage is
+    /// folded into the child's utilization average.
     ///
     /// The caller is responsible for computing `next_base_fee` via
     /// [`crate::calculator::calculate_next_base_fee`].
-    pub fn next_block(&self, next_base_fee: u64, next_height: u64) -> Self {
+    pub fn next_block(
+        &self,
+        config: &FeeMarketConfig,
+        next_base_fee: u64,
+        next_height: u64,
+    ) -> Self {
         Self {
             base_fee_per_cu: next_base_fee,
             parent_gas_used: self.current_gas_used,
             current_gas_used: 0,
             height: next_height,
+            current_accounts_written: 0,
+            paused: self.paused,
+            pause_fee: self.pause_fee,
+            ema_utilization: self.smoothed_utilization(config),
         }
     }
 
+    /// Freeze the base fee of every following block at `fixed_base_fee`
+    /// until [`Self::resume`] is called.  Used in emergencies such as a DoS
+    /// or fee manipulation attack.
+    pub fn pause(&mut self, fixed_base_fee: u64) {
+        self.paused = true;
+        self.pause_fee = fixed_base_fee;
+    }
+
+    /// Lift a pause.  The next block's base fee adjusts from this block's
+    /// base fee as usual.
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.pause_fee = 0;
+    }
+
     /// Block utilization as a ratio (0.0 – …).
     /// Values above 1.0 should not happen under normal operation but are not
     /// clamped here to aid debugging.
@@ -68,6 +149,183 @@ impl BlockFeeState {
         }
         self.current_gas_used as f64 / max_block_cu as f64
     }
+
+    /// Parent-block usage relative to the target, clamped to `[0.0, 2.0]`.
+    ///
+    /// `1.0` means the parent block hit the target exactly; `2.0` means it
+    /// used (at least) twice the target.  A zero target counts as fully
+    /// congested whenever the parent used any compute.
+    pub fn congestion_level(&self, config: &FeeMarketConfig) -> f64 {
+        let target = config.target_gas();
+        if target == 0 {
+            return if self.parent_gas_used == 0 { 0.0 } else { 2.0 };
+        }
+        (self.parent_gas_used as f64 / target as f64).clamp(0.0, 2.0)
+    }
+
+    /// Parent-block usage relative to the target, folded into the moving
+    /// average, in units of 1 / [`UTILIZATION_SCALE`]:
+    ///
+    /// ```text
+    /// parent = parent_gas_used × UTILIZATION_SCALE / target
+    /// (alpha_bps × parent + (10 000 − alpha_bps) × ema_utilization) / 10 000
+    /// ```
+    ///
+    /// where `alpha_bps` is `config.utilization_ema_alpha_bps`.  This is the
+    /// utilization the base fee responds to.  Both divisions round down.  A
+    /// zero target counts the parent as idle when it used no compute and as
+    /// twice the target otherwise, as in [`Self::congestion_level`].
+    pub fn smoothed_utilization(&self, config: &FeeMarketConfig) -> u64 {
+        let target = config.target_gas();
+        let parent = if target == 0 {
+            if self.parent_gas_used == 0 {
+                0
+            } else {
+                2 * UTILIZATION_SCALE as u128
+            }
+        } else {
+            self.parent_gas_used as u128 * UTILIZATION_SCALE as u128 / target as u128
+        };
+        let alpha = config
+            .utilization_ema_alpha_bps
+            .min(MAX_UTILIZATION_EMA_ALPHA_BPS) as u128;
+        let smoothed = (alpha * parent
+            + (MAX_UTILIZATION_EMA_ALPHA_BPS as u128 - alpha) * self.ema_utilization as u128)
+            / MAX_UTILIZATION_EMA_ALPHA_BPS as u128;
+        smoothed.min(u64::MAX as u128) as u64
+    }
+
+    /// Parent gas usage as seen by the base fee formula: the smoothed
+    /// utilization scaled back to compute units, rounded to the nearest
+    /// unit.  With smoothing disabled (`utilization_ema_alpha_bps` of
+    /// 10 000) this is exactly `parent_gas_used`.
+    pub(crate) fn smoothed_gas_used(&self, config: &FeeMarketConfig) -> u64 {
+        if config.utilization_ema_alpha_bps >= MAX_UTILIZATION_EMA_ALPHA_BPS {
+            return self.parent_gas_used;
+        }
+        let scale = UTILIZATION_SCALE as u128;
+        let gas = (self.smoothed_utilization(config) as u128 * config.target_gas() as u128
+            + scale / 2)
+            / scale;
+        gas.min(u64::MAX as u128) as u64
+    }
+
+    /// Returns `true` if the parent block used more than the target.
+    #[inline]
+    pub fn is_congested(&self, config: &FeeMarketConfig) -> bool {
+        self.parent_gas_used > config.target_gas()
+    }
+
+    /// Returns `true` if the parent block used more than 150 % of the target.
+    #[inline]
+    pub fn is_severely_congested(&self, config: &FeeMarketConfig) -> bool {
+        self.congestion_level(config) > 1.5
+    }
+
+    /// Project the base fee over the next `n` blocks, assuming each block
+    /// consumes `utilization_bps` of the target (10 000 = exactly target).
+    ///
+    /// Entry `i` is the base fee of the block `i + 1` blocks after this one.
+    pub fn simulate_n_blocks(
+        &self,
+        config: &FeeMarketConfig,
+        n: usize,
+        utilization_bps: u16,
+    ) -> Vec<u64> {
+        let gas_used = synthetic_gas_used(config, utilization_bps);
+        let mut base_fee = self.base_fee_per_cu;
+        let mut ema_utilization = self.smoothed_utilization(config);
+        (0..n)
+            .map(|_| {
+                let parent = self.synthetic_parent(base_fee, gas_used, ema_utilization);
+                base_fee = calculate_next_base_fee(config, &parent);
+                ema_utilization = parent.smoothed_utilization(config);
+                base_fee
+            })
+            .collect()
+    }
+
+    /// Project the base fee forward until it reaches `target_fee`, assuming
+    /// each block consumes `utilization_bps` of the target.
+    ///
+    /// Returns the trajectory and the number of blocks taken.  Stops early if
+    /// the fee and the utilization average both stop moving (e.g. pinned at
+    /// `max_base_fee`) before reaching `target_fee`, in which case the last
+    /// entry is below `target_fee`.
+    pub fn simulate_to_target_fee(
+        &self,
+        config: &FeeMarketConfig,
+        target_fee: u64,
+        utilization_bps: u16,
+    ) -> (Vec<u64>, usize) {
+        let gas_used = synthetic_gas_used(config, utilization_bps);
+        let mut trajectory = Vec::new();
+        let mut base_fee = self.base_fee_per_cu;
+        let mut ema_utilization = self.smoothed_utilization(config);
+        while base_fee < target_fee {
+            let parent = self.synthetic_parent(base_fee, gas_used, ema_utilization);
+            let next = calculate_next_base_fee(config, &parent);
+            let next_ema_utilization = parent.smoothed_utilization(config);
+            if next == base_fee && next_ema_utilization == ema_utilization {
+                break;
+            }
+            base_fee = next;
+            ema_utilization = next_ema_utilization;
+            trajectory.push(base_fee);
+        }
+        let blocks = trajectory.len();
+        (trajectory, blocks)
+    }
+
+    /// State pricing the block after one at `base_fee` that used `gas_used`,
+    /// with `ema_utilization` as the average before that block, under this
+    /// state's pause setting.
+    fn synthetic_parent(
+        &self,
+        base_fee: u64,
+        gas_used: u64,
+        ema_utilization: u64,
+    ) -> BlockFeeState {
+        BlockFeeState {
+            base_fee_per_cu: base_fee,
+            parent_gas_used: gas_used,
+            current_gas_used: 0,
+            height: 0,
+            current_accounts_written: 0,
+            paused: self.paused,
+            pause_fee: self.pause_fee,
+            ema_utilization,
+        }
+    }
+}
+
+/// Compute units used by a synthetic block at `utilization_bps` of target.
+fn synthetic_gas_used(config: &FeeMarketConfig, utilization_bps: u16) -> u64 {
+    let gas = config.target_gas() as u128 * utilization_bps as u128 / 10_000;
+    gas.min(u64::MAX as u128) as u64
+}
+
+/// Resources a transaction consumes, each priced on its own dimension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct TransactionResources {
+    /// Compute units consumed (or requested).
+    pub compute_units: u64,
+    /// Number of read-only accounts loaded.  Not currently charged.
+    pub accounts_read: u32,
+    /// Number of writable accounts, charged at
+    /// [`FeeMarketConfig::write_fee_per_account`] each.
+    pub accounts_written: u32,
+}
+
+impl TransactionResources {
+    /// Resources for a transaction that only consumes compute.
+    pub const fn compute_only(compute_units: u64) -> Self {
+        Self {
+            compute_units,
+            accounts_read: 0,
+            accounts_written: 0,
+        }
+    }
 }
 
 /// Breakdown of a single transaction's fee.
@@ -75,12 +333,28 @@ impl BlockFeeState {
 pub struct TransactionFee {
     /// Base fee component: `base_fee_per_cu × compute_units_used`.
     pub base_fee: u64,
-    /// Priority fee component: `priority_fee_per_cu × compute_units_used`.
-    pub priority_fee: u64,
-    /// Total fee (`base_fee + priority_fee`).
+    /// Validator's part of the priority fee
+    /// `priority_fee_per_cu × compute_units_used`.
+    pub priority_fee_validator: u64,
+    /// Passive staking reward pool's part of the priority fee:
+    /// `priority_fee_passive_stake_bps` of it.
+    pub priority_fee_stake_pool: u64,
+    /// Storage fee component: `write_fee_per_account × accounts_written`.
+    pub storage_fee: u64,
+    /// Total fee
+    /// (`base_fee + priority_fee_validator + priority_fee_stake_pool + storage_fee`).
     pub total_fee: u64,
 }
 
+impl TransactionFee {
+    /// The whole priority fee, before it is split between the validator and
+    /// the passive staking reward pool.
+    pub fn priority_fee(&self) -> u64 {
+        self.priority_fee_validator
+            .saturating_add(self.priority_fee_stake_pool)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,38 +366,96 @@ mod tests {
         assert_eq!(state.parent_gas_used, 0);
         assert_eq!(state.current_gas_used, 0);
         assert_eq!(state.height, 0);
+        assert_eq!(state.current_accounts_written, 0);
+        assert_eq!(state.ema_utilization, UTILIZATION_SCALE);
     }
 
     #[test]
     fn test_record_gas() {
         let mut state = BlockFeeState::genesis(5_000);
-        assert_eq!(state.record_gas(1_000_000), 1_000_000);
-        assert_eq!(state.record_gas(2_000_000), 3_000_000);
+        assert_eq!(state.record_gas(1_000_000, 2), 1_000_000);
+        assert_eq!(state.record_gas(2_000_000, 3), 3_000_000);
         assert_eq!(state.current_gas_used, 3_000_000);
+        assert_eq!(state.current_accounts_written, 5);
     }
 
     #[test]
     fn test_record_gas_saturates() {
         let mut state = BlockFeeState::genesis(5_000);
         state.current_gas_used = u64::MAX - 10;
-        assert_eq!(state.record_gas(100), u64::MAX);
+        assert_eq!(state.record_gas(100, 0), u64::MAX);
     }
 
     #[test]
     fn test_next_block() {
         let mut state = BlockFeeState::genesis(5_000);
-        state.record_gas(10_000_000);
-        let child = state.next_block(6_000, 1);
+        state.record_gas(10_000_000, 4);
+        let child = state.next_block(&FeeMarketConfig::default(), 6_000, 1);
         assert_eq!(child.base_fee_per_cu, 6_000);
         assert_eq!(child.parent_gas_used, 10_000_000);
         assert_eq!(child.current_gas_used, 0);
         assert_eq!(child.height, 1);
+        assert_eq!(child.current_accounts_written, 0);
+    }
+
+    #[test]
+    fn test_next_block_folds_parent_into_ema() {
+        let config = FeeMarketConfig::default();
+        let state = BlockFeeState {
+            parent_gas_used: config.target_gas() * 2,
+            ..BlockFeeState::genesis(5_000)
+        };
+        // 0.1 × 2.0 + 0.9 × 1.0
+        assert_eq!(state.smoothed_utilization(&config), 1_100_000_000_000);
+        let child = state.next_block(&config, 5_000, 1);
+        assert_eq!(child.ema_utilization, 1_100_000_000_000);
+    }
+
+    #[test]
+    fn test_smoothed_utilization_without_smoothing() {
+        let config = FeeMarketConfig {
+            utilization_ema_alpha_bps: 10_000,
+            ..FeeMarketConfig::default()
+        };
+        let state = BlockFeeState {
+            parent_gas_used: config.target_gas() / 2,
+            ema_utilization: 1_700_000_000_000,
+            ..BlockFeeState::genesis(5_000)
+        };
+        assert_eq!(state.smoothed_utilization(&config), 500_000_000_000);
+        assert_eq!(state.smoothed_gas_used(&config), config.target_gas() / 2);
+    }
+
+    #[test]
+    fn test_smoothed_utilization_settles_on_parent_usage() {
+        let config = FeeMarketConfig::default();
+        let mut state = BlockFeeState {
+            parent_gas_used: config.target_gas() / 3,
+            ..BlockFeeState::genesis(5_000)
+        };
+        for _ in 0..1_000 {
+            state.ema_utilization = state.smoothed_utilization(&config);
+        }
+        assert_eq!(state.smoothed_utilization(&config), state.ema_utilization);
+        assert_eq!(state.ema_utilization, 333_333_333_333);
+        assert_eq!(state.smoothed_gas_used(&config), config.target_gas() / 3);
+    }
+
+    #[test]
+    fn test_smoothed_utilization_saturates() {
+        let config = FeeMarketConfig::default();
+        let state = BlockFeeState {
+            parent_gas_used: u64::MAX,
+            ema_utilization: u64::MAX,
+            ..BlockFeeState::genesis(5_000)
+        };
+        assert_eq!(state.smoothed_utilization(&config), u64::MAX);
     }
 
     #[test]
     fn test_utilization() {
         let mut state = BlockFeeState::genesis(5_000);
-        state.record_gas(24_000_000);
+        state.record_gas(24_000_000, 0);
         let util = state.utilization(48_000_000);
         assert!((util - 0.5).abs() < 1e-9);
     }
@@ -134,6 +466,50 @@ mod tests {
         assert_eq!(state.utilization(0), 0.0);
     }
 
+    #[test]
+    fn test_congestion_at_zero_utilization() {
+        let config = FeeMarketConfig::default();
+        let state = BlockFeeState::genesis(5_000);
+        assert_eq!(state.congestion_level(&config), 0.0);
+        assert!(!state.is_congested(&config));
+        assert!(!state.is_severely_congested(&config));
+    }
+
+    #[test]
+    fn test_congestion_at_target() {
+        let config = FeeMarketConfig::default();
+        let state = BlockFeeState {
+            parent_gas_used: config.target_gas(),
+            ..BlockFeeState::genesis(5_000)
+        };
+        assert!((state.congestion_level(&config) - 1.0).abs() < 1e-9);
+        // Exactly at target is not "over" the target.
+        assert!(!state.is_congested(&config));
+        assert!(!state.is_severely_congested(&config));
+    }
+
+    #[test]
+    fn test_congestion_at_double_target() {
+        let config = FeeMarketConfig::default();
+        let state = BlockFeeState {
+            parent_gas_used: config.target_gas() * 2,
+            ..BlockFeeState::genesis(5_000)
+        };
+        assert!((state.congestion_level(&config) - 2.0).abs() < 1e-9);
+        assert!(state.is_congested(&config));
+        assert!(state.is_severely_congested(&config));
+    }
+
+    #[test]
+    fn test_congestion_level_clamped() {
+        let config = FeeMarketConfig::default();
+        let state = BlockFeeState {
+            parent_gas_used: config.target_gas() * 10,
+            ..BlockFeeState::genesis(5_000)
+        };
+        assert_eq!(state.congestion_level(&config), 2.0);
+    }
+
     #[test]
     fn test_borsh_roundtrip() {
         let state = BlockFeeState {
@@ -141,6 +517,10 @@ mod tests {
             parent_gas_used: 999_999,
             current_gas_used: 500_000,
             height: 42,
+            current_accounts_written: 17,
+            paused: false,
+            pause_fee: 0,
+            ema_utilization: 1_250_000_000_000,
         };
         let bytes = borsh::to_vec(&state).unwrap();
         let decoded: BlockFeeState = borsh::from_slice(&bytes).unwrap();
@@ -2,32 +2,66 @@
 
 use crate::{
     calculator::{
-        calculate_next_base_fee, calculate_transaction_fee, validate_config,
-        validate_transaction_fee,
+        calculate_next_base_fee, calculate_transaction_fee, estimate_inclusion_fee,
+        validate_config, validate_transaction_fee,
+    },
+    config::{
+        FeeMarketConfig, MAX_PRIORITY_FEE_PASSIVE_STAKE_BPS, MAX_UTILIZATION_EMA_ALPHA_BPS,
+        MIN_UTILIZATION_EMA_ALPHA_BPS,
     },
-    config::FeeMarketConfig,
     error::FeeError,
-    state::BlockFeeState,
+    history::{FeeHistory, FeeHistoryEntry},
+    priority::{
+        estimate_priority_fee_for_percentile, PriorityFeeHistory, PriorityFeeStats,
+        MAX_PRIORITY_FEE_HISTORY_SIZE,
+    },
+    state::{BlockFeeState, TransactionFee, TransactionResources, UTILIZATION_SCALE},
 };
 
 // ---------------------------------------------------------------------------
 // Helper: default config short-hand
 // ---------------------------------------------------------------------------
 
+/// Default config with utilization smoothing disabled, so expectations
+/// follow the plain EIP-1559 formula.  Section 20 covers smoothing.
 fn cfg() -> FeeMarketConfig {
+    FeeMarketConfig {
+        utilization_ema_alpha_bps: MAX_UTILIZATION_EMA_ALPHA_BPS,
+        ..FeeMarketConfig::default()
+    }
+}
+
+/// Default config, which smooths utilization with `alpha = 10 %`.
+fn smoothed_cfg() -> FeeMarketConfig {
     FeeMarketConfig::default()
 }
 
-/// Create a state where the parent used `parent_cu` compute units.
+/// Create a state where the parent used `parent_cu` compute units, as did
+/// every block before it under the default target.
 fn state_with_parent(base_fee: u64, parent_cu: u64, height: u64) -> BlockFeeState {
     BlockFeeState {
         base_fee_per_cu: base_fee,
         parent_gas_used: parent_cu,
         current_gas_used: 0,
         height,
+        current_accounts_written: 0,
+        paused: false,
+        pause_fee: 0,
+        ema_utilization: (parent_cu as u128 * UTILIZATION_SCALE as u128
+            / cfg().target_gas() as u128) as u64,
     }
 }
 
+/// Price a compute-only transaction under the default config.
+fn compute_fee(base_fee_per_cu: u64, priority_fee_per_cu: u64, cu: u64) -> TransactionFee {
+    calculate_transaction_fee(
+        &cfg(),
+        base_fee_per_cu,
+        priority_fee_per_cu,
+        &TransactionResources::compute_only(cu),
+    )
+}
+
 // ===========================================================================
 // 1. Base fee increases when blocks are full
 // ===========================================================================
@@ -273,30 +307,30 @@ fn alternating_full_empty_oscillates_around_initial() {
 
 #[test]
 fn transaction_fee_basic() {
-    let fee = calculate_transaction_fee(5_000, 100, 200_000);
+    let fee = compute_fee(5_000, 100, 200_000);
     assert_eq!(fee.base_fee, 5_000 * 200_000);
-    assert_eq!(fee.priority_fee, 100 * 200_000);
-    assert_eq!(fee.total_fee, fee.base_fee + fee.priority_fee);
+    assert_eq!(fee.priority_fee_validator, 100 * 200_000);
+    assert_eq!(fee.total_fee, fee.base_fee + fee.priority_fee_validator);
 }
 
 #[test]
 fn transaction_fee_zero_priority() {
-    let fee = calculate_transaction_fee(5_000, 0, 200_000);
-    assert_eq!(fee.priority_fee, 0);
+    let fee = compute_fee(5_000, 0, 200_000);
+    assert_eq!(fee.priority_fee(), 0);
     assert_eq!(fee.total_fee, fee.base_fee);
 }
 
 #[test]
 fn transaction_fee_zero_cu() {
-    let fee = calculate_transaction_fee(5_000, 100, 0);
+    let fee = compute_fee(5_000, 100, 0);
     assert_eq!(fee.base_fee, 0);
-    assert_eq!(fee.priority_fee, 0);
+    assert_eq!(fee.priority_fee(), 0);
     assert_eq!(fee.total_fee, 0);
 }
 
 #[test]
 fn transaction_fee_saturates() {
-    let fee = calculate_transaction_fee(u64::MAX, u64::MAX, u64::MAX);
+    let fee = compute_fee(u64::MAX, u64::MAX, u64::MAX);
     assert_eq!(fee.total_fee, u64::MAX, "should saturate, not overflow");
 }
 
@@ -306,10 +340,10 @@ fn transaction_fee_saturates() {
 
 #[test]
 fn higher_priority_fee_means_higher_total() {
-    let low = calculate_transaction_fee(5_000, 10, 200_000);
-    let high = calculate_transaction_fee(5_000, 1_000, 200_000);
+    let low = compute_fee(5_000, 10, 200_000);
+    let high = compute_fee(5_000, 1_000, 200_000);
     assert!(high.total_fee > low.total_fee);
-    assert!(high.priority_fee > low.priority_fee);
+    assert!(high.priority_fee() > low.priority_fee());
     // Base fees should be identical.
     assert_eq!(low.base_fee, high.base_fee);
 }
@@ -321,7 +355,7 @@ fn priority_fee_ordering_is_total_ordering() {
     let priorities = [0u64, 1, 10, 100, 1_000, 10_000];
     let totals: Vec<u64> = priorities
         .iter()
-        .map(|&p| calculate_transaction_fee(base, p, cu).total_fee)
+        .map(|&p| compute_fee(base, p, cu).total_fee)
         .collect();
     for window in totals.windows(2) {
         assert!(
@@ -342,7 +376,7 @@ fn validate_ok() {
         100_000_000_000, // plenty of lamports
         100,             // priority
         5_000,           // base fee
-        200_000,         // CU
+        &TransactionResources::compute_only(200_000),
         &config,
     );
     assert!(result.is_ok());
@@ -357,7 +391,7 @@ fn validate_insufficient_fee() {
         1, // far too little
         0,
         5_000,
-        200_000,
+        &TransactionResources::compute_only(200_000),
         &config,
     );
     assert!(matches!(result, Err(FeeError::InsufficientFee { .. })));
@@ -373,7 +407,7 @@ fn validate_priority_too_low() {
         100_000_000_000,
         50, // below minimum of 100
         5_000,
-        200_000,
+        &TransactionResources::compute_only(200_000),
         &config,
     );
     assert!(matches!(result, Err(FeeError::PriorityFeeTooLow { .. })));
@@ -386,7 +420,7 @@ fn validate_cu_exceed_max() {
         100_000_000_000,
         0,
         5_000,
-        config.max_block_compute_units + 1, // too many CU
+        &TransactionResources::compute_only(config.max_block_compute_units + 1), // too many CU
         &config,
     );
     assert!(matches!(
@@ -401,7 +435,13 @@ fn validate_exact_boundary() {
     let base = 5_000u64;
     let cu = 200_000u64;
     let exact_fee = base * cu; // no priority
-    let result = validate_transaction_fee(exact_fee, 0, base, cu, &config);
+    let result = validate_transaction_fee(
+        exact_fee,
+        0,
+        base,
+        &TransactionResources::compute_only(cu),
+        &config,
+    );
     assert!(result.is_ok(), "should accept exact amount");
 }
 
@@ -411,13 +451,43 @@ fn validate_one_lamport_short() {
     let base = 5_000u64;
     let cu = 200_000u64;
     let exact_fee = base * cu;
-    let result = validate_transaction_fee(exact_fee - 1, 0, base, cu, &config);
+    let result = validate_transaction_fee(
+        exact_fee - 1,
+        0,
+        base,
+        &TransactionResources::compute_only(cu),
+        &config,
+    );
     assert!(
         matches!(result, Err(FeeError::InsufficientFee { .. })),
         "should reject when 1 lamport short"
     );
 }
 
+#[test]
+fn validate_includes_storage_fee() {
+    let config = cfg();
+    let base = 5_000u64;
+    let cu = 200_000u64;
+    let resources = TransactionResources {
+        compute_units: cu,
+        accounts_read: 2,
+        accounts_written: 3,
+    };
+    let full_fee = base * cu + 3 * config.write_fee_per_account;
+    assert!(config.write_fee_per_account > 0);
+
+    // Enough for compute alone is not enough once the writes are priced.
+    let result = validate_transaction_fee(full_fee - 1, 0, base, &resources, &config);
+    assert!(matches!(
+        result,
+        Err(FeeError::InsufficientFee { required, .. }) if required == full_fee
+    ));
+    let fee = validate_transaction_fee(full_fee, 0, base, &resources, &config).unwrap();
+    assert_eq!(fee.storage_fee, 3 * config.write_fee_per_account);
+    assert_eq!(fee.total_fee, full_fee);
+}
+
 // ===========================================================================
 // 10. Config validation
 // ===========================================================================
@@ -464,6 +534,68 @@ fn validate_config_utilization_over_100() {
     ));
 }
 
+#[test]
+fn validate_config_priority_fee_history_too_large() {
+    let config = FeeMarketConfig {
+        priority_fee_history_size: u64::MAX,
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_config(&config),
+        Err(FeeError::InvalidConfig { .. })
+    ));
+    // An unvalidated config still yields a bounded history.
+    assert_eq!(
+        PriorityFeeHistory::new(&config).capacity(),
+        MAX_PRIORITY_FEE_HISTORY_SIZE as usize
+    );
+}
+
+#[test]
+fn validate_config_passive_stake_share_too_large() {
+    let config = FeeMarketConfig {
+        priority_fee_passive_stake_bps: MAX_PRIORITY_FEE_PASSIVE_STAKE_BPS,
+        ..Default::default()
+    };
+    assert!(validate_config(&config).is_ok());
+    let config = FeeMarketConfig {
+        priority_fee_passive_stake_bps: MAX_PRIORITY_FEE_PASSIVE_STAKE_BPS + 1,
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_config(&config),
+        Err(FeeError::InvalidConfig { .. })
+    ));
+}
+
+#[test]
+fn validate_config_utilization_ema_alpha_range() {
+    for alpha in [
+        MIN_UTILIZATION_EMA_ALPHA_BPS,
+        1_000,
+        MAX_UTILIZATION_EMA_ALPHA_BPS,
+    ] {
+        let config = FeeMarketConfig {
+            utilization_ema_alpha_bps: alpha,
+            ..Default::default()
+        };
+        assert!(validate_config(&config).is_ok(), "alpha {alpha}");
+    }
+    for alpha in [0, MIN_UTILIZATION_EMA_ALPHA_BPS - 1, 10_001, u16::MAX] {
+        let config = FeeMarketConfig {
+            utilization_ema_alpha_bps: alpha,
+            ..Default::default()
+        };
+        assert!(
+            matches!(
+                validate_config(&config),
+                Err(FeeError::InvalidConfig { .. })
+            ),
+            "alpha {alpha}"
+        );
+    }
+}
+
 // ===========================================================================
 // 11. Edge cases
 // ===========================================================================
@@ -505,8 +637,8 @@ fn edge_case_very_large_base_fee() {
 #[test]
 fn edge_case_block_fee_state_next_block() {
     let mut parent = BlockFeeState::genesis(5_000);
-    parent.record_gas(30_000_000);
-    let child = parent.next_block(5_500, 1);
+    parent.record_gas(30_000_000, 0);
+    let child = parent.next_block(&cfg(), 5_500, 1);
     assert_eq!(child.base_fee_per_cu, 5_500);
     assert_eq!(child.parent_gas_used, 30_000_000);
     assert_eq!(child.current_gas_used, 0);
@@ -550,9 +682,9 @@ fn simulation_gradual_congestion_then_relief() {
     for i in 0..20 {
         let usage = 24_000_000 + (i as u64) * 1_200_000; // 24 M → 48 M
         state.current_gas_used = 0;
-        state.record_gas(usage);
+        state.record_gas(usage, 0);
         let next = calculate_next_base_fee(&config, &state);
-        state = state.next_block(next, state.height + 1);
+        state = state.next_block(&cfg(), next, state.height + 1);
         fees.push(next);
     }
 
@@ -561,7 +693,7 @@ fn simulation_gradual_congestion_then_relief() {
     // 20 blocks of zero load.
     for _ in 0..20 {
         let next = calculate_next_base_fee(&config, &state);
-        state = state.next_block(next, state.height + 1);
+        state = state.next_block(&cfg(), next, state.height + 1);
         fees.push(next);
     }
 
@@ -588,6 +720,80 @@ fn simulation_steady_state_at_target() {
     }
 }
 
+#[test]
+fn simulate_n_blocks_matches_step_by_step() {
+    let config = cfg();
+    let start = state_with_parent(10_000, 0, 0);
+    let trajectory = start.simulate_n_blocks(&config, 10, 15_000);
+    assert_eq!(trajectory.len(), 10);
+
+    let mut fee = start.base_fee_per_cu;
+    for (i, projected) in trajectory.iter().enumerate() {
+        fee = calculate_next_base_fee(&config, &state_with_parent(fee, 36_000_000, 0));
+        assert_eq!(*projected, fee, "block {i}");
+    }
+    assert!(start.simulate_n_blocks(&config, 0, 15_000).is_empty());
+}
+
+#[test]
+fn simulate_at_target_is_flat() {
+    let config = cfg();
+    let start = state_with_parent(100_000, 0, 0);
+    assert!(start
+        .simulate_n_blocks(&config, 50, 10_000)
+        .iter()
+        .all(|&fee| fee == 100_000));
+}
+
+#[test]
+fn fee_doubles_within_fee_double_blocks_at_150pct() {
+    for denominator in [4, 8, 16] {
+        let config = FeeMarketConfig {
+            base_fee_change_denominator: denominator,
+            ..cfg()
+        };
+        let start = BlockFeeState::genesis(config.min_base_fee);
+        let target = config.min_base_fee * 2;
+        let (trajectory, blocks) = start.simulate_to_target_fee(&config, target, 15_000);
+        assert_eq!(blocks, trajectory.len());
+        assert!(*trajectory.last().unwrap() >= target);
+        assert!(
+            blocks as u64 <= config.fee_double_blocks(),
+            "denominator {denominator}: took {blocks} blocks, bound {}",
+            config.fee_double_blocks()
+        );
+    }
+}
+
+#[test]
+fn simulate_to_target_fee_stops_at_ceiling() {
+    let config = cfg();
+    let start = BlockFeeState::genesis(config.max_base_fee - 1);
+    let (trajectory, blocks) =
+        start.simulate_to_target_fee(&config, u64::MAX, 20_000);
+    assert_eq!(blocks, 1);
+    assert_eq!(trajectory, vec![config.max_base_fee]);
+
+    // Already at or above the target: nothing to simulate.
+    let (trajectory, blocks) = start.simulate_to_target_fee(&config, 1, 20_000);
+    assert!(trajectory.is_empty());
+    assert_eq!(blocks, 0);
+}
+
+#[test]
+fn estimate_inclusion_fee_uses_projected_base_fee() {
+    let config = cfg();
+    let state = state_with_parent(10_000, 0, 0);
+    let resources = TransactionResources::compute_only(200_000);
+    let now = estimate_inclusion_fee(&config, &state, 0, 20_000, 100, &resources);
+    assert_eq!(now, compute_fee(10_000, 100, 200_000));
+
+    let projected = *state.simulate_n_blocks(&config, 5, 20_000).last().unwrap();
+    let later = estimate_inclusion_fee(&config, &state, 5, 20_000, 100, &resources);
+    assert_eq!(later, compute_fee(projected, 100, 200_000));
+    assert!(later.total_fee > now.total_fee);
+}
+
 // ===========================================================================
 // 13. Denominator sensitivity
 // ===========================================================================
@@ -627,3 +833,557 @@ fn error_messages_are_readable() {
     assert!(msg.contains("100"));
     assert!(msg.contains("1000000"));
 }
+
+// ===========================================================================
+// 15. Priority fee statistics
+// ===========================================================================
+
+fn tx_with_priority(priority_fee: u64) -> TransactionFee {
+    TransactionFee {
+        base_fee: 5_000,
+        priority_fee_validator: priority_fee,
+        priority_fee_stake_pool: 0,
+        storage_fee: 0,
+        total_fee: 5_000 + priority_fee,
+    }
+}
+
+#[test]
+fn priority_fee_statistics_uniform() {
+    // Shuffled 1..=1000: order must not matter.
+    let fees: Vec<TransactionFee> = (0..1_000u64)
+        .map(|i| tx_with_priority((i * 7_919) % 1_000 + 1))
+        .collect();
+    let stats = cfg().priority_fee_statistics(&fees);
+    assert_eq!(stats.p25, 250);
+    assert_eq!(stats.p50, 500);
+    assert_eq!(stats.p75, 750);
+    assert_eq!(stats.p90, 900);
+    assert_eq!(stats.p99, 990);
+    assert_eq!(stats.mean, 500);
+    assert_eq!(stats.max, 1_000);
+    assert_eq!(estimate_priority_fee_for_percentile(&fees, 50.0), stats.p50);
+
+    // Raw samples in any unit summarise identically.
+    let samples: Vec<u64> = fees.iter().map(|fee| fee.priority_fee()).collect();
+    assert_eq!(PriorityFeeStats::from_samples(&samples), stats);
+}
+
+#[test]
+fn priority_fee_statistics_pareto() {
+    // Deterministic Pareto(x_m = 1_000, alpha = 1.5) sample via the inverse CDF.
+    const SCALE: f64 = 1_000.0;
+    const ALPHA: f64 = 1.5;
+    let n = 10_000;
+    let fees: Vec<TransactionFee> = (0..n)
+        .map(|i| {
+            let u = (i as f64 + 0.5) / n as f64;
+            tx_with_priority((SCALE / (1.0 - u).powf(1.0 / ALPHA)) as u64)
+        })
+        .collect();
+    let stats = cfg().priority_fee_statistics(&fees);
+
+    // Percentiles are monotone and bounded by the maximum.
+    assert!(stats.p25 <= stats.p50);
+    assert!(stats.p50 <= stats.p75);
+    assert!(stats.p75 <= stats.p90);
+    assert!(stats.p90 <= stats.p99);
+    assert!(stats.p99 <= stats.max);
+
+    // Median matches the analytic x_m · 2^(1/alpha) within 1 %.
+    let median = SCALE * 2f64.powf(1.0 / ALPHA);
+    assert!((stats.p50 as f64 - median).abs() / median < 0.01);
+
+    // Right-skewed: mean above median, and a long tail.
+    assert!(stats.mean > stats.p50);
+    assert!(stats.p99 > 10 * stats.p50);
+}
+
+#[test]
+fn priority_fee_statistics_edge_cases() {
+    assert_eq!(
+        cfg().priority_fee_statistics(&[]),
+        Default::default()
+    );
+    assert_eq!(estimate_priority_fee_for_percentile(&[], 90.0), 0);
+
+    let single = [tx_with_priority(42)];
+    let stats = cfg().priority_fee_statistics(&single);
+    assert_eq!((stats.p25, stats.p99, stats.mean, stats.max), (42, 42, 42, 42));
+
+    let fees: Vec<TransactionFee> = (1..=10).map(tx_with_priority).collect();
+    assert_eq!(estimate_priority_fee_for_percentile(&fees, 0.0), 1);
+    assert_eq!(estimate_priority_fee_for_percentile(&fees, -5.0), 1);
+    assert_eq!(estimate_priority_fee_for_percentile(&fees, 100.0), 10);
+    assert_eq!(estimate_priority_fee_for_percentile(&fees, 250.0), 10);
+}
+
+#[test]
+fn priority_fee_history_is_bounded() {
+    let config = FeeMarketConfig {
+        priority_fee_history_size: 3,
+        ..Default::default()
+    };
+    let mut history = PriorityFeeHistory::new(&config);
+    assert!(history.is_empty());
+    for fee in 1..=5 {
+        history.record(tx_with_priority(fee));
+    }
+    assert_eq!(history.len(), 3);
+    let retained: Vec<u64> = history.fees().iter().map(|f| f.priority_fee()).collect();
+    assert_eq!(retained, vec![3, 4, 5]);
+
+    let mut disabled = PriorityFeeHistory::with_capacity(0);
+    disabled.record(tx_with_priority(1));
+    assert!(disabled.is_empty());
+}
+
+// ===========================================================================
+// 16. Multi-dimensional pricing (storage writes)
+// ===========================================================================
+
+#[test]
+fn storage_fee_is_zero_without_writes() {
+    // Backward compatibility: no writes prices exactly like the compute-only formula.
+    for &(base, priority, cu) in &[(5_000u64, 100u64, 200_000u64), (7, 0, 1), (0, 0, 0)] {
+        let resources = TransactionResources {
+            compute_units: cu,
+            accounts_read: 12,
+            accounts_written: 0,
+        };
+        let fee = calculate_transaction_fee(&cfg(), base, priority, &resources);
+        assert_eq!(fee.storage_fee, 0);
+        assert_eq!(fee.base_fee, base * cu);
+        assert_eq!(fee.priority_fee(), priority * cu);
+        assert_eq!(fee.total_fee, base * cu + priority * cu);
+    }
+}
+
+#[test]
+fn storage_fee_charges_per_written_account() {
+    let config = cfg();
+    let resources = TransactionResources {
+        compute_units: 200_000,
+        accounts_read: 4,
+        accounts_written: 3,
+    };
+    let fee = calculate_transaction_fee(&config, 5_000, 100, &resources);
+    assert_eq!(fee.base_fee, 5_000 * 200_000);
+    assert_eq!(fee.priority_fee_validator, 100 * 200_000);
+    assert_eq!(fee.storage_fee, 3 * config.write_fee_per_account);
+    assert_eq!(
+        fee.total_fee,
+        fee.base_fee + fee.priority_fee_validator + fee.storage_fee
+    );
+
+    // Read-only accounts are not charged.
+    let more_reads = TransactionResources {
+        accounts_read: 40,
+        ..resources
+    };
+    assert_eq!(
+        calculate_transaction_fee(&config, 5_000, 100, &more_reads),
+        fee
+    );
+}
+
+#[test]
+fn storage_fee_uses_config_tunable() {
+    let config = FeeMarketConfig {
+        write_fee_per_account: 123,
+        ..Default::default()
+    };
+    let resources = TransactionResources {
+        compute_units: 0,
+        accounts_read: 0,
+        accounts_written: 10,
+    };
+    let fee = calculate_transaction_fee(&config, 5_000, 0, &resources);
+    assert_eq!(fee.storage_fee, 1_230);
+    assert_eq!(fee.total_fee, 1_230);
+}
+
+#[test]
+fn storage_fee_saturates() {
+    let config = FeeMarketConfig {
+        write_fee_per_account: u64::MAX,
+        ..Default::default()
+    };
+    let resources = TransactionResources {
+        compute_units: 1,
+        accounts_read: 0,
+        accounts_written: u32::MAX,
+    };
+    let fee = calculate_transaction_fee(&config, 1, 0, &resources);
+    assert_eq!(fee.storage_fee, u64::MAX);
+    assert_eq!(fee.total_fee, u64::MAX);
+}
+
+#[test]
+fn record_gas_tracks_account_write_demand() {
+    let mut state = BlockFeeState::genesis(5_000);
+    state.record_gas(200_000, 3);
+    state.record_gas(100_000, 0);
+    state.record_gas(50_000, 2);
+    assert_eq!(state.current_gas_used, 350_000);
+    assert_eq!(state.current_accounts_written, 5);
+
+    // Write demand does not move the compute-based base fee.
+    let compute_only = BlockFeeState {
+        current_accounts_written: 0,
+        ..state
+    };
+    assert_eq!(
+        calculate_next_base_fee(&cfg(), &state.next_block(&cfg(), 5_000, 1)),
+        calculate_next_base_fee(&cfg(), &compute_only.next_block(&cfg(), 5_000, 1))
+    );
+}
+
+// ===========================================================================
+// 17. Fee history and priority fee suggestions
+// ===========================================================================
+
+#[test]
+fn fee_history_evicts_oldest_at_capacity() {
+    let config = cfg();
+    let state = BlockFeeState::genesis(5_000);
+    let mut history = FeeHistory::new(3);
+    for height in 1..=5 {
+        state.push_history_entry(&config, &mut history, height, &[height * 10]);
+    }
+    assert_eq!(history.len(), 3);
+    let heights: Vec<u64> = history.entries.iter().map(|e| e.height).collect();
+    assert_eq!(heights, vec![3, 4, 5]);
+
+    let mut disabled = FeeHistory::new(0);
+    state.push_history_entry(&config, &mut disabled, 1, &[1]);
+    assert!(disabled.is_empty());
+}
+
+#[test]
+fn fee_history_entry_summarises_block() {
+    let config = cfg();
+    let mut state = BlockFeeState::genesis(7_000);
+    state.record_gas(config.target_gas() * 3 / 2, 0);
+    let mut history = FeeHistory::new(10);
+    let fees: Vec<u64> = (1..=100).rev().collect();
+    state.push_history_entry(&config, &mut history, 42, &fees);
+
+    let entry = history.entries[0];
+    assert_eq!(entry.height, 42);
+    assert_eq!(entry.base_fee, 7_000);
+    assert_eq!(
+        (entry.p25_priority, entry.p50_priority, entry.p75_priority),
+        (25, 50, 75)
+    );
+    assert_eq!(entry.block_utilization_bps, 5_000);
+
+    // An empty block sits 100 % below target.
+    let empty = BlockFeeState::genesis(7_000);
+    empty.push_history_entry(&config, &mut history, 43, &[]);
+    assert_eq!(history.entries[1].block_utilization_bps, -10_000);
+    assert_eq!(history.entries[1].p50_priority, 0);
+}
+
+#[test]
+fn fee_history_entry_interpolates_between_quartiles() {
+    let entry = FeeHistoryEntry {
+        p25_priority: 100,
+        p50_priority: 200,
+        p75_priority: 1_200,
+        ..Default::default()
+    };
+    assert_eq!(entry.priority_at(0), 100);
+    assert_eq!(entry.priority_at(25), 100);
+    assert_eq!(entry.priority_at(30), 120);
+    assert_eq!(entry.priority_at(50), 200);
+    assert_eq!(entry.priority_at(60), 600);
+    assert_eq!(entry.priority_at(75), 1_200);
+    assert_eq!(entry.priority_at(100), 1_200);
+}
+
+#[test]
+fn suggest_priority_fee_empty_history() {
+    assert_eq!(FeeHistory::new(10).suggest_priority_fee(50), 0);
+}
+
+#[test]
+fn suggest_priority_fee_tracks_true_median() {
+    let config = cfg();
+    let state = BlockFeeState::genesis(config.min_base_fee);
+    let mut history = FeeHistory::new(100);
+    let mut all_fees = Vec::new();
+
+    for height in 0..100u64 {
+        // Each block's fees are a different scale (±10 %) and offset.
+        let scale = 90 + (height * 7) % 21;
+        let offset = (height * 13) % 50;
+        let fees: Vec<u64> = (1..=200u64).map(|k| k * scale + offset).collect();
+        state.push_history_entry(&config, &mut history, height, &fees);
+        all_fees.extend(fees);
+    }
+    assert_eq!(history.len(), 100);
+
+    all_fees.sort_unstable();
+    let true_median = all_fees[all_fees.len() / 2] as f64;
+    let suggested = history.suggest_priority_fee(50) as f64;
+    assert!(
+        (suggested - true_median).abs() <= true_median * 0.05,
+        "suggested {suggested} not within 5% of true median {true_median}"
+    );
+
+    // Higher percentiles never suggest a lower fee.
+    assert!(history.suggest_priority_fee(75) >= history.suggest_priority_fee(50));
+    assert!(history.suggest_priority_fee(50) >= history.suggest_priority_fee(25));
+}
+
+// ===========================================================================
+// 18. Emergency pause
+// ===========================================================================
+
+#[test]
+fn paused_base_fee_ignores_utilization() {
+    let config = cfg();
+    let mut state = state_with_parent(20_000, config.max_block_compute_units, 1);
+    state.pause(8_000);
+
+    for parent_cu in [0, config.target_gas(), config.max_block_compute_units] {
+        state.parent_gas_used = parent_cu;
+        assert_eq!(calculate_next_base_fee(&config, &state), 8_000);
+    }
+    // The fixed fee is used as given, even below the configured floor.
+    state.pause(1);
+    assert_eq!(calculate_next_base_fee(&config, &state), 1);
+}
+
+#[test]
+fn pause_carries_over_to_child_blocks_until_resumed() {
+    let config = cfg();
+    let mut state = BlockFeeState::genesis(config.min_base_fee * 4);
+    state.pause(config.min_base_fee * 2);
+
+    for height in 1..=10 {
+        state.record_gas(config.max_block_compute_units, 0);
+        let next_fee = calculate_next_base_fee(&config, &state);
+        state = state.next_block(&config, next_fee, height);
+        assert!(state.paused);
+        assert_eq!(state.base_fee_per_cu, config.min_base_fee * 2);
+    }
+
+    state.resume();
+    state.record_gas(config.max_block_compute_units, 0);
+    let next_fee = calculate_next_base_fee(&config, &state);
+    assert!(next_fee > config.min_base_fee * 2, "adjustment resumes");
+    assert!(!state.next_block(&config, next_fee, 11).paused);
+}
+
+#[test]
+fn paused_projection_stays_at_pause_fee() {
+    let config = cfg();
+    let mut state = BlockFeeState::genesis(config.min_base_fee);
+    state.pause(config.min_base_fee * 3);
+    assert_eq!(
+        state.simulate_n_blocks(&config, 5, 20_000),
+        vec![config.min_base_fee * 3; 5]
+    );
+}
+
+// ===========================================================================
+// 19. Priority fee redistribution to passive stakers
+// ===========================================================================
+
+#[test]
+fn priority_fee_goes_to_validator_by_default() {
+    let fee = calculate_transaction_fee(
+        &cfg(),
+        5_000,
+        100,
+        &TransactionResources::compute_only(200_000),
+    );
+    assert_eq!(fee.priority_fee_validator, 20_000_000);
+    assert_eq!(fee.priority_fee_stake_pool, 0);
+}
+
+#[test]
+fn priority_fee_split_with_passive_stakers() {
+    let config = FeeMarketConfig {
+        priority_fee_passive_stake_bps: 2_500,
+        ..Default::default()
+    };
+    let fee = calculate_transaction_fee(
+        &config,
+        5_000,
+        100,
+        &TransactionResources::compute_only(200_000),
+    );
+    assert_eq!(fee.priority_fee_validator, 15_000_000);
+    assert_eq!(fee.priority_fee_stake_pool, 5_000_000);
+    assert_eq!(fee.priority_fee(), 20_000_000);
+    assert_eq!(fee.total_fee, 1_000_000_000 + 20_000_000);
+
+    // The stake pool share rounds down; the validator keeps the dust.
+    let fee = calculate_transaction_fee(&config, 0, 3, &TransactionResources::compute_only(1));
+    assert_eq!(fee.priority_fee_stake_pool, 0);
+    assert_eq!(fee.priority_fee_validator, 3);
+}
+
+#[test]
+fn priority_fee_split_does_not_affect_statistics() {
+    let config = FeeMarketConfig {
+        priority_fee_passive_stake_bps: MAX_PRIORITY_FEE_PASSIVE_STAKE_BPS,
+        ..Default::default()
+    };
+    let resources = TransactionResources::compute_only(1_000);
+    let fees: Vec<TransactionFee> = [10, 20, 30]
+        .into_iter()
+        .map(|priority| calculate_transaction_fee(&config, 5_000, priority, &resources))
+        .collect();
+    let stats = config.priority_fee_statistics(&fees);
+    assert_eq!(stats.p50, 20_000);
+    assert_eq!(stats.max, 30_000);
+}
+
+// ===========================================================================
+// 20. EMA-smoothed utilization
+// ===========================================================================
+
+/// Run `blocks` blocks that each use `gas_used`, starting from `state`.
+fn run_blocks(
+    config: &FeeMarketConfig,
+    mut state: BlockFeeState,
+    gas_used: u64,
+    blocks: u64,
+) -> BlockFeeState {
+    for _ in 0..blocks {
+        state.record_gas(gas_used, 0);
+        let next = calculate_next_base_fee(config, &state);
+        state = state.next_block(config, next, state.height + 1);
+    }
+    state
+}
+
+#[test]
+fn smoothing_matches_unsmoothed_formula_in_steady_state() {
+    // When every past block used what the parent did, the average equals the
+    // parent's usage and smoothing changes nothing.
+    for parent_cu in [
+        0, 12_000_000, 18_000_000, 24_000_000, 24_000_001, 30_000_000, 36_000_000, 48_000_000,
+    ] {
+        for base_fee in [5_000, 10_000, 1_000_000] {
+            let state = state_with_parent(base_fee, parent_cu, 0);
+            assert_eq!(
+                calculate_next_base_fee(&smoothed_cfg(), &state),
+                calculate_next_base_fee(&cfg(), &state),
+                "parent {parent_cu}, base fee {base_fee}"
+            );
+        }
+    }
+}
+
+#[test]
+fn unsmoothed_formula_ignores_utilization_history() {
+    for ema_utilization in [
+        0,
+        UTILIZATION_SCALE / 2,
+        UTILIZATION_SCALE,
+        2 * UTILIZATION_SCALE,
+        u64::MAX,
+    ] {
+        let state = BlockFeeState {
+            ema_utilization,
+            ..state_with_parent(10_000, 48_000_000, 0)
+        };
+        assert_eq!(calculate_next_base_fee(&cfg(), &state), 11_250);
+    }
+}
+
+#[test]
+fn single_spike_block_moves_smoothed_fee_less_than_5pct() {
+    let config = smoothed_cfg();
+    let start = state_with_parent(1_000_000, config.target_gas(), 0);
+    let steady = run_blocks(&config, start, config.target_gas(), 20);
+    assert_eq!(steady.base_fee_per_cu, 1_000_000, "at target the fee holds");
+
+    // One completely full block (twice the target) after a calm history.
+    let spiked = run_blocks(&config, steady, config.max_block_compute_units, 1);
+    let next = calculate_next_base_fee(&config, &spiked);
+    let change = next.abs_diff(steady.base_fee_per_cu);
+    assert!(
+        change * 20 < steady.base_fee_per_cu,
+        "spike moved the fee by {change}"
+    );
+
+    // Without smoothing the same spike moves the fee by the full 12.5 %.
+    let unsmoothed = BlockFeeState {
+        parent_gas_used: config.max_block_compute_units,
+        ..steady
+    };
+    assert_eq!(calculate_next_base_fee(&cfg(), &unsmoothed), 1_125_000);
+}
+
+#[test]
+fn smoothed_sustained_congestion_drives_fee_to_ceiling() {
+    let config = smoothed_cfg();
+    let mut state = BlockFeeState::genesis(config.min_base_fee);
+    let mut previous = state.base_fee_per_cu;
+    for _ in 0..500 {
+        state = run_blocks(&config, state, config.max_block_compute_units, 1);
+        assert!(
+            state.base_fee_per_cu >= previous,
+            "fee never falls under load"
+        );
+        previous = state.base_fee_per_cu;
+    }
+    assert_eq!(state.base_fee_per_cu, config.max_base_fee);
+
+    let (trajectory, _) = BlockFeeState::genesis(config.min_base_fee).simulate_to_target_fee(
+        &config,
+        u64::MAX,
+        20_000,
+    );
+    assert_eq!(trajectory.last(), Some(&config.max_base_fee));
+}
+
+#[test]
+fn smoothing_damps_alternating_full_empty_blocks() {
+    let swing = |config: &FeeMarketConfig| {
+        let mut state = BlockFeeState::genesis(1_000_000);
+        let mut fees = Vec::new();
+        for i in 0..40 {
+            let usage = if i % 2 == 0 {
+                config.max_block_compute_units
+            } else {
+                0
+            };
+            state = run_blocks(config, state, usage, 1);
+            fees.push(state.base_fee_per_cu);
+        }
+        let tail = &fees[20..];
+        tail.iter().max().unwrap() - tail.iter().min().unwrap()
+    };
+    let smoothed = swing(&smoothed_cfg());
+    let unsmoothed = swing(&cfg());
+    assert!(
+        smoothed * 2 < unsmoothed,
+        "smoothed swing {smoothed}, unsmoothed swing {unsmoothed}"
+    );
+}
+
+#[test]
+fn smoothed_simulate_n_blocks_matches_block_chain() {
+    let config = smoothed_cfg();
+    let start = BlockFeeState::genesis(10_000);
+    let trajectory = start.simulate_n_blocks(&config, 10, 15_000);
+
+    let mut state = start;
+    for (i, projected) in trajectory.iter().enumerate() {
+        state.current_gas_used = 36_000_000;
+        let child = state.next_block(&config, state.base_fee_per_cu, i as u64 + 1);
+        let fee = calculate_next_base_fee(&config, &child);
+        assert_eq!(*projected, fee, "block {i}");
+        state = BlockFeeState {
+            base_fee_per_cu: fee,
+            ..child
+        };
+    }
+}
@@ -12,8 +12,9 @@ use {
     arbitrary::{Arbitrary, Unstructured},
     libfuzzer_sys::fuzz_target,
     solana_hash::Hash,
-    solana_pubkey::Pubkey,
+    solana_keypair::Keypair,
     solana_signature::Signature,
+    solana_signer::Signer,
     trv1_consensus_bft::{
         BftConfig, ConsensusEngine, ConsensusMessage, ConsensusStep, ProposedBlock, ValidatorSet,
     },
@@ -84,12 +85,12 @@ fuzz_target!(|data: &[u8]| {
         Err(_) => return,
     };
 
-    let validator_pks: Vec<Pubkey> = (0..num_validators)
+    let validator_kps: Vec<Keypair> = (0..num_validators)
         .map(|i| {
-            let mut bytes = [0u8; 32];
-            bytes[0] = i as u8;
-            bytes[31] = 0xAA; // marker
-            Pubkey::new_from_array(bytes)
+            let mut secret = [0u8; 32];
+            secret[0] = i as u8;
+            secret[31] = 0xAA; // marker
+            Keypair::new_from_array(secret)
         })
         .collect();
 
@@ -98,18 +99,17 @@ fuzz_target!(|data: &[u8]| {
         .collect();
 
     let vs = ValidatorSet::new(
-        validator_pks
+        validator_kps
             .iter()
             .zip(stakes.iter())
-            .map(|(pk, s)| (*pk, *s))
+            .map(|(kp, s)| (kp.pubkey(), *s))
             .collect(),
     );
 
     let config = BftConfig::default();
 
     // We fuzz as validator 0.
-    let our_pk = validator_pks[0];
-    let mut engine = ConsensusEngine::new(config, our_pk, vs.clone());
+    let mut engine = ConsensusEngine::new(config, validator_kps[0].insecure_clone(), vs.clone());
     let _ = engine.start_new_height(1);
 
     // Track committed blocks for invariant checking.
@@ -134,7 +134,7 @@ fuzz_target!(|data: &[u8]| {
                 valid_round,
             } => {
                 let idx = proposer_idx % num_validators;
-                let proposer = validator_pks[idx];
+                let proposer = validator_kps[idx].pubkey();
                 let block = ProposedBlock {
                     parent_hash: Hash::default(),
                     height: engine.height(),
@@ -142,6 +142,7 @@ fuzz_target!(|data: &[u8]| {
                     transactions: vec![],
                     state_root: Hash::new_from_array([idx as u8; 32]),
                     proposer,
+                    evidence: vec![],
                 };
                 current_proposal_hash = Some(block.hash());
 
@@ -152,7 +153,9 @@ fuzz_target!(|data: &[u8]| {
                     proposer,
                     signature: Signature::default(),
                     valid_round,
-                };
+                    polka_certificate: None,
+                }
+                .signed(&validator_kps[idx]);
                 engine.on_proposal(msg)
             }
 
@@ -161,7 +164,7 @@ fuzz_target!(|data: &[u8]| {
                 vote_for_proposal,
             } => {
                 let idx = voter_idx % num_validators;
-                let voter = validator_pks[idx];
+                let voter = validator_kps[idx].pubkey();
                 let block_hash = if vote_for_proposal {
                     current_proposal_hash
                 } else {
@@ -173,7 +176,8 @@ fuzz_target!(|data: &[u8]| {
                     block_hash,
                     voter,
                     signature: Signature::default(),
-                };
+                }
+                .signed(&validator_kps[idx]);
                 engine.on_prevote(msg)
             }
 
@@ -182,7 +186,7 @@ fuzz_target!(|data: &[u8]| {
                 vote_for_proposal,
             } => {
                 let idx = voter_idx % num_validators;
-                let voter = validator_pks[idx];
+                let voter = validator_kps[idx].pubkey();
                 let block_hash = if vote_for_proposal {
                     current_proposal_hash
                 } else {
@@ -194,7 +198,8 @@ fuzz_target!(|data: &[u8]| {
                     block_hash,
                     voter,
                     signature: Signature::default(),
-                };
+                }
+                .signed(&validator_kps[idx]);
                 engine.on_precommit(msg)
             }
 
@@ -17,7 +17,7 @@ use {
             validate_transaction_fee,
         },
         config::FeeMarketConfig,
-        state::BlockFeeState,
+        state::{BlockFeeState, TransactionResources, UTILIZATION_SCALE},
     },
 };
 
@@ -31,14 +31,20 @@ struct FuzzInput {
     max_block_compute_units: u64,
     base_fee_change_denominator: u64,
     min_priority_fee: u64,
+    write_fee_per_account: u64,
+    priority_fee_passive_stake_bps: u16,
+    utilization_ema_alpha_bps: u16,
 
     // State
     base_fee_per_cu: u64,
     parent_gas_used: u64,
+    ema_utilization: u64,
 
     // Transaction
     priority_fee_per_cu: u64,
     compute_units_used: u64,
+    accounts_read: u32,
+    accounts_written: u32,
     offered_lamports: u64,
 
     // Multi-block sequence length
@@ -54,10 +60,16 @@ impl<'a> Arbitrary<'a> for FuzzInput {
             max_block_compute_units: u.arbitrary()?,
             base_fee_change_denominator: u.arbitrary()?,
             min_priority_fee: u.arbitrary()?,
+            write_fee_per_account: u.arbitrary()?,
+            priority_fee_passive_stake_bps: u.arbitrary()?,
+            utilization_ema_alpha_bps: u.int_in_range(100..=10_000)?,
             base_fee_per_cu: u.arbitrary()?,
             parent_gas_used: u.arbitrary()?,
+            ema_utilization: u.int_in_range(0..=4 * UTILIZATION_SCALE)?,
             priority_fee_per_cu: u.arbitrary()?,
             compute_units_used: u.arbitrary()?,
+            accounts_read: u.arbitrary()?,
+            accounts_written: u.arbitrary()?,
             offered_lamports: u.arbitrary()?,
             sequence_len: u.int_in_range(1..=50)?,
         })
@@ -80,6 +92,10 @@ fuzz_target!(|data: &[u8]| {
         max_block_compute_units: input.max_block_compute_units,
         base_fee_change_denominator: input.base_fee_change_denominator,
         min_priority_fee: input.min_priority_fee,
+        priority_fee_history_size: 1_000,
+        write_fee_per_account: input.write_fee_per_account,
+        priority_fee_passive_stake_bps: input.priority_fee_passive_stake_bps,
+        utilization_ema_alpha_bps: input.utilization_ema_alpha_bps,
     };
 
     let state = BlockFeeState {
@@ -87,6 +103,10 @@ fuzz_target!(|data: &[u8]| {
         parent_gas_used: input.parent_gas_used,
         current_gas_used: 0,
         height: 0,
+        current_accounts_written: 0,
+        paused: false,
+        pause_fee: 0,
+        ema_utilization: input.ema_utilization,
     };
 
     // Must not panic regardless of inputs.
@@ -108,20 +128,51 @@ fuzz_target!(|data: &[u8]| {
 
     // ── Test 2: calculate_transaction_fee must not panic ──
 
+    let resources = TransactionResources {
+        compute_units: input.compute_units_used,
+        accounts_read: input.accounts_read,
+        accounts_written: input.accounts_written,
+    };
     let tx_fee = calculate_transaction_fee(
+        &config,
         input.base_fee_per_cu,
         input.priority_fee_per_cu,
-        input.compute_units_used,
+        &resources,
     );
 
-    // Invariant: total_fee >= base_fee (since priority >= 0, both components >= 0).
+    // Invariant: total_fee >= each component (all components >= 0).
     assert!(tx_fee.total_fee >= tx_fee.base_fee);
-    assert!(tx_fee.total_fee >= tx_fee.priority_fee);
+    assert!(tx_fee.total_fee >= tx_fee.priority_fee());
+    assert!(tx_fee.total_fee >= tx_fee.storage_fee);
+
+    // Invariant: the priority fee is split without creating or losing lamports.
+    assert_eq!(
+        tx_fee.priority_fee(),
+        input
+            .priority_fee_per_cu
+            .saturating_mul(input.compute_units_used)
+    );
 
-    // Invariant: total_fee == base_fee + priority_fee (with saturation).
-    let expected_total = tx_fee.base_fee.saturating_add(tx_fee.priority_fee);
+    // Invariant: total_fee == base_fee + priority_fee + storage_fee (with saturation).
+    let expected_total = tx_fee
+        .base_fee
+        .saturating_add(tx_fee.priority_fee())
+        .saturating_add(tx_fee.storage_fee);
     assert_eq!(tx_fee.total_fee, expected_total);
 
+    // Invariant: no writes prices exactly like the compute-only formula.
+    let compute_only = calculate_transaction_fee(
+        &config,
+        input.base_fee_per_cu,
+        input.priority_fee_per_cu,
+        &TransactionResources::compute_only(input.compute_units_used),
+    );
+    assert_eq!(compute_only.storage_fee, 0);
+    let compute_only_total = compute_only
+        .base_fee
+        .saturating_add(compute_only.priority_fee());
+    assert_eq!(compute_only.total_fee, compute_only_total);
+
     // ── Test 3: validate_transaction_fee must not panic ──
 
     // Use a valid config for validation (ensure min <= max and denominator > 0).
@@ -132,6 +183,10 @@ fuzz_target!(|data: &[u8]| {
         max_block_compute_units: input.max_block_compute_units.max(1),
         base_fee_change_denominator: input.base_fee_change_denominator.max(1),
         min_priority_fee: input.min_priority_fee,
+        priority_fee_history_size: 1_000,
+        write_fee_per_account: input.write_fee_per_account,
+        priority_fee_passive_stake_bps: input.priority_fee_passive_stake_bps,
+        utilization_ema_alpha_bps: input.utilization_ema_alpha_bps,
     };
 
     // Must not panic.
@@ -139,7 +194,7 @@ fuzz_target!(|data: &[u8]| {
         input.offered_lamports,
         input.priority_fee_per_cu,
         input.base_fee_per_cu,
-        input.compute_units_used,
+        &resources,
         &valid_config,
     );
 
@@ -166,6 +221,10 @@ fuzz_target!(|data: &[u8]| {
                 parent_gas_used: input.parent_gas_used, // same utilization pattern
                 current_gas_used: 0,
                 height: i + 1,
+                current_accounts_written: 0,
+                paused: false,
+                pause_fee: 0,
+                ema_utilization: current_state.smoothed_utilization(&config),
             };
         }
     }
@@ -182,12 +241,20 @@ fuzz_target!(|data: &[u8]| {
             parent_gas_used: input.parent_gas_used.min(input.parent_gas_used.wrapping_add(1000)),
             current_gas_used: 0,
             height: 0,
+            current_accounts_written: 0,
+            paused: false,
+            pause_fee: 0,
+            ema_utilization: input.ema_utilization,
         };
         let state_high = BlockFeeState {
             base_fee_per_cu: input.base_fee_per_cu,
             parent_gas_used: input.parent_gas_used.max(input.parent_gas_used.wrapping_add(1000)),
             current_gas_used: 0,
             height: 0,
+            current_accounts_written: 0,
+            paused: false,
+            pause_fee: 0,
+            ema_utilization: input.ema_utilization,
         };
 
         let fee_low = calculate_next_base_fee(&config, &state_low);
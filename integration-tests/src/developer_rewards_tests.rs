@@ -5,14 +5,33 @@
 
 use {
     crate::harness::SOL,
+    borsh::BorshDeserialize,
+    solana_account::{self as account, AccountSharedData, ReadableAccount, WritableAccount},
+    solana_clock::Clock,
+    solana_instruction::{error::InstructionError, AccountMeta},
+    solana_program_runtime::{
+        invoke_context::mock_process_instruction, loaded_programs::ProgramCacheEntry,
+    },
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    solana_sdk_ids::{incinerator, native_loader, system_program, sysvar},
+    std::sync::Arc,
     trv1_developer_rewards_program::{
+        attribution::{compute_unit_share, compute_unit_weighted_shares},
         constants::{
-            COOLDOWN_SLOTS, MAX_PROGRAM_FEE_SHARE_BPS, MIN_COMPUTE_UNITS_THRESHOLD, TOTAL_BPS,
+            fee_collector, COOLDOWN_SLOTS, MAX_PROGRAM_FEE_SHARE_BPS, MAX_REGISTRY_PAGE_SIZE,
+            MAX_REVENUE_SPLIT_RECIPIENTS, MIN_COMPUTE_UNITS_THRESHOLD, TOTAL_BPS,
+            TRANSFER_ACCEPT_WINDOW_SLOTS,
         },
         error::DeveloperRewardsError,
-        state::{EpochFeeTracker, ProgramRevenueConfig, RevenueSplit},
+        instruction::DeveloperRewardsInstruction,
+        state::{
+            find_epoch_stats_address, find_epoch_tracker_address, find_fee_pool_address,
+            find_registry_index_address, find_revenue_config_address, DeveloperEpochStats,
+            EpochFeeTracker, ProgramRegistryIndex, ProgramRevenueConfig, RegistryPage,
+            RevenueSplit,
+        },
     },
-    solana_pubkey::Pubkey,
 };
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -38,6 +57,10 @@ fn test_register_revenue_config_defaults() {
         last_epoch: 0,
         eligible_after_slot: current_slot + COOLDOWN_SLOTS,
         unclaimed_fees: 0,
+        last_config_update_epoch: 0,
+        registration_slot: current_slot,
+        pending_update_authority: None,
+        pending_transfer_slot: 0,
     };
 
     assert_eq!(config.version, 1);
@@ -49,6 +72,10 @@ fn test_register_revenue_config_defaults() {
     assert_eq!(config.total_fees_earned, 0);
     assert_eq!(config.unclaimed_fees, 0);
     assert_eq!(config.eligible_after_slot, current_slot + COOLDOWN_SLOTS);
+    assert_eq!(
+        config.remaining_cooldown_slots(current_slot),
+        COOLDOWN_SLOTS
+    );
 }
 
 #[test]
@@ -81,6 +108,10 @@ fn test_duplicate_registration_rejected() {
         last_epoch: 0,
         eligible_after_slot: 0,
         unclaimed_fees: 0,
+        last_config_update_epoch: 0,
+        registration_slot: 0,
+        pending_update_authority: None,
+        pending_transfer_slot: 0,
     };
     // The processor checks data[0] != 0 (the version byte after serialization).
     assert_ne!(config.version, 0, "Already-initialized config should be rejected");
@@ -107,6 +138,10 @@ fn test_update_revenue_recipient() {
         last_epoch: 0,
         eligible_after_slot: 0,
         unclaimed_fees: 0,
+        last_config_update_epoch: 0,
+        registration_slot: 0,
+        pending_update_authority: None,
+        pending_transfer_slot: 0,
     };
 
     assert_eq!(config.revenue_recipient, old_recipient);
@@ -129,6 +164,10 @@ fn test_unauthorized_update_fails() {
         last_epoch: 0,
         eligible_after_slot: 0,
         unclaimed_fees: 0,
+        last_config_update_epoch: 0,
+        registration_slot: 0,
+        pending_update_authority: None,
+        pending_transfer_slot: 0,
     };
 
     let unauthorized_signer = Pubkey::new_unique();
@@ -181,19 +220,19 @@ fn test_valid_three_way_split() {
 }
 
 #[test]
-fn test_valid_ten_way_split() {
-    let recipients: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+fn test_valid_five_way_split() {
+    let recipients: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
     let splits: Vec<RevenueSplit> = recipients
         .iter()
         .map(|r| RevenueSplit {
             recipient: *r,
-            share_bps: 1_000, // 10% each
+            share_bps: 2_000, // 20% each
         })
         .collect();
 
     let total: u32 = splits.iter().map(|s| s.share_bps as u32).sum();
     assert_eq!(total, TOTAL_BPS as u32);
-    assert_eq!(splits.len(), 10);
+    assert_eq!(splits.len(), MAX_REVENUE_SPLIT_RECIPIENTS);
 }
 
 #[test]
@@ -274,17 +313,8 @@ fn test_duplicate_recipients_rejected() {
 }
 
 #[test]
-fn test_max_split_recipients_is_10() {
-    let splits: Vec<RevenueSplit> = (0..11)
-        .map(|_| RevenueSplit {
-            recipient: Pubkey::new_unique(),
-            share_bps: 909, // won't sum to 10000 but we're testing count
-        })
-        .collect();
-    assert!(
-        splits.len() > 10,
-        "More than 10 recipients should be rejected by TooManySplitRecipients"
-    );
+fn test_max_split_recipients_is_5() {
+    assert_eq!(MAX_REVENUE_SPLIT_RECIPIENTS, 5);
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -350,6 +380,23 @@ fn test_cooldown_allows_after_7_days() {
     );
 }
 
+#[test]
+fn test_remaining_cooldown_slots() {
+    let mut config = revenue_config(Pubkey::new_unique());
+    config.registration_slot = 100_000;
+
+    assert_eq!(config.remaining_cooldown_slots(100_000), COOLDOWN_SLOTS);
+    assert_eq!(config.remaining_cooldown_slots(100_001), COOLDOWN_SLOTS - 1);
+    assert_eq!(
+        config.remaining_cooldown_slots(100_000 + COOLDOWN_SLOTS - 1),
+        1
+    );
+    assert_eq!(config.remaining_cooldown_slots(100_000 + COOLDOWN_SLOTS), 0);
+    assert_eq!(config.remaining_cooldown_slots(u64::MAX), 0);
+    // A clock behind the registration slot never shortens the cooldown.
+    assert_eq!(config.remaining_cooldown_slots(0), COOLDOWN_SLOTS);
+}
+
 #[test]
 fn test_cooldown_boundary_exact() {
     let registration_slot = 100_000;
@@ -424,6 +471,10 @@ fn test_epoch_cap_resets_on_new_epoch() {
         last_epoch: 5,
         eligible_after_slot: 0,
         unclaimed_fees: 90 * SOL,
+        last_config_update_epoch: 0,
+        registration_slot: 0,
+        pending_update_authority: None,
+        pending_transfer_slot: 0,
     };
 
     let new_epoch = 6;
@@ -452,13 +503,30 @@ fn test_fee_attribution_single_program() {
 
 #[test]
 fn test_fee_attribution_two_programs() {
-    // A transaction invoking two programs: dev share is split equally.
-    // (In the actual runtime, the split may be CU-weighted, but equal is
-    // the simple model.)
+    // A transaction invoking two programs with equal compute units: dev share
+    // is split equally.
     let total_dev_share = 100 * SOL;
-    let programs_invoked = 2;
-    let per_program_share = total_dev_share / programs_invoked;
-    assert_eq!(per_program_share, 50 * SOL);
+    let shares = compute_unit_weighted_shares(
+        total_dev_share,
+        &[
+            (Pubkey::new_unique(), 50_000),
+            (Pubkey::new_unique(), 50_000),
+        ],
+    );
+    assert_eq!(shares[0].1, 50 * SOL);
+    assert_eq!(shares[1].1, 50 * SOL);
+}
+
+#[test]
+fn test_fee_attribution_weighted_by_compute_units() {
+    // A heavy protocol using 80% of the CUs next to a small helper using 20%.
+    let protocol = Pubkey::new_unique();
+    let helper = Pubkey::new_unique();
+    let shares = compute_unit_weighted_shares(100 * SOL, &[(protocol, 800_000), (helper, 200_000)]);
+    assert_eq!(shares, vec![(protocol, 80 * SOL), (helper, 20 * SOL)]);
+
+    // No compute units at all attributes nothing.
+    assert_eq!(compute_unit_share(100 * SOL, 0, 0), 0);
 }
 
 #[test]
@@ -487,6 +555,10 @@ fn test_claim_accumulates_correctly() {
         last_epoch: 0,
         eligible_after_slot: 0,
         unclaimed_fees: 0,
+        last_config_update_epoch: 0,
+        registration_slot: 0,
+        pending_update_authority: None,
+        pending_transfer_slot: 0,
     };
 
     // Simulate 3 credits
@@ -550,6 +622,7 @@ fn test_epoch_fee_tracker_rollover() {
         version: 1,
         epoch: 5,
         total_developer_fees: 500 * SOL,
+        unclaimed_developer_fees: 0,
     };
 
     let new_epoch = 6;
@@ -568,6 +641,7 @@ fn test_epoch_fee_tracker_same_epoch_accumulates() {
         version: 1,
         epoch: 5,
         total_developer_fees: 100 * SOL,
+        unclaimed_developer_fees: 0,
     };
 
     // Same epoch — accumulate
@@ -591,6 +665,17 @@ fn test_epoch_fee_tracker_max_size() {
     assert_eq!(EpochFeeTracker::MAX_SIZE, 64);
 }
 
+#[test]
+fn test_developer_epoch_stats_max_size() {
+    let stats = DeveloperEpochStats {
+        version: 1,
+        epoch: u64::MAX,
+        program_id: Pubkey::new_unique(),
+        fees_earned_this_epoch: u64::MAX,
+    };
+    assert!(borsh::to_vec(&stats).unwrap().len() <= DeveloperEpochStats::MAX_SIZE);
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // 11. Fee-split schedule constants
 // ═══════════════════════════════════════════════════════════════════════════
@@ -624,3 +709,1089 @@ fn test_transition_epochs() {
     use trv1_developer_rewards_program::constants::TRANSITION_EPOCHS;
     assert_eq!(TRANSITION_EPOCHS, 912);
 }
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 12. Processor: per-epoch fee cap
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn dev_rewards(
+    instruction: &DeveloperRewardsInstruction,
+    accounts: &mut Vec<(Pubkey, AccountSharedData)>,
+    metas: Vec<AccountMeta>,
+    expected_result: Result<(), InstructionError>,
+) {
+    let keys: Vec<Pubkey> = accounts.iter().map(|(key, _)| *key).collect();
+    *accounts = keys
+        .into_iter()
+        .zip(mock_process_instruction(
+            &trv1_developer_rewards_program::id(),
+            None,
+            &borsh::to_vec(instruction).unwrap(),
+            accounts.clone(),
+            metas,
+            expected_result,
+            trv1_developer_rewards_program::processor::Entrypoint::vm,
+            |invoke_context| {
+                // Register the system program for registry index creation.
+                invoke_context.program_cache_for_tx_batch.replenish(
+                    system_program::id(),
+                    Arc::new(ProgramCacheEntry::new_builtin(
+                        0,
+                        0,
+                        solana_system_program::system_processor::Entrypoint::vm,
+                    )),
+                );
+            },
+            |_invoke_context| {},
+        ))
+        .collect();
+}
+
+/// The system program's executable account, for instructions that create
+/// accounts through it.
+fn system_program_account() -> AccountSharedData {
+    let mut account = AccountSharedData::new(1, 0, &native_loader::id());
+    account.set_executable(true);
+    account
+}
+
+fn revenue_config(program_id: Pubkey) -> ProgramRevenueConfig {
+    ProgramRevenueConfig {
+        version: 1,
+        program_id,
+        revenue_recipient: Pubkey::new_unique(),
+        update_authority: Pubkey::new_unique(),
+        is_active: true,
+        revenue_splits: Vec::new(),
+        total_fees_earned: 0,
+        epoch_fees_earned: 0,
+        last_epoch: 0,
+        eligible_after_slot: 0,
+        unclaimed_fees: 0,
+        last_config_update_epoch: 0,
+        registration_slot: 0,
+        pending_update_authority: None,
+        pending_transfer_slot: 0,
+    }
+}
+
+fn config_account(config: &ProgramRevenueConfig) -> AccountSharedData {
+    let data = borsh::to_vec(config).unwrap();
+    let mut account =
+        AccountSharedData::new(SOL, data.len(), &trv1_developer_rewards_program::id());
+    account.set_data_from_slice(&data);
+    account
+}
+
+fn clock_account(slot: u64, epoch: u64) -> AccountSharedData {
+    let clock = Clock {
+        slot,
+        epoch,
+        ..Clock::default()
+    };
+    account::create_account_shared_data_for_test(&clock)
+}
+
+struct CreditFixture {
+    accounts: Vec<(Pubkey, AccountSharedData)>,
+    metas: Vec<AccountMeta>,
+    program_id: Pubkey,
+}
+
+impl CreditFixture {
+    const CONFIG: usize = 1;
+    const TRACKER: usize = 2;
+    const STATS: usize = 4;
+    const POOL: usize = 5;
+    const BURN: usize = 6;
+
+    fn new(pool_lamports: u64) -> Self {
+        let owner = trv1_developer_rewards_program::id();
+        let program_id = Pubkey::new_unique();
+        let accounts = vec![
+            (
+                fee_collector::id(),
+                AccountSharedData::new(SOL, 0, &Pubkey::default()),
+            ),
+            (
+                find_revenue_config_address(&program_id).0,
+                config_account(&revenue_config(program_id)),
+            ),
+            (
+                find_epoch_tracker_address().0,
+                AccountSharedData::new(SOL, EpochFeeTracker::MAX_SIZE, &owner),
+            ),
+            (sysvar::clock::id(), clock_account(COOLDOWN_SLOTS, 1)),
+            (
+                find_epoch_stats_address(&program_id).0,
+                AccountSharedData::new(SOL, DeveloperEpochStats::MAX_SIZE, &owner),
+            ),
+            (
+                find_fee_pool_address().0,
+                AccountSharedData::new(pool_lamports, 0, &owner),
+            ),
+            (
+                incinerator::id(),
+                AccountSharedData::new(0, 0, &Pubkey::default()),
+            ),
+        ];
+        let metas = accounts
+            .iter()
+            .enumerate()
+            .map(|(i, (key, _))| match i {
+                0 => AccountMeta::new_readonly(*key, true),
+                3 => AccountMeta::new_readonly(*key, false),
+                _ => AccountMeta::new(*key, false),
+            })
+            .collect();
+        Self {
+            accounts,
+            metas,
+            program_id,
+        }
+    }
+
+    fn set_epoch(&mut self, epoch: u64) {
+        self.accounts[3].1 = clock_account(COOLDOWN_SLOTS, epoch);
+    }
+
+    fn credit(&mut self, amount: u64, expected_result: Result<(), InstructionError>) {
+        self.credit_share(
+            amount,
+            MIN_COMPUTE_UNITS_THRESHOLD,
+            MIN_COMPUTE_UNITS_THRESHOLD,
+            expected_result,
+        );
+    }
+
+    fn credit_share(
+        &mut self,
+        amount: u64,
+        compute_units_consumed: u64,
+        total_compute_units: u64,
+        expected_result: Result<(), InstructionError>,
+    ) {
+        let instruction = DeveloperRewardsInstruction::CreditDeveloperFees {
+            program_id: self.program_id,
+            amount,
+            compute_units_consumed,
+            total_compute_units,
+        };
+        dev_rewards(
+            &instruction,
+            &mut self.accounts,
+            self.metas.clone(),
+            expected_result,
+        );
+    }
+
+    fn config(&self) -> ProgramRevenueConfig {
+        ProgramRevenueConfig::try_from_slice(self.accounts[Self::CONFIG].1.data()).unwrap()
+    }
+
+    fn tracker(&self) -> EpochFeeTracker {
+        EpochFeeTracker::try_from_slice(self.accounts[Self::TRACKER].1.data()).unwrap()
+    }
+
+    fn stats(&self) -> DeveloperEpochStats {
+        DeveloperEpochStats::try_from_slice(self.accounts[Self::STATS].1.data()).unwrap()
+    }
+
+    fn lamports(&self, index: usize) -> u64 {
+        self.accounts[index].1.lamports()
+    }
+}
+
+#[test]
+fn test_continuous_fees_capped_at_exactly_10_percent() {
+    let mut fixture = CreditFixture::new(1_000 * SOL);
+
+    // A program that is the only fee earner keeps earning all epoch long.
+    for i in 1..=100u64 {
+        fixture.credit(SOL, Ok(()));
+
+        let total = fixture.tracker().total_developer_fees;
+        assert_eq!(total, i * SOL);
+        assert_eq!(
+            fixture.config().unclaimed_fees,
+            total * MAX_PROGRAM_FEE_SHARE_BPS as u64 / TOTAL_BPS as u64,
+            "program must hold exactly 10% of total developer fees after credit {i}"
+        );
+    }
+
+    let stats = fixture.stats();
+    assert_eq!(stats.epoch, 1);
+    assert_eq!(stats.program_id, fixture.program_id);
+    assert_eq!(stats.fees_earned_this_epoch, 10 * SOL);
+    assert_eq!(fixture.config().epoch_fees_earned, 10 * SOL);
+    assert_eq!(fixture.config().total_fees_earned, 10 * SOL);
+    assert_eq!(fixture.tracker().unclaimed_developer_fees, 10 * SOL);
+
+    // The excess left the developer pool for the burn pool.
+    assert_eq!(fixture.lamports(CreditFixture::BURN), 90 * SOL);
+    assert_eq!(fixture.lamports(CreditFixture::POOL), 910 * SOL);
+}
+
+#[test]
+fn test_credit_under_cap_is_attributed_in_full() {
+    let mut fixture = CreditFixture::new(1_000 * SOL);
+    let tracker = EpochFeeTracker {
+        version: 1,
+        epoch: 1,
+        total_developer_fees: 100 * SOL,
+        unclaimed_developer_fees: 0,
+    };
+    fixture.accounts[CreditFixture::TRACKER]
+        .1
+        .set_data_from_slice(&borsh::to_vec(&tracker).unwrap());
+
+    // 5 SOL of 105 SOL total is under the 10.5 SOL cap.
+    fixture.credit(5 * SOL, Ok(()));
+
+    assert_eq!(fixture.config().unclaimed_fees, 5 * SOL);
+    assert_eq!(fixture.stats().fees_earned_this_epoch, 5 * SOL);
+    assert_eq!(fixture.tracker().total_developer_fees, 105 * SOL);
+    assert_eq!(fixture.lamports(CreditFixture::BURN), 0);
+    assert_eq!(fixture.lamports(CreditFixture::POOL), 1_000 * SOL);
+}
+
+#[test]
+fn test_credit_is_weighted_by_compute_units() {
+    let tracker = EpochFeeTracker {
+        version: 1,
+        epoch: 1,
+        total_developer_fees: 100 * SOL,
+        unclaimed_developer_fees: 0,
+    };
+    let tracker_data = borsh::to_vec(&tracker).unwrap();
+
+    // Both programs were invoked by one transaction with a 5 SOL developer
+    // share; the protocol consumed 80% of the CUs and the helper 20%.
+    let mut protocol = CreditFixture::new(1_000 * SOL);
+    protocol.accounts[CreditFixture::TRACKER]
+        .1
+        .set_data_from_slice(&tracker_data);
+    protocol.credit_share(5 * SOL, 80_000, 100_000, Ok(()));
+
+    let mut helper = CreditFixture::new(1_000 * SOL);
+    helper.accounts[CreditFixture::TRACKER]
+        .1
+        .set_data_from_slice(&tracker_data);
+    helper.credit_share(5 * SOL, 20_000, 100_000, Ok(()));
+
+    assert_eq!(protocol.config().unclaimed_fees, 4 * SOL);
+    assert_eq!(protocol.tracker().total_developer_fees, 104 * SOL);
+    assert_eq!(helper.config().unclaimed_fees, SOL);
+    assert_eq!(helper.tracker().total_developer_fees, 101 * SOL);
+}
+
+#[test]
+fn test_credit_rejects_compute_units_above_transaction_total() {
+    let mut fixture = CreditFixture::new(1_000 * SOL);
+    fixture.credit_share(
+        SOL,
+        20_000,
+        10_000,
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::InvalidComputeUnitShare as u32,
+        )),
+    );
+}
+
+#[test]
+fn test_epoch_stats_reset_on_new_epoch() {
+    let mut fixture = CreditFixture::new(1_000 * SOL);
+    fixture.credit(10 * SOL, Ok(()));
+    assert_eq!(fixture.stats().fees_earned_this_epoch, SOL);
+
+    fixture.set_epoch(2);
+    fixture.credit(20 * SOL, Ok(()));
+
+    let stats = fixture.stats();
+    assert_eq!(stats.epoch, 2);
+    assert_eq!(stats.fees_earned_this_epoch, 2 * SOL);
+    assert_eq!(fixture.tracker().total_developer_fees, 20 * SOL);
+    assert_eq!(fixture.config().unclaimed_fees, 3 * SOL);
+    assert_eq!(fixture.lamports(CreditFixture::BURN), 27 * SOL);
+}
+
+#[test]
+fn test_credit_requires_the_programs_own_stats_and_pool() {
+    let invalid_pda = || {
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::InvalidPda as u32,
+        ))
+    };
+
+    // Any other counter would read as zero and reopen the cap.
+    let mut fixture = CreditFixture::new(1_000 * SOL);
+    let fresh = Pubkey::new_unique();
+    fixture.accounts[CreditFixture::STATS].0 = fresh;
+    fixture.metas[CreditFixture::STATS] = AccountMeta::new(fresh, false);
+    fixture.credit(SOL, invalid_pda());
+
+    // The counter must be owned by the program.
+    let mut fixture = CreditFixture::new(1_000 * SOL);
+    fixture.accounts[CreditFixture::STATS]
+        .1
+        .set_owner(Pubkey::new_unique());
+    fixture.credit(
+        SOL,
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::InvalidAccountOwner as u32,
+        )),
+    );
+
+    // Any excess must come out of the developer fee pool.
+    let mut fixture = CreditFixture::new(1_000 * SOL);
+    let pool = Pubkey::new_unique();
+    fixture.accounts[CreditFixture::POOL].0 = pool;
+    fixture.metas[CreditFixture::POOL] = AccountMeta::new(pool, false);
+    fixture.credit(SOL, invalid_pda());
+
+    // A fresh tracker would forget the fees already owed out of the pool.
+    let mut fixture = CreditFixture::new(1_000 * SOL);
+    let tracker = Pubkey::new_unique();
+    fixture.accounts[CreditFixture::TRACKER].0 = tracker;
+    fixture.metas[CreditFixture::TRACKER] = AccountMeta::new(tracker, false);
+    fixture.credit(SOL, invalid_pda());
+
+    // The config must be the credited program's.
+    let mut fixture = CreditFixture::new(1_000 * SOL);
+    let config = find_revenue_config_address(&Pubkey::new_unique()).0;
+    fixture.accounts[CreditFixture::CONFIG].0 = config;
+    fixture.metas[CreditFixture::CONFIG] = AccountMeta::new(config, false);
+    fixture.credit(SOL, invalid_pda());
+}
+
+#[test]
+fn test_credit_only_by_the_fee_collector() {
+    let unauthorized = || {
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::UnauthorizedFeeCollector as u32,
+        ))
+    };
+
+    // Anyone else could burn pool funds by naming an amount.
+    let mut fixture = CreditFixture::new(1_000 * SOL);
+    let caller = Pubkey::new_unique();
+    fixture.accounts[0].0 = caller;
+    fixture.metas[0] = AccountMeta::new_readonly(caller, true);
+    fixture.credit(SOL, unauthorized());
+
+    // The collector must sign.
+    let mut fixture = CreditFixture::new(1_000 * SOL);
+    fixture.metas[0] = AccountMeta::new_readonly(fee_collector::id(), false);
+    fixture.credit(SOL, unauthorized());
+
+    assert_eq!(fixture.lamports(CreditFixture::POOL), 1_000 * SOL);
+    assert_eq!(fixture.lamports(CreditFixture::BURN), 0);
+}
+
+#[test]
+fn test_credit_limited_to_unattributed_pool_funds() {
+    // 95 of the pool's 100 SOL are already owed to programs.
+    let mut fixture = CreditFixture::new(100 * SOL);
+    let tracker = EpochFeeTracker {
+        version: 1,
+        epoch: 1,
+        total_developer_fees: 0,
+        unclaimed_developer_fees: 95 * SOL,
+    };
+    fixture.accounts[CreditFixture::TRACKER]
+        .1
+        .set_data_from_slice(&borsh::to_vec(&tracker).unwrap());
+    let insufficient = || {
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::InsufficientPoolFunds as u32,
+        ))
+    };
+
+    fixture.credit(10 * SOL, insufficient());
+
+    // 0.5 SOL is credited and 4.5 SOL burned, using up the 5 SOL.
+    fixture.credit(5 * SOL, Ok(()));
+    assert_eq!(fixture.config().unclaimed_fees, SOL / 2);
+    assert_eq!(
+        fixture.tracker().unclaimed_developer_fees,
+        95 * SOL + SOL / 2
+    );
+    assert_eq!(fixture.lamports(CreditFixture::POOL), 95 * SOL + SOL / 2);
+
+    fixture.credit(1, insufficient());
+}
+
+#[test]
+fn test_excess_requires_funded_developer_pool() {
+    let mut fixture = CreditFixture::new(SOL);
+    fixture.credit(
+        10 * SOL,
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::InsufficientPoolFunds as u32,
+        )),
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 13. Processor: revenue split configuration & claims
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn split(recipient: Pubkey, share_bps: u16) -> RevenueSplit {
+    RevenueSplit {
+        recipient,
+        share_bps,
+    }
+}
+
+fn update_revenue_config(
+    config: &ProgramRevenueConfig,
+    recipients: Vec<RevenueSplit>,
+    epoch: u64,
+    expected_result: Result<(), InstructionError>,
+) -> ProgramRevenueConfig {
+    let config_key = Pubkey::new_unique();
+    let mut accounts = vec![
+        (
+            config.update_authority,
+            AccountSharedData::new(SOL, 0, &Pubkey::default()),
+        ),
+        (config_key, config_account(config)),
+        (sysvar::clock::id(), clock_account(0, epoch)),
+    ];
+    let metas = vec![
+        AccountMeta::new_readonly(config.update_authority, true),
+        AccountMeta::new(config_key, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    dev_rewards(
+        &DeveloperRewardsInstruction::UpdateRevenueConfig {
+            program_id: config.program_id,
+            recipients,
+        },
+        &mut accounts,
+        metas,
+        expected_result,
+    );
+    ProgramRevenueConfig::try_from_slice(accounts[1].1.data()).unwrap()
+}
+
+#[test]
+fn test_claim_three_way_split_remainder_to_first_recipient() {
+    let treasury = Pubkey::new_unique();
+    let dev_fund = Pubkey::new_unique();
+    let grants = Pubkey::new_unique();
+
+    let mut config = revenue_config(Pubkey::new_unique());
+    config.revenue_splits = vec![
+        split(treasury, 7_000),
+        split(dev_fund, 2_000),
+        split(grants, 1_000),
+    ];
+    // 10 SOL + 7 lamports does not divide evenly: 2 lamports of dust.
+    config.unclaimed_fees = 10 * SOL + 7;
+
+    let owner = trv1_developer_rewards_program::id();
+    let claimer = Pubkey::new_unique();
+    let config_key = Pubkey::new_unique();
+    let pool = find_fee_pool_address().0;
+    let tracker = find_epoch_tracker_address().0;
+    let tracker_data = borsh::to_vec(&EpochFeeTracker {
+        version: 1,
+        epoch: 0,
+        total_developer_fees: 0,
+        unclaimed_developer_fees: 15 * SOL,
+    })
+    .unwrap();
+    let mut tracker_account = AccountSharedData::new(SOL, tracker_data.len(), &owner);
+    tracker_account.set_data_from_slice(&tracker_data);
+    let mut accounts = vec![
+        (claimer, AccountSharedData::new(SOL, 0, &Pubkey::default())),
+        (config_key, config_account(&config)),
+        (pool, AccountSharedData::new(100 * SOL, 0, &owner)),
+        (tracker, tracker_account),
+        (treasury, AccountSharedData::new(0, 0, &Pubkey::default())),
+        (dev_fund, AccountSharedData::new(0, 0, &Pubkey::default())),
+        (grants, AccountSharedData::new(0, 0, &Pubkey::default())),
+    ];
+    let metas = vec![
+        AccountMeta::new_readonly(claimer, true),
+        AccountMeta::new(config_key, false),
+        AccountMeta::new(pool, false),
+        AccountMeta::new(tracker, false),
+        AccountMeta::new(treasury, false),
+        AccountMeta::new(dev_fund, false),
+        AccountMeta::new(grants, false),
+    ];
+    let claim = DeveloperRewardsInstruction::ClaimDeveloperFees {
+        program_id: config.program_id,
+    };
+
+    // Recipients passed out of split order are rejected.
+    let mut swapped = metas.clone();
+    swapped.swap(5, 6);
+    dev_rewards(
+        &claim,
+        &mut accounts.clone(),
+        swapped,
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::RecipientMismatch as u32,
+        )),
+    );
+
+    dev_rewards(&claim, &mut accounts, metas, Ok(()));
+
+    assert_eq!(accounts[4].1.lamports(), 7 * SOL + 6); // 7_000_000_004 + 2 dust
+    assert_eq!(accounts[5].1.lamports(), 2 * SOL + 1);
+    assert_eq!(accounts[6].1.lamports(), SOL);
+    assert_eq!(accounts[2].1.lamports(), 90 * SOL - 7);
+
+    let config = ProgramRevenueConfig::try_from_slice(accounts[1].1.data()).unwrap();
+    assert_eq!(config.unclaimed_fees, 0);
+    // The pool no longer owes the claimed fees.
+    let tracker = EpochFeeTracker::try_from_slice(accounts[3].1.data()).unwrap();
+    assert_eq!(tracker.unclaimed_developer_fees, 5 * SOL - 7);
+}
+
+#[test]
+fn test_update_revenue_config_cooldown() {
+    let mut config = revenue_config(Pubkey::new_unique());
+    config.last_config_update_epoch = 5;
+    let recipients = vec![
+        split(Pubkey::new_unique(), 6_000),
+        split(Pubkey::new_unique(), 4_000),
+    ];
+
+    // Same epoch as the last change: rejected.
+    update_revenue_config(
+        &config,
+        recipients.clone(),
+        5,
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::ConfigUpdateCooldown as u32,
+        )),
+    );
+
+    // One epoch later the change goes through and restarts the cooldown.
+    let updated = update_revenue_config(&config, recipients.clone(), 6, Ok(()));
+    assert_eq!(updated.revenue_splits, recipients);
+    assert_eq!(updated.last_config_update_epoch, 6);
+    update_revenue_config(
+        &updated,
+        vec![split(Pubkey::new_unique(), 10_000)],
+        6,
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::ConfigUpdateCooldown as u32,
+        )),
+    );
+}
+
+#[test]
+fn test_update_revenue_config_rejects_invalid_splits() {
+    let config = revenue_config(Pubkey::new_unique());
+
+    let six_way: Vec<RevenueSplit> = (0..6)
+        .map(|i| split(Pubkey::new_unique(), if i == 0 { 5_000 } else { 1_000 }))
+        .collect();
+    update_revenue_config(
+        &config,
+        six_way,
+        1,
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::TooManySplitRecipients as u32,
+        )),
+    );
+
+    update_revenue_config(
+        &config,
+        vec![
+            split(Pubkey::new_unique(), 7_000),
+            split(Pubkey::new_unique(), 2_000),
+        ],
+        1,
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::InvalidSplitTotal as u32,
+        )),
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 14. Processor: registration cooldown
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_registered_program_earns_only_after_cooldown() {
+    let owner = trv1_developer_rewards_program::id();
+    let upgrade_authority = Pubkey::new_unique();
+    let program_id = Pubkey::new_unique();
+    let config_key = find_revenue_config_address(&program_id).0;
+    let programdata = Pubkey::new_unique();
+    let registry_index = find_registry_index_address().0;
+
+    // Minimal upgradeable-loader programdata: Some(upgrade_authority) at [12..45].
+    let mut programdata_data = vec![0u8; 45];
+    programdata_data[12] = 1;
+    programdata_data[13..45].copy_from_slice(upgrade_authority.as_ref());
+    let mut programdata_account = AccountSharedData::new(SOL, 45, &Pubkey::new_unique());
+    programdata_account.set_data_from_slice(&programdata_data);
+
+    let mut accounts = vec![
+        (
+            upgrade_authority,
+            AccountSharedData::new(SOL, 0, &Pubkey::default()),
+        ),
+        (
+            config_key,
+            AccountSharedData::new(SOL, ProgramRevenueConfig::MAX_SIZE, &owner),
+        ),
+        (programdata, programdata_account),
+        (system_program::id(), system_program_account()),
+        (sysvar::clock::id(), clock_account(0, 0)),
+        (registry_index, AccountSharedData::new(SOL, 0, &owner)),
+        (
+            sysvar::rent::id(),
+            account::create_account_shared_data_for_test(&Rent::default()),
+        ),
+    ];
+    let metas = vec![
+        AccountMeta::new(upgrade_authority, true),
+        AccountMeta::new(config_key, false),
+        AccountMeta::new_readonly(programdata, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new(registry_index, false),
+    ];
+    dev_rewards(
+        &DeveloperRewardsInstruction::RegisterRevenueRecipient {
+            program_id,
+            recipient: Pubkey::new_unique(),
+        },
+        &mut accounts,
+        metas,
+        Ok(()),
+    );
+    let config = ProgramRevenueConfig::try_from_slice(accounts[1].1.data()).unwrap();
+    assert_eq!(config.registration_slot, 0);
+
+    let mut fixture = CreditFixture::new(1_000 * SOL);
+    fixture.program_id = program_id;
+    fixture.accounts[CreditFixture::CONFIG].1 = accounts[1].1.clone();
+
+    // ~4.6 days after registration: still cooling down.
+    fixture.accounts[3].1 = clock_account(1_000_000, 1);
+    fixture.credit(
+        SOL,
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::CooldownNotElapsed as u32,
+        )),
+    );
+    assert_eq!(
+        fixture.config().remaining_cooldown_slots(1_000_000),
+        512_000
+    );
+
+    // Just past the 7-day cooldown the program earns fees.
+    fixture.accounts[3].1 = clock_account(1_512_001, 1);
+    fixture.credit(SOL, Ok(()));
+    assert!(fixture.config().unclaimed_fees > 0);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 15. Processor: program registry index
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn dev_rewards_with_return_data(
+    instruction: &DeveloperRewardsInstruction,
+    accounts: &mut Vec<(Pubkey, AccountSharedData)>,
+    metas: Vec<AccountMeta>,
+    expected_result: Result<(), InstructionError>,
+) -> Vec<u8> {
+    let mut return_data = Vec::new();
+    let keys: Vec<Pubkey> = accounts.iter().map(|(key, _)| *key).collect();
+    *accounts = keys
+        .into_iter()
+        .zip(mock_process_instruction(
+            &trv1_developer_rewards_program::id(),
+            None,
+            &borsh::to_vec(instruction).unwrap(),
+            accounts.clone(),
+            metas,
+            expected_result,
+            trv1_developer_rewards_program::processor::Entrypoint::vm,
+            |_invoke_context| {},
+            |invoke_context| {
+                return_data = invoke_context
+                    .transaction_context
+                    .get_return_data()
+                    .1
+                    .to_vec();
+            },
+        ))
+        .collect();
+    return_data
+}
+
+struct RegistryFixture {
+    index: (Pubkey, AccountSharedData),
+    /// Lamports left with the upgrade authority after the last registration.
+    payer_lamports: u64,
+}
+
+impl RegistryFixture {
+    fn new() -> Self {
+        Self {
+            index: (
+                find_registry_index_address().0,
+                AccountSharedData::new(SOL, 0, &trv1_developer_rewards_program::id()),
+            ),
+            payer_lamports: 0,
+        }
+    }
+
+    /// Register `program_id`, with the registering upgrade authority
+    /// paying any rent the index needs.
+    fn register(&mut self, program_id: Pubkey, expected_result: Result<(), InstructionError>) {
+        self.register_at(
+            find_revenue_config_address(&program_id).0,
+            program_id,
+            expected_result,
+        );
+    }
+
+    fn register_at(
+        &mut self,
+        config_key: Pubkey,
+        program_id: Pubkey,
+        expected_result: Result<(), InstructionError>,
+    ) {
+        let owner = trv1_developer_rewards_program::id();
+        let upgrade_authority = Pubkey::new_unique();
+        let mut programdata_data = vec![0u8; 45];
+        programdata_data[12] = 1;
+        programdata_data[13..45].copy_from_slice(upgrade_authority.as_ref());
+        let mut programdata_account = AccountSharedData::new(SOL, 45, &Pubkey::new_unique());
+        programdata_account.set_data_from_slice(&programdata_data);
+
+        let mut accounts = vec![
+            (
+                upgrade_authority,
+                AccountSharedData::new(SOL, 0, &Pubkey::default()),
+            ),
+            (
+                config_key,
+                AccountSharedData::new(SOL, ProgramRevenueConfig::MAX_SIZE, &owner),
+            ),
+            (Pubkey::new_unique(), programdata_account),
+            (system_program::id(), system_program_account()),
+            (sysvar::clock::id(), clock_account(0, 0)),
+            self.index.clone(),
+        ];
+        let metas = accounts
+            .iter()
+            .enumerate()
+            .map(|(i, (key, _))| match i {
+                0 => AccountMeta::new(*key, true),
+                1 | 5 => AccountMeta::new(*key, false),
+                _ => AccountMeta::new_readonly(*key, false),
+            })
+            .collect();
+        accounts.push((
+            sysvar::rent::id(),
+            account::create_account_shared_data_for_test(&Rent::default()),
+        ));
+        dev_rewards(
+            &DeveloperRewardsInstruction::RegisterRevenueRecipient {
+                program_id,
+                recipient: Pubkey::new_unique(),
+            },
+            &mut accounts,
+            metas,
+            expected_result,
+        );
+        self.payer_lamports = accounts[0].1.lamports();
+        self.index = accounts[5].clone();
+    }
+
+    fn page(&self, offset: u64, limit: u8) -> RegistryPage {
+        let mut accounts = vec![self.index.clone()];
+        let metas = vec![AccountMeta::new_readonly(self.index.0, false)];
+        let return_data = dev_rewards_with_return_data(
+            &DeveloperRewardsInstruction::GetRegistryPage { offset, limit },
+            &mut accounts,
+            metas,
+            Ok(()),
+        );
+        RegistryPage::try_from_slice(&return_data).unwrap()
+    }
+
+    fn index(&self) -> ProgramRegistryIndex {
+        ProgramRegistryIndex::try_from_slice(self.index.1.data()).unwrap()
+    }
+}
+
+#[test]
+fn test_registry_pages_cover_every_program_once() {
+    let mut fixture = RegistryFixture::new();
+    let programs: Vec<Pubkey> = (0..25).map(|_| Pubkey::new_unique()).collect();
+    for program_id in &programs {
+        fixture.register(*program_id, Ok(()));
+    }
+
+    // The index grew to hold every registration, in order.
+    let index = fixture.index();
+    assert_eq!(index.entries, programs);
+    assert_eq!(index.next_page_seed, None);
+    assert_eq!(
+        fixture.index.1.data().len(),
+        borsh::to_vec(&index).unwrap().len()
+    );
+
+    let first = fixture.page(0, MAX_REGISTRY_PAGE_SIZE);
+    assert_eq!(first.total_entries, 25);
+    assert_eq!(first.entries, programs[..20]);
+
+    let second = fixture.page(20, MAX_REGISTRY_PAGE_SIZE);
+    assert_eq!(second.total_entries, 25);
+    assert_eq!(second.entries, programs[20..]);
+
+    let mut seen: Vec<Pubkey> = first.entries.into_iter().chain(second.entries).collect();
+    seen.sort();
+    seen.dedup();
+    assert_eq!(seen.len(), 25, "pages must not overlap");
+
+    assert!(fixture.page(25, MAX_REGISTRY_PAGE_SIZE).entries.is_empty());
+}
+
+#[test]
+fn test_registry_page_limit_is_clamped() {
+    let mut fixture = RegistryFixture::new();
+    for _ in 0..25 {
+        fixture.register(Pubkey::new_unique(), Ok(()));
+    }
+    assert_eq!(fixture.page(0, u8::MAX).entries.len(), 20);
+    assert_eq!(fixture.page(3, 4).entries, fixture.index().entries[3..7]);
+}
+
+#[test]
+fn test_register_rejects_wrong_registry_index() {
+    let mut fixture = RegistryFixture::new();
+    fixture.index.0 = Pubkey::new_unique();
+    fixture.register(
+        Pubkey::new_unique(),
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::InvalidPda as u32,
+        )),
+    );
+}
+
+#[test]
+fn test_register_rejects_wrong_revenue_config() {
+    let mut fixture = RegistryFixture::new();
+    let program_id = Pubkey::new_unique();
+    for config_key in [
+        Pubkey::new_unique(),
+        find_revenue_config_address(&Pubkey::new_unique()).0,
+    ] {
+        fixture.register_at(
+            config_key,
+            program_id,
+            Err(InstructionError::Custom(
+                DeveloperRewardsError::InvalidPda as u32,
+            )),
+        );
+    }
+    assert!(fixture.index.1.data().is_empty());
+}
+
+#[test]
+fn test_registry_index_created_on_first_registration() {
+    let mut fixture = RegistryFixture::new();
+    fixture.index.1 = AccountSharedData::new(0, 0, &system_program::id());
+
+    let first = Pubkey::new_unique();
+    fixture.register(first, Ok(()));
+    let rent = Rent::default();
+    let size = fixture.index.1.data().len();
+    assert_eq!(
+        *fixture.index.1.owner(),
+        trv1_developer_rewards_program::id()
+    );
+    assert_eq!(fixture.index().entries, vec![first]);
+    assert_eq!(fixture.index.1.lamports(), rent.minimum_balance(size));
+    assert_eq!(fixture.payer_lamports, SOL - rent.minimum_balance(size));
+
+    // Each later registration tops the index up to the rent-exempt minimum
+    // for its new size.
+    let second = Pubkey::new_unique();
+    fixture.register(second, Ok(()));
+    let grown = fixture.index.1.data().len();
+    assert_eq!(grown, size + 32);
+    assert_eq!(fixture.index().entries, vec![first, second]);
+    assert_eq!(fixture.index.1.lamports(), rent.minimum_balance(grown));
+    assert_eq!(
+        fixture.payer_lamports,
+        SOL - (rent.minimum_balance(grown) - rent.minimum_balance(size))
+    );
+}
+
+#[test]
+fn test_registry_index_lists_each_program_once() {
+    let mut fixture = RegistryFixture::new();
+    let program_id = Pubkey::new_unique();
+    fixture.index.1 = AccountSharedData::new(SOL, 0, &trv1_developer_rewards_program::id());
+    let data = borsh::to_vec(&ProgramRegistryIndex {
+        version: 1,
+        entries: vec![program_id],
+        next_page_seed: None,
+    })
+    .unwrap();
+    fixture.index.1.set_data_from_slice(&data);
+
+    // A fresh revenue config for an already-listed program leaves the index
+    // unchanged.
+    fixture.register(program_id, Ok(()));
+    assert_eq!(fixture.index().entries, vec![program_id]);
+    assert_eq!(fixture.index.1.data(), &data[..]);
+    assert_eq!(fixture.payer_lamports, SOL);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 16. Processor: revenue config transfer
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Runs an update-authority instruction (`[signer, config, clock]`) at `slot`
+/// and returns the resulting config account.
+fn authority_instruction(
+    instruction: &DeveloperRewardsInstruction,
+    signer: Pubkey,
+    config: &AccountSharedData,
+    slot: u64,
+    expected_result: Result<(), InstructionError>,
+) -> AccountSharedData {
+    let config_key = Pubkey::new_unique();
+    let mut accounts = vec![
+        (signer, AccountSharedData::new(SOL, 0, &Pubkey::default())),
+        (config_key, config.clone()),
+        (sysvar::clock::id(), clock_account(slot, 0)),
+    ];
+    let metas = vec![
+        AccountMeta::new_readonly(signer, true),
+        AccountMeta::new(config_key, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    dev_rewards(instruction, &mut accounts, metas, expected_result);
+    accounts[1].1.clone()
+}
+
+#[test]
+fn test_upgrade_handover_moves_config_to_new_authority() {
+    let program_id = Pubkey::new_unique();
+    let mut config = revenue_config(program_id);
+    let old_authority = config.update_authority;
+    let new_authority = Pubkey::new_unique();
+    let mut account = config_account(&config);
+
+    // Before the upgrade, the old authority nominates the new one.
+    account = authority_instruction(
+        &DeveloperRewardsInstruction::TransferProgramConfig {
+            program_id,
+            new_authority,
+        },
+        old_authority,
+        &account,
+        1_000,
+        Ok(()),
+    );
+    config = ProgramRevenueConfig::try_from_slice(account.data()).unwrap();
+    assert_eq!(config.update_authority, old_authority);
+    assert_eq!(config.pending_update_authority, Some(new_authority));
+    assert_eq!(config.pending_transfer_slot, 1_000);
+
+    // Nobody else can accept the nomination.
+    authority_instruction(
+        &DeveloperRewardsInstruction::AcceptProgramConfig { program_id },
+        Pubkey::new_unique(),
+        &account,
+        2_000,
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::UnauthorizedUpdateAuthority as u32,
+        )),
+    );
+
+    // After the upgrade, the new authority accepts at the edge of the window.
+    account = authority_instruction(
+        &DeveloperRewardsInstruction::AcceptProgramConfig { program_id },
+        new_authority,
+        &account,
+        1_000 + TRANSFER_ACCEPT_WINDOW_SLOTS,
+        Ok(()),
+    );
+    config = ProgramRevenueConfig::try_from_slice(account.data()).unwrap();
+    assert_eq!(config.update_authority, new_authority);
+    assert_eq!(config.pending_update_authority, None);
+
+    // The new authority now controls the recipient; the old one does not.
+    let new_recipient = Pubkey::new_unique();
+    let update = DeveloperRewardsInstruction::UpdateRevenueRecipient {
+        program_id,
+        new_recipient,
+    };
+    authority_instruction(
+        &update,
+        old_authority,
+        &account,
+        1_000 + TRANSFER_ACCEPT_WINDOW_SLOTS,
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::UnauthorizedUpdateAuthority as u32,
+        )),
+    );
+    account = authority_instruction(
+        &update,
+        new_authority,
+        &account,
+        1_000 + TRANSFER_ACCEPT_WINDOW_SLOTS,
+        Ok(()),
+    );
+    config = ProgramRevenueConfig::try_from_slice(account.data()).unwrap();
+    assert_eq!(config.revenue_recipient, new_recipient);
+}
+
+#[test]
+fn test_accept_after_transfer_window_fails() {
+    let program_id = Pubkey::new_unique();
+    let config = revenue_config(program_id);
+    let new_authority = Pubkey::new_unique();
+
+    let account = authority_instruction(
+        &DeveloperRewardsInstruction::TransferProgramConfig {
+            program_id,
+            new_authority,
+        },
+        config.update_authority,
+        &config_account(&config),
+        1_000,
+        Ok(()),
+    );
+    authority_instruction(
+        &DeveloperRewardsInstruction::AcceptProgramConfig { program_id },
+        new_authority,
+        &account,
+        1_001 + TRANSFER_ACCEPT_WINDOW_SLOTS,
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::TransferWindowExpired as u32,
+        )),
+    );
+}
+
+#[test]
+fn test_accept_without_pending_transfer_fails() {
+    let program_id = Pubkey::new_unique();
+    authority_instruction(
+        &DeveloperRewardsInstruction::AcceptProgramConfig { program_id },
+        Pubkey::new_unique(),
+        &config_account(&revenue_config(program_id)),
+        1_000,
+        Err(InstructionError::Custom(
+            DeveloperRewardsError::NoPendingTransfer as u32,
+        )),
+    );
+}
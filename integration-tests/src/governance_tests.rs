@@ -0,0 +1,371 @@
+//! Integration tests for TRv1 Governance program.
+//!
+//! Drives proposals through the governance and treasury program processors:
+//! a TreasurySpend proposal is created, voted through, timelocked, and
+//! disbursed by the treasury program via CPI.
+
+use {
+    crate::harness::SOL,
+    solana_account::{self as account, AccountSharedData, ReadableAccount, WritableAccount},
+    solana_clock::Clock,
+    solana_hash::Hash,
+    solana_instruction::{error::InstructionError, AccountMeta},
+    solana_passive_stake_program::state::PassiveStakeAccount,
+    solana_program_runtime::{
+        invoke_context::{mock_process_instruction, BuiltinFunctionWithContext},
+        loaded_programs::ProgramCacheEntry,
+    },
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    solana_sdk_ids::{native_loader, system_program, sysvar},
+    solana_treasury_program::{instruction::TreasuryInstruction, state::TreasuryConfig},
+    std::sync::Arc,
+    trv1_governance_program::{
+        instruction::GovernanceInstruction,
+        state::{
+            find_delegation_record_address, find_vote_record_address, GovernanceConfig, Proposal,
+            ProposalStatus, ProposalType, Vote,
+        },
+    },
+};
+
+// ─── Helpers ─────────────────────────────────────────────────────────────────
+
+/// Run `data` through `program_id`'s processor and write the resulting
+/// account state back into `accounts`.
+///
+/// The treasury and system builtins are registered so that governance can
+/// CPI into the treasury and create its PDAs.
+fn process(
+    program_id: Pubkey,
+    entrypoint: BuiltinFunctionWithContext,
+    data: Vec<u8>,
+    accounts: &mut [(Pubkey, AccountSharedData)],
+    metas: Vec<AccountMeta>,
+    expected_result: Result<(), InstructionError>,
+) {
+    // The mock appends its own program account for `program_id`.
+    let transaction_accounts: Vec<_> = accounts
+        .iter()
+        .filter(|(key, _)| *key != program_id)
+        .cloned()
+        .collect();
+    let keys: Vec<_> = transaction_accounts.iter().map(|(key, _)| *key).collect();
+    let after = mock_process_instruction(
+        &program_id,
+        None,
+        &data,
+        transaction_accounts,
+        metas,
+        expected_result,
+        entrypoint,
+        |invoke_context| {
+            invoke_context.program_cache_for_tx_batch.replenish(
+                solana_treasury_program::id(),
+                Arc::new(ProgramCacheEntry::new_builtin(
+                    0,
+                    0,
+                    solana_treasury_program::processor::Entrypoint::vm,
+                )),
+            );
+            invoke_context.program_cache_for_tx_batch.replenish(
+                system_program::id(),
+                Arc::new(ProgramCacheEntry::new_builtin(
+                    0,
+                    0,
+                    solana_system_program::system_processor::Entrypoint::vm,
+                )),
+            );
+        },
+        |_invoke_context| {},
+    );
+    for (key, updated) in keys.into_iter().zip(after) {
+        if let Some((_, account)) = accounts.iter_mut().find(|(k, _)| *k == key) {
+            *account = updated;
+        }
+    }
+}
+
+fn governance(
+    instruction: &GovernanceInstruction,
+    accounts: &mut [(Pubkey, AccountSharedData)],
+    metas: Vec<AccountMeta>,
+    expected_result: Result<(), InstructionError>,
+) {
+    process(
+        trv1_governance_program::id(),
+        trv1_governance_program::processor::Entrypoint::vm,
+        bincode::serialize(instruction).unwrap(),
+        accounts,
+        metas,
+        expected_result,
+    );
+}
+
+fn treasury(
+    instruction: &TreasuryInstruction,
+    accounts: &mut [(Pubkey, AccountSharedData)],
+    metas: Vec<AccountMeta>,
+) {
+    process(
+        solana_treasury_program::id(),
+        solana_treasury_program::processor::Entrypoint::vm,
+        bincode::serialize(instruction).unwrap(),
+        accounts,
+        metas,
+        Ok(()),
+    );
+}
+
+fn set_epoch(accounts: &mut [(Pubkey, AccountSharedData)], epoch: u64) {
+    let clock = Clock {
+        epoch,
+        ..Clock::default()
+    };
+    for (key, account) in accounts.iter_mut() {
+        if *key == sysvar::clock::id() {
+            *account = account::create_account_shared_data_for_test(&clock);
+        }
+    }
+}
+
+fn lamports(accounts: &[(Pubkey, AccountSharedData)], key: &Pubkey) -> u64 {
+    accounts
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, account)| account.lamports())
+        .unwrap()
+}
+
+fn data<'a>(accounts: &'a [(Pubkey, AccountSharedData)], key: &Pubkey) -> &'a [u8] {
+    accounts
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, account)| account.data())
+        .unwrap()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+//  1. TreasurySpend proposals
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_treasury_spend_proposal_end_to_end() {
+    let multisig = Pubkey::new_unique();
+    let governance_config = Pubkey::new_unique();
+    let proposal = Pubkey::new_unique();
+    let voter = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let (vote_record, _) = find_vote_record_address(0, &voter);
+    let (voter_delegation, _) = find_delegation_record_address(&voter);
+    let treasury_config = Pubkey::new_unique();
+    let treasury_account = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let treasury_balance = 1_000 * SOL;
+    // The treasury's default epoch allowance: 10% of its balance.
+    let spend_amount = 100 * SOL;
+    let recipient_balance = SOL;
+
+    // Active governance: one epoch of voting, one epoch of timelock.
+    let config = GovernanceConfig {
+        is_active: true,
+        authority: multisig,
+        proposal_threshold: 0,
+        voting_period_epochs: 1,
+        quorum_bps: 3_000,
+        pass_threshold_bps: 5_000,
+        veto_threshold_bps: 3_333,
+        timelock_epochs: 1,
+        emergency_multisig: Pubkey::new_unique(),
+        next_proposal_id: 0,
+        max_delegation_epochs: 90,
+        vote_change_lockout_epochs: 0,
+        proposal_deposit: 0,
+        // The voter below holds half of the eligible voting power.
+        total_governance_supply: 100 * SOL,
+        expiry_grace_epochs: 30,
+        sweeper_reward_lamports: 0,
+    };
+    let mut config_account = AccountSharedData::new(
+        1,
+        GovernanceConfig::SERIALIZED_SIZE,
+        &trv1_governance_program::id(),
+    );
+    config
+        .serialize_into(config_account.data_as_mut_slice())
+        .unwrap();
+
+    // A 360-day passive stake votes with 0.5× weight.
+    let stake_state = PassiveStakeAccount {
+        authority: voter,
+        amount: 100 * SOL,
+        lock_days: 360,
+        lock_start: 0,
+        lock_end: 0,
+        unclaimed_rewards: 0,
+        last_reward_epoch: 0,
+        is_permanent: false,
+        vote_weight_bps: 5_000,
+        lock_start_epoch: 0,
+        auto_compound: false,
+    };
+    let mut stake_account = AccountSharedData::new(
+        1,
+        PassiveStakeAccount::SERIALIZED_SIZE,
+        &solana_passive_stake_program::id(),
+    );
+    stake_state
+        .serialize_into(stake_account.data_as_mut_slice())
+        .unwrap();
+
+    let mut program_account = AccountSharedData::new(1, 0, &native_loader::id());
+    program_account.set_executable(true);
+
+    let mut accounts = vec![
+        (
+            multisig,
+            AccountSharedData::new(SOL, 0, &system_program::id()),
+        ),
+        (governance_config, config_account),
+        (
+            proposal,
+            AccountSharedData::new(1, Proposal::SERIALIZED_SIZE, &trv1_governance_program::id()),
+        ),
+        (voter, AccountSharedData::new(SOL, 0, &system_program::id())),
+        (
+            voter_delegation,
+            AccountSharedData::new(0, 0, &system_program::id()),
+        ),
+        (stake, stake_account),
+        (
+            vote_record,
+            AccountSharedData::new(0, 0, &system_program::id()),
+        ),
+        (
+            treasury_config,
+            AccountSharedData::new(
+                1,
+                TreasuryConfig::SERIALIZED_SIZE,
+                &solana_treasury_program::id(),
+            ),
+        ),
+        (
+            treasury_account,
+            AccountSharedData::new(treasury_balance, 0, &solana_treasury_program::id()),
+        ),
+        (
+            recipient,
+            AccountSharedData::new(recipient_balance, 0, &system_program::id()),
+        ),
+        (
+            sysvar::clock::id(),
+            account::create_account_shared_data_for_test(&Clock::default()),
+        ),
+        (
+            sysvar::rent::id(),
+            account::create_account_shared_data_for_test(&Rent::default()),
+        ),
+        (system_program::id(), program_account.clone()),
+        (solana_treasury_program::id(), program_account),
+    ];
+
+    // The multisig sets up the treasury and hands it to governance.
+    treasury(
+        &TreasuryInstruction::InitializeTreasury {
+            authority: multisig,
+            treasury_account,
+        },
+        &mut accounts,
+        vec![
+            AccountMeta::new(multisig, true),
+            AccountMeta::new(treasury_config, false),
+        ],
+    );
+    treasury(
+        &TreasuryInstruction::UpdateAuthority {
+            new_authority: governance_config,
+        },
+        &mut accounts,
+        vec![
+            AccountMeta::new_readonly(multisig, true),
+            AccountMeta::new(treasury_config, false),
+        ],
+    );
+
+    // Epoch 0: propose and vote.
+    let mut memo = [0; 32];
+    memo[..15].copy_from_slice(b"ecosystem grant");
+    governance(
+        &GovernanceInstruction::CreateProposal {
+            title: b"Fund ecosystem grant".to_vec(),
+            description_hash: Hash::default(),
+            proposal_type: ProposalType::TreasurySpend {
+                recipient,
+                amount: spend_amount,
+                memo,
+            },
+        },
+        &mut accounts,
+        vec![
+            AccountMeta::new_readonly(voter, true),
+            AccountMeta::new(governance_config, false),
+            AccountMeta::new(proposal, false),
+            AccountMeta::new_readonly(stake, false),
+        ],
+        Ok(()),
+    );
+    governance(
+        &GovernanceInstruction::CastVote {
+            proposal_id: 0,
+            vote: Vote::For,
+        },
+        &mut accounts,
+        vec![
+            AccountMeta::new(voter, true),
+            AccountMeta::new(proposal, false),
+            AccountMeta::new_readonly(governance_config, false),
+            AccountMeta::new_readonly(stake, false),
+            AccountMeta::new(vote_record, false),
+            AccountMeta::new_readonly(voter_delegation, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        Ok(()),
+    );
+
+    let execute = GovernanceInstruction::ExecuteProposal { proposal_id: 0 };
+    let execute_metas = vec![
+        AccountMeta::new_readonly(voter, true),
+        AccountMeta::new(proposal, false),
+        AccountMeta::new(governance_config, false),
+        AccountMeta::new(treasury_config, false),
+        AccountMeta::new(recipient, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(treasury_account, false),
+        AccountMeta::new_readonly(solana_treasury_program::id(), false),
+    ];
+
+    // Epoch 1: the vote is tallied and the proposal enters its timelock.
+    set_epoch(&mut accounts, 1);
+    governance(&execute, &mut accounts, execute_metas.clone(), Ok(()));
+    let state = Proposal::deserialize(data(&accounts, &proposal)).unwrap();
+    assert_eq!(state.status, ProposalStatus::Timelocked);
+    assert_eq!(state.votes_for, 50 * SOL);
+    assert_eq!(lamports(&accounts, &recipient), recipient_balance);
+
+    // Epoch 2: the timelock has expired and the treasury pays out.
+    set_epoch(&mut accounts, 2);
+    governance(&execute, &mut accounts, execute_metas, Ok(()));
+    let state = Proposal::deserialize(data(&accounts, &proposal)).unwrap();
+    assert_eq!(state.status, ProposalStatus::Executed);
+    assert_eq!(
+        lamports(&accounts, &recipient),
+        recipient_balance + spend_amount
+    );
+    assert_eq!(
+        lamports(&accounts, &treasury_account),
+        treasury_balance - spend_amount
+    );
+    let treasury_state = TreasuryConfig::deserialize(data(&accounts, &treasury_config)).unwrap();
+    assert_eq!(treasury_state.total_disbursed, spend_amount);
+    assert_eq!(treasury_state.last_updated_epoch, 2);
+}
@@ -9,12 +9,14 @@
 //! 2. **Developer Rewards** — revenue recipient registration, multi-splits,
 //!    anti-gaming (CU threshold, 7-day cooldown, 10% epoch cap)
 //! 3. **Treasury** — initialize, disburse, authority transfer, governance
-//!    activation
+//!    activation, M-of-N multisig authority
 //! 4. **Slashing** — double-sign penalties, escalating offenses, permanent bans,
 //!    delegator protection, jailing/unjailing
 //! 5. **Validator Set** — 200-cap active set, standby rotation, jailed exclusion
 //! 6. **Fee Distribution** — epoch-dependent 4-way split (burn/validator/treasury/dev)
 //! 7. **Inflation** — flat 5% annual on staked supply only
+//! 8. **Governance** — TreasurySpend proposals voted through and disbursed by
+//!    the treasury program
 
 pub mod harness;
 
@@ -38,3 +40,6 @@ mod fee_distribution_tests;
 
 #[cfg(test)]
 mod inflation_tests;
+
+#[cfg(test)]
+mod governance_tests;
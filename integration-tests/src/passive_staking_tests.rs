@@ -5,20 +5,32 @@
 
 use {
     crate::harness::{self, SOL, TRv1TestHarness},
+    solana_account::{self as account, AccountSharedData, ReadableAccount, WritableAccount},
+    solana_clock::Clock,
+    solana_instruction::{error::InstructionError, AccountMeta},
     solana_passive_stake_program::{
         constants::{
-            self, BPS_DENOMINATOR, EARLY_UNLOCK_PENALTY_30_DAY_BPS,
-            EARLY_UNLOCK_PENALTY_360_DAY_BPS, EARLY_UNLOCK_PENALTY_90_DAY_BPS,
-            EARLY_UNLOCK_PENALTY_180_DAY_BPS, EARLY_UNLOCK_PENALTY_NO_LOCK_BPS,
-            PERMANENT_LOCK_DAYS, REWARD_RATE_30_DAY_BPS, REWARD_RATE_90_DAY_BPS,
-            REWARD_RATE_180_DAY_BPS, REWARD_RATE_360_DAY_BPS, REWARD_RATE_NO_LOCK_BPS,
-            REWARD_RATE_PERMANENT_BPS, SECONDS_PER_DAY, TIER_180_DAY, TIER_30_DAY,
-            TIER_360_DAY, TIER_90_DAY, TIER_NO_LOCK, VOTE_WEIGHT_180_DAY,
-            VOTE_WEIGHT_30_DAY, VOTE_WEIGHT_360_DAY, VOTE_WEIGHT_90_DAY,
+            self, BPS_DENOMINATOR, EARLY_UNLOCK_PENALTY_180_DAY_BPS,
+            EARLY_UNLOCK_PENALTY_30_DAY_BPS, EARLY_UNLOCK_PENALTY_360_DAY_BPS,
+            EARLY_UNLOCK_PENALTY_90_DAY_BPS, EARLY_UNLOCK_PENALTY_NO_LOCK_BPS,
+            MAX_BATCH_CLAIM_ACCOUNTS, MAX_STAKE_AMOUNT, MAX_STAKE_PER_ADDRESS, PERMANENT_LOCK_DAYS,
+            REWARD_RATE_180_DAY_BPS, REWARD_RATE_30_DAY_BPS, REWARD_RATE_360_DAY_BPS,
+            REWARD_RATE_90_DAY_BPS, REWARD_RATE_NO_LOCK_BPS, REWARD_RATE_PERMANENT_BPS,
+            SECONDS_PER_DAY, TIER_180_DAY, TIER_30_DAY, TIER_360_DAY, TIER_90_DAY, TIER_NO_LOCK,
+            VOTE_WEIGHT_180_DAY, VOTE_WEIGHT_30_DAY, VOTE_WEIGHT_360_DAY, VOTE_WEIGHT_90_DAY,
             VOTE_WEIGHT_NO_LOCK, VOTE_WEIGHT_PERMANENT,
         },
-        state::PassiveStakeAccount,
+        error::PassiveStakeError,
+        instruction::{ClaimSummary, PassiveStakeInstruction},
+        state::{find_stake_summary_address, PassiveStakeAccount, StakeSummaryAccount},
     },
+    solana_program_runtime::{
+        invoke_context::mock_process_instruction, loaded_programs::ProgramCacheEntry,
+    },
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    solana_sdk_ids::{native_loader, system_program, sysvar},
+    std::sync::Arc,
 };
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -496,6 +508,8 @@ fn test_passive_stake_account_serialization_roundtrip() {
         last_reward_epoch: 100,
         is_permanent: false,
         vote_weight_bps: VOTE_WEIGHT_90_DAY,
+        lock_start_epoch: 100,
+        auto_compound: false,
     };
 
     let mut buf = vec![0u8; PassiveStakeAccount::SERIALIZED_SIZE];
@@ -507,8 +521,33 @@ fn test_passive_stake_account_serialization_roundtrip() {
 
 #[test]
 fn test_passive_stake_account_serialized_size() {
-    // 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 2 = 84
-    assert_eq!(PassiveStakeAccount::SERIALIZED_SIZE, 84);
+    // 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 2 + 8 + 1 = 93
+    assert_eq!(PassiveStakeAccount::SERIALIZED_SIZE, 93);
+}
+
+#[test]
+fn test_deserialize_legacy_84_byte_account() {
+    let state = PassiveStakeAccount {
+        authority: Pubkey::new_unique(),
+        amount: 42 * SOL,
+        lock_days: TIER_90_DAY,
+        lock_start: 1_700_000_000,
+        lock_end: 1_700_000_000 + 90 * SECONDS_PER_DAY,
+        unclaimed_rewards: 7,
+        last_reward_epoch: 12,
+        is_permanent: false,
+        vote_weight_bps: VOTE_WEIGHT_90_DAY,
+        lock_start_epoch: 0,
+        auto_compound: false,
+    };
+    let mut buf = vec![0u8; PassiveStakeAccount::SERIALIZED_SIZE];
+    state.serialize_into(&mut buf).unwrap();
+
+    // The original layout is the current one without its last two fields.
+    assert_eq!(PassiveStakeAccount::LEGACY_SERIALIZED_SIZE, 84);
+    let legacy = &buf[..PassiveStakeAccount::LEGACY_SERIALIZED_SIZE];
+    assert_eq!(PassiveStakeAccount::deserialize(legacy).unwrap(), state);
+    assert!(PassiveStakeAccount::deserialize(&legacy[..83]).is_err());
 }
 
 #[test]
@@ -541,6 +580,8 @@ fn test_claim_zeroes_unclaimed_rewards() {
         last_reward_epoch: 100,
         is_permanent: false,
         vote_weight_bps: VOTE_WEIGHT_90_DAY,
+        lock_start_epoch: 0,
+        auto_compound: false,
     };
 
     let claimed = state.unclaimed_rewards;
@@ -563,6 +604,8 @@ fn test_claim_with_zero_rewards_should_fail() {
         last_reward_epoch: 50,
         is_permanent: false,
         vote_weight_bps: VOTE_WEIGHT_30_DAY,
+        lock_start_epoch: 0,
+        auto_compound: false,
     };
     assert_eq!(state.unclaimed_rewards, 0, "Should have no rewards to claim");
 }
@@ -583,6 +626,8 @@ fn test_permanent_lock_state() {
         last_reward_epoch: 0,
         is_permanent: true,
         vote_weight_bps: VOTE_WEIGHT_PERMANENT,
+        lock_start_epoch: 0,
+        auto_compound: false,
     };
 
     assert!(state.is_permanent);
@@ -609,3 +654,1276 @@ fn test_permanent_lock_earns_highest_rewards() {
         ratio
     );
 }
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 12. Processor: top-up
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Run `instruction` through the passive-stake processor and write the
+/// resulting account state back into `accounts`.
+fn passive_stake(
+    instruction: &PassiveStakeInstruction,
+    accounts: &mut [(Pubkey, AccountSharedData)],
+    metas: Vec<AccountMeta>,
+    expected_result: Result<(), InstructionError>,
+) {
+    passive_stake_with_return_data(instruction, accounts, metas, expected_result);
+}
+
+/// Like [`passive_stake`], also returning the instruction's return data.
+fn passive_stake_with_return_data(
+    instruction: &PassiveStakeInstruction,
+    accounts: &mut [(Pubkey, AccountSharedData)],
+    metas: Vec<AccountMeta>,
+    expected_result: Result<(), InstructionError>,
+) -> Vec<u8> {
+    let mut return_data = Vec::new();
+    let program_id = solana_passive_stake_program::id();
+    // The mock appends its own program account for `program_id`.
+    let transaction_accounts: Vec<_> = accounts
+        .iter()
+        .filter(|(key, _)| *key != program_id)
+        .cloned()
+        .collect();
+    let keys: Vec<_> = transaction_accounts.iter().map(|(key, _)| *key).collect();
+    let after = mock_process_instruction(
+        &program_id,
+        None,
+        &bincode::serialize(instruction).unwrap(),
+        transaction_accounts,
+        metas,
+        expected_result,
+        solana_passive_stake_program::processor::Entrypoint::vm,
+        |invoke_context| {
+            invoke_context.program_cache_for_tx_batch.replenish(
+                system_program::id(),
+                Arc::new(ProgramCacheEntry::new_builtin(
+                    0,
+                    0,
+                    solana_system_program::system_processor::Entrypoint::vm,
+                )),
+            );
+        },
+        |invoke_context| {
+            return_data = invoke_context
+                .transaction_context
+                .get_return_data()
+                .1
+                .to_vec();
+        },
+    );
+    for (key, updated) in keys.into_iter().zip(after) {
+        if let Some((_, account)) = accounts.iter_mut().find(|(k, _)| *k == key) {
+            *account = updated;
+        }
+    }
+    return_data
+}
+
+fn set_epoch(accounts: &mut [(Pubkey, AccountSharedData)], epoch: u64) {
+    let clock = Clock {
+        epoch,
+        ..Clock::default()
+    };
+    for (key, account) in accounts.iter_mut() {
+        if *key == sysvar::clock::id() {
+            *account = account::create_account_shared_data_for_test(&clock);
+        }
+    }
+}
+
+fn account_of<'a>(
+    accounts: &'a [(Pubkey, AccountSharedData)],
+    key: &Pubkey,
+) -> &'a AccountSharedData {
+    accounts
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, account)| account)
+        .unwrap()
+}
+
+fn stake_state(accounts: &[(Pubkey, AccountSharedData)], key: &Pubkey) -> PassiveStakeAccount {
+    PassiveStakeAccount::deserialize(account_of(accounts, key).data()).unwrap()
+}
+
+/// `authority`'s stake summary PDA, recording `total_staked` lamports.
+fn stake_summary_account(authority: Pubkey, total_staked: u64) -> (Pubkey, AccountSharedData) {
+    let mut account = AccountSharedData::new(
+        Rent::default().minimum_balance(StakeSummaryAccount::SERIALIZED_SIZE),
+        StakeSummaryAccount::SERIALIZED_SIZE,
+        &solana_passive_stake_program::id(),
+    );
+    StakeSummaryAccount {
+        authority,
+        total_staked,
+    }
+    .serialize_into(account.data_as_mut_slice())
+    .unwrap();
+    (find_stake_summary_address(&authority).0, account)
+}
+
+/// `authority`'s stake summary PDA before its first deposit creates it.
+fn uncreated_stake_summary_account(authority: Pubkey) -> (Pubkey, AccountSharedData) {
+    (
+        find_stake_summary_address(&authority).0,
+        AccountSharedData::new(0, 0, &system_program::id()),
+    )
+}
+
+fn total_staked(accounts: &[(Pubkey, AccountSharedData)], authority: &Pubkey) -> u64 {
+    let summary = account_of(accounts, &find_stake_summary_address(authority).0);
+    StakeSummaryAccount::deserialize(summary.data())
+        .unwrap()
+        .total_staked
+}
+
+/// A funded authority, a reward pool, and a passive stake position of
+/// `amount` lamports created at epoch 0, recorded in the authority's stake
+/// summary.
+fn stake_fixture(
+    authority: Pubkey,
+    stake: Pubkey,
+    pool: Pubkey,
+    amount: u64,
+    lock_days: u64,
+) -> Vec<(Pubkey, AccountSharedData)> {
+    let is_permanent = lock_days == PERMANENT_LOCK_DAYS;
+    let state = PassiveStakeAccount {
+        authority,
+        amount,
+        lock_days,
+        lock_start: 0,
+        lock_end: if is_permanent {
+            0
+        } else {
+            lock_days as i64 * SECONDS_PER_DAY
+        },
+        unclaimed_rewards: 0,
+        last_reward_epoch: 0,
+        is_permanent,
+        vote_weight_bps: constants::vote_weight_bps_for_tier(lock_days).unwrap(),
+        lock_start_epoch: 0,
+        auto_compound: false,
+    };
+    let mut stake_account = AccountSharedData::new(
+        amount,
+        PassiveStakeAccount::SERIALIZED_SIZE,
+        &solana_passive_stake_program::id(),
+    );
+    state
+        .serialize_into(stake_account.data_as_mut_slice())
+        .unwrap();
+
+    let mut program_account = AccountSharedData::new(1, 0, &native_loader::id());
+    program_account.set_executable(true);
+
+    vec![
+        (
+            authority,
+            AccountSharedData::new(100 * SOL, 0, &system_program::id()),
+        ),
+        (stake, stake_account),
+        (
+            pool,
+            AccountSharedData::new(1_000 * SOL, 0, &solana_passive_stake_program::id()),
+        ),
+        (
+            sysvar::clock::id(),
+            account::create_account_shared_data_for_test(&Clock::default()),
+        ),
+        (
+            sysvar::rent::id(),
+            account::create_account_shared_data_for_test(&Rent::default()),
+        ),
+        (system_program::id(), program_account),
+        stake_summary_account(authority, amount),
+    ]
+}
+
+/// Advance the clock to `epoch` and checkpoint `stake`'s rewards there.
+fn calculate_rewards(
+    accounts: &mut [(Pubkey, AccountSharedData)],
+    stake: &Pubkey,
+    epoch: u64,
+    validator_reward_rate: u64,
+) {
+    set_epoch(accounts, epoch);
+    passive_stake(
+        &PassiveStakeInstruction::CalculateEpochRewards {
+            current_epoch: epoch,
+            validator_reward_rate,
+        },
+        accounts,
+        vec![AccountMeta::new(*stake, false)],
+        Ok(()),
+    );
+}
+
+#[test]
+fn test_legacy_account_grows_on_first_write() {
+    let authority = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let mut accounts = stake_fixture(authority, stake, pool, 100 * SOL, TIER_90_DAY);
+    let before = stake_state(&accounts, &stake);
+    let legacy = account_of(&accounts, &stake).data()
+        [..PassiveStakeAccount::LEGACY_SERIALIZED_SIZE]
+        .to_vec();
+    let (_, stake_account) = accounts.iter_mut().find(|(key, _)| *key == stake).unwrap();
+    stake_account.set_data_from_slice(&legacy);
+
+    calculate_rewards(&mut accounts, &stake, 5, 500);
+    let stake_account = account_of(&accounts, &stake);
+    assert_eq!(
+        stake_account.data().len(),
+        PassiveStakeAccount::SERIALIZED_SIZE
+    );
+    assert_eq!(stake_account.lamports(), 100 * SOL);
+    let after = stake_state(&accounts, &stake);
+    assert_eq!(after.amount, before.amount);
+    assert_eq!(after.lock_end, before.lock_end);
+    assert_eq!(after.last_reward_epoch, 5);
+    assert_eq!(after.lock_start_epoch, 0);
+    assert!(!after.auto_compound);
+}
+
+fn top_up_metas(authority: Pubkey, stake: Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(authority, true),
+        AccountMeta::new(stake, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(find_stake_summary_address(&authority).0, false),
+    ]
+}
+
+#[test]
+fn test_top_up_preserves_reward_continuity() {
+    let authority = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let validator_rate = 500;
+    let mut accounts = stake_fixture(authority, stake, pool, 100 * SOL, TIER_90_DAY);
+    let before = stake_state(&accounts, &stake);
+
+    let top_up = PassiveStakeInstruction::TopUpStake {
+        stake_account: stake,
+        additional_lamports: 50 * SOL,
+    };
+
+    // Epochs 1..=10 have not been checkpointed yet.
+    set_epoch(&mut accounts, 10);
+    passive_stake(
+        &top_up,
+        &mut accounts,
+        top_up_metas(authority, stake),
+        Err(PassiveStakeError::UnclaimedRewardsPending.into()),
+    );
+
+    // Checkpointed but not claimed.
+    calculate_rewards(&mut accounts, &stake, 10, validator_rate);
+    let first_period = compute_epoch_reward(100 * SOL, validator_rate, TIER_90_DAY) * 10;
+    assert_eq!(
+        stake_state(&accounts, &stake).unclaimed_rewards,
+        first_period
+    );
+    passive_stake(
+        &top_up,
+        &mut accounts,
+        top_up_metas(authority, stake),
+        Err(PassiveStakeError::UnclaimedRewardsPending.into()),
+    );
+
+    passive_stake(
+        &PassiveStakeInstruction::ClaimRewards,
+        &mut accounts,
+        vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(stake, false),
+            AccountMeta::new(pool, false),
+        ],
+        Ok(()),
+    );
+    let authority_balance = account_of(&accounts, &authority).lamports();
+    assert_eq!(authority_balance, 100 * SOL + first_period);
+
+    // Below the minimum top-up.
+    passive_stake(
+        &PassiveStakeInstruction::TopUpStake {
+            stake_account: stake,
+            additional_lamports: SOL / 2,
+        },
+        &mut accounts,
+        top_up_metas(authority, stake),
+        Err(PassiveStakeError::AmountTooSmall.into()),
+    );
+
+    // Only the position's authority may top it up.
+    let stranger = Pubkey::new_unique();
+    accounts.push((
+        stranger,
+        AccountSharedData::new(100 * SOL, 0, &system_program::id()),
+    ));
+    accounts.push(uncreated_stake_summary_account(stranger));
+    passive_stake(
+        &top_up,
+        &mut accounts,
+        top_up_metas(stranger, stake),
+        Err(PassiveStakeError::MissingAuthoritySignature.into()),
+    );
+
+    passive_stake(
+        &top_up,
+        &mut accounts,
+        top_up_metas(authority, stake),
+        Ok(()),
+    );
+    let after = stake_state(&accounts, &stake);
+    assert_eq!(after.amount, 150 * SOL);
+    assert_eq!(after.lock_days, before.lock_days);
+    assert_eq!(after.lock_start, before.lock_start);
+    assert_eq!(after.lock_end, before.lock_end);
+    assert_eq!(after.vote_weight_bps, before.vote_weight_bps);
+    // Governance snapshots taken before the top-up no longer count it.
+    assert_eq!(after.lock_start_epoch, 10);
+    assert_eq!(account_of(&accounts, &stake).lamports(), 150 * SOL);
+    assert_eq!(
+        account_of(&accounts, &authority).lamports(),
+        authority_balance - 50 * SOL
+    );
+    assert_eq!(total_staked(&accounts, &authority), 150 * SOL);
+
+    // Epochs 11..=20 accrue on the topped-up amount.
+    calculate_rewards(&mut accounts, &stake, 20, validator_rate);
+    let second_period = compute_epoch_reward(150 * SOL, validator_rate, TIER_90_DAY) * 10;
+    assert_eq!(
+        stake_state(&accounts, &stake).unclaimed_rewards,
+        second_period
+    );
+    assert!(second_period > first_period);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 13. Processor: split
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn split_metas(
+    authority: Pubkey,
+    stake: Pubkey,
+    new_stake: Pubkey,
+    pool: Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(authority, true),
+        AccountMeta::new(stake, false),
+        AccountMeta::new(new_stake, false),
+        AccountMeta::new(pool, false),
+    ]
+}
+
+#[test]
+fn test_split_positions_earn_rewards_independently() {
+    let authority = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let new_stake = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let validator_rate = 500;
+    let mut accounts = stake_fixture(authority, stake, pool, 100 * SOL, TIER_180_DAY);
+    accounts.push((
+        new_stake,
+        AccountSharedData::new(
+            0,
+            PassiveStakeAccount::SERIALIZED_SIZE,
+            &solana_passive_stake_program::id(),
+        ),
+    ));
+    let split = |split_amount| PassiveStakeInstruction::SplitStake {
+        stake_account: stake,
+        split_amount,
+        new_stake_account: new_stake,
+    };
+
+    // Epochs 1..=5 have not been checkpointed yet.
+    set_epoch(&mut accounts, 5);
+    passive_stake(
+        &split(30 * SOL),
+        &mut accounts,
+        split_metas(authority, stake, new_stake, pool),
+        Err(PassiveStakeError::UnclaimedRewardsPending.into()),
+    );
+    calculate_rewards(&mut accounts, &stake, 5, validator_rate);
+    let pre_split_rewards = stake_state(&accounts, &stake).unclaimed_rewards;
+    assert_eq!(
+        pre_split_rewards,
+        compute_epoch_reward(100 * SOL, validator_rate, TIER_180_DAY) * 5
+    );
+
+    // Both sides must stay at or above the minimum position size.
+    for split_amount in [SOL / 2, 100 * SOL - SOL / 2, 100 * SOL, 200 * SOL] {
+        passive_stake(
+            &split(split_amount),
+            &mut accounts,
+            split_metas(authority, stake, new_stake, pool),
+            Err(PassiveStakeError::AmountTooSmall.into()),
+        );
+    }
+
+    let original = stake_state(&accounts, &stake);
+    passive_stake(
+        &split(30 * SOL),
+        &mut accounts,
+        split_metas(authority, stake, new_stake, pool),
+        Ok(()),
+    );
+    assert_eq!(
+        account_of(&accounts, &authority).lamports(),
+        100 * SOL + pre_split_rewards
+    );
+
+    let remaining = stake_state(&accounts, &stake);
+    let split_off = stake_state(&accounts, &new_stake);
+    assert_eq!(remaining.amount, 70 * SOL);
+    assert_eq!(split_off.amount, 30 * SOL);
+    for position in [&remaining, &split_off] {
+        assert_eq!(position.authority, authority);
+        assert_eq!(position.unclaimed_rewards, 0);
+        assert_eq!(position.lock_days, original.lock_days);
+        assert_eq!(position.lock_start, original.lock_start);
+        assert_eq!(position.lock_end, original.lock_end);
+        assert_eq!(position.lock_start_epoch, original.lock_start_epoch);
+        assert_eq!(position.vote_weight_bps, original.vote_weight_bps);
+    }
+    assert_eq!(account_of(&accounts, &stake).lamports(), 70 * SOL);
+    assert_eq!(account_of(&accounts, &new_stake).lamports(), 30 * SOL);
+
+    // The new account cannot be split into twice.
+    passive_stake(
+        &split(10 * SOL),
+        &mut accounts,
+        split_metas(authority, stake, new_stake, pool),
+        Err(PassiveStakeError::AccountAlreadyInitialized.into()),
+    );
+
+    // Epochs 6..=15 accrue on each position's own amount.
+    calculate_rewards(&mut accounts, &stake, 15, validator_rate);
+    calculate_rewards(&mut accounts, &new_stake, 15, validator_rate);
+    assert_eq!(
+        stake_state(&accounts, &stake).unclaimed_rewards,
+        compute_epoch_reward(70 * SOL, validator_rate, TIER_180_DAY) * 10
+    );
+    assert_eq!(
+        stake_state(&accounts, &new_stake).unclaimed_rewards,
+        compute_epoch_reward(30 * SOL, validator_rate, TIER_180_DAY) * 10
+    );
+}
+
+#[test]
+fn test_split_rejects_stake_account_as_rewards_pool() {
+    let authority = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let new_stake = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let mut accounts = stake_fixture(authority, stake, pool, 100 * SOL, TIER_180_DAY);
+    accounts.push((
+        new_stake,
+        AccountSharedData::new(
+            0,
+            PassiveStakeAccount::SERIALIZED_SIZE,
+            &solana_passive_stake_program::id(),
+        ),
+    ));
+    calculate_rewards(&mut accounts, &stake, 5, 500);
+    let before = stake_state(&accounts, &stake);
+    assert!(before.unclaimed_rewards > 0);
+
+    // The rewards must not be paid out of either position's principal.
+    for aliased_pool in [stake, new_stake] {
+        passive_stake(
+            &PassiveStakeInstruction::SplitStake {
+                stake_account: stake,
+                split_amount: 30 * SOL,
+                new_stake_account: new_stake,
+            },
+            &mut accounts,
+            split_metas(authority, stake, new_stake, aliased_pool),
+            Err(PassiveStakeError::InvalidAccountData.into()),
+        );
+    }
+    assert_eq!(stake_state(&accounts, &stake), before);
+    assert_eq!(account_of(&accounts, &stake).lamports(), 100 * SOL);
+    assert_eq!(account_of(&accounts, &authority).lamports(), 100 * SOL);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 14. Processor: lock extension
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn extend_lock(
+    accounts: &mut [(Pubkey, AccountSharedData)],
+    authority: Pubkey,
+    stake: Pubkey,
+    pool: Pubkey,
+    new_lock_days: u64,
+    expected_result: Result<(), InstructionError>,
+) {
+    passive_stake(
+        &PassiveStakeInstruction::ExtendLock {
+            stake_account: stake,
+            new_lock_days,
+        },
+        accounts,
+        vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(stake, false),
+            AccountMeta::new(pool, false),
+        ],
+        expected_result,
+    );
+}
+
+#[test]
+fn test_extend_no_lock_to_30_day() {
+    let authority = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let validator_rate = 500;
+    let mut accounts = stake_fixture(authority, stake, pool, 100 * SOL, TIER_NO_LOCK);
+
+    // Rewards up to the extension are paid at the no-lock rate.
+    set_epoch(&mut accounts, 3);
+    extend_lock(
+        &mut accounts,
+        authority,
+        stake,
+        pool,
+        TIER_30_DAY,
+        Err(PassiveStakeError::UnclaimedRewardsPending.into()),
+    );
+    calculate_rewards(&mut accounts, &stake, 3, validator_rate);
+    let no_lock_rewards = compute_epoch_reward(100 * SOL, validator_rate, TIER_NO_LOCK) * 3;
+
+    extend_lock(&mut accounts, authority, stake, pool, TIER_30_DAY, Ok(()));
+    assert_eq!(
+        account_of(&accounts, &authority).lamports(),
+        100 * SOL + no_lock_rewards
+    );
+
+    let state = stake_state(&accounts, &stake);
+    assert_eq!(state.lock_days, TIER_30_DAY);
+    assert_eq!(state.vote_weight_bps, VOTE_WEIGHT_30_DAY);
+    assert_eq!(state.lock_end, 30 * SECONDS_PER_DAY);
+    assert_eq!(state.unclaimed_rewards, 0);
+    assert_eq!(state.lock_start_epoch, 3);
+    assert!(!state.is_permanent);
+
+    // Later epochs accrue at the 30-day rate.
+    calculate_rewards(&mut accounts, &stake, 13, validator_rate);
+    assert_eq!(
+        stake_state(&accounts, &stake).unclaimed_rewards,
+        compute_epoch_reward(100 * SOL, validator_rate, TIER_30_DAY) * 10
+    );
+}
+
+#[test]
+fn test_extend_lock_rejects_downgrades_and_invalid_tiers() {
+    let authority = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let mut accounts = stake_fixture(authority, stake, pool, 100 * SOL, TIER_90_DAY);
+
+    for new_lock_days in [TIER_NO_LOCK, TIER_30_DAY, TIER_90_DAY, 45] {
+        extend_lock(
+            &mut accounts,
+            authority,
+            stake,
+            pool,
+            new_lock_days,
+            Err(PassiveStakeError::InvalidLockTier.into()),
+        );
+    }
+    assert_eq!(stake_state(&accounts, &stake).lock_days, TIER_90_DAY);
+}
+
+#[test]
+fn test_extend_permanent_lock_fails() {
+    let authority = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let mut accounts = stake_fixture(authority, stake, pool, 100 * SOL, TIER_360_DAY);
+
+    extend_lock(
+        &mut accounts,
+        authority,
+        stake,
+        pool,
+        PERMANENT_LOCK_DAYS,
+        Ok(()),
+    );
+    let state = stake_state(&accounts, &stake);
+    assert!(state.is_permanent);
+    assert_eq!(state.lock_end, 0);
+    assert_eq!(state.vote_weight_bps, VOTE_WEIGHT_PERMANENT);
+
+    extend_lock(
+        &mut accounts,
+        authority,
+        stake,
+        pool,
+        PERMANENT_LOCK_DAYS,
+        Err(PassiveStakeError::InvalidLockTier.into()),
+    );
+}
+
+#[test]
+fn test_extend_lock_rejects_stake_account_as_rewards_pool() {
+    let authority = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let mut accounts = stake_fixture(authority, stake, pool, 100 * SOL, TIER_NO_LOCK);
+
+    // The aliasing is rejected before the checkpoints are looked at.
+    set_epoch(&mut accounts, 3);
+    extend_lock(
+        &mut accounts,
+        authority,
+        stake,
+        stake,
+        TIER_30_DAY,
+        Err(PassiveStakeError::InvalidAccountData.into()),
+    );
+    calculate_rewards(&mut accounts, &stake, 3, 500);
+    let before = stake_state(&accounts, &stake);
+    extend_lock(
+        &mut accounts,
+        authority,
+        stake,
+        stake,
+        TIER_30_DAY,
+        Err(PassiveStakeError::InvalidAccountData.into()),
+    );
+    assert_eq!(stake_state(&accounts, &stake), before);
+    assert_eq!(account_of(&accounts, &stake).lamports(), 100 * SOL);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 15. Processor: compounding
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn compound(
+    accounts: &mut [(Pubkey, AccountSharedData)],
+    authority: Pubkey,
+    stake: Pubkey,
+    pool: Pubkey,
+    auto_compound: Option<bool>,
+    expected_result: Result<(), InstructionError>,
+) {
+    passive_stake(
+        &PassiveStakeInstruction::CompoundRewards {
+            stake_account: stake,
+            auto_compound,
+        },
+        accounts,
+        vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(stake, false),
+            AccountMeta::new(pool, false),
+        ],
+        expected_result,
+    );
+}
+
+/// `CalculateEpochRewards` with the rewards pool supplied for auto-compounding.
+fn calculate_rewards_with_pool(
+    accounts: &mut [(Pubkey, AccountSharedData)],
+    stake: &Pubkey,
+    pool: &Pubkey,
+    epoch: u64,
+    validator_reward_rate: u64,
+) {
+    set_epoch(accounts, epoch);
+    passive_stake(
+        &PassiveStakeInstruction::CalculateEpochRewards {
+            current_epoch: epoch,
+            validator_reward_rate,
+        },
+        accounts,
+        vec![
+            AccountMeta::new(*stake, false),
+            AccountMeta::new(*pool, false),
+        ],
+        Ok(()),
+    );
+}
+
+#[test]
+fn test_compound_rewards_into_principal() {
+    let authority = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let validator_rate = 500;
+    let mut accounts = stake_fixture(authority, stake, pool, 100 * SOL, TIER_90_DAY);
+
+    calculate_rewards(&mut accounts, &stake, 10, validator_rate);
+    let rewards = stake_state(&accounts, &stake).unclaimed_rewards;
+    assert!(rewards > 0);
+
+    compound(&mut accounts, authority, stake, pool, None, Ok(()));
+    let state = stake_state(&accounts, &stake);
+    assert_eq!(state.amount, 100 * SOL + rewards);
+    assert_eq!(state.unclaimed_rewards, 0);
+    assert_eq!(state.lock_start_epoch, 0);
+    assert!(!state.auto_compound);
+    assert_eq!(
+        account_of(&accounts, &stake).lamports(),
+        100 * SOL + rewards
+    );
+    assert_eq!(
+        account_of(&accounts, &pool).lamports(),
+        1_000 * SOL - rewards
+    );
+    assert_eq!(account_of(&accounts, &authority).lamports(), 100 * SOL);
+
+    // Nothing left to compound.
+    compound(
+        &mut accounts,
+        authority,
+        stake,
+        pool,
+        None,
+        Err(PassiveStakeError::NoRewardsToClaim.into()),
+    );
+
+    // With auto-compound on, the crank reinvests each epoch's rewards.
+    compound(&mut accounts, authority, stake, pool, Some(true), Ok(()));
+    assert!(stake_state(&accounts, &stake).auto_compound);
+    let principal = stake_state(&accounts, &stake).amount;
+    calculate_rewards_with_pool(&mut accounts, &stake, &pool, 11, validator_rate);
+    let epoch_reward = compute_epoch_reward(principal, validator_rate, TIER_90_DAY);
+    let state = stake_state(&accounts, &stake);
+    assert_eq!(state.amount, principal + epoch_reward);
+    assert_eq!(state.unclaimed_rewards, 0);
+    assert_eq!(
+        account_of(&accounts, &stake).lamports(),
+        principal + epoch_reward
+    );
+
+    // Without the pool there is nothing to fund the principal from.
+    calculate_rewards(&mut accounts, &stake, 12, validator_rate);
+    let state = stake_state(&accounts, &stake);
+    assert_eq!(state.amount, principal + epoch_reward);
+    assert_eq!(
+        state.unclaimed_rewards,
+        compute_epoch_reward(principal + epoch_reward, validator_rate, TIER_90_DAY)
+    );
+}
+
+#[test]
+fn test_compound_permanent_lock_capped_at_max_stake() {
+    let authority = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let validator_rate = 500;
+    let mut accounts = stake_fixture(
+        authority,
+        stake,
+        pool,
+        MAX_STAKE_AMOUNT - SOL,
+        PERMANENT_LOCK_DAYS,
+    );
+
+    compound(&mut accounts, authority, stake, pool, Some(true), Ok(()));
+
+    // A day's rewards would take the position past the maximum, so they
+    // accrue as claimable rewards instead.
+    calculate_rewards_with_pool(&mut accounts, &stake, &pool, 1, validator_rate);
+    let state = stake_state(&accounts, &stake);
+    assert_eq!(state.amount, MAX_STAKE_AMOUNT - SOL);
+    assert!(state.unclaimed_rewards > SOL);
+
+    compound(
+        &mut accounts,
+        authority,
+        stake,
+        pool,
+        None,
+        Err(PassiveStakeError::AmountTooLarge.into()),
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 16. Processor: batch reward claims
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn claim_multiple_metas(authority: Pubkey, pool: Pubkey, stakes: &[Pubkey]) -> Vec<AccountMeta> {
+    let mut metas = vec![
+        AccountMeta::new(authority, true),
+        AccountMeta::new(pool, false),
+    ];
+    metas.extend(stakes.iter().map(|stake| AccountMeta::new(*stake, false)));
+    metas
+}
+
+/// Positions of 10, 20 and 30 SOL in different tiers, all owned by
+/// `authority` and with rewards calculated through epoch 10.
+fn batch_claim_fixture(
+    authority: Pubkey,
+    pool: Pubkey,
+) -> (Vec<(Pubkey, AccountSharedData)>, Vec<Pubkey>) {
+    let stakes = vec![
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let mut accounts = stake_fixture(authority, stakes[0], pool, 10 * SOL, TIER_30_DAY);
+    for (stake, amount, lock_days) in [
+        (stakes[1], 20 * SOL, TIER_180_DAY),
+        (stakes[2], 30 * SOL, PERMANENT_LOCK_DAYS),
+    ] {
+        let position = stake_fixture(authority, stake, pool, amount, lock_days)
+            .into_iter()
+            .find(|(key, _)| *key == stake)
+            .unwrap();
+        accounts.push(position);
+    }
+    for stake in &stakes {
+        calculate_rewards(&mut accounts, stake, 10, 500);
+    }
+    (accounts, stakes)
+}
+
+#[test]
+fn test_claim_multiple_rewards_matches_individual_claims() {
+    let authority = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let (mut accounts, stakes) = batch_claim_fixture(authority, pool);
+
+    // Claim each position separately on a copy of the same state.
+    let mut individual = accounts.clone();
+    let mut individual_claims = Vec::new();
+    for stake in &stakes {
+        let before = account_of(&individual, &authority).lamports();
+        passive_stake(
+            &PassiveStakeInstruction::ClaimRewards,
+            &mut individual,
+            vec![
+                AccountMeta::new(authority, true),
+                AccountMeta::new(*stake, false),
+                AccountMeta::new(pool, false),
+            ],
+            Ok(()),
+        );
+        individual_claims.push(account_of(&individual, &authority).lamports() - before);
+    }
+
+    let return_data = passive_stake_with_return_data(
+        &PassiveStakeInstruction::ClaimMultipleRewards {
+            stake_accounts: stakes.clone(),
+        },
+        &mut accounts,
+        claim_multiple_metas(authority, pool, &stakes),
+        Ok(()),
+    );
+    let summary: ClaimSummary = bincode::deserialize(&return_data).unwrap();
+    assert_eq!(summary.per_account, individual_claims);
+    assert_eq!(
+        summary.total_lamports,
+        individual_claims.iter().sum::<u64>()
+    );
+    assert_eq!(
+        account_of(&accounts, &authority).lamports(),
+        account_of(&individual, &authority).lamports()
+    );
+    assert_eq!(
+        account_of(&accounts, &pool).lamports(),
+        1_000 * SOL - summary.total_lamports
+    );
+    for stake in &stakes {
+        assert_eq!(stake_state(&accounts, stake).unclaimed_rewards, 0);
+    }
+
+    // Everything has been claimed.
+    passive_stake(
+        &PassiveStakeInstruction::ClaimMultipleRewards {
+            stake_accounts: stakes.clone(),
+        },
+        &mut accounts,
+        claim_multiple_metas(authority, pool, &stakes),
+        Err(PassiveStakeError::NoRewardsToClaim.into()),
+    );
+}
+
+#[test]
+fn test_claim_multiple_rewards_authority_mismatch_aborts() {
+    let authority = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let (mut accounts, mut stakes) = batch_claim_fixture(authority, pool);
+
+    // A position belonging to someone else, in the middle of the batch.
+    let foreign = Pubkey::new_unique();
+    let foreign_position =
+        stake_fixture(Pubkey::new_unique(), foreign, pool, 10 * SOL, TIER_30_DAY)
+            .into_iter()
+            .find(|(key, _)| *key == foreign)
+            .unwrap();
+    accounts.push(foreign_position);
+    calculate_rewards(&mut accounts, &foreign, 10, 500);
+    stakes.insert(1, foreign);
+
+    let before = accounts.clone();
+    passive_stake(
+        &PassiveStakeInstruction::ClaimMultipleRewards {
+            stake_accounts: stakes.clone(),
+        },
+        &mut accounts,
+        claim_multiple_metas(authority, pool, &stakes),
+        Err(PassiveStakeError::AuthorityMismatch.into()),
+    );
+    assert_eq!(accounts, before);
+}
+
+#[test]
+fn test_claim_multiple_rewards_account_limit() {
+    let authority = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let (mut accounts, _stakes) = batch_claim_fixture(authority, pool);
+    let stakes: Vec<_> = (0..=MAX_BATCH_CLAIM_ACCOUNTS)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+
+    passive_stake(
+        &PassiveStakeInstruction::ClaimMultipleRewards {
+            stake_accounts: stakes.clone(),
+        },
+        &mut accounts,
+        claim_multiple_metas(authority, pool, &stakes),
+        Err(PassiveStakeError::TooManyAccounts.into()),
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 17. Processor: authority transfer
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn transfer_authority_metas(
+    authority: Pubkey,
+    new_authority: Pubkey,
+    stake: Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new(new_authority, true),
+        AccountMeta::new(stake, false),
+        AccountMeta::new(find_stake_summary_address(&authority).0, false),
+        AccountMeta::new(find_stake_summary_address(&new_authority).0, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]
+}
+
+fn claim_rewards_metas(authority: Pubkey, stake: Pubkey, pool: Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(authority, true),
+        AccountMeta::new(stake, false),
+        AccountMeta::new(pool, false),
+    ]
+}
+
+#[test]
+fn test_transfer_stake_authority_mid_lock() {
+    let authority = Pubkey::new_unique();
+    let new_authority = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let validator_rate = 500;
+    let mut accounts = stake_fixture(authority, stake, pool, 100 * SOL, TIER_90_DAY);
+    accounts.push((
+        new_authority,
+        AccountSharedData::new(SOL, 0, &system_program::id()),
+    ));
+    accounts.push(uncreated_stake_summary_account(new_authority));
+
+    calculate_rewards(&mut accounts, &stake, 10, validator_rate);
+    let before = stake_state(&accounts, &stake);
+    assert!(before.unclaimed_rewards > 0);
+
+    let transfer = PassiveStakeInstruction::TransferStakeAuthority {
+        stake_account: stake,
+        new_authority,
+    };
+    passive_stake(
+        &transfer,
+        &mut accounts,
+        transfer_authority_metas(authority, new_authority, stake),
+        Ok(()),
+    );
+
+    // The lock and accrued rewards carry over; the voting power only counts
+    // on proposals snapshotted from the transfer epoch on.
+    let after = stake_state(&accounts, &stake);
+    assert_eq!(
+        after,
+        PassiveStakeAccount {
+            authority: new_authority,
+            lock_start_epoch: 10,
+            ..before.clone()
+        }
+    );
+    assert_eq!(account_of(&accounts, &stake).lamports(), 100 * SOL);
+
+    // The principal now counts against the new authority's cap; its
+    // summary was created with the new authority paying rent.
+    assert_eq!(total_staked(&accounts, &authority), 0);
+    assert_eq!(total_staked(&accounts, &new_authority), 100 * SOL);
+    let summary_rent = Rent::default().minimum_balance(StakeSummaryAccount::SERIALIZED_SIZE);
+    assert_eq!(
+        account_of(&accounts, &new_authority).lamports(),
+        SOL - summary_rent
+    );
+
+    // The old key has lost control of the position.
+    passive_stake(
+        &transfer,
+        &mut accounts,
+        transfer_authority_metas(authority, new_authority, stake),
+        Err(PassiveStakeError::MissingAuthoritySignature.into()),
+    );
+    passive_stake(
+        &PassiveStakeInstruction::ClaimRewards,
+        &mut accounts,
+        claim_rewards_metas(authority, stake, pool),
+        Err(PassiveStakeError::MissingAuthoritySignature.into()),
+    );
+
+    // Rewards keep accruing at the same tier, and the new key claims them.
+    calculate_rewards(&mut accounts, &stake, 20, validator_rate);
+    let rewards = stake_state(&accounts, &stake).unclaimed_rewards;
+    assert_eq!(
+        rewards,
+        before.unclaimed_rewards
+            + 10 * compute_epoch_reward(100 * SOL, validator_rate, TIER_90_DAY)
+    );
+    passive_stake(
+        &PassiveStakeInstruction::ClaimRewards,
+        &mut accounts,
+        claim_rewards_metas(new_authority, stake, pool),
+        Ok(()),
+    );
+    assert_eq!(
+        account_of(&accounts, &new_authority).lamports(),
+        SOL - summary_rent + rewards
+    );
+    assert_eq!(account_of(&accounts, &authority).lamports(), 100 * SOL);
+    assert_eq!(stake_state(&accounts, &stake).unclaimed_rewards, 0);
+}
+
+#[test]
+fn test_transfer_stake_authority_requires_both_signatures() {
+    let authority = Pubkey::new_unique();
+    let new_authority = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let mut accounts = stake_fixture(authority, stake, pool, 10 * SOL, TIER_30_DAY);
+    accounts.push((
+        new_authority,
+        AccountSharedData::new(SOL, 0, &system_program::id()),
+    ));
+    accounts.push(uncreated_stake_summary_account(new_authority));
+    let transfer = PassiveStakeInstruction::TransferStakeAuthority {
+        stake_account: stake,
+        new_authority,
+    };
+
+    // The new key has to sign, so a position can't be sent to a typo.
+    let mut metas = transfer_authority_metas(authority, new_authority, stake);
+    metas[1].is_signer = false;
+    passive_stake(
+        &transfer,
+        &mut accounts,
+        metas,
+        Err(PassiveStakeError::MissingAuthoritySignature.into()),
+    );
+
+    let mut metas = transfer_authority_metas(authority, new_authority, stake);
+    metas[0].is_signer = false;
+    passive_stake(
+        &transfer,
+        &mut accounts,
+        metas,
+        Err(PassiveStakeError::MissingAuthoritySignature.into()),
+    );
+
+    // A signer other than the one named in the instruction data.
+    let impostor = Pubkey::new_unique();
+    accounts.push((
+        impostor,
+        AccountSharedData::new(SOL, 0, &system_program::id()),
+    ));
+    accounts.push(uncreated_stake_summary_account(impostor));
+    passive_stake(
+        &transfer,
+        &mut accounts,
+        transfer_authority_metas(authority, impostor, stake),
+        Err(InstructionError::InvalidArgument),
+    );
+
+    assert_eq!(stake_state(&accounts, &stake).authority, authority);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 18. Processor: per-address stake cap
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn initialize_metas(authority: Pubkey, stake: Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(authority, true),
+        AccountMeta::new(stake, false),
+        AccountMeta::new(find_stake_summary_address(&authority).0, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]
+}
+
+#[test]
+fn test_max_stake_per_address() {
+    let authority = Pubkey::new_unique();
+    let stakes = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let mut program_account = AccountSharedData::new(1, 0, &native_loader::id());
+    program_account.set_executable(true);
+    let mut accounts = vec![
+        (
+            authority,
+            AccountSharedData::new(MAX_STAKE_PER_ADDRESS + 10 * SOL, 0, &system_program::id()),
+        ),
+        uncreated_stake_summary_account(authority),
+        (
+            sysvar::clock::id(),
+            account::create_account_shared_data_for_test(&Clock::default()),
+        ),
+        (
+            sysvar::rent::id(),
+            account::create_account_shared_data_for_test(&Rent::default()),
+        ),
+        (system_program::id(), program_account),
+    ];
+    for stake in stakes {
+        accounts.push((
+            stake,
+            AccountSharedData::new(
+                0,
+                PassiveStakeAccount::SERIALIZED_SIZE,
+                &solana_passive_stake_program::id(),
+            ),
+        ));
+    }
+    let initialize = |amount| PassiveStakeInstruction::InitializePassiveStake {
+        lock_days: TIER_NO_LOCK,
+        amount,
+    };
+
+    // Stake up to exactly the cap across two positions; the first deposit
+    // creates the summary.
+    passive_stake(
+        &initialize(MAX_STAKE_PER_ADDRESS - SOL),
+        &mut accounts,
+        initialize_metas(authority, stakes[0]),
+        Ok(()),
+    );
+    assert_eq!(
+        total_staked(&accounts, &authority),
+        MAX_STAKE_PER_ADDRESS - SOL
+    );
+    passive_stake(
+        &initialize(SOL),
+        &mut accounts,
+        initialize_metas(authority, stakes[1]),
+        Ok(()),
+    );
+    assert_eq!(total_staked(&accounts, &authority), MAX_STAKE_PER_ADDRESS);
+
+    // One more lamport, as a new position or a top-up, is over the cap.
+    let before = accounts.clone();
+    passive_stake(
+        &initialize(1),
+        &mut accounts,
+        initialize_metas(authority, stakes[2]),
+        Err(PassiveStakeError::MaxStakeExceeded.into()),
+    );
+    passive_stake(
+        &PassiveStakeInstruction::TopUpStake {
+            stake_account: stakes[0],
+            additional_lamports: constants::MIN_TOP_UP_AMOUNT,
+        },
+        &mut accounts,
+        top_up_metas(authority, stakes[0]),
+        Err(PassiveStakeError::MaxStakeExceeded.into()),
+    );
+    assert_eq!(accounts, before);
+
+    // Unlocking a position frees its headroom.
+    passive_stake(
+        &PassiveStakeInstruction::Unlock,
+        &mut accounts,
+        vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(stakes[1], false),
+            AccountMeta::new(find_stake_summary_address(&authority).0, false),
+        ],
+        Ok(()),
+    );
+    assert_eq!(
+        total_staked(&accounts, &authority),
+        MAX_STAKE_PER_ADDRESS - SOL
+    );
+    passive_stake(
+        &initialize(SOL),
+        &mut accounts,
+        initialize_metas(authority, stakes[2]),
+        Ok(()),
+    );
+    assert_eq!(total_staked(&accounts, &authority), MAX_STAKE_PER_ADDRESS);
+    assert_eq!(stake_state(&accounts, &stakes[2]).amount, SOL);
+}
+
+#[test]
+fn test_transfer_stake_authority_rejects_new_authority_with_stake() {
+    let authority = Pubkey::new_unique();
+    let new_authority = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let mut accounts = stake_fixture(authority, stake, pool, 10 * SOL, TIER_30_DAY);
+    accounts.push((
+        new_authority,
+        AccountSharedData::new(SOL, 0, &system_program::id()),
+    ));
+    accounts.push(stake_summary_account(new_authority, 1));
+    let transfer = PassiveStakeInstruction::TransferStakeAuthority {
+        stake_account: stake,
+        new_authority,
+    };
+
+    // Even a single staked lamport means the position would be merged.
+    passive_stake(
+        &transfer,
+        &mut accounts,
+        transfer_authority_metas(authority, new_authority, stake),
+        Err(PassiveStakeError::NewAuthorityHasStake.into()),
+    );
+    assert_eq!(stake_state(&accounts, &stake).authority, authority);
+    assert_eq!(total_staked(&accounts, &authority), 10 * SOL);
+    assert_eq!(total_staked(&accounts, &new_authority), 1);
+
+    // An authority whose positions have all been withdrawn can take it.
+    let summary = find_stake_summary_address(&new_authority).0;
+    accounts.retain(|(key, _)| *key != summary);
+    accounts.push(stake_summary_account(new_authority, 0));
+    passive_stake(
+        &transfer,
+        &mut accounts,
+        transfer_authority_metas(authority, new_authority, stake),
+        Ok(()),
+    );
+    assert_eq!(stake_state(&accounts, &stake).authority, new_authority);
+    assert_eq!(total_staked(&accounts, &new_authority), 10 * SOL);
+}
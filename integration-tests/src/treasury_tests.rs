@@ -1,18 +1,27 @@
 //! Integration tests for TRv1 Treasury program.
 //!
-//! Tests initialization, disbursements, authority transitions, and governance
-//! activation.
+//! Tests initialization, disbursements, authority transitions, governance
+//! activation, multisig authorities, recurring disbursements and the epoch
+//! allowance (driven through the treasury processor).
 
 use {
     crate::harness::{SOL, TRv1TestHarness},
+    solana_account::{self as account, AccountSharedData, ReadableAccount},
+    solana_clock::Clock,
+    solana_instruction::{error::InstructionError, AccountMeta},
     solana_keypair::Keypair,
+    solana_program_runtime::invoke_context::mock_process_instruction,
     solana_pubkey::Pubkey,
+    solana_sdk_ids::sysvar,
     solana_signer::Signer,
     solana_treasury_program::{
         error::TreasuryError,
         instruction::TreasuryInstruction,
         processor::MAX_MEMO_LEN,
-        state::{TreasuryConfig, TREASURY_CONFIG_DISCRIMINATOR},
+        state::{
+            MultisigAuthority, PendingDisbursal, RecurringDisbursement, TreasuryConfig,
+            DEFAULT_EPOCH_ALLOWANCE_BPS, TREASURY_CONFIG_DISCRIMINATOR,
+        },
     },
 };
 
@@ -32,6 +41,9 @@ fn test_treasury_config_creation() {
         total_received: 0,
         total_disbursed: 0,
         last_updated_epoch: 0,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: 0,
     };
 
     assert_eq!(config.authority, authority);
@@ -50,6 +62,9 @@ fn test_treasury_config_serialization_roundtrip() {
         total_received: 42 * SOL,
         total_disbursed: 10 * SOL,
         last_updated_epoch: 100,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: 0,
     };
 
     let mut buf = vec![0u8; TreasuryConfig::SERIALIZED_SIZE];
@@ -61,8 +76,8 @@ fn test_treasury_config_serialization_roundtrip() {
 
 #[test]
 fn test_treasury_config_serialized_size() {
-    // 1 + 32 + 32 + 1 + 8 + 8 + 8 = 90 bytes
-    assert_eq!(TreasuryConfig::SERIALIZED_SIZE, 90);
+    // 1 + 32 + 32 + 1 + 8 + 8 + 8 + 2 + 8 + 8 = 108 bytes
+    assert_eq!(TreasuryConfig::SERIALIZED_SIZE, 108);
 }
 
 #[test]
@@ -84,6 +99,9 @@ fn test_double_initialization_prevented() {
         total_received: 0,
         total_disbursed: 0,
         last_updated_epoch: 0,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: 0,
     };
 
     let mut buf = vec![0u8; TreasuryConfig::SERIALIZED_SIZE];
@@ -110,6 +128,9 @@ fn test_disburse_updates_tracking() {
         total_received: 1000 * SOL,
         total_disbursed: 0,
         last_updated_epoch: 0,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: 0,
     };
 
     let disburse_amount = 100 * SOL;
@@ -132,6 +153,9 @@ fn test_multiple_disbursements_accumulate() {
         total_received: 1000 * SOL,
         total_disbursed: 0,
         last_updated_epoch: 0,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: 0,
     };
 
     for i in 1..=5 {
@@ -203,6 +227,9 @@ fn test_update_authority() {
         total_received: 0,
         total_disbursed: 0,
         last_updated_epoch: 0,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: 0,
     };
 
     assert_eq!(config.authority, multisig_authority);
@@ -225,6 +252,9 @@ fn test_unauthorized_authority_update_fails() {
         total_received: 0,
         total_disbursed: 0,
         last_updated_epoch: 0,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: 0,
     };
 
     let attacker = Pubkey::new_unique();
@@ -246,6 +276,9 @@ fn test_authority_transition_preserves_state() {
         total_received: 500 * SOL,
         total_disbursed: 100 * SOL,
         last_updated_epoch: 50,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: 0,
     };
 
     let treasury_account_before = config.treasury_account;
@@ -276,6 +309,9 @@ fn test_unauthorized_disburse_rejected() {
         total_received: 1000 * SOL,
         total_disbursed: 0,
         last_updated_epoch: 0,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: 0,
     };
 
     let unauthorized_signer = Pubkey::new_unique();
@@ -309,6 +345,9 @@ fn test_activate_governance() {
         total_received: 500 * SOL,
         total_disbursed: 100 * SOL,
         last_updated_epoch: 100,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: 0,
     };
 
     assert!(!config.governance_active);
@@ -328,6 +367,9 @@ fn test_governance_activation_is_one_way() {
         total_received: 0,
         total_disbursed: 0,
         last_updated_epoch: 0,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: 0,
     };
 
     // Attempting to activate again should be rejected with GovernanceAlreadyActive
@@ -350,6 +392,9 @@ fn test_governance_preserves_authority_control() {
         total_received: 0,
         total_disbursed: 0,
         last_updated_epoch: 0,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: 0,
     };
 
     config.governance_active = true;
@@ -371,6 +416,9 @@ fn test_full_lifecycle_multisig_to_governance() {
         total_received: 0,
         total_disbursed: 0,
         last_updated_epoch: 0,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: 0,
     };
 
     // 2. Multisig makes disbursements
@@ -462,3 +510,981 @@ fn test_activate_governance_instruction_construction() {
         "Should be ActivateGovernance variant"
     );
 }
+
+// ═══════════════════════════════════════════════════════════════════════════
+//  7. Multisig authority
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Run `instruction` through the treasury processor and write the resulting
+/// account state back into `accounts`.
+fn process(
+    instruction: &TreasuryInstruction,
+    accounts: &mut [(Pubkey, AccountSharedData)],
+    metas: Vec<AccountMeta>,
+    expected_result: Result<(), InstructionError>,
+) {
+    let after = mock_process_instruction(
+        &solana_treasury_program::id(),
+        None,
+        &bincode::serialize(instruction).unwrap(),
+        accounts.to_vec(),
+        metas,
+        expected_result,
+        solana_treasury_program::processor::Entrypoint::vm,
+        |_invoke_context| {},
+        |_invoke_context| {},
+    );
+    for ((_, account), updated) in accounts.iter_mut().zip(after) {
+        *account = updated;
+    }
+}
+
+fn custom(error: TreasuryError) -> InstructionError {
+    InstructionError::Custom(error as u32)
+}
+
+fn lamports(accounts: &[(Pubkey, AccountSharedData)], key: &Pubkey) -> u64 {
+    accounts
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, account)| account.lamports())
+        .unwrap()
+}
+
+fn data<'a>(accounts: &'a [(Pubkey, AccountSharedData)], key: &Pubkey) -> &'a [u8] {
+    accounts
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, account)| account.data())
+        .unwrap()
+}
+
+/// A treasury controlled by a single key, and an uninitialised multisig
+/// account with five prospective signers.
+struct MultisigTreasury {
+    authority: Pubkey,
+    config: Pubkey,
+    treasury: Pubkey,
+    recipient: Pubkey,
+    multisig: Pubkey,
+    signers: [Pubkey; 5],
+    accounts: Vec<(Pubkey, AccountSharedData)>,
+}
+
+impl MultisigTreasury {
+    fn new(treasury_lamports: u64) -> Self {
+        let authority = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let multisig = Pubkey::new_unique();
+        let signers = [(); 5].map(|_| Pubkey::new_unique());
+
+        let program_id = solana_treasury_program::id();
+        let mut config_account =
+            AccountSharedData::new(1, TreasuryConfig::SERIALIZED_SIZE, &program_id);
+        let mut data = vec![0; TreasuryConfig::SERIALIZED_SIZE];
+        TreasuryConfig {
+            authority,
+            treasury_account: treasury,
+            governance_active: false,
+            total_received: treasury_lamports,
+            total_disbursed: 0,
+            last_updated_epoch: 0,
+            epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+            disbursed_this_epoch: 0,
+            last_disbursement_epoch: 0,
+        }
+        .serialize_into(&mut data)
+        .unwrap();
+        config_account.set_data_from_slice(&data);
+
+        let mut accounts = vec![
+            (authority, AccountSharedData::new(1, 0, &Pubkey::default())),
+            (config, config_account),
+            (
+                treasury,
+                AccountSharedData::new(treasury_lamports, 0, &program_id),
+            ),
+            (recipient, AccountSharedData::new(0, 0, &Pubkey::default())),
+            (
+                multisig,
+                AccountSharedData::new(1, MultisigAuthority::SERIALIZED_SIZE, &program_id),
+            ),
+            (
+                sysvar::clock::id(),
+                account::create_account_shared_data_for_test(&Clock::default()),
+            ),
+        ];
+        for signer in signers {
+            accounts.push((signer, AccountSharedData::new(1, 0, &Pubkey::default())));
+        }
+        Self {
+            authority,
+            config,
+            treasury,
+            recipient,
+            multisig,
+            signers,
+            accounts,
+        }
+    }
+
+    /// Create a 3-of-5 multisig and make it the treasury authority.
+    fn hand_over_to_multisig(&mut self) {
+        process(
+            &TreasuryInstruction::InitializeMultisig {
+                signers: self.signers.to_vec(),
+                threshold: 3,
+            },
+            &mut self.accounts,
+            vec![
+                AccountMeta::new_readonly(self.signers[0], true),
+                AccountMeta::new(self.multisig, false),
+            ],
+            Ok(()),
+        );
+        process(
+            &TreasuryInstruction::UpdateAuthority {
+                new_authority: self.multisig,
+            },
+            &mut self.accounts,
+            vec![
+                AccountMeta::new_readonly(self.authority, true),
+                AccountMeta::new(self.config, false),
+            ],
+            Ok(()),
+        );
+    }
+
+    fn config(&self) -> TreasuryConfig {
+        TreasuryConfig::deserialize(data(&self.accounts, &self.config)).unwrap()
+    }
+}
+
+#[test]
+fn test_multisig_replaces_single_key_authority() {
+    let mut fixture = MultisigTreasury::new(1_000 * SOL);
+
+    // The multisig itself is validated.
+    for (signers, threshold) in [
+        (fixture.signers.to_vec(), 0),
+        (fixture.signers.to_vec(), 6),
+        (vec![fixture.signers[0], fixture.signers[0]], 1),
+    ] {
+        process(
+            &TreasuryInstruction::InitializeMultisig { signers, threshold },
+            &mut fixture.accounts,
+            vec![
+                AccountMeta::new_readonly(fixture.signers[0], true),
+                AccountMeta::new(fixture.multisig, false),
+            ],
+            Err(custom(TreasuryError::InvalidMultisig)),
+        );
+    }
+
+    fixture.hand_over_to_multisig();
+    let multisig =
+        MultisigAuthority::deserialize(data(&fixture.accounts, &fixture.multisig)).unwrap();
+    assert_eq!(multisig.signers, fixture.signers.to_vec());
+    assert_eq!(multisig.threshold, 3);
+    assert_eq!(fixture.config().authority, fixture.multisig);
+
+    // The old key has lost control.
+    let disburse = TreasuryInstruction::Disburse {
+        amount: 10 * SOL,
+        recipient: fixture.recipient,
+        memo: "grant".to_string(),
+    };
+    process(
+        &disburse,
+        &mut fixture.accounts,
+        vec![
+            AccountMeta::new_readonly(fixture.authority, true),
+            AccountMeta::new(fixture.config, false),
+            AccountMeta::new(fixture.treasury, false),
+            AccountMeta::new(fixture.recipient, false),
+        ],
+        Err(custom(TreasuryError::AuthorityMismatch)),
+    );
+
+    // The multisig can hand control on, e.g. to governance.
+    let governance = Pubkey::new_unique();
+    let update = TreasuryInstruction::UpdateAuthority {
+        new_authority: governance,
+    };
+    let mut metas = vec![
+        AccountMeta::new_readonly(fixture.multisig, false),
+        AccountMeta::new(fixture.config, false),
+    ];
+    metas.extend(
+        fixture.signers[2..]
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, true)),
+    );
+    process(&update, &mut fixture.accounts, metas, Ok(()));
+    assert_eq!(fixture.config().authority, governance);
+}
+
+#[test]
+fn test_multisig_disburse_requires_threshold_signatures() {
+    let mut fixture = MultisigTreasury::new(1_000 * SOL);
+    fixture.hand_over_to_multisig();
+
+    let disburse = TreasuryInstruction::Disburse {
+        amount: 10 * SOL,
+        recipient: fixture.recipient,
+        memo: "grant".to_string(),
+    };
+    let metas = |signers: &[Pubkey]| {
+        let mut metas = vec![
+            AccountMeta::new_readonly(fixture.multisig, false),
+            AccountMeta::new(fixture.config, false),
+            AccountMeta::new(fixture.treasury, false),
+            AccountMeta::new(fixture.recipient, false),
+        ];
+        metas.extend(
+            signers
+                .iter()
+                .map(|signer| AccountMeta::new_readonly(*signer, true)),
+        );
+        metas
+    };
+
+    // Two signatures, a repeated signature, or an outsider's are not enough.
+    let outsider = fixture.authority;
+    for signers in [
+        &fixture.signers[..2],
+        &[fixture.signers[0], fixture.signers[1], fixture.signers[1]][..],
+        &[fixture.signers[0], fixture.signers[1], outsider][..],
+    ] {
+        process(
+            &disburse,
+            &mut fixture.accounts,
+            metas(signers),
+            Err(custom(TreasuryError::InsufficientSignatures)),
+        );
+    }
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 0);
+
+    process(
+        &disburse,
+        &mut fixture.accounts,
+        metas(&fixture.signers[1..4]),
+        Ok(()),
+    );
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 10 * SOL);
+    assert_eq!(lamports(&fixture.accounts, &fixture.treasury), 990 * SOL);
+    assert_eq!(fixture.config().total_disbursed, 10 * SOL);
+}
+
+#[test]
+fn test_multisig_own_signature_is_not_authority() {
+    let mut fixture = MultisigTreasury::new(1_000 * SOL);
+    fixture.hand_over_to_multisig();
+
+    let disburse = TreasuryInstruction::Disburse {
+        amount: 10 * SOL,
+        recipient: fixture.recipient,
+        memo: "grant".to_string(),
+    };
+    // Whoever holds the multisig account's key still needs the members'
+    // signatures, and signing a second time as a member counts for nothing.
+    for extra in [
+        vec![],
+        vec![fixture.multisig],
+        fixture.signers[..2].to_vec(),
+    ] {
+        let mut metas = vec![
+            AccountMeta::new_readonly(fixture.multisig, true),
+            AccountMeta::new(fixture.config, false),
+            AccountMeta::new(fixture.treasury, false),
+            AccountMeta::new(fixture.recipient, false),
+        ];
+        metas.extend(
+            extra
+                .iter()
+                .map(|signer| AccountMeta::new_readonly(*signer, true)),
+        );
+        process(
+            &disburse,
+            &mut fixture.accounts,
+            metas,
+            Err(custom(TreasuryError::InsufficientSignatures)),
+        );
+    }
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 0);
+}
+
+#[test]
+fn test_initialize_rejects_account_of_another_type() {
+    let mut fixture = MultisigTreasury::new(1_000 * SOL);
+    fixture.hand_over_to_multisig();
+
+    // Neither the treasury config nor the multisig can be overwritten with
+    // a new multisig or a pending disbursal.
+    for target in [fixture.config, fixture.multisig] {
+        process(
+            &TreasuryInstruction::InitializeMultisig {
+                signers: fixture.signers.to_vec(),
+                threshold: 1,
+            },
+            &mut fixture.accounts,
+            vec![
+                AccountMeta::new_readonly(fixture.signers[0], true),
+                AccountMeta::new(target, false),
+            ],
+            Err(custom(TreasuryError::AlreadyInitialized)),
+        );
+        process(
+            &TreasuryInstruction::ProposeDisbursal {
+                disbursal_id: 1,
+                amount: 10 * SOL,
+                recipient: fixture.recipient,
+                memo: String::new(),
+            },
+            &mut fixture.accounts,
+            vec![
+                AccountMeta::new_readonly(fixture.signers[0], true),
+                AccountMeta::new_readonly(fixture.multisig, false),
+                AccountMeta::new(target, false),
+            ],
+            Err(custom(TreasuryError::AlreadyInitialized)),
+        );
+    }
+    assert_eq!(fixture.config().authority, fixture.multisig);
+}
+
+#[test]
+fn test_multisig_two_phase_disbursal() {
+    let mut fixture = MultisigTreasury::new(1_000 * SOL);
+    fixture.hand_over_to_multisig();
+    let pending = Pubkey::new_unique();
+    fixture.accounts.push((
+        pending,
+        AccountSharedData::new(
+            1,
+            PendingDisbursal::SERIALIZED_SIZE,
+            &solana_treasury_program::id(),
+        ),
+    ));
+
+    let signer_metas = |signer: Pubkey| {
+        vec![
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new_readonly(fixture.multisig, false),
+            AccountMeta::new(pending, false),
+        ]
+    };
+    let propose = TreasuryInstruction::ProposeDisbursal {
+        disbursal_id: 7,
+        amount: 25 * SOL,
+        recipient: fixture.recipient,
+        memo: "operations".to_string(),
+    };
+    process(
+        &propose,
+        &mut fixture.accounts,
+        signer_metas(fixture.authority),
+        Err(custom(TreasuryError::NotMultisigSigner)),
+    );
+    process(
+        &propose,
+        &mut fixture.accounts,
+        signer_metas(fixture.signers[0]),
+        Ok(()),
+    );
+
+    let sign = TreasuryInstruction::SignDisbursal { disbursal_id: 7 };
+    process(
+        &sign,
+        &mut fixture.accounts,
+        signer_metas(fixture.signers[0]),
+        Err(custom(TreasuryError::DisbursalAlreadySigned)),
+    );
+    process(
+        &TreasuryInstruction::SignDisbursal { disbursal_id: 8 },
+        &mut fixture.accounts,
+        signer_metas(fixture.signers[1]),
+        Err(custom(TreasuryError::DisbursalMismatch)),
+    );
+    process(
+        &sign,
+        &mut fixture.accounts,
+        signer_metas(fixture.signers[1]),
+        Ok(()),
+    );
+
+    // Anyone can execute, but only once three signers have approved.
+    let executor = fixture.authority;
+    let execute = TreasuryInstruction::ExecuteDisbursal { disbursal_id: 7 };
+    let execute_metas = vec![
+        AccountMeta::new_readonly(executor, true),
+        AccountMeta::new(fixture.config, false),
+        AccountMeta::new(fixture.treasury, false),
+        AccountMeta::new(fixture.recipient, false),
+        AccountMeta::new_readonly(fixture.multisig, false),
+        AccountMeta::new(pending, false),
+    ];
+    process(
+        &execute,
+        &mut fixture.accounts,
+        execute_metas.clone(),
+        Err(custom(TreasuryError::InsufficientSignatures)),
+    );
+
+    process(
+        &sign,
+        &mut fixture.accounts,
+        signer_metas(fixture.signers[4]),
+        Ok(()),
+    );
+    process(
+        &execute,
+        &mut fixture.accounts,
+        execute_metas.clone(),
+        Ok(()),
+    );
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 25 * SOL);
+    assert_eq!(lamports(&fixture.accounts, &fixture.treasury), 975 * SOL);
+
+    let disbursal = PendingDisbursal::deserialize(data(&fixture.accounts, &pending)).unwrap();
+    assert!(disbursal.executed);
+    assert_eq!(
+        disbursal.approvals,
+        vec![fixture.signers[0], fixture.signers[1], fixture.signers[4]]
+    );
+
+    process(
+        &execute,
+        &mut fixture.accounts,
+        execute_metas,
+        Err(custom(TreasuryError::DisbursalAlreadyExecuted)),
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+//  8. Recurring disbursements
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn set_epoch(accounts: &mut [(Pubkey, AccountSharedData)], epoch: u64) {
+    let clock = Clock {
+        epoch,
+        ..Clock::default()
+    };
+    for (key, account) in accounts.iter_mut() {
+        if *key == sysvar::clock::id() {
+            *account = account::create_account_shared_data_for_test(&clock);
+        }
+    }
+}
+
+impl MultisigTreasury {
+    /// Add an uninitialised recurring disbursement account and create a
+    /// grant in it, signed by the single-key authority.
+    fn create_recurring(
+        &mut self,
+        amount_per_epoch: u64,
+        start_epoch: u64,
+        end_epoch: u64,
+    ) -> Pubkey {
+        let recurring = Pubkey::new_unique();
+        self.accounts.push((
+            recurring,
+            AccountSharedData::new(
+                1,
+                RecurringDisbursement::SERIALIZED_SIZE,
+                &solana_treasury_program::id(),
+            ),
+        ));
+        let metas = self.authority_metas(recurring);
+        process(
+            &TreasuryInstruction::CreateRecurringDisbursement {
+                disbursement_id: 1,
+                recipient: self.recipient,
+                amount_per_epoch,
+                start_epoch,
+                end_epoch,
+            },
+            &mut self.accounts,
+            metas,
+            Ok(()),
+        );
+        recurring
+    }
+
+    /// Accounts for `CreateRecurringDisbursement`.
+    fn authority_metas(&self, recurring: Pubkey) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.treasury, false),
+            AccountMeta::new(recurring, false),
+        ]
+    }
+
+    /// Accounts for `CancelRecurring`.
+    fn cancel_metas(&self, recurring: Pubkey) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.config, false),
+            AccountMeta::new(self.treasury, false),
+            AccountMeta::new(recurring, false),
+            AccountMeta::new(self.recipient, false),
+        ]
+    }
+
+    fn execute_recurring(
+        &mut self,
+        recurring: Pubkey,
+        expected_result: Result<(), InstructionError>,
+    ) {
+        let caller = self.signers[0];
+        process(
+            &TreasuryInstruction::ExecuteRecurring { disbursement_id: 1 },
+            &mut self.accounts,
+            vec![
+                AccountMeta::new_readonly(caller, true),
+                AccountMeta::new(self.config, false),
+                AccountMeta::new(recurring, false),
+                AccountMeta::new(self.recipient, false),
+            ],
+            expected_result,
+        );
+    }
+}
+
+#[test]
+fn test_recurring_grant_paid_every_epoch() {
+    let mut fixture = MultisigTreasury::new(2_000 * SOL);
+    let recurring = fixture.create_recurring(10 * SOL, 0, 12);
+
+    // The whole 12-epoch grant is escrowed up front.
+    assert_eq!(lamports(&fixture.accounts, &fixture.treasury), 1_880 * SOL);
+    assert_eq!(lamports(&fixture.accounts, &recurring), 1 + 120 * SOL);
+    fixture.execute_recurring(recurring, Err(custom(TreasuryError::RecurringNotDue)));
+
+    for epoch in 1..=12 {
+        // Nobody executes in epoch 5; epoch 6 catches up.
+        if epoch == 5 {
+            continue;
+        }
+        set_epoch(&mut fixture.accounts, epoch);
+        fixture.execute_recurring(recurring, Ok(()));
+        assert_eq!(
+            lamports(&fixture.accounts, &fixture.recipient),
+            epoch * 10 * SOL
+        );
+        fixture.execute_recurring(recurring, Err(custom(TreasuryError::RecurringNotDue)));
+    }
+
+    assert_eq!(lamports(&fixture.accounts, &recurring), 1);
+    assert_eq!(fixture.config().total_disbursed, 120 * SOL);
+    let disbursement =
+        RecurringDisbursement::deserialize(data(&fixture.accounts, &recurring)).unwrap();
+    assert_eq!(disbursement.last_disbursed_epoch, 12);
+
+    // Only the authority can set up a grant, and the schedule must be sane.
+    let other = Pubkey::new_unique();
+    fixture.accounts.push((
+        other,
+        AccountSharedData::new(
+            1,
+            RecurringDisbursement::SERIALIZED_SIZE,
+            &solana_treasury_program::id(),
+        ),
+    ));
+    let create = |start_epoch, end_epoch| TreasuryInstruction::CreateRecurringDisbursement {
+        disbursement_id: 2,
+        recipient: fixture.recipient,
+        amount_per_epoch: SOL,
+        start_epoch,
+        end_epoch,
+    };
+    let metas = fixture.authority_metas(other);
+    let mut outsider_metas = metas.clone();
+    outsider_metas[0] = AccountMeta::new_readonly(fixture.signers[0], true);
+    process(
+        &create(12, 24),
+        &mut fixture.accounts,
+        outsider_metas,
+        Err(custom(TreasuryError::AuthorityMismatch)),
+    );
+    process(
+        &create(12, 12),
+        &mut fixture.accounts,
+        metas,
+        Err(custom(TreasuryError::InvalidSchedule)),
+    );
+}
+
+#[test]
+fn test_recurring_cancel_refunds_remaining_balance() {
+    let mut fixture = MultisigTreasury::new(2_000 * SOL);
+    let recurring = fixture.create_recurring(10 * SOL, 0, 12);
+
+    set_epoch(&mut fixture.accounts, 3);
+    fixture.execute_recurring(recurring, Ok(()));
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 30 * SOL);
+
+    // Epochs 4 and 5 accrue but are never executed; cancelling in epoch 5
+    // pays them to the recipient and returns the remaining seven epochs.
+    set_epoch(&mut fixture.accounts, 5);
+    let cancel = TreasuryInstruction::CancelRecurring { disbursement_id: 1 };
+    let metas = fixture.cancel_metas(recurring);
+    let mut outsider_metas = metas.clone();
+    outsider_metas[0] = AccountMeta::new_readonly(fixture.signers[0], true);
+    process(
+        &cancel,
+        &mut fixture.accounts,
+        outsider_metas,
+        Err(custom(TreasuryError::AuthorityMismatch)),
+    );
+    let mut wrong_recipient = metas.clone();
+    wrong_recipient[4] = AccountMeta::new(fixture.treasury, false);
+    process(
+        &cancel,
+        &mut fixture.accounts,
+        wrong_recipient,
+        Err(custom(TreasuryError::RecipientMismatch)),
+    );
+    process(&cancel, &mut fixture.accounts, metas.clone(), Ok(()));
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 50 * SOL);
+    assert_eq!(lamports(&fixture.accounts, &fixture.treasury), 1_950 * SOL);
+    assert_eq!(lamports(&fixture.accounts, &recurring), 1);
+    assert_eq!(fixture.config().total_disbursed, 50 * SOL);
+
+    fixture.execute_recurring(recurring, Err(custom(TreasuryError::RecurringEnded)));
+    process(
+        &cancel,
+        &mut fixture.accounts,
+        metas,
+        Err(custom(TreasuryError::RecurringEnded)),
+    );
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 50 * SOL);
+}
+
+#[test]
+fn test_recurring_execution_after_end_epoch_fails() {
+    let mut fixture = MultisigTreasury::new(1_000 * SOL);
+    let recurring = fixture.create_recurring(10 * SOL, 1, 4);
+
+    set_epoch(&mut fixture.accounts, 4);
+    fixture.execute_recurring(recurring, Ok(()));
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 30 * SOL);
+
+    set_epoch(&mut fixture.accounts, 5);
+    fixture.execute_recurring(recurring, Err(custom(TreasuryError::RecurringEnded)));
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 30 * SOL);
+}
+
+#[test]
+fn test_recurring_unclaimed_epochs_expire_at_end_epoch() {
+    let mut fixture = MultisigTreasury::new(1_000 * SOL);
+    let recurring = fixture.create_recurring(10 * SOL, 1, 4);
+
+    // Nobody executed before the grant ended: nothing more is paid, and
+    // cancelling returns the whole escrow to the treasury.
+    set_epoch(&mut fixture.accounts, 5);
+    fixture.execute_recurring(recurring, Err(custom(TreasuryError::RecurringEnded)));
+    process(
+        &TreasuryInstruction::CancelRecurring { disbursement_id: 1 },
+        &mut fixture.accounts,
+        fixture.cancel_metas(recurring),
+        Ok(()),
+    );
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 0);
+    assert_eq!(lamports(&fixture.accounts, &fixture.treasury), 1_000 * SOL);
+
+    // A grant cannot be created for epochs that are already over.
+    let metas = fixture.authority_metas(recurring);
+    process(
+        &TreasuryInstruction::CreateRecurringDisbursement {
+            disbursement_id: 2,
+            recipient: fixture.recipient,
+            amount_per_epoch: SOL,
+            start_epoch: 1,
+            end_epoch: 5,
+        },
+        &mut fixture.accounts,
+        metas,
+        Err(custom(TreasuryError::InvalidSchedule)),
+    );
+}
+
+#[test]
+fn test_recurring_escrow_cannot_fund_other_disbursements() {
+    let mut fixture = MultisigTreasury::new(1_000 * SOL);
+    let recurring = fixture.create_recurring(10 * SOL, 0, 6);
+    assert_eq!(lamports(&fixture.accounts, &recurring), 1 + 60 * SOL);
+
+    // The escrow is owned by the treasury program, but only the configured
+    // treasury account may be the source of a `Disburse`.
+    let mut metas = fixture.disburse_metas();
+    metas[2] = AccountMeta::new(recurring, false);
+    process(
+        &TreasuryInstruction::Disburse {
+            amount: 60 * SOL,
+            recipient: fixture.recipient,
+            memo: "drain".to_string(),
+        },
+        &mut fixture.accounts,
+        metas,
+        Err(custom(TreasuryError::InvalidAccountData)),
+    );
+
+    // Nor the source of a multisig-approved disbursal.
+    fixture.hand_over_to_multisig();
+    let pending = Pubkey::new_unique();
+    fixture.accounts.push((
+        pending,
+        AccountSharedData::new(
+            1,
+            PendingDisbursal::SERIALIZED_SIZE,
+            &solana_treasury_program::id(),
+        ),
+    ));
+    let signer_metas = |signer: Pubkey| {
+        vec![
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new_readonly(fixture.multisig, false),
+            AccountMeta::new(pending, false),
+        ]
+    };
+    process(
+        &TreasuryInstruction::ProposeDisbursal {
+            disbursal_id: 1,
+            amount: 60 * SOL,
+            recipient: fixture.recipient,
+            memo: "drain".to_string(),
+        },
+        &mut fixture.accounts,
+        signer_metas(fixture.signers[0]),
+        Ok(()),
+    );
+    for signer in &fixture.signers[1..3] {
+        process(
+            &TreasuryInstruction::SignDisbursal { disbursal_id: 1 },
+            &mut fixture.accounts,
+            signer_metas(*signer),
+            Ok(()),
+        );
+    }
+    process(
+        &TreasuryInstruction::ExecuteDisbursal { disbursal_id: 1 },
+        &mut fixture.accounts,
+        vec![
+            AccountMeta::new_readonly(fixture.authority, true),
+            AccountMeta::new(fixture.config, false),
+            AccountMeta::new(recurring, false),
+            AccountMeta::new(fixture.recipient, false),
+            AccountMeta::new_readonly(fixture.multisig, false),
+            AccountMeta::new(pending, false),
+        ],
+        Err(custom(TreasuryError::InvalidAccountData)),
+    );
+
+    assert_eq!(lamports(&fixture.accounts, &recurring), 1 + 60 * SOL);
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 0);
+}
+
+#[test]
+fn test_recurring_grant_charged_to_creation_epoch_allowance() {
+    // 10% of a 1,000 SOL treasury may leave per epoch, and the whole grant
+    // counts against the epoch it is created in.
+    let mut fixture = MultisigTreasury::new(1_000 * SOL);
+    let recurring = Pubkey::new_unique();
+    fixture.accounts.push((
+        recurring,
+        AccountSharedData::new(
+            1,
+            RecurringDisbursement::SERIALIZED_SIZE,
+            &solana_treasury_program::id(),
+        ),
+    ));
+    let metas = fixture.authority_metas(recurring);
+    process(
+        &TreasuryInstruction::CreateRecurringDisbursement {
+            disbursement_id: 1,
+            recipient: fixture.recipient,
+            amount_per_epoch: 10 * SOL,
+            start_epoch: 0,
+            end_epoch: 12,
+        },
+        &mut fixture.accounts,
+        metas,
+        Err(custom(TreasuryError::EpochAllowanceExceeded)),
+    );
+    assert_eq!(lamports(&fixture.accounts, &fixture.treasury), 1_000 * SOL);
+
+    let recurring = fixture.create_recurring(10 * SOL, 0, 9);
+    assert_eq!(lamports(&fixture.accounts, &recurring), 1 + 90 * SOL);
+    assert_eq!(fixture.config().disbursed_this_epoch, 90 * SOL);
+
+    // The 910 SOL left allow 91 SOL this epoch, so only 1 SOL remains.
+    let disburse = |amount| TreasuryInstruction::Disburse {
+        amount,
+        recipient: fixture.recipient,
+        memo: "operations".to_string(),
+    };
+    let metas = fixture.disburse_metas();
+    process(
+        &disburse(2 * SOL),
+        &mut fixture.accounts,
+        metas.clone(),
+        Err(custom(TreasuryError::EpochAllowanceExceeded)),
+    );
+    process(&disburse(SOL), &mut fixture.accounts, metas.clone(), Ok(()));
+
+    // Paying out the escrowed installments later does not use up the
+    // allowance of the epoch they are paid in.
+    set_epoch(&mut fixture.accounts, 3);
+    fixture.execute_recurring(recurring, Ok(()));
+    assert_eq!(lamports(&fixture.accounts, &fixture.treasury), 909 * SOL);
+    process(&disburse(90 * SOL), &mut fixture.accounts, metas, Ok(()));
+    assert_eq!(fixture.config().disbursed_this_epoch, 90 * SOL);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+//  9. Epoch allowance
+// ═══════════════════════════════════════════════════════════════════════════
+
+impl MultisigTreasury {
+    /// Accounts for a `Disburse` signed by the single-key authority.
+    fn disburse_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.authority, true),
+            AccountMeta::new(self.config, false),
+            AccountMeta::new(self.treasury, false),
+            AccountMeta::new(self.recipient, false),
+        ]
+    }
+}
+
+#[test]
+fn test_epoch_allowance_limits_disbursements() {
+    // The default allowance lets 10% of the treasury go out per epoch.
+    let mut fixture = MultisigTreasury::new(1_000_000);
+    assert_eq!(fixture.config().epoch_allowance_bps, 1_000);
+    let disburse = |amount| TreasuryInstruction::Disburse {
+        amount,
+        recipient: fixture.recipient,
+        memo: "operations".to_string(),
+    };
+    let metas = fixture.disburse_metas();
+
+    process(
+        &disburse(50_000),
+        &mut fixture.accounts,
+        metas.clone(),
+        Ok(()),
+    );
+    process(
+        &disburse(60_000),
+        &mut fixture.accounts,
+        metas.clone(),
+        Err(custom(TreasuryError::EpochAllowanceExceeded)),
+    );
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 50_000);
+    assert_eq!(fixture.config().disbursed_this_epoch, 50_000);
+
+    // The next epoch starts a fresh allowance.
+    set_epoch(&mut fixture.accounts, 1);
+    process(&disburse(60_000), &mut fixture.accounts, metas, Ok(()));
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 110_000);
+    assert_eq!(lamports(&fixture.accounts, &fixture.treasury), 890_000);
+    let config = fixture.config();
+    assert_eq!(config.disbursed_this_epoch, 60_000);
+    assert_eq!(config.last_disbursement_epoch, 1);
+    assert_eq!(config.total_disbursed, 110_000);
+}
+
+#[test]
+fn test_epoch_allowance_measured_against_the_treasury_account() {
+    // A large account passed in place of the treasury must not inflate the
+    // allowance.
+    let mut fixture = MultisigTreasury::new(1_000_000);
+    let decoy = Pubkey::new_unique();
+    fixture.accounts.push((
+        decoy,
+        AccountSharedData::new(100_000_000, 0, &solana_treasury_program::id()),
+    ));
+    let mut metas = fixture.disburse_metas();
+    metas[2] = AccountMeta::new(decoy, false);
+    process(
+        &TreasuryInstruction::Disburse {
+            amount: 500_000,
+            recipient: fixture.recipient,
+            memo: "operations".to_string(),
+        },
+        &mut fixture.accounts,
+        metas,
+        Err(custom(TreasuryError::InvalidAccountData)),
+    );
+    assert_eq!(lamports(&fixture.accounts, &decoy), 100_000_000);
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 0);
+    assert_eq!(fixture.config().disbursed_this_epoch, 0);
+}
+
+#[test]
+fn test_epoch_allowance_changes_only_under_governance() {
+    let mut fixture = MultisigTreasury::new(1_000_000);
+    let update_metas = vec![
+        AccountMeta::new_readonly(fixture.authority, true),
+        AccountMeta::new(fixture.config, false),
+    ];
+    let set_allowance = |epoch_allowance_bps| TreasuryInstruction::SetEpochAllowance {
+        epoch_allowance_bps,
+    };
+
+    // The launch authority cannot lift its own limit.
+    process(
+        &set_allowance(2_000),
+        &mut fixture.accounts,
+        update_metas.clone(),
+        Err(custom(TreasuryError::GovernanceNotActive)),
+    );
+
+    process(
+        &TreasuryInstruction::ActivateGovernance,
+        &mut fixture.accounts,
+        update_metas.clone(),
+        Ok(()),
+    );
+    let mut outsider_metas = update_metas.clone();
+    outsider_metas[0] = AccountMeta::new_readonly(fixture.signers[0], true);
+    process(
+        &set_allowance(2_000),
+        &mut fixture.accounts,
+        outsider_metas,
+        Err(custom(TreasuryError::AuthorityMismatch)),
+    );
+    for invalid in [0, 10_001] {
+        process(
+            &set_allowance(invalid),
+            &mut fixture.accounts,
+            update_metas.clone(),
+            Err(custom(TreasuryError::InvalidEpochAllowance)),
+        );
+    }
+    process(
+        &set_allowance(2_000),
+        &mut fixture.accounts,
+        update_metas,
+        Ok(()),
+    );
+    assert_eq!(fixture.config().epoch_allowance_bps, 2_000);
+
+    let metas = fixture.disburse_metas();
+    process(
+        &TreasuryInstruction::Disburse {
+            amount: 150_000,
+            recipient: fixture.recipient,
+            memo: String::new(),
+        },
+        &mut fixture.accounts,
+        metas,
+        Ok(()),
+    );
+    assert_eq!(lamports(&fixture.accounts, &fixture.recipient), 150_000);
+}
@@ -7,6 +7,16 @@
 //! - **Gauge**: value that can go up or down (e.g., current base fee)
 //! - **Histogram**: distribution of observations (e.g., finality times)
 //!
+//! A **RateCounter** additionally counts events over a sliding time window
+//! (e.g., blocks produced in the last minute) and is exported as a gauge.
+//!
+//! [`TRv1Metrics::begin_epoch`] marks epoch boundaries so counters can also be
+//! read per epoch (e.g., fees burned this epoch) through
+//! [`EpochMetricsSnapshot`].
+//!
+//! Subsystems can add metrics of their own without touching [`TRv1Metrics`]
+//! by registering them in a [`registry::MetricRegistry`].
+//!
 //! ## Usage
 //!
 //! ```rust
@@ -28,10 +38,19 @@
 //! let prom_text = trv1_monitoring::prometheus::encode(&snapshot);
 //! ```
 
+pub mod open_metrics;
 pub mod prometheus;
+#[cfg(feature = "push-gateway")]
+pub mod push_gateway;
+pub mod registry;
+#[cfg(feature = "serde")]
+mod serde_helpers;
 
 use parking_lot::Mutex;
+use registry::Registry;
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
 
 // ---------------------------------------------------------------------------
 // Metric primitives
@@ -40,9 +59,7 @@ use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
 /// A monotonically increasing counter.
 pub struct Counter {
     value: AtomicU64,
-    #[allow(dead_code)]
     name: &'static str,
-    #[allow(dead_code)]
     help: &'static str,
 }
 
@@ -74,14 +91,22 @@ impl Counter {
     pub fn reset(&self) {
         self.value.store(0, Ordering::Relaxed);
     }
+
+    /// Prometheus metric name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Prometheus `# HELP` text.
+    pub fn help(&self) -> &'static str {
+        self.help
+    }
 }
 
 /// A gauge that can go up or down.
 pub struct Gauge {
     value: AtomicI64,
-    #[allow(dead_code)]
     name: &'static str,
-    #[allow(dead_code)]
     help: &'static str,
 }
 
@@ -118,6 +143,16 @@ impl Gauge {
     pub fn get(&self) -> i64 {
         self.value.load(Ordering::Relaxed)
     }
+
+    /// Prometheus metric name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Prometheus `# HELP` text.
+    pub fn help(&self) -> &'static str {
+        self.help
+    }
 }
 
 /// A histogram that collects observations into configurable buckets.
@@ -126,9 +161,7 @@ pub struct Histogram {
     counts: Vec<AtomicU64>,
     sum: Mutex<f64>,
     count: AtomicU64,
-    #[allow(dead_code)]
     name: &'static str,
-    #[allow(dead_code)]
     help: &'static str,
 }
 
@@ -178,6 +211,171 @@ impl Histogram {
             .map(|(bound, count)| (*bound, count.load(Ordering::Relaxed)))
             .collect()
     }
+
+    /// Estimate the `p`-quantile (`p` in [0.0, 1.0]) of the observations.
+    ///
+    /// See [`bucket_percentile`] for the estimation method and edge cases.
+    pub fn percentile(&self, p: f64) -> f64 {
+        bucket_percentile(&self.get_buckets(), self.get_count(), p)
+    }
+
+    /// Estimated median.
+    pub fn p50(&self) -> f64 {
+        self.percentile(0.50)
+    }
+
+    /// Estimated 95th percentile.
+    pub fn p95(&self) -> f64 {
+        self.percentile(0.95)
+    }
+
+    /// Estimated 99th percentile.
+    pub fn p99(&self) -> f64 {
+        self.percentile(0.99)
+    }
+
+    /// Prometheus metric name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Prometheus `# HELP` text.
+    pub fn help(&self) -> &'static str {
+        self.help
+    }
+}
+
+/// A counter over a sliding time window.
+///
+/// Each `add` is stored with its timestamp; entries older than the window
+/// are pruned on every call, so memory is bounded by the event rate.
+pub struct RateCounter {
+    window: Duration,
+    events: Mutex<VecDeque<(Instant, u64)>>,
+    name: &'static str,
+    help: &'static str,
+}
+
+impl RateCounter {
+    /// Create a counter that reports over the trailing `window`.
+    pub fn new(name: &'static str, help: &'static str, window: Duration) -> Self {
+        Self {
+            window,
+            events: Mutex::new(VecDeque::new()),
+            name,
+            help,
+        }
+    }
+
+    /// Record one event now.
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    /// Record `v` events now.
+    pub fn add(&self, v: u64) {
+        self.add_at(v, Instant::now());
+    }
+
+    /// Record `v` events at `at`.  Timestamps are expected to be
+    /// non-decreasing.
+    pub fn add_at(&self, v: u64, at: Instant) {
+        let mut events = self.events.lock();
+        self.prune(&mut events, at);
+        events.push_back((at, v));
+    }
+
+    /// Events recorded in the window ending at `now`, i.e. after
+    /// `now - window`.
+    pub fn count_in_window(&self, now: Instant) -> u64 {
+        let mut events = self.events.lock();
+        self.prune(&mut events, now);
+        events
+            .iter()
+            .fold(0u64, |total, &(_, v)| total.saturating_add(v))
+    }
+
+    /// Average events per second over the window ending at `now`.
+    pub fn rate_per_second(&self, now: Instant) -> f64 {
+        let secs = self.window.as_secs_f64();
+        if secs == 0.0 {
+            return 0.0;
+        }
+        self.count_in_window(now) as f64 / secs
+    }
+
+    /// The window length.
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Prometheus metric name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Prometheus `# HELP` text.
+    pub fn help(&self) -> &'static str {
+        self.help
+    }
+
+    fn prune(&self, events: &mut VecDeque<(Instant, u64)>, now: Instant) {
+        while let Some(&(at, _)) = events.front() {
+            if now.saturating_duration_since(at) < self.window {
+                break;
+            }
+            events.pop_front();
+        }
+    }
+}
+
+/// Estimate the `p`-quantile from cumulative histogram buckets.
+///
+/// `buckets` are `(upper_bound, cumulative_count)` pairs as returned by
+/// [`Histogram::get_buckets`], and `count` is the total number of
+/// observations, including those above the last bound.  Following the
+/// Prometheus `histogram_quantile` technique, the target rank `p * count` is
+/// located in the first bucket whose cumulative count reaches it, and the
+/// result is linearly interpolated between that bucket's lower and upper
+/// bounds.  The lowest bucket's lower bound is 0.
+///
+/// Edge cases:
+/// - an empty histogram returns `f64::NAN`;
+/// - `p == 0.0` returns 0.0;
+/// - `p == 1.0`, or a rank falling above the last bound, returns
+///   `f64::INFINITY` (the implicit `+Inf` bucket has no upper bound);
+/// - `p` outside [0.0, 1.0] returns `f64::NEG_INFINITY` / `f64::INFINITY`,
+///   and a NaN `p` returns `f64::NAN`.
+pub fn bucket_percentile(buckets: &[(f64, u64)], count: u64, p: f64) -> f64 {
+    if count == 0 || p.is_nan() {
+        return f64::NAN;
+    }
+    if p < 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p == 0.0 {
+        return 0.0;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    let rank = p * count as f64;
+    let mut lower_bound = 0.0;
+    let mut lower_count = 0u64;
+    for &(upper_bound, cumulative) in buckets {
+        if cumulative as f64 >= rank {
+            let in_bucket = cumulative.saturating_sub(lower_count);
+            if in_bucket == 0 {
+                return upper_bound;
+            }
+            let fraction = (rank - lower_count as f64) / in_bucket as f64;
+            return lower_bound + (upper_bound - lower_bound) * fraction;
+        }
+        lower_bound = upper_bound;
+        lower_count = cumulative;
+    }
+    f64::INFINITY
 }
 
 // ---------------------------------------------------------------------------
@@ -196,14 +394,73 @@ pub fn default_round_buckets() -> Vec<f64> {
     vec![1.0, 2.0, 3.0, 4.0, 5.0, 10.0]
 }
 
+// ---------------------------------------------------------------------------
+// Health scoring configuration
+// ---------------------------------------------------------------------------
+
+/// Weights applied to each component of the consensus health score.
+///
+/// The weights should sum to 1.0 so the score stays within [0.0, 1.0].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthWeights {
+    /// Weight of `1.0 - missed_proposals_rate`.
+    pub proposals: f64,
+    /// Weight of the finality-time score.
+    pub finality: f64,
+    /// Weight of the active-validator fraction.
+    pub active_validators: f64,
+}
+
+impl Default for HealthWeights {
+    fn default() -> Self {
+        Self {
+            proposals: 0.4,
+            finality: 0.4,
+            active_validators: 0.2,
+        }
+    }
+}
+
+/// Thresholds used to derive aggregated health indicators.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitoringConfig {
+    /// Average finality time (ms) at or above which the finality score is 0.
+    pub max_acceptable_finality_ms: f64,
+    /// Number of validators expected in the active set.
+    pub expected_validators: u64,
+    /// Weights for [`TRv1Metrics::consensus_health_score`].
+    pub health_weights: HealthWeights,
+    /// Window of the [`RateCounter`]s in [`TRv1Metrics`].
+    pub rate_window: Duration,
+}
+
+impl Default for MonitoringConfig {
+    fn default() -> Self {
+        Self {
+            max_acceptable_finality_ms: 5_000.0,
+            expected_validators: 200,
+            health_weights: HealthWeights::default(),
+            rate_window: Duration::from_secs(60),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // TRv1 Metrics
 // ---------------------------------------------------------------------------
 
 /// Complete metrics collection for a TRv1 validator node.
 pub struct TRv1Metrics {
+    /// Thresholds for derived indicators such as the health score.
+    pub config: MonitoringConfig,
+    /// When this collection was created; exported as the OpenMetrics
+    /// `_created` timestamp of counters and histograms.
+    pub created: SystemTime,
+
     // -- Consensus --
     pub blocks_produced: Counter,
+    /// Blocks produced over the trailing `config.rate_window`.
+    pub blocks_produced_rate: RateCounter,
     pub consensus_rounds: Histogram,
     pub finality_time_ms: Histogram,
     pub missed_proposals: Counter,
@@ -212,6 +469,8 @@ pub struct TRv1Metrics {
     pub current_base_fee: Gauge,
     pub block_utilization: Gauge,
     pub total_fees_burned: Counter,
+    /// Fees burned (lamports) over the trailing `config.rate_window`.
+    pub total_fees_burned_rate: RateCounter,
     pub total_fees_treasury: Counter,
     pub total_fees_dev: Counter,
     pub total_fees_validator: Counter,
@@ -222,6 +481,12 @@ pub struct TRv1Metrics {
     pub cold_storage_size: Gauge,
     pub cache_hit_rate: Gauge,
     pub cache_evictions: Counter,
+    /// Cache evictions over the trailing `config.rate_window`.
+    pub cache_evictions_rate: RateCounter,
+    /// Accounts archived to cold storage by state rent expiry.
+    pub total_archived: Counter,
+    /// Archived accounts revived back into the accounts DB.
+    pub total_revived: Counter,
 
     // -- Staking --
     pub total_staked: Gauge,
@@ -238,17 +503,48 @@ pub struct TRv1Metrics {
     pub passive_stake_tier_3: Gauge,
     pub passive_stake_tier_4: Gauge,
     pub passive_stake_tier_5: Gauge,
+
+    // -- Network --
+    /// Consensus message bytes sent, measured before compression.
+    pub total_bytes_sent_uncompressed: Counter,
+    /// Consensus message bytes sent, measured as written to the wire.
+    pub total_bytes_sent_compressed: Counter,
+    /// Inbound consensus messages dropped by per-peer rate limiting.
+    pub peer_rate_limited_messages: Counter,
+    /// Block sync requests skipped because the height was already in flight.
+    pub duplicate_requests_avoided: Counter,
+    /// Block sync requests re-sent after a response timeout.
+    pub sync_retries: Counter,
+    /// Synced blocks whose hash did not match a sync checkpoint.
+    pub sync_checkpoint_failures: Counter,
+
+    // -- Epochs --
+    epochs: Mutex<EpochHistory>,
 }
 
 impl TRv1Metrics {
     /// Create a new metrics instance with all counters at zero.
     pub fn new() -> Self {
+        Self::with_config(MonitoringConfig::default())
+    }
+
+    /// Create a new metrics instance with custom health thresholds.
+    pub fn with_config(config: MonitoringConfig) -> Self {
+        let rate_window = config.rate_window;
         Self {
+            config,
+            created: SystemTime::now(),
+
             // Consensus
             blocks_produced: Counter::new(
                 "trv1_blocks_produced_total",
                 "Total number of blocks produced by this validator",
             ),
+            blocks_produced_rate: RateCounter::new(
+                "trv1_blocks_produced_rate",
+                "Blocks produced over the trailing rate window",
+                rate_window,
+            ),
             consensus_rounds: Histogram::new(
                 "trv1_consensus_rounds",
                 "Number of consensus rounds needed to finalize a block",
@@ -277,6 +573,11 @@ impl TRv1Metrics {
                 "trv1_fees_burned_total",
                 "Total fees burned (lamports)",
             ),
+            total_fees_burned_rate: RateCounter::new(
+                "trv1_fees_burned_rate",
+                "Fees burned (lamports) over the trailing rate window",
+                rate_window,
+            ),
             total_fees_treasury: Counter::new(
                 "trv1_fees_treasury_total",
                 "Total fees sent to treasury (lamports)",
@@ -311,6 +612,19 @@ impl TRv1Metrics {
                 "trv1_cache_evictions_total",
                 "Total number of cache evictions",
             ),
+            cache_evictions_rate: RateCounter::new(
+                "trv1_cache_evictions_rate",
+                "Cache evictions over the trailing rate window",
+                rate_window,
+            ),
+            total_archived: Counter::new(
+                "trv1_accounts_archived_total",
+                "Total number of accounts archived to cold storage",
+            ),
+            total_revived: Counter::new(
+                "trv1_accounts_revived_total",
+                "Total number of archived accounts revived",
+            ),
 
             // Staking
             total_staked: Gauge::new(
@@ -363,9 +677,88 @@ impl TRv1Metrics {
                 "trv1_passive_stake_tier_permanent_lamports",
                 "Passive stake in permanent tier (lamports)",
             ),
+
+            // Network
+            total_bytes_sent_uncompressed: Counter::new(
+                "trv1_net_sent_uncompressed_bytes_total",
+                "Consensus message bytes sent, before compression",
+            ),
+            total_bytes_sent_compressed: Counter::new(
+                "trv1_net_sent_compressed_bytes_total",
+                "Consensus message bytes sent, after compression",
+            ),
+            peer_rate_limited_messages: Counter::new(
+                "trv1_net_peer_rate_limited_messages_total",
+                "Inbound consensus messages dropped by per-peer rate limiting",
+            ),
+            duplicate_requests_avoided: Counter::new(
+                "trv1_net_sync_duplicate_requests_avoided_total",
+                "Block sync requests skipped because the height was already in flight",
+            ),
+            sync_retries: Counter::new(
+                "trv1_net_sync_retries_total",
+                "Block sync requests re-sent after a response timeout",
+            ),
+            sync_checkpoint_failures: Counter::new(
+                "trv1_net_sync_checkpoint_failures_total",
+                "Synced blocks that did not match a sync checkpoint",
+            ),
+
+            // Epochs
+            epochs: Mutex::new(EpochHistory::default()),
         }
     }
 
+    /// Start tracking `epoch`, returning its snapshot with `start` and `end`
+    /// both set to the current values.
+    ///
+    /// The epoch in progress, if any, ends here: it is finished with the same
+    /// snapshot and pushed onto the history, which keeps the last
+    /// [`EPOCH_HISTORY_LEN`] completed epochs.
+    pub fn begin_epoch(&self, epoch: u64) -> EpochMetricsSnapshot {
+        let now = self.snapshot();
+        let started = EpochMetricsSnapshot {
+            epoch,
+            start: now.clone(),
+            end: now.clone(),
+        };
+
+        let mut epochs = self.epochs.lock();
+        if let Some(mut finished) = epochs.current.replace(started.clone()) {
+            finished.end = now;
+            if epochs.completed.len() == EPOCH_HISTORY_LEN {
+                epochs.completed.pop_front();
+            }
+            epochs.completed.push_back(finished);
+        }
+        started
+    }
+
+    /// The epoch in progress, finished as of now, or `None` before the first
+    /// [`TRv1Metrics::begin_epoch`].
+    pub fn current_epoch_metrics(&self) -> Option<EpochMetricsSnapshot> {
+        let current = self.epochs.lock().current.clone()?;
+        Some(current.finish(self))
+    }
+
+    /// The last `n` completed epochs, oldest first.  At most
+    /// [`EPOCH_HISTORY_LEN`] are retained.
+    pub fn epoch_history(&self, n: usize) -> Vec<EpochMetricsSnapshot> {
+        let epochs = self.epochs.lock();
+        let skip = epochs.completed.len().saturating_sub(n);
+        epochs.completed.iter().skip(skip).cloned().collect()
+    }
+
+    /// Ratio of bytes on the wire to bytes before compression; 1.0 until
+    /// anything has been sent.
+    pub fn compression_ratio(&self) -> f64 {
+        let uncompressed = self.total_bytes_sent_uncompressed.get();
+        if uncompressed == 0 {
+            return 1.0;
+        }
+        self.total_bytes_sent_compressed.get() as f64 / uncompressed as f64
+    }
+
     /// Get a reference to a passive stake tier gauge by index.
     pub fn passive_stake_tier(&self, index: usize) -> Option<&Gauge> {
         match index {
@@ -379,23 +772,83 @@ impl TRv1Metrics {
         }
     }
 
+    /// Aggregated consensus health in [0.0, 1.0], where 1.0 is fully healthy.
+    ///
+    /// Computed as `w1 * (1 - missed_proposals_rate) + w2 * finality_score
+    /// + w3 * active_validator_fraction`, with:
+    /// - `missed_proposals_rate = missed / (produced + missed)`
+    /// - `finality_score = 1 - clamp(avg_finality_ms / max_acceptable_finality_ms, 0, 1)`
+    /// - `active_validator_fraction = active / expected`, clamped to [0, 1]
+    ///
+    /// A component with no samples yet (no proposals, no finality
+    /// observations) scores 0, so a node that has not participated in
+    /// consensus is reported as unhealthy rather than healthy.
+    pub fn consensus_health_score(&self) -> f64 {
+        let weights = self.config.health_weights;
+
+        let produced = self.blocks_produced.get();
+        let missed = self.missed_proposals.get();
+        let total_proposals = produced.saturating_add(missed);
+        let missed_rate = if total_proposals == 0 {
+            1.0
+        } else {
+            missed as f64 / total_proposals as f64
+        };
+
+        let finality_count = self.finality_time_ms.get_count();
+        let finality_score =
+            if finality_count == 0 || self.config.max_acceptable_finality_ms <= 0.0 {
+                0.0
+            } else {
+                let avg_finality_ms = self.finality_time_ms.get_sum() / finality_count as f64;
+                1.0 - (avg_finality_ms / self.config.max_acceptable_finality_ms).clamp(0.0, 1.0)
+            };
+
+        let active_fraction = if self.config.expected_validators == 0 {
+            1.0
+        } else {
+            (self.active_validators.get() as f64 / self.config.expected_validators as f64)
+                .clamp(0.0, 1.0)
+        };
+
+        let score = weights.proposals * (1.0 - missed_rate)
+            + weights.finality * finality_score
+            + weights.active_validators * active_fraction;
+        score.clamp(0.0, 1.0)
+    }
+
     /// Take a full snapshot of all metrics for export.
     pub fn snapshot(&self) -> MetricsSnapshot {
+        let now = Instant::now();
         MetricsSnapshot {
+            created_timestamp_ms: self
+                .created
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |since| since.as_millis() as u64),
+
             // Consensus
             blocks_produced: self.blocks_produced.get(),
+            blocks_produced_rate: self.blocks_produced_rate.count_in_window(now),
             consensus_rounds_count: self.consensus_rounds.get_count(),
             consensus_rounds_sum: self.consensus_rounds.get_sum(),
             consensus_rounds_buckets: self.consensus_rounds.get_buckets(),
+            consensus_rounds_p50: self.consensus_rounds.p50(),
+            consensus_rounds_p95: self.consensus_rounds.p95(),
+            consensus_rounds_p99: self.consensus_rounds.p99(),
             finality_time_count: self.finality_time_ms.get_count(),
             finality_time_sum: self.finality_time_ms.get_sum(),
             finality_time_buckets: self.finality_time_ms.get_buckets(),
+            finality_time_p50: self.finality_time_ms.p50(),
+            finality_time_p95: self.finality_time_ms.p95(),
+            finality_time_p99: self.finality_time_ms.p99(),
             missed_proposals: self.missed_proposals.get(),
+            consensus_health_score: self.consensus_health_score(),
 
             // Fee Market
             current_base_fee: self.current_base_fee.get(),
             block_utilization: self.block_utilization.get(),
             total_fees_burned: self.total_fees_burned.get(),
+            total_fees_burned_rate: self.total_fees_burned_rate.count_in_window(now),
             total_fees_treasury: self.total_fees_treasury.get(),
             total_fees_dev: self.total_fees_dev.get(),
             total_fees_validator: self.total_fees_validator.get(),
@@ -406,6 +859,9 @@ impl TRv1Metrics {
             cold_storage_size: self.cold_storage_size.get(),
             cache_hit_rate: self.cache_hit_rate.get(),
             cache_evictions: self.cache_evictions.get(),
+            cache_evictions_rate: self.cache_evictions_rate.count_in_window(now),
+            total_archived: self.total_archived.get(),
+            total_revived: self.total_revived.get(),
 
             // Staking
             total_staked: self.total_staked.get(),
@@ -424,6 +880,14 @@ impl TRv1Metrics {
                 self.passive_stake_tier_4.get(),
                 self.passive_stake_tier_5.get(),
             ],
+
+            // Network
+            total_bytes_sent_uncompressed: self.total_bytes_sent_uncompressed.get(),
+            total_bytes_sent_compressed: self.total_bytes_sent_compressed.get(),
+            peer_rate_limited_messages: self.peer_rate_limited_messages.get(),
+            duplicate_requests_avoided: self.duplicate_requests_avoided.get(),
+            sync_retries: self.sync_retries.get(),
+            sync_checkpoint_failures: self.sync_checkpoint_failures.get(),
         }
     }
 }
@@ -434,27 +898,115 @@ impl Default for TRv1Metrics {
     }
 }
 
+/// Every [`Counter`], [`Gauge`] and [`Histogram`] field; keep in sync with the
+/// struct.  Rate counters and derived indicators are not registry metrics.
+impl Registry for TRv1Metrics {
+    fn iter_counters(&self) -> Box<dyn Iterator<Item = &Counter> + '_> {
+        Box::new(
+            [
+                &self.blocks_produced,
+                &self.missed_proposals,
+                &self.total_fees_burned,
+                &self.total_fees_treasury,
+                &self.total_fees_dev,
+                &self.total_fees_validator,
+                &self.cache_evictions,
+                &self.total_archived,
+                &self.total_revived,
+                &self.total_bytes_sent_uncompressed,
+                &self.total_bytes_sent_compressed,
+                &self.peer_rate_limited_messages,
+                &self.duplicate_requests_avoided,
+                &self.sync_retries,
+                &self.sync_checkpoint_failures,
+            ]
+            .into_iter(),
+        )
+    }
+
+    fn iter_gauges(&self) -> Box<dyn Iterator<Item = &Gauge> + '_> {
+        Box::new(
+            [
+                &self.current_base_fee,
+                &self.block_utilization,
+                &self.hot_cache_size,
+                &self.warm_storage_size,
+                &self.cold_storage_size,
+                &self.cache_hit_rate,
+                &self.total_staked,
+                &self.staking_participation_rate,
+                &self.active_validators,
+                &self.standby_validators,
+                &self.jailed_validators,
+                &self.passive_stake_total,
+                &self.passive_stake_tier_0,
+                &self.passive_stake_tier_1,
+                &self.passive_stake_tier_2,
+                &self.passive_stake_tier_3,
+                &self.passive_stake_tier_4,
+                &self.passive_stake_tier_5,
+            ]
+            .into_iter(),
+        )
+    }
+
+    fn iter_histograms(&self) -> Box<dyn Iterator<Item = &Histogram> + '_> {
+        Box::new([&self.consensus_rounds, &self.finality_time_ms].into_iter())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Snapshot (point-in-time export)
 // ---------------------------------------------------------------------------
 
+/// Labels of the passive stake tiers, in `passive_stake_by_tier` order.
+pub const PASSIVE_STAKE_TIER_LABELS: [&str; 6] =
+    ["no_lock", "30d", "90d", "180d", "360d", "permanent"];
+
 /// A serialisable point-in-time snapshot of all TRv1 metrics.
-#[derive(Debug, Clone)]
+///
+/// With the `serde` feature enabled, `passive_stake_by_tier` is encoded as a
+/// map keyed by [`PASSIVE_STAKE_TIER_LABELS`] and NaN percentiles as `null`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MetricsSnapshot {
+    /// Creation time of the source [`TRv1Metrics`], in milliseconds since
+    /// the Unix epoch.
+    pub created_timestamp_ms: u64,
+
     // Consensus
     pub blocks_produced: u64,
+    /// Blocks produced over the trailing rate window.
+    pub blocks_produced_rate: u64,
     pub consensus_rounds_count: u64,
     pub consensus_rounds_sum: f64,
     pub consensus_rounds_buckets: Vec<(f64, u64)>,
+    /// Estimated quantiles of `consensus_rounds`; NaN when empty.
+    #[cfg_attr(feature = "serde", serde(with = "serde_helpers::nan_as_null"))]
+    pub consensus_rounds_p50: f64,
+    #[cfg_attr(feature = "serde", serde(with = "serde_helpers::nan_as_null"))]
+    pub consensus_rounds_p95: f64,
+    #[cfg_attr(feature = "serde", serde(with = "serde_helpers::nan_as_null"))]
+    pub consensus_rounds_p99: f64,
     pub finality_time_count: u64,
     pub finality_time_sum: f64,
     pub finality_time_buckets: Vec<(f64, u64)>,
+    /// Estimated quantiles of `finality_time_ms`; NaN when empty.
+    #[cfg_attr(feature = "serde", serde(with = "serde_helpers::nan_as_null"))]
+    pub finality_time_p50: f64,
+    #[cfg_attr(feature = "serde", serde(with = "serde_helpers::nan_as_null"))]
+    pub finality_time_p95: f64,
+    #[cfg_attr(feature = "serde", serde(with = "serde_helpers::nan_as_null"))]
+    pub finality_time_p99: f64,
     pub missed_proposals: u64,
+    pub consensus_health_score: f64,
 
     // Fee Market
     pub current_base_fee: i64,
     pub block_utilization: i64,
     pub total_fees_burned: u64,
+    /// Fees burned over the trailing rate window.
+    pub total_fees_burned_rate: u64,
     pub total_fees_treasury: u64,
     pub total_fees_dev: u64,
     pub total_fees_validator: u64,
@@ -465,6 +1017,10 @@ pub struct MetricsSnapshot {
     pub cold_storage_size: i64,
     pub cache_hit_rate: i64,
     pub cache_evictions: u64,
+    /// Cache evictions over the trailing rate window.
+    pub cache_evictions_rate: u64,
+    pub total_archived: u64,
+    pub total_revived: u64,
 
     // Staking
     pub total_staked: i64,
@@ -475,7 +1031,142 @@ pub struct MetricsSnapshot {
 
     // Passive Staking
     pub passive_stake_total: i64,
+    #[cfg_attr(feature = "serde", serde(with = "serde_helpers::tier_map"))]
     pub passive_stake_by_tier: [i64; 6],
+
+    // Network
+    pub total_bytes_sent_uncompressed: u64,
+    pub total_bytes_sent_compressed: u64,
+    pub peer_rate_limited_messages: u64,
+    pub duplicate_requests_avoided: u64,
+    pub sync_retries: u64,
+    pub sync_checkpoint_failures: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Epoch aggregation
+// ---------------------------------------------------------------------------
+
+/// Number of completed epochs [`TRv1Metrics`] retains.
+pub const EPOCH_HISTORY_LEN: usize = 10;
+
+/// The epoch in progress and the most recently completed epochs.
+#[derive(Default)]
+struct EpochHistory {
+    current: Option<EpochMetricsSnapshot>,
+    completed: VecDeque<EpochMetricsSnapshot>,
+}
+
+/// Metrics at the start and end of an epoch.
+///
+/// The `*_this_epoch` helpers return the growth of a counter between the two
+/// snapshots, saturating at zero if the counter was reset in between.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EpochMetricsSnapshot {
+    pub epoch: u64,
+    pub start: MetricsSnapshot,
+    pub end: MetricsSnapshot,
+}
+
+impl EpochMetricsSnapshot {
+    /// Capture the current values of `metrics` as the end of this epoch.
+    pub fn finish(self, metrics: &TRv1Metrics) -> Self {
+        Self {
+            end: metrics.snapshot(),
+            ..self
+        }
+    }
+
+    fn delta(&self, counter: impl Fn(&MetricsSnapshot) -> u64) -> u64 {
+        counter(&self.end).saturating_sub(counter(&self.start))
+    }
+
+    // Consensus
+
+    pub fn blocks_this_epoch(&self) -> u64 {
+        self.delta(|s| s.blocks_produced)
+    }
+
+    pub fn missed_proposals_this_epoch(&self) -> u64 {
+        self.delta(|s| s.missed_proposals)
+    }
+
+    /// Observations of the consensus rounds histogram.
+    pub fn consensus_rounds_observed_this_epoch(&self) -> u64 {
+        self.delta(|s| s.consensus_rounds_count)
+    }
+
+    /// Observations of the finality time histogram.
+    pub fn finality_observed_this_epoch(&self) -> u64 {
+        self.delta(|s| s.finality_time_count)
+    }
+
+    // Fee Market
+
+    pub fn fees_burned_this_epoch(&self) -> u64 {
+        self.delta(|s| s.total_fees_burned)
+    }
+
+    pub fn fees_treasury_this_epoch(&self) -> u64 {
+        self.delta(|s| s.total_fees_treasury)
+    }
+
+    pub fn fees_dev_this_epoch(&self) -> u64 {
+        self.delta(|s| s.total_fees_dev)
+    }
+
+    pub fn fees_validator_this_epoch(&self) -> u64 {
+        self.delta(|s| s.total_fees_validator)
+    }
+
+    /// Fees burned or distributed to any recipient.
+    pub fn fees_collected_this_epoch(&self) -> u64 {
+        self.fees_burned_this_epoch()
+            .saturating_add(self.fees_treasury_this_epoch())
+            .saturating_add(self.fees_dev_this_epoch())
+            .saturating_add(self.fees_validator_this_epoch())
+    }
+
+    // Storage
+
+    pub fn cache_evictions_this_epoch(&self) -> u64 {
+        self.delta(|s| s.cache_evictions)
+    }
+
+    pub fn accounts_archived_this_epoch(&self) -> u64 {
+        self.delta(|s| s.total_archived)
+    }
+
+    pub fn accounts_revived_this_epoch(&self) -> u64 {
+        self.delta(|s| s.total_revived)
+    }
+
+    // Network
+
+    pub fn bytes_sent_uncompressed_this_epoch(&self) -> u64 {
+        self.delta(|s| s.total_bytes_sent_uncompressed)
+    }
+
+    pub fn bytes_sent_compressed_this_epoch(&self) -> u64 {
+        self.delta(|s| s.total_bytes_sent_compressed)
+    }
+
+    pub fn peer_rate_limited_messages_this_epoch(&self) -> u64 {
+        self.delta(|s| s.peer_rate_limited_messages)
+    }
+
+    pub fn sync_duplicate_requests_avoided_this_epoch(&self) -> u64 {
+        self.delta(|s| s.duplicate_requests_avoided)
+    }
+
+    pub fn sync_retries_this_epoch(&self) -> u64 {
+        self.delta(|s| s.sync_retries)
+    }
+
+    pub fn sync_checkpoint_failures_this_epoch(&self) -> u64 {
+        self.delta(|s| s.sync_checkpoint_failures)
+    }
 }
 
 #[cfg(test)]
@@ -523,6 +1214,114 @@ mod tests {
         assert_eq!(buckets[2], (100.0, 3)); // 5.0, 25.0, 75.0 ≤ 100
     }
 
+    #[test]
+    fn test_histogram_percentile() {
+        let h = Histogram::new("test", "test histogram", vec![10.0, 20.0, 30.0]);
+        assert!(h.p50().is_nan());
+
+        for v in [5.0, 8.0, 12.0, 15.0, 18.0, 25.0] {
+            h.observe(v);
+        }
+        // Cumulative counts: ≤10: 2, ≤20: 5, ≤30: 6.
+        // p25: rank 1.5 in (0, 10] holding 2 → 0 + 10 × 1.5/2 = 7.5
+        assert!((h.percentile(0.25) - 7.5).abs() < 1e-9);
+        // p50: rank 3 in (10, 20] holding 3 → 10 + 10 × 1/3
+        assert!((h.p50() - (10.0 + 10.0 / 3.0)).abs() < 1e-9);
+        // p95: rank 5.7 in (20, 30] holding 1 → 20 + 10 × 0.7 = 27
+        assert!((h.p95() - 27.0).abs() < 1e-9);
+        // p99: rank 5.94 → 20 + 10 × 0.94 = 29.4
+        assert!((h.p99() - 29.4).abs() < 1e-9);
+
+        assert_eq!(h.percentile(0.0), 0.0);
+        assert_eq!(h.percentile(1.0), f64::INFINITY);
+
+        // A rank beyond the last finite bound falls in the +Inf bucket.
+        h.observe(100.0);
+        h.observe(100.0);
+        assert_eq!(h.percentile(0.9), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_rate_counter_sliding_window() {
+        let rate = RateCounter::new("test", "test rate", Duration::from_secs(2));
+        let t0 = Instant::now();
+        rate.add_at(1, t0);
+        for i in 0..9u64 {
+            rate.add_at(1, t0 + Duration::from_millis(1_200 + i * 225));
+        }
+
+        let now = t0 + Duration::from_secs(3);
+        // The event at t0 is older than the 2s window; the other 9 remain.
+        assert_eq!(rate.count_in_window(now), 9);
+        assert!((rate.rate_per_second(now) - 4.5).abs() < 1e-9);
+        // Once the window has passed every event, the count drops to zero.
+        assert_eq!(rate.count_in_window(t0 + Duration::from_secs(10)), 0);
+
+        let m = TRv1Metrics::new();
+        assert_eq!(m.blocks_produced_rate.window(), Duration::from_secs(60));
+        m.blocks_produced_rate.inc();
+        assert_eq!(m.blocks_produced_rate.count_in_window(Instant::now()), 1);
+    }
+
+    #[test]
+    fn test_snapshot_percentiles() {
+        let m = TRv1Metrics::new();
+        let snap = m.snapshot();
+        assert!(snap.finality_time_p50.is_nan());
+        assert!(snap.consensus_rounds_p99.is_nan());
+
+        m.finality_time_ms.observe(300.0);
+        m.consensus_rounds.observe(1.0);
+        let snap = m.snapshot();
+        // The single finality observation lies in (250, 500].
+        assert!(snap.finality_time_p50 > 250.0 && snap.finality_time_p50 <= 500.0);
+        assert_eq!(snap.finality_time_p50, m.finality_time_ms.p50());
+        assert!(snap.consensus_rounds_p95 > 0.0 && snap.consensus_rounds_p95 <= 1.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snapshot_json_roundtrip() {
+        let m = TRv1Metrics::new();
+        m.blocks_produced.add(12);
+        m.missed_proposals.add(3);
+        m.consensus_rounds.observe(1.0);
+        m.consensus_rounds.observe(3.0);
+        m.finality_time_ms.observe(420.0);
+        m.finality_time_ms.observe(1_500.0);
+        m.current_base_fee.set(7_500);
+        m.total_fees_burned.add(1_000_000);
+        m.cache_hit_rate.set(97);
+        m.active_validators.set(150);
+        for (i, lamports) in [10i64, 20, 30, 40, 50, 60].into_iter().enumerate() {
+            m.passive_stake_tier(i).unwrap().set(lamports);
+        }
+
+        let snap = m.snapshot();
+        let json = serde_json::to_string(&snap).unwrap();
+        let decoded: MetricsSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, snap);
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let tiers = &value["passive_stake_by_tier"];
+        assert_eq!(tiers["no_lock"], 10);
+        assert_eq!(tiers["30d"], 20);
+        assert_eq!(tiers["permanent"], 60);
+
+        // Empty histograms have NaN percentiles, encoded as null.
+        let empty = TRv1Metrics::new().snapshot();
+        let json = serde_json::to_string(&empty).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["finality_time_p50"].is_null());
+        let decoded: MetricsSnapshot = serde_json::from_str(&json).unwrap();
+        assert!(decoded.finality_time_p50.is_nan());
+        assert_eq!(decoded.blocks_produced, 0);
+
+        // Unknown tier labels are rejected.
+        let bad = json.replace("\"no_lock\"", "\"1y\"");
+        assert!(serde_json::from_str::<MetricsSnapshot>(&bad).is_err());
+    }
+
     #[test]
     fn test_metrics_snapshot() {
         let m = TRv1Metrics::new();
@@ -539,6 +1338,152 @@ mod tests {
         assert_eq!(snap.passive_stake_by_tier[0], 1_000_000);
     }
 
+    #[test]
+    fn test_consensus_health_score_fresh_node_is_unhealthy() {
+        let m = TRv1Metrics::new();
+        assert_eq!(m.consensus_health_score(), 0.0);
+
+        // Validators are up but nothing has been produced or finalized yet.
+        m.active_validators.set(m.config.expected_validators as i64);
+        let weights = m.config.health_weights;
+        assert!((m.consensus_health_score() - weights.active_validators).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_compression_ratio() {
+        let m = TRv1Metrics::new();
+        assert_eq!(m.compression_ratio(), 1.0);
+        m.total_bytes_sent_uncompressed.add(4_000);
+        m.total_bytes_sent_compressed.add(1_000);
+        assert_eq!(m.compression_ratio(), 0.25);
+
+        let snap = m.snapshot();
+        assert_eq!(snap.total_bytes_sent_uncompressed, 4_000);
+        assert_eq!(snap.total_bytes_sent_compressed, 1_000);
+    }
+
+    #[test]
+    fn test_consensus_health_score_ideal() {
+        let m = TRv1Metrics::new();
+        m.blocks_produced.add(1_000);
+        m.finality_time_ms.observe(0.0);
+        m.active_validators.set(m.config.expected_validators as i64);
+        assert!((m.consensus_health_score() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_consensus_health_score_degrades_with_missed_proposals() {
+        let m = TRv1Metrics::new();
+        m.active_validators.set(m.config.expected_validators as i64);
+        m.blocks_produced.add(100);
+        let healthy = m.consensus_health_score();
+
+        m.missed_proposals.add(900);
+        let degraded = m.consensus_health_score();
+        assert!(degraded < healthy);
+        // 90% missed costs 90% of the proposal weight.
+        assert!((healthy - degraded - 0.4 * 0.9).abs() < 1e-9);
+
+        // Mostly missed proposals on a stalled, depleted network → near 0.
+        m.blocks_produced.reset();
+        m.missed_proposals.add(1_000_000);
+        m.finality_time_ms.observe(m.config.max_acceptable_finality_ms * 2.0);
+        m.active_validators.set(0);
+        assert!(m.consensus_health_score() < 1e-3);
+    }
+
+    #[test]
+    fn test_epoch_deltas() {
+        let m = TRv1Metrics::new();
+        assert!(m.current_epoch_metrics().is_none());
+        // Activity before the epoch is not attributed to it.
+        m.blocks_produced.add(50);
+        m.total_fees_burned.add(1_000);
+
+        let epoch = m.begin_epoch(7);
+        assert_eq!(epoch.epoch, 7);
+        assert_eq!(epoch.blocks_this_epoch(), 0);
+        assert_eq!(epoch.fees_burned_this_epoch(), 0);
+        assert_eq!(epoch.fees_collected_this_epoch(), 0);
+        assert_eq!(epoch.cache_evictions_this_epoch(), 0);
+        assert_eq!(epoch.peer_rate_limited_messages_this_epoch(), 0);
+
+        m.blocks_produced.add(3);
+        m.missed_proposals.inc();
+        m.consensus_rounds.observe(1.0);
+        m.finality_time_ms.observe(400.0);
+        m.total_fees_burned.add(500);
+        m.total_fees_treasury.add(200);
+        m.total_fees_dev.add(100);
+        m.total_fees_validator.add(200);
+        m.cache_evictions.add(9);
+        m.total_archived.add(4);
+        m.total_revived.inc();
+        m.total_bytes_sent_uncompressed.add(4_000);
+        m.total_bytes_sent_compressed.add(1_000);
+        m.peer_rate_limited_messages.add(2);
+        m.duplicate_requests_avoided.add(3);
+        m.sync_retries.add(2);
+        m.sync_checkpoint_failures.inc();
+
+        let epoch = epoch.finish(&m);
+        assert_eq!(epoch.blocks_this_epoch(), 3);
+        assert_eq!(epoch.missed_proposals_this_epoch(), 1);
+        assert_eq!(epoch.consensus_rounds_observed_this_epoch(), 1);
+        assert_eq!(epoch.finality_observed_this_epoch(), 1);
+        assert_eq!(epoch.fees_burned_this_epoch(), 500);
+        assert_eq!(epoch.fees_treasury_this_epoch(), 200);
+        assert_eq!(epoch.fees_dev_this_epoch(), 100);
+        assert_eq!(epoch.fees_validator_this_epoch(), 200);
+        assert_eq!(epoch.fees_collected_this_epoch(), 1_000);
+        assert_eq!(epoch.cache_evictions_this_epoch(), 9);
+        assert_eq!(epoch.accounts_archived_this_epoch(), 4);
+        assert_eq!(epoch.accounts_revived_this_epoch(), 1);
+        assert_eq!(epoch.bytes_sent_uncompressed_this_epoch(), 4_000);
+        assert_eq!(epoch.bytes_sent_compressed_this_epoch(), 1_000);
+        assert_eq!(epoch.peer_rate_limited_messages_this_epoch(), 2);
+        assert_eq!(epoch.sync_duplicate_requests_avoided_this_epoch(), 3);
+        assert_eq!(epoch.sync_retries_this_epoch(), 2);
+        assert_eq!(epoch.sync_checkpoint_failures_this_epoch(), 1);
+
+        // The tracked epoch in progress sees the same increments.
+        let current = m.current_epoch_metrics().unwrap();
+        assert_eq!(current.blocks_this_epoch(), 3);
+        assert_eq!(current.fees_burned_this_epoch(), 500);
+
+        // A counter reset mid-epoch saturates instead of underflowing.
+        m.blocks_produced.reset();
+        assert_eq!(m.current_epoch_metrics().unwrap().blocks_this_epoch(), 0);
+    }
+
+    #[test]
+    fn test_epoch_history_ring_buffer() {
+        let m = TRv1Metrics::new();
+        assert!(m.epoch_history(5).is_empty());
+
+        for epoch in 0..12u64 {
+            m.begin_epoch(epoch);
+            m.blocks_produced.add(epoch);
+        }
+
+        // Epochs 0..=10 completed; only the last 10 are kept.
+        let all = m.epoch_history(usize::MAX);
+        assert_eq!(all.len(), EPOCH_HISTORY_LEN);
+        assert_eq!(all.first().unwrap().epoch, 1);
+        assert_eq!(all.last().unwrap().epoch, 10);
+        for summary in &all {
+            assert_eq!(summary.blocks_this_epoch(), summary.epoch);
+        }
+        // Consecutive epochs share their boundary snapshot.
+        for pair in all.windows(2) {
+            assert_eq!(pair[0].end.blocks_produced, pair[1].start.blocks_produced);
+        }
+
+        let last_five: Vec<u64> = m.epoch_history(5).iter().map(|e| e.epoch).collect();
+        assert_eq!(last_five, vec![6, 7, 8, 9, 10]);
+        assert_eq!(m.current_epoch_metrics().unwrap().epoch, 11);
+    }
+
     #[test]
     fn test_passive_stake_tier_accessor() {
         let m = TRv1Metrics::new();
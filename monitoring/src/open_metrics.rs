@@ -0,0 +1,403 @@
+//! OpenMetrics text format exporter for TRv1 metrics.
+//!
+//! Encodes a [`MetricsSnapshot`] into the [OpenMetrics text exposition
+//! format](https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md),
+//! served with [`CONTENT_TYPE`].  Unlike the [`prometheus`](crate::prometheus)
+//! encoder, family metadata names the family rather than the sample (the
+//! `_total` suffix lives on the counter sample only), units are declared with
+//! `# UNIT`, counters and histograms carry a `_created` sample, and the
+//! exposition ends with `# EOF`.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use trv1_monitoring::{TRv1Metrics, open_metrics};
+//!
+//! let metrics = TRv1Metrics::new();
+//! metrics.blocks_produced.add(42);
+//!
+//! let text = open_metrics::encode(&metrics.snapshot());
+//! let samples = open_metrics::decode(&text).unwrap();
+//! assert_eq!(samples["trv1_blocks_produced_total"], 42.0);
+//! ```
+
+use {
+    crate::{MetricsSnapshot, PASSIVE_STAKE_TIER_LABELS},
+    std::collections::HashMap,
+};
+
+/// HTTP `Content-Type` of the output of [`encode`].
+pub const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Encode a metrics snapshot into OpenMetrics text exposition format.
+pub fn encode(snap: &MetricsSnapshot) -> String {
+    let mut out = String::with_capacity(8192);
+    // OpenMetrics timestamps are seconds since the Unix epoch.
+    let created = snap.created_timestamp_ms as f64 / 1_000.0;
+
+    // -----------------------------------------------------------------------
+    // Consensus
+    // -----------------------------------------------------------------------
+    write_counter(&mut out, "trv1_blocks_produced", None,
+        "Total number of blocks produced by this validator",
+        snap.blocks_produced, created);
+
+    write_gauge(&mut out, "trv1_blocks_produced_rate", None,
+        "Blocks produced over the trailing rate window",
+        snap.blocks_produced_rate as f64);
+
+    write_histogram(&mut out, "trv1_consensus_rounds", None,
+        "Number of consensus rounds needed to finalize a block",
+        &snap.consensus_rounds_buckets, snap.consensus_rounds_sum,
+        snap.consensus_rounds_count, created);
+
+    write_histogram(&mut out, "trv1_finality_time_ms", Some("ms"),
+        "Time from proposal to commit in milliseconds",
+        &snap.finality_time_buckets, snap.finality_time_sum,
+        snap.finality_time_count, created);
+
+    write_counter(&mut out, "trv1_missed_proposals", None,
+        "Total number of missed block proposals",
+        snap.missed_proposals, created);
+
+    write_gauge(&mut out, "trv1_consensus_health_score", None,
+        "Aggregated consensus health indicator in [0, 1]",
+        snap.consensus_health_score);
+
+    // -----------------------------------------------------------------------
+    // Fee Market
+    // -----------------------------------------------------------------------
+    write_gauge(&mut out, "trv1_current_base_fee", None,
+        "Current base fee per compute unit in lamports",
+        snap.current_base_fee as f64);
+
+    write_gauge(&mut out, "trv1_block_utilization_bps", Some("bps"),
+        "Current block utilization in basis points (10000 = 100%)",
+        snap.block_utilization as f64);
+
+    write_counter(&mut out, "trv1_fees_burned", None,
+        "Total fees burned (lamports)", snap.total_fees_burned, created);
+
+    write_gauge(&mut out, "trv1_fees_burned_rate", None,
+        "Fees burned (lamports) over the trailing rate window",
+        snap.total_fees_burned_rate as f64);
+
+    write_counter(&mut out, "trv1_fees_treasury", None,
+        "Total fees sent to treasury (lamports)", snap.total_fees_treasury, created);
+
+    write_counter(&mut out, "trv1_fees_dev", None,
+        "Total fees sent to developer fund (lamports)", snap.total_fees_dev, created);
+
+    write_counter(&mut out, "trv1_fees_validator", None,
+        "Total fees distributed to validators (lamports)", snap.total_fees_validator,
+        created);
+
+    // -----------------------------------------------------------------------
+    // Storage
+    // -----------------------------------------------------------------------
+    write_gauge(&mut out, "trv1_hot_cache_size_bytes", Some("bytes"),
+        "Size of the hot (in-memory) account cache in bytes",
+        snap.hot_cache_size as f64);
+
+    write_gauge(&mut out, "trv1_warm_storage_size_bytes", Some("bytes"),
+        "Size of warm (SSD) storage in bytes",
+        snap.warm_storage_size as f64);
+
+    write_gauge(&mut out, "trv1_cold_storage_size_bytes", Some("bytes"),
+        "Size of cold (archival) storage in bytes",
+        snap.cold_storage_size as f64);
+
+    write_gauge(&mut out, "trv1_cache_hit_rate_bps", Some("bps"),
+        "Account cache hit rate in basis points (10000 = 100%)",
+        snap.cache_hit_rate as f64);
+
+    write_counter(&mut out, "trv1_cache_evictions", None,
+        "Total number of cache evictions",
+        snap.cache_evictions, created);
+
+    write_gauge(&mut out, "trv1_cache_evictions_rate", None,
+        "Cache evictions over the trailing rate window",
+        snap.cache_evictions_rate as f64);
+
+    write_counter(&mut out, "trv1_accounts_archived", None,
+        "Total number of accounts archived to cold storage",
+        snap.total_archived, created);
+
+    write_counter(&mut out, "trv1_accounts_revived", None,
+        "Total number of archived accounts revived",
+        snap.total_revived, created);
+
+    // -----------------------------------------------------------------------
+    // Staking
+    // -----------------------------------------------------------------------
+    write_gauge(&mut out, "trv1_total_staked_lamports", Some("lamports"),
+        "Total lamports staked across all validators",
+        snap.total_staked as f64);
+
+    write_gauge(&mut out, "trv1_staking_participation_rate_bps", Some("bps"),
+        "Staking participation rate in basis points",
+        snap.staking_participation_rate as f64);
+
+    write_gauge(&mut out, "trv1_active_validators", None,
+        "Number of active validators in the current set",
+        snap.active_validators as f64);
+
+    write_gauge(&mut out, "trv1_standby_validators", None,
+        "Number of standby validators",
+        snap.standby_validators as f64);
+
+    write_gauge(&mut out, "trv1_jailed_validators", None,
+        "Number of jailed validators",
+        snap.jailed_validators as f64);
+
+    // -----------------------------------------------------------------------
+    // Passive Staking
+    // -----------------------------------------------------------------------
+    write_gauge(&mut out, "trv1_passive_stake_total_lamports", Some("lamports"),
+        "Total lamports in passive staking",
+        snap.passive_stake_total as f64);
+
+    for (i, &name) in PASSIVE_STAKE_TIER_LABELS.iter().enumerate() {
+        let metric_name = format!("trv1_passive_stake_tier_{name}_lamports");
+        let help = format!("Passive stake in {name} tier (lamports)");
+        write_gauge(&mut out, &metric_name, Some("lamports"), &help,
+            snap.passive_stake_by_tier[i] as f64);
+    }
+
+    // -----------------------------------------------------------------------
+    // Network
+    // -----------------------------------------------------------------------
+    write_counter(&mut out, "trv1_net_sent_uncompressed_bytes", Some("bytes"),
+        "Consensus message bytes sent, before compression",
+        snap.total_bytes_sent_uncompressed, created);
+
+    write_counter(&mut out, "trv1_net_sent_compressed_bytes", Some("bytes"),
+        "Consensus message bytes sent, after compression",
+        snap.total_bytes_sent_compressed, created);
+
+    write_counter(&mut out, "trv1_net_peer_rate_limited_messages", None,
+        "Inbound consensus messages dropped by per-peer rate limiting",
+        snap.peer_rate_limited_messages, created);
+
+    write_counter(&mut out, "trv1_net_sync_duplicate_requests_avoided", None,
+        "Block sync requests skipped because the height was already in flight",
+        snap.duplicate_requests_avoided, created);
+
+    write_counter(&mut out, "trv1_net_sync_retries", None,
+        "Block sync requests re-sent after a response timeout",
+        snap.sync_retries, created);
+
+    write_counter(&mut out, "trv1_net_sync_checkpoint_failures", None,
+        "Synced blocks that did not match a sync checkpoint",
+        snap.sync_checkpoint_failures, created);
+
+    out.push_str("# EOF\n");
+    out
+}
+
+/// Parse an OpenMetrics exposition into a map from sample (name plus label
+/// set, e.g. `trv1_consensus_rounds_bucket{le="1.0"}`) to value.
+///
+/// Metadata lines are skipped.  Fails on a malformed sample line or a missing
+/// `# EOF` terminator.  Intended for tests and tooling, not as a general
+/// OpenMetrics parser.
+pub fn decode(text: &str) -> Result<HashMap<String, f64>, String> {
+    let mut samples = HashMap::new();
+    let mut terminated = false;
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index.saturating_add(1);
+        if terminated {
+            return Err(format!("line {line_number}: content after # EOF"));
+        }
+        if line == "# EOF" {
+            terminated = true;
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+        // Label values we emit never contain `}` or whitespace.
+        let split_at = match line.find('}') {
+            Some(close) => close.saturating_add(1),
+            None => line
+                .find(' ')
+                .ok_or_else(|| format!("line {line_number}: missing value"))?,
+        };
+        let (name, rest) = line.split_at(split_at);
+        let value = rest
+            .split_whitespace()
+            .next()
+            .ok_or_else(|| format!("line {line_number}: missing value"))?;
+        let value = value
+            .parse::<f64>()
+            .map_err(|err| format!("line {line_number}: invalid value {value:?}: {err}"))?;
+        samples.insert(name.to_string(), value);
+    }
+    if !terminated {
+        return Err("missing # EOF".to_string());
+    }
+    Ok(samples)
+}
+
+// ---------------------------------------------------------------------------
+// Helper writers
+// ---------------------------------------------------------------------------
+
+fn write_metadata(out: &mut String, name: &str, kind: &str, unit: Option<&str>, help: &str) {
+    out.push_str(&format!("# TYPE {name} {kind}\n"));
+    if let Some(unit) = unit {
+        out.push_str(&format!("# UNIT {name} {unit}\n"));
+    }
+    out.push_str(&format!("# HELP {name} {help}\n"));
+}
+
+fn write_counter(
+    out: &mut String,
+    name: &str,
+    unit: Option<&str>,
+    help: &str,
+    value: u64,
+    created: f64,
+) {
+    write_metadata(out, name, "counter", unit, help);
+    out.push_str(&format!("{name}_total {value}\n"));
+    out.push_str(&format!("{name}_created {}\n", format_float(created)));
+}
+
+fn write_gauge(out: &mut String, name: &str, unit: Option<&str>, help: &str, value: f64) {
+    write_metadata(out, name, "gauge", unit, help);
+    out.push_str(&format!("{name} {}\n", format_float(value)));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_histogram(
+    out: &mut String,
+    name: &str,
+    unit: Option<&str>,
+    help: &str,
+    buckets: &[(f64, u64)],
+    sum: f64,
+    count: u64,
+    created: f64,
+) {
+    write_metadata(out, name, "histogram", unit, help);
+    for (bound, cumulative_count) in buckets.iter().filter(|(bound, _)| bound.is_finite()) {
+        out.push_str(&format!(
+            "{name}_bucket{{le=\"{}\"}} {cumulative_count}\n",
+            format_float(*bound)
+        ));
+    }
+    // The +Inf bucket is mandatory and must equal `_count`.
+    out.push_str(&format!("{name}_bucket{{le=\"+Inf\"}} {count}\n"));
+    out.push_str(&format!("{name}_count {count}\n"));
+    out.push_str(&format!("{name}_sum {}\n", format_float(sum)));
+    out.push_str(&format!("{name}_created {}\n", format_float(created)));
+}
+
+/// OpenMetrics spells non-finite values `+Inf`, `-Inf` and `NaN`, and
+/// canonical `le` values always carry a decimal point (`1.0`, not `1`).
+fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        format!("{value:?}")
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TRv1Metrics;
+
+    #[test]
+    fn test_encode_openmetrics_framing() {
+        let metrics = TRv1Metrics::new();
+        metrics.blocks_produced.add(7);
+        let text = encode(&metrics.snapshot());
+
+        assert!(text.ends_with("# EOF\n"));
+        assert!(!text.contains("\n\n"), "OpenMetrics forbids blank lines");
+        // Family metadata names the family; the sample carries `_total`.
+        assert!(text.contains("# TYPE trv1_blocks_produced counter\n"));
+        assert!(text.contains("trv1_blocks_produced_total 7\n"));
+        assert!(text.contains("trv1_blocks_produced_created "));
+        assert!(text.contains("# UNIT trv1_finality_time_ms ms\n"));
+        assert!(text.contains("# UNIT trv1_hot_cache_size_bytes bytes\n"));
+        assert!(text.contains("trv1_consensus_rounds_created "));
+
+        // TYPE precedes every other line of its family.
+        let type_pos = text.find("# TYPE trv1_consensus_rounds histogram").unwrap();
+        let sample_pos = text.find("trv1_consensus_rounds_bucket").unwrap();
+        assert!(type_pos < sample_pos);
+    }
+
+    #[test]
+    fn test_decode_roundtrip() {
+        let metrics = TRv1Metrics::new();
+        metrics.blocks_produced.add(12);
+        metrics.missed_proposals.add(3);
+        metrics.consensus_rounds.observe(1.0);
+        metrics.consensus_rounds.observe(3.0);
+        metrics.consensus_rounds.observe(12.0);
+        metrics.finality_time_ms.observe(420.0);
+        metrics.finality_time_ms.observe(1_500.0);
+        metrics.current_base_fee.set(7_500);
+        metrics.block_utilization.set(6_250);
+        metrics.total_fees_burned.add(1_000_000);
+        metrics.cache_evictions.add(9);
+        metrics.cache_evictions_rate.add(9);
+        metrics.active_validators.set(150);
+        metrics.passive_stake_tier_3.set(-1);
+        metrics.total_bytes_sent_uncompressed.add(65_536);
+        metrics.total_bytes_sent_compressed.add(16_384);
+
+        let snap = metrics.snapshot();
+        let samples = decode(&encode(&snap)).unwrap();
+
+        assert_eq!(samples["trv1_blocks_produced_total"], 12.0);
+        assert_eq!(samples["trv1_missed_proposals_total"], 3.0);
+        assert_eq!(samples["trv1_fees_burned_total"], 1_000_000.0);
+        assert_eq!(samples["trv1_cache_evictions_total"], 9.0);
+        assert_eq!(samples["trv1_cache_evictions_rate"], 9.0);
+        assert_eq!(samples["trv1_current_base_fee"], 7_500.0);
+        assert_eq!(samples["trv1_block_utilization_bps"], 6_250.0);
+        assert_eq!(samples["trv1_active_validators"], 150.0);
+        assert_eq!(samples["trv1_passive_stake_tier_180d_lamports"], -1.0);
+        assert_eq!(samples["trv1_net_sent_uncompressed_bytes_total"], 65_536.0);
+        assert_eq!(samples["trv1_net_sent_compressed_bytes_total"], 16_384.0);
+        assert_eq!(samples["trv1_consensus_health_score"], snap.consensus_health_score);
+        let created = snap.created_timestamp_ms as f64 / 1_000.0;
+        assert_eq!(samples["trv1_blocks_produced_created"], created);
+        assert_eq!(samples["trv1_finality_time_ms_created"], created);
+
+        // Histogram buckets, including the one beyond the largest bound.
+        assert_eq!(samples["trv1_consensus_rounds_bucket{le=\"1.0\"}"], 1.0);
+        assert_eq!(samples["trv1_consensus_rounds_bucket{le=\"3.0\"}"], 2.0);
+        assert_eq!(samples["trv1_consensus_rounds_bucket{le=\"10.0\"}"], 2.0);
+        assert_eq!(samples["trv1_consensus_rounds_bucket{le=\"+Inf\"}"], 3.0);
+        assert_eq!(samples["trv1_consensus_rounds_count"], 3.0);
+        assert_eq!(samples["trv1_consensus_rounds_sum"], 16.0);
+        assert_eq!(samples["trv1_finality_time_ms_count"], 2.0);
+        assert_eq!(samples["trv1_finality_time_ms_sum"], 1_920.0);
+        for (bound, count) in &snap.finality_time_buckets {
+            let key = format!("trv1_finality_time_ms_bucket{{le=\"{}\"}}", format_float(*bound));
+            assert_eq!(samples[&key], *count as f64, "{key}");
+        }
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed_input() {
+        assert!(decode("trv1_active_validators 1\n").is_err());
+        assert!(decode("trv1_active_validators one\n# EOF\n").is_err());
+        assert!(decode("# EOF\ntrv1_active_validators 1\n").is_err());
+        assert_eq!(decode("# EOF\n").unwrap().len(), 0);
+    }
+}
@@ -17,7 +17,10 @@
 //! // Serve `text` on /metrics endpoint
 //! ```
 
-use crate::MetricsSnapshot;
+use crate::{registry::Registry, MetricsSnapshot, PASSIVE_STAKE_TIER_LABELS};
+
+/// HTTP `Content-Type` of the output of [`encode`].
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
 
 /// Encode a metrics snapshot into Prometheus text exposition format.
 pub fn encode(snap: &MetricsSnapshot) -> String {
@@ -30,6 +33,10 @@ pub fn encode(snap: &MetricsSnapshot) -> String {
         "Total number of blocks produced by this validator",
         snap.blocks_produced);
 
+    write_gauge(&mut out, "trv1_blocks_produced_rate",
+        "Blocks produced over the trailing rate window",
+        snap.blocks_produced_rate as i64);
+
     write_histogram(&mut out, "trv1_consensus_rounds",
         "Number of consensus rounds needed to finalize a block",
         &snap.consensus_rounds_buckets, snap.consensus_rounds_sum, snap.consensus_rounds_count);
@@ -42,6 +49,10 @@ pub fn encode(snap: &MetricsSnapshot) -> String {
         "Total number of missed block proposals",
         snap.missed_proposals);
 
+    write_gauge_f64(&mut out, "trv1_consensus_health_score",
+        "Aggregated consensus health indicator in [0, 1]",
+        snap.consensus_health_score);
+
     // -----------------------------------------------------------------------
     // Fee Market
     // -----------------------------------------------------------------------
@@ -56,6 +67,10 @@ pub fn encode(snap: &MetricsSnapshot) -> String {
     write_counter(&mut out, "trv1_fees_burned_total",
         "Total fees burned (lamports)", snap.total_fees_burned);
 
+    write_gauge(&mut out, "trv1_fees_burned_rate",
+        "Fees burned (lamports) over the trailing rate window",
+        snap.total_fees_burned_rate as i64);
+
     write_counter(&mut out, "trv1_fees_treasury_total",
         "Total fees sent to treasury (lamports)", snap.total_fees_treasury);
 
@@ -88,6 +103,18 @@ pub fn encode(snap: &MetricsSnapshot) -> String {
         "Total number of cache evictions",
         snap.cache_evictions);
 
+    write_gauge(&mut out, "trv1_cache_evictions_rate",
+        "Cache evictions over the trailing rate window",
+        snap.cache_evictions_rate as i64);
+
+    write_counter(&mut out, "trv1_accounts_archived_total",
+        "Total number of accounts archived to cold storage",
+        snap.total_archived);
+
+    write_counter(&mut out, "trv1_accounts_revived_total",
+        "Total number of archived accounts revived",
+        snap.total_revived);
+
     // -----------------------------------------------------------------------
     // Staking
     // -----------------------------------------------------------------------
@@ -118,13 +145,75 @@ pub fn encode(snap: &MetricsSnapshot) -> String {
         "Total lamports in passive staking",
         snap.passive_stake_total);
 
-    let tier_names = ["no_lock", "30d", "90d", "180d", "360d", "permanent"];
-    for (i, &name) in tier_names.iter().enumerate() {
+    for (i, &name) in PASSIVE_STAKE_TIER_LABELS.iter().enumerate() {
         let metric_name = format!("trv1_passive_stake_tier_{name}_lamports");
         let help = format!("Passive stake in {name} tier (lamports)");
         write_gauge(&mut out, &metric_name, &help, snap.passive_stake_by_tier[i]);
     }
 
+    // -----------------------------------------------------------------------
+    // Network
+    // -----------------------------------------------------------------------
+    write_counter(&mut out, "trv1_net_sent_uncompressed_bytes_total",
+        "Consensus message bytes sent, before compression",
+        snap.total_bytes_sent_uncompressed);
+
+    write_counter(&mut out, "trv1_net_sent_compressed_bytes_total",
+        "Consensus message bytes sent, after compression",
+        snap.total_bytes_sent_compressed);
+
+    write_counter(&mut out, "trv1_net_peer_rate_limited_messages_total",
+        "Inbound consensus messages dropped by per-peer rate limiting",
+        snap.peer_rate_limited_messages);
+
+    write_counter(&mut out, "trv1_net_sync_duplicate_requests_avoided_total",
+        "Block sync requests skipped because the height was already in flight",
+        snap.duplicate_requests_avoided);
+
+    write_counter(&mut out, "trv1_net_sync_retries_total",
+        "Block sync requests re-sent after a response timeout",
+        snap.sync_retries);
+
+    write_counter(&mut out, "trv1_net_sync_checkpoint_failures_total",
+        "Synced blocks that did not match a sync checkpoint",
+        snap.sync_checkpoint_failures);
+
+    out
+}
+
+/// Encode every metric of `registry`, sorted by name within each type, into
+/// Prometheus text exposition format.
+///
+/// Use this for a [`MetricRegistry`](crate::registry::MetricRegistry) of
+/// dynamically registered metrics, appended to the output of [`encode`].
+pub fn encode_registry(registry: &(impl Registry + ?Sized)) -> String {
+    let mut out = String::with_capacity(4096);
+
+    let mut counters: Vec<_> = registry.iter_counters().collect();
+    counters.sort_by_key(|counter| counter.name());
+    for counter in counters {
+        write_counter(&mut out, counter.name(), counter.help(), counter.get());
+    }
+
+    let mut gauges: Vec<_> = registry.iter_gauges().collect();
+    gauges.sort_by_key(|gauge| gauge.name());
+    for gauge in gauges {
+        write_gauge(&mut out, gauge.name(), gauge.help(), gauge.get());
+    }
+
+    let mut histograms: Vec<_> = registry.iter_histograms().collect();
+    histograms.sort_by_key(|histogram| histogram.name());
+    for histogram in histograms {
+        write_histogram(
+            &mut out,
+            histogram.name(),
+            histogram.help(),
+            &histogram.get_buckets(),
+            histogram.get_sum(),
+            histogram.get_count(),
+        );
+    }
+
     out
 }
 
@@ -144,6 +233,12 @@ fn write_gauge(out: &mut String, name: &str, help: &str, value: i64) {
     out.push_str(&format!("{name} {value}\n\n"));
 }
 
+fn write_gauge_f64(out: &mut String, name: &str, help: &str, value: f64) {
+    out.push_str(&format!("# HELP {name} {help}\n"));
+    out.push_str(&format!("# TYPE {name} gauge\n"));
+    out.push_str(&format!("{name} {value}\n\n"));
+}
+
 fn write_histogram(
     out: &mut String,
     name: &str,
@@ -201,6 +296,7 @@ mod tests {
         assert!(text.contains("trv1_finality_time_ms_count 1"));
         assert!(text.contains("trv1_active_validators 100"));
         assert!(text.contains("trv1_passive_stake_tier_no_lock_lamports 500000000"));
+        assert!(text.contains("# TYPE trv1_consensus_health_score gauge"));
     }
 
     #[test]
@@ -212,12 +308,15 @@ mod tests {
         // All metric families should have HELP and TYPE lines
         let expected_metrics = [
             "trv1_blocks_produced_total",
+            "trv1_blocks_produced_rate",
             "trv1_consensus_rounds",
             "trv1_finality_time_ms",
             "trv1_missed_proposals_total",
+            "trv1_consensus_health_score",
             "trv1_current_base_fee",
             "trv1_block_utilization_bps",
             "trv1_fees_burned_total",
+            "trv1_fees_burned_rate",
             "trv1_fees_treasury_total",
             "trv1_fees_dev_total",
             "trv1_fees_validator_total",
@@ -226,6 +325,9 @@ mod tests {
             "trv1_cold_storage_size_bytes",
             "trv1_cache_hit_rate_bps",
             "trv1_cache_evictions_total",
+            "trv1_cache_evictions_rate",
+            "trv1_accounts_archived_total",
+            "trv1_accounts_revived_total",
             "trv1_total_staked_lamports",
             "trv1_staking_participation_rate_bps",
             "trv1_active_validators",
@@ -238,6 +340,12 @@ mod tests {
             "trv1_passive_stake_tier_180d_lamports",
             "trv1_passive_stake_tier_360d_lamports",
             "trv1_passive_stake_tier_permanent_lamports",
+            "trv1_net_sent_uncompressed_bytes_total",
+            "trv1_net_sent_compressed_bytes_total",
+            "trv1_net_peer_rate_limited_messages_total",
+            "trv1_net_sync_duplicate_requests_avoided_total",
+            "trv1_net_sync_retries_total",
+            "trv1_net_sync_checkpoint_failures_total",
         ];
 
         for metric in &expected_metrics {
@@ -264,4 +372,64 @@ mod tests {
         assert!(text.contains("trv1_consensus_rounds_sum 9"));
         assert!(text.contains("trv1_consensus_rounds_count 3"));
     }
+
+    #[test]
+    fn test_encode_registry() {
+        let mut registry = crate::registry::MetricRegistry::new();
+        registry
+            .register_counter("plugin_events_total", "Events seen")
+            .add(3);
+        registry
+            .register_gauge("plugin_queue_depth", "Queued events")
+            .set(2);
+        registry
+            .register_histogram("plugin_latency_ms", "Handling latency", vec![1.0, 10.0])
+            .observe(4.0);
+
+        let text = encode_registry(&registry);
+        assert!(text.contains("# TYPE plugin_events_total counter\nplugin_events_total 3\n"));
+        assert!(text.contains("# TYPE plugin_queue_depth gauge\nplugin_queue_depth 2\n"));
+        assert!(text.contains("# HELP plugin_latency_ms Handling latency"));
+        assert!(text.contains("plugin_latency_ms_bucket{le=\"10\"} 1"));
+        assert!(text.contains("plugin_latency_ms_count 1"));
+    }
+
+    #[test]
+    fn test_encode_registry_matches_encode_for_trv1_metrics() {
+        let metrics = TRv1Metrics::new();
+        metrics.blocks_produced.add(42);
+        metrics.active_validators.set(100);
+        metrics.consensus_rounds.observe(2.0);
+
+        // Every registry sample of the static metrics is also in the full
+        // export, under the same name and value.
+        let full = encode(&metrics.snapshot());
+        let registry_text = encode_registry(&metrics);
+        let samples = registry_text
+            .lines()
+            .filter(|l| !l.is_empty() && !l.starts_with('#'));
+        for line in samples {
+            assert!(full.lines().any(|l| l == line), "missing {line:?}");
+        }
+    }
+
+    #[test]
+    fn test_encode_rate_counters() {
+        let metrics = TRv1Metrics::new();
+        metrics.blocks_produced_rate.add(3);
+        metrics.total_fees_burned_rate.add(5_000);
+
+        let text = encode(&metrics.snapshot());
+        for (rate, value) in [
+            (&metrics.blocks_produced_rate, 3),
+            (&metrics.total_fees_burned_rate, 5_000),
+            (&metrics.cache_evictions_rate, 0),
+        ] {
+            let name = rate.name();
+            assert!(text.contains(&format!(
+                "# HELP {name} {}\n# TYPE {name} gauge\n{name} {value}\n",
+                rate.help()
+            )));
+        }
+    }
 }
@@ -0,0 +1,257 @@
+//! Prometheus Pushgateway client for TRv1 metrics.
+//!
+//! Validators that cannot be scraped, e.g. because they sit behind NAT, push
+//! their metrics to a [Pushgateway](https://github.com/prometheus/pushgateway)
+//! instead.  Each push POSTs a snapshot to
+//! `{url}/metrics/job/{job}/instance/{instance}`, replacing the metrics the
+//! gateway holds for that job and instance.
+//!
+//! The body uses the [`prometheus`] text format rather than
+//! [`open_metrics`](crate::open_metrics): the Pushgateway only parses the
+//! classic text exposition format.
+//!
+//! ## Usage
+//!
+//! ```rust,no_run
+//! use {
+//!     std::sync::{atomic::AtomicBool, Arc},
+//!     trv1_monitoring::{
+//!         push_gateway::{self, PushGatewayConfig},
+//!         TRv1Metrics,
+//!     },
+//! };
+//!
+//! let metrics = Arc::new(TRv1Metrics::new());
+//! let exit = Arc::new(AtomicBool::new(false));
+//! let config = PushGatewayConfig {
+//!     url: "http://pushgateway:9091".to_string(),
+//!     push_interval_secs: 15,
+//!     job: "trv1-validator".to_string(),
+//!     instance: "validator-0".to_string(),
+//! };
+//! let pusher = push_gateway::start_background_pusher(config, metrics, exit);
+//! ```
+
+use {
+    crate::{prometheus, MetricsSnapshot, TRv1Metrics},
+    log::*,
+    reqwest::{blocking::Client, header::CONTENT_TYPE, StatusCode},
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread::{self, JoinHandle},
+        time::{Duration, Instant},
+    },
+    thiserror::Error,
+};
+
+/// Timeout of a single push request.
+const PUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often the background pusher checks its exit flag between pushes.
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Error)]
+pub enum PushError {
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error("push gateway rejected metrics: {status} {body}")]
+    Rejected { status: StatusCode, body: String },
+}
+
+/// Where and how often a validator pushes its metrics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PushGatewayConfig {
+    /// Base URL of the Pushgateway, e.g. `http://pushgateway:9091`.
+    pub url: String,
+    pub push_interval_secs: u64,
+    /// Value of the `job` grouping label.
+    pub job: String,
+    /// Value of the `instance` grouping label.
+    pub instance: String,
+}
+
+/// Pushes metrics snapshots for one job and instance.
+///
+/// `job` and `instance` are used as URL path segments and must not contain
+/// `/`.
+pub struct PushGatewayClient {
+    pub url: String,
+    pub job: String,
+    pub instance: String,
+    client: Client,
+}
+
+impl PushGatewayClient {
+    pub fn new(
+        url: impl Into<String>,
+        job: impl Into<String>,
+        instance: impl Into<String>,
+    ) -> Result<Self, PushError> {
+        let client = Client::builder().timeout(PUSH_TIMEOUT).build()?;
+        Ok(Self {
+            url: url.into(),
+            job: job.into(),
+            instance: instance.into(),
+            client,
+        })
+    }
+
+    pub fn from_config(config: &PushGatewayConfig) -> Result<Self, PushError> {
+        Self::new(&config.url, &config.job, &config.instance)
+    }
+
+    /// The Pushgateway endpoint of this client's grouping key.
+    pub fn push_url(&self) -> String {
+        format!(
+            "{}/metrics/job/{}/instance/{}",
+            self.url.trim_end_matches('/'),
+            self.job,
+            self.instance
+        )
+    }
+
+    /// Push `snapshot`, replacing the gateway's metrics of the same names for
+    /// this job and instance.
+    pub fn push(&self, snapshot: &MetricsSnapshot) -> Result<(), PushError> {
+        let response = self
+            .client
+            .post(self.push_url())
+            .header(CONTENT_TYPE, prometheus::CONTENT_TYPE)
+            .body(prometheus::encode(snapshot))
+            .send()?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().unwrap_or_default();
+            return Err(PushError::Rejected { status, body });
+        }
+        Ok(())
+    }
+}
+
+/// Spawn a thread that pushes a snapshot of `metrics` every
+/// `config.push_interval_secs`, starting immediately, until `exit` is set.
+///
+/// Failed pushes are logged and retried at the next interval.
+pub fn start_background_pusher(
+    config: PushGatewayConfig,
+    metrics: Arc<TRv1Metrics>,
+    exit: Arc<AtomicBool>,
+) -> JoinHandle<()> {
+    thread::Builder::new()
+        .name("trv1MetricsPush".into())
+        .spawn(move || {
+            let client = match PushGatewayClient::from_config(&config) {
+                Ok(client) => client,
+                Err(err) => {
+                    error!("push gateway client instantiation failed: {err}");
+                    return;
+                }
+            };
+            let interval = Duration::from_secs(config.push_interval_secs);
+
+            while !exit.load(Ordering::Relaxed) {
+                if let Err(err) = client.push(&metrics.snapshot()) {
+                    warn!("metrics push to {} failed: {err}", client.push_url());
+                }
+
+                let next_push = Instant::now() + interval;
+                while !exit.load(Ordering::Relaxed) && Instant::now() < next_push {
+                    thread::sleep(EXIT_POLL_INTERVAL);
+                }
+            }
+        })
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, mockito::Matcher};
+
+    #[test]
+    fn test_push_url() {
+        let client = PushGatewayClient::new("http://gateway:9091/", "trv1", "v0").unwrap();
+        assert_eq!(
+            client.push_url(),
+            "http://gateway:9091/metrics/job/trv1/instance/v0"
+        );
+    }
+
+    #[test]
+    fn test_push_sends_prometheus_body() {
+        let metrics = TRv1Metrics::new();
+        metrics.blocks_produced.add(42);
+        metrics.current_base_fee.set(5_000);
+        let snapshot = metrics.snapshot();
+
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/metrics/job/trv1-validator/instance/validator-0")
+            .match_header("content-type", prometheus::CONTENT_TYPE)
+            .match_body(Matcher::Exact(prometheus::encode(&snapshot)))
+            .with_status(200)
+            .create();
+
+        let client = PushGatewayClient::new(server.url(), "trv1-validator", "validator-0").unwrap();
+        client.push(&snapshot).unwrap();
+        mock.assert();
+    }
+
+    #[test]
+    fn test_push_rejected() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/metrics/job/trv1/instance/v0")
+            .with_status(400)
+            .with_body("text format parsing error")
+            .create();
+
+        let client = PushGatewayClient::new(server.url(), "trv1", "v0").unwrap();
+        match client.push(&TRv1Metrics::new().snapshot()) {
+            Err(PushError::Rejected { status, body }) => {
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+                assert_eq!(body, "text format parsing error");
+            }
+            other => panic!("expected rejection, got {other:?}"),
+        }
+        mock.assert();
+    }
+
+    #[test]
+    fn test_background_pusher() {
+        let metrics = Arc::new(TRv1Metrics::new());
+        metrics.blocks_produced.add(7);
+
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/metrics/job/trv1/instance/v0")
+            .match_body(Matcher::Regex(
+                "(?m)^trv1_blocks_produced_total 7$".to_string(),
+            ))
+            .with_status(200)
+            .expect(1)
+            .create();
+
+        let exit = Arc::new(AtomicBool::new(false));
+        let config = PushGatewayConfig {
+            url: server.url(),
+            // Long enough that only the initial push happens.
+            push_interval_secs: 3_600,
+            job: "trv1".to_string(),
+            instance: "v0".to_string(),
+        };
+        let pusher = start_background_pusher(config, metrics, exit.clone());
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !mock.matched() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        exit.store(true, Ordering::Relaxed);
+        pusher.join().unwrap();
+        mock.assert();
+    }
+}
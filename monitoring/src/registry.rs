@@ -0,0 +1,251 @@
+//! Dynamic metric registration.
+//!
+//! [`TRv1Metrics`](crate::TRv1Metrics) has a fixed set of fields.  Subsystems
+//! that want their own metrics, such as plugins, register them in a
+//! [`MetricRegistry`] instead and export it with
+//! [`prometheus::encode_registry`](crate::prometheus::encode_registry).  Both
+//! implement [`Registry`], so the same encoder serves either.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use trv1_monitoring::{prometheus, registry::{MetricRegistry, Registry}};
+//!
+//! let mut registry = MetricRegistry::new();
+//! let requests = registry.register_counter("plugin_requests_total", "Requests handled");
+//! requests.add(3);
+//!
+//! assert_eq!(registry.snapshot_dynamic()["plugin_requests_total"], 3.0);
+//! let text = prometheus::encode_registry(&registry);
+//! ```
+
+use {
+    crate::{Counter, Gauge, Histogram},
+    std::{collections::HashMap, sync::Arc},
+};
+
+/// A collection of metrics that can be enumerated for export.
+pub trait Registry {
+    fn iter_counters(&self) -> Box<dyn Iterator<Item = &Counter> + '_>;
+    fn iter_gauges(&self) -> Box<dyn Iterator<Item = &Gauge> + '_>;
+    fn iter_histograms(&self) -> Box<dyn Iterator<Item = &Histogram> + '_>;
+
+    /// Current values as a flat map from Prometheus sample name to value.
+    ///
+    /// Counters and gauges appear under their name; each histogram expands to
+    /// `{name}_count`, `{name}_sum` and one `{name}_bucket{le="..."}` per
+    /// bucket, matching the lines of
+    /// [`prometheus::encode_registry`](crate::prometheus::encode_registry).
+    fn snapshot_dynamic(&self) -> HashMap<String, f64> {
+        let mut samples = HashMap::new();
+        for counter in self.iter_counters() {
+            samples.insert(counter.name().to_string(), counter.get() as f64);
+        }
+        for gauge in self.iter_gauges() {
+            samples.insert(gauge.name().to_string(), gauge.get() as f64);
+        }
+        for histogram in self.iter_histograms() {
+            let name = histogram.name();
+            for (bound, count) in histogram.get_buckets() {
+                samples.insert(format!("{name}_bucket{{le=\"{bound}\"}}"), count as f64);
+            }
+            samples.insert(format!("{name}_count"), histogram.get_count() as f64);
+            samples.insert(format!("{name}_sum"), histogram.get_sum());
+        }
+        samples
+    }
+}
+
+/// Metrics registered at runtime.
+///
+/// Registration takes `&mut self` and hands back a shared handle, so the
+/// owner typically registers during setup and subsystems keep the handles
+/// they record into.  Registering a name again returns the existing metric.
+#[derive(Default)]
+pub struct MetricRegistry {
+    counters: HashMap<&'static str, Arc<Counter>>,
+    gauges: HashMap<&'static str, Arc<Gauge>>,
+    histograms: HashMap<&'static str, Arc<Histogram>>,
+}
+
+impl MetricRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a counter, or return the one already registered as `name`.
+    ///
+    /// Panics if `name` is registered as a gauge or histogram.
+    pub fn register_counter(&mut self, name: &'static str, help: &'static str) -> Arc<Counter> {
+        if let Some(counter) = self.counters.get(name) {
+            return counter.clone();
+        }
+        self.assert_unregistered(name);
+        self.counters
+            .entry(name)
+            .or_insert_with(|| Arc::new(Counter::new(name, help)))
+            .clone()
+    }
+
+    /// Register a gauge, or return the one already registered as `name`.
+    ///
+    /// Panics if `name` is registered as a counter or histogram.
+    pub fn register_gauge(&mut self, name: &'static str, help: &'static str) -> Arc<Gauge> {
+        if let Some(gauge) = self.gauges.get(name) {
+            return gauge.clone();
+        }
+        self.assert_unregistered(name);
+        self.gauges
+            .entry(name)
+            .or_insert_with(|| Arc::new(Gauge::new(name, help)))
+            .clone()
+    }
+
+    /// Register a histogram, or return the one already registered as `name`
+    /// (keeping its original buckets).
+    ///
+    /// Panics if `name` is registered as a counter or gauge.
+    pub fn register_histogram(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        buckets: Vec<f64>,
+    ) -> Arc<Histogram> {
+        if let Some(histogram) = self.histograms.get(name) {
+            return histogram.clone();
+        }
+        self.assert_unregistered(name);
+        self.histograms
+            .entry(name)
+            .or_insert_with(|| Arc::new(Histogram::new(name, help, buckets)))
+            .clone()
+    }
+
+    /// Number of registered metrics of all types.
+    pub fn len(&self) -> usize {
+        self.counters
+            .len()
+            .saturating_add(self.gauges.len())
+            .saturating_add(self.histograms.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn assert_unregistered(&self, name: &str) {
+        assert!(
+            !self.counters.contains_key(name)
+                && !self.gauges.contains_key(name)
+                && !self.histograms.contains_key(name),
+            "metric {name} is already registered with a different type"
+        );
+    }
+}
+
+impl Registry for MetricRegistry {
+    fn iter_counters(&self) -> Box<dyn Iterator<Item = &Counter> + '_> {
+        Box::new(self.counters.values().map(|counter| counter.as_ref()))
+    }
+
+    fn iter_gauges(&self) -> Box<dyn Iterator<Item = &Gauge> + '_> {
+        Box::new(self.gauges.values().map(|gauge| gauge.as_ref()))
+    }
+
+    fn iter_histograms(&self) -> Box<dyn Iterator<Item = &Histogram> + '_> {
+        Box::new(self.histograms.values().map(|histogram| histogram.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::TRv1Metrics, std::thread};
+
+    #[test]
+    fn test_registered_counter_appears_in_snapshot() {
+        let mut registry = MetricRegistry::new();
+        assert!(registry.is_empty());
+        assert!(registry.snapshot_dynamic().is_empty());
+
+        let counter = registry.register_counter("plugin_events_total", "Events seen");
+        counter.add(5);
+        let samples = registry.snapshot_dynamic();
+        assert_eq!(samples["plugin_events_total"], 5.0);
+
+        // Registered after the first snapshot.
+        let gauge = registry.register_gauge("plugin_queue_depth", "Queued events");
+        gauge.set(-2);
+        let histogram = registry.register_histogram(
+            "plugin_latency_ms",
+            "Event handling latency",
+            vec![1.0, 10.0],
+        );
+        histogram.observe(4.0);
+        assert_eq!(registry.len(), 3);
+
+        let samples = registry.snapshot_dynamic();
+        assert_eq!(samples["plugin_events_total"], 5.0);
+        assert_eq!(samples["plugin_queue_depth"], -2.0);
+        assert_eq!(samples["plugin_latency_ms_count"], 1.0);
+        assert_eq!(samples["plugin_latency_ms_sum"], 4.0);
+        assert_eq!(samples["plugin_latency_ms_bucket{le=\"1\"}"], 0.0);
+        assert_eq!(samples["plugin_latency_ms_bucket{le=\"10\"}"], 1.0);
+    }
+
+    #[test]
+    fn test_register_same_name_returns_existing() {
+        let mut registry = MetricRegistry::new();
+        let first = registry.register_counter("plugin_events_total", "Events seen");
+        let second = registry.register_counter("plugin_events_total", "Ignored");
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(second.help(), "Events seen");
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "already registered with a different type")]
+    fn test_register_name_with_different_type_panics() {
+        let mut registry = MetricRegistry::new();
+        registry.register_counter("plugin_events", "Events seen");
+        registry.register_gauge("plugin_events", "Events seen");
+    }
+
+    #[test]
+    fn test_registry_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<MetricRegistry>();
+
+        let mut registry = MetricRegistry::new();
+        let counter = registry.register_counter("plugin_events_total", "Events seen");
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| counter.inc());
+            }
+        });
+        assert_eq!(registry.snapshot_dynamic()["plugin_events_total"], 4.0);
+    }
+
+    #[test]
+    fn test_trv1_metrics_registry_matches_snapshot() {
+        let metrics = TRv1Metrics::new();
+        metrics.blocks_produced.add(42);
+        metrics.current_base_fee.set(5_000);
+        metrics.passive_stake_tier_5.set(7);
+        metrics.finality_time_ms.observe(300.0);
+
+        assert_eq!(metrics.iter_counters().count(), 15);
+        assert_eq!(metrics.iter_gauges().count(), 18);
+        assert_eq!(metrics.iter_histograms().count(), 2);
+
+        let samples = metrics.snapshot_dynamic();
+        let snapshot = metrics.snapshot();
+        assert_eq!(
+            samples["trv1_blocks_produced_total"],
+            snapshot.blocks_produced as f64
+        );
+        assert_eq!(samples["trv1_current_base_fee"], 5_000.0);
+        assert_eq!(samples["trv1_passive_stake_tier_permanent_lamports"], 7.0);
+        assert_eq!(samples["trv1_finality_time_ms_count"], 1.0);
+        assert_eq!(samples["trv1_finality_time_ms_sum"], 300.0);
+    }
+}
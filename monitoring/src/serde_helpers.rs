@@ -0,0 +1,56 @@
+//! Serde adapters for [`MetricsSnapshot`](crate::MetricsSnapshot) fields
+//! whose natural Rust shape is not a good wire shape.
+
+use {
+    crate::PASSIVE_STAKE_TIER_LABELS,
+    serde::{de::Error as _, ser::SerializeMap, Deserialize, Deserializer, Serializer},
+    std::collections::HashMap,
+};
+
+/// `[i64; 6]` indexed by tier ⇄ map keyed by tier label
+/// (`{"no_lock": .., "30d": .., ..}`).
+pub mod tier_map {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(tiers: &[i64; 6], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(tiers.len()))?;
+        for (label, value) in PASSIVE_STAKE_TIER_LABELS.iter().zip(tiers) {
+            map.serialize_entry(label, value)?;
+        }
+        map.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[i64; 6], D::Error> {
+        let mut map = HashMap::<String, i64>::deserialize(deserializer)?;
+        let mut tiers = [0i64; 6];
+        for (label, tier) in PASSIVE_STAKE_TIER_LABELS.iter().zip(tiers.iter_mut()) {
+            *tier = map
+                .remove(*label)
+                .ok_or_else(|| D::Error::missing_field(label))?;
+        }
+        if let Some(label) = map.keys().next() {
+            return Err(D::Error::unknown_field(label, &PASSIVE_STAKE_TIER_LABELS));
+        }
+        Ok(tiers)
+    }
+}
+
+/// `f64` that may be NaN ⇄ number or `null`.
+///
+/// JSON has no NaN, so an empty histogram's percentiles are written as
+/// `null` and read back as NaN.
+pub mod nan_as_null {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        if value.is_nan() {
+            serializer.serialize_none()
+        } else {
+            serializer.serialize_some(value)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::NAN))
+    }
+}
@@ -0,0 +1,48 @@
+//! Compute-unit weighted attribution of a transaction's developer fees.
+//!
+//! When a transaction invokes several programs, each program earns the
+//! fraction of the developer share matching its fraction of the compute units
+//! consumed by all invoked programs.
+
+use solana_pubkey::Pubkey;
+
+/// Share of `amount` earned by a program that consumed `program_compute_units`
+/// out of `total_compute_units`. Rounds down; returns 0 when
+/// `total_compute_units` is 0.
+pub fn compute_unit_share(
+    amount: u64,
+    program_compute_units: u64,
+    total_compute_units: u64,
+) -> u64 {
+    if total_compute_units == 0 {
+        return 0;
+    }
+    let share = u128::from(amount)
+        .saturating_mul(u128::from(program_compute_units.min(total_compute_units)))
+        .checked_div(u128::from(total_compute_units))
+        .unwrap_or(0);
+    share as u64
+}
+
+/// Splits `amount` across `invoked_programs` (program ID, compute units
+/// consumed) in proportion to compute units. Rounding dust is left
+/// unattributed.
+pub fn compute_unit_weighted_shares(
+    amount: u64,
+    invoked_programs: &[(Pubkey, u64)],
+) -> Vec<(Pubkey, u64)> {
+    let total_compute_units = invoked_programs
+        .iter()
+        .fold(0u64, |total, (_, compute_units)| {
+            total.saturating_add(*compute_units)
+        });
+    invoked_programs
+        .iter()
+        .map(|(program_id, compute_units)| {
+            (
+                *program_id,
+                compute_unit_share(amount, *compute_units, total_compute_units),
+            )
+        })
+        .collect()
+}
@@ -19,6 +19,18 @@ pub const MAX_PROGRAM_FEE_SHARE_BPS: u16 = 1_000; // 10%
 /// Total basis points — all revenue splits for a program must sum to this.
 pub const TOTAL_BPS: u16 = 10_000;
 
+/// Maximum number of recipients in a program's revenue split. Each recipient
+/// is an extra account on every claim transaction, so this keeps claims small.
+pub const MAX_REVENUE_SPLIT_RECIPIENTS: usize = 5;
+
+/// Number of epochs that must pass between two `UpdateRevenueConfig` calls.
+pub const REVENUE_CONFIG_UPDATE_COOLDOWN_EPOCHS: u64 = 1;
+
+/// Slots a pending `TransferProgramConfig` stays acceptable. At ~400 ms/slot
+/// this is roughly 2 days — enough to land a program upgrade and its
+/// authority change.
+pub const TRANSFER_ACCEPT_WINDOW_SLOTS: u64 = 432_000;
+
 // ── Fee-split schedule (basis points) ────────────────────────────────────────
 // Each constant set represents one phase of the 5-year transition.
 
@@ -48,5 +60,22 @@ pub const REVENUE_CONFIG_SEED: &[u8] = b"program_revenue_config";
 /// Seed prefix for the developer fee pool account.
 pub const FEE_POOL_SEED: &[u8] = b"developer_fee_pool";
 
+/// The runtime's developer fee collector — the only signer accepted by
+/// `CreditDeveloperFees`.  It deposits each transaction's developer share
+/// into the fee pool before crediting it.
+pub mod fee_collector {
+    solana_pubkey::declare_id!("DevFeeCo11ector11111111111111111111111111111");
+}
+
 /// Seed prefix for the epoch tracker account.
 pub const EPOCH_TRACKER_SEED: &[u8] = b"epoch_tracker";
+
+/// Seed prefix for deriving per-program `DeveloperEpochStats` PDAs.
+pub const EPOCH_STATS_SEED: &[u8] = b"developer_epoch_stats";
+
+/// Seed for the singleton `ProgramRegistryIndex` PDA.
+pub const REGISTRY_INDEX_SEED: &[u8] = b"program_registry_index";
+
+/// Maximum number of entries returned by one `GetRegistryPage` call. Keeps
+/// the serialized page well inside the 1 KiB return-data limit.
+pub const MAX_REGISTRY_PAGE_SIZE: u8 = 20;
@@ -31,7 +31,7 @@ pub enum DeveloperRewardsError {
     #[error("Revenue split contains a zero-share entry")]
     ZeroShareInSplit = 6,
 
-    #[error("Too many revenue split recipients (max 10)")]
+    #[error("Too many revenue split recipients (max 5)")]
     TooManySplitRecipients = 7,
 
     #[error("Duplicate recipient in revenue splits")]
@@ -69,6 +69,31 @@ pub enum DeveloperRewardsError {
 
     #[error("Invalid PDA derivation")]
     InvalidPda = 18,
+
+    // ── Revenue config updates ───────────────────────────────────────────
+    #[error("Revenue config was updated too recently")]
+    ConfigUpdateCooldown = 19,
+
+    #[error("Revenue splits already configured; use UpdateRevenueConfig")]
+    SplitsAlreadyConfigured = 20,
+
+    #[error("Recipient account does not match the revenue config")]
+    RecipientMismatch = 21,
+
+    // ── Fee attribution ──────────────────────────────────────────────────
+    #[error("Program compute units exceed the transaction's invoked compute units")]
+    InvalidComputeUnitShare = 22,
+
+    // ── Authority transfer ───────────────────────────────────────────────
+    #[error("No revenue config transfer is pending")]
+    NoPendingTransfer = 23,
+
+    #[error("Revenue config transfer was not accepted in time")]
+    TransferWindowExpired = 24,
+
+    // ── Fee crediting ────────────────────────────────────────────────────
+    #[error("Signer is not the runtime's developer fee collector")]
+    UnauthorizedFeeCollector = 25,
 }
 
 impl From<DeveloperRewardsError> for solana_program_error::ProgramError {
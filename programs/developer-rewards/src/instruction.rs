@@ -14,13 +14,17 @@ pub enum DeveloperRewardsInstruction {
     /// Only callable by the program's **upgrade authority**.
     ///
     /// Accounts expected:
-    ///   0. `[signer]`   Upgrade authority of the target program.
+    ///   0. `[writable, signer]` Upgrade authority of the target program;
+    ///                    pays rent for the registry index.
     ///   1. `[writable]` ProgramRevenueConfig PDA
     ///                    (`[REVENUE_CONFIG_SEED, program_id]`).
     ///   2. `[]`         The target program's programdata account
     ///                    (to verify upgrade authority).
     ///   3. `[]`         System program (for account creation).
     ///   4. `[]`         Clock sysvar.
+    ///   5. `[writable]` ProgramRegistryIndex PDA (`[REGISTRY_INDEX_SEED]`);
+    ///                    created on first use and grown by one entry, with
+    ///                    the upgrade authority topping up its rent.
     RegisterRevenueRecipient {
         /// The program to register.
         program_id: Pubkey,
@@ -42,9 +46,11 @@ pub enum DeveloperRewardsInstruction {
         new_recipient: Pubkey,
     },
 
-    /// Set up multi-recipient revenue splits for a program.
+    /// Set up the initial multi-recipient revenue split for a program.
     ///
-    /// Shares must sum to 10 000 bps. Replaces any existing splits.
+    /// Shares must sum to 10 000 bps across at most
+    /// `MAX_REVENUE_SPLIT_RECIPIENTS` entries. Fails if splits are already
+    /// configured — use `UpdateRevenueConfig` to change them.
     /// Only callable by `update_authority`.
     ///
     /// Accounts expected:
@@ -60,16 +66,19 @@ pub enum DeveloperRewardsInstruction {
     /// Claim accumulated developer fees for a program.
     ///
     /// Transfers lamports from the developer fee pool to the configured
-    /// recipient(s).
+    /// recipient(s), one transfer per split entry. Rounding dust goes to the
+    /// first recipient.
     ///
     /// Accounts expected:
     ///   0. `[signer]`   Revenue recipient (or any signer — permissionless
     ///                    claiming is safe since funds always go to the
     ///                    configured recipient).
     ///   1. `[writable]` ProgramRevenueConfig PDA.
-    ///   2. `[writable]` Developer fee pool account (PDA).
-    ///   3. `[writable]` Recipient account(s) — one per split entry, or the
-    ///                    primary `revenue_recipient` if no splits.
+    ///   2. `[writable]` Developer fee pool PDA (`[FEE_POOL_SEED]`).
+    ///   3. `[writable]` EpochFeeTracker PDA (`[EPOCH_TRACKER_SEED]`).
+    ///   4. `[writable]` Recipient account(s) — one per split entry, in split
+    ///                    order, or the primary `revenue_recipient` if no
+    ///                    splits.
     ClaimDeveloperFees {
         /// The program whose fees to claim.
         program_id: Pubkey,
@@ -79,17 +88,99 @@ pub enum DeveloperRewardsInstruction {
     /// transaction. This is invoked by the runtime fee-distribution logic,
     /// **not** by external users.
     ///
+    /// `amount` is the developer share of the whole transaction. The program
+    /// is credited `amount × compute_units_consumed / total_compute_units`,
+    /// its compute-unit weighted share among all invoked programs.
+    ///
+    /// A program is never credited more than `MAX_PROGRAM_FEE_SHARE_BPS` of
+    /// the epoch's total developer fees. Any amount above that headroom is
+    /// moved from the developer fee pool to the burn pool instead.
+    ///
+    /// Only the runtime's `fee_collector` may credit, and never more than the
+    /// fee pool holds beyond the fees already owed to programs.
+    ///
     /// Accounts expected:
-    ///   0. `[signer]`   Fee-payer (runtime-injected).
+    ///   0. `[signer]`   The runtime's developer fee collector.
     ///   1. `[writable]` ProgramRevenueConfig PDA.
-    ///   2. `[writable]` EpochFeeTracker PDA.
+    ///   2. `[writable]` EpochFeeTracker PDA (`[EPOCH_TRACKER_SEED]`).
     ///   3. `[]`         Clock sysvar.
+    ///   4. `[writable]` DeveloperEpochStats PDA
+    ///                    (`[EPOCH_STATS_SEED, program_id]`).
+    ///   5. `[writable]` Developer fee pool PDA (`[FEE_POOL_SEED]`).
+    ///   6. `[writable]` Burn pool (the incinerator).
     CreditDeveloperFees {
         /// The program being credited.
         program_id: Pubkey,
-        /// Lamports to credit.
+        /// Developer share of the transaction's fees (lamports), before
+        /// weighting by compute units.
         amount: u64,
         /// Compute units the program consumed in this transaction.
         compute_units_consumed: u64,
+        /// Compute units consumed by all programs the transaction invoked.
+        total_compute_units: u64,
+    },
+
+    /// Replace the revenue split of a program.
+    ///
+    /// Same validation as `AddRevenueSplit`. Only callable by
+    /// `update_authority`, and at most once per
+    /// `REVENUE_CONFIG_UPDATE_COOLDOWN_EPOCHS` since registration or the
+    /// previous update.
+    ///
+    /// Accounts expected:
+    ///   0. `[signer]`   Current update authority.
+    ///   1. `[writable]` ProgramRevenueConfig PDA.
+    ///   2. `[]`         Clock sysvar.
+    UpdateRevenueConfig {
+        /// The program whose splits to replace.
+        program_id: Pubkey,
+        /// New set of revenue splits.
+        recipients: Vec<RevenueSplit>,
+    },
+
+    /// Read a page of the program registry.
+    ///
+    /// Read-only. Sets the transaction return data to a borsh-serialized
+    /// `RegistryPage` holding at most `MAX_REGISTRY_PAGE_SIZE` program IDs.
+    ///
+    /// Accounts expected:
+    ///   0. `[]`         ProgramRegistryIndex PDA.
+    GetRegistryPage {
+        /// Index of the first entry to return.
+        offset: u64,
+        /// Maximum entries to return; clamped to `MAX_REGISTRY_PAGE_SIZE`.
+        limit: u8,
+    },
+
+    /// Nominate a new update authority for a program's revenue config.
+    ///
+    /// Step one of a two-step handover, typically signed by the old authority
+    /// before a program upgrade that changes the upgrade authority. Replaces
+    /// any earlier nomination. Only callable by `update_authority`.
+    ///
+    /// Accounts expected:
+    ///   0. `[signer]`   Current update authority.
+    ///   1. `[writable]` ProgramRevenueConfig PDA.
+    ///   2. `[]`         Clock sysvar.
+    TransferProgramConfig {
+        /// The program whose config to hand over.
+        program_id: Pubkey,
+        /// The authority that may accept the config.
+        new_authority: Pubkey,
+    },
+
+    /// Accept a pending revenue config transfer and become its update
+    /// authority.
+    ///
+    /// Must be signed by the nominated authority within
+    /// `TRANSFER_ACCEPT_WINDOW_SLOTS` of the `TransferProgramConfig` call.
+    ///
+    /// Accounts expected:
+    ///   0. `[signer]`   Nominated authority.
+    ///   1. `[writable]` ProgramRevenueConfig PDA.
+    ///   2. `[]`         Clock sysvar.
+    AcceptProgramConfig {
+        /// The program whose config to accept.
+        program_id: Pubkey,
     },
 }
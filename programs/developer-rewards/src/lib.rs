@@ -20,10 +20,24 @@
 //!   first ~7 days (≈ 1 512 000 slots).
 //! * **10 % per-epoch cap** — no single program may receive more than 10 % of
 //!   total developer fees in one epoch.
+//!
+//! # Multi-program transactions
+//!
+//! A transaction's developer share is split across the programs it invoked in
+//! proportion to the compute units each consumed (see [`attribution`]), so a
+//! small helper program invoked next to a heavy protocol earns a small share.
+//!
+//! # Program registry
+//!
+//! Every registration also appends the program to a singleton
+//! [`ProgramRegistryIndex`](state::ProgramRegistryIndex) PDA. Clients page
+//! through it with the read-only `GetRegistryPage` instruction, which returns
+//! at most 20 program IDs per call as return data.
 
 #![cfg(feature = "agave-unstable-api")]
 #![allow(clippy::arithmetic_side_effects)]
 
+pub mod attribution;
 pub mod constants;
 pub mod error;
 pub mod instruction;
@@ -4,13 +4,18 @@
 
 use {
     crate::{
+        attribution::compute_unit_share,
         constants::{
-            COOLDOWN_SLOTS, MAX_PROGRAM_FEE_SHARE_BPS,
-            MIN_COMPUTE_UNITS_THRESHOLD, TOTAL_BPS,
+            fee_collector, COOLDOWN_SLOTS, MAX_PROGRAM_FEE_SHARE_BPS, MAX_REVENUE_SPLIT_RECIPIENTS,
+            MIN_COMPUTE_UNITS_THRESHOLD, REVENUE_CONFIG_UPDATE_COOLDOWN_EPOCHS, TOTAL_BPS,
         },
         error::DeveloperRewardsError,
         instruction::DeveloperRewardsInstruction,
-        state::{EpochFeeTracker, ProgramRevenueConfig, RevenueSplit},
+        state::{
+            find_epoch_stats_address, find_epoch_tracker_address, find_fee_pool_address,
+            find_registry_index_address, find_revenue_config_address, DeveloperEpochStats,
+            EpochFeeTracker, ProgramRegistryIndex, ProgramRevenueConfig, RevenueSplit,
+        },
     },
     borsh::BorshDeserialize,
     solana_instruction::error::InstructionError,
@@ -18,7 +23,9 @@ use {
         declare_process_instruction, invoke_context::InvokeContext,
     },
     solana_pubkey::Pubkey,
+    solana_sdk_ids::{incinerator, system_program},
     solana_svm_log_collector::ic_msg,
+    solana_system_interface::instruction as system_instruction,
 };
 
 /// Default compute-unit budget for developer-rewards instructions.
@@ -66,7 +73,32 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
             program_id,
             amount,
             compute_units_consumed,
-        } => process_credit(invoke_context, &program_id, amount, compute_units_consumed),
+            total_compute_units,
+        } => process_credit(
+            invoke_context,
+            &program_id,
+            amount,
+            compute_units_consumed,
+            total_compute_units,
+        ),
+
+        DeveloperRewardsInstruction::UpdateRevenueConfig {
+            program_id,
+            recipients,
+        } => process_update_revenue_config(invoke_context, &program_id, &recipients),
+
+        DeveloperRewardsInstruction::GetRegistryPage { offset, limit } => {
+            process_get_registry_page(invoke_context, offset, limit)
+        }
+
+        DeveloperRewardsInstruction::TransferProgramConfig {
+            program_id,
+            new_authority,
+        } => process_transfer_program_config(invoke_context, &program_id, &new_authority),
+
+        DeveloperRewardsInstruction::AcceptProgramConfig { program_id } => {
+            process_accept_program_config(invoke_context, &program_id)
+        }
     }
 });
 
@@ -75,7 +107,7 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
 // ─────────────────────────────────────────────────────────────────────────────
 
 fn process_register(
-    invoke_context: &InvokeContext,
+    invoke_context: &mut InvokeContext,
     program_id: &Pubkey,
     recipient: &Pubkey,
 ) -> Result<(), InstructionError> {
@@ -91,6 +123,9 @@ fn process_register(
     // Account 1: ProgramRevenueConfig PDA (writable)
     {
         let config_account = instruction_context.try_borrow_instruction_account(1)?;
+        if *config_account.get_key() != find_revenue_config_address(program_id).0 {
+            return Err(DeveloperRewardsError::InvalidPda.into());
+        }
         let existing_data = config_account.get_data();
         if !existing_data.is_empty() && existing_data[0] != 0 {
             return Err(DeveloperRewardsError::ConfigAlreadyExists.into());
@@ -117,7 +152,7 @@ fn process_register(
         }
     }
 
-    // Get current slot from Clock sysvar
+    // Get current slot/epoch from Clock sysvar
     let clock = invoke_context.get_sysvar_cache().get_clock()?;
     let current_slot = clock.slot;
 
@@ -134,6 +169,10 @@ fn process_register(
         last_epoch: 0,
         eligible_after_slot: current_slot.saturating_add(COOLDOWN_SLOTS),
         unclaimed_fees: 0,
+        last_config_update_epoch: clock.epoch,
+        registration_slot: current_slot,
+        pending_update_authority: None,
+        pending_transfer_slot: 0,
     };
 
     // Serialize into the account.
@@ -145,6 +184,69 @@ fn process_register(
         return Err(DeveloperRewardsError::AccountDataTooSmall.into());
     }
     config_account.set_data_from_slice(&serialized)?;
+    drop(config_account);
+
+    // Account 5: ProgramRegistryIndex PDA (writable) — append the program.
+    let (index_key, index_exists, index_lamports, mut index) = {
+        let index_account = instruction_context.try_borrow_instruction_account(5)?;
+        if *index_account.get_key() != find_registry_index_address().0 {
+            return Err(DeveloperRewardsError::InvalidPda.into());
+        }
+        let index_exists = *index_account.get_owner() == id();
+        let index = if index_exists {
+            deserialize_registry_index(&index_account)?
+        } else {
+            ProgramRegistryIndex {
+                version: 1,
+                ..Default::default()
+            }
+        };
+        (
+            *index_account.get_key(),
+            index_exists,
+            index_account.get_lamports(),
+            index,
+        )
+    };
+    if !index.entries.contains(program_id) {
+        index.entries.push(*program_id);
+        let serialized = borsh::to_vec(&index)
+            .map_err(|_| InstructionError::InvalidAccountData)?;
+
+        // The registering authority pays for the index: it creates the
+        // account on first use and tops up rent as the index grows.
+        if instruction_context.get_key_of_instruction_account(3)? != &system_program::id() {
+            return Err(InstructionError::IncorrectProgramId);
+        }
+        let rent_exempt = invoke_context
+            .get_sysvar_cache()
+            .get_rent()?
+            .minimum_balance(serialized.len());
+        if !index_exists {
+            // As a builtin, this program signs for its own PDA by naming it.
+            invoke_context.native_invoke(
+                system_instruction::create_account(
+                    &signer_key,
+                    &index_key,
+                    rent_exempt,
+                    serialized.len() as u64,
+                    &id(),
+                ),
+                &[signer_key, index_key],
+            )?;
+        } else if rent_exempt > index_lamports {
+            invoke_context.native_invoke(
+                system_instruction::transfer(&signer_key, &index_key, rent_exempt - index_lamports),
+                &[signer_key],
+            )?;
+        }
+
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+        let mut index_account = instruction_context.try_borrow_instruction_account(5)?;
+        // `set_data_from_slice` resizes the account to fit the grown index.
+        index_account.set_data_from_slice(&serialized)?;
+    }
 
     ic_msg!(
         invoke_context,
@@ -156,6 +258,41 @@ fn process_register(
     Ok(())
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// GetRegistryPage
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn process_get_registry_page(
+    invoke_context: &mut InvokeContext,
+    offset: u64,
+    limit: u8,
+) -> Result<(), InstructionError> {
+    let page = {
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+
+        // Account 0: ProgramRegistryIndex PDA
+        let index_account = instruction_context.try_borrow_instruction_account(0)?;
+        if *index_account.get_key() != find_registry_index_address().0 {
+            return Err(DeveloperRewardsError::InvalidPda.into());
+        }
+        deserialize_registry_index(&index_account)?.page(offset, limit)
+    };
+
+    ic_msg!(
+        invoke_context,
+        "Registry page at offset {}: {} of {} programs",
+        offset,
+        page.entries.len(),
+        page.total_entries
+    );
+
+    let return_data = borsh::to_vec(&page).map_err(|_| InstructionError::GenericError)?;
+    invoke_context
+        .transaction_context
+        .set_return_data(id(), return_data)
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // UpdateRevenueRecipient
 // ─────────────────────────────────────────────────────────────────────────────
@@ -196,38 +333,104 @@ fn process_update_recipient(
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// AddRevenueSplit
+// TransferProgramConfig / AcceptProgramConfig
 // ─────────────────────────────────────────────────────────────────────────────
 
-fn process_add_revenue_split(
+fn process_transfer_program_config(
     invoke_context: &InvokeContext,
     _program_id: &Pubkey,
-    splits: &[RevenueSplit],
+    new_authority: &Pubkey,
 ) -> Result<(), InstructionError> {
     let transaction_context = &invoke_context.transaction_context;
     let instruction_context = transaction_context.get_current_instruction_context()?;
 
-    // Validate split vector.
-    if splits.len() > 10 {
-        return Err(DeveloperRewardsError::TooManySplitRecipients.into());
+    // Account 0: signer (must be update authority)
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(InstructionError::MissingRequiredSignature);
     }
+    let signer_key = *instruction_context.get_key_of_instruction_account(0)?;
 
-    let mut total_bps: u32 = 0;
-    for (i, s) in splits.iter().enumerate() {
-        if s.share_bps == 0 {
-            return Err(DeveloperRewardsError::ZeroShareInSplit.into());
-        }
-        total_bps = total_bps.saturating_add(s.share_bps as u32);
+    // Account 1: ProgramRevenueConfig PDA (writable)
+    let mut config_account = instruction_context.try_borrow_instruction_account(1)?;
+    let mut config = deserialize_config(&config_account)?;
 
-        for other in splits.iter().skip(i + 1) {
-            if s.recipient == other.recipient {
-                return Err(DeveloperRewardsError::DuplicateRecipient.into());
-            }
-        }
+    if config.update_authority != signer_key {
+        return Err(DeveloperRewardsError::UnauthorizedUpdateAuthority.into());
     }
-    if total_bps != TOTAL_BPS as u32 {
-        return Err(DeveloperRewardsError::InvalidSplitTotal.into());
+
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    config.pending_update_authority = Some(*new_authority);
+    config.pending_transfer_slot = clock.slot;
+    serialize_config(&config, &mut config_account)?;
+
+    ic_msg!(
+        invoke_context,
+        "Revenue config for program {} offered to {} at slot {}",
+        config.program_id,
+        new_authority,
+        clock.slot
+    );
+
+    Ok(())
+}
+
+fn process_accept_program_config(
+    invoke_context: &InvokeContext,
+    _program_id: &Pubkey,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    // Account 0: signer (must be the nominated authority)
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(InstructionError::MissingRequiredSignature);
     }
+    let signer_key = *instruction_context.get_key_of_instruction_account(0)?;
+
+    // Account 1: ProgramRevenueConfig PDA (writable)
+    let mut config_account = instruction_context.try_borrow_instruction_account(1)?;
+    let mut config = deserialize_config(&config_account)?;
+
+    let Some(pending_authority) = config.pending_update_authority else {
+        return Err(DeveloperRewardsError::NoPendingTransfer.into());
+    };
+    if pending_authority != signer_key {
+        return Err(DeveloperRewardsError::UnauthorizedUpdateAuthority.into());
+    }
+
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    if !config.transfer_window_open(clock.slot) {
+        return Err(DeveloperRewardsError::TransferWindowExpired.into());
+    }
+
+    config.update_authority = pending_authority;
+    config.pending_update_authority = None;
+    config.pending_transfer_slot = 0;
+    serialize_config(&config, &mut config_account)?;
+
+    ic_msg!(
+        invoke_context,
+        "Revenue config for program {} accepted by {}",
+        config.program_id,
+        pending_authority
+    );
+
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// AddRevenueSplit
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn process_add_revenue_split(
+    invoke_context: &InvokeContext,
+    _program_id: &Pubkey,
+    splits: &[RevenueSplit],
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    validate_revenue_splits(splits)?;
 
     // Account 0: signer (must be update authority)
     if !instruction_context.is_instruction_account_signer(0)? {
@@ -242,8 +445,13 @@ fn process_add_revenue_split(
     if config.update_authority != signer_key {
         return Err(DeveloperRewardsError::UnauthorizedUpdateAuthority.into());
     }
+    if !config.revenue_splits.is_empty() {
+        return Err(DeveloperRewardsError::SplitsAlreadyConfigured.into());
+    }
 
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
     config.revenue_splits = splits.to_vec();
+    config.last_config_update_epoch = clock.epoch;
     serialize_config(&config, &mut config_account)?;
 
     ic_msg!(
@@ -256,6 +464,57 @@ fn process_add_revenue_split(
     Ok(())
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// UpdateRevenueConfig
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn process_update_revenue_config(
+    invoke_context: &InvokeContext,
+    _program_id: &Pubkey,
+    recipients: &[RevenueSplit],
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    validate_revenue_splits(recipients)?;
+
+    // Account 0: signer (must be update authority)
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(InstructionError::MissingRequiredSignature);
+    }
+    let signer_key = *instruction_context.get_key_of_instruction_account(0)?;
+
+    // Account 1: ProgramRevenueConfig PDA (writable)
+    let mut config_account = instruction_context.try_borrow_instruction_account(1)?;
+    let mut config = deserialize_config(&config_account)?;
+
+    if config.update_authority != signer_key {
+        return Err(DeveloperRewardsError::UnauthorizedUpdateAuthority.into());
+    }
+
+    // Anti-churn: one update per cooldown window.
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    let next_update_epoch = config
+        .last_config_update_epoch
+        .saturating_add(REVENUE_CONFIG_UPDATE_COOLDOWN_EPOCHS);
+    if clock.epoch < next_update_epoch {
+        return Err(DeveloperRewardsError::ConfigUpdateCooldown.into());
+    }
+
+    config.revenue_splits = recipients.to_vec();
+    config.last_config_update_epoch = clock.epoch;
+    serialize_config(&config, &mut config_account)?;
+
+    ic_msg!(
+        invoke_context,
+        "Replaced revenue splits for program {} ({} recipients)",
+        config.program_id,
+        recipients.len()
+    );
+
+    Ok(())
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // ClaimDeveloperFees
 // ─────────────────────────────────────────────────────────────────────────────
@@ -273,46 +532,76 @@ fn process_claim(
     }
 
     // Account 1: ProgramRevenueConfig PDA (writable)
-    let claim_amount;
-    let config_program_id;
-    {
-        let mut config_account = instruction_context.try_borrow_instruction_account(1)?;
-        let mut config = deserialize_config(&config_account)?;
+    let mut config_account = instruction_context.try_borrow_instruction_account(1)?;
+    let mut config = deserialize_config(&config_account)?;
 
-        if !config.is_active {
-            return Err(DeveloperRewardsError::ConfigNotActive.into());
-        }
-        if config.unclaimed_fees == 0 {
-            return Err(DeveloperRewardsError::NoFeesToClaim.into());
-        }
+    if !config.is_active {
+        return Err(DeveloperRewardsError::ConfigNotActive.into());
+    }
+    if config.unclaimed_fees == 0 {
+        return Err(DeveloperRewardsError::NoFeesToClaim.into());
+    }
+
+    let claim_amount = config.unclaimed_fees;
+    let payouts = split_claim(&config, claim_amount);
 
-        claim_amount = config.unclaimed_fees;
-        config_program_id = config.program_id;
-        config.unclaimed_fees = 0;
+    // Account 4+: recipient(s), in split order.
+    for (i, (recipient, _)) in payouts.iter().enumerate() {
+        let index = 4u16.saturating_add(i as u16);
+        if instruction_context.get_key_of_instruction_account(index)? != recipient {
+            return Err(DeveloperRewardsError::RecipientMismatch.into());
+        }
+    }
 
-        serialize_config(&config, &mut config_account)?;
+    // Account 3: EpochFeeTracker PDA (writable) — the claimed fees are no
+    // longer owed out of the pool.
+    {
+        let mut tracker_account = instruction_context.try_borrow_instruction_account(3)?;
+        if *tracker_account.get_key() != find_epoch_tracker_address().0 {
+            return Err(DeveloperRewardsError::InvalidPda.into());
+        }
+        if *tracker_account.get_owner() != id() {
+            return Err(DeveloperRewardsError::InvalidAccountOwner.into());
+        }
+        let mut tracker = deserialize_tracker(&tracker_account)?;
+        tracker.unclaimed_developer_fees = tracker
+            .unclaimed_developer_fees
+            .saturating_sub(claim_amount);
+        serialize_tracker(&tracker, &mut tracker_account)?;
     }
 
     // Account 2: Developer fee pool (writable) — source of funds.
     {
         let mut pool_account = instruction_context.try_borrow_instruction_account(2)?;
+        if *pool_account.get_key() != find_fee_pool_address().0 {
+            return Err(DeveloperRewardsError::InvalidPda.into());
+        }
         if pool_account.get_lamports() < claim_amount {
             return Err(DeveloperRewardsError::InsufficientPoolFunds.into());
         }
         pool_account.checked_sub_lamports(claim_amount)?;
     }
 
-    // Account 3+: recipient(s)
-    {
-        let mut recipient_account = instruction_context.try_borrow_instruction_account(3)?;
-        recipient_account.checked_add_lamports(claim_amount)?;
+    // One transfer per recipient; entries whose share rounds to zero are
+    // skipped.
+    for (i, (_, lamports)) in payouts.iter().enumerate() {
+        if *lamports == 0 {
+            continue;
+        }
+        let mut recipient_account =
+            instruction_context.try_borrow_instruction_account(4u16.saturating_add(i as u16))?;
+        recipient_account.checked_add_lamports(*lamports)?;
     }
 
+    config.unclaimed_fees = 0;
+    serialize_config(&config, &mut config_account)?;
+
     ic_msg!(
         invoke_context,
-        "Claimed {} lamports for program {}",
+        "Claimed {} lamports for program {} ({} recipients)",
         claim_amount,
-        config_program_id
+        config.program_id,
+        payouts.len()
     );
 
     Ok(())
@@ -324,17 +613,36 @@ fn process_claim(
 
 fn process_credit(
     invoke_context: &InvokeContext,
-    _program_id: &Pubkey,
-    amount: u64,
+    program_id: &Pubkey,
+    transaction_amount: u64,
     compute_units_consumed: u64,
+    total_compute_units: u64,
 ) -> Result<(), InstructionError> {
     let transaction_context = &invoke_context.transaction_context;
     let instruction_context = transaction_context.get_current_instruction_context()?;
 
+    // Account 0: the runtime's fee collector. A credit moves pool funds, so
+    // nobody else may choose the amount.
+    if *instruction_context.get_key_of_instruction_account(0)? != fee_collector::id()
+        || !instruction_context.is_instruction_account_signer(0)?
+    {
+        return Err(DeveloperRewardsError::UnauthorizedFeeCollector.into());
+    }
+
     // Anti-gaming: minimum CU threshold
     if compute_units_consumed < MIN_COMPUTE_UNITS_THRESHOLD {
         return Err(DeveloperRewardsError::BelowMinComputeUnits.into());
     }
+    if compute_units_consumed > total_compute_units {
+        return Err(DeveloperRewardsError::InvalidComputeUnitShare.into());
+    }
+
+    // Only the program's CU-weighted share of the transaction is attributed.
+    let amount = compute_unit_share(
+        transaction_amount,
+        compute_units_consumed,
+        total_compute_units,
+    );
 
     // Get current slot/epoch from Clock sysvar
     let clock = invoke_context.get_sysvar_cache().get_clock()?;
@@ -345,6 +653,12 @@ fn process_credit(
     let mut config;
     {
         let config_account = instruction_context.try_borrow_instruction_account(1)?;
+        if *config_account.get_key() != find_revenue_config_address(program_id).0 {
+            return Err(DeveloperRewardsError::InvalidPda.into());
+        }
+        if *config_account.get_owner() != id() {
+            return Err(DeveloperRewardsError::InvalidAccountOwner.into());
+        }
         config = deserialize_config(&config_account)?;
     }
 
@@ -353,28 +667,75 @@ fn process_credit(
     }
 
     // Anti-gaming: 7-day cooldown
-    if current_slot < config.eligible_after_slot {
+    let remaining_cooldown = config.remaining_cooldown_slots(current_slot);
+    if remaining_cooldown > 0 {
+        ic_msg!(
+            invoke_context,
+            "Program {} is in its registration cooldown for {} more slots",
+            config.program_id,
+            remaining_cooldown
+        );
         return Err(DeveloperRewardsError::CooldownNotElapsed.into());
     }
 
-    // Epoch roll-over
-    if config.last_epoch != current_epoch {
-        config.epoch_fees_earned = 0;
-        config.last_epoch = current_epoch;
+    // Account 5: Developer fee pool PDA — source of any excess.
+    if *instruction_context.get_key_of_instruction_account(5)? != find_fee_pool_address().0 {
+        return Err(DeveloperRewardsError::InvalidPda.into());
     }
 
     // Anti-gaming: per-epoch cap (10% of total dev fees)
     // We read the epoch tracker from account 2
 
     let mut tracker_account = instruction_context.try_borrow_instruction_account(2)?;
+    if *tracker_account.get_key() != find_epoch_tracker_address().0 {
+        return Err(DeveloperRewardsError::InvalidPda.into());
+    }
+    if *tracker_account.get_owner() != id() {
+        return Err(DeveloperRewardsError::InvalidAccountOwner.into());
+    }
     let mut tracker = deserialize_tracker(&tracker_account)?;
 
+    // Only fees that have reached the pool and are not already owed to a
+    // program can be attributed (or burned).
+    let pool_lamports = instruction_context
+        .try_borrow_instruction_account(5)?
+        .get_lamports();
+    let unattributed = pool_lamports.saturating_sub(tracker.unclaimed_developer_fees);
+    if amount > unattributed {
+        ic_msg!(
+            invoke_context,
+            "Credit of {} lamports exceeds the {} unattributed lamports in the fee pool",
+            amount,
+            unattributed
+        );
+        return Err(DeveloperRewardsError::InsufficientPoolFunds.into());
+    }
+
     if tracker.epoch != current_epoch {
         tracker.epoch = current_epoch;
         tracker.total_developer_fees = 0;
     }
 
-    let projected_program = config.epoch_fees_earned.saturating_add(amount);
+    // Account 4: DeveloperEpochStats PDA (writable) — the program's counter.
+    // Any other account would read as a fresh, zeroed counter and reset the
+    // cap.
+    let mut stats_account = instruction_context.try_borrow_instruction_account(4)?;
+    if *stats_account.get_key() != find_epoch_stats_address(&config.program_id).0 {
+        return Err(DeveloperRewardsError::InvalidPda.into());
+    }
+    if *stats_account.get_owner() != id() {
+        return Err(DeveloperRewardsError::InvalidAccountOwner.into());
+    }
+    let mut stats = deserialize_epoch_stats(&stats_account, &config.program_id)?;
+
+    if stats.program_id != config.program_id {
+        return Err(DeveloperRewardsError::InvalidPda.into());
+    }
+    if stats.epoch != current_epoch {
+        stats.epoch = current_epoch;
+        stats.fees_earned_this_epoch = 0;
+    }
+
     let projected_total = tracker.total_developer_fees.saturating_add(amount);
 
     let max_allowed = (projected_total as u128)
@@ -382,15 +743,46 @@ fn process_credit(
         .checked_div(TOTAL_BPS as u128)
         .unwrap_or(0) as u64;
 
-    if projected_program > max_allowed {
-        return Err(DeveloperRewardsError::EpochFeeCapExceeded.into());
+    // Only the remaining headroom is attributed; the rest is burned.
+    let headroom = max_allowed.saturating_sub(stats.fees_earned_this_epoch);
+    let credited = amount.min(headroom);
+    let excess = amount - credited;
+
+    if excess > 0 {
+        // Account 6: burn pool — receives everything above the cap.
+        let mut burn_account = instruction_context.try_borrow_instruction_account(6)?;
+        if *burn_account.get_key() != incinerator::id() {
+            return Err(InstructionError::InvalidArgument);
+        }
+
+        // Account 5: Developer fee pool (writable) — source of the excess.
+        let mut pool_account = instruction_context.try_borrow_instruction_account(5)?;
+        if pool_account.get_lamports() < excess {
+            return Err(DeveloperRewardsError::InsufficientPoolFunds.into());
+        }
+        pool_account.checked_sub_lamports(excess)?;
+        burn_account.checked_add_lamports(excess)?;
+
+        ic_msg!(
+            invoke_context,
+            "Program {} reached the epoch fee cap: credited {}, burned {} lamports",
+            config.program_id,
+            credited,
+            excess
+        );
     }
 
     // Credit the fees
-    config.epoch_fees_earned = projected_program;
-    config.total_fees_earned = config.total_fees_earned.saturating_add(amount);
-    config.unclaimed_fees = config.unclaimed_fees.saturating_add(amount);
+    stats.fees_earned_this_epoch = stats.fees_earned_this_epoch.saturating_add(credited);
+    config.epoch_fees_earned = stats.fees_earned_this_epoch;
+    config.last_epoch = current_epoch;
+    config.total_fees_earned = config.total_fees_earned.saturating_add(credited);
+    config.unclaimed_fees = config.unclaimed_fees.saturating_add(credited);
     tracker.total_developer_fees = projected_total;
+    tracker.unclaimed_developer_fees = tracker.unclaimed_developer_fees.saturating_add(credited);
+
+    serialize_epoch_stats(&stats, &mut stats_account)?;
+    drop(stats_account);
 
     serialize_tracker(&tracker, &mut tracker_account)?;
     drop(tracker_account);
@@ -405,6 +797,58 @@ fn process_credit(
 // Helpers
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Validates a revenue split: 1..=`MAX_REVENUE_SPLIT_RECIPIENTS` distinct
+/// recipients with non-zero shares summing to `TOTAL_BPS`.
+fn validate_revenue_splits(splits: &[RevenueSplit]) -> Result<(), InstructionError> {
+    if splits.len() > MAX_REVENUE_SPLIT_RECIPIENTS {
+        return Err(DeveloperRewardsError::TooManySplitRecipients.into());
+    }
+
+    let mut total_bps: u32 = 0;
+    for (i, s) in splits.iter().enumerate() {
+        if s.share_bps == 0 {
+            return Err(DeveloperRewardsError::ZeroShareInSplit.into());
+        }
+        total_bps = total_bps.saturating_add(s.share_bps as u32);
+
+        for other in splits.iter().skip(i + 1) {
+            if s.recipient == other.recipient {
+                return Err(DeveloperRewardsError::DuplicateRecipient.into());
+            }
+        }
+    }
+    if total_bps != TOTAL_BPS as u32 {
+        return Err(DeveloperRewardsError::InvalidSplitTotal.into());
+    }
+    Ok(())
+}
+
+/// Splits `amount` between the config's recipients. Without splits the
+/// primary recipient receives everything; otherwise each entry gets its
+/// pro-rata share and the rounding remainder goes to the first recipient.
+fn split_claim(config: &ProgramRevenueConfig, amount: u64) -> Vec<(Pubkey, u64)> {
+    if config.revenue_splits.is_empty() {
+        return vec![(config.revenue_recipient, amount)];
+    }
+
+    let mut payouts: Vec<(Pubkey, u64)> = config
+        .revenue_splits
+        .iter()
+        .map(|split| {
+            let share = (amount as u128)
+                .saturating_mul(split.share_bps as u128)
+                .checked_div(TOTAL_BPS as u128)
+                .unwrap_or(0) as u64;
+            (split.recipient, share)
+        })
+        .collect();
+    let distributed: u64 = payouts.iter().map(|(_, share)| share).sum();
+    payouts[0].1 = payouts[0]
+        .1
+        .saturating_add(amount.saturating_sub(distributed));
+    payouts
+}
+
 fn deserialize_config(
     account: &solana_transaction_context::instruction_accounts::BorrowedInstructionAccount<'_, '_>,
 ) -> Result<ProgramRevenueConfig, InstructionError> {
@@ -430,6 +874,22 @@ fn serialize_config(
     Ok(())
 }
 
+/// A never-written index account (empty or zero-filled) reads as an empty
+/// index.
+fn deserialize_registry_index(
+    account: &solana_transaction_context::instruction_accounts::BorrowedInstructionAccount<'_, '_>,
+) -> Result<ProgramRegistryIndex, InstructionError> {
+    let data = account.get_data();
+    if data.is_empty() || data.iter().all(|&b| b == 0) {
+        return Ok(ProgramRegistryIndex {
+            version: 1,
+            ..Default::default()
+        });
+    }
+    ProgramRegistryIndex::try_from_slice(data)
+        .map_err(|_| InstructionError::InvalidAccountData)
+}
+
 fn deserialize_tracker(
     account: &solana_transaction_context::instruction_accounts::BorrowedInstructionAccount<'_, '_>,
 ) -> Result<EpochFeeTracker, InstructionError> {
@@ -457,3 +917,33 @@ fn serialize_tracker(
     account.set_data_from_slice(&serialized)?;
     Ok(())
 }
+
+fn deserialize_epoch_stats(
+    account: &solana_transaction_context::instruction_accounts::BorrowedInstructionAccount<'_, '_>,
+    program_id: &Pubkey,
+) -> Result<DeveloperEpochStats, InstructionError> {
+    let data = account.get_data();
+    if data.is_empty() || data.iter().all(|&b| b == 0) {
+        return Ok(DeveloperEpochStats {
+            version: 1,
+            program_id: *program_id,
+            ..Default::default()
+        });
+    }
+    DeveloperEpochStats::try_from_slice(data)
+        .map_err(|_| InstructionError::InvalidAccountData)
+}
+
+fn serialize_epoch_stats(
+    stats: &DeveloperEpochStats,
+    account: &mut solana_transaction_context::instruction_accounts::BorrowedInstructionAccount<'_, '_>,
+) -> Result<(), InstructionError> {
+    let serialized = borsh::to_vec(stats)
+        .map_err(|_| InstructionError::InvalidAccountData)?;
+    let data_len = account.get_data().len();
+    if data_len < serialized.len() {
+        return Err(DeveloperRewardsError::AccountDataTooSmall.into());
+    }
+    account.set_data_from_slice(&serialized)?;
+    Ok(())
+}
@@ -1,6 +1,11 @@
 //! On-chain account state for the TRv1 Developer Rewards program.
 
 use {
+    crate::constants::{
+        COOLDOWN_SLOTS, EPOCH_STATS_SEED, EPOCH_TRACKER_SEED, FEE_POOL_SEED,
+        MAX_REGISTRY_PAGE_SIZE, REGISTRY_INDEX_SEED, REVENUE_CONFIG_SEED,
+        TRANSFER_ACCEPT_WINDOW_SLOTS,
+    },
     borsh::{BorshDeserialize, BorshSerialize},
     solana_pubkey::Pubkey,
 };
@@ -28,8 +33,9 @@ pub struct ProgramRevenueConfig {
     /// Whether this config is active.
     pub is_active: bool,
 
-    /// Optional: split between multiple recipients.
-    /// When empty, 100 % goes to `revenue_recipient`.
+    /// Optional: split between multiple recipients (at most
+    /// `MAX_REVENUE_SPLIT_RECIPIENTS`). When empty, 100 % goes to
+    /// `revenue_recipient`.
     pub revenue_splits: Vec<RevenueSplit>,
 
     /// Total fees earned (lifetime, lamports).
@@ -47,6 +53,32 @@ pub struct ProgramRevenueConfig {
 
     /// Accumulated unclaimed fees (lamports).
     pub unclaimed_fees: u64,
+
+    /// Epoch in which the config was registered or its splits last changed.
+    /// `UpdateRevenueConfig` is rejected until the cooldown has passed.
+    pub last_config_update_epoch: u64,
+
+    /// Slot in which the program was registered. Fees are only credited once
+    /// `COOLDOWN_SLOTS` have passed since this slot.
+    pub registration_slot: u64,
+
+    /// Authority nominated by `TransferProgramConfig`, waiting to call
+    /// `AcceptProgramConfig`.
+    pub pending_update_authority: Option<Pubkey>,
+
+    /// Slot in which `pending_update_authority` was nominated. The transfer
+    /// lapses `TRANSFER_ACCEPT_WINDOW_SLOTS` after this slot.
+    pub pending_transfer_slot: u64,
+}
+
+/// Address of the `ProgramRevenueConfig` PDA for `program_id`.
+pub fn find_revenue_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REVENUE_CONFIG_SEED, program_id.as_ref()], &crate::id())
+}
+
+/// Address of the developer fee pool PDA.
+pub fn find_fee_pool_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_POOL_SEED], &crate::id())
 }
 
 /// A single entry in a multi-recipient split.
@@ -71,8 +103,101 @@ pub struct EpochFeeTracker {
     /// The epoch these counters apply to.
     pub epoch: u64,
 
-    /// Total developer fees distributed this epoch (lamports).
+    /// Total developer fees distributed this epoch (lamports), including any
+    /// portion burned because a program hit its per-epoch cap.
     pub total_developer_fees: u64,
+
+    /// Fees credited to programs but not yet claimed, across all programs
+    /// and epochs (lamports).  The fee pool's balance above this is what has
+    /// accrued and not yet been attributed.
+    pub unclaimed_developer_fees: u64,
+}
+
+/// Address of the `EpochFeeTracker` PDA.
+pub fn find_epoch_tracker_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EPOCH_TRACKER_SEED], &crate::id())
+}
+
+/// Per-program counter backing the per-epoch fee cap.
+///
+/// Derived as a PDA: `[EPOCH_STATS_SEED, program_id]`. The counter resets
+/// whenever `epoch` differs from the current clock epoch.
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct DeveloperEpochStats {
+    /// Discriminator / version tag.
+    pub version: u8,
+
+    /// The epoch `fees_earned_this_epoch` applies to.
+    pub epoch: u64,
+
+    /// The program these stats belong to.
+    pub program_id: Pubkey,
+
+    /// Fees attributed to the program in `epoch` (lamports).
+    pub fees_earned_this_epoch: u64,
+}
+
+/// Address of the `DeveloperEpochStats` PDA for `program_id`.
+pub fn find_epoch_stats_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EPOCH_STATS_SEED, program_id.as_ref()], &crate::id())
+}
+
+// ── Program registry ─────────────────────────────────────────────────────────
+
+/// Append-only list of every program that has registered a revenue config,
+/// in registration order. Lets clients enumerate registered programs without
+/// a `getProgramAccounts` scan.
+///
+/// Derived as a PDA: `[REGISTRY_INDEX_SEED]`. The account is created by the
+/// first registration and grown by 32 bytes on each one after that.
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ProgramRegistryIndex {
+    /// Discriminator / version tag.
+    pub version: u8,
+
+    /// Registered program IDs, oldest first.
+    pub entries: Vec<Pubkey>,
+
+    /// Reserved for chaining a follow-on index account once this one reaches
+    /// the maximum account size. Always `None` for now.
+    pub next_page_seed: Option<u64>,
+}
+
+/// Address of the `ProgramRegistryIndex` PDA.
+pub fn find_registry_index_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_INDEX_SEED], &crate::id())
+}
+
+/// One page of the registry, returned by `GetRegistryPage` as return data.
+#[derive(Clone, Debug, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct RegistryPage {
+    /// Total number of registered programs, so clients know when to stop.
+    pub total_entries: u64,
+
+    /// At most `MAX_REGISTRY_PAGE_SIZE` program IDs starting at the requested
+    /// offset.
+    pub entries: Vec<Pubkey>,
+}
+
+impl ProgramRegistryIndex {
+    /// Returns up to `limit` entries starting at `offset`, with `limit`
+    /// clamped to `MAX_REGISTRY_PAGE_SIZE`. Offsets past the end yield an
+    /// empty page.
+    pub fn page(&self, offset: u64, limit: u8) -> RegistryPage {
+        let limit = usize::from(limit.min(MAX_REGISTRY_PAGE_SIZE));
+        let entries = usize::try_from(offset)
+            .ok()
+            .and_then(|offset| self.entries.get(offset..))
+            .unwrap_or_default()
+            .iter()
+            .take(limit)
+            .copied()
+            .collect();
+        RegistryPage {
+            total_entries: self.entries.len() as u64,
+            entries,
+        }
+    }
 }
 
 // ── Size helpers ─────────────────────────────────────────────────────────────
@@ -80,12 +205,32 @@ pub struct EpochFeeTracker {
 impl ProgramRevenueConfig {
     /// Conservative upper-bound account size.
     /// version(1) + program_id(32) + recipient(32) + authority(32) + is_active(1)
-    /// + vec_len(4) + 10 * (32 + 2) + 5 * u64(8) = 1 + 32 + 32 + 32 + 1 + 4
-    ///   + 340 + 40 = 482 bytes.  We round up for safety.
+    /// + vec_len(4) + 5 * (32 + 2) + 8 * u64(8) + pending_authority(1 + 32)
+    ///   = 1 + 32 + 32 + 32 + 1 + 4 + 170 + 64 + 33 = 369 bytes.  We round up
+    ///   for safety.
     pub const MAX_SIZE: usize = 512;
+
+    /// Slots left in the registration cooldown at `current_slot`; zero once
+    /// the program is eligible for fee revenue.
+    pub fn remaining_cooldown_slots(&self, current_slot: u64) -> u64 {
+        COOLDOWN_SLOTS.saturating_sub(current_slot.saturating_sub(self.registration_slot))
+    }
+
+    /// Whether a transfer nominated at `pending_transfer_slot` can still be
+    /// accepted at `current_slot`.
+    pub fn transfer_window_open(&self, current_slot: u64) -> bool {
+        current_slot.saturating_sub(self.pending_transfer_slot) <= TRANSFER_ACCEPT_WINDOW_SLOTS
+    }
 }
 
 impl EpochFeeTracker {
-    /// version(1) + epoch(8) + total_developer_fees(8) = 17 bytes; round up.
+    /// version(1) + epoch(8) + total_developer_fees(8)
+    /// + unclaimed_developer_fees(8) = 25 bytes; round up.
+    pub const MAX_SIZE: usize = 64;
+}
+
+impl DeveloperEpochStats {
+    /// version(1) + epoch(8) + program_id(32) + fees_earned_this_epoch(8)
+    /// = 49 bytes; round up.
     pub const MAX_SIZE: usize = 64;
 }
@@ -30,6 +30,46 @@ pub const DEFAULT_VETO_THRESHOLD_BPS: u16 = 3_333;
 /// Default timelock: 2 epochs (≈ 2 days) delay after passing before execution.
 pub const DEFAULT_TIMELOCK_EPOCHS: u64 = 2;
 
+/// Default delegation lifetime: 90 epochs (≈ 90 days) before a vote
+/// delegation must be renewed.
+pub const DEFAULT_MAX_DELEGATION_EPOCHS: u64 = 90;
+
+/// Default vote-change lockout: votes may be changed until the voting period
+/// ends.
+pub const DEFAULT_VOTE_CHANGE_LOCKOUT_EPOCHS: u64 = 0;
+
+/// Default proposal deposit: 1_000 SOL equivalent in lamports, escrowed by
+/// the proposer while governance is active.
+pub const DEFAULT_PROPOSAL_DEPOSIT: u64 = 1_000_000_000_000;
+
+/// Default expiry grace: a timelocked proposal not executed within 30 epochs
+/// (≈ 30 days) of its execution epoch can be swept to `Expired`.
+pub const DEFAULT_EXPIRY_GRACE_EPOCHS: u64 = 30;
+
+/// Default sweeper reward: 1 SOL equivalent in lamports, taken from the
+/// deposit of each proposal swept to `Expired`.
+pub const DEFAULT_SWEEPER_REWARD_LAMPORTS: u64 = 1_000_000_000;
+
+// ---------------------------------------------------------------------------
+// PDA seeds
+// ---------------------------------------------------------------------------
+
+/// Seed prefix of a proposal's deposit escrow, followed by the governance
+/// config address and the little-endian proposal ID.
+pub const PROPOSAL_DEPOSIT_SEED: &[u8] = b"proposal_deposit";
+
+/// Seed prefix of a delegator's delegation record, followed by the
+/// delegator's address.
+pub const DELEGATION_RECORD_SEED: &[u8] = b"delegation_record";
+
+/// Seed prefix of a voter's vote record, followed by the little-endian
+/// proposal ID and the voter's address.
+pub const VOTE_RECORD_SEED: &[u8] = b"vote";
+
+/// Seed of the network params account, the one `NetworkParams` account the
+/// runtime reads (e.g. for a fee market pause).
+pub const NETWORK_PARAMS_SEED: &[u8] = b"network_params";
+
 // ---------------------------------------------------------------------------
 // Special thresholds
 // ---------------------------------------------------------------------------
@@ -37,6 +77,14 @@ pub const DEFAULT_TIMELOCK_EPOCHS: u64 = 2;
 /// Supermajority threshold for `EmergencyUnlock` proposals: 80%.
 pub const EMERGENCY_UNLOCK_PASS_THRESHOLD_BPS: u16 = 8_000;
 
+/// Lowest pass threshold a `GovernanceConfigUpdate` proposal may set, so
+/// that a passing coalition cannot lower the bar for its own proposals.
+pub const MIN_GOVERNED_PASS_THRESHOLD_BPS: u16 = 5_000;
+
+/// Lowest timelock a `GovernanceConfigUpdate` proposal may set, so that
+/// passed proposals always leave time for the emergency multisig to react.
+pub const MIN_GOVERNED_TIMELOCK_EPOCHS: u64 = 1;
+
 // ---------------------------------------------------------------------------
 // Voting weight multipliers (in basis points, 10_000 = 1.0×)
 //
@@ -88,6 +136,125 @@ pub const MAX_TITLE_LEN: usize = 64;
 /// Maximum memo length in bytes (for TreasurySpend).
 pub const MAX_MEMO_LEN: usize = 32;
 
+/// Maximum number of memo bytes included in the TreasurySpend execution log.
+pub const MAX_LOGGED_MEMO_LEN: usize = 64;
+
+/// Maximum number of steps in a `MultiStep` proposal.
+pub const MAX_PROPOSAL_STEPS: usize = 5;
+
+/// Maximum number of proposals in one `SweepExpiredProposals` instruction.
+pub const MAX_SWEEP_PROPOSALS: usize = 10;
+
 /// Maximum number of active proposals at any given time.
 /// Prevents state bloat.
 pub const MAX_ACTIVE_PROPOSALS: u64 = 100;
+
+// ---------------------------------------------------------------------------
+// Network parameters
+// ---------------------------------------------------------------------------
+
+/// A network parameter that `ParameterChange` proposals may set, with the
+/// inclusive range of values it accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkParamSpec {
+    /// Identifier used by `ParameterChange { param_id, .. }`.
+    pub param_id: u32,
+    /// Human-readable name, for logs and tooling.
+    pub name: &'static str,
+    /// Smallest accepted value.
+    pub min_value: u64,
+    /// Largest accepted value.
+    pub max_value: u64,
+}
+
+/// Every parameter governance can change.  Proposals naming any other
+/// `param_id`, or a value outside the listed range, are rejected.
+pub const NETWORK_PARAMS: &[NetworkParamSpec] = &[
+    NetworkParamSpec {
+        param_id: 0,
+        name: "fee_burn_bps",
+        min_value: 0,
+        max_value: BPS_DENOMINATOR,
+    },
+    NetworkParamSpec {
+        param_id: 1,
+        name: "fee_validator_bps",
+        min_value: 0,
+        max_value: BPS_DENOMINATOR,
+    },
+    NetworkParamSpec {
+        param_id: 2,
+        name: "fee_treasury_bps",
+        min_value: 0,
+        max_value: BPS_DENOMINATOR,
+    },
+    NetworkParamSpec {
+        param_id: 3,
+        name: "fee_developer_bps",
+        min_value: 0,
+        max_value: BPS_DENOMINATOR,
+    },
+    NetworkParamSpec {
+        param_id: 4,
+        name: "base_fee_lamports",
+        min_value: 1,
+        max_value: 1_000_000,
+    },
+    NetworkParamSpec {
+        param_id: 5,
+        name: "max_active_validators",
+        min_value: 4,
+        max_value: 1_000,
+    },
+    NetworkParamSpec {
+        param_id: 6,
+        name: "staking_reward_rate_bps",
+        min_value: 0,
+        max_value: BPS_DENOMINATOR,
+    },
+    NetworkParamSpec {
+        param_id: 7,
+        name: "early_unlock_penalty_bps",
+        min_value: 0,
+        max_value: 5_000,
+    },
+    NetworkParamSpec {
+        param_id: FEE_MARKET_PAUSED_PARAM,
+        name: "fee_market_paused",
+        min_value: 0,
+        max_value: 1,
+    },
+    NetworkParamSpec {
+        param_id: FEE_MARKET_PAUSE_FEE_PARAM,
+        name: "fee_market_pause_fee",
+        min_value: 1,
+        max_value: MAX_FEE_MARKET_PAUSE_FEE,
+    },
+    NetworkParamSpec {
+        param_id: FEE_MARKET_MULTISIG_RESUME_PARAM,
+        name: "fee_market_multisig_resume",
+        min_value: 0,
+        max_value: 1,
+    },
+];
+
+/// `1` while the fee market is paused by `PauseFeeMarket`.  Setting it to `0`
+/// through a `ParameterChange` proposal resumes the fee market.
+pub const FEE_MARKET_PAUSED_PARAM: u32 = 8;
+
+/// Fixed base fee per compute unit (lamports) applied while the fee market is
+/// paused.
+pub const FEE_MARKET_PAUSE_FEE_PARAM: u32 = 9;
+
+/// `1` lets the emergency multisig lift a pause with `ResumeFeeMarket`; while
+/// `0` (the default) only a `ParameterChange` proposal can.
+pub const FEE_MARKET_MULTISIG_RESUME_PARAM: u32 = 10;
+
+/// Highest fixed base fee a pause may set, matching the fee market's default
+/// `max_base_fee` ceiling (50 M lamports per CU).
+pub const MAX_FEE_MARKET_PAUSE_FEE: u64 = 50_000_000;
+
+/// Look up a parameter in [`NETWORK_PARAMS`].
+pub fn network_param_spec(param_id: u32) -> Option<&'static NetworkParamSpec> {
+    NETWORK_PARAMS.iter().find(|spec| spec.param_id == param_id)
+}
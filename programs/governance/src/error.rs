@@ -90,6 +90,80 @@ pub enum GovernanceError {
 
     #[error("Proposal has expired without reaching quorum")]
     ProposalExpired,
+
+    #[error("Multi-step proposal must contain between 1 and 5 steps")]
+    InvalidStepCount,
+
+    #[error("A step of a multi-step proposal failed — no step was applied")]
+    MultiStepExecutionFailed,
+
+    #[error("Treasury balance is insufficient for the proposal's spends")]
+    InsufficientFunds,
+
+    #[error("Unknown network parameter or value out of range")]
+    InvalidNetworkParameter,
+
+    #[error("Delegation would create a cycle")]
+    CircularDelegation,
+
+    #[error("Delegation record cannot be changed until it expires and its votes close")]
+    DelegationNotRevocable,
+
+    #[error("Delegation record does not lead to the voter")]
+    InvalidDelegation,
+
+    #[error("Votes on this proposal can no longer be changed")]
+    VoteChangeLockedOut,
+
+    #[error("Deposit escrow account does not match the proposal")]
+    InvalidDepositEscrow,
+
+    #[error("Proposal deposit cannot be claimed until the proposal is settled")]
+    DepositNotClaimable,
+
+    #[error("Proposal deposit has already been claimed")]
+    DepositAlreadyClaimed,
+
+    #[error("Sweep must name between 1 and 10 proposals, each with its deposit escrow")]
+    InvalidSweepCount,
+
+    #[error("The fee market is not paused")]
+    FeeMarketNotPaused,
+
+    #[error("Resuming the fee market requires a governance proposal")]
+    FeeMarketResumeRequiresProposal,
+
+    #[error("Delegation record account does not match the delegator")]
+    InvalidDelegationRecord,
+
+    #[error("Voter has delegated their voting power")]
+    VoterHasDelegated,
+
+    #[error("Vote record account does not match the voter and proposal")]
+    InvalidVoteRecord,
+
+    #[error("Governance is active; config changes require a governance proposal")]
+    ConfigChangeRequiresProposal,
+}
+
+/// Details of a failed multi-step execution.
+///
+/// `GovernanceError` is surfaced to the runtime as a `u32` custom error code,
+/// so it cannot carry data.  The processor logs this richer form and records
+/// the failure as `ProposalStatus::ExecutionFailed`.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Multi-step execution failed at step {step_index}: {reason}")]
+pub struct MultiStepExecutionError {
+    /// Zero-based index of the step that failed.
+    pub step_index: u8,
+    /// Why the step failed.
+    pub reason: GovernanceError,
+}
+
+impl From<MultiStepExecutionError> for GovernanceError {
+    fn from(_: MultiStepExecutionError) -> Self {
+        GovernanceError::MultiStepExecutionFailed
+    }
 }
 
 // Note: `InstructionError` has a blanket `From<T: ToPrimitive>` impl,
@@ -13,7 +13,7 @@
 //! participants will see post-activation.
 
 use {
-    crate::state::{ProposalType, Vote},
+    crate::state::{ProposalStep, ProposalType, Vote},
     serde::{Deserialize, Serialize},
     solana_hash::Hash,
     solana_pubkey::Pubkey,
@@ -33,6 +33,11 @@ pub enum GovernanceInstruction {
     /// 0. `[signer, writable]` — Initialiser (becomes initial authority).
     /// 1. `[writable]`         — Governance config account (pre-allocated,
     ///                           owned by this program, uninitialised).
+    /// 2. `[writable]`         — Optional: network params account
+    ///                           (`find_network_params_address`), created
+    ///                           here and paid for by the initialiser if it
+    ///                           does not exist yet.
+    /// 3. `[]`                 — System program (with account 2).
     ///
     /// # Data
     ///
@@ -55,14 +60,22 @@ pub enum GovernanceInstruction {
     ///
     /// When governance is **inactive**: requires the authority (multisig) to sign.
     ///
+    /// While governance is active and `proposal_deposit` is non-zero, the
+    /// proposer also transfers `proposal_deposit` lamports into the
+    /// proposal's deposit escrow.  `ClaimDeposit` settles it once the
+    /// proposal's outcome is final.
+    ///
     /// # Accounts expected
     ///
-    /// 0. `[signer]`           — Proposer (or authority if governance inactive).
+    /// 0. `[signer, writable]` — Proposer (or authority if governance inactive).
     /// 1. `[writable]`         — Governance config account.
     /// 2. `[writable]`         — Proposal account (pre-allocated, uninitialised).
     /// 3. `[]`                 — Proposer's passive stake account (for weight
     ///                           proof when governance is active; ignored when
     ///                           inactive).
+    /// 4. `[writable]`         — Deposit escrow (PDA from
+    ///                           `find_deposit_escrow_address`; deposit only).
+    /// 5. `[]`                 — System program (deposit only).
     CreateProposal {
         title: Vec<u8>,
         description_hash: Hash,
@@ -74,14 +87,34 @@ pub enum GovernanceInstruction {
     /// Only valid when governance is **active** and the proposal status is
     /// `Active` and the current epoch is within the voting period.
     ///
+    /// Voting power is taken from passive stake locked no later than the
+    /// proposal's `voting_power_snapshot_epoch` (its creation epoch); later
+    /// locks have no voting power on the proposal.  The voter's weight is
+    /// their own power plus the power of every unexpired delegation to them.
+    /// A voter whose own delegation is in force cannot vote, and each counted
+    /// delegation stays in force until voting on the proposal ends.
+    ///
+    /// Voting again replaces the voter's previous vote, as long as the
+    /// current epoch is before `voting_ends_epoch - vote_change_lockout_epochs`.
+    ///
     /// # Accounts expected
     ///
-    /// 0. `[signer]`           — Voter.
+    /// 0. `[writable, signer]` — Voter (pays for a new vote record).
     /// 1. `[writable]`         — Proposal account.
     /// 2. `[]`                 — Governance config account.
-    /// 3. `[]`                 — Voter's passive stake account (for weight proof).
-    /// 4. `[writable]`         — Vote record account (PDA derived from
-    ///                           proposal_id + voter; created on first vote).
+    /// 3. `[]`                 — Voter's passive stake account (for weight proof;
+    ///                           owned by the passive stake program).
+    /// 4. `[writable]`         — Vote record PDA (`find_vote_record_address`;
+    ///                           created on first vote, overwritten on a vote
+    ///                           change).
+    /// 5. `[]`                 — Voter's delegation record PDA (need not exist).
+    /// 6. `[]`                 — System program.
+    /// 7.. (varies)            — Pairs of (`[writable]` delegation record,
+    ///                           `[]` delegator's passive stake account) for
+    ///                           power delegated to the voter.  A record may
+    ///                           point at the voter or at the delegator of an
+    ///                           earlier pair, so whole delegation chains are
+    ///                           counted.
     CastVote {
         proposal_id: u64,
         vote: Vote,
@@ -101,8 +134,32 @@ pub enum GovernanceInstruction {
     /// 0. `[signer]`           — Executor (anyone if active, authority if inactive).
     /// 1. `[writable]`         — Proposal account.
     /// 2. `[writable]`         — Governance config account.
-    /// 3+. (varies)            — Additional accounts required by the proposal
-    ///                           type (treasury account, program buffer, etc.).
+    /// 3. `[writable]`         — Network params account (required when the
+    ///                           proposal contains a ParameterChange step;
+    ///                           at `find_network_params_address`).
+    ///
+    /// When the proposal contains a TreasurySpend or TreasuryAllowanceUpdate
+    /// step, the following accounts come next (at 3 onwards, or 4 onwards
+    /// after the network params account):
+    ///
+    /// +0. `[writable]`        — Treasury config account (its authority must be
+    ///                           the governance config account).
+    /// +1. `[writable]`        — Recipient account (unused without a
+    ///                           TreasurySpend step).
+    /// +2. `[]`                — System program.
+    /// +3. `[writable]`        — Treasury account (source of lamports; its
+    ///                           balance must cover every spend).
+    /// +4. `[]`                — Treasury program.
+    /// +5.. `[writable]`       — Recipients of any further TreasurySpend steps.
+    ///
+    /// Tallying an `Active` proposal whose vote failed also succeeds,
+    /// recording `Expired`, `Vetoed`, or `Rejected` so that its deposit can be
+    /// claimed.
+    ///
+    /// If a step cannot be applied the instruction still succeeds, recording
+    /// `ExecutionFailed` on the proposal so it cannot be retried.  A failed
+    /// treasury disbursement fails the instruction instead, leaving the
+    /// proposal executable.
     ExecuteProposal {
         proposal_id: u64,
     },
@@ -139,16 +196,24 @@ pub enum GovernanceInstruction {
     ///
     /// # Accounts expected
     ///
-    /// 0. `[signer]`           — Current authority (multisig).
+    /// 0. `[writable, signer]` — Current authority (multisig).
     /// 1. `[writable]`         — Governance config account.
+    /// 2. `[]`                 — System program (only to migrate a config still
+    ///                           in the legacy layout; the authority pays its
+    ///                           extra rent).
     ActivateGovernance,
 
-    /// Update governance configuration.  Only the authority can do this.
+    /// Update governance configuration.  Only the authority can do this,
+    /// and only before governance is activated; afterwards the config changes
+    /// through `GovernanceConfigUpdate` proposals.
     ///
     /// # Accounts expected
     ///
-    /// 0. `[signer]`           — Current authority.
+    /// 0. `[writable, signer]` — Current authority.
     /// 1. `[writable]`         — Governance config account.
+    /// 2. `[]`                 — System program (only to migrate a config still
+    ///                           in the legacy layout; the authority pays its
+    ///                           extra rent).
     UpdateConfig {
         proposal_threshold: u64,
         voting_period_epochs: u64,
@@ -158,4 +223,141 @@ pub enum GovernanceInstruction {
         timelock_epochs: u64,
         emergency_multisig: Pubkey,
     },
+
+    /// Create a proposal that executes several steps atomically, in order.
+    ///
+    /// Appended last so existing variant tags stay stable on the wire.
+    ///
+    /// Same authorisation rules and accounts as `CreateProposal`.  The steps
+    /// are stored as `ProposalType::MultiStep`; at most
+    /// `MAX_PROPOSAL_STEPS` (5) steps are allowed.  If any step fails during
+    /// `ExecuteProposal`, none of the steps take effect and the proposal
+    /// moves to `ExecutionFailed`.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer, writable]` — Proposer (or authority if governance inactive).
+    /// 1. `[writable]`         — Governance config account.
+    /// 2. `[writable]`         — Proposal account (pre-allocated, uninitialised).
+    /// 3. `[]`                 — Proposer's passive stake account.
+    /// 4. `[writable]`         — Deposit escrow (deposit only).
+    /// 5. `[]`                 — System program (deposit only).
+    CreateMultiStepProposal {
+        title: Vec<u8>,
+        description_hash: Hash,
+        steps: Vec<ProposalStep>,
+    },
+
+    /// Delegate the signer's voting power to `delegate`.
+    ///
+    /// Creates the delegator's delegation record, or redirects and renews an
+    /// existing one.  The delegation lapses `max_delegation_epochs` after it
+    /// was made.  Delegating to oneself, or to anyone whose delegation chain
+    /// leads back to the delegator, fails with `CircularDelegation`.  A record
+    /// whose power was counted on a proposal cannot be redirected until
+    /// voting on that proposal ends.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[writable, signer]` — Delegator (pays rent for a new record).
+    /// 1. `[]`                 — Governance config account.
+    /// 2. `[writable]`         — Delegator's delegation record
+    ///                           (`find_delegation_record_address`; created on
+    ///                           first use).
+    /// 3. `[]`                 — System program.
+    /// 4.. `[]`                — The delegate's delegation record, then that
+    ///                           delegate's, and so on along the chain.
+    DelegateVote {
+        delegate: Pubkey,
+    },
+
+    /// Settle a proposal's deposit once its outcome is final.  Only the
+    /// proposer can do this, once.
+    ///
+    /// Executed proposals (including `ExecutionFailed`) refund the whole
+    /// deposit; rejected proposals refund half and forfeit half to the
+    /// treasury; expired, vetoed, and cancelled proposals forfeit it all.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer, writable]` — Proposer.
+    /// 1. `[writable]`         — Proposal account.
+    /// 2. `[]`                 — Governance config account.
+    /// 3. `[writable]`         — Deposit escrow.
+    /// 4. `[]`                 — Treasury config account (its authority must be
+    ///                           the governance config account).
+    /// 5. `[writable]`         — Treasury account.
+    /// 6. `[]`                 — System program.
+    ClaimDeposit {
+        proposal_id: u64,
+    },
+
+    /// Record the total eligible voting power that quorum is measured
+    /// against.  Only the authority can do this, and only before governance
+    /// is activated; afterwards the supply changes through
+    /// `GovernanceSupplyUpdate` proposals.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[writable, signer]` — Current authority.
+    /// 1. `[writable]`         — Governance config account.
+    /// 2. `[]`                 — System program (only to migrate a config still
+    ///                           in the legacy layout; the authority pays its
+    ///                           extra rent).
+    UpdateGovernanceSupply {
+        total_governance_supply: u64,
+    },
+
+    /// Expire stale proposals.  Anyone can call this.
+    ///
+    /// An `Active` proposal whose voting period is over is tallied first: if
+    /// it passed it moves to `Timelocked` as `ExecuteProposal` would have,
+    /// otherwise it takes its failed status (`Rejected`, `Vetoed` or
+    /// `Expired`).  A `Timelocked` proposal is expired once more than
+    /// `expiry_grace_epochs` have passed since its `execution_epoch`.  Other
+    /// proposals are left untouched.  The sweeper is paid
+    /// `sweeper_reward_lamports` from each failed or expired proposal's
+    /// deposit, or the whole deposit if it is smaller; passed proposals pay
+    /// nothing.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer, writable]` — Sweeper.
+    /// 1. `[]`                 — Governance config account.
+    /// 2. `[]`                 — System program.
+    ///
+    /// Followed by 1 to `MAX_SWEEP_PROPOSALS` pairs of:
+    ///
+    /// +0. `[writable]`        — Proposal account.
+    /// +1. `[writable]`        — Its deposit escrow.
+    SweepExpiredProposals,
+
+    /// Freeze the fee market's base fee at `fixed_base_fee` lamports per CU,
+    /// regardless of utilization.  Only the emergency multisig can do this.
+    ///
+    /// Sets the `fee_market_paused` and `fee_market_pause_fee` network
+    /// parameters.  Pausing again while paused changes the fixed fee.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer]`           — Emergency multisig.
+    /// 1. `[]`                 — Governance config account.
+    /// 2. `[writable]`         — Network params account
+    ///                           (`find_network_params_address`).
+    PauseFeeMarket { fixed_base_fee: u64 },
+
+    /// Lift a fee market pause.
+    ///
+    /// Only the emergency multisig can do this, and only while the
+    /// `fee_market_multisig_resume` network parameter is 1.  Otherwise the
+    /// pause is lifted by a `ParameterChange` proposal setting
+    /// `fee_market_paused` to 0.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer]`           — Emergency multisig.
+    /// 1. `[]`                 — Governance config account.
+    /// 2. `[writable]`         — Network params account
+    ///                           (`find_network_params_address`).
+    ResumeFeeMarket,
 }
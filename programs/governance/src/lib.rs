@@ -22,9 +22,27 @@
 //! - `is_active == true` (one-way flip via `ActivateGovernance`)
 //! - Anyone with enough staked tokens can create proposals
 //! - Voting is open for `voting_period_epochs`
+//! - A vote only counts if for + against votes reach `quorum_bps` of
+//!   `total_governance_supply` (kept current with `GovernanceSupplyUpdate`
+//!   proposals); otherwise the proposal expires
+//! - The authority can no longer call `UpdateConfig` or
+//!   `UpdateGovernanceSupply`; the config only changes through proposals
 //! - Votes are weighted by passive staking commitment
+//! - Stakers can delegate their voting power to a representative with
+//!   `DelegateVote`; delegations lapse after `max_delegation_epochs`, and
+//!   one counted on an open proposal holds until its voting ends
+//! - Proposers escrow `proposal_deposit` lamports, reclaimed with
+//!   `ClaimDeposit`: refunded if the proposal passes, half forfeited to the
+//!   treasury if it is rejected, all forfeited if it expires or is vetoed
 //! - Passed proposals enter a timelock before execution
+//! - Anyone can tally proposals left untallied after voting, or expire ones
+//!   unexecuted `expiry_grace_epochs` after their timelock, with
+//!   `SweepExpiredProposals`, earning `sweeper_reward_lamports` of the deposit
+//!   of each proposal that failed or expired
 //! - Emergency multisig can cancel dangerous proposals
+//! - Emergency multisig can pause the fee market at a fixed base fee with
+//!   `PauseFeeMarket`; a `ParameterChange` proposal lifts the pause (or the
+//!   multisig, with `ResumeFeeMarket`, once governance allows it)
 //!
 //! ## Voting Weight
 //!
@@ -41,12 +59,21 @@
 //!
 //! ## Proposal Types
 //!
-//! - **ParameterChange**: modify a network parameter
-//! - **TreasurySpend**: disburse funds from the treasury
+//! - **ParameterChange**: set a network parameter (see `constants::NETWORK_PARAMS`)
+//!   in the governance-owned `NetworkParams` account
+//! - **TreasurySpend**: disburse funds from the treasury via the treasury
+//!   program's `Disburse`, signed by the governance config account
 //! - **EmergencyUnlock**: unlock a permanently locked account (80% supermajority)
 //! - **ProgramUpgrade**: upgrade a program binary
 //! - **FeatureToggle**: activate/deactivate a runtime feature
 //! - **TextProposal**: signaling only, no on-chain effect
+//! - **GovernanceConfigUpdate**: change the governance voting parameters
+//!   themselves (pass threshold at least 50%, timelock at least 1 epoch)
+//! - **TreasuryAllowanceUpdate**: change how much of the treasury may be
+//!   disbursed per epoch, via the treasury program's `SetEpochAllowance`
+//! - **GovernanceSupplyUpdate**: change the voting power quorum is measured
+//!   against
+//! - **MultiStep**: up to 5 of the above, executed atomically in order
 
 #![cfg(feature = "agave-unstable-api")]
 #![allow(clippy::arithmetic_side_effects)]
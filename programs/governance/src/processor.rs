@@ -2,22 +2,36 @@
 
 use {
     crate::{
-        constants::{BPS_DENOMINATOR, EMERGENCY_UNLOCK_PASS_THRESHOLD_BPS},
-        error::GovernanceError,
+        constants::{
+            network_param_spec, BPS_DENOMINATOR, DEFAULT_EXPIRY_GRACE_EPOCHS,
+            DEFAULT_MAX_DELEGATION_EPOCHS, DEFAULT_PROPOSAL_DEPOSIT,
+            DEFAULT_SWEEPER_REWARD_LAMPORTS, DEFAULT_VOTE_CHANGE_LOCKOUT_EPOCHS,
+            EMERGENCY_UNLOCK_PASS_THRESHOLD_BPS, FEE_MARKET_MULTISIG_RESUME_PARAM,
+            FEE_MARKET_PAUSED_PARAM, FEE_MARKET_PAUSE_FEE_PARAM, MAX_FEE_MARKET_PAUSE_FEE,
+            MAX_LOGGED_MEMO_LEN, MAX_PROPOSAL_STEPS, MAX_SWEEP_PROPOSALS,
+            MIN_GOVERNED_PASS_THRESHOLD_BPS, MIN_GOVERNED_TIMELOCK_EPOCHS,
+        },
+        error::{GovernanceError, MultiStepExecutionError},
         instruction::GovernanceInstruction,
         state::{
-            GovernanceConfig, Proposal, ProposalStatus, ProposalType, Vote, VoteRecord,
-            GOVERNANCE_CONFIG_DISCRIMINATOR, PROPOSAL_DISCRIMINATOR, VOTE_RECORD_DISCRIMINATOR,
+            find_delegation_record_address, find_deposit_escrow_address,
+            find_network_params_address, find_vote_record_address, DelegationRecord,
+            GovernanceConfig, NetworkParams, Proposal, ProposalStatus, ProposalStep, ProposalType,
+            Vote, VoteRecord, DELEGATION_RECORD_DISCRIMINATOR, GOVERNANCE_CONFIG_DISCRIMINATOR,
+            PROPOSAL_DISCRIMINATOR,
         },
         vote_weight::voting_power_from_passive_stake_data,
     },
     log::*,
     solana_bincode::limited_deserialize,
     solana_hash::Hash,
-    solana_instruction::error::InstructionError,
+    solana_instruction::{error::InstructionError, AccountMeta, Instruction},
     solana_program_runtime::{declare_process_instruction, invoke_context::InvokeContext},
     solana_pubkey::Pubkey,
+    solana_sdk_ids::system_program,
     solana_svm_log_collector::ic_msg,
+    solana_system_interface::instruction as system_instruction,
+    solana_treasury_program::{instruction::TreasuryInstruction, state::TreasuryConfig},
 };
 
 /// Default compute-unit budget for governance instructions.
@@ -69,6 +83,11 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
             description_hash,
             proposal_type,
         } => process_create_proposal(invoke_context, title, description_hash, proposal_type),
+        GovernanceInstruction::CreateMultiStepProposal {
+            title,
+            description_hash,
+            steps,
+        } => process_create_multi_step_proposal(invoke_context, title, description_hash, steps),
         GovernanceInstruction::CastVote { proposal_id, vote } => {
             process_cast_vote(invoke_context, proposal_id, vote)
         }
@@ -102,6 +121,22 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
             timelock_epochs,
             emergency_multisig,
         ),
+        GovernanceInstruction::DelegateVote { delegate } => {
+            process_delegate_vote(invoke_context, delegate)
+        }
+        GovernanceInstruction::ClaimDeposit { proposal_id } => {
+            process_claim_deposit(invoke_context, proposal_id)
+        }
+        GovernanceInstruction::UpdateGovernanceSupply {
+            total_governance_supply,
+        } => process_update_governance_supply(invoke_context, total_governance_supply),
+        GovernanceInstruction::SweepExpiredProposals => {
+            process_sweep_expired_proposals(invoke_context)
+        }
+        GovernanceInstruction::PauseFeeMarket { fixed_base_fee } => {
+            process_pause_fee_market(invoke_context, fixed_base_fee)
+        }
+        GovernanceInstruction::ResumeFeeMarket => process_resume_fee_market(invoke_context),
     }
 });
 
@@ -151,8 +186,11 @@ fn save_governance_config(
     let instruction_context = transaction_context.get_current_instruction_context()?;
     let mut account = instruction_context.try_borrow_instruction_account(account_index)?;
 
+    // A legacy-layout config is written back in place while it can be; a
+    // config that outgrew it must have been funded by
+    // `migrate_governance_config` first.
     let mut data = account.get_data().to_vec();
-    if data.len() < GovernanceConfig::SERIALIZED_SIZE {
+    if data.len() < GovernanceConfig::SERIALIZED_SIZE && !config.fits_legacy_layout() {
         data.resize(GovernanceConfig::SERIALIZED_SIZE, 0);
     }
     config
@@ -161,6 +199,62 @@ fn save_governance_config(
     account.set_data_from_slice(&data)
 }
 
+/// Grow a `GovernanceConfig` at instruction account `account_index` that is
+/// still in the legacy layout to `GovernanceConfig::SERIALIZED_SIZE`.
+///
+/// The authority's admin instructions call this after checking the
+/// authority's signature.  The authority (instruction account 0) tops up
+/// the account's rent-exempt balance through the system program, which
+/// must then be passed at instruction account 2.  Configs already at full
+/// size are left untouched.
+fn migrate_governance_config(
+    invoke_context: &mut InvokeContext,
+    account_index: u16,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let (config_key, lamports, data_len) = {
+        let account = instruction_context.try_borrow_instruction_account(account_index)?;
+        (
+            *account.get_key(),
+            account.get_lamports(),
+            account.get_data().len(),
+        )
+    };
+    if data_len >= GovernanceConfig::SERIALIZED_SIZE {
+        return Ok(());
+    }
+
+    instruction_context.check_number_of_instruction_accounts(3)?;
+    if instruction_context.get_key_of_instruction_account(2)? != &system_program::id() {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+    let authority = *instruction_context.get_key_of_instruction_account(0)?;
+    let rent = invoke_context.get_sysvar_cache().get_rent()?;
+    let shortfall = rent
+        .minimum_balance(GovernanceConfig::SERIALIZED_SIZE)
+        .saturating_sub(lamports);
+    if shortfall > 0 {
+        invoke_context.native_invoke(
+            system_instruction::transfer(&authority, &config_key, shortfall),
+            &[authority],
+        )?;
+    }
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let mut account = instruction_context.try_borrow_instruction_account(account_index)?;
+    account.set_data_length(GovernanceConfig::SERIALIZED_SIZE)?;
+    ic_msg!(
+        invoke_context,
+        "Migrated governance config {} to the current layout ({} lamports of rent from {})",
+        config_key,
+        shortfall,
+        authority
+    );
+    Ok(())
+}
+
 /// Save a `Proposal` back to instruction account at `index`.
 fn save_proposal(
     invoke_context: &InvokeContext,
@@ -172,8 +266,8 @@ fn save_proposal(
     let mut account = instruction_context.try_borrow_instruction_account(account_index)?;
 
     let mut data = account.get_data().to_vec();
-    if data.len() < Proposal::SERIALIZED_SIZE {
-        data.resize(Proposal::SERIALIZED_SIZE, 0);
+    if data.is_empty() {
+        data.resize(proposal.serialized_size(), 0);
     }
     proposal
         .serialize_into(&mut data)
@@ -181,6 +275,374 @@ fn save_proposal(
     account.set_data_from_slice(&data)
 }
 
+/// Load the `NetworkParams` from instruction account at `index`.
+///
+/// The account must be owned by this program and large enough to hold every
+/// known parameter; a zeroed account holds no values yet.
+fn load_network_params(
+    invoke_context: &InvokeContext,
+    account_index: u16,
+) -> Result<NetworkParams, InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let account = instruction_context.try_borrow_instruction_account(account_index)?;
+
+    // Only the account at the network params address is read by the runtime.
+    if account.get_key() != &find_network_params_address().0 {
+        ic_msg!(
+            invoke_context,
+            "network params account {} is not the network params address",
+            account.get_key()
+        );
+        return Err(GovernanceError::InvalidAccountData.into());
+    }
+    if account.get_owner() != &id() {
+        ic_msg!(
+            invoke_context,
+            "ExecuteProposal: network params account not owned by governance program"
+        );
+        return Err(GovernanceError::InvalidAccountOwner.into());
+    }
+    let data = account.get_data();
+    if data.len() < NetworkParams::SERIALIZED_SIZE {
+        return Err(GovernanceError::InvalidAccountData.into());
+    }
+    NetworkParams::deserialize(data).map_err(|_| GovernanceError::InvalidAccountData.into())
+}
+
+/// Create the network params account (instruction account 2) at its PDA,
+/// paid for by instruction account 0, with the system program at account 3.
+/// An account that already exists is left as it is.
+fn create_network_params(invoke_context: &mut InvokeContext) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    instruction_context.check_number_of_instruction_accounts(4)?;
+
+    let params_key = *instruction_context.get_key_of_instruction_account(2)?;
+    if params_key != find_network_params_address().0 {
+        return Err(GovernanceError::InvalidAccountData.into());
+    }
+    if instruction_context.get_key_of_instruction_account(3)? != &system_program::id() {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+    if instruction_context
+        .try_borrow_instruction_account(2)?
+        .get_owner()
+        == &id()
+    {
+        return Ok(());
+    }
+
+    let payer = *instruction_context.get_key_of_instruction_account(0)?;
+    let rent = invoke_context.get_sysvar_cache().get_rent()?;
+    // As a builtin, this program signs for its own PDA by naming it.
+    invoke_context.native_invoke(
+        system_instruction::create_account(
+            &payer,
+            &params_key,
+            rent.minimum_balance(NetworkParams::SERIALIZED_SIZE),
+            NetworkParams::SERIALIZED_SIZE as u64,
+            &id(),
+        ),
+        &[payer, params_key],
+    )?;
+    Ok(())
+}
+
+/// Save `NetworkParams` back to instruction account at `index`.
+fn save_network_params(
+    invoke_context: &InvokeContext,
+    account_index: u16,
+    params: &NetworkParams,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let mut account = instruction_context.try_borrow_instruction_account(account_index)?;
+
+    let mut data = account.get_data().to_vec();
+    params
+        .serialize_into(&mut data)
+        .map_err(|_| GovernanceError::InvalidAccountData)?;
+    account.set_data_from_slice(&data)
+}
+
+/// Number of accounts `ExecuteProposal` needs for TreasurySpend and
+/// TreasuryAllowanceUpdate steps, not counting the recipients of further
+/// steps.
+const TREASURY_SPEND_ACCOUNTS: u16 = 5;
+
+/// The treasury accounts a proposal's TreasurySpend steps disburse from, and
+/// its TreasuryAllowanceUpdate steps configure.
+struct TreasurySpendAccounts {
+    treasury_config: Pubkey,
+    treasury_account: Pubkey,
+    /// Lamports held by `treasury_account` before execution.
+    balance: u64,
+}
+
+/// Load the `TreasuryConfig` from instruction account at `index`.
+///
+/// Its authority must be `governance_config`: only the treasury governed by
+/// this config may be paid from or into.
+fn load_treasury_config(
+    invoke_context: &InvokeContext,
+    account_index: u16,
+    governance_config: &Pubkey,
+) -> Result<TreasuryConfig, InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let config_account = instruction_context.try_borrow_instruction_account(account_index)?;
+
+    if config_account.get_owner() != &solana_treasury_program::id() {
+        ic_msg!(
+            invoke_context,
+            "Treasury config account not owned by treasury program"
+        );
+        return Err(GovernanceError::InvalidAccountOwner.into());
+    }
+    let treasury_config = TreasuryConfig::deserialize(config_account.get_data())
+        .map_err(|_| GovernanceError::InvalidAccountData)?;
+    if treasury_config.authority != *governance_config {
+        ic_msg!(
+            invoke_context,
+            "Treasury authority {} is not the governance config",
+            treasury_config.authority
+        );
+        return Err(GovernanceError::AuthorityMismatch.into());
+    }
+    Ok(treasury_config)
+}
+
+/// Load and check the TreasurySpend accounts starting at instruction account
+/// `first_index` (see `GovernanceInstruction::ExecuteProposal`).
+///
+/// The treasury config's authority must be `governance_config`, which signs
+/// the `Disburse` CPIs.
+fn load_treasury_spend_accounts(
+    invoke_context: &InvokeContext,
+    first_index: u16,
+    governance_config: &Pubkey,
+) -> Result<TreasurySpendAccounts, InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    instruction_context
+        .check_number_of_instruction_accounts(first_index + TREASURY_SPEND_ACCOUNTS)?;
+
+    let treasury_config = load_treasury_config(invoke_context, first_index, governance_config)?;
+
+    if instruction_context.get_key_of_instruction_account(first_index + 2)? != &system_program::id()
+        || instruction_context.get_key_of_instruction_account(first_index + 4)?
+            != &solana_treasury_program::id()
+    {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+
+    let treasury_account = instruction_context.try_borrow_instruction_account(first_index + 3)?;
+    if treasury_account.get_key() != &treasury_config.treasury_account {
+        ic_msg!(
+            invoke_context,
+            "ExecuteProposal: treasury account does not match treasury config"
+        );
+        return Err(GovernanceError::InvalidAccountData.into());
+    }
+
+    Ok(TreasurySpendAccounts {
+        treasury_config: *instruction_context.get_key_of_instruction_account(first_index)?,
+        treasury_account: treasury_config.treasury_account,
+        balance: treasury_account.get_lamports(),
+    })
+}
+
+/// Disburse `amount` lamports to `recipient` through the treasury program.
+///
+/// The governance config account is the treasury's authority; as a builtin,
+/// this program signs for it by passing it to `native_invoke` (the builtin
+/// equivalent of `invoke_signed`).
+fn disburse_from_treasury(
+    invoke_context: &mut InvokeContext,
+    governance_config: Pubkey,
+    treasury: &TreasurySpendAccounts,
+    recipient: Pubkey,
+    amount: u64,
+    memo: &[u8],
+) -> Result<(), InstructionError> {
+    let data = bincode::serialize(&TreasuryInstruction::Disburse {
+        amount,
+        recipient,
+        memo: memo_text(memo),
+    })
+    .map_err(|_| InstructionError::InvalidInstructionData)?;
+    let instruction = Instruction {
+        program_id: solana_treasury_program::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(governance_config, true),
+            AccountMeta::new(treasury.treasury_config, false),
+            AccountMeta::new(treasury.treasury_account, false),
+            AccountMeta::new(recipient, false),
+        ],
+        data,
+    };
+    invoke_context.native_invoke(instruction, &[governance_config])
+}
+
+/// Set the treasury's epoch allowance through the treasury program's
+/// `SetEpochAllowance`, signed by the governance config account like
+/// `disburse_from_treasury`.
+fn set_treasury_epoch_allowance(
+    invoke_context: &mut InvokeContext,
+    governance_config: Pubkey,
+    treasury: &TreasurySpendAccounts,
+    epoch_allowance_bps: u16,
+) -> Result<(), InstructionError> {
+    let data = bincode::serialize(&TreasuryInstruction::SetEpochAllowance {
+        epoch_allowance_bps,
+    })
+    .map_err(|_| InstructionError::InvalidInstructionData)?;
+    let instruction = Instruction {
+        program_id: solana_treasury_program::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(governance_config, true),
+            AccountMeta::new(treasury.treasury_config, false),
+        ],
+        data,
+    };
+    invoke_context.native_invoke(instruction, &[governance_config])
+}
+
+/// A TreasurySpend memo as text: trailing zero padding is dropped and
+/// invalid UTF-8 replaced.
+fn memo_text(memo: &[u8]) -> String {
+    let len = memo
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |i| i + 1);
+    String::from_utf8_lossy(&memo[..len]).into_owned()
+}
+
+/// `text` cut to at most `max_len` bytes on a character boundary.
+fn truncate_str(text: &str, max_len: usize) -> &str {
+    let mut len = text.len().min(max_len);
+    while !text.is_char_boundary(len) {
+        len -= 1;
+    }
+    &text[..len]
+}
+
+/// Whether `decisive_votes` (for + against) make up at least `quorum_bps` of
+/// the total governance supply.  Quorum is never reached while the supply is
+/// unknown (0).
+fn quorum_reached(decisive_votes: u64, config: &GovernanceConfig) -> Result<bool, GovernanceError> {
+    if config.total_governance_supply == 0 {
+        return Ok(false);
+    }
+    let participation_bps = (decisive_votes as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(GovernanceError::ArithmeticOverflow)?
+        .checked_div(config.total_governance_supply as u128)
+        .ok_or(GovernanceError::ArithmeticOverflow)?;
+    Ok(participation_bps >= config.quorum_bps as u128)
+}
+
+/// Tally an `Active` proposal whose voting period has ended, returning its
+/// new status: `Timelocked` if it passed; `Vetoed` or `Rejected` if it
+/// failed the vote; `Expired` if nobody voted or quorum was not reached.
+fn tally_proposal(
+    invoke_context: &InvokeContext,
+    proposal: &Proposal,
+    config: &GovernanceConfig,
+    instruction_name: &str,
+) -> Result<ProposalStatus, InstructionError> {
+    // Check if veto threshold was reached.
+    let total_votes = proposal
+        .votes_for
+        .checked_add(proposal.votes_against)
+        .ok_or(GovernanceError::ArithmeticOverflow)?
+        .checked_add(proposal.votes_abstain)
+        .ok_or(GovernanceError::ArithmeticOverflow)?
+        .checked_add(proposal.veto_votes)
+        .ok_or(GovernanceError::ArithmeticOverflow)?;
+
+    if total_votes == 0 {
+        ic_msg!(
+            invoke_context,
+            "{}: proposal {} expired (no votes)",
+            instruction_name,
+            proposal.id
+        );
+        return Ok(ProposalStatus::Expired);
+    }
+
+    // Check veto: veto_votes / total_votes >= veto_threshold_bps / 10_000
+    let veto_pct = (proposal.veto_votes as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(GovernanceError::ArithmeticOverflow)?
+        .checked_div(total_votes as u128)
+        .ok_or(GovernanceError::ArithmeticOverflow)?;
+
+    if veto_pct >= config.veto_threshold_bps as u128 {
+        ic_msg!(
+            invoke_context,
+            "{}: proposal {} vetoed",
+            instruction_name,
+            proposal.id
+        );
+        return Ok(ProposalStatus::Vetoed);
+    }
+
+    // Check pass threshold: votes_for / (votes_for + votes_against) >= pass_threshold
+    let decisive_votes = proposal
+        .votes_for
+        .checked_add(proposal.votes_against)
+        .ok_or(GovernanceError::ArithmeticOverflow)?;
+
+    // Check quorum: decisive votes / total governance supply
+    // >= quorum_bps / 10_000.  Without quorum the vote never
+    // took place, so the proposal expires rather than failing.
+    if !quorum_reached(decisive_votes, config)? {
+        ic_msg!(
+            invoke_context,
+            "{}: {} ({} of {} voting power decided, quorum {}bps)",
+            instruction_name,
+            GovernanceError::QuorumNotReached,
+            decisive_votes,
+            config.total_governance_supply,
+            config.quorum_bps
+        );
+        return Ok(ProposalStatus::Expired);
+    }
+
+    let pass_threshold = effective_pass_threshold(proposal, config);
+
+    if decisive_votes == 0 {
+        ic_msg!(
+            invoke_context,
+            "{}: proposal {} has no decisive votes",
+            instruction_name,
+            proposal.id
+        );
+        return Ok(ProposalStatus::Rejected);
+    }
+
+    let for_pct = (proposal.votes_for as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(GovernanceError::ArithmeticOverflow)?
+        .checked_div(decisive_votes as u128)
+        .ok_or(GovernanceError::ArithmeticOverflow)?;
+
+    if for_pct < pass_threshold as u128 {
+        ic_msg!(
+            invoke_context,
+            "{}: proposal {} did not meet the pass threshold",
+            instruction_name,
+            proposal.id
+        );
+        return Ok(ProposalStatus::Rejected);
+    }
+
+    // Proposal passes — move to Timelocked.
+    Ok(ProposalStatus::Timelocked)
+}
+
 /// Determine the effective pass threshold for a proposal type.
 /// EmergencyUnlock uses 80% supermajority; everything else uses the config default.
 fn effective_pass_threshold(proposal: &Proposal, config: &GovernanceConfig) -> u16 {
@@ -200,9 +662,12 @@ fn effective_pass_threshold(proposal: &Proposal, config: &GovernanceConfig) -> u
 /// Accounts:
 ///   0. `[signer, writable]` — Initialiser.
 ///   1. `[writable]`         — Governance config account.
+///   2. `[writable]`         — Optional: network params account, created if
+///                             missing.
+///   3. `[]`                 — System program (with account 2).
 #[allow(clippy::too_many_arguments)]
 fn process_initialize_governance(
-    invoke_context: &InvokeContext,
+    invoke_context: &mut InvokeContext,
     authority: Pubkey,
     proposal_threshold: u64,
     voting_period_epochs: u64,
@@ -267,10 +732,20 @@ fn process_initialize_governance(
         timelock_epochs,
         emergency_multisig,
         next_proposal_id: 0,
+        max_delegation_epochs: DEFAULT_MAX_DELEGATION_EPOCHS,
+        vote_change_lockout_epochs: DEFAULT_VOTE_CHANGE_LOCKOUT_EPOCHS,
+        proposal_deposit: DEFAULT_PROPOSAL_DEPOSIT,
+        total_governance_supply: 0,
+        expiry_grace_epochs: DEFAULT_EXPIRY_GRACE_EPOCHS,
+        sweeper_reward_lamports: DEFAULT_SWEEPER_REWARD_LAMPORTS,
     };
 
     save_governance_config(invoke_context, 1, &config)?;
 
+    if instruction_context.get_number_of_instruction_accounts() > 2 {
+        create_network_params(invoke_context)?;
+    }
+
     ic_msg!(
         invoke_context,
         "InitializeGovernance: authority={}, governance disabled at launch",
@@ -282,12 +757,14 @@ fn process_initialize_governance(
 /// `CreateProposal`
 ///
 /// Accounts:
-///   0. `[signer]`           — Proposer (or authority if inactive).
+///   0. `[signer, writable]` — Proposer (or authority if inactive).
 ///   1. `[writable]`         — Governance config account.
 ///   2. `[writable]`         — Proposal account (pre-allocated, uninitialised).
 ///   3. `[]`                 — Proposer's passive stake account (weight proof).
+///   4. `[writable]`         — Deposit escrow (deposit only).
+///   5. `[]`                 — System program (deposit only).
 fn process_create_proposal(
-    invoke_context: &InvokeContext,
+    invoke_context: &mut InvokeContext,
     title_vec: Vec<u8>,
     description_hash: Hash,
     proposal_type: ProposalType,
@@ -302,6 +779,7 @@ fn process_create_proposal(
         return Err(GovernanceError::MissingAuthoritySignature.into());
     }
     let proposer = *instruction_context.get_key_of_instruction_account(0)?;
+    let config_key = *instruction_context.get_key_of_instruction_account(1)?;
 
     // Convert title Vec<u8> to fixed [u8; 64], zero-padded.
     if title_vec.len() > crate::constants::MAX_TITLE_LEN {
@@ -310,9 +788,35 @@ fn process_create_proposal(
     let mut title = [0u8; 64];
     title[..title_vec.len()].copy_from_slice(&title_vec);
 
+    if let ProposalType::MultiStep { steps } = &proposal_type {
+        if steps.is_empty() || steps.len() > MAX_PROPOSAL_STEPS {
+            ic_msg!(
+                invoke_context,
+                "CreateProposal: multi-step proposal has {} steps (max {})",
+                steps.len(),
+                MAX_PROPOSAL_STEPS
+            );
+            return Err(GovernanceError::InvalidStepCount.into());
+        }
+    }
+    for (step_index, step) in proposal_type.steps().iter().enumerate() {
+        if let Err(err) = validate_step(step) {
+            ic_msg!(
+                invoke_context,
+                "CreateProposal: step {} is invalid: {}",
+                step_index,
+                err
+            );
+            return Err(err.into());
+        }
+    }
+
     // Load config.
     let mut config = load_governance_config(invoke_context, 1)?;
 
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    let current_epoch = clock.epoch;
+
     if config.is_active {
         // Governance is active — verify proposer has enough staked tokens.
         instruction_context.check_number_of_instruction_accounts(4)?;
@@ -321,8 +825,9 @@ fn process_create_proposal(
         let stake_data = stake_account.get_data().to_vec();
         drop(stake_account);
 
-        let (_amount, voting_power) = voting_power_from_passive_stake_data(&stake_data)
-            .ok_or(GovernanceError::InsufficientStakeForProposal)?;
+        let (_amount, voting_power) =
+            voting_power_from_passive_stake_data(&stake_data, current_epoch)
+                .ok_or(GovernanceError::InsufficientStakeForProposal)?;
 
         if voting_power < config.proposal_threshold {
             ic_msg!(
@@ -355,11 +860,18 @@ fn process_create_proposal(
             ic_msg!(invoke_context, "CreateProposal: proposal account already initialised");
             return Err(GovernanceError::AlreadyInitialized.into());
         }
+        if matches!(proposal_type, ProposalType::MultiStep { .. })
+            && data.len() < Proposal::MULTI_STEP_SERIALIZED_SIZE
+        {
+            ic_msg!(
+                invoke_context,
+                "CreateProposal: multi-step proposal account must be at least {} bytes",
+                Proposal::MULTI_STEP_SERIALIZED_SIZE
+            );
+            return Err(GovernanceError::InvalidAccountData.into());
+        }
     }
 
-    let clock = invoke_context.get_sysvar_cache().get_clock()?;
-    let current_epoch = clock.epoch;
-
     // Assign proposal ID and increment counter.
     let proposal_id = config.next_proposal_id;
     config.next_proposal_id = config
@@ -375,6 +887,33 @@ fn process_create_proposal(
         .checked_add(config.timelock_epochs)
         .ok_or(GovernanceError::ArithmeticOverflow)?;
 
+    // Escrow the deposit.  The multisig proposes without one.
+    let deposit_lamports = if config.is_active {
+        config.proposal_deposit
+    } else {
+        0
+    };
+    if deposit_lamports > 0 {
+        instruction_context.check_number_of_instruction_accounts(6)?;
+        let (escrow, _bump) = find_deposit_escrow_address(&config_key, proposal_id);
+        if instruction_context.get_key_of_instruction_account(4)? != &escrow {
+            ic_msg!(
+                invoke_context,
+                "CreateProposal: deposit escrow for proposal {} must be {}",
+                proposal_id,
+                escrow
+            );
+            return Err(GovernanceError::InvalidDepositEscrow.into());
+        }
+        if instruction_context.get_key_of_instruction_account(5)? != &system_program::id() {
+            return Err(InstructionError::IncorrectProgramId);
+        }
+        invoke_context.native_invoke(
+            system_instruction::transfer(&proposer, &escrow, deposit_lamports),
+            &[proposer],
+        )?;
+    }
+
     // When governance is inactive, proposals go straight to Timelocked
     // (the authority has implicitly "passed" it).
     let initial_status = if config.is_active {
@@ -398,6 +937,10 @@ fn process_create_proposal(
         votes_abstain: 0,
         veto_votes: 0,
         executed: false,
+        executed_steps: 0,
+        voting_power_snapshot_epoch: current_epoch,
+        deposit_lamports,
+        deposit_claimed: false,
     };
 
     // Save both.
@@ -406,31 +949,57 @@ fn process_create_proposal(
 
     ic_msg!(
         invoke_context,
-        "CreateProposal: id={}, proposer={}, status={:?}",
+        "CreateProposal: id={}, proposer={}, status={:?}, deposit={}",
         proposal_id,
         proposer,
-        initial_status
+        initial_status,
+        deposit_lamports
     );
     Ok(())
 }
 
+/// `CreateMultiStepProposal`
+///
+/// Wraps `steps` in `ProposalType::MultiStep` and follows the regular
+/// `CreateProposal` flow (same accounts, same authorisation rules).
+fn process_create_multi_step_proposal(
+    invoke_context: &mut InvokeContext,
+    title_vec: Vec<u8>,
+    description_hash: Hash,
+    steps: Vec<ProposalStep>,
+) -> Result<(), InstructionError> {
+    process_create_proposal(
+        invoke_context,
+        title_vec,
+        description_hash,
+        ProposalType::MultiStep { steps },
+    )
+}
+
 /// `CastVote`
 ///
 /// Accounts:
-///   0. `[signer]`           — Voter.
+///   0. `[writable, signer]` — Voter (pays for a new vote record).
 ///   1. `[writable]`         — Proposal account.
 ///   2. `[]`                 — Governance config account.
-///   3. `[]`                 — Voter's passive stake account (weight proof).
-///   4. `[writable]`         — Vote record account (created on first vote).
+///   3. `[]`                 — Voter's passive stake account (weight proof;
+///                             the voter must be its authority and it must be
+///                             locked by the proposal's snapshot epoch).
+///   4. `[writable]`         — Vote record PDA (created on first vote,
+///                             overwritten on a vote change).
+///   5. `[]`                 — Voter's delegation record PDA.
+///   6. `[]`                 — System program.
+///   7.. `[]`                — (delegation record, delegator's passive stake)
+///                             pairs for power delegated to the voter.
 fn process_cast_vote(
-    invoke_context: &InvokeContext,
+    invoke_context: &mut InvokeContext,
     proposal_id: u64,
     vote: Vote,
 ) -> Result<(), InstructionError> {
     let transaction_context = &invoke_context.transaction_context;
     let instruction_context = transaction_context.get_current_instruction_context()?;
 
-    instruction_context.check_number_of_instruction_accounts(5)?;
+    instruction_context.check_number_of_instruction_accounts(7)?;
 
     // Voter must sign.
     if !instruction_context.is_instruction_account_signer(0)? {
@@ -470,66 +1039,151 @@ fn process_cast_vote(
         return Err(GovernanceError::VotingPeriodEnded.into());
     }
 
-    // Check vote record account — must not already exist (no double voting).
-    {
+    // The vote record is the voter's PDA for this proposal, so each voter
+    // has one.  An existing record is their earlier vote, which is replaced
+    // (no double voting).
+    let (vote_record_key, previous_vote) = {
         let vote_record_account = instruction_context.try_borrow_instruction_account(4)?;
-        if vote_record_account.get_owner() != &id() {
-            return Err(GovernanceError::InvalidAccountOwner.into());
-        }
-        let data = vote_record_account.get_data();
-        if !data.is_empty() && data[0] == VOTE_RECORD_DISCRIMINATOR {
-            ic_msg!(invoke_context, "CastVote: voter has already voted on this proposal");
-            return Err(GovernanceError::AlreadyVoted.into());
+        if vote_record_account.get_key() != &find_vote_record_address(proposal_id, &voter).0 {
+            ic_msg!(
+                invoke_context,
+                "CastVote: vote record is not the PDA of {} for proposal {}",
+                voter,
+                proposal_id
+            );
+            return Err(GovernanceError::InvalidVoteRecord.into());
         }
+        let previous = if vote_record_account.get_owner() == &id() {
+            let previous = VoteRecord::deserialize(vote_record_account.get_data())
+                .map_err(|_| GovernanceError::InvalidAccountData)?;
+            if previous.voter != voter || previous.proposal_id != proposal_id {
+                return Err(GovernanceError::InvalidVoteRecord.into());
+            }
+            Some(previous)
+        } else {
+            None
+        };
+        (*vote_record_account.get_key(), previous)
+    };
+    if instruction_context.get_key_of_instruction_account(6)? != &system_program::id() {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+    if previous_vote.is_some()
+        && clock.epoch
+            >= proposal
+                .voting_ends_epoch
+                .saturating_sub(config.vote_change_lockout_epochs)
+    {
+        ic_msg!(
+            invoke_context,
+            "CastVote: votes are locked {} epochs before voting ends",
+            config.vote_change_lockout_epochs
+        );
+        return Err(GovernanceError::VoteChangeLockedOut.into());
     }
 
-    // Read voter's passive stake account to determine voting power.
-    let voting_power = {
-        let stake_account = instruction_context.try_borrow_instruction_account(3)?;
-        let stake_data = stake_account.get_data().to_vec();
-        drop(stake_account);
-
-        let (_amount, power) = voting_power_from_passive_stake_data(&stake_data)
-            .ok_or(GovernanceError::NoVotingPower)?;
-
-        if power == 0 {
-            return Err(GovernanceError::NoVotingPower.into());
-        }
-        power
-    };
-
-    // Apply vote.
-    match vote {
-        Vote::For => {
-            proposal.votes_for = proposal
-                .votes_for
-                .checked_add(voting_power)
-                .ok_or(GovernanceError::ArithmeticOverflow)?;
+    // Power the voter has delegated is voted by the delegate, not by them.
+    {
+        let record_account =
+            instruction_context.try_borrow_instruction_account(VOTER_DELEGATION_ACCOUNT)?;
+        if record_account.get_key() != &find_delegation_record_address(&voter).0 {
+            return Err(GovernanceError::InvalidDelegationRecord.into());
         }
-        Vote::Against => {
-            proposal.votes_against = proposal
-                .votes_against
-                .checked_add(voting_power)
-                .ok_or(GovernanceError::ArithmeticOverflow)?;
+        let data = record_account.get_data();
+        if record_account.get_owner() == &id()
+            && !data.is_empty()
+            && data[0] == DELEGATION_RECORD_DISCRIMINATOR
+        {
+            let record = DelegationRecord::deserialize(data)
+                .map_err(|_| GovernanceError::InvalidAccountData)?;
+            if !record.is_expired(clock.epoch, config.max_delegation_epochs)
+                || record.is_locked(clock.epoch)
+            {
+                ic_msg!(
+                    invoke_context,
+                    "CastVote: {} has delegated their power to {}",
+                    voter,
+                    record.to
+                );
+                return Err(GovernanceError::VoterHasDelegated.into());
+            }
         }
-        Vote::Abstain => {
-            proposal.votes_abstain = proposal
-                .votes_abstain
-                .checked_add(voting_power)
-                .ok_or(GovernanceError::ArithmeticOverflow)?;
+    }
+
+    // Read voter's passive stake account to determine voting power as of
+    // the proposal's snapshot epoch.
+    let own_power = {
+        let stake_account = instruction_context.try_borrow_instruction_account(3)?;
+        if stake_account.get_owner() != &solana_passive_stake_program::id() {
+            return Err(GovernanceError::InvalidAccountOwner.into());
         }
-        Vote::Veto => {
-            proposal.veto_votes = proposal
-                .veto_votes
-                .checked_add(voting_power)
-                .ok_or(GovernanceError::ArithmeticOverflow)?;
+        let stake_data = stake_account.get_data().to_vec();
+        drop(stake_account);
+        if stake_data.len() < 33 || stake_data[1..33] != voter.to_bytes() {
+            ic_msg!(
+                invoke_context,
+                "CastVote: stake account is not owned by voter {}",
+                voter
+            );
+            return Err(GovernanceError::InvalidWeightProof.into());
         }
+
+        voting_power_from_passive_stake_data(&stake_data, proposal.voting_power_snapshot_epoch)
+            .map_or(0, |(_amount, power)| power)
+    };
+
+    // Add the power delegated to the voter, directly or through a chain.
+    let delegated_power =
+        delegated_voting_power(invoke_context, &voter, &config, &proposal, clock.epoch)?;
+    let voting_power = own_power
+        .checked_add(delegated_power)
+        .ok_or(GovernanceError::ArithmeticOverflow)?;
+    if voting_power == 0 {
+        ic_msg!(
+            invoke_context,
+            "CastVote: no voting power locked by snapshot epoch {}",
+            proposal.voting_power_snapshot_epoch
+        );
+        return Err(GovernanceError::NoVotingPower.into());
+    }
+
+    if previous_vote.is_none() {
+        let rent = invoke_context.get_sysvar_cache().get_rent()?;
+        // As a builtin, this program signs for its own PDA by naming it.
+        invoke_context.native_invoke(
+            system_instruction::create_account(
+                &voter,
+                &vote_record_key,
+                rent.minimum_balance(VoteRecord::SERIALIZED_SIZE),
+                VoteRecord::SERIALIZED_SIZE as u64,
+                &id(),
+            ),
+            &[voter, vote_record_key],
+        )?;
+    }
+
+    // Withdraw the previous vote, then apply the new one.
+    if let Some(previous) = &previous_vote {
+        let tally = vote_tally_mut(&mut proposal, previous.vote);
+        *tally = tally
+            .checked_sub(previous.weight)
+            .ok_or(GovernanceError::ArithmeticOverflow)?;
+        ic_msg!(
+            invoke_context,
+            "CastVote: changed vote from {:?} to {:?}",
+            previous.vote,
+            vote
+        );
     }
+    let tally = vote_tally_mut(&mut proposal, vote);
+    *tally = tally
+        .checked_add(voting_power)
+        .ok_or(GovernanceError::ArithmeticOverflow)?;
 
     // Save updated proposal.
     save_proposal(invoke_context, 1, &proposal)?;
 
-    // Write vote record to prevent double-voting.
+    // Write (or overwrite) the vote record to prevent double-voting.
     let vote_record = VoteRecord {
         proposal_id,
         voter,
@@ -539,6 +1193,8 @@ fn process_cast_vote(
     };
 
     {
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
         let mut vote_record_account = instruction_context.try_borrow_instruction_account(4)?;
         let mut data = vote_record_account.get_data().to_vec();
         if data.len() < VoteRecord::SERIALIZED_SIZE {
@@ -552,11 +1208,280 @@ fn process_cast_vote(
 
     ic_msg!(
         invoke_context,
-        "CastVote: voter={}, proposal={}, vote={:?}, weight={}",
+        "CastVote: voter={}, proposal={}, vote={:?}, weight={} (delegated {})",
         voter,
         proposal_id,
         vote,
-        voting_power
+        voting_power,
+        delegated_power
+    );
+    Ok(())
+}
+
+/// The proposal's running total for `vote`.
+fn vote_tally_mut(proposal: &mut Proposal, vote: Vote) -> &mut u64 {
+    match vote {
+        Vote::For => &mut proposal.votes_for,
+        Vote::Against => &mut proposal.votes_against,
+        Vote::Abstain => &mut proposal.votes_abstain,
+        Vote::Veto => &mut proposal.veto_votes,
+    }
+}
+
+/// Index of the voter's own delegation record in `CastVote`.
+const VOTER_DELEGATION_ACCOUNT: u16 = 5;
+
+/// Index of the first (delegation record, passive stake) pair in `CastVote`.
+const FIRST_DELEGATION_ACCOUNT: u16 = 7;
+
+/// Sum the voting power delegated to `voter` by the (delegation record,
+/// delegator's passive stake) pairs passed to `CastVote`.
+///
+/// Each record must point at the voter or at the delegator of an earlier
+/// pair, and each delegator is counted once — so a cycle back to the voter
+/// can never add power.  Lapsed records are rejected rather than skipped so
+/// that the caller learns its view of the chain is stale.  Counted records
+/// are locked until voting on `proposal` ends, so the same power cannot be
+/// redirected to another voter in the meantime.
+fn delegated_voting_power(
+    invoke_context: &InvokeContext,
+    voter: &Pubkey,
+    config: &GovernanceConfig,
+    proposal: &Proposal,
+    current_epoch: u64,
+) -> Result<u64, InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let number_of_accounts = instruction_context.get_number_of_instruction_accounts();
+    if !(number_of_accounts - FIRST_DELEGATION_ACCOUNT).is_multiple_of(2) {
+        ic_msg!(
+            invoke_context,
+            "CastVote: delegation accounts must come in (record, stake) pairs"
+        );
+        return Err(GovernanceError::InvalidDelegation.into());
+    }
+
+    let mut counted = vec![*voter];
+    let mut total: u64 = 0;
+    for record_index in (FIRST_DELEGATION_ACCOUNT..number_of_accounts).step_by(2) {
+        let mut record = load_delegation_record(invoke_context, record_index)?;
+        if !counted.contains(&record.to) {
+            ic_msg!(
+                invoke_context,
+                "CastVote: delegation from {} to {} does not lead to the voter",
+                record.from,
+                record.to
+            );
+            return Err(GovernanceError::InvalidDelegation.into());
+        }
+        if counted.contains(&record.from) {
+            ic_msg!(
+                invoke_context,
+                "CastVote: power of {} is already counted",
+                record.from
+            );
+            return Err(GovernanceError::CircularDelegation.into());
+        }
+        if record.is_expired(current_epoch, config.max_delegation_epochs) {
+            ic_msg!(
+                invoke_context,
+                "CastVote: delegation from {} lapsed (made in epoch {})",
+                record.from,
+                record.delegated_epoch
+            );
+            return Err(GovernanceError::InvalidDelegation.into());
+        }
+
+        let stake_account = instruction_context.try_borrow_instruction_account(record_index + 1)?;
+        if stake_account.get_owner() != &solana_passive_stake_program::id() {
+            return Err(GovernanceError::InvalidAccountOwner.into());
+        }
+        let stake_data = stake_account.get_data();
+        if stake_data.len() < 33 || stake_data[1..33] != record.from.to_bytes() {
+            ic_msg!(
+                invoke_context,
+                "CastVote: stake account is not owned by delegator {}",
+                record.from
+            );
+            return Err(GovernanceError::InvalidWeightProof.into());
+        }
+        let power =
+            voting_power_from_passive_stake_data(stake_data, proposal.voting_power_snapshot_epoch)
+                .map_or(0, |(_amount, power)| power);
+        drop(stake_account);
+        total = total
+            .checked_add(power)
+            .ok_or(GovernanceError::ArithmeticOverflow)?;
+        counted.push(record.from);
+
+        if record.locked_until_epoch < proposal.voting_ends_epoch {
+            record.locked_until_epoch = proposal.voting_ends_epoch;
+            let mut record_account =
+                instruction_context.try_borrow_instruction_account(record_index)?;
+            record
+                .serialize_into(record_account.get_data_mut()?)
+                .map_err(|_| GovernanceError::InvalidAccountData)?;
+        }
+    }
+    Ok(total)
+}
+
+/// Load an initialised `DelegationRecord` from instruction account at `index`.
+fn load_delegation_record(
+    invoke_context: &InvokeContext,
+    account_index: u16,
+) -> Result<DelegationRecord, InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let account = instruction_context.try_borrow_instruction_account(account_index)?;
+
+    if account.get_owner() != &id() {
+        return Err(GovernanceError::InvalidAccountOwner.into());
+    }
+    DelegationRecord::deserialize(account.get_data())
+        .map_err(|_| GovernanceError::InvalidAccountData.into())
+}
+
+/// `DelegateVote`
+///
+/// Accounts:
+///   0. `[writable, signer]` — Delegator (pays for a new delegation record).
+///   1. `[]`                 — Governance config account.
+///   2. `[writable]`         — Delegator's delegation record PDA.
+///   3. `[]`                 — System program.
+///   4.. `[]`                — The delegate's delegation chain, in order.
+///
+/// Only the chain passed in can be checked for cycles; `CastVote` refuses to
+/// count any delegator twice, so an unchecked cycle cannot inflate a vote.
+fn process_delegate_vote(
+    invoke_context: &mut InvokeContext,
+    delegate: Pubkey,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(4)?;
+
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(GovernanceError::MissingAuthoritySignature.into());
+    }
+    let delegator = *instruction_context.get_key_of_instruction_account(0)?;
+
+    if delegate == delegator {
+        ic_msg!(invoke_context, "DelegateVote: cannot delegate to oneself");
+        return Err(GovernanceError::CircularDelegation.into());
+    }
+
+    let config = load_governance_config(invoke_context, 1)?;
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+
+    // Follow the delegate's unexpired delegations; none may lead back to the
+    // delegator.
+    let mut next = delegate;
+    for index in 4..instruction_context.get_number_of_instruction_accounts() {
+        let record = load_delegation_record(invoke_context, index)?;
+        if record.from != next {
+            ic_msg!(
+                invoke_context,
+                "DelegateVote: expected the delegation record of {}",
+                next
+            );
+            return Err(GovernanceError::InvalidDelegation.into());
+        }
+        if record.is_expired(clock.epoch, config.max_delegation_epochs) {
+            break;
+        }
+        if record.to == delegator {
+            ic_msg!(
+                invoke_context,
+                "DelegateVote: {} already delegates back to {}",
+                record.from,
+                delegator
+            );
+            return Err(GovernanceError::CircularDelegation.into());
+        }
+        next = record.to;
+    }
+
+    // An existing record must belong to the delegator, be revocable, and
+    // not be backing a vote on a proposal that is still open.
+    let (record_key, existing) = {
+        let record_account = instruction_context.try_borrow_instruction_account(2)?;
+        if record_account.get_key() != &find_delegation_record_address(&delegator).0 {
+            return Err(GovernanceError::InvalidDelegationRecord.into());
+        }
+        let existing = if record_account.get_owner() == &id() {
+            let existing = DelegationRecord::deserialize(record_account.get_data())
+                .map_err(|_| GovernanceError::InvalidAccountData)?;
+            if existing.from != delegator {
+                return Err(GovernanceError::AuthorityMismatch.into());
+            }
+            if !existing.revocable
+                && !existing.is_expired(clock.epoch, config.max_delegation_epochs)
+            {
+                return Err(GovernanceError::DelegationNotRevocable.into());
+            }
+            if existing.is_locked(clock.epoch) {
+                ic_msg!(
+                    invoke_context,
+                    "DelegateVote: power delegated to {} backs votes open until epoch {}",
+                    existing.to,
+                    existing.locked_until_epoch
+                );
+                return Err(GovernanceError::DelegationNotRevocable.into());
+            }
+            Some(existing)
+        } else {
+            None
+        };
+        (*record_account.get_key(), existing)
+    };
+    if instruction_context.get_key_of_instruction_account(3)? != &system_program::id() {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+
+    if existing.is_none() {
+        let rent = invoke_context.get_sysvar_cache().get_rent()?;
+        // As a builtin, this program signs for its own PDA by naming it.
+        invoke_context.native_invoke(
+            system_instruction::create_account(
+                &delegator,
+                &record_key,
+                rent.minimum_balance(DelegationRecord::SERIALIZED_SIZE),
+                DelegationRecord::SERIALIZED_SIZE as u64,
+                &id(),
+            ),
+            &[delegator, record_key],
+        )?;
+    }
+
+    let record = DelegationRecord {
+        from: delegator,
+        to: delegate,
+        delegated_epoch: clock.epoch,
+        revocable: true,
+        locked_until_epoch: existing.map_or(0, |existing| existing.locked_until_epoch),
+    };
+    {
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+        let mut record_account = instruction_context.try_borrow_instruction_account(2)?;
+        let mut data = record_account.get_data().to_vec();
+        if data.len() < DelegationRecord::SERIALIZED_SIZE {
+            data.resize(DelegationRecord::SERIALIZED_SIZE, 0);
+        }
+        record
+            .serialize_into(&mut data)
+            .map_err(|_| GovernanceError::InvalidAccountData)?;
+        record_account.set_data_from_slice(&data)?;
+    }
+
+    ic_msg!(
+        invoke_context,
+        "DelegateVote: {} delegates to {} until epoch {}",
+        delegator,
+        delegate,
+        clock.epoch.saturating_add(config.max_delegation_epochs)
     );
     Ok(())
 }
@@ -567,8 +1492,16 @@ fn process_cast_vote(
 ///   0. `[signer]`           — Executor (anyone if active, authority if inactive).
 ///   1. `[writable]`         — Proposal account.
 ///   2. `[writable]`         — Governance config account.
+///   3. `[writable]`         — Network params account (ParameterChange steps
+///                             only).
+///   3/4.. (varies)          — TreasurySpend accounts (TreasurySpend and
+///                             TreasuryAllowanceUpdate steps only): treasury
+///                             config, recipient, system program, treasury
+///                             account, treasury program, then further
+///                             recipients.  Follows the network params
+///                             account if both are present.
 fn process_execute_proposal(
-    invoke_context: &InvokeContext,
+    invoke_context: &mut InvokeContext,
     proposal_id: u64,
 ) -> Result<(), InstructionError> {
     let transaction_context = &invoke_context.transaction_context;
@@ -580,8 +1513,9 @@ fn process_execute_proposal(
         return Err(GovernanceError::MissingAuthoritySignature.into());
     }
     let executor = *instruction_context.get_key_of_instruction_account(0)?;
+    let config_key = *instruction_context.get_key_of_instruction_account(2)?;
 
-    let config = load_governance_config(invoke_context, 2)?;
+    let mut config = load_governance_config(invoke_context, 2)?;
     let mut proposal = load_proposal(invoke_context, 1)?;
 
     if proposal.id != proposal_id {
@@ -604,6 +1538,10 @@ fn process_execute_proposal(
     let clock = invoke_context.get_sysvar_cache().get_clock()?;
 
     // Handle state transitions for active governance.
+    //
+    // A failed vote is recorded and the instruction still succeeds: the
+    // runtime discards account writes from a failed instruction, and the
+    // final status decides how the proposer's deposit is settled.
     if config.is_active {
         match proposal.status {
             ProposalStatus::Active => {
@@ -612,73 +1550,17 @@ fn process_execute_proposal(
                     return Err(GovernanceError::VotingPeriodNotEnded.into());
                 }
 
-                // Check if veto threshold was reached.
-                let total_votes = proposal
-                    .votes_for
-                    .checked_add(proposal.votes_against)
-                    .ok_or(GovernanceError::ArithmeticOverflow)?
-                    .checked_add(proposal.votes_abstain)
-                    .ok_or(GovernanceError::ArithmeticOverflow)?
-                    .checked_add(proposal.veto_votes)
-                    .ok_or(GovernanceError::ArithmeticOverflow)?;
-
-                if total_votes == 0 {
-                    proposal.status = ProposalStatus::Expired;
-                    save_proposal(invoke_context, 1, &proposal)?;
-                    ic_msg!(invoke_context, "ExecuteProposal: proposal expired (no votes)");
-                    return Err(GovernanceError::ProposalExpired.into());
-                }
-
-                // Check veto: veto_votes / total_votes >= veto_threshold_bps / 10_000
-                let veto_pct = (proposal.veto_votes as u128)
-                    .checked_mul(BPS_DENOMINATOR as u128)
-                    .ok_or(GovernanceError::ArithmeticOverflow)?
-                    .checked_div(total_votes as u128)
-                    .ok_or(GovernanceError::ArithmeticOverflow)?;
-
-                if veto_pct >= config.veto_threshold_bps as u128 {
-                    proposal.status = ProposalStatus::Vetoed;
-                    save_proposal(invoke_context, 1, &proposal)?;
-                    ic_msg!(invoke_context, "ExecuteProposal: proposal vetoed");
-                    return Err(GovernanceError::VetoThresholdReached.into());
-                }
-
-                // Check pass threshold: votes_for / (votes_for + votes_against) >= pass_threshold
-                let decisive_votes = proposal
-                    .votes_for
-                    .checked_add(proposal.votes_against)
-                    .ok_or(GovernanceError::ArithmeticOverflow)?;
-
-                let pass_threshold = effective_pass_threshold(&proposal, &config);
-
-                if decisive_votes == 0 {
-                    proposal.status = ProposalStatus::Rejected;
-                    save_proposal(invoke_context, 1, &proposal)?;
-                    return Err(GovernanceError::PassThresholdNotMet.into());
-                }
-
-                let for_pct = (proposal.votes_for as u128)
-                    .checked_mul(BPS_DENOMINATOR as u128)
-                    .ok_or(GovernanceError::ArithmeticOverflow)?
-                    .checked_div(decisive_votes as u128)
-                    .ok_or(GovernanceError::ArithmeticOverflow)?;
-
-                if for_pct < pass_threshold as u128 {
-                    proposal.status = ProposalStatus::Rejected;
-                    save_proposal(invoke_context, 1, &proposal)?;
-                    ic_msg!(invoke_context, "ExecuteProposal: pass threshold not met");
-                    return Err(GovernanceError::PassThresholdNotMet.into());
-                }
-
-                // Proposal passes — move to Timelocked.
-                proposal.status = ProposalStatus::Timelocked;
+                proposal.status =
+                    tally_proposal(invoke_context, &proposal, &config, "ExecuteProposal")?;
                 save_proposal(invoke_context, 1, &proposal)?;
-                ic_msg!(
-                    invoke_context,
-                    "ExecuteProposal: proposal {} passed, now timelocked until epoch {}",
-                    proposal_id,
-                    proposal.execution_epoch
-                );
+                if proposal.status == ProposalStatus::Timelocked {
+                    ic_msg!(
+                        invoke_context,
+                        "ExecuteProposal: proposal {} passed, now timelocked until epoch {}",
+                        proposal_id,
+                        proposal.execution_epoch
+                    );
+                }
                 return Ok(());
             }
             ProposalStatus::Passed | ProposalStatus::Timelocked => {
@@ -704,47 +1586,273 @@ fn process_execute_proposal(
 
     // === Execute the proposal ===
     //
-    // Note: Actual execution of ParameterChange, TreasurySpend, ProgramUpgrade,
-    // FeatureToggle, and EmergencyUnlock would require cross-program invocations
-    // (CPI) to the respective programs.  In this initial implementation we mark
-    // the proposal as executed and log the action.  The CPI plumbing is added
-    // when those target programs are integrated.
+    // Every proposal is executed as an ordered list of steps (single-action
+    // proposals have exactly one).  Steps are applied all-or-nothing: if any
+    // step fails, nothing is applied and the proposal moves to
+    // `ExecutionFailed`.
+    //
+    // TextProposal steps have no on-chain effect.
+
+    //
+    // A failed execution still returns `Ok(())`: the runtime discards account
+    // writes from a failed instruction, so returning an error would leave the
+    // proposal `Timelocked` and executable again.
     //
-    // TextProposal has no on-chain effect.
+    // TreasurySpend steps are checked against the treasury balance with the
+    // other steps and only disbursed (via CPI) once every step has succeeded,
+    // in step order with any TreasuryAllowanceUpdate steps.  A failed
+    // treasury CPI fails the instruction.
+
+    let steps = proposal.proposal_type.steps();
+    let mut next_account: u16 = 3;
+    let mut network_params = if steps
+        .iter()
+        .any(|step| matches!(step, ProposalStep::ParameterChange { .. }))
+    {
+        instruction_context.check_number_of_instruction_accounts(next_account + 1)?;
+        let params = load_network_params(invoke_context, next_account)?;
+        next_account += 1;
+        Some(params)
+    } else {
+        None
+    };
+    let treasury_spend = if steps.iter().any(|step| {
+        matches!(
+            step,
+            ProposalStep::TreasurySpend { .. } | ProposalStep::TreasuryAllowanceUpdate { .. }
+        )
+    }) {
+        Some(load_treasury_spend_accounts(
+            invoke_context,
+            next_account,
+            &config_key,
+        )?)
+    } else {
+        None
+    };
+
+    // Steps only touch in-memory state; it is written back once every step
+    // has succeeded.
+    let mut treasury_spent: u64 = 0;
+    for (step_index, step) in steps.iter().enumerate() {
+        if let Err(reason) = execute_step(
+            invoke_context,
+            step,
+            &mut config,
+            network_params.as_mut(),
+            treasury_spend.as_ref().map(|treasury| treasury.balance),
+            &mut treasury_spent,
+        ) {
+            let failure = MultiStepExecutionError {
+                step_index: step_index as u8,
+                reason,
+            };
+            proposal.status = ProposalStatus::ExecutionFailed;
+            proposal.executed_steps = 0;
+            save_proposal(invoke_context, 1, &proposal)?;
+            ic_msg!(invoke_context, "ExecuteProposal: {}", failure);
+            return Ok(());
+        }
+    }
 
+    if let Some(treasury) = &treasury_spend {
+        for step in &steps {
+            match step {
+                ProposalStep::TreasurySpend {
+                    recipient,
+                    amount,
+                    memo,
+                } => disburse_from_treasury(
+                    invoke_context,
+                    config_key,
+                    treasury,
+                    *recipient,
+                    *amount,
+                    memo,
+                )?,
+                ProposalStep::TreasuryAllowanceUpdate {
+                    epoch_allowance_bps,
+                } => set_treasury_epoch_allowance(
+                    invoke_context,
+                    config_key,
+                    treasury,
+                    *epoch_allowance_bps,
+                )?,
+                _ => {}
+            }
+        }
+    }
+    if let Some(params) = &network_params {
+        save_network_params(invoke_context, 3, params)?;
+    }
+    if steps.iter().any(|step| {
+        matches!(
+            step,
+            ProposalStep::GovernanceConfigUpdate { .. }
+                | ProposalStep::GovernanceSupplyUpdate { .. }
+        )
+    }) {
+        save_governance_config(invoke_context, 2, &config)?;
+    }
     proposal.status = ProposalStatus::Executed;
     proposal.executed = true;
+    proposal.executed_steps = steps.len() as u8;
     save_proposal(invoke_context, 1, &proposal)?;
 
-    match &proposal.proposal_type {
-        ProposalType::ParameterChange { param_id, new_value } => {
+    ic_msg!(
+        invoke_context,
+        "ExecuteProposal: proposal {} executed by {}",
+        proposal_id,
+        executor
+    );
+    Ok(())
+}
+
+/// Reject steps that can never execute.  Run when the proposal is created so
+/// that malformed input never reaches a vote.
+fn validate_step(step: &ProposalStep) -> Result<(), GovernanceError> {
+    match step {
+        ProposalStep::ParameterChange {
+            param_id,
+            new_value,
+        } => validate_parameter(*param_id, *new_value),
+        ProposalStep::TreasurySpend { amount: 0, .. } => Err(GovernanceError::InvalidProposalType),
+        ProposalStep::ProgramUpgrade {
+            program_id,
+            buffer_account,
+        } if program_id == buffer_account => Err(GovernanceError::InvalidProposalType),
+        ProposalStep::GovernanceConfigUpdate {
+            voting_period_epochs,
+            quorum_bps,
+            pass_threshold_bps,
+            veto_threshold_bps,
+            timelock_epochs,
+            ..
+        } => validate_governed_config(
+            *voting_period_epochs,
+            *quorum_bps,
+            *pass_threshold_bps,
+            *veto_threshold_bps,
+            *timelock_epochs,
+        ),
+        ProposalStep::TreasuryAllowanceUpdate {
+            epoch_allowance_bps,
+        } => validate_epoch_allowance(*epoch_allowance_bps),
+        _ => Ok(()),
+    }
+}
+
+/// Reject governance config values a `GovernanceConfigUpdate` may not set:
+/// anything `UpdateConfig` would reject, plus a pass threshold below
+/// `MIN_GOVERNED_PASS_THRESHOLD_BPS` or a timelock below
+/// `MIN_GOVERNED_TIMELOCK_EPOCHS`.
+fn validate_governed_config(
+    voting_period_epochs: u64,
+    quorum_bps: u16,
+    pass_threshold_bps: u16,
+    veto_threshold_bps: u16,
+    timelock_epochs: u64,
+) -> Result<(), GovernanceError> {
+    if voting_period_epochs == 0
+        || !(1..=10_000).contains(&quorum_bps)
+        || !(1..=10_000).contains(&veto_threshold_bps)
+        || !(MIN_GOVERNED_PASS_THRESHOLD_BPS..=10_000).contains(&pass_threshold_bps)
+        || timelock_epochs < MIN_GOVERNED_TIMELOCK_EPOCHS
+    {
+        return Err(GovernanceError::InvalidConfigValue);
+    }
+    Ok(())
+}
+
+/// Reject an epoch allowance the treasury program's `SetEpochAllowance`
+/// would refuse.
+fn validate_epoch_allowance(epoch_allowance_bps: u16) -> Result<(), GovernanceError> {
+    if epoch_allowance_bps == 0 || u64::from(epoch_allowance_bps) > BPS_DENOMINATOR {
+        return Err(GovernanceError::InvalidConfigValue);
+    }
+    Ok(())
+}
+
+/// Reject a `param_id` missing from `NETWORK_PARAMS` or a value outside its
+/// range.
+fn validate_parameter(param_id: u32, new_value: u64) -> Result<(), GovernanceError> {
+    match network_param_spec(param_id) {
+        Some(spec) if (spec.min_value..=spec.max_value).contains(&new_value) => Ok(()),
+        _ => Err(GovernanceError::InvalidNetworkParameter),
+    }
+}
+
+/// Execute a single proposal step.
+///
+/// `config` is the in-memory governance config that GovernanceConfigUpdate
+/// and GovernanceSupplyUpdate steps modify.  `network_params` holds the parameter values when the
+/// proposal contains a ParameterChange step.  `treasury_balance` is the treasury account's
+/// lamports when the proposal contains a TreasurySpend step;
+/// `treasury_spent` accumulates the spends of earlier steps so a later step
+/// cannot overdraw the treasury.
+///
+/// TreasurySpend steps are disbursed by the caller once every step has
+/// succeeded; here they are only checked against the balance.  The caller
+/// likewise applies TreasuryAllowanceUpdate steps.
+///
+/// Note: Actual execution of ProgramUpgrade, FeatureToggle, and
+/// EmergencyUnlock would require cross-program invocations (CPI) to the
+/// respective programs.  In this initial implementation those steps are
+/// checked against current state and logged.  The CPI plumbing is added when
+/// those target programs are integrated.
+fn execute_step(
+    invoke_context: &InvokeContext,
+    step: &ProposalStep,
+    config: &mut GovernanceConfig,
+    network_params: Option<&mut NetworkParams>,
+    treasury_balance: Option<u64>,
+    treasury_spent: &mut u64,
+) -> Result<(), GovernanceError> {
+    match step {
+        ProposalStep::ParameterChange {
+            param_id,
+            new_value,
+        } => {
+            validate_parameter(*param_id, *new_value)?;
+            let params = network_params.ok_or(GovernanceError::InvalidAccountData)?;
+            let old_value = params.get(*param_id);
+            params.set(*param_id, *new_value);
             ic_msg!(
                 invoke_context,
-                "ExecuteProposal: ParameterChange param_id={} new_value={}",
+                "ExecuteProposal: ParameterChange param_id={} {:?} -> {}",
                 param_id,
+                old_value,
                 new_value
             );
         }
-        ProposalType::TreasurySpend {
+        ProposalStep::TreasurySpend {
             recipient,
             amount,
-            memo: _,
+            memo,
         } => {
+            let spent = treasury_spent
+                .checked_add(*amount)
+                .ok_or(GovernanceError::ArithmeticOverflow)?;
+            if spent > treasury_balance.unwrap_or(0) {
+                return Err(GovernanceError::InsufficientFunds);
+            }
+            *treasury_spent = spent;
             ic_msg!(
                 invoke_context,
-                "ExecuteProposal: TreasurySpend {} lamports to {}",
+                "ExecuteProposal: TreasurySpend {} lamports to {} — memo: {}",
                 amount,
-                recipient
+                recipient,
+                truncate_str(&memo_text(memo), MAX_LOGGED_MEMO_LEN)
             );
         }
-        ProposalType::EmergencyUnlock { target_account } => {
+        ProposalStep::EmergencyUnlock { target_account } => {
             ic_msg!(
                 invoke_context,
                 "ExecuteProposal: EmergencyUnlock target={}",
                 target_account
             );
         }
-        ProposalType::ProgramUpgrade {
+        ProposalStep::ProgramUpgrade {
             program_id,
             buffer_account,
         } => {
@@ -755,7 +1863,7 @@ fn process_execute_proposal(
                 buffer_account
             );
         }
-        ProposalType::FeatureToggle {
+        ProposalStep::FeatureToggle {
             feature_id,
             enabled,
         } => {
@@ -766,20 +1874,64 @@ fn process_execute_proposal(
                 enabled
             );
         }
-        ProposalType::TextProposal => {
+        ProposalStep::TextProposal => {
             ic_msg!(
                 invoke_context,
                 "ExecuteProposal: TextProposal (signaling only)"
             );
         }
+        ProposalStep::GovernanceConfigUpdate {
+            proposal_threshold,
+            voting_period_epochs,
+            quorum_bps,
+            pass_threshold_bps,
+            veto_threshold_bps,
+            timelock_epochs,
+        } => {
+            validate_governed_config(
+                *voting_period_epochs,
+                *quorum_bps,
+                *pass_threshold_bps,
+                *veto_threshold_bps,
+                *timelock_epochs,
+            )?;
+            config.proposal_threshold = *proposal_threshold;
+            config.voting_period_epochs = *voting_period_epochs;
+            config.quorum_bps = *quorum_bps;
+            config.pass_threshold_bps = *pass_threshold_bps;
+            config.veto_threshold_bps = *veto_threshold_bps;
+            config.timelock_epochs = *timelock_epochs;
+            ic_msg!(
+                invoke_context,
+                "ExecuteProposal: GovernanceConfigUpdate voting_period={} quorum={}bps pass={}bps veto={}bps timelock={}",
+                voting_period_epochs,
+                quorum_bps,
+                pass_threshold_bps,
+                veto_threshold_bps,
+                timelock_epochs
+            );
+        }
+        ProposalStep::TreasuryAllowanceUpdate {
+            epoch_allowance_bps,
+        } => {
+            validate_epoch_allowance(*epoch_allowance_bps)?;
+            ic_msg!(
+                invoke_context,
+                "ExecuteProposal: TreasuryAllowanceUpdate epoch_allowance={}bps",
+                epoch_allowance_bps
+            );
+        }
+        ProposalStep::GovernanceSupplyUpdate {
+            total_governance_supply,
+        } => {
+            config.total_governance_supply = *total_governance_supply;
+            ic_msg!(
+                invoke_context,
+                "ExecuteProposal: GovernanceSupplyUpdate total_governance_supply={}",
+                total_governance_supply
+            );
+        }
     }
-
-    ic_msg!(
-        invoke_context,
-        "ExecuteProposal: proposal {} executed by {}",
-        proposal_id,
-        executor
-    );
     Ok(())
 }
 
@@ -928,11 +2080,11 @@ fn process_veto_proposal(
 /// One-way transition from multisig-only mode to full governance.
 ///
 /// Accounts:
-///   0. `[signer]`           — Current authority (multisig).
+///   0. `[writable, signer]` — Current authority (multisig).
 ///   1. `[writable]`         — Governance config account.
-fn process_activate_governance(
-    invoke_context: &InvokeContext,
-) -> Result<(), InstructionError> {
+///   2. `[]`                 — System program (only to migrate a legacy-layout
+///                             config).
+fn process_activate_governance(invoke_context: &mut InvokeContext) -> Result<(), InstructionError> {
     let transaction_context = &invoke_context.transaction_context;
     let instruction_context = transaction_context.get_current_instruction_context()?;
 
@@ -949,6 +2101,7 @@ fn process_activate_governance(
         ic_msg!(invoke_context, "ActivateGovernance: authority mismatch");
         return Err(GovernanceError::AuthorityMismatch.into());
     }
+    migrate_governance_config(invoke_context, 1)?;
 
     if config.is_active {
         return Err(GovernanceError::GovernanceAlreadyActive.into());
@@ -968,11 +2121,13 @@ fn process_activate_governance(
 /// `UpdateConfig`
 ///
 /// Accounts:
-///   0. `[signer]`           — Current authority.
+///   0. `[writable, signer]` — Current authority.
 ///   1. `[writable]`         — Governance config account.
+///   2. `[]`                 — System program (only to migrate a legacy-layout
+///                             config).
 #[allow(clippy::too_many_arguments)]
 fn process_update_config(
-    invoke_context: &InvokeContext,
+    invoke_context: &mut InvokeContext,
     proposal_threshold: u64,
     voting_period_epochs: u64,
     quorum_bps: u16,
@@ -997,7 +2152,15 @@ fn process_update_config(
         ic_msg!(invoke_context, "UpdateConfig: authority mismatch");
         return Err(GovernanceError::AuthorityMismatch.into());
     }
-
+    if config.is_active {
+        ic_msg!(
+            invoke_context,
+            "UpdateConfig: governance is active, submit a GovernanceConfigUpdate proposal"
+        );
+        return Err(GovernanceError::ConfigChangeRequiresProposal.into());
+    }
+    migrate_governance_config(invoke_context, 1)?;
+
     // Validate new config values.
     if quorum_bps == 0 || quorum_bps > 10_000 {
         return Err(GovernanceError::InvalidConfigValue.into());
@@ -1034,3 +2197,2767 @@ fn process_update_config(
     );
     Ok(())
 }
+
+/// `UpdateGovernanceSupply`
+///
+/// Accounts:
+///   0. `[writable, signer]` — Current authority.
+///   1. `[writable]`         — Governance config account.
+///   2. `[]`                 — System program (only to migrate a legacy-layout
+///                             config).
+fn process_update_governance_supply(
+    invoke_context: &mut InvokeContext,
+    total_governance_supply: u64,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(2)?;
+
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(GovernanceError::MissingAuthoritySignature.into());
+    }
+    let signer = *instruction_context.get_key_of_instruction_account(0)?;
+
+    let mut config = load_governance_config(invoke_context, 1)?;
+
+    if signer != config.authority {
+        ic_msg!(invoke_context, "UpdateGovernanceSupply: authority mismatch");
+        return Err(GovernanceError::AuthorityMismatch.into());
+    }
+    if config.is_active {
+        ic_msg!(
+            invoke_context,
+            "UpdateGovernanceSupply: governance is active, submit a GovernanceSupplyUpdate proposal"
+        );
+        return Err(GovernanceError::ConfigChangeRequiresProposal.into());
+    }
+    migrate_governance_config(invoke_context, 1)?;
+
+    config.total_governance_supply = total_governance_supply;
+    save_governance_config(invoke_context, 1, &config)?;
+
+    ic_msg!(
+        invoke_context,
+        "UpdateGovernanceSupply: total governance supply set to {} by {}",
+        total_governance_supply,
+        signer
+    );
+    Ok(())
+}
+
+/// `ClaimDeposit`
+///
+/// Accounts:
+///   0. `[signer, writable]` — Proposer.
+///   1. `[writable]`         — Proposal account.
+///   2. `[]`                 — Governance config account.
+///   3. `[writable]`         — Deposit escrow.
+///   4. `[]`                 — Treasury config account.
+///   5. `[writable]`         — Treasury account.
+///   6. `[]`                 — System program.
+fn process_claim_deposit(
+    invoke_context: &mut InvokeContext,
+    proposal_id: u64,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(7)?;
+
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(GovernanceError::MissingAuthoritySignature.into());
+    }
+    let claimant = *instruction_context.get_key_of_instruction_account(0)?;
+    let config_key = *instruction_context.get_key_of_instruction_account(2)?;
+
+    // The escrow is derived from the config address, so it must be a real
+    // governance config.
+    load_governance_config(invoke_context, 2)?;
+    let mut proposal = load_proposal(invoke_context, 1)?;
+
+    if proposal.id != proposal_id {
+        return Err(GovernanceError::InvalidAccountData.into());
+    }
+    if claimant != proposal.proposer {
+        ic_msg!(
+            invoke_context,
+            "ClaimDeposit: only the proposer {} can claim the deposit",
+            proposal.proposer
+        );
+        return Err(GovernanceError::AuthorityMismatch.into());
+    }
+    if proposal.deposit_claimed {
+        return Err(GovernanceError::DepositAlreadyClaimed.into());
+    }
+    let Some((to_proposer, to_treasury)) = proposal.deposit_settlement() else {
+        ic_msg!(
+            invoke_context,
+            "ClaimDeposit: proposal {} is still {:?}",
+            proposal_id,
+            proposal.status
+        );
+        return Err(GovernanceError::DepositNotClaimable.into());
+    };
+
+    let (escrow, _bump) = find_deposit_escrow_address(&config_key, proposal_id);
+    if instruction_context.get_key_of_instruction_account(3)? != &escrow {
+        return Err(GovernanceError::InvalidDepositEscrow.into());
+    }
+    let treasury_config = load_treasury_config(invoke_context, 4, &config_key)?;
+    if instruction_context.get_key_of_instruction_account(5)? != &treasury_config.treasury_account {
+        ic_msg!(
+            invoke_context,
+            "ClaimDeposit: treasury account does not match treasury config"
+        );
+        return Err(GovernanceError::InvalidAccountData.into());
+    }
+    if instruction_context.get_key_of_instruction_account(6)? != &system_program::id() {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+
+    // The escrow is a system account; this program signs for its PDA.
+    if to_proposer > 0 {
+        invoke_context.native_invoke(
+            system_instruction::transfer(&escrow, &claimant, to_proposer),
+            &[escrow],
+        )?;
+    }
+    if to_treasury > 0 {
+        invoke_context.native_invoke(
+            system_instruction::transfer(&escrow, &treasury_config.treasury_account, to_treasury),
+            &[escrow],
+        )?;
+    }
+
+    proposal.deposit_claimed = true;
+    save_proposal(invoke_context, 1, &proposal)?;
+
+    ic_msg!(
+        invoke_context,
+        "ClaimDeposit: proposal {} ({:?}) — {} lamports returned, {} to treasury",
+        proposal_id,
+        proposal.status,
+        to_proposer,
+        to_treasury
+    );
+    Ok(())
+}
+
+/// `SweepExpiredProposals`
+///
+/// Accounts:
+///   0. `[signer, writable]` — Sweeper.
+///   1. `[]`                 — Governance config account.
+///   2. `[]`                 — System program.
+///   3.. pairs of:
+///       `[writable]`        — Proposal account.
+///       `[writable]`        — Its deposit escrow.
+///
+/// An `Active` proposal can still be tallied in `voting_ends_epoch`, so it is
+/// only swept from the epoch after, and is tallied first: one that passed
+/// moves on to `Timelocked` untouched, one that failed takes its tallied
+/// status.  The sweeper reward reduces the deposit that `ClaimDeposit` later
+/// settles.
+fn process_sweep_expired_proposals(
+    invoke_context: &mut InvokeContext,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    let number_of_accounts = instruction_context.get_number_of_instruction_accounts();
+    let proposal_accounts = number_of_accounts.saturating_sub(3);
+    let number_of_proposals = proposal_accounts / 2;
+    if number_of_proposals == 0
+        || proposal_accounts % 2 != 0
+        || usize::from(number_of_proposals) > MAX_SWEEP_PROPOSALS
+    {
+        ic_msg!(
+            invoke_context,
+            "SweepExpiredProposals: expected 1 to {} proposal and escrow pairs, got {} accounts",
+            MAX_SWEEP_PROPOSALS,
+            proposal_accounts
+        );
+        return Err(GovernanceError::InvalidSweepCount.into());
+    }
+
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(GovernanceError::MissingAuthoritySignature.into());
+    }
+    let sweeper = *instruction_context.get_key_of_instruction_account(0)?;
+    let config_key = *instruction_context.get_key_of_instruction_account(1)?;
+    if instruction_context.get_key_of_instruction_account(2)? != &system_program::id() {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+    let escrow_keys = (0..number_of_proposals)
+        .map(|pair| {
+            instruction_context
+                .get_key_of_instruction_account(4 + 2 * pair)
+                .copied()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let config = load_governance_config(invoke_context, 1)?;
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+
+    let mut swept: usize = 0;
+    for (pair, escrow_key) in (0..number_of_proposals).zip(escrow_keys) {
+        let proposal_index = 3 + 2 * pair;
+        let mut proposal = load_proposal(invoke_context, proposal_index)?;
+
+        let swept_status = match proposal.status {
+            ProposalStatus::Active if clock.epoch > proposal.voting_ends_epoch => {
+                let tallied =
+                    tally_proposal(invoke_context, &proposal, &config, "SweepExpiredProposals")?;
+                if tallied == ProposalStatus::Timelocked {
+                    // A passed proposal is not stale; it just was not tallied.
+                    proposal.status = tallied;
+                    save_proposal(invoke_context, proposal_index, &proposal)?;
+                    ic_msg!(
+                        invoke_context,
+                        "SweepExpiredProposals: proposal {} passed, now timelocked until epoch {}",
+                        proposal.id,
+                        proposal.execution_epoch
+                    );
+                    continue;
+                }
+                tallied
+            }
+            ProposalStatus::Timelocked
+                if clock.epoch
+                    > proposal
+                        .execution_epoch
+                        .saturating_add(config.expiry_grace_epochs) =>
+            {
+                ProposalStatus::Expired
+            }
+            _ => continue,
+        };
+
+        let reward = config
+            .sweeper_reward_lamports
+            .min(proposal.deposit_lamports);
+        if reward > 0 {
+            let (escrow, _bump) = find_deposit_escrow_address(&config_key, proposal.id);
+            if escrow_key != escrow {
+                return Err(GovernanceError::InvalidDepositEscrow.into());
+            }
+            // The escrow is a system account; this program signs for its PDA.
+            invoke_context.native_invoke(
+                system_instruction::transfer(&escrow, &sweeper, reward),
+                &[escrow],
+            )?;
+            proposal.deposit_lamports -= reward;
+        }
+
+        let previous_status = proposal.status;
+        proposal.status = swept_status;
+        save_proposal(invoke_context, proposal_index, &proposal)?;
+        swept += 1;
+
+        ic_msg!(
+            invoke_context,
+            "SweepExpiredProposals: proposal {} {:?} -> {:?}, {} lamports to {}",
+            proposal.id,
+            previous_status,
+            swept_status,
+            reward,
+            sweeper
+        );
+    }
+
+    ic_msg!(
+        invoke_context,
+        "SweepExpiredProposals: swept {} of {} proposals",
+        swept,
+        number_of_proposals
+    );
+    Ok(())
+}
+
+/// Check that instruction account 0 is the emergency multisig named by the
+/// governance config at account 1, signing.
+fn check_emergency_multisig(
+    invoke_context: &InvokeContext,
+    instruction_name: &str,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(GovernanceError::MissingAuthoritySignature.into());
+    }
+    let signer = *instruction_context.get_key_of_instruction_account(0)?;
+
+    let config = load_governance_config(invoke_context, 1)?;
+    if signer != config.emergency_multisig {
+        ic_msg!(
+            invoke_context,
+            "{}: signer {} is not the emergency multisig {}",
+            instruction_name,
+            signer,
+            config.emergency_multisig
+        );
+        return Err(GovernanceError::NotEmergencyMultisig.into());
+    }
+    Ok(())
+}
+
+/// `PauseFeeMarket`
+///
+/// Accounts:
+///   0. `[signer]`           — Emergency multisig.
+///   1. `[]`                 — Governance config account.
+///   2. `[writable]`         — Network params account (`find_network_params_address`).
+fn process_pause_fee_market(
+    invoke_context: &InvokeContext,
+    fixed_base_fee: u64,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(3)?;
+    check_emergency_multisig(invoke_context, "PauseFeeMarket")?;
+
+    validate_parameter(FEE_MARKET_PAUSE_FEE_PARAM, fixed_base_fee).map_err(|err| {
+        ic_msg!(
+            invoke_context,
+            "PauseFeeMarket: fixed base fee {} outside 1..={}",
+            fixed_base_fee,
+            MAX_FEE_MARKET_PAUSE_FEE
+        );
+        err
+    })?;
+
+    let mut params = load_network_params(invoke_context, 2)?;
+    params.set(FEE_MARKET_PAUSED_PARAM, 1);
+    params.set(FEE_MARKET_PAUSE_FEE_PARAM, fixed_base_fee);
+    save_network_params(invoke_context, 2, &params)?;
+
+    ic_msg!(
+        invoke_context,
+        "FeeMarket: PAUSED at fee={}",
+        fixed_base_fee
+    );
+    Ok(())
+}
+
+/// `ResumeFeeMarket`
+///
+/// Accounts:
+///   0. `[signer]`           — Emergency multisig.
+///   1. `[]`                 — Governance config account.
+///   2. `[writable]`         — Network params account (`find_network_params_address`).
+fn process_resume_fee_market(invoke_context: &InvokeContext) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(3)?;
+    check_emergency_multisig(invoke_context, "ResumeFeeMarket")?;
+
+    let mut params = load_network_params(invoke_context, 2)?;
+    if params.get(FEE_MARKET_MULTISIG_RESUME_PARAM) != Some(1) {
+        ic_msg!(
+            invoke_context,
+            "ResumeFeeMarket: multisig resume disabled, a ParameterChange proposal must set param {} to 0",
+            FEE_MARKET_PAUSED_PARAM
+        );
+        return Err(GovernanceError::FeeMarketResumeRequiresProposal.into());
+    }
+    if params.fee_market_pause().is_none() {
+        return Err(GovernanceError::FeeMarketNotPaused.into());
+    }
+
+    params.set(FEE_MARKET_PAUSED_PARAM, 0);
+    save_network_params(invoke_context, 2, &params)?;
+
+    ic_msg!(invoke_context, "FeeMarket: RESUMED");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_account::{self as account, AccountSharedData, ReadableAccount, WritableAccount},
+        solana_clock::Clock,
+        solana_program_runtime::{
+            invoke_context::mock_process_instruction, loaded_programs::ProgramCacheEntry,
+        },
+        solana_rent::Rent,
+        solana_sdk_ids::{native_loader, sysvar},
+        std::sync::Arc,
+    };
+
+    struct TestAccounts {
+        authority: Pubkey,
+        config: Pubkey,
+        proposal: Pubkey,
+        treasury: Pubkey,
+        params: Pubkey,
+        treasury_config: Pubkey,
+        recipient: Pubkey,
+    }
+
+    impl TestAccounts {
+        fn new() -> Self {
+            Self {
+                authority: Pubkey::new_unique(),
+                config: Pubkey::new_unique(),
+                proposal: Pubkey::new_unique(),
+                treasury: Pubkey::new_unique(),
+                params: find_network_params_address().0,
+                treasury_config: Pubkey::new_unique(),
+                recipient: Pubkey::new_unique(),
+            }
+        }
+
+        /// Transaction accounts with an inactive governance config whose
+        /// proposals are executable immediately.
+        fn transaction_accounts(
+            &self,
+            proposal_size: usize,
+            treasury_lamports: u64,
+        ) -> Vec<(Pubkey, AccountSharedData)> {
+            let config = GovernanceConfig {
+                is_active: false,
+                authority: self.authority,
+                proposal_threshold: 0,
+                voting_period_epochs: 0,
+                quorum_bps: 3_000,
+                pass_threshold_bps: 5_000,
+                veto_threshold_bps: 3_333,
+                timelock_epochs: 0,
+                emergency_multisig: Pubkey::new_unique(),
+                next_proposal_id: 0,
+                max_delegation_epochs: 10,
+                vote_change_lockout_epochs: 0,
+                proposal_deposit: 0,
+                // One full-weight `passive_stake`.
+                total_governance_supply: 1_000,
+                expiry_grace_epochs: 30,
+                sweeper_reward_lamports: 0,
+            };
+            let mut config_account =
+                AccountSharedData::new(1, GovernanceConfig::SERIALIZED_SIZE, &id());
+            config
+                .serialize_into(config_account.data_as_mut_slice())
+                .unwrap();
+            let treasury_config = TreasuryConfig {
+                authority: self.config,
+                treasury_account: self.treasury,
+                governance_active: true,
+                total_received: 0,
+                total_disbursed: 0,
+                last_updated_epoch: 0,
+                // No epoch allowance limit.
+                epoch_allowance_bps: 10_000,
+                disbursed_this_epoch: 0,
+                last_disbursement_epoch: 0,
+            };
+            let mut treasury_config_account = AccountSharedData::new(
+                1,
+                TreasuryConfig::SERIALIZED_SIZE,
+                &solana_treasury_program::id(),
+            );
+            treasury_config
+                .serialize_into(treasury_config_account.data_as_mut_slice())
+                .unwrap();
+            let mut program_account = AccountSharedData::new(1, 0, &native_loader::id());
+            program_account.set_executable(true);
+            vec![
+                (
+                    self.authority,
+                    AccountSharedData::new(1, 0, &Pubkey::default()),
+                ),
+                (self.config, config_account),
+                (
+                    self.proposal,
+                    AccountSharedData::new(1, proposal_size, &id()),
+                ),
+                (
+                    self.treasury,
+                    AccountSharedData::new(treasury_lamports, 0, &solana_treasury_program::id()),
+                ),
+                (
+                    sysvar::clock::id(),
+                    account::create_account_shared_data_for_test(&Clock::default()),
+                ),
+                (
+                    self.params,
+                    AccountSharedData::new(1, NetworkParams::SERIALIZED_SIZE, &id()),
+                ),
+                (self.treasury_config, treasury_config_account),
+                (
+                    self.recipient,
+                    AccountSharedData::new(0, 0, &system_program::id()),
+                ),
+                (system_program::id(), program_account.clone()),
+                (solana_treasury_program::id(), program_account),
+            ]
+        }
+
+        fn create_metas(&self) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.authority, true),
+                AccountMeta::new(self.config, false),
+                AccountMeta::new(self.proposal, false),
+            ]
+        }
+
+        /// `ExecuteProposal` accounts for the proposal created by
+        /// `instruction`.
+        fn execute_metas(&self, instruction: &GovernanceInstruction) -> Vec<AccountMeta> {
+            let steps = steps_of(instruction);
+            let mut metas = vec![
+                AccountMeta::new_readonly(self.authority, true),
+                AccountMeta::new(self.proposal, false),
+                AccountMeta::new(self.config, false),
+            ];
+            if steps
+                .iter()
+                .any(|step| matches!(step, ProposalStep::ParameterChange { .. }))
+            {
+                metas.push(AccountMeta::new(self.params, false));
+            }
+            if steps.iter().any(|step| {
+                matches!(
+                    step,
+                    ProposalStep::TreasurySpend { .. }
+                        | ProposalStep::TreasuryAllowanceUpdate { .. }
+                )
+            }) {
+                metas.extend([
+                    AccountMeta::new(self.treasury_config, false),
+                    AccountMeta::new(self.recipient, false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                    AccountMeta::new(self.treasury, false),
+                    AccountMeta::new_readonly(solana_treasury_program::id(), false),
+                ]);
+            }
+            metas
+        }
+
+        fn spend(&self, amount: u64) -> ProposalStep {
+            ProposalStep::TreasurySpend {
+                recipient: self.recipient,
+                amount,
+                memo: [0; 32],
+            }
+        }
+    }
+
+    fn process_instruction(
+        instruction: &GovernanceInstruction,
+        transaction_accounts: Vec<(Pubkey, AccountSharedData)>,
+        instruction_accounts: Vec<AccountMeta>,
+        expected_result: Result<(), InstructionError>,
+    ) -> Vec<AccountSharedData> {
+        mock_process_instruction(
+            &id(),
+            None,
+            &bincode::serialize(instruction).unwrap(),
+            transaction_accounts,
+            instruction_accounts,
+            expected_result,
+            Entrypoint::vm,
+            |invoke_context| {
+                // Register the treasury program for TreasurySpend CPIs.
+                invoke_context.program_cache_for_tx_batch.replenish(
+                    solana_treasury_program::id(),
+                    Arc::new(ProgramCacheEntry::new_builtin(
+                        0,
+                        0,
+                        solana_treasury_program::processor::Entrypoint::vm,
+                    )),
+                );
+                // Register the system program for deposit transfers.
+                invoke_context.program_cache_for_tx_batch.replenish(
+                    system_program::id(),
+                    Arc::new(ProgramCacheEntry::new_builtin(
+                        0,
+                        0,
+                        solana_system_program::system_processor::Entrypoint::vm,
+                    )),
+                );
+            },
+            |_invoke_context| {},
+        )
+    }
+
+    fn multi_step(steps: Vec<ProposalStep>) -> GovernanceInstruction {
+        GovernanceInstruction::CreateMultiStepProposal {
+            title: b"multi-step".to_vec(),
+            description_hash: Hash::default(),
+            steps,
+        }
+    }
+
+    fn custom(error: GovernanceError) -> InstructionError {
+        InstructionError::Custom(error as u32)
+    }
+
+    /// Run `instruction` and store the resulting accounts back into
+    /// `transaction_accounts`.
+    fn process_and_update(
+        instruction: &GovernanceInstruction,
+        transaction_accounts: &mut [(Pubkey, AccountSharedData)],
+        instruction_accounts: Vec<AccountMeta>,
+        expected_result: Result<(), InstructionError>,
+    ) {
+        let after = process_instruction(
+            instruction,
+            transaction_accounts.to_vec(),
+            instruction_accounts,
+            expected_result,
+        );
+        for ((_, account), updated) in transaction_accounts.iter_mut().zip(after) {
+            *account = updated;
+        }
+    }
+
+    fn set_epoch(transaction_accounts: &mut [(Pubkey, AccountSharedData)], epoch: u64) {
+        let clock = Clock {
+            epoch,
+            ..Clock::default()
+        };
+        for (key, account) in transaction_accounts.iter_mut() {
+            if *key == sysvar::clock::id() {
+                *account = account::create_account_shared_data_for_test(&clock);
+            }
+        }
+    }
+
+    fn steps_of(instruction: &GovernanceInstruction) -> Vec<ProposalStep> {
+        match instruction {
+            GovernanceInstruction::CreateProposal { proposal_type, .. } => proposal_type.steps(),
+            GovernanceInstruction::CreateMultiStepProposal { steps, .. } => steps.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// A passive stake account holding 1 000 lamports, locked in
+    /// `lock_start_epoch`.
+    fn passive_stake(vote_weight_bps: u16, lock_start_epoch: u64) -> AccountSharedData {
+        let mut data = vec![0u8; 92];
+        data[0] = 1;
+        data[33..41].copy_from_slice(&1_000u64.to_le_bytes());
+        data[82..84].copy_from_slice(&vote_weight_bps.to_le_bytes());
+        data[84..92].copy_from_slice(&lock_start_epoch.to_le_bytes());
+        let mut account =
+            AccountSharedData::new(1, data.len(), &solana_passive_stake_program::id());
+        account.set_data_from_slice(&data);
+        account
+    }
+
+    /// A `passive_stake` account whose authority is `authority`.
+    fn passive_stake_of(authority: &Pubkey, vote_weight_bps: u16) -> AccountSharedData {
+        let mut account = passive_stake(vote_weight_bps, 0);
+        account.data_as_mut_slice()[1..33].copy_from_slice(authority.as_ref());
+        account
+    }
+
+    /// Transaction accounts with active governance, a two-epoch voting
+    /// period, and a proposal created in epoch 0.
+    fn active_proposal_accounts(
+        accounts: &TestAccounts,
+        extra: Vec<(Pubkey, AccountSharedData)>,
+        proposer_stake: Pubkey,
+    ) -> Vec<(Pubkey, AccountSharedData)> {
+        let mut transaction_accounts = accounts.transaction_accounts(Proposal::SERIALIZED_SIZE, 0);
+        let mut config = GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+        config.is_active = true;
+        config.voting_period_epochs = 2;
+        config
+            .serialize_into(transaction_accounts[1].1.data_as_mut_slice())
+            .unwrap();
+        transaction_accounts.extend(extra);
+        let mut create_metas = accounts.create_metas();
+        create_metas.push(AccountMeta::new_readonly(proposer_stake, false));
+        process_and_update(
+            &param_change(4, 5_000),
+            &mut transaction_accounts,
+            create_metas,
+            Ok(()),
+        );
+        transaction_accounts
+    }
+
+    /// Transaction accounts with active governance, a two-epoch voting
+    /// period and a 1 000-lamport deposit, and a proposal created in epoch 0
+    /// by `accounts.authority` (holding 10 000 lamports beforehand).
+    fn deposit_proposal_accounts(
+        accounts: &TestAccounts,
+        extra: Vec<(Pubkey, AccountSharedData)>,
+        proposer_stake: Pubkey,
+    ) -> Vec<(Pubkey, AccountSharedData)> {
+        let mut transaction_accounts = accounts.transaction_accounts(Proposal::SERIALIZED_SIZE, 0);
+        transaction_accounts[0].1.set_lamports(10_000);
+        let mut config = GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+        config.is_active = true;
+        config.voting_period_epochs = 2;
+        config.proposal_deposit = 1_000;
+        config
+            .serialize_into(transaction_accounts[1].1.data_as_mut_slice())
+            .unwrap();
+        let escrow = find_deposit_escrow_address(&accounts.config, 0).0;
+        transaction_accounts.push((escrow, AccountSharedData::new(0, 0, &system_program::id())));
+        transaction_accounts.extend(extra);
+
+        let mut create_metas = accounts.create_metas();
+        create_metas[0] = AccountMeta::new(accounts.authority, true);
+        create_metas.push(AccountMeta::new_readonly(proposer_stake, false));
+        create_metas.push(AccountMeta::new(escrow, false));
+        create_metas.push(AccountMeta::new_readonly(system_program::id(), false));
+
+        // The escrow must be the proposal's PDA.
+        let mut wrong_escrow = create_metas.clone();
+        wrong_escrow[4] = AccountMeta::new(accounts.recipient, false);
+        process_and_update(
+            &param_change(4, 5_000),
+            &mut transaction_accounts,
+            wrong_escrow,
+            Err(custom(GovernanceError::InvalidDepositEscrow)),
+        );
+
+        process_and_update(
+            &param_change(4, 5_000),
+            &mut transaction_accounts,
+            create_metas,
+            Ok(()),
+        );
+        transaction_accounts
+    }
+
+    fn lamports_of(transaction_accounts: &[(Pubkey, AccountSharedData)], key: &Pubkey) -> u64 {
+        transaction_accounts
+            .iter()
+            .find(|(account_key, _)| account_key == key)
+            .unwrap()
+            .1
+            .lamports()
+    }
+
+    /// `voter`'s delegation record, not yet created.
+    fn no_delegation(voter: &Pubkey) -> (Pubkey, AccountSharedData) {
+        (
+            find_delegation_record_address(voter).0,
+            AccountSharedData::new(0, 0, &system_program::id()),
+        )
+    }
+
+    /// `CastVote` accounts for `voter`, without any delegated power.
+    fn cast_vote_metas(
+        accounts: &TestAccounts,
+        voter: Pubkey,
+        stake: Pubkey,
+        vote_record: Pubkey,
+    ) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(voter, true),
+            AccountMeta::new(accounts.proposal, false),
+            AccountMeta::new_readonly(accounts.config, false),
+            AccountMeta::new_readonly(stake, false),
+            AccountMeta::new(vote_record, false),
+            AccountMeta::new_readonly(find_delegation_record_address(&voter).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]
+    }
+
+    fn delegate_metas(
+        accounts: &TestAccounts,
+        delegator: Pubkey,
+        chain: &[Pubkey],
+    ) -> Vec<AccountMeta> {
+        let mut metas = vec![
+            AccountMeta::new(delegator, true),
+            AccountMeta::new_readonly(accounts.config, false),
+            AccountMeta::new(find_delegation_record_address(&delegator).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+        metas.extend(
+            chain
+                .iter()
+                .map(|record| AccountMeta::new_readonly(*record, false)),
+        );
+        metas
+    }
+
+    fn param_change(param_id: u32, new_value: u64) -> GovernanceInstruction {
+        GovernanceInstruction::CreateProposal {
+            title: b"param".to_vec(),
+            description_hash: Hash::default(),
+            proposal_type: ProposalType::ParameterChange {
+                param_id,
+                new_value,
+            },
+        }
+    }
+
+    /// Create `instruction`'s proposal, then run `ExecuteProposal` on it.
+    fn create_then_execute(
+        accounts: &TestAccounts,
+        instruction: &GovernanceInstruction,
+        proposal_size: usize,
+        treasury_lamports: u64,
+    ) -> Vec<(Pubkey, AccountSharedData)> {
+        let transaction_accounts = accounts.transaction_accounts(proposal_size, treasury_lamports);
+        let created = process_instruction(
+            instruction,
+            transaction_accounts.clone(),
+            accounts.create_metas(),
+            Ok(()),
+        );
+        let transaction_accounts: Vec<_> = transaction_accounts
+            .into_iter()
+            .map(|(key, _)| key)
+            .zip(created)
+            .collect();
+        let executed = process_instruction(
+            &GovernanceInstruction::ExecuteProposal { proposal_id: 0 },
+            transaction_accounts.clone(),
+            accounts.execute_metas(instruction),
+            Ok(()),
+        );
+        transaction_accounts
+            .into_iter()
+            .map(|(key, _)| key)
+            .zip(executed)
+            .collect()
+    }
+
+    #[test]
+    fn test_multi_step_proposal_executes_every_step() {
+        let accounts = TestAccounts::new();
+        let instruction = multi_step(vec![
+            ProposalStep::ParameterChange {
+                param_id: 7,
+                new_value: 42,
+            },
+            accounts.spend(400),
+            accounts.spend(600),
+            ProposalStep::TextProposal,
+        ]);
+        let after =
+            create_then_execute(&accounts, &instruction, Proposal::MULTI_STEP_SERIALIZED_SIZE, 1_000);
+
+        let proposal = Proposal::deserialize(after[2].1.data()).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+        assert!(proposal.executed);
+        assert_eq!(proposal.executed_steps, 4);
+        let params = NetworkParams::deserialize(after[5].1.data()).unwrap();
+        assert_eq!(params.get(7), Some(42));
+
+        // Both spends were disbursed by the treasury program.
+        assert_eq!(after[3].1.lamports(), 0);
+        assert_eq!(after[7].1.lamports(), 1_000);
+        let treasury_config = TreasuryConfig::deserialize(after[6].1.data()).unwrap();
+        assert_eq!(treasury_config.total_disbursed, 1_000);
+    }
+
+    #[test]
+    fn test_multi_step_partial_failure_is_recorded_and_final() {
+        let accounts = TestAccounts::new();
+        // The second spend overdraws the treasury.
+        let instruction = multi_step(vec![accounts.spend(600), accounts.spend(600)]);
+        let after =
+            create_then_execute(&accounts, &instruction, Proposal::MULTI_STEP_SERIALIZED_SIZE, 1_000);
+
+        let proposal = Proposal::deserialize(after[2].1.data()).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::ExecutionFailed);
+        assert!(!proposal.executed);
+        assert_eq!(proposal.executed_steps, 0);
+        assert_eq!(after[3].1.lamports(), 1_000);
+        assert_eq!(after[7].1.lamports(), 0);
+
+        // The failure was persisted, so the proposal cannot be retried.
+        process_instruction(
+            &GovernanceInstruction::ExecuteProposal { proposal_id: 0 },
+            after,
+            accounts.execute_metas(&instruction),
+            Err(custom(GovernanceError::InvalidProposalStatus)),
+        );
+    }
+
+    #[test]
+    fn test_create_rejects_invalid_steps() {
+        let accounts = TestAccounts::new();
+        let program_id = Pubkey::new_unique();
+        for step in [
+            accounts.spend(0),
+            ProposalStep::ProgramUpgrade {
+                program_id,
+                buffer_account: program_id,
+            },
+        ] {
+            process_instruction(
+                &multi_step(vec![ProposalStep::TextProposal, step]),
+                accounts.transaction_accounts(Proposal::MULTI_STEP_SERIALIZED_SIZE, 0),
+                accounts.create_metas(),
+                Err(custom(GovernanceError::InvalidProposalType)),
+            );
+        }
+        process_instruction(
+            &GovernanceInstruction::CreateProposal {
+                title: b"spend nothing".to_vec(),
+                description_hash: Hash::default(),
+                proposal_type: ProposalType::TreasurySpend {
+                    recipient: Pubkey::new_unique(),
+                    amount: 0,
+                    memo: [0; 32],
+                },
+            },
+            accounts.transaction_accounts(Proposal::SERIALIZED_SIZE, 0),
+            accounts.create_metas(),
+            Err(custom(GovernanceError::InvalidProposalType)),
+        );
+    }
+
+    #[test]
+    fn test_proposal_account_sizes() {
+        let accounts = TestAccounts::new();
+
+        // Multi-step proposals need the larger account up front.
+        process_instruction(
+            &multi_step(vec![ProposalStep::TextProposal]),
+            accounts.transaction_accounts(Proposal::SERIALIZED_SIZE, 0),
+            accounts.create_metas(),
+            Err(custom(GovernanceError::InvalidAccountData)),
+        );
+
+        // Single-action proposals keep their 512-byte account through
+        // creation and execution.
+        let instruction = param_change(1, 2);
+        let after = create_then_execute(&accounts, &instruction, Proposal::SERIALIZED_SIZE, 0);
+        assert_eq!(after[2].1.data().len(), Proposal::SERIALIZED_SIZE);
+        let proposal = Proposal::deserialize(after[2].1.data()).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+    }
+
+    #[test]
+    fn test_parameter_change_lifecycle_updates_network_params() {
+        let accounts = TestAccounts::new();
+        let voter = Pubkey::new_unique();
+        let stake = Pubkey::new_unique();
+        let vote_record = find_vote_record_address(0, &voter).0;
+
+        let mut transaction_accounts = accounts.transaction_accounts(Proposal::SERIALIZED_SIZE, 0);
+        let mut config = GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+        config.is_active = true;
+        config.voting_period_epochs = 1;
+        config.timelock_epochs = 1;
+        config
+            .serialize_into(transaction_accounts[1].1.data_as_mut_slice())
+            .unwrap();
+        transaction_accounts.extend(voter_accounts(&[voter]));
+        // A passive stake account with a 1.0× voting weight.
+        transaction_accounts.push((stake, passive_stake_of(&voter, 10_000)));
+
+        // Created at epoch 0: voting until epoch 1, executable at epoch 2.
+        let instruction = param_change(4, 5_000);
+        let mut create_metas = accounts.create_metas();
+        create_metas.push(AccountMeta::new_readonly(stake, false));
+        process_and_update(
+            &instruction,
+            &mut transaction_accounts,
+            create_metas,
+            Ok(()),
+        );
+        process_and_update(
+            &GovernanceInstruction::CastVote {
+                proposal_id: 0,
+                vote: Vote::For,
+            },
+            &mut transaction_accounts,
+            cast_vote_metas(&accounts, voter, stake, vote_record),
+            Ok(()),
+        );
+
+        // Tallying moves the proposal into its timelock; nothing changes yet.
+        let execute = GovernanceInstruction::ExecuteProposal { proposal_id: 0 };
+        set_epoch(&mut transaction_accounts, 1);
+        process_and_update(
+            &execute,
+            &mut transaction_accounts,
+            accounts.execute_metas(&instruction),
+            Ok(()),
+        );
+        let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Timelocked);
+        let params = NetworkParams::deserialize(transaction_accounts[5].1.data()).unwrap();
+        assert_eq!(params.get(4), None);
+
+        process_and_update(
+            &execute,
+            &mut transaction_accounts,
+            accounts.execute_metas(&instruction),
+            Err(custom(GovernanceError::TimelockNotExpired)),
+        );
+
+        set_epoch(&mut transaction_accounts, 2);
+        process_and_update(
+            &execute,
+            &mut transaction_accounts,
+            accounts.execute_metas(&instruction),
+            Ok(()),
+        );
+        let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+        let params = NetworkParams::deserialize(transaction_accounts[5].1.data()).unwrap();
+        assert_eq!(params.get(4), Some(5_000));
+    }
+
+    #[test]
+    fn test_create_rejects_unknown_or_out_of_range_parameters() {
+        let accounts = TestAccounts::new();
+        for (param_id, new_value) in [(99, 1), (0, 10_001), (4, 0), (7, 5_001)] {
+            process_instruction(
+                &param_change(param_id, new_value),
+                accounts.transaction_accounts(Proposal::SERIALIZED_SIZE, 0),
+                accounts.create_metas(),
+                Err(custom(GovernanceError::InvalidNetworkParameter)),
+            );
+        }
+        process_instruction(
+            &multi_step(vec![
+                ProposalStep::TextProposal,
+                ProposalStep::ParameterChange {
+                    param_id: 99,
+                    new_value: 1,
+                },
+            ]),
+            accounts.transaction_accounts(Proposal::MULTI_STEP_SERIALIZED_SIZE, 0),
+            accounts.create_metas(),
+            Err(custom(GovernanceError::InvalidNetworkParameter)),
+        );
+    }
+
+    #[test]
+    fn test_parameter_change_requires_governance_owned_params_account() {
+        let accounts = TestAccounts::new();
+        let instruction = param_change(6, 800);
+        let mut transaction_accounts = accounts.transaction_accounts(Proposal::SERIALIZED_SIZE, 0);
+        process_and_update(
+            &instruction,
+            &mut transaction_accounts,
+            accounts.create_metas(),
+            Ok(()),
+        );
+        let execute = GovernanceInstruction::ExecuteProposal { proposal_id: 0 };
+
+        // Missing params account.
+        let mut metas = accounts.execute_metas(&instruction);
+        metas.truncate(3);
+        process_instruction(
+            &execute,
+            transaction_accounts.clone(),
+            metas,
+            Err(InstructionError::MissingAccount),
+        );
+
+        // A governance-owned params account away from the network params
+        // address, which the runtime would never read.
+        let mut elsewhere = transaction_accounts.clone();
+        elsewhere[5].0 = Pubkey::new_unique();
+        let mut metas = accounts.execute_metas(&instruction);
+        metas[3].pubkey = elsewhere[5].0;
+        process_instruction(
+            &execute,
+            elsewhere,
+            metas,
+            Err(custom(GovernanceError::InvalidAccountData)),
+        );
+
+        // Params account owned by someone else.
+        let mut foreign = transaction_accounts.clone();
+        foreign[5].1 =
+            AccountSharedData::new(1, NetworkParams::SERIALIZED_SIZE, &Pubkey::new_unique());
+        process_instruction(
+            &execute,
+            foreign,
+            accounts.execute_metas(&instruction),
+            Err(custom(GovernanceError::InvalidAccountOwner)),
+        );
+
+        // An existing value is overwritten.
+        let mut params = NetworkParams::default();
+        params.set(6, 500);
+        params.set(2, 4_500);
+        params
+            .serialize_into(transaction_accounts[5].1.data_as_mut_slice())
+            .unwrap();
+        process_and_update(
+            &execute,
+            &mut transaction_accounts,
+            accounts.execute_metas(&instruction),
+            Ok(()),
+        );
+        let params = NetworkParams::deserialize(transaction_accounts[5].1.data()).unwrap();
+        assert_eq!(params.get(6), Some(800));
+        assert_eq!(params.get(2), Some(4_500));
+    }
+
+    #[test]
+    fn test_failed_multi_step_leaves_network_params_unchanged() {
+        let accounts = TestAccounts::new();
+        let instruction = multi_step(vec![
+            ProposalStep::ParameterChange {
+                param_id: 0,
+                new_value: 2_500,
+            },
+            accounts.spend(2_000),
+        ]);
+        let after =
+            create_then_execute(&accounts, &instruction, Proposal::MULTI_STEP_SERIALIZED_SIZE, 1_000);
+
+        let proposal = Proposal::deserialize(after[2].1.data()).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::ExecutionFailed);
+        let params = NetworkParams::deserialize(after[5].1.data()).unwrap();
+        assert_eq!(params, NetworkParams::default());
+    }
+
+    #[test]
+    fn test_treasury_spend_disburses_to_recipient() {
+        let accounts = TestAccounts::new();
+        let mut memo = [0; 32];
+        memo[..11].copy_from_slice(b"grant round");
+        let spend = |amount| GovernanceInstruction::CreateProposal {
+            title: b"spend".to_vec(),
+            description_hash: Hash::default(),
+            proposal_type: ProposalType::TreasurySpend {
+                recipient: accounts.recipient,
+                amount,
+                memo,
+            },
+        };
+
+        let after = create_then_execute(&accounts, &spend(300), Proposal::SERIALIZED_SIZE, 1_000);
+        let proposal = Proposal::deserialize(after[2].1.data()).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+        assert_eq!(after[3].1.lamports(), 700);
+        assert_eq!(after[7].1.lamports(), 300);
+        let treasury_config = TreasuryConfig::deserialize(after[6].1.data()).unwrap();
+        assert_eq!(treasury_config.total_disbursed, 300);
+
+        // Spending more than the treasury holds fails without moving lamports.
+        let after = create_then_execute(&accounts, &spend(1_001), Proposal::SERIALIZED_SIZE, 1_000);
+        let proposal = Proposal::deserialize(after[2].1.data()).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::ExecutionFailed);
+        assert_eq!(after[3].1.lamports(), 1_000);
+        assert_eq!(after[7].1.lamports(), 0);
+    }
+
+    #[test]
+    fn test_treasury_spend_requires_matching_treasury_accounts() {
+        let accounts = TestAccounts::new();
+        let instruction = multi_step(vec![accounts.spend(100)]);
+        let mut transaction_accounts =
+            accounts.transaction_accounts(Proposal::MULTI_STEP_SERIALIZED_SIZE, 1_000);
+        process_and_update(
+            &instruction,
+            &mut transaction_accounts,
+            accounts.create_metas(),
+            Ok(()),
+        );
+        let execute = GovernanceInstruction::ExecuteProposal { proposal_id: 0 };
+
+        // The treasury must be controlled by the governance config.
+        let mut foreign = transaction_accounts.clone();
+        let mut treasury_config = TreasuryConfig::deserialize(foreign[6].1.data()).unwrap();
+        treasury_config.authority = accounts.authority;
+        treasury_config
+            .serialize_into(foreign[6].1.data_as_mut_slice())
+            .unwrap();
+        process_instruction(
+            &execute,
+            foreign,
+            accounts.execute_metas(&instruction),
+            Err(custom(GovernanceError::AuthorityMismatch)),
+        );
+
+        // The lamport source must be the configured treasury account.
+        let mut metas = accounts.execute_metas(&instruction);
+        metas[6] = AccountMeta::new(accounts.params, false);
+        process_instruction(
+            &execute,
+            transaction_accounts.clone(),
+            metas,
+            Err(custom(GovernanceError::InvalidAccountData)),
+        );
+
+        // The treasury program must be passed for the CPI.
+        let mut metas = accounts.execute_metas(&instruction);
+        metas[7] = AccountMeta::new_readonly(accounts.params, false);
+        process_instruction(
+            &execute,
+            transaction_accounts,
+            metas,
+            Err(InstructionError::IncorrectProgramId),
+        );
+    }
+
+    #[test]
+    fn test_treasury_allowance_update_sets_epoch_allowance() {
+        let accounts = TestAccounts::new();
+        let allowance_update = |epoch_allowance_bps| GovernanceInstruction::CreateProposal {
+            title: b"treasury allowance".to_vec(),
+            description_hash: Hash::default(),
+            proposal_type: ProposalType::TreasuryAllowanceUpdate {
+                epoch_allowance_bps,
+            },
+        };
+
+        // Allowances the treasury would refuse never reach a vote.
+        for invalid in [0, 10_001] {
+            process_instruction(
+                &allowance_update(invalid),
+                accounts.transaction_accounts(Proposal::SERIALIZED_SIZE, 1_000),
+                accounts.create_metas(),
+                Err(custom(GovernanceError::InvalidConfigValue)),
+            );
+        }
+
+        let after = create_then_execute(
+            &accounts,
+            &allowance_update(500),
+            Proposal::SERIALIZED_SIZE,
+            1_000,
+        );
+        let proposal = Proposal::deserialize(after[2].1.data()).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+        let treasury_config = TreasuryConfig::deserialize(after[6].1.data()).unwrap();
+        assert_eq!(treasury_config.epoch_allowance_bps, 500);
+        assert_eq!(after[3].1.lamports(), 1_000);
+    }
+
+    #[test]
+    fn test_memo_text() {
+        let mut memo = [0; 32];
+        memo[..4].copy_from_slice(b"fund");
+        assert_eq!(memo_text(&memo), "fund");
+        assert_eq!(memo_text(&[0; 32]), "");
+
+        // Invalid UTF-8 is replaced, which can grow the text past the memo
+        // length; logging truncates on a character boundary.
+        let text = memo_text(&[0xff; 32]);
+        assert_eq!(text.len(), 96);
+        let logged = truncate_str(&text, MAX_LOGGED_MEMO_LEN);
+        assert_eq!(logged.len(), 63);
+        assert_eq!(truncate_str("fund", MAX_LOGGED_MEMO_LEN), "fund");
+    }
+
+    #[test]
+    fn test_stake_locked_after_proposal_creation_cannot_vote() {
+        let accounts = TestAccounts::new();
+        let voter = Pubkey::new_unique();
+        let late_voter = Pubkey::new_unique();
+        let stake = Pubkey::new_unique();
+        let late_stake = Pubkey::new_unique();
+        let vote_record = find_vote_record_address(0, &voter).0;
+        let late_vote_record = find_vote_record_address(0, &late_voter).0;
+        // A permanent lock taken out after the proposal was created.
+        let mut late_stake_account = passive_stake(15_000, 1);
+        late_stake_account.data_as_mut_slice()[1..33].copy_from_slice(late_voter.as_ref());
+
+        let mut transaction_accounts = accounts.transaction_accounts(Proposal::SERIALIZED_SIZE, 0);
+        let mut config = GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+        config.is_active = true;
+        config.voting_period_epochs = 2;
+        config
+            .serialize_into(transaction_accounts[1].1.data_as_mut_slice())
+            .unwrap();
+        transaction_accounts.extend(voter_accounts(&[voter, late_voter]));
+        transaction_accounts.extend([
+            (stake, passive_stake_of(&voter, 1_000)),
+            (late_stake, late_stake_account),
+        ]);
+
+        let mut create_metas = accounts.create_metas();
+        create_metas.push(AccountMeta::new_readonly(stake, false));
+        process_and_update(
+            &param_change(4, 5_000),
+            &mut transaction_accounts,
+            create_metas,
+            Ok(()),
+        );
+        let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+        assert_eq!(proposal.voting_power_snapshot_epoch, 0);
+
+        set_epoch(&mut transaction_accounts, 1);
+        let vote = GovernanceInstruction::CastVote {
+            proposal_id: 0,
+            vote: Vote::Against,
+        };
+        let vote_metas =
+            |voter, stake, vote_record| cast_vote_metas(&accounts, voter, stake, vote_record);
+        process_and_update(
+            &vote,
+            &mut transaction_accounts,
+            vote_metas(late_voter, late_stake, late_vote_record),
+            Err(custom(GovernanceError::NoVotingPower)),
+        );
+
+        // Stake locked before the snapshot still votes.
+        process_and_update(
+            &vote,
+            &mut transaction_accounts,
+            vote_metas(voter, stake, vote_record),
+            Ok(()),
+        );
+        let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+        assert_eq!(proposal.votes_against, 100);
+    }
+
+    /// `keys` as funded system accounts, each with an uncreated delegation
+    /// record and vote record on proposal 0, plus the rent sysvar for
+    /// creating the records.
+    fn voter_accounts(keys: &[Pubkey]) -> Vec<(Pubkey, AccountSharedData)> {
+        let mut accounts = vec![(
+            sysvar::rent::id(),
+            account::create_account_shared_data_for_test(&Rent::default()),
+        )];
+        for key in keys {
+            accounts.push((
+                *key,
+                AccountSharedData::new(1_000_000_000, 0, &system_program::id()),
+            ));
+            accounts.push(no_delegation(key));
+            accounts.push((
+                find_vote_record_address(0, key).0,
+                AccountSharedData::new(0, 0, &system_program::id()),
+            ));
+        }
+        accounts
+    }
+
+    fn delegation_of(
+        transaction_accounts: &[(Pubkey, AccountSharedData)],
+        delegator: &Pubkey,
+    ) -> DelegationRecord {
+        let record = find_delegation_record_address(delegator).0;
+        DelegationRecord::deserialize(
+            transaction_accounts
+                .iter()
+                .find(|(key, _)| *key == record)
+                .unwrap()
+                .1
+                .data(),
+        )
+        .unwrap()
+    }
+
+    /// `CastVote` accounts for `voter` counting the power of each
+    /// (delegator, delegator's stake) pair.
+    fn delegated_vote_metas(
+        accounts: &TestAccounts,
+        voter: Pubkey,
+        stake: Pubkey,
+        vote_record: Pubkey,
+        pairs: &[(Pubkey, Pubkey)],
+    ) -> Vec<AccountMeta> {
+        let mut metas = cast_vote_metas(accounts, voter, stake, vote_record);
+        for (delegator, stake) in pairs {
+            metas.push(AccountMeta::new(
+                find_delegation_record_address(delegator).0,
+                false,
+            ));
+            metas.push(AccountMeta::new_readonly(*stake, false));
+        }
+        metas
+    }
+
+    #[test]
+    fn test_delegation_chain_votes_with_combined_weight() {
+        let accounts = TestAccounts::new();
+        let [alice, bob, carol] = [(); 3].map(|_| Pubkey::new_unique());
+        let [alice_stake, bob_stake, carol_stake] = [(); 3].map(|_| Pubkey::new_unique());
+        let [alice_record, bob_record] =
+            [alice, bob].map(|key| find_delegation_record_address(&key).0);
+        let vote_record = find_vote_record_address(0, &carol).0;
+
+        let mut extra = voter_accounts(&[alice, bob, carol]);
+        extra.extend([
+            (alice_stake, passive_stake_of(&alice, 5_000)),
+            (bob_stake, passive_stake_of(&bob, 3_000)),
+            (carol_stake, passive_stake_of(&carol, 1_000)),
+        ]);
+        let mut transaction_accounts = active_proposal_accounts(&accounts, extra, carol_stake);
+
+        // Alice → Bob → Carol.
+        process_and_update(
+            &GovernanceInstruction::DelegateVote { delegate: bob },
+            &mut transaction_accounts,
+            delegate_metas(&accounts, alice, &[]),
+            Ok(()),
+        );
+        process_and_update(
+            &GovernanceInstruction::DelegateVote { delegate: carol },
+            &mut transaction_accounts,
+            delegate_metas(&accounts, bob, &[]),
+            Ok(()),
+        );
+
+        // Carol → Alice would close the loop.
+        process_and_update(
+            &GovernanceInstruction::DelegateVote { delegate: alice },
+            &mut transaction_accounts,
+            delegate_metas(&accounts, carol, &[alice_record, bob_record]),
+            Err(custom(GovernanceError::CircularDelegation)),
+        );
+        process_and_update(
+            &GovernanceInstruction::DelegateVote { delegate: carol },
+            &mut transaction_accounts,
+            delegate_metas(&accounts, carol, &[]),
+            Err(custom(GovernanceError::CircularDelegation)),
+        );
+
+        set_epoch(&mut transaction_accounts, 1);
+        let vote = GovernanceInstruction::CastVote {
+            proposal_id: 0,
+            vote: Vote::For,
+        };
+        let vote_metas = |pairs: &[(Pubkey, Pubkey)]| {
+            delegated_vote_metas(&accounts, carol, carol_stake, vote_record, pairs)
+        };
+
+        // Alice delegates to Bob, so her record only counts after Bob's.
+        process_and_update(
+            &vote,
+            &mut transaction_accounts,
+            vote_metas(&[(alice, alice_stake), (bob, bob_stake)]),
+            Err(custom(GovernanceError::InvalidDelegation)),
+        );
+        // Power must come from the delegator's own stake.
+        process_and_update(
+            &vote,
+            &mut transaction_accounts,
+            vote_metas(&[(bob, alice_stake)]),
+            Err(custom(GovernanceError::InvalidWeightProof)),
+        );
+        // Each delegator counts once.
+        process_and_update(
+            &vote,
+            &mut transaction_accounts,
+            vote_metas(&[(bob, bob_stake), (bob, bob_stake)]),
+            Err(custom(GovernanceError::CircularDelegation)),
+        );
+
+        process_and_update(
+            &vote,
+            &mut transaction_accounts,
+            vote_metas(&[(bob, bob_stake), (alice, alice_stake)]),
+            Ok(()),
+        );
+        let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+        // Carol 100 + Bob 300 + Alice 500.
+        assert_eq!(proposal.votes_for, 900);
+        let record = VoteRecord::deserialize(
+            transaction_accounts
+                .iter()
+                .find(|(key, _)| *key == vote_record)
+                .unwrap()
+                .1
+                .data(),
+        )
+        .unwrap();
+        assert_eq!(record.weight, 900);
+    }
+
+    #[test]
+    fn test_delegated_power_is_counted_once() {
+        let accounts = TestAccounts::new();
+        let [alice, bob, carol] = [(); 3].map(|_| Pubkey::new_unique());
+        let [alice_stake, bob_stake, carol_stake] = [(); 3].map(|_| Pubkey::new_unique());
+        let [alice_vote, bob_vote] = [alice, bob].map(|key| find_vote_record_address(0, &key).0);
+        let forged_stake = Pubkey::new_unique();
+
+        let mut extra = voter_accounts(&[alice, bob, carol]);
+        let mut forged = passive_stake_of(&alice, 5_000);
+        forged.set_owner(alice);
+        extra.extend([
+            (alice_stake, passive_stake_of(&alice, 5_000)),
+            (bob_stake, passive_stake_of(&bob, 3_000)),
+            (carol_stake, passive_stake_of(&carol, 1_000)),
+            (forged_stake, forged),
+        ]);
+        let mut transaction_accounts = active_proposal_accounts(&accounts, extra, carol_stake);
+
+        // The record must be the delegator's PDA.
+        let mut wrong_record = delegate_metas(&accounts, alice, &[]);
+        wrong_record[2] = AccountMeta::new(find_delegation_record_address(&bob).0, false);
+        process_and_update(
+            &GovernanceInstruction::DelegateVote { delegate: bob },
+            &mut transaction_accounts,
+            wrong_record,
+            Err(custom(GovernanceError::InvalidDelegationRecord)),
+        );
+        process_and_update(
+            &GovernanceInstruction::DelegateVote { delegate: bob },
+            &mut transaction_accounts,
+            delegate_metas(&accounts, alice, &[]),
+            Ok(()),
+        );
+        // The delegator paid for the record.
+        let record_rent = Rent::default().minimum_balance(DelegationRecord::SERIALIZED_SIZE);
+        assert_eq!(
+            lamports_of(&transaction_accounts, &alice),
+            1_000_000_000 - record_rent
+        );
+
+        set_epoch(&mut transaction_accounts, 1);
+        let vote = GovernanceInstruction::CastVote {
+            proposal_id: 0,
+            vote: Vote::For,
+        };
+
+        // Alice's power is Bob's to vote while her delegation is in force.
+        process_and_update(
+            &vote,
+            &mut transaction_accounts,
+            cast_vote_metas(&accounts, alice, alice_stake, alice_vote),
+            Err(custom(GovernanceError::VoterHasDelegated)),
+        );
+        // Her record must be the one checked.
+        let mut wrong_record = cast_vote_metas(&accounts, alice, alice_stake, alice_vote);
+        wrong_record[5] = AccountMeta::new_readonly(find_delegation_record_address(&bob).0, false);
+        process_and_update(
+            &vote,
+            &mut transaction_accounts,
+            wrong_record,
+            Err(custom(GovernanceError::InvalidDelegationRecord)),
+        );
+        // Delegated power must be held by the passive stake program.
+        process_and_update(
+            &vote,
+            &mut transaction_accounts,
+            delegated_vote_metas(
+                &accounts,
+                bob,
+                bob_stake,
+                bob_vote,
+                &[(alice, forged_stake)],
+            ),
+            Err(custom(GovernanceError::InvalidAccountOwner)),
+        );
+
+        process_and_update(
+            &vote,
+            &mut transaction_accounts,
+            delegated_vote_metas(&accounts, bob, bob_stake, bob_vote, &[(alice, alice_stake)]),
+            Ok(()),
+        );
+        let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+        // Bob 300 + Alice 500.
+        assert_eq!(proposal.votes_for, 800);
+        assert_eq!(
+            delegation_of(&transaction_accounts, &alice).locked_until_epoch,
+            proposal.voting_ends_epoch
+        );
+
+        // Alice cannot hand the same power to Carol while the vote is open.
+        process_and_update(
+            &GovernanceInstruction::DelegateVote { delegate: carol },
+            &mut transaction_accounts,
+            delegate_metas(&accounts, alice, &[]),
+            Err(custom(GovernanceError::DelegationNotRevocable)),
+        );
+
+        // Once voting ends she may redirect it.
+        set_epoch(&mut transaction_accounts, proposal.voting_ends_epoch);
+        process_and_update(
+            &GovernanceInstruction::DelegateVote { delegate: carol },
+            &mut transaction_accounts,
+            delegate_metas(&accounts, alice, &[]),
+            Ok(()),
+        );
+        assert_eq!(delegation_of(&transaction_accounts, &alice).to, carol);
+        // The record already existed, so nothing more was charged.
+        assert_eq!(
+            lamports_of(&transaction_accounts, &alice),
+            1_000_000_000 - record_rent
+        );
+    }
+
+    #[test]
+    fn test_lapsed_delegation_is_not_counted() {
+        let accounts = TestAccounts::new();
+        let delegator = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let delegator_stake = Pubkey::new_unique();
+        let delegate_stake = Pubkey::new_unique();
+        let vote_record = find_vote_record_address(0, &delegate).0;
+
+        let mut extra = voter_accounts(&[delegator, delegate]);
+        extra.extend([
+            (delegator_stake, passive_stake_of(&delegator, 5_000)),
+            (delegate_stake, passive_stake_of(&delegate, 1_000)),
+        ]);
+        let mut transaction_accounts = active_proposal_accounts(&accounts, extra, delegate_stake);
+
+        // Keep voting open past the delegation's lapse.
+        let mut proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+        proposal.voting_ends_epoch = 20;
+        proposal
+            .serialize_into(transaction_accounts[2].1.data_as_mut_slice())
+            .unwrap();
+
+        // Delegated in epoch 0; `max_delegation_epochs` is 10.
+        process_and_update(
+            &GovernanceInstruction::DelegateVote { delegate },
+            &mut transaction_accounts,
+            delegate_metas(&accounts, delegator, &[]),
+            Ok(()),
+        );
+        let delegation = delegation_of(&transaction_accounts, &delegator);
+        assert!(delegation.revocable);
+        assert!(!delegation.is_expired(9, 10));
+        assert!(delegation.is_expired(10, 10));
+
+        set_epoch(&mut transaction_accounts, 10);
+        let vote = GovernanceInstruction::CastVote {
+            proposal_id: 0,
+            vote: Vote::Against,
+        };
+        process_and_update(
+            &vote,
+            &mut transaction_accounts,
+            delegated_vote_metas(
+                &accounts,
+                delegate,
+                delegate_stake,
+                vote_record,
+                &[(delegator, delegator_stake)],
+            ),
+            Err(custom(GovernanceError::InvalidDelegation)),
+        );
+
+        // The delegate still votes with their own power.
+        process_and_update(
+            &vote,
+            &mut transaction_accounts,
+            cast_vote_metas(&accounts, delegate, delegate_stake, vote_record),
+            Ok(()),
+        );
+        let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+        assert_eq!(proposal.votes_against, 100);
+    }
+
+    #[test]
+    fn test_vote_can_change_until_lockout() {
+        let accounts = TestAccounts::new();
+        let voter = Pubkey::new_unique();
+        let other_voter = Pubkey::new_unique();
+        let stake = Pubkey::new_unique();
+        let other_stake = Pubkey::new_unique();
+        let vote_record = find_vote_record_address(0, &voter).0;
+        let other_vote_record = find_vote_record_address(0, &other_voter).0;
+
+        let mut extra = voter_accounts(&[voter, other_voter]);
+        extra.extend([
+            (stake, passive_stake_of(&voter, 5_000)),
+            (other_stake, passive_stake_of(&other_voter, 1_000)),
+        ]);
+        let mut transaction_accounts = active_proposal_accounts(&accounts, extra, stake);
+        // Voting ends in epoch 2; changes are locked from epoch 1.
+        let mut config = GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+        config.vote_change_lockout_epochs = 1;
+        config
+            .serialize_into(transaction_accounts[1].1.data_as_mut_slice())
+            .unwrap();
+
+        let vote_metas =
+            |voter, stake, vote_record| cast_vote_metas(&accounts, voter, stake, vote_record);
+        let cast = |vote| GovernanceInstruction::CastVote {
+            proposal_id: 0,
+            vote,
+        };
+        let tallies = |transaction_accounts: &[(Pubkey, AccountSharedData)]| {
+            let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+            (
+                proposal.votes_for,
+                proposal.votes_against,
+                proposal.votes_abstain,
+            )
+        };
+
+        // Power only comes from the voter's own stake.
+        process_and_update(
+            &cast(Vote::For),
+            &mut transaction_accounts,
+            vote_metas(other_voter, stake, other_vote_record),
+            Err(custom(GovernanceError::InvalidWeightProof)),
+        );
+
+        process_and_update(
+            &cast(Vote::For),
+            &mut transaction_accounts,
+            vote_metas(voter, stake, vote_record),
+            Ok(()),
+        );
+        assert_eq!(tallies(&transaction_accounts), (500, 0, 0));
+
+        process_and_update(
+            &cast(Vote::Against),
+            &mut transaction_accounts,
+            vote_metas(voter, stake, vote_record),
+            Ok(()),
+        );
+        assert_eq!(tallies(&transaction_accounts), (0, 500, 0));
+
+        process_and_update(
+            &cast(Vote::Abstain),
+            &mut transaction_accounts,
+            vote_metas(voter, stake, vote_record),
+            Ok(()),
+        );
+        assert_eq!(tallies(&transaction_accounts), (0, 0, 500));
+        let record = VoteRecord::deserialize(
+            transaction_accounts
+                .iter()
+                .find(|(key, _)| *key == vote_record)
+                .unwrap()
+                .1
+                .data(),
+        )
+        .unwrap();
+        assert_eq!(record.vote, Vote::Abstain);
+        assert_eq!(record.weight, 500);
+        // Only the first vote paid for the record.
+        assert_eq!(
+            lamports_of(&transaction_accounts, &voter),
+            1_000_000_000 - Rent::default().minimum_balance(VoteRecord::SERIALIZED_SIZE)
+        );
+
+        // Another voter cannot overwrite this voter's record.
+        process_and_update(
+            &cast(Vote::For),
+            &mut transaction_accounts,
+            vote_metas(other_voter, other_stake, vote_record),
+            Err(custom(GovernanceError::InvalidVoteRecord)),
+        );
+
+        set_epoch(&mut transaction_accounts, 1);
+        process_and_update(
+            &cast(Vote::For),
+            &mut transaction_accounts,
+            vote_metas(voter, stake, vote_record),
+            Err(custom(GovernanceError::VoteChangeLockedOut)),
+        );
+        // First votes are still accepted during the lockout.
+        process_and_update(
+            &cast(Vote::For),
+            &mut transaction_accounts,
+            vote_metas(other_voter, other_stake, other_vote_record),
+            Ok(()),
+        );
+        assert_eq!(tallies(&transaction_accounts), (100, 0, 500));
+    }
+
+    #[test]
+    fn test_proposal_deposit_settlement() {
+        let accounts = TestAccounts::new();
+        let escrow = find_deposit_escrow_address(&accounts.config, 0).0;
+        let voter = Pubkey::new_unique();
+        let stake = Pubkey::new_unique();
+        let vote_record = find_vote_record_address(0, &voter).0;
+        let claim = GovernanceInstruction::ClaimDeposit { proposal_id: 0 };
+        let claim_metas = |claimant| {
+            vec![
+                AccountMeta::new(claimant, true),
+                AccountMeta::new(accounts.proposal, false),
+                AccountMeta::new_readonly(accounts.config, false),
+                AccountMeta::new(escrow, false),
+                AccountMeta::new_readonly(accounts.treasury_config, false),
+                AccountMeta::new(accounts.treasury, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ]
+        };
+        let execute = GovernanceInstruction::ExecuteProposal { proposal_id: 0 };
+        let execute_metas = vec![
+            AccountMeta::new_readonly(accounts.authority, true),
+            AccountMeta::new(accounts.proposal, false),
+            AccountMeta::new(accounts.config, false),
+            AccountMeta::new(accounts.params, false),
+        ];
+
+        // (vote cast, final status, lamports refunded, lamports forfeited)
+        for (vote, status, refunded, forfeited) in [
+            (Some(Vote::For), ProposalStatus::Executed, 1_000, 0),
+            (Some(Vote::Against), ProposalStatus::Rejected, 500, 500),
+            (None, ProposalStatus::Expired, 0, 1_000),
+            (Some(Vote::Veto), ProposalStatus::Vetoed, 0, 1_000),
+        ] {
+            let mut extra = voter_accounts(&[voter]);
+            extra.push((stake, passive_stake_of(&voter, 5_000)));
+            let mut transaction_accounts = deposit_proposal_accounts(&accounts, extra, stake);
+            let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+            assert_eq!(proposal.deposit_lamports, 1_000);
+            assert_eq!(
+                lamports_of(&transaction_accounts, &accounts.authority),
+                9_000
+            );
+            assert_eq!(lamports_of(&transaction_accounts, &escrow), 1_000);
+
+            // Nothing can be claimed while the vote is open.
+            process_and_update(
+                &claim,
+                &mut transaction_accounts,
+                claim_metas(accounts.authority),
+                Err(custom(GovernanceError::DepositNotClaimable)),
+            );
+
+            if let Some(vote) = vote {
+                process_and_update(
+                    &GovernanceInstruction::CastVote {
+                        proposal_id: 0,
+                        vote,
+                    },
+                    &mut transaction_accounts,
+                    cast_vote_metas(&accounts, voter, stake, vote_record),
+                    Ok(()),
+                );
+            }
+            set_epoch(&mut transaction_accounts, 2);
+            process_and_update(
+                &execute,
+                &mut transaction_accounts,
+                execute_metas.clone(),
+                Ok(()),
+            );
+            if status == ProposalStatus::Executed {
+                // The first call only tallies the vote; the timelock is zero.
+                process_and_update(
+                    &execute,
+                    &mut transaction_accounts,
+                    execute_metas.clone(),
+                    Ok(()),
+                );
+            }
+            let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+            assert_eq!(proposal.status, status);
+
+            // Only the proposer can claim.
+            process_and_update(
+                &claim,
+                &mut transaction_accounts,
+                claim_metas(accounts.recipient),
+                Err(custom(GovernanceError::AuthorityMismatch)),
+            );
+            process_and_update(
+                &claim,
+                &mut transaction_accounts,
+                claim_metas(accounts.authority),
+                Ok(()),
+            );
+            assert_eq!(
+                lamports_of(&transaction_accounts, &accounts.authority),
+                9_000 + refunded
+            );
+            assert_eq!(
+                lamports_of(&transaction_accounts, &accounts.treasury),
+                forfeited
+            );
+            assert_eq!(lamports_of(&transaction_accounts, &escrow), 0);
+            let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+            assert!(proposal.deposit_claimed);
+
+            process_and_update(
+                &claim,
+                &mut transaction_accounts,
+                claim_metas(accounts.authority),
+                Err(custom(GovernanceError::DepositAlreadyClaimed)),
+            );
+        }
+    }
+
+    #[test]
+    fn test_execute_expires_proposal_without_quorum() {
+        let accounts = TestAccounts::new();
+        // Three validators (1.0× weight) with 1 000 000 voting power in total.
+        let validators = [(); 3].map(|_| Pubkey::new_unique());
+        let stakes = [(); 3].map(|_| Pubkey::new_unique());
+        let records = validators.map(|validator| find_vote_record_address(0, &validator).0);
+        let mut extra = voter_accounts(&validators);
+        for ((validator, stake), amount) in validators
+            .iter()
+            .zip(&stakes)
+            .zip([400_000u64, 400_000, 200_000])
+        {
+            let mut stake_account = passive_stake_of(validator, 10_000);
+            stake_account.data_as_mut_slice()[33..41].copy_from_slice(&amount.to_le_bytes());
+            extra.push((*stake, stake_account));
+        }
+        let mut transaction_accounts = active_proposal_accounts(&accounts, extra, stakes[2]);
+        let mut config = GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+        config.total_governance_supply = 1_000_000;
+        config
+            .serialize_into(transaction_accounts[1].1.data_as_mut_slice())
+            .unwrap();
+        assert_eq!(config.quorum_bps, 3_000);
+
+        let vote = |transaction_accounts: &mut Vec<_>, index: usize| {
+            process_and_update(
+                &GovernanceInstruction::CastVote {
+                    proposal_id: 0,
+                    vote: Vote::For,
+                },
+                transaction_accounts,
+                cast_vote_metas(&accounts, validators[index], stakes[index], records[index]),
+                Ok(()),
+            );
+        };
+        let execute = |transaction_accounts: &mut Vec<_>| {
+            set_epoch(transaction_accounts, 2);
+            process_and_update(
+                &GovernanceInstruction::ExecuteProposal { proposal_id: 0 },
+                transaction_accounts,
+                vec![
+                    AccountMeta::new_readonly(accounts.authority, true),
+                    AccountMeta::new(accounts.proposal, false),
+                    AccountMeta::new(accounts.config, false),
+                ],
+                Ok(()),
+            );
+            Proposal::deserialize(transaction_accounts[2].1.data()).unwrap()
+        };
+
+        // 200 000 of 1 000 000 (20%) is unanimous but below the 30% quorum.
+        vote(&mut transaction_accounts, 2);
+        let mut with_quorum = transaction_accounts.clone();
+        let proposal = execute(&mut transaction_accounts);
+        assert_eq!(proposal.votes_for, 200_000);
+        assert_eq!(proposal.votes_against, 0);
+        assert_eq!(proposal.status, ProposalStatus::Expired);
+
+        // A second validator brings participation to 60%.
+        vote(&mut with_quorum, 1);
+        let proposal = execute(&mut with_quorum);
+        assert_eq!(proposal.votes_for, 600_000);
+        assert_eq!(proposal.status, ProposalStatus::Timelocked);
+
+        // Exactly 30% is enough; nothing is while the supply is unknown.
+        assert!(quorum_reached(300_000, &config).unwrap());
+        assert!(!quorum_reached(299_999, &config).unwrap());
+        let unknown_supply = GovernanceConfig {
+            total_governance_supply: 0,
+            ..config
+        };
+        assert!(!quorum_reached(1_000_000, &unknown_supply).unwrap());
+    }
+
+    #[test]
+    fn test_governance_config_update_lifecycle() {
+        let accounts = TestAccounts::new();
+        let voter = Pubkey::new_unique();
+        let stake = Pubkey::new_unique();
+        let weak_stake = Pubkey::new_unique();
+        let vote_record = find_vote_record_address(0, &voter).0;
+        let next_proposal = Pubkey::new_unique();
+
+        let mut transaction_accounts = accounts.transaction_accounts(Proposal::SERIALIZED_SIZE, 0);
+        let mut config = GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+        config.is_active = true;
+        config.voting_period_epochs = 2;
+        config
+            .serialize_into(transaction_accounts[1].1.data_as_mut_slice())
+            .unwrap();
+        transaction_accounts.extend(voter_accounts(&[voter]));
+        transaction_accounts.extend([
+            // 1 000 voting power, the whole governance supply.
+            (stake, passive_stake_of(&voter, 10_000)),
+            // 100 voting power.
+            (weak_stake, passive_stake(1_000, 0)),
+            (
+                next_proposal,
+                AccountSharedData::new(1, Proposal::SERIALIZED_SIZE, &id()),
+            ),
+        ]);
+
+        let config_update =
+            |pass_threshold_bps, timelock_epochs| GovernanceInstruction::CreateProposal {
+                title: b"governance config".to_vec(),
+                description_hash: Hash::default(),
+                proposal_type: ProposalType::GovernanceConfigUpdate {
+                    proposal_threshold: 500,
+                    voting_period_epochs: 3,
+                    quorum_bps: 4_000,
+                    pass_threshold_bps,
+                    veto_threshold_bps: 2_500,
+                    timelock_epochs,
+                },
+            };
+        let mut create_metas = accounts.create_metas();
+        create_metas.push(AccountMeta::new_readonly(stake, false));
+
+        // Governance cannot lower its own safeguards below the floors.
+        for invalid in [config_update(4_999, 2), config_update(6_000, 0)] {
+            process_and_update(
+                &invalid,
+                &mut transaction_accounts,
+                create_metas.clone(),
+                Err(custom(GovernanceError::InvalidConfigValue)),
+            );
+        }
+
+        let instruction = config_update(6_000, 2);
+        process_and_update(
+            &instruction,
+            &mut transaction_accounts,
+            create_metas,
+            Ok(()),
+        );
+
+        set_epoch(&mut transaction_accounts, 1);
+        process_and_update(
+            &GovernanceInstruction::CastVote {
+                proposal_id: 0,
+                vote: Vote::For,
+            },
+            &mut transaction_accounts,
+            cast_vote_metas(&accounts, voter, stake, vote_record),
+            Ok(()),
+        );
+
+        // The first call tallies the vote, the second executes it.  The
+        // proposal itself was created under the old zero-epoch timelock.
+        set_epoch(&mut transaction_accounts, 2);
+        let execute = GovernanceInstruction::ExecuteProposal { proposal_id: 0 };
+        for expected_status in [ProposalStatus::Timelocked, ProposalStatus::Executed] {
+            process_and_update(
+                &execute,
+                &mut transaction_accounts,
+                accounts.execute_metas(&instruction),
+                Ok(()),
+            );
+            let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+            assert_eq!(proposal.status, expected_status);
+        }
+
+        let config = GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+        assert_eq!(config.proposal_threshold, 500);
+        assert_eq!(config.voting_period_epochs, 3);
+        assert_eq!(config.quorum_bps, 4_000);
+        assert_eq!(config.pass_threshold_bps, 6_000);
+        assert_eq!(config.veto_threshold_bps, 2_500);
+        assert_eq!(config.timelock_epochs, 2);
+        assert!(config.is_active);
+        assert_eq!(config.authority, accounts.authority);
+        assert_eq!(config.next_proposal_id, 1);
+
+        // Subsequent proposals are held to the new parameters.
+        set_epoch(&mut transaction_accounts, 5);
+        let next_metas = |proposer_stake| {
+            vec![
+                AccountMeta::new_readonly(accounts.authority, true),
+                AccountMeta::new(accounts.config, false),
+                AccountMeta::new(next_proposal, false),
+                AccountMeta::new_readonly(proposer_stake, false),
+            ]
+        };
+        process_and_update(
+            &param_change(4, 5_000),
+            &mut transaction_accounts,
+            next_metas(weak_stake),
+            Err(custom(GovernanceError::InsufficientStakeForProposal)),
+        );
+        process_and_update(
+            &param_change(4, 5_000),
+            &mut transaction_accounts,
+            next_metas(stake),
+            Ok(()),
+        );
+        let (_, next_proposal_account) = transaction_accounts
+            .iter()
+            .find(|(key, _)| *key == next_proposal)
+            .unwrap();
+        let proposal = Proposal::deserialize(next_proposal_account.data()).unwrap();
+        assert_eq!(proposal.id, 1);
+        assert_eq!(proposal.voting_ends_epoch, 8);
+        assert_eq!(proposal.execution_epoch, 10);
+    }
+
+    #[test]
+    fn test_config_changes_require_proposal_after_activation() {
+        let accounts = TestAccounts::new();
+        let voter = Pubkey::new_unique();
+        let stake = Pubkey::new_unique();
+        let vote_record = find_vote_record_address(0, &voter).0;
+
+        let mut transaction_accounts = accounts.transaction_accounts(Proposal::SERIALIZED_SIZE, 0);
+        transaction_accounts.extend(voter_accounts(&[voter]));
+        // 1 000 voting power.
+        transaction_accounts.push((stake, passive_stake_of(&voter, 10_000)));
+        let authority_metas = vec![
+            AccountMeta::new(accounts.authority, true),
+            AccountMeta::new(accounts.config, false),
+        ];
+        let update_config = GovernanceInstruction::UpdateConfig {
+            proposal_threshold: 0,
+            voting_period_epochs: 2,
+            quorum_bps: 3_000,
+            pass_threshold_bps: 5_000,
+            veto_threshold_bps: 3_333,
+            timelock_epochs: 0,
+            emergency_multisig: Pubkey::new_unique(),
+        };
+        let update_supply =
+            |total_governance_supply| GovernanceInstruction::UpdateGovernanceSupply {
+                total_governance_supply,
+            };
+
+        // Before activation the authority manages the config directly.
+        process_and_update(
+            &update_config,
+            &mut transaction_accounts,
+            authority_metas.clone(),
+            Ok(()),
+        );
+        process_and_update(
+            &update_supply(1_500),
+            &mut transaction_accounts,
+            authority_metas.clone(),
+            Ok(()),
+        );
+        process_and_update(
+            &GovernanceInstruction::ActivateGovernance,
+            &mut transaction_accounts,
+            authority_metas.clone(),
+            Ok(()),
+        );
+
+        // Afterwards it cannot.
+        let before = transaction_accounts.clone();
+        for instruction in [update_config, update_supply(2_000)] {
+            process_and_update(
+                &instruction,
+                &mut transaction_accounts,
+                authority_metas.clone(),
+                Err(custom(GovernanceError::ConfigChangeRequiresProposal)),
+            );
+        }
+        assert_eq!(transaction_accounts, before);
+        let config = GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+        assert_eq!(config.total_governance_supply, 1_500);
+
+        // The supply changes through an executed proposal instead.
+        let instruction = GovernanceInstruction::CreateProposal {
+            title: b"governance supply".to_vec(),
+            description_hash: Hash::default(),
+            proposal_type: ProposalType::GovernanceSupplyUpdate {
+                total_governance_supply: 2_000,
+            },
+        };
+        let mut create_metas = accounts.create_metas();
+        create_metas.push(AccountMeta::new_readonly(stake, false));
+        process_and_update(
+            &instruction,
+            &mut transaction_accounts,
+            create_metas,
+            Ok(()),
+        );
+        set_epoch(&mut transaction_accounts, 1);
+        process_and_update(
+            &GovernanceInstruction::CastVote {
+                proposal_id: 0,
+                vote: Vote::For,
+            },
+            &mut transaction_accounts,
+            cast_vote_metas(&accounts, voter, stake, vote_record),
+            Ok(()),
+        );
+        set_epoch(&mut transaction_accounts, 2);
+        let execute = GovernanceInstruction::ExecuteProposal { proposal_id: 0 };
+        for expected_status in [ProposalStatus::Timelocked, ProposalStatus::Executed] {
+            process_and_update(
+                &execute,
+                &mut transaction_accounts,
+                accounts.execute_metas(&instruction),
+                Ok(()),
+            );
+            let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+            assert_eq!(proposal.status, expected_status);
+        }
+        let config = GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+        assert_eq!(config.total_governance_supply, 2_000);
+        assert_eq!(config.voting_period_epochs, 2);
+    }
+
+    #[test]
+    fn test_sweep_expires_active_proposal_past_deadline() {
+        let accounts = TestAccounts::new();
+        let escrow = find_deposit_escrow_address(&accounts.config, 0).0;
+        let stake = Pubkey::new_unique();
+        let sweeper = Pubkey::new_unique();
+        let mut transaction_accounts = deposit_proposal_accounts(
+            &accounts,
+            vec![
+                (stake, passive_stake(5_000, 0)),
+                (sweeper, AccountSharedData::new(0, 0, &system_program::id())),
+            ],
+            stake,
+        );
+        let mut config = GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+        config.sweeper_reward_lamports = 100;
+        config
+            .serialize_into(transaction_accounts[1].1.data_as_mut_slice())
+            .unwrap();
+
+        let sweep = GovernanceInstruction::SweepExpiredProposals;
+        let sweep_metas = vec![
+            AccountMeta::new(sweeper, true),
+            AccountMeta::new_readonly(accounts.config, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(accounts.proposal, false),
+            AccountMeta::new(escrow, false),
+        ];
+        process_and_update(
+            &sweep,
+            &mut transaction_accounts,
+            sweep_metas[..4].to_vec(),
+            Err(custom(GovernanceError::InvalidSweepCount)),
+        );
+
+        // The vote can still be tallied in its last epoch.
+        set_epoch(&mut transaction_accounts, 2);
+        process_and_update(
+            &sweep,
+            &mut transaction_accounts,
+            sweep_metas.clone(),
+            Ok(()),
+        );
+        let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Active);
+        assert_eq!(lamports_of(&transaction_accounts, &sweeper), 0);
+
+        set_epoch(&mut transaction_accounts, 3);
+        process_and_update(
+            &sweep,
+            &mut transaction_accounts,
+            sweep_metas.clone(),
+            Ok(()),
+        );
+        let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Expired);
+        assert_eq!(proposal.deposit_lamports, 900);
+        assert_eq!(lamports_of(&transaction_accounts, &sweeper), 100);
+        assert_eq!(lamports_of(&transaction_accounts, &escrow), 900);
+
+        // Already expired: nothing more to sweep.
+        process_and_update(&sweep, &mut transaction_accounts, sweep_metas, Ok(()));
+        assert_eq!(lamports_of(&transaction_accounts, &sweeper), 100);
+
+        // The rest of the deposit is forfeited as usual.
+        process_and_update(
+            &GovernanceInstruction::ClaimDeposit { proposal_id: 0 },
+            &mut transaction_accounts,
+            vec![
+                AccountMeta::new(accounts.authority, true),
+                AccountMeta::new(accounts.proposal, false),
+                AccountMeta::new_readonly(accounts.config, false),
+                AccountMeta::new(escrow, false),
+                AccountMeta::new_readonly(accounts.treasury_config, false),
+                AccountMeta::new(accounts.treasury, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            Ok(()),
+        );
+        assert_eq!(lamports_of(&transaction_accounts, &escrow), 0);
+        assert_eq!(lamports_of(&transaction_accounts, &accounts.treasury), 900);
+    }
+
+    #[test]
+    fn test_sweep_tallies_active_proposal_before_expiring_it() {
+        let accounts = TestAccounts::new();
+        let escrow = find_deposit_escrow_address(&accounts.config, 0).0;
+        let voter = Pubkey::new_unique();
+        let stake = Pubkey::new_unique();
+        let sweeper = Pubkey::new_unique();
+        let vote_record = find_vote_record_address(0, &voter).0;
+        let sweep_metas = vec![
+            AccountMeta::new(sweeper, true),
+            AccountMeta::new_readonly(accounts.config, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(accounts.proposal, false),
+            AccountMeta::new(escrow, false),
+        ];
+
+        // (vote cast, status after the sweep, sweeper reward)
+        for (vote, status, reward) in [
+            (Vote::For, ProposalStatus::Timelocked, 0),
+            (Vote::Against, ProposalStatus::Rejected, 100),
+            (Vote::Veto, ProposalStatus::Vetoed, 100),
+        ] {
+            let mut extra = voter_accounts(&[voter]);
+            extra.push((stake, passive_stake_of(&voter, 5_000)));
+            extra.push((sweeper, AccountSharedData::new(0, 0, &system_program::id())));
+            let mut transaction_accounts = deposit_proposal_accounts(&accounts, extra, stake);
+            let mut config =
+                GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+            config.sweeper_reward_lamports = 100;
+            config
+                .serialize_into(transaction_accounts[1].1.data_as_mut_slice())
+                .unwrap();
+
+            process_and_update(
+                &GovernanceInstruction::CastVote {
+                    proposal_id: 0,
+                    vote,
+                },
+                &mut transaction_accounts,
+                cast_vote_metas(&accounts, voter, stake, vote_record),
+                Ok(()),
+            );
+            set_epoch(&mut transaction_accounts, 3);
+            process_and_update(
+                &GovernanceInstruction::SweepExpiredProposals,
+                &mut transaction_accounts,
+                sweep_metas.clone(),
+                Ok(()),
+            );
+
+            // A passed proposal keeps its whole deposit.
+            let proposal = Proposal::deserialize(transaction_accounts[2].1.data()).unwrap();
+            assert_eq!(proposal.status, status);
+            assert_eq!(proposal.deposit_lamports, 1_000 - reward);
+            assert_eq!(lamports_of(&transaction_accounts, &sweeper), reward);
+            assert_eq!(lamports_of(&transaction_accounts, &escrow), 1_000 - reward);
+        }
+    }
+
+    #[test]
+    fn test_sweep_expires_timelocked_proposal_past_grace() {
+        let accounts = TestAccounts::new();
+        let later_proposal = Pubkey::new_unique();
+        let sweeper = Pubkey::new_unique();
+        let mut transaction_accounts = accounts.transaction_accounts(Proposal::SERIALIZED_SIZE, 0);
+        transaction_accounts.extend([
+            (
+                later_proposal,
+                AccountSharedData::new(1, Proposal::SERIALIZED_SIZE, &id()),
+            ),
+            (sweeper, AccountSharedData::new(0, 0, &system_program::id())),
+        ]);
+
+        // Inactive governance: both proposals are timelocked until their
+        // creation epoch, 0 and 10.
+        let instruction = param_change(4, 5_000);
+        process_and_update(
+            &instruction,
+            &mut transaction_accounts,
+            accounts.create_metas(),
+            Ok(()),
+        );
+        set_epoch(&mut transaction_accounts, 10);
+        let mut later_metas = accounts.create_metas();
+        later_metas[2] = AccountMeta::new(later_proposal, false);
+        process_and_update(&instruction, &mut transaction_accounts, later_metas, Ok(()));
+
+        // Neither deposit is escrowed, so any account stands in for the
+        // escrows.
+        let sweep = GovernanceInstruction::SweepExpiredProposals;
+        let sweep_metas = vec![
+            AccountMeta::new(sweeper, true),
+            AccountMeta::new_readonly(accounts.config, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(accounts.proposal, false),
+            AccountMeta::new(accounts.recipient, false),
+            AccountMeta::new(later_proposal, false),
+            AccountMeta::new(accounts.recipient, false),
+        ];
+        let statuses = |transaction_accounts: &[(Pubkey, AccountSharedData)]| {
+            [accounts.proposal, later_proposal].map(|key| {
+                let (_, account) = transaction_accounts
+                    .iter()
+                    .find(|(account_key, _)| *account_key == key)
+                    .unwrap();
+                Proposal::deserialize(account.data()).unwrap().status
+            })
+        };
+
+        // The 30-epoch grace of the first proposal runs through epoch 30.
+        set_epoch(&mut transaction_accounts, 30);
+        process_and_update(
+            &sweep,
+            &mut transaction_accounts,
+            sweep_metas.clone(),
+            Ok(()),
+        );
+        assert_eq!(
+            statuses(&transaction_accounts),
+            [ProposalStatus::Timelocked, ProposalStatus::Timelocked]
+        );
+
+        set_epoch(&mut transaction_accounts, 31);
+        process_and_update(&sweep, &mut transaction_accounts, sweep_metas, Ok(()));
+        assert_eq!(
+            statuses(&transaction_accounts),
+            [ProposalStatus::Expired, ProposalStatus::Timelocked]
+        );
+        assert_eq!(lamports_of(&transaction_accounts, &sweeper), 0);
+
+        // An expired proposal can no longer be executed; the valid one can.
+        process_and_update(
+            &GovernanceInstruction::ExecuteProposal { proposal_id: 0 },
+            &mut transaction_accounts,
+            accounts.execute_metas(&instruction),
+            Err(custom(GovernanceError::InvalidProposalStatus)),
+        );
+        let mut execute_later = accounts.execute_metas(&instruction);
+        execute_later[1] = AccountMeta::new(later_proposal, false);
+        process_and_update(
+            &GovernanceInstruction::ExecuteProposal { proposal_id: 1 },
+            &mut transaction_accounts,
+            execute_later,
+            Ok(()),
+        );
+        assert_eq!(
+            statuses(&transaction_accounts),
+            [ProposalStatus::Expired, ProposalStatus::Executed]
+        );
+    }
+
+    #[test]
+    fn test_deposit_settlement_split() {
+        let proposal = |status, deposit_lamports| Proposal {
+            id: 0,
+            proposer: Pubkey::new_unique(),
+            title: [0; 64],
+            description_hash: Hash::default(),
+            proposal_type: ProposalType::TextProposal,
+            status,
+            created_epoch: 0,
+            voting_ends_epoch: 0,
+            execution_epoch: 0,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            veto_votes: 0,
+            executed: false,
+            executed_steps: 0,
+            voting_power_snapshot_epoch: 0,
+            deposit_lamports,
+            deposit_claimed: false,
+        };
+        assert_eq!(
+            proposal(ProposalStatus::ExecutionFailed, 7).deposit_settlement(),
+            Some((7, 0))
+        );
+        // The odd lamport of a rejected proposal goes to the treasury.
+        assert_eq!(
+            proposal(ProposalStatus::Rejected, 7).deposit_settlement(),
+            Some((3, 4))
+        );
+        assert_eq!(
+            proposal(ProposalStatus::Cancelled, 7).deposit_settlement(),
+            Some((0, 7))
+        );
+        assert_eq!(
+            proposal(ProposalStatus::Timelocked, 7).deposit_settlement(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_initialize_governance_creates_network_params() {
+        let initializer = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+        let params = find_network_params_address().0;
+        let initialize = GovernanceInstruction::InitializeGovernance {
+            authority: initializer,
+            proposal_threshold: 0,
+            voting_period_epochs: 10,
+            quorum_bps: 3_000,
+            pass_threshold_bps: 5_000,
+            veto_threshold_bps: 3_333,
+            timelock_epochs: 2,
+            emergency_multisig: Pubkey::new_unique(),
+        };
+        let mut program_account = AccountSharedData::new(1, 0, &native_loader::id());
+        program_account.set_executable(true);
+        let transaction_accounts = |params: Pubkey| {
+            vec![
+                (
+                    initializer,
+                    AccountSharedData::new(1_000_000_000, 0, &system_program::id()),
+                ),
+                (
+                    config,
+                    AccountSharedData::new(1, GovernanceConfig::SERIALIZED_SIZE, &id()),
+                ),
+                (params, AccountSharedData::default()),
+                (system_program::id(), program_account.clone()),
+                (
+                    sysvar::rent::id(),
+                    account::create_account_shared_data_for_test(&Rent::default()),
+                ),
+            ]
+        };
+        let metas = |params: Pubkey| {
+            vec![
+                AccountMeta::new(initializer, true),
+                AccountMeta::new(config, false),
+                AccountMeta::new(params, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ]
+        };
+
+        let after = process_instruction(
+            &initialize,
+            transaction_accounts(params),
+            metas(params),
+            Ok(()),
+        );
+        let rent = Rent::default().minimum_balance(NetworkParams::SERIALIZED_SIZE);
+        assert_eq!(after[2].owner(), &id());
+        assert_eq!(after[2].data().len(), NetworkParams::SERIALIZED_SIZE);
+        assert_eq!(after[2].lamports(), rent);
+        assert_eq!(after[0].lamports(), 1_000_000_000 - rent);
+        assert_eq!(
+            NetworkParams::deserialize(after[2].data()).unwrap(),
+            NetworkParams::default()
+        );
+
+        // Any other address is refused.
+        let elsewhere = Pubkey::new_unique();
+        process_instruction(
+            &initialize,
+            transaction_accounts(elsewhere),
+            metas(elsewhere),
+            Err(custom(GovernanceError::InvalidAccountData)),
+        );
+
+        // The network params account is optional.
+        let mut metas = metas(params);
+        metas.truncate(2);
+        process_instruction(&initialize, transaction_accounts(params), metas, Ok(()));
+    }
+
+    /// Transaction accounts whose governance config names the returned key
+    /// as its emergency multisig.
+    fn fee_market_pause_accounts(
+        accounts: &TestAccounts,
+    ) -> (Vec<(Pubkey, AccountSharedData)>, Pubkey) {
+        let multisig = Pubkey::new_unique();
+        let mut transaction_accounts = accounts.transaction_accounts(Proposal::SERIALIZED_SIZE, 0);
+        let mut config = GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+        config.emergency_multisig = multisig;
+        config
+            .serialize_into(transaction_accounts[1].1.data_as_mut_slice())
+            .unwrap();
+        transaction_accounts.push((multisig, AccountSharedData::new(1, 0, &Pubkey::default())));
+        (transaction_accounts, multisig)
+    }
+
+    fn fee_market_pause_metas(accounts: &TestAccounts, signer: Pubkey) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new_readonly(accounts.config, false),
+            AccountMeta::new(accounts.params, false),
+        ]
+    }
+
+    #[test]
+    fn test_pause_fee_market_fixes_base_fee() {
+        let accounts = TestAccounts::new();
+        let (mut transaction_accounts, multisig) = fee_market_pause_accounts(&accounts);
+
+        // Only the emergency multisig can pause.
+        process_and_update(
+            &GovernanceInstruction::PauseFeeMarket {
+                fixed_base_fee: 5_000,
+            },
+            &mut transaction_accounts,
+            fee_market_pause_metas(&accounts, accounts.authority),
+            Err(custom(GovernanceError::NotEmergencyMultisig)),
+        );
+        for fixed_base_fee in [0, MAX_FEE_MARKET_PAUSE_FEE + 1] {
+            process_and_update(
+                &GovernanceInstruction::PauseFeeMarket { fixed_base_fee },
+                &mut transaction_accounts,
+                fee_market_pause_metas(&accounts, multisig),
+                Err(custom(GovernanceError::InvalidNetworkParameter)),
+            );
+        }
+        let params = NetworkParams::deserialize(transaction_accounts[5].1.data()).unwrap();
+        assert_eq!(params.fee_market_pause(), None);
+
+        process_and_update(
+            &GovernanceInstruction::PauseFeeMarket {
+                fixed_base_fee: 5_000,
+            },
+            &mut transaction_accounts,
+            fee_market_pause_metas(&accounts, multisig),
+            Ok(()),
+        );
+        let params = NetworkParams::deserialize(transaction_accounts[5].1.data()).unwrap();
+        assert_eq!(params.fee_market_pause(), Some(5_000));
+        assert_eq!(params.get(FEE_MARKET_PAUSED_PARAM), Some(1));
+
+        // Pausing again while paused changes the fixed fee.
+        process_and_update(
+            &GovernanceInstruction::PauseFeeMarket {
+                fixed_base_fee: 7_500,
+            },
+            &mut transaction_accounts,
+            fee_market_pause_metas(&accounts, multisig),
+            Ok(()),
+        );
+        let params = NetworkParams::deserialize(transaction_accounts[5].1.data()).unwrap();
+        assert_eq!(params.fee_market_pause(), Some(7_500));
+    }
+
+    #[test]
+    fn test_resume_fee_market_by_multisig_only_when_enabled() {
+        let accounts = TestAccounts::new();
+        let (mut transaction_accounts, multisig) = fee_market_pause_accounts(&accounts);
+        let resume = GovernanceInstruction::ResumeFeeMarket;
+
+        process_and_update(
+            &GovernanceInstruction::PauseFeeMarket {
+                fixed_base_fee: 5_000,
+            },
+            &mut transaction_accounts,
+            fee_market_pause_metas(&accounts, multisig),
+            Ok(()),
+        );
+        process_and_update(
+            &resume,
+            &mut transaction_accounts,
+            fee_market_pause_metas(&accounts, multisig),
+            Err(custom(GovernanceError::FeeMarketResumeRequiresProposal)),
+        );
+
+        let mut params = NetworkParams::deserialize(transaction_accounts[5].1.data()).unwrap();
+        params.set(FEE_MARKET_MULTISIG_RESUME_PARAM, 1);
+        params
+            .serialize_into(transaction_accounts[5].1.data_as_mut_slice())
+            .unwrap();
+        process_and_update(
+            &resume,
+            &mut transaction_accounts,
+            fee_market_pause_metas(&accounts, accounts.authority),
+            Err(custom(GovernanceError::NotEmergencyMultisig)),
+        );
+        process_and_update(
+            &resume,
+            &mut transaction_accounts,
+            fee_market_pause_metas(&accounts, multisig),
+            Ok(()),
+        );
+        let params = NetworkParams::deserialize(transaction_accounts[5].1.data()).unwrap();
+        assert_eq!(params.fee_market_pause(), None);
+
+        process_and_update(
+            &resume,
+            &mut transaction_accounts,
+            fee_market_pause_metas(&accounts, multisig),
+            Err(custom(GovernanceError::FeeMarketNotPaused)),
+        );
+    }
+
+    #[test]
+    fn test_resume_fee_market_by_parameter_change_proposal() {
+        let accounts = TestAccounts::new();
+        let (mut transaction_accounts, multisig) = fee_market_pause_accounts(&accounts);
+
+        process_and_update(
+            &GovernanceInstruction::PauseFeeMarket {
+                fixed_base_fee: 5_000,
+            },
+            &mut transaction_accounts,
+            fee_market_pause_metas(&accounts, multisig),
+            Ok(()),
+        );
+
+        let instruction = param_change(FEE_MARKET_PAUSED_PARAM, 0);
+        process_and_update(
+            &instruction,
+            &mut transaction_accounts,
+            accounts.create_metas(),
+            Ok(()),
+        );
+        process_and_update(
+            &GovernanceInstruction::ExecuteProposal { proposal_id: 0 },
+            &mut transaction_accounts,
+            accounts.execute_metas(&instruction),
+            Ok(()),
+        );
+        let params = NetworkParams::deserialize(transaction_accounts[5].1.data()).unwrap();
+        assert_eq!(params.fee_market_pause(), None);
+        // The pause fee stays recorded; it only applies while paused.
+        assert_eq!(params.get(FEE_MARKET_PAUSE_FEE_PARAM), Some(5_000));
+    }
+
+    #[test]
+    fn test_legacy_config_is_read_and_migrated_by_authority() {
+        let accounts = TestAccounts::new();
+        let mut transaction_accounts = accounts.transaction_accounts(0, 0);
+        let rent = Rent::default();
+        transaction_accounts.push((
+            sysvar::rent::id(),
+            account::create_account_shared_data_for_test(&rent),
+        ));
+        transaction_accounts[0]
+            .1
+            .set_lamports(rent.minimum_balance(0) + 1_000_000_000);
+
+        // A config written before the delegation and deposit fields existed.
+        let legacy_lamports = rent.minimum_balance(GovernanceConfig::LEGACY_SERIALIZED_SIZE);
+        let full = transaction_accounts[1].1.data().to_vec();
+        let mut legacy = AccountSharedData::new(
+            legacy_lamports,
+            GovernanceConfig::LEGACY_SERIALIZED_SIZE,
+            &id(),
+        );
+        legacy
+            .data_as_mut_slice()
+            .copy_from_slice(&full[..GovernanceConfig::LEGACY_SERIALIZED_SIZE]);
+        transaction_accounts[1].1 = legacy;
+
+        let config = GovernanceConfig::deserialize(transaction_accounts[1].1.data()).unwrap();
+        assert_eq!(config.authority, accounts.authority);
+        assert_eq!(config.quorum_bps, 3_000);
+        assert_eq!(config.max_delegation_epochs, DEFAULT_MAX_DELEGATION_EPOCHS);
+        assert_eq!(config.proposal_deposit, DEFAULT_PROPOSAL_DEPOSIT);
+        assert_eq!(config.total_governance_supply, 0);
+        assert!(config.fits_legacy_layout());
+
+        // Migration needs the system program to move the authority's rent.
+        let update_supply = GovernanceInstruction::UpdateGovernanceSupply {
+            total_governance_supply: 1_000_000,
+        };
+        process_and_update(
+            &update_supply,
+            &mut transaction_accounts,
+            vec![
+                AccountMeta::new(accounts.authority, true),
+                AccountMeta::new(accounts.config, false),
+            ],
+            Err(InstructionError::MissingAccount),
+        );
+        process_and_update(
+            &update_supply,
+            &mut transaction_accounts,
+            vec![
+                AccountMeta::new(accounts.authority, true),
+                AccountMeta::new(accounts.config, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            Ok(()),
+        );
+
+        let config_account = &transaction_accounts[1].1;
+        assert_eq!(
+            config_account.data().len(),
+            GovernanceConfig::SERIALIZED_SIZE
+        );
+        assert_eq!(
+            config_account.lamports(),
+            rent.minimum_balance(GovernanceConfig::SERIALIZED_SIZE)
+        );
+        let config = GovernanceConfig::deserialize(config_account.data()).unwrap();
+        assert_eq!(config.total_governance_supply, 1_000_000);
+        assert_eq!(config.authority, accounts.authority);
+        assert_eq!(config.max_delegation_epochs, DEFAULT_MAX_DELEGATION_EPOCHS);
+    }
+}
@@ -1,6 +1,12 @@
 //! Account state types for the TRv1 Governance program.
 
 use {
+    crate::constants::{
+        DEFAULT_EXPIRY_GRACE_EPOCHS, DEFAULT_MAX_DELEGATION_EPOCHS, DEFAULT_PROPOSAL_DEPOSIT,
+        DEFAULT_SWEEPER_REWARD_LAMPORTS, DEFAULT_VOTE_CHANGE_LOCKOUT_EPOCHS,
+        DELEGATION_RECORD_SEED, FEE_MARKET_PAUSED_PARAM, FEE_MARKET_PAUSE_FEE_PARAM,
+        NETWORK_PARAMS, NETWORK_PARAMS_SEED, PROPOSAL_DEPOSIT_SEED, VOTE_RECORD_SEED,
+    },
     borsh::{BorshDeserialize, BorshSerialize},
     serde::{Deserialize, Serialize},
     solana_hash::Hash,
@@ -20,6 +26,12 @@ pub const PROPOSAL_DISCRIMINATOR: u8 = 2;
 /// Discriminator for `VoteRecord` accounts (prevents double-voting).
 pub const VOTE_RECORD_DISCRIMINATOR: u8 = 3;
 
+/// Discriminator for `NetworkParams` accounts.
+pub const NETWORK_PARAMS_DISCRIMINATOR: u8 = 4;
+
+/// Discriminator for `DelegationRecord` accounts.
+pub const DELEGATION_RECORD_DISCRIMINATOR: u8 = 5;
+
 // ---------------------------------------------------------------------------
 // GovernanceConfig
 // ---------------------------------------------------------------------------
@@ -64,6 +76,31 @@ pub struct GovernanceConfig {
 
     /// Running proposal ID counter (monotonically increasing).
     pub next_proposal_id: u64,
+
+    /// Epochs after which a vote delegation lapses and must be renewed.
+    pub max_delegation_epochs: u64,
+
+    /// Epochs before `voting_ends_epoch` during which votes can no longer
+    /// be changed.
+    pub vote_change_lockout_epochs: u64,
+
+    /// Lamports a proposer escrows when creating a proposal while governance
+    /// is active.  0 disables the deposit.
+    pub proposal_deposit: u64,
+
+    /// Total eligible (commitment-weighted) voting power that `quorum_bps`
+    /// is measured against.  Set by the authority with
+    /// `UpdateGovernanceSupply` before activation and by
+    /// `GovernanceSupplyUpdate` proposals after; while it is 0 no proposal
+    /// can reach quorum.
+    pub total_governance_supply: u64,
+
+    /// Epochs after `execution_epoch` that a timelocked proposal stays
+    /// executable before `SweepExpiredProposals` may expire it.
+    pub expiry_grace_epochs: u64,
+
+    /// Lamports paid from a swept proposal's deposit to the sweeper.
+    pub sweeper_reward_lamports: u64,
 }
 
 impl GovernanceConfig {
@@ -81,8 +118,24 @@ impl GovernanceConfig {
     ///   timelock_epochs      (8)
     ///   emergency_multisig   (32)
     ///   next_proposal_id     (8)
-    ///   = 104 bytes
-    pub const SERIALIZED_SIZE: usize = 1 + 1 + 32 + 8 + 8 + 2 + 2 + 2 + 8 + 32 + 8;
+    ///   max_delegation_epochs (8)
+    ///   vote_change_lockout_epochs (8)
+    ///   proposal_deposit     (8)
+    ///   total_governance_supply (8)
+    ///   expiry_grace_epochs  (8)
+    ///   sweeper_reward_lamports (8)
+    ///   = 152 bytes
+    pub const SERIALIZED_SIZE: usize =
+        1 + 1 + 32 + 8 + 8 + 2 + 2 + 2 + 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+
+    /// Size of the original layout, which ended at `next_proposal_id`
+    /// (104 bytes).
+    ///
+    /// Accounts created with it are read with the later fields at their
+    /// `InitializeGovernance` defaults, and written back in place for as
+    /// long as those fields keep their defaults.  The authority's admin
+    /// instructions grow them to [`Self::SERIALIZED_SIZE`].
+    pub const LEGACY_SERIALIZED_SIZE: usize = 1 + 1 + 32 + 8 + 8 + 2 + 2 + 2 + 8 + 32 + 8;
 
     /// Deserialise from raw account data (expects leading discriminator).
     pub fn deserialize(data: &[u8]) -> Result<Self, std::io::Error> {
@@ -93,12 +146,24 @@ impl GovernanceConfig {
             ));
         }
         let mut cursor = &data[1..];
+        if data.len() < Self::SERIALIZED_SIZE {
+            let legacy: LegacyGovernanceConfig = BorshDeserialize::deserialize_reader(&mut cursor)?;
+            return Ok(legacy.into());
+        }
         BorshDeserialize::deserialize_reader(&mut cursor)
     }
 
     /// Serialise into raw account data (prepends discriminator).
+    ///
+    /// A buffer of [`Self::LEGACY_SERIALIZED_SIZE`] is accepted when
+    /// [`Self::fits_legacy_layout`] holds.
     pub fn serialize_into(&self, data: &mut [u8]) -> Result<(), std::io::Error> {
-        if data.len() < Self::SERIALIZED_SIZE {
+        if data.len() >= Self::SERIALIZED_SIZE {
+            data[0] = GOVERNANCE_CONFIG_DISCRIMINATOR;
+            let mut cursor = &mut data[1..];
+            return BorshSerialize::serialize(self, &mut cursor);
+        }
+        if data.len() < Self::LEGACY_SERIALIZED_SIZE || !self.fits_legacy_layout() {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
                 "account data buffer too small for GovernanceConfig",
@@ -106,7 +171,69 @@ impl GovernanceConfig {
         }
         data[0] = GOVERNANCE_CONFIG_DISCRIMINATOR;
         let mut cursor = &mut data[1..];
-        BorshSerialize::serialize(self, &mut cursor)
+        BorshSerialize::serialize(&LegacyGovernanceConfig::from(self), &mut cursor)
+    }
+
+    /// Whether every field missing from the legacy layout still holds the
+    /// value a legacy account is read with, so nothing is lost by writing
+    /// the legacy layout.
+    pub fn fits_legacy_layout(&self) -> bool {
+        GovernanceConfig::from(LegacyGovernanceConfig::from(self)) == *self
+    }
+}
+
+/// The fields of [`GovernanceConfig::LEGACY_SERIALIZED_SIZE`] accounts.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct LegacyGovernanceConfig {
+    is_active: bool,
+    authority: Pubkey,
+    proposal_threshold: u64,
+    voting_period_epochs: u64,
+    quorum_bps: u16,
+    pass_threshold_bps: u16,
+    veto_threshold_bps: u16,
+    timelock_epochs: u64,
+    emergency_multisig: Pubkey,
+    next_proposal_id: u64,
+}
+
+impl From<&GovernanceConfig> for LegacyGovernanceConfig {
+    fn from(config: &GovernanceConfig) -> Self {
+        Self {
+            is_active: config.is_active,
+            authority: config.authority,
+            proposal_threshold: config.proposal_threshold,
+            voting_period_epochs: config.voting_period_epochs,
+            quorum_bps: config.quorum_bps,
+            pass_threshold_bps: config.pass_threshold_bps,
+            veto_threshold_bps: config.veto_threshold_bps,
+            timelock_epochs: config.timelock_epochs,
+            emergency_multisig: config.emergency_multisig,
+            next_proposal_id: config.next_proposal_id,
+        }
+    }
+}
+
+impl From<LegacyGovernanceConfig> for GovernanceConfig {
+    fn from(legacy: LegacyGovernanceConfig) -> Self {
+        Self {
+            is_active: legacy.is_active,
+            authority: legacy.authority,
+            proposal_threshold: legacy.proposal_threshold,
+            voting_period_epochs: legacy.voting_period_epochs,
+            quorum_bps: legacy.quorum_bps,
+            pass_threshold_bps: legacy.pass_threshold_bps,
+            veto_threshold_bps: legacy.veto_threshold_bps,
+            timelock_epochs: legacy.timelock_epochs,
+            emergency_multisig: legacy.emergency_multisig,
+            next_proposal_id: legacy.next_proposal_id,
+            max_delegation_epochs: DEFAULT_MAX_DELEGATION_EPOCHS,
+            vote_change_lockout_epochs: DEFAULT_VOTE_CHANGE_LOCKOUT_EPOCHS,
+            proposal_deposit: DEFAULT_PROPOSAL_DEPOSIT,
+            total_governance_supply: 0,
+            expiry_grace_epochs: DEFAULT_EXPIRY_GRACE_EPOCHS,
+            sweeper_reward_lamports: DEFAULT_SWEEPER_REWARD_LAMPORTS,
+        }
     }
 }
 
@@ -145,6 +272,158 @@ pub enum ProposalType {
     },
     /// Text-only signaling proposal (no on-chain execution).
     TextProposal,
+    /// Several actions executed atomically, in order.  Either every step
+    /// succeeds or none of them take effect.
+    MultiStep {
+        steps: Vec<ProposalStep>,
+    },
+    /// Replace the voting parameters of the `GovernanceConfig`, applying to
+    /// every proposal created afterwards.  Cannot set `pass_threshold_bps`
+    /// below 50% or `timelock_epochs` below 1.
+    GovernanceConfigUpdate {
+        proposal_threshold: u64,
+        voting_period_epochs: u64,
+        quorum_bps: u16,
+        pass_threshold_bps: u16,
+        veto_threshold_bps: u16,
+        timelock_epochs: u64,
+    },
+    /// Set the share of the treasury balance, in basis points (1 to 10 000),
+    /// that may be disbursed per epoch, via the treasury program's
+    /// `SetEpochAllowance`.
+    TreasuryAllowanceUpdate {
+        epoch_allowance_bps: u16,
+    },
+    /// Replace the `total_governance_supply` that quorum is measured
+    /// against.
+    GovernanceSupplyUpdate {
+        total_governance_supply: u64,
+    },
+}
+
+impl ProposalType {
+    /// The ordered list of steps this proposal executes.
+    ///
+    /// Single-action proposals yield exactly one step; `MultiStep` yields its
+    /// steps as-is.
+    pub fn steps(&self) -> Vec<ProposalStep> {
+        match self {
+            ProposalType::ParameterChange {
+                param_id,
+                new_value,
+            } => vec![ProposalStep::ParameterChange {
+                param_id: *param_id,
+                new_value: *new_value,
+            }],
+            ProposalType::TreasurySpend {
+                recipient,
+                amount,
+                memo,
+            } => vec![ProposalStep::TreasurySpend {
+                recipient: *recipient,
+                amount: *amount,
+                memo: *memo,
+            }],
+            ProposalType::EmergencyUnlock { target_account } => {
+                vec![ProposalStep::EmergencyUnlock {
+                    target_account: *target_account,
+                }]
+            }
+            ProposalType::ProgramUpgrade {
+                program_id,
+                buffer_account,
+            } => vec![ProposalStep::ProgramUpgrade {
+                program_id: *program_id,
+                buffer_account: *buffer_account,
+            }],
+            ProposalType::FeatureToggle {
+                feature_id,
+                enabled,
+            } => vec![ProposalStep::FeatureToggle {
+                feature_id: *feature_id,
+                enabled: *enabled,
+            }],
+            ProposalType::TextProposal => vec![ProposalStep::TextProposal],
+            ProposalType::MultiStep { steps } => steps.clone(),
+            ProposalType::GovernanceConfigUpdate {
+                proposal_threshold,
+                voting_period_epochs,
+                quorum_bps,
+                pass_threshold_bps,
+                veto_threshold_bps,
+                timelock_epochs,
+            } => vec![ProposalStep::GovernanceConfigUpdate {
+                proposal_threshold: *proposal_threshold,
+                voting_period_epochs: *voting_period_epochs,
+                quorum_bps: *quorum_bps,
+                pass_threshold_bps: *pass_threshold_bps,
+                veto_threshold_bps: *veto_threshold_bps,
+                timelock_epochs: *timelock_epochs,
+            }],
+            ProposalType::TreasuryAllowanceUpdate {
+                epoch_allowance_bps,
+            } => vec![ProposalStep::TreasuryAllowanceUpdate {
+                epoch_allowance_bps: *epoch_allowance_bps,
+            }],
+            ProposalType::GovernanceSupplyUpdate {
+                total_governance_supply,
+            } => vec![ProposalStep::GovernanceSupplyUpdate {
+                total_governance_supply: *total_governance_supply,
+            }],
+        }
+    }
+}
+
+/// A single action inside a `ProposalType::MultiStep` proposal.
+///
+/// Mirrors every single-action `ProposalType` variant.  `MultiStep` itself is
+/// deliberately absent so that multi-step proposals cannot be nested.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub enum ProposalStep {
+    /// See [`ProposalType::ParameterChange`].
+    ParameterChange {
+        param_id: u32,
+        new_value: u64,
+    },
+    /// See [`ProposalType::TreasurySpend`].
+    TreasurySpend {
+        recipient: Pubkey,
+        amount: u64,
+        memo: [u8; 32],
+    },
+    /// See [`ProposalType::EmergencyUnlock`].
+    EmergencyUnlock {
+        target_account: Pubkey,
+    },
+    /// See [`ProposalType::ProgramUpgrade`].
+    ProgramUpgrade {
+        program_id: Pubkey,
+        buffer_account: Pubkey,
+    },
+    /// See [`ProposalType::FeatureToggle`].
+    FeatureToggle {
+        feature_id: u32,
+        enabled: bool,
+    },
+    /// See [`ProposalType::TextProposal`].
+    TextProposal,
+    /// See [`ProposalType::GovernanceConfigUpdate`].
+    GovernanceConfigUpdate {
+        proposal_threshold: u64,
+        voting_period_epochs: u64,
+        quorum_bps: u16,
+        pass_threshold_bps: u16,
+        veto_threshold_bps: u16,
+        timelock_epochs: u64,
+    },
+    /// See [`ProposalType::TreasuryAllowanceUpdate`].
+    TreasuryAllowanceUpdate {
+        epoch_allowance_bps: u16,
+    },
+    /// See [`ProposalType::GovernanceSupplyUpdate`].
+    GovernanceSupplyUpdate {
+        total_governance_supply: u64,
+    },
 }
 
 /// Lifecycle status of a proposal.
@@ -169,6 +448,8 @@ pub enum ProposalStatus {
     Cancelled = 7,
     /// Voting period ended without reaching quorum.
     Expired = 8,
+    /// A step of a multi-step proposal failed; no step took effect.
+    ExecutionFailed = 9,
 }
 
 /// The vote choice a participant casts.
@@ -225,21 +506,36 @@ pub struct Proposal {
 
     /// Whether the proposal has been executed.
     pub executed: bool,
+
+    /// Number of steps that completed during execution.  Equals the step
+    /// count once executed; reset to 0 when a multi-step execution reverts.
+    pub executed_steps: u8,
+
+    /// Epoch at which voting power is measured (the creation epoch).  Only
+    /// passive stake locked at or before this epoch may vote.
+    pub voting_power_snapshot_epoch: u64,
+
+    /// Lamports the proposer escrowed at creation (0 if no deposit was
+    /// required).
+    pub deposit_lamports: u64,
+
+    /// Whether the deposit has been settled by `ClaimDeposit`.
+    pub deposit_claimed: bool,
 }
 
 impl Proposal {
-    /// Conservative upper bound on serialised size.
+    /// Conservative upper bound on serialised size of a single-action proposal.
     ///
     /// The actual size varies by `ProposalType` variant, but we allocate the
     /// maximum to keep accounts fixed-size.
     ///
-    /// Layout (worst case — TreasurySpend is largest variant):
+    /// Layout (worst case — TreasurySpend is largest single-action variant):
     ///   discriminator       (1)
     ///   id                  (8)
     ///   proposer            (32)
     ///   title               (64)
     ///   description_hash    (32)
-    ///   proposal_type tag   (4)  (borsh enum discriminant)
+    ///   proposal_type tag   (1)  (borsh enum discriminant)
     ///   proposal_type data  (72) (TreasurySpend: Pubkey(32) + u64(8) + [u8;32](32))
     ///   status              (1)
     ///   created_epoch       (8)
@@ -250,11 +546,31 @@ impl Proposal {
     ///   votes_abstain       (8)
     ///   veto_votes          (8)
     ///   executed            (1)
-    ///   = 271 bytes
+    ///   executed_steps      (1)
+    ///   voting_power_snapshot_epoch (8)
+    ///   deposit_lamports    (8)
+    ///   deposit_claimed     (1)
+    ///   = 286 bytes
     ///
     /// We round up to 512 for future extensibility.
     pub const SERIALIZED_SIZE: usize = 512;
 
+    /// Conservative upper bound on serialised size of a `MultiStep` proposal.
+    ///
+    /// Same layout as above, with the proposal type replaced by a steps
+    /// length prefix (4) and up to `MAX_PROPOSAL_STEPS` TreasurySpend steps
+    /// (5 × 73 = 365), giving 582 bytes.  Only multi-step proposals pay rent
+    /// for the larger account.
+    pub const MULTI_STEP_SERIALIZED_SIZE: usize = 1024;
+
+    /// Account size required to store this proposal.
+    pub fn serialized_size(&self) -> usize {
+        match self.proposal_type {
+            ProposalType::MultiStep { .. } => Self::MULTI_STEP_SERIALIZED_SIZE,
+            _ => Self::SERIALIZED_SIZE,
+        }
+    }
+
     /// Deserialise from raw account data (expects leading discriminator).
     pub fn deserialize(data: &[u8]) -> Result<Self, std::io::Error> {
         if data.is_empty() || data[0] != PROPOSAL_DISCRIMINATOR {
@@ -269,7 +585,7 @@ impl Proposal {
 
     /// Serialise into raw account data (prepends discriminator).
     pub fn serialize_into(&self, data: &mut [u8]) -> Result<(), std::io::Error> {
-        if data.len() < Self::SERIALIZED_SIZE {
+        if data.len() < self.serialized_size() {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
                 "account data buffer too small for Proposal",
@@ -280,18 +596,63 @@ impl Proposal {
         BorshSerialize::serialize(self, &mut cursor)
     }
 
-    /// Returns `true` if this is an `EmergencyUnlock` proposal.
+    /// Returns `true` if this is an `EmergencyUnlock` proposal, or a
+    /// multi-step proposal containing an `EmergencyUnlock` step.
     pub fn is_emergency_unlock(&self) -> bool {
-        matches!(self.proposal_type, ProposalType::EmergencyUnlock { .. })
+        match &self.proposal_type {
+            ProposalType::EmergencyUnlock { .. } => true,
+            ProposalType::MultiStep { steps } => steps
+                .iter()
+                .any(|step| matches!(step, ProposalStep::EmergencyUnlock { .. })),
+            _ => false,
+        }
+    }
+
+    /// How the escrowed deposit is settled, as `(to_proposer, to_treasury)`
+    /// lamports, or `None` while the outcome is still open.
+    ///
+    /// A proposal that passed the vote — executed, or failed during
+    /// execution — gets its deposit back.  A rejected proposal gets half back;
+    /// the other half (and any odd lamport) goes to the treasury.  Expired,
+    /// vetoed, and cancelled proposals forfeit the whole deposit.
+    pub fn deposit_settlement(&self) -> Option<(u64, u64)> {
+        let deposit = self.deposit_lamports;
+        match self.status {
+            ProposalStatus::Executed | ProposalStatus::ExecutionFailed => Some((deposit, 0)),
+            ProposalStatus::Rejected => Some((deposit / 2, deposit - deposit / 2)),
+            ProposalStatus::Expired | ProposalStatus::Vetoed | ProposalStatus::Cancelled => {
+                Some((0, deposit))
+            }
+            ProposalStatus::Draft
+            | ProposalStatus::Active
+            | ProposalStatus::Passed
+            | ProposalStatus::Timelocked => None,
+        }
     }
 }
 
+/// Address of the escrow holding proposal `proposal_id`'s deposit.
+///
+/// The escrow is a system-owned PDA of this program; the program signs for
+/// it to pay the deposit out in `ClaimDeposit`.
+pub fn find_deposit_escrow_address(governance_config: &Pubkey, proposal_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PROPOSAL_DEPOSIT_SEED,
+            governance_config.as_ref(),
+            &proposal_id.to_le_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
 // ---------------------------------------------------------------------------
 // VoteRecord — prevents double-voting
 // ---------------------------------------------------------------------------
 
-/// Per-voter record for a given proposal.  Created on first vote; existence
-/// prevents the same voter from voting again on the same proposal.
+/// Per-voter record for a given proposal.  Created on first vote and
+/// overwritten when the voter changes their vote, so each voter is counted
+/// once per proposal.
 #[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
 pub struct VoteRecord {
     /// The proposal this vote is for.
@@ -346,3 +707,195 @@ impl VoteRecord {
         BorshSerialize::serialize(self, &mut cursor)
     }
 }
+
+/// Address of `voter`'s `VoteRecord` for proposal `proposal_id`.
+///
+/// `CastVote` creates it on the voter's first vote, so a voter has exactly
+/// one record per proposal.
+pub fn find_vote_record_address(proposal_id: u64, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOTE_RECORD_SEED, &proposal_id.to_le_bytes(), voter.as_ref()],
+        &crate::id(),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// DelegationRecord — proxy voting
+// ---------------------------------------------------------------------------
+
+/// A delegator's standing instruction to let `to` vote with their power.
+///
+/// One record per delegator, at `find_delegation_record_address(from)`.
+/// `DelegateVote` creates or redirects it; it lapses `max_delegation_epochs`
+/// after `delegated_epoch`.  Once `CastVote` has counted its power on a
+/// proposal, the record stays in force until voting on that proposal ends.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct DelegationRecord {
+    /// The delegator whose passive stake backs the delegated power.
+    pub from: Pubkey,
+
+    /// The delegate who votes with it.
+    pub to: Pubkey,
+
+    /// Epoch when the delegation was created or last renewed.
+    pub delegated_epoch: u64,
+
+    /// Whether the delegator may redirect the record before it lapses.
+    pub revocable: bool,
+
+    /// End of voting on the last proposal the delegated power was counted
+    /// on; until then the record can be neither redirected nor bypassed.
+    pub locked_until_epoch: u64,
+}
+
+impl DelegationRecord {
+    /// Serialised size:
+    ///   discriminator    (1)
+    ///   from             (32)
+    ///   to               (32)
+    ///   delegated_epoch  (8)
+    ///   revocable           (1)
+    ///   locked_until_epoch  (8)
+    ///   = 82 bytes
+    pub const SERIALIZED_SIZE: usize = 1 + 32 + 32 + 8 + 1 + 8;
+
+    /// Deserialise from raw account data.
+    pub fn deserialize(data: &[u8]) -> Result<Self, std::io::Error> {
+        if data.is_empty() || data[0] != DELEGATION_RECORD_DISCRIMINATOR {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "missing or invalid delegation record discriminator",
+            ));
+        }
+        let mut cursor = &data[1..];
+        BorshDeserialize::deserialize_reader(&mut cursor)
+    }
+
+    /// Serialise into raw account data.
+    pub fn serialize_into(&self, data: &mut [u8]) -> Result<(), std::io::Error> {
+        if data.len() < Self::SERIALIZED_SIZE {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "account data buffer too small for DelegationRecord",
+            ));
+        }
+        data[0] = DELEGATION_RECORD_DISCRIMINATOR;
+        let mut cursor = &mut data[1..];
+        BorshSerialize::serialize(self, &mut cursor)
+    }
+
+    /// Whether the delegation has lapsed by `current_epoch`.
+    pub fn is_expired(&self, current_epoch: u64, max_delegation_epochs: u64) -> bool {
+        current_epoch >= self.delegated_epoch.saturating_add(max_delegation_epochs)
+    }
+
+    /// Whether its power was counted on a proposal still open at
+    /// `current_epoch`.
+    pub fn is_locked(&self, current_epoch: u64) -> bool {
+        current_epoch < self.locked_until_epoch
+    }
+}
+
+/// Address of `delegator`'s `DelegationRecord`.
+///
+/// `DelegateVote` creates it on first use, and `CastVote` reads it to check
+/// that the voter has not handed their power to someone else.
+pub fn find_delegation_record_address(delegator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DELEGATION_RECORD_SEED, delegator.as_ref()], &crate::id())
+}
+
+// ---------------------------------------------------------------------------
+// NetworkParams — values set by ParameterChange proposals
+// ---------------------------------------------------------------------------
+
+/// A single network parameter value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct NetworkParam {
+    /// Identifier from `constants::NETWORK_PARAMS`.
+    pub param_id: u32,
+    /// Current value.
+    pub value: u64,
+}
+
+/// Tunable network parameters, stored in a governance-owned account and
+/// updated only by executed `ParameterChange` steps.
+///
+/// Parameters that were never set are absent; readers fall back to their
+/// built-in defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct NetworkParams {
+    /// Values set so far, sorted by `param_id`.
+    pub values: Vec<NetworkParam>,
+}
+
+impl NetworkParams {
+    /// Serialised size with every known parameter set:
+    ///   discriminator  (1)
+    ///   values length  (4)
+    ///   values         (12 × NETWORK_PARAMS.len())
+    pub const SERIALIZED_SIZE: usize = 1 + 4 + 12 * NETWORK_PARAMS.len();
+
+    /// Current value of `param_id`, if it has been set.
+    pub fn get(&self, param_id: u32) -> Option<u64> {
+        self.values
+            .binary_search_by_key(&param_id, |param| param.param_id)
+            .ok()
+            .map(|index| self.values[index].value)
+    }
+
+    /// The fixed base fee while the fee market is paused, or `None` when it
+    /// runs normally.
+    pub fn fee_market_pause(&self) -> Option<u64> {
+        (self.get(FEE_MARKET_PAUSED_PARAM) == Some(1))
+            .then(|| self.get(FEE_MARKET_PAUSE_FEE_PARAM))
+            .flatten()
+    }
+
+    /// Set `param_id` to `value`, keeping `values` sorted.
+    pub fn set(&mut self, param_id: u32, value: u64) {
+        match self
+            .values
+            .binary_search_by_key(&param_id, |param| param.param_id)
+        {
+            Ok(index) => self.values[index].value = value,
+            Err(index) => self.values.insert(index, NetworkParam { param_id, value }),
+        }
+    }
+
+    /// Deserialise from raw account data.  An uninitialised (zeroed)
+    /// account holds no values.
+    pub fn deserialize(data: &[u8]) -> Result<Self, std::io::Error> {
+        match data.first() {
+            Some(&0) => Ok(Self::default()),
+            Some(&NETWORK_PARAMS_DISCRIMINATOR) => {
+                let mut cursor = &data[1..];
+                BorshDeserialize::deserialize_reader(&mut cursor)
+            }
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "missing or invalid network params discriminator",
+            )),
+        }
+    }
+
+    /// Serialise into raw account data (prepends discriminator).
+    pub fn serialize_into(&self, data: &mut [u8]) -> Result<(), std::io::Error> {
+        if data.len() < Self::SERIALIZED_SIZE {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "account data buffer too small for NetworkParams",
+            ));
+        }
+        data[0] = NETWORK_PARAMS_DISCRIMINATOR;
+        let mut cursor = &mut data[1..];
+        BorshSerialize::serialize(self, &mut cursor)
+    }
+}
+
+/// Address of the network params account.
+///
+/// `InitializeGovernance` creates it, and the runtime reads the fee market
+/// pause from it when deriving each block's base fee.
+pub fn find_network_params_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NETWORK_PARAMS_SEED], &crate::id())
+}
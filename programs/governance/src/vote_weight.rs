@@ -83,9 +83,15 @@ pub fn calculate_voting_power(amount: u64, source: StakeSource) -> Option<u64> {
 /// known offset (byte 82–83 of the account data, after the discriminator).
 /// The `amount` field is at bytes 33–40 (after discriminator + authority).
 ///
+/// Only locks created at or before `snapshot_epoch` count, so stake moved
+/// into a lock after a proposal was created cannot vote on it.
+///
 /// Returns `Some((amount, effective_power))` on success, `None` if the data is
-/// too short or the weight is zero.
-pub fn voting_power_from_passive_stake_data(data: &[u8]) -> Option<(u64, u64)> {
+/// too short, the weight is zero, or the lock started after `snapshot_epoch`.
+pub fn voting_power_from_passive_stake_data(
+    data: &[u8],
+    snapshot_epoch: u64,
+) -> Option<(u64, u64)> {
     // Passive stake account layout:
     //   [0]      discriminator (1 byte, must be 1)
     //   [1..33]  authority     (32 bytes)
@@ -97,10 +103,13 @@ pub fn voting_power_from_passive_stake_data(data: &[u8]) -> Option<(u64, u64)> {
     //   [73..81] last_reward_epoch (8 bytes)
     //   [81]     is_permanent  (1 byte)
     //   [82..84] vote_weight_bps (2 bytes, little-endian u16)
+    //   [84..92] lock_start_epoch (8 bytes, little-endian u64; absent from
+    //            legacy 84-byte accounts, whose locks predate every proposal)
     const MIN_LEN: usize = 84;
     const DISCRIMINATOR_OFFSET: usize = 0;
     const AMOUNT_OFFSET: usize = 33;
     const VOTE_WEIGHT_OFFSET: usize = 82;
+    const LOCK_START_EPOCH_OFFSET: usize = 84;
 
     if data.len() < MIN_LEN {
         return None;
@@ -121,6 +130,14 @@ pub fn voting_power_from_passive_stake_data(data: &[u8]) -> Option<(u64, u64)> {
         return None; // No voting power.
     }
 
+    let lock_start_epoch = match data.get(LOCK_START_EPOCH_OFFSET..LOCK_START_EPOCH_OFFSET + 8) {
+        Some(bytes) => u64::from_le_bytes(bytes.try_into().ok()?),
+        None => 0,
+    };
+    if lock_start_epoch > snapshot_epoch {
+        return None; // Locked after the snapshot.
+    }
+
     let amount_128 = amount as u128;
     let weight_128 = weight_bps as u128;
     let denom = BPS_DENOMINATOR as u128;
@@ -194,7 +211,7 @@ mod tests {
     fn test_voting_power_from_passive_stake_data() {
         // Build a minimal passive-stake account buffer:
         //   discriminator=1, authority=zeros(32), amount=1000 LE, ..., vote_weight_bps=5000 LE
-        let mut data = vec![0u8; 84];
+        let mut data = vec![0u8; 92];
         data[0] = 1; // discriminator
         // authority: 32 zero bytes (1..33)
         // amount at [33..41]: 1000 = 0xe8, 0x03, ...
@@ -206,19 +223,48 @@ mod tests {
         data[81] = 0;
         // vote_weight_bps at [82..84]: 5000
         data[82..84].copy_from_slice(&5_000u16.to_le_bytes());
+        // lock_start_epoch at [84..92]: 0
 
-        let result = voting_power_from_passive_stake_data(&data);
+        let result = voting_power_from_passive_stake_data(&data, 0);
         assert_eq!(result, Some((1_000, 500))); // 1000 × 5000/10000 = 500
     }
 
     #[test]
     fn test_voting_power_from_passive_stake_no_weight() {
-        let mut data = vec![0u8; 84];
+        let mut data = vec![0u8; 92];
         data[0] = 1;
         data[33..41].copy_from_slice(&1_000u64.to_le_bytes());
         // vote_weight_bps = 0 → no voting power
         data[82..84].copy_from_slice(&0u16.to_le_bytes());
 
-        assert_eq!(voting_power_from_passive_stake_data(&data), None);
+        assert_eq!(voting_power_from_passive_stake_data(&data, 0), None);
+    }
+
+    #[test]
+    fn test_voting_power_from_passive_stake_snapshot_epoch() {
+        let mut data = vec![0u8; 92];
+        data[0] = 1;
+        data[33..41].copy_from_slice(&1_000u64.to_le_bytes());
+        data[82..84].copy_from_slice(&15_000u16.to_le_bytes());
+        data[84..92].copy_from_slice(&10u64.to_le_bytes());
+
+        // Locked in epoch 10: counts for snapshots from epoch 10 on.
+        assert_eq!(voting_power_from_passive_stake_data(&data, 9), None);
+        assert_eq!(
+            voting_power_from_passive_stake_data(&data, 10),
+            Some((1_000, 1_500))
+        );
+        assert_eq!(
+            voting_power_from_passive_stake_data(&data, 11),
+            Some((1_000, 1_500))
+        );
+
+        // Legacy 84-byte accounts, from before `lock_start_epoch` was
+        // recorded, count for every snapshot.
+        assert_eq!(
+            voting_power_from_passive_stake_data(&data[..84], 0),
+            Some((1_000, 1_500))
+        );
+        assert_eq!(voting_power_from_passive_stake_data(&data[..83], 10), None);
     }
 }
@@ -74,6 +74,32 @@ pub const EARLY_UNLOCK_PENALTY_90_DAY_BPS: u64 = 500;   // 5.0%
 pub const EARLY_UNLOCK_PENALTY_180_DAY_BPS: u64 = 750;  // 7.5%
 pub const EARLY_UNLOCK_PENALTY_360_DAY_BPS: u64 = 1_250; // 12.5%
 
+// ---------------------------------------------------------------------------
+// Position limits (in lamports)
+// ---------------------------------------------------------------------------
+
+/// Minimum lamports that can be added to an existing position (1 SOL).
+pub const MIN_TOP_UP_AMOUNT: u64 = 1_000_000_000;
+
+/// Minimum lamports each side of a split position must hold (1 SOL).
+pub const MIN_STAKE_AMOUNT: u64 = 1_000_000_000;
+
+/// Maximum lamports a permanent position may grow to by compounding
+/// rewards (100M SOL).  Permanent principal never leaves the program.
+pub const MAX_STAKE_AMOUNT: u64 = 100_000_000 * 1_000_000_000;
+
+/// Maximum positions `ClaimMultipleRewards` accepts in one instruction,
+/// keeping it within the compute budget.
+pub const MAX_BATCH_CLAIM_ACCOUNTS: usize = 10;
+
+/// Maximum principal a single authority may deposit across all of its
+/// positions (10M SOL), tracked by its `StakeSummaryAccount`.
+pub const MAX_STAKE_PER_ADDRESS: u64 = 10_000_000 * 1_000_000_000;
+
+/// Seed of the per-authority `StakeSummaryAccount` PDA:
+/// `[STAKE_SUMMARY_SEED, authority]`.
+pub const STAKE_SUMMARY_SEED: &[u8] = b"stake_summary";
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -117,6 +143,15 @@ pub fn early_unlock_penalty_bps_for_tier(lock_days: u64) -> Option<u64> {
     }
 }
 
+/// Returns the governance voting power of `amount` staked in the given tier:
+/// `amount × vote_weight_bps / BPS_DENOMINATOR`, saturating at `u64::MAX`.
+/// Invalid tiers carry no voting power.
+pub fn vote_weight_for_stake_position(amount: u64, lock_days: u64) -> u64 {
+    let weight_bps = vote_weight_bps_for_tier(lock_days).unwrap_or(0);
+    let power = amount as u128 * weight_bps as u128 / BPS_DENOMINATOR as u128;
+    power.min(u64::MAX as u128) as u64
+}
+
 /// Returns `true` if `lock_days` is a valid tier value.
 pub fn is_valid_tier(lock_days: u64) -> bool {
     matches!(
@@ -124,3 +159,22 @@ pub fn is_valid_tier(lock_days: u64) -> bool {
         TIER_NO_LOCK | TIER_30_DAY | TIER_90_DAY | TIER_180_DAY | TIER_360_DAY | PERMANENT_LOCK_DAYS
     )
 }
+
+/// Returns the reward accrued in one epoch by `amount` staked in the given
+/// tier, given the validator reward rate in bps:
+/// `amount × validator_rate × tier_rate / (BPS² × 365)`.
+///
+/// Saturates at `u64::MAX`. Returns `None` for invalid tiers.
+pub fn compute_epoch_reward(
+    amount: u64,
+    validator_reward_rate_bps: u64,
+    lock_days: u64,
+) -> Option<u64> {
+    let tier_rate_bps = reward_rate_bps_for_tier(lock_days)?;
+    let denom = (BPS_DENOMINATOR as u128) * (BPS_DENOMINATOR as u128) * 365;
+    let reward = (amount as u128)
+        .saturating_mul(validator_reward_rate_bps as u128)
+        .saturating_mul(tier_rate_bps as u128)
+        / denom;
+    Some(reward.min(u64::MAX as u128) as u64)
+}
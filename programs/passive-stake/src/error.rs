@@ -42,6 +42,27 @@ pub enum PassiveStakeError {
 
     #[error("Account is not rent exempt")]
     NotRentExempt,
+
+    #[error("Amount is below the minimum for this operation")]
+    AmountTooSmall,
+
+    #[error("Rewards must be calculated for the current epoch and claimed first")]
+    UnclaimedRewardsPending,
+
+    #[error("Stake amount would exceed the protocol maximum")]
+    AmountTooLarge,
+
+    #[error("Passive stake accounts do not share the same authority")]
+    AuthorityMismatch,
+
+    #[error("Too many passive stake accounts in one instruction")]
+    TooManyAccounts,
+
+    #[error("Authority's total stake would exceed the per-address maximum")]
+    MaxStakeExceeded,
+
+    #[error("New authority already holds passive stake")]
+    NewAuthorityHasStake,
 }
 
 // Note: InstructionError conversion is provided by the blanket
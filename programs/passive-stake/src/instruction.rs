@@ -3,7 +3,10 @@
 //! All instructions are serialised / deserialised via `bincode` to stay
 //! consistent with the other Agave built-in programs.
 
-use serde::{Deserialize, Serialize};
+use {
+    serde::{Deserialize, Serialize},
+    solana_pubkey::Pubkey,
+};
 
 /// Instructions supported by the Passive Stake program.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -11,11 +14,16 @@ pub enum PassiveStakeInstruction {
     /// Create a new passive stake account, transfer `amount` lamports from the
     /// funding account, and configure the lock tier.
     ///
+    /// Fails if the authority's total deposits would exceed
+    /// `MAX_STAKE_PER_ADDRESS`.
+    ///
     /// # Accounts expected
     ///
     /// 0. `[signer, writable]` — Funding / authority account (pays lamports).
     /// 1. `[writable]`         — Passive stake account (must be pre-allocated
     ///                           with the correct size and owned by this program).
+    /// 2. `[writable]`         — Authority's stake summary PDA (created on first use).
+    /// 3. `[]`                 — System program.
     ///
     /// # Data
     ///
@@ -43,6 +51,7 @@ pub enum PassiveStakeInstruction {
     ///
     /// 0. `[signer, writable]` — Authority account (receives principal).
     /// 1. `[writable]`         — Passive stake account.
+    /// 2. `[writable]`         — Authority's stake summary PDA.
     Unlock,
 
     /// Early-unlock a non-permanent lock **before** the lock period expires.
@@ -54,14 +63,19 @@ pub enum PassiveStakeInstruction {
     ///
     /// 0. `[signer, writable]` — Authority account (receives remainder).
     /// 1. `[writable]`         — Passive stake account.
+    /// 2. `[writable]`         — Authority's stake summary PDA.
     EarlyUnlock,
 
     /// Calculate epoch rewards for a passive stake account.
     /// Typically invoked at epoch boundaries by the runtime or a crank.
     ///
+    /// Positions with `auto_compound` set have new rewards moved from the
+    /// rewards pool into their principal when the pool is supplied.
+    ///
     /// # Accounts expected
     ///
     /// 0. `[writable]` — Passive stake account.
+    /// 1. `[writable]` — (optional) Rewards pool account, for auto-compounding.
     ///
     /// # Data
     ///
@@ -72,4 +86,151 @@ pub enum PassiveStakeInstruction {
         current_epoch: u64,
         validator_reward_rate: u64,
     },
+
+    /// Add lamports to an existing passive stake position.
+    ///
+    /// The lock tier and lock end are unchanged.  Because rewards are
+    /// proportional to the staked amount, the position must have had its
+    /// rewards calculated for the current epoch and claimed beforehand.
+    /// Subject to `MAX_STAKE_PER_ADDRESS` like `InitializePassiveStake`.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer, writable]` — Authority account (pays lamports).
+    /// 1. `[writable]`         — Passive stake account.
+    /// 2. `[]`                 — System program.
+    /// 3. `[writable]`         — Authority's stake summary PDA (created on first use).
+    ///
+    /// # Data
+    ///
+    /// * `stake_account`       — Address of the passive stake account.
+    /// * `additional_lamports` — Lamports to add (at least `MIN_TOP_UP_AMOUNT`).
+    TopUpStake {
+        stake_account: Pubkey,
+        additional_lamports: u64,
+    },
+
+    /// Move `split_amount` lamports of a position into a new position with
+    /// the same tier and lock window.
+    ///
+    /// Rewards must have been calculated for the current epoch; any
+    /// unclaimed rewards on the original position are paid out first.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer, writable]` — Authority account (receives unclaimed rewards).
+    /// 1. `[writable]`         — Passive stake account being split.
+    /// 2. `[writable]`         — New passive stake account (must be pre-allocated
+    ///                           with the correct size and owned by this program).
+    /// 3. `[writable]`         — Rewards pool account (source of reward lamports).
+    ///
+    /// # Data
+    ///
+    /// * `stake_account`     — Address of the passive stake account being split.
+    /// * `split_amount`      — Lamports moved into the new position.
+    /// * `new_stake_account` — Address of the new passive stake account.
+    SplitStake {
+        stake_account: Pubkey,
+        split_amount: u64,
+        new_stake_account: Pubkey,
+    },
+
+    /// Move a non-permanent position to a longer tier without penalty.
+    ///
+    /// The new lock ends `new_lock_days` from now, or at the existing lock
+    /// end if that is later.  Rewards must have been calculated for the
+    /// current epoch; any unclaimed rewards are paid out at the old tier
+    /// rate first.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer, writable]` — Authority account (receives unclaimed rewards).
+    /// 1. `[writable]`         — Passive stake account.
+    /// 2. `[writable]`         — Rewards pool account (source of reward lamports).
+    ///
+    /// # Data
+    ///
+    /// * `stake_account` — Address of the passive stake account.
+    /// * `new_lock_days` — New lock tier; must be longer than the current one.
+    ExtendLock {
+        stake_account: Pubkey,
+        new_lock_days: u64,
+    },
+
+    /// Reinvest all unclaimed rewards into the position's principal, and
+    /// optionally turn automatic compounding at epoch boundaries on or off.
+    ///
+    /// Rewards must have been calculated for the current epoch.  Permanent
+    /// positions cannot compound beyond `MAX_STAKE_AMOUNT`.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer]`   — Authority account.
+    /// 1. `[writable]` — Passive stake account.
+    /// 2. `[writable]` — Rewards pool account (source of reward lamports).
+    ///
+    /// # Data
+    ///
+    /// * `stake_account` — Address of the passive stake account.
+    /// * `auto_compound` — New auto-compound setting, or `None` to keep it.
+    CompoundRewards {
+        stake_account: Pubkey,
+        auto_compound: Option<bool>,
+    },
+
+    /// Claim the unclaimed rewards of several positions owned by the same
+    /// authority in a single transfer.
+    ///
+    /// Sets a bincode-encoded [`ClaimSummary`] as the instruction's return
+    /// data.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer, writable]` — Authority account (receives rewards).
+    /// 1. `[writable]`         — Rewards pool account (source of reward lamports).
+    /// 2.. `[writable]`        — Passive stake accounts, in `stake_accounts` order.
+    ///
+    /// # Data
+    ///
+    /// * `stake_accounts` — Up to `MAX_BATCH_CLAIM_ACCOUNTS` passive stake accounts.
+    ClaimMultipleRewards { stake_accounts: Vec<Pubkey> },
+
+    /// Hand a position over to a new authority, e.g. after replacing a
+    /// hardware wallet.
+    ///
+    /// Both authorities must sign.  The lock, tier, unclaimed rewards, and
+    /// vote weight carry over unchanged, but the lock start epoch is reset
+    /// to the current epoch so the position cannot vote a second time on
+    /// proposals snapshotted before the transfer.
+    ///
+    /// The position's amount moves between the two authorities' stake
+    /// summaries.  To keep positions from being merged, the transfer fails
+    /// if `new_authority`'s summary shows any stake.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer]`           — Current authority.
+    /// 1. `[signer, writable]` — New authority (pays for its stake summary if new).
+    /// 2. `[writable]`         — Passive stake account.
+    /// 3. `[writable]`         — Current authority's stake summary PDA.
+    /// 4. `[writable]`         — New authority's stake summary PDA.
+    /// 5. `[]`                 — System program.
+    ///
+    /// # Data
+    ///
+    /// * `stake_account` — Address of the passive stake account.
+    /// * `new_authority` — Address of the new authority.
+    TransferStakeAuthority {
+        stake_account: Pubkey,
+        new_authority: Pubkey,
+    },
+}
+
+/// Return data of `ClaimMultipleRewards`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimSummary {
+    /// Lamports transferred to the authority.
+    pub total_lamports: u64,
+    /// Lamports claimed from each position, in `stake_accounts` order.
+    pub per_account: Vec<u64>,
 }
@@ -4,12 +4,16 @@ use {
     crate::{
         constants::{
             early_unlock_penalty_bps_for_tier, is_valid_tier, reward_rate_bps_for_tier,
-            vote_weight_bps_for_tier, BPS_DENOMINATOR, PERMANENT_LOCK_DAYS, SECONDS_PER_DAY,
-            TIER_NO_LOCK,
+            vote_weight_bps_for_tier, BPS_DENOMINATOR, MAX_BATCH_CLAIM_ACCOUNTS, MAX_STAKE_AMOUNT,
+            MAX_STAKE_PER_ADDRESS, MIN_STAKE_AMOUNT, MIN_TOP_UP_AMOUNT, PERMANENT_LOCK_DAYS,
+            SECONDS_PER_DAY, TIER_NO_LOCK,
         },
         error::PassiveStakeError,
-        instruction::PassiveStakeInstruction,
-        state::{PassiveStakeAccount, PASSIVE_STAKE_ACCOUNT_DISCRIMINATOR},
+        instruction::{ClaimSummary, PassiveStakeInstruction},
+        state::{
+            find_stake_summary_address, PassiveStakeAccount, StakeSummaryAccount,
+            PASSIVE_STAKE_ACCOUNT_DISCRIMINATOR,
+        },
     },
     log::*,
     solana_bincode::limited_deserialize,
@@ -17,7 +21,11 @@ use {
     solana_program_runtime::{
         declare_process_instruction, invoke_context::InvokeContext,
     },
+    solana_pubkey::Pubkey,
+    solana_sdk_ids::system_program,
     solana_svm_log_collector::ic_msg,
+    solana_system_interface::instruction as system_instruction,
+    solana_transaction_context::instruction_accounts::BorrowedInstructionAccount,
 };
 
 /// Default compute-unit budget for passive-stake instructions.
@@ -57,9 +65,318 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
             current_epoch,
             validator_reward_rate,
         } => process_calculate_epoch_rewards(invoke_context, current_epoch, validator_reward_rate),
+        PassiveStakeInstruction::TopUpStake {
+            stake_account,
+            additional_lamports,
+        } => process_top_up_stake(invoke_context, stake_account, additional_lamports),
+        PassiveStakeInstruction::SplitStake {
+            stake_account,
+            split_amount,
+            new_stake_account,
+        } => process_split_stake(
+            invoke_context,
+            stake_account,
+            split_amount,
+            new_stake_account,
+        ),
+        PassiveStakeInstruction::ExtendLock {
+            stake_account,
+            new_lock_days,
+        } => process_extend_lock(invoke_context, stake_account, new_lock_days),
+        PassiveStakeInstruction::CompoundRewards {
+            stake_account,
+            auto_compound,
+        } => process_compound_rewards(invoke_context, stake_account, auto_compound),
+        PassiveStakeInstruction::ClaimMultipleRewards { stake_accounts } => {
+            process_claim_multiple_rewards(invoke_context, stake_accounts)
+        }
+        PassiveStakeInstruction::TransferStakeAuthority {
+            stake_account,
+            new_authority,
+        } => process_transfer_stake_authority(invoke_context, stake_account, new_authority),
     }
 });
 
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Pay out `state`'s unclaimed rewards from the rewards pool at
+/// `pool_index` to the authority at account 0, returning the amount paid.
+///
+/// Rewards accrue per epoch on the position's current amount and tier, so
+/// those may only change once everything up to the current epoch has been
+/// calculated and settled at the old values.
+///
+/// Callers reject an aliased pool with [`check_rewards_pool_not_aliased`]
+/// before reading the position.
+fn claim_checkpointed_rewards(
+    invoke_context: &InvokeContext,
+    state: &mut PassiveStakeAccount,
+    pool_index: u16,
+    instruction_name: &str,
+) -> Result<u64, InstructionError> {
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    if state.last_reward_epoch < clock.epoch {
+        ic_msg!(
+            invoke_context,
+            "{}: rewards calculated through epoch {} (current {})",
+            instruction_name,
+            state.last_reward_epoch,
+            clock.epoch
+        );
+        return Err(PassiveStakeError::UnclaimedRewardsPending.into());
+    }
+
+    let rewards = state.unclaimed_rewards;
+    if rewards == 0 {
+        return Ok(0);
+    }
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    {
+        let mut pool_account = instruction_context.try_borrow_instruction_account(pool_index)?;
+        if pool_account.get_lamports() < rewards {
+            ic_msg!(
+                invoke_context,
+                "{}: reward pool has insufficient lamports",
+                instruction_name
+            );
+            return Err(PassiveStakeError::InsufficientLamports.into());
+        }
+        pool_account.checked_sub_lamports(rewards)?;
+    }
+    {
+        let mut authority_account = instruction_context.try_borrow_instruction_account(0)?;
+        authority_account.checked_add_lamports(rewards)?;
+    }
+
+    state.unclaimed_rewards = 0;
+    Ok(rewards)
+}
+
+/// Check that the rewards pool at `pool_index` is none of the stake accounts
+/// at indices `1..pool_index`, so no payout comes out of a position's
+/// principal.
+fn check_rewards_pool_not_aliased(
+    invoke_context: &InvokeContext,
+    pool_index: u16,
+    instruction_name: &str,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let pool_key = instruction_context.get_key_of_instruction_account(pool_index)?;
+    for stake_index in 1..pool_index {
+        if instruction_context.get_key_of_instruction_account(stake_index)? == pool_key {
+            ic_msg!(
+                invoke_context,
+                "{}: rewards pool is a stake account of this instruction",
+                instruction_name
+            );
+            return Err(PassiveStakeError::InvalidAccountData.into());
+        }
+    }
+    Ok(())
+}
+
+/// Move `rewards` lamports from the rewards pool into a passive stake
+/// account so they can be added to its principal.
+fn move_pool_rewards_into_stake(
+    invoke_context: &InvokeContext,
+    pool_account: &mut BorrowedInstructionAccount,
+    stake_account: &mut BorrowedInstructionAccount,
+    rewards: u64,
+    instruction_name: &str,
+) -> Result<(), InstructionError> {
+    // Another position's principal is never a rewards pool.
+    if pool_account.get_data().first() == Some(&PASSIVE_STAKE_ACCOUNT_DISCRIMINATOR) {
+        ic_msg!(
+            invoke_context,
+            "{}: rewards pool is a passive stake account",
+            instruction_name
+        );
+        return Err(PassiveStakeError::InvalidAccountData.into());
+    }
+    if pool_account.get_lamports() < rewards {
+        ic_msg!(
+            invoke_context,
+            "{}: reward pool has insufficient lamports",
+            instruction_name
+        );
+        return Err(PassiveStakeError::InsufficientLamports.into());
+    }
+    pool_account.checked_sub_lamports(rewards)?;
+    stake_account.checked_add_lamports(rewards)?;
+    Ok(())
+}
+
+/// Read `authority`'s stake summary from `summary_account`, checking it is
+/// the authority's PDA.  A summary that has not been created yet (still a
+/// system account, or zero-filled) reads as nothing deposited.
+fn read_stake_summary(
+    invoke_context: &InvokeContext,
+    summary_account: &BorrowedInstructionAccount,
+    authority: &Pubkey,
+    instruction_name: &str,
+) -> Result<StakeSummaryAccount, InstructionError> {
+    if summary_account.get_key() != &find_stake_summary_address(authority).0 {
+        ic_msg!(
+            invoke_context,
+            "{}: {} is not the stake summary of {}",
+            instruction_name,
+            summary_account.get_key(),
+            authority
+        );
+        return Err(InstructionError::InvalidSeeds);
+    }
+    let data = summary_account.get_data();
+    let is_new = summary_account.get_owner() == &system_program::id() && data.is_empty();
+    if !is_new && summary_account.get_owner() != &id() {
+        return Err(PassiveStakeError::InvalidAccountOwner.into());
+    }
+    if is_new || data.iter().all(|byte| *byte == 0) {
+        return Ok(StakeSummaryAccount {
+            authority: *authority,
+            total_staked: 0,
+        });
+    }
+    StakeSummaryAccount::deserialize(data).map_err(|_| PassiveStakeError::InvalidAccountData.into())
+}
+
+fn write_stake_summary(
+    summary_account: &mut BorrowedInstructionAccount,
+    summary: &StakeSummaryAccount,
+) -> Result<(), InstructionError> {
+    let mut data = summary_account.get_data().to_vec();
+    if data.len() < StakeSummaryAccount::SERIALIZED_SIZE {
+        data.resize(StakeSummaryAccount::SERIALIZED_SIZE, 0);
+    }
+    summary
+        .serialize_into(&mut data)
+        .map_err(|_| PassiveStakeError::InvalidAccountData)?;
+    summary_account.set_data_from_slice(&data)
+}
+
+/// Write `state` to a passive-stake account, growing an account still in the
+/// legacy layout to `PassiveStakeAccount::SERIALIZED_SIZE`.
+///
+/// The few extra bytes of rent are covered by the position's own balance,
+/// which holds at least `MIN_STAKE_AMOUNT` on top of the rent it was created
+/// with; no lamports move.
+fn write_passive_stake(
+    stake_account: &mut BorrowedInstructionAccount,
+    state: &PassiveStakeAccount,
+) -> Result<(), InstructionError> {
+    let mut data = stake_account.get_data().to_vec();
+    if data.len() < PassiveStakeAccount::SERIALIZED_SIZE {
+        data.resize(PassiveStakeAccount::SERIALIZED_SIZE, 0);
+    }
+    state
+        .serialize_into(&mut data)
+        .map_err(|_| PassiveStakeError::InvalidAccountData)?;
+    stake_account.set_data_from_slice(&data)
+}
+
+/// Add a deposit of `amount` lamports to `authority`'s stake summary at
+/// `summary_index`, returning the new total.
+///
+/// The summary PDA is created on first use, with `authority` paying rent;
+/// the system program must be in the transaction.  Fails with
+/// `MaxStakeExceeded` if the total would pass `MAX_STAKE_PER_ADDRESS`.
+fn add_to_stake_summary(
+    invoke_context: &mut InvokeContext,
+    summary_index: u16,
+    authority: &Pubkey,
+    amount: u64,
+    instruction_name: &str,
+) -> Result<u64, InstructionError> {
+    let (summary_key, needs_create, mut summary) = {
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+        let summary_account = instruction_context.try_borrow_instruction_account(summary_index)?;
+        let summary = read_stake_summary(
+            invoke_context,
+            &summary_account,
+            authority,
+            instruction_name,
+        )?;
+        (
+            *summary_account.get_key(),
+            summary_account.get_owner() != &id(),
+            summary,
+        )
+    };
+
+    let total = summary
+        .total_staked
+        .checked_add(amount)
+        .ok_or(PassiveStakeError::ArithmeticOverflow)?;
+    if total > MAX_STAKE_PER_ADDRESS {
+        ic_msg!(
+            invoke_context,
+            "{}: {} would have {} lamports staked, above the per-address maximum of {}",
+            instruction_name,
+            authority,
+            total,
+            MAX_STAKE_PER_ADDRESS
+        );
+        return Err(PassiveStakeError::MaxStakeExceeded.into());
+    }
+    summary.total_staked = total;
+
+    if needs_create {
+        let rent = invoke_context.get_sysvar_cache().get_rent()?;
+        // As a builtin, this program signs for its own PDA by naming it.
+        invoke_context.native_invoke(
+            system_instruction::create_account(
+                authority,
+                &summary_key,
+                rent.minimum_balance(StakeSummaryAccount::SERIALIZED_SIZE),
+                StakeSummaryAccount::SERIALIZED_SIZE as u64,
+                &id(),
+            ),
+            &[*authority, summary_key],
+        )?;
+    }
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let mut summary_account = instruction_context.try_borrow_instruction_account(summary_index)?;
+    write_stake_summary(&mut summary_account, &summary)?;
+    Ok(total)
+}
+
+/// Remove `amount` lamports of withdrawn principal from `authority`'s stake
+/// summary at `summary_index`, freeing headroom under
+/// `MAX_STAKE_PER_ADDRESS`.
+///
+/// Compounded rewards grow a position without passing through its summary,
+/// so the total saturates at zero.  Positions opened before the summary
+/// existed leave nothing to update.
+fn remove_from_stake_summary(
+    invoke_context: &InvokeContext,
+    summary_index: u16,
+    authority: &Pubkey,
+    amount: u64,
+    instruction_name: &str,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let mut summary_account = instruction_context.try_borrow_instruction_account(summary_index)?;
+    let mut summary = read_stake_summary(
+        invoke_context,
+        &summary_account,
+        authority,
+        instruction_name,
+    )?;
+    if summary_account.get_owner() != &id() {
+        return Ok(());
+    }
+    summary.total_staked = summary.total_staked.saturating_sub(amount);
+    write_stake_summary(&mut summary_account, &summary)
+}
+
 // ---------------------------------------------------------------------------
 // Instruction handlers
 // ---------------------------------------------------------------------------
@@ -69,8 +386,10 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
 /// Accounts:
 ///   0. `[signer, writable]` — Funding authority (source of lamports).
 ///   1. `[writable]`         — Passive stake account (pre-created, owned by this program).
+///   2. `[writable]`         — Authority's stake summary PDA.
+///   3. `[]`                 — System program.
 fn process_initialize_passive_stake(
-    invoke_context: &InvokeContext,
+    invoke_context: &mut InvokeContext,
     lock_days: u64,
     amount: u64,
 ) -> Result<(), InstructionError> {
@@ -89,7 +408,7 @@ fn process_initialize_passive_stake(
     }
 
     // --- Check accounts ---
-    instruction_context.check_number_of_instruction_accounts(2)?;
+    instruction_context.check_number_of_instruction_accounts(4)?;
 
     // Authority must be a signer.
     if !instruction_context.is_instruction_account_signer(0)? {
@@ -98,6 +417,10 @@ fn process_initialize_passive_stake(
 
     let authority_pubkey = *instruction_context.get_key_of_instruction_account(0)?;
 
+    if instruction_context.get_key_of_instruction_account(3)? != &system_program::id() {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+
     // --- Verify stake account is owned by this program and uninitialised ---
     {
         let stake_account = instruction_context.try_borrow_instruction_account(1)?;
@@ -112,6 +435,17 @@ fn process_initialize_passive_stake(
         }
     }
 
+    // --- Enforce the per-address cap ---
+    let total_staked = add_to_stake_summary(
+        invoke_context,
+        2,
+        &authority_pubkey,
+        amount,
+        "InitializePassiveStake",
+    )?;
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
     // --- Transfer lamports from authority to stake account ---
     {
         let mut authority_account = instruction_context.try_borrow_instruction_account(0)?;
@@ -160,24 +494,20 @@ fn process_initialize_passive_stake(
         last_reward_epoch: current_epoch,
         is_permanent,
         vote_weight_bps: vote_weight,
+        lock_start_epoch: current_epoch,
+        auto_compound: false,
     };
 
     let mut stake_account = instruction_context.try_borrow_instruction_account(1)?;
-    let mut data = stake_account.get_data().to_vec();
-    if data.len() < PassiveStakeAccount::SERIALIZED_SIZE {
-        data.resize(PassiveStakeAccount::SERIALIZED_SIZE, 0);
-    }
-    state
-        .serialize_into(&mut data)
-        .map_err(|_| PassiveStakeError::InvalidAccountData)?;
-    stake_account.set_data_from_slice(&data)?;
+    write_passive_stake(&mut stake_account, &state)?;
 
     ic_msg!(
         invoke_context,
-        "InitializePassiveStake: {} lamports locked for {} days by {}",
+        "InitializePassiveStake: {} lamports locked for {} days by {}, {} staked in total",
         amount,
         lock_days,
-        authority_pubkey
+        authority_pubkey,
+        total_staked
     );
     Ok(())
 }
@@ -224,11 +554,7 @@ fn process_claim_rewards(invoke_context: &InvokeContext) -> Result<(), Instructi
         rewards_to_claim = state.unclaimed_rewards;
         state.unclaimed_rewards = 0;
 
-        let mut buf = stake_account.get_data().to_vec();
-        state
-            .serialize_into(&mut buf)
-            .map_err(|_| PassiveStakeError::InvalidAccountData)?;
-        stake_account.set_data_from_slice(&buf)?;
+        write_passive_stake(&mut stake_account, &state)?;
     }
 
     // --- Transfer rewards from pool to authority ---
@@ -259,11 +585,12 @@ fn process_claim_rewards(invoke_context: &InvokeContext) -> Result<(), Instructi
 /// Accounts:
 ///   0. `[signer, writable]` — Authority (receives principal).
 ///   1. `[writable]`         — Passive stake account.
+///   2. `[writable]`         — Authority's stake summary PDA.
 fn process_unlock(invoke_context: &InvokeContext) -> Result<(), InstructionError> {
     let transaction_context = &invoke_context.transaction_context;
     let instruction_context = transaction_context.get_current_instruction_context()?;
 
-    instruction_context.check_number_of_instruction_accounts(2)?;
+    instruction_context.check_number_of_instruction_accounts(3)?;
 
     if !instruction_context.is_instruction_account_signer(0)? {
         return Err(PassiveStakeError::MissingAuthoritySignature.into());
@@ -272,6 +599,7 @@ fn process_unlock(invoke_context: &InvokeContext) -> Result<(), InstructionError
     let authority_pubkey = *instruction_context.get_key_of_instruction_account(0)?;
 
     let principal;
+    let released;
     {
         let mut stake_account = instruction_context.try_borrow_instruction_account(1)?;
         if stake_account.get_owner() != &id() {
@@ -311,19 +639,21 @@ fn process_unlock(invoke_context: &InvokeContext) -> Result<(), InstructionError
         let zeroed = vec![0u8; stake_account.get_data().len()];
         stake_account.set_data_from_slice(&zeroed)?;
         // Move all lamports (principal + any remaining rent) to authority.
-        let remaining = stake_account.get_lamports();
-        stake_account.checked_sub_lamports(remaining)?;
+        released = stake_account.get_lamports();
+        stake_account.checked_sub_lamports(released)?;
     }
 
     {
         let mut authority_account = instruction_context.try_borrow_instruction_account(0)?;
-        authority_account.checked_add_lamports(principal)?;
+        authority_account.checked_add_lamports(released)?;
     }
 
+    remove_from_stake_summary(invoke_context, 2, &authority_pubkey, principal, "Unlock")?;
+
     ic_msg!(
         invoke_context,
         "Unlock: {} lamports returned to {}",
-        principal,
+        released,
         authority_pubkey
     );
     Ok(())
@@ -334,11 +664,12 @@ fn process_unlock(invoke_context: &InvokeContext) -> Result<(), InstructionError
 /// Accounts:
 ///   0. `[signer, writable]` — Authority (receives principal minus penalty).
 ///   1. `[writable]`         — Passive stake account.
+///   2. `[writable]`         — Authority's stake summary PDA.
 fn process_early_unlock(invoke_context: &InvokeContext) -> Result<(), InstructionError> {
     let transaction_context = &invoke_context.transaction_context;
     let instruction_context = transaction_context.get_current_instruction_context()?;
 
-    instruction_context.check_number_of_instruction_accounts(2)?;
+    instruction_context.check_number_of_instruction_accounts(3)?;
 
     if !instruction_context.is_instruction_account_signer(0)? {
         return Err(PassiveStakeError::MissingAuthoritySignature.into());
@@ -346,6 +677,7 @@ fn process_early_unlock(invoke_context: &InvokeContext) -> Result<(), Instructio
 
     let authority_pubkey = *instruction_context.get_key_of_instruction_account(0)?;
 
+    let principal;
     let return_amount;
     let penalty;
     {
@@ -382,6 +714,7 @@ fn process_early_unlock(invoke_context: &InvokeContext) -> Result<(), Instructio
             .amount
             .checked_sub(penalty)
             .ok_or(PassiveStakeError::ArithmeticOverflow)?;
+        principal = state.amount;
 
         // Zero out account data (close the position).
         let zeroed = vec![0u8; stake_account.get_data().len()];
@@ -400,6 +733,15 @@ fn process_early_unlock(invoke_context: &InvokeContext) -> Result<(), Instructio
         authority_account.checked_add_lamports(return_amount)?;
     }
 
+    // The burned penalty leaves the authority's total along with the rest.
+    remove_from_stake_summary(
+        invoke_context,
+        2,
+        &authority_pubkey,
+        principal,
+        "EarlyUnlock",
+    )?;
+
     ic_msg!(
         invoke_context,
         "EarlyUnlock: {} lamports returned, {} lamports burned as penalty for {}",
@@ -487,25 +829,750 @@ fn process_calculate_epoch_rewards(
         .try_into()
         .unwrap_or(u64::MAX);
 
-    state.unclaimed_rewards = state
-        .unclaimed_rewards
-        .checked_add(total_new_rewards_u64)
-        .ok_or(PassiveStakeError::ArithmeticOverflow)?;
+    // Auto-compounding needs the pool to fund the new principal.  Permanent
+    // positions at the cap keep accruing claimable rewards instead.
+    let compound = state.auto_compound
+        && total_new_rewards_u64 > 0
+        && instruction_context.get_number_of_instruction_accounts() > 1
+        && (!state.is_permanent
+            || state.amount.saturating_add(total_new_rewards_u64) <= MAX_STAKE_AMOUNT);
+    if compound {
+        let mut pool_account = instruction_context.try_borrow_instruction_account(1)?;
+        move_pool_rewards_into_stake(
+            invoke_context,
+            &mut pool_account,
+            &mut stake_account,
+            total_new_rewards_u64,
+            "CalculateEpochRewards",
+        )?;
+        state.amount = state
+            .amount
+            .checked_add(total_new_rewards_u64)
+            .ok_or(PassiveStakeError::ArithmeticOverflow)?;
+    } else {
+        state.unclaimed_rewards = state
+            .unclaimed_rewards
+            .checked_add(total_new_rewards_u64)
+            .ok_or(PassiveStakeError::ArithmeticOverflow)?;
+    }
 
     state.last_reward_epoch = current_epoch;
 
-    let mut buf = stake_account.get_data().to_vec();
-    state
-        .serialize_into(&mut buf)
-        .map_err(|_| PassiveStakeError::InvalidAccountData)?;
-    stake_account.set_data_from_slice(&buf)?;
+    write_passive_stake(&mut stake_account, &state)?;
 
     ic_msg!(
         invoke_context,
-        "CalculateEpochRewards: {} new reward lamports for {} epochs (tier {}d)",
+        "CalculateEpochRewards: {} new reward lamports for {} epochs (tier {}d, compounded: {})",
         total_new_rewards_u64,
         epochs_elapsed,
-        state.lock_days
+        state.lock_days,
+        compound
     );
     Ok(())
 }
+
+/// `TopUpStake { stake_account, additional_lamports }`
+///
+/// Accounts:
+///   0. `[signer, writable]` — Authority (source of lamports).
+///   1. `[writable]`         — Passive stake account.
+///   2. `[]`                 — System program.
+///   3. `[writable]`         — Authority's stake summary PDA.
+fn process_top_up_stake(
+    invoke_context: &mut InvokeContext,
+    stake_account_key: Pubkey,
+    additional_lamports: u64,
+) -> Result<(), InstructionError> {
+    if additional_lamports < MIN_TOP_UP_AMOUNT {
+        ic_msg!(
+            invoke_context,
+            "TopUpStake: {} lamports is below the minimum top-up of {}",
+            additional_lamports,
+            MIN_TOP_UP_AMOUNT
+        );
+        return Err(PassiveStakeError::AmountTooSmall.into());
+    }
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(4)?;
+
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(PassiveStakeError::MissingAuthoritySignature.into());
+    }
+
+    let authority_pubkey = *instruction_context.get_key_of_instruction_account(0)?;
+
+    if instruction_context.get_key_of_instruction_account(1)? != &stake_account_key {
+        ic_msg!(
+            invoke_context,
+            "TopUpStake: stake account does not match instruction data"
+        );
+        return Err(InstructionError::InvalidArgument);
+    }
+    if instruction_context.get_key_of_instruction_account(2)? != &system_program::id() {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+
+    let mut state;
+    {
+        let stake_account = instruction_context.try_borrow_instruction_account(1)?;
+        if stake_account.get_owner() != &id() {
+            return Err(PassiveStakeError::InvalidAccountOwner.into());
+        }
+        state = PassiveStakeAccount::deserialize(stake_account.get_data())
+            .map_err(|_| PassiveStakeError::InvalidAccountData)?;
+    }
+
+    if state.authority != authority_pubkey {
+        ic_msg!(invoke_context, "TopUpStake: authority mismatch");
+        return Err(PassiveStakeError::MissingAuthoritySignature.into());
+    }
+
+    // Rewards accrue per epoch on `amount`, so the amount may only change at
+    // a checkpoint: everything up to the current epoch has been calculated at
+    // the old amount and paid out.
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    if state.last_reward_epoch < clock.epoch || state.unclaimed_rewards > 0 {
+        ic_msg!(
+            invoke_context,
+            "TopUpStake: rewards calculated through epoch {} (current {}), {} unclaimed",
+            state.last_reward_epoch,
+            clock.epoch,
+            state.unclaimed_rewards
+        );
+        return Err(PassiveStakeError::UnclaimedRewardsPending.into());
+    }
+
+    state.amount = state
+        .amount
+        .checked_add(additional_lamports)
+        .ok_or(PassiveStakeError::ArithmeticOverflow)?;
+    // The larger position must not vote on proposals snapshotted before it.
+    state.lock_start_epoch = clock.epoch;
+
+    add_to_stake_summary(
+        invoke_context,
+        3,
+        &authority_pubkey,
+        additional_lamports,
+        "TopUpStake",
+    )?;
+
+    invoke_context.native_invoke(
+        system_instruction::transfer(&authority_pubkey, &stake_account_key, additional_lamports),
+        &[authority_pubkey],
+    )?;
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let mut stake_account = instruction_context.try_borrow_instruction_account(1)?;
+    write_passive_stake(&mut stake_account, &state)?;
+
+    ic_msg!(
+        invoke_context,
+        "TopUpStake: {} lamports added by {}, position now {} lamports",
+        additional_lamports,
+        authority_pubkey,
+        state.amount
+    );
+    Ok(())
+}
+
+/// `SplitStake { stake_account, split_amount, new_stake_account }`
+///
+/// Accounts:
+///   0. `[signer, writable]` — Authority (receives unclaimed rewards).
+///   1. `[writable]`         — Passive stake account being split.
+///   2. `[writable]`         — New passive stake account (pre-created, owned by this program).
+///   3. `[writable]`         — Rewards pool account (lamport source).
+fn process_split_stake(
+    invoke_context: &InvokeContext,
+    stake_account_key: Pubkey,
+    split_amount: u64,
+    new_stake_account_key: Pubkey,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(4)?;
+
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(PassiveStakeError::MissingAuthoritySignature.into());
+    }
+
+    let authority_pubkey = *instruction_context.get_key_of_instruction_account(0)?;
+
+    if instruction_context.get_key_of_instruction_account(1)? != &stake_account_key
+        || instruction_context.get_key_of_instruction_account(2)? != &new_stake_account_key
+        || stake_account_key == new_stake_account_key
+    {
+        ic_msg!(
+            invoke_context,
+            "SplitStake: stake accounts do not match instruction data"
+        );
+        return Err(InstructionError::InvalidArgument);
+    }
+    check_rewards_pool_not_aliased(invoke_context, 3, "SplitStake")?;
+
+    let mut state;
+    {
+        let stake_account = instruction_context.try_borrow_instruction_account(1)?;
+        if stake_account.get_owner() != &id() {
+            return Err(PassiveStakeError::InvalidAccountOwner.into());
+        }
+        state = PassiveStakeAccount::deserialize(stake_account.get_data())
+            .map_err(|_| PassiveStakeError::InvalidAccountData)?;
+    }
+
+    if state.authority != authority_pubkey {
+        ic_msg!(invoke_context, "SplitStake: authority mismatch");
+        return Err(PassiveStakeError::MissingAuthoritySignature.into());
+    }
+
+    let remaining_amount = state.amount.saturating_sub(split_amount);
+    if split_amount >= state.amount
+        || split_amount < MIN_STAKE_AMOUNT
+        || remaining_amount < MIN_STAKE_AMOUNT
+    {
+        ic_msg!(
+            invoke_context,
+            "SplitStake: both positions must hold at least {} lamports (split {} of {})",
+            MIN_STAKE_AMOUNT,
+            split_amount,
+            state.amount
+        );
+        return Err(PassiveStakeError::AmountTooSmall.into());
+    }
+
+    // --- Verify new stake account is owned by this program and uninitialised ---
+    {
+        let new_stake_account = instruction_context.try_borrow_instruction_account(2)?;
+        if new_stake_account.get_owner() != &id() {
+            ic_msg!(
+                invoke_context,
+                "SplitStake: new stake account not owned by passive-stake program"
+            );
+            return Err(PassiveStakeError::InvalidAccountOwner.into());
+        }
+        let data = new_stake_account.get_data();
+        if !data.is_empty() && data[0] == PASSIVE_STAKE_ACCOUNT_DISCRIMINATOR {
+            ic_msg!(
+                invoke_context,
+                "SplitStake: new stake account already initialised"
+            );
+            return Err(PassiveStakeError::AccountAlreadyInitialized.into());
+        }
+    }
+
+    // --- Pay out unclaimed rewards at the pre-split amount ---
+    let rewards_claimed = claim_checkpointed_rewards(invoke_context, &mut state, 3, "SplitStake")?;
+
+    // --- Move principal and write both positions ---
+    let new_state = PassiveStakeAccount {
+        amount: split_amount,
+        unclaimed_rewards: 0,
+        ..state
+    };
+    state.amount = remaining_amount;
+
+    {
+        let mut stake_account = instruction_context.try_borrow_instruction_account(1)?;
+        stake_account.checked_sub_lamports(split_amount)?;
+        write_passive_stake(&mut stake_account, &state)?;
+    }
+    {
+        let mut new_stake_account = instruction_context.try_borrow_instruction_account(2)?;
+        new_stake_account.checked_add_lamports(split_amount)?;
+        write_passive_stake(&mut new_stake_account, &new_state)?;
+    }
+
+    ic_msg!(
+        invoke_context,
+        "SplitStake: {} lamports split into {}, {} remain, {} reward lamports claimed by {}",
+        split_amount,
+        new_stake_account_key,
+        remaining_amount,
+        rewards_claimed,
+        authority_pubkey
+    );
+    Ok(())
+}
+
+/// `ExtendLock { stake_account, new_lock_days }`
+///
+/// Accounts:
+///   0. `[signer, writable]` — Authority (receives unclaimed rewards).
+///   1. `[writable]`         — Passive stake account.
+///   2. `[writable]`         — Rewards pool account (lamport source).
+fn process_extend_lock(
+    invoke_context: &InvokeContext,
+    stake_account_key: Pubkey,
+    new_lock_days: u64,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    if !is_valid_tier(new_lock_days) {
+        ic_msg!(
+            invoke_context,
+            "ExtendLock: invalid lock tier {new_lock_days}"
+        );
+        return Err(PassiveStakeError::InvalidLockTier.into());
+    }
+
+    instruction_context.check_number_of_instruction_accounts(3)?;
+
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(PassiveStakeError::MissingAuthoritySignature.into());
+    }
+
+    let authority_pubkey = *instruction_context.get_key_of_instruction_account(0)?;
+
+    if instruction_context.get_key_of_instruction_account(1)? != &stake_account_key {
+        ic_msg!(
+            invoke_context,
+            "ExtendLock: stake account does not match instruction data"
+        );
+        return Err(InstructionError::InvalidArgument);
+    }
+    check_rewards_pool_not_aliased(invoke_context, 2, "ExtendLock")?;
+
+    let mut state;
+    {
+        let stake_account = instruction_context.try_borrow_instruction_account(1)?;
+        if stake_account.get_owner() != &id() {
+            return Err(PassiveStakeError::InvalidAccountOwner.into());
+        }
+        state = PassiveStakeAccount::deserialize(stake_account.get_data())
+            .map_err(|_| PassiveStakeError::InvalidAccountData)?;
+    }
+
+    if state.authority != authority_pubkey {
+        ic_msg!(invoke_context, "ExtendLock: authority mismatch");
+        return Err(PassiveStakeError::MissingAuthoritySignature.into());
+    }
+
+    if state.is_permanent {
+        ic_msg!(
+            invoke_context,
+            "ExtendLock: permanent locks cannot be extended"
+        );
+        return Err(PassiveStakeError::InvalidLockTier.into());
+    }
+
+    // Tier values are ordered by lock length, permanent (u64::MAX) last.
+    if new_lock_days <= state.lock_days {
+        ic_msg!(
+            invoke_context,
+            "ExtendLock: new tier {}d must be longer than current tier {}d",
+            new_lock_days,
+            state.lock_days
+        );
+        return Err(PassiveStakeError::InvalidLockTier.into());
+    }
+
+    // --- Settle rewards at the old tier rate ---
+    let rewards_claimed = claim_checkpointed_rewards(invoke_context, &mut state, 2, "ExtendLock")?;
+
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    let (lock_end, is_permanent) = if new_lock_days == PERMANENT_LOCK_DAYS {
+        (0i64, true)
+    } else {
+        let duration_secs = (new_lock_days as i64)
+            .checked_mul(SECONDS_PER_DAY)
+            .ok_or(PassiveStakeError::ArithmeticOverflow)?;
+        let end = clock
+            .unix_timestamp
+            .checked_add(duration_secs)
+            .ok_or(PassiveStakeError::ArithmeticOverflow)?;
+        // Never shorten an existing lock.
+        (end.max(state.lock_end), false)
+    };
+
+    let old_lock_days = state.lock_days;
+    state.lock_days = new_lock_days;
+    state.lock_end = lock_end;
+    state.is_permanent = is_permanent;
+    state.vote_weight_bps =
+        vote_weight_bps_for_tier(new_lock_days).ok_or(PassiveStakeError::InvalidLockTier)?;
+    // The heavier vote must not count on proposals snapshotted before it.
+    state.lock_start_epoch = clock.epoch;
+
+    let mut stake_account = instruction_context.try_borrow_instruction_account(1)?;
+    write_passive_stake(&mut stake_account, &state)?;
+
+    ic_msg!(
+        invoke_context,
+        "ExtendLock: tier {}d -> {}d, lock ends at {}, {} reward lamports claimed by {}",
+        old_lock_days,
+        new_lock_days,
+        lock_end,
+        rewards_claimed,
+        authority_pubkey
+    );
+    Ok(())
+}
+
+/// `CompoundRewards { stake_account, auto_compound }`
+///
+/// Accounts:
+///   0. `[signer]`   — Authority.
+///   1. `[writable]` — Passive stake account.
+///   2. `[writable]` — Rewards pool account (lamport source).
+fn process_compound_rewards(
+    invoke_context: &InvokeContext,
+    stake_account_key: Pubkey,
+    auto_compound: Option<bool>,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(3)?;
+
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(PassiveStakeError::MissingAuthoritySignature.into());
+    }
+
+    let authority_pubkey = *instruction_context.get_key_of_instruction_account(0)?;
+
+    if instruction_context.get_key_of_instruction_account(1)? != &stake_account_key {
+        ic_msg!(
+            invoke_context,
+            "CompoundRewards: stake account does not match instruction data"
+        );
+        return Err(InstructionError::InvalidArgument);
+    }
+
+    let mut stake_account = instruction_context.try_borrow_instruction_account(1)?;
+    if stake_account.get_owner() != &id() {
+        return Err(PassiveStakeError::InvalidAccountOwner.into());
+    }
+    let mut state = PassiveStakeAccount::deserialize(stake_account.get_data())
+        .map_err(|_| PassiveStakeError::InvalidAccountData)?;
+
+    if state.authority != authority_pubkey {
+        ic_msg!(invoke_context, "CompoundRewards: authority mismatch");
+        return Err(PassiveStakeError::MissingAuthoritySignature.into());
+    }
+
+    if let Some(auto_compound) = auto_compound {
+        state.auto_compound = auto_compound;
+    }
+
+    // Same amount as `ClaimRewards` would pay out.
+    let rewards = state.unclaimed_rewards;
+    if rewards == 0 && auto_compound.is_none() {
+        return Err(PassiveStakeError::NoRewardsToClaim.into());
+    }
+
+    if rewards > 0 {
+        // Rewards accrue per epoch on `amount`, so it may only grow once
+        // everything up to the current epoch has been calculated.
+        let clock = invoke_context.get_sysvar_cache().get_clock()?;
+        if state.last_reward_epoch < clock.epoch {
+            ic_msg!(
+                invoke_context,
+                "CompoundRewards: rewards calculated through epoch {} (current {})",
+                state.last_reward_epoch,
+                clock.epoch
+            );
+            return Err(PassiveStakeError::UnclaimedRewardsPending.into());
+        }
+
+        let new_amount = state
+            .amount
+            .checked_add(rewards)
+            .ok_or(PassiveStakeError::ArithmeticOverflow)?;
+        if state.is_permanent && new_amount > MAX_STAKE_AMOUNT {
+            ic_msg!(
+                invoke_context,
+                "CompoundRewards: {} lamports exceeds the permanent-lock maximum of {}",
+                new_amount,
+                MAX_STAKE_AMOUNT
+            );
+            return Err(PassiveStakeError::AmountTooLarge.into());
+        }
+
+        let mut pool_account = instruction_context.try_borrow_instruction_account(2)?;
+        move_pool_rewards_into_stake(
+            invoke_context,
+            &mut pool_account,
+            &mut stake_account,
+            rewards,
+            "CompoundRewards",
+        )?;
+
+        // Reinvested rewards are not new capital, so governance eligibility
+        // (`lock_start_epoch`) is left alone.
+        state.amount = new_amount;
+        state.unclaimed_rewards = 0;
+    }
+
+    write_passive_stake(&mut stake_account, &state)?;
+
+    ic_msg!(
+        invoke_context,
+        "CompoundRewards: {} reward lamports reinvested for {}, position now {} lamports, auto-compound {}",
+        rewards,
+        authority_pubkey,
+        state.amount,
+        state.auto_compound
+    );
+    Ok(())
+}
+
+/// `ClaimMultipleRewards { stake_accounts }`
+///
+/// Accounts:
+///   0. `[signer, writable]` — Authority (reward recipient).
+///   1. `[writable]`         — Rewards pool account (lamport source).
+///   2.. `[writable]`        — Passive stake accounts, in `stake_accounts` order.
+fn process_claim_multiple_rewards(
+    invoke_context: &mut InvokeContext,
+    stake_accounts: Vec<Pubkey>,
+) -> Result<(), InstructionError> {
+    if stake_accounts.is_empty() {
+        return Err(InstructionError::InvalidArgument);
+    }
+    if stake_accounts.len() > MAX_BATCH_CLAIM_ACCOUNTS {
+        ic_msg!(
+            invoke_context,
+            "ClaimMultipleRewards: {} accounts exceeds the limit of {}",
+            stake_accounts.len(),
+            MAX_BATCH_CLAIM_ACCOUNTS
+        );
+        return Err(PassiveStakeError::TooManyAccounts.into());
+    }
+    for (i, key) in stake_accounts.iter().enumerate() {
+        if stake_accounts[..i].contains(key) {
+            ic_msg!(invoke_context, "ClaimMultipleRewards: {} listed twice", key);
+            return Err(InstructionError::InvalidArgument);
+        }
+    }
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(2 + stake_accounts.len() as u16)?;
+
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(PassiveStakeError::MissingAuthoritySignature.into());
+    }
+
+    let authority_pubkey = *instruction_context.get_key_of_instruction_account(0)?;
+
+    // --- Validate every position before touching any of them ---
+    let mut states = Vec::with_capacity(stake_accounts.len());
+    for (i, key) in stake_accounts.iter().enumerate() {
+        let index = 2 + i as u16;
+        if instruction_context.get_key_of_instruction_account(index)? != key {
+            ic_msg!(
+                invoke_context,
+                "ClaimMultipleRewards: account {} does not match instruction data",
+                index
+            );
+            return Err(InstructionError::InvalidArgument);
+        }
+
+        let stake_account = instruction_context.try_borrow_instruction_account(index)?;
+        if stake_account.get_owner() != &id() {
+            return Err(PassiveStakeError::InvalidAccountOwner.into());
+        }
+        let state = PassiveStakeAccount::deserialize(stake_account.get_data())
+            .map_err(|_| PassiveStakeError::InvalidAccountData)?;
+
+        if state.authority != authority_pubkey {
+            ic_msg!(
+                invoke_context,
+                "ClaimMultipleRewards: {} belongs to {}, not {}",
+                key,
+                state.authority,
+                authority_pubkey
+            );
+            return Err(PassiveStakeError::AuthorityMismatch.into());
+        }
+        states.push(state);
+    }
+
+    let per_account: Vec<u64> = states.iter().map(|state| state.unclaimed_rewards).collect();
+    let total_lamports = per_account
+        .iter()
+        .try_fold(0u64, |total, rewards| total.checked_add(*rewards))
+        .ok_or(PassiveStakeError::ArithmeticOverflow)?;
+    if total_lamports == 0 {
+        return Err(PassiveStakeError::NoRewardsToClaim.into());
+    }
+
+    // --- Zero every position's rewards ---
+    for (i, mut state) in states.into_iter().enumerate() {
+        let mut stake_account = instruction_context.try_borrow_instruction_account(2 + i as u16)?;
+        state.unclaimed_rewards = 0;
+        write_passive_stake(&mut stake_account, &state)?;
+    }
+
+    // --- One transfer from the pool to the authority ---
+    {
+        let mut pool_account = instruction_context.try_borrow_instruction_account(1)?;
+        if pool_account.get_data().first() == Some(&PASSIVE_STAKE_ACCOUNT_DISCRIMINATOR) {
+            ic_msg!(
+                invoke_context,
+                "ClaimMultipleRewards: rewards pool is a passive stake account"
+            );
+            return Err(PassiveStakeError::InvalidAccountData.into());
+        }
+        if pool_account.get_lamports() < total_lamports {
+            ic_msg!(
+                invoke_context,
+                "ClaimMultipleRewards: reward pool has insufficient lamports"
+            );
+            return Err(PassiveStakeError::InsufficientLamports.into());
+        }
+        pool_account.checked_sub_lamports(total_lamports)?;
+    }
+    {
+        let mut authority_account = instruction_context.try_borrow_instruction_account(0)?;
+        authority_account.checked_add_lamports(total_lamports)?;
+    }
+
+    ic_msg!(
+        invoke_context,
+        "ClaimMultipleRewards: {} lamports from {} positions claimed by {}",
+        total_lamports,
+        per_account.len(),
+        authority_pubkey
+    );
+
+    let summary = ClaimSummary {
+        total_lamports,
+        per_account,
+    };
+    let return_data = bincode::serialize(&summary).map_err(|_| InstructionError::GenericError)?;
+    invoke_context
+        .transaction_context
+        .set_return_data(id(), return_data)
+}
+
+/// `TransferStakeAuthority { stake_account, new_authority }`
+///
+/// Accounts:
+///   0. `[signer]`           — Current authority.
+///   1. `[signer, writable]` — New authority.
+///   2. `[writable]`         — Passive stake account.
+///   3. `[writable]`         — Current authority's stake summary PDA.
+///   4. `[writable]`         — New authority's stake summary PDA.
+///   5. `[]`                 — System program.
+fn process_transfer_stake_authority(
+    invoke_context: &mut InvokeContext,
+    stake_account_key: Pubkey,
+    new_authority: Pubkey,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(6)?;
+
+    // The new key must sign so a position can't be sent to an address
+    // nobody controls.
+    if !instruction_context.is_instruction_account_signer(0)?
+        || !instruction_context.is_instruction_account_signer(1)?
+    {
+        return Err(PassiveStakeError::MissingAuthoritySignature.into());
+    }
+
+    let authority_pubkey = *instruction_context.get_key_of_instruction_account(0)?;
+
+    if instruction_context.get_key_of_instruction_account(1)? != &new_authority {
+        ic_msg!(
+            invoke_context,
+            "TransferStakeAuthority: new authority does not match instruction data"
+        );
+        return Err(InstructionError::InvalidArgument);
+    }
+    if instruction_context.get_key_of_instruction_account(2)? != &stake_account_key {
+        ic_msg!(
+            invoke_context,
+            "TransferStakeAuthority: stake account does not match instruction data"
+        );
+        return Err(InstructionError::InvalidArgument);
+    }
+    if new_authority == authority_pubkey {
+        return Err(InstructionError::InvalidArgument);
+    }
+    if instruction_context.get_key_of_instruction_account(5)? != &system_program::id() {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+
+    let mut state;
+    {
+        let stake_account = instruction_context.try_borrow_instruction_account(2)?;
+        if stake_account.get_owner() != &id() {
+            return Err(PassiveStakeError::InvalidAccountOwner.into());
+        }
+        state = PassiveStakeAccount::deserialize(stake_account.get_data())
+            .map_err(|_| PassiveStakeError::InvalidAccountData)?;
+    }
+
+    if state.authority != authority_pubkey {
+        ic_msg!(invoke_context, "TransferStakeAuthority: authority mismatch");
+        return Err(PassiveStakeError::MissingAuthoritySignature.into());
+    }
+
+    // Only an authority with nothing staked can take over a position.
+    {
+        let summary_account = instruction_context.try_borrow_instruction_account(4)?;
+        let summary = read_stake_summary(
+            invoke_context,
+            &summary_account,
+            &new_authority,
+            "TransferStakeAuthority",
+        )?;
+        if summary.total_staked > 0 {
+            ic_msg!(
+                invoke_context,
+                "TransferStakeAuthority: {} already has {} lamports staked",
+                new_authority,
+                summary.total_staked
+            );
+            return Err(PassiveStakeError::NewAuthorityHasStake.into());
+        }
+    }
+
+    // The position's principal counts against the new authority's cap.
+    add_to_stake_summary(
+        invoke_context,
+        4,
+        &new_authority,
+        state.amount,
+        "TransferStakeAuthority",
+    )?;
+    remove_from_stake_summary(
+        invoke_context,
+        3,
+        &authority_pubkey,
+        state.amount,
+        "TransferStakeAuthority",
+    )?;
+
+    state.authority = new_authority;
+    // The old authority may already have voted with this position, so it
+    // must not count again on proposals snapshotted before the transfer.
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    state.lock_start_epoch = clock.epoch;
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let mut stake_account = instruction_context.try_borrow_instruction_account(2)?;
+    write_passive_stake(&mut stake_account, &state)?;
+
+    ic_msg!(
+        invoke_context,
+        "TransferStakeAuthority: {} authority {} -> {}",
+        stake_account_key,
+        authority_pubkey,
+        new_authority
+    );
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 //! Account state types for the Passive Stake program.
 
 use {
+    crate::constants::STAKE_SUMMARY_SEED,
     borsh::{BorshDeserialize, BorshSerialize},
     solana_pubkey::Pubkey,
 };
@@ -9,6 +10,9 @@ use {
 /// to distinguish it from uninitialized or foreign account data.
 pub const PASSIVE_STAKE_ACCOUNT_DISCRIMINATOR: u8 = 1;
 
+/// Discriminator byte written at the start of every stake summary account.
+pub const STAKE_SUMMARY_ACCOUNT_DISCRIMINATOR: u8 = 2;
+
 /// On-chain state for a single passive stake position.
 ///
 /// Serialised with Borsh; the first byte of account data is the discriminator.
@@ -45,6 +49,16 @@ pub struct PassiveStakeAccount {
     /// Governance voting-weight multiplier in basis points.
     ///   10_000 bps = 1.00×
     pub vote_weight_bps: u16,
+
+    /// Epoch in which the lock was created, or last gained voting power.
+    ///
+    /// Governance only counts locks created no later than a proposal's
+    /// voting-power snapshot epoch.
+    pub lock_start_epoch: u64,
+
+    /// Whether epoch reward calculation reinvests new rewards into `amount`
+    /// instead of accumulating them in `unclaimed_rewards`.
+    pub auto_compound: bool,
 }
 
 impl PassiveStakeAccount {
@@ -61,8 +75,18 @@ impl PassiveStakeAccount {
     ///   + last_reward_epoch (8)
     ///   + is_permanent (1)
     ///   + vote_weight_bps (2)
-    ///   = 84 bytes
-    pub const SERIALIZED_SIZE: usize = 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 2;
+    ///   + lock_start_epoch (8)
+    ///   + auto_compound (1)
+    ///   = 93 bytes
+    pub const SERIALIZED_SIZE: usize = 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 2 + 8 + 1;
+
+    /// Size of the original layout, which ended at `vote_weight_bps`
+    /// (84 bytes).
+    ///
+    /// Positions created with it are read with `lock_start_epoch` 0 (the
+    /// lock predates every proposal) and `auto_compound` off, and are grown
+    /// to [`Self::SERIALIZED_SIZE`] the next time the program writes them.
+    pub const LEGACY_SERIALIZED_SIZE: usize = 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 2;
 
     /// Deserialise from raw account data (expects leading discriminator byte).
     pub fn deserialize(data: &[u8]) -> Result<Self, std::io::Error> {
@@ -73,6 +97,11 @@ impl PassiveStakeAccount {
             ));
         }
         let mut cursor = &data[1..];
+        if data.len() < Self::SERIALIZED_SIZE {
+            let legacy: LegacyPassiveStakeAccount =
+                BorshDeserialize::deserialize_reader(&mut cursor)?;
+            return Ok(legacy.into());
+        }
         BorshDeserialize::deserialize_reader(&mut cursor)
     }
 
@@ -89,3 +118,86 @@ impl PassiveStakeAccount {
         BorshSerialize::serialize(self, &mut cursor)
     }
 }
+
+/// The fields of [`PassiveStakeAccount::LEGACY_SERIALIZED_SIZE`] accounts.
+#[derive(BorshDeserialize)]
+struct LegacyPassiveStakeAccount {
+    authority: Pubkey,
+    amount: u64,
+    lock_days: u64,
+    lock_start: i64,
+    lock_end: i64,
+    unclaimed_rewards: u64,
+    last_reward_epoch: u64,
+    is_permanent: bool,
+    vote_weight_bps: u16,
+}
+
+impl From<LegacyPassiveStakeAccount> for PassiveStakeAccount {
+    fn from(legacy: LegacyPassiveStakeAccount) -> Self {
+        Self {
+            authority: legacy.authority,
+            amount: legacy.amount,
+            lock_days: legacy.lock_days,
+            lock_start: legacy.lock_start,
+            lock_end: legacy.lock_end,
+            unclaimed_rewards: legacy.unclaimed_rewards,
+            last_reward_epoch: legacy.last_reward_epoch,
+            is_permanent: legacy.is_permanent,
+            vote_weight_bps: legacy.vote_weight_bps,
+            lock_start_epoch: 0,
+            auto_compound: false,
+        }
+    }
+}
+
+/// Running total of the principal an authority has deposited across all of
+/// its positions, checked against `MAX_STAKE_PER_ADDRESS`.
+///
+/// Derived as a PDA: `[STAKE_SUMMARY_SEED, authority]`.  Created on the
+/// authority's first deposit and updated by `InitializePassiveStake`,
+/// `TopUpStake`, `Unlock`, `EarlyUnlock`, and `TransferStakeAuthority`.
+/// Rewards compounded into a position are not deposits and are not counted.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct StakeSummaryAccount {
+    /// Authority whose positions this summary covers.
+    pub authority: Pubkey,
+
+    /// Lamports deposited into the authority's open positions.
+    pub total_staked: u64,
+}
+
+impl StakeSummaryAccount {
+    /// Serialised size: discriminator (1) + authority (32) + total_staked (8).
+    pub const SERIALIZED_SIZE: usize = 1 + 32 + 8;
+
+    /// Deserialise from raw account data (expects leading discriminator byte).
+    pub fn deserialize(data: &[u8]) -> Result<Self, std::io::Error> {
+        if data.is_empty() || data[0] != STAKE_SUMMARY_ACCOUNT_DISCRIMINATOR {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "missing or invalid stake-summary discriminator",
+            ));
+        }
+        let mut cursor = &data[1..];
+        BorshDeserialize::deserialize_reader(&mut cursor)
+    }
+
+    /// Serialise into raw account data (prepends discriminator byte).
+    pub fn serialize_into(&self, data: &mut [u8]) -> Result<(), std::io::Error> {
+        if data.len() < Self::SERIALIZED_SIZE {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "account data buffer too small",
+            ));
+        }
+        data[0] = STAKE_SUMMARY_ACCOUNT_DISCRIMINATOR;
+        let mut cursor = &mut data[1..];
+        BorshSerialize::serialize(self, &mut cursor)
+    }
+}
+
+/// Address of the `StakeSummaryAccount` PDA for `authority`.
+pub fn find_stake_summary_address(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_SUMMARY_SEED, authority.as_ref()], &crate::id())
+}
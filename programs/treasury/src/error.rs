@@ -42,6 +42,45 @@ pub enum TreasuryError {
 
     #[error("Arithmetic overflow")]
     ArithmeticOverflow,
+
+    #[error("Multisig needs 1 to 11 unique signers and a threshold of 1 to their count")]
+    InvalidMultisig,
+
+    #[error("Fewer multisig signers approved than the threshold requires")]
+    InsufficientSignatures,
+
+    #[error("Signer is not a member of the multisig")]
+    NotMultisigSigner,
+
+    #[error("Signer has already approved this disbursal")]
+    DisbursalAlreadySigned,
+
+    #[error("Disbursal has already been executed")]
+    DisbursalAlreadyExecuted,
+
+    #[error("Pending disbursal does not match the instruction or multisig")]
+    DisbursalMismatch,
+
+    #[error("Recurring disbursement must end after it starts and after the current epoch")]
+    InvalidSchedule,
+
+    #[error("Recurring disbursement has already been paid through the current epoch")]
+    RecurringNotDue,
+
+    #[error("Recurring disbursement has ended or was cancelled")]
+    RecurringEnded,
+
+    #[error("Recurring disbursement does not match the instruction or treasury")]
+    RecurringMismatch,
+
+    #[error("Disbursement exceeds the treasury's allowance for this epoch")]
+    EpochAllowanceExceeded,
+
+    #[error("Epoch allowance must be between 1 and 10000 basis points")]
+    InvalidEpochAllowance,
+
+    #[error("Governance is not active")]
+    GovernanceNotActive,
 }
 
 // Note: InstructionError conversion is provided by the blanket
@@ -34,14 +34,19 @@ pub enum TreasuryInstruction {
 
     /// Disburse lamports from the treasury to a recipient.
     ///
-    /// Requires the current authority's signature.
+    /// Requires the current authority's signature.  Counts against the
+    /// epoch allowance (`TreasuryConfig::epoch_allowance_bps`).
     ///
     /// # Accounts expected
     ///
     /// 0. `[signer]`   — Authority (must match `TreasuryConfig.authority`).
     /// 1. `[writable]`  — Treasury config account.
-    /// 2. `[writable]`  — Treasury token account (source of lamports).
+    /// 2. `[writable]`  — Treasury token account (must match
+    ///                    `TreasuryConfig.treasury_account`).
     /// 3. `[writable]`  — Recipient account.
+    /// 4.. `[signer]`   — Multisig signers, when the authority is a
+    ///                    `MultisigAuthority`; `threshold` distinct members
+    ///                    must sign, whether or not account 0 does.
     ///
     /// # Data
     ///
@@ -62,6 +67,9 @@ pub enum TreasuryInstruction {
     ///
     /// 0. `[signer]`   — Current authority.
     /// 1. `[writable]`  — Treasury config account.
+    /// 2.. `[signer]`   — Multisig signers, when the authority is a
+    ///                    `MultisigAuthority`; `threshold` distinct members
+    ///                    must sign, whether or not account 0 does.
     ///
     /// # Data
     ///
@@ -79,5 +87,141 @@ pub enum TreasuryInstruction {
     ///
     /// 0. `[signer]`   — Current authority.
     /// 1. `[writable]`  — Treasury config account.
+    /// 2.. `[signer]`   — Multisig signers, when the authority is a
+    ///                    `MultisigAuthority`; `threshold` distinct members
+    ///                    must sign, whether or not account 0 does.
     ActivateGovernance,
+
+    /// Create an M-of-N `MultisigAuthority`.
+    ///
+    /// The multisig has no power until `UpdateAuthority` makes it the
+    /// treasury authority.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer]`   — Initialiser.
+    /// 1. `[writable]`  — Multisig account (pre-allocated, owned by this
+    ///                    program, uninitialised).
+    ///
+    /// # Data
+    ///
+    /// * `signers`   — Unique member keys (at most `MAX_MULTISIG_SIGNERS`).
+    /// * `threshold` — Approvals required, between 1 and `signers.len()`.
+    InitializeMultisig {
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    },
+
+    /// Propose a disbursal on behalf of a multisig authority, counting as the
+    /// proposer's approval.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer]`   — Multisig signer.
+    /// 1. `[]`          — Multisig account.
+    /// 2. `[writable]`  — Pending disbursal account (pre-allocated, owned by
+    ///                    this program, uninitialised).
+    ProposeDisbursal {
+        disbursal_id: u64,
+        amount: u64,
+        recipient: Pubkey,
+        memo: String,
+    },
+
+    /// Approve a pending disbursal.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer]`   — Multisig signer.
+    /// 1. `[]`          — Multisig account.
+    /// 2. `[writable]`  — Pending disbursal account.
+    SignDisbursal {
+        disbursal_id: u64,
+    },
+
+    /// Execute a pending disbursal approved by `threshold` signers of the
+    /// treasury's multisig authority.  Anyone can call this.  Counts against
+    /// the epoch allowance like `Disburse`.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer]`   — Executor.
+    /// 1. `[writable]`  — Treasury config account.
+    /// 2. `[writable]`  — Treasury token account (must match
+    ///                    `TreasuryConfig.treasury_account`).
+    /// 3. `[writable]`  — Recipient account.
+    /// 4. `[]`          — Multisig account (must be the treasury authority).
+    /// 5. `[writable]`  — Pending disbursal account.
+    ExecuteDisbursal {
+        disbursal_id: u64,
+    },
+
+    /// Set up a grant of `amount_per_epoch` lamports for every epoch after
+    /// `start_epoch` through `end_epoch`.
+    ///
+    /// The whole grant moves from the treasury into the recurring
+    /// disbursement account, which pays it out, and counts against the
+    /// epoch allowance of the epoch it is created in.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer]`   — Authority.
+    /// 1. `[writable]`  — Treasury config account.
+    /// 2. `[writable]`  — Treasury token account (source of lamports).
+    /// 3. `[writable]`  — Recurring disbursement account (pre-allocated,
+    ///                    owned by this program, uninitialised).
+    /// 4.. `[signer]`   — Multisig signers, for a multisig authority.
+    CreateRecurringDisbursement {
+        disbursement_id: u64,
+        recipient: Pubkey,
+        amount_per_epoch: u64,
+        start_epoch: u64,
+        end_epoch: u64,
+    },
+
+    /// Pay the recipient of a recurring disbursement for every epoch since
+    /// it was last paid.  Anyone can call this until `end_epoch`; lamports
+    /// not claimed by then go back to the treasury on `CancelRecurring`.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer]`   — Caller (anyone).
+    /// 1. `[writable]`  — Treasury config account.
+    /// 2. `[writable]`  — Recurring disbursement account.
+    /// 3. `[writable]`  — Recipient account.
+    ExecuteRecurring {
+        disbursement_id: u64,
+    },
+
+    /// Stop a recurring disbursement.  Epochs that accrued through the
+    /// current epoch but were not yet executed are paid to the recipient
+    /// first (unless the grant is past `end_epoch`, when they have expired);
+    /// the rest of the escrow is returned to the treasury.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer]`   — Authority.
+    /// 1. `[writable]`  — Treasury config account.
+    /// 2. `[writable]`  — Treasury token account.
+    /// 3. `[writable]`  — Recurring disbursement account.
+    /// 4. `[writable]`  — Recipient account.
+    /// 5.. `[signer]`   — Multisig signers, for a multisig authority.
+    CancelRecurring {
+        disbursement_id: u64,
+    },
+
+    /// Set the share of the treasury balance, in basis points, that may be
+    /// disbursed per epoch.
+    ///
+    /// Only allowed once governance is active, so in practice only a
+    /// governance proposal can change it.
+    ///
+    /// # Accounts expected
+    ///
+    /// 0. `[signer]`   — Authority (governance).
+    /// 1. `[writable]`  — Treasury config account.
+    /// 2.. `[signer]`   — Multisig signers, for a multisig authority.
+    SetEpochAllowance {
+        epoch_allowance_bps: u16,
+    },
 }
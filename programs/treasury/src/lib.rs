@@ -15,6 +15,28 @@
 //! The program itself is authority-agnostic — it only checks that the
 //! `authority` pubkey stored in `TreasuryConfig` has signed the transaction.
 //!
+//! The authority can also be an on-chain `MultisigAuthority` account, which
+//! approves when `threshold` of its signers sign.  Multisig signers can
+//! either all sign one `Disburse`, or collect approvals over several
+//! transactions: `ProposeDisbursal` and `SignDisbursal` record them in a
+//! `PendingDisbursal` account, and `ExecuteDisbursal` pays out once enough
+//! have signed.
+//!
+//! ## Epoch allowance
+//!
+//! At most `epoch_allowance_bps` of the treasury balance (10% by default) can
+//! leave the treasury per epoch, through disbursements or recurring grants.
+//! The allowance can only be changed with `SetEpochAllowance` once governance
+//! is active, so even the launch multisig cannot empty the treasury at once.
+//!
+//! ## Recurring disbursements
+//!
+//! Grants that pay out every epoch live in `RecurringDisbursement` accounts.
+//! `CreateRecurringDisbursement` escrows the whole grant from the treasury,
+//! anyone can call `ExecuteRecurring` to pay the recipient for the epochs
+//! since the last payment, and `CancelRecurring` pays what has accrued and
+//! returns the rest.
+//!
 //! ## Instructions
 //!
 //! | Instruction          | Description                                      |
@@ -23,6 +45,14 @@
 //! | Disburse             | Send lamports from treasury to a recipient        |
 //! | UpdateAuthority      | Transfer control to a new authority key            |
 //! | ActivateGovernance   | Flip the governance_active flag                   |
+//! | InitializeMultisig   | Create an M-of-N multisig authority               |
+//! | ProposeDisbursal     | Propose a multisig disbursal                      |
+//! | SignDisbursal        | Approve a pending multisig disbursal              |
+//! | ExecuteDisbursal     | Pay out a sufficiently approved disbursal         |
+//! | CreateRecurringDisbursement | Escrow a grant paid out every epoch       |
+//! | ExecuteRecurring     | Pay a recurring grant up to the current epoch     |
+//! | CancelRecurring      | Stop a recurring grant and refund the rest        |
+//! | SetEpochAllowance    | Change the per-epoch disbursement allowance       |
 
 #![cfg(feature = "agave-unstable-api")]
 #![allow(clippy::arithmetic_side_effects)]
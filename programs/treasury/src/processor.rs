@@ -4,7 +4,10 @@ use {
     crate::{
         error::TreasuryError,
         instruction::TreasuryInstruction,
-        state::{TreasuryConfig, TREASURY_CONFIG_DISCRIMINATOR},
+        state::{
+            MultisigAuthority, PendingDisbursal, RecurringDisbursement, TreasuryConfig,
+            BPS_DENOMINATOR, DEFAULT_EPOCH_ALLOWANCE_BPS,
+        },
     },
     log::*,
     solana_bincode::limited_deserialize,
@@ -57,9 +60,363 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
             process_update_authority(invoke_context, new_authority)
         }
         TreasuryInstruction::ActivateGovernance => process_activate_governance(invoke_context),
+        TreasuryInstruction::InitializeMultisig { signers, threshold } => {
+            process_initialize_multisig(invoke_context, signers, threshold)
+        }
+        TreasuryInstruction::ProposeDisbursal {
+            disbursal_id,
+            amount,
+            recipient,
+            memo,
+        } => process_propose_disbursal(invoke_context, disbursal_id, amount, recipient, memo),
+        TreasuryInstruction::SignDisbursal { disbursal_id } => {
+            process_sign_disbursal(invoke_context, disbursal_id)
+        }
+        TreasuryInstruction::ExecuteDisbursal { disbursal_id } => {
+            process_execute_disbursal(invoke_context, disbursal_id)
+        }
+        TreasuryInstruction::CreateRecurringDisbursement {
+            disbursement_id,
+            recipient,
+            amount_per_epoch,
+            start_epoch,
+            end_epoch,
+        } => process_create_recurring_disbursement(
+            invoke_context,
+            disbursement_id,
+            recipient,
+            amount_per_epoch,
+            start_epoch,
+            end_epoch,
+        ),
+        TreasuryInstruction::ExecuteRecurring { disbursement_id } => {
+            process_execute_recurring(invoke_context, disbursement_id)
+        }
+        TreasuryInstruction::CancelRecurring { disbursement_id } => {
+            process_cancel_recurring(invoke_context, disbursement_id)
+        }
+        TreasuryInstruction::SetEpochAllowance {
+            epoch_allowance_bps,
+        } => process_set_epoch_allowance(invoke_context, epoch_allowance_bps),
     }
 });
 
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Load the treasury config at `account_index`, checking its owner.
+fn load_treasury_config(
+    invoke_context: &InvokeContext,
+    account_index: u16,
+) -> Result<TreasuryConfig, InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let account = instruction_context.try_borrow_instruction_account(account_index)?;
+    if account.get_owner() != &id() {
+        return Err(TreasuryError::InvalidAccountOwner.into());
+    }
+    TreasuryConfig::deserialize(account.get_data())
+        .map_err(|_| TreasuryError::NotInitialized.into())
+}
+
+/// Load the multisig at `account_index`, checking its owner.
+fn load_multisig(
+    invoke_context: &InvokeContext,
+    account_index: u16,
+) -> Result<MultisigAuthority, InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let account = instruction_context.try_borrow_instruction_account(account_index)?;
+    if account.get_owner() != &id() {
+        return Err(TreasuryError::InvalidAccountOwner.into());
+    }
+    MultisigAuthority::deserialize(account.get_data())
+        .map_err(|_| TreasuryError::NotInitialized.into())
+}
+
+/// Load the pending disbursal at `account_index`, checking its owner.
+fn load_pending_disbursal(
+    invoke_context: &InvokeContext,
+    account_index: u16,
+) -> Result<PendingDisbursal, InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let account = instruction_context.try_borrow_instruction_account(account_index)?;
+    if account.get_owner() != &id() {
+        return Err(TreasuryError::InvalidAccountOwner.into());
+    }
+    PendingDisbursal::deserialize(account.get_data())
+        .map_err(|_| TreasuryError::InvalidAccountData.into())
+}
+
+/// Load the recurring disbursement at `account_index`, checking its owner.
+fn load_recurring_disbursement(
+    invoke_context: &InvokeContext,
+    account_index: u16,
+) -> Result<RecurringDisbursement, InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let account = instruction_context.try_borrow_instruction_account(account_index)?;
+    if account.get_owner() != &id() {
+        return Err(TreasuryError::InvalidAccountOwner.into());
+    }
+    RecurringDisbursement::deserialize(account.get_data())
+        .map_err(|_| TreasuryError::InvalidAccountData.into())
+}
+
+/// Write account state with `serialize`, growing the account at
+/// `account_index` to `size` bytes first if it is smaller.
+fn save_account_state(
+    invoke_context: &InvokeContext,
+    account_index: u16,
+    size: usize,
+    serialize: impl FnOnce(&mut [u8]) -> Result<(), std::io::Error>,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let mut account = instruction_context.try_borrow_instruction_account(account_index)?;
+    let mut data = account.get_data().to_vec();
+    if data.len() < size {
+        data.resize(size, 0);
+    }
+    serialize(&mut data).map_err(|_| TreasuryError::InvalidAccountData)?;
+    account.set_data_from_slice(&data)
+}
+
+fn save_treasury_config(
+    invoke_context: &InvokeContext,
+    account_index: u16,
+    config: &TreasuryConfig,
+) -> Result<(), InstructionError> {
+    save_account_state(
+        invoke_context,
+        account_index,
+        TreasuryConfig::SERIALIZED_SIZE,
+        |data| config.serialize_into(data),
+    )
+}
+
+fn save_pending_disbursal(
+    invoke_context: &InvokeContext,
+    account_index: u16,
+    disbursal: &PendingDisbursal,
+) -> Result<(), InstructionError> {
+    save_account_state(
+        invoke_context,
+        account_index,
+        PendingDisbursal::SERIALIZED_SIZE,
+        |data| disbursal.serialize_into(data),
+    )
+}
+
+fn save_recurring_disbursement(
+    invoke_context: &InvokeContext,
+    account_index: u16,
+    disbursement: &RecurringDisbursement,
+) -> Result<(), InstructionError> {
+    save_account_state(
+        invoke_context,
+        account_index,
+        RecurringDisbursement::SERIALIZED_SIZE,
+        |data| disbursement.serialize_into(data),
+    )
+}
+
+/// Check that the account at `account_index` is owned by this program and
+/// holds no state of any kind yet: its data must be all zero, so that one
+/// account type can never be initialised over another.
+fn check_uninitialized(
+    invoke_context: &InvokeContext,
+    account_index: u16,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let account = instruction_context.try_borrow_instruction_account(account_index)?;
+    if account.get_owner() != &id() {
+        return Err(TreasuryError::InvalidAccountOwner.into());
+    }
+    if account.get_data().iter().any(|byte| *byte != 0) {
+        return Err(TreasuryError::AlreadyInitialized.into());
+    }
+    Ok(())
+}
+
+/// Check that the treasury authority approved this instruction.
+///
+/// A key authority signs as account `authority_index`.  A multisig authority
+/// is passed as account `authority_index` and approves only when
+/// `threshold` distinct members sign as accounts `signers_start` onwards; a
+/// signature by the multisig account's own key counts for nothing.
+fn check_authority(
+    invoke_context: &InvokeContext,
+    config: &TreasuryConfig,
+    authority_index: u16,
+    signers_start: u16,
+    instruction_name: &str,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    let authority = *instruction_context.get_key_of_instruction_account(authority_index)?;
+    let multisig = load_multisig(invoke_context, authority_index).ok();
+    if multisig.is_none() && !instruction_context.is_instruction_account_signer(authority_index)? {
+        return Err(TreasuryError::MissingAuthoritySignature.into());
+    }
+
+    if authority != config.authority {
+        ic_msg!(invoke_context, "{}: authority mismatch", instruction_name);
+        return Err(TreasuryError::AuthorityMismatch.into());
+    }
+
+    if let Some(multisig) = multisig {
+        let mut signers = Vec::new();
+        for index in signers_start..instruction_context.get_number_of_instruction_accounts() {
+            let key = *instruction_context.get_key_of_instruction_account(index)?;
+            if key != authority && instruction_context.is_instruction_account_signer(index)? {
+                signers.push(key);
+            }
+        }
+        let approvals = multisig.count_approvals(&signers);
+        if approvals < usize::from(multisig.threshold) {
+            ic_msg!(
+                invoke_context,
+                "{}: {} of {} required multisig signatures",
+                instruction_name,
+                approvals,
+                multisig.threshold
+            );
+            return Err(TreasuryError::InsufficientSignatures.into());
+        }
+    }
+    Ok(())
+}
+
+/// Check that instruction account `index` is the treasury account named in
+/// `config`, so no other program-owned account (such as a recurring
+/// disbursement escrow) can stand in as the source of funds.
+fn check_treasury_account(
+    invoke_context: &InvokeContext,
+    config: &TreasuryConfig,
+    index: u16,
+    instruction_name: &str,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    if *instruction_context.get_key_of_instruction_account(index)? != config.treasury_account {
+        ic_msg!(
+            invoke_context,
+            "{}: treasury account mismatch",
+            instruction_name
+        );
+        return Err(TreasuryError::InvalidAccountData.into());
+    }
+    Ok(())
+}
+
+/// Count `amount` lamports leaving the treasury account (2) against the
+/// epoch allowance in `config`.  The caller saves `config`.
+///
+/// The allowance is a share of account 2's balance, so account 2 must be
+/// the configured treasury account.
+fn charge_epoch_allowance(
+    invoke_context: &InvokeContext,
+    config: &mut TreasuryConfig,
+    amount: u64,
+    instruction_name: &str,
+) -> Result<(), InstructionError> {
+    check_treasury_account(invoke_context, config, 2, instruction_name)?;
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let balance = instruction_context
+        .try_borrow_instruction_account(2)?
+        .get_lamports();
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    if let Err(err) = config.charge_epoch_allowance(clock.epoch, balance, amount) {
+        ic_msg!(
+            invoke_context,
+            "{}: {} lamports would exceed the {}bps epoch allowance ({} already disbursed)",
+            instruction_name,
+            amount,
+            config.epoch_allowance_bps,
+            config.disbursed_this_epoch
+        );
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+/// Move `amount` lamports between two instruction accounts, failing with
+/// `InsufficientFunds` if the source holds less.
+fn transfer_lamports(
+    invoke_context: &InvokeContext,
+    from_index: u16,
+    to_index: u16,
+    amount: u64,
+    instruction_name: &str,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    {
+        let mut from_account = instruction_context.try_borrow_instruction_account(from_index)?;
+        if from_account.get_lamports() < amount {
+            ic_msg!(
+                invoke_context,
+                "{}: insufficient funds ({} < {})",
+                instruction_name,
+                from_account.get_lamports(),
+                amount
+            );
+            return Err(TreasuryError::InsufficientFunds.into());
+        }
+        from_account.checked_sub_lamports(amount)?;
+    }
+    let mut to_account = instruction_context.try_borrow_instruction_account(to_index)?;
+    to_account.checked_add_lamports(amount)
+}
+
+/// Send `amount` lamports from account 2 (the treasury, or an escrow holding
+/// treasury funds) to the recipient (3) and record it in `config`, which is
+/// saved back to account 1.
+fn disburse(
+    invoke_context: &InvokeContext,
+    mut config: TreasuryConfig,
+    amount: u64,
+    recipient: Pubkey,
+    memo: &str,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    // --- Validate recipient account matches instruction data ---
+    let recipient_key = *instruction_context.get_key_of_instruction_account(3)?;
+    if recipient_key != recipient {
+        ic_msg!(invoke_context, "Disburse: recipient mismatch");
+        return Err(TreasuryError::RecipientMismatch.into());
+    }
+
+    // --- Update tracking ---
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    config.total_disbursed = config
+        .total_disbursed
+        .checked_add(amount)
+        .ok_or(TreasuryError::ArithmeticOverflow)?;
+    config.last_updated_epoch = clock.epoch;
+    save_treasury_config(invoke_context, 1, &config)?;
+
+    // --- Transfer lamports from treasury account to recipient ---
+    transfer_lamports(invoke_context, 2, 3, amount, "Disburse")?;
+
+    ic_msg!(
+        invoke_context,
+        "Disburse: {} lamports to {} — memo: {}",
+        amount,
+        recipient,
+        memo
+    );
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Instruction handlers
 // ---------------------------------------------------------------------------
@@ -94,8 +451,7 @@ fn process_initialize_treasury(
             );
             return Err(TreasuryError::InvalidAccountOwner.into());
         }
-        let data = config_account.get_data();
-        if !data.is_empty() && data[0] == TREASURY_CONFIG_DISCRIMINATOR {
+        if config_account.get_data().iter().any(|byte| *byte != 0) {
             ic_msg!(
                 invoke_context,
                 "InitializeTreasury: config account already initialised"
@@ -115,6 +471,9 @@ fn process_initialize_treasury(
         total_received: 0,
         total_disbursed: 0,
         last_updated_epoch: clock.epoch,
+        epoch_allowance_bps: DEFAULT_EPOCH_ALLOWANCE_BPS,
+        disbursed_this_epoch: 0,
+        last_disbursement_epoch: clock.epoch,
     };
 
     let mut config_account = instruction_context.try_borrow_instruction_account(1)?;
@@ -143,6 +502,7 @@ fn process_initialize_treasury(
 ///   1. `[writable]`  — Treasury config account.
 ///   2. `[writable]`  — Treasury token account (source of lamports).
 ///   3. `[writable]`  — Recipient account.
+///   4.. `[signer]`   — Multisig signers, for a multisig authority.
 fn process_disburse(
     invoke_context: &InvokeContext,
     amount: u64,
@@ -162,185 +522,567 @@ fn process_disburse(
         return Err(TreasuryError::MemoTooLong.into());
     }
 
-    // --- Authority must sign ---
+    // --- Load config & check authority ---
+    let mut config = load_treasury_config(invoke_context, 1)?;
+    check_authority(invoke_context, &config, 0, 4, "Disburse")?;
+    charge_epoch_allowance(invoke_context, &mut config, amount, "Disburse")?;
+
+    disburse(invoke_context, config, amount, recipient, &memo)
+}
+
+/// `UpdateAuthority { new_authority }`
+///
+/// Accounts:
+///   0. `[signer]`   — Current authority.
+///   1. `[writable]`  — Treasury config account.
+///   2.. `[signer]`   — Multisig signers, for a multisig authority.
+fn process_update_authority(
+    invoke_context: &InvokeContext,
+    new_authority: Pubkey,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(2)?;
+
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+
+    let mut config = load_treasury_config(invoke_context, 1)?;
+    check_authority(invoke_context, &config, 0, 2, "UpdateAuthority")?;
+
+    let old_authority = config.authority;
+    config.authority = new_authority;
+    config.last_updated_epoch = clock.epoch;
+    save_treasury_config(invoke_context, 1, &config)?;
+
+    ic_msg!(
+        invoke_context,
+        "UpdateAuthority: {} → {}",
+        old_authority,
+        new_authority
+    );
+    Ok(())
+}
+
+/// `ActivateGovernance`
+///
+/// Accounts:
+///   0. `[signer]`   — Current authority.
+///   1. `[writable]`  — Treasury config account.
+///   2.. `[signer]`   — Multisig signers, for a multisig authority.
+fn process_activate_governance(
+    invoke_context: &InvokeContext,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(2)?;
+
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+
+    let mut config = load_treasury_config(invoke_context, 1)?;
+    check_authority(invoke_context, &config, 0, 2, "ActivateGovernance")?;
+
+    if config.governance_active {
+        ic_msg!(invoke_context, "ActivateGovernance: governance is already active");
+        return Err(TreasuryError::GovernanceAlreadyActive.into());
+    }
+
+    config.governance_active = true;
+    config.last_updated_epoch = clock.epoch;
+    save_treasury_config(invoke_context, 1, &config)?;
+
+    ic_msg!(
+        invoke_context,
+        "ActivateGovernance: governance activated by {}",
+        config.authority
+    );
+    Ok(())
+}
+
+/// `InitializeMultisig { signers, threshold }`
+///
+/// Accounts:
+///   0. `[signer]`   — Initialiser.
+///   1. `[writable]`  — Multisig account (pre-allocated, uninitialised).
+fn process_initialize_multisig(
+    invoke_context: &InvokeContext,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(2)?;
+
     if !instruction_context.is_instruction_account_signer(0)? {
         return Err(TreasuryError::MissingAuthoritySignature.into());
     }
-    let signer_pubkey = *instruction_context.get_key_of_instruction_account(0)?;
 
-    // --- Load & validate config ---
-    let clock = invoke_context.get_sysvar_cache().get_clock()?;
-    {
-        let mut config_account = instruction_context.try_borrow_instruction_account(1)?;
-        if config_account.get_owner() != &id() {
-            return Err(TreasuryError::InvalidAccountOwner.into());
-        }
+    let multisig = MultisigAuthority { signers, threshold };
+    if !multisig.is_valid() {
+        ic_msg!(
+            invoke_context,
+            "InitializeMultisig: invalid {}-of-{} multisig",
+            threshold,
+            multisig.signers.len()
+        );
+        return Err(TreasuryError::InvalidMultisig.into());
+    }
 
-        let data = config_account.get_data().to_vec();
-        let mut config = TreasuryConfig::deserialize(&data)
-            .map_err(|_| TreasuryError::NotInitialized)?;
+    check_uninitialized(invoke_context, 1)?;
+    save_account_state(
+        invoke_context,
+        1,
+        MultisigAuthority::SERIALIZED_SIZE,
+        |data| multisig.serialize_into(data),
+    )?;
 
-        if config.authority != signer_pubkey {
-            ic_msg!(invoke_context, "Disburse: authority mismatch");
-            return Err(TreasuryError::AuthorityMismatch.into());
-        }
+    ic_msg!(
+        invoke_context,
+        "InitializeMultisig: {}-of-{} multisig {}",
+        threshold,
+        multisig.signers.len(),
+        instruction_context.get_key_of_instruction_account(1)?
+    );
+    Ok(())
+}
 
-        // --- Validate recipient account matches instruction data ---
-        let recipient_key = *instruction_context.get_key_of_instruction_account(3)?;
-        if recipient_key != recipient {
-            ic_msg!(invoke_context, "Disburse: recipient mismatch");
-            return Err(TreasuryError::RecipientMismatch.into());
-        }
+/// `ProposeDisbursal { disbursal_id, amount, recipient, memo }`
+///
+/// Accounts:
+///   0. `[signer]`   — Multisig signer.
+///   1. `[]`          — Multisig account.
+///   2. `[writable]`  — Pending disbursal account (pre-allocated, uninitialised).
+fn process_propose_disbursal(
+    invoke_context: &InvokeContext,
+    disbursal_id: u64,
+    amount: u64,
+    recipient: Pubkey,
+    memo: String,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
 
-        // --- Update tracking ---
-        config.total_disbursed = config
-            .total_disbursed
-            .checked_add(amount)
-            .ok_or(TreasuryError::ArithmeticOverflow)?;
-        config.last_updated_epoch = clock.epoch;
+    instruction_context.check_number_of_instruction_accounts(3)?;
 
-        let mut buf = config_account.get_data().to_vec();
-        config
-            .serialize_into(&mut buf)
-            .map_err(|_| TreasuryError::InvalidAccountData)?;
-        config_account.set_data_from_slice(&buf)?;
+    if amount == 0 {
+        return Err(TreasuryError::ZeroDisbursement.into());
+    }
+    if memo.len() > MAX_MEMO_LEN {
+        return Err(TreasuryError::MemoTooLong.into());
     }
 
-    // --- Transfer lamports from treasury account to recipient ---
-    {
-        let mut treasury_account = instruction_context.try_borrow_instruction_account(2)?;
-        if treasury_account.get_lamports() < amount {
-            ic_msg!(
-                invoke_context,
-                "Disburse: insufficient funds ({} < {})",
-                treasury_account.get_lamports(),
-                amount
-            );
-            return Err(TreasuryError::InsufficientFunds.into());
-        }
-        treasury_account.checked_sub_lamports(amount)?;
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(TreasuryError::MissingAuthoritySignature.into());
     }
-    {
-        let mut recipient_account = instruction_context.try_borrow_instruction_account(3)?;
-        recipient_account.checked_add_lamports(amount)?;
+    let proposer = *instruction_context.get_key_of_instruction_account(0)?;
+    let multisig_key = *instruction_context.get_key_of_instruction_account(1)?;
+
+    let multisig = load_multisig(invoke_context, 1)?;
+    if !multisig.signers.contains(&proposer) {
+        return Err(TreasuryError::NotMultisigSigner.into());
     }
 
+    check_uninitialized(invoke_context, 2)?;
+    let disbursal = PendingDisbursal {
+        disbursal_id,
+        multisig: multisig_key,
+        amount,
+        recipient,
+        memo,
+        approvals: vec![proposer],
+        executed: false,
+    };
+    save_pending_disbursal(invoke_context, 2, &disbursal)?;
+
     ic_msg!(
         invoke_context,
-        "Disburse: {} lamports to {} — memo: {}",
+        "ProposeDisbursal: #{} of {} lamports to {} proposed by {}",
+        disbursal_id,
         amount,
         recipient,
-        memo
+        proposer
     );
     Ok(())
 }
 
-/// `UpdateAuthority { new_authority }`
+/// `SignDisbursal { disbursal_id }`
 ///
 /// Accounts:
-///   0. `[signer]`   — Current authority.
-///   1. `[writable]`  — Treasury config account.
-fn process_update_authority(
+///   0. `[signer]`   — Multisig signer.
+///   1. `[]`          — Multisig account.
+///   2. `[writable]`  — Pending disbursal account.
+fn process_sign_disbursal(
     invoke_context: &InvokeContext,
-    new_authority: Pubkey,
+    disbursal_id: u64,
 ) -> Result<(), InstructionError> {
     let transaction_context = &invoke_context.transaction_context;
     let instruction_context = transaction_context.get_current_instruction_context()?;
 
-    instruction_context.check_number_of_instruction_accounts(2)?;
+    instruction_context.check_number_of_instruction_accounts(3)?;
 
     if !instruction_context.is_instruction_account_signer(0)? {
         return Err(TreasuryError::MissingAuthoritySignature.into());
     }
-    let signer_pubkey = *instruction_context.get_key_of_instruction_account(0)?;
-
-    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    let signer = *instruction_context.get_key_of_instruction_account(0)?;
+    let multisig_key = *instruction_context.get_key_of_instruction_account(1)?;
 
-    let mut config_account = instruction_context.try_borrow_instruction_account(1)?;
-    if config_account.get_owner() != &id() {
-        return Err(TreasuryError::InvalidAccountOwner.into());
+    let multisig = load_multisig(invoke_context, 1)?;
+    let mut disbursal = load_pending_disbursal(invoke_context, 2)?;
+    if disbursal.disbursal_id != disbursal_id || disbursal.multisig != multisig_key {
+        return Err(TreasuryError::DisbursalMismatch.into());
+    }
+    if disbursal.executed {
+        return Err(TreasuryError::DisbursalAlreadyExecuted.into());
     }
+    if !multisig.signers.contains(&signer) {
+        return Err(TreasuryError::NotMultisigSigner.into());
+    }
+    if disbursal.approvals.contains(&signer) {
+        return Err(TreasuryError::DisbursalAlreadySigned.into());
+    }
+
+    disbursal.approvals.push(signer);
+    save_pending_disbursal(invoke_context, 2, &disbursal)?;
+
+    ic_msg!(
+        invoke_context,
+        "SignDisbursal: #{} approved by {} ({} of {})",
+        disbursal_id,
+        signer,
+        multisig.count_approvals(&disbursal.approvals),
+        multisig.threshold
+    );
+    Ok(())
+}
+
+/// `ExecuteDisbursal { disbursal_id }`
+///
+/// Accounts:
+///   0. `[signer]`   — Executor.
+///   1. `[writable]`  — Treasury config account.
+///   2. `[writable]`  — Treasury token account (source of lamports).
+///   3. `[writable]`  — Recipient account.
+///   4. `[]`          — Multisig account (the treasury authority).
+///   5. `[writable]`  — Pending disbursal account.
+fn process_execute_disbursal(
+    invoke_context: &InvokeContext,
+    disbursal_id: u64,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
 
-    let data = config_account.get_data().to_vec();
-    let mut config =
-        TreasuryConfig::deserialize(&data).map_err(|_| TreasuryError::NotInitialized)?;
+    instruction_context.check_number_of_instruction_accounts(6)?;
 
-    if config.authority != signer_pubkey {
-        ic_msg!(invoke_context, "UpdateAuthority: authority mismatch");
+    if !instruction_context.is_instruction_account_signer(0)? {
+        return Err(TreasuryError::MissingAuthoritySignature.into());
+    }
+    let multisig_key = *instruction_context.get_key_of_instruction_account(4)?;
+
+    let mut config = load_treasury_config(invoke_context, 1)?;
+    if multisig_key != config.authority {
+        ic_msg!(invoke_context, "ExecuteDisbursal: authority mismatch");
         return Err(TreasuryError::AuthorityMismatch.into());
     }
+    check_treasury_account(invoke_context, &config, 2, "ExecuteDisbursal")?;
+    let multisig = load_multisig(invoke_context, 4)?;
+    let mut disbursal = load_pending_disbursal(invoke_context, 5)?;
+    if disbursal.disbursal_id != disbursal_id || disbursal.multisig != multisig_key {
+        return Err(TreasuryError::DisbursalMismatch.into());
+    }
+    if disbursal.executed {
+        return Err(TreasuryError::DisbursalAlreadyExecuted.into());
+    }
+    let approvals = multisig.count_approvals(&disbursal.approvals);
+    if approvals < usize::from(multisig.threshold) {
+        ic_msg!(
+            invoke_context,
+            "ExecuteDisbursal: #{} has {} of {} required approvals",
+            disbursal_id,
+            approvals,
+            multisig.threshold
+        );
+        return Err(TreasuryError::InsufficientSignatures.into());
+    }
 
-    let old_authority = config.authority;
-    config.authority = new_authority;
+    charge_epoch_allowance(
+        invoke_context,
+        &mut config,
+        disbursal.amount,
+        "ExecuteDisbursal",
+    )?;
+    disbursal.executed = true;
+    save_pending_disbursal(invoke_context, 5, &disbursal)?;
+
+    disburse(
+        invoke_context,
+        config,
+        disbursal.amount,
+        disbursal.recipient,
+        &disbursal.memo,
+    )
+}
+
+/// `CreateRecurringDisbursement { disbursement_id, recipient, amount_per_epoch, start_epoch, end_epoch }`
+///
+/// Accounts:
+///   0. `[signer]`   — Authority.
+///   1. `[writable]`  — Treasury config account.
+///   2. `[writable]`  — Treasury token account (source of lamports).
+///   3. `[writable]`  — Recurring disbursement account (pre-allocated, uninitialised).
+///   4.. `[signer]`   — Multisig signers, for a multisig authority.
+fn process_create_recurring_disbursement(
+    invoke_context: &InvokeContext,
+    disbursement_id: u64,
+    recipient: Pubkey,
+    amount_per_epoch: u64,
+    start_epoch: u64,
+    end_epoch: u64,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(4)?;
+
+    if amount_per_epoch == 0 {
+        return Err(TreasuryError::ZeroDisbursement.into());
+    }
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    if end_epoch <= start_epoch || end_epoch <= clock.epoch {
+        return Err(TreasuryError::InvalidSchedule.into());
+    }
+
+    let mut config = load_treasury_config(invoke_context, 1)?;
+    check_authority(invoke_context, &config, 0, 4, "CreateRecurringDisbursement")?;
+    check_treasury_account(invoke_context, &config, 2, "CreateRecurringDisbursement")?;
+
+    let disbursement = RecurringDisbursement {
+        disbursement_id,
+        config: *instruction_context.get_key_of_instruction_account(1)?,
+        recipient,
+        amount_per_epoch,
+        start_epoch,
+        end_epoch,
+        last_disbursed_epoch: start_epoch,
+        cancelled: false,
+    };
+    let total = disbursement
+        .amount_through(end_epoch)
+        .ok_or(TreasuryError::ArithmeticOverflow)?;
+
+    check_uninitialized(invoke_context, 3)?;
+    charge_epoch_allowance(
+        invoke_context,
+        &mut config,
+        total,
+        "CreateRecurringDisbursement",
+    )?;
     config.last_updated_epoch = clock.epoch;
+    save_treasury_config(invoke_context, 1, &config)?;
+    save_recurring_disbursement(invoke_context, 3, &disbursement)?;
 
-    let mut buf = config_account.get_data().to_vec();
-    config
-        .serialize_into(&mut buf)
-        .map_err(|_| TreasuryError::InvalidAccountData)?;
-    config_account.set_data_from_slice(&buf)?;
+    // --- Escrow the whole grant in the recurring disbursement account ---
+    transfer_lamports(invoke_context, 2, 3, total, "CreateRecurringDisbursement")?;
 
     ic_msg!(
         invoke_context,
-        "UpdateAuthority: {} → {}",
-        old_authority,
-        new_authority
+        "CreateRecurringDisbursement: #{} pays {} lamports per epoch to {} for epochs {}..={}",
+        disbursement_id,
+        amount_per_epoch,
+        recipient,
+        start_epoch.saturating_add(1),
+        end_epoch
     );
     Ok(())
 }
 
-/// `ActivateGovernance`
+/// `ExecuteRecurring { disbursement_id }`
 ///
 /// Accounts:
-///   0. `[signer]`   — Current authority.
+///   0. `[signer]`   — Caller (anyone).
 ///   1. `[writable]`  — Treasury config account.
-fn process_activate_governance(
+///   2. `[writable]`  — Recurring disbursement account.
+///   3. `[writable]`  — Recipient account.
+fn process_execute_recurring(
     invoke_context: &InvokeContext,
+    disbursement_id: u64,
 ) -> Result<(), InstructionError> {
     let transaction_context = &invoke_context.transaction_context;
     let instruction_context = transaction_context.get_current_instruction_context()?;
 
-    instruction_context.check_number_of_instruction_accounts(2)?;
+    instruction_context.check_number_of_instruction_accounts(4)?;
 
     if !instruction_context.is_instruction_account_signer(0)? {
         return Err(TreasuryError::MissingAuthoritySignature.into());
     }
-    let signer_pubkey = *instruction_context.get_key_of_instruction_account(0)?;
+    let config_key = *instruction_context.get_key_of_instruction_account(1)?;
+
+    let config = load_treasury_config(invoke_context, 1)?;
+    let mut disbursement = load_recurring_disbursement(invoke_context, 2)?;
+    if disbursement.disbursement_id != disbursement_id || disbursement.config != config_key {
+        return Err(TreasuryError::RecurringMismatch.into());
+    }
 
     let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    if disbursement.cancelled || clock.epoch > disbursement.end_epoch {
+        ic_msg!(
+            invoke_context,
+            "ExecuteRecurring: #{} has ended",
+            disbursement_id
+        );
+        return Err(TreasuryError::RecurringEnded.into());
+    }
+    if clock.epoch <= disbursement.last_disbursed_epoch {
+        ic_msg!(
+            invoke_context,
+            "ExecuteRecurring: #{} already paid through epoch {}",
+            disbursement_id,
+            disbursement.last_disbursed_epoch
+        );
+        return Err(TreasuryError::RecurringNotDue.into());
+    }
 
-    let mut config_account = instruction_context.try_borrow_instruction_account(1)?;
-    if config_account.get_owner() != &id() {
-        return Err(TreasuryError::InvalidAccountOwner.into());
+    let amount = disbursement
+        .amount_through(clock.epoch)
+        .ok_or(TreasuryError::ArithmeticOverflow)?;
+    disbursement.last_disbursed_epoch = clock.epoch;
+    save_recurring_disbursement(invoke_context, 2, &disbursement)?;
+
+    let memo = format!("recurring disbursement #{disbursement_id}");
+    disburse(
+        invoke_context,
+        config,
+        amount,
+        disbursement.recipient,
+        &memo,
+    )
+}
+
+/// `CancelRecurring { disbursement_id }`
+///
+/// Accounts:
+///   0. `[signer]`   — Authority.
+///   1. `[writable]`  — Treasury config account.
+///   2. `[writable]`  — Treasury token account.
+///   3. `[writable]`  — Recurring disbursement account.
+///   4. `[writable]`  — Recipient account.
+///   5.. `[signer]`   — Multisig signers, for a multisig authority.
+fn process_cancel_recurring(
+    invoke_context: &InvokeContext,
+    disbursement_id: u64,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(5)?;
+
+    let config_key = *instruction_context.get_key_of_instruction_account(1)?;
+    let mut config = load_treasury_config(invoke_context, 1)?;
+    check_authority(invoke_context, &config, 0, 5, "CancelRecurring")?;
+    check_treasury_account(invoke_context, &config, 2, "CancelRecurring")?;
+
+    let mut disbursement = load_recurring_disbursement(invoke_context, 3)?;
+    if disbursement.disbursement_id != disbursement_id || disbursement.config != config_key {
+        return Err(TreasuryError::RecurringMismatch.into());
+    }
+    if disbursement.cancelled {
+        return Err(TreasuryError::RecurringEnded.into());
+    }
+    if *instruction_context.get_key_of_instruction_account(4)? != disbursement.recipient {
+        ic_msg!(invoke_context, "CancelRecurring: recipient mismatch");
+        return Err(TreasuryError::RecipientMismatch.into());
     }
 
-    let data = config_account.get_data().to_vec();
-    let mut config =
-        TreasuryConfig::deserialize(&data).map_err(|_| TreasuryError::NotInitialized)?;
+    // Epochs that accrued before the cancellation are still owed to the
+    // recipient, as `ExecuteRecurring` would have paid them this epoch.
+    // Once the grant has ended, unclaimed epochs have expired.
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
+    let accrued = if clock.epoch > disbursement.end_epoch {
+        0
+    } else {
+        disbursement
+            .amount_through(clock.epoch)
+            .ok_or(TreasuryError::ArithmeticOverflow)?
+    };
+    if accrued > 0 {
+        disbursement.last_disbursed_epoch = clock.epoch;
+    }
+    let refund = disbursement
+        .amount_through(disbursement.end_epoch)
+        .ok_or(TreasuryError::ArithmeticOverflow)?;
+    disbursement.cancelled = true;
+    save_recurring_disbursement(invoke_context, 3, &disbursement)?;
 
-    if config.authority != signer_pubkey {
-        ic_msg!(invoke_context, "ActivateGovernance: authority mismatch");
-        return Err(TreasuryError::AuthorityMismatch.into());
+    // --- Pay the recipient what has accrued ---
+    if accrued > 0 {
+        config.total_disbursed = config
+            .total_disbursed
+            .checked_add(accrued)
+            .ok_or(TreasuryError::ArithmeticOverflow)?;
+        config.last_updated_epoch = clock.epoch;
+        save_treasury_config(invoke_context, 1, &config)?;
+        transfer_lamports(invoke_context, 3, 4, accrued, "CancelRecurring")?;
     }
 
-    if config.governance_active {
-        ic_msg!(invoke_context, "ActivateGovernance: governance is already active");
-        return Err(TreasuryError::GovernanceAlreadyActive.into());
+    // --- Return the unpaid part of the grant to the treasury ---
+    transfer_lamports(invoke_context, 3, 2, refund, "CancelRecurring")?;
+
+    ic_msg!(
+        invoke_context,
+        "CancelRecurring: #{} cancelled, {} lamports paid to {}, {} lamports returned to the treasury",
+        disbursement_id,
+        accrued,
+        disbursement.recipient,
+        refund
+    );
+    Ok(())
+}
+
+/// `SetEpochAllowance { epoch_allowance_bps }`
+///
+/// Accounts:
+///   0. `[signer]`   — Authority (governance).
+///   1. `[writable]`  — Treasury config account.
+///   2.. `[signer]`   — Multisig signers, for a multisig authority.
+fn process_set_epoch_allowance(
+    invoke_context: &InvokeContext,
+    epoch_allowance_bps: u16,
+) -> Result<(), InstructionError> {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+
+    instruction_context.check_number_of_instruction_accounts(2)?;
+
+    if epoch_allowance_bps == 0 || u64::from(epoch_allowance_bps) > BPS_DENOMINATOR {
+        return Err(TreasuryError::InvalidEpochAllowance.into());
     }
 
-    config.governance_active = true;
-    config.last_updated_epoch = clock.epoch;
+    let clock = invoke_context.get_sysvar_cache().get_clock()?;
 
-    let mut buf = config_account.get_data().to_vec();
-    config
-        .serialize_into(&mut buf)
-        .map_err(|_| TreasuryError::InvalidAccountData)?;
-    config_account.set_data_from_slice(&buf)?;
+    let mut config = load_treasury_config(invoke_context, 1)?;
+    check_authority(invoke_context, &config, 0, 2, "SetEpochAllowance")?;
+
+    // Before governance takes over, the allowance is what keeps the launch
+    // authority from emptying the treasury, so that authority cannot lift it.
+    if !config.governance_active {
+        ic_msg!(
+            invoke_context,
+            "SetEpochAllowance: governance is not active"
+        );
+        return Err(TreasuryError::GovernanceNotActive.into());
+    }
+
+    let old_allowance_bps = config.epoch_allowance_bps;
+    config.epoch_allowance_bps = epoch_allowance_bps;
+    config.last_updated_epoch = clock.epoch;
+    save_treasury_config(invoke_context, 1, &config)?;
 
     ic_msg!(
         invoke_context,
-        "ActivateGovernance: governance activated by {}",
-        signer_pubkey
+        "SetEpochAllowance: {}bps → {}bps",
+        old_allowance_bps,
+        epoch_allowance_bps
     );
     Ok(())
 }
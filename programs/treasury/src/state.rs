@@ -1,6 +1,7 @@
 //! Account state types for the Treasury program.
 
 use {
+    crate::{error::TreasuryError, processor::MAX_MEMO_LEN},
     borsh::{BorshDeserialize, BorshSerialize},
     solana_pubkey::Pubkey,
 };
@@ -9,6 +10,24 @@ use {
 /// to distinguish it from uninitialized or foreign account data.
 pub const TREASURY_CONFIG_DISCRIMINATOR: u8 = 1;
 
+/// Discriminator byte of a `MultisigAuthority` account.
+pub const MULTISIG_AUTHORITY_DISCRIMINATOR: u8 = 2;
+
+/// Discriminator byte of a `PendingDisbursal` account.
+pub const PENDING_DISBURSAL_DISCRIMINATOR: u8 = 3;
+
+/// Discriminator byte of a `RecurringDisbursement` account.
+pub const RECURRING_DISBURSEMENT_DISCRIMINATOR: u8 = 4;
+
+/// Maximum number of signers of a `MultisigAuthority`.
+pub const MAX_MULTISIG_SIGNERS: usize = 11;
+
+/// Basis points in 100%.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Epoch allowance a new treasury starts with: 10% of its balance.
+pub const DEFAULT_EPOCH_ALLOWANCE_BPS: u16 = 1_000;
+
 /// On-chain configuration and accounting state for the TRv1 treasury.
 ///
 /// Serialised with Borsh; the first byte of account data is the discriminator.
@@ -19,7 +38,9 @@ pub const TREASURY_CONFIG_DISCRIMINATOR: u8 = 1;
 pub struct TreasuryConfig {
     /// Current treasury authority (multisig at launch, governance later).
     ///
-    /// Every mutating instruction requires a signature from this key.
+    /// Every mutating instruction requires a signature from this key, or, if
+    /// it is the address of a `MultisigAuthority` account, signatures from
+    /// `threshold` of its signers.
     pub authority: Pubkey,
 
     /// Treasury token account (where fees accumulate).
@@ -44,6 +65,20 @@ pub struct TreasuryConfig {
 
     /// Epoch at which this config was last updated.
     pub last_updated_epoch: u64,
+
+    /// Largest share of the treasury balance, in basis points, that may be
+    /// disbursed in a single epoch.
+    ///
+    /// Starts at `DEFAULT_EPOCH_ALLOWANCE_BPS` and can only be changed by
+    /// `SetEpochAllowance` once governance is active, so a compromised
+    /// launch multisig cannot drain the treasury at once.
+    pub epoch_allowance_bps: u16,
+
+    /// Lamports disbursed during `last_disbursement_epoch`.
+    pub disbursed_this_epoch: u64,
+
+    /// Epoch of the most recent disbursement.
+    pub last_disbursement_epoch: u64,
 }
 
 impl TreasuryConfig {
@@ -57,8 +92,11 @@ impl TreasuryConfig {
     ///   total_received   (8)
     ///   total_disbursed  (8)
     ///   last_updated_epoch(8)
-    ///   = 90 bytes
-    pub const SERIALIZED_SIZE: usize = 1 + 32 + 32 + 1 + 8 + 8 + 8;
+    ///   epoch_allowance_bps(2)
+    ///   disbursed_this_epoch(8)
+    ///   last_disbursement_epoch(8)
+    ///   = 108 bytes
+    pub const SERIALIZED_SIZE: usize = 1 + 32 + 32 + 1 + 8 + 8 + 8 + 2 + 8 + 8;
 
     /// Deserialise from raw account data (expects leading discriminator byte).
     pub fn deserialize(data: &[u8]) -> Result<Self, std::io::Error> {
@@ -84,4 +122,252 @@ impl TreasuryConfig {
         let mut cursor = &mut data[1..];
         BorshSerialize::serialize(self, &mut cursor)
     }
+
+    /// Count `amount` lamports, disbursed in `epoch` from a treasury holding
+    /// `balance`, against the epoch allowance.
+    ///
+    /// The running total starts over in every epoch after
+    /// `last_disbursement_epoch`.  Leaves the config unchanged on error.
+    pub fn charge_epoch_allowance(
+        &mut self,
+        epoch: u64,
+        balance: u64,
+        amount: u64,
+    ) -> Result<(), TreasuryError> {
+        let allowance =
+            balance as u128 * self.epoch_allowance_bps as u128 / BPS_DENOMINATOR as u128;
+        let disbursed = if epoch > self.last_disbursement_epoch {
+            0
+        } else {
+            self.disbursed_this_epoch
+        };
+        let disbursed = disbursed
+            .checked_add(amount)
+            .ok_or(TreasuryError::ArithmeticOverflow)?;
+        if disbursed as u128 > allowance {
+            return Err(TreasuryError::EpochAllowanceExceeded);
+        }
+        self.disbursed_this_epoch = disbursed;
+        self.last_disbursement_epoch = epoch;
+        Ok(())
+    }
+}
+
+/// An M-of-N signer set that can act as the treasury authority.
+///
+/// Lives in a pre-allocated account owned by the treasury program, created
+/// with `InitializeMultisig`.  Pointing `TreasuryConfig::authority` at the
+/// account (via `UpdateAuthority`) puts the treasury under the multisig.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct MultisigAuthority {
+    /// Keys allowed to approve on behalf of the multisig (unique).
+    pub signers: Vec<Pubkey>,
+
+    /// Number of `signers` whose approval is required.
+    pub threshold: u8,
+}
+
+impl MultisigAuthority {
+    /// Account size of a multisig with `MAX_MULTISIG_SIGNERS` signers.
+    ///
+    /// Layout:
+    ///   discriminator   (1)
+    ///   signers         (4 + 32 × MAX_MULTISIG_SIGNERS)
+    ///   threshold       (1)
+    pub const SERIALIZED_SIZE: usize = 1 + 4 + 32 * MAX_MULTISIG_SIGNERS + 1;
+
+    /// Deserialise from raw account data (expects leading discriminator byte).
+    pub fn deserialize(data: &[u8]) -> Result<Self, std::io::Error> {
+        if data.is_empty() || data[0] != MULTISIG_AUTHORITY_DISCRIMINATOR {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "missing or invalid multisig authority discriminator",
+            ));
+        }
+        let mut cursor = &data[1..];
+        BorshDeserialize::deserialize_reader(&mut cursor)
+    }
+
+    /// Serialise into raw account data (prepends discriminator byte).
+    pub fn serialize_into(&self, data: &mut [u8]) -> Result<(), std::io::Error> {
+        if data.len() < Self::SERIALIZED_SIZE {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "account data buffer too small",
+            ));
+        }
+        data[0] = MULTISIG_AUTHORITY_DISCRIMINATOR;
+        let mut cursor = &mut data[1..];
+        BorshSerialize::serialize(self, &mut cursor)
+    }
+
+    /// Whether 1 ≤ `threshold` ≤ number of signers ≤ `MAX_MULTISIG_SIGNERS`
+    /// and no signer is listed twice.
+    pub fn is_valid(&self) -> bool {
+        let unique = self
+            .signers
+            .iter()
+            .enumerate()
+            .all(|(i, signer)| !self.signers[..i].contains(signer));
+        self.threshold >= 1
+            && usize::from(self.threshold) <= self.signers.len()
+            && self.signers.len() <= MAX_MULTISIG_SIGNERS
+            && unique
+    }
+
+    /// Number of distinct multisig signers among `approvers`.
+    pub fn count_approvals<'a>(&self, approvers: impl IntoIterator<Item = &'a Pubkey>) -> usize {
+        let mut approved = Vec::new();
+        for approver in approvers {
+            if self.signers.contains(approver) && !approved.contains(&approver) {
+                approved.push(approver);
+            }
+        }
+        approved.len()
+    }
+}
+
+/// A disbursal proposed by a multisig signer, executed once `threshold`
+/// signers have approved it with `SignDisbursal`.
+///
+/// Lives in a pre-allocated account owned by the treasury program.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct PendingDisbursal {
+    /// Identifier chosen by the proposer; every instruction on the
+    /// disbursal repeats it.
+    pub disbursal_id: u64,
+
+    /// The `MultisigAuthority` account whose signers approve it.
+    pub multisig: Pubkey,
+
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub memo: String,
+
+    /// Signers that have approved so far, the proposer first.
+    pub approvals: Vec<Pubkey>,
+
+    /// Set by `ExecuteDisbursal`; an executed disbursal cannot run again.
+    pub executed: bool,
+}
+
+impl PendingDisbursal {
+    /// Account size of a disbursal with a maximum-length memo and approvals
+    /// from `MAX_MULTISIG_SIGNERS` signers.
+    ///
+    /// Layout:
+    ///   discriminator   (1)
+    ///   disbursal_id    (8)
+    ///   multisig        (32)
+    ///   amount          (8)
+    ///   recipient       (32)
+    ///   memo            (4 + MAX_MEMO_LEN)
+    ///   approvals       (4 + 32 × MAX_MULTISIG_SIGNERS)
+    ///   executed        (1)
+    pub const SERIALIZED_SIZE: usize =
+        1 + 8 + 32 + 8 + 32 + 4 + MAX_MEMO_LEN + 4 + 32 * MAX_MULTISIG_SIGNERS + 1;
+
+    /// Deserialise from raw account data (expects leading discriminator byte).
+    pub fn deserialize(data: &[u8]) -> Result<Self, std::io::Error> {
+        if data.is_empty() || data[0] != PENDING_DISBURSAL_DISCRIMINATOR {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "missing or invalid pending disbursal discriminator",
+            ));
+        }
+        let mut cursor = &data[1..];
+        BorshDeserialize::deserialize_reader(&mut cursor)
+    }
+
+    /// Serialise into raw account data (prepends discriminator byte).
+    pub fn serialize_into(&self, data: &mut [u8]) -> Result<(), std::io::Error> {
+        if data.len() < Self::SERIALIZED_SIZE {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "account data buffer too small",
+            ));
+        }
+        data[0] = PENDING_DISBURSAL_DISCRIMINATOR;
+        let mut cursor = &mut data[1..];
+        BorshSerialize::serialize(self, &mut cursor)
+    }
+}
+
+/// A grant paid out every epoch from `start_epoch` (exclusive) through
+/// `end_epoch`.
+///
+/// Lives in a pre-allocated account owned by the treasury program, which
+/// escrows the unpaid amount: `CreateRecurringDisbursement` moves the whole
+/// grant out of the treasury, `ExecuteRecurring` pays the recipient what has
+/// accrued, and `CancelRecurring` settles anything still accrued before
+/// returning the rest.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct RecurringDisbursement {
+    /// Identifier chosen by the authority; every instruction on the
+    /// disbursement repeats it.
+    pub disbursement_id: u64,
+
+    /// Treasury config account the grant was paid from.
+    pub config: Pubkey,
+
+    pub recipient: Pubkey,
+    pub amount_per_epoch: u64,
+    pub start_epoch: u64,
+    pub end_epoch: u64,
+
+    /// Epoch up to which the recipient has been paid; starts at
+    /// `start_epoch`.
+    pub last_disbursed_epoch: u64,
+
+    /// Set by `CancelRecurring`; nothing more is paid out.
+    pub cancelled: bool,
+}
+
+impl RecurringDisbursement {
+    /// Serialised size (discriminator + borsh payload).
+    ///
+    /// Layout:
+    ///   discriminator        (1)
+    ///   disbursement_id      (8)
+    ///   config               (32)
+    ///   recipient            (32)
+    ///   amount_per_epoch     (8)
+    ///   start_epoch          (8)
+    ///   end_epoch            (8)
+    ///   last_disbursed_epoch (8)
+    ///   cancelled            (1)
+    ///   = 106 bytes
+    pub const SERIALIZED_SIZE: usize = 1 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+
+    /// Deserialise from raw account data (expects leading discriminator byte).
+    pub fn deserialize(data: &[u8]) -> Result<Self, std::io::Error> {
+        if data.is_empty() || data[0] != RECURRING_DISBURSEMENT_DISCRIMINATOR {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "missing or invalid recurring disbursement discriminator",
+            ));
+        }
+        let mut cursor = &data[1..];
+        BorshDeserialize::deserialize_reader(&mut cursor)
+    }
+
+    /// Serialise into raw account data (prepends discriminator byte).
+    pub fn serialize_into(&self, data: &mut [u8]) -> Result<(), std::io::Error> {
+        if data.len() < Self::SERIALIZED_SIZE {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "account data buffer too small",
+            ));
+        }
+        data[0] = RECURRING_DISBURSEMENT_DISCRIMINATOR;
+        let mut cursor = &mut data[1..];
+        BorshSerialize::serialize(self, &mut cursor)
+    }
+
+    /// Lamports for the epochs after `last_disbursed_epoch` through
+    /// `through_epoch`, or `None` on overflow.
+    pub fn amount_through(&self, through_epoch: u64) -> Option<u64> {
+        self.amount_per_epoch
+            .checked_mul(through_epoch.saturating_sub(self.last_disbursed_epoch))
+    }
 }
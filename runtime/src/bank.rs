@@ -592,6 +592,7 @@ impl PartialEq for Bank {
             trv1_slashing: _,
             trv1_fee_state: _,
             trv1_fee_config: _,
+            trv1_priority_fee_history: _,
             // Ignore new fields explicitly if they do not impact PartialEq.
             // Adding ".." will remove compile-time checks that if a new field
             // is added to the struct, this PartialEq is accordingly updated.
@@ -950,6 +951,11 @@ pub struct Bank {
     /// TRv1: Fee market configuration. Stored per-bank for future
     /// governance-driven config updates.
     pub trv1_fee_config: trv1_fee_market::FeeMarketConfig,
+
+    /// TRv1: Rolling window of recently landed transaction fees. Each bank
+    /// starts from a copy of its parent's, so sibling forks never see each
+    /// other's fees. Source for priority fee statistics.
+    pub trv1_priority_fee_history: RwLock<trv1_fee_market::PriorityFeeHistory>,
 }
 
 #[derive(Debug)]
@@ -1163,6 +1169,9 @@ impl Bank {
                 trv1_fee_market::FeeMarketConfig::default().min_base_fee,
             )),
             trv1_fee_config: trv1_fee_market::FeeMarketConfig::default(),
+            trv1_priority_fee_history: RwLock::new(trv1_fee_market::PriorityFeeHistory::new(
+                &trv1_fee_market::FeeMarketConfig::default(),
+            )),
         };
 
         bank.transaction_processor =
@@ -1416,6 +1425,9 @@ impl Bank {
                 trv1_fee_market::FeeMarketConfig::default().min_base_fee,
             )),
             trv1_fee_config: parent.trv1_fee_config.clone(),
+            trv1_priority_fee_history: RwLock::new(
+                parent.trv1_priority_fee_history.read().unwrap().clone(),
+            ),
         };
 
         // TRv1: compute next base fee from parent's utilization
@@ -1791,6 +1803,11 @@ impl Bank {
             }
         }
 
+        // TRv1: Publish the archive root of the epoch that just ended, so
+        // revivals of accounts archived during it can verify their proofs.
+        #[cfg(feature = "trv1-tiered-storage")]
+        self.publish_trv1_archive_root(parent_epoch);
+
         // Save a snapshot of stakes for use in consensus and stake weighted networking
         let leader_schedule_epoch = self.epoch_schedule.get_leader_schedule_epoch(slot);
         let (_, update_epoch_stakes_time_us) =
@@ -1825,6 +1842,25 @@ impl Bank {
             .set_environments(new_environments);
     }
 
+    /// Store the archive root of `epoch` at its `archive_root_address`,
+    /// funded rent-exempt.
+    #[cfg(feature = "trv1-tiered-storage")]
+    fn publish_trv1_archive_root(&self, epoch: Epoch) {
+        use solana_accounts_db::state_rent_expiry::archive_root_address;
+
+        let Some(adapter) = self.rc.accounts.accounts_db.trv1_adapter.as_ref() else {
+            return;
+        };
+        let root = adapter.publish_epoch_root(epoch, self.slot());
+        let mut account = root.to_account(0);
+        account.set_lamports(self.get_minimum_balance_for_rent_exemption(account.data().len()));
+        self.store_account_and_update_capitalization(&archive_root_address(epoch), &account);
+        info!(
+            "TRv1: published archive root {} of epoch {} ({} accounts)",
+            root.root, epoch, root.num_leaves
+        );
+    }
+
     pub fn byte_limit_for_scans(&self) -> Option<usize> {
         self.rc
             .accounts
@@ -2000,6 +2036,9 @@ impl Bank {
                 trv1_fee_market::FeeMarketConfig::default().min_base_fee,
             )),
             trv1_fee_config: trv1_fee_market::FeeMarketConfig::default(),
+            trv1_priority_fee_history: RwLock::new(trv1_fee_market::PriorityFeeHistory::new(
+                &trv1_fee_market::FeeMarketConfig::default(),
+            )),
         };
 
         // Sanity assertions between bank snapshot and genesis config
@@ -2122,6 +2161,11 @@ impl Bank {
         &self.trv1_fee_config
     }
 
+    /// TRv1: priority fee distribution over recently landed transactions.
+    pub fn trv1_priority_fee_stats(&self) -> trv1_fee_market::PriorityFeeStats {
+        trv1_fee_integration::priority_fee_statistics(self)
+    }
+
     pub fn genesis_creation_time(&self) -> UnixTimestamp {
         self.genesis_creation_time
     }
@@ -3893,16 +3937,31 @@ impl Bank {
 
         self.filter_program_errors_and_collect_fee_details(&processing_results);
 
-        // TRv1: record compute units consumed for EIP-1559 base fee calculation
+        // TRv1: record compute units consumed for EIP-1559 base fee calculation,
+        // and account writes for storage-write demand
         {
-            let total_cu: u64 = processing_results
+            let (total_cu, total_accounts_written) = sanitized_txs
                 .iter()
-                .filter_map(|r| r.processed_transaction())
-                .map(|tx| tx.executed_units())
-                .sum();
-            if total_cu > 0 {
-                trv1_fee_integration::record_transaction_compute(self, total_cu);
+                .zip(processing_results.iter())
+                .filter_map(|(tx, r)| r.processed_transaction().map(|p| (tx, p)))
+                .fold((0u64, 0u64), |(cu, written), (tx, processed_tx)| {
+                    (
+                        cu.saturating_add(processed_tx.executed_units()),
+                        written.saturating_add(tx.num_write_locks()),
+                    )
+                });
+            if total_cu > 0 || total_accounts_written > 0 {
+                trv1_fee_integration::record_transaction_compute(
+                    self,
+                    total_cu,
+                    total_accounts_written,
+                );
             }
+            trv1_fee_integration::record_transaction_fees(
+                self,
+                sanitized_txs,
+                &processing_results,
+            );
         }
 
         timings.saturating_add_in_place(ExecuteTimingType::StoreUs, store_accounts_us);
@@ -6,25 +6,36 @@
 //!
 //! 1. **`update_base_fee_for_new_block`** — called when a new bank/slot is
 //!    created (from parent). Derives the next base fee from the parent's
-//!    utilization and stores it in the bank.
+//!    utilization, or the governance fee market pause, and stores it in the
+//!    bank.
 //!
 //! 2. **`validate_trv1_fee`** — called during transaction validation to ensure
 //!    the payer can afford the EIP-1559 base fee + priority fee.
 //!
 //! 3. **`record_transaction_compute`** — called after a transaction executes to
-//!    record its compute-unit usage into the block's running total.
+//!    record its compute-unit usage and account writes into the block's
+//!    running totals.
+//!
+//!    **`record_transaction_fees`** — called alongside it to append each
+//!    processed transaction's fee to the bank's priority fee history.
 //!
 //! 4. **`finalize_block_fees`** — called at end of block (freeze) to seal the
 //!    fee state for serialization.
 
 use {
     super::Bank,
+    solana_account::ReadableAccount,
+    solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
+    solana_svm::transaction_processing_result::{
+        TransactionProcessingResult, TransactionProcessingResultExtensions,
+    },
     trv1_fee_market::{
         calculator::{calculate_next_base_fee, calculate_transaction_fee, validate_transaction_fee},
         BlockFeeState, FeeMarketConfig,
-        state::TransactionFee,
-        FeeError,
+        state::{TransactionFee, TransactionResources},
+        FeeError, PriorityFeeStats,
     },
+    trv1_governance_program::state::{find_network_params_address, NetworkParams},
     log::info,
 };
 
@@ -33,12 +44,21 @@ use {
 /// Reads the parent bank's fee state (gas used, base fee) and computes
 /// the new base fee using the EIP-1559 algorithm. Stores the result
 /// in the child bank's `trv1_fee_state`.
+///
+/// A fee market pause set through governance (`PauseFeeMarket`) as of the
+/// end of the parent block is applied first, so a paused market holds the
+/// fixed fee and a resumed one adjusts from the parent's usage again.
 pub fn update_base_fee_for_new_block(bank: &Bank, parent: &Bank) {
     let config = FeeMarketConfig::default();
-    let parent_state = parent.trv1_fee_state.read().unwrap();
+    let mut parent_state = *parent.trv1_fee_state.read().unwrap();
+    match governance_fee_market_pause(parent) {
+        Some(fixed_base_fee) => parent_state.pause(fixed_base_fee),
+        None if parent_state.paused => parent_state.resume(),
+        None => {}
+    }
 
     let next_base_fee = calculate_next_base_fee(&config, &parent_state);
-    let next_state = parent_state.next_block(next_base_fee, bank.slot());
+    let next_state = parent_state.next_block(&config, next_base_fee, bank.slot());
 
     info!(
         "TRv1 fee market: slot={} base_fee={} (parent_gas_used={}, parent_base_fee={})",
@@ -52,6 +72,21 @@ pub fn update_base_fee_for_new_block(bank: &Bank, parent: &Bank) {
     *fee_state = next_state;
 }
 
+/// The fixed base fee set by a governance fee market pause, or `None` while
+/// the market runs normally.
+///
+/// Read from the governance program's network params account; anything
+/// else at that address is ignored.
+fn governance_fee_market_pause(bank: &Bank) -> Option<u64> {
+    let account = bank.get_account(&find_network_params_address().0)?;
+    if account.owner() != &trv1_governance_program::id() {
+        return None;
+    }
+    NetworkParams::deserialize(account.data())
+        .ok()?
+        .fee_market_pause()
+}
+
 /// Get the current base fee per compute unit from the bank.
 pub fn get_current_base_fee(bank: &Bank) -> u64 {
     bank.trv1_fee_state.read().unwrap().base_fee_per_cu
@@ -68,34 +103,78 @@ pub fn get_block_fee_state(bank: &Bank) -> BlockFeeState {
 /// - `bank`: the current bank (to read base fee)
 /// - `offered_lamports`: the fee payer's available balance (or declared max fee)
 /// - `priority_fee_per_cu`: the user's chosen priority fee per CU
-/// - `requested_cu`: compute units the transaction requests
+/// - `resources`: compute units the transaction requests and accounts it writes
 ///
 /// Returns a `TransactionFee` breakdown on success, or a `FeeError` on failure.
 pub fn validate_trv1_fee(
     bank: &Bank,
     offered_lamports: u64,
     priority_fee_per_cu: u64,
-    requested_cu: u64,
+    resources: &TransactionResources,
 ) -> Result<TransactionFee, FeeError> {
-    let config = FeeMarketConfig::default();
     let base_fee_per_cu = get_current_base_fee(bank);
 
     validate_transaction_fee(
         offered_lamports,
         priority_fee_per_cu,
         base_fee_per_cu,
-        requested_cu,
-        &config,
+        resources,
+        &bank.trv1_fee_config,
     )
 }
 
-/// Record compute units consumed by a transaction in the current block.
+/// Record compute units consumed and accounts written by transactions in the
+/// current block.
 ///
 /// Called after a transaction successfully executes to update the running
-/// gas tally used for the next block's base fee calculation.
-pub fn record_transaction_compute(bank: &Bank, compute_units_used: u64) {
+/// gas tally used for the next block's base fee calculation, and the
+/// account-write tally that tracks storage demand.
+pub fn record_transaction_compute(bank: &Bank, compute_units_used: u64, accounts_written: u64) {
     let mut fee_state = bank.trv1_fee_state.write().unwrap();
-    fee_state.record_gas(compute_units_used);
+    fee_state.record_gas(compute_units_used, accounts_written);
+}
+
+/// Record the fees paid by processed transactions in the priority fee history.
+///
+/// The base fee component is charged on executed compute units at the
+/// current base fee; the priority fee is the prioritization fee (lamports)
+/// the transaction paid, split between the validator and the passive
+/// staking reward pool per `priority_fee_passive_stake_bps`; the storage fee
+/// prices each account the transaction write-locks.
+pub fn record_transaction_fees(
+    bank: &Bank,
+    sanitized_txs: &[impl TransactionWithMeta],
+    processing_results: &[TransactionProcessingResult],
+) {
+    let base_fee_per_cu = get_current_base_fee(bank);
+    let write_fee_per_account = bank.trv1_fee_config.write_fee_per_account;
+    let mut history = bank.trv1_priority_fee_history.write().unwrap();
+    for (tx, processed_tx) in sanitized_txs
+        .iter()
+        .zip(processing_results)
+        .filter_map(|(tx, r)| r.processed_transaction().map(|p| (tx, p)))
+    {
+        let base_fee = base_fee_per_cu.saturating_mul(processed_tx.executed_units());
+        let priority_fee = processed_tx.fee_details().prioritization_fee();
+        let (priority_fee_validator, priority_fee_stake_pool) =
+            bank.trv1_fee_config.split_priority_fee(priority_fee);
+        let storage_fee = write_fee_per_account.saturating_mul(tx.num_write_locks());
+        history.record(TransactionFee {
+            base_fee,
+            priority_fee_validator,
+            priority_fee_stake_pool,
+            storage_fee,
+            total_fee: base_fee
+                .saturating_add(priority_fee)
+                .saturating_add(storage_fee),
+        });
+    }
+}
+
+/// Priority fee distribution over the bank's recent transaction history.
+pub fn priority_fee_statistics(bank: &Bank) -> PriorityFeeStats {
+    let fees = bank.trv1_priority_fee_history.read().unwrap().fees();
+    bank.trv1_fee_config.priority_fee_statistics(&fees)
 }
 
 /// Called at end of block to finalize fee state.
@@ -120,15 +199,34 @@ pub fn finalize_block_fees(bank: &Bank) {
 pub fn estimate_transaction_fee(
     bank: &Bank,
     priority_fee_per_cu: u64,
-    compute_units: u64,
+    resources: &TransactionResources,
 ) -> TransactionFee {
     let base_fee_per_cu = get_current_base_fee(bank);
-    calculate_transaction_fee(base_fee_per_cu, priority_fee_per_cu, compute_units)
+    calculate_transaction_fee(
+        &bank.trv1_fee_config,
+        base_fee_per_cu,
+        priority_fee_per_cu,
+        resources,
+    )
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {
+        super::*,
+        crate::genesis_utils::create_genesis_config,
+        solana_account::{AccountSharedData, WritableAccount},
+        solana_compute_budget_interface::ComputeBudgetInstruction,
+        solana_message::Message,
+        solana_pubkey::Pubkey,
+        solana_signer::Signer,
+        solana_system_interface::instruction as system_instruction,
+        solana_system_transaction as system_transaction,
+        solana_transaction::Transaction,
+        std::sync::Arc,
+        trv1_fee_market::UTILIZATION_SCALE,
+        trv1_governance_program::constants::{FEE_MARKET_PAUSED_PARAM, FEE_MARKET_PAUSE_FEE_PARAM},
+    };
 
     #[test]
     fn test_genesis_block_fee_state() {
@@ -140,11 +238,17 @@ mod tests {
 
     #[test]
     fn test_transaction_fee_calculation() {
-        let fee = calculate_transaction_fee(5_000, 100, 200_000);
+        let config = FeeMarketConfig::default();
+        let fee = calculate_transaction_fee(
+            &config,
+            5_000,
+            100,
+            &TransactionResources::compute_only(200_000),
+        );
         // base: 5_000 * 200_000 = 1_000_000_000
         // priority: 100 * 200_000 = 20_000_000
         assert_eq!(fee.base_fee, 1_000_000_000);
-        assert_eq!(fee.priority_fee, 20_000_000);
+        assert_eq!(fee.priority_fee_validator, 20_000_000);
         assert_eq!(fee.total_fee, 1_020_000_000);
     }
 
@@ -155,7 +259,7 @@ mod tests {
             2_000_000_000, // 2 SOL offered
             100,           // priority fee per CU
             5_000,         // base fee per CU
-            200_000,       // 200k CU
+            &TransactionResources::compute_only(200_000),
             &config,
         );
         assert!(result.is_ok());
@@ -170,7 +274,7 @@ mod tests {
             100, // way too little
             100,
             5_000,
-            200_000,
+            &TransactionResources::compute_only(200_000),
             &config,
         );
         assert!(result.is_err());
@@ -185,7 +289,7 @@ mod tests {
         let config = FeeMarketConfig::default();
         let mut state = BlockFeeState::genesis(5_000);
         // Simulate above-target usage (target is 24M, max is 48M)
-        state.record_gas(36_000_000);
+        state.record_gas(36_000_000, 0);
         let parent_state = state;
 
         let next_fee = calculate_next_base_fee(&config, &parent_state);
@@ -203,6 +307,10 @@ mod tests {
             parent_gas_used: 12_000_000, // below target
             current_gas_used: 12_000_000,
             height: 5,
+            current_accounts_written: 0,
+            paused: false,
+            pause_fee: 0,
+            ema_utilization: UTILIZATION_SCALE,
         };
 
         let next_fee = calculate_next_base_fee(&config, &state);
@@ -211,4 +319,147 @@ mod tests {
             "base fee should decrease when below target"
         );
     }
+
+    #[test]
+    fn test_priority_fee_history_is_per_fork() {
+        let genesis = create_genesis_config(0);
+        let parent = Arc::new(Bank::new_for_tests(&genesis.genesis_config));
+        let fee = |priority_fee| TransactionFee {
+            priority_fee_validator: priority_fee,
+            total_fee: priority_fee,
+            ..TransactionFee::default()
+        };
+        parent
+            .trv1_priority_fee_history
+            .write()
+            .unwrap()
+            .record(fee(10));
+
+        let fork_a = Bank::new_from_parent(parent.clone(), &Pubkey::default(), 1);
+        let fork_b = Bank::new_from_parent(parent.clone(), &Pubkey::default(), 2);
+        fork_a
+            .trv1_priority_fee_history
+            .write()
+            .unwrap()
+            .record(fee(500));
+
+        let fees = |bank: &Bank| bank.trv1_priority_fee_history.read().unwrap().fees();
+        assert_eq!(fees(&fork_a), vec![fee(10), fee(500)]);
+        assert_eq!(fees(&fork_b), vec![fee(10)]);
+        assert_eq!(fees(&parent), vec![fee(10)]);
+    }
+
+    #[test]
+    fn test_recorded_fee_includes_storage_fee() {
+        let genesis = create_genesis_config(1_000_000_000);
+        let bank = Bank::new_for_tests(&genesis.genesis_config);
+        let tx = system_transaction::transfer(
+            &genesis.mint_keypair,
+            &Pubkey::new_unique(),
+            1_000_000,
+            bank.last_blockhash(),
+        );
+        bank.process_transaction(&tx).unwrap();
+
+        // The payer and the recipient are both written.
+        let fees = bank.trv1_priority_fee_history.read().unwrap().fees();
+        let fee = fees.last().unwrap();
+        let write_fee_per_account = bank.trv1_fee_config.write_fee_per_account;
+        assert!(write_fee_per_account > 0);
+        assert_eq!(fee.storage_fee, 2 * write_fee_per_account);
+        assert_eq!(
+            fee.total_fee,
+            fee.base_fee + fee.priority_fee_validator + fee.storage_fee
+        );
+    }
+
+    #[test]
+    fn test_recorded_priority_fee_split_with_stake_pool() {
+        let genesis = create_genesis_config(1_000_000_000);
+        let mut bank = Bank::new_for_tests(&genesis.genesis_config);
+        bank.trv1_fee_config.priority_fee_passive_stake_bps = 2_500;
+        let payer = genesis.mint_keypair.pubkey();
+        let message = Message::new(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(10_000),
+                // 1 lamport per CU
+                ComputeBudgetInstruction::set_compute_unit_price(1_000_000),
+                system_instruction::transfer(&payer, &Pubkey::new_unique(), 1_000_000),
+            ],
+            Some(&payer),
+        );
+        let tx = Transaction::new(&[&genesis.mint_keypair], message, bank.last_blockhash());
+        bank.process_transaction(&tx).unwrap();
+
+        // A quarter of the 10 000 lamport priority fee goes to passive stakers.
+        let fees = bank.trv1_priority_fee_history.read().unwrap().fees();
+        let fee = fees.last().unwrap();
+        assert_eq!(fee.priority_fee_stake_pool, 2_500);
+        assert_eq!(fee.priority_fee_validator, 7_500);
+        assert_eq!(fee.total_fee, fee.base_fee + 10_000 + fee.storage_fee);
+    }
+
+    /// Store governance network params with the fee market paused at
+    /// `pause`, or running when `None`.
+    fn store_fee_market_pause(bank: &Bank, pause: Option<u64>) {
+        let mut params = NetworkParams::default();
+        match pause {
+            Some(fixed_base_fee) => {
+                params.set(FEE_MARKET_PAUSED_PARAM, 1);
+                params.set(FEE_MARKET_PAUSE_FEE_PARAM, fixed_base_fee);
+            }
+            None => params.set(FEE_MARKET_PAUSED_PARAM, 0),
+        }
+        let mut account = AccountSharedData::new(
+            1,
+            NetworkParams::SERIALIZED_SIZE,
+            &trv1_governance_program::id(),
+        );
+        params.serialize_into(account.data_as_mut_slice()).unwrap();
+        bank.store_account(&find_network_params_address().0, &account);
+    }
+
+    #[test]
+    fn test_governance_pause_freezes_base_fee() {
+        let genesis = create_genesis_config(0);
+        let parent = Arc::new(Bank::new_for_tests(&genesis.genesis_config));
+        store_fee_market_pause(&parent, Some(7_500));
+        record_transaction_compute(&parent, 48_000_000, 0);
+
+        // Full blocks would raise the fee, but the pause holds it.
+        let child = Arc::new(Bank::new_from_parent(parent, &Pubkey::default(), 1));
+        assert_eq!(get_current_base_fee(&child), 7_500);
+        assert!(get_block_fee_state(&child).paused);
+        record_transaction_compute(&child, 48_000_000, 0);
+        let grandchild = Arc::new(Bank::new_from_parent(child, &Pubkey::default(), 2));
+        assert_eq!(get_current_base_fee(&grandchild), 7_500);
+
+        // Once governance resumes the market, the fee adjusts from the
+        // paused fee again.
+        store_fee_market_pause(&grandchild, None);
+        record_transaction_compute(&grandchild, 48_000_000, 0);
+        let resumed = Bank::new_from_parent(grandchild, &Pubkey::default(), 3);
+        let state = get_block_fee_state(&resumed);
+        assert!(!state.paused);
+        assert!(state.base_fee_per_cu > 7_500);
+    }
+
+    #[test]
+    fn test_pause_ignored_unless_owned_by_governance() {
+        let genesis = create_genesis_config(0);
+        let parent = Arc::new(Bank::new_for_tests(&genesis.genesis_config));
+        store_fee_market_pause(&parent, Some(7_500));
+        let mut account = parent
+            .get_account(&find_network_params_address().0)
+            .unwrap();
+        account.set_owner(Pubkey::new_unique());
+        parent.store_account(&find_network_params_address().0, &account);
+
+        let child = Bank::new_from_parent(parent, &Pubkey::default(), 1);
+        assert!(!get_block_fee_state(&child).paused);
+        assert_eq!(
+            get_current_base_fee(&child),
+            FeeMarketConfig::default().min_base_fee
+        );
+    }
 }
@@ -17,7 +17,7 @@
 
 use {
     solana_pubkey::Pubkey,
-    std::collections::HashMap,
+    std::collections::{HashMap, HashSet},
 };
 
 // ---------------------------------------------------------------------------
@@ -129,6 +129,9 @@ pub struct SlashingState {
     pub config: SlashingConfig,
     /// Map from **validator node identity** pubkey to jail status.
     pub jail_statuses: HashMap<Pubkey, ValidatorJailStatus>,
+    /// Double-sign offenses already slashed, keyed by (validator, height,
+    /// round), so the same evidence is never punished twice.
+    pub processed_evidence: HashSet<(Pubkey, u64, u32)>,
 }
 
 impl SlashingState {
@@ -136,9 +139,22 @@ impl SlashingState {
         Self {
             config: SlashingConfig::default(),
             jail_statuses: HashMap::new(),
+            processed_evidence: HashSet::new(),
         }
     }
 
+    /// Whether the double-sign by `validator` at `(height, round)` has
+    /// already been slashed.
+    pub fn is_evidence_processed(&self, validator: &Pubkey, height: u64, round: u32) -> bool {
+        self.processed_evidence.contains(&(*validator, height, round))
+    }
+
+    /// Record the double-sign by `validator` at `(height, round)` as
+    /// processed.  Returns `false` if it already was.
+    pub fn record_evidence(&mut self, validator: &Pubkey, height: u64, round: u32) -> bool {
+        self.processed_evidence.insert((*validator, height, round))
+    }
+
     // -----------------------------------------------------------------------
     // Core operations
     // -----------------------------------------------------------------------
@@ -457,6 +473,21 @@ mod tests {
         assert!(!state.is_jailed_or_banned(&online_validator));
     }
 
+    #[test]
+    fn test_evidence_recorded_once() {
+        let mut state = SlashingState::new();
+        let validator = Pubkey::new_unique();
+
+        assert!(!state.is_evidence_processed(&validator, 7, 1));
+        assert!(state.record_evidence(&validator, 7, 1));
+        assert!(state.is_evidence_processed(&validator, 7, 1));
+        assert!(!state.record_evidence(&validator, 7, 1));
+
+        // Another round is a separate offense.
+        assert!(!state.is_evidence_processed(&validator, 7, 2));
+        assert!(state.record_evidence(&validator, 7, 2));
+    }
+
     #[test]
     fn test_jailed_set() {
         let mut state = SlashingState::new();
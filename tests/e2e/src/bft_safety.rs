@@ -0,0 +1,146 @@
+//! E2E Test: BFT Safety
+//!
+//! Verifies consensus behaviour under faults:
+//! - Partition a 7-validator network 4 vs 3 → verify the side holding a
+//!   quorum of stake keeps producing blocks and the other side stalls
+//! - Heal the partition → verify every validator produces blocks again
+//! - Inject a double-signing validator → verify evidence is collected and
+//!   the honest validators keep finalizing
+//! - Inject nil-voting and offline validators → verify liveness holds until
+//!   they control 1/3 of the stake
+
+use trv1_e2e_tests::helpers::*;
+use trv1_consensus_bft::EvidenceKind;
+use solana_pubkey::Pubkey;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: Network partition → minority stalls, majority continues
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_network_partition_minority_stalls() {
+    init_logging();
+    println!("\n========================================");
+    println!("  BFT SAFETY: 4 vs 3 network partition");
+    println!("========================================\n");
+
+    // The four-validator side holds 80% of the stake, above the 2/3 quorum.
+    let majority = make_pubkeys(4);
+    let minority = make_pubkeys(3);
+    let stakes: Vec<(Pubkey, u64)> = majority
+        .iter()
+        .map(|pk| (*pk, 3_000_000_000_000)) // 3000 SOL
+        .chain(minority.iter().map(|pk| (*pk, 1_000_000_000_000))) // 1000 SOL
+        .collect();
+    let mut net = SimNetwork::new(&stakes);
+
+    net.partition(&majority, &minority);
+    assert!(!net.is_reachable(&majority[0], &minority[0]));
+    assert!(!net.is_reachable(&minority[2], &majority[3]));
+    assert!(net.is_reachable(&majority[0], &majority[1]));
+    assert!(net.is_reachable(&minority[0], &minority[1]));
+    println!("✓ Partition blocks traffic across the boundary only");
+
+    let blocks_proposed = |net: &SimNetwork, pks: &[Pubkey]| -> Vec<u64> {
+        pks.iter()
+            .map(|pk| net.validator(pk).unwrap().blocks_proposed)
+            .collect()
+    };
+
+    let start_slot = net.current_slot;
+    net.advance_to_epoch(net.current_epoch + 5);
+    let partitioned_slots = net.current_slot - start_slot;
+
+    assert!(
+        blocks_proposed(&net, &majority).iter().all(|&n| n > 0),
+        "Every majority validator should keep producing blocks"
+    );
+    assert!(
+        blocks_proposed(&net, &minority).iter().all(|&n| n == 0),
+        "Minority validators cannot reach quorum"
+    );
+    assert!(net.blocks_produced > 0 && net.blocks_produced < partitioned_slots);
+    println!(
+        "✓ {} of {} slots produced while partitioned; minority stalled",
+        net.blocks_produced, partitioned_slots
+    );
+
+    net.heal_partition();
+    let blocks_before_heal = net.blocks_produced;
+    let minority_before_heal = blocks_proposed(&net, &minority);
+    net.produce_epoch();
+
+    assert_eq!(net.blocks_produced - blocks_before_heal, SLOTS_PER_EPOCH);
+    assert!(blocks_proposed(&net, &minority)
+        .iter()
+        .zip(&minority_before_heal)
+        .all(|(after, before)| after > before));
+    println!("✓ Consensus resumed on every validator after healing");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: Double-signing validator → evidence collected, honest 3 finalize
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_byzantine_double_sign_collects_evidence() {
+    init_logging();
+    println!("\n========================================");
+    println!("  BFT SAFETY: Byzantine double-signer");
+    println!("========================================\n");
+
+    let pks = make_pubkeys(4);
+    let stakes: Vec<(Pubkey, u64)> = pks.iter().map(|pk| (*pk, 1_000_000_000_000)).collect();
+    let mut net = SimNetwork::new(&stakes);
+    let byzantine = pks[0];
+    net.set_byzantine_behavior(&byzantine, ByzantineMode::DoubleSign);
+
+    net.produce_empty_blocks(10);
+
+    let evidence = net.collected_evidence();
+    assert_eq!(evidence.len(), 10, "One offense per height");
+    for (ev, height) in evidence.iter().zip(1..) {
+        assert_eq!(ev.voter, byzantine);
+        assert_eq!(ev.height, height);
+        assert_eq!(ev.kind(), Some(EvidenceKind::ConflictingPrevote));
+    }
+    println!(
+        "✓ {} double-sign evidence records collected",
+        evidence.len()
+    );
+
+    // The three honest validators hold 75% of the stake.
+    assert_eq!(net.blocks_produced, 10);
+    println!("✓ Remaining validators finalized all 10 blocks");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: Nil-voting and offline validators → liveness lost past 1/3 stake
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_byzantine_nil_votes_and_offline() {
+    init_logging();
+    println!("\n========================================");
+    println!("  BFT SAFETY: Nil-voting and offline");
+    println!("========================================\n");
+
+    let pks = make_pubkeys(4);
+    let stakes: Vec<(Pubkey, u64)> = pks.iter().map(|pk| (*pk, 1_000_000_000_000)).collect();
+    let mut net = SimNetwork::new(&stakes);
+
+    // A quarter of the stake misbehaving is tolerated, except for the slots
+    // the offline validator should have proposed.
+    net.set_byzantine_behavior(&pks[0], ByzantineMode::Offline);
+    net.produce_empty_blocks(8);
+    assert_eq!(net.validator(&pks[0]).unwrap().blocks_proposed, 0);
+    assert_eq!(net.blocks_produced, 6);
+    println!("✓ Offline validator's slots skipped, others finalized");
+
+    // Half the stake withholding prevotes halts the network.
+    net.set_byzantine_behavior(&pks[1], ByzantineMode::NilVote);
+    net.produce_empty_blocks(8);
+    assert_eq!(net.blocks_produced, 6);
+    assert!(net.collected_evidence().is_empty());
+    println!("✓ No blocks finalized without a quorum of prevotes");
+}
@@ -6,11 +6,17 @@
 //! - Verify 4-way fee split (burn, validator, treasury, developer)
 //! - Verify fee transition progresses over epochs
 //! - Submit transactions to a deployed program → verify developer gets fee share
+//! - Verify newly registered programs earn nothing until the cooldown elapses
+//! - Verify an emergency pause fixes the base fee until governance lifts it
 
 use trv1_e2e_tests::helpers::*;
 use trv1_fee_market::{
     calculator::{calculate_next_base_fee, calculate_transaction_fee, validate_transaction_fee},
-    BlockFeeState, FeeMarketConfig,
+    BlockFeeState, FeeMarketConfig, TransactionResources, UTILIZATION_SCALE,
+};
+use trv1_governance_program::{
+    constants::{FEE_MARKET_MULTISIG_RESUME_PARAM, FEE_MARKET_PAUSED_PARAM},
+    state::ProposalStep,
 };
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -34,9 +40,9 @@ fn test_base_fee_rises_above_target() {
     // Produce 20 blocks with above-target utilization.
     for i in 0..20 {
         let block_cu = config.max_block_compute_units * 75 / 100; // 75% full
-        state.record_gas(block_cu);
+        state.record_gas(block_cu, 0);
         let next_fee = calculate_next_base_fee(&config, &state);
-        state = state.next_block(next_fee, i + 1);
+        state = state.next_block(&config, next_fee, i + 1);
     }
 
     assert!(
@@ -70,6 +76,10 @@ fn test_base_fee_drops_below_target() {
         parent_gas_used: 0,
         current_gas_used: 0,
         height: 0,
+        current_accounts_written: 0,
+        paused: false,
+        pause_fee: 0,
+        ema_utilization: UTILIZATION_SCALE,
     };
     let initial_fee = state.base_fee_per_cu;
 
@@ -77,7 +87,7 @@ fn test_base_fee_drops_below_target() {
     for i in 0..20 {
         // No gas used → below target.
         let next_fee = calculate_next_base_fee(&config, &state);
-        state = state.next_block(next_fee, i + 1);
+        state = state.next_block(&config, next_fee, i + 1);
     }
 
     assert!(
@@ -108,7 +118,7 @@ fn test_base_fee_floor_with_empty_blocks() {
 
     for i in 0..100 {
         let next_fee = calculate_next_base_fee(&config, &state);
-        state = state.next_block(next_fee, i + 1);
+        state = state.next_block(&config, next_fee, i + 1);
     }
 
     assert_eq!(
@@ -132,24 +142,26 @@ fn test_varying_priority_fees() {
     println!("  FEE LIFECYCLE: Varying priority fees");
     println!("========================================\n");
 
+    let config = FeeMarketConfig::default();
     let base_fee = 5_000;
     let cu = 200_000;
+    let resources = TransactionResources::compute_only(cu);
 
     // Transaction with zero priority.
-    let fee_zero = calculate_transaction_fee(base_fee, 0, cu);
+    let fee_zero = calculate_transaction_fee(&config, base_fee, 0, &resources);
     assert_eq!(fee_zero.base_fee, base_fee * cu);
-    assert_eq!(fee_zero.priority_fee, 0);
+    assert_eq!(fee_zero.priority_fee(), 0);
     assert_eq!(fee_zero.total_fee, base_fee * cu);
     println!("  Zero priority: total={}", fee_zero.total_fee);
 
     // Transaction with low priority.
-    let fee_low = calculate_transaction_fee(base_fee, 100, cu);
-    assert_eq!(fee_low.priority_fee, 100 * cu);
+    let fee_low = calculate_transaction_fee(&config, base_fee, 100, &resources);
+    assert_eq!(fee_low.priority_fee(), 100 * cu);
     assert!(fee_low.total_fee > fee_zero.total_fee);
     println!("  Low priority (100/CU): total={}", fee_low.total_fee);
 
     // Transaction with high priority.
-    let fee_high = calculate_transaction_fee(base_fee, 10_000, cu);
+    let fee_high = calculate_transaction_fee(&config, base_fee, 10_000, &resources);
     assert!(fee_high.total_fee > fee_low.total_fee);
     println!("  High priority (10k/CU): total={}", fee_high.total_fee);
 
@@ -230,6 +242,41 @@ fn test_four_way_fee_split() {
     println!("✓ 4-way fee split verified at launch ratios\n");
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: Priority fee share routed to the passive staking pool
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_priority_fee_share_to_passive_stake_pool() {
+    init_logging();
+    println!("\n========================================");
+    println!("  FEE LIFECYCLE: Priority fees to passive stakers");
+    println!("========================================\n");
+
+    let (mut net, _pks) = standard_3_validator_network();
+    net.fee_config.priority_fee_passive_stake_bps = 2_000; // 20%
+    let user = Pubkey::new_unique();
+    net.credit(&user, 100_000_000_000_000);
+
+    let tx = SimTransaction {
+        sender: user,
+        compute_units: 200_000,
+        priority_fee_per_cu: 100,
+        invoked_programs: vec![],
+    };
+    let base_fee = net.fee_state.base_fee_per_cu * tx.compute_units;
+    let fees = net.produce_block(&[tx]);
+    assert_eq!(fees, base_fee + 100 * 200_000);
+
+    // 20% of the 20M lamport priority fee skips the 4-way split.
+    assert_eq!(net.passive_stake_pool, 4_000_000);
+    let split = net.total_burned + net.treasury_fees + net.validator_fees + net.developer_fees;
+    assert_eq!(split, fees - 4_000_000);
+    assert_eq!(net.total_fees_collected, fees);
+
+    println!("✓ Passive staking pool received {} lamports", net.passive_stake_pool);
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Test: Fee split transition over epochs
 // ─────────────────────────────────────────────────────────────────────────────
@@ -298,7 +345,7 @@ fn test_fee_validation_insufficient_funds() {
         10_000_000_000, // 10 SOL
         100,            // priority
         5_000,          // base fee
-        200_000,        // CU
+        &TransactionResources::compute_only(200_000),
         &config,
     );
     assert!(result.is_ok());
@@ -306,10 +353,10 @@ fn test_fee_validation_insufficient_funds() {
 
     // Insufficient fee.
     let result = validate_transaction_fee(
-        1,     // 1 lamport
+        1, // 1 lamport
         100,
         5_000,
-        200_000,
+        &TransactionResources::compute_only(200_000),
         &config,
     );
     assert!(result.is_err());
@@ -320,7 +367,7 @@ fn test_fee_validation_insufficient_funds() {
         u64::MAX,
         100,
         5_000,
-        config.max_block_compute_units + 1,
+        &TransactionResources::compute_only(config.max_block_compute_units + 1),
         &config,
     );
     assert!(result.is_err());
@@ -376,6 +423,89 @@ fn test_developer_fee_attribution_multiple_programs() {
     println!("✓ Developer fees correctly attributed to multiple programs\n");
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: Co-invoked programs split developer fees by compute units
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_developer_fee_attribution_weighted_by_compute_units() {
+    init_logging();
+    println!("\n========================================");
+    println!("  FEE LIFECYCLE: CU-weighted developer attribution");
+    println!("========================================\n");
+
+    let (mut net, _pks) = standard_3_validator_network();
+    let user = Pubkey::new_unique();
+    net.credit(&user, 100_000_000_000_000);
+
+    // One transaction invokes a DeFi protocol (80% of CUs) and a small helper
+    // program (20% of CUs).
+    let protocol = Pubkey::new_unique();
+    let helper = Pubkey::new_unique();
+    let tx = SimTransaction {
+        sender: user,
+        compute_units: 500_000,
+        priority_fee_per_cu: 100,
+        invoked_programs: vec![(protocol, 400_000), (helper, 100_000)],
+    };
+    net.produce_block(&[tx]);
+
+    let to_developer = net.developer_fees;
+    assert!(to_developer > 0);
+    let protocol_fees = *net.developer_reward_accounts.get(&protocol).unwrap_or(&0);
+    let helper_fees = *net.developer_reward_accounts.get(&helper).unwrap_or(&0);
+    assert_eq!(protocol_fees, to_developer * 80 / 100);
+    assert_eq!(helper_fees, to_developer * 20 / 100);
+    println!(
+        "  Developer share {}: protocol={}, helper={}",
+        to_developer, protocol_fees, helper_fees
+    );
+
+    println!("✓ Developer fees split 80/20 by compute units\n");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: Newly registered programs earn nothing during the 7-day cooldown
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_developer_fee_cooldown_for_new_program() {
+    init_logging();
+    println!("\n========================================");
+    println!("  FEE LIFECYCLE: Developer registration cooldown");
+    println!("========================================\n");
+
+    let (mut net, _pks) = standard_3_validator_network();
+    let user = Pubkey::new_unique();
+    net.credit(&user, 100_000_000_000_000);
+
+    let program = Pubkey::new_unique();
+    net.register_developer_program(program);
+
+    // Slot 1_000_000: still inside the ~7-day cooldown.
+    net.warp_to_slot(999_999);
+    net.produce_block(&program_transactions(20, user, program));
+    assert_eq!(net.current_slot, 1_000_000);
+    assert_eq!(net.developer_cooldown_remaining(&program), 512_000);
+    assert_eq!(
+        *net.developer_reward_accounts.get(&program).unwrap_or(&0),
+        0,
+        "Program must not earn fees during its cooldown"
+    );
+    println!("  Slot 1_000_000: no developer fees (cooldown)");
+
+    // Slot 1_512_001: cooldown elapsed.
+    net.warp_to_slot(1_512_000);
+    net.produce_block(&program_transactions(20, user, program));
+    assert_eq!(net.current_slot, 1_512_001);
+    assert_eq!(net.developer_cooldown_remaining(&program), 0);
+    let earned = *net.developer_reward_accounts.get(&program).unwrap_or(&0);
+    assert!(earned > 0, "Program should earn fees after the cooldown");
+    println!("  Slot 1_512_001: earned {} lamports", earned);
+
+    println!("✓ Developer fees gated by the registration cooldown\n");
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Test: Full fee lifecycle through epochs with utilization changes
 // ─────────────────────────────────────────────────────────────────────────────
@@ -401,8 +531,8 @@ fn test_full_fee_lifecycle_through_epochs() {
     for _ in 0..SLOTS_PER_EPOCH * 2 {
         let mut txs: Vec<SimTransaction> = random_transactions(20, &users);
         for tx in &mut txs {
-            tx.invoked_program = Some(program);
             tx.compute_units = 1_000_000; // Large CU per tx
+            tx.invoked_programs = vec![(program, tx.compute_units)];
         }
         net.produce_block(&txs);
     }
@@ -433,6 +563,107 @@ fn test_full_fee_lifecycle_through_epochs() {
     println!("FULL FEE LIFECYCLE TEST PASSED ✓\n");
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: Emergency pause fixes the base fee until it is lifted
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_emergency_fee_market_pause() {
+    init_logging();
+    println!("\n========================================");
+    println!("  FEE LIFECYCLE: Emergency fee market pause");
+    println!("========================================\n");
+
+    let (mut net, pks) = standard_3_validator_network();
+    let authority = pks[0];
+    let multisig = net.governance.as_ref().unwrap().emergency_multisig;
+    let users = make_pubkeys(10);
+    for u in &users {
+        net.credit(u, 1_000_000_000_000_000); // 1M SOL each
+    }
+    let heavy_block = |users: &[Pubkey]| {
+        let mut txs = random_transactions(40, users);
+        for tx in &mut txs {
+            tx.compute_units = 1_000_000;
+        }
+        txs
+    };
+
+    // Heavy load pushes the base fee up before the pause.
+    for _ in 0..20 {
+        net.produce_block(&heavy_block(&users));
+    }
+    assert!(net.fee_state.base_fee_per_cu > net.fee_config.min_base_fee);
+
+    // Only the emergency multisig can pause.
+    let fixed_fee = 50_000;
+    assert!(net.pause_fee_market(&authority, fixed_fee).is_err());
+    net.pause_fee_market(&multisig, fixed_fee).unwrap();
+
+    // Neither heavy nor empty blocks move the fee while paused.
+    net.produce_block(&heavy_block(&users));
+    for i in 0..40 {
+        if i % 2 == 0 {
+            net.produce_block(&heavy_block(&users));
+        } else {
+            net.produce_block(&[]);
+        }
+        assert_eq!(net.fee_state.base_fee_per_cu, fixed_fee);
+    }
+    println!("✓ Base fee held at {} for 40 blocks of mixed load", fixed_fee);
+
+    // Multisig resumes are disabled until governance enables them.
+    assert!(net.resume_fee_market(&multisig).is_err());
+
+    // A ParameterChange proposal lifts the pause.
+    let prop_id = net
+        .create_multi_step_proposal(
+            &authority,
+            "Resume fee market",
+            vec![ProposalStep::ParameterChange {
+                param_id: FEE_MARKET_PAUSED_PARAM,
+                new_value: 0,
+            }],
+        )
+        .unwrap();
+    let exec_epoch = net.proposals[prop_id as usize].execution_epoch;
+    net.advance_to_epoch(exec_epoch);
+    assert_eq!(net.fee_state.base_fee_per_cu, fixed_fee);
+    net.execute_proposal(prop_id).unwrap();
+    assert!(!net.fee_state.paused);
+
+    // Adjustment resumes: heavy load raises the fee again once the
+    // utilization average catches up with it after the idle epochs.
+    for _ in 0..30 {
+        net.produce_block(&heavy_block(&users));
+    }
+    assert!(net.fee_state.base_fee_per_cu > fixed_fee);
+    println!("✓ Base fee adjusts again after the pause is lifted");
+
+    // With multisig resumes enabled, the multisig can lift its own pause.
+    let prop_id = net
+        .create_multi_step_proposal(
+            &authority,
+            "Allow multisig fee market resume",
+            vec![ProposalStep::ParameterChange {
+                param_id: FEE_MARKET_MULTISIG_RESUME_PARAM,
+                new_value: 1,
+            }],
+        )
+        .unwrap();
+    let exec_epoch = net.proposals[prop_id as usize].execution_epoch;
+    net.advance_to_epoch(exec_epoch);
+    net.execute_proposal(prop_id).unwrap();
+    net.pause_fee_market(&multisig, fixed_fee).unwrap();
+    net.produce_block(&[]);
+    assert_eq!(net.fee_state.base_fee_per_cu, fixed_fee);
+    net.resume_fee_market(&multisig).unwrap();
+    net.produce_block(&[]);
+    assert!(net.fee_state.base_fee_per_cu < fixed_fee);
+
+    println!("EMERGENCY FEE MARKET PAUSE TEST PASSED ✓\n");
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Helper
 // ─────────────────────────────────────────────────────────────────────────────
@@ -8,8 +8,10 @@
 //! - Pass proposal → timelock → execute
 //! - Test veto flow
 //! - Test emergency unlock (80% supermajority)
+//! - Multi-step proposals execute atomically
 
 use trv1_e2e_tests::helpers::*;
+use trv1_governance_program::state::ProposalStep;
 use trv1_governance_program::vote_weight::{calculate_voting_power, StakeSource};
 use solana_pubkey::Pubkey;
 
@@ -434,3 +436,123 @@ fn test_multiple_parallel_proposals() {
     println!("✓ Proposal 4: Expired (no votes)");
     println!("✓ All 5 parallel proposals resolved correctly");
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: Multi-step proposal executes every step in order
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_multi_step_proposal_executes_all_steps() {
+    init_logging();
+    println!("\n========================================");
+    println!("  GOVERNANCE: Multi-step proposal");
+    println!("========================================\n");
+
+    let (mut net, pks) = standard_3_validator_network();
+    let authority = pks[0];
+    net.treasury.as_mut().unwrap().balance = 1_000_000;
+
+    let recipient = Pubkey::new_unique();
+    let steps = vec![
+        ProposalStep::ParameterChange {
+            param_id: 1,
+            new_value: 42,
+        },
+        ProposalStep::TreasurySpend {
+            recipient,
+            amount: 400_000,
+            memo: [0u8; 32],
+        },
+        ProposalStep::FeatureToggle {
+            feature_id: 7,
+            enabled: true,
+        },
+    ];
+    let prop_id = net
+        .create_multi_step_proposal(&authority, "Fee update + grant + feature", steps)
+        .unwrap();
+    println!("✓ Multi-step proposal #{} created", prop_id);
+
+    let exec_epoch = net.proposals[prop_id as usize].execution_epoch;
+    net.advance_to_epoch(exec_epoch);
+    net.execute_proposal(prop_id).unwrap();
+
+    let proposal = &net.proposals[prop_id as usize];
+    assert_eq!(proposal.status, SimProposalStatus::Executed);
+    assert_eq!(proposal.executed_steps, 3);
+    assert_eq!(net.governance_parameters.get(&1), Some(&42));
+    assert_eq!(net.treasury.as_ref().unwrap().balance, 600_000);
+    assert_eq!(net.balance(&recipient), 400_000);
+    println!("✓ All 3 steps applied");
+
+    // More than MAX_PROPOSAL_STEPS is rejected up front.
+    let too_many = vec![ProposalStep::TextProposal; 6];
+    assert!(net
+        .create_multi_step_proposal(&authority, "Too many steps", too_many)
+        .is_err());
+    assert!(net
+        .create_multi_step_proposal(&authority, "No steps", Vec::new())
+        .is_err());
+    println!("✓ Step count limits enforced");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: Partial failure of a multi-step proposal reverts every step
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_multi_step_partial_failure_reverts_all_steps() {
+    init_logging();
+    println!("\n========================================");
+    println!("  GOVERNANCE: Multi-step partial failure");
+    println!("========================================\n");
+
+    let (mut net, pks) = standard_3_validator_network();
+    let authority = pks[0];
+    net.treasury.as_mut().unwrap().balance = 1_000_000;
+
+    let recipient_a = Pubkey::new_unique();
+    let recipient_b = Pubkey::new_unique();
+    let steps = vec![
+        ProposalStep::ParameterChange {
+            param_id: 1,
+            new_value: 42,
+        },
+        ProposalStep::TreasurySpend {
+            recipient: recipient_a,
+            amount: 700_000,
+            memo: [0u8; 32],
+        },
+        // Exceeds the remaining treasury balance — fails.
+        ProposalStep::TreasurySpend {
+            recipient: recipient_b,
+            amount: 700_000,
+            memo: [0u8; 32],
+        },
+    ];
+    let prop_id = net
+        .create_multi_step_proposal(&authority, "Over-budget grants", steps)
+        .unwrap();
+
+    let exec_epoch = net.proposals[prop_id as usize].execution_epoch;
+    net.advance_to_epoch(exec_epoch);
+    assert!(net.execute_proposal(prop_id).is_err());
+    println!("✓ Execution failed on step 2");
+
+    let proposal = &net.proposals[prop_id as usize];
+    assert_eq!(proposal.status, SimProposalStatus::ExecutionFailed);
+    assert_eq!(proposal.executed_steps, 0);
+    assert!(!proposal.executed);
+
+    // Steps 0 and 1 succeeded in isolation but must have been reverted.
+    assert!(net.governance_parameters.get(&1).is_none());
+    assert_eq!(net.treasury.as_ref().unwrap().balance, 1_000_000);
+    assert_eq!(net.treasury.as_ref().unwrap().total_disbursed, 0);
+    assert_eq!(net.balance(&recipient_a), 0);
+    assert_eq!(net.balance(&recipient_b), 0);
+    println!("✓ Earlier steps reverted — no state changed");
+
+    // A failed proposal cannot be re-executed.
+    assert!(net.execute_proposal(prop_id).is_err());
+    println!("✓ ExecutionFailed proposal cannot be retried");
+}
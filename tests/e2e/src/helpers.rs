@@ -7,19 +7,52 @@
 //! - Epoch management and transitions
 //! - Account state tracking
 //! - Passive staking, governance, treasury, and developer rewards bookkeeping
+//! - State rent archival through the background archival worker
 
 use {
-    rand::Rng,
+    proptest::{
+        arbitrary::{any, Arbitrary},
+        strategy::{BoxedStrategy, Strategy},
+    },
+    rand::{rngs::StdRng, Rng, SeedableRng},
+    serde::{Deserialize, Serialize},
+    solana_account::AccountSharedData,
+    solana_accounts_db::{
+        archival_worker::{
+            ArchivalCandidate, ArchivalTask, ArchivalWorker, DEFAULT_ARCHIVAL_QUEUE_CAPACITY,
+            DEFAULT_ENQUEUE_TIMEOUT,
+        },
+        state_rent_expiry::{ArchiveIndex, StateRentConfig},
+    },
     solana_hash::Hash,
     solana_pubkey::Pubkey,
-    std::collections::HashMap,
+    solana_signature::Signature,
+    std::{
+        collections::{HashMap, HashSet},
+        fs::File,
+        io::{BufRead, BufReader, Write},
+        path::Path,
+        sync::{Arc, Mutex},
+    },
     trv1_consensus_bft::{
-        BftConfig, ConsensusEngine, ConsensusMessage, EngineOutput, ValidatorInfo, ValidatorSet,
+        BftConfig, ConsensusEngine, ConsensusMessage, ConsensusStep, DoubleSignEvidence,
+        EngineOutput, EvidenceCollector, TimeoutScheduler, ValidatorInfo, ValidatorSet,
+    },
+    trv1_developer_rewards_program::{
+        attribution::compute_unit_weighted_shares, constants::COOLDOWN_SLOTS,
     },
     trv1_fee_market::{
         calculator::{calculate_next_base_fee, calculate_transaction_fee},
-        BlockFeeState, FeeMarketConfig,
+        BlockFeeState, FeeMarketConfig, TransactionResources,
     },
+    trv1_governance_program::{
+        constants::{
+            FEE_MARKET_MULTISIG_RESUME_PARAM, FEE_MARKET_PAUSED_PARAM, FEE_MARKET_PAUSE_FEE_PARAM,
+            MAX_FEE_MARKET_PAUSE_FEE, MAX_PROPOSAL_STEPS,
+        },
+        state::ProposalStep,
+    },
+    trv1_monitoring::{default_round_buckets, Counter, Histogram, TRv1Metrics},
 };
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -131,6 +164,8 @@ pub struct SimValidator {
     pub double_signed: bool,
     /// Total slashed from own stake.
     pub total_slashed: u64,
+    /// Blocks this validator proposed that reached quorum.
+    pub blocks_proposed: u64,
 }
 
 impl SimValidator {
@@ -146,6 +181,7 @@ impl SimValidator {
             total_delegation: 0,
             double_signed: false,
             total_slashed: 0,
+            blocks_proposed: 0,
         }
     }
 
@@ -162,6 +198,68 @@ impl SimValidator {
     }
 }
 
+/// Misbehaviour injected into a validator's consensus votes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ByzantineMode {
+    /// Always prevotes nil.
+    NilVote,
+    /// Casts two conflicting prevotes every height.
+    DoubleSign,
+    /// Neither proposes nor votes.
+    Offline,
+    /// Prevotes nil half of the time.
+    RandomEquivocation,
+}
+
+/// Latency model for consensus votes, see [`SimNetwork::set_network_delay`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NetworkDelay {
+    /// Mean vote delivery delay in milliseconds.
+    pub mean_ms: f64,
+    /// Standard deviation of the delivery delay in milliseconds.
+    pub std_ms: f64,
+    /// Probability that a vote is lost outright.
+    pub drop_probability: f64,
+}
+
+impl NetworkDelay {
+    /// Sample a delivery delay from `N(mean_ms, std_ms²)`, floored at zero.
+    fn sample_ms<R: Rng>(&self, rng: &mut R) -> f64 {
+        // Box-Muller; `1 - u` keeps the logarithm's argument in (0, 1].
+        let u1 = 1.0 - rng.random::<f64>();
+        let u2 = rng.random::<f64>();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+        (self.mean_ms + self.std_ms * z).max(0.0)
+    }
+}
+
+/// Consensus counters collected while the network runs.
+pub struct SimConsensusStats {
+    /// Votes that arrived after the round's timeout.
+    pub delayed_votes: Counter,
+    /// Votes lost by the network.
+    pub dropped_votes: Counter,
+    /// Rounds needed to commit each produced block.
+    pub consensus_rounds: Histogram,
+}
+
+impl Default for SimConsensusStats {
+    fn default() -> Self {
+        Self {
+            delayed_votes: Counter::new(
+                "trv1_sim_delayed_votes",
+                "Votes that arrived after the round timeout",
+            ),
+            dropped_votes: Counter::new("trv1_sim_dropped_votes", "Votes lost by the network"),
+            consensus_rounds: Histogram::new(
+                "trv1_consensus_rounds",
+                "Number of consensus rounds needed to finalize a block",
+                default_round_buckets(),
+            ),
+        }
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Simulated Passive Stake
 // ─────────────────────────────────────────────────────────────────────────────
@@ -179,6 +277,57 @@ pub struct SimPassiveStake {
     pub is_permanent: bool,
     pub vote_weight_bps: u16,
     pub active: bool,
+    /// Cumulative rewards accrued over the position's lifetime.
+    pub total_rewards: u64,
+    /// Reinvest epoch rewards into `amount` instead of `unclaimed_rewards`.
+    pub auto_compound: bool,
+}
+
+/// Number of passive staking tiers (no-lock … permanent).
+pub const PASSIVE_STAKE_TIER_COUNT: usize = 6;
+
+/// Index of a lock tier in `PassiveStakeSummary::by_tier`
+/// (0 = no lock, 1 = 30d, 2 = 90d, 3 = 180d, 4 = 360d, 5 = permanent).
+pub fn passive_tier_index(lock_days: u64) -> Option<usize> {
+    use solana_passive_stake_program::constants::*;
+    match lock_days {
+        TIER_NO_LOCK => Some(0),
+        TIER_30_DAY => Some(1),
+        TIER_90_DAY => Some(2),
+        TIER_180_DAY => Some(3),
+        TIER_360_DAY => Some(4),
+        PERMANENT_LOCK_DAYS => Some(5),
+        _ => None,
+    }
+}
+
+/// Per-tier slice of a `PassiveStakeSummary`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TierSummary {
+    /// Principal currently staked in active positions of this tier.
+    pub staked: u64,
+    /// Cumulative rewards accrued by positions of this tier.
+    pub rewards: u64,
+    /// Number of active positions in this tier.
+    pub count: u64,
+}
+
+/// Aggregate view of passive staking at a given epoch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PassiveStakeSummary {
+    pub epoch: u64,
+    /// Principal across all active positions.
+    pub total_staked: u64,
+    /// Cumulative rewards accrued by all positions since genesis.
+    pub total_rewards_paid: u64,
+    /// Cumulative early-unlock penalties burned.
+    pub total_burned_penalties: u64,
+    /// Positions ever created.
+    pub positions_created: u64,
+    /// Positions that have been unlocked (normally or early).
+    pub positions_expired: u64,
+    /// Indexed by [`passive_tier_index`].
+    pub by_tier: [TierSummary; PASSIVE_STAKE_TIER_COUNT],
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -186,13 +335,67 @@ pub struct SimPassiveStake {
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// A simulated transaction for the test network.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimTransaction {
     pub sender: Pubkey,
     pub compute_units: u64,
     pub priority_fee_per_cu: u64,
-    /// Optional: the program this transaction invokes (for developer fee attribution).
-    pub invoked_program: Option<Pubkey>,
+    /// Programs this transaction invokes with the compute units each consumed;
+    /// developer fees are attributed in proportion to those compute units.
+    pub invoked_programs: Vec<(Pubkey, u64)>,
+}
+
+impl Arbitrary for SimTransaction {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Compute units in `1_000..500_000`, a priority fee in `0..10_000`
+    /// lamports per CU, and an invoked program consuming every compute unit
+    /// about half of the time.
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<[u8; 32]>(),
+            1_000..500_000u64,
+            0..10_000u64,
+            proptest::option::of(any::<[u8; 32]>()),
+        )
+            .prop_map(
+                |(sender, compute_units, priority_fee_per_cu, program)| SimTransaction {
+                    sender: Pubkey::new_from_array(sender),
+                    compute_units,
+                    priority_fee_per_cu,
+                    invoked_programs: program
+                        .map(|program| (Pubkey::new_from_array(program), compute_units))
+                        .into_iter()
+                        .collect(),
+                },
+            )
+            .boxed()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Event log (record / replay)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// One `produce_block` call, as written to the event log by
+/// [`SimNetwork::record_mode`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimEvent {
+    /// Slot the block was produced at.
+    pub slot: u64,
+    pub transactions: Vec<SimTransaction>,
+    /// Seed the network RNG was reset to before producing the block.
+    pub rng_seed: u64,
+}
+
+/// First line of an event log: the setup the recorded blocks start from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SimLogHeader {
+    validator_stakes: Vec<(Pubkey, u64)>,
+    byzantine_modes: Vec<(Pubkey, ByzantineMode)>,
+    #[serde(default)]
+    network_delay: Option<NetworkDelay>,
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -210,6 +413,8 @@ pub enum SimProposalStatus {
     Executed,
     Cancelled,
     Expired,
+    /// A step failed during execution; no step was applied.
+    ExecutionFailed,
 }
 
 /// A governance proposal in the simulated network.
@@ -228,6 +433,10 @@ pub struct SimProposal {
     pub veto_votes: u64,
     pub is_emergency_unlock: bool,
     pub executed: bool,
+    /// Ordered actions applied on execution.
+    pub steps: Vec<ProposalStep>,
+    /// Steps applied by the last execution (0 after a reverted execution).
+    pub executed_steps: u8,
 }
 
 /// Governance configuration.
@@ -288,28 +497,63 @@ pub struct SimNetwork {
 
     // ── Passive staking ──────────────────────────────────────────────────
     pub passive_stakes: Vec<SimPassiveStake>,
+    /// Cumulative passive staking rewards accrued across all positions.
+    pub passive_rewards_paid: u64,
+    /// Cumulative early-unlock penalties burned.
+    pub passive_penalties_burned: u64,
+    /// Lamports routed to the passive staking reward pool from priority
+    /// fees (`FeeMarketConfig::priority_fee_passive_stake_bps`).
+    pub passive_stake_pool: u64,
 
     // ── Governance ───────────────────────────────────────────────────────
     pub governance: Option<SimGovernanceConfig>,
     pub proposals: Vec<SimProposal>,
+    /// param_id → value, as set by executed `ParameterChange` steps.
+    pub governance_parameters: HashMap<u32, u64>,
 
     // ── Treasury ─────────────────────────────────────────────────────────
     pub treasury: Option<SimTreasury>,
 
+    // ── State rent ───────────────────────────────────────────────────────
+    /// Data accounts the archival worker evaluates at each epoch boundary.
+    pub data_accounts: HashMap<Pubkey, ArchivalCandidate>,
+    /// Background archival thread; `None` until `enable_state_rent_archival`.
+    pub archival_worker: Option<ArchivalWorker>,
+
     // ── Developer rewards tracking ───────────────────────────────────────
     /// program_id → accumulated developer fees.
     pub developer_reward_accounts: HashMap<Pubkey, u64>,
+    /// program_id → registration slot. Registered programs earn nothing
+    /// until `COOLDOWN_SLOTS` have passed, matching the on-chain check.
+    pub developer_registrations: HashMap<Pubkey, u64>,
 
     // ── Consensus tracking ───────────────────────────────────────────────
+    pub bft_config: BftConfig,
     pub blocks_produced: u64,
     pub epoch_history: Vec<EpochSummary>,
+    /// Pairs of validator groups that cannot reach each other.
+    pub partitions: Vec<(HashSet<Pubkey>, HashSet<Pubkey>)>,
+    pub byzantine_modes: HashMap<Pubkey, ByzantineMode>,
+    /// Sees every prevote cast, so double-signs are recorded as evidence.
+    pub evidence_collector: EvidenceCollector,
+    /// Vote latency model; `None` delivers every vote instantly.
+    pub network_delay: Option<NetworkDelay>,
+    pub stats: SimConsensusStats,
+    /// Node metrics; `on_epoch_transition` starts a new epoch in them, so
+    /// `metrics.epoch_history` holds per-epoch blocks and fees.
+    pub metrics: TRv1Metrics,
+    /// Source of all simulation randomness. Reseeded before every block so
+    /// a recorded run can be replayed exactly.
+    pub rng: StdRng,
+    /// Event log that produced blocks are appended to, see `record_mode`.
+    pub event_log: Option<File>,
 
     // ── Unix-time simulation ─────────────────────────────────────────────
     pub simulated_unix_time: i64,
 }
 
 /// Summary of a completed epoch.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EpochSummary {
     pub epoch: u64,
     pub slots_in_epoch: u64,
@@ -333,6 +577,9 @@ impl SimNetwork {
             balances.insert(v.pubkey, 100_000_000_000); // 100 SOL operating
         }
 
+        let metrics = TRv1Metrics::new();
+        metrics.begin_epoch(0);
+
         Self {
             validators,
             current_slot: 0,
@@ -347,12 +594,28 @@ impl SimNetwork {
             validator_fees: 0,
             developer_fees: 0,
             passive_stakes: Vec::new(),
+            passive_rewards_paid: 0,
+            passive_stake_pool: 0,
+            passive_penalties_burned: 0,
             governance: None,
             proposals: Vec::new(),
+            governance_parameters: HashMap::new(),
             treasury: None,
+            data_accounts: HashMap::new(),
+            archival_worker: None,
             developer_reward_accounts: HashMap::new(),
+            developer_registrations: HashMap::new(),
+            bft_config: BftConfig::default(),
             blocks_produced: 0,
             epoch_history: Vec::new(),
+            partitions: Vec::new(),
+            byzantine_modes: HashMap::new(),
+            evidence_collector: EvidenceCollector::new(),
+            network_delay: None,
+            stats: SimConsensusStats::default(),
+            metrics,
+            rng: StdRng::from_os_rng(),
+            event_log: None,
             simulated_unix_time: 1_700_000_000, // ~Nov 2023
         }
     }
@@ -456,10 +719,161 @@ impl SimNetwork {
         }
     }
 
+    // ── Network partitions ───────────────────────────────────────────────
+
+    /// Block all communication between `group_a` and `group_b`. Validators
+    /// in neither group still reach everyone.
+    pub fn partition(&mut self, group_a: &[Pubkey], group_b: &[Pubkey]) {
+        self.partitions.push((
+            group_a.iter().copied().collect(),
+            group_b.iter().copied().collect(),
+        ));
+        println!(
+            "  [PARTITION] {} validators cut off from {} validators",
+            group_a.len(),
+            group_b.len()
+        );
+    }
+
+    /// Whether a message from `from` can reach `to`.
+    pub fn is_reachable(&self, from: &Pubkey, to: &Pubkey) -> bool {
+        !self.partitions.iter().any(|(a, b)| {
+            (a.contains(from) && b.contains(to)) || (b.contains(from) && a.contains(to))
+        })
+    }
+
+    /// Remove all partitions.
+    pub fn heal_partition(&mut self) {
+        self.partitions.clear();
+        println!("  [PARTITION] Network healed");
+    }
+
+    // ── Byzantine behaviour ──────────────────────────────────────────────
+
+    /// Make `pubkey` misbehave as described by `mode` from the next block on.
+    pub fn set_byzantine_behavior(&mut self, pubkey: &Pubkey, mode: ByzantineMode) {
+        self.byzantine_modes.insert(*pubkey, mode);
+        println!("  [BYZANTINE] Validator {} set to {:?}", pubkey, mode);
+    }
+
+    /// Double-sign evidence gathered from the prevotes cast so far.
+    pub fn collected_evidence(&self) -> Vec<DoubleSignEvidence> {
+        self.evidence_collector.evidence().to_vec()
+    }
+
+    // ── Network latency ──────────────────────────────────────────────────
+
+    /// Delay every consensus vote according to `delay` from the next block
+    /// on. Heights then run up to `max_rounds_per_height` rounds, since a
+    /// vote that misses one round's timeout may make the next.
+    pub fn set_network_delay(&mut self, delay: NetworkDelay) {
+        self.network_delay = Some(delay);
+        println!(
+            "  [NETWORK] Vote delay {:.0}±{:.0}ms, drop probability {}",
+            delay.mean_ms, delay.std_ms, delay.drop_probability
+        );
+    }
+
+    /// Whether a vote cast in `round` reaches the proposer before the
+    /// prevote timeout. Always true without a network delay.
+    fn vote_arrives_in_time(&mut self, round: u32) -> bool {
+        let Some(delay) = self.network_delay else {
+            return true;
+        };
+        if self.rng.random_bool(delay.drop_probability) {
+            self.stats.dropped_votes.inc();
+            return false;
+        }
+        let timeout = TimeoutScheduler::new(self.bft_config.clone())
+            .timeout_for(ConsensusStep::Prevote, round);
+        if delay.sample_ms(&mut self.rng) > timeout.as_millis() as f64 {
+            self.stats.delayed_votes.inc();
+            return false;
+        }
+        true
+    }
+
+    /// Cast the active set's prevotes for `block_hash` in `round` and return
+    /// whether the ones reaching `proposer` in time carry a quorum of the
+    /// active stake.
+    ///
+    /// Every delivered prevote passes through the evidence collector.
+    /// Equivocating validators are not counted toward the quorum.
+    fn tally_prevotes(
+        &mut self,
+        active_set: &ValidatorSet,
+        proposer: &Pubkey,
+        block_hash: Hash,
+        round: u32,
+    ) -> bool {
+        let height = self.current_slot;
+        let mut prevote_stake = 0u64;
+        for v in active_set.iter() {
+            if !self.is_reachable(&v.pubkey, proposer) {
+                continue;
+            }
+            let mode = self.byzantine_modes.get(&v.pubkey).copied();
+            if mode == Some(ByzantineMode::Offline) || !self.vote_arrives_in_time(round) {
+                continue;
+            }
+            let voted_hash = match mode {
+                Some(ByzantineMode::NilVote) => None,
+                Some(ByzantineMode::RandomEquivocation) if self.rng.random_bool(0.5) => None,
+                _ => Some(block_hash),
+            };
+            let prevote = |block_hash| ConsensusMessage::Prevote {
+                height,
+                round,
+                block_hash,
+                voter: v.pubkey,
+                signature: Signature::default(),
+            };
+            self.evidence_collector
+                .check_and_record(&prevote(voted_hash));
+            if mode == Some(ByzantineMode::DoubleSign) {
+                let conflicting = prevote(Some(Hash::new_unique()));
+                if self
+                    .evidence_collector
+                    .check_and_record(&conflicting)
+                    .is_some()
+                {
+                    println!(
+                        "  [EVIDENCE] Validator {} double-signed at height {}",
+                        v.pubkey, height
+                    );
+                }
+                continue;
+            }
+            if voted_hash.is_some() {
+                prevote_stake += v.stake;
+            }
+        }
+        prevote_stake >= active_set.quorum_stake(self.bft_config.finality_threshold)
+    }
+
     // ── Block production ─────────────────────────────────────────────────
 
     /// Produce a single block (slot). Selects proposer round-robin by stake weight.
+    ///
+    /// The slot is skipped, and 0 returned, when the proposer is offline or
+    /// its block does not gather a prevote quorum. With a network delay set,
+    /// a failed round is retried up to `max_rounds_per_height` times.
     pub fn produce_block(&mut self, transactions: &[SimTransaction]) -> u64 {
+        let rng_seed = self.rng.random();
+        if let Some(log) = self.event_log.as_mut() {
+            let event = SimEvent {
+                slot: self.current_slot + 1,
+                transactions: transactions.to_vec(),
+                rng_seed,
+            };
+            let line = serde_json::to_string(&event).expect("SimEvent serializes");
+            writeln!(log, "{line}").expect("failed to write event log");
+        }
+        self.produce_block_seeded(transactions, rng_seed)
+    }
+
+    fn produce_block_seeded(&mut self, transactions: &[SimTransaction], rng_seed: u64) -> u64 {
+        self.rng = StdRng::seed_from_u64(rng_seed);
         let prev_epoch = self.current_epoch;
         self.current_slot += 1;
         self.current_epoch = self.current_slot / self.slots_per_epoch;
@@ -495,16 +909,35 @@ impl SimNetwork {
             }
         }
 
+        if self.byzantine_modes.get(&proposer_pk) == Some(&ByzantineMode::Offline) {
+            return 0;
+        }
+        let max_rounds = if self.network_delay.is_some() {
+            self.bft_config.max_rounds_per_height
+        } else {
+            1
+        };
+        let block_hash = Hash::new_unique();
+        let Some(round) = (0..max_rounds)
+            .find(|&round| self.tally_prevotes(&active_set, &proposer_pk, block_hash, round))
+        else {
+            return 0;
+        };
+        self.stats.consensus_rounds.observe((round + 1) as f64);
+
         // Process transactions and collect fees.
         let mut block_cu = 0u64;
         let mut block_fees = 0u64;
+        let mut block_stake_pool_fees = 0u64;
         for tx in transactions {
             let fee = calculate_transaction_fee(
+                &self.fee_config,
                 self.fee_state.base_fee_per_cu,
                 tx.priority_fee_per_cu,
-                tx.compute_units,
+                &TransactionResources::compute_only(tx.compute_units),
             );
-            block_fees += fee.total_fee;
+            block_fees += fee.total_fee - fee.priority_fee_stake_pool;
+            block_stake_pool_fees += fee.priority_fee_stake_pool;
             block_cu += tx.compute_units;
 
             // Deduct from sender balance.
@@ -512,26 +945,96 @@ impl SimNetwork {
             *sender_balance = sender_balance.saturating_sub(fee.total_fee);
         }
 
-        // Record gas usage.
-        self.fee_state.record_gas(block_cu);
+        // Record gas usage.  Simulated transactions do not write accounts.
+        self.fee_state.record_gas(block_cu, 0);
 
         // Distribute fees according to schedule.
-        self.distribute_fees(block_fees, &proposer_pk, transactions);
+        self.distribute_fees(block_fees, block_stake_pool_fees, &proposer_pk, transactions);
 
         // Advance fee state for next block.
         let next_base_fee = calculate_next_base_fee(&self.fee_config, &self.fee_state);
-        self.fee_state = self.fee_state.next_block(next_base_fee, self.current_slot);
+        self.fee_state =
+            self.fee_state
+                .next_block(&self.fee_config, next_base_fee, self.current_slot);
 
         self.blocks_produced += 1;
+        self.metrics.blocks_produced.inc();
 
         // Reward the proposer for producing a block.
         if let Some(v) = self.validator_mut(&proposer_pk) {
             // Base block reward: a small fixed amount per block.
             let block_reward = 1_000_000; // 0.001 SOL
             v.rewards_earned += block_reward;
+            v.blocks_proposed += 1;
+        }
+
+        block_fees + block_stake_pool_fees
+    }
+
+    // ── Record / replay ──────────────────────────────────────────────────
+
+    /// Start writing every `produce_block` call to a JSON lines event log
+    /// at `path`, replacing any existing file.
+    ///
+    /// The log starts with the current validators, Byzantine modes and
+    /// network delay, so call this once setup is done and before producing
+    /// blocks. Other setup (partitions, passive stakes, governance) is not
+    /// recorded.
+    pub fn record_mode(&mut self, path: &Path) {
+        let header = SimLogHeader {
+            validator_stakes: self
+                .validators
+                .iter()
+                .map(|v| (v.pubkey, v.stake))
+                .collect(),
+            byzantine_modes: self
+                .byzantine_modes
+                .iter()
+                .map(|(pk, mode)| (*pk, *mode))
+                .collect(),
+            network_delay: self.network_delay,
+        };
+        let mut log = File::create(path).expect("failed to create event log");
+        let line = serde_json::to_string(&header).expect("SimLogHeader serializes");
+        writeln!(log, "{line}").expect("failed to write event log");
+        self.event_log = Some(log);
+    }
+
+    /// Rebuild a network from the event log at `path` and replay every
+    /// recorded block.
+    ///
+    /// Each block runs with its recorded transactions and RNG seed, so the
+    /// result matches the recorded run regardless of any randomness drawn
+    /// in between.
+    pub fn replay_mode(path: &Path) -> Self {
+        let file = File::open(path).expect("failed to open event log");
+        let mut lines = BufReader::new(file)
+            .lines()
+            .map(|line| line.expect("failed to read event log"));
+        let header: SimLogHeader = serde_json::from_str(&lines.next().expect("event log is empty"))
+            .expect("malformed event log header");
+
+        let mut network = Self::new(&header.validator_stakes);
+        network.byzantine_modes = header.byzantine_modes.into_iter().collect();
+        network.network_delay = header.network_delay;
+        for line in lines {
+            let event: SimEvent = serde_json::from_str(&line).expect("malformed event log entry");
+            if event.slot > network.current_slot + 1 {
+                network.warp_to_slot(event.slot - 1);
+            }
+            network.produce_block_seeded(&event.transactions, event.rng_seed);
         }
+        network
+    }
 
-        block_fees
+    /// Jump to `slot` without producing the skipped blocks. The next
+    /// `produce_block` call produces slot `slot + 1`. Epoch transitions in
+    /// the skipped range are not processed; use this only to exercise
+    /// slot-based timers.
+    pub fn warp_to_slot(&mut self, slot: u64) {
+        assert!(slot >= self.current_slot, "cannot warp backwards");
+        self.current_slot = slot;
+        self.current_epoch = slot / self.slots_per_epoch;
     }
 
     /// Produce N empty blocks (fast-forward).
@@ -559,9 +1062,14 @@ impl SimNetwork {
     fn distribute_fees(
         &mut self,
         total_fees: u64,
+        stake_pool_fees: u64,
         proposer: &Pubkey,
         transactions: &[SimTransaction],
     ) {
+        // The passive staking pool's share of priority fees bypasses the
+        // 4-way split.
+        self.total_fees_collected += stake_pool_fees;
+        self.passive_stake_pool += stake_pool_fees;
         if total_fees == 0 {
             return;
         }
@@ -571,13 +1079,18 @@ impl SimNetwork {
 
         let burn = total_fees * split.burn_bps / BPS_DENOM;
         let to_validator = total_fees * split.validator_bps / BPS_DENOM;
-        let to_treasury = total_fees * split.treasury_bps / BPS_DENOM;
         let to_developer = total_fees * split.developer_bps / BPS_DENOM;
+        // Treasury absorbs the rounding dust so every lamport is accounted for.
+        let to_treasury = total_fees - burn - to_validator - to_developer;
 
         self.total_burned += burn;
         self.validator_fees += to_validator;
         self.treasury_fees += to_treasury;
         self.developer_fees += to_developer;
+        self.metrics.total_fees_burned.add(burn);
+        self.metrics.total_fees_validator.add(to_validator);
+        self.metrics.total_fees_treasury.add(to_treasury);
+        self.metrics.total_fees_dev.add(to_developer);
 
         // Credit the block proposer their validator share.
         if let Some(v) = self.validator_mut(proposer) {
@@ -590,16 +1103,17 @@ impl SimNetwork {
             t.total_received += to_treasury;
         }
 
-        // Attribute developer fees to invoked programs.
-        let programs_in_block: Vec<Pubkey> = transactions
+        // Attribute developer fees to invoked programs, weighted by the
+        // compute units each consumed.
+        let programs_in_block: Vec<(Pubkey, u64)> = transactions
             .iter()
-            .filter_map(|tx| tx.invoked_program)
+            .flat_map(|tx| tx.invoked_programs.iter().copied())
             .collect();
-        if !programs_in_block.is_empty() {
-            let per_program = to_developer / programs_in_block.len() as u64;
-            for prog in &programs_in_block {
-                *self.developer_reward_accounts.entry(*prog).or_insert(0) += per_program;
+        for (prog, share) in compute_unit_weighted_shares(to_developer, &programs_in_block) {
+            if self.developer_cooldown_remaining(&prog) > 0 {
+                continue;
             }
+            *self.developer_reward_accounts.entry(prog).or_insert(0) += share;
         }
     }
 
@@ -635,7 +1149,16 @@ impl SimNetwork {
         }
 
         // Calculate passive staking rewards.
-        self.calculate_passive_staking_rewards(completed_epoch + 1, validator_reward_rate_bps);
+        let passive_rewards =
+            self.calculate_passive_staking_rewards(completed_epoch + 1, validator_reward_rate_bps);
+        self.passive_rewards_paid += passive_rewards;
+
+        // Votes from past epochs can no longer conflict with new ones.
+        self.evidence_collector
+            .prune(self.current_slot.saturating_sub(self.slots_per_epoch));
+
+        // Hand the data accounts to the archival worker for the new epoch.
+        self.enqueue_archival_candidates(completed_epoch + 1);
 
         let summary = EpochSummary {
             epoch: completed_epoch,
@@ -646,6 +1169,7 @@ impl SimNetwork {
             total_stake: total_active_stake,
         };
         self.epoch_history.push(summary);
+        self.metrics.begin_epoch(completed_epoch + 1);
     }
 
     // ── Passive staking ──────────────────────────────────────────────────
@@ -684,6 +1208,8 @@ impl SimNetwork {
             is_permanent,
             vote_weight_bps,
             active: true,
+            total_rewards: 0,
+            auto_compound: false,
         };
 
         self.passive_stakes.push(stake);
@@ -696,7 +1222,9 @@ impl SimNetwork {
     }
 
     /// Calculate passive staking rewards for all active positions.
-    fn calculate_passive_staking_rewards(&mut self, current_epoch: u64, validator_rate_bps: u64) {
+    /// Returns the total rewards accrued across all positions.
+    fn calculate_passive_staking_rewards(&mut self, current_epoch: u64, validator_rate_bps: u64) -> u64 {
+        let mut accrued = 0u64;
         for stake in self.passive_stakes.iter_mut() {
             if !stake.active || current_epoch <= stake.last_reward_epoch {
                 continue;
@@ -716,9 +1244,50 @@ impl SimNetwork {
             let reward_per_epoch = amount * v_rate * t_rate / denom;
             let total_new = reward_per_epoch * epochs_elapsed as u128;
 
-            stake.unclaimed_rewards += total_new as u64;
+            // Mirrors the program: auto-compounding positions reinvest, except
+            // permanent ones that would pass the protocol maximum.
+            let compound = stake.auto_compound
+                && (!stake.is_permanent
+                    || stake.amount + total_new as u64
+                        <= solana_passive_stake_program::constants::MAX_STAKE_AMOUNT);
+            if compound {
+                stake.amount += total_new as u64;
+            } else {
+                stake.unclaimed_rewards += total_new as u64;
+            }
+            stake.total_rewards += total_new as u64;
             stake.last_reward_epoch = current_epoch;
+            accrued += total_new as u64;
         }
+        accrued
+    }
+
+    /// Aggregate the current passive staking state, overall and per tier.
+    pub fn epoch_passive_stake_summary(&self) -> PassiveStakeSummary {
+        let mut summary = PassiveStakeSummary {
+            epoch: self.current_epoch,
+            total_rewards_paid: self.passive_rewards_paid,
+            total_burned_penalties: self.passive_penalties_burned,
+            positions_created: self.passive_stakes.len() as u64,
+            ..PassiveStakeSummary::default()
+        };
+
+        for stake in &self.passive_stakes {
+            let tier = passive_tier_index(stake.lock_days).map(|i| &mut summary.by_tier[i]);
+            if let Some(tier) = tier {
+                tier.rewards += stake.total_rewards;
+                if stake.active {
+                    tier.staked += stake.amount;
+                    tier.count += 1;
+                }
+            }
+            if stake.active {
+                summary.total_staked += stake.amount;
+            } else {
+                summary.positions_expired += 1;
+            }
+        }
+        summary
     }
 
     /// Claim rewards from a passive stake position.
@@ -735,6 +1304,29 @@ impl SimNetwork {
         rewards
     }
 
+    /// Turn automatic compounding of epoch rewards on or off for a position.
+    pub fn set_passive_auto_compound(&mut self, stake_idx: usize, auto_compound: bool) {
+        self.passive_stakes[stake_idx].auto_compound = auto_compound;
+    }
+
+    /// Reinvest a position's unclaimed rewards into its principal.
+    pub fn compound_passive_rewards(&mut self, stake_idx: usize) -> Result<u64, &'static str> {
+        let stake = &mut self.passive_stakes[stake_idx];
+        let rewards = stake.unclaimed_rewards;
+        if stake.is_permanent
+            && stake.amount + rewards > solana_passive_stake_program::constants::MAX_STAKE_AMOUNT
+        {
+            return Err("Compounding would exceed the maximum stake amount");
+        }
+        stake.amount += rewards;
+        stake.unclaimed_rewards = 0;
+        println!(
+            "  [COMPOUND] Stake #{}: {} lamports reinvested, position now {}",
+            stake_idx, rewards, stake.amount
+        );
+        Ok(rewards)
+    }
+
     /// Unlock a passive stake after lock expiry (returns principal).
     pub fn unlock_passive_stake(&mut self, stake_idx: usize) -> Result<u64, &'static str> {
         let stake = &self.passive_stakes[stake_idx];
@@ -782,6 +1374,7 @@ impl SimNetwork {
 
         self.passive_stakes[stake_idx].active = false;
         self.total_burned += penalty; // Penalty is burned.
+        self.passive_penalties_burned += penalty;
 
         *self.balances.entry(authority).or_insert(0) += returned;
         println!(
@@ -828,6 +1421,39 @@ impl SimNetwork {
         title: &str,
         is_emergency_unlock: bool,
     ) -> Result<u64, &'static str> {
+        let steps = if is_emergency_unlock {
+            vec![ProposalStep::EmergencyUnlock {
+                target_account: Pubkey::new_unique(),
+            }]
+        } else {
+            vec![ProposalStep::TextProposal]
+        };
+        self.push_proposal(proposer, title, steps)
+    }
+
+    /// Create a multi-step proposal whose steps are applied atomically on
+    /// execution.
+    pub fn create_multi_step_proposal(
+        &mut self,
+        proposer: &Pubkey,
+        title: &str,
+        steps: Vec<ProposalStep>,
+    ) -> Result<u64, &'static str> {
+        if steps.is_empty() || steps.len() > MAX_PROPOSAL_STEPS {
+            return Err("Multi-step proposal must contain between 1 and 5 steps");
+        }
+        self.push_proposal(proposer, title, steps)
+    }
+
+    fn push_proposal(
+        &mut self,
+        proposer: &Pubkey,
+        title: &str,
+        steps: Vec<ProposalStep>,
+    ) -> Result<u64, &'static str> {
+        let is_emergency_unlock = steps
+            .iter()
+            .any(|step| matches!(step, ProposalStep::EmergencyUnlock { .. }));
         let gov = self.governance.as_mut().ok_or("Governance not initialized")?;
 
         if !gov.is_active && *proposer != gov.authority {
@@ -860,6 +1486,8 @@ impl SimNetwork {
             veto_votes: 0,
             is_emergency_unlock,
             executed: false,
+            steps,
+            executed_steps: 0,
         });
 
         println!(
@@ -965,13 +1593,17 @@ impl SimNetwork {
     }
 
     /// Execute a timelocked proposal.
+    ///
+    /// Steps are applied in order against a scratch copy of the affected
+    /// state; the copy is committed only if every step succeeds.
     pub fn execute_proposal(&mut self, proposal_id: u64) -> Result<(), &'static str> {
-        let proposal = self
+        let idx = self
             .proposals
-            .iter_mut()
-            .find(|p| p.id == proposal_id)
+            .iter()
+            .position(|p| p.id == proposal_id)
             .ok_or("Proposal not found")?;
 
+        let proposal = &self.proposals[idx];
         if proposal.status != SimProposalStatus::Timelocked {
             return Err("Proposal is not Timelocked");
         }
@@ -979,8 +1611,61 @@ impl SimNetwork {
             return Err("Timelock has not expired");
         }
 
+        let mut treasury = self.treasury.clone();
+        let mut balances = self.balances.clone();
+        let mut parameters = self.governance_parameters.clone();
+
+        for (step_index, step) in proposal.steps.iter().enumerate() {
+            let result = match step {
+                ProposalStep::ParameterChange {
+                    param_id,
+                    new_value,
+                } => {
+                    parameters.insert(*param_id, *new_value);
+                    Ok(())
+                }
+                ProposalStep::TreasurySpend {
+                    recipient, amount, ..
+                } => match treasury.as_mut() {
+                    None => Err("Treasury not initialized"),
+                    Some(t) if t.balance < *amount => Err("Insufficient treasury balance"),
+                    Some(t) => {
+                        t.balance -= amount;
+                        t.total_disbursed += amount;
+                        *balances.entry(*recipient).or_insert(0) += amount;
+                        Ok(())
+                    }
+                },
+                ProposalStep::EmergencyUnlock { .. }
+                | ProposalStep::ProgramUpgrade { .. }
+                | ProposalStep::FeatureToggle { .. }
+                | ProposalStep::TextProposal
+                | ProposalStep::GovernanceConfigUpdate { .. }
+                | ProposalStep::TreasuryAllowanceUpdate { .. }
+                | ProposalStep::GovernanceSupplyUpdate { .. } => Ok(()),
+            };
+
+            if let Err(reason) = result {
+                let proposal = &mut self.proposals[idx];
+                proposal.status = SimProposalStatus::ExecutionFailed;
+                proposal.executed_steps = 0;
+                println!(
+                    "  [GOV] Proposal #{} FAILED at step {}: {} (all steps reverted)",
+                    proposal_id, step_index, reason
+                );
+                return Err("Multi-step execution failed");
+            }
+        }
+
+        self.treasury = treasury;
+        self.balances = balances;
+        self.governance_parameters = parameters;
+        self.apply_fee_market_pause();
+
+        let proposal = &mut self.proposals[idx];
         proposal.status = SimProposalStatus::Executed;
         proposal.executed = true;
+        proposal.executed_steps = proposal.steps.len() as u8;
         println!("  [GOV] Proposal #{} EXECUTED", proposal_id);
         Ok(())
     }
@@ -1008,6 +1693,60 @@ impl SimNetwork {
         }
     }
 
+    /// Pause the fee market at `fixed_base_fee` (emergency multisig only).
+    /// Blocks produced after the current one are priced at the fixed fee
+    /// whatever their utilization.
+    pub fn pause_fee_market(&mut self, signer: &Pubkey, fixed_base_fee: u64) -> Result<(), &'static str> {
+        let gov = self.governance.as_ref().ok_or("Governance not initialized")?;
+        if *signer != gov.emergency_multisig {
+            return Err("Only emergency multisig can pause the fee market");
+        }
+        if fixed_base_fee == 0 || fixed_base_fee > MAX_FEE_MARKET_PAUSE_FEE {
+            return Err("Fixed base fee out of range");
+        }
+        self.governance_parameters.insert(FEE_MARKET_PAUSED_PARAM, 1);
+        self.governance_parameters.insert(FEE_MARKET_PAUSE_FEE_PARAM, fixed_base_fee);
+        self.apply_fee_market_pause();
+        println!("  [GOV] FeeMarket: PAUSED at fee={}", fixed_base_fee);
+        Ok(())
+    }
+
+    /// Lift a fee market pause (emergency multisig only, and only once a
+    /// proposal has enabled multisig resumes).  Otherwise a `ParameterChange`
+    /// proposal setting `FEE_MARKET_PAUSED_PARAM` to 0 lifts it.
+    pub fn resume_fee_market(&mut self, signer: &Pubkey) -> Result<(), &'static str> {
+        let gov = self.governance.as_ref().ok_or("Governance not initialized")?;
+        if *signer != gov.emergency_multisig {
+            return Err("Only emergency multisig can resume the fee market");
+        }
+        if self.governance_parameters.get(&FEE_MARKET_MULTISIG_RESUME_PARAM) != Some(&1) {
+            return Err("Resuming the fee market requires a governance proposal");
+        }
+        if !self.fee_state.paused {
+            return Err("Fee market is not paused");
+        }
+        self.governance_parameters.insert(FEE_MARKET_PAUSED_PARAM, 0);
+        self.apply_fee_market_pause();
+        println!("  [GOV] FeeMarket: RESUMED");
+        Ok(())
+    }
+
+    /// Bring `fee_state` in line with the fee market pause parameters.
+    fn apply_fee_market_pause(&mut self) {
+        match self.governance_parameters.get(&FEE_MARKET_PAUSED_PARAM) {
+            Some(1) => {
+                let fixed_base_fee = self
+                    .governance_parameters
+                    .get(&FEE_MARKET_PAUSE_FEE_PARAM)
+                    .copied()
+                    .unwrap_or(self.fee_state.base_fee_per_cu);
+                self.fee_state.pause(fixed_base_fee);
+            }
+            Some(_) if self.fee_state.paused => self.fee_state.resume(),
+            _ => {}
+        }
+    }
+
     // ── Treasury ─────────────────────────────────────────────────────────
 
     /// Initialize the treasury.
@@ -1065,6 +1804,103 @@ impl SimNetwork {
         Ok(())
     }
 
+    // ── Developer rewards ────────────────────────────────────────────────
+
+    /// Register `program_id` for developer rewards at the current slot.
+    pub fn register_developer_program(&mut self, program_id: Pubkey) {
+        self.developer_registrations.insert(program_id, self.current_slot);
+        println!(
+            "  [DEV-REWARDS] Registered program {} at slot {}",
+            program_id, self.current_slot
+        );
+    }
+
+    /// Slots left in `program_id`'s registration cooldown (mirrors
+    /// `ProgramRevenueConfig::remaining_cooldown_slots`). Unregistered
+    /// programs are not subject to the cooldown in the simulation.
+    pub fn developer_cooldown_remaining(&self, program_id: &Pubkey) -> u64 {
+        self.developer_registrations
+            .get(program_id)
+            .map(|registration_slot| {
+                COOLDOWN_SLOTS.saturating_sub(self.current_slot.saturating_sub(*registration_slot))
+            })
+            .unwrap_or(0)
+    }
+
+    // ── State rent ───────────────────────────────────────────────────────
+
+    /// Start the archival worker. From the next epoch boundary on, every
+    /// data account not yet archived is enqueued for it to evaluate.
+    pub fn enable_state_rent_archival(&mut self, config: StateRentConfig) {
+        self.archival_worker = Some(ArchivalWorker::start(
+            config,
+            Arc::new(Mutex::new(ArchiveIndex::new())),
+            DEFAULT_ARCHIVAL_QUEUE_CAPACITY,
+            DEFAULT_ENQUEUE_TIMEOUT,
+        ));
+        println!("  [STATE-RENT] Archival worker started");
+    }
+
+    /// Create a data account at the current slot.
+    pub fn create_data_account(&mut self, pubkey: Pubkey, account: AccountSharedData) {
+        self.data_accounts.insert(
+            pubkey,
+            ArchivalCandidate {
+                pubkey,
+                account,
+                last_active_slot: self.current_slot,
+                creation_slot: self.current_slot,
+            },
+        );
+    }
+
+    /// Index the archival worker registers archived accounts in.
+    pub fn archive_index(&self) -> Option<&Arc<Mutex<ArchiveIndex>>> {
+        self.archival_worker
+            .as_ref()
+            .map(|worker| worker.archive_index())
+    }
+
+    /// Stop the archival worker once it has finished the queued tasks.
+    pub fn stop_archival_worker(&mut self) {
+        if let Some(worker) = self.archival_worker.take() {
+            worker.join().expect("archival worker panicked");
+            println!("  [STATE-RENT] Archival worker stopped");
+        }
+    }
+
+    fn enqueue_archival_candidates(&self, epoch: u64) {
+        let Some(worker) = self.archival_worker.as_ref() else {
+            return;
+        };
+        let candidates: Vec<ArchivalCandidate> = {
+            let index = worker.archive_index().lock().unwrap();
+            self.data_accounts
+                .values()
+                .filter(|candidate| !index.is_archived(&candidate.pubkey))
+                .cloned()
+                .collect()
+        };
+        if candidates.is_empty() {
+            return;
+        }
+
+        let count = candidates.len();
+        let task = ArchivalTask {
+            current_slot: self.current_slot,
+            current_epoch: epoch,
+            candidates,
+        };
+        match worker.enqueue(task) {
+            Ok(()) => println!(
+                "  [STATE-RENT] Enqueued {} accounts for archival in epoch {}",
+                count, epoch
+            ),
+            // The accounts are enqueued again at the next boundary.
+            Err(_) => println!("  [WARN] Archival queue full, skipping epoch {}", epoch),
+        }
+    }
+
     // ── Utility ──────────────────────────────────────────────────────────
 
     /// Get or create a balance entry.
@@ -1087,12 +1923,13 @@ impl SimNetwork {
             self.fee_state.base_fee_per_cu
         );
         println!(
-            "Fees: collected={} burned={} treasury={} validator={} developer={}",
+            "Fees: collected={} burned={} treasury={} validator={} developer={} passive_stake_pool={}",
             self.total_fees_collected,
             self.total_burned,
             self.treasury_fees,
             self.validator_fees,
-            self.developer_fees
+            self.developer_fees,
+            self.passive_stake_pool
         );
         println!("Validators:");
         for v in &self.validators {
@@ -1150,7 +1987,7 @@ pub fn random_transactions(n: usize, senders: &[Pubkey]) -> Vec<SimTransaction>
             sender: senders[rng.random_range(0..senders.len())],
             compute_units: rng.random_range(10_000..500_000),
             priority_fee_per_cu: rng.random_range(0..1_000),
-            invoked_program: None,
+            invoked_programs: Vec::new(),
         })
         .collect()
 }
@@ -1163,11 +2000,14 @@ pub fn program_transactions(
 ) -> Vec<SimTransaction> {
     let mut rng = rand::rng();
     (0..n)
-        .map(|_| SimTransaction {
-            sender,
-            compute_units: rng.random_range(50_000..200_000),
-            priority_fee_per_cu: rng.random_range(100..500),
-            invoked_program: Some(program),
+        .map(|_| {
+            let compute_units = rng.random_range(50_000..200_000);
+            SimTransaction {
+                sender,
+                compute_units,
+                priority_fee_per_cu: rng.random_range(100..500),
+                invoked_programs: vec![(program, compute_units)],
+            }
         })
         .collect()
 }
@@ -0,0 +1,82 @@
+//! E2E Test: Network Delay
+//!
+//! Verifies consensus under realistic vote latency:
+//! - Apply 800±400ms vote delay to a 4-validator network → verify some
+//!   heights need more than one round and the round histogram records it
+//! - Verify a network without delay always commits in the first round
+
+use trv1_e2e_tests::helpers::*;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: High latency → multi-round heights
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_high_latency_requires_multiple_rounds() {
+    init_logging();
+    println!("\n========================================");
+    println!("  NETWORK DELAY: 800±400ms vote latency");
+    println!("========================================\n");
+
+    let pks = make_pubkeys(4);
+    let stakes: Vec<_> = pks.iter().map(|pk| (*pk, 1_000_000_000_000)).collect();
+    let mut net = SimNetwork::new(&stakes);
+    net.set_network_delay(NetworkDelay {
+        mean_ms: 800.0,
+        std_ms: 400.0,
+        drop_probability: 0.05,
+    });
+
+    // The round-0 prevote timeout is 1000ms, so roughly a third of the votes
+    // miss it and a quorum of 3 of 4 often needs a second round.
+    net.produce_empty_blocks(100);
+
+    let stats = &net.stats;
+    let rounds_observed = stats.consensus_rounds.get_count();
+    assert_eq!(rounds_observed, net.blocks_produced);
+    assert!(
+        net.blocks_produced > 90,
+        "Later rounds should rescue most heights"
+    );
+    assert!(
+        stats.delayed_votes.get() > 0,
+        "Some votes should miss the timeout"
+    );
+    assert!(
+        stats.dropped_votes.get() > 0,
+        "Some votes should be dropped"
+    );
+
+    let (_, first_round_commits) = stats.consensus_rounds.get_buckets()[0];
+    assert!(
+        first_round_commits < rounds_observed,
+        "Some heights should need more than one round"
+    );
+    assert!(stats.consensus_rounds.get_sum() > rounds_observed as f64);
+    println!(
+        "✓ {} blocks, {} committed in round 1, {} delayed and {} dropped votes",
+        net.blocks_produced,
+        first_round_commits,
+        stats.delayed_votes.get(),
+        stats.dropped_votes.get()
+    );
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: No delay → every height commits in round 1
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_no_delay_commits_in_first_round() {
+    init_logging();
+    let (mut net, _) = standard_3_validator_network();
+    net.produce_empty_blocks(20);
+
+    let stats = &net.stats;
+    assert_eq!(net.blocks_produced, 20);
+    assert_eq!(stats.consensus_rounds.get_count(), 20);
+    assert_eq!(stats.consensus_rounds.get_buckets()[0], (1.0, 20));
+    assert_eq!(stats.delayed_votes.get(), 0);
+    assert_eq!(stats.dropped_votes.get(), 0);
+    println!("✓ Without delay every block commits in the first round");
+}
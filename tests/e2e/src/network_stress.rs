@@ -11,7 +11,9 @@ use trv1_e2e_tests::helpers::*;
 use trv1_consensus_bft::{
     BftConfig, ConsensusEngine, EvidenceCollector, ValidatorSet,
 };
+use solana_keypair::Keypair;
 use solana_pubkey::Pubkey;
+use solana_signer::Signer;
 use std::collections::HashMap;
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -194,9 +196,10 @@ fn test_no_double_spend() {
         let cu = 100_000u64;
         let priority = 100u64;
         let tx_fee = trv1_fee_market::calculator::calculate_transaction_fee(
+            &net.fee_config,
             net.fee_state.base_fee_per_cu,
             priority,
-            cu,
+            &trv1_fee_market::TransactionResources::compute_only(cu),
         );
 
         if expected_balance < tx_fee.total_fee {
@@ -211,7 +214,7 @@ fn test_no_double_spend() {
             sender: user,
             compute_units: cu,
             priority_fee_per_cu: priority,
-            invoked_program: None,
+            invoked_programs: Vec::new(),
         }];
 
         net.produce_block(&txs);
@@ -247,20 +250,20 @@ fn test_consensus_liveness_bft() {
     println!("========================================\n");
 
     let n = 4;
-    let pks = make_pubkeys(n);
-    let stakes: Vec<(Pubkey, u64)> = pks
+    let keypairs: Vec<Keypair> = (0..n).map(|_| Keypair::new()).collect();
+    let stakes: Vec<(Pubkey, u64)> = keypairs
         .iter()
         .enumerate()
-        .map(|(i, pk)| (*pk, (i as u64 + 1) * 100))
+        .map(|(i, kp)| (kp.pubkey(), (i as u64 + 1) * 100))
         .collect();
 
     let validator_set = ValidatorSet::new(stakes.clone());
     let config = BftConfig::default();
 
     // Each validator creates its own engine.
-    let mut engines: Vec<ConsensusEngine> = pks
+    let mut engines: Vec<ConsensusEngine> = keypairs
         .iter()
-        .map(|pk| ConsensusEngine::new(config.clone(), *pk, validator_set.clone()))
+        .map(|kp| ConsensusEngine::new(config.clone(), kp.insecure_clone(), validator_set.clone()))
         .collect();
 
     // Start height 0.
@@ -324,9 +327,9 @@ fn test_double_sign_evidence_detection() {
     assert!(evidence.is_some());
 
     let ev = evidence.unwrap();
-    assert_eq!(ev.validator, bad_validator);
+    assert_eq!(ev.voter, bad_validator);
     assert_eq!(ev.height, 100);
-    assert_eq!(ev.kind, EvidenceKind::ConflictingPrevote);
+    assert_eq!(ev.kind(), Some(EvidenceKind::ConflictingPrevote));
     println!("✓ Double-sign detected for validator {} at height 100", bad_validator);
 
     // Verify evidence is stored.
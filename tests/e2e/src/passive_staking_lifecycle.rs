@@ -8,6 +8,7 @@
 //! - Let 30-day lock expire → unlock → verify principal returned
 //! - Try early unlock → verify penalty burned
 //! - Verify permanent lock cannot be unlocked
+//! - Auto-compound rewards daily for a year
 
 use trv1_e2e_tests::helpers::*;
 use solana_passive_stake_program::constants::*;
@@ -463,3 +464,117 @@ fn test_full_passive_staking_lifecycle() {
     assert!(!net.passive_stakes[idx].active);
     println!("✓ Full lifecycle completed successfully");
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: Per-epoch passive stake summary aggregates by tier
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_epoch_passive_stake_summary() {
+    init_logging();
+    println!("\n========================================");
+    println!("  PASSIVE STAKING: Epoch summary");
+    println!("========================================\n");
+
+    let (mut net, _pks) = standard_3_validator_network();
+
+    let tiers = [
+        TIER_NO_LOCK,
+        TIER_30_DAY,
+        TIER_90_DAY,
+        TIER_180_DAY,
+        TIER_360_DAY,
+        PERMANENT_LOCK_DAYS,
+    ];
+    let stake_amount = 10_000_000_000_000u64; // 10k SOL
+    for lock_days in tiers {
+        let user = Pubkey::new_unique();
+        net.credit(&user, stake_amount);
+        net.create_passive_stake(user, stake_amount, lock_days);
+    }
+
+    net.advance_to_epoch(10);
+    let summary = net.epoch_passive_stake_summary();
+
+    assert_eq!(summary.epoch, 10);
+    assert_eq!(summary.positions_created, 6);
+    assert_eq!(summary.positions_expired, 0);
+    assert_eq!(summary.total_staked, stake_amount * 6);
+    for (i, tier) in summary.by_tier.iter().enumerate() {
+        assert_eq!(tier.count, 1, "tier {} count", i);
+        assert_eq!(tier.staked, stake_amount, "tier {} staked", i);
+        assert!(tier.rewards > 0, "tier {} earned nothing", i);
+    }
+    assert!(summary.by_tier[5].rewards > summary.by_tier[0].rewards);
+    println!(
+        "✓ Permanent rewards {} > no-lock rewards {}",
+        summary.by_tier[5].rewards, summary.by_tier[0].rewards
+    );
+
+    let tier_rewards: u64 = summary.by_tier.iter().map(|t| t.rewards).sum();
+    assert_eq!(summary.total_rewards_paid, tier_rewards);
+    println!("✓ total_rewards_paid = {} matches tier sum", summary.total_rewards_paid);
+
+    // Early-unlock the 90-day position: penalty is tracked, position expires.
+    let (_returned, penalty) = net.early_unlock_passive_stake(2).unwrap();
+    let summary = net.epoch_passive_stake_summary();
+    assert_eq!(summary.positions_expired, 1);
+    assert_eq!(summary.total_burned_penalties, penalty);
+    assert_eq!(summary.by_tier[2].count, 0);
+    assert_eq!(summary.total_staked, stake_amount * 5);
+    println!("✓ Early unlock reflected: penalty={} burned", penalty);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: Auto-compounding daily for a year approximates continuous compounding
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_auto_compound_365_epochs() {
+    init_logging();
+    println!("\n========================================");
+    println!("  PASSIVE STAKING: Auto-compound for 365 epochs");
+    println!("========================================\n");
+
+    let (mut net, _pks) = standard_3_validator_network();
+    let user = Pubkey::new_unique();
+    let amount = 10_000_000_000_000u64; // 10k SOL
+    net.credit(&user, amount * 2);
+
+    let simple = net.create_passive_stake(user, amount, PERMANENT_LOCK_DAYS);
+    let compounding = net.create_passive_stake(user, amount, PERMANENT_LOCK_DAYS);
+    net.set_passive_auto_compound(compounding, true);
+
+    // One epoch per day.
+    net.advance_to_epoch(365);
+
+    // Permanent tier at a 5% validator rate: 120% × 5% = 6% a year.
+    let annual_rate = 500.0 * REWARD_RATE_PERMANENT_BPS as f64 / (BPS_DENOMINATOR as f64).powi(2);
+    let principal = amount as f64;
+
+    let simple_stake = &net.passive_stakes[simple];
+    assert_eq!(simple_stake.amount, amount);
+    let simple_total = (simple_stake.amount + simple_stake.unclaimed_rewards) as f64;
+    assert!((simple_total / (principal * (1.0 + annual_rate)) - 1.0).abs() < 1e-6);
+
+    let compound_stake = &net.passive_stakes[compounding];
+    assert_eq!(compound_stake.unclaimed_rewards, 0);
+    let compounded = compound_stake.amount as f64;
+    let daily = principal * (1.0 + annual_rate / 365.0).powi(365);
+    let continuous = principal * annual_rate.exp();
+    println!(
+        "  simple={} compounded={} daily-formula={:.0} continuous={:.0}",
+        simple_total, compounded, daily, continuous
+    );
+
+    // Integer rounding loses about a lamport per epoch.
+    assert!((compounded - daily).abs() < 1_000.0);
+    assert!((compounded / continuous - 1.0).abs() < 1e-5);
+    assert!(compounded > simple_total);
+    assert_eq!(
+        compound_stake.total_rewards,
+        compound_stake.amount - amount,
+        "every reward lamport was reinvested"
+    );
+    println!("✓ Daily auto-compounding tracks e^rt");
+}
@@ -0,0 +1,68 @@
+//! E2E Test: State Rent Lifecycle
+//!
+//! Verifies that the archival worker is fed at epoch boundaries:
+//! - Nothing is enqueued before the first boundary
+//! - 100 large inactive accounts are archived across one boundary and
+//!   registered in the archive index, while a small account is kept
+
+use {
+    solana_account::AccountSharedData,
+    solana_accounts_db::state_rent_expiry::StateRentConfig,
+    solana_pubkey::Pubkey,
+    std::sync::atomic::Ordering,
+    trv1_e2e_tests::helpers::*,
+};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: Large accounts archived at the epoch boundary
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_epoch_transition_archives_inactive_accounts() {
+    init_logging();
+    println!("\n========================================");
+    println!("  STATE RENT: Archival at epoch boundary");
+    println!("========================================\n");
+
+    let (mut net, _pks) = standard_3_validator_network();
+    let cold_storage = tempfile::tempdir().unwrap();
+    // Simulated epochs are far shorter than a day, so any account is
+    // eligible once it has been handed to the worker.
+    net.enable_state_rent_archival(StateRentConfig {
+        archive_after_days: 0,
+        min_account_age_slots: 0,
+        cold_storage_path: cold_storage.path().to_path_buf(),
+        ..StateRentConfig::for_testing()
+    });
+
+    let large = make_pubkeys(100);
+    for (i, pubkey) in large.iter().enumerate() {
+        net.create_data_account(
+            *pubkey,
+            AccountSharedData::new(1_000_000, 4_096 + i * 64, &Pubkey::new_unique()),
+        );
+    }
+    let small = Pubkey::new_unique();
+    net.create_data_account(small, AccountSharedData::new(1_000_000, 16, &Pubkey::new_unique()));
+
+    // Mid-epoch nothing has been handed to the worker.
+    net.produce_empty_blocks(SLOTS_PER_EPOCH / 2);
+    let stats = net.archival_worker.as_ref().unwrap().stats();
+    assert_eq!(stats.tasks_processed.load(Ordering::Relaxed), 0);
+    assert_eq!(net.archival_worker.as_ref().unwrap().queue_len(), 0);
+
+    net.produce_epoch();
+    assert_eq!(net.current_epoch, 1);
+    let index = net.archive_index().unwrap().clone();
+    net.stop_archival_worker();
+
+    let index = index.lock().unwrap();
+    assert_eq!(index.len(), 100);
+    for pubkey in &large {
+        let archived = index.get(pubkey).unwrap();
+        assert_eq!(archived.archive_epoch, 1);
+        assert_eq!(archived.archive_slot, SLOTS_PER_EPOCH);
+    }
+    assert!(!index.is_archived(&small));
+    println!("✓ {} accounts archived in epoch 1", index.len());
+}
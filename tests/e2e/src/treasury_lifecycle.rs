@@ -85,6 +85,27 @@ fn test_treasury_receives_fee_share() {
         "✓ Treasury received ≈45% of total fees ({} / {})",
         net.treasury_fees, net.total_fees_collected
     );
+
+    // The per-epoch metrics add up to the totals: five completed epochs
+    // plus the one just started.
+    let completed = net.metrics.epoch_history(usize::MAX);
+    let epochs: Vec<u64> = completed.iter().map(|e| e.epoch).collect();
+    assert_eq!(epochs, vec![0, 1, 2, 3, 4]);
+    let current = net.metrics.current_epoch_metrics().unwrap();
+    assert_eq!(current.epoch, 5);
+    let all_epochs = || completed.iter().chain(std::iter::once(&current));
+    assert_eq!(
+        all_epochs()
+            .map(|e| e.fees_treasury_this_epoch())
+            .sum::<u64>(),
+        net.treasury_fees
+    );
+    assert_eq!(
+        all_epochs().map(|e| e.blocks_this_epoch()).sum::<u64>(),
+        net.blocks_produced
+    );
+    assert!(completed.iter().all(|e| e.fees_treasury_this_epoch() > 0));
+    println!("✓ Per-epoch treasury fees add up to {}", net.treasury_fees);
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
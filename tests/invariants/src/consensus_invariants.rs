@@ -4,14 +4,18 @@
 //! 1. Safety: no two different blocks committed at the same height.
 //! 2. Liveness: if 2/3+ honest validators, eventually commits.
 //! 3. Validity: only proposed blocks can be committed.
+//! 4. Quorum monotonicity: adding a validator never lowers the quorum and
+//!    removing one never raises it; a 2/3+ quorum is always a majority.
 
 #[cfg(test)]
 mod tests {
     use {
         proptest::prelude::*,
         solana_hash::Hash,
+        solana_keypair::Keypair,
         solana_pubkey::Pubkey,
         solana_signature::Signature,
+        solana_signer::Signer,
         trv1_consensus_bft::{
             BftConfig, ConsensusEngine, ConsensusMessage, ProposedBlock,
             ValidatorSet, proposer_for_round,
@@ -20,22 +24,26 @@ mod tests {
 
     // ── Helpers ──
 
-    fn make_validator_set(n: usize, stakes: &[u64]) -> (Vec<Pubkey>, ValidatorSet) {
-        let pks: Vec<Pubkey> = (0..n)
+    fn make_validator_set(n: usize, stakes: &[u64]) -> (Vec<Keypair>, ValidatorSet) {
+        let kps: Vec<Keypair> = (0..n)
             .map(|i| {
-                let mut bytes = [0u8; 32];
-                bytes[0] = i as u8;
-                bytes[31] = 0xBB;
-                Pubkey::new_from_array(bytes)
+                let mut secret = [0u8; 32];
+                secret[0] = i as u8;
+                secret[31] = 0xBB;
+                Keypair::new_from_array(secret)
             })
             .collect();
         let vs = ValidatorSet::new(
-            pks.iter()
+            kps.iter()
                 .zip(stakes.iter())
-                .map(|(pk, s)| (*pk, *s))
+                .map(|(kp, s)| (kp.pubkey(), *s))
                 .collect(),
         );
-        (pks, vs)
+        (kps, vs)
+    }
+
+    fn keypair_of<'a>(kps: &'a [Keypair], pk: &Pubkey) -> &'a Keypair {
+        kps.iter().find(|kp| kp.pubkey() == *pk).unwrap()
     }
 
     fn make_block(height: u64, proposer: Pubkey) -> ProposedBlock {
@@ -51,6 +59,7 @@ mod tests {
             transactions: vec![],
             state_root: Hash::new_from_array(state_root_bytes),
             proposer,
+            evidence: vec![],
         }
     }
 
@@ -58,52 +67,56 @@ mod tests {
         height: u64,
         round: u32,
         block: &ProposedBlock,
-        proposer: Pubkey,
+        proposer: &Keypair,
     ) -> ConsensusMessage {
         ConsensusMessage::Proposal {
             height,
             round,
             block: block.clone(),
-            proposer,
+            proposer: proposer.pubkey(),
             signature: Signature::default(),
             valid_round: None,
+            polka_certificate: None,
         }
+        .signed(proposer)
     }
 
     fn make_prevote_msg(
         height: u64,
         round: u32,
         block_hash: Option<Hash>,
-        voter: Pubkey,
+        voter: &Keypair,
     ) -> ConsensusMessage {
         ConsensusMessage::Prevote {
             height,
             round,
             block_hash,
-            voter,
+            voter: voter.pubkey(),
             signature: Signature::default(),
         }
+        .signed(voter)
     }
 
     fn make_precommit_msg(
         height: u64,
         round: u32,
         block_hash: Option<Hash>,
-        voter: Pubkey,
+        voter: &Keypair,
     ) -> ConsensusMessage {
         ConsensusMessage::Precommit {
             height,
             round,
             block_hash,
-            voter,
+            voter: voter.pubkey(),
             signature: Signature::default(),
         }
+        .signed(voter)
     }
 
     /// Run a complete happy-path round and return the committed block hash (if any).
     fn run_honest_round(
         engine: &mut ConsensusEngine,
-        all_pks: &[Pubkey],
+        all_kps: &[Keypair],
         vs: &ValidatorSet,
         height: u64,
         round: u32,
@@ -117,24 +130,24 @@ mod tests {
         let block_hash = block.hash();
 
         // Deliver proposal.
-        let proposal = make_proposal(height, round, &block, proposer_pk);
+        let proposal = make_proposal(height, round, &block, keypair_of(all_kps, &proposer_pk));
         let _ = engine.on_proposal(proposal);
 
         // Deliver prevotes from all validators (simulating honest network).
-        for pk in all_pks {
-            if *pk == engine.identity().clone() {
+        for kp in all_kps {
+            if kp.pubkey() == *engine.identity() {
                 continue; // Engine already voted.
             }
-            let _ = engine.on_prevote(make_prevote_msg(height, round, Some(block_hash), *pk));
+            let _ = engine.on_prevote(make_prevote_msg(height, round, Some(block_hash), kp));
         }
 
         // Deliver precommits from all validators.
-        for pk in all_pks {
-            if *pk == engine.identity().clone() {
+        for kp in all_kps {
+            if kp.pubkey() == *engine.identity() {
                 continue;
             }
             let output =
-                engine.on_precommit(make_precommit_msg(height, round, Some(block_hash), *pk));
+                engine.on_precommit(make_precommit_msg(height, round, Some(block_hash), kp));
             if let Some(committed) = output.committed_block {
                 return Some(committed.block.hash());
             }
@@ -158,17 +171,18 @@ mod tests {
             height in 1..=100u64,
         ) {
             let stakes: Vec<u64> = vec![100; num_validators];
-            let (pks, vs) = make_validator_set(num_validators, &stakes);
+            let (kps, vs) = make_validator_set(num_validators, &stakes);
 
             let config = BftConfig::default();
             let mut committed_hashes: Vec<Hash> = Vec::new();
 
             // Run separate engines for each validator.
             for i in 0..num_validators {
-                let mut engine = ConsensusEngine::new(config.clone(), pks[i], vs.clone());
+                let mut engine =
+                    ConsensusEngine::new(config.clone(), kps[i].insecure_clone(), vs.clone());
                 engine.start_new_height(height);
 
-                if let Some(hash) = run_honest_round(&mut engine, &pks, &vs, height, 0) {
+                if let Some(hash) = run_honest_round(&mut engine, &kps, &vs, height, 0) {
                     committed_hashes.push(hash);
                 }
             }
@@ -201,13 +215,13 @@ mod tests {
             height in 1..=1000u64,
         ) {
             let stakes: Vec<u64> = vec![100; num_validators];
-            let (pks, vs) = make_validator_set(num_validators, &stakes);
+            let (kps, vs) = make_validator_set(num_validators, &stakes);
 
             let config = BftConfig::default();
-            let mut engine = ConsensusEngine::new(config, pks[0], vs.clone());
+            let mut engine = ConsensusEngine::new(config, kps[0].insecure_clone(), vs.clone());
             engine.start_new_height(height);
 
-            let committed = run_honest_round(&mut engine, &pks, &vs, height, 0);
+            let committed = run_honest_round(&mut engine, &kps, &vs, height, 0);
             prop_assert!(
                 committed.is_some(),
                 "Failed to commit with all honest validators at height {height}"
@@ -222,10 +236,10 @@ mod tests {
             height in 1..=100u64,
         ) {
             let stakes: Vec<u64> = vec![100; num_validators];
-            let (pks, vs) = make_validator_set(num_validators, &stakes);
+            let (kps, vs) = make_validator_set(num_validators, &stakes);
 
             let config = BftConfig::default();
-            let mut engine = ConsensusEngine::new(config, pks[0], vs.clone());
+            let mut engine = ConsensusEngine::new(config, kps[0].insecure_clone(), vs.clone());
             engine.start_new_height(height);
 
             // Determine how many validators are online (2/3 + 1).
@@ -234,27 +248,27 @@ mod tests {
             let online_count = ((quorum_stake + 99) / 100) as usize; // ceil division
 
             // Only use online validators' votes.
-            let online_pks: Vec<Pubkey> = pks[..online_count.min(num_validators)].to_vec();
+            let online_kps = &kps[..online_count.min(num_validators)];
 
             let proposer_pk = proposer_for_round(&vs, height, 0).unwrap();
             let block = make_block(height, proposer_pk);
             let block_hash = block.hash();
 
             // Deliver proposal.
-            let _ = engine.on_proposal(make_proposal(height, 0, &block, proposer_pk));
+            let _ = engine.on_proposal(
+                make_proposal(height, 0, &block, keypair_of(&kps, &proposer_pk)),
+            );
 
-            // Deliver prevotes from online validators only.
-            for pk in &online_pks {
-                if *pk == pks[0] { continue; }
-                let _ = engine.on_prevote(make_prevote_msg(height, 0, Some(block_hash), *pk));
+            // Deliver prevotes from online validators only (skipping our own).
+            for kp in &online_kps[1..] {
+                let _ = engine.on_prevote(make_prevote_msg(height, 0, Some(block_hash), kp));
             }
 
             // Deliver precommits from online validators only.
             let mut committed = false;
-            for pk in &online_pks {
-                if *pk == pks[0] { continue; }
+            for kp in &online_kps[1..] {
                 let output = engine.on_precommit(
-                    make_precommit_msg(height, 0, Some(block_hash), *pk),
+                    make_precommit_msg(height, 0, Some(block_hash), kp),
                 );
                 if output.committed_block.is_some() {
                     committed = true;
@@ -284,9 +298,9 @@ mod tests {
             height in 1..=500u64,
         ) {
             let stakes: Vec<u64> = vec![100; num_validators];
-            let (pks, vs) = make_validator_set(num_validators, &stakes);
+            let (kps, vs) = make_validator_set(num_validators, &stakes);
             let config = BftConfig::default();
-            let mut engine = ConsensusEngine::new(config, pks[0], vs.clone());
+            let mut engine = ConsensusEngine::new(config, kps[0].insecure_clone(), vs.clone());
             engine.start_new_height(height);
 
             let proposer_pk = proposer_for_round(&vs, height, 0).unwrap();
@@ -294,15 +308,17 @@ mod tests {
             let expected_hash = block.hash();
 
             // Deliver proposal.
-            let _ = engine.on_proposal(make_proposal(height, 0, &block, proposer_pk));
+            let _ = engine.on_proposal(
+                make_proposal(height, 0, &block, keypair_of(&kps, &proposer_pk)),
+            );
 
             // Deliver votes.
-            for pk in &pks[1..] {
-                let _ = engine.on_prevote(make_prevote_msg(height, 0, Some(expected_hash), *pk));
+            for kp in &kps[1..] {
+                let _ = engine.on_prevote(make_prevote_msg(height, 0, Some(expected_hash), kp));
             }
-            for pk in &pks[1..] {
+            for kp in &kps[1..] {
                 let output = engine.on_precommit(
-                    make_precommit_msg(height, 0, Some(expected_hash), *pk),
+                    make_precommit_msg(height, 0, Some(expected_hash), kp),
                 );
                 if let Some(committed) = output.committed_block {
                     // ── INVARIANT: committed block hash matches proposal ──
@@ -361,7 +377,7 @@ mod tests {
             num_validators in 2..=6usize,
         ) {
             let stakes: Vec<u64> = vec![1; num_validators];
-            let (_pks, vs) = make_validator_set(num_validators, &stakes);
+            let (_kps, vs) = make_validator_set(num_validators, &stakes);
 
             let total_stake = vs.total_stake();
             let mut selected: std::collections::HashSet<Pubkey> = std::collections::HashSet::new();
@@ -408,4 +424,75 @@ mod tests {
             }
         }
     }
+
+    // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+    // 6. Quorum monotonicity
+    // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+    /// 6667 bps, the 2/3+ finality threshold.
+    const TWO_THIRDS_THRESHOLD: f64 = 0.6667;
+
+    /// 1 to 500 validators with stakes in 1..=10^15 lamports.
+    fn validator_stakes() -> impl Strategy<Value = Vec<u64>> {
+        prop::collection::vec(1..=1_000_000_000_000_000u64, 1..=500)
+    }
+
+    fn validator_set_with_stakes(stakes: &[u64]) -> ValidatorSet {
+        ValidatorSet::new(
+            stakes
+                .iter()
+                .map(|stake| (Pubkey::new_unique(), *stake))
+                .collect(),
+        )
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(200))]
+
+        #[test]
+        fn quorum_never_decreases_when_validator_added(
+            stakes in validator_stakes(),
+            new_stake in 1..=1_000_000_000_000_000u64,
+        ) {
+            let threshold = BftConfig::default().finality_threshold;
+            let mut vs = validator_set_with_stakes(&stakes);
+            let old_quorum = vs.quorum_stake(threshold);
+
+            vs.upsert(Pubkey::new_unique(), new_stake);
+            let new_quorum = vs.quorum_stake(threshold);
+            prop_assert!(
+                new_quorum >= old_quorum,
+                "Adding {} stake lowered the quorum from {} to {}", new_stake, old_quorum, new_quorum
+            );
+        }
+
+        #[test]
+        fn quorum_never_increases_when_validator_removed(
+            stakes in validator_stakes(),
+            removed in any::<prop::sample::Index>(),
+        ) {
+            let threshold = BftConfig::default().finality_threshold;
+            let mut vs = validator_set_with_stakes(&stakes);
+            let old_quorum = vs.quorum_stake(threshold);
+
+            let pubkey = vs.get(removed.index(vs.len())).unwrap().pubkey;
+            vs.remove(&pubkey);
+            let new_quorum = vs.quorum_stake(threshold);
+            prop_assert!(
+                new_quorum <= old_quorum,
+                "Removing {} raised the quorum from {} to {}", pubkey, old_quorum, new_quorum
+            );
+        }
+
+        #[test]
+        fn two_thirds_quorum_is_a_strict_majority(stakes in validator_stakes()) {
+            let vs = validator_set_with_stakes(&stakes);
+            let quorum = vs.quorum_stake(TWO_THIRDS_THRESHOLD);
+            prop_assert_eq!(vs.total_stake(), stakes.iter().sum::<u64>());
+            prop_assert!(
+                quorum as u128 * 2 > vs.total_stake() as u128,
+                "Quorum {} is not more than half of {}", quorum, vs.total_stake()
+            );
+        }
+    }
 }
@@ -7,10 +7,22 @@
 //! 4. Early unlock penalty >= tier's defined rate.
 //! 5. Permanent locks cannot be unlocked.
 //! 6. Three-strike permanent ban is irreversible.
+//! 7. The passive stake program's tier tables are bounded and strictly
+//!    monotone in lock days, and its per-epoch reward cannot overflow.
+//! 8. Governance vote weight never decreases with lock days, and the
+//!    simulated network assigns the program's weights.
+//!
+//! The properties in 7 and 8 check the program's own constants rather than
+//! local copies, so a table regression such as swapping the 90- and 180-day
+//! reward rates fails `program_tier_tables_strictly_monotone`. The
+//! randomized ones need the `proptest` feature.
 
 #[cfg(test)]
 mod tests {
-    use proptest::prelude::*;
+    use {
+        proptest::prelude::*, solana_passive_stake_program::constants as program,
+        trv1_e2e_tests::helpers::standard_3_validator_network,
+    };
 
     // ── Constants (from slashing.rs and passive-stake constants) ──
 
@@ -394,4 +406,137 @@ mod tests {
             prev_bps = bps;
         }
     }
+
+    // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+    // 9. Program tier tables
+    // ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+
+    /// The program's valid tiers, in lock-day order.
+    const PROGRAM_TIERS: [u64; 6] = [
+        program::TIER_NO_LOCK,
+        program::TIER_30_DAY,
+        program::TIER_90_DAY,
+        program::TIER_180_DAY,
+        program::TIER_360_DAY,
+        program::PERMANENT_LOCK_DAYS,
+    ];
+
+    #[test]
+    fn program_vote_weight_extremes() {
+        // No-lock stakers cannot vote.
+        assert_eq!(
+            program::vote_weight_bps_for_tier(program::TIER_NO_LOCK),
+            Some(0)
+        );
+        assert_eq!(
+            program::vote_weight_for_stake_position(1_000_000_000, program::TIER_NO_LOCK),
+            0
+        );
+
+        // Permanent locks carry the largest weight of any tier.
+        let max_weight = PROGRAM_TIERS
+            .iter()
+            .map(|&tier| program::vote_weight_bps_for_tier(tier).unwrap())
+            .max()
+            .unwrap();
+        assert_eq!(
+            program::vote_weight_bps_for_tier(program::PERMANENT_LOCK_DAYS),
+            Some(max_weight)
+        );
+        assert_eq!(max_weight, program::VOTE_WEIGHT_PERMANENT);
+    }
+
+    #[test]
+    fn sim_network_vote_weight_matches_program() {
+        let (mut net, pks) = standard_3_validator_network();
+        let amount = 10_000_000_000;
+        for tier in PROGRAM_TIERS {
+            let idx = net.create_passive_stake(pks[0], amount, tier);
+            let weight_bps = net.passive_stakes[idx].vote_weight_bps;
+            assert_eq!(Some(weight_bps), program::vote_weight_bps_for_tier(tier));
+            assert_eq!(
+                program::vote_weight_for_stake_position(amount, tier),
+                amount * weight_bps as u64 / BPS_DENOMINATOR
+            );
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    mod program_tiers {
+        use super::*;
+
+        fn program_tier() -> impl Strategy<Value = u64> {
+            prop::sample::select(PROGRAM_TIERS.to_vec())
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(200))]
+
+            #[test]
+            fn program_reward_rate_bounded(tier in program_tier()) {
+                let rate = program::reward_rate_bps_for_tier(tier).unwrap();
+                prop_assert!(
+                    rate <= program::REWARD_RATE_PERMANENT_BPS,
+                    "Tier {tier} reward rate {rate} exceeds the permanent rate"
+                );
+            }
+
+            #[test]
+            fn program_tier_tables_strictly_monotone(x in program_tier(), y in program_tier()) {
+                prop_assume!(x != y);
+                let (shorter, longer) = (x.min(y), x.max(y));
+
+                let rate = |tier| program::reward_rate_bps_for_tier(tier).unwrap();
+                prop_assert!(
+                    rate(shorter) < rate(longer),
+                    "Reward rate of tier {shorter} ({}) not below tier {longer} ({})",
+                    rate(shorter),
+                    rate(longer)
+                );
+
+                let weight = |tier| program::vote_weight_bps_for_tier(tier).unwrap();
+                prop_assert!(
+                    weight(shorter) < weight(longer),
+                    "Vote weight of tier {shorter} ({}) not below tier {longer} ({})",
+                    weight(shorter),
+                    weight(longer)
+                );
+            }
+
+            #[test]
+            fn program_epoch_reward_does_not_overflow(
+                amount in 1..=u64::MAX / 2,
+                tier in program_tier(),
+            ) {
+                let reward = program::compute_epoch_reward(amount, 500, tier).unwrap();
+
+                // The exact value fits in u64, so the saturating result is exact.
+                let exact = amount as u128
+                    * 500
+                    * program::reward_rate_bps_for_tier(tier).unwrap() as u128
+                    / (BPS_DENOMINATOR as u128 * BPS_DENOMINATOR as u128 * 365);
+                prop_assert!(exact <= u64::MAX as u128);
+                prop_assert_eq!(reward as u128, exact);
+                prop_assert!(reward < amount, "One epoch cannot earn the principal");
+            }
+
+            /// Every pair of tiers, shorter lock first.
+            #[test]
+            fn program_vote_weight_monotone(
+                pair in prop::sample::subsequence(PROGRAM_TIERS.to_vec(), 2),
+                amount in 1..=u64::MAX / 2,
+            ) {
+                let (shorter, longer) = (pair[0], pair[1]);
+                let weight = |tier| program::vote_weight_bps_for_tier(tier).unwrap();
+                prop_assert!(
+                    weight(shorter) <= weight(longer),
+                    "Tier {shorter} outweighs tier {longer}"
+                );
+                prop_assert!(
+                    program::vote_weight_for_stake_position(amount, shorter)
+                        <= program::vote_weight_for_stake_position(amount, longer)
+                );
+            }
+        }
+    }
 }
@@ -12,7 +12,7 @@ use {
         input_parsers::keypair_of,
         input_validators::{
             is_keypair_or_ask_keyword, is_non_zero, is_parsable, is_pow2, is_pubkey,
-            is_pubkey_or_keypair, is_slot, is_within_range, validate_cpu_ranges,
+            is_pubkey_or_keypair, is_slot, is_url, is_within_range, validate_cpu_ranges,
             validate_maximum_full_snapshot_archives_to_retain,
             validate_maximum_incremental_snapshot_archives_to_retain,
         },
@@ -544,6 +544,47 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .hidden(hidden_unless_forced())
             .help("Disable reporting of OS disk statistics."),
     )
+    .arg(
+        Arg::with_name("metrics_push_gateway_url")
+            .long("metrics-push-gateway-url")
+            .value_name("URL")
+            .takes_value(true)
+            .validator(is_url)
+            .help(
+                "Push TRv1 metrics to the Prometheus Pushgateway at this URL, e.g. \
+                 http://pushgateway:9091",
+            ),
+    )
+    .arg(
+        Arg::with_name("metrics_push_interval")
+            .long("metrics-push-interval")
+            .value_name("SECONDS")
+            .takes_value(true)
+            .validator(is_non_zero)
+            .default_value("15")
+            .help("How often to push TRv1 metrics to the Pushgateway"),
+    )
+    .arg(
+        Arg::with_name("metrics_push_job")
+            .long("metrics-push-job")
+            .value_name("NAME")
+            .takes_value(true)
+            .validator(is_push_gateway_label)
+            .default_value("trv1-validator")
+            .help("Value of the job label TRv1 metrics are pushed under"),
+    )
+    .arg(
+        Arg::with_name("metrics_push_instance")
+            .long("metrics-push-instance")
+            .value_name("NAME")
+            .takes_value(true)
+            .requires("metrics_push_gateway_url")
+            .validator(is_push_gateway_label)
+            .help(
+                "Value of the instance label TRv1 metrics are pushed under [default: the \
+                 validator's identity pubkey]",
+            ),
+    )
     .arg(
         Arg::with_name("snapshot_version")
             .long("snapshot-version")
@@ -1295,6 +1336,15 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
     .args(&blockstore_options::args())
 }
 
+// The Pushgateway takes job and instance as URL path segments.
+fn is_push_gateway_label(label: String) -> std::result::Result<(), String> {
+    if label.is_empty() || label.contains('/') {
+        Err(format!("must be non-empty and must not contain '/': {label}"))
+    } else {
+        Ok(())
+    }
+}
+
 fn validators_set(
     identity_pubkey: &Pubkey,
     matches: &ArgMatches<'_>,
@@ -84,6 +84,7 @@ use {
         str::{self, FromStr},
         sync::{atomic::AtomicBool, Arc, RwLock},
     },
+    trv1_monitoring::push_gateway::PushGatewayConfig,
 };
 
 #[derive(Debug, PartialEq, Eq)]
@@ -811,6 +812,17 @@ pub fn execute(
         no_os_network_stats_reporting: matches.is_present("no_os_network_stats_reporting"),
         no_os_cpu_stats_reporting: matches.is_present("no_os_cpu_stats_reporting"),
         no_os_disk_stats_reporting: matches.is_present("no_os_disk_stats_reporting"),
+        metrics_push_gateway: matches.value_of("metrics_push_gateway_url").map(|url| {
+            PushGatewayConfig {
+                url: url.to_string(),
+                push_interval_secs: value_t_or_exit!(matches, "metrics_push_interval", u64),
+                job: value_t_or_exit!(matches, "metrics_push_job", String),
+                instance: matches
+                    .value_of("metrics_push_instance")
+                    .map(str::to_string)
+                    .unwrap_or_else(|| identity_keypair.pubkey().to_string()),
+            }
+        }),
         // The validator needs to open many files, check that the process has
         // permission to do so in order to fail quickly and give a direct error
         enforce_ulimit_nofile: true,